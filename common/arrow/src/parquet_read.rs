@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use arrow::datatypes::DataType;
 use arrow::datatypes::Field;
 use arrow::error::Result;
 use arrow::io::parquet::read::to_deserializer;
@@ -23,13 +24,36 @@ use futures::AsyncSeekExt;
 use parquet2::metadata::ColumnChunkMetaData;
 use parquet2::metadata::RowGroupMetaData;
 
+// The paths of the leaves of the field, the children of a struct are followed, the other
+// nested types, e.g. list, are taken as a whole.
+fn get_field_paths(field: &Field, path: &mut Vec<String>, paths: &mut Vec<Vec<String>>) {
+    path.push(field.name.clone());
+    match field.data_type().to_logical_type() {
+        DataType::Struct(children) => {
+            for child in children {
+                get_field_paths(child, path, paths);
+            }
+        }
+        _ => paths.push(path.clone()),
+    }
+    path.pop();
+}
+
+// The columns of the field, in the order of its leaves. Only the children of the struct kept by
+// the field are read, e.g. the field `a struct<b>` of the file `a struct<b, c>` reads `a.b` only.
 fn get_field_columns<'a>(
     columns: &'a [ColumnChunkMetaData],
-    field_name: &str,
+    field: &Field,
 ) -> Vec<&'a ColumnChunkMetaData> {
-    columns
+    let mut paths = vec![];
+    get_field_paths(field, &mut vec![], &mut paths);
+    paths
         .iter()
-        .filter(|x| x.descriptor().path_in_schema()[0] == field_name)
+        .flat_map(|path| {
+            columns
+                .iter()
+                .filter(move |x| x.descriptor().path_in_schema().starts_with(path))
+        })
         .collect()
 }
 
@@ -50,9 +74,9 @@ where
 async fn read_columns_async<'a, R: AsyncRead + AsyncSeek + Send + Unpin>(
     reader: &mut R,
     columns: &'a [ColumnChunkMetaData],
-    field_name: &str,
+    field: &Field,
 ) -> Result<Vec<(&'a ColumnChunkMetaData, Vec<u8>)>> {
-    let col_metas = get_field_columns(columns, field_name);
+    let col_metas = get_field_columns(columns, field);
     let mut cols = Vec::with_capacity(col_metas.len());
     for meta in col_metas {
         cols.push((meta, _read_single_column_async(reader, meta).await?))
@@ -69,7 +93,7 @@ pub async fn read_columns_many_async<'a, R: AsyncRead + AsyncSeek + Send + Unpin
 ) -> Result<Vec<ArrayIter<'a>>> {
     let mut arrays = Vec::with_capacity(fields.len());
     for field in fields {
-        let columns = read_columns_async(reader, row_group.columns(), &field.name).await?;
+        let columns = read_columns_async(reader, row_group.columns(), field).await?;
         arrays.push(to_deserializer(
             columns,
             field.to_owned(),
@@ -119,6 +119,10 @@ pub enum TableReference {
         database: Option<Identifier>,
         table: Identifier,
         alias: Option<TableAlias>,
+        // Optional `AT (...)` clause for time travel
+        travel_point: Option<TimeTravelPoint>,
+        // Optional `TABLESAMPLE ...` clause
+        sample: Option<TableSample>,
     },
     // Derived table, which can be a subquery or joined tables or combination of them
     Subquery {
@@ -134,6 +138,26 @@ pub enum TableReference {
     Join(Join),
 }
 
+// `AT (SNAPSHOT => 'snapshot_id')` or `AT (TIMESTAMP => expr)`
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeTravelPoint {
+    Snapshot(String),
+    Timestamp(Box<Expr>),
+}
+
+// `TABLESAMPLE BLOCK (n PERCENT)` or `TABLESAMPLE ROW (n PERCENT)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableSample {
+    pub method: SampleMethod,
+    pub percent: Literal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SampleMethod {
+    Block,
+    Row,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TableAlias {
     pub name: Identifier,
@@ -200,6 +224,30 @@ impl Display for TableAlias {
     }
 }
 
+impl Display for TimeTravelPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeTravelPoint::Snapshot(sid) => {
+                write!(f, "AT (SNAPSHOT => '{sid}')")?;
+            }
+            TimeTravelPoint::Timestamp(ts) => {
+                write!(f, "AT (TIMESTAMP => {ts})")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for TableSample {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let method = match self.method {
+            SampleMethod::Block => "BLOCK",
+            SampleMethod::Row => "ROW",
+        };
+        write!(f, "TABLESAMPLE {method} ({} PERCENT)", self.percent)
+    }
+}
+
 impl Display for TableReference {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -207,8 +255,16 @@ impl Display for TableReference {
                 database,
                 table,
                 alias,
+                travel_point,
+                sample,
             } => {
                 write_period_separated_list(f, database.iter().chain(Some(table)))?;
+                if let Some(travel_point) = travel_point {
+                    write!(f, " {travel_point}")?;
+                }
+                if let Some(sample) = sample {
+                    write!(f, " {sample}")?;
+                }
                 if let Some(alias) = alias {
                     write!(f, " AS {alias}")?;
                 }
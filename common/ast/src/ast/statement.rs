@@ -260,6 +260,7 @@ pub enum OptimizeTableAction {
     All,
     Purge,
     Compact,
+    CompactSegment,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -526,6 +527,7 @@ impl<'a> Display for Statement<'a> {
                         OptimizeTableAction::All => write!(f, " ALL")?,
                         OptimizeTableAction::Purge => write!(f, " PURGE")?,
                         OptimizeTableAction::Compact => write!(f, " COMPACT")?,
+                        OptimizeTableAction::CompactSegment => write!(f, " COMPACT SEGMENT")?,
                     }
                 }
             }
@@ -22,8 +22,10 @@ pub mod util;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use crate::ast::Expr;
 use crate::ast::Statement;
 use crate::parser::error::pretty_print_error;
+use crate::parser::expr::expr;
 use crate::parser::statement::statements;
 use crate::parser::token::Token;
 use crate::parser::token::TokenKind;
@@ -50,3 +52,21 @@ pub fn parse_sql<'a>(sql_tokens: &'a [Token<'a>]) -> Result<Vec<Statement<'a>>>
         Err(nom::Err::Incomplete(_)) => unreachable!(),
     }
 }
+
+/// Parse a SQL string of a single expression into `Expr`.
+pub fn parse_expr<'a>(sql_tokens: &'a [Token<'a>]) -> Result<Expr> {
+    match expr(sql_tokens) {
+        Ok((rest, expr)) if rest[0].kind == TokenKind::EOI => Ok(expr),
+        Ok((rest, _)) => Err(ErrorCode::SyntaxException(pretty_print_error(
+            sql_tokens[0].source,
+            vec![(
+                rest[0].span.clone(),
+                "unable to parse rest of the expression".to_owned(),
+            )],
+        ))),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => Err(ErrorCode::SyntaxException(
+            pretty_print_error(sql_tokens[0].source, err.to_labels()),
+        )),
+        Err(nom::Err::Incomplete(_)) => unreachable!(),
+    }
+}
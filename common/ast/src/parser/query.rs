@@ -126,9 +126,9 @@ pub fn table_reference(i: Input) -> IResult<TableReference> {
 pub fn aliased_table(i: Input) -> IResult<TableReference> {
     map(
         rule! {
-            #ident ~ ( "." ~ #ident )? ~ #table_alias?
+            #ident ~ ( "." ~ #ident )? ~ #travel_point? ~ #table_sample? ~ #table_alias?
         },
-        |(fst, snd, alias)| {
+        |(fst, snd, travel_point, sample, alias)| {
             let (database, table) = match (fst, snd) {
                 (database, Some((_, table))) => (Some(database), table),
                 (table, None) => (None, table),
@@ -138,11 +138,41 @@ pub fn aliased_table(i: Input) -> IResult<TableReference> {
                 database,
                 table,
                 alias,
+                travel_point,
+                sample,
             }
         },
     )(i)
 }
 
+pub fn travel_point(i: Input) -> IResult<TimeTravelPoint> {
+    let at_snapshot = map(
+        rule! { AT ~ "(" ~ SNAPSHOT ~ "=>" ~ #literal_string ~ ")" },
+        |(_, _, _, _, s, _)| TimeTravelPoint::Snapshot(s),
+    );
+    let at_timestamp = map(
+        rule! { AT ~ "(" ~ TIMESTAMP ~ "=>" ~ #expr ~ ")" },
+        |(_, _, _, _, e, _)| TimeTravelPoint::Timestamp(Box::new(e)),
+    );
+
+    rule!(
+        #at_snapshot
+        | #at_timestamp
+    )(i)
+}
+
+pub fn table_sample(i: Input) -> IResult<TableSample> {
+    let method = alt((
+        value(SampleMethod::Block, rule! { BLOCK }),
+        value(SampleMethod::Row, rule! { ROW }),
+    ));
+
+    map(
+        rule! { TABLESAMPLE ~ ^#method ~ ^"(" ~ ^#literal ~ ^PERCENT ~ ^")" },
+        |(_, method, _, percent, _, _)| TableSample { method, percent },
+    )(i)
+}
+
 pub fn table_alias(i: Input) -> IResult<TableAlias> {
     map(
         rule! { #ident | #map(rule! { AS ~ #ident_after_as }, |(_, name)| name) },
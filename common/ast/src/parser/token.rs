@@ -234,6 +234,8 @@ pub enum TokenKind {
     AS,
     #[token("ASC", ignore(ascii_case))]
     ASC,
+    #[token("AT", ignore(ascii_case))]
+    AT,
     #[token("AWS_KEY_ID", ignore(ascii_case))]
     AWS_KEY_ID,
     #[token("AWS_SECRET_KEY", ignore(ascii_case))]
@@ -242,6 +244,8 @@ pub enum TokenKind {
     BETWEEN,
     #[token("BIGINT", ignore(ascii_case))]
     BIGINT,
+    #[token("BLOCK", ignore(ascii_case))]
+    BLOCK,
     #[token("BOOL", ignore(ascii_case))]
     BOOL,
     #[token("BOOLEAN", ignore(ascii_case))]
@@ -466,6 +470,8 @@ pub enum TokenKind {
     PARQUET,
     #[token("PATTERN", ignore(ascii_case))]
     PATTERN,
+    #[token("PERCENT", ignore(ascii_case))]
+    PERCENT,
     #[token("PIPELINE", ignore(ascii_case))]
     PIPELINE,
     #[token("PLAINTEXT_PASSWORD", ignore(ascii_case))]
@@ -490,12 +496,16 @@ pub enum TokenKind {
     RIGHT,
     #[token("RLIKE", ignore(ascii_case))]
     RLIKE,
+    #[token("ROW", ignore(ascii_case))]
+    ROW,
     #[token("SCHEMA", ignore(ascii_case))]
     SCHEMA,
     #[token("SCHEMAS", ignore(ascii_case))]
     SCHEMAS,
     #[token("SECOND", ignore(ascii_case))]
     SECOND,
+    #[token("SEGMENT", ignore(ascii_case))]
+    SEGMENT,
     #[token("SELECT", ignore(ascii_case))]
     SELECT,
     #[token("SET", ignore(ascii_case))]
@@ -514,6 +524,8 @@ pub enum TokenKind {
     SKIP_HEADER,
     #[token("SMALLINT", ignore(ascii_case))]
     SMALLINT,
+    #[token("SNAPSHOT", ignore(ascii_case))]
+    SNAPSHOT,
     #[token("STAGE", ignore(ascii_case))]
     STAGE,
     #[token("STATUS", ignore(ascii_case))]
@@ -526,6 +538,8 @@ pub enum TokenKind {
     TABLE,
     #[token("TABLES", ignore(ascii_case))]
     TABLES,
+    #[token("TABLESAMPLE", ignore(ascii_case))]
+    TABLESAMPLE,
     #[token("TENANTSETTING", ignore(ascii_case))]
     TENANTSETTING,
     #[token("THEN", ignore(ascii_case))]
@@ -851,7 +865,7 @@ impl TokenKind {
             // | TokenKind::SOME
             // | TokenKind::SYMMETRIC
             | TokenKind::TABLE
-            // | TokenKind::TABLESAMPLE
+            | TokenKind::TABLESAMPLE
             | TokenKind::THEN
             | TokenKind::TRAILING
             | TokenKind::TRUE
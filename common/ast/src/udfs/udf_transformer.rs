@@ -68,8 +68,13 @@ impl UDFTransformer {
         })
     }
 
-    fn clone_expr_with_replacement<F>(original_expr: &Expr, replacement_fn: &F) -> Result<Expr>
-    where F: Fn(&Expr) -> Result<Option<Expr>> {
+    pub fn clone_expr_with_replacement<F>(
+        original_expr: &Expr,
+        replacement_fn: &F,
+    ) -> Result<Expr>
+    where
+        F: Fn(&Expr) -> Result<Option<Expr>>,
+    {
         let replacement_opt = replacement_fn(original_expr)?;
 
         match replacement_opt {
@@ -200,6 +200,8 @@ fn test_query() {
             group by c_count
             order by custdist desc, c_count asc, totacctbal
             limit 10, totacctbal"#,
+        r#"select * from t tablesample block (10 percent) as a"#,
+        r#"select * from t1 tablesample row (0.5 percent)"#,
     ];
 
     for case in cases {
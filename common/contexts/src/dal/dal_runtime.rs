@@ -18,6 +18,8 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use common_base::tokio::runtime::Handle;
+use common_tracing::tracing;
+use common_tracing::tracing::Instrument;
 use opendal::ops::OpCreate;
 use opendal::ops::OpDelete;
 use opendal::ops::OpList;
@@ -39,6 +41,9 @@ use opendal::ObjectStreamer;
 /// However, the new processor framework will make sure that all async task running
 /// in the same, global, separate, IO only async runtime, so we can remove `DalRuntime`
 /// after new processor framework finished.
+///
+/// Every operation is traced in a span, which is carried into the storage runtime,
+/// so that object storage calls are chained to the query that issued them.
 #[derive(Clone, Debug)]
 pub struct DalRuntime {
     inner: Option<Arc<dyn Accessor>>,
@@ -75,56 +80,62 @@ impl Layer for DalRuntime {
 
 #[async_trait]
 impl Accessor for DalRuntime {
+    #[tracing::instrument(level = "debug", name = "dal_create", skip_all, fields(path = %args.path))]
     async fn create(&self, args: &OpCreate) -> Result<()> {
         let op = self.get_inner()?;
         let args = args.clone();
         self.runtime
-            .spawn(async move { op.create(&args).await })
+            .spawn(async move { op.create(&args).await }.in_current_span())
             .await
             .expect("join must success")
     }
 
+    #[tracing::instrument(level = "debug", name = "dal_read", skip_all, fields(path = %args.path))]
     async fn read(&self, args: &OpRead) -> Result<BytesReader> {
         let op = self.get_inner()?;
         let args = args.clone();
         self.runtime
-            .spawn(async move { op.read(&args).await })
+            .spawn(async move { op.read(&args).await }.in_current_span())
             .await
             .expect("join must success")
     }
 
+    #[tracing::instrument(level = "debug", name = "dal_write", skip_all, fields(path = %args.path))]
     async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
         let op = self.get_inner()?;
         let args = args.clone();
         self.runtime
-            .spawn(async move { op.write(&args).await })
+            .spawn(async move { op.write(&args).await }.in_current_span())
             .await
             .expect("join must success")
     }
 
+    #[tracing::instrument(level = "debug", name = "dal_stat", skip_all, fields(path = %args.path))]
     async fn stat(&self, args: &OpStat) -> Result<Metadata> {
         let op = self.get_inner()?;
         let args = args.clone();
         self.runtime
-            .spawn(async move { op.stat(&args).await })
+            .spawn(async move { op.stat(&args).await }.in_current_span())
             .await
             .expect("join must success")
     }
 
+    #[tracing::instrument(level = "debug", name = "dal_delete", skip_all, fields(path = %args.path))]
     async fn delete(&self, args: &OpDelete) -> Result<()> {
         let op = self.get_inner()?;
         let args = args.clone();
         self.runtime
-            .spawn(async move { op.delete(&args).await })
+            .spawn(async move { op.delete(&args).await }.in_current_span())
             .await
             .expect("join must success")
     }
 
+    #[tracing::instrument(level = "debug", name = "dal_list", skip_all, fields(path = %args.path))]
     async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
         let op = self.get_inner()?;
         let args = args.clone();
         self.runtime
-            .spawn(async move { op.list(&args).await })
+            .spawn(async move { op.list(&args).await }.in_current_span())
             .await
             .expect("join must success")
     }
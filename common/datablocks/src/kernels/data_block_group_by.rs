@@ -17,6 +17,7 @@ use common_datavalues::DataType;
 use common_datavalues::TypeID;
 use common_exception::Result;
 
+use crate::kernels::HashMethodDictionarySerializer;
 use crate::kernels::HashMethodKeysU16;
 use crate::kernels::HashMethodKeysU32;
 use crate::kernels::HashMethodKeysU64;
@@ -41,6 +42,18 @@ impl DataBlock {
             }
         }
 
+        // Group by strings, which are usually of low cardinality, e.g. the names.
+        let mut all_strings = !column_names.is_empty();
+        for col in column_names {
+            let column = block.try_column_by_name(col)?;
+            all_strings &= column.data_type_id() == TypeID::String;
+        }
+        if all_strings {
+            return Ok(HashMethodKind::DictionarySerializer(
+                HashMethodDictionarySerializer::default(),
+            ));
+        }
+
         let mut group_key_len = 0;
         for col in column_names {
             let column = block.try_column_by_name(col)?;
@@ -80,6 +93,15 @@ impl DataBlock {
                 blocks
             }
 
+            HashMethodKind::DictionarySerializer(s) => {
+                let blocks = s
+                    .group_by(block, column_names)?
+                    .iter()
+                    .map(|(_, _, b)| b.clone())
+                    .collect();
+                blocks
+            }
+
             HashMethodKind::SingleString(s) => {
                 let blocks = s
                     .group_by(block, column_names)?
@@ -119,6 +119,19 @@ pub trait HashMethod {
         group_columns: &[&'a ColumnRef],
         rows: usize,
     ) -> Result<Vec<Self::HashKey<'a>>>;
+
+    /// Build the distinct keys of the rows, with the index of the key of each row in them.
+    ///
+    /// The indices are None if the keys are built for each of the rows, as `build_keys` does,
+    /// which is the default.
+    #[allow(clippy::type_complexity)]
+    fn build_distinct_keys<'a>(
+        &self,
+        group_columns: &[&'a ColumnRef],
+        rows: usize,
+    ) -> Result<(Vec<Self::HashKey<'a>>, Option<Vec<u32>>)> {
+        Ok((self.build_keys(group_columns, rows)?, None))
+    }
 }
 
 pub type HashMethodKeysU8 = HashMethodFixedKeys<u8>;
@@ -130,6 +143,7 @@ pub type HashMethodKeysU64 = HashMethodFixedKeys<u64>;
 /// that is the 'numeric' or 'binary` representation of each column value as hash key.
 pub enum HashMethodKind {
     Serializer(HashMethodSerializer),
+    DictionarySerializer(HashMethodDictionarySerializer),
     SingleString(HashMethodSingleString),
     KeysU8(HashMethodKeysU8),
     KeysU16(HashMethodKeysU16),
@@ -141,6 +155,7 @@ impl HashMethodKind {
     pub fn name(&self) -> String {
         match self {
             HashMethodKind::Serializer(v) => v.name(),
+            HashMethodKind::DictionarySerializer(v) => v.name(),
             HashMethodKind::SingleString(v) => v.name(),
             HashMethodKind::KeysU8(v) => v.name(),
             HashMethodKind::KeysU16(v) => v.name(),
@@ -151,6 +166,7 @@ impl HashMethodKind {
     pub fn data_type(&self) -> DataTypeImpl {
         match self {
             HashMethodKind::Serializer(_) => Vu8::to_data_type(),
+            HashMethodKind::DictionarySerializer(_) => Vu8::to_data_type(),
             HashMethodKind::SingleString(_) => Vu8::to_data_type(),
             HashMethodKind::KeysU8(_) => u8::to_data_type(),
             HashMethodKind::KeysU16(_) => u16::to_data_type(),
@@ -266,6 +282,136 @@ impl HashMethod for HashMethodSerializer {
     }
 }
 
+/// A special case for Group by Strings, which are usually of low cardinality, e.g. the
+/// `GROUP BY country, device`.
+///
+/// The strings of each column are encoded to the ids of a dictionary of the block, and the
+/// ids of the columns are combined to the ids of the groups, thus only the distinct keys are
+/// serialized and hashed into the aggregate state. The keys are the same as the ones of
+/// [`HashMethodSerializer`].
+///
+/// The dictionaries are given up once they grow past half of the rows of the block, the keys of
+/// such a block are serialized for each of the rows, as the ones of [`HashMethodSerializer`] are.
+///
+/// The dictionaries only speed up the building of the keys. The groups are still kept in a
+/// single-level hash table, and are spilled by the final aggregation partitioned by the hash of
+/// their keys, as the groups of the other methods are.
+// TODO: a two-level hash table, bucketed by the same hash as the spilled partitions, would let
+// the buckets be spilled and merged without hashing the keys again.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HashMethodDictionarySerializer {}
+
+/// The max ratio of the distinct keys to the rows of a block, for which the keys are encoded by
+/// dictionaries.
+const MAX_DISTINCT_RATIO: f64 = 0.5;
+
+impl HashMethodDictionarySerializer {
+    #[inline]
+    pub fn get_key(&self, column: &StringColumn, row: usize) -> Vec<u8> {
+        let v = column.get_data(row);
+        v.to_owned()
+    }
+
+    pub fn deserialize_group_columns(
+        &self,
+        keys: Vec<Vec<u8>>,
+        group_fields: &[DataField],
+    ) -> Result<Vec<ColumnRef>> {
+        HashMethodSerializer::default().deserialize_group_columns(keys, group_fields)
+    }
+
+    /// Encodes the strings of the column to the ids of a dictionary, in the order of their first
+    /// occurrences. None if the dictionary grows past `max_distinct` strings.
+    fn dictionary_ids(column: &StringColumn, rows: usize, max_distinct: usize) -> Option<Vec<u32>> {
+        let mut dictionary = HashMap::<&[u8], u32, ahash::RandomState>::default();
+        let mut ids = Vec::with_capacity(rows);
+        for row in 0..rows {
+            let next_id = dictionary.len() as u32;
+            ids.push(*dictionary.entry(column.get_data(row)).or_insert(next_id));
+            if dictionary.len() > max_distinct {
+                return None;
+            }
+        }
+        Some(ids)
+    }
+
+    /// Combines the ids of the groups and the ids of a column to the ids of the finer groups,
+    /// in the order of their first occurrences as well. None if there are more than
+    /// `max_distinct` groups.
+    fn combine_ids(group_ids: &[u32], ids: &[u32], max_distinct: usize) -> Option<Vec<u32>> {
+        let mut groups = HashMap::<(u32, u32), u32, ahash::RandomState>::default();
+        let mut combined = Vec::with_capacity(ids.len());
+        for (group_id, id) in group_ids.iter().zip(ids.iter()) {
+            let next_id = groups.len() as u32;
+            combined.push(*groups.entry((*group_id, *id)).or_insert(next_id));
+            if groups.len() > max_distinct {
+                return None;
+            }
+        }
+        Some(combined)
+    }
+}
+
+impl HashMethod for HashMethodDictionarySerializer {
+    type HashKey<'a> = SmallVu8;
+
+    fn name(&self) -> String {
+        "DictionarySerializer".to_string()
+    }
+
+    fn build_keys(
+        &self,
+        group_columns: &[&ColumnRef],
+        rows: usize,
+    ) -> Result<Vec<Self::HashKey<'_>>> {
+        let (keys, indices) = self.build_distinct_keys(group_columns, rows)?;
+        Ok(match indices {
+            None => keys,
+            Some(indices) => indices.iter().map(|i| keys[*i as usize].clone()).collect(),
+        })
+    }
+
+    fn build_distinct_keys(
+        &self,
+        group_columns: &[&ColumnRef],
+        rows: usize,
+    ) -> Result<(Vec<Self::HashKey<'_>>, Option<Vec<u32>>)> {
+        let max_distinct = (rows as f64 * MAX_DISTINCT_RATIO) as usize;
+        let mut group_ids = vec![0u32; rows];
+        for (index, column) in group_columns.iter().enumerate() {
+            let str_column: &StringColumn = Series::check_get(column)?;
+            let ids = match index {
+                0 => Self::dictionary_ids(str_column, rows, max_distinct),
+                _ => Self::dictionary_ids(str_column, rows, max_distinct)
+                    .and_then(|ids| Self::combine_ids(&group_ids, &ids, max_distinct)),
+            };
+            group_ids = match ids {
+                Some(ids) => ids,
+                // too many distinct keys to be worth the dictionaries
+                None => {
+                    let keys = HashMethodSerializer::default().build_keys(group_columns, rows)?;
+                    return Ok((keys, None));
+                }
+            };
+        }
+
+        // The ids are in the order of their first occurrences, so are the first rows of them.
+        let mut first_rows = Vec::new();
+        for (row, group_id) in group_ids.iter().enumerate() {
+            if *group_id as usize == first_rows.len() {
+                first_rows.push(row as u32);
+            }
+        }
+
+        let mut keys = vec![SmallVu8::new(); first_rows.len()];
+        for column in group_columns {
+            let distinct_column = Series::take(column, &first_rows)?;
+            Series::serialize(&distinct_column, &mut keys, None)?;
+        }
+        Ok((keys, Some(group_ids)))
+    }
+}
+
 pub struct HashMethodFixedKeys<T> {
     t: PhantomData<T>,
 }
@@ -59,3 +59,55 @@ fn test_data_block_group_by_hash() -> Result<()> {
     ]);
     Ok(())
 }
+
+#[test]
+fn test_data_block_group_by_dictionary() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", i8::to_data_type()),
+        DataField::new("x", Vu8::to_data_type()),
+        DataField::new("y", Vu8::to_data_type()),
+    ]);
+
+    let block = DataBlock::create(schema, vec![
+        Series::from_data(vec![1i8, 1, 2, 1, 2, 3]),
+        Series::from_data(vec!["x1", "x1", "x2", "x1", "x2", "x1"]),
+        Series::from_data(vec!["y1", "y2", "y1", "y2", "y1", "y1"]),
+    ]);
+
+    let method = DataBlock::choose_hash_method(&block, &["x".to_string(), "y".to_string()])?;
+    assert_eq!(
+        method.name(),
+        HashMethodDictionarySerializer::default().name()
+    );
+
+    let method = DataBlock::choose_hash_method(&block, &["a".to_string(), "x".to_string()])?;
+    assert_eq!(method.name(), HashMethodSerializer::default().name());
+
+    let group_columns = vec![
+        block.try_column_by_name("x")?,
+        block.try_column_by_name("y")?,
+    ];
+    let hash = HashMethodDictionarySerializer::default();
+    let (keys, indices) = hash.build_distinct_keys(&group_columns, block.num_rows())?;
+    assert_eq!(indices, Some(vec![0, 1, 2, 1, 2, 0]));
+
+    // The keys are the same as the ones of the serializer.
+    let serialized_keys = HashMethodSerializer::default().build_keys(&group_columns, 6)?;
+    assert_eq!(keys, vec![
+        serialized_keys[0].clone(),
+        serialized_keys[1].clone(),
+        serialized_keys[2].clone(),
+    ]);
+    assert_eq!(hash.build_keys(&group_columns, 6)?, serialized_keys);
+
+    // Too many distinct keys, which are serialized for each of the rows.
+    let column = Series::from_data(vec!["x1", "x2", "x3", "x4", "x1", "x5"]);
+    let group_columns = vec![&column];
+    let (keys, indices) = hash.build_distinct_keys(&group_columns, 6)?;
+    assert_eq!(indices, None);
+    assert_eq!(
+        keys,
+        HashMethodSerializer::default().build_keys(&group_columns, 6)?
+    );
+    Ok(())
+}
@@ -27,15 +27,41 @@ pub struct DataField {
     name: String,
     /// default_expr is serialized representation from PlanExpression
     default_expr: Option<Vec<u8>>,
+    /// the expression of a generated column, see [ComputedExpr]
+    #[serde(default)]
+    #[ignore_malloc_size_of = "insignificant"]
+    computed_expr: Option<ComputedExpr>,
     #[ignore_malloc_size_of = "insignificant"]
     data_type: DataTypeImpl,
 }
 
+/// The expression which generates the values of a column, serialized from PlanExpression.
+#[derive(serde::Serialize, serde::Deserialize, Eq, PartialEq, Clone, Debug)]
+pub enum ComputedExpr {
+    /// The values are computed when the rows are written, and stored as the other columns
+    Stored(Vec<u8>),
+    /// The values are computed when the column is read, nothing is stored
+    Virtual(Vec<u8>),
+}
+
+impl ComputedExpr {
+    pub fn expr(&self) -> &Vec<u8> {
+        match self {
+            ComputedExpr::Stored(expr) | ComputedExpr::Virtual(expr) => expr,
+        }
+    }
+
+    pub fn is_stored(&self) -> bool {
+        matches!(self, ComputedExpr::Stored(_))
+    }
+}
+
 impl DataField {
     pub fn new(name: &str, data_type: DataTypeImpl) -> Self {
         DataField {
             name: name.to_string(),
             default_expr: None,
+            computed_expr: None,
             data_type,
         }
     }
@@ -45,6 +71,7 @@ impl DataField {
         DataField {
             name: name.to_string(),
             default_expr: None,
+            computed_expr: None,
             data_type,
         }
     }
@@ -55,6 +82,12 @@ impl DataField {
         self
     }
 
+    #[must_use]
+    pub fn with_computed_expr(mut self, computed_expr: Option<ComputedExpr>) -> Self {
+        self.computed_expr = computed_expr;
+        self
+    }
+
     pub fn name(&self) -> &String {
         &self.name
     }
@@ -67,6 +100,10 @@ impl DataField {
         &self.default_expr
     }
 
+    pub fn computed_expr(&self) -> &Option<ComputedExpr> {
+        &self.computed_expr
+    }
+
     #[inline]
     pub fn is_nullable(&self) -> bool {
         self.data_type.is_nullable()
@@ -123,6 +160,13 @@ impl std::fmt::Debug for DataField {
                 &String::from_utf8(default_expr.to_owned()).unwrap(),
             );
         }
+        if let Some(ref computed_expr) = self.computed_expr {
+            debug_struct.field(
+                "computed_expr",
+                &String::from_utf8(computed_expr.expr().to_owned()).unwrap(),
+            );
+            debug_struct.field("stored", &computed_expr.is_stored());
+        }
         debug_struct.finish()
     }
 }
@@ -142,6 +142,10 @@ build_exceptions! {
     // Tenant error codes.
     TenantIsEmpty(1101),
     IndexOutOfBounds(1102),
+
+    // Resource error codes.
+    QueryMemoryLimitExceeded(1103),
+    QueryQueueTimeout(1104),
 }
 
 // Metasvr errors [2001, 3000].
@@ -153,6 +157,8 @@ build_exceptions! {
 
     TableVersionMismatched(2009),
     OCCRetryFailure(2011),
+    TableCommitConflict(2012),
+    ReadOnlyTable(2013),
 
     // User api error codes.
     UnknownUser(2201),
@@ -167,6 +173,9 @@ build_exceptions! {
     IllegalMetaState(2304),
     MetaNodeInternalError(2305),
     ViewAlreadyExists(2306),
+    AggregatingIndexAlreadyExists(2307),
+    VirtualColumnAlreadyExists(2308),
+    ColumnAlreadyExists(2309),
 
     // Cluster error codes.
     ClusterUnknownNode(2401),
@@ -177,11 +186,38 @@ build_exceptions! {
     StageAlreadyExists(2502),
     IllegalUserStageFormat(2503),
 
+    // Pipe error codes.
+    UnknownPipe(2511),
+    PipeAlreadyExists(2512),
+    IllegalPipeFormat(2513),
+    PipeSourceError(2514),
+
     // User defined function error codes.
     IllegalUDFFormat(2601),
     UnknownUDF(2602),
     UdfAlreadyExists(2603),
 
+    // Row access policy error codes.
+    UnknownRowAccessPolicy(2611),
+    RowAccessPolicyAlreadyExists(2612),
+    IllegalRowAccessPolicy(2613),
+
+    // Network policy error codes.
+    UnknownNetworkPolicy(2621),
+    NetworkPolicyAlreadyExists(2622),
+    IllegalNetworkPolicy(2623),
+    NetworkPolicyViolation(2624),
+
+    // Connection error codes.
+    UnknownConnection(2631),
+    ConnectionAlreadyExists(2632),
+    IllegalConnection(2633),
+
+    // Backup error codes.
+    UnknownBackup(2641),
+    BackupAlreadyExists(2642),
+    IllegalBackup(2643),
+
     // Database error codes.
     UnknownDatabaseEngine(2701),
     UnknownTableEngine(2702),
@@ -191,6 +227,7 @@ build_exceptions! {
     ShareAlreadyExists(2705),
     UnknownShare(2706),
     UnknownShareId(2707),
+    WrongShareObject(2708),
 
     // Variable error codes.
     UnknownVariable(2801),
@@ -206,6 +243,7 @@ build_exceptions! {
 build_exceptions! {
     StorageNotFound(3001),
     StoragePermissionDenied(3002),
+    TableHistoricalDataNotFound(3003),
     StorageOther(4000)
 }
 
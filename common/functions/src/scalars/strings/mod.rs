@@ -103,6 +103,7 @@ pub use substring_index::SubstringIndexFunction;
 pub use trim::LTrimFunction;
 pub use trim::RTrimFunction;
 pub use trim::TrimFunction;
+pub use unhex::TryUnhexFunction;
 pub use unhex::UnhexFunction;
 pub use upper::UpperFunction;
 
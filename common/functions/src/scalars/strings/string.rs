@@ -57,6 +57,7 @@ use crate::scalars::StrcmpFunction;
 use crate::scalars::SubstringFunction;
 use crate::scalars::SubstringIndexFunction;
 use crate::scalars::TrimFunction;
+use crate::scalars::TryUnhexFunction;
 use crate::scalars::UnhexFunction;
 use crate::scalars::UpperFunction;
 
@@ -89,9 +90,14 @@ impl StringFunction {
         factory.register("regexp_replace", RegexpReplaceFunction::desc());
         factory.register("regexp_substr", RegexpSubStrFunction::desc());
         factory.register("bin", BinFunction::desc());
+        factory.register("to_binary_string", BinFunction::desc());
         factory.register("oct", OctFunction::desc());
         factory.register("hex", HexFunction::desc());
+        factory.register("to_hex", HexFunction::desc());
         factory.register("unhex", UnhexFunction::desc());
+        factory.register("from_hex", UnhexFunction::desc());
+        factory.register("try_unhex", TryUnhexFunction::desc());
+        factory.register("try_from_hex", TryUnhexFunction::desc());
         factory.register("repeat", RepeatFunction::desc());
         factory.register("substring", SubstringFunction::desc());
         factory.register("mid", SubstringFunction::desc());
@@ -24,16 +24,22 @@ use crate::scalars::FunctionContext;
 use crate::scalars::FunctionDescription;
 use crate::scalars::FunctionFeatures;
 
+#[doc(alias = "TryFromHexFunction")]
+pub type TryUnhexFunction = UnhexFunctionImpl<true>;
+
+#[doc(alias = "FromHexFunction")]
+pub type UnhexFunction = UnhexFunctionImpl<false>;
+
 #[derive(Clone)]
-pub struct UnhexFunction {
-    _display_name: String,
+pub struct UnhexFunctionImpl<const SUPPRESS_PARSE_ERROR: bool> {
+    display_name: String,
 }
 
-impl UnhexFunction {
+impl<const SUPPRESS_PARSE_ERROR: bool> UnhexFunctionImpl<SUPPRESS_PARSE_ERROR> {
     pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
         assert_string(args[0])?;
-        Ok(Box::new(UnhexFunction {
-            _display_name: display_name.to_string(),
+        Ok(Box::new(UnhexFunctionImpl::<SUPPRESS_PARSE_ERROR> {
+            display_name: display_name.to_string(),
         }))
     }
 
@@ -43,13 +49,17 @@ impl UnhexFunction {
     }
 }
 
-impl Function for UnhexFunction {
+impl<const SUPPRESS_PARSE_ERROR: bool> Function for UnhexFunctionImpl<SUPPRESS_PARSE_ERROR> {
     fn name(&self) -> &str {
-        "unhex"
+        &*self.display_name
     }
 
     fn return_type(&self) -> DataTypeImpl {
-        StringType::new_impl()
+        if SUPPRESS_PARSE_ERROR {
+            NullableType::new_impl(StringType::new_impl())
+        } else {
+            StringType::new_impl()
+        }
     }
 
     fn eval(
@@ -58,46 +68,40 @@ impl Function for UnhexFunction {
         columns: &ColumnsWithField,
         input_rows: usize,
     ) -> Result<ColumnRef> {
-        const BUFFER_SIZE: usize = 32;
+        let viewer = Vu8::try_create_viewer(columns[0].column())?;
 
-        let col = columns[0]
-            .column()
-            .as_any()
-            .downcast_ref::<StringColumn>()
-            .unwrap();
+        if SUPPRESS_PARSE_ERROR {
+            let mut builder = NullableColumnBuilder::<Vu8>::with_capacity(input_rows);
+            for (i, val) in viewer.iter().enumerate() {
+                let mut buffer = vec![0u8; val.len() / 2];
+                match hex::decode_to_slice(val, &mut buffer) {
+                    Ok(()) => builder.append(&buffer, viewer.valid_at(i)),
+                    Err(_) => builder.append_null(),
+                }
+            }
+            return Ok(builder.build(input_rows));
+        }
 
         let mut builder: ColumnBuilder<Vu8> = ColumnBuilder::with_capacity(input_rows);
-
-        for val in col.iter() {
-            if val.len() <= BUFFER_SIZE * 2 {
-                let size = val.len() / 2;
-                let mut buffer = vec![0u8; size];
-                let buffer = &mut buffer[0..size];
-
-                match hex::decode_to_slice(val, buffer) {
-                    Ok(()) => builder.append(buffer),
-                    Err(err) => {
-                        return Err(ErrorCode::UnexpectedError(format!(
-                            "{} can not unhex because: {}",
-                            String::from_utf8_lossy(val),
-                            err
-                        )))
-                    }
+        for val in viewer.iter() {
+            let mut buffer = vec![0u8; val.len() / 2];
+            match hex::decode_to_slice(val, &mut buffer) {
+                Ok(()) => builder.append(&buffer),
+                Err(err) => {
+                    return Err(ErrorCode::UnexpectedError(format!(
+                        "{} can not unhex because: {}",
+                        String::from_utf8_lossy(val),
+                        err
+                    )))
                 }
-            } else {
-                return Err(ErrorCode::UnexpectedError(format!(
-                    "{} is too long than buffer size",
-                    String::from_utf8_lossy(val)
-                )));
             }
         }
-
         Ok(builder.build(input_rows))
     }
 }
 
-impl fmt::Display for UnhexFunction {
+impl<const SUPPRESS_PARSE_ERROR: bool> fmt::Display for UnhexFunctionImpl<SUPPRESS_PARSE_ERROR> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "UNHEX")
+        write!(f, "{}", self.display_name.to_uppercase())
     }
 }
@@ -62,6 +62,11 @@ impl<T> fmt::Display for UUIDCreatorFunction<T> {
 
 pub trait UUIDCreator {
     fn create() -> Uuid;
+
+    /// Whether a different uuid is created for each row, instead of one for all the rows
+    fn per_row() -> bool {
+        false
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -71,6 +76,10 @@ impl UUIDCreator for UUIDv4 {
     fn create() -> Uuid {
         Uuid::new_v4()
     }
+
+    fn per_row() -> bool {
+        true
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -99,6 +108,13 @@ where T: UUIDCreator + Clone + Sync + Send + 'static
         _columns: &common_datavalues::ColumnsWithField,
         input_rows: usize,
     ) -> Result<common_datavalues::ColumnRef> {
+        if T::per_row() {
+            let uuids = (0..input_rows)
+                .map(|_| T::create().to_string())
+                .collect::<Vec<_>>();
+            return Ok(StringColumn::new_from_slice(uuids).arc());
+        }
+
         let uuid = T::create();
         let col = StringColumn::new_from_slice(vec![uuid.to_string()]);
 
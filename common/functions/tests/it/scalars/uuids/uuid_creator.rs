@@ -12,8 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
+
 use common_datavalues::prelude::*;
 use common_exception::Result;
+use common_functions::scalars::FunctionContext;
+use common_functions::scalars::FunctionFactory;
 
 use crate::scalars::scalar_function_test::test_scalar_functions;
 use crate::scalars::scalar_function_test::ScalarFunctionTest;
@@ -41,3 +45,14 @@ fn test_uuid_creator_functions() -> Result<()> {
 
     test_scalar_functions("gen_zero_uuid", &tests)
 }
+
+#[test]
+fn test_uuid_v4_per_row() -> Result<()> {
+    // the random uuids are not shared by the rows, e.g. the ones filled by `DEFAULT uuid()`
+    let func = FunctionFactory::instance().get("uuid", &[])?;
+    let column = func.eval(FunctionContext::default(), &[], 3)?;
+    let column = Series::check_get_scalar::<Vu8>(&column)?;
+    let uuids = column.iter().collect::<HashSet<_>>();
+    assert_eq!(uuids.len(), 3);
+    Ok(())
+}
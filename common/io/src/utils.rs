@@ -122,13 +122,9 @@ pub fn is_control_ascii(c: u8) -> bool {
 }
 
 pub fn parse_escape_string(bs: &[u8]) -> String {
+    // The escapes are ascii, so the multi-byte chars are kept as they are.
     let bs = parse_escape_bytes(bs);
-
-    let mut cs = Vec::with_capacity(bs.len());
-    for b in bs {
-        cs.push(b as char);
-    }
-    cs.iter().collect()
+    String::from_utf8_lossy(&bs).to_string()
 }
 
 pub fn parse_escape_bytes(bs: &[u8]) -> Vec<u8> {
@@ -44,4 +44,11 @@ fn parse_escape() {
     for c in cases {
         assert_eq!(parse_escape_bytes(c[0].as_bytes()), c[1].as_bytes());
     }
+
+    let cases = vec![["||", "||"], ["\\r\\n", "\r\n"], ["‖", "‖"], [
+        "\\t‖", "\t‖",
+    ]];
+    for c in cases {
+        assert_eq!(parse_escape_string(c[0].as_bytes()), c[1]);
+    }
 }
@@ -0,0 +1,172 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::escape_for_key;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_types::Connection;
+use common_meta_types::MatchSeq;
+use common_meta_types::MatchSeqExt;
+use common_meta_types::OkOrExist;
+use common_meta_types::Operation;
+use common_meta_types::SeqV;
+use common_meta_types::UpsertKVAction;
+use common_proto_conv::FromToProto;
+use common_protos::pb;
+use common_protos::prost::Message;
+
+use crate::connection::ConnectionApi;
+
+static CONNECTION_API_KEY_PREFIX: &str = "__fd_connections";
+
+pub struct ConnectionMgr {
+    kv_api: Arc<dyn KVApi>,
+    connection_prefix: String,
+}
+
+impl ConnectionMgr {
+    pub fn create(kv_api: Arc<dyn KVApi>, tenant: &str) -> Result<Self> {
+        if tenant.is_empty() {
+            return Err(ErrorCode::TenantIsEmpty(
+                "Tenant can not empty(while connection mgr create)",
+            ));
+        }
+
+        Ok(ConnectionMgr {
+            kv_api,
+            connection_prefix: format!("{}/{}", CONNECTION_API_KEY_PREFIX, escape_for_key(tenant)?),
+        })
+    }
+
+    fn serialize(connection: &Connection) -> Result<Vec<u8>> {
+        let p = connection
+            .to_pb()
+            .map_err(|e| ErrorCode::IllegalConnection(e.to_string()))?;
+        let mut buf = vec![];
+        p.encode(&mut buf)
+            .map_err(|e| ErrorCode::IllegalConnection(e.to_string()))?;
+        Ok(buf)
+    }
+
+    fn deserialize(data: &[u8]) -> Result<Connection> {
+        let p = pb::Connection::decode(data)
+            .map_err(|e| ErrorCode::IllegalConnection(e.to_string()))?;
+        Connection::from_pb(p).map_err(|e| ErrorCode::IllegalConnection(e.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl ConnectionApi for ConnectionMgr {
+    async fn add_connection(&self, connection: Connection) -> Result<u64> {
+        let seq = MatchSeq::Exact(0);
+        let val = Operation::Update(Self::serialize(&connection)?);
+        let key = format!(
+            "{}/{}",
+            self.connection_prefix,
+            escape_for_key(&connection.name)?
+        );
+        let upsert_info = self
+            .kv_api
+            .upsert_kv(UpsertKVAction::new(&key, seq, val, None));
+
+        let res = upsert_info.await?.into_add_result()?;
+
+        match res.res {
+            OkOrExist::Ok(v) => Ok(v.seq),
+            OkOrExist::Exists(v) => Err(ErrorCode::ConnectionAlreadyExists(format!(
+                "Connection already exists, seq [{}]",
+                v.seq
+            ))),
+        }
+    }
+
+    async fn get_connection(&self, name: &str, seq: Option<u64>) -> Result<SeqV<Connection>> {
+        let key = format!("{}/{}", self.connection_prefix, escape_for_key(name)?);
+        let res = self.kv_api.get_kv(&key).await?;
+        let seq_value = res
+            .ok_or_else(|| ErrorCode::UnknownConnection(format!("Unknown connection {}", name)))?;
+
+        match MatchSeq::from(seq).match_seq(&seq_value) {
+            Ok(_) => Ok(SeqV {
+                seq: seq_value.seq,
+                meta: seq_value.meta,
+                data: Self::deserialize(&seq_value.data)?,
+            }),
+            Err(_) => Err(ErrorCode::UnknownConnection(format!(
+                "Unknown connection {}",
+                name
+            ))),
+        }
+    }
+
+    async fn update_connection(&self, connection: Connection, seq: Option<u64>) -> Result<u64> {
+        // Only an existing connection can be updated.
+        let seq = match seq {
+            None => MatchSeq::GE(1),
+            Some(seq) => MatchSeq::Exact(seq),
+        };
+        let val = Operation::Update(Self::serialize(&connection)?);
+        let key = format!(
+            "{}/{}",
+            self.connection_prefix,
+            escape_for_key(&connection.name)?
+        );
+        let upsert_info = self
+            .kv_api
+            .upsert_kv(UpsertKVAction::new(&key, seq, val, None));
+
+        match upsert_info.await?.result {
+            Some(SeqV { seq: s, .. }) => Ok(s),
+            None => Err(ErrorCode::UnknownConnection(format!(
+                "Unknown connection {}, or seq not match",
+                connection.name
+            ))),
+        }
+    }
+
+    async fn get_connections(&self) -> Result<Vec<Connection>> {
+        let values = self.kv_api.prefix_list_kv(&self.connection_prefix).await?;
+
+        let mut connections = Vec::with_capacity(values.len());
+        for (_, value) in values {
+            connections.push(Self::deserialize(&value.data)?);
+        }
+        Ok(connections)
+    }
+
+    async fn drop_connection(&self, name: &str, seq: Option<u64>) -> Result<()> {
+        let key = format!("{}/{}", self.connection_prefix, escape_for_key(name)?);
+        let res = self
+            .kv_api
+            .upsert_kv(UpsertKVAction::new(
+                &key,
+                seq.into(),
+                Operation::Delete,
+                None,
+            ))
+            .await?;
+
+        if res.prev.is_some() && res.result.is_none() {
+            Ok(())
+        } else {
+            Err(ErrorCode::UnknownConnection(format!(
+                "Unknown connection {}",
+                name
+            )))
+        }
+    }
+}
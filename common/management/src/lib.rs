@@ -13,7 +13,11 @@
 // limitations under the License.
 
 mod cluster;
+mod connection;
+mod network_policy;
+mod pipe;
 mod role;
+mod row_access_policy;
 mod setting;
 mod stage;
 mod udf;
@@ -22,8 +26,16 @@ mod warehouse;
 
 pub use cluster::ClusterApi;
 pub use cluster::ClusterMgr;
+pub use connection::ConnectionApi;
+pub use connection::ConnectionMgr;
+pub use network_policy::NetworkPolicyApi;
+pub use network_policy::NetworkPolicyMgr;
+pub use pipe::PipeApi;
+pub use pipe::PipeMgr;
 pub use role::RoleApi;
 pub use role::RoleMgr;
+pub use row_access_policy::RowAccessPolicyApi;
+pub use row_access_policy::RowAccessPolicyMgr;
 pub use setting::SettingApi;
 pub use setting::SettingMgr;
 pub use stage::StageApi;
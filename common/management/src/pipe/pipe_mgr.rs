@@ -0,0 +1,164 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::escape_for_key;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_types::IntoSeqV;
+use common_meta_types::MatchSeq;
+use common_meta_types::MatchSeqExt;
+use common_meta_types::OkOrExist;
+use common_meta_types::Operation;
+use common_meta_types::PipeFileInfo;
+use common_meta_types::PipeInfo;
+use common_meta_types::SeqV;
+use common_meta_types::UpsertKVAction;
+
+use crate::pipe::PipeApi;
+
+static PIPE_API_KEY_PREFIX: &str = "__fd_pipes";
+static PIPE_FILE_API_KEY_PREFIX: &str = "__fd_pipe_files";
+
+pub struct PipeMgr {
+    kv_api: Arc<dyn KVApi>,
+    pipe_prefix: String,
+    pipe_file_prefix: String,
+}
+
+impl PipeMgr {
+    pub fn create(kv_api: Arc<dyn KVApi>, tenant: &str) -> Result<Self> {
+        if tenant.is_empty() {
+            return Err(ErrorCode::TenantIsEmpty(
+                "Tenant can not empty(while pipe mgr create)",
+            ));
+        }
+
+        Ok(PipeMgr {
+            kv_api,
+            pipe_prefix: format!("{}/{}", PIPE_API_KEY_PREFIX, escape_for_key(tenant)?),
+            pipe_file_prefix: format!("{}/{}", PIPE_FILE_API_KEY_PREFIX, escape_for_key(tenant)?),
+        })
+    }
+
+    // The prefix of the files of a pipe, which ends with `/`, so that it is not a prefix of the
+    // files of other pipes.
+    fn pipe_files_prefix(&self, pipe_name: &str) -> Result<String> {
+        Ok(format!(
+            "{}/{}/",
+            self.pipe_file_prefix,
+            escape_for_key(pipe_name)?
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl PipeApi for PipeMgr {
+    async fn add_pipe(&self, info: PipeInfo) -> Result<u64> {
+        let seq = MatchSeq::Exact(0);
+        let val = Operation::Update(serde_json::to_vec(&info)?);
+        let key = format!("{}/{}", self.pipe_prefix, escape_for_key(&info.pipe_name)?);
+        let upsert_info = self
+            .kv_api
+            .upsert_kv(UpsertKVAction::new(&key, seq, val, None));
+
+        let res = upsert_info.await?.into_add_result()?;
+
+        match res.res {
+            OkOrExist::Ok(v) => Ok(v.seq),
+            OkOrExist::Exists(v) => Err(ErrorCode::PipeAlreadyExists(format!(
+                "Pipe already exists, seq [{}]",
+                v.seq
+            ))),
+        }
+    }
+
+    async fn get_pipe(&self, name: &str, seq: Option<u64>) -> Result<SeqV<PipeInfo>> {
+        let key = format!("{}/{}", self.pipe_prefix, escape_for_key(name)?);
+        let res = self.kv_api.get_kv(&key).await?;
+        let seq_value =
+            res.ok_or_else(|| ErrorCode::UnknownPipe(format!("Unknown pipe {}", name)))?;
+
+        match MatchSeq::from(seq).match_seq(&seq_value) {
+            Ok(_) => Ok(seq_value.into_seqv()?),
+            Err(_) => Err(ErrorCode::UnknownPipe(format!("Unknown pipe {}", name))),
+        }
+    }
+
+    async fn get_pipes(&self) -> Result<Vec<PipeInfo>> {
+        let values = self.kv_api.prefix_list_kv(&self.pipe_prefix).await?;
+
+        let mut pipe_infos = Vec::with_capacity(values.len());
+        for (_, value) in values {
+            let pipe_info = PipeInfo::try_from(value.data)?;
+            pipe_infos.push(pipe_info);
+        }
+        Ok(pipe_infos)
+    }
+
+    async fn drop_pipe(&self, name: &str, seq: Option<u64>) -> Result<()> {
+        let key = format!("{}/{}", self.pipe_prefix, escape_for_key(name)?);
+        let res = self
+            .kv_api
+            .upsert_kv(UpsertKVAction::new(
+                &key,
+                seq.into(),
+                Operation::Delete,
+                None,
+            ))
+            .await?;
+
+        if res.prev.is_none() || res.result.is_some() {
+            return Err(ErrorCode::UnknownPipe(format!("Unknown pipe {}", name)));
+        }
+
+        let prefix = self.pipe_files_prefix(name)?;
+        for (key, _) in self.kv_api.prefix_list_kv(&prefix).await? {
+            self.kv_api
+                .upsert_kv(UpsertKVAction::new(
+                    &key,
+                    MatchSeq::Any,
+                    Operation::Delete,
+                    None,
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn add_pipe_files(&self, pipe_name: &str, files: Vec<PipeFileInfo>) -> Result<()> {
+        let prefix = self.pipe_files_prefix(pipe_name)?;
+        for file in files {
+            let key = format!("{}{}", prefix, escape_for_key(&file.file_name)?);
+            let val = Operation::Update(serde_json::to_vec(&file)?);
+            self.kv_api
+                .upsert_kv(UpsertKVAction::new(&key, MatchSeq::Any, val, None))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn get_pipe_files(&self, pipe_name: &str) -> Result<Vec<PipeFileInfo>> {
+        let prefix = self.pipe_files_prefix(pipe_name)?;
+        let values = self.kv_api.prefix_list_kv(&prefix).await?;
+
+        let mut files = Vec::with_capacity(values.len());
+        for (_, value) in values {
+            files.push(PipeFileInfo::try_from(value.data)?);
+        }
+        Ok(files)
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::escape_for_key;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_types::MatchSeq;
+use common_meta_types::MatchSeqExt;
+use common_meta_types::OkOrExist;
+use common_meta_types::Operation;
+use common_meta_types::RowAccessPolicy;
+use common_meta_types::SeqV;
+use common_meta_types::UpsertKVAction;
+use common_proto_conv::FromToProto;
+use common_protos::pb;
+use common_protos::prost::Message;
+
+use crate::row_access_policy::RowAccessPolicyApi;
+
+static ROW_ACCESS_POLICY_API_KEY_PREFIX: &str = "__fd_row_access_policies";
+
+pub struct RowAccessPolicyMgr {
+    kv_api: Arc<dyn KVApi>,
+    policy_prefix: String,
+}
+
+impl RowAccessPolicyMgr {
+    pub fn create(kv_api: Arc<dyn KVApi>, tenant: &str) -> Result<Self> {
+        if tenant.is_empty() {
+            return Err(ErrorCode::TenantIsEmpty(
+                "Tenant can not empty(while row access policy mgr create)",
+            ));
+        }
+
+        Ok(RowAccessPolicyMgr {
+            kv_api,
+            policy_prefix: format!(
+                "{}/{}",
+                ROW_ACCESS_POLICY_API_KEY_PREFIX,
+                escape_for_key(tenant)?
+            ),
+        })
+    }
+
+    // The policies are kept as protobuf messages, which stay readable across versions.
+    fn serialize(policy: &RowAccessPolicy) -> Result<Vec<u8>> {
+        let p = policy
+            .to_pb()
+            .map_err(|e| ErrorCode::IllegalRowAccessPolicy(e.to_string()))?;
+        let mut buf = vec![];
+        p.encode(&mut buf)
+            .map_err(|e| ErrorCode::IllegalRowAccessPolicy(e.to_string()))?;
+        Ok(buf)
+    }
+
+    fn deserialize(data: &[u8]) -> Result<RowAccessPolicy> {
+        let p = pb::RowAccessPolicy::decode(data)
+            .map_err(|e| ErrorCode::IllegalRowAccessPolicy(e.to_string()))?;
+        RowAccessPolicy::from_pb(p).map_err(|e| ErrorCode::IllegalRowAccessPolicy(e.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl RowAccessPolicyApi for RowAccessPolicyMgr {
+    async fn add_policy(&self, policy: RowAccessPolicy) -> Result<u64> {
+        let seq = MatchSeq::Exact(0);
+        let val = Operation::Update(Self::serialize(&policy)?);
+        let key = format!("{}/{}", self.policy_prefix, escape_for_key(&policy.name)?);
+        let upsert_info = self
+            .kv_api
+            .upsert_kv(UpsertKVAction::new(&key, seq, val, None));
+
+        let res = upsert_info.await?.into_add_result()?;
+
+        match res.res {
+            OkOrExist::Ok(v) => Ok(v.seq),
+            OkOrExist::Exists(v) => Err(ErrorCode::RowAccessPolicyAlreadyExists(format!(
+                "Row access policy already exists, seq [{}]",
+                v.seq
+            ))),
+        }
+    }
+
+    async fn get_policy(&self, name: &str, seq: Option<u64>) -> Result<SeqV<RowAccessPolicy>> {
+        let key = format!("{}/{}", self.policy_prefix, escape_for_key(name)?);
+        let res = self.kv_api.get_kv(&key).await?;
+        let seq_value = res.ok_or_else(|| {
+            ErrorCode::UnknownRowAccessPolicy(format!("Unknown row access policy {}", name))
+        })?;
+
+        match MatchSeq::from(seq).match_seq(&seq_value) {
+            Ok(_) => Ok(SeqV {
+                seq: seq_value.seq,
+                meta: seq_value.meta,
+                data: Self::deserialize(&seq_value.data)?,
+            }),
+            Err(_) => Err(ErrorCode::UnknownRowAccessPolicy(format!(
+                "Unknown row access policy {}",
+                name
+            ))),
+        }
+    }
+
+    async fn get_policies(&self) -> Result<Vec<RowAccessPolicy>> {
+        let values = self.kv_api.prefix_list_kv(&self.policy_prefix).await?;
+
+        let mut policies = Vec::with_capacity(values.len());
+        for (_, value) in values {
+            policies.push(Self::deserialize(&value.data)?);
+        }
+        Ok(policies)
+    }
+
+    async fn drop_policy(&self, name: &str, seq: Option<u64>) -> Result<()> {
+        let key = format!("{}/{}", self.policy_prefix, escape_for_key(name)?);
+        let res = self
+            .kv_api
+            .upsert_kv(UpsertKVAction::new(
+                &key,
+                seq.into(),
+                Operation::Delete,
+                None,
+            ))
+            .await?;
+
+        if res.prev.is_some() && res.result.is_none() {
+            Ok(())
+        } else {
+            Err(ErrorCode::UnknownRowAccessPolicy(format!(
+                "Unknown row access policy {}",
+                name
+            )))
+        }
+    }
+}
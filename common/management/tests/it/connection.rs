@@ -0,0 +1,123 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datavalues::chrono::TimeZone;
+use common_datavalues::chrono::Utc;
+use common_exception::Result;
+use common_management::*;
+use common_meta_api::KVApi;
+use common_meta_embedded::MetaEmbedded;
+use common_meta_types::Connection;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_add_connection() -> Result<()> {
+    let (kv_api, connection_api) = new_connection_api().await?;
+
+    let connection = create_test_connection();
+    connection_api.add_connection(connection.clone()).await?;
+    let value = kv_api.get_kv("__fd_connections/admin/my_s3").await?;
+    assert!(value.is_some());
+
+    let got = connection_api.get_connection("my_s3", None).await?;
+    assert_eq!(got.seq, 1);
+    assert_eq!(got.data, connection);
+
+    match connection_api.add_connection(connection).await {
+        Ok(_) => panic!("Already exists add connection must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2632),
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_update_connection() -> Result<()> {
+    let (_, connection_api) = new_connection_api().await?;
+
+    let mut connection = create_test_connection();
+    match connection_api
+        .update_connection(connection.clone(), None)
+        .await
+    {
+        Ok(_) => panic!("Unknown connection update must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2631),
+    }
+
+    connection_api.add_connection(connection.clone()).await?;
+    connection.encrypted_credentials = vec![5, 6, 7, 8];
+    connection.updated_on = Utc.ymd(2022, 6, 2).and_hms(12, 0, 0);
+    let seq = connection_api
+        .update_connection(connection.clone(), Some(1))
+        .await?;
+    assert_eq!(seq, 2);
+
+    let got = connection_api.get_connection("my_s3", None).await?;
+    assert_eq!(got.data, connection);
+
+    match connection_api
+        .update_connection(connection.clone(), Some(1))
+        .await
+    {
+        Ok(_) => panic!("Seq mismatched connection update must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2631),
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_drop_connection() -> Result<()> {
+    let (_, connection_api) = new_connection_api().await?;
+
+    let connection = create_test_connection();
+    connection_api.add_connection(connection.clone()).await?;
+
+    let connections = connection_api.get_connections().await?;
+    assert_eq!(connections, vec![connection.clone()]);
+
+    connection_api
+        .drop_connection(&connection.name, None)
+        .await?;
+
+    let connections = connection_api.get_connections().await?;
+    assert_eq!(connections, vec![]);
+
+    match connection_api.drop_connection(&connection.name, None).await {
+        Ok(_) => panic!("Unknown connection drop must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2631),
+    }
+
+    Ok(())
+}
+
+fn create_test_connection() -> Connection {
+    Connection {
+        name: "my_s3".to_string(),
+        storage_type: "s3".to_string(),
+        encrypted_credentials: vec![1, 2, 3, 4],
+        master_key_id: "0123456789abcdef".to_string(),
+        comment: "".to_string(),
+        created_on: Utc.ymd(2022, 6, 1).and_hms(12, 0, 0),
+        updated_on: Utc.ymd(2022, 6, 1).and_hms(12, 0, 0),
+    }
+}
+
+async fn new_connection_api() -> Result<(Arc<MetaEmbedded>, ConnectionMgr)> {
+    let test_api = Arc::new(MetaEmbedded::new_temp().await?);
+    let mgr = ConnectionMgr::create(test_api.clone(), "admin")?;
+    Ok((test_api, mgr))
+}
@@ -13,6 +13,10 @@
 // limitations under the License.
 
 mod cluster;
+mod connection;
+mod network_policy;
+mod pipe;
+mod row_access_policy;
 mod setting;
 mod stage;
 mod udf;
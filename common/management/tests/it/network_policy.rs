@@ -0,0 +1,84 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datavalues::chrono::TimeZone;
+use common_datavalues::chrono::Utc;
+use common_exception::Result;
+use common_management::*;
+use common_meta_api::KVApi;
+use common_meta_embedded::MetaEmbedded;
+use common_meta_types::NetworkPolicy;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_add_network_policy() -> Result<()> {
+    let (kv_api, policy_api) = new_network_policy_api().await?;
+
+    let policy = create_test_policy();
+    policy_api.add_policy(policy.clone()).await?;
+    let value = kv_api.get_kv("__fd_network_policies/admin/office").await?;
+    assert!(value.is_some());
+
+    let got = policy_api.get_policy("office", None).await?;
+    assert_eq!(got.seq, 1);
+    assert_eq!(got.data, policy);
+
+    match policy_api.add_policy(policy).await {
+        Ok(_) => panic!("Already exists add network policy must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2622),
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_drop_network_policy() -> Result<()> {
+    let (_, policy_api) = new_network_policy_api().await?;
+
+    let policy = create_test_policy();
+    policy_api.add_policy(policy.clone()).await?;
+
+    let policies = policy_api.get_policies().await?;
+    assert_eq!(policies, vec![policy.clone()]);
+
+    policy_api.drop_policy(&policy.name, None).await?;
+
+    let policies = policy_api.get_policies().await?;
+    assert_eq!(policies, vec![]);
+
+    match policy_api.drop_policy(&policy.name, None).await {
+        Ok(_) => panic!("Unknown network policy drop must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2621),
+    }
+
+    Ok(())
+}
+
+fn create_test_policy() -> NetworkPolicy {
+    NetworkPolicy {
+        name: "office".to_string(),
+        allowed_ip_list: vec!["192.168.1.0/24".to_string()],
+        blocked_ip_list: vec!["192.168.1.99".to_string()],
+        comment: "".to_string(),
+        created_on: Utc.ymd(2022, 6, 1).and_hms(12, 0, 0),
+    }
+}
+
+async fn new_network_policy_api() -> Result<(Arc<MetaEmbedded>, NetworkPolicyMgr)> {
+    let test_api = Arc::new(MetaEmbedded::new_temp().await?);
+    let mgr = NetworkPolicyMgr::create(test_api.clone(), "admin")?;
+    Ok((test_api, mgr))
+}
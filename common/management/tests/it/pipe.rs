@@ -0,0 +1,157 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::Result;
+use common_management::*;
+use common_meta_api::KVApi;
+use common_meta_embedded::MetaEmbedded;
+use common_meta_types::KafkaSource;
+use common_meta_types::PipeFileInfo;
+use common_meta_types::PipeFileStatus;
+use common_meta_types::PipeInfo;
+use common_meta_types::PipeSource;
+use common_meta_types::SeqV;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_add_pipe() -> Result<()> {
+    let (kv_api, pipe_api) = new_pipe_api().await?;
+
+    let pipe_info = create_test_pipe_info();
+    pipe_api.add_pipe(pipe_info.clone()).await?;
+    let value = kv_api.get_kv("__fd_pipes/admin/mypipe").await?;
+
+    match value {
+        Some(SeqV {
+            seq: 1,
+            meta: _,
+            data: value,
+        }) => {
+            assert_eq!(value, serde_json::to_vec(&pipe_info)?);
+        }
+        catch => panic!("GetKVActionReply{:?}", catch),
+    }
+
+    let got = pipe_api.get_pipe("mypipe", None).await?;
+    assert_eq!(got.data, pipe_info);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_already_exists_add_pipe() -> Result<()> {
+    let (_, pipe_api) = new_pipe_api().await?;
+
+    let pipe_info = create_test_pipe_info();
+    pipe_api.add_pipe(pipe_info.clone()).await?;
+
+    match pipe_api.add_pipe(pipe_info.clone()).await {
+        Ok(_) => panic!("Already exists add pipe must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2512),
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_successfully_drop_pipe() -> Result<()> {
+    let (_, pipe_api) = new_pipe_api().await?;
+
+    let pipe_info = create_test_pipe_info();
+    pipe_api.add_pipe(pipe_info.clone()).await?;
+
+    let pipes = pipe_api.get_pipes().await?;
+    assert_eq!(pipes, vec![pipe_info.clone()]);
+
+    pipe_api.drop_pipe(&pipe_info.pipe_name, None).await?;
+
+    let pipes = pipe_api.get_pipes().await?;
+    assert_eq!(pipes, vec![]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_pipe_files() -> Result<()> {
+    let (_, pipe_api) = new_pipe_api().await?;
+
+    let pipe_info = create_test_pipe_info();
+    pipe_api.add_pipe(pipe_info.clone()).await?;
+
+    // the files of a pipe whose name starts with the name of the pipe
+    let other_file = PipeFileInfo {
+        file_name: "c.csv".to_string(),
+        ..Default::default()
+    };
+    pipe_api.add_pipe_files("mypipe2", vec![other_file]).await?;
+
+    let loaded = PipeFileInfo {
+        file_name: "a/b.csv".to_string(),
+        rows: 10,
+        ..Default::default()
+    };
+    let failed = PipeFileInfo {
+        file_name: "a/c.csv".to_string(),
+        status: PipeFileStatus::Failed,
+        error: "bad csv".to_string(),
+        ..Default::default()
+    };
+    pipe_api
+        .add_pipe_files("mypipe", vec![loaded.clone(), failed.clone()])
+        .await?;
+
+    let files = pipe_api.get_pipe_files("mypipe").await?;
+    assert_eq!(files, vec![loaded, failed]);
+
+    // the files are dropped along with the pipe
+    pipe_api.drop_pipe("mypipe", None).await?;
+    let files = pipe_api.get_pipe_files("mypipe").await?;
+    assert_eq!(files, vec![]);
+    let files = pipe_api.get_pipe_files("mypipe2").await?;
+    assert_eq!(files.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_unknown_pipe_drop_pipe() -> Result<()> {
+    let (_, pipe_api) = new_pipe_api().await?;
+
+    match pipe_api.drop_pipe("UNKNOWN_ID", None).await {
+        Ok(_) => panic!("Unknown pipe drop pipe must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2511),
+    }
+
+    Ok(())
+}
+
+fn create_test_pipe_info() -> PipeInfo {
+    PipeInfo {
+        pipe_name: "mypipe".to_string(),
+        database: "default".to_string(),
+        table: "events".to_string(),
+        source: PipeSource::Kafka(KafkaSource {
+            brokers: vec!["localhost:9092".to_string()],
+            topic: "events".to_string(),
+        }),
+        ..Default::default()
+    }
+}
+
+async fn new_pipe_api() -> Result<(Arc<MetaEmbedded>, PipeMgr)> {
+    let test_api = Arc::new(MetaEmbedded::new_temp().await?);
+    let mgr = PipeMgr::create(test_api.clone(), "admin")?;
+    Ok((test_api, mgr))
+}
@@ -15,6 +15,8 @@
 
 use std::sync::Arc;
 
+use common_meta_types::AddShareAccountsReply;
+use common_meta_types::AddShareAccountsReq;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateDatabaseReq;
 use common_meta_types::CreateShareReply;
@@ -31,16 +33,26 @@ use common_meta_types::DropTableReq;
 use common_meta_types::GetDatabaseReq;
 use common_meta_types::GetShareReq;
 use common_meta_types::GetTableReq;
+use common_meta_types::GrantShareObjectReply;
+use common_meta_types::GrantShareObjectReq;
 use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
 use common_meta_types::MetaError;
 use common_meta_types::MetaId;
+use common_meta_types::RemoveShareAccountsReply;
+use common_meta_types::RemoveShareAccountsReq;
 use common_meta_types::RenameTableReply;
 use common_meta_types::RenameTableReq;
+use common_meta_types::RevokeShareObjectReply;
+use common_meta_types::RevokeShareObjectReq;
 use common_meta_types::ShareInfo;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateMultiTableMetaReply;
+use common_meta_types::UpdateMultiTableMetaReq;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
 
@@ -84,6 +96,16 @@ pub trait MetaApi: Send + Sync {
         req: UpsertTableOptionReq,
     ) -> Result<UpsertTableOptionReply, MetaError>;
 
+    async fn update_table_meta(
+        &self,
+        req: UpdateTableMetaReq,
+    ) -> Result<UpdateTableMetaReply, MetaError>;
+
+    async fn update_multi_table_meta(
+        &self,
+        req: UpdateMultiTableMetaReq,
+    ) -> Result<UpdateMultiTableMetaReply, MetaError>;
+
     // share
     async fn create_share(&self, req: CreateShareReq) -> Result<CreateShareReply, MetaError>;
 
@@ -91,5 +113,25 @@ pub trait MetaApi: Send + Sync {
 
     async fn get_share(&self, req: GetShareReq) -> Result<Arc<ShareInfo>, MetaError>;
 
+    async fn grant_share_object(
+        &self,
+        req: GrantShareObjectReq,
+    ) -> Result<GrantShareObjectReply, MetaError>;
+
+    async fn revoke_share_object(
+        &self,
+        req: RevokeShareObjectReq,
+    ) -> Result<RevokeShareObjectReply, MetaError>;
+
+    async fn add_share_accounts(
+        &self,
+        req: AddShareAccountsReq,
+    ) -> Result<AddShareAccountsReply, MetaError>;
+
+    async fn remove_share_accounts(
+        &self,
+        req: RemoveShareAccountsReq,
+    ) -> Result<RemoveShareAccountsReply, MetaError>;
+
     fn name(&self) -> String;
 }
@@ -17,6 +17,7 @@ use std::sync::Arc;
 use common_datavalues::chrono::Utc;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
+use common_meta_types::AddShareAccountsReq;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateDatabaseReq;
 use common_meta_types::CreateShareReq;
@@ -29,12 +30,18 @@ use common_meta_types::DropTableReq;
 use common_meta_types::GetDatabaseReq;
 use common_meta_types::GetShareReq;
 use common_meta_types::GetTableReq;
+use common_meta_types::GrantShareObjectReq;
 use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
+use common_meta_types::RemoveShareAccountsReq;
 use common_meta_types::RenameTableReq;
+use common_meta_types::RevokeShareObjectReq;
+use common_meta_types::ShareGrantObject;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateMultiTableMetaReq;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReq;
 use common_tracing::tracing;
 
@@ -618,6 +625,99 @@ impl MetaApiTestSuite {
                 assert_eq!(table.options().get("key1"), Some(&"val1".into()));
             }
         }
+
+        tracing::info!("--- update table meta");
+        {
+            let new_schema = Arc::new(DataSchema::new(vec![
+                DataField::new("number", u64::to_data_type()),
+                DataField::new("added", u64::to_data_type()),
+            ]));
+
+            tracing::info!("--- update table meta with a new schema");
+            {
+                let table = mt.get_table((tenant, "db1", "tb2").into()).await.unwrap();
+                let mut new_table_meta = table.meta.clone();
+                new_table_meta.schema = new_schema.clone();
+
+                mt.update_table_meta(UpdateTableMetaReq::new(&table.ident, new_table_meta))
+                    .await?;
+
+                let got = mt.get_table((tenant, "db1", "tb2").into()).await.unwrap();
+                assert_eq!(got.schema(), new_schema);
+                assert_eq!(got.options().get("key1"), Some(&"val1".into()));
+                assert!(got.ident.version > table.ident.version);
+            }
+
+            tracing::info!("--- update table meta with a mismatched version");
+            {
+                let table = mt.get_table((tenant, "db1", "tb2").into()).await.unwrap();
+                let mut new_table_meta = table.meta.clone();
+                new_table_meta.schema = schema();
+
+                let got = mt
+                    .update_table_meta(UpdateTableMetaReq::new(
+                        &TableIdent {
+                            table_id: table.ident.table_id,
+                            version: table.ident.version - 1,
+                        },
+                        new_table_meta,
+                    ))
+                    .await;
+
+                let err = ErrorCode::from(got.unwrap_err());
+                assert_eq!(ErrorCode::TableVersionMismatched("").code(), err.code());
+
+                // table is not affected.
+                let table = mt.get_table((tenant, "db1", "tb2").into()).await.unwrap();
+                assert_eq!(table.schema(), new_schema);
+            }
+
+            tracing::info!("--- update multi table meta with a mismatched version");
+            {
+                let table = mt.get_table((tenant, "db1", "tb2").into()).await.unwrap();
+                let mut new_table_meta = table.meta.clone();
+                new_table_meta.schema = schema();
+
+                let got = mt
+                    .update_multi_table_meta(UpdateMultiTableMetaReq {
+                        update_table_metas: vec![
+                            UpdateTableMetaReq::new(&table.ident, new_table_meta.clone()),
+                            UpdateTableMetaReq::new(
+                                &TableIdent {
+                                    table_id: table.ident.table_id,
+                                    version: table.ident.version - 1,
+                                },
+                                new_table_meta,
+                            ),
+                        ],
+                    })
+                    .await;
+
+                let err = ErrorCode::from(got.unwrap_err());
+                assert_eq!(ErrorCode::TableVersionMismatched("").code(), err.code());
+
+                // none of the updates is applied.
+                let got = mt.get_table((tenant, "db1", "tb2").into()).await.unwrap();
+                assert_eq!(got.schema(), new_schema);
+                assert_eq!(got.ident.version, table.ident.version);
+            }
+
+            tracing::info!("--- update multi table meta");
+            {
+                let table = mt.get_table((tenant, "db1", "tb2").into()).await.unwrap();
+                let mut new_table_meta = table.meta.clone();
+                new_table_meta.schema = schema();
+
+                mt.update_multi_table_meta(UpdateMultiTableMetaReq {
+                    update_table_metas: vec![UpdateTableMetaReq::new(&table.ident, new_table_meta)],
+                })
+                .await?;
+
+                let got = mt.get_table((tenant, "db1", "tb2").into()).await.unwrap();
+                assert_eq!(got.schema(), schema());
+                assert!(got.ident.version > table.ident.version);
+            }
+        }
         tracing::info!("--- drop table");
         {
             tracing::info!("--- drop table with if_exists = false");
@@ -1119,6 +1219,115 @@ impl MetaApiTestSuite {
 
         Ok(())
     }
+
+    pub async fn share_grant_revoke<MT: MetaApi>(&self, mt: &MT) -> anyhow::Result<()> {
+        let tenant = "tenant1";
+        let share_name = "share1";
+        let db = ShareGrantObject::Database("db1".to_string());
+        let tbl = ShareGrantObject::Table("db1".to_string(), "tb1".to_string());
+        let other_tbl = ShareGrantObject::Table("db2".to_string(), "tb1".to_string());
+
+        mt.create_share(CreateShareReq {
+            if_not_exists: false,
+            tenant: tenant.to_string(),
+            share_name: share_name.to_string(),
+        })
+        .await?;
+
+        let grant = |object: &ShareGrantObject| GrantShareObjectReq {
+            tenant: tenant.to_string(),
+            share_name: share_name.to_string(),
+            object: object.clone(),
+        };
+        let revoke = |object: &ShareGrantObject| RevokeShareObjectReq {
+            tenant: tenant.to_string(),
+            share_name: share_name.to_string(),
+            object: object.clone(),
+        };
+
+        tracing::info!("--- grant a table before its database");
+        {
+            let res = mt.grant_share_object(grant(&tbl)).await;
+            let err = res.unwrap_err();
+            assert_eq!(
+                ErrorCode::WrongShareObject("").code(),
+                ErrorCode::from(err).code()
+            );
+        }
+
+        tracing::info!("--- grant database and table");
+        {
+            mt.grant_share_object(grant(&db)).await?;
+            mt.grant_share_object(grant(&tbl)).await?;
+            // granting twice is a no-op
+            mt.grant_share_object(grant(&tbl)).await?;
+
+            let res = mt.get_share(GetShareReq::new(tenant, share_name)).await?;
+            assert!(res.meta.has_granted(&db));
+            assert!(res.meta.has_granted(&tbl));
+            assert_eq!(vec!["tb1".to_string()], res.meta.tables);
+        }
+
+        tracing::info!("--- grant a table of another database");
+        {
+            let res = mt.grant_share_object(grant(&other_tbl)).await;
+            let err = res.unwrap_err();
+            assert_eq!(
+                ErrorCode::WrongShareObject("").code(),
+                ErrorCode::from(err).code()
+            );
+        }
+
+        tracing::info!("--- add and remove accounts");
+        {
+            mt.add_share_accounts(AddShareAccountsReq {
+                tenant: tenant.to_string(),
+                share_name: share_name.to_string(),
+                accounts: vec!["tenant2".to_string(), "tenant3".to_string()],
+            })
+            .await?;
+            mt.remove_share_accounts(RemoveShareAccountsReq {
+                tenant: tenant.to_string(),
+                share_name: share_name.to_string(),
+                accounts: vec!["tenant3".to_string()],
+            })
+            .await?;
+
+            let res = mt.get_share(GetShareReq::new(tenant, share_name)).await?;
+            assert!(res.meta.has_shared_with("tenant2"));
+            assert!(!res.meta.has_shared_with("tenant3"));
+            assert!(!res.meta.has_shared_with(tenant));
+        }
+
+        tracing::info!("--- revoke database revokes its tables");
+        {
+            mt.revoke_share_object(revoke(&tbl)).await?;
+            let res = mt.get_share(GetShareReq::new(tenant, share_name)).await?;
+            assert!(res.meta.has_granted(&db));
+            assert!(!res.meta.has_granted(&tbl));
+
+            mt.grant_share_object(grant(&tbl)).await?;
+            mt.revoke_share_object(revoke(&db)).await?;
+            let res = mt.get_share(GetShareReq::new(tenant, share_name)).await?;
+            assert_eq!(None, res.meta.database);
+            assert!(res.meta.tables.is_empty());
+        }
+
+        tracing::info!("--- update an unknown share");
+        {
+            let res = mt
+                .add_share_accounts(AddShareAccountsReq {
+                    tenant: tenant.to_string(),
+                    share_name: "absent".to_string(),
+                    accounts: vec!["tenant2".to_string()],
+                })
+                .await;
+            let err = res.unwrap_err();
+            assert_eq!(ErrorCode::unknown_share_code(), ErrorCode::from(err).code());
+        }
+
+        Ok(())
+    }
 }
 
 impl MetaApiTestSuite {
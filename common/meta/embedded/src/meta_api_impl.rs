@@ -16,6 +16,8 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use common_meta_api::MetaApi;
+use common_meta_types::AddShareAccountsReply;
+use common_meta_types::AddShareAccountsReq;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateDatabaseReq;
 use common_meta_types::CreateShareReply;
@@ -32,16 +34,26 @@ use common_meta_types::DropTableReq;
 use common_meta_types::GetDatabaseReq;
 use common_meta_types::GetShareReq;
 use common_meta_types::GetTableReq;
+use common_meta_types::GrantShareObjectReply;
+use common_meta_types::GrantShareObjectReq;
 use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
 use common_meta_types::MetaError;
 use common_meta_types::MetaId;
+use common_meta_types::RemoveShareAccountsReply;
+use common_meta_types::RemoveShareAccountsReq;
 use common_meta_types::RenameTableReply;
 use common_meta_types::RenameTableReq;
+use common_meta_types::RevokeShareObjectReply;
+use common_meta_types::RevokeShareObjectReq;
 use common_meta_types::ShareInfo;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateMultiTableMetaReply;
+use common_meta_types::UpdateMultiTableMetaReq;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
 
@@ -126,6 +138,24 @@ impl MetaApi for MetaEmbedded {
         Ok(reply)
     }
 
+    async fn update_table_meta(
+        &self,
+        req: UpdateTableMetaReq,
+    ) -> Result<UpdateTableMetaReply, MetaError> {
+        let sm = self.inner.lock().await;
+        let reply = sm.update_table_meta(req).await?;
+        Ok(reply)
+    }
+
+    async fn update_multi_table_meta(
+        &self,
+        req: UpdateMultiTableMetaReq,
+    ) -> Result<UpdateMultiTableMetaReply, MetaError> {
+        let sm = self.inner.lock().await;
+        let reply = sm.update_multi_table_meta(req).await?;
+        Ok(reply)
+    }
+
     async fn create_share(&self, req: CreateShareReq) -> Result<CreateShareReply, MetaError> {
         let sm = self.inner.lock().await;
         let reply = sm.create_share(req).await?;
@@ -144,6 +174,42 @@ impl MetaApi for MetaEmbedded {
         Ok(reply)
     }
 
+    async fn grant_share_object(
+        &self,
+        req: GrantShareObjectReq,
+    ) -> Result<GrantShareObjectReply, MetaError> {
+        let sm = self.inner.lock().await;
+        let reply = sm.grant_share_object(req).await?;
+        Ok(reply)
+    }
+
+    async fn revoke_share_object(
+        &self,
+        req: RevokeShareObjectReq,
+    ) -> Result<RevokeShareObjectReply, MetaError> {
+        let sm = self.inner.lock().await;
+        let reply = sm.revoke_share_object(req).await?;
+        Ok(reply)
+    }
+
+    async fn add_share_accounts(
+        &self,
+        req: AddShareAccountsReq,
+    ) -> Result<AddShareAccountsReply, MetaError> {
+        let sm = self.inner.lock().await;
+        let reply = sm.add_share_accounts(req).await?;
+        Ok(reply)
+    }
+
+    async fn remove_share_accounts(
+        &self,
+        req: RemoveShareAccountsReq,
+    ) -> Result<RemoveShareAccountsReply, MetaError> {
+        let sm = self.inner.lock().await;
+        let reply = sm.remove_share_accounts(req).await?;
+        Ok(reply)
+    }
+
     fn name(&self) -> String {
         "meta-embedded".to_string()
     }
@@ -65,3 +65,9 @@ async fn test_meta_embedded_share_create_get_dro() -> anyhow::Result<()> {
     let mt = MetaEmbedded::new_temp().await?;
     MetaApiTestSuite {}.share_create_get_drop(&mt).await
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_embedded_share_grant_revoke() -> anyhow::Result<()> {
+    let mt = MetaEmbedded::new_temp().await?;
+    MetaApiTestSuite {}.share_grant_revoke(&mt).await
+}
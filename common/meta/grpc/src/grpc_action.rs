@@ -17,6 +17,8 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::AddShareAccountsReply;
+use common_meta_types::AddShareAccountsReq;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateDatabaseReq;
 use common_meta_types::CreateShareReply;
@@ -34,15 +36,25 @@ use common_meta_types::GetDatabaseReq;
 use common_meta_types::GetKVActionReply;
 use common_meta_types::GetShareReq;
 use common_meta_types::GetTableReq;
+use common_meta_types::GrantShareObjectReply;
+use common_meta_types::GrantShareObjectReq;
 use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
 use common_meta_types::MGetKVActionReply;
 use common_meta_types::MetaId;
 use common_meta_types::PrefixListReply;
+use common_meta_types::RemoveShareAccountsReply;
+use common_meta_types::RemoveShareAccountsReq;
 use common_meta_types::RenameTableReply;
 use common_meta_types::RenameTableReq;
+use common_meta_types::RevokeShareObjectReply;
+use common_meta_types::RevokeShareObjectReq;
 use common_meta_types::ShareInfo;
 use common_meta_types::TableInfo;
+use common_meta_types::UpdateMultiTableMetaReply;
+use common_meta_types::UpdateMultiTableMetaReq;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertKVAction;
 use common_meta_types::UpsertKVActionReply;
 use common_meta_types::UpsertTableOptionReply;
@@ -63,9 +75,15 @@ pub enum MetaGrpcWriteReq {
     DropTable(DropTableReq),
     RenameTable(RenameTableReq),
     CommitTable(UpsertTableOptionReq),
+    UpdateTableMeta(UpdateTableMetaReq),
+    UpdateMultiTableMeta(UpdateMultiTableMetaReq),
 
     CreateShare(CreateShareReq),
     DropShare(DropShareReq),
+    GrantShareObject(GrantShareObjectReq),
+    RevokeShareObject(RevokeShareObjectReq),
+    AddShareAccounts(AddShareAccountsReq),
+    RemoveShareAccounts(RemoveShareAccountsReq),
 
     UpsertKV(UpsertKVAction),
 }
@@ -227,6 +245,14 @@ impl RequestFor for UpsertTableOptionReq {
     type Reply = UpsertTableOptionReply;
 }
 
+impl RequestFor for UpdateTableMetaReq {
+    type Reply = UpdateTableMetaReply;
+}
+
+impl RequestFor for UpdateMultiTableMetaReq {
+    type Reply = UpdateMultiTableMetaReply;
+}
+
 impl RequestFor for ListTableReq {
     type Reply = Vec<Arc<TableInfo>>;
 }
@@ -246,3 +272,19 @@ impl RequestFor for DropShareReq {
 impl RequestFor for GetShareReq {
     type Reply = Arc<ShareInfo>;
 }
+
+impl RequestFor for GrantShareObjectReq {
+    type Reply = GrantShareObjectReply;
+}
+
+impl RequestFor for RevokeShareObjectReq {
+    type Reply = RevokeShareObjectReply;
+}
+
+impl RequestFor for AddShareAccountsReq {
+    type Reply = AddShareAccountsReply;
+}
+
+impl RequestFor for RemoveShareAccountsReq {
+    type Reply = RemoveShareAccountsReply;
+}
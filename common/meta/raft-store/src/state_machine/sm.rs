@@ -58,6 +58,7 @@ use common_meta_types::PbSeqV;
 use common_meta_types::RenameTableReq;
 use common_meta_types::SeqV;
 use common_meta_types::ShareInfo;
+use common_meta_types::ShareMeta;
 use common_meta_types::TableAlreadyExists;
 use common_meta_types::TableMeta;
 use common_meta_types::TxnCondition;
@@ -874,6 +875,91 @@ impl StateMachine {
         )))
     }
 
+    #[tracing::instrument(level = "debug", skip(self, txn_tree))]
+    fn apply_update_table_meta_cmd(
+        &self,
+        req: &common_meta_types::UpdateTableMetaReq,
+        txn_tree: &TransactionSledTree,
+    ) -> MetaStorageResult<AppliedState> {
+        let table_tree = txn_tree.key_space::<Tables>();
+        let prev = table_tree.get(&req.table_id)?;
+
+        // Unlike other Cmd, prev to be None is not allowed for update-table-meta.
+        let prev = prev.ok_or_else(|| {
+            MetaStorageError::AppError(AppError::UnknownTableId(UnknownTableId::new(
+                req.table_id,
+                "apply_update_table_meta_cmd".to_string(),
+            )))
+        })?;
+
+        if req.seq.match_seq(&prev).is_err() {
+            let res = AppliedState::TableMeta(Change::new(Some(prev.clone()), Some(prev)));
+            return Ok(res);
+        }
+
+        let new_seq = self.txn_incr_seq(Tables::NAME, txn_tree)?;
+        let sv = SeqV {
+            seq: new_seq,
+            meta: prev.meta.clone(),
+            data: req.new_table_meta.clone(),
+        };
+
+        table_tree.insert(&req.table_id, &sv)?;
+
+        Ok(AppliedState::TableMeta(Change::new_with_id(
+            req.table_id,
+            Some(prev),
+            Some(sv),
+        )))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self, txn_tree))]
+    fn apply_update_multi_table_meta_cmd(
+        &self,
+        req: &common_meta_types::UpdateMultiTableMetaReq,
+        txn_tree: &TransactionSledTree,
+    ) -> MetaStorageResult<AppliedState> {
+        let table_tree = txn_tree.key_space::<Tables>();
+
+        // all the seqs are checked before any table is updated, so that either all of the
+        // tables are updated or none is.
+        let mut prevs = Vec::with_capacity(req.update_table_metas.len());
+        for update in &req.update_table_metas {
+            let prev = table_tree.get(&update.table_id)?.ok_or_else(|| {
+                MetaStorageError::AppError(AppError::UnknownTableId(UnknownTableId::new(
+                    update.table_id,
+                    "apply_update_multi_table_meta_cmd".to_string(),
+                )))
+            })?;
+
+            if update.seq.match_seq(&prev).is_err() {
+                let res = AppliedState::TableMeta(Change::new_with_id(
+                    update.table_id,
+                    Some(prev.clone()),
+                    Some(prev),
+                ));
+                return Ok(res);
+            }
+            prevs.push(prev);
+        }
+
+        let mut res = AppliedState::None;
+        for (update, prev) in req.update_table_metas.iter().zip(prevs.into_iter()) {
+            let new_seq = self.txn_incr_seq(Tables::NAME, txn_tree)?;
+            let sv = SeqV {
+                seq: new_seq,
+                meta: prev.meta.clone(),
+                data: update.new_table_meta.clone(),
+            };
+
+            table_tree.insert(&update.table_id, &sv)?;
+
+            res =
+                AppliedState::TableMeta(Change::new_with_id(update.table_id, Some(prev), Some(sv)));
+        }
+        Ok(res)
+    }
+
     /// Apply a `Cmd` to state machine.
     ///
     /// Already applied log should be filtered out before passing into this function.
@@ -911,6 +997,32 @@ impl StateMachine {
 
             Cmd::DropShare(req) => self.apply_drop_share_cmd(req, txn_tree),
 
+            Cmd::GrantShareObject(req) => {
+                self.apply_update_share_meta(&req.tenant, &req.share_name, txn_tree, |meta| {
+                    meta.grant_object(&req.object);
+                })
+            }
+
+            Cmd::RevokeShareObject(req) => {
+                self.apply_update_share_meta(&req.tenant, &req.share_name, txn_tree, |meta| {
+                    meta.revoke_object(&req.object)
+                })
+            }
+
+            Cmd::AddShareAccounts(req) => {
+                self.apply_update_share_meta(&req.tenant, &req.share_name, txn_tree, |meta| {
+                    meta.accounts.extend(req.accounts.iter().cloned())
+                })
+            }
+
+            Cmd::RemoveShareAccounts(req) => {
+                self.apply_update_share_meta(&req.tenant, &req.share_name, txn_tree, |meta| {
+                    for account in &req.accounts {
+                        meta.accounts.remove(account);
+                    }
+                })
+            }
+
             Cmd::UpsertKV {
                 key,
                 seq,
@@ -920,6 +1032,12 @@ impl StateMachine {
 
             Cmd::UpsertTableOptions(ref req) => self.apply_upsert_table_options_cmd(req, txn_tree),
 
+            Cmd::UpdateTableMeta(ref req) => self.apply_update_table_meta_cmd(req, txn_tree),
+
+            Cmd::UpdateMultiTableMeta(ref req) => {
+                self.apply_update_multi_table_meta_cmd(req, txn_tree)
+            }
+
             Cmd::Transaction(txn) => self.apply_txn_cmd(txn, txn_tree),
         }
     }
@@ -1403,6 +1521,58 @@ impl StateMachine {
         Ok(AppliedState::ShareInfo(Change::new(None, None)))
     }
 
+    /// Updates the meta of a share with `f`.
+    ///
+    /// It returns `(None, None)` if the share does not exist, and an unchanged state if `f`
+    /// leaves the meta as it is.
+    fn apply_update_share_meta(
+        &self,
+        tenant: &str,
+        share_name: &str,
+        txn_tree: &TransactionSledTree,
+        f: impl FnOnce(&mut ShareMeta),
+    ) -> MetaStorageResult<AppliedState> {
+        let share_lookup_tree = txn_tree.key_space::<ShareLookup>();
+        let share_tree = txn_tree.key_space::<Shares>();
+
+        let share_lookup_key = ShareLookupKey::new(tenant.to_string(), share_name.to_string());
+        let share_id = match share_lookup_tree.get(&share_lookup_key)? {
+            Some(seq_share_id) => seq_share_id.data.0,
+            None => return Ok(AppliedState::ShareInfo(Change::new(None, None))),
+        };
+        let prev = match share_tree.get(&share_id)? {
+            Some(prev) => prev,
+            None => return Ok(AppliedState::ShareInfo(Change::new(None, None))),
+        };
+
+        let mut share_info = prev.data.clone();
+        f(&mut share_info.meta);
+        if share_info == prev.data {
+            return Ok(AppliedState::ShareInfo(Change::nochange_with_id(
+                share_id,
+                Some(prev),
+            )));
+        }
+
+        let (prev, result) = self.txn_sub_tree_upsert(
+            &share_tree,
+            &share_id,
+            &MatchSeq::Exact(prev.seq),
+            Operation::Update(share_info),
+            None,
+        )?;
+
+        tracing::debug!(
+            "applied update Share: {}, share_id: {}",
+            share_name,
+            share_id
+        );
+
+        Ok(AppliedState::ShareInfo(Change::new_with_id(
+            share_id, prev, result,
+        )))
+    }
+
     pub fn get_share_id(&self, tenant: &str, share_name: &str) -> MetaStorageResult<u64> {
         let seq_share_id = self
             .share_lookup()
@@ -17,6 +17,8 @@ use std::sync::Arc;
 
 use common_meta_api::MetaApi;
 use common_meta_types::anyerror::AnyError;
+use common_meta_types::AddShareAccountsReply;
+use common_meta_types::AddShareAccountsReq;
 use common_meta_types::AppError;
 use common_meta_types::Change;
 use common_meta_types::Cmd;
@@ -40,13 +42,19 @@ use common_meta_types::DropTableReq;
 use common_meta_types::GetDatabaseReq;
 use common_meta_types::GetShareReq;
 use common_meta_types::GetTableReq;
+use common_meta_types::GrantShareObjectReply;
+use common_meta_types::GrantShareObjectReq;
 use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
 use common_meta_types::MetaError;
 use common_meta_types::MetaId;
 use common_meta_types::MetaStorageError;
+use common_meta_types::RemoveShareAccountsReply;
+use common_meta_types::RemoveShareAccountsReq;
 use common_meta_types::RenameTableReply;
 use common_meta_types::RenameTableReq;
+use common_meta_types::RevokeShareObjectReply;
+use common_meta_types::RevokeShareObjectReq;
 use common_meta_types::ShareAlreadyExists;
 use common_meta_types::ShareInfo;
 use common_meta_types::TableAlreadyExists;
@@ -58,8 +66,13 @@ use common_meta_types::UnknownDatabase;
 use common_meta_types::UnknownShare;
 use common_meta_types::UnknownTable;
 use common_meta_types::UnknownTableId;
+use common_meta_types::UpdateMultiTableMetaReply;
+use common_meta_types::UpdateMultiTableMetaReq;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
+use common_meta_types::WrongShareObject;
 use common_tracing::tracing;
 
 use crate::state_machine::DatabaseLookupKey;
@@ -338,6 +351,67 @@ impl MetaApi for StateMachine {
         Ok(UpsertTableOptionReply {})
     }
 
+    async fn update_table_meta(
+        &self,
+        req: UpdateTableMetaReq,
+    ) -> Result<UpdateTableMetaReply, MetaError> {
+        let cmd = Cmd::UpdateTableMeta(req.clone());
+
+        let res = self.sm_tree.txn(true, |t| {
+            let r = self.apply_cmd(&cmd, &t)?;
+            Ok(r)
+        })?;
+        if !res.changed() {
+            let ch: Change<TableMeta> = res.try_into().unwrap();
+            let (prev, _result) = ch.unwrap();
+
+            let ae = AppError::from(TableVersionMismatched::new(
+                req.table_id,
+                req.seq,
+                prev.seq,
+                "update_table_meta",
+            ));
+            return Err(MetaError::from(ae));
+        }
+
+        Ok(UpdateTableMetaReply {})
+    }
+
+    async fn update_multi_table_meta(
+        &self,
+        req: UpdateMultiTableMetaReq,
+    ) -> Result<UpdateMultiTableMetaReply, MetaError> {
+        if req.update_table_metas.is_empty() {
+            return Ok(UpdateMultiTableMetaReply {});
+        }
+        let cmd = Cmd::UpdateMultiTableMeta(req.clone());
+
+        let res = self.sm_tree.txn(true, |t| {
+            let r = self.apply_cmd(&cmd, &t)?;
+            Ok(r)
+        })?;
+        if !res.changed() {
+            let mut ch: Change<TableMeta> = res.try_into().unwrap();
+            let table_id = ch.ident.take().unwrap();
+            let (prev, _result) = ch.unwrap();
+            let update = req
+                .update_table_metas
+                .iter()
+                .find(|r| r.table_id == table_id)
+                .unwrap();
+
+            let ae = AppError::from(TableVersionMismatched::new(
+                table_id,
+                update.seq,
+                prev.seq,
+                "update_multi_table_meta",
+            ));
+            return Err(MetaError::from(ae));
+        }
+
+        Ok(UpdateMultiTableMetaReply {})
+    }
+
     async fn create_share(&self, req: CreateShareReq) -> Result<CreateShareReply, MetaError> {
         let share_name = &req.share_name;
         let if_not_exists = req.if_not_exists;
@@ -388,6 +462,83 @@ impl MetaApi for StateMachine {
         Ok(Arc::new(seq_share_info.data))
     }
 
+    async fn grant_share_object(
+        &self,
+        req: GrantShareObjectReq,
+    ) -> Result<GrantShareObjectReply, MetaError> {
+        let res = self.sm_tree.txn(true, |t| {
+            let r = self.apply_cmd(&Cmd::GrantShareObject(req.clone()), &t)?;
+            Ok(r)
+        })?;
+
+        let ch: Change<ShareInfo, u64> = res.try_into().unwrap();
+        let (_prev, result) = ch.unpack_data();
+
+        match result {
+            None => {
+                let ae = AppError::from(UnknownShare::new(&req.share_name, "grant share object"));
+                Err(MetaError::from(ae))
+            }
+            // the object is rejected by the share
+            Some(share_info) if !share_info.meta.has_granted(&req.object) => {
+                let ae = AppError::from(WrongShareObject::new(req.object.to_string()));
+                Err(MetaError::from(ae))
+            }
+            Some(_) => Ok(GrantShareObjectReply {}),
+        }
+    }
+
+    async fn revoke_share_object(
+        &self,
+        req: RevokeShareObjectReq,
+    ) -> Result<RevokeShareObjectReply, MetaError> {
+        let res = self.sm_tree.txn(true, |t| {
+            let r = self.apply_cmd(&Cmd::RevokeShareObject(req.clone()), &t)?;
+            Ok(r)
+        })?;
+
+        if res.result().is_none() {
+            let ae = AppError::from(UnknownShare::new(&req.share_name, "revoke share object"));
+            return Err(MetaError::from(ae));
+        }
+
+        Ok(RevokeShareObjectReply {})
+    }
+
+    async fn add_share_accounts(
+        &self,
+        req: AddShareAccountsReq,
+    ) -> Result<AddShareAccountsReply, MetaError> {
+        let res = self.sm_tree.txn(true, |t| {
+            let r = self.apply_cmd(&Cmd::AddShareAccounts(req.clone()), &t)?;
+            Ok(r)
+        })?;
+
+        if res.result().is_none() {
+            let ae = AppError::from(UnknownShare::new(&req.share_name, "add share accounts"));
+            return Err(MetaError::from(ae));
+        }
+
+        Ok(AddShareAccountsReply {})
+    }
+
+    async fn remove_share_accounts(
+        &self,
+        req: RemoveShareAccountsReq,
+    ) -> Result<RemoveShareAccountsReply, MetaError> {
+        let res = self.sm_tree.txn(true, |t| {
+            let r = self.apply_cmd(&Cmd::RemoveShareAccounts(req.clone()), &t)?;
+            Ok(r)
+        })?;
+
+        if res.result().is_none() {
+            let ae = AppError::from(UnknownShare::new(&req.share_name, "remove share accounts"));
+            return Err(MetaError::from(ae));
+        }
+
+        Ok(RemoveShareAccountsReply {})
+    }
+
     fn name(&self) -> String {
         "StateMachine".to_string()
     }
@@ -99,3 +99,13 @@ async fn test_meta_embedded_share_create_get_drop() -> anyhow::Result<()> {
 
     MetaApiTestSuite {}.share_create_get_drop(&sm).await
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_embedded_share_grant_revoke() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+    let tc = new_raft_test_context();
+    let sm = StateMachine::open(&tc.raft_config, 1).await?;
+
+    MetaApiTestSuite {}.share_grant_revoke(&sm).await
+}
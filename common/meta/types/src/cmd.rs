@@ -21,6 +21,7 @@ use serde::Deserializer;
 use serde::Serialize;
 
 use crate::compatibility::cmd_00000000_20220427::Cmd as LatestVersionCmd;
+use crate::AddShareAccountsReq;
 use crate::CreateDatabaseReq;
 use crate::CreateShareReq;
 use crate::CreateTableReq;
@@ -28,12 +29,17 @@ use crate::DatabaseNameIdent;
 use crate::DropDatabaseReq;
 use crate::DropShareReq;
 use crate::DropTableReq;
+use crate::GrantShareObjectReq;
 use crate::KVMeta;
 use crate::MatchSeq;
 use crate::Node;
 use crate::Operation;
+use crate::RemoveShareAccountsReq;
 use crate::RenameTableReq;
+use crate::RevokeShareObjectReq;
 use crate::TxnRequest;
+use crate::UpdateMultiTableMetaReq;
+use crate::UpdateTableMetaReq;
 use crate::UpsertTableOptionReq;
 
 /// A Cmd describes what a user want to do to raft state machine
@@ -77,6 +83,16 @@ pub enum Cmd {
 
     DropShare(DropShareReq),
 
+    /// Grant an object to a share, the share is left unchanged if the object can not be granted.
+    GrantShareObject(GrantShareObjectReq),
+
+    RevokeShareObject(RevokeShareObjectReq),
+
+    /// Share a share with tenants
+    AddShareAccounts(AddShareAccountsReq),
+
+    RemoveShareAccounts(RemoveShareAccountsReq),
+
     /// Update, remove or insert table options.
     ///
     /// This Cmd requires a present table to operate on.
@@ -86,6 +102,23 @@ pub enum Cmd {
     /// Otherwise it returns the TableMeta before and after update.
     UpsertTableOptions(UpsertTableOptionReq),
 
+    /// Replace the meta of a table, e.g., to change the schema of it.
+    ///
+    /// This Cmd requires a present table to operate on.
+    /// Otherwise an `UnknownTableId` is returned.
+    ///
+    /// With mismatched seq, it returns a unchanged state: (prev:TableMeta, prev:TableMeta)
+    /// Otherwise it returns the TableMeta before and after update.
+    UpdateTableMeta(UpdateTableMetaReq),
+
+    /// Replace the meta of several tables at once.
+    ///
+    /// All the tables are required to be present, and the seqs of them are checked before any
+    /// of them is updated. With any mismatched seq, none is updated and it returns a unchanged
+    /// state of that table: (prev:TableMeta, prev:TableMeta).
+    /// Otherwise it returns the TableMeta before and after update of the last table.
+    UpdateMultiTableMeta(UpdateMultiTableMetaReq),
+
     /// Update or insert a general purpose kv store
     UpsertKV {
         key: String,
@@ -125,8 +158,14 @@ impl fmt::Display for Cmd {
             Cmd::DropTable(req) => req.fmt(f),
             Cmd::RenameTable(req) => req.fmt(f),
             Cmd::UpsertTableOptions(req) => req.fmt(f),
+            Cmd::UpdateTableMeta(req) => req.fmt(f),
+            Cmd::UpdateMultiTableMeta(req) => req.fmt(f),
             Cmd::CreateShare(req) => req.fmt(f),
             Cmd::DropShare(req) => req.fmt(f),
+            Cmd::GrantShareObject(req) => req.fmt(f),
+            Cmd::RevokeShareObject(req) => req.fmt(f),
+            Cmd::AddShareAccounts(req) => req.fmt(f),
+            Cmd::RemoveShareAccounts(req) => req.fmt(f),
             Cmd::UpsertKV {
                 key,
                 seq,
@@ -259,7 +298,13 @@ impl<'de> Deserialize<'de> for Cmd {
             }
             LatestVersionCmd::CreateShare(x) => Cmd::CreateShare(x),
             LatestVersionCmd::DropShare(x) => Cmd::DropShare(x),
+            LatestVersionCmd::GrantShareObject(x) => Cmd::GrantShareObject(x),
+            LatestVersionCmd::RevokeShareObject(x) => Cmd::RevokeShareObject(x),
+            LatestVersionCmd::AddShareAccounts(x) => Cmd::AddShareAccounts(x),
+            LatestVersionCmd::RemoveShareAccounts(x) => Cmd::RemoveShareAccounts(x),
             LatestVersionCmd::UpsertTableOptions(x) => Cmd::UpsertTableOptions(x),
+            LatestVersionCmd::UpdateTableMeta(x) => Cmd::UpdateTableMeta(x),
+            LatestVersionCmd::UpdateMultiTableMeta(x) => Cmd::UpdateMultiTableMeta(x),
             LatestVersionCmd::UpsertKV {
                 key,
                 seq,
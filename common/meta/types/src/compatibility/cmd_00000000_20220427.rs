@@ -16,16 +16,22 @@ use openraft::NodeId;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::AddShareAccountsReq;
 use crate::CreateShareReq;
 use crate::DatabaseMeta;
 use crate::DatabaseNameIdent;
 use crate::DropShareReq;
+use crate::GrantShareObjectReq;
 use crate::KVMeta;
 use crate::MatchSeq;
 use crate::Node;
 use crate::Operation;
+use crate::RemoveShareAccountsReq;
+use crate::RevokeShareObjectReq;
 use crate::TableMeta;
 use crate::TxnRequest;
+use crate::UpdateMultiTableMetaReq;
+use crate::UpdateTableMetaReq;
 use crate::UpsertTableOptionReq;
 
 /// Compatible with latest changes made in 34e89c99 on 20220413
@@ -109,9 +115,23 @@ pub enum Cmd {
     CreateShare(CreateShareReq),
     // latest add
     DropShare(DropShareReq),
+    // latest add
+    GrantShareObject(GrantShareObjectReq),
+    // latest add
+    RevokeShareObject(RevokeShareObjectReq),
+    // latest add
+    AddShareAccounts(AddShareAccountsReq),
+    // latest add
+    RemoveShareAccounts(RemoveShareAccountsReq),
 
     UpsertTableOptions(UpsertTableOptionReq),
 
+    // latest add
+    UpdateTableMeta(UpdateTableMetaReq),
+
+    // latest add
+    UpdateMultiTableMeta(UpdateMultiTableMetaReq),
+
     UpsertKV {
         key: String,
         seq: MatchSeq,
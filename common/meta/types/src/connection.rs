@@ -0,0 +1,39 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::chrono::DateTime;
+use common_datavalues::chrono::Utc;
+
+/*
+CREATE CONNECTION [ IF NOT EXISTS ] <connection_name>
+    STORAGE_TYPE = 's3'
+    CREDENTIALS = ( AWS_KEY_ID = '<string>' AWS_SECRET_KEY = '<string>' )
+  [ COMMENT = '<string_literal>' ]
+ */
+
+/// The credentials of a storage, which the stages refer to by name instead of inlining them.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct Connection {
+    pub name: String,
+    /// The type of the storage, only `s3` for now.
+    pub storage_type: String,
+    /// The credentials options, encrypted by the master key of the query nodes.
+    pub encrypted_credentials: Vec<u8>,
+    /// The id of the master key which encrypted the credentials.
+    pub master_key_id: String,
+    pub comment: String,
+    pub created_on: DateTime<Utc>,
+    /// The last time the credentials were set or encrypted again.
+    pub updated_on: DateTime<Utc>,
+}
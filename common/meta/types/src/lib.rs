@@ -19,6 +19,7 @@ mod change;
 mod cluster;
 mod cmd;
 pub mod config;
+mod connection;
 mod database;
 mod endpoint;
 mod errors;
@@ -32,10 +33,12 @@ mod meta_network_errors;
 mod meta_raft_errors;
 mod meta_result_error;
 mod meta_storage_errors;
+mod network_policy;
 mod operation;
 mod raft_txid;
 mod raft_types;
 mod role_info;
+mod row_access_policy;
 mod seq_num;
 mod seq_value;
 mod table;
@@ -44,6 +47,7 @@ mod user_defined_function;
 mod user_grant;
 mod user_identity;
 mod user_info;
+mod user_pipe;
 mod user_privilege;
 mod user_quota;
 mod user_setting;
@@ -75,6 +79,7 @@ pub use cluster::Node;
 pub use cluster::NodeInfo;
 pub use cluster::Slot;
 pub use cmd::Cmd;
+pub use connection::Connection;
 pub use database::CreateDatabaseReply;
 pub use database::CreateDatabaseReq;
 pub use database::DatabaseIdent;
@@ -126,6 +131,8 @@ pub use meta_storage_errors::UnknownDatabaseId;
 pub use meta_storage_errors::UnknownShare;
 pub use meta_storage_errors::UnknownTable;
 pub use meta_storage_errors::UnknownTableId;
+pub use meta_storage_errors::WrongShareObject;
+pub use network_policy::NetworkPolicy;
 pub use operation::MetaId;
 pub use operation::MetaVersion;
 pub use operation::Operation;
@@ -151,17 +158,30 @@ pub use raft_types::LogIndex;
 pub use raft_types::NodeId;
 pub use raft_types::Term;
 pub use role_info::RoleInfo;
+pub use row_access_policy::RowAccessPolicy;
+pub use row_access_policy::RowAccessPolicyReference;
+pub use row_access_policy::TABLE_OPT_KEY_ROW_ACCESS_POLICY;
 pub use seq_num::SeqNum;
 pub use seq_value::IntoSeqV;
 pub use seq_value::KVMeta;
 pub use seq_value::PbSeqV;
 pub use seq_value::SeqV;
+pub use share::AddShareAccountsReply;
+pub use share::AddShareAccountsReq;
 pub use share::CreateShareReply;
 pub use share::CreateShareReq;
 pub use share::DropShareReply;
 pub use share::DropShareReq;
 pub use share::GetShareReq;
+pub use share::GrantShareObjectReply;
+pub use share::GrantShareObjectReq;
+pub use share::RemoveShareAccountsReply;
+pub use share::RemoveShareAccountsReq;
+pub use share::RevokeShareObjectReply;
+pub use share::RevokeShareObjectReq;
+pub use share::ShareGrantObject;
 pub use share::ShareInfo;
+pub use share::ShareMeta;
 pub use table::CreateTableReply;
 pub use table::CreateTableReq;
 pub use table::DropTableReply;
@@ -174,6 +194,11 @@ pub use table::TableIdent;
 pub use table::TableInfo;
 pub use table::TableMeta;
 pub use table::TableNameIndent;
+pub use table::TableUsage;
+pub use table::UpdateMultiTableMetaReply;
+pub use table::UpdateMultiTableMetaReq;
+pub use table::UpdateTableMetaReply;
+pub use table::UpdateTableMetaReq;
 pub use table::UpsertTableOptionReply;
 pub use table::UpsertTableOptionReq;
 pub use user_auth::AuthInfo;
@@ -187,6 +212,12 @@ pub use user_identity::UserIdentity;
 pub use user_info::UserInfo;
 pub use user_info::UserOption;
 pub use user_info::UserOptionFlag;
+pub use user_pipe::KafkaSource;
+pub use user_pipe::PipeFileInfo;
+pub use user_pipe::PipeFileStatus;
+pub use user_pipe::PipeInfo;
+pub use user_pipe::PipeSource;
+pub use user_pipe::StagePipeSource;
 pub use user_privilege::UserPrivilegeSet;
 pub use user_privilege::UserPrivilegeType;
 pub use user_quota::UserQuota;
@@ -222,6 +222,20 @@ impl UnknownShareId {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, thiserror::Error)]
+#[error("WrongShareObject: {obj_name} does not belong to the database that is being shared")]
+pub struct WrongShareObject {
+    obj_name: String,
+}
+
+impl WrongShareObject {
+    pub fn new(obj_name: impl Into<String>) -> Self {
+        Self {
+            obj_name: obj_name.into(),
+        }
+    }
+}
+
 #[derive(Error, Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum AppError {
     #[error(transparent)]
@@ -253,6 +267,9 @@ pub enum AppError {
 
     #[error(transparent)]
     UnknownShareId(#[from] UnknownShareId),
+
+    #[error(transparent)]
+    WrongShareObject(#[from] WrongShareObject),
 }
 
 impl AppErrorMessage for UnknownDatabase {
@@ -301,6 +318,15 @@ impl AppErrorMessage for UnknownShareId {
     }
 }
 
+impl AppErrorMessage for WrongShareObject {
+    fn message(&self) -> String {
+        format!(
+            "{} does not belong to the database that is being shared",
+            self.obj_name
+        )
+    }
+}
+
 impl From<AppError> for ErrorCode {
     fn from(app_err: AppError) -> Self {
         match app_err {
@@ -316,6 +342,7 @@ impl From<AppError> for ErrorCode {
             AppError::ShareAlreadyExists(err) => ErrorCode::ShareAlreadyExists(err.message()),
             AppError::UnknownShare(err) => ErrorCode::UnknownShare(err.message()),
             AppError::UnknownShareId(err) => ErrorCode::UnknownShareId(err.message()),
+            AppError::WrongShareObject(err) => ErrorCode::WrongShareObject(err.message()),
         }
     }
 }
@@ -0,0 +1,109 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+
+use common_datavalues::chrono::DateTime;
+use common_datavalues::chrono::Utc;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/*
+CREATE NETWORK POLICY [ IF NOT EXISTS ] <policy_name>
+    ALLOWED_IP_LIST = ( '<cidr>' [ , ... ] )
+  [ BLOCKED_IP_LIST = ( '<cidr>' [ , ... ] ) ]
+  [ COMMENT = '<string_literal>' ]
+ */
+
+/// The client addresses the users with the policy can connect from.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct NetworkPolicy {
+    pub name: String,
+    /// The IP ranges in CIDR notation, such as `192.168.1.0/24`, or the single addresses.
+    pub allowed_ip_list: Vec<String>,
+    /// The IP ranges rejected even if they are allowed.
+    pub blocked_ip_list: Vec<String>,
+    pub comment: String,
+    pub created_on: DateTime<Utc>,
+}
+
+impl NetworkPolicy {
+    /// Checks the IP ranges of the policy, which are only parsed on use.
+    pub fn validate(&self) -> Result<()> {
+        for ip_range in self.allowed_ip_list.iter().chain(&self.blocked_ip_list) {
+            parse_ip_range(ip_range)?;
+        }
+        Ok(())
+    }
+
+    pub fn is_allowed(&self, ip: &IpAddr) -> Result<bool> {
+        for ip_range in &self.blocked_ip_list {
+            if ip_range_contains(ip_range, ip)? {
+                return Ok(false);
+            }
+        }
+        for ip_range in &self.allowed_ip_list {
+            if ip_range_contains(ip_range, ip)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+fn parse_ip_range(ip_range: &str) -> Result<(IpAddr, u32)> {
+    let illegal = || ErrorCode::IllegalNetworkPolicy(format!("Illegal IP range: {}", ip_range));
+    let (addr, prefix_len) = match ip_range.split_once('/') {
+        Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+        None => (ip_range, None),
+    };
+    let addr = addr.trim().parse::<IpAddr>().map_err(|_| illegal())?;
+    let max_prefix_len = match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    };
+    let prefix_len = match prefix_len {
+        Some(v) => v.trim().parse::<u32>().map_err(|_| illegal())?,
+        None => max_prefix_len,
+    };
+    if prefix_len > max_prefix_len {
+        return Err(illegal());
+    }
+    Ok((addr, prefix_len))
+}
+
+fn ip_range_contains(ip_range: &str, ip: &IpAddr) -> Result<bool> {
+    let (addr, prefix_len) = parse_ip_range(ip_range)?;
+    // The clients of a server listening on IPv6 connect with IPv4-mapped addresses.
+    let ip = match ip {
+        IpAddr::V6(v6) if addr.is_ipv4() => match v6.segments() {
+            [0, 0, 0, 0, 0, 0xffff, ..] => IpAddr::V4(Ipv4Addr::from(u128::from(*v6) as u32)),
+            _ => return Ok(false),
+        },
+        _ => *ip,
+    };
+
+    match (addr, ip) {
+        (IpAddr::V4(addr), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix_len).unwrap_or(0);
+            Ok(u32::from(addr) & mask == u32::from(ip) & mask)
+        }
+        (IpAddr::V6(addr), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix_len).unwrap_or(0);
+            Ok(u128::from(addr) & mask == u128::from(ip) & mask)
+        }
+        _ => Ok(false),
+    }
+}
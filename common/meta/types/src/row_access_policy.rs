@@ -0,0 +1,52 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::chrono::DateTime;
+use common_datavalues::chrono::Utc;
+use common_datavalues::DataField;
+
+/*
+CREATE ROW ACCESS POLICY [ IF NOT EXISTS ] <policy_name>
+    AS ( <arg_name> <arg_type> [ , ... ] ) RETURNS BOOLEAN -> <expression>
+  [ EXEMPT ROLES = ( '<role_name>' [ , ... ] ) ]
+  [ COMMENT = '<string_literal>' ]
+
+ALTER TABLE <table_name> ADD ROW ACCESS POLICY <policy_name> ON ( <column> [ , ... ] )
+ALTER TABLE <table_name> DROP ROW ACCESS POLICY <policy_name>
+ */
+
+/// The table option in which a table keeps the row access policy attached to it.
+pub const TABLE_OPT_KEY_ROW_ACCESS_POLICY: &str = "row_access_policy";
+
+/// A boolean expression of the columns of a table, filtering the rows a query can read.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct RowAccessPolicy {
+    pub name: String,
+    /// The arguments of the expression, bound to the columns the policy is attached on.
+    pub args: Vec<DataField>,
+    /// The boolean expression, in SQL.
+    pub body: String,
+    /// The roles which read all the rows, the policy is not applied to them.
+    pub exempt_roles: Vec<String>,
+    pub comment: String,
+    pub created_on: DateTime<Utc>,
+}
+
+/// The policy attached to a table, with the columns passed to its arguments.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct RowAccessPolicyReference {
+    pub policy: String,
+    pub columns: Vec<String>,
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeSet;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -39,6 +40,87 @@ impl ShareInfo {
 pub struct ShareMeta {
     pub database: Option<String>,
     pub tables: Vec<String>,
+
+    /// Tenants that the share is shared with.
+    #[serde(default)]
+    pub accounts: BTreeSet<String>,
+}
+
+impl ShareMeta {
+    /// Grants an object to the share.
+    ///
+    /// A share contains the objects of at most one database: the usage of the database has to
+    /// be granted before the tables of it. Returns false if the object can not be granted.
+    pub fn grant_object(&mut self, object: &ShareGrantObject) -> bool {
+        match object {
+            ShareGrantObject::Database(db_name) => match &self.database {
+                Some(database) => database == db_name,
+                None => {
+                    self.database = Some(db_name.clone());
+                    true
+                }
+            },
+            ShareGrantObject::Table(db_name, table_name) => {
+                if self.database.as_ref() != Some(db_name) {
+                    return false;
+                }
+                if !self.tables.contains(table_name) {
+                    self.tables.push(table_name.clone());
+                }
+                true
+            }
+        }
+    }
+
+    /// Revokes an object from the share, revoking a database revokes all its tables as well.
+    pub fn revoke_object(&mut self, object: &ShareGrantObject) {
+        match object {
+            ShareGrantObject::Database(db_name) => {
+                if self.database.as_ref() == Some(db_name) {
+                    self.database = None;
+                    self.tables.clear();
+                }
+            }
+            ShareGrantObject::Table(db_name, table_name) => {
+                if self.database.as_ref() == Some(db_name) {
+                    self.tables.retain(|t| t != table_name);
+                }
+            }
+        }
+    }
+
+    pub fn has_granted(&self, object: &ShareGrantObject) -> bool {
+        match object {
+            ShareGrantObject::Database(db_name) => self.database.as_ref() == Some(db_name),
+            ShareGrantObject::Table(db_name, table_name) => {
+                self.database.as_ref() == Some(db_name) && self.tables.contains(table_name)
+            }
+        }
+    }
+
+    /// Whether the objects of the share can be resolved by `tenant`.
+    pub fn has_shared_with(&self, tenant: &str) -> bool {
+        self.accounts.contains(tenant)
+    }
+}
+
+/// An object of the tenant that owns a share, which can be granted to the share.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum ShareGrantObject {
+    Database(String),
+    /// Database name and table name
+    Table(String, String),
+}
+
+impl Display for ShareGrantObject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShareGrantObject::Database(db_name) => write!(f, "DATABASE {}", db_name),
+            ShareGrantObject::Table(db_name, table_name) => {
+                write!(f, "TABLE {}.{}", db_name, table_name)
+            }
+        }
+    }
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
@@ -93,3 +175,83 @@ impl GetShareReq {
         }
     }
 }
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct GrantShareObjectReq {
+    pub tenant: String,
+    pub share_name: String,
+    pub object: ShareGrantObject,
+}
+
+impl Display for GrantShareObjectReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "grant_share_object:{}/{} {}",
+            self.tenant, self.share_name, self.object
+        )
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct GrantShareObjectReply {}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct RevokeShareObjectReq {
+    pub tenant: String,
+    pub share_name: String,
+    pub object: ShareGrantObject,
+}
+
+impl Display for RevokeShareObjectReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "revoke_share_object:{}/{} {}",
+            self.tenant, self.share_name, self.object
+        )
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct RevokeShareObjectReply {}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct AddShareAccountsReq {
+    pub tenant: String,
+    pub share_name: String,
+    pub accounts: Vec<String>,
+}
+
+impl Display for AddShareAccountsReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "add_share_accounts:{}/{} {:?}",
+            self.tenant, self.share_name, self.accounts
+        )
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct AddShareAccountsReply {}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct RemoveShareAccountsReq {
+    pub tenant: String,
+    pub share_name: String,
+    pub accounts: Vec<String>,
+}
+
+impl Display for RemoveShareAccountsReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "remove_share_accounts:{}/{} {:?}",
+            self.tenant, self.share_name, self.accounts
+        )
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct RemoveShareAccountsReply {}
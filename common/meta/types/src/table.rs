@@ -113,6 +113,24 @@ pub struct TableMeta {
     pub created_on: DateTime<Utc>,
     pub updated_on: DateTime<Utc>,
     pub comment: String,
+    /// The storage usage of the table, as of the last commit of it
+    #[serde(default)]
+    pub usage: TableUsage,
+}
+
+/// The storage usage of a table, refreshed by the engine whenever it commits a new version.
+///
+/// It lets the usage of the tables be queried without reading anything from the storage.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+pub struct TableUsage {
+    pub number_of_rows: u64,
+    pub number_of_blocks: u64,
+    /// Uncompressed bytes of the data
+    pub data_bytes: u64,
+    /// Bytes of the data as stored
+    pub compressed_data_bytes: u64,
+    /// Bytes of the index files kept alongside the data
+    pub index_data_bytes: u64,
 }
 
 impl TableInfo {
@@ -172,6 +190,7 @@ impl Default for TableMeta {
             created_on: Default::default(),
             updated_on: Default::default(),
             comment: "".to_string(),
+            usage: Default::default(),
         }
     }
 }
@@ -305,6 +324,59 @@ impl Display for UpsertTableOptionReq {
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
 pub struct UpsertTableOptionReply {}
 
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct UpdateTableMetaReq {
+    pub table_id: u64,
+    pub seq: MatchSeq,
+    pub new_table_meta: TableMeta,
+}
+
+impl UpdateTableMetaReq {
+    pub fn new(table_ident: &TableIdent, new_table_meta: TableMeta) -> UpdateTableMetaReq {
+        UpdateTableMetaReq {
+            table_id: table_ident.table_id,
+            seq: MatchSeq::Exact(table_ident.version),
+            new_table_meta,
+        }
+    }
+}
+
+impl Display for UpdateTableMetaReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "update-table-meta: table-id:{}({:?}) = {}",
+            self.table_id, self.seq, self.new_table_meta
+        )
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct UpdateTableMetaReply {}
+
+/// Updates the meta of several tables at once, either all of them are updated, or none is if
+/// the seq of any of them mismatches.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct UpdateMultiTableMetaReq {
+    pub update_table_metas: Vec<UpdateTableMetaReq>,
+}
+
+impl Display for UpdateMultiTableMetaReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "update-multi-table-meta: [")?;
+        for (i, req) in self.update_table_metas.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", req)?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct UpdateMultiTableMetaReply {}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
 pub struct GetTableReq {
     pub inner: TableNameIndent,
@@ -26,11 +26,13 @@ pub enum GrantObject {
     Global,
     Database(String),
     Table(String, String),
+    Stage(String),
+    UDF(String),
 }
 
 impl GrantObject {
     /// Comparing the grant objects, the Database object contains all the Table objects inside it.
-    /// Global object contains all the Database objects.
+    /// Global object contains all the Database, Stage and UDF objects.
     pub fn contains(&self, object: &GrantObject) -> bool {
         match (self, object) {
             (GrantObject::Global, _) => true,
@@ -41,6 +43,10 @@ impl GrantObject {
                 (lhs_db == rhs_db) && (lhs_table == rhs_table)
             }
             (GrantObject::Table(_, _), _) => false,
+            (GrantObject::Stage(lhs), GrantObject::Stage(rhs)) => lhs == rhs,
+            (GrantObject::Stage(_), _) => false,
+            (GrantObject::UDF(lhs), GrantObject::UDF(rhs)) => lhs == rhs,
+            (GrantObject::UDF(_), _) => false,
         }
     }
 
@@ -50,6 +56,8 @@ impl GrantObject {
             GrantObject::Global => UserPrivilegeSet::available_privileges_on_global(),
             GrantObject::Database(_) => UserPrivilegeSet::available_privileges_on_database(),
             GrantObject::Table(_, _) => UserPrivilegeSet::available_privileges_on_table(),
+            GrantObject::Stage(_) => UserPrivilegeSet::available_privileges_on_stage(),
+            GrantObject::UDF(_) => UserPrivilegeSet::available_privileges_on_udf(),
         }
     }
 }
@@ -60,6 +68,8 @@ impl fmt::Display for GrantObject {
             GrantObject::Global => write!(f, "*.*"),
             GrantObject::Database(ref db) => write!(f, "'{}'.*", db),
             GrantObject::Table(ref db, ref table) => write!(f, "'{}'.'{}'", db, table),
+            GrantObject::Stage(ref stage) => write!(f, "STAGE '{}'", stage),
+            GrantObject::UDF(ref udf) => write!(f, "FUNCTION '{}'", udf),
         }
     }
 }
@@ -81,7 +91,9 @@ impl GrantEntry {
             return false;
         }
 
+        // the owner of an object has all the privileges on it.
         self.privileges.contains(privilege)
+            || self.privileges.contains(UserPrivilegeType::Ownership)
     }
 
     pub fn matches_entry(&self, object: &GrantObject) -> bool {
@@ -143,6 +155,16 @@ impl UserGrantSet {
             .any(|e| e.verify_privilege(object, privilege))
     }
 
+    /// The objects inside the container object, whose ownership is granted.
+    pub fn owned_objects(&self, container: &GrantObject) -> Vec<GrantObject> {
+        self.entries
+            .iter()
+            .filter(|e| e.privileges.contains(UserPrivilegeType::Ownership))
+            .filter(|e| container.contains(&e.object))
+            .map(|e| e.object.clone())
+            .collect()
+    }
+
     pub fn grant_privileges(&mut self, object: &GrantObject, privileges: UserPrivilegeSet) {
         let privileges: BitFlags<UserPrivilegeType> = privileges.into();
         let mut new_entries: Vec<GrantEntry> = vec![];
@@ -172,7 +194,7 @@ impl UserGrantSet {
             .map(|e| {
                 if e.matches_entry(object) {
                     let mut e = e.clone();
-                    e.privileges ^= privileges;
+                    e.privileges.remove(privileges);
                     e
                 } else {
                     e.clone()
@@ -0,0 +1,150 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::chrono::DateTime;
+use common_datavalues::chrono::Utc;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::CopyOptions;
+use crate::FileFormatOptions;
+
+/*
+CREATE PIPE [ IF NOT EXISTS ] <pipe_name>
+    INTO [<database>.]<table>
+    FROM KAFKA ( BROKERS = '<host:port>[,<host:port>...]' TOPIC = '<topic>' )
+  [ FILE_FORMAT = ( TYPE = { CSV | JSON | AVRO } [ formatTypeOptions ] ) ]
+  [ COMMENTS = '<string_literal>' ]
+
+CREATE PIPE [ IF NOT EXISTS ] <pipe_name>
+    INTO [<database>.]<table>
+    FROM @<stage_name>[/<path>]
+  [ PATTERN = '<regex_pattern>' ]
+  [ FILE_FORMAT = ( TYPE = { CSV | JSON | PARQUET } [ formatTypeOptions ] ) ]
+  [ ON_ERROR = { CONTINUE | SKIP_FILE | SKIP_FILE_<num> | ABORT_STATEMENT } ]
+  [ SIZE_LIMIT = <num> ]
+  [ COMMENTS = '<string_literal>' ]
+ */
+
+#[derive(serde::Serialize, serde::Deserialize, Default, Clone, Debug, Eq, PartialEq)]
+#[serde(default)]
+pub struct KafkaSource {
+    pub brokers: Vec<String>,
+    pub topic: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Default, Clone, Debug, Eq, PartialEq)]
+#[serde(default)]
+pub struct StagePipeSource {
+    // `@<stage_name>[/<path>]`
+    pub location: String,
+    pub pattern: String,
+    pub copy_options: CopyOptions,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum PipeSource {
+    Kafka(KafkaSource),
+    // The new files of a stage.
+    Stage(StagePipeSource),
+}
+
+impl Default for PipeSource {
+    fn default() -> Self {
+        Self::Kafka(KafkaSource::default())
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Default, Clone, Debug, Eq, PartialEq)]
+#[serde(default)]
+pub struct PipeInfo {
+    pub pipe_name: String,
+    pub database: String,
+    pub table: String,
+    pub source: PipeSource,
+    pub file_format_options: FileFormatOptions,
+    pub comment: String,
+}
+
+impl PipeInfo {
+    /// The table option in which the pipe keeps the source positions it has
+    /// committed, so they are written atomically with the loaded data.
+    pub fn offsets_option_key(&self) -> String {
+        format!("pipe_offsets.{}", self.pipe_name)
+    }
+}
+
+impl TryFrom<Vec<u8>> for PipeInfo {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(info) => Ok(info),
+            Err(serialize_error) => Err(ErrorCode::IllegalPipeFormat(format!(
+                "Cannot deserialize pipe from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum PipeFileStatus {
+    Loaded,
+    Failed,
+}
+
+impl Default for PipeFileStatus {
+    fn default() -> Self {
+        Self::Loaded
+    }
+}
+
+/// A file of a stage consumed by a pipe, in the load history of the pipe.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(default)]
+pub struct PipeFileInfo {
+    pub file_name: String,
+    pub status: PipeFileStatus,
+    pub rows: u64,
+    // The cause, if the file failed to load.
+    pub error: String,
+    pub load_time: DateTime<Utc>,
+}
+
+impl Default for PipeFileInfo {
+    fn default() -> Self {
+        Self {
+            file_name: "".to_string(),
+            status: PipeFileStatus::default(),
+            rows: 0,
+            error: "".to_string(),
+            load_time: Utc::now(),
+        }
+    }
+}
+
+impl TryFrom<Vec<u8>> for PipeFileInfo {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(info) => Ok(info),
+            Err(serialize_error) => Err(ErrorCode::IllegalPipeFormat(format!(
+                "Cannot deserialize pipe file from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
@@ -49,6 +49,8 @@ pub enum UserPrivilegeType {
     Grant = 1 << 12,
     // Privilege to Create Stage.
     CreateStage = 1 << 13,
+    // Ownership of an object, which has all the privileges on it.
+    Ownership = 1 << 14,
     // TODO: remove this later
     Set = 1 << 4,
 }
@@ -86,6 +88,7 @@ impl std::fmt::Display for UserPrivilegeType {
             UserPrivilegeType::CreateRole => "CREATE ROLE",
             UserPrivilegeType::CreateStage => "CREATE STAGE",
             UserPrivilegeType::Grant => "GRANT",
+            UserPrivilegeType::Ownership => "OWNERSHIP",
             UserPrivilegeType::Set => "SET",
         })
     }
@@ -128,6 +131,16 @@ impl UserPrivilegeSet {
         make_bitflags!(UserPrivilegeType::{ Create | Update | Select | Insert | Delete | Drop | Alter | Grant }).into()
     }
 
+    /// The privileges available to a stage, USAGE allows to read and write its files.
+    pub fn available_privileges_on_stage() -> Self {
+        make_bitflags!(UserPrivilegeType::{ Usage | Alter | Drop }).into()
+    }
+
+    /// The privileges available to a user defined function, USAGE allows to call it.
+    pub fn available_privileges_on_udf() -> Self {
+        make_bitflags!(UserPrivilegeType::{ Usage | Alter | Drop }).into()
+    }
+
     // TODO: remove this, as ALL has different meanings on different objects
     pub fn all_privileges() -> Self {
         ALL_PRIVILEGES.into()
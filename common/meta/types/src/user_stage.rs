@@ -86,6 +86,7 @@ pub enum StageFileFormatType {
     Orc,
     Parquet,
     Xml,
+    Tsv,
 }
 
 impl Default for StageFileFormatType {
@@ -104,10 +105,10 @@ impl FromStr for StageFileFormatType {
             "ORC" => Ok(StageFileFormatType::Orc),
             "PARQUET" => Ok(StageFileFormatType::Parquet),
             "XML" => Ok(StageFileFormatType::Xml),
-            _ => Err(
-                "Unknown file format type, must one of { CSV | JSON | AVRO | ORC | PARQUET | XML }"
-                    .to_string(),
-            ),
+            "TSV" => Ok(StageFileFormatType::Tsv),
+            _ => Err("Unknown file format type, \
+                 must one of { CSV | TSV | JSON | AVRO | ORC | PARQUET | XML }"
+                .to_string()),
         }
     }
 }
@@ -120,6 +121,12 @@ pub struct FileFormatOptions {
     pub skip_header: u64,
     pub field_delimiter: String,
     pub record_delimiter: String,
+    // The character enclosing the fields, as ENCLOSED BY of MySQL, or NONE.
+    // Empty is the default of the format, `"` for CSV and NONE for TSV.
+    pub enclosed_by: String,
+    // The character escaping the special characters, as ESCAPED BY of MySQL, or NONE.
+    // Empty is the default of the format, NONE for CSV and `\` for TSV.
+    pub escaped_by: String,
     pub compression: StageFileCompression,
 }
 
@@ -130,6 +137,8 @@ impl Default for FileFormatOptions {
             record_delimiter: "\n".to_string(),
             field_delimiter: ",".to_string(),
             skip_header: 0,
+            enclosed_by: "".to_string(),
+            escaped_by: "".to_string(),
             compression: StageFileCompression::default(),
         }
     }
@@ -144,6 +153,8 @@ pub struct StageS3Storage {
     pub credentials_aws_key_id: String,
     pub credentials_aws_secret_key: String,
     pub encryption_master_key: String,
+    /// The connection holding the credentials, instead of the credentials above.
+    pub connection: String,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -171,6 +182,8 @@ pub enum OnErrorMode {
     SkipFile,
     SkipFileNum(u64),
     AbortStatement,
+    // Appended to keep the encoding of the modes above.
+    SkipFilePercent(u64),
 }
 
 impl Default for OnErrorMode {
@@ -186,14 +199,22 @@ impl FromStr for OnErrorMode {
             "" => Ok(OnErrorMode::None),
             "CONTINUE" => Ok(OnErrorMode::Continue),
             "SKIP_FILE" => Ok(OnErrorMode::SkipFile),
+            "ABORT_STATEMENT" => Ok(OnErrorMode::AbortStatement),
             v => {
                 let num_str = v.replace("SKIP_FILE_", "");
-                let nums = num_str.parse::<u64>();
+                let nums = match num_str.strip_suffix('%') {
+                    Some(percent_str) => percent_str
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|p| *p <= 100)
+                        .map(OnErrorMode::SkipFilePercent),
+                    None => num_str.parse::<u64>().ok().map(OnErrorMode::SkipFileNum),
+                };
                 match nums{
-                    Ok(v) => { Ok(OnErrorMode::SkipFileNum(v)) }
-                    Err(_) => {
+                    Some(v) => { Ok(v) }
+                    None => {
                         Err(
-                            format!("Unknown OnError mode:{:?}, must one of {{ CONTINUE | SKIP_FILE | SKIP_FILE_<num> | ABORT_STATEMENT }}", v)
+                            format!("Unknown OnError mode:{:?}, must one of {{ CONTINUE | SKIP_FILE | SKIP_FILE_<num> | SKIP_FILE_<num>% | ABORT_STATEMENT }}", v)
                         )
                     }
                 }
@@ -202,11 +223,46 @@ impl FromStr for OnErrorMode {
     }
 }
 
+impl OnErrorMode {
+    /// Whether the rows which fail to parse are skipped instead of failing the load.
+    pub fn skip_error_rows(&self) -> bool {
+        !matches!(self, OnErrorMode::None | OnErrorMode::AbortStatement)
+    }
+
+    /// Whether a file may be skipped as a whole, which is known once the file is parsed.
+    pub fn is_skip_file(&self) -> bool {
+        matches!(
+            self,
+            OnErrorMode::SkipFile | OnErrorMode::SkipFileNum(_) | OnErrorMode::SkipFilePercent(_)
+        )
+    }
+
+    /// Whether a file is skipped, with `error_rows` of its `parsed_rows` failed to parse.
+    pub fn skip_file(&self, error_rows: u64, parsed_rows: u64) -> bool {
+        if error_rows == 0 {
+            return false;
+        }
+
+        match self {
+            OnErrorMode::SkipFile => true,
+            OnErrorMode::SkipFileNum(num) => error_rows >= *num,
+            OnErrorMode::SkipFilePercent(percent) => {
+                error_rows.saturating_mul(100) > percent.saturating_mul(parsed_rows)
+            }
+            _ => false,
+        }
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Default, Clone, Debug, Eq, PartialEq)]
 #[serde(default)]
 pub struct CopyOptions {
     pub on_error: OnErrorMode,
     pub size_limit: usize,
+    /// If true, the files are removed from the stage once they are loaded
+    pub purge: bool,
+    /// The path in the stage to write the rows failing to parse to, not kept if empty
+    pub rejected_records: String,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Default, Clone, Debug, Eq, PartialEq)]
@@ -15,6 +15,7 @@
 mod cluster;
 mod compatible;
 mod match_seq;
+mod network_policy;
 mod user_defined_function;
 mod user_grant;
 mod user_info;
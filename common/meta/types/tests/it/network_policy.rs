@@ -0,0 +1,69 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::IpAddr;
+
+use common_datavalues::chrono::TimeZone;
+use common_datavalues::chrono::Utc;
+use common_exception::exception::Result;
+use common_meta_types::NetworkPolicy;
+
+fn new_policy(allowed: &[&str], blocked: &[&str]) -> NetworkPolicy {
+    NetworkPolicy {
+        name: "office".to_string(),
+        allowed_ip_list: allowed.iter().map(|v| v.to_string()).collect(),
+        blocked_ip_list: blocked.iter().map(|v| v.to_string()).collect(),
+        comment: "".to_string(),
+        created_on: Utc.ymd(2022, 6, 1).and_hms(12, 0, 0),
+    }
+}
+
+fn ip(v: &str) -> IpAddr {
+    v.parse().unwrap()
+}
+
+#[test]
+fn test_network_policy_is_allowed() -> Result<()> {
+    let policy = new_policy(&["192.168.1.0/24", "10.0.0.1"], &["192.168.1.99"]);
+    policy.validate()?;
+    assert!(policy.is_allowed(&ip("192.168.1.1"))?);
+    assert!(policy.is_allowed(&ip("10.0.0.1"))?);
+    assert!(!policy.is_allowed(&ip("10.0.0.2"))?);
+    assert!(!policy.is_allowed(&ip("192.168.2.1"))?);
+    // Blocked even if allowed.
+    assert!(!policy.is_allowed(&ip("192.168.1.99"))?);
+    // IPv4-mapped IPv6 address.
+    assert!(policy.is_allowed(&ip("::ffff:192.168.1.1"))?);
+    assert!(!policy.is_allowed(&ip("::1"))?);
+
+    let policy = new_policy(&["0.0.0.0/0", "fd00::/8"], &[]);
+    assert!(policy.is_allowed(&ip("8.8.8.8"))?);
+    assert!(policy.is_allowed(&ip("fd12::1"))?);
+    assert!(!policy.is_allowed(&ip("fe80::1"))?);
+
+    Ok(())
+}
+
+#[test]
+fn test_network_policy_validate() -> Result<()> {
+    for ip_range in ["192.168.1.0/33", "192.168.1", "10.0.0.0/x", "::1/129"] {
+        let policy = new_policy(&[ip_range], &[]);
+        match policy.validate() {
+            Ok(_) => panic!("Illegal IP range {} must be return Err.", ip_range),
+            Err(cause) => assert_eq!(cause.code(), 2623),
+        }
+    }
+
+    Ok(())
+}
@@ -77,6 +77,26 @@ fn test_grant_object_contains() -> Result<()> {
             rhs: GrantObject::Database("db1".into()),
             expect: false,
         },
+        Test {
+            lhs: GrantObject::Global,
+            rhs: GrantObject::Stage("s1".into()),
+            expect: true,
+        },
+        Test {
+            lhs: GrantObject::Stage("s1".into()),
+            rhs: GrantObject::Stage("s2".into()),
+            expect: false,
+        },
+        Test {
+            lhs: GrantObject::Database("s1".into()),
+            rhs: GrantObject::Stage("s1".into()),
+            expect: false,
+        },
+        Test {
+            lhs: GrantObject::UDF("f1".into()),
+            rhs: GrantObject::UDF("f1".into()),
+            expect: true,
+        },
     ];
     for t in tests {
         assert_eq!(
@@ -200,3 +220,49 @@ fn test_user_grant_set() -> Result<()> {
     ));
     Ok(())
 }
+
+#[test]
+fn test_user_grant_set_ownership() -> Result<()> {
+    let mut grants = UserGrantSet::empty();
+    grants.grant_privileges(
+        &GrantObject::Database("db1".into()),
+        make_bitflags!(UserPrivilegeType::{Ownership}).into(),
+    );
+    grants.grant_privileges(
+        &GrantObject::Table("db2".into(), "table1".into()),
+        make_bitflags!(UserPrivilegeType::{Ownership | Select}).into(),
+    );
+
+    // the owner has all the privileges on the object and the objects inside it
+    assert!(grants.verify_privilege(
+        &GrantObject::Table("db1".into(), "table1".into()),
+        UserPrivilegeType::Drop
+    ));
+    assert!(!grants.verify_privilege(&GrantObject::Global, UserPrivilegeType::Create));
+    assert_eq!(grants.owned_objects(&GrantObject::Global), vec![
+        GrantObject::Database("db1".into()),
+        GrantObject::Table("db2".into(), "table1".into()),
+    ]);
+    assert_eq!(
+        grants.owned_objects(&GrantObject::Database("db2".into())),
+        vec![GrantObject::Table("db2".into(), "table1".into())]
+    );
+
+    // revoking a privilege not granted keeps the others
+    grants.revoke_privileges(
+        &GrantObject::Table("db2".into(), "table1".into()),
+        make_bitflags!(UserPrivilegeType::{Ownership | Insert}).into(),
+    );
+    assert!(grants
+        .owned_objects(&GrantObject::Database("db2".into()))
+        .is_empty());
+    assert!(grants.verify_privilege(
+        &GrantObject::Table("db2".into(), "table1".into()),
+        UserPrivilegeType::Select
+    ));
+    assert!(!grants.verify_privilege(
+        &GrantObject::Table("db2".into(), "table1".into()),
+        UserPrivilegeType::Insert
+    ));
+    Ok(())
+}
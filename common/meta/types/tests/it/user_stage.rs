@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::str::FromStr;
+
 use common_exception::exception::Result;
+use common_meta_types::OnErrorMode;
 use common_meta_types::UserStageInfo;
 
 #[test]
@@ -25,3 +28,54 @@ fn test_user_stage() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_on_error_mode() -> Result<()> {
+    let tests = vec![
+        ("", OnErrorMode::None),
+        ("continue", OnErrorMode::Continue),
+        ("SKIP_FILE", OnErrorMode::SkipFile),
+        ("SKIP_FILE_3", OnErrorMode::SkipFileNum(3)),
+        ("skip_file_10%", OnErrorMode::SkipFilePercent(10)),
+        ("ABORT_STATEMENT", OnErrorMode::AbortStatement),
+    ];
+    for (s, expect) in tests {
+        assert_eq!(OnErrorMode::from_str(s), Ok(expect), "{}", s);
+    }
+
+    for s in ["SKIP_FILE_x", "SKIP_FILE_101%", "SKIP_FILE_-1%"] {
+        assert!(OnErrorMode::from_str(s).is_err(), "{}", s);
+    }
+
+    // The encoding of the existing modes is kept.
+    let ser = serde_json::to_string(&OnErrorMode::SkipFileNum(3))?;
+    assert_eq!(ser, r#"{"SkipFileNum":3}"#);
+    let ser = serde_json::to_string(&OnErrorMode::SkipFilePercent(10))?;
+    assert_eq!(ser, r#"{"SkipFilePercent":10}"#);
+    let de = serde_json::from_str::<OnErrorMode>(&ser)?;
+    assert_eq!(de, OnErrorMode::SkipFilePercent(10));
+
+    Ok(())
+}
+
+#[test]
+fn test_on_error_mode_skip_file() -> Result<()> {
+    // (mode, error rows, parsed rows, expect)
+    let tests = vec![
+        (OnErrorMode::Continue, 10, 10, false),
+        (OnErrorMode::SkipFile, 0, 10, false),
+        (OnErrorMode::SkipFile, 1, 10, true),
+        (OnErrorMode::SkipFileNum(3), 2, 10, false),
+        (OnErrorMode::SkipFileNum(3), 3, 10, true),
+        (OnErrorMode::SkipFilePercent(10), 1, 10, false),
+        (OnErrorMode::SkipFilePercent(10), 2, 10, true),
+        (OnErrorMode::SkipFilePercent(0), 1, 1000, true),
+        (OnErrorMode::SkipFilePercent(100), 10, 10, false),
+    ];
+    for (mode, error_rows, parsed_rows, expect) in tests {
+        let actual = mode.skip_file(error_rows, parsed_rows);
+        assert_eq!(actual, expect, "{:?} {} {}", mode, error_rows, parsed_rows);
+    }
+
+    Ok(())
+}
@@ -12,14 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod plan_aggregating_index_create;
 mod plan_aggregator_final;
 mod plan_aggregator_partial;
 mod plan_broadcast;
 mod plan_call;
+mod plan_connection_alter;
+mod plan_connection_create;
+mod plan_connection_drop;
 mod plan_copy;
+mod plan_database_backup;
 mod plan_database_create;
 mod plan_database_drop;
+mod plan_database_restore;
 mod plan_database_show_create;
+mod plan_delete;
 mod plan_empty;
 mod plan_explain;
 mod plan_expression;
@@ -37,10 +44,14 @@ mod plan_expression_visitor;
 mod plan_filter;
 mod plan_having;
 mod plan_insert_into;
+mod plan_insert_multi_table;
 mod plan_kill;
 mod plan_limit;
 mod plan_limit_by;
 mod plan_list;
+mod plan_merge;
+mod plan_network_policy_create;
+mod plan_network_policy_drop;
 mod plan_node;
 mod plan_node_builder;
 mod plan_node_display;
@@ -52,6 +63,8 @@ mod plan_node_stage;
 mod plan_node_statistics;
 mod plan_node_visitor;
 mod plan_partition;
+mod plan_pipe_create;
+mod plan_pipe_drop;
 mod plan_privilege_grant;
 mod plan_privilege_revoke;
 mod plan_projection;
@@ -61,30 +74,49 @@ mod plan_role_create;
 mod plan_role_drop;
 mod plan_role_grant;
 mod plan_role_revoke;
+mod plan_row_access_policy_create;
+mod plan_row_access_policy_drop;
 mod plan_select;
 mod plan_setting;
+mod plan_share_alter_tenants;
+mod plan_share_create;
+mod plan_share_drop;
+mod plan_share_grant;
+mod plan_share_revoke;
 mod plan_show;
+mod plan_show_connections;
 mod plan_show_databases;
 mod plan_show_engines;
 mod plan_show_functions;
 mod plan_show_grants;
 mod plan_show_metrics;
+mod plan_show_network_policies;
 mod plan_show_processlist;
 mod plan_show_roles;
+mod plan_show_row_access_policies;
 mod plan_show_settings;
 mod plan_show_tab_stat;
 mod plan_show_tables;
 mod plan_show_users;
 mod plan_sink;
 mod plan_sort;
+mod plan_stream_create;
 mod plan_subqueries_set;
+mod plan_table_add_virtual_column;
+mod plan_table_alter_column;
+mod plan_table_analyze;
 mod plan_table_create;
 mod plan_table_describe;
 mod plan_table_drop;
+mod plan_table_flashback;
 mod plan_table_optimize;
+mod plan_table_recluster;
 mod plan_table_rename;
+mod plan_table_row_access_policy;
 mod plan_table_show_create;
 mod plan_table_truncate;
+mod plan_table_vacuum;
+mod plan_update;
 mod plan_use_database;
 mod plan_user_alter;
 mod plan_user_create;
@@ -99,15 +131,25 @@ mod plan_view_alter;
 mod plan_view_create;
 mod plan_view_drop;
 
+pub use plan_aggregating_index_create::AggregatingIndexDefinition;
+pub use plan_aggregating_index_create::CreateAggregatingIndexPlan;
 pub use plan_aggregator_final::AggregatorFinalPlan;
 pub use plan_aggregator_partial::AggregatorPartialPlan;
 pub use plan_broadcast::BroadcastPlan;
 pub use plan_call::CallPlan;
+pub use plan_connection_alter::AlterConnectionPlan;
+pub use plan_connection_create::CreateConnectionPlan;
+pub use plan_connection_drop::DropConnectionPlan;
 pub use plan_copy::CopyPlan;
 pub use plan_copy::ValidationMode;
+pub use plan_copy::COPY_PURGE_SCHEMA;
+pub use plan_database_backup::BackupDatabasePlan;
+pub use plan_database_backup::BACKUP_SCHEMA;
 pub use plan_database_create::CreateDatabasePlan;
 pub use plan_database_drop::DropDatabasePlan;
+pub use plan_database_restore::RestoreDatabasePlan;
 pub use plan_database_show_create::ShowCreateDatabasePlan;
+pub use plan_delete::DeletePlan;
 pub use plan_empty::EmptyPlan;
 pub use plan_explain::ExplainPlan;
 pub use plan_explain::ExplainType;
@@ -151,13 +193,23 @@ pub use plan_having::HavingPlan;
 pub use plan_insert_into::InsertInputSource;
 pub use plan_insert_into::InsertPlan;
 pub use plan_insert_into::InsertValueBlock;
+pub use plan_insert_multi_table::InsertMultiTableBranch;
+pub use plan_insert_multi_table::InsertMultiTableInto;
+pub use plan_insert_multi_table::InsertMultiTablePlan;
 pub use plan_kill::KillPlan;
 pub use plan_limit::LimitPlan;
 pub use plan_limit_by::LimitByPlan;
 pub use plan_list::ListPlan;
+pub use plan_merge::MergeInsertAction;
+pub use plan_merge::MergeMatchedAction;
+pub use plan_merge::MergePlan;
+pub use plan_network_policy_create::CreateNetworkPolicyPlan;
+pub use plan_network_policy_drop::DropNetworkPolicyPlan;
 pub use plan_node::PlanNode;
 pub use plan_node_builder::PlanBuilder;
+pub use plan_node_display_indent::PlanNodeAnnotator;
 pub use plan_node_extras::Extras;
+pub use plan_node_extras::TableSample;
 pub use plan_node_rewriter::PlanRewriter;
 pub use plan_node_rewriter::RewriteHelper;
 pub use plan_node_s3_stage_table::S3StageTableInfo;
@@ -168,6 +220,8 @@ pub use plan_node_visitor::PlanVisitor;
 pub use plan_partition::PartInfo;
 pub use plan_partition::PartInfoPtr;
 pub use plan_partition::Partitions;
+pub use plan_pipe_create::CreatePipePlan;
+pub use plan_pipe_drop::DropPipePlan;
 pub use plan_privilege_grant::GrantPrivilegePlan;
 pub use plan_privilege_revoke::RevokePrivilegePlan;
 pub use plan_projection::ProjectionPlan;
@@ -178,18 +232,28 @@ pub use plan_role_create::CreateRolePlan;
 pub use plan_role_drop::DropRolePlan;
 pub use plan_role_grant::GrantRolePlan;
 pub use plan_role_revoke::RevokeRolePlan;
+pub use plan_row_access_policy_create::CreateRowAccessPolicyPlan;
+pub use plan_row_access_policy_drop::DropRowAccessPolicyPlan;
 pub use plan_select::SelectPlan;
 pub use plan_setting::SettingPlan;
 pub use plan_setting::VarValue;
+pub use plan_share_alter_tenants::AlterShareTenantsPlan;
+pub use plan_share_create::CreateSharePlan;
+pub use plan_share_drop::DropSharePlan;
+pub use plan_share_grant::GrantShareObjectPlan;
+pub use plan_share_revoke::RevokeShareObjectPlan;
 pub use plan_show::PlanShowKind;
 pub use plan_show::ShowPlan;
+pub use plan_show_connections::ShowConnectionsPlan;
 pub use plan_show_databases::ShowDatabasesPlan;
 pub use plan_show_engines::ShowEnginesPlan;
 pub use plan_show_functions::ShowFunctionsPlan;
 pub use plan_show_grants::ShowGrantsPlan;
 pub use plan_show_metrics::ShowMetricsPlan;
+pub use plan_show_network_policies::ShowNetworkPoliciesPlan;
 pub use plan_show_processlist::ShowProcessListsPlan;
 pub use plan_show_roles::ShowRolesPlan;
+pub use plan_show_row_access_policies::ShowRowAccessPoliciesPlan;
 pub use plan_show_settings::ShowSettingsPlan;
 pub use plan_show_tab_stat::ShowTabStatPlan;
 pub use plan_show_tables::ShowTablesPlan;
@@ -197,17 +261,31 @@ pub use plan_show_users::ShowUsersPlan;
 pub use plan_sink::SinkPlan;
 pub use plan_sink::SINK_SCHEMA;
 pub use plan_sort::SortPlan;
+pub use plan_stream_create::CreateStreamPlan;
 pub use plan_subqueries_set::SubQueriesSetPlan;
+pub use plan_table_add_virtual_column::AddVirtualColumnPlan;
+pub use plan_table_add_virtual_column::VirtualColumnDefinition;
+pub use plan_table_alter_column::AlterColumnAction;
+pub use plan_table_alter_column::AlterColumnPlan;
+pub use plan_table_analyze::AnalyzeTablePlan;
 pub use plan_table_create::CreateTablePlan;
 pub use plan_table_create::TableOptions;
 pub use plan_table_describe::DescribeTablePlan;
 pub use plan_table_drop::DropTablePlan;
+pub use plan_table_flashback::FlashbackPoint;
+pub use plan_table_flashback::FlashbackTablePlan;
 pub use plan_table_optimize::Optimization;
 pub use plan_table_optimize::OptimizeTablePlan;
+pub use plan_table_recluster::ReclusterTablePlan;
 pub use plan_table_rename::RenameTableEntity;
 pub use plan_table_rename::RenameTablePlan;
+pub use plan_table_row_access_policy::AlterRowAccessPolicyPlan;
+pub use plan_table_row_access_policy::RowAccessPolicyAction;
 pub use plan_table_show_create::ShowCreateTablePlan;
 pub use plan_table_truncate::TruncateTablePlan;
+pub use plan_table_vacuum::VacuumTablePlan;
+pub use plan_table_vacuum::VACUUM_SCHEMA;
+pub use plan_update::UpdatePlan;
 pub use plan_use_database::UseDatabasePlan;
 pub use plan_user_alter::AlterUserPlan;
 pub use plan_user_create::CreateUserPlan;
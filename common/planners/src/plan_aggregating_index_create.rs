@@ -0,0 +1,107 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+
+use crate::Expression;
+
+/// The definition of an aggregating index, i.e. the results of
+/// `SELECT keys, aggregates FROM table GROUP BY keys`.
+///
+/// Only the aggregate functions the results of which can be merged from their partial results
+/// are supported, so that an index can be refreshed incrementally, and can answer the queries
+/// grouping by a part of the keys.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct AggregatingIndexDefinition {
+    /// The grouping columns
+    pub keys: Vec<String>,
+    /// The aggregate functions, and the columns they aggregate, `None` for `count(*)`
+    pub aggregates: Vec<(String, Option<String>)>,
+}
+
+impl AggregatingIndexDefinition {
+    pub const FUNCTIONS: [&'static str; 4] = ["sum", "count", "min", "max"];
+
+    pub fn aggregate_exprs(&self) -> Vec<Expression> {
+        self.aggregates
+            .iter()
+            .map(|(op, column)| Expression::AggregateFunction {
+                op: op.clone(),
+                distinct: false,
+                params: vec![],
+                args: column
+                    .iter()
+                    .map(|c| Expression::Column(c.clone()))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// The position of the aggregate which `expr` is, if it is an aggregate of the index.
+    pub fn position_of(&self, expr: &Expression) -> Option<usize> {
+        let (op, column) = match expr {
+            Expression::AggregateFunction {
+                op,
+                distinct: false,
+                params,
+                args,
+            } if params.is_empty() => match args.as_slice() {
+                [] => (op, None),
+                [Expression::Column(column)] => (op, Some(column)),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        self.aggregates
+            .iter()
+            .position(|(o, c)| o.eq_ignore_ascii_case(op) && c.as_ref() == column)
+    }
+
+    /// The expression which merges the partial results of the `idx`-th aggregate, which are
+    /// stored in the column of the index named by the aggregate.
+    pub fn merge_expr(&self, idx: usize) -> Expression {
+        let (op, _) = &self.aggregates[idx];
+        let op = match op.as_str() {
+            "count" => "sum",
+            op => op,
+        };
+        Expression::AggregateFunction {
+            op: op.to_owned(),
+            distinct: false,
+            params: vec![],
+            args: vec![Expression::Column(
+                self.aggregate_exprs()[idx].column_name(),
+            )],
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct CreateAggregatingIndexPlan {
+    pub if_not_exists: bool,
+    pub db: String,
+    /// The table on which the index is created
+    pub table: String,
+    pub index: String,
+    pub definition: AggregatingIndexDefinition,
+}
+
+impl CreateAggregatingIndexPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
@@ -16,10 +16,22 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::str::FromStr;
 
-use common_datavalues::DataSchemaRef;
+use common_datavalues::prelude::*;
 use common_meta_types::MetaId;
+use once_cell::sync::Lazy;
 
+use crate::Expression;
 use crate::ReadDataSourcePlan;
+use crate::SourceInfo;
+
+/// The result of a COPY with `PURGE = TRUE`, one row per loaded file.
+pub static COPY_PURGE_SCHEMA: Lazy<DataSchemaRef> = Lazy::new(|| {
+    DataSchemaRefExt::create(vec![
+        DataField::new("file", Vu8::to_data_type()),
+        DataField::new("purge_status", Vu8::to_data_type()),
+        DataField::new("error", Vu8::to_data_type()),
+    ])
+});
 
 #[derive(serde::Serialize, serde::Deserialize, PartialEq, Clone, Debug)]
 pub enum ValidationMode {
@@ -62,11 +74,26 @@ pub struct CopyPlan {
     pub validation_mode: ValidationMode,
     pub files: Vec<String>,
     pub pattern: String,
+    /// The expressions of `schema`, over the columns inferred from the files, by position.
+    /// If it is empty, the files are read by `schema` directly.
+    pub transformation: Vec<Expression>,
 }
 
 impl CopyPlan {
+    /// The output schema, which is the purge status of the files if they are purged.
+    /// The columns to write are always `self.schema`.
     pub fn schema(&self) -> DataSchemaRef {
-        self.schema.clone()
+        match self.purge() {
+            true => COPY_PURGE_SCHEMA.clone(),
+            false => self.schema.clone(),
+        }
+    }
+
+    pub fn purge(&self) -> bool {
+        match &self.from.source_info {
+            SourceInfo::S3StageSource(table_info) => table_info.stage_info.copy_options.purge,
+            _ => false,
+        }
     }
 }
 
@@ -81,6 +108,9 @@ impl Debug for CopyPlan {
         if !self.pattern.is_empty() {
             write!(f, " ,pattern:{:?}", self.pattern)?;
         }
+        if !self.transformation.is_empty() {
+            write!(f, " ,transformation:{:?}", self.transformation)?;
+        }
         write!(f, " ,validation_mode:{:?}", self.validation_mode)
     }
 }
@@ -0,0 +1,42 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_meta_types::UserStageInfo;
+use once_cell::sync::Lazy;
+
+pub static BACKUP_SCHEMA: Lazy<DataSchemaRef> = Lazy::new(|| {
+    DataSchemaRefExt::create(vec![
+        DataField::new("tables", u64::to_data_type()),
+        DataField::new("files", u64::to_data_type()),
+        DataField::new("bytes", u64::to_data_type()),
+    ])
+});
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct BackupDatabasePlan {
+    pub tenant: String,
+    pub database: String,
+    /// The stage the backup is written into, at `path` of it
+    pub stage_info: UserStageInfo,
+    pub path: String,
+    /// If true, the users of the tenant are backed up as well
+    pub with_users: bool,
+}
+
+impl BackupDatabasePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        BACKUP_SCHEMA.clone()
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataSchemaRef;
+use common_meta_types::UserStageInfo;
+
+use crate::BACKUP_SCHEMA;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct RestoreDatabasePlan {
+    pub tenant: String,
+    /// The database to restore the backup as, which must not exist
+    pub database: String,
+    /// The stage the backup is read from, at `path` of it
+    pub stage_info: UserStageInfo,
+    pub path: String,
+    /// If true, the users kept by the backup are restored as well
+    pub with_users: bool,
+}
+
+impl RestoreDatabasePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        BACKUP_SCHEMA.clone()
+    }
+}
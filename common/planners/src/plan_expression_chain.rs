@@ -54,13 +54,20 @@ impl ExpressionChain {
         struct ExpressionActionVisitor(*mut ExpressionChain);
 
         impl ExpressionVisitor for ExpressionActionVisitor {
-            fn pre_visit(self, _expr: &Expression) -> Result<Recursion<Self>> {
-                Ok(Recursion::Continue(self))
+            fn pre_visit(self, expr: &Expression) -> Result<Recursion<Self>> {
+                // the repeated subexpressions are evaluated once, by the first action of them
+                let evaluated = unsafe { (*self.0).has_function(&expr.column_name()) };
+                match evaluated {
+                    true => Ok(Recursion::Stop(self)),
+                    false => Ok(Recursion::Continue(self)),
+                }
             }
 
             fn post_visit(self, expr: &Expression) -> Result<Self> {
                 unsafe {
-                    (*self.0).add_expr(expr)?;
+                    if !(*self.0).has_function(&expr.column_name()) {
+                        (*self.0).add_expr(expr)?;
+                    }
                     Ok(self)
                 }
             }
@@ -70,6 +77,13 @@ impl ExpressionChain {
         Ok(())
     }
 
+    fn has_function(&self, name: &str) -> bool {
+        self.actions.iter().any(|action| match action {
+            ExpressionAction::Function(f) => f.name == name,
+            _ => false,
+        })
+    }
+
     fn add_expr(&mut self, expr: &Expression) -> Result<()> {
         match expr {
             Expression::Alias(name, sub_expr) => {
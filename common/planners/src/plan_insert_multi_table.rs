@@ -0,0 +1,66 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_meta_types::MetaId;
+
+use crate::Expression;
+use crate::PlanNode;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct InsertMultiTableInto {
+    pub database_name: String,
+    pub table_name: String,
+    pub table_id: MetaId,
+    /// The values of all the columns of the target table, evaluated over the source rows
+    pub values: Vec<Expression>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct InsertMultiTableBranch {
+    /// The condition of the branch, `None` for the ELSE branch, which is always the last one
+    pub condition: Option<Expression>,
+    pub intos: Vec<InsertMultiTableInto>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct InsertMultiTablePlan {
+    /// INSERT FIRST if true: a source row goes to the first branch the condition of which
+    /// holds, otherwise INSERT ALL: a source row goes to every branch the condition of which
+    /// holds
+    pub first: bool,
+    pub branches: Vec<InsertMultiTableBranch>,
+    /// The plan producing the source rows
+    pub source: Box<PlanNode>,
+}
+
+impl InsertMultiTablePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+
+    /// The targets of the plan, each of which appears once, in order of appearance.
+    pub fn targets(&self) -> Vec<&InsertMultiTableInto> {
+        let mut targets: Vec<&InsertMultiTableInto> = vec![];
+        for into in self.branches.iter().flat_map(|b| b.intos.iter()) {
+            if targets.iter().all(|t| t.table_id != into.table_id) {
+                targets.push(into);
+            }
+        }
+        targets
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_meta_types::MetaId;
+
+use crate::Expression;
+use crate::PlanNode;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub enum MergeMatchedAction {
+    Update {
+        condition: Option<Expression>,
+        /// The columns to update, and the new values of them
+        update_list: Vec<(String, Expression)>,
+    },
+    Delete {
+        condition: Option<Expression>,
+    },
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct MergeInsertAction {
+    pub condition: Option<Expression>,
+    /// The values of all the columns of the target table
+    pub values: Vec<Expression>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct MergePlan {
+    pub database_name: String,
+    pub table_name: String,
+    pub table_id: MetaId,
+    /// The plan producing the source rows
+    pub source: Box<PlanNode>,
+    pub source_alias: String,
+    /// The columns of the target table, each of which is compared with a value of the source
+    /// row for equality, a target row is matched by a source row if all of them are equal
+    pub on: Vec<(String, Expression)>,
+    /// The actions of the matched rows, the first one the condition of which holds applies
+    pub matched: Vec<MergeMatchedAction>,
+    /// The actions of the source rows which are not matched
+    pub not_matched: Vec<MergeInsertAction>,
+}
+
+impl MergePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+
+    /// The schema of the source rows, of which the columns are qualified by the alias of the
+    /// source, as they are referred to by the expressions of the plan.
+    pub fn source_schema(&self) -> DataSchemaRef {
+        let fields = self
+            .source
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| {
+                let name = Self::source_column_name(&self.source_alias, f.name());
+                DataField::new(&name, f.data_type().clone())
+            })
+            .collect();
+        DataSchemaRefExt::create(fields)
+    }
+
+    pub fn source_column_name(source_alias: &str, column: &str) -> String {
+        format!("{}.{}", source_alias, column)
+    }
+}
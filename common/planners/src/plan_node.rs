@@ -16,25 +16,45 @@ use std::sync::Arc;
 
 use common_datavalues::DataSchemaRef;
 
+use crate::AddVirtualColumnPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AlterColumnPlan;
+use crate::AlterConnectionPlan;
+use crate::AlterRowAccessPolicyPlan;
+use crate::AlterShareTenantsPlan;
 use crate::AlterUserPlan;
 use crate::AlterUserUDFPlan;
 use crate::AlterViewPlan;
+use crate::AnalyzeTablePlan;
+use crate::BackupDatabasePlan;
 use crate::BroadcastPlan;
 use crate::CallPlan;
 use crate::CopyPlan;
+use crate::CreateAggregatingIndexPlan;
+use crate::CreateConnectionPlan;
 use crate::CreateDatabasePlan;
+use crate::CreateNetworkPolicyPlan;
+use crate::CreatePipePlan;
 use crate::CreateRolePlan;
+use crate::CreateRowAccessPolicyPlan;
+use crate::CreateSharePlan;
+use crate::CreateStreamPlan;
 use crate::CreateTablePlan;
 use crate::CreateUserPlan;
 use crate::CreateUserStagePlan;
 use crate::CreateUserUDFPlan;
 use crate::CreateViewPlan;
+use crate::DeletePlan;
 use crate::DescribeTablePlan;
 use crate::DescribeUserStagePlan;
+use crate::DropConnectionPlan;
 use crate::DropDatabasePlan;
+use crate::DropNetworkPolicyPlan;
+use crate::DropPipePlan;
 use crate::DropRolePlan;
+use crate::DropRowAccessPolicyPlan;
+use crate::DropSharePlan;
 use crate::DropTablePlan;
 use crate::DropUserPlan;
 use crate::DropUserStagePlan;
@@ -44,21 +64,28 @@ use crate::EmptyPlan;
 use crate::ExplainPlan;
 use crate::ExpressionPlan;
 use crate::FilterPlan;
+use crate::FlashbackTablePlan;
 use crate::GrantPrivilegePlan;
 use crate::GrantRolePlan;
+use crate::GrantShareObjectPlan;
 use crate::HavingPlan;
+use crate::InsertMultiTablePlan;
 use crate::InsertPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
 use crate::ListPlan;
+use crate::MergePlan;
 use crate::OptimizeTablePlan;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
+use crate::ReclusterTablePlan;
 use crate::RemotePlan;
 use crate::RenameTablePlan;
+use crate::RestoreDatabasePlan;
 use crate::RevokePrivilegePlan;
 use crate::RevokeRolePlan;
+use crate::RevokeShareObjectPlan;
 use crate::SelectPlan;
 use crate::SettingPlan;
 use crate::ShowCreateDatabasePlan;
@@ -69,7 +96,9 @@ use crate::SortPlan;
 use crate::StagePlan;
 use crate::SubQueriesSetPlan;
 use crate::TruncateTablePlan;
+use crate::UpdatePlan;
 use crate::UseDatabasePlan;
+use crate::VacuumTablePlan;
 
 #[allow(clippy::large_enum_variant)]
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
@@ -100,6 +129,16 @@ pub enum PlanNode {
 
     // Insert.
     Insert(InsertPlan),
+    InsertMultiTable(InsertMultiTablePlan),
+
+    // Delete.
+    Delete(DeletePlan),
+
+    // Update.
+    Update(UpdatePlan),
+
+    // Merge.
+    Merge(MergePlan),
 
     // Copy.
     Copy(CopyPlan),
@@ -116,6 +155,8 @@ pub enum PlanNode {
     // Database.
     CreateDatabase(CreateDatabasePlan),
     DropDatabase(DropDatabasePlan),
+    BackupDatabase(BackupDatabasePlan),
+    RestoreDatabase(RestoreDatabasePlan),
     ShowCreateDatabase(ShowCreateDatabasePlan),
 
     // Table.
@@ -124,6 +165,13 @@ pub enum PlanNode {
     RenameTable(RenameTablePlan),
     TruncateTable(TruncateTablePlan),
     OptimizeTable(OptimizeTablePlan),
+    VacuumTable(VacuumTablePlan),
+    AnalyzeTable(AnalyzeTablePlan),
+    FlashbackTable(FlashbackTablePlan),
+    AddVirtualColumn(AddVirtualColumnPlan),
+    AlterColumn(AlterColumnPlan),
+    AlterRowAccessPolicy(AlterRowAccessPolicyPlan),
+    ReclusterTable(ReclusterTablePlan),
     DescribeTable(DescribeTablePlan),
     ShowCreateTable(ShowCreateTablePlan),
 
@@ -132,6 +180,12 @@ pub enum PlanNode {
     DropView(DropViewPlan),
     AlterView(AlterViewPlan),
 
+    // Stream.
+    CreateStream(CreateStreamPlan),
+
+    // Aggregating index.
+    CreateAggregatingIndex(CreateAggregatingIndexPlan),
+
     // User.
     CreateUser(CreateUserPlan),
     AlterUser(AlterUserPlan),
@@ -154,11 +208,35 @@ pub enum PlanNode {
     DropUserStage(DropUserStagePlan),
     DescribeUserStage(DescribeUserStagePlan),
 
+    // Pipe.
+    CreatePipe(CreatePipePlan),
+    DropPipe(DropPipePlan),
+
+    // Row access policy.
+    CreateRowAccessPolicy(CreateRowAccessPolicyPlan),
+    DropRowAccessPolicy(DropRowAccessPolicyPlan),
+
+    // Network policy.
+    CreateNetworkPolicy(CreateNetworkPolicyPlan),
+    DropNetworkPolicy(DropNetworkPolicyPlan),
+
+    // Connection.
+    CreateConnection(CreateConnectionPlan),
+    AlterConnection(AlterConnectionPlan),
+    DropConnection(DropConnectionPlan),
+
     // UDF.
     CreateUserUDF(CreateUserUDFPlan),
     DropUserUDF(DropUserUDFPlan),
     AlterUserUDF(AlterUserUDFPlan),
 
+    // Share.
+    CreateShare(CreateSharePlan),
+    DropShare(DropSharePlan),
+    GrantShareObject(GrantShareObjectPlan),
+    RevokeShareObject(RevokeShareObjectPlan),
+    AlterShareTenants(AlterShareTenantsPlan),
+
     // Use.
     UseDatabase(UseDatabasePlan),
 
@@ -199,6 +277,16 @@ impl PlanNode {
 
             // Insert.
             PlanNode::Insert(v) => v.schema(),
+            PlanNode::InsertMultiTable(v) => v.schema(),
+
+            // Delete.
+            PlanNode::Delete(v) => v.schema(),
+
+            // Update.
+            PlanNode::Update(v) => v.schema(),
+
+            // Merge.
+            PlanNode::Merge(v) => v.schema(),
 
             // Copy.
             PlanNode::Copy(v) => v.schema(),
@@ -212,6 +300,8 @@ impl PlanNode {
             // Database.
             PlanNode::CreateDatabase(v) => v.schema(),
             PlanNode::DropDatabase(v) => v.schema(),
+            PlanNode::BackupDatabase(v) => v.schema(),
+            PlanNode::RestoreDatabase(v) => v.schema(),
             PlanNode::ShowCreateDatabase(v) => v.schema(),
 
             // Table.
@@ -220,11 +310,20 @@ impl PlanNode {
             PlanNode::RenameTable(v) => v.schema(),
             PlanNode::TruncateTable(v) => v.schema(),
             PlanNode::OptimizeTable(v) => v.schema(),
+            PlanNode::VacuumTable(v) => v.schema(),
+            PlanNode::AnalyzeTable(v) => v.schema(),
+            PlanNode::FlashbackTable(v) => v.schema(),
+            PlanNode::AddVirtualColumn(v) => v.schema(),
+            PlanNode::AlterColumn(v) => v.schema(),
+            PlanNode::AlterRowAccessPolicy(v) => v.schema(),
+            PlanNode::ReclusterTable(v) => v.schema(),
             PlanNode::DescribeTable(v) => v.schema(),
             PlanNode::ShowCreateTable(v) => v.schema(),
 
             // View.
             PlanNode::CreateView(v) => v.schema(),
+            PlanNode::CreateStream(v) => v.schema(),
+            PlanNode::CreateAggregatingIndex(v) => v.schema(),
             PlanNode::AlterView(v) => v.schema(),
             PlanNode::DropView(v) => v.schema(),
 
@@ -250,6 +349,23 @@ impl PlanNode {
             PlanNode::DropUserStage(v) => v.schema(),
             PlanNode::DescribeUserStage(v) => v.schema(),
 
+            // Pipe.
+            PlanNode::CreatePipe(v) => v.schema(),
+            PlanNode::DropPipe(v) => v.schema(),
+
+            // Row access policy.
+            PlanNode::CreateRowAccessPolicy(v) => v.schema(),
+            PlanNode::DropRowAccessPolicy(v) => v.schema(),
+
+            // Network policy.
+            PlanNode::CreateNetworkPolicy(v) => v.schema(),
+            PlanNode::DropNetworkPolicy(v) => v.schema(),
+
+            // Connection.
+            PlanNode::CreateConnection(v) => v.schema(),
+            PlanNode::AlterConnection(v) => v.schema(),
+            PlanNode::DropConnection(v) => v.schema(),
+
             // List
             PlanNode::List(v) => v.schema(),
 
@@ -258,6 +374,13 @@ impl PlanNode {
             PlanNode::DropUserUDF(v) => v.schema(),
             PlanNode::AlterUserUDF(v) => v.schema(),
 
+            // Share.
+            PlanNode::CreateShare(v) => v.schema(),
+            PlanNode::DropShare(v) => v.schema(),
+            PlanNode::GrantShareObject(v) => v.schema(),
+            PlanNode::RevokeShareObject(v) => v.schema(),
+            PlanNode::AlterShareTenants(v) => v.schema(),
+
             // Use.
             PlanNode::UseDatabase(v) => v.schema(),
 
@@ -297,6 +420,16 @@ impl PlanNode {
 
             // Insert.
             PlanNode::Insert(_) => "InsertPlan",
+            PlanNode::InsertMultiTable(_) => "InsertMultiTablePlan",
+
+            // Delete.
+            PlanNode::Delete(_) => "DeletePlan",
+
+            // Update.
+            PlanNode::Update(_) => "UpdatePlan",
+
+            // Merge.
+            PlanNode::Merge(_) => "MergePlan",
 
             // Copy.
             PlanNode::Copy(_) => "CopyPlan",
@@ -310,6 +443,8 @@ impl PlanNode {
             // Database.
             PlanNode::CreateDatabase(_) => "CreateDatabasePlan",
             PlanNode::DropDatabase(_) => "DropDatabasePlan",
+            PlanNode::BackupDatabase(_) => "BackupDatabasePlan",
+            PlanNode::RestoreDatabase(_) => "RestoreDatabasePlan",
             PlanNode::ShowCreateDatabase(_) => "ShowCreateDatabasePlan",
 
             // Table.
@@ -318,11 +453,20 @@ impl PlanNode {
             PlanNode::RenameTable(_) => "RenameTablePlan",
             PlanNode::TruncateTable(_) => "TruncateTablePlan",
             PlanNode::OptimizeTable(_) => "OptimizeTablePlan",
+            PlanNode::VacuumTable(_) => "VacuumTablePlan",
+            PlanNode::AnalyzeTable(_) => "AnalyzeTablePlan",
+            PlanNode::FlashbackTable(_) => "FlashbackTablePlan",
+            PlanNode::AddVirtualColumn(_) => "AddVirtualColumnPlan",
+            PlanNode::AlterColumn(_) => "AlterColumnPlan",
+            PlanNode::AlterRowAccessPolicy(_) => "AlterRowAccessPolicyPlan",
+            PlanNode::ReclusterTable(_) => "ReclusterTablePlan",
             PlanNode::ShowCreateTable(_) => "ShowCreateTablePlan",
             PlanNode::DescribeTable(_) => "DescribeTablePlan",
 
             // View.
             PlanNode::CreateView(_) => "CreateViewPlan",
+            PlanNode::CreateStream(_) => "CreateStreamPlan",
+            PlanNode::CreateAggregatingIndex(_) => "CreateAggregatingIndexPlan",
             PlanNode::AlterView(_) => "AlterViewPlan",
             PlanNode::DropView(_) => "DropViewPlan",
 
@@ -348,6 +492,23 @@ impl PlanNode {
             PlanNode::DropUserStage(_) => "DropUserStagePlan",
             PlanNode::DescribeUserStage(_) => "DescribeUserStagePlan",
 
+            // Pipe.
+            PlanNode::CreatePipe(_) => "CreatePipePlan",
+            PlanNode::DropPipe(_) => "DropPipePlan",
+
+            // Row access policy.
+            PlanNode::CreateRowAccessPolicy(_) => "CreateRowAccessPolicyPlan",
+            PlanNode::DropRowAccessPolicy(_) => "DropRowAccessPolicyPlan",
+
+            // Network policy.
+            PlanNode::CreateNetworkPolicy(_) => "CreateNetworkPolicyPlan",
+            PlanNode::DropNetworkPolicy(_) => "DropNetworkPolicyPlan",
+
+            // Connection.
+            PlanNode::CreateConnection(_) => "CreateConnectionPlan",
+            PlanNode::AlterConnection(_) => "AlterConnectionPlan",
+            PlanNode::DropConnection(_) => "DropConnectionPlan",
+
             // List
             PlanNode::List(_) => "ListPlan",
 
@@ -356,6 +517,13 @@ impl PlanNode {
             PlanNode::DropUserUDF(_) => "DropUserUDFPlan",
             PlanNode::AlterUserUDF(_) => "AlterUserUDFPlan",
 
+            // Share.
+            PlanNode::CreateShare(_) => "CreateSharePlan",
+            PlanNode::DropShare(_) => "DropSharePlan",
+            PlanNode::GrantShareObject(_) => "GrantShareObjectPlan",
+            PlanNode::RevokeShareObject(_) => "RevokeShareObjectPlan",
+            PlanNode::AlterShareTenants(_) => "AlterShareTenantsPlan",
+
             // Use.
             PlanNode::UseDatabase(_) => "UseDatabasePlan",
 
@@ -21,6 +21,7 @@ use common_datavalues::DataField;
 use common_datavalues::DataSchema;
 use common_datavalues::DataType;
 
+use crate::plan_node_display_indent::PlanNodeAnnotator;
 use crate::plan_node_display_indent::PlanNodeIndentFormatDisplay;
 use crate::PlanNode;
 
@@ -29,6 +30,14 @@ impl PlanNode {
         PlanNodeIndentFormatDisplay::create(0, self, false)
     }
 
+    /// Same as `display_indent_format`, but appends the annotation of each node to its line.
+    pub fn display_indent_format_with<'a>(
+        &'a self,
+        annotator: PlanNodeAnnotator<'a>,
+    ) -> impl fmt::Display + 'a {
+        PlanNodeIndentFormatDisplay::create(0, self, false).with_annotator(Some(annotator))
+    }
+
     pub fn display_graphviz(&self) -> impl fmt::Display + '_ {
         struct Wrapper<'a>(&'a PlanNode);
         impl<'a> fmt::Display for Wrapper<'a> {
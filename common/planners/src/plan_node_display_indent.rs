@@ -39,10 +39,15 @@ use crate::SortPlan;
 use crate::StagePlan;
 use crate::SubQueriesSetPlan;
 
+/// Returns the extra text appended to the line of a plan node, e.g. the runtime metrics of
+/// EXPLAIN ANALYZE.
+pub type PlanNodeAnnotator<'a> = &'a dyn Fn(&PlanNode) -> Option<String>;
+
 pub struct PlanNodeIndentFormatDisplay<'a> {
     indent: usize,
     node: &'a PlanNode,
     printed_indent: bool,
+    annotator: Option<PlanNodeAnnotator<'a>>,
 }
 
 impl<'a> PlanNodeIndentFormatDisplay<'a> {
@@ -51,8 +56,14 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
             indent,
             node,
             printed_indent: printed,
+            annotator: None,
         }
     }
+
+    pub fn with_annotator(mut self, annotator: Option<PlanNodeAnnotator<'a>>) -> Self {
+        self.annotator = annotator;
+        self
+    }
 }
 
 impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
@@ -96,6 +107,7 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
                     }
 
                     PlanNodeIndentFormatDisplay::create(self.indent, input.as_ref(), printed)
+                        .with_annotator(self.annotator)
                         .fmt(f)?;
                     printed = true;
                 }
@@ -104,6 +116,10 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             }
         }?;
 
+        if let Some(annotation) = self.annotator.and_then(|annotator| annotator(self.node)) {
+            write!(f, " {}", annotation)?;
+        }
+
         let new_indent = self.indent + 1;
         for input in self.node.inputs() {
             if matches!(input.as_ref(), PlanNode::Empty(_)) {
@@ -111,7 +127,9 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             }
 
             writeln!(f)?;
-            PlanNodeIndentFormatDisplay::create(new_indent, &input, false).fmt(f)?;
+            PlanNodeIndentFormatDisplay::create(new_indent, &input, false)
+                .with_annotator(self.annotator)
+                .fmt(f)?;
         }
 
         fmt::Result::Ok(())
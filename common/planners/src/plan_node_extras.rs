@@ -25,6 +25,17 @@ pub struct Extras {
     pub limit: Option<usize>,
     /// Optional order_by expression plan
     pub order_by: Vec<Expression>,
+    /// Optional sampling of the data to read
+    pub sample: Option<TableSample>,
+}
+
+/// Sampling of a table by `TABLESAMPLE`, in the percentage of the data to read.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TableSample {
+    /// Samples the blocks while pruning, the blocks sampled are read entirely
+    Block(f64),
+    /// Samples the rows read, each row is kept independently
+    Row(f64),
 }
 
 impl Extras {
@@ -34,6 +45,7 @@ impl Extras {
             filters: vec![],
             limit: None,
             order_by: vec![],
+            sample: None,
         }
     }
 }
@@ -23,24 +23,44 @@ use common_exception::Result;
 
 use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
+use crate::AddVirtualColumnPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AlterColumnPlan;
+use crate::AlterConnectionPlan;
+use crate::AlterRowAccessPolicyPlan;
+use crate::AlterShareTenantsPlan;
 use crate::AlterUserPlan;
 use crate::AlterUserUDFPlan;
 use crate::AlterViewPlan;
+use crate::AnalyzeTablePlan;
+use crate::BackupDatabasePlan;
 use crate::CallPlan;
 use crate::CopyPlan;
+use crate::CreateAggregatingIndexPlan;
+use crate::CreateConnectionPlan;
 use crate::CreateDatabasePlan;
+use crate::CreateNetworkPolicyPlan;
+use crate::CreatePipePlan;
 use crate::CreateRolePlan;
+use crate::CreateRowAccessPolicyPlan;
+use crate::CreateSharePlan;
+use crate::CreateStreamPlan;
 use crate::CreateTablePlan;
 use crate::CreateUserPlan;
 use crate::CreateUserStagePlan;
 use crate::CreateUserUDFPlan;
 use crate::CreateViewPlan;
+use crate::DeletePlan;
 use crate::DescribeTablePlan;
 use crate::DescribeUserStagePlan;
+use crate::DropConnectionPlan;
 use crate::DropDatabasePlan;
+use crate::DropNetworkPolicyPlan;
+use crate::DropPipePlan;
 use crate::DropRolePlan;
+use crate::DropRowAccessPolicyPlan;
+use crate::DropSharePlan;
 use crate::DropTablePlan;
 use crate::DropUserPlan;
 use crate::DropUserStagePlan;
@@ -53,23 +73,30 @@ use crate::ExpressionPlan;
 use crate::ExpressionRewriter;
 use crate::Expressions;
 use crate::FilterPlan;
+use crate::FlashbackTablePlan;
 use crate::GrantPrivilegePlan;
 use crate::GrantRolePlan;
+use crate::GrantShareObjectPlan;
 use crate::HavingPlan;
+use crate::InsertMultiTablePlan;
 use crate::InsertPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
 use crate::ListPlan;
+use crate::MergePlan;
 use crate::OptimizeTablePlan;
 use crate::PlanBuilder;
 use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
+use crate::ReclusterTablePlan;
 use crate::RemotePlan;
 use crate::RenameTablePlan;
+use crate::RestoreDatabasePlan;
 use crate::RevokePrivilegePlan;
 use crate::RevokeRolePlan;
+use crate::RevokeShareObjectPlan;
 use crate::SelectPlan;
 use crate::SettingPlan;
 use crate::ShowCreateDatabasePlan;
@@ -79,7 +106,9 @@ use crate::SinkPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
+use crate::UpdatePlan;
 use crate::UseDatabasePlan;
+use crate::VacuumTablePlan;
 
 /// `PlanRewriter` is a visitor that can help to rewrite `PlanNode`
 /// By default, a `PlanRewriter` will traverse the plan tree in pre-order and return rewritten plan tree.
@@ -129,6 +158,16 @@ pub trait PlanRewriter: Sized {
 
             // Insert.
             PlanNode::Insert(plan) => self.rewrite_insert_into(plan),
+            PlanNode::InsertMultiTable(plan) => self.rewrite_insert_multi_table(plan),
+
+            // Delete.
+            PlanNode::Delete(plan) => self.rewrite_delete(plan),
+
+            // Update.
+            PlanNode::Update(plan) => self.rewrite_update(plan),
+
+            // Merge.
+            PlanNode::Merge(plan) => self.rewrite_merge(plan),
 
             // Copy.
             PlanNode::Copy(plan) => self.rewrite_copy(plan),
@@ -142,6 +181,8 @@ pub trait PlanRewriter: Sized {
             // Database.
             PlanNode::CreateDatabase(plan) => self.rewrite_create_database(plan),
             PlanNode::DropDatabase(plan) => self.rewrite_drop_database(plan),
+            PlanNode::BackupDatabase(plan) => self.rewrite_backup_database(plan),
+            PlanNode::RestoreDatabase(plan) => self.rewrite_restore_database(plan),
             PlanNode::ShowCreateDatabase(plan) => self.rewrite_show_create_database(plan),
 
             // Table.
@@ -150,11 +191,20 @@ pub trait PlanRewriter: Sized {
             PlanNode::RenameTable(plan) => self.rewrite_rename_table(plan),
             PlanNode::TruncateTable(plan) => self.rewrite_truncate_table(plan),
             PlanNode::OptimizeTable(plan) => self.rewrite_optimize_table(plan),
+            PlanNode::VacuumTable(plan) => self.rewrite_vacuum_table(plan),
+            PlanNode::AnalyzeTable(plan) => self.rewrite_analyze_table(plan),
+            PlanNode::FlashbackTable(plan) => self.rewrite_flashback_table(plan),
+            PlanNode::AddVirtualColumn(plan) => self.rewrite_add_virtual_column(plan),
+            PlanNode::AlterColumn(plan) => self.rewrite_alter_column(plan),
+            PlanNode::AlterRowAccessPolicy(plan) => self.rewrite_alter_row_access_policy(plan),
+            PlanNode::ReclusterTable(plan) => self.rewrite_recluster_table(plan),
             PlanNode::DescribeTable(plan) => self.rewrite_describe_table(plan),
             PlanNode::ShowCreateTable(plan) => self.rewrite_show_create_table(plan),
 
             // View.
             PlanNode::CreateView(plan) => self.rewrite_create_view(plan),
+            PlanNode::CreateStream(plan) => self.rewrite_create_stream(plan),
+            PlanNode::CreateAggregatingIndex(plan) => self.rewrite_create_aggregating_index(plan),
             PlanNode::AlterView(plan) => self.rewrite_alter_view(plan),
             PlanNode::DropView(plan) => self.rewrite_drop_view(plan),
 
@@ -179,6 +229,23 @@ pub trait PlanRewriter: Sized {
             PlanNode::CreateUserStage(plan) => self.rewrite_create_user_stage(plan),
             PlanNode::DropUserStage(plan) => self.rewrite_drop_user_stage(plan),
             PlanNode::DescribeUserStage(plan) => self.rewrite_describe_user_stage(plan),
+
+            // Pipe.
+            PlanNode::CreatePipe(plan) => self.rewrite_create_pipe(plan),
+            PlanNode::DropPipe(plan) => self.rewrite_drop_pipe(plan),
+
+            // Row access policy.
+            PlanNode::CreateRowAccessPolicy(plan) => self.rewrite_create_row_access_policy(plan),
+            PlanNode::DropRowAccessPolicy(plan) => self.rewrite_drop_row_access_policy(plan),
+
+            // Network policy.
+            PlanNode::CreateNetworkPolicy(plan) => self.rewrite_create_network_policy(plan),
+            PlanNode::DropNetworkPolicy(plan) => self.rewrite_drop_network_policy(plan),
+
+            // Connection.
+            PlanNode::CreateConnection(plan) => self.rewrite_create_connection(plan),
+            PlanNode::AlterConnection(plan) => self.rewrite_alter_connection(plan),
+            PlanNode::DropConnection(plan) => self.rewrite_drop_connection(plan),
             PlanNode::List(plan) => self.rewrite_list(plan),
 
             // UDF.
@@ -186,6 +253,13 @@ pub trait PlanRewriter: Sized {
             PlanNode::DropUserUDF(plan) => self.rewrite_drop_user_udf(plan),
             PlanNode::AlterUserUDF(plan) => self.rewrite_alter_user_udf(plan),
 
+            // Share.
+            PlanNode::CreateShare(plan) => self.rewrite_create_share(plan),
+            PlanNode::DropShare(plan) => self.rewrite_drop_share(plan),
+            PlanNode::GrantShareObject(plan) => self.rewrite_grant_share_object(plan),
+            PlanNode::RevokeShareObject(plan) => self.rewrite_revoke_share_object(plan),
+            PlanNode::AlterShareTenants(plan) => self.rewrite_alter_share_tenants(plan),
+
             // Use.
             PlanNode::UseDatabase(plan) => self.rewrite_use_database(plan),
 
@@ -360,10 +434,52 @@ pub trait PlanRewriter: Sized {
         Ok(PlanNode::OptimizeTable(plan.clone()))
     }
 
+    fn rewrite_vacuum_table(&mut self, plan: &VacuumTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::VacuumTable(plan.clone()))
+    }
+
+    fn rewrite_analyze_table(&mut self, plan: &AnalyzeTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::AnalyzeTable(plan.clone()))
+    }
+
+    fn rewrite_flashback_table(&mut self, plan: &FlashbackTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::FlashbackTable(plan.clone()))
+    }
+
+    fn rewrite_add_virtual_column(&mut self, plan: &AddVirtualColumnPlan) -> Result<PlanNode> {
+        Ok(PlanNode::AddVirtualColumn(plan.clone()))
+    }
+
+    fn rewrite_alter_column(&mut self, plan: &AlterColumnPlan) -> Result<PlanNode> {
+        Ok(PlanNode::AlterColumn(plan.clone()))
+    }
+
+    fn rewrite_alter_row_access_policy(
+        &mut self,
+        plan: &AlterRowAccessPolicyPlan,
+    ) -> Result<PlanNode> {
+        Ok(PlanNode::AlterRowAccessPolicy(plan.clone()))
+    }
+
+    fn rewrite_recluster_table(&mut self, plan: &ReclusterTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::ReclusterTable(plan.clone()))
+    }
+
     fn rewrite_create_view(&mut self, plan: &CreateViewPlan) -> Result<PlanNode> {
         Ok(PlanNode::CreateView(plan.clone()))
     }
 
+    fn rewrite_create_stream(&mut self, plan: &CreateStreamPlan) -> Result<PlanNode> {
+        Ok(PlanNode::CreateStream(plan.clone()))
+    }
+
+    fn rewrite_create_aggregating_index(
+        &mut self,
+        plan: &CreateAggregatingIndexPlan,
+    ) -> Result<PlanNode> {
+        Ok(PlanNode::CreateAggregatingIndex(plan.clone()))
+    }
+
     fn rewrite_drop_view(&mut self, plan: &DropViewPlan) -> Result<PlanNode> {
         Ok(PlanNode::DropView(plan.clone()))
     }
@@ -404,10 +520,34 @@ pub trait PlanRewriter: Sized {
         Ok(PlanNode::DropDatabase(plan.clone()))
     }
 
+    fn rewrite_backup_database(&mut self, plan: &BackupDatabasePlan) -> Result<PlanNode> {
+        Ok(PlanNode::BackupDatabase(plan.clone()))
+    }
+
+    fn rewrite_restore_database(&mut self, plan: &RestoreDatabasePlan) -> Result<PlanNode> {
+        Ok(PlanNode::RestoreDatabase(plan.clone()))
+    }
+
     fn rewrite_insert_into(&mut self, plan: &InsertPlan) -> Result<PlanNode> {
         Ok(PlanNode::Insert(plan.clone()))
     }
 
+    fn rewrite_insert_multi_table(&mut self, plan: &InsertMultiTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::InsertMultiTable(plan.clone()))
+    }
+
+    fn rewrite_delete(&mut self, plan: &DeletePlan) -> Result<PlanNode> {
+        Ok(PlanNode::Delete(plan.clone()))
+    }
+
+    fn rewrite_update(&mut self, plan: &UpdatePlan) -> Result<PlanNode> {
+        Ok(PlanNode::Update(plan.clone()))
+    }
+
+    fn rewrite_merge(&mut self, plan: &MergePlan) -> Result<PlanNode> {
+        Ok(PlanNode::Merge(plan.clone()))
+    }
+
     fn rewrite_copy(&mut self, plan: &CopyPlan) -> Result<PlanNode> {
         Ok(PlanNode::Copy(plan.clone()))
     }
@@ -472,6 +612,51 @@ pub trait PlanRewriter: Sized {
         Ok(PlanNode::DropUserStage(plan.clone()))
     }
 
+    fn rewrite_create_pipe(&mut self, plan: &CreatePipePlan) -> Result<PlanNode> {
+        Ok(PlanNode::CreatePipe(plan.clone()))
+    }
+
+    fn rewrite_drop_pipe(&mut self, plan: &DropPipePlan) -> Result<PlanNode> {
+        Ok(PlanNode::DropPipe(plan.clone()))
+    }
+
+    fn rewrite_create_row_access_policy(
+        &mut self,
+        plan: &CreateRowAccessPolicyPlan,
+    ) -> Result<PlanNode> {
+        Ok(PlanNode::CreateRowAccessPolicy(plan.clone()))
+    }
+
+    fn rewrite_drop_row_access_policy(
+        &mut self,
+        plan: &DropRowAccessPolicyPlan,
+    ) -> Result<PlanNode> {
+        Ok(PlanNode::DropRowAccessPolicy(plan.clone()))
+    }
+
+    fn rewrite_create_network_policy(
+        &mut self,
+        plan: &CreateNetworkPolicyPlan,
+    ) -> Result<PlanNode> {
+        Ok(PlanNode::CreateNetworkPolicy(plan.clone()))
+    }
+
+    fn rewrite_drop_network_policy(&mut self, plan: &DropNetworkPolicyPlan) -> Result<PlanNode> {
+        Ok(PlanNode::DropNetworkPolicy(plan.clone()))
+    }
+
+    fn rewrite_create_connection(&mut self, plan: &CreateConnectionPlan) -> Result<PlanNode> {
+        Ok(PlanNode::CreateConnection(plan.clone()))
+    }
+
+    fn rewrite_alter_connection(&mut self, plan: &AlterConnectionPlan) -> Result<PlanNode> {
+        Ok(PlanNode::AlterConnection(plan.clone()))
+    }
+
+    fn rewrite_drop_connection(&mut self, plan: &DropConnectionPlan) -> Result<PlanNode> {
+        Ok(PlanNode::DropConnection(plan.clone()))
+    }
+
     fn rewrite_sink(&mut self, plan: &SinkPlan) -> Result<PlanNode> {
         Ok(PlanNode::Sink(plan.clone()))
     }
@@ -495,6 +680,26 @@ pub trait PlanRewriter: Sized {
     fn rewrite_alter_user_udf(&mut self, plan: &AlterUserUDFPlan) -> Result<PlanNode> {
         Ok(PlanNode::AlterUserUDF(plan.clone()))
     }
+
+    fn rewrite_create_share(&mut self, plan: &CreateSharePlan) -> Result<PlanNode> {
+        Ok(PlanNode::CreateShare(plan.clone()))
+    }
+
+    fn rewrite_drop_share(&mut self, plan: &DropSharePlan) -> Result<PlanNode> {
+        Ok(PlanNode::DropShare(plan.clone()))
+    }
+
+    fn rewrite_grant_share_object(&mut self, plan: &GrantShareObjectPlan) -> Result<PlanNode> {
+        Ok(PlanNode::GrantShareObject(plan.clone()))
+    }
+
+    fn rewrite_revoke_share_object(&mut self, plan: &RevokeShareObjectPlan) -> Result<PlanNode> {
+        Ok(PlanNode::RevokeShareObject(plan.clone()))
+    }
+
+    fn rewrite_alter_share_tenants(&mut self, plan: &AlterShareTenantsPlan) -> Result<PlanNode> {
+        Ok(PlanNode::AlterShareTenants(plan.clone()))
+    }
 }
 
 pub struct RewriteHelper {}
@@ -16,24 +16,44 @@ use common_exception::Result;
 
 use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
+use crate::AddVirtualColumnPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AlterColumnPlan;
+use crate::AlterConnectionPlan;
+use crate::AlterRowAccessPolicyPlan;
+use crate::AlterShareTenantsPlan;
 use crate::AlterUserPlan;
 use crate::AlterUserUDFPlan;
 use crate::AlterViewPlan;
+use crate::AnalyzeTablePlan;
+use crate::BackupDatabasePlan;
 use crate::CallPlan;
 use crate::CopyPlan;
+use crate::CreateAggregatingIndexPlan;
+use crate::CreateConnectionPlan;
 use crate::CreateDatabasePlan;
+use crate::CreateNetworkPolicyPlan;
+use crate::CreatePipePlan;
 use crate::CreateRolePlan;
+use crate::CreateRowAccessPolicyPlan;
+use crate::CreateSharePlan;
+use crate::CreateStreamPlan;
 use crate::CreateTablePlan;
 use crate::CreateUserPlan;
 use crate::CreateUserStagePlan;
 use crate::CreateUserUDFPlan;
 use crate::CreateViewPlan;
+use crate::DeletePlan;
 use crate::DescribeTablePlan;
 use crate::DescribeUserStagePlan;
+use crate::DropConnectionPlan;
 use crate::DropDatabasePlan;
+use crate::DropNetworkPolicyPlan;
+use crate::DropPipePlan;
 use crate::DropRolePlan;
+use crate::DropRowAccessPolicyPlan;
+use crate::DropSharePlan;
 use crate::DropTablePlan;
 use crate::DropUserPlan;
 use crate::DropUserStagePlan;
@@ -44,22 +64,29 @@ use crate::ExplainPlan;
 use crate::Expression;
 use crate::ExpressionPlan;
 use crate::FilterPlan;
+use crate::FlashbackTablePlan;
 use crate::GrantPrivilegePlan;
 use crate::GrantRolePlan;
+use crate::GrantShareObjectPlan;
 use crate::HavingPlan;
+use crate::InsertMultiTablePlan;
 use crate::InsertPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
 use crate::ListPlan;
+use crate::MergePlan;
 use crate::OptimizeTablePlan;
 use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
+use crate::ReclusterTablePlan;
 use crate::RemotePlan;
 use crate::RenameTablePlan;
+use crate::RestoreDatabasePlan;
 use crate::RevokePrivilegePlan;
 use crate::RevokeRolePlan;
+use crate::RevokeShareObjectPlan;
 use crate::SelectPlan;
 use crate::SettingPlan;
 use crate::ShowCreateDatabasePlan;
@@ -69,7 +96,9 @@ use crate::SinkPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
+use crate::UpdatePlan;
 use crate::UseDatabasePlan;
+use crate::VacuumTablePlan;
 
 /// `PlanVisitor` implements visitor pattern(reference [syn](https://docs.rs/syn/1.0.72/syn/visit/trait.Visit.html)) for `PlanNode`.
 ///
@@ -141,6 +170,16 @@ pub trait PlanVisitor {
 
             // Insert.
             PlanNode::Insert(plan) => self.visit_insert_into(plan),
+            PlanNode::InsertMultiTable(plan) => self.visit_insert_multi_table(plan),
+
+            // Delete.
+            PlanNode::Delete(plan) => self.visit_delete(plan),
+
+            // Update.
+            PlanNode::Update(plan) => self.visit_update(plan),
+
+            // Merge.
+            PlanNode::Merge(plan) => self.visit_merge(plan),
 
             // Copy.
             PlanNode::Copy(plan) => self.visit_copy(plan),
@@ -154,6 +193,8 @@ pub trait PlanVisitor {
             // Database.
             PlanNode::CreateDatabase(plan) => self.visit_create_database(plan),
             PlanNode::DropDatabase(plan) => self.visit_drop_database(plan),
+            PlanNode::BackupDatabase(plan) => self.visit_backup_database(plan),
+            PlanNode::RestoreDatabase(plan) => self.visit_restore_database(plan),
             PlanNode::ShowCreateDatabase(plan) => self.visit_show_create_database(plan),
 
             // Table.
@@ -162,11 +203,20 @@ pub trait PlanVisitor {
             PlanNode::RenameTable(plan) => self.visit_rename_table(plan),
             PlanNode::TruncateTable(plan) => self.visit_truncate_table(plan),
             PlanNode::OptimizeTable(plan) => self.visit_optimize_table(plan),
+            PlanNode::VacuumTable(plan) => self.visit_vacuum_table(plan),
+            PlanNode::AnalyzeTable(plan) => self.visit_analyze_table(plan),
+            PlanNode::FlashbackTable(plan) => self.visit_flashback_table(plan),
+            PlanNode::AddVirtualColumn(plan) => self.visit_add_virtual_column(plan),
+            PlanNode::AlterColumn(plan) => self.visit_alter_column(plan),
+            PlanNode::AlterRowAccessPolicy(plan) => self.visit_alter_row_access_policy(plan),
+            PlanNode::ReclusterTable(plan) => self.visit_recluster_table(plan),
             PlanNode::DescribeTable(plan) => self.visit_describe_table(plan),
             PlanNode::ShowCreateTable(plan) => self.visit_show_create_table(plan),
 
             // View.
             PlanNode::CreateView(v) => self.visit_create_view(v),
+            PlanNode::CreateStream(v) => self.visit_create_stream(v),
+            PlanNode::CreateAggregatingIndex(v) => self.visit_create_aggregating_index(v),
             PlanNode::AlterView(v) => self.visit_alter_view(v),
             PlanNode::DropView(v) => self.visit_drop_view(v),
 
@@ -191,6 +241,23 @@ pub trait PlanVisitor {
             PlanNode::CreateUserStage(plan) => self.visit_create_user_stage(plan),
             PlanNode::DropUserStage(plan) => self.visit_drop_user_stage(plan),
             PlanNode::DescribeUserStage(plan) => self.visit_describe_user_stage(plan),
+
+            // Pipe.
+            PlanNode::CreatePipe(plan) => self.visit_create_pipe(plan),
+            PlanNode::DropPipe(plan) => self.visit_drop_pipe(plan),
+
+            // Row access policy.
+            PlanNode::CreateRowAccessPolicy(plan) => self.visit_create_row_access_policy(plan),
+            PlanNode::DropRowAccessPolicy(plan) => self.visit_drop_row_access_policy(plan),
+
+            // Network policy.
+            PlanNode::CreateNetworkPolicy(plan) => self.visit_create_network_policy(plan),
+            PlanNode::DropNetworkPolicy(plan) => self.visit_drop_network_policy(plan),
+
+            // Connection.
+            PlanNode::CreateConnection(plan) => self.visit_create_connection(plan),
+            PlanNode::AlterConnection(plan) => self.visit_alter_connection(plan),
+            PlanNode::DropConnection(plan) => self.visit_drop_connection(plan),
             PlanNode::List(plan) => self.visit_list(plan),
 
             // UDF.
@@ -198,6 +265,13 @@ pub trait PlanVisitor {
             PlanNode::DropUserUDF(plan) => self.visit_drop_user_udf(plan),
             PlanNode::AlterUserUDF(plan) => self.visit_alter_user_udf(plan),
 
+            // Share.
+            PlanNode::CreateShare(plan) => self.visit_create_share(plan),
+            PlanNode::DropShare(plan) => self.visit_drop_share(plan),
+            PlanNode::GrantShareObject(plan) => self.visit_grant_share_object(plan),
+            PlanNode::RevokeShareObject(plan) => self.visit_revoke_share_object(plan),
+            PlanNode::AlterShareTenants(plan) => self.visit_alter_share_tenants(plan),
+
             // Use.
             PlanNode::UseDatabase(plan) => self.visit_use_database(plan),
 
@@ -321,6 +395,14 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_backup_database(&mut self, _: &BackupDatabasePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_restore_database(&mut self, _: &RestoreDatabasePlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_create_table(&mut self, _: &CreateTablePlan) -> Result<()> {
         Ok(())
     }
@@ -373,6 +455,34 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_vacuum_table(&mut self, _: &VacuumTablePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_analyze_table(&mut self, _: &AnalyzeTablePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_flashback_table(&mut self, _: &FlashbackTablePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_add_virtual_column(&mut self, _: &AddVirtualColumnPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_alter_column(&mut self, _: &AlterColumnPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_alter_row_access_policy(&mut self, _: &AlterRowAccessPolicyPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_recluster_table(&mut self, _: &ReclusterTablePlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_describe_user_stage(&mut self, _: &DescribeUserStagePlan) -> Result<()> {
         Ok(())
     }
@@ -397,6 +507,22 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_insert_multi_table(&mut self, _: &InsertMultiTablePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_delete(&mut self, _: &DeletePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_update(&mut self, _: &UpdatePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_merge(&mut self, _: &MergePlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_copy(&mut self, _: &CopyPlan) -> Result<()> {
         Ok(())
     }
@@ -417,6 +543,14 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_create_stream(&mut self, _: &CreateStreamPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_create_aggregating_index(&mut self, _: &CreateAggregatingIndexPlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_drop_view(&mut self, _: &DropViewPlan) -> Result<()> {
         Ok(())
     }
@@ -440,6 +574,42 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_create_pipe(&mut self, _: &CreatePipePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_drop_pipe(&mut self, _: &DropPipePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_create_row_access_policy(&mut self, _: &CreateRowAccessPolicyPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_drop_row_access_policy(&mut self, _: &DropRowAccessPolicyPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_create_network_policy(&mut self, _: &CreateNetworkPolicyPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_drop_network_policy(&mut self, _: &DropNetworkPolicyPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_create_connection(&mut self, _: &CreateConnectionPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_alter_connection(&mut self, _: &AlterConnectionPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_drop_connection(&mut self, _: &DropConnectionPlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_show_create_database(&mut self, _: &ShowCreateDatabasePlan) -> Result<()> {
         Ok(())
     }
@@ -459,4 +629,24 @@ pub trait PlanVisitor {
     fn visit_alter_user_udf(&mut self, _: &AlterUserUDFPlan) -> Result<()> {
         Ok(())
     }
+
+    fn visit_create_share(&mut self, _: &CreateSharePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_drop_share(&mut self, _: &DropSharePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_grant_share_object(&mut self, _: &GrantShareObjectPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_revoke_share_object(&mut self, _: &RevokeShareObjectPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_alter_share_tenants(&mut self, _: &AlterShareTenantsPlan) -> Result<()> {
+        Ok(())
+    }
 }
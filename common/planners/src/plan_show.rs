@@ -18,13 +18,16 @@ use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
 
 use crate::plan_show_tab_stat::ShowTabStatPlan;
+use crate::ShowConnectionsPlan;
 use crate::ShowDatabasesPlan;
 use crate::ShowEnginesPlan;
 use crate::ShowFunctionsPlan;
 use crate::ShowGrantsPlan;
 use crate::ShowMetricsPlan;
+use crate::ShowNetworkPoliciesPlan;
 use crate::ShowProcessListsPlan;
 use crate::ShowRolesPlan;
+use crate::ShowRowAccessPoliciesPlan;
 use crate::ShowSettingsPlan;
 use crate::ShowTablesPlan;
 use crate::ShowUsersPlan;
@@ -52,6 +55,9 @@ pub enum ShowPlan {
     ShowUsers(ShowUsersPlan),
     ShowGrants(ShowGrantsPlan),
     ShowRoles(ShowRolesPlan),
+    ShowRowAccessPolicies(ShowRowAccessPoliciesPlan),
+    ShowNetworkPolicies(ShowNetworkPoliciesPlan),
+    ShowConnections(ShowConnectionsPlan),
     ShowTabStat(ShowTabStatPlan),
 }
 
@@ -0,0 +1,60 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
+
+use crate::Expression;
+
+/// A path of a Variant column, the values of which are extracted and stored columnar when the
+/// blocks of the table are written, e.g. `v:data.user.id`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct VirtualColumnDefinition {
+    /// The Variant column the values are extracted from
+    pub column: String,
+    /// The path of the values, as that of `get_path`
+    pub path: String,
+}
+
+impl VirtualColumnDefinition {
+    /// The expression extracting the values, `name` is the name of the virtual column
+    pub fn expr(&self, name: &str) -> Expression {
+        Expression::MapAccess {
+            name: name.to_string(),
+            args: vec![
+                Expression::Column(self.column.clone()),
+                Expression::create_literal(DataValue::String(self.path.as_bytes().to_vec())),
+            ],
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct AddVirtualColumnPlan {
+    pub if_exists: bool,
+    pub database: String,
+    pub table: String,
+    /// The name of the virtual column, which is that of the path expression
+    pub name: String,
+    pub definition: VirtualColumnDefinition,
+}
+
+impl AddVirtualColumnPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
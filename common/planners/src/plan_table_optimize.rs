@@ -23,7 +23,9 @@ bitflags! {
     pub struct Optimization: u32 {
         const PURGE   = 0b00000001;
         const COMPACT = 0b00000010;
-        const ALL = Self::PURGE.bits | Self::COMPACT.bits;
+        const COMPACT_SEGMENT = 0b00000100;
+        const EXPIRE = 0b00001000;
+        const ALL = Self::PURGE.bits | Self::COMPACT.bits | Self::EXPIRE.bits;
     }
 }
 
@@ -32,6 +34,8 @@ pub struct OptimizeTablePlan {
     pub database: String,
     pub table: String,
     pub operation: Optimization,
+    /// Max number of blocks to be rewritten by the compaction, unlimited if None
+    pub limit: Option<usize>,
 }
 
 impl OptimizeTablePlan {
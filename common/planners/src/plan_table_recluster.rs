@@ -0,0 +1,39 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+
+use crate::Expression;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ReclusterTablePlan {
+    pub if_exists: bool,
+    pub database: String,
+    pub table: String,
+    /// Keeps reclustering until the table is well clustered, otherwise only one round is done
+    pub is_final: bool,
+    /// Only the blocks which may contain the matching rows are reclustered
+    pub selection: Option<Expression>,
+    /// Max number of blocks to be rewritten by a round, the default of the table if None
+    pub limit: Option<usize>,
+}
+
+impl ReclusterTablePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
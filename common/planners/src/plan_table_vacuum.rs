@@ -0,0 +1,41 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use once_cell::sync::Lazy;
+
+pub static VACUUM_SCHEMA: Lazy<DataSchemaRef> = Lazy::new(|| {
+    DataSchemaRefExt::create(vec![
+        DataField::new("snapshots", u64::to_data_type()),
+        DataField::new("segments", u64::to_data_type()),
+        DataField::new("blocks", u64::to_data_type()),
+        DataField::new("bytes_reclaimed", u64::to_data_type()),
+    ])
+});
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct VacuumTablePlan {
+    pub database: String,
+    pub table: String,
+    /// Historical data newer than this (in hours) is retained
+    pub retain_hours: u64,
+    /// If true, only reports what would be removed
+    pub dry_run: bool,
+}
+
+impl VacuumTablePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        VACUUM_SCHEMA.clone()
+    }
+}
@@ -241,6 +241,10 @@ impl FromToProto<pb::TableMeta> for mt::TableMeta {
             created_on: DateTime::<Utc>::from_pb(p.created_on)?,
             updated_on: DateTime::<Utc>::from_pb(p.updated_on)?,
             comment: p.comment,
+            usage: match p.usage {
+                Some(usage) => mt::TableUsage::from_pb(usage)?,
+                None => Default::default(),
+            },
         };
         Ok(v)
     }
@@ -256,6 +260,34 @@ impl FromToProto<pb::TableMeta> for mt::TableMeta {
             created_on: self.created_on.to_pb()?,
             updated_on: self.updated_on.to_pb()?,
             comment: self.comment.clone(),
+            usage: Some(self.usage.to_pb()?),
+        };
+        Ok(p)
+    }
+}
+
+impl FromToProto<pb::TableUsage> for mt::TableUsage {
+    fn from_pb(p: pb::TableUsage) -> Result<Self, Incompatible> {
+        check_ver(p.ver)?;
+
+        let v = Self {
+            number_of_rows: p.number_of_rows,
+            number_of_blocks: p.number_of_blocks,
+            data_bytes: p.data_bytes,
+            compressed_data_bytes: p.compressed_data_bytes,
+            index_data_bytes: p.index_data_bytes,
+        };
+        Ok(v)
+    }
+
+    fn to_pb(&self) -> Result<pb::TableUsage, Incompatible> {
+        let p = pb::TableUsage {
+            ver: VER,
+            number_of_rows: self.number_of_rows,
+            number_of_blocks: self.number_of_blocks,
+            data_bytes: self.data_bytes,
+            compressed_data_bytes: self.compressed_data_bytes,
+            index_data_bytes: self.index_data_bytes,
         };
         Ok(p)
     }
@@ -299,7 +331,11 @@ impl FromToProto<pb::DataField> for dv::DataField {
                 reason: "DataField.data_type can not be None".to_string(),
             })?)?,
         )
-        .with_default_expr(p.default_expr);
+        .with_default_expr(p.default_expr)
+        .with_computed_expr(match p.computed_expr {
+            Some(computed_expr) => Some(dv::ComputedExpr::from_pb(computed_expr)?),
+            None => None,
+        });
         Ok(v)
     }
 
@@ -309,6 +345,36 @@ impl FromToProto<pb::DataField> for dv::DataField {
             name: self.name().clone(),
             default_expr: self.default_expr().clone(),
             data_type: Some(self.data_type().to_pb()?),
+            computed_expr: match self.computed_expr() {
+                Some(computed_expr) => Some(computed_expr.to_pb()?),
+                None => None,
+            },
+        };
+        Ok(p)
+    }
+}
+
+impl FromToProto<pb::ComputedExpr> for dv::ComputedExpr {
+    fn from_pb(p: pb::ComputedExpr) -> Result<Self, Incompatible> {
+        check_ver(p.ver)?;
+
+        match p.expr {
+            Some(pb::computed_expr::Expr::Stored(expr)) => Ok(dv::ComputedExpr::Stored(expr)),
+            Some(pb::computed_expr::Expr::Virtual(expr)) => Ok(dv::ComputedExpr::Virtual(expr)),
+            None => Err(Incompatible {
+                reason: "ComputedExpr.expr can not be None".to_string(),
+            }),
+        }
+    }
+
+    fn to_pb(&self) -> Result<pb::ComputedExpr, Incompatible> {
+        let expr = match self {
+            dv::ComputedExpr::Stored(expr) => pb::computed_expr::Expr::Stored(expr.clone()),
+            dv::ComputedExpr::Virtual(expr) => pb::computed_expr::Expr::Virtual(expr.clone()),
+        };
+        let p = pb::ComputedExpr {
+            ver: VER,
+            expr: Some(expr),
         };
         Ok(p)
     }
@@ -628,6 +694,103 @@ impl FromToProto<pb::Variant> for dv::VariantType {
     }
 }
 
+impl FromToProto<pb::RowAccessPolicy> for mt::RowAccessPolicy {
+    fn from_pb(p: pb::RowAccessPolicy) -> Result<Self, Incompatible> {
+        check_ver(p.ver)?;
+
+        let mut args = Vec::with_capacity(p.args.len());
+        for arg in p.args {
+            args.push(dv::DataField::from_pb(arg)?);
+        }
+
+        let v = Self {
+            name: p.name,
+            args,
+            body: p.body,
+            exempt_roles: p.exempt_roles,
+            comment: p.comment,
+            created_on: DateTime::<Utc>::from_pb(p.created_on)?,
+        };
+        Ok(v)
+    }
+
+    fn to_pb(&self) -> Result<pb::RowAccessPolicy, Incompatible> {
+        let mut args = Vec::with_capacity(self.args.len());
+        for arg in &self.args {
+            args.push(arg.to_pb()?);
+        }
+
+        let p = pb::RowAccessPolicy {
+            ver: VER,
+            name: self.name.clone(),
+            args,
+            body: self.body.clone(),
+            exempt_roles: self.exempt_roles.clone(),
+            comment: self.comment.clone(),
+            created_on: self.created_on.to_pb()?,
+        };
+        Ok(p)
+    }
+}
+
+impl FromToProto<pb::NetworkPolicy> for mt::NetworkPolicy {
+    fn from_pb(p: pb::NetworkPolicy) -> Result<Self, Incompatible> {
+        check_ver(p.ver)?;
+
+        let v = Self {
+            name: p.name,
+            allowed_ip_list: p.allowed_ip_list,
+            blocked_ip_list: p.blocked_ip_list,
+            comment: p.comment,
+            created_on: DateTime::<Utc>::from_pb(p.created_on)?,
+        };
+        Ok(v)
+    }
+
+    fn to_pb(&self) -> Result<pb::NetworkPolicy, Incompatible> {
+        let p = pb::NetworkPolicy {
+            ver: VER,
+            name: self.name.clone(),
+            allowed_ip_list: self.allowed_ip_list.clone(),
+            blocked_ip_list: self.blocked_ip_list.clone(),
+            comment: self.comment.clone(),
+            created_on: self.created_on.to_pb()?,
+        };
+        Ok(p)
+    }
+}
+
+impl FromToProto<pb::Connection> for mt::Connection {
+    fn from_pb(p: pb::Connection) -> Result<Self, Incompatible> {
+        check_ver(p.ver)?;
+
+        let v = Self {
+            name: p.name,
+            storage_type: p.storage_type,
+            encrypted_credentials: p.encrypted_credentials,
+            master_key_id: p.master_key_id,
+            comment: p.comment,
+            created_on: DateTime::<Utc>::from_pb(p.created_on)?,
+            updated_on: DateTime::<Utc>::from_pb(p.updated_on)?,
+        };
+        Ok(v)
+    }
+
+    fn to_pb(&self) -> Result<pb::Connection, Incompatible> {
+        let p = pb::Connection {
+            ver: VER,
+            name: self.name.clone(),
+            storage_type: self.storage_type.clone(),
+            encrypted_credentials: self.encrypted_credentials.clone(),
+            master_key_id: self.master_key_id.clone(),
+            comment: self.comment.clone(),
+            created_on: self.created_on.to_pb()?,
+            updated_on: self.updated_on.to_pb()?,
+        };
+        Ok(p)
+    }
+}
+
 impl FromToProto<String> for DateTime<Utc> {
     fn from_pb(p: String) -> Result<Self, Incompatible> {
         let v = DateTime::<Utc>::from_str(&p).map_err(|e| Incompatible {
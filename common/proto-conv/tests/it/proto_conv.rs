@@ -67,6 +67,8 @@ fn new_table_info() -> mt::TableInfo {
                     .with_default_expr(Some(b"a==b".to_vec())),
                     dv::DataField::new("bool", dv::BooleanType::default().into()),
                     dv::DataField::new("int8", dv::Int8Type::default().into()),
+                    dv::DataField::new("computed", dv::Int8Type::default().into())
+                        .with_computed_expr(Some(dv::ComputedExpr::Stored(b"int8".to_vec()))),
                     dv::DataField::new("int16", dv::Int16Type::default().into()),
                     dv::DataField::new("int32", dv::Int32Type::default().into()),
                     dv::DataField::new("int64", dv::Int64Type::default().into()),
@@ -105,10 +107,53 @@ fn new_table_info() -> mt::TableInfo {
             created_on: Utc.ymd(2014, 11, 28).and_hms(12, 0, 9),
             updated_on: Utc.ymd(2014, 11, 29).and_hms(12, 0, 10),
             comment: s("table_comment"),
+            usage: mt::TableUsage {
+                number_of_rows: 10,
+                number_of_blocks: 2,
+                data_bytes: 1024,
+                compressed_data_bytes: 512,
+                index_data_bytes: 64,
+            },
         },
     }
 }
 
+fn new_row_access_policy() -> mt::RowAccessPolicy {
+    mt::RowAccessPolicy {
+        name: s("region_policy"),
+        args: vec![
+            dv::DataField::new("region", dv::StringType::default().into()),
+            dv::DataField::new("level", dv::Int32Type::default().into()),
+        ],
+        body: s("region = 'eu' or level > 3"),
+        exempt_roles: vec![s("admin"), s("auditor")],
+        comment: s("foo"),
+        created_on: Utc.ymd(2014, 11, 28).and_hms(12, 0, 9),
+    }
+}
+
+fn new_network_policy() -> mt::NetworkPolicy {
+    mt::NetworkPolicy {
+        name: s("office"),
+        allowed_ip_list: vec![s("192.168.1.0/24"), s("10.0.0.1")],
+        blocked_ip_list: vec![s("192.168.1.99")],
+        comment: s("foo"),
+        created_on: Utc.ymd(2014, 11, 28).and_hms(12, 0, 9),
+    }
+}
+
+fn new_connection() -> mt::Connection {
+    mt::Connection {
+        name: s("my_s3"),
+        storage_type: s("s3"),
+        encrypted_credentials: vec![1, 2, 3, 4],
+        master_key_id: s("0123456789abcdef"),
+        comment: s("foo"),
+        created_on: Utc.ymd(2014, 11, 28).and_hms(12, 0, 9),
+        updated_on: Utc.ymd(2014, 11, 29).and_hms(12, 0, 9),
+    }
+}
+
 #[test]
 fn test_pb_from_to() -> anyhow::Result<()> {
     let db = new_db_info();
@@ -121,6 +166,21 @@ fn test_pb_from_to() -> anyhow::Result<()> {
     let got = mt::TableInfo::from_pb(p)?;
     assert_eq!(tbl, got);
 
+    let policy = new_row_access_policy();
+    let p = policy.to_pb()?;
+    let got = mt::RowAccessPolicy::from_pb(p)?;
+    assert_eq!(policy, got);
+
+    let policy = new_network_policy();
+    let p = policy.to_pb()?;
+    let got = mt::NetworkPolicy::from_pb(p)?;
+    assert_eq!(policy, got);
+
+    let connection = new_connection();
+    let p = connection.to_pb()?;
+    let got = mt::Connection::from_pb(p)?;
+    assert_eq!(connection, got);
+
     Ok(())
 }
 
@@ -295,6 +355,8 @@ fn test_load_old() -> anyhow::Result<()> {
                 created_on: Utc.ymd(2014, 11, 28).and_hms(12, 0, 9),
                 updated_on: Utc.ymd(2014, 11, 29).and_hms(12, 0, 10),
                 comment: s("table_comment"),
+                // not in the old data, thus loaded as default
+                usage: Default::default(),
             },
         };
         assert_eq!(want, got);
@@ -0,0 +1,264 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use csv_async::ByteRecord;
+use futures::AsyncRead;
+use futures::AsyncReadExt;
+
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Delimiter {
+    Field,
+    Record,
+    // The data may be the start of a delimiter, more data is needed to know.
+    Partial,
+}
+
+/// Reads the records of the delimiters with more than one byte, e.g. `||`, or of the escaped
+/// data, which the csv reader doesn't support. A field may be enclosed by the quote, in which
+/// two quotes are a quote and the delimiters are data. The escape character escapes the next
+/// character anywhere, as the `ESCAPED BY` of MySQL, and the field of `\N` is NULL.
+pub struct DelimitedReader<R> {
+    reader: R,
+    field_delimiter: Vec<u8>,
+    record_delimiter: Vec<u8>,
+    quote: Option<u8>,
+    escape: Option<u8>,
+    skip_header: bool,
+    buffer: Vec<u8>,
+    pos: usize,
+    eof: bool,
+    nulls: Vec<bool>,
+}
+
+impl<R> DelimitedReader<R>
+where R: AsyncRead + Unpin + Send
+{
+    pub fn create(
+        reader: R,
+        field_delimiter: Vec<u8>,
+        record_delimiter: Vec<u8>,
+        quote: Option<u8>,
+        escape: Option<u8>,
+        skip_header: bool,
+    ) -> Self {
+        DelimitedReader {
+            reader,
+            field_delimiter,
+            record_delimiter,
+            quote,
+            // The quote escaping itself is the same as two quotes.
+            escape: escape.filter(|e| Some(*e) != quote),
+            skip_header,
+            buffer: vec![],
+            pos: 0,
+            eof: false,
+            nulls: vec![],
+        }
+    }
+
+    /// Whether the fields of the last record are NULL.
+    pub fn nulls(&self) -> &[bool] {
+        &self.nulls
+    }
+
+    /// Reads the next record, returns false at the end of the data.
+    pub async fn read_record(&mut self, record: &mut ByteRecord) -> Result<bool> {
+        loop {
+            record.clear();
+            let mut nulls = std::mem::take(&mut self.nulls);
+            nulls.clear();
+            let end = self.parse_record(record, &mut nulls);
+            self.nulls = nulls;
+            match end {
+                Some(end) => {
+                    self.pos = end;
+                    // As the csv reader, the empty lines are skipped.
+                    if record.len() == 1 && record.as_slice().is_empty() {
+                        continue;
+                    }
+                    if self.skip_header {
+                        self.skip_header = false;
+                        continue;
+                    }
+                    return Ok(true);
+                }
+                None if self.eof => return Ok(false),
+                None => self.fill().await?,
+            }
+        }
+    }
+
+    async fn fill(&mut self) -> Result<()> {
+        self.buffer.drain(..self.pos);
+        self.pos = 0;
+
+        let len = self.buffer.len();
+        self.buffer.resize(len + READ_BUFFER_SIZE, 0);
+        let n = self.reader.read(&mut self.buffer[len..]).await?;
+        self.buffer.truncate(len + n);
+        self.eof = n == 0;
+        Ok(())
+    }
+
+    fn match_delimiter(&self, data: &[u8]) -> Option<Delimiter> {
+        // The longer is matched first, as the other may be its prefix.
+        let delimiters = match self.record_delimiter.len() >= self.field_delimiter.len() {
+            true => [
+                (&self.record_delimiter, Delimiter::Record),
+                (&self.field_delimiter, Delimiter::Field),
+            ],
+            false => [
+                (&self.field_delimiter, Delimiter::Field),
+                (&self.record_delimiter, Delimiter::Record),
+            ],
+        };
+
+        for (delimiter, kind) in delimiters {
+            if data.starts_with(delimiter) {
+                return Some(kind);
+            }
+            if !self.eof && delimiter.starts_with(data) {
+                return Some(Delimiter::Partial);
+            }
+        }
+        None
+    }
+
+    // Parses the next record of the buffer, returns the end of it in the buffer, or None if more
+    // data is needed. The bytes between the special characters are copied at once.
+    fn parse_record(&self, record: &mut ByteRecord, nulls: &mut Vec<bool>) -> Option<usize> {
+        let data = &self.buffer[self.pos..];
+        if data.is_empty() {
+            return None;
+        }
+
+        let quote = self.quote;
+        let escape = self.escape;
+        let field_byte = self.field_delimiter[0];
+        let record_byte = self.record_delimiter[0];
+        let is_special = |b: &u8| {
+            Some(*b) == quote || Some(*b) == escape || *b == field_byte || *b == record_byte
+        };
+
+        let mut field = vec![];
+        let mut field_start = true;
+        let mut quoted = false;
+        // The field starts with the escaped `N`, it's NULL if nothing follows.
+        let mut null = false;
+        let mut i = 0;
+        while i < data.len() {
+            if Some(data[i]) == escape {
+                match data.get(i + 1) {
+                    None if !self.eof => return None,
+                    None => field.push(data[i]),
+                    Some(b) => {
+                        null = field_start && *b == b'N';
+                        field.push(unescape(*b));
+                    }
+                }
+                field_start = false;
+                i += 2;
+                continue;
+            }
+
+            if quoted {
+                let n = data[i..]
+                    .iter()
+                    .position(|b| Some(*b) == quote || Some(*b) == escape)
+                    .unwrap_or(data.len() - i);
+                field.extend_from_slice(&data[i..i + n]);
+                i += n;
+                if i < data.len() && Some(data[i]) == quote {
+                    i += 1;
+                    match data.get(i) {
+                        Some(b) if Some(*b) == quote => {
+                            field.push(*b);
+                            i += 1;
+                        }
+                        None if !self.eof => return None,
+                        _ => quoted = false,
+                    }
+                }
+                continue;
+            }
+
+            let n = data[i..]
+                .iter()
+                .position(is_special)
+                .unwrap_or(data.len() - i);
+            if n > 0 {
+                field.extend_from_slice(&data[i..i + n]);
+                field_start = false;
+                i += n;
+                continue;
+            }
+
+            if Some(data[i]) == quote && field_start {
+                quoted = true;
+                field_start = false;
+                i += 1;
+                continue;
+            }
+
+            match self.match_delimiter(&data[i..]) {
+                Some(Delimiter::Partial) => return None,
+                Some(Delimiter::Record) => {
+                    // As the csv reader, `\r\n` ends the record if it's `\n`.
+                    if self.record_delimiter == b"\n" && field.last() == Some(&b'\r') {
+                        field.pop();
+                    }
+                    nulls.push(null && field == b"N");
+                    record.push_field(&field);
+                    return Some(self.pos + i + self.record_delimiter.len());
+                }
+                Some(Delimiter::Field) => {
+                    nulls.push(null && field == b"N");
+                    record.push_field(&field);
+                    field.clear();
+                    field_start = true;
+                    null = false;
+                    i += self.field_delimiter.len();
+                }
+                None => {
+                    field.push(data[i]);
+                    field_start = false;
+                    i += 1;
+                }
+            }
+        }
+
+        if !self.eof {
+            return None;
+        }
+        nulls.push(null && field == b"N");
+        record.push_field(&field);
+        Some(self.pos + data.len())
+    }
+}
+
+// The escaped characters of MySQL, the others are themselves.
+fn unescape(b: u8) -> u8 {
+    match b {
+        b'0' => b'\0',
+        b'b' => b'\x08',
+        b'n' => b'\n',
+        b'r' => b'\r',
+        b't' => b'\t',
+        b'Z' => b'\x1a',
+        b => b,
+    }
+}
@@ -12,11 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod delimited_reader;
 mod source;
 mod source_csv;
 mod source_ndjson;
 mod source_parquet;
 
+pub use source::RejectedRecord;
 pub use source::Source;
 pub use source_csv::CsvSource;
 pub use source_csv::CsvSourceBuilder;
@@ -24,3 +26,4 @@ pub use source_ndjson::NDJsonSource;
 pub use source_ndjson::NDJsonSourceBuilder;
 pub use source_parquet::ParquetSource;
 pub use source_parquet::ParquetSourceBuilder;
+pub use source_parquet::RowGroupFilter;
@@ -16,7 +16,27 @@ use async_trait::async_trait;
 use common_datablocks::DataBlock;
 use common_exception::Result;
 
+/// A row skipped by the source for failing to parse.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RejectedRecord {
+    /// The number of the row in the source, starts from 0
+    pub row: usize,
+    /// The raw row, empty if it can't be read
+    pub record: String,
+    pub error: String,
+}
+
 #[async_trait]
 pub trait Source: Send {
     async fn read(&mut self) -> Result<Option<DataBlock>>;
+
+    /// The number of the rows skipped for failing to parse, if the source skips them.
+    fn error_rows(&self) -> usize {
+        0
+    }
+
+    /// Takes the rows skipped for failing to parse, if the source keeps them.
+    fn take_rejected_records(&mut self) -> Vec<RejectedRecord> {
+        vec![]
+    }
 }
@@ -17,16 +17,19 @@ use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataType;
 use common_datavalues::TypeDeserializer;
+use common_datavalues::TypeDeserializerImpl;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
 use common_io::prelude::FormatSettings;
 use csv_async::AsyncReader;
 use csv_async::AsyncReaderBuilder;
+use csv_async::ByteRecord;
 use csv_async::Terminator;
-use futures::stream::StreamExt;
 use futures::AsyncRead;
 
+use crate::sources::delimited_reader::DelimitedReader;
+use crate::RejectedRecord;
 use crate::Source;
 
 #[derive(Debug, Clone)]
@@ -36,25 +39,23 @@ pub struct CsvSourceBuilder {
     empty_as_default: bool,
     block_size: usize,
     size_limit: usize,
-    field_delimiter: u8,
-    record_delimiter: Terminator,
+    skip_error_rows: bool,
+    keep_rejected_records: bool,
+    field_delimiter: Vec<u8>,
+    record_delimiter: Vec<u8>,
+    quote: Option<u8>,
+    escape: Option<u8>,
 }
 
 impl CsvSourceBuilder {
     pub fn create(schema: DataSchemaRef, format_settings: FormatSettings) -> Self {
         let field_delimiter = match format_settings.field_delimiter.len() {
-            n if n >= 1 => format_settings.field_delimiter[0],
-            _ => b',',
+            n if n >= 1 => format_settings.field_delimiter.clone(),
+            _ => vec![b','],
         };
         let record_delimiter = match format_settings.record_delimiter.len() {
-            n if n >= 1 => format_settings.record_delimiter[0],
-            _ => b'\n',
-        };
-
-        let record_delimiter = if record_delimiter == b'\n' || record_delimiter == b'\r' {
-            Terminator::CRLF
-        } else {
-            Terminator::Any(record_delimiter)
+            n if n >= 1 => format_settings.record_delimiter.clone(),
+            _ => vec![b'\n'],
         };
 
         let empty_as_default = format_settings.empty_as_default;
@@ -68,9 +69,23 @@ impl CsvSourceBuilder {
             empty_as_default,
             block_size: 10000,
             size_limit: usize::MAX,
+            skip_error_rows: false,
+            keep_rejected_records: false,
+            quote: Some(b'"'),
+            escape: None,
         }
     }
 
+    // The builder of TSV, as the default of MySQL `SELECT ... INTO OUTFILE`: the fields are
+    // separated by `\t`, not enclosed, and the special characters are escaped by `\`.
+    pub fn create_tsv(schema: DataSchemaRef, format_settings: FormatSettings) -> Self {
+        let mut builder = Self::create(schema, format_settings);
+        builder.field_delimiter = vec![b'\t'];
+        builder.quote = None;
+        builder.escape = Some(b'\\');
+        builder
+    }
+
     pub fn block_size(&mut self, block_size: usize) -> &mut Self {
         self.block_size = block_size;
         self
@@ -81,6 +96,19 @@ impl CsvSourceBuilder {
         self
     }
 
+    // Whether to skip the rows which fail to parse instead of returning the error,
+    // the skipped rows are counted by `CsvSource::error_rows`
+    pub fn skip_error_rows(&mut self, skip_error_rows: bool) -> &mut Self {
+        self.skip_error_rows = skip_error_rows;
+        self
+    }
+
+    // Whether to keep the skipped rows, which are taken by `CsvSource::take_rejected_records`
+    pub fn keep_rejected_records(&mut self, keep_rejected_records: bool) -> &mut Self {
+        self.keep_rejected_records = keep_rejected_records;
+        self
+    }
+
     // Whether to skip the header
     pub fn skip_header(&mut self, skip_header: bool) -> &mut Self {
         self.skip_header = skip_header;
@@ -89,60 +117,165 @@ impl CsvSourceBuilder {
 
     pub fn field_delimiter(&mut self, field_delimiter_str: &str) -> &mut Self {
         if !field_delimiter_str.is_empty() {
-            let field_delimiter = match field_delimiter_str.len() {
-                n if n >= 1 => field_delimiter_str.as_bytes()[0],
-                _ => b',',
-            };
-            self.field_delimiter = field_delimiter;
+            self.field_delimiter = field_delimiter_str.as_bytes().to_vec();
         }
         self
     }
 
     pub fn record_delimiter(&mut self, record_delimiter_str: &str) -> &mut Self {
         if !record_delimiter_str.is_empty() {
-            let record_delimiter = match record_delimiter_str.len() {
-                n if n >= 1 => record_delimiter_str.as_bytes()[0],
-                _ => b'\n',
-            };
+            self.record_delimiter = record_delimiter_str.as_bytes().to_vec();
+        }
+        self
+    }
 
-            let record_delimiter = if record_delimiter == b'\n' || record_delimiter == b'\r' {
-                Terminator::CRLF
-            } else {
-                Terminator::Any(record_delimiter)
-            };
-            self.record_delimiter = record_delimiter;
+    // The character enclosing the fields, `NONE` if the fields are not enclosed.
+    pub fn enclosed_by(&mut self, enclosed_by_str: &str) -> &mut Self {
+        if let Some(quote) = parse_character(enclosed_by_str) {
+            self.quote = quote;
         }
         self
     }
 
+    // The character escaping the special characters, `NONE` if nothing is escaped.
+    pub fn escaped_by(&mut self, escaped_by_str: &str) -> &mut Self {
+        if let Some(escape) = parse_character(escaped_by_str) {
+            self.escape = escape;
+        }
+        self
+    }
+
+    // The csv reader supports the delimiters of one byte, and `\r\n` which it takes as `\n`.
+    // It escapes nothing out of the quotes, so the escaped data is read by `DelimitedReader`.
+    fn fits_csv_reader(&self) -> bool {
+        self.field_delimiter.len() == 1
+            && (self.record_delimiter.len() == 1 || self.record_delimiter == b"\r\n")
+            && self.escape.is_none()
+    }
+
     pub fn build<R>(&self, reader: R) -> Result<CsvSource<R>>
     where R: AsyncRead + Unpin + Send {
         CsvSource::try_create(self.clone(), reader)
     }
 }
 
+// None if the option is not given, `NONE` is Some(None).
+fn parse_character(s: &str) -> Option<Option<u8>> {
+    match s {
+        "" => None,
+        s if s.eq_ignore_ascii_case("none") => Some(None),
+        s => Some(s.as_bytes().first().copied()),
+    }
+}
+
+enum RecordReader<R> {
+    Csv(AsyncReader<R>),
+    Delimited(DelimitedReader<R>),
+}
+
 pub struct CsvSource<R> {
     builder: CsvSourceBuilder,
-    reader: AsyncReader<R>,
+    reader: RecordReader<R>,
     rows: usize,
+    error_rows: usize,
+    rejected_records: Vec<RejectedRecord>,
 }
 
 impl<R> CsvSource<R>
 where R: AsyncRead + Unpin + Send
 {
     fn try_create(builder: CsvSourceBuilder, reader: R) -> Result<Self> {
-        let reader = AsyncReaderBuilder::new()
-            .has_headers(builder.skip_header)
-            .delimiter(builder.field_delimiter)
-            .terminator(builder.record_delimiter)
-            .create_reader(reader);
+        let reader = match builder.fits_csv_reader() {
+            true => {
+                let terminator = match builder.record_delimiter[0] {
+                    b'\n' | b'\r' => Terminator::CRLF,
+                    b => Terminator::Any(b),
+                };
+                RecordReader::Csv(
+                    AsyncReaderBuilder::new()
+                        .has_headers(builder.skip_header)
+                        .delimiter(builder.field_delimiter[0])
+                        .terminator(terminator)
+                        .quoting(builder.quote.is_some())
+                        .quote(builder.quote.unwrap_or(b'"'))
+                        .create_reader(reader),
+                )
+            }
+            false => RecordReader::Delimited(DelimitedReader::create(
+                reader,
+                builder.field_delimiter.clone(),
+                builder.record_delimiter.clone(),
+                builder.quote,
+                builder.escape,
+                builder.skip_header,
+            )),
+        };
 
         Ok(Self {
             builder,
             reader,
             rows: 0,
+            error_rows: 0,
+            rejected_records: vec![],
         })
     }
+
+    async fn read_record(&mut self, record: &mut ByteRecord) -> Result<bool> {
+        match &mut self.reader {
+            RecordReader::Csv(reader) => reader
+                .read_byte_record(record)
+                .await
+                .map_err_to_code(ErrorCode::BadBytes, || {
+                    format!("Parse csv error at line {}", self.rows + self.error_rows)
+                }),
+            RecordReader::Delimited(reader) => reader.read_record(record).await,
+        }
+    }
+
+    /// Reads the next record and returns its number of fields, or None at the end of the data.
+    ///
+    /// It infers the schema of the data, so the record is not counted as a read row.
+    pub async fn read_num_fields(&mut self) -> Result<Option<usize>> {
+        let mut record = ByteRecord::new();
+        match self.read_record(&mut record).await? {
+            true if !record.is_empty() => Ok(Some(record.len())),
+            _ => Ok(None),
+        }
+    }
+}
+
+// Appends the record to the columns, a failed record leaves none of its values.
+// The fields of `nulls` are NULL, e.g. `\N` of the escaped data.
+fn append_record(
+    packs: &mut [TypeDeserializerImpl],
+    record: &ByteRecord,
+    nulls: &[bool],
+    empty_as_default: bool,
+) -> Result<()> {
+    let res = packs.iter_mut().enumerate().try_for_each(|(col, pack)| {
+        let res = match record.get(col) {
+            Some(_) if nulls.get(col) == Some(&true) => {
+                if !pack.de_null() {
+                    pack.de_default();
+                }
+                Ok(())
+            }
+            Some(bytes) if !(bytes.is_empty() && empty_as_default) => pack.de_whole_text(bytes),
+            _ => {
+                pack.de_default();
+                Ok(())
+            }
+        };
+        res.map_err(|cause| (col, cause))
+    });
+
+    if let Err((col, cause)) = res {
+        for pack in packs.iter_mut().take(col) {
+            pack.pop_data_value()?;
+        }
+        return Err(cause);
+    }
+    Ok(())
 }
 
 #[async_trait]
@@ -164,28 +297,46 @@ where R: AsyncRead + Unpin + Send
             .collect::<Vec<_>>();
 
         let mut rows = 0;
-        let mut records = self.reader.byte_records();
+        let mut record = ByteRecord::new();
 
-        while let Some(record) = records.next().await {
-            let record = record.map_err_to_code(ErrorCode::BadBytes, || {
-                format!("Parse csv error at line {}", self.rows)
-            })?;
+        loop {
+            let read = self.read_record(&mut record).await;
 
-            if record.is_empty() {
-                break;
-            }
-            for (col, pack) in packs.iter_mut().enumerate() {
-                match record.get(col) {
-                    Some(bytes) => {
-                        if bytes.is_empty() && self.builder.empty_as_default {
-                            pack.de_default();
-                        } else {
-                            pack.de_whole_text(bytes)?
-                        }
-                    }
-                    None => pack.de_default(),
+            let nulls = match &self.reader {
+                RecordReader::Csv(_) => &[][..],
+                RecordReader::Delimited(reader) => reader.nulls(),
+            };
+            let (res, parsed) = match read {
+                Ok(false) => break,
+                Ok(true) if record.is_empty() => break,
+                Ok(true) => {
+                    let empty_as_default = self.builder.empty_as_default;
+                    let res = append_record(&mut packs, &record, nulls, empty_as_default);
+                    (res, true)
+                }
+                Err(cause) => (Err(cause), false),
+            };
+            if let Err(cause) = res {
+                if !self.builder.skip_error_rows {
+                    return Err(cause);
+                }
+                if self.builder.keep_rejected_records {
+                    // The fields are joined back, the quotes of the raw row are not kept.
+                    let delimiter = String::from_utf8_lossy(&self.builder.field_delimiter);
+                    let fields = match parsed {
+                        true => record.iter().map(String::from_utf8_lossy).collect(),
+                        false => vec![],
+                    };
+                    self.rejected_records.push(RejectedRecord {
+                        row: self.rows + self.error_rows,
+                        record: fields.join(delimiter.as_ref()),
+                        error: cause.message(),
+                    });
                 }
+                self.error_rows += 1;
+                continue;
             }
+
             rows += 1;
             self.rows += 1;
 
@@ -211,4 +362,12 @@ where R: AsyncRead + Unpin + Send
 
         Ok(Some(DataBlock::create(self.builder.schema.clone(), series)))
     }
+
+    fn error_rows(&self) -> usize {
+        self.error_rows
+    }
+
+    fn take_rejected_records(&mut self) -> Vec<RejectedRecord> {
+        std::mem::take(&mut self.rejected_records)
+    }
 }
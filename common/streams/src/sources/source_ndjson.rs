@@ -19,12 +19,14 @@ use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataType;
 use common_datavalues::TypeDeserializer;
+use common_datavalues::TypeDeserializerImpl;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
 use futures::AsyncBufRead;
 use futures::AsyncBufReadExt;
 
+use crate::RejectedRecord;
 use crate::Source;
 
 #[derive(Debug, Clone)]
@@ -32,6 +34,8 @@ pub struct NDJsonSourceBuilder {
     schema: DataSchemaRef,
     block_size: usize,
     size_limit: usize,
+    skip_error_rows: bool,
+    keep_rejected_records: bool,
 }
 
 impl NDJsonSourceBuilder {
@@ -40,6 +44,8 @@ impl NDJsonSourceBuilder {
             schema,
             block_size: 10000,
             size_limit: usize::MAX,
+            skip_error_rows: false,
+            keep_rejected_records: false,
         }
     }
 
@@ -53,6 +59,19 @@ impl NDJsonSourceBuilder {
         self
     }
 
+    // Whether to skip the rows which fail to parse instead of returning the error,
+    // the skipped rows are counted by `NDJsonSource::error_rows`
+    pub fn skip_error_rows(&mut self, skip_error_rows: bool) -> &mut Self {
+        self.skip_error_rows = skip_error_rows;
+        self
+    }
+
+    // Whether to keep the skipped rows, which are taken by `NDJsonSource::take_rejected_records`
+    pub fn keep_rejected_records(&mut self, keep_rejected_records: bool) -> &mut Self {
+        self.keep_rejected_records = keep_rejected_records;
+        self
+    }
+
     pub fn build<R>(&self, reader: R) -> Result<NDJsonSource<R>>
     where R: AsyncBufRead + Unpin + Send {
         NDJsonSource::try_create(self.clone(), reader)
@@ -63,6 +82,8 @@ pub struct NDJsonSource<R> {
     builder: NDJsonSourceBuilder,
     reader: R,
     rows: usize,
+    error_rows: usize,
+    rejected_records: Vec<RejectedRecord>,
     buffer: String,
 }
 
@@ -74,6 +95,8 @@ where R: AsyncBufRead + Unpin + Send
             builder,
             reader,
             rows: 0,
+            error_rows: 0,
+            rejected_records: vec![],
             buffer: String::new(),
         })
     }
@@ -92,6 +115,42 @@ fn maybe_truncated(s: &str, limit: usize) -> Cow<'_, str> {
     }
 }
 
+// Appends the json row to the columns, a failed row leaves none of its values.
+fn append_json(
+    packs: &mut [TypeDeserializerImpl],
+    fields: &[(&String, String)],
+    json: &serde_json::Value,
+    row: usize,
+) -> Result<()> {
+    let res = fields
+        .iter()
+        .zip(packs.iter_mut())
+        .enumerate()
+        .try_for_each(|(col, ((name, type_name), deser))| {
+            let value = &json[name];
+            deser.de_json(value).map_err(|e| {
+                let value_str = format!("{:?}", value);
+                let cause = ErrorCode::BadBytes(format!(
+                    "error at row {} column {}: type={}, err={}, value={}",
+                    row,
+                    name,
+                    type_name,
+                    e.message(),
+                    maybe_truncated(&value_str, 1024),
+                ));
+                (col, cause)
+            })
+        });
+
+    if let Err((col, cause)) = res {
+        for pack in packs.iter_mut().take(col) {
+            pack.pop_data_value()?;
+        }
+        return Err(cause);
+    }
+    Ok(())
+}
+
 #[async_trait]
 impl<R> Source for NDJsonSource<R>
 where R: AsyncBufRead + Unpin + Send
@@ -139,21 +198,22 @@ where R: AsyncBufRead + Unpin + Send
                 continue;
             }
 
-            let json: serde_json::Value = serde_json::from_reader(self.buffer.as_bytes())?;
-
-            for ((name, type_name), deser) in fields.iter().zip(packs.iter_mut()) {
-                let value = &json[name];
-                deser.de_json(value).map_err(|e| {
-                    let value_str = format!("{:?}", value);
-                    ErrorCode::BadBytes(format!(
-                        "error at row {} column {}: type={}, err={}, value={}",
-                        rows,
-                        name,
-                        type_name,
-                        e.message(),
-                        maybe_truncated(&value_str, 1024),
-                    ))
-                })?;
+            let res = serde_json::from_reader::<_, serde_json::Value>(self.buffer.as_bytes())
+                .map_err(ErrorCode::from)
+                .and_then(|json| append_json(&mut packs, &fields, &json, rows));
+            if let Err(cause) = res {
+                if !self.builder.skip_error_rows {
+                    return Err(cause);
+                }
+                if self.builder.keep_rejected_records {
+                    self.rejected_records.push(RejectedRecord {
+                        row: self.rows + self.error_rows,
+                        record: self.buffer.trim().to_string(),
+                        error: cause.message(),
+                    });
+                }
+                self.error_rows += 1;
+                continue;
             }
 
             rows += 1;
@@ -181,4 +241,12 @@ where R: AsyncBufRead + Unpin + Send
 
         Ok(Some(DataBlock::create(self.builder.schema.clone(), series)))
     }
+
+    fn error_rows(&self) -> usize {
+        self.error_rows
+    }
+
+    fn take_rejected_records(&mut self) -> Vec<RejectedRecord> {
+        std::mem::take(&mut self.rejected_records)
+    }
 }
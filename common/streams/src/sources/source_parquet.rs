@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -20,6 +21,7 @@ use common_arrow::arrow::datatypes::Schema as ArrowSchema;
 use common_arrow::arrow::io::parquet::read::read_metadata_async;
 use common_arrow::arrow::io::parquet::read::schema::FileMetaData;
 use common_arrow::arrow::io::parquet::read::RowGroupDeserializer;
+use common_arrow::parquet::metadata::RowGroupMetaData;
 use common_arrow::read_columns_many_async;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
@@ -33,12 +35,19 @@ use futures::AsyncSeek;
 
 use crate::Source;
 
+/// Decides by the metadata of a row group, e.g. the statistics of the columns, whether it may
+/// hold the rows wanted. The row groups ruled out are skipped without reading.
+pub trait RowGroupFilter: fmt::Debug + Send + Sync {
+    fn keep(&self, row_group: &RowGroupMetaData) -> Result<bool>;
+}
+
 #[derive(Debug, Clone)]
 pub struct ParquetSourceBuilder {
     schema: DataSchemaRef,
     projection: Vec<usize>,
     size_limit: usize,
     metadata: Option<FileMetaData>,
+    row_group_filter: Option<Arc<dyn RowGroupFilter>>,
 }
 
 impl ParquetSourceBuilder {
@@ -49,6 +58,7 @@ impl ParquetSourceBuilder {
             projection: (0..size).collect(),
             size_limit: usize::MAX,
             metadata: None,
+            row_group_filter: None,
         }
     }
 
@@ -62,6 +72,11 @@ impl ParquetSourceBuilder {
         self
     }
 
+    pub fn row_group_filter(&mut self, row_group_filter: Arc<dyn RowGroupFilter>) -> &mut Self {
+        self.row_group_filter = Some(row_group_filter);
+        self
+    }
+
     pub fn build<R>(&self, reader: R) -> Result<ParquetSource<R>>
     where R: AsyncRead + AsyncSeek + Unpin + Send {
         Ok(ParquetSource::create(self.clone(), reader))
@@ -72,6 +87,8 @@ pub struct ParquetSource<R> {
     reader: R,
     builder: ParquetSourceBuilder,
     current_row_group: usize,
+    // The schema of the columns read, by the projection.
+    projected_schema: DataSchemaRef,
     arrow_table_schema: ArrowSchema,
     rows: usize,
 }
@@ -80,12 +97,13 @@ impl<R> ParquetSource<R>
 where R: AsyncRead + AsyncSeek + Unpin + Send
 {
     fn create(builder: ParquetSourceBuilder, reader: R) -> Self {
-        let arrow_table_schema =
-            Arc::new(builder.schema.project(builder.projection.clone())).to_arrow();
+        let projected_schema = Arc::new(builder.schema.project(builder.projection.clone()));
+        let arrow_table_schema = projected_schema.to_arrow();
 
         ParquetSource {
             reader,
             builder,
+            projected_schema,
             arrow_table_schema,
             current_row_group: 0,
             rows: 0,
@@ -115,20 +133,22 @@ where R: AsyncRead + AsyncSeek + Unpin + Send
             }
         };
 
+        if let Some(filter) = &self.builder.row_group_filter {
+            while self.current_row_group < metadata.row_groups.len()
+                && !filter.keep(&metadata.row_groups[self.current_row_group])?
+            {
+                self.current_row_group += 1;
+            }
+        }
+
         if self.current_row_group >= metadata.row_groups.len() {
             return Ok(None);
         }
 
-        let fields = &self.arrow_table_schema.fields;
-
+        // Only the columns of the projected fields are read, and only the children of the structs
+        // kept by the fields, down to the leaves.
         let row_group = &metadata.row_groups[self.current_row_group];
-        let fields_to_read: Vec<&Field> = self
-            .builder
-            .projection
-            .clone()
-            .into_iter()
-            .map(|idx| &fields[idx])
-            .collect();
+        let fields_to_read: Vec<&Field> = self.arrow_table_schema.fields.iter().collect();
 
         let column_chunks =
             read_columns_many_async(&mut self.reader, row_group, fields_to_read, None)
@@ -144,7 +164,7 @@ where R: AsyncRead + AsyncSeek + Unpin + Send
             Some(chunk) => chunk.map_err(|e| ErrorCode::ParquetError(e.to_string()))?,
         };
 
-        let mut block = DataBlock::from_chunk(&self.builder.schema, &chunk)?;
+        let mut block = DataBlock::from_chunk(&self.projected_schema, &chunk)?;
         self.current_row_group += 1;
         self.rows += block.num_rows();
 
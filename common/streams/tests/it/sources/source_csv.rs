@@ -162,3 +162,175 @@ async fn test_parse_csv_text() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_parse_csv_skip_error_rows() -> Result<()> {
+    let bytes = "1,'Beijing',100\nx,'Shanghai',80\n3,'Guangzhou',60\n4,'Shenzhen',y\n".as_bytes();
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", i8::to_data_type()),
+        DataField::new("b", Vu8::to_data_type()),
+        DataField::new("c", f64::to_data_type()),
+    ]);
+
+    // The error rows fail the read by default.
+    let builder = CsvSourceBuilder::create(schema.clone(), FormatSettings::default());
+    let mut csv_source = builder.build(futures::io::Cursor::new(bytes))?;
+    assert!(csv_source.read().await.is_err());
+
+    let mut builder = CsvSourceBuilder::create(schema, FormatSettings::default());
+    builder.skip_error_rows(true);
+    builder.keep_rejected_records(true);
+    let mut csv_source = builder.build(futures::io::Cursor::new(bytes))?;
+
+    // The values of a row failed in its last column are not kept either.
+    let block = csv_source.read().await?.unwrap();
+    assert_blocks_eq(
+        vec![
+            "+---+-------------+-----+",
+            "| a | b           | c   |",
+            "+---+-------------+-----+",
+            "| 1 | 'Beijing'   | 100 |",
+            "| 3 | 'Guangzhou' | 60  |",
+            "+---+-------------+-----+",
+        ],
+        &[block],
+    );
+    assert!(csv_source.read().await?.is_none());
+    assert_eq!(csv_source.error_rows(), 2);
+
+    let rejected = csv_source
+        .take_rejected_records()
+        .into_iter()
+        .map(|r| (r.row, r.record))
+        .collect::<Vec<_>>();
+    assert_eq!(rejected, vec![
+        (1, "x,'Shanghai',80".to_string()),
+        (3, "4,'Shenzhen',y".to_string()),
+    ]);
+    assert!(csv_source.take_rejected_records().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_parse_csv_multi_byte_delimiter() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", i8::to_data_type()),
+        DataField::new("b", Vu8::to_data_type()),
+        DataField::new("c", f64::to_data_type()),
+    ]);
+
+    // (field delimiter, record delimiter, data)
+    let tests = vec![
+        (
+            "||",
+            "\n",
+            "1||\"1||1\"||1.11\r\n2||2||2\n\n3||\"3\"\"3\"||3",
+        ),
+        ("‖", "$$", "1‖\"1‖1\"‖1.11$$2‖2‖2$$$$3‖\"3\"\"3\"‖3$$"),
+        ("|", "||", "1|\"1|1\"|1.11||2|2|2||3|\"3\"\"3\"|3||"),
+    ];
+
+    for (field_delimiter, record_delimiter, data) in tests {
+        for block_size in [10, 2] {
+            let mut builder = CsvSourceBuilder::create(schema.clone(), FormatSettings::default());
+            builder.field_delimiter(field_delimiter);
+            builder.record_delimiter(record_delimiter);
+            builder.block_size(block_size);
+
+            let mut csv_source = builder.build(futures::io::Cursor::new(data.as_bytes()))?;
+            let mut blocks = vec![];
+            while let Some(block) = csv_source.read().await? {
+                blocks.push(block);
+            }
+
+            let expect_b = format!("1{}1", field_delimiter);
+            let expect = vec![
+                "+---+------+------+".to_string(),
+                "| a | b    | c    |".to_string(),
+                "+---+------+------+".to_string(),
+                format!("| 1 | {:<4} | 1.11 |", expect_b),
+                "| 2 | 2    | 2    |".to_string(),
+                "| 3 | 3\"3  | 3    |".to_string(),
+                "+---+------+------+".to_string(),
+            ];
+            let expect = expect.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+            assert_blocks_eq(expect, &blocks);
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_parse_tsv_escaped() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", i8::to_data_type()),
+        DataField::new_nullable("b", Vu8::to_data_type()),
+        DataField::new("c", Vu8::to_data_type()),
+    ]);
+
+    // As the output of `SELECT ... INTO OUTFILE` of MySQL.
+    let tests =
+        vec![
+        // The default, not enclosed and escaped by `\`.
+        ("", "", "1\t\\N\ta\\tb\n2\tN\ta\\\nb\n3\t\\\\N\t\\\"a\\\"\n"),
+        // FIELDS ENCLOSED BY '"'
+        (
+            "\"",
+            "",
+            "\"1\"\t\\N\t\"a\\tb\"\n\"2\"\t\"N\"\t\"a\\\nb\"\n\"3\"\t\"\\\\N\"\t\"\\\"a\\\"\"\n",
+        ),
+        // FIELDS ENCLOSED BY '"' ESCAPED BY ''
+        ("\"", "NONE", "1\tnull\t\"a\tb\"\n2\tN\t\"a\nb\"\n3\t\\N\t\"\"\"a\"\"\"\n"),
+    ];
+
+    for (enclosed_by, escaped_by, data) in tests {
+        let mut builder = CsvSourceBuilder::create_tsv(schema.clone(), FormatSettings::default());
+        builder.enclosed_by(enclosed_by);
+        builder.escaped_by(escaped_by);
+
+        let mut tsv_source = builder.build(futures::io::Cursor::new(data.as_bytes()))?;
+        let mut blocks = vec![];
+        while let Some(block) = tsv_source.read().await? {
+            blocks.push(block);
+        }
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].num_rows(), 3);
+
+        let b = blocks[0].column(1);
+        assert_eq!(b.get(0), DataValue::Null);
+        assert_eq!(b.get(1), DataValue::String(b"N".to_vec()));
+        assert_eq!(b.get(2), DataValue::String(b"\\N".to_vec()));
+
+        let c = blocks[0].column(2);
+        assert_eq!(c.get(0), DataValue::String(b"a\tb".to_vec()));
+        assert_eq!(c.get(1), DataValue::String(b"a\nb".to_vec()));
+        assert_eq!(c.get(2), DataValue::String(b"\"a\"".to_vec()));
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_csv_read_num_fields() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![]);
+    let tests = vec![
+        (false, "1,\"a,b\",c\n2,d\n", Some(3)),
+        (true, "a,b\n1,2\n", Some(2)),
+        (true, "a,b\n", None),
+        (false, "", None),
+    ];
+
+    for (skip_header, data, expect) in tests {
+        let mut builder = CsvSourceBuilder::create(schema.clone(), FormatSettings::default());
+        builder.skip_header(skip_header);
+
+        let mut csv_source = builder.build(futures::io::Cursor::new(data.as_bytes()))?;
+        assert_eq!(csv_source.read_num_fields().await?, expect, "{:?}", data);
+    }
+
+    Ok(())
+}
@@ -58,3 +58,58 @@ async fn test_source_ndjson() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_source_ndjson_skip_error_rows() -> Result<()> {
+    use common_datavalues::prelude::*;
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", i8::to_data_type()),
+        DataField::new("b", Vu8::to_data_type()),
+    ]);
+
+    let bytes = r#"{"a":1, "b":"1"}
+    {"a":2, "b":"2"
+    {"a":3, "b":"3"}
+    {"a":4, "b":[4]}
+    "#
+    .as_bytes();
+
+    // The error rows fail the read by default.
+    let builder = NDJsonSourceBuilder::create(schema.clone());
+    let mut json_source = builder.build(futures::io::Cursor::new(bytes))?;
+    assert!(json_source.read().await.is_err());
+
+    let mut builder = NDJsonSourceBuilder::create(schema);
+    builder.skip_error_rows(true);
+    builder.keep_rejected_records(true);
+    let mut json_source = builder.build(futures::io::Cursor::new(bytes))?;
+
+    // The values of a row failed in its last column are not kept either.
+    let block = json_source.read().await?.unwrap();
+    assert_blocks_eq(
+        vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | 1 |",
+            "| 3 | 3 |",
+            "+---+---+",
+        ],
+        &[block],
+    );
+    assert!(json_source.read().await?.is_none());
+    assert_eq!(json_source.error_rows(), 2);
+
+    let rejected = json_source
+        .take_rejected_records()
+        .into_iter()
+        .map(|r| (r.row, r.record))
+        .collect::<Vec<_>>();
+    assert_eq!(rejected, vec![
+        (1, r#"{"a":2, "b":"2""#.to_string()),
+        (3, r#"{"a":4, "b":[4]}"#.to_string()),
+    ]);
+
+    Ok(())
+}
@@ -13,13 +13,18 @@
 // limitations under the License.
 
 use std::fs::File;
+use std::sync::Arc;
 
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::parquet::encoding::Encoding;
+use common_arrow::parquet::metadata::RowGroupMetaData;
 use common_base::tokio;
 use common_datablocks::assert_blocks_eq;
 use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_streams::ParquetSourceBuilder;
+use common_streams::RowGroupFilter;
 use common_streams::Source;
 use futures::io::BufReader;
 use opendal::services::fs;
@@ -113,3 +118,118 @@ async fn test_source_parquet() -> Result<()> {
     assert_eq!(page_nums_expects, page_nums);
     Ok(())
 }
+
+#[derive(Debug)]
+struct NumRowsFilter(usize);
+
+impl RowGroupFilter for NumRowsFilter {
+    fn keep(&self, row_group: &RowGroupMetaData) -> Result<bool> {
+        Ok(row_group.num_rows() as usize != self.0)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_source_parquet_row_group_filter() -> Result<()> {
+    use common_arrow::arrow::io::parquet::write::*;
+    use common_datavalues::prelude::*;
+
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", i64::to_data_type())]);
+    let arrow_schema = schema.to_arrow();
+    let options = WriteOptions {
+        write_statistics: true,
+        compression: Compression::Lz4Raw,
+        version: Version::V2,
+    };
+
+    // Three row groups of 1, 2 and 3 rows.
+    let chunks = (1..=3)
+        .map(|n| {
+            let column = Series::from_data(vec![n as i64; n]);
+            Chunk::try_from(DataBlock::create(schema.clone(), vec![column]))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let encodings = vec![Encoding::Plain];
+    let row_groups = RowGroupIterator::try_new(
+        chunks.into_iter().map(Ok),
+        &arrow_schema,
+        options,
+        encodings,
+    )?;
+    let mut data = vec![];
+    common_arrow::write_parquet_file(&mut data, row_groups, arrow_schema, options)
+        .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+
+    let mut builder = ParquetSourceBuilder::create(schema);
+    builder.row_group_filter(Arc::new(NumRowsFilter(2)));
+    let mut parquet_source = builder.build(futures::io::Cursor::new(data))?;
+    let mut blocks = vec![];
+    while let Some(block) = parquet_source.read().await? {
+        blocks.push(block);
+    }
+
+    assert_blocks_eq(
+        vec![
+            "+---+", "| a |", "+---+", "| 1 |", "| 3 |", "| 3 |", "| 3 |", "+---+",
+        ],
+        &blocks,
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_source_parquet_projection() -> Result<()> {
+    use common_arrow::arrow::io::parquet::write::*;
+    use common_datavalues::prelude::*;
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", i8::to_data_type()),
+        DataField::new("b", Vu8::to_data_type()),
+        DataField::new("c", i64::to_data_type()),
+    ]);
+    let arrow_schema = schema.to_arrow();
+    let options = WriteOptions {
+        write_statistics: true,
+        compression: Compression::Lz4Raw,
+        version: Version::V2,
+    };
+
+    let block = DataBlock::create(schema.clone(), vec![
+        Series::from_data(vec![1i8, 2, 3]),
+        Series::from_data(vec!["1", "2", "3"]),
+        Series::from_data(vec![10i64, 20, 30]),
+    ]);
+    let chunks = vec![Chunk::try_from(block)?];
+    let encodings = vec![Encoding::Plain; arrow_schema.fields.len()];
+    let row_groups = RowGroupIterator::try_new(
+        chunks.into_iter().map(Ok),
+        &arrow_schema,
+        options,
+        encodings,
+    )?;
+    let mut data = vec![];
+    common_arrow::write_parquet_file(&mut data, row_groups, arrow_schema, options)
+        .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+
+    // Only the columns projected are read, in the order of the projection.
+    let mut builder = ParquetSourceBuilder::create(schema);
+    builder.projection(vec![2, 1]);
+    let mut parquet_source = builder.build(futures::io::Cursor::new(data))?;
+    let mut blocks = vec![];
+    while let Some(block) = parquet_source.read().await? {
+        blocks.push(block);
+    }
+
+    assert_blocks_eq(
+        vec![
+            "+----+---+",
+            "| c  | b |",
+            "+----+---+",
+            "| 10 | 1 |",
+            "| 20 | 2 |",
+            "| 30 | 3 |",
+            "+----+---+",
+        ],
+        &blocks,
+    );
+    Ok(())
+}
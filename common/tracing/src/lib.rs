@@ -28,6 +28,7 @@ pub use tracing_appender;
 pub use tracing_futures;
 pub use tracing_subscriber;
 pub use tracing_to_jaeger::extract_remote_span_as_parent;
+pub use tracing_to_jaeger::extract_remote_span_from_http_headers;
 pub use tracing_to_jaeger::inject_span_to_tonic_request;
 
 #[macro_export]
@@ -20,6 +20,10 @@ use std::sync::Once;
 use once_cell::sync::Lazy;
 use opentelemetry::global;
 use opentelemetry::sdk::propagation::TraceContextPropagator;
+use opentelemetry::sdk::trace::Tracer;
+use opentelemetry::sdk::Resource;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
 use tracing::Event;
 use tracing::Subscriber;
 use tracing_appender::non_blocking::WorkerGuard;
@@ -42,6 +46,8 @@ use tracing_subscriber::registry::LookupSpan;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::Registry;
 
+const OTEL_EXPORTER_OTLP_ENDPOINT: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
 /// Init tracing for unittest.
 /// Write logs to file `unittest`.
 pub fn init_default_ut_tracing() {
@@ -67,6 +73,10 @@ static GLOBAL_UT_LOG_GUARD: Lazy<Arc<Mutex<Option<Vec<WorkerGuard>>>>> =
 /// To adjust batch sending delay, use `OTEL_BSP_SCHEDULE_DELAY`:
 /// RUST_LOG=trace OTEL_BSP_SCHEDULE_DELAY=1 cargo test
 ///
+/// To report tracing data to an OTLP collector(e.g. Tempo, or a jaeger with OTLP enabled) instead,
+/// assign its grpc address with `OTEL_EXPORTER_OTLP_ENDPOINT`:
+/// OTEL_EXPORTER_OTLP_ENDPOINT=http://127.0.0.1:4317 RUST_LOG=trace cargo test
+///
 // TODO(xp): use DATABEND_JAEGER to assign jaeger server address.
 pub fn init_global_tracing(app_name: &str, dir: &str, level: &str) -> Vec<WorkerGuard> {
     let mut guards = vec![];
@@ -85,12 +95,9 @@ pub fn init_global_tracing(app_name: &str, dir: &str, level: &str) -> Vec<Worker
     let file_logging_layer = BunyanFormattingLayer::new(app_name.to_string(), rolling_writer);
     guards.push(rolling_writer_guard);
 
-    // Jaeger layer.
+    // Jaeger or OTLP layer.
     global::set_text_map_propagator(TraceContextPropagator::new());
-    let tracer = opentelemetry_jaeger::new_pipeline()
-        .with_service_name(app_name)
-        .install_batch(opentelemetry::runtime::Tokio)
-        .expect("install");
+    let tracer = init_tracer(app_name);
     let jaeger_layer = Some(tracing_opentelemetry::layer().with_tracer(tracer));
 
     // Use env RUST_LOG to initialize log if present.
@@ -113,6 +120,27 @@ pub fn init_global_tracing(app_name: &str, dir: &str, level: &str) -> Vec<Worker
     guards
 }
 
+fn init_tracer(app_name: &str) -> Tracer {
+    match env::var(OTEL_EXPORTER_OTLP_ENDPOINT) {
+        Ok(endpoint) => {
+            let exporter = opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint);
+            let resource = Resource::new(vec![KeyValue::new("service.name", app_name.to_string())]);
+            opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(exporter)
+                .with_trace_config(opentelemetry::sdk::trace::config().with_resource(resource))
+                .install_batch(opentelemetry::runtime::Tokio)
+                .expect("install")
+        }
+        Err(_) => opentelemetry_jaeger::new_pipeline()
+            .with_service_name(app_name)
+            .install_batch(opentelemetry::runtime::Tokio)
+            .expect("install"),
+    }
+}
+
 pub fn init_query_logger(
     log_name: &str,
     dir: &str,
@@ -52,6 +52,21 @@ impl<'a> Extractor for MetadataMapExtractor<'a> {
     }
 }
 
+/// Extract tracing info from http request headers.
+struct HeaderMapExtractor<'a>(&'a http::HeaderMap);
+
+impl<'a> Extractor for HeaderMapExtractor<'a> {
+    /// Get a value for a key from the HeaderMap, or None if it is not a valid &str
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    /// Collect all the keys from the HeaderMap.
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect::<Vec<_>>()
+    }
+}
+
 /// Inject current tracing::Span info into tonic request meta
 /// before sending request to a tonic server.
 /// Then the tonic server will be able to chain a distributed tracing.
@@ -87,3 +102,15 @@ pub fn extract_remote_span_as_parent<T>(request: &tonic::Request<T>) {
     let span = tracing::Span::current();
     span.set_parent(parent_cx);
 }
+
+/// Extract tracing context, e.g. the W3C `traceparent` header, from http request headers
+/// and set it as the parent of `span`,
+/// to chain the span of a http client with the span handling its request.
+///
+/// A http request handler should call this before entering `span`.
+pub fn extract_remote_span_from_http_headers(span: &tracing::Span, headers: &http::HeaderMap) {
+    let parent_cx =
+        global::get_text_map_propagator(|prop| prop.extract(&HeaderMapExtractor(headers)));
+
+    span.set_parent(parent_cx);
+}
@@ -77,6 +77,14 @@ impl ActionHandler {
                 let r = self.handle(a).await;
                 RaftReply::from(r)
             }
+            MetaGrpcWriteReq::UpdateTableMeta(a) => {
+                let r = self.handle(a).await;
+                RaftReply::from(r)
+            }
+            MetaGrpcWriteReq::UpdateMultiTableMeta(a) => {
+                let r = self.handle(a).await;
+                RaftReply::from(r)
+            }
 
             // share
             MetaGrpcWriteReq::CreateShare(a) => {
@@ -87,6 +95,22 @@ impl ActionHandler {
                 let r = self.handle(a).await;
                 RaftReply::from(r)
             }
+            MetaGrpcWriteReq::GrantShareObject(a) => {
+                let r = self.handle(a).await;
+                RaftReply::from(r)
+            }
+            MetaGrpcWriteReq::RevokeShareObject(a) => {
+                let r = self.handle(a).await;
+                RaftReply::from(r)
+            }
+            MetaGrpcWriteReq::AddShareAccounts(a) => {
+                let r = self.handle(a).await;
+                RaftReply::from(r)
+            }
+            MetaGrpcWriteReq::RemoveShareAccounts(a) => {
+                let r = self.handle(a).await;
+                RaftReply::from(r)
+            }
         }
     }
 
@@ -17,15 +17,23 @@ use std::sync::Arc;
 
 use common_meta_grpc::GetTableExtReq;
 use common_meta_types::AddResult;
+use common_meta_types::AddShareAccountsReply;
+use common_meta_types::AddShareAccountsReq;
 use common_meta_types::AppError;
 use common_meta_types::Change;
+use common_meta_types::Cmd::AddShareAccounts;
 use common_meta_types::Cmd::CreateDatabase;
 use common_meta_types::Cmd::CreateShare;
 use common_meta_types::Cmd::CreateTable;
 use common_meta_types::Cmd::DropDatabase;
 use common_meta_types::Cmd::DropShare;
 use common_meta_types::Cmd::DropTable;
+use common_meta_types::Cmd::GrantShareObject;
+use common_meta_types::Cmd::RemoveShareAccounts;
 use common_meta_types::Cmd::RenameTable;
+use common_meta_types::Cmd::RevokeShareObject;
+use common_meta_types::Cmd::UpdateMultiTableMeta;
+use common_meta_types::Cmd::UpdateTableMeta;
 use common_meta_types::Cmd::UpsertTableOptions;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateDatabaseReq;
@@ -45,13 +53,20 @@ use common_meta_types::DropTableReq;
 use common_meta_types::GetDatabaseReq;
 use common_meta_types::GetShareReq;
 use common_meta_types::GetTableReq;
+use common_meta_types::GrantShareObjectReply;
+use common_meta_types::GrantShareObjectReq;
 use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
 use common_meta_types::LogEntry;
+use common_meta_types::MatchSeq;
 use common_meta_types::MetaError;
 use common_meta_types::OkOrExist;
+use common_meta_types::RemoveShareAccountsReply;
+use common_meta_types::RemoveShareAccountsReq;
 use common_meta_types::RenameTableReply;
 use common_meta_types::RenameTableReq;
+use common_meta_types::RevokeShareObjectReply;
+use common_meta_types::RevokeShareObjectReq;
 use common_meta_types::ShareAlreadyExists;
 use common_meta_types::ShareInfo;
 use common_meta_types::TableAlreadyExists;
@@ -63,8 +78,13 @@ use common_meta_types::UnknownDatabase;
 use common_meta_types::UnknownShare;
 use common_meta_types::UnknownTable;
 use common_meta_types::UnknownTableId;
+use common_meta_types::UpdateMultiTableMetaReply;
+use common_meta_types::UpdateMultiTableMetaReq;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
+use common_meta_types::WrongShareObject;
 use common_tracing::tracing;
 
 use crate::executor::action_handler::RequestHandler;
@@ -315,6 +335,82 @@ impl RequestHandler<UpsertTableOptionReq> for ActionHandler {
     }
 }
 
+#[async_trait::async_trait]
+impl RequestHandler<UpdateTableMetaReq> for ActionHandler {
+    async fn handle(&self, req: UpdateTableMetaReq) -> Result<UpdateTableMetaReply, MetaError> {
+        let cr = LogEntry {
+            txid: None,
+            cmd: UpdateTableMeta(req.clone()),
+        };
+
+        let res = self.meta_node.write(cr).await?;
+
+        if !res.changed() {
+            let ch: Change<TableMeta> = res
+                .try_into()
+                .map_err(|e: &str| MetaError::MetaServiceError(e.to_string()))?;
+            // safe unwrap: res not changed, so `prev` and `result` are not None.
+            let (prev, _result) = ch.unwrap();
+
+            let ae = AppError::from(TableVersionMismatched::new(
+                req.table_id,
+                req.seq,
+                prev.seq,
+                "RequestHandler: update_table_meta",
+            ));
+
+            return Err(MetaError::from(ae));
+        }
+
+        Ok(UpdateTableMetaReply {})
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<UpdateMultiTableMetaReq> for ActionHandler {
+    async fn handle(
+        &self,
+        req: UpdateMultiTableMetaReq,
+    ) -> Result<UpdateMultiTableMetaReply, MetaError> {
+        if req.update_table_metas.is_empty() {
+            return Ok(UpdateMultiTableMetaReply {});
+        }
+
+        let cr = LogEntry {
+            txid: None,
+            cmd: UpdateMultiTableMeta(req.clone()),
+        };
+
+        let res = self.meta_node.write(cr).await?;
+
+        if !res.changed() {
+            let mut ch: Change<TableMeta> = res
+                .try_into()
+                .map_err(|e: &str| MetaError::MetaServiceError(e.to_string()))?;
+            let table_id = ch.ident.take().expect("Some(table_id)");
+            // safe unwrap: res not changed, so `prev` and `result` are not None.
+            let (prev, _result) = ch.unwrap();
+            let expected_seq = req
+                .update_table_metas
+                .iter()
+                .find(|r| r.table_id == table_id)
+                .map(|r| r.seq)
+                .unwrap_or(MatchSeq::Any);
+
+            let ae = AppError::from(TableVersionMismatched::new(
+                table_id,
+                expected_seq,
+                prev.seq,
+                "RequestHandler: update_multi_table_meta",
+            ));
+
+            return Err(MetaError::from(ae));
+        }
+
+        Ok(UpdateMultiTableMetaReply {})
+    }
+}
+
 #[async_trait::async_trait]
 impl RequestHandler<CreateShareReq> for ActionHandler {
     async fn handle(&self, req: CreateShareReq) -> Result<CreateShareReply, MetaError> {
@@ -381,3 +477,108 @@ impl RequestHandler<GetShareReq> for ActionHandler {
         Ok(res)
     }
 }
+
+#[async_trait::async_trait]
+impl RequestHandler<GrantShareObjectReq> for ActionHandler {
+    async fn handle(&self, req: GrantShareObjectReq) -> Result<GrantShareObjectReply, MetaError> {
+        let cr = LogEntry {
+            txid: None,
+            cmd: GrantShareObject(req.clone()),
+        };
+
+        let res = self.meta_node.write(cr).await?;
+
+        let ch: Change<ShareInfo> = res
+            .try_into()
+            .map_err(|e: &str| MetaError::MetaServiceError(e.to_string()))?;
+        let (_prev, result) = ch.unpack_data();
+
+        match result {
+            None => {
+                let ae = AppError::from(UnknownShare::new(
+                    req.share_name,
+                    "RequestHandler: grant_share_object",
+                ));
+                Err(MetaError::from(ae))
+            }
+            // the object is rejected by the share
+            Some(share_info) if !share_info.meta.has_granted(&req.object) => {
+                let ae = AppError::from(WrongShareObject::new(req.object.to_string()));
+                Err(MetaError::from(ae))
+            }
+            Some(_) => Ok(GrantShareObjectReply {}),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<RevokeShareObjectReq> for ActionHandler {
+    async fn handle(&self, req: RevokeShareObjectReq) -> Result<RevokeShareObjectReply, MetaError> {
+        let share_name = req.share_name.clone();
+        let cr = LogEntry {
+            txid: None,
+            cmd: RevokeShareObject(req),
+        };
+
+        let res = self.meta_node.write(cr).await?;
+
+        if res.result().is_none() {
+            let ae = AppError::from(UnknownShare::new(
+                share_name,
+                "RequestHandler: revoke_share_object",
+            ));
+            return Err(MetaError::from(ae));
+        }
+
+        Ok(RevokeShareObjectReply {})
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<AddShareAccountsReq> for ActionHandler {
+    async fn handle(&self, req: AddShareAccountsReq) -> Result<AddShareAccountsReply, MetaError> {
+        let share_name = req.share_name.clone();
+        let cr = LogEntry {
+            txid: None,
+            cmd: AddShareAccounts(req),
+        };
+
+        let res = self.meta_node.write(cr).await?;
+
+        if res.result().is_none() {
+            let ae = AppError::from(UnknownShare::new(
+                share_name,
+                "RequestHandler: add_share_accounts",
+            ));
+            return Err(MetaError::from(ae));
+        }
+
+        Ok(AddShareAccountsReply {})
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<RemoveShareAccountsReq> for ActionHandler {
+    async fn handle(
+        &self,
+        req: RemoveShareAccountsReq,
+    ) -> Result<RemoveShareAccountsReply, MetaError> {
+        let share_name = req.share_name.clone();
+        let cr = LogEntry {
+            txid: None,
+            cmd: RemoveShareAccounts(req),
+        };
+
+        let res = self.meta_node.write(cr).await?;
+
+        if res.result().is_none() {
+            let ae = AppError::from(UnknownShare::new(
+                share_name,
+                "RequestHandler: remove_share_accounts",
+            ));
+            return Err(MetaError::from(ae));
+        }
+
+        Ok(RemoveShareAccountsReply {})
+    }
+}
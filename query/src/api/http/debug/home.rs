@@ -38,7 +38,9 @@ impl PProfRequest {
 #[poem::handler]
 pub async fn debug_home_handler() -> impl IntoResponse {
     Html(format!(
-        r#"<a href="/debug/pprof/profile?seconds={}">pprof/profile</a>"#,
+        r#"<a href="/debug/pprof/profile?seconds={}">pprof/profile</a><br>
+<a href="/debug/pipeline/profile?seconds={}">pipeline/profile</a>"#,
+        PProfRequest::default_seconds(),
         PProfRequest::default_seconds()
     ))
 }
@@ -0,0 +1,88 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio::time::interval;
+use common_base::tokio::time::Duration;
+use common_tracing::tracing;
+use poem::web::Data;
+use poem::web::IntoResponse;
+use poem::web::Json;
+use poem::web::Query;
+
+use crate::pipelines::new::PipelineSampler;
+use crate::sessions::SessionManager;
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct PipelineProfileRequest {
+    #[serde(default = "PipelineProfileRequest::default_seconds")]
+    pub(crate) seconds: u64,
+    #[serde(default = "PipelineProfileRequest::default_frequency")]
+    pub(crate) frequency: u64,
+    // Only sample the pipelines of the query if set.
+    pub(crate) query_id: Option<String>,
+}
+
+impl PipelineProfileRequest {
+    pub(crate) fn default_seconds() -> u64 {
+        5
+    }
+    pub(crate) fn default_frequency() -> u64 {
+        99
+    }
+}
+
+impl Default for PipelineProfileRequest {
+    fn default() -> Self {
+        PipelineProfileRequest {
+            seconds: Self::default_seconds(),
+            frequency: Self::default_frequency(),
+            query_id: None,
+        }
+    }
+}
+
+// Sample the processors running in the pipelines of the queries, and return a speedscope profile
+// of each query, which can be opened in https://www.speedscope.app as a flamegraph.
+#[poem::handler]
+pub async fn debug_pipeline_profile_handler(
+    sessions_extension: Data<&Arc<SessionManager>>,
+    req: Option<Query<PipelineProfileRequest>>,
+) -> poem::Result<impl IntoResponse> {
+    let req = req.map(|query| query.0).unwrap_or_default();
+    tracing::info!(
+        "start pipeline profile request second: {:?} frequency: {:?} query_id: {:?}",
+        req.seconds,
+        req.frequency,
+        req.query_id
+    );
+
+    let frequency = req.frequency.clamp(1, 1000);
+    let period = Duration::from_secs(1) / frequency as u32;
+    let mut ticker = interval(period);
+    let mut sampler = PipelineSampler::create();
+    for _ in 0..req.seconds * frequency {
+        ticker.tick().await;
+        for (query_id, pipelines) in sessions_extension.0.running_pipelines() {
+            if matches!(&req.query_id, Some(id) if id != &query_id) {
+                continue;
+            }
+            sampler.sample(&query_id, &pipelines, period);
+        }
+    }
+
+    tracing::info!("finished pipeline profile request");
+    Ok(Json(sampler.finish()))
+}
@@ -60,6 +60,10 @@ impl HttpService {
             .at(
                 "/debug/pprof/profile",
                 get(super::http::debug::pprof::debug_pprof_handler),
+            )
+            .at(
+                "/debug/pipeline/profile",
+                get(super::http::debug::pipeline::debug_pipeline_profile_handler),
             );
 
         #[cfg(feature = "memory-profiling")]
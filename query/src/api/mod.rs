@@ -21,6 +21,8 @@ pub use rpc::DatabendQueryFlightDispatcher;
 pub use rpc::DatabendQueryFlightService;
 pub use rpc::FlightAction;
 pub use rpc::FlightClient;
+pub use rpc::FlightDataStream;
+pub use rpc::FlightStream;
 pub use rpc::FlightTicket;
 pub use rpc::ShuffleAction;
 pub use rpc::StreamTicket;
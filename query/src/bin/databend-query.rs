@@ -25,7 +25,9 @@ use databend_query::api::HttpService;
 use databend_query::api::RpcService;
 use databend_query::configs::Config;
 use databend_query::metrics::MetricService;
+use databend_query::pipes::PipeRunner;
 use databend_query::servers::ClickHouseHandler;
+use databend_query::servers::FlightSQLHandler;
 use databend_query::servers::HttpHandler;
 use databend_query::servers::MySQLHandler;
 use databend_query::servers::Server;
@@ -112,6 +114,17 @@ async fn main(_global_tracker: Arc<RuntimeTracker>) -> common_exception::Result<
         );
     }
 
+    // Flight SQL handler.
+    {
+        let hostname = conf.query.flight_sql_handler_host.clone();
+        let listening = format!("{}:{}", hostname, conf.query.flight_sql_handler_port);
+
+        let mut srv = FlightSQLHandler::create(session_manager.clone());
+        let listening = srv.start(listening.parse()?).await?;
+        shutdown_handle.add_service(srv);
+        tracing::info!("Flight SQL handler listening on {}", listening);
+    }
+
     // Metric API service.
     {
         let address = conf.query.metric_api_address.clone();
@@ -151,6 +164,12 @@ async fn main(_global_tracker: Arc<RuntimeTracker>) -> common_exception::Result<
         );
     }
 
+    // Pipe runner.
+    {
+        PipeRunner::create(session_manager.clone()).start();
+        tracing::info!("Pipe runner started.");
+    }
+
     tracing::info!("Ready for connections.");
     shutdown_handle.wait_for_termination_request().await;
     tracing::info!("Shutdown server.");
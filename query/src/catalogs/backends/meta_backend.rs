@@ -17,6 +17,8 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use common_meta_api::MetaApi;
+use common_meta_types::AddShareAccountsReply;
+use common_meta_types::AddShareAccountsReq;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateDatabaseReq;
 use common_meta_types::CreateShareReply;
@@ -33,16 +35,26 @@ use common_meta_types::DropTableReq;
 use common_meta_types::GetDatabaseReq;
 use common_meta_types::GetShareReq;
 use common_meta_types::GetTableReq;
+use common_meta_types::GrantShareObjectReply;
+use common_meta_types::GrantShareObjectReq;
 use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
 use common_meta_types::MetaError;
 use common_meta_types::MetaId;
+use common_meta_types::RemoveShareAccountsReply;
+use common_meta_types::RemoveShareAccountsReq;
 use common_meta_types::RenameTableReply;
 use common_meta_types::RenameTableReq;
+use common_meta_types::RevokeShareObjectReply;
+use common_meta_types::RevokeShareObjectReq;
 use common_meta_types::ShareInfo;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateMultiTableMetaReply;
+use common_meta_types::UpdateMultiTableMetaReq;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
 
@@ -171,6 +183,22 @@ impl MetaApi for MetaBackend {
             .await
     }
 
+    async fn update_table_meta(
+        &self,
+        req: UpdateTableMetaReq,
+    ) -> std::result::Result<UpdateTableMetaReply, MetaError> {
+        self.query_backend(move |cli| async move { cli.update_table_meta(req).await })
+            .await
+    }
+
+    async fn update_multi_table_meta(
+        &self,
+        req: UpdateMultiTableMetaReq,
+    ) -> std::result::Result<UpdateMultiTableMetaReply, MetaError> {
+        self.query_backend(move |cli| async move { cli.update_multi_table_meta(req).await })
+            .await
+    }
+
     async fn create_share(&self, req: CreateShareReq) -> Result<CreateShareReply, MetaError> {
         self.query_backend(move |cli| async move { cli.create_share(req).await })
             .await
@@ -186,6 +214,38 @@ impl MetaApi for MetaBackend {
             .await
     }
 
+    async fn grant_share_object(
+        &self,
+        req: GrantShareObjectReq,
+    ) -> Result<GrantShareObjectReply, MetaError> {
+        self.query_backend(move |cli| async move { cli.grant_share_object(req).await })
+            .await
+    }
+
+    async fn revoke_share_object(
+        &self,
+        req: RevokeShareObjectReq,
+    ) -> Result<RevokeShareObjectReply, MetaError> {
+        self.query_backend(move |cli| async move { cli.revoke_share_object(req).await })
+            .await
+    }
+
+    async fn add_share_accounts(
+        &self,
+        req: AddShareAccountsReq,
+    ) -> Result<AddShareAccountsReply, MetaError> {
+        self.query_backend(move |cli| async move { cli.add_share_accounts(req).await })
+            .await
+    }
+
+    async fn remove_share_accounts(
+        &self,
+        req: RemoveShareAccountsReq,
+    ) -> Result<RemoveShareAccountsReply, MetaError> {
+        self.query_backend(move |cli| async move { cli.remove_share_accounts(req).await })
+            .await
+    }
+
     fn name(&self) -> String {
         "meta-remote".to_owned()
     }
@@ -17,18 +17,36 @@ use std::sync::Arc;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_types::AddShareAccountsReply;
+use common_meta_types::AddShareAccountsReq;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateDatabaseReq;
+use common_meta_types::CreateShareReply;
+use common_meta_types::CreateShareReq;
 use common_meta_types::CreateTableReq;
 use common_meta_types::DropDatabaseReq;
+use common_meta_types::DropShareReply;
+use common_meta_types::DropShareReq;
 use common_meta_types::DropTableReply;
 use common_meta_types::DropTableReq;
+use common_meta_types::GetShareReq;
+use common_meta_types::GrantShareObjectReply;
+use common_meta_types::GrantShareObjectReq;
 use common_meta_types::MetaId;
+use common_meta_types::RemoveShareAccountsReply;
+use common_meta_types::RemoveShareAccountsReq;
 use common_meta_types::RenameTableReply;
 use common_meta_types::RenameTableReq;
+use common_meta_types::RevokeShareObjectReply;
+use common_meta_types::RevokeShareObjectReq;
+use common_meta_types::ShareInfo;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateMultiTableMetaReply;
+use common_meta_types::UpdateMultiTableMetaReq;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
 use common_tracing::tracing;
@@ -324,6 +342,51 @@ impl Catalog for DatabaseCatalog {
         self.mutable_catalog.upsert_table_option(req).await
     }
 
+    async fn update_table_meta(&self, req: UpdateTableMetaReq) -> Result<UpdateTableMetaReply> {
+        self.mutable_catalog.update_table_meta(req).await
+    }
+
+    async fn update_multi_table_meta(
+        &self,
+        req: UpdateMultiTableMetaReq,
+    ) -> Result<UpdateMultiTableMetaReply> {
+        self.mutable_catalog.update_multi_table_meta(req).await
+    }
+
+    async fn create_share(&self, req: CreateShareReq) -> Result<CreateShareReply> {
+        self.mutable_catalog.create_share(req).await
+    }
+
+    async fn drop_share(&self, req: DropShareReq) -> Result<DropShareReply> {
+        self.mutable_catalog.drop_share(req).await
+    }
+
+    async fn get_share(&self, req: GetShareReq) -> Result<Arc<ShareInfo>> {
+        self.mutable_catalog.get_share(req).await
+    }
+
+    async fn grant_share_object(&self, req: GrantShareObjectReq) -> Result<GrantShareObjectReply> {
+        self.mutable_catalog.grant_share_object(req).await
+    }
+
+    async fn revoke_share_object(
+        &self,
+        req: RevokeShareObjectReq,
+    ) -> Result<RevokeShareObjectReply> {
+        self.mutable_catalog.revoke_share_object(req).await
+    }
+
+    async fn add_share_accounts(&self, req: AddShareAccountsReq) -> Result<AddShareAccountsReply> {
+        self.mutable_catalog.add_share_accounts(req).await
+    }
+
+    async fn remove_share_accounts(
+        &self,
+        req: RemoveShareAccountsReq,
+    ) -> Result<RemoveShareAccountsReply> {
+        self.mutable_catalog.remove_share_accounts(req).await
+    }
+
     fn get_table_function(
         &self,
         func_name: &str,
@@ -16,18 +16,36 @@ use std::sync::Arc;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_types::AddShareAccountsReply;
+use common_meta_types::AddShareAccountsReq;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateDatabaseReq;
+use common_meta_types::CreateShareReply;
+use common_meta_types::CreateShareReq;
 use common_meta_types::CreateTableReq;
 use common_meta_types::DropDatabaseReq;
+use common_meta_types::DropShareReply;
+use common_meta_types::DropShareReq;
 use common_meta_types::DropTableReply;
 use common_meta_types::DropTableReq;
+use common_meta_types::GetShareReq;
+use common_meta_types::GrantShareObjectReply;
+use common_meta_types::GrantShareObjectReq;
 use common_meta_types::MetaId;
+use common_meta_types::RemoveShareAccountsReply;
+use common_meta_types::RemoveShareAccountsReq;
 use common_meta_types::RenameTableReply;
 use common_meta_types::RenameTableReq;
+use common_meta_types::RevokeShareObjectReply;
+use common_meta_types::RevokeShareObjectReq;
+use common_meta_types::ShareInfo;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateMultiTableMetaReply;
+use common_meta_types::UpdateMultiTableMetaReq;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
 
@@ -156,4 +174,67 @@ impl Catalog for ImmutableCatalog {
             req
         )))
     }
+
+    async fn update_table_meta(&self, req: UpdateTableMetaReq) -> Result<UpdateTableMetaReply> {
+        Err(ErrorCode::UnImplement(format!(
+            "Update table meta not allowed for system database {:?}",
+            req
+        )))
+    }
+
+    async fn update_multi_table_meta(
+        &self,
+        req: UpdateMultiTableMetaReq,
+    ) -> Result<UpdateMultiTableMetaReply> {
+        Err(ErrorCode::UnImplement(format!(
+            "Update table meta not allowed for system database {:?}",
+            req
+        )))
+    }
+
+    async fn create_share(&self, _req: CreateShareReq) -> Result<CreateShareReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot create share in system catalog",
+        ))
+    }
+
+    async fn drop_share(&self, _req: DropShareReq) -> Result<DropShareReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot drop share in system catalog",
+        ))
+    }
+
+    async fn get_share(&self, _req: GetShareReq) -> Result<Arc<ShareInfo>> {
+        Err(ErrorCode::UnImplement("Cannot get share in system catalog"))
+    }
+
+    async fn grant_share_object(&self, _req: GrantShareObjectReq) -> Result<GrantShareObjectReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot grant share object in system catalog",
+        ))
+    }
+
+    async fn revoke_share_object(
+        &self,
+        _req: RevokeShareObjectReq,
+    ) -> Result<RevokeShareObjectReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot revoke share object in system catalog",
+        ))
+    }
+
+    async fn add_share_accounts(&self, _req: AddShareAccountsReq) -> Result<AddShareAccountsReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot add share accounts in system catalog",
+        ))
+    }
+
+    async fn remove_share_accounts(
+        &self,
+        _req: RemoveShareAccountsReq,
+    ) -> Result<RemoveShareAccountsReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot remove share accounts in system catalog",
+        ))
+    }
 }
@@ -18,26 +18,44 @@ use std::sync::Arc;
 use common_exception::Result;
 use common_meta_api::MetaApi;
 use common_meta_embedded::MetaEmbedded;
+use common_meta_types::AddShareAccountsReply;
+use common_meta_types::AddShareAccountsReq;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateDatabaseReq;
+use common_meta_types::CreateShareReply;
+use common_meta_types::CreateShareReq;
 use common_meta_types::CreateTableReq;
 use common_meta_types::DatabaseIdent;
 use common_meta_types::DatabaseInfo;
 use common_meta_types::DatabaseMeta;
 use common_meta_types::DatabaseNameIdent;
 use common_meta_types::DropDatabaseReq;
+use common_meta_types::DropShareReply;
+use common_meta_types::DropShareReq;
 use common_meta_types::DropTableReply;
 use common_meta_types::DropTableReq;
 use common_meta_types::GetDatabaseReq;
+use common_meta_types::GetShareReq;
 use common_meta_types::GetTableReq;
+use common_meta_types::GrantShareObjectReply;
+use common_meta_types::GrantShareObjectReq;
 use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
 use common_meta_types::MetaId;
+use common_meta_types::RemoveShareAccountsReply;
+use common_meta_types::RemoveShareAccountsReq;
 use common_meta_types::RenameTableReply;
 use common_meta_types::RenameTableReq;
+use common_meta_types::RevokeShareObjectReply;
+use common_meta_types::RevokeShareObjectReq;
+use common_meta_types::ShareInfo;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateMultiTableMetaReply;
+use common_meta_types::UpdateMultiTableMetaReq;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
 use common_tracing::tracing;
@@ -262,6 +280,60 @@ impl Catalog for MutableCatalog {
         Ok(res)
     }
 
+    async fn update_table_meta(&self, req: UpdateTableMetaReq) -> Result<UpdateTableMetaReply> {
+        let res = self.ctx.meta.update_table_meta(req).await?;
+        Ok(res)
+    }
+
+    async fn update_multi_table_meta(
+        &self,
+        req: UpdateMultiTableMetaReq,
+    ) -> Result<UpdateMultiTableMetaReply> {
+        let res = self.ctx.meta.update_multi_table_meta(req).await?;
+        Ok(res)
+    }
+
+    async fn create_share(&self, req: CreateShareReq) -> Result<CreateShareReply> {
+        let res = self.ctx.meta.create_share(req).await?;
+        Ok(res)
+    }
+
+    async fn drop_share(&self, req: DropShareReq) -> Result<DropShareReply> {
+        let res = self.ctx.meta.drop_share(req).await?;
+        Ok(res)
+    }
+
+    async fn get_share(&self, req: GetShareReq) -> Result<Arc<ShareInfo>> {
+        let res = self.ctx.meta.get_share(req).await?;
+        Ok(res)
+    }
+
+    async fn grant_share_object(&self, req: GrantShareObjectReq) -> Result<GrantShareObjectReply> {
+        let res = self.ctx.meta.grant_share_object(req).await?;
+        Ok(res)
+    }
+
+    async fn revoke_share_object(
+        &self,
+        req: RevokeShareObjectReq,
+    ) -> Result<RevokeShareObjectReply> {
+        let res = self.ctx.meta.revoke_share_object(req).await?;
+        Ok(res)
+    }
+
+    async fn add_share_accounts(&self, req: AddShareAccountsReq) -> Result<AddShareAccountsReply> {
+        let res = self.ctx.meta.add_share_accounts(req).await?;
+        Ok(res)
+    }
+
+    async fn remove_share_accounts(
+        &self,
+        req: RemoveShareAccountsReq,
+    ) -> Result<RemoveShareAccountsReply> {
+        let res = self.ctx.meta.remove_share_accounts(req).await?;
+        Ok(res)
+    }
+
     fn get_table_engines(&self) -> Vec<StorageDescription> {
         self.ctx.storage_factory.get_storage_descriptors()
     }
@@ -82,6 +82,15 @@ impl<Key: HashTableKeyable, Entity: HashTableEntity<Key>> HashTable<Key, Entity>
         self.size == 0
     }
 
+    /// The memory in bytes allocated for the entities.
+    pub fn memory_size(&self) -> usize {
+        let entities = match self.zero_entity_raw {
+            None => self.grower.max_size() as usize,
+            Some(_) => self.grower.max_size() as usize + 1,
+        };
+        entities * mem::size_of::<Entity>()
+    }
+
     #[inline(always)]
     pub fn iter(&self) -> HashTableIter<Key, Entity> {
         HashTableIter::create(self.grower.max_size(), self.entities, self.zero_entity)
@@ -34,6 +34,16 @@ pub struct LogConfig {
     #[clap(long = "log-query-enabled")]
     #[serde(alias = "log_query_enabled")]
     pub query_enabled: bool,
+
+    /// Persist the finished queries into the fuse table `system_history.query_history`
+    #[clap(long = "log-query-history-enabled")]
+    #[serde(alias = "log_query_history_enabled")]
+    pub query_history_enabled: bool,
+
+    /// Days to keep the queries in the query history
+    #[clap(long = "log-query-history-retention-days", default_value = "7")]
+    #[serde(alias = "log_query_history_retention_days")]
+    pub query_history_retention_days: u64,
 }
 
 impl Default for LogConfig {
@@ -42,6 +52,8 @@ impl Default for LogConfig {
             level: "INFO".to_string(),
             dir: "./_logs".to_string(),
             query_enabled: false,
+            query_history_enabled: false,
+            query_history_retention_days: 7,
         }
     }
 }
@@ -55,6 +55,12 @@ pub struct QueryConfig {
     #[clap(long, default_value = "10000")]
     pub http_handler_result_timeout_millis: u64,
 
+    #[clap(long, default_value = "127.0.0.1")]
+    pub flight_sql_handler_host: String,
+
+    #[clap(long, default_value = "8900")]
+    pub flight_sql_handler_port: u16,
+
     #[clap(long, default_value = "127.0.0.1:9090")]
     pub flight_api_address: String,
 
@@ -143,7 +149,10 @@ pub struct QueryConfig {
     #[clap(long, default_value = "_cache")]
     pub table_disk_cache_root: String,
 
-    /// Table disk cache size (mb)
+    /// Table disk cache size (mb), 0 to disable the disk cache
+    ///
+    /// If the table cache is enabled, the column chunks of the blocks read by the queries are
+    /// cached on the local disk, under `table_disk_cache_root`.
     #[clap(long, default_value = "1024")]
     pub table_disk_cache_mb_size: u64,
 
@@ -153,6 +162,38 @@ pub struct QueryConfig {
 
     #[clap(long, default_value_t)]
     pub jwt_key_file: String,
+
+    /// The issuer of the OIDC identity provider, empty to disable the OIDC authentication
+    #[clap(long, default_value_t)]
+    pub oidc_issuer: String,
+
+    /// The client id of databend at the OIDC identity provider, the audience of the ID tokens
+    #[clap(long, default_value_t)]
+    pub oidc_client_id: String,
+
+    /// The JWKS url of the OIDC identity provider, discovered from the issuer if empty
+    #[clap(long, default_value_t)]
+    pub oidc_jwks_url: String,
+
+    /// The claim of the ID tokens holding the user name
+    #[clap(long, default_value = "sub")]
+    pub oidc_user_claim: String,
+
+    /// The claim of the ID tokens holding the roles of the user
+    #[clap(long, default_value = "groups")]
+    pub oidc_roles_claim: String,
+
+    /// Create the OIDC users on their first login
+    #[clap(long)]
+    pub oidc_auto_provision: bool,
+
+    /// The file of the 256 bits master key, in hex, encrypting the credentials of the connections
+    #[clap(long, default_value_t)]
+    pub connection_master_key_file: String,
+
+    /// The file of the master key before the rotation, still decrypting the connections
+    #[clap(long, default_value_t)]
+    pub connection_previous_master_key_file: String,
 }
 
 impl Default for QueryConfig {
@@ -169,6 +210,8 @@ impl Default for QueryConfig {
             http_handler_host: "127.0.0.1".to_string(),
             http_handler_port: 8000,
             http_handler_result_timeout_millis: 10000,
+            flight_sql_handler_host: "127.0.0.1".to_string(),
+            flight_sql_handler_port: 8900,
             flight_api_address: "127.0.0.1:9090".to_string(),
             admin_api_address: "127.0.0.1:8080".to_string(),
             metric_api_address: "127.0.0.1:7070".to_string(),
@@ -197,6 +240,14 @@ impl Default for QueryConfig {
             table_disk_cache_mb_size: 1024,
             management_mode: false,
             jwt_key_file: "".to_string(),
+            oidc_issuer: "".to_string(),
+            oidc_client_id: "".to_string(),
+            oidc_jwks_url: "".to_string(),
+            oidc_user_claim: "sub".to_string(),
+            oidc_roles_claim: "groups".to_string(),
+            oidc_auto_provision: false,
+            connection_master_key_file: "".to_string(),
+            connection_previous_master_key_file: "".to_string(),
         }
     }
 }
@@ -33,7 +33,7 @@ pub struct SystemDatabase {
 
 impl SystemDatabase {
     pub fn create(sys_db_meta: &mut InMemoryMetas, config: &Config) -> Self {
-        let table_list: Vec<Arc<dyn Table>> = vec![
+        let mut table_list: Vec<Arc<dyn Table>> = vec![
             system::OneTable::create(sys_db_meta.next_table_id()),
             system::FunctionsTable::create(sys_db_meta.next_table_id()),
             system::ContributorsTable::create(sys_db_meta.next_table_id()),
@@ -55,8 +55,20 @@ impl SystemDatabase {
             )),
             system::EnginesTable::create(sys_db_meta.next_table_id()),
             system::RolesTable::create(sys_db_meta.next_table_id()),
+            system::PipeFilesTable::create(sys_db_meta.next_table_id()),
+            system::PipeErrorsTable::create(sys_db_meta.next_table_id()),
+            system::RowAccessPoliciesTable::create(sys_db_meta.next_table_id()),
+            system::NetworkPoliciesTable::create(sys_db_meta.next_table_id()),
+            system::ConnectionsTable::create(sys_db_meta.next_table_id()),
+            system::TablesUsageTable::create(sys_db_meta.next_table_id()),
         ];
 
+        if config.log.query_history_enabled {
+            table_list.push(system::QueryProfileTable::create(
+                sys_db_meta.next_table_id(),
+            ));
+        }
+
         for tbl in table_list.into_iter() {
             sys_db_meta.insert("system", tbl);
         }
@@ -0,0 +1,175 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_base::tokio::sync::oneshot;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use futures::TryStreamExt;
+
+use crate::sessions::QueryContext;
+use crate::storages::Table;
+
+/// Tenant, database and table of the buffered inserts.
+type BatchKey = (String, String, String);
+
+/// The buffered inserts into a table, which are committed as one snapshot.
+struct Batch {
+    id: u64,
+    /// The context of the first insert of the batch, by which the batch is committed
+    ctx: Arc<QueryContext>,
+    table: Arc<dyn Table>,
+    blocks: Vec<DataBlock>,
+    data_size: usize,
+    /// The inserts waiting for the batch to be committed
+    waiters: Vec<oneshot::Sender<Result<()>>>,
+}
+
+impl Batch {
+    async fn flush(self) {
+        let result = self.commit().await;
+        if let Err(e) = &result {
+            tracing::warn!(
+                "failed to commit {} async inserts into table {}: {}",
+                self.waiters.len(),
+                self.table.name(),
+                e
+            );
+        }
+        for waiter in self.waiters {
+            let _ = waiter.send(result.clone());
+        }
+    }
+
+    async fn commit(&self) -> Result<()> {
+        let block = DataBlock::concat_blocks(&self.blocks)?;
+        let stream: SendableDataBlockStream = Box::pin(futures::stream::iter(vec![Ok(block)]));
+        let operations = self
+            .table
+            .append_data(self.ctx.clone(), stream)
+            .await?
+            .try_collect()
+            .await?;
+        self.table
+            .commit_insertion(self.ctx.clone(), operations, false)
+            .await
+    }
+}
+
+/// Buffers the small inserts into a table and commits them together, so that high-frequency
+/// writers do not create a snapshot and a few tiny files for each of their inserts.
+///
+/// The inserts into a table are committed once their data exceeds `async_insert_max_data_size`
+/// bytes, or `async_insert_busy_timeout` milliseconds after the first one of them is buffered,
+/// by the settings of the first insert.
+#[derive(Default)]
+pub struct AsyncInsertQueue {
+    next_batch_id: AtomicU64,
+    batches: Mutex<HashMap<BatchKey, Batch>>,
+}
+
+impl AsyncInsertQueue {
+    pub fn create() -> Arc<AsyncInsertQueue> {
+        Arc::new(AsyncInsertQueue::default())
+    }
+
+    /// Buffers the block, which is of the schema of the table. Returns the receiver of the
+    /// result of the commit of it.
+    pub fn push(
+        self: &Arc<Self>,
+        ctx: Arc<QueryContext>,
+        database: &str,
+        table: Arc<dyn Table>,
+        block: DataBlock,
+    ) -> Result<oneshot::Receiver<Result<()>>> {
+        let settings = ctx.get_settings();
+        let max_data_size = settings.get_async_insert_max_data_size()? as usize;
+        let busy_timeout = Duration::from_millis(settings.get_async_insert_busy_timeout()?);
+        let key = (
+            ctx.get_tenant(),
+            database.to_owned(),
+            table.name().to_owned(),
+        );
+        let (sender, receiver) = oneshot::channel();
+
+        let mut full = vec![];
+        {
+            let mut batches = self.batches.lock();
+            // the table is altered since the batch is created, the batch is committed first
+            if matches!(batches.get(&key), Some(b) if b.table.schema() != table.schema()) {
+                full.extend(batches.remove(&key));
+            }
+            let batch = batches.entry(key.clone()).or_insert_with(|| {
+                let id = self.next_batch_id.fetch_add(1, Ordering::Relaxed);
+                self.schedule_flush(key.clone(), id, busy_timeout);
+                Batch {
+                    id,
+                    ctx,
+                    table,
+                    blocks: vec![],
+                    data_size: 0,
+                    waiters: vec![],
+                }
+            });
+            batch.data_size += block.memory_size();
+            batch.blocks.push(block);
+            batch.waiters.push(sender);
+            if batch.data_size >= max_data_size {
+                full.extend(batches.remove(&key));
+            }
+        }
+        for batch in full {
+            tokio::spawn(batch.flush());
+        }
+        Ok(receiver)
+    }
+
+    /// Commits all the buffered inserts, e.g. the server is shutting down.
+    pub async fn flush_all(&self) {
+        let batches = {
+            let mut batches = self.batches.lock();
+            batches.drain().map(|(_, batch)| batch).collect::<Vec<_>>()
+        };
+        for batch in batches {
+            batch.flush().await;
+        }
+    }
+
+    /// Commits the batch `id` after `timeout`, unless it is committed before that.
+    fn schedule_flush(self: &Arc<Self>, key: BatchKey, id: u64, timeout: Duration) {
+        let queue = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+            let batch = {
+                let mut batches = queue.batches.lock();
+                match batches.get(&key) {
+                    Some(batch) if batch.id == id => batches.remove(&key),
+                    _ => None,
+                }
+            };
+            if let Some(batch) = batch {
+                batch.flush().await;
+            }
+        });
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UpsertTableOptionReq;
+use common_meta_types::UserPrivilegeType;
+use common_planners::CreateAggregatingIndexPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::FUSE_OPT_KEY_AGGREGATING_INDEX_PREFIX;
+
+pub struct CreateAggregatingIndexInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateAggregatingIndexPlan,
+}
+
+impl CreateAggregatingIndexInterpreter {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        plan: CreateAggregatingIndexPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(CreateAggregatingIndexInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateAggregatingIndexInterpreter {
+    fn name(&self) -> &str {
+        "CreateAggregatingIndexInterpreter"
+    }
+
+    async fn execute(&self, _: Option<SendableDataBlockStream>) -> Result<SendableDataBlockStream> {
+        let db = self.plan.db.as_str();
+        let table = self.plan.table.as_str();
+        self.ctx
+            .get_current_session()
+            .validate_privilege(
+                &GrantObject::Table(db.into(), table.into()),
+                UserPrivilegeType::Alter,
+            )
+            .await?;
+
+        let tbl = self.ctx.get_table(db, table).await?;
+        // the indexes are kept aside the data of fuse tables
+        FuseTable::try_from_table(tbl.as_ref())?;
+
+        let key = format!(
+            "{}{}",
+            FUSE_OPT_KEY_AGGREGATING_INDEX_PREFIX, self.plan.index
+        );
+        if tbl.get_table_info().options().contains_key(&key) {
+            return match self.plan.if_not_exists {
+                true => Ok(Box::pin(DataBlockStream::create(
+                    self.plan.schema(),
+                    None,
+                    vec![],
+                ))),
+                false => Err(ErrorCode::AggregatingIndexAlreadyExists(format!(
+                    "Aggregating index {} already exists on table {}",
+                    self.plan.index, table
+                ))),
+            };
+        }
+
+        let catalog = self.ctx.get_catalog();
+        let definition = serde_json::to_string(&self.plan.definition)?;
+        catalog
+            .upsert_table_option(UpsertTableOptionReq::new(
+                &tbl.get_table_info().ident,
+                &key,
+                definition,
+            ))
+            .await?;
+
+        // the index is built from the current snapshot of the table
+        let tenant = self.ctx.get_tenant();
+        let tbl = catalog.get_table(tenant.as_str(), db, table).await?;
+        FuseTable::try_from_table(tbl.as_ref())?
+            .refresh_aggregating_indexes(&self.ctx)
+            .await?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -16,9 +16,12 @@ use std::sync::Arc;
 
 use common_exception::Result;
 use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeSet;
+use common_meta_types::UserPrivilegeType;
 
 use crate::catalogs::Catalog;
 use crate::sessions::QueryContext;
+use crate::users::UserApiProvider;
 
 pub async fn validate_grant_object_exists(
     ctx: &Arc<QueryContext>,
@@ -50,8 +53,73 @@ pub async fn validate_grant_object_exists(
                 )));
             }
         }
+        GrantObject::Stage(stage_name) => {
+            ctx.get_user_manager()
+                .get_stage(&tenant, stage_name)
+                .await?;
+        }
+        GrantObject::UDF(udf_name) => {
+            ctx.get_user_manager().get_udf(&tenant, udf_name).await?;
+        }
         GrantObject::Global => (),
     }
 
     Ok(())
 }
+
+/// The creator of an object is granted its ownership, which the current session sees at once.
+/// The built-in users are skipped, they are not persisted and have all the privileges anyway.
+pub async fn grant_ownership_to_creator(
+    ctx: &Arc<QueryContext>,
+    object: GrantObject,
+) -> Result<()> {
+    let user = ctx.get_current_user()?;
+    if UserApiProvider::is_builtin_user(&user.name) {
+        return Ok(());
+    }
+
+    let tenant = ctx.get_tenant();
+    let user_mgr = ctx.get_user_manager();
+    let ownership = UserPrivilegeSet::from(vec![UserPrivilegeType::Ownership]);
+    user_mgr
+        .grant_privileges_to_user(&tenant, user.identity(), object, ownership)
+        .await?;
+    let user = user_mgr.get_user(&tenant, user.identity()).await?;
+    ctx.get_current_session().set_current_user(user);
+    Ok(())
+}
+
+/// Revokes the ownership of the object from all the users and roles, e.g. the object is dropped,
+/// or its ownership is transferred. With `cascade`, the ownership of the objects inside it, like
+/// the tables of a dropped database, is revoked too.
+pub async fn revoke_ownership(
+    ctx: &Arc<QueryContext>,
+    object: &GrantObject,
+    cascade: bool,
+) -> Result<()> {
+    let tenant = ctx.get_tenant();
+    let user_mgr = ctx.get_user_manager();
+    let ownership = UserPrivilegeSet::from(vec![UserPrivilegeType::Ownership]);
+    let owned = |objects: Vec<GrantObject>| {
+        objects
+            .into_iter()
+            .filter(|o| cascade || o == object)
+            .collect::<Vec<_>>()
+    };
+
+    for user in user_mgr.get_users(&tenant).await? {
+        for owned_object in owned(user.grants.owned_objects(object)) {
+            user_mgr
+                .revoke_privileges_from_user(&tenant, user.identity(), owned_object, ownership)
+                .await?;
+        }
+    }
+    for role in user_mgr.get_roles(&tenant).await? {
+        for owned_object in owned(role.grants.owned_objects(object)) {
+            user_mgr
+                .revoke_privileges_from_role(&tenant, role.name.clone(), owned_object, ownership)
+                .await?;
+        }
+    }
+    Ok(())
+}
@@ -16,16 +16,24 @@ use std::path::Path;
 use std::sync::Arc;
 
 use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_functions::scalars::CastFunction;
+use common_functions::scalars::FunctionContext;
 use common_io::prelude::S3File;
+use common_meta_types::StageStorage;
+use common_meta_types::StageType;
+use common_meta_types::UserStageInfo;
 use common_planners::CopyPlan;
 use common_planners::ReadDataSourcePlan;
 use common_planners::SourceInfo;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
+use futures::StreamExt;
 use futures::TryStreamExt;
+use opendal::Operator;
 use regex::Regex;
 
 use crate::interpreters::stream::ProcessorExecutorStream;
@@ -33,10 +41,19 @@ use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::pipelines::new::executor::PipelineCompleteExecutor;
 use crate::pipelines::new::executor::PipelinePullingExecutor;
+use crate::pipelines::new::processors::ExpressionTransform;
+use crate::pipelines::new::processors::TransformAddOn;
+use crate::pipelines::new::processors::TransformCastSchema;
 use crate::pipelines::new::NewPipeline;
 use crate::sessions::QueryContext;
 use crate::storages::StageSource;
 
+const MAX_CONCURRENT_PURGE_DELETION: usize = 16;
+
+const PURGE_STATUS_PURGED: &str = "PURGED";
+const PURGE_STATUS_SKIPPED: &str = "SKIPPED";
+const PURGE_STATUS_FAILED: &str = "FAILED";
+
 pub struct CopyInterpreter {
     ctx: Arc<QueryContext>,
     plan: CopyPlan,
@@ -44,7 +61,11 @@ pub struct CopyInterpreter {
 
 impl CopyInterpreter {
     pub fn try_create(ctx: Arc<QueryContext>, plan: CopyPlan) -> Result<InterpreterPtr> {
-        Ok(Arc::new(CopyInterpreter { ctx, plan }))
+        Ok(Arc::new(CopyInterpreter::create(ctx, plan)))
+    }
+
+    pub fn create(ctx: Arc<QueryContext>, plan: CopyPlan) -> CopyInterpreter {
+        CopyInterpreter { ctx, plan }
     }
 
     // List the files.
@@ -92,7 +113,67 @@ impl CopyInterpreter {
         plan
     }
 
-    // Read a file and commit it to the table.
+    // The files are read by the columns inferred from them, if they are transformed.
+    async fn rewrite_read_plan_schema(
+        &self,
+        mut plan: ReadDataSourcePlan,
+        files: &[String],
+    ) -> Result<ReadDataSourcePlan> {
+        if let SourceInfo::S3StageSource(ref mut s3) = plan.source_info {
+            s3.schema = StageSource::infer_schema(&self.ctx, &s3.stage_info, files).await?;
+        }
+        Ok(plan)
+    }
+
+    // Evaluate the transformation over the columns of the files, and cast the results to the
+    // columns to write by position.
+    fn add_transformation(&self, pipeline: &mut NewPipeline, schema: DataSchemaRef) -> Result<()> {
+        let exprs = &self.plan.transformation;
+        let fields = exprs
+            .iter()
+            .map(|expr| expr.to_data_field(&schema))
+            .collect::<Result<Vec<_>>>()?;
+        let transformed_schema = DataSchemaRefExt::create(fields);
+        pipeline.add_transform(|transform_input_port, transform_output_port| {
+            ExpressionTransform::try_create(
+                transform_input_port,
+                transform_output_port,
+                schema.clone(),
+                transformed_schema.clone(),
+                exprs.clone(),
+                self.ctx.clone(),
+            )
+        })?;
+
+        let mut functions = Vec::with_capacity(self.plan.schema.fields().len());
+        for (target_field, transformed_field) in self
+            .plan
+            .schema
+            .fields()
+            .iter()
+            .zip(transformed_schema.fields().iter())
+        {
+            let target_type_name = target_field.data_type().name();
+            let from_type = transformed_field.data_type().clone();
+            functions.push(CastFunction::create("cast", &target_type_name, from_type)?);
+        }
+        let tz = self.ctx.get_settings().get_timezone()?;
+        let tz = String::from_utf8(tz).map_err(|_| {
+            ErrorCode::LogicalError("Timezone has been checked and should be valid.")
+        })?;
+        let func_ctx = FunctionContext { tz };
+        pipeline.add_transform(|transform_input_port, transform_output_port| {
+            TransformCastSchema::try_create(
+                transform_input_port,
+                transform_output_port,
+                self.plan.schema.clone(),
+                functions.clone(),
+                func_ctx.clone(),
+            )
+        })
+    }
+
+    // Read the files and write them to the table, returns the operation log to commit.
     // Progress:
     // 1. Build a select pipeline
     // 2. Execute the pipeline and get the stream
@@ -100,12 +181,23 @@ impl CopyInterpreter {
     // Note:
     //  We parse the `s3://` to ReadSourcePlan instead of to a SELECT plan is that:
     #[tracing::instrument(level = "debug", name = "copy_files_to_table", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
-    async fn copy_files_to_table(&self, files: Vec<String>) -> Result<Vec<DataBlock>> {
+    pub async fn copy_files_to_table(&self, files: Vec<String>) -> Result<Vec<DataBlock>> {
         let ctx = self.ctx.clone();
         let settings = self.ctx.get_settings();
 
+        // Nothing to copy, e.g. none of the files matches the pattern.
+        if files.is_empty() {
+            return Ok(vec![]);
+        }
+
         let mut pipeline = NewPipeline::create();
-        let read_source_plan = self.plan.from.clone();
+        let mut read_source_plan = self.plan.from.clone();
+        let transformed = !self.plan.transformation.is_empty();
+        if transformed {
+            read_source_plan = self
+                .rewrite_read_plan_schema(read_source_plan, &files)
+                .await?;
+        }
         let read_source_plan = Self::rewrite_read_plan_file_name(read_source_plan, files);
         tracing::info!("copy_files_to_table: source plan:{:?}", read_source_plan);
         let table = ctx.build_table_from_source_plan(&read_source_plan)?;
@@ -114,10 +206,28 @@ impl CopyInterpreter {
             return Err(e);
         }
 
+        if transformed {
+            self.add_transformation(&mut pipeline, read_source_plan.schema())?;
+        }
+
         let table = ctx
             .get_table(&self.plan.db_name, &self.plan.tbl_name)
             .await?;
 
+        // the columns which are not copied from the files are filled with their defaults
+        let need_fill_missing_columns = table.schema() != self.plan.schema;
+        if need_fill_missing_columns {
+            pipeline.add_transform(|transform_input_port, transform_output_port| {
+                TransformAddOn::try_create(
+                    transform_input_port,
+                    transform_output_port,
+                    self.plan.schema.clone(),
+                    table.schema(),
+                    ctx.clone(),
+                )
+            })?;
+        }
+
         if ctx.get_settings().get_enable_new_processor_framework()? != 0
             && self.ctx.get_cluster().is_empty()
         {
@@ -145,6 +255,88 @@ impl CopyInterpreter {
 
         Ok(operations)
     }
+
+    // Remove the loaded files from the stage, returns the purge status of each file.
+    // Note:
+    //  This runs after the insertion is committed, so a failed deletion is reported in the
+    //  result instead of failing the COPY. The files are kept if:
+    //  1. SIZE_LIMIT is set, the files may be loaded partially.
+    //  2. ON_ERROR skips the rows failing to parse, the files may be loaded partially.
+    //  3. The stage is external without any credentials, which is read-only.
+    #[tracing::instrument(level = "debug", name = "purge_files", skip(self, files), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn purge_files(&self, files: &[String]) -> Result<DataBlock> {
+        let stage_info = match &self.plan.from.source_info {
+            SourceInfo::S3StageSource(table_info) => &table_info.stage_info,
+            other => {
+                return Err(ErrorCode::LogicalError(format!(
+                    "Cannot purge files for the source info: {:?}",
+                    other
+                )))
+            }
+        };
+
+        let statuses: Vec<(&str, String)> = match Self::purge_skipped_reason(stage_info) {
+            Some(reason) => files
+                .iter()
+                .map(|_| (PURGE_STATUS_SKIPPED, reason.to_string()))
+                .collect(),
+            None => match StageSource::get_op(&self.ctx, stage_info).await {
+                Ok(op) => {
+                    futures::stream::iter(files)
+                        .map(|file| Self::purge_file(&op, file))
+                        .buffered(MAX_CONCURRENT_PURGE_DELETION)
+                        .collect::<Vec<_>>()
+                        .await
+                }
+                Err(e) => files
+                    .iter()
+                    .map(|_| (PURGE_STATUS_FAILED, e.message()))
+                    .collect(),
+            },
+        };
+        tracing::info!("copy purge files:{:?}, statuses:{:?}", files, statuses);
+
+        let (purge_statuses, errors): (Vec<_>, Vec<_>) = statuses.into_iter().unzip();
+        Ok(DataBlock::create(self.plan.schema(), vec![
+            Series::from_data(files.iter().map(|s| s.as_bytes()).collect::<Vec<_>>()),
+            Series::from_data(
+                purge_statuses
+                    .iter()
+                    .map(|s| s.as_bytes())
+                    .collect::<Vec<_>>(),
+            ),
+            Series::from_data(errors.iter().map(|s| s.as_bytes()).collect::<Vec<_>>()),
+        ]))
+    }
+
+    fn purge_skipped_reason(stage_info: &UserStageInfo) -> Option<&'static str> {
+        if stage_info.copy_options.size_limit > 0 {
+            return Some("SIZE_LIMIT is set, the file may be loaded partially");
+        }
+
+        if stage_info.copy_options.on_error.skip_error_rows() {
+            return Some("ON_ERROR may skip the rows of the file");
+        }
+
+        if stage_info.stage_type == StageType::External {
+            match &stage_info.stage_params.storage {
+                StageStorage::S3(s3) => {
+                    if s3.connection.is_empty() && s3.credentials_aws_key_id.is_empty() {
+                        return Some("the external stage without credentials is read-only");
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    async fn purge_file(op: &Operator, file: &str) -> (&'static str, String) {
+        match op.object(file).delete().await {
+            Ok(_) => (PURGE_STATUS_PURGED, "".to_string()),
+            Err(e) => (PURGE_STATUS_FAILED, ErrorCode::from(e).message()),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -180,7 +372,7 @@ impl Interpreter for CopyInterpreter {
 
         tracing::info!("copy file list:{:?}, pattern:{}", &files, pattern,);
 
-        let write_results = self.copy_files_to_table(files).await?;
+        let write_results = self.copy_files_to_table(files.clone()).await?;
 
         let table = self
             .ctx
@@ -192,10 +384,16 @@ impl Interpreter for CopyInterpreter {
             .commit_insertion(self.ctx.clone(), write_results, false)
             .await?;
 
+        // Purge the loaded files, only after the data is committed.
+        let blocks = match self.plan.purge() {
+            true => vec![self.purge_files(&files).await?],
+            false => vec![],
+        };
+
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
             None,
-            vec![],
+            blocks,
         )))
     }
 }
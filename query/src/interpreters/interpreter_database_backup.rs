@@ -0,0 +1,124 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
+use common_planners::BackupDatabasePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::QueryContext;
+use crate::storages::DatabaseBackup;
+use crate::storages::StageSource;
+use crate::storages::TableBackup;
+use crate::storages::BACKUP_FORMAT_VERSION;
+
+pub struct BackupDatabaseInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: BackupDatabasePlan,
+}
+
+impl BackupDatabaseInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: BackupDatabasePlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(BackupDatabaseInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for BackupDatabaseInterpreter {
+    fn name(&self) -> &str {
+        "BackupDatabaseInterpreter"
+    }
+
+    #[tracing::instrument(level = "debug", skip(self, _input_stream), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn execute(
+        &self,
+        _input_stream: Option<SendableDataBlockStream>,
+    ) -> Result<SendableDataBlockStream> {
+        self.ctx
+            .get_current_session()
+            .validate_privilege(&GrantObject::Global, UserPrivilegeType::Super)
+            .await?;
+
+        let plan = &self.plan;
+        let operator = StageSource::get_op(&self.ctx, &plan.stage_info).await?;
+        let dir = plan.path.trim_end_matches('/');
+        if DatabaseBackup::exists(&operator, dir).await? {
+            return Err(ErrorCode::BackupAlreadyExists(format!(
+                "a backup already exists at {}",
+                plan.stage_info.stage_name
+            )));
+        }
+
+        let catalog = self.ctx.get_catalog();
+        let database = catalog.get_database(&plan.tenant, &plan.database).await?;
+        let (mut files, mut bytes) = (0, 0);
+        let mut tables = vec![];
+        for table in catalog.list_tables(&plan.tenant, &plan.database).await? {
+            let table_info = table.get_table_info();
+            let table_id = table_info.ident.table_id;
+            let table_dir = DatabaseBackup::table_dir(dir, table_id);
+            let data = table
+                .backup(self.ctx.clone(), &operator, &table_dir)
+                .await?;
+            if let Some(data) = &data {
+                files += data.files;
+                bytes += data.bytes;
+            }
+            tables.push(TableBackup {
+                name: table.name().to_string(),
+                table_id,
+                meta: table_info.meta.clone(),
+                data,
+            });
+        }
+
+        let users = if plan.with_users {
+            let user_mgr = self.ctx.get_user_manager();
+            user_mgr.get_users(&plan.tenant).await?
+        } else {
+            vec![]
+        };
+
+        // The manifest goes last, a backup without it is incomplete.
+        let backup = DatabaseBackup {
+            format_version: BACKUP_FORMAT_VERSION,
+            database: plan.database.clone(),
+            database_meta: database.get_db_info().meta.clone(),
+            created_on: Utc::now(),
+            tables,
+            users,
+        };
+        backup.write(&operator, dir).await?;
+
+        let schema = plan.schema();
+        let block = DataBlock::create(schema.clone(), vec![
+            Series::from_data(vec![backup.tables.len() as u64]),
+            Series::from_data(vec![files]),
+            Series::from_data(vec![bytes]),
+        ]);
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
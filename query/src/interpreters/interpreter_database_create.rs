@@ -23,6 +23,7 @@ use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 
 use crate::catalogs::Catalog;
+use crate::interpreters::interpreter_common::grant_ownership_to_creator;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
@@ -56,8 +57,17 @@ impl Interpreter for CreateDatabaseInterpreter {
             .await?;
 
         let catalog = self.ctx.get_catalog();
+        let tenant = self.plan.tenant.as_str();
+        let exists =
+            self.plan.if_not_exists && catalog.exists_database(tenant, &self.plan.db).await?;
         catalog.create_database(self.plan.clone().into()).await?;
 
+        // The creator owns the new database.
+        if !exists {
+            let object = GrantObject::Database(self.plan.db.clone());
+            grant_ownership_to_creator(&self.ctx, object).await?;
+        }
+
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
             None,
@@ -22,6 +22,7 @@ use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::Catalog;
+use crate::interpreters::interpreter_common::revoke_ownership;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
@@ -47,14 +48,18 @@ impl Interpreter for DropDatabaseInterpreter {
         &self,
         _input_stream: Option<SendableDataBlockStream>,
     ) -> Result<SendableDataBlockStream> {
+        let object = GrantObject::Database(self.plan.db.clone());
         self.ctx
             .get_current_session()
-            .validate_privilege(&GrantObject::Global, UserPrivilegeType::Drop)
+            .validate_privilege(&object, UserPrivilegeType::Drop)
             .await?;
 
         let catalog = self.ctx.get_catalog();
         catalog.drop_database(self.plan.clone().into()).await?;
 
+        // The ownership of the database and its tables goes away with them.
+        revoke_ownership(&self.ctx, &object, true).await?;
+
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
             None,
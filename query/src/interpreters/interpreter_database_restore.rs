@@ -0,0 +1,149 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_types::CreateDatabaseReq;
+use common_meta_types::CreateTableReq;
+use common_meta_types::DatabaseNameIdent;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
+use common_planners::RestoreDatabasePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::interpreter_common::grant_ownership_to_creator;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::QueryContext;
+use crate::sql::OPT_KEY_CLONED_FROM;
+use crate::sql::OPT_KEY_DATABASE_ID;
+use crate::sql::OPT_KEY_EXTERNAL_LOCATION;
+use crate::sql::OPT_KEY_READ_ONLY_ATTACHED;
+use crate::sql::OPT_KEY_SNAPSHOT_LOC;
+use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::storages::DatabaseBackup;
+use crate::storages::StageSource;
+
+pub struct RestoreDatabaseInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: RestoreDatabasePlan,
+}
+
+impl RestoreDatabaseInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: RestoreDatabasePlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(RestoreDatabaseInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for RestoreDatabaseInterpreter {
+    fn name(&self) -> &str {
+        "RestoreDatabaseInterpreter"
+    }
+
+    #[tracing::instrument(level = "debug", skip(self, _input_stream), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn execute(
+        &self,
+        _input_stream: Option<SendableDataBlockStream>,
+    ) -> Result<SendableDataBlockStream> {
+        self.ctx
+            .get_current_session()
+            .validate_privilege(&GrantObject::Global, UserPrivilegeType::Super)
+            .await?;
+
+        let plan = &self.plan;
+        let operator = StageSource::get_op(&self.ctx, &plan.stage_info).await?;
+        let dir = plan.path.trim_end_matches('/');
+        let backup = DatabaseBackup::read(&operator, dir).await?;
+
+        let catalog = self.ctx.get_catalog();
+        catalog
+            .create_database(CreateDatabaseReq {
+                if_not_exists: false,
+                name_ident: DatabaseNameIdent {
+                    tenant: plan.tenant.clone(),
+                    db_name: plan.database.clone(),
+                },
+                meta: backup.database_meta.clone(),
+            })
+            .await?;
+        let object = GrantObject::Database(plan.database.clone());
+        grant_ownership_to_creator(&self.ctx, object).await?;
+        let database = catalog.get_database(&plan.tenant, &plan.database).await?;
+        let db_id = database.get_db_info().ident.db_id;
+
+        let (mut files, mut bytes) = (0, 0);
+        for table in &backup.tables {
+            // The restored tables are tables of their own, the options binding them to the
+            // data of the tables backed up, or of others, are dropped.
+            let mut meta = table.meta.clone();
+            for key in [
+                OPT_KEY_SNAPSHOT_LOCATION,
+                OPT_KEY_SNAPSHOT_LOC,
+                OPT_KEY_CLONED_FROM,
+                OPT_KEY_READ_ONLY_ATTACHED,
+                OPT_KEY_EXTERNAL_LOCATION,
+            ] {
+                meta.options.remove(key);
+            }
+            if meta.options.contains_key(OPT_KEY_DATABASE_ID) {
+                meta.options
+                    .insert(OPT_KEY_DATABASE_ID.to_owned(), db_id.to_string());
+            }
+            meta.usage = Default::default();
+
+            catalog
+                .create_table(CreateTableReq {
+                    if_not_exists: false,
+                    tenant: plan.tenant.clone(),
+                    db_name: plan.database.clone(),
+                    table_name: table.name.clone(),
+                    table_meta: meta,
+                })
+                .await?;
+
+            if let Some(data) = &table.data {
+                let tbl = catalog
+                    .get_table(&plan.tenant, &plan.database, &table.name)
+                    .await?;
+                let table_dir = DatabaseBackup::table_dir(dir, table.table_id);
+                tbl.restore(self.ctx.clone(), &operator, &table_dir, data)
+                    .await?;
+                files += data.files;
+                bytes += data.bytes;
+            }
+        }
+
+        if plan.with_users {
+            let user_mgr = self.ctx.get_user_manager();
+            for user in backup.users {
+                user_mgr.add_user(&plan.tenant, user, true).await?;
+            }
+        }
+
+        let schema = plan.schema();
+        let block = DataBlock::create(schema.clone(), vec![
+            Series::from_data(vec![backup.tables.len() as u64]),
+            Series::from_data(vec![files]),
+            Series::from_data(vec![bytes]),
+        ]);
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
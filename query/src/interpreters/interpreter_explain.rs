@@ -13,19 +13,28 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::Result;
+use common_io::prelude::convert_byte_size;
 use common_planners::ExplainPlan;
 use common_planners::ExplainType;
+use common_planners::PlanNode;
+use common_planners::SelectPlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
 
 use crate::interpreters::plan_schedulers;
+use crate::interpreters::stream::ProcessorExecutorStream;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::optimizers::Optimizers;
+use crate::pipelines::new::executor::PipelinePullingExecutor;
+use crate::pipelines::new::QueryPipelineBuilder;
 use crate::pipelines::processors::PipelineBuilder;
 use crate::sessions::QueryContext;
 
@@ -50,6 +59,7 @@ impl Interpreter for ExplainInterpreter {
             ExplainType::Graph => self.explain_graph(),
             ExplainType::Syntax => self.explain_syntax(),
             ExplainType::Pipeline => self.explain_pipeline(),
+            ExplainType::Analyze => self.explain_analyze().await,
         }?;
 
         Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
@@ -110,4 +120,77 @@ impl ExplainInterpreter {
         );
         Ok(DataBlock::create(schema, vec![formatted_pipeline]))
     }
+
+    /// Execute the query with the new processor framework, and render the metrics collected by
+    /// the pipes of each plan node next to it.
+    async fn explain_analyze(&self) -> Result<DataBlock> {
+        let schema = self.schema();
+        let optimizer = Optimizers::without_scatters(self.ctx.clone());
+        let plan = plan_schedulers::apply_plan_rewrite(optimizer, &self.explain.input)?;
+
+        let select_plan = SelectPlan {
+            input: Arc::new(plan),
+        };
+        let builder = QueryPipelineBuilder::create(self.ctx.clone());
+        let (mut pipeline, profiles) = builder.finalize_with_profiles(&select_plan)?;
+        let max_threads = self.ctx.get_settings().get_max_threads()? as usize;
+        pipeline.set_max_threads(max_threads);
+
+        let instant = Instant::now();
+        let async_runtime = self.ctx.get_storage_runtime();
+        let executor = PipelinePullingExecutor::try_create(async_runtime, pipeline)?;
+        let executor_stream = Box::pin(ProcessorExecutorStream::create(executor)?);
+        let mut stream = self.ctx.try_create_abortable(executor_stream)?;
+        let (mut rows, mut bytes) = (0, 0);
+        while let Some(block) = stream.next().await {
+            let block = block?;
+            rows += block.num_rows();
+            bytes += block.memory_size();
+        }
+        let elapsed = instant.elapsed();
+
+        let annotator = |node: &PlanNode| {
+            let pipes = profiles.get(node)?;
+            let output = pipes.last()?;
+            let elapsed = pipes.iter().map(|pipe| pipe.elapsed()).sum::<Duration>();
+            let processors = pipes.iter().map(|pipe| pipe.processors).sum::<usize>();
+            Some(format!(
+                "[output_rows: {}, output_bytes: {}, elapsed: {:.3} ms, processors: {}]",
+                output.output_rows(),
+                convert_byte_size(output.output_bytes() as f64),
+                elapsed.as_secs_f64() * 1000f64,
+                processors,
+            ))
+        };
+
+        let scan = self.ctx.get_scan_progress_value();
+        let spill = self.ctx.get_spill_progress_value();
+        let mut lines = format!(
+            "{}",
+            select_plan.input.display_indent_format_with(&annotator)
+        )
+        .lines()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>();
+        lines.push(format!(
+            "Total: [result_rows: {}, result_bytes: {}, elapsed: {:.3} ms]",
+            rows,
+            convert_byte_size(bytes as f64),
+            elapsed.as_secs_f64() * 1000f64,
+        ));
+        lines.push(format!(
+            "Scanned: [rows: {}, bytes: {}]",
+            scan.rows,
+            convert_byte_size(scan.bytes as f64),
+        ));
+        lines.push(format!(
+            "Spilled: [rows: {}, bytes: {}]",
+            spill.rows,
+            convert_byte_size(spill.bytes as f64),
+        ));
+
+        let formatted_plan =
+            Series::from_data(lines.iter().map(|s| s.as_bytes()).collect::<Vec<_>>());
+        Ok(DataBlock::create(schema, vec![formatted_plan]))
+    }
 }
@@ -26,50 +26,82 @@ use super::CreateUserStageInterpreter;
 use super::ListInterpreter;
 use crate::interpreters::interpreter_show_engines::ShowEnginesInterpreter;
 use crate::interpreters::interpreter_table_rename::RenameTableInterpreter;
+use crate::interpreters::AddVirtualColumnInterpreter;
+use crate::interpreters::AlterColumnInterpreter;
+use crate::interpreters::AlterConnectionInterpreter;
+use crate::interpreters::AlterRowAccessPolicyInterpreter;
+use crate::interpreters::AlterShareTenantsInterpreter;
 use crate::interpreters::AlterUserInterpreter;
 use crate::interpreters::AlterUserUDFInterpreter;
+use crate::interpreters::AnalyzeTableInterpreter;
+use crate::interpreters::BackupDatabaseInterpreter;
 use crate::interpreters::CallInterpreter;
 use crate::interpreters::CopyInterpreter;
+use crate::interpreters::CreateAggregatingIndexInterpreter;
+use crate::interpreters::CreateConnectionInterpreter;
 use crate::interpreters::CreateDatabaseInterpreter;
+use crate::interpreters::CreateNetworkPolicyInterpreter;
+use crate::interpreters::CreatePipeInterpreter;
 use crate::interpreters::CreateRoleInterpreter;
+use crate::interpreters::CreateRowAccessPolicyInterpreter;
+use crate::interpreters::CreateShareInterpreter;
+use crate::interpreters::CreateStreamInterpreter;
 use crate::interpreters::CreateTableInterpreter;
 use crate::interpreters::CreateUserInterpreter;
 use crate::interpreters::CreateUserUDFInterpreter;
 use crate::interpreters::CreateViewInterpreter;
+use crate::interpreters::DeleteInterpreter;
 use crate::interpreters::DescribeTableInterpreter;
+use crate::interpreters::DropConnectionInterpreter;
 use crate::interpreters::DropDatabaseInterpreter;
+use crate::interpreters::DropNetworkPolicyInterpreter;
+use crate::interpreters::DropPipeInterpreter;
 use crate::interpreters::DropRoleInterpreter;
+use crate::interpreters::DropRowAccessPolicyInterpreter;
+use crate::interpreters::DropShareInterpreter;
 use crate::interpreters::DropTableInterpreter;
 use crate::interpreters::DropUserInterpreter;
 use crate::interpreters::DropUserUDFInterpreter;
 use crate::interpreters::DropViewInterpreter;
 use crate::interpreters::EmptyInterpreter;
 use crate::interpreters::ExplainInterpreter;
+use crate::interpreters::FlashbackTableInterpreter;
 use crate::interpreters::GrantPrivilegeInterpreter;
 use crate::interpreters::GrantRoleInterpreter;
+use crate::interpreters::GrantShareObjectInterpreter;
 use crate::interpreters::InsertInterpreter;
+use crate::interpreters::InsertMultiTableInterpreter;
 use crate::interpreters::InterceptorInterpreter;
 use crate::interpreters::Interpreter;
 use crate::interpreters::KillInterpreter;
+use crate::interpreters::MergeInterpreter;
 use crate::interpreters::OptimizeTableInterpreter;
+use crate::interpreters::ReclusterTableInterpreter;
+use crate::interpreters::RestoreDatabaseInterpreter;
 use crate::interpreters::RevokePrivilegeInterpreter;
 use crate::interpreters::RevokeRoleInterpreter;
+use crate::interpreters::RevokeShareObjectInterpreter;
 use crate::interpreters::SelectInterpreter;
 use crate::interpreters::SettingInterpreter;
+use crate::interpreters::ShowConnectionsInterpreter;
 use crate::interpreters::ShowCreateDatabaseInterpreter;
 use crate::interpreters::ShowCreateTableInterpreter;
 use crate::interpreters::ShowDatabasesInterpreter;
 use crate::interpreters::ShowFunctionsInterpreter;
 use crate::interpreters::ShowGrantsInterpreter;
 use crate::interpreters::ShowMetricsInterpreter;
+use crate::interpreters::ShowNetworkPoliciesInterpreter;
 use crate::interpreters::ShowProcessListInterpreter;
 use crate::interpreters::ShowRolesInterpreter;
+use crate::interpreters::ShowRowAccessPoliciesInterpreter;
 use crate::interpreters::ShowSettingsInterpreter;
 use crate::interpreters::ShowTabStatInterpreter;
 use crate::interpreters::ShowTablesInterpreter;
 use crate::interpreters::ShowUsersInterpreter;
 use crate::interpreters::TruncateTableInterpreter;
+use crate::interpreters::UpdateInterpreter;
 use crate::interpreters::UseDatabaseInterpreter;
+use crate::interpreters::VacuumTableInterpreter;
 use crate::sessions::QueryContext;
 
 /// InterpreterFactory is the entry of Interpreter.
@@ -84,6 +116,10 @@ impl InterpreterFactory {
             PlanNode::Select(v) => SelectInterpreter::try_create(ctx_clone, v),
             PlanNode::Explain(v) => ExplainInterpreter::try_create(ctx_clone, v),
             PlanNode::Insert(v) => InsertInterpreter::try_create(ctx_clone, v),
+            PlanNode::InsertMultiTable(v) => InsertMultiTableInterpreter::try_create(ctx_clone, v),
+            PlanNode::Delete(v) => DeleteInterpreter::try_create(ctx_clone, v),
+            PlanNode::Update(v) => UpdateInterpreter::try_create(ctx_clone, v),
+            PlanNode::Merge(v) => MergeInterpreter::try_create(ctx_clone, v),
             PlanNode::Copy(v) => CopyInterpreter::try_create(ctx_clone, v),
             PlanNode::Call(v) => CallInterpreter::try_create(ctx_clone, v),
             PlanNode::Show(ShowPlan::ShowDatabases(v)) => {
@@ -119,10 +155,21 @@ impl InterpreterFactory {
             PlanNode::Show(ShowPlan::ShowRoles(v)) => {
                 ShowRolesInterpreter::try_create(ctx_clone, v)
             }
+            PlanNode::Show(ShowPlan::ShowRowAccessPolicies(v)) => {
+                ShowRowAccessPoliciesInterpreter::try_create(ctx_clone, v)
+            }
+            PlanNode::Show(ShowPlan::ShowNetworkPolicies(v)) => {
+                ShowNetworkPoliciesInterpreter::try_create(ctx_clone, v)
+            }
+            PlanNode::Show(ShowPlan::ShowConnections(v)) => {
+                ShowConnectionsInterpreter::try_create(ctx_clone, v)
+            }
 
             // Database related transforms.
             PlanNode::CreateDatabase(v) => CreateDatabaseInterpreter::try_create(ctx_clone, v),
             PlanNode::DropDatabase(v) => DropDatabaseInterpreter::try_create(ctx_clone, v),
+            PlanNode::BackupDatabase(v) => BackupDatabaseInterpreter::try_create(ctx_clone, v),
+            PlanNode::RestoreDatabase(v) => RestoreDatabaseInterpreter::try_create(ctx_clone, v),
             PlanNode::ShowCreateDatabase(v) => {
                 ShowCreateDatabaseInterpreter::try_create(ctx_clone, v)
             }
@@ -133,11 +180,24 @@ impl InterpreterFactory {
             PlanNode::RenameTable(v) => RenameTableInterpreter::try_create(ctx_clone, v),
             PlanNode::TruncateTable(v) => TruncateTableInterpreter::try_create(ctx_clone, v),
             PlanNode::OptimizeTable(v) => OptimizeTableInterpreter::try_create(ctx_clone, v),
+            PlanNode::VacuumTable(v) => VacuumTableInterpreter::try_create(ctx_clone, v),
+            PlanNode::AnalyzeTable(v) => AnalyzeTableInterpreter::try_create(ctx_clone, v),
+            PlanNode::FlashbackTable(v) => FlashbackTableInterpreter::try_create(ctx_clone, v),
+            PlanNode::AddVirtualColumn(v) => AddVirtualColumnInterpreter::try_create(ctx_clone, v),
+            PlanNode::AlterColumn(v) => AlterColumnInterpreter::try_create(ctx_clone, v),
+            PlanNode::AlterRowAccessPolicy(v) => {
+                AlterRowAccessPolicyInterpreter::try_create(ctx_clone, v)
+            }
+            PlanNode::ReclusterTable(v) => ReclusterTableInterpreter::try_create(ctx_clone, v),
             PlanNode::DescribeTable(v) => DescribeTableInterpreter::try_create(ctx_clone, v),
             PlanNode::ShowCreateTable(v) => ShowCreateTableInterpreter::try_create(ctx_clone, v),
 
             // View related transforms
             PlanNode::CreateView(v) => CreateViewInterpreter::try_create(ctx_clone, v),
+            PlanNode::CreateStream(v) => CreateStreamInterpreter::try_create(ctx_clone, v),
+            PlanNode::CreateAggregatingIndex(v) => {
+                CreateAggregatingIndexInterpreter::try_create(ctx_clone, v)
+            }
             PlanNode::AlterView(v) => AlterViewInterpreter::try_create(ctx_clone, v),
             PlanNode::DropView(v) => DropViewInterpreter::try_create(ctx_clone, v),
 
@@ -161,6 +221,17 @@ impl InterpreterFactory {
             PlanNode::DropUserUDF(v) => DropUserUDFInterpreter::try_create(ctx_clone, v),
             PlanNode::AlterUserUDF(v) => AlterUserUDFInterpreter::try_create(ctx_clone, v),
 
+            // Share related transforms
+            PlanNode::CreateShare(v) => CreateShareInterpreter::try_create(ctx_clone, v),
+            PlanNode::DropShare(v) => DropShareInterpreter::try_create(ctx_clone, v),
+            PlanNode::GrantShareObject(v) => GrantShareObjectInterpreter::try_create(ctx_clone, v),
+            PlanNode::RevokeShareObject(v) => {
+                RevokeShareObjectInterpreter::try_create(ctx_clone, v)
+            }
+            PlanNode::AlterShareTenants(v) => {
+                AlterShareTenantsInterpreter::try_create(ctx_clone, v)
+            }
+
             // Stage related transforms
             PlanNode::CreateUserStage(v) => CreateUserStageInterpreter::try_create(ctx_clone, v),
             PlanNode::DropUserStage(v) => DropUserStageInterpreter::try_create(ctx_clone, v),
@@ -168,6 +239,31 @@ impl InterpreterFactory {
                 DescribeUserStageInterpreter::try_create(ctx_clone, v)
             }
 
+            // Pipe related transforms
+            PlanNode::CreatePipe(v) => CreatePipeInterpreter::try_create(ctx_clone, v),
+            PlanNode::DropPipe(v) => DropPipeInterpreter::try_create(ctx_clone, v),
+
+            // Row access policy related transforms
+            PlanNode::CreateRowAccessPolicy(v) => {
+                CreateRowAccessPolicyInterpreter::try_create(ctx_clone, v)
+            }
+            PlanNode::DropRowAccessPolicy(v) => {
+                DropRowAccessPolicyInterpreter::try_create(ctx_clone, v)
+            }
+
+            // Network policy related transforms
+            PlanNode::CreateNetworkPolicy(v) => {
+                CreateNetworkPolicyInterpreter::try_create(ctx_clone, v)
+            }
+            PlanNode::DropNetworkPolicy(v) => {
+                DropNetworkPolicyInterpreter::try_create(ctx_clone, v)
+            }
+
+            // Connection related transforms
+            PlanNode::CreateConnection(v) => CreateConnectionInterpreter::try_create(ctx_clone, v),
+            PlanNode::AlterConnection(v) => AlterConnectionInterpreter::try_create(ctx_clone, v),
+            PlanNode::DropConnection(v) => DropConnectionInterpreter::try_create(ctx_clone, v),
+
             // others
             PlanNode::List(v) => ListInterpreter::try_create(ctx_clone, v),
             PlanNode::UseDatabase(v) => UseDatabaseInterpreter::try_create(ctx_clone, v),
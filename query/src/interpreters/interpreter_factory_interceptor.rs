@@ -27,12 +27,15 @@ use crate::interpreters::InterpreterPtr;
 use crate::interpreters::InterpreterQueryLog;
 use crate::pipelines::new::SourcePipeBuilder;
 use crate::sessions::QueryContext;
+use crate::sessions::QuerySlot;
+use crate::sessions::SessionType;
 
 pub struct InterceptorInterpreter {
     ctx: Arc<QueryContext>,
     inner: InterpreterPtr,
     query_log: InterpreterQueryLog,
     source_pipe_builder: Mutex<Option<SourcePipeBuilder>>,
+    query_slot: Mutex<Option<QuerySlot>>,
 }
 
 impl InterceptorInterpreter {
@@ -42,6 +45,7 @@ impl InterceptorInterpreter {
             inner,
             query_log: InterpreterQueryLog::create(ctx, Some(plan)),
             source_pipe_builder: Mutex::new(None),
+            query_slot: Mutex::new(None),
         }
     }
 }
@@ -75,6 +79,12 @@ impl Interpreter for InterceptorInterpreter {
 
     async fn start(&self) -> Result<()> {
         let session = self.ctx.get_current_session();
+        // The stages of the distributed queries are admitted by the node the query is sent to.
+        if !matches!(session.get_type(), SessionType::FlightRPC) {
+            let query_slot = self.ctx.acquire_query_slot().await?;
+            *self.query_slot.lock() = query_slot;
+        }
+
         let now = SystemTime::now();
         if session.get_type().is_user_session() {
             session
@@ -87,6 +97,9 @@ impl Interpreter for InterceptorInterpreter {
     }
 
     async fn finish(&self) -> Result<()> {
+        // Release the slot of the user for the queued queries.
+        self.query_slot.lock().take();
+
         let session = self.ctx.get_current_session();
         let now = SystemTime::now();
         session.get_status().write().query_finish();
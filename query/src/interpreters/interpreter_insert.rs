@@ -15,6 +15,7 @@
 use std::collections::VecDeque;
 use std::sync::Arc;
 
+use common_datablocks::DataBlock;
 use common_datavalues::DataType;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -25,6 +26,7 @@ use common_meta_types::GrantObject;
 use common_meta_types::UserPrivilegeType;
 use common_planners::InsertInputSource;
 use common_planners::InsertPlan;
+use common_planners::InsertValueBlock;
 use common_planners::PlanNode;
 use common_planners::SelectPlan;
 use common_streams::DataBlockStream;
@@ -168,6 +170,56 @@ impl InsertInterpreter {
         )))
     }
 
+    /// Buffers the values in the async insert queue, which commits them along with the other
+    /// inserts into the table.
+    async fn execute_async(&self, values: &InsertValueBlock) -> Result<SendableDataBlockStream> {
+        let plan = &self.plan;
+        self.ctx
+            .get_current_session()
+            .validate_privilege(
+                &GrantObject::Table(plan.database_name.clone(), plan.table_name.clone()),
+                UserPrivilegeType::Insert,
+            )
+            .await?;
+
+        let table = self
+            .ctx
+            .get_table(&plan.database_name, &plan.table_name)
+            .await?;
+
+        // the buffered blocks are of the schema of the table
+        let stream: SendableDataBlockStream =
+            Box::pin(futures::stream::iter(vec![Ok(values.block.clone())]));
+        let stream: SendableDataBlockStream = if table.schema() != plan.schema() {
+            Box::pin(AddOnStream::try_create(
+                stream,
+                plan.schema(),
+                table.schema(),
+                self.ctx.clone(),
+            )?)
+        } else {
+            stream
+        };
+        let blocks = stream.try_collect::<Vec<_>>().await?;
+        let block = DataBlock::concat_blocks(&blocks)?;
+
+        if block.num_rows() > 0 {
+            let queue = self.ctx.get_async_insert_queue();
+            let committed = queue.push(self.ctx.clone(), &plan.database_name, table, block)?;
+            if self.ctx.get_settings().get_wait_for_async_insert()? != 0 {
+                committed.await.map_err(|_| {
+                    ErrorCode::LogicalError("The async insert is dropped before committed")
+                })??;
+            }
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+
     fn check_schema_cast(&self, plan_node: &PlanNode) -> common_exception::Result<bool> {
         let output_schema = &self.plan.schema;
         let select_schema = plan_node.schema();
@@ -197,6 +249,14 @@ impl Interpreter for InsertInterpreter {
     ) -> Result<SendableDataBlockStream> {
         let settings = self.ctx.get_settings();
 
+        // the inserts of values are buffered if async insert is enabled, except the overwrites,
+        // which replace the data of the table
+        if let InsertInputSource::Values(values) = &self.plan.source {
+            if settings.get_enable_async_insert()? != 0 && !self.plan.overwrite {
+                return self.execute_async(values).await;
+            }
+        }
+
         // Use insert in new processor
         if settings.get_enable_new_processor_framework()? != 0 && self.ctx.get_cluster().is_empty()
         {
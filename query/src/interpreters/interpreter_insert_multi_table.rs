@@ -0,0 +1,255 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio::sync::mpsc;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
+use common_planners::InsertMultiTableInto;
+use common_planners::InsertMultiTablePlan;
+use common_planners::SelectPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use futures::future::try_join;
+use futures::future::try_join_all;
+use futures::TryStreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::interpreters::SelectInterpreter;
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::FuseTable;
+
+pub struct InsertMultiTableInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: InsertMultiTablePlan,
+}
+
+impl InsertMultiTableInterpreter {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        plan: InsertMultiTablePlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(InsertMultiTableInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for InsertMultiTableInterpreter {
+    fn name(&self) -> &str {
+        "InsertMultiTableInterpreter"
+    }
+
+    async fn execute(
+        &self,
+        _input_stream: Option<SendableDataBlockStream>,
+    ) -> Result<SendableDataBlockStream> {
+        let targets = self.plan.targets();
+        let mut tables = Vec::with_capacity(targets.len());
+        for target in &targets {
+            let db_name = target.database_name.as_str();
+            let tbl_name = target.table_name.as_str();
+            self.ctx
+                .get_current_session()
+                .validate_privilege(
+                    &GrantObject::Table(db_name.into(), tbl_name.into()),
+                    UserPrivilegeType::Insert,
+                )
+                .await?;
+
+            // the insertions are committed atomically, which is supported by FUSE tables only
+            let table = self.ctx.get_table(db_name, tbl_name).await?;
+            FuseTable::try_from_table(table.as_ref())?;
+            tables.push(table);
+        }
+        let schemas = tables.iter().map(|t| t.schema()).collect();
+        let router = Router::try_create(&self.ctx, &self.plan, &targets, schemas)?;
+
+        // the privileges of the source are checked by the select of it
+        let select_interpreter = SelectInterpreter::try_create(self.ctx.clone(), SelectPlan {
+            input: Arc::new((*self.plan.source).clone()),
+        })?;
+        let mut source = select_interpreter.execute(None).await?;
+
+        let mut senders = Vec::with_capacity(tables.len());
+        let mut appends = Vec::with_capacity(tables.len());
+        for table in &tables {
+            let (sender, receiver) = mpsc::channel::<Result<DataBlock>>(1);
+            senders.push(sender);
+            let stream: SendableDataBlockStream = Box::pin(ReceiverStream::new(receiver));
+            let ctx = self.ctx.clone();
+            appends.push(async move {
+                let operations = table.append_data(ctx, stream).await?;
+                operations.try_collect::<Vec<_>>().await
+            });
+        }
+
+        // one scan of the source feeds the insertions into all the targets, which end as the
+        // senders are dropped
+        let route = async move {
+            while let Some(block) = source.try_next().await? {
+                for (target, block) in router.route(&block)? {
+                    // the receiver is dropped only if the insertion fails, of which the error
+                    // is returned by the insertion
+                    let _ = senders[target].send(Ok(block)).await;
+                }
+            }
+            Ok::<_, ErrorCode>(())
+        };
+        let (_, operations) = try_join(route, try_join_all(appends)).await?;
+
+        let operations = tables.into_iter().zip(operations).collect();
+        FuseTable::commit_multi_table_insertion(self.ctx.clone(), operations).await?;
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
+
+/// The positions of the evaluated values of a target of a branch.
+struct IntoColumns {
+    /// index of the target in the targets of the plan
+    target: usize,
+    values: Vec<usize>,
+}
+
+/// The positions of the evaluated condition and values of a branch.
+struct BranchColumns {
+    condition: Option<usize>,
+    intos: Vec<IntoColumns>,
+}
+
+/// Evaluates the conditions and the values of all the branches over the source rows at once,
+/// and routes the rows to the targets.
+struct Router {
+    executor: ExpressionExecutor,
+    first: bool,
+    branches: Vec<BranchColumns>,
+    schemas: Vec<DataSchemaRef>,
+}
+
+impl Router {
+    fn try_create(
+        ctx: &Arc<QueryContext>,
+        plan: &InsertMultiTablePlan,
+        targets: &[&InsertMultiTableInto],
+        schemas: Vec<DataSchemaRef>,
+    ) -> Result<Self> {
+        let mut exprs = vec![];
+        let mut branches = Vec::with_capacity(plan.branches.len());
+        for branch in &plan.branches {
+            let condition = branch.condition.as_ref().map(|condition| {
+                exprs.push(condition.clone());
+                exprs.len() - 1
+            });
+            let mut intos = Vec::with_capacity(branch.intos.len());
+            for into in &branch.intos {
+                let target = targets
+                    .iter()
+                    .position(|t| t.table_id == into.table_id)
+                    .ok_or_else(|| {
+                        ErrorCode::LogicalError(format!("Unknown target {}", into.table_name))
+                    })?;
+                let mut values = Vec::with_capacity(into.values.len());
+                for value in &into.values {
+                    values.push(exprs.len());
+                    exprs.push(value.clone());
+                }
+                intos.push(IntoColumns { target, values });
+            }
+            branches.push(BranchColumns { condition, intos });
+        }
+
+        let input_schema = plan.source.schema();
+        let fields = exprs
+            .iter()
+            .map(|expr| expr.to_data_field(&input_schema))
+            .collect::<Result<Vec<_>>>()?;
+        let executor = ExpressionExecutor::try_create(
+            ctx.clone(),
+            "insert multi table executor",
+            input_schema,
+            DataSchemaRefExt::create(fields),
+            exprs,
+            false,
+        )?;
+        Ok(Router {
+            executor,
+            first: plan.first,
+            branches,
+            schemas,
+        })
+    }
+
+    /// Returns the blocks of the rows going to the targets, along with the index of the target
+    /// of each block.
+    fn route(&self, block: &DataBlock) -> Result<Vec<(usize, DataBlock)>> {
+        let mut routed = vec![];
+        if block.num_rows() == 0 {
+            return Ok(routed);
+        }
+
+        let evaluated = self.executor.execute(block)?;
+        // whether a row is matched by the condition of any of the branches, the rows of the
+        // ELSE branch are the ones which are matched by none of them
+        let mut matched = vec![false; block.num_rows()];
+        for branch in &self.branches {
+            let rows = match branch.condition {
+                Some(condition) => {
+                    let predicate = DataBlock::cast_to_nonull_boolean(evaluated.column(condition))?;
+                    let mut rows = vec![];
+                    for (row, matched) in matched.iter_mut().enumerate() {
+                        if predicate.get_bool(row)? {
+                            // INSERT FIRST: a row goes to the first branch it matches only
+                            if !self.first || !*matched {
+                                rows.push(row as u32);
+                            }
+                            *matched = true;
+                        }
+                    }
+                    rows
+                }
+                None => (0..block.num_rows())
+                    .filter(|row| !matched[*row])
+                    .map(|row| row as u32)
+                    .collect::<Vec<_>>(),
+            };
+            if rows.is_empty() {
+                continue;
+            }
+
+            for into in &branch.intos {
+                let columns = into
+                    .values
+                    .iter()
+                    .map(|pos| evaluated.column(*pos).convert_full_column())
+                    .collect::<Vec<_>>();
+                let values = DataBlock::create(self.schemas[into.target].clone(), columns);
+                let values = DataBlock::block_take_by_indices(&values, &rows)?;
+                routed.push((into.target, values));
+            }
+        }
+        Ok(routed)
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
+use common_planners::MergeMatchedAction;
+use common_planners::MergePlan;
+use common_planners::SelectPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use futures::TryStreamExt;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::interpreters::SelectInterpreter;
+use crate::sessions::QueryContext;
+
+pub struct MergeInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: MergePlan,
+}
+
+impl MergeInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: MergePlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(MergeInterpreter { ctx, plan }))
+    }
+
+    /// The privileges required by the actions of the plan.
+    fn required_privileges(&self) -> Vec<UserPrivilegeType> {
+        let mut privileges = vec![];
+        for action in &self.plan.matched {
+            let privilege = match action {
+                MergeMatchedAction::Update { .. } => UserPrivilegeType::Update,
+                MergeMatchedAction::Delete { .. } => UserPrivilegeType::Delete,
+            };
+            if !privileges.contains(&privilege) {
+                privileges.push(privilege);
+            }
+        }
+        if !self.plan.not_matched.is_empty() {
+            privileges.push(UserPrivilegeType::Insert);
+        }
+        privileges
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for MergeInterpreter {
+    fn name(&self) -> &str {
+        "MergeInterpreter"
+    }
+
+    async fn execute(
+        &self,
+        _input_stream: Option<SendableDataBlockStream>,
+    ) -> Result<SendableDataBlockStream> {
+        let db_name = self.plan.database_name.as_str();
+        let tbl_name = self.plan.table_name.as_str();
+
+        for privilege in self.required_privileges() {
+            self.ctx
+                .get_current_session()
+                .validate_privilege(
+                    &GrantObject::Table(db_name.into(), tbl_name.into()),
+                    privilege,
+                )
+                .await?;
+        }
+
+        // the privileges of the source are checked by the select of it
+        let select_interpreter = SelectInterpreter::try_create(self.ctx.clone(), SelectPlan {
+            input: Arc::new((*self.plan.source).clone()),
+        })?;
+        let source = select_interpreter
+            .execute(None)
+            .await?
+            .try_collect()
+            .await?;
+
+        let tbl = self.ctx.get_table(db_name, tbl_name).await?;
+        tbl.merge(self.ctx.clone(), self.plan.clone(), source)
+            .await?;
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::CreatePipePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::FuseTable;
+
+#[derive(Debug)]
+pub struct CreatePipeInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreatePipePlan,
+}
+
+impl CreatePipeInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreatePipePlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(CreatePipeInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreatePipeInterpreter {
+    fn name(&self) -> &str {
+        "CreatePipeInterpreter"
+    }
+
+    #[tracing::instrument(level = "info", skip(self, _input_stream), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn execute(
+        &self,
+        _input_stream: Option<SendableDataBlockStream>,
+    ) -> Result<SendableDataBlockStream> {
+        let plan = self.plan.clone();
+        let pipe_info = plan.pipe_info;
+
+        // The offsets of the pipe are committed along with the fuse snapshots of the table.
+        let table = self
+            .ctx
+            .get_table(&pipe_info.database, &pipe_info.table)
+            .await?;
+        FuseTable::try_from_table(table.as_ref())?;
+
+        let user_mgr = self.ctx.get_user_manager();
+        user_mgr
+            .add_pipe(&plan.tenant, pipe_info, plan.if_not_exists)
+            .await?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
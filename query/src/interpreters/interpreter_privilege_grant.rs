@@ -18,11 +18,13 @@ use common_exception::Result;
 use common_meta_types::GrantObject;
 use common_meta_types::PrincipalIdentity;
 use common_meta_types::UserPrivilegeSet;
+use common_meta_types::UserPrivilegeType;
 use common_planners::GrantPrivilegePlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 
+use crate::interpreters::interpreter_common::revoke_ownership;
 use crate::interpreters::interpreter_common::validate_grant_object_exists;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
@@ -57,7 +59,15 @@ impl Interpreter for GrantPrivilegeInterpreter {
         validate_grant_object_exists(&self.ctx, &plan.on).await?;
 
         // TODO: check user existence
-        // TODO: check privilege on granting on the grant object
+        self.ctx
+            .get_current_session()
+            .validate_privilege(&plan.on, UserPrivilegeType::Grant)
+            .await?;
+
+        // An object has only one owner, the ownership is transferred from the current owner.
+        if plan.priv_types.has_privilege(UserPrivilegeType::Ownership) {
+            revoke_ownership(&self.ctx, &plan.on, false).await?;
+        }
 
         let tenant = self.ctx.get_tenant();
         let user_mgr = self.ctx.get_user_manager();
@@ -85,7 +95,17 @@ impl Interpreter for GrantPrivilegeInterpreter {
 /// Check if there's any privilege which can not be granted to this GrantObject.
 /// Some global privileges can not be granted to a database or table, for example,
 /// a KILL statement is meaningless for a table.
+/// The OWNERSHIP of a database, table, stage or UDF is granted alone.
 pub fn validate_grant_privileges(object: &GrantObject, privileges: UserPrivilegeSet) -> Result<()> {
+    if privileges.has_privilege(UserPrivilegeType::Ownership) {
+        if privileges.iter().count() > 1 || object == &GrantObject::Global {
+            return Err(common_exception::ErrorCode::IllegalGrant(
+                "Illegal GRANT/REVOKE command; the OWNERSHIP of an object is granted alone",
+            ));
+        }
+        return Ok(());
+    }
+
     let available_privileges = object.available_privileges();
     let ok = privileges
         .iter()
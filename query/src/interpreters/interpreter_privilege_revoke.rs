@@ -16,6 +16,7 @@ use std::sync::Arc;
 
 use common_exception::Result;
 use common_meta_types::PrincipalIdentity;
+use common_meta_types::UserPrivilegeType;
 use common_planners::RevokePrivilegePlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
@@ -54,7 +55,10 @@ impl Interpreter for RevokePrivilegeInterpreter {
         validate_grant_object_exists(&self.ctx, &plan.on).await?;
 
         // TODO: check user existence
-        // TODO: check privilege on granting on the grant object
+        self.ctx
+            .get_current_session()
+            .validate_privilege(&plan.on, UserPrivilegeType::Grant)
+            .await?;
 
         let tenant = self.ctx.get_tenant();
         let user_mgr = self.ctx.get_user_manager();
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
@@ -21,12 +22,14 @@ use common_datavalues::prelude::Series;
 use common_datavalues::prelude::SeriesFrom;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_infallible::Mutex;
 use common_planners::PlanNode;
 use common_tracing::tracing;
 use serde::Serialize;
 use serde_json;
 
 use crate::sessions::QueryContext;
+use crate::sessions::SessionType;
 
 #[derive(Clone, Copy, Serialize)]
 pub enum LogType {
@@ -104,6 +107,7 @@ pub struct LogEvent {
 pub struct InterpreterQueryLog {
     ctx: Arc<QueryContext>,
     plan: Option<PlanNode>,
+    started_at: Arc<Mutex<Option<SystemTime>>>,
 }
 
 fn error_fields(log_type: LogType, err: Option<ErrorCode>) -> (LogType, i32, String, String) {
@@ -131,7 +135,11 @@ fn error_fields(log_type: LogType, err: Option<ErrorCode>) -> (LogType, i32, Str
 
 impl InterpreterQueryLog {
     pub fn create(ctx: Arc<QueryContext>, plan: Option<PlanNode>) -> Self {
-        InterpreterQueryLog { ctx, plan }
+        InterpreterQueryLog {
+            ctx,
+            plan,
+            started_at: Arc::new(Mutex::new(None)),
+        }
     }
 
     async fn write_log(&self, event: &LogEvent) -> Result<()> {
@@ -217,6 +225,8 @@ impl InterpreterQueryLog {
     }
 
     pub async fn log_start(&self, now: SystemTime, err: Option<ErrorCode>) -> Result<()> {
+        *self.started_at.lock() = Some(now);
+
         // User.
         let handler_type = self.ctx.get_current_session().get_type().to_string();
         let tenant_id = self.ctx.get_tenant();
@@ -440,6 +450,30 @@ impl InterpreterQueryLog {
             extra: "".to_string(),
         };
 
-        self.write_log(&log_event).await
+        self.write_log(&log_event).await?;
+        self.record_history(&log_event, now).await;
+        Ok(())
+    }
+
+    /// Persists the finished query into the query history, if enabled. The queries of the
+    /// internal sessions are not recorded, and a failure never fails the query itself.
+    async fn record_history(&self, event: &LogEvent, now: SystemTime) {
+        if !self.ctx.get_config().log.query_history_enabled {
+            return;
+        }
+        let session_type = self.ctx.get_current_session().get_type();
+        if !session_type.is_user_session() || matches!(session_type, SessionType::FlightRPC) {
+            return;
+        }
+
+        let started_at = *self.started_at.lock();
+        let duration = started_at
+            .and_then(|started_at| now.duration_since(started_at).ok())
+            .unwrap_or(Duration::ZERO);
+        self.ctx
+            .get_query_history()
+            .record(self.ctx.clone(), event, duration)
+            .await
+            .unwrap_or_else(|e| tracing::warn!("fail to record query history {:?}", e));
     }
 }
@@ -12,10 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_types::GrantObject;
 use common_meta_types::PrincipalIdentity;
+use common_meta_types::UserPrivilegeType;
 use common_planners::GrantRolePlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
@@ -24,6 +28,7 @@ use common_tracing::tracing;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
+use crate::users::role_cache_mgr::find_all_related_roles;
 
 #[derive(Debug)]
 pub struct GrantRoleInterpreter {
@@ -52,7 +57,10 @@ impl Interpreter for GrantRoleInterpreter {
         let tenant = self.ctx.get_tenant();
         let user_mgr = self.ctx.get_user_manager();
 
-        // TODO: check privileges
+        self.ctx
+            .get_current_session()
+            .validate_privilege(&GrantObject::Global, UserPrivilegeType::Grant)
+            .await?;
 
         // Check if the grant role exists.
         user_mgr.get_role(&tenant, plan.role.clone()).await?;
@@ -63,6 +71,20 @@ impl Interpreter for GrantRoleInterpreter {
                     .await?;
             }
             PrincipalIdentity::Role(role) => {
+                // The role hierarchy is acyclic, the granted role can not include the grantee.
+                let roles = user_mgr
+                    .get_roles(&tenant)
+                    .await?
+                    .into_iter()
+                    .map(|r| (r.identity(), r))
+                    .collect::<HashMap<_, _>>();
+                let related_roles = find_all_related_roles(&roles, &[plan.role.clone()]);
+                if related_roles.iter().any(|r| r.name == role) {
+                    return Err(ErrorCode::IllegalGrant(format!(
+                        "Illegal GRANT ROLE command; role '{}' is granted to role '{}'",
+                        role, plan.role
+                    )));
+                }
                 user_mgr
                     .grant_role_to_role(&tenant, role, plan.role)
                     .await?;
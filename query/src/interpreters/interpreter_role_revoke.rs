@@ -15,7 +15,9 @@
 use std::sync::Arc;
 
 use common_exception::Result;
+use common_meta_types::GrantObject;
 use common_meta_types::PrincipalIdentity;
+use common_meta_types::UserPrivilegeType;
 use common_planners::RevokeRolePlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
@@ -48,6 +50,11 @@ impl Interpreter for RevokeRoleInterpreter {
         &self,
         _input_stream: Option<SendableDataBlockStream>,
     ) -> Result<SendableDataBlockStream> {
+        self.ctx
+            .get_current_session()
+            .validate_privilege(&GrantObject::Global, UserPrivilegeType::Grant)
+            .await?;
+
         let plan = self.plan.clone();
         let tenant = self.ctx.get_tenant();
         let user_mgr = self.ctx.get_user_manager();
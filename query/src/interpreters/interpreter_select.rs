@@ -18,6 +18,7 @@ use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_planners::PlanNode;
 use common_planners::SelectPlan;
+use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 
@@ -25,6 +26,7 @@ use crate::interpreters::plan_schedulers;
 use crate::interpreters::stream::ProcessorExecutorStream;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
+use crate::interpreters::QueryResultCache;
 use crate::optimizers::Optimizers;
 use crate::pipelines::new::executor::PipelinePullingExecutor;
 use crate::pipelines::new::NewPipeline;
@@ -50,6 +52,32 @@ impl SelectInterpreter {
             &self.select.input,
         )
     }
+
+    fn build_new_pipeline(&self, optimized_plan: PlanNode) -> Result<NewPipeline> {
+        let settings = self.ctx.get_settings();
+        let builder = QueryPipelineBuilder::create(self.ctx.clone());
+
+        let select_plan = SelectPlan {
+            input: Arc::new(optimized_plan),
+        };
+        let mut new_pipeline = builder.finalize(&select_plan)?;
+        new_pipeline.set_max_threads(settings.get_max_threads()? as usize);
+        Ok(new_pipeline)
+    }
+
+    async fn execute_plan(&self, optimized_plan: PlanNode) -> Result<SendableDataBlockStream> {
+        let settings = self.ctx.get_settings();
+
+        if settings.get_enable_new_processor_framework()? != 0 && self.ctx.get_cluster().is_empty()
+        {
+            let async_runtime = self.ctx.get_storage_runtime();
+            let new_pipeline = self.build_new_pipeline(optimized_plan)?;
+            let executor = PipelinePullingExecutor::try_create(async_runtime, new_pipeline)?;
+            let executor_stream = Box::pin(ProcessorExecutorStream::create(executor)?);
+            return Ok(Box::pin(self.ctx.try_create_abortable(executor_stream)?));
+        }
+        plan_schedulers::schedule_query(&self.ctx, &optimized_plan).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -73,31 +101,29 @@ impl Interpreter for SelectInterpreter {
         _input_stream: Option<SendableDataBlockStream>,
     ) -> Result<SendableDataBlockStream> {
         let settings = self.ctx.get_settings();
+        let optimized_plan = self.rewrite_plan()?;
 
-        if settings.get_enable_new_processor_framework()? != 0 && self.ctx.get_cluster().is_empty()
-        {
-            let async_runtime = self.ctx.get_storage_runtime();
-            let new_pipeline = self.create_new_pipeline()?;
-            let executor = PipelinePullingExecutor::try_create(async_runtime, new_pipeline)?;
-            let executor_stream = Box::pin(ProcessorExecutorStream::create(executor)?);
-            return Ok(Box::pin(self.ctx.try_create_abortable(executor_stream)?));
+        if settings.get_enable_query_result_cache()? == 0 {
+            return self.execute_plan(optimized_plan).await;
         }
-        let optimized_plan = self.rewrite_plan()?;
-        plan_schedulers::schedule_query(&self.ctx, &optimized_plan).await
+        let cache_key = match QueryResultCache::cache_key(&self.ctx, &optimized_plan)? {
+            None => return self.execute_plan(optimized_plan).await,
+            Some(cache_key) => cache_key,
+        };
+
+        let cache = self.ctx.get_query_result_cache();
+        if let Some(blocks) = cache.get(&self.ctx, &cache_key) {
+            tracing::debug!("query result cache hit: {}", cache_key);
+            let schema = self.select.schema();
+            return Ok(Box::pin(DataBlockStream::create(schema, None, blocks)));
+        }
+        let stream = self.execute_plan(optimized_plan).await?;
+        cache.cache_stream(&self.ctx, cache_key, stream)
     }
 
     /// This method will create a new pipeline
     /// The QueryPipelineBuilder will use the optimized plan to generate a NewPipeline
     fn create_new_pipeline(&self) -> Result<NewPipeline> {
-        let settings = self.ctx.get_settings();
-        let builder = QueryPipelineBuilder::create(self.ctx.clone());
-
-        let optimized_plan = self.rewrite_plan()?;
-        let select_plan = SelectPlan {
-            input: Arc::new(optimized_plan),
-        };
-        let mut new_pipeline = builder.finalize(&select_plan)?;
-        new_pipeline.set_max_threads(settings.get_max_threads()? as usize);
-        Ok(new_pipeline)
+        self.build_new_pipeline(self.rewrite_plan()?)
     }
 }
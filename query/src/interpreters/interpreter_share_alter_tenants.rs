@@ -0,0 +1,90 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::AddShareAccountsReq;
+use common_meta_types::GrantObject;
+use common_meta_types::RemoveShareAccountsReq;
+use common_meta_types::UserPrivilegeType;
+use common_planners::AlterShareTenantsPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::QueryContext;
+
+pub struct AlterShareTenantsInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: AlterShareTenantsPlan,
+}
+
+impl AlterShareTenantsInterpreter {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        plan: AlterShareTenantsPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(AlterShareTenantsInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AlterShareTenantsInterpreter {
+    fn name(&self) -> &str {
+        "AlterShareTenantsInterpreter"
+    }
+
+    async fn execute(
+        &self,
+        _input_stream: Option<SendableDataBlockStream>,
+    ) -> Result<SendableDataBlockStream> {
+        self.ctx
+            .get_current_session()
+            .validate_privilege(&GrantObject::Global, UserPrivilegeType::Grant)
+            .await?;
+
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog();
+
+        let res = if plan.is_add {
+            let req = AddShareAccountsReq {
+                tenant: plan.tenant.clone(),
+                share_name: plan.share.clone(),
+                accounts: plan.accounts.clone(),
+            };
+            catalog.add_share_accounts(req).await.map(|_| ())
+        } else {
+            let req = RemoveShareAccountsReq {
+                tenant: plan.tenant.clone(),
+                share_name: plan.share.clone(),
+                accounts: plan.accounts.clone(),
+            };
+            catalog.remove_share_accounts(req).await.map(|_| ())
+        };
+        match res {
+            Err(e) if plan.if_exists && e.code() == ErrorCode::unknown_share_code() => {}
+            res => res?,
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_meta_types::CreateTableReq;
+use common_meta_types::GrantObject;
+use common_meta_types::TableMeta;
+use common_meta_types::UserPrivilegeType;
+use common_planners::CreateStreamPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::FuseTable;
+use crate::storages::stream::stream_table::OPT_KEY_OFFSET;
+use crate::storages::stream::stream_table::OPT_KEY_TABLE_DATABASE;
+use crate::storages::stream::stream_table::OPT_KEY_TABLE_ID;
+use crate::storages::stream::stream_table::OPT_KEY_TABLE_NAME;
+use crate::storages::stream::stream_table::STREAM_ENGINE;
+use crate::storages::stream::StreamTable;
+
+pub struct CreateStreamInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateStreamPlan,
+}
+
+impl CreateStreamInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreateStreamPlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(CreateStreamInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateStreamInterpreter {
+    fn name(&self) -> &str {
+        "CreateStreamInterpreter"
+    }
+
+    async fn execute(&self, _: Option<SendableDataBlockStream>) -> Result<SendableDataBlockStream> {
+        self.ctx
+            .get_current_session()
+            .validate_privilege(
+                &GrantObject::Database(self.plan.db.clone()),
+                UserPrivilegeType::Create,
+            )
+            .await?;
+        // the stream returns the rows of the table
+        self.ctx
+            .get_current_session()
+            .validate_select_privilege(&self.plan.table_db, &self.plan.table)
+            .await?;
+
+        let table = self
+            .ctx
+            .get_table(&self.plan.table_db, &self.plan.table)
+            .await?;
+        // only fuse tables keep the lineage of snapshots
+        let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+
+        // the changes are tracked since the current snapshot of the table
+        let mut options = BTreeMap::new();
+        options.insert(OPT_KEY_TABLE_ID.to_string(), table.get_id().to_string());
+        options.insert(OPT_KEY_TABLE_NAME.to_string(), table.name().to_string());
+        options.insert(
+            OPT_KEY_TABLE_DATABASE.to_string(),
+            self.plan.table_db.clone(),
+        );
+        if let Some(snapshot) = fuse_table.read_table_snapshot(self.ctx.as_ref()).await? {
+            options.insert(
+                OPT_KEY_OFFSET.to_string(),
+                snapshot.snapshot_id.to_simple().to_string(),
+            );
+        }
+
+        let plan = CreateTableReq {
+            if_not_exists: self.plan.if_not_exists,
+            tenant: self.plan.tenant.clone(),
+            db_name: self.plan.db.clone(),
+            table_name: self.plan.stream.clone(),
+            table_meta: TableMeta {
+                schema: StreamTable::stream_schema(&table.schema())?,
+                engine: STREAM_ENGINE.to_string(),
+                options,
+                ..Default::default()
+            },
+        };
+        self.ctx.get_catalog().create_table(plan).await?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UpsertTableOptionReq;
+use common_meta_types::UserPrivilegeType;
+use common_planners::AddVirtualColumnPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::FUSE_OPT_KEY_VIRTUAL_COLUMN_PREFIX;
+
+pub struct AddVirtualColumnInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: AddVirtualColumnPlan,
+}
+
+impl AddVirtualColumnInterpreter {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        plan: AddVirtualColumnPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(AddVirtualColumnInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AddVirtualColumnInterpreter {
+    fn name(&self) -> &str {
+        "AddVirtualColumnInterpreter"
+    }
+
+    async fn execute(&self, _: Option<SendableDataBlockStream>) -> Result<SendableDataBlockStream> {
+        let plan = &self.plan;
+        self.ctx
+            .get_current_session()
+            .validate_privilege(
+                &GrantObject::Table(plan.database.clone(), plan.table.clone()),
+                UserPrivilegeType::Alter,
+            )
+            .await?;
+
+        let tbl = match self.ctx.get_table(&plan.database, &plan.table).await {
+            Ok(tbl) => tbl,
+            Err(e) if plan.if_exists && e.code() == ErrorCode::unknown_table_code() => {
+                return Ok(Box::pin(DataBlockStream::create(
+                    plan.schema(),
+                    None,
+                    vec![],
+                )));
+            }
+            Err(e) => return Err(e),
+        };
+        // the virtual columns are kept aside the blocks of fuse tables
+        FuseTable::try_from_table(tbl.as_ref())?;
+
+        let schema = tbl.schema();
+        let column = schema.field_with_name(&plan.definition.column)?;
+        if remove_nullable(column.data_type()).data_type_id() != TypeID::Variant {
+            return Err(ErrorCode::BadArguments(format!(
+                "Virtual columns can only be added on Variant columns, but column {} is {}",
+                column.name(),
+                column.data_type().name()
+            )));
+        }
+        let key = format!("{}{}", FUSE_OPT_KEY_VIRTUAL_COLUMN_PREFIX, plan.name);
+        if schema.has_field(&plan.name) || tbl.get_table_info().options().contains_key(&key) {
+            return Err(ErrorCode::VirtualColumnAlreadyExists(format!(
+                "Virtual column {} already exists on table {}",
+                plan.name, plan.table
+            )));
+        }
+
+        // only the blocks written from now on extract the virtual column
+        let definition = serde_json::to_string(&plan.definition)?;
+        self.ctx
+            .get_catalog()
+            .upsert_table_option(UpsertTableOptionReq::new(
+                &tbl.get_table_info().ident,
+                &key,
+                definition,
+            ))
+            .await?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
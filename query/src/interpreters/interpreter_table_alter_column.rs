@@ -0,0 +1,80 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
+use common_planners::AlterColumnPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::FuseTable;
+
+pub struct AlterColumnInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: AlterColumnPlan,
+}
+
+impl AlterColumnInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: AlterColumnPlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(AlterColumnInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AlterColumnInterpreter {
+    fn name(&self) -> &str {
+        "AlterColumnInterpreter"
+    }
+
+    async fn execute(&self, _: Option<SendableDataBlockStream>) -> Result<SendableDataBlockStream> {
+        let plan = &self.plan;
+        self.ctx
+            .get_current_session()
+            .validate_privilege(
+                &GrantObject::Table(plan.database.clone(), plan.table.clone()),
+                UserPrivilegeType::Alter,
+            )
+            .await?;
+
+        let tbl = match self.ctx.get_table(&plan.database, &plan.table).await {
+            Ok(tbl) => tbl,
+            Err(e) if plan.if_exists && e.code() == ErrorCode::unknown_table_code() => {
+                return Ok(Box::pin(DataBlockStream::create(
+                    plan.schema(),
+                    None,
+                    vec![],
+                )));
+            }
+            Err(e) => return Err(e),
+        };
+        // the blocks of fuse tables are read by the ids of the columns, which are kept
+        // across the changes of the schema
+        let tbl = FuseTable::try_from_table(tbl.as_ref())?;
+        tbl.check_mutable()?;
+        tbl.do_alter_column(self.ctx.as_ref(), &plan.action).await?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -29,6 +29,7 @@ use common_streams::SendableDataBlockStream;
 
 use super::InsertInterpreter;
 use crate::catalogs::Catalog;
+use crate::interpreters::interpreter_common::grant_ownership_to_creator;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
@@ -121,7 +122,7 @@ impl CreateTableInterpreter {
         let catalog = self.ctx.get_catalog();
 
         // TODO: maybe the table creation and insertion should be a transaction, but it may require create_table support 2pc.
-        catalog.create_table(self.plan.clone().into()).await?;
+        self.create_table_in_catalog().await?;
         let table = catalog
             .get_table(tenant.as_str(), &self.plan.db, &self.plan.table)
             .await?;
@@ -163,8 +164,7 @@ impl CreateTableInterpreter {
     }
 
     async fn create_table(&self) -> Result<SendableDataBlockStream> {
-        let catalog = self.ctx.get_catalog();
-        catalog.create_table(self.plan.clone().into()).await?;
+        self.create_table_in_catalog().await?;
 
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
@@ -172,4 +172,18 @@ impl CreateTableInterpreter {
             vec![],
         )))
     }
+
+    /// Creates the table in the catalog, the creator owns the new table.
+    async fn create_table_in_catalog(&self) -> Result<()> {
+        let catalog = self.ctx.get_catalog();
+        let (tenant, db, table) = (&self.plan.tenant, &self.plan.db, &self.plan.table);
+        let exists = self.plan.if_not_exists && catalog.exists_table(tenant, db, table).await?;
+        catalog.create_table(self.plan.clone().into()).await?;
+
+        if !exists {
+            let object = GrantObject::Table(db.clone(), table.clone());
+            grant_ownership_to_creator(&self.ctx, object).await?;
+        }
+        Ok(())
+    }
 }
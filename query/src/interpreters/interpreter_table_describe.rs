@@ -97,7 +97,11 @@ impl Interpreter for DescribeTableInterpreter {
                     default_exprs.push(format!("{}", value));
                 }
             }
-            extras.push("".to_string());
+            extras.push(match field.computed_expr() {
+                Some(computed_expr) if computed_expr.is_stored() => "STORED GENERATED".to_string(),
+                Some(_) => "VIRTUAL GENERATED".to_string(),
+                None => "".to_string(),
+            });
         }
 
         let desc_schema = self.plan.schema();
@@ -23,6 +23,7 @@ use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::Catalog;
+use crate::interpreters::interpreter_common::revoke_ownership;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
@@ -53,12 +54,10 @@ impl Interpreter for DropTableInterpreter {
         let tbl_name = self.plan.table.as_str();
         let tbl = self.ctx.get_table(db_name, tbl_name).await.ok();
 
+        let object = GrantObject::Table(db_name.into(), tbl_name.into());
         self.ctx
             .get_current_session()
-            .validate_privilege(
-                &GrantObject::Database(db_name.into()),
-                UserPrivilegeType::Drop,
-            )
+            .validate_privilege(&object, UserPrivilegeType::Drop)
             .await?;
 
         if let Some(table) = &tbl {
@@ -72,12 +71,17 @@ impl Interpreter for DropTableInterpreter {
 
         let catalog = self.ctx.get_catalog();
         catalog.drop_table(self.plan.clone().into()).await?;
+        revoke_ownership(&self.ctx, &object, false).await?;
 
         // `drop_table` throws several types of exceptions
         // thus `optimize` operation is executed after it.
+        // The data of read only tables are not owned by them, and are kept.
         if let Some(tbl) = tbl {
             let keep_last_snapshot = false;
-            tbl.optimize(self.ctx.clone(), keep_last_snapshot).await?;
+            match tbl.optimize(self.ctx.clone(), keep_last_snapshot).await {
+                Err(e) if e.code() == ErrorCode::read_only_table_code() => {}
+                res => res?,
+            }
         }
 
         Ok(Box::pin(DataBlockStream::create(
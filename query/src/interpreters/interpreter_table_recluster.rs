@@ -0,0 +1,88 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
+use common_planners::ReclusterTablePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::FuseTable;
+
+pub struct ReclusterTableInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: ReclusterTablePlan,
+}
+
+impl ReclusterTableInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: ReclusterTablePlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(ReclusterTableInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for ReclusterTableInterpreter {
+    fn name(&self) -> &str {
+        "ReclusterTableInterpreter"
+    }
+
+    async fn execute(&self, _: Option<SendableDataBlockStream>) -> Result<SendableDataBlockStream> {
+        let plan = &self.plan;
+        self.ctx
+            .get_current_session()
+            .validate_privilege(
+                &GrantObject::Table(plan.database.clone(), plan.table.clone()),
+                UserPrivilegeType::Alter,
+            )
+            .await?;
+
+        let tenant = self.ctx.get_tenant();
+        loop {
+            // each round commits a snapshot, the table is fetched from the catalog directly,
+            // since the context caches the table of the previous round
+            let tbl = match self
+                .ctx
+                .get_catalog()
+                .get_table(tenant.as_str(), &plan.database, &plan.table)
+                .await
+            {
+                Ok(tbl) => tbl,
+                Err(e) if plan.if_exists && e.code() == ErrorCode::unknown_table_code() => break,
+                Err(e) => return Err(e),
+            };
+            let tbl = FuseTable::try_from_table(tbl.as_ref())?;
+            tbl.check_mutable()?;
+            let improved = tbl
+                .do_recluster(&self.ctx, &plan.selection, plan.limit)
+                .await?;
+            if !plan.is_final || !improved {
+                break;
+            }
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
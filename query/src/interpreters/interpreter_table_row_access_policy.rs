@@ -0,0 +1,170 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::MatchSeq;
+use common_meta_types::RowAccessPolicyReference;
+use common_meta_types::UpsertTableOptionReq;
+use common_meta_types::UserPrivilegeType;
+use common_meta_types::TABLE_OPT_KEY_ROW_ACCESS_POLICY;
+use common_planners::AlterRowAccessPolicyPlan;
+use common_planners::RowAccessPolicyAction;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::QueryContext;
+use crate::storages::view::view_table::VIEW_ENGINE;
+use crate::storages::Table;
+
+pub struct AlterRowAccessPolicyInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: AlterRowAccessPolicyPlan,
+}
+
+impl AlterRowAccessPolicyInterpreter {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        plan: AlterRowAccessPolicyPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(AlterRowAccessPolicyInterpreter { ctx, plan }))
+    }
+
+    async fn check_attachable(
+        &self,
+        table: &dyn Table,
+        reference: &RowAccessPolicyReference,
+    ) -> Result<()> {
+        let tenant = self.ctx.get_tenant();
+        let policy = self
+            .ctx
+            .get_user_manager()
+            .get_row_access_policy(&tenant, &reference.policy)
+            .await?;
+
+        if policy.args.len() != reference.columns.len() {
+            return Err(ErrorCode::IllegalRowAccessPolicy(format!(
+                "Row access policy {} requires {} columns, but got {}",
+                policy.name,
+                policy.args.len(),
+                reference.columns.len()
+            )));
+        }
+
+        let schema = table.schema();
+        for (arg, column) in policy.args.iter().zip(reference.columns.iter()) {
+            let field = schema.field_with_name(column)?;
+            let column_type = remove_nullable(field.data_type());
+            if column_type.data_type_id() != remove_nullable(arg.data_type()).data_type_id() {
+                return Err(ErrorCode::IllegalRowAccessPolicy(format!(
+                    "The argument {} of row access policy {} is {}, but column {} is {}",
+                    arg.name(),
+                    policy.name,
+                    arg.data_type().name(),
+                    column,
+                    field.data_type().name()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AlterRowAccessPolicyInterpreter {
+    fn name(&self) -> &str {
+        "AlterRowAccessPolicyInterpreter"
+    }
+
+    async fn execute(&self, _: Option<SendableDataBlockStream>) -> Result<SendableDataBlockStream> {
+        let plan = &self.plan;
+        self.ctx
+            .get_current_session()
+            .validate_privilege(
+                &GrantObject::Table(plan.database.clone(), plan.table.clone()),
+                UserPrivilegeType::Alter,
+            )
+            .await?;
+
+        let tbl = match self.ctx.get_table(&plan.database, &plan.table).await {
+            Ok(tbl) => tbl,
+            Err(e) if plan.if_exists && e.code() == ErrorCode::unknown_table_code() => {
+                return Ok(Box::pin(DataBlockStream::create(
+                    plan.schema(),
+                    None,
+                    vec![],
+                )));
+            }
+            Err(e) => return Err(e),
+        };
+        // the policies of the tables a view reads are applied to the view
+        if tbl.engine() == VIEW_ENGINE {
+            return Err(ErrorCode::IllegalRowAccessPolicy(format!(
+                "Row access policies can not be attached to the view {}",
+                plan.table
+            )));
+        }
+
+        let table_info = tbl.get_table_info();
+        let attached = match table_info.options().get(TABLE_OPT_KEY_ROW_ACCESS_POLICY) {
+            Some(v) => Some(serde_json::from_str::<RowAccessPolicyReference>(v)?),
+            None => None,
+        };
+
+        let value = match (&plan.action, attached) {
+            (RowAccessPolicyAction::Add(_), Some(attached)) => {
+                return Err(ErrorCode::IllegalRowAccessPolicy(format!(
+                    "Table {} already has the row access policy {}",
+                    plan.table, attached.policy
+                )));
+            }
+            (RowAccessPolicyAction::Add(reference), None) => {
+                self.check_attachable(tbl.as_ref(), reference).await?;
+                Some(serde_json::to_string(reference)?)
+            }
+            (RowAccessPolicyAction::Drop(policy), Some(attached)) if &attached.policy == policy => {
+                None
+            }
+            (RowAccessPolicyAction::Drop(policy), _) => {
+                return Err(ErrorCode::UnknownRowAccessPolicy(format!(
+                    "Row access policy {} is not attached to table {}",
+                    policy, plan.table
+                )));
+            }
+        };
+
+        let mut options = HashMap::new();
+        options.insert(TABLE_OPT_KEY_ROW_ACCESS_POLICY.to_string(), value);
+        let req = UpsertTableOptionReq {
+            table_id: table_info.ident.table_id,
+            seq: MatchSeq::Exact(table_info.ident.version),
+            options,
+        };
+        self.ctx.get_catalog().upsert_table_option(req).await?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -74,11 +74,25 @@ impl Interpreter for ShowCreateTableInterpreter {
                     }
                     None => "".to_string(),
                 };
+                let computed_expr = match field.computed_expr() {
+                    Some(computed_expr) => {
+                        let expression: Expression =
+                            serde_json::from_slice::<Expression>(computed_expr.expr())?;
+                        let kind = if computed_expr.is_stored() {
+                            "STORED"
+                        } else {
+                            "VIRTUAL"
+                        };
+                        format!(" AS ({}) {}", expression.column_name(), kind)
+                    }
+                    None => "".to_string(),
+                };
                 let column = format!(
-                    "  `{}` {}{}",
+                    "  `{}` {}{}{}",
                     field.name(),
                     format_data_type_sql(field.data_type()),
-                    default_expr
+                    default_expr,
+                    computed_expr
                 );
                 columns.push(column);
             }
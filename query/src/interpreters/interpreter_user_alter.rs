@@ -50,6 +50,10 @@ impl Interpreter for AlterUserInterpreter {
         let plan = self.plan.clone();
         let tenant = self.ctx.get_tenant();
         let user_mgr = self.ctx.get_user_manager();
+        if let Some(policy) = plan.user_option.as_ref().and_then(|o| o.network_policy()) {
+            // Make sure the network policy exists before assigning it.
+            user_mgr.get_network_policy(&tenant, policy).await?;
+        }
         if plan.auth_info.is_some() || plan.user_option.is_some() {
             user_mgr
                 .update_user(&tenant, plan.user, plan.auth_info, plan.user_option)
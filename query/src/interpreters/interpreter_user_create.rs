@@ -53,6 +53,10 @@ impl Interpreter for CreateUserInterpreter {
         let plan = self.plan.clone();
         let tenant = self.ctx.get_tenant();
         let user_mgr = self.ctx.get_user_manager();
+        if let Some(policy) = plan.user_option.network_policy() {
+            // Make sure the network policy exists before assigning it.
+            user_mgr.get_network_policy(&tenant, policy).await?;
+        }
         let user_info = UserInfo {
             auth_info: plan.auth_info.clone(),
             name: plan.user.username,
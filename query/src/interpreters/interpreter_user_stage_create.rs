@@ -15,12 +15,15 @@
 use std::sync::Arc;
 
 use common_exception::Result;
+use common_meta_types::GrantObject;
 use common_meta_types::StageType;
+use common_meta_types::UserPrivilegeType;
 use common_planners::CreateUserStagePlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 
+use crate::interpreters::interpreter_common::grant_ownership_to_creator;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
@@ -48,9 +51,15 @@ impl Interpreter for CreateUserStageInterpreter {
         &self,
         _input_stream: Option<SendableDataBlockStream>,
     ) -> Result<SendableDataBlockStream> {
+        self.ctx
+            .get_current_session()
+            .validate_privilege(&GrantObject::Global, UserPrivilegeType::Create)
+            .await?;
+
         let plan = self.plan.clone();
         let user_mgr = self.ctx.get_user_manager();
         let user_stage = plan.user_stage_info;
+        let object = GrantObject::Stage(user_stage.stage_name.clone());
 
         if user_stage.stage_type == StageType::Internal {
             let prefix = format!("stage/{}/", user_stage.stage_name);
@@ -58,10 +67,15 @@ impl Interpreter for CreateUserStageInterpreter {
             op.object(&prefix).create().await?
         }
 
-        let _create_stage = user_mgr
+        let seq = user_mgr
             .add_stage(&plan.tenant, user_stage, plan.if_not_exists)
             .await?;
 
+        // The creator owns the new stage, an existing stage is not created again.
+        if seq != u64::MIN {
+            grant_ownership_to_creator(&self.ctx, object).await?;
+        }
+
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
             None,
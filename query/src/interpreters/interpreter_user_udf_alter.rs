@@ -15,6 +15,8 @@
 use std::sync::Arc;
 
 use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
 use common_planners::AlterUserUDFPlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
@@ -48,6 +50,11 @@ impl Interpreter for AlterUserUDFInterpreter {
         _input_stream: Option<SendableDataBlockStream>,
     ) -> Result<SendableDataBlockStream> {
         let plan = self.plan.clone();
+        let object = GrantObject::UDF(plan.udf.name.clone());
+        self.ctx
+            .get_current_session()
+            .validate_privilege(&object, UserPrivilegeType::Alter)
+            .await?;
 
         let tenant = self.ctx.get_tenant();
         let user_mgr = self.ctx.get_user_manager();
@@ -15,11 +15,14 @@
 use std::sync::Arc;
 
 use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
 use common_planners::CreateUserUDFPlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 
+use crate::interpreters::interpreter_common::grant_ownership_to_creator;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
@@ -47,11 +50,22 @@ impl Interpreter for CreateUserUDFInterpreter {
         &self,
         _input_stream: Option<SendableDataBlockStream>,
     ) -> Result<SendableDataBlockStream> {
+        self.ctx
+            .get_current_session()
+            .validate_privilege(&GrantObject::Global, UserPrivilegeType::Create)
+            .await?;
+
         let plan = self.plan.clone();
         let tenant = self.ctx.get_tenant();
         let user_mgr = self.ctx.get_user_manager();
         let udf = plan.udf;
-        let _ = user_mgr.add_udf(&tenant, udf, plan.if_not_exists).await?;
+        let object = GrantObject::UDF(udf.name.clone());
+        let seq = user_mgr.add_udf(&tenant, udf, plan.if_not_exists).await?;
+
+        // The creator owns the new function, an existing function is not created again.
+        if seq != u64::MIN {
+            grant_ownership_to_creator(&self.ctx, object).await?;
+        }
 
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
@@ -15,11 +15,14 @@
 use std::sync::Arc;
 
 use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
 use common_planners::DropUserUDFPlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 
+use crate::interpreters::interpreter_common::revoke_ownership;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
@@ -48,11 +51,18 @@ impl Interpreter for DropUserUDFInterpreter {
         _input_stream: Option<SendableDataBlockStream>,
     ) -> Result<SendableDataBlockStream> {
         let plan = self.plan.clone();
+        let object = GrantObject::UDF(plan.name.clone());
+        self.ctx
+            .get_current_session()
+            .validate_privilege(&object, UserPrivilegeType::Drop)
+            .await?;
+
         let tenant = self.ctx.get_tenant();
         let user_mgr = self.ctx.get_user_manager();
         user_mgr
             .drop_udf(&tenant, plan.name.as_str(), plan.if_exists)
             .await?;
+        revoke_ownership(&self.ctx, &object, false).await?;
 
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
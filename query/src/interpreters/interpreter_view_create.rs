@@ -26,6 +26,7 @@ use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::Catalog;
+use crate::interpreters::interpreter_common::grant_ownership_to_creator;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
@@ -95,6 +96,10 @@ impl CreateViewInterpreter {
         };
         catalog.create_table(plan).await?;
 
+        // The creator owns the new view.
+        let object = GrantObject::Table(self.plan.db.clone(), self.plan.viewname.clone());
+        grant_ownership_to_creator(&self.ctx, object).await?;
+
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
             None,
@@ -24,6 +24,7 @@ use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::Catalog;
+use crate::interpreters::interpreter_common::revoke_ownership;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
@@ -58,12 +59,10 @@ impl Interpreter for DropViewInterpreter {
             .await
             .ok();
 
+        let object = GrantObject::Table(db_name.clone(), viewname.clone());
         self.ctx
             .get_current_session()
-            .validate_privilege(
-                &GrantObject::Database(db_name.clone()),
-                UserPrivilegeType::Drop,
-            )
+            .validate_privilege(&object, UserPrivilegeType::Drop)
             .await?;
 
         if let Some(table) = &tbl {
@@ -83,6 +82,7 @@ impl Interpreter for DropViewInterpreter {
             table_name: viewname,
         };
         catalog.drop_table(plan).await?;
+        revoke_ownership(&self.ctx, &object, false).await?;
 
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
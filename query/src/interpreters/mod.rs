@@ -12,21 +12,35 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod async_insert_queue;
 mod interpreter;
+mod interpreter_aggregating_index_create;
 mod interpreter_call;
 mod interpreter_common;
+mod interpreter_connection_alter;
+mod interpreter_connection_create;
+mod interpreter_connection_drop;
 mod interpreter_copy;
+mod interpreter_database_backup;
 mod interpreter_database_create;
 mod interpreter_database_drop;
+mod interpreter_database_restore;
 mod interpreter_database_show_create;
+mod interpreter_delete;
 mod interpreter_empty;
 mod interpreter_explain;
 mod interpreter_factory;
 mod interpreter_factory_interceptor;
 mod interpreter_insert;
+mod interpreter_insert_multi_table;
 mod interpreter_insert_with_stream;
 mod interpreter_kill;
 mod interpreter_list;
+mod interpreter_merge;
+mod interpreter_network_policy_create;
+mod interpreter_network_policy_drop;
+mod interpreter_pipe_create;
+mod interpreter_pipe_drop;
 mod interpreter_privilege_grant;
 mod interpreter_privilege_revoke;
 mod interpreter_query_log;
@@ -34,27 +48,46 @@ mod interpreter_role_create;
 mod interpreter_role_drop;
 mod interpreter_role_grant;
 mod interpreter_role_revoke;
+mod interpreter_row_access_policy_create;
+mod interpreter_row_access_policy_drop;
 mod interpreter_select;
 mod interpreter_select_v2;
 mod interpreter_setting;
+mod interpreter_share_alter_tenants;
+mod interpreter_share_create;
+mod interpreter_share_drop;
+mod interpreter_share_grant_object;
+mod interpreter_share_revoke_object;
+mod interpreter_show_connections;
 mod interpreter_show_databases;
 mod interpreter_show_engines;
 mod interpreter_show_functions;
 mod interpreter_show_grants;
 mod interpreter_show_metrics;
+mod interpreter_show_network_policies;
 mod interpreter_show_processlist;
 mod interpreter_show_roles;
+mod interpreter_show_row_access_policies;
 mod interpreter_show_settings;
 mod interpreter_show_tab_stat;
 mod interpreter_show_tables;
 mod interpreter_show_users;
+mod interpreter_stream_create;
+mod interpreter_table_add_virtual_column;
+mod interpreter_table_alter_column;
+mod interpreter_table_analyze;
 mod interpreter_table_create;
 mod interpreter_table_describe;
 mod interpreter_table_drop;
+mod interpreter_table_flashback;
 mod interpreter_table_optimize;
+mod interpreter_table_recluster;
 mod interpreter_table_rename;
+mod interpreter_table_row_access_policy;
 mod interpreter_table_show_create;
 mod interpreter_table_truncate;
+mod interpreter_table_vacuum;
+mod interpreter_update;
 mod interpreter_use_database;
 mod interpreter_user_alter;
 mod interpreter_user_create;
@@ -69,22 +102,38 @@ mod interpreter_view_alter;
 mod interpreter_view_create;
 mod interpreter_view_drop;
 mod plan_schedulers;
+mod query_history;
+mod query_result_cache;
 mod stream;
 
+pub use async_insert_queue::AsyncInsertQueue;
 pub use interpreter::Interpreter;
 pub use interpreter::InterpreterPtr;
+pub use interpreter_aggregating_index_create::CreateAggregatingIndexInterpreter;
 pub use interpreter_call::CallInterpreter;
+pub use interpreter_connection_alter::AlterConnectionInterpreter;
+pub use interpreter_connection_create::CreateConnectionInterpreter;
+pub use interpreter_connection_drop::DropConnectionInterpreter;
 pub use interpreter_copy::CopyInterpreter;
+pub use interpreter_database_backup::BackupDatabaseInterpreter;
 pub use interpreter_database_create::CreateDatabaseInterpreter;
 pub use interpreter_database_drop::DropDatabaseInterpreter;
+pub use interpreter_database_restore::RestoreDatabaseInterpreter;
 pub use interpreter_database_show_create::ShowCreateDatabaseInterpreter;
+pub use interpreter_delete::DeleteInterpreter;
 pub use interpreter_empty::EmptyInterpreter;
 pub use interpreter_explain::ExplainInterpreter;
 pub use interpreter_factory::InterpreterFactory;
 pub use interpreter_factory_interceptor::InterceptorInterpreter;
 pub use interpreter_insert::InsertInterpreter;
+pub use interpreter_insert_multi_table::InsertMultiTableInterpreter;
 pub use interpreter_kill::KillInterpreter;
 pub use interpreter_list::ListInterpreter;
+pub use interpreter_merge::MergeInterpreter;
+pub use interpreter_network_policy_create::CreateNetworkPolicyInterpreter;
+pub use interpreter_network_policy_drop::DropNetworkPolicyInterpreter;
+pub use interpreter_pipe_create::CreatePipeInterpreter;
+pub use interpreter_pipe_drop::DropPipeInterpreter;
 pub use interpreter_privilege_grant::GrantPrivilegeInterpreter;
 pub use interpreter_privilege_revoke::RevokePrivilegeInterpreter;
 pub use interpreter_query_log::InterpreterQueryLog;
@@ -94,26 +143,45 @@ pub use interpreter_role_create::CreateRoleInterpreter;
 pub use interpreter_role_drop::DropRoleInterpreter;
 pub use interpreter_role_grant::GrantRoleInterpreter;
 pub use interpreter_role_revoke::RevokeRoleInterpreter;
+pub use interpreter_row_access_policy_create::CreateRowAccessPolicyInterpreter;
+pub use interpreter_row_access_policy_drop::DropRowAccessPolicyInterpreter;
 pub use interpreter_select::SelectInterpreter;
 pub use interpreter_select_v2::SelectInterpreterV2;
 pub use interpreter_setting::SettingInterpreter;
+pub use interpreter_share_alter_tenants::AlterShareTenantsInterpreter;
+pub use interpreter_share_create::CreateShareInterpreter;
+pub use interpreter_share_drop::DropShareInterpreter;
+pub use interpreter_share_grant_object::GrantShareObjectInterpreter;
+pub use interpreter_share_revoke_object::RevokeShareObjectInterpreter;
+pub use interpreter_show_connections::ShowConnectionsInterpreter;
 pub use interpreter_show_databases::ShowDatabasesInterpreter;
 pub use interpreter_show_functions::ShowFunctionsInterpreter;
 pub use interpreter_show_grants::ShowGrantsInterpreter;
 pub use interpreter_show_metrics::ShowMetricsInterpreter;
+pub use interpreter_show_network_policies::ShowNetworkPoliciesInterpreter;
 pub use interpreter_show_processlist::ShowProcessListInterpreter;
 pub use interpreter_show_roles::ShowRolesInterpreter;
+pub use interpreter_show_row_access_policies::ShowRowAccessPoliciesInterpreter;
 pub use interpreter_show_settings::ShowSettingsInterpreter;
 pub use interpreter_show_tab_stat::ShowTabStatInterpreter;
 pub use interpreter_show_tables::ShowTablesInterpreter;
 pub use interpreter_show_users::ShowUsersInterpreter;
+pub use interpreter_stream_create::CreateStreamInterpreter;
+pub use interpreter_table_add_virtual_column::AddVirtualColumnInterpreter;
+pub use interpreter_table_alter_column::AlterColumnInterpreter;
+pub use interpreter_table_analyze::AnalyzeTableInterpreter;
 pub use interpreter_table_create::CreateTableInterpreter;
 pub use interpreter_table_describe::DescribeTableInterpreter;
 pub use interpreter_table_drop::DropTableInterpreter;
+pub use interpreter_table_flashback::FlashbackTableInterpreter;
 pub use interpreter_table_optimize::OptimizeTableInterpreter;
+pub use interpreter_table_recluster::ReclusterTableInterpreter;
 pub use interpreter_table_rename::RenameTableInterpreter;
+pub use interpreter_table_row_access_policy::AlterRowAccessPolicyInterpreter;
 pub use interpreter_table_show_create::ShowCreateTableInterpreter;
 pub use interpreter_table_truncate::TruncateTableInterpreter;
+pub use interpreter_table_vacuum::VacuumTableInterpreter;
+pub use interpreter_update::UpdateInterpreter;
 pub use interpreter_use_database::UseDatabaseInterpreter;
 pub use interpreter_user_alter::AlterUserInterpreter;
 pub use interpreter_user_create::CreateUserInterpreter;
@@ -126,3 +194,7 @@ pub use interpreter_view_alter::AlterViewInterpreter;
 pub use interpreter_view_create::CreateViewInterpreter;
 pub use interpreter_view_drop::DropViewInterpreter;
 pub use plan_schedulers::PlanScheduler;
+pub use query_history::QueryHistory;
+pub use query_history::QUERY_HISTORY_DATABASE;
+pub use query_history::QUERY_HISTORY_TABLE;
+pub use query_result_cache::QueryResultCache;
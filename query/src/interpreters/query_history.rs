@@ -0,0 +1,232 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_meta_types::CreateDatabaseReq;
+use common_meta_types::CreateTableReq;
+use common_meta_types::DatabaseMeta;
+use common_meta_types::DatabaseNameIdent;
+use common_meta_types::TableMeta;
+use common_planners::col;
+use common_planners::DeletePlan;
+use common_planners::Expression;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::LogEvent;
+use crate::interpreters::LogType;
+use crate::pipelines::new::PipeProfileValues;
+use crate::sessions::QueryContext;
+use crate::sql::OPT_KEY_DATABASE_ID;
+use crate::storages::Table;
+
+pub const QUERY_HISTORY_DATABASE: &str = "system_history";
+pub const QUERY_HISTORY_TABLE: &str = "query_history";
+
+/// How often the expired queries are deleted from the query history of a tenant.
+const PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Persists the finished queries into the fuse table `system_history.query_history` of their
+/// tenant, which is created by the first query recorded.
+///
+/// The records are buffered by the async insert queue, so that a busy server commits one
+/// snapshot for many queries. The queries older than `log.query_history_retention_days` days
+/// are deleted at most once per `PURGE_INTERVAL`.
+#[derive(Default)]
+pub struct QueryHistory {
+    purged_at: Mutex<HashMap<String, Instant>>,
+}
+
+impl QueryHistory {
+    pub fn create() -> Arc<QueryHistory> {
+        Arc::new(QueryHistory::default())
+    }
+
+    pub fn schema() -> DataSchemaRef {
+        DataSchemaRefExt::create(vec![
+            DataField::new("query_id", Vu8::to_data_type()),
+            DataField::new("query_kind", Vu8::to_data_type()),
+            DataField::new("query_text", Vu8::to_data_type()),
+            DataField::new("handler_type", Vu8::to_data_type()),
+            DataField::new("sql_user", Vu8::to_data_type()),
+            DataField::new("current_database", Vu8::to_data_type()),
+            DataField::new("client_address", Vu8::to_data_type()),
+            DataField::new("event_time", TimestampType::new_impl(6)),
+            DataField::new("duration_ms", u64::to_data_type()),
+            DataField::new("scan_rows", u64::to_data_type()),
+            DataField::new("scan_bytes", u64::to_data_type()),
+            DataField::new("written_rows", u64::to_data_type()),
+            DataField::new("written_bytes", u64::to_data_type()),
+            DataField::new("result_rows", u64::to_data_type()),
+            DataField::new("result_bytes", u64::to_data_type()),
+            DataField::new("memory_usage", u64::to_data_type()),
+            DataField::new("status", Vu8::to_data_type()),
+            DataField::new("exception_code", i32::to_data_type()),
+            DataField::new("exception", Vu8::to_data_type()),
+            DataField::new("profile", Vu8::to_data_type()),
+        ])
+    }
+
+    /// Records the finished query of `event`, which took `duration`.
+    pub async fn record(
+        &self,
+        ctx: Arc<QueryContext>,
+        event: &LogEvent,
+        duration: Duration,
+    ) -> Result<()> {
+        let table = Self::get_or_create_table(&ctx).await?;
+
+        let status = match event.log_type {
+            LogType::Error => "Error",
+            LogType::Aborted => "Aborted",
+            _ => "Finish",
+        };
+        // The metrics of the pipes of each pipeline built for the query.
+        let profile = ctx
+            .get_pipeline_profiles()
+            .iter()
+            .map(|pipes| pipes.iter().map(|pipe| pipe.get_values()).collect())
+            .collect::<Vec<Vec<PipeProfileValues>>>();
+        let profile = serde_json::to_string(&profile)?;
+
+        let block = DataBlock::create(Self::schema(), vec![
+            Series::from_data(vec![event.query_id.as_str()]),
+            Series::from_data(vec![event.query_kind.as_str()]),
+            Series::from_data(vec![event.query_text.as_str()]),
+            Series::from_data(vec![event.handler_type.as_str()]),
+            Series::from_data(vec![event.sql_user.as_str()]),
+            Series::from_data(vec![event.current_database.as_str()]),
+            Series::from_data(vec![event.client_address.as_str()]),
+            Series::from_data(vec![event.event_time as i64 * 1000]),
+            Series::from_data(vec![duration.as_millis() as u64]),
+            Series::from_data(vec![event.scan_rows]),
+            Series::from_data(vec![event.scan_bytes]),
+            Series::from_data(vec![event.written_rows]),
+            Series::from_data(vec![event.written_bytes]),
+            Series::from_data(vec![event.result_rows]),
+            Series::from_data(vec![event.result_bytes]),
+            Series::from_data(vec![event.memory_usage]),
+            Series::from_data(vec![status]),
+            Series::from_data(vec![event.exception_code]),
+            Series::from_data(vec![event.exception.as_str()]),
+            Series::from_data(vec![profile.as_str()]),
+        ]);
+        // The record is committed in the background, with the records of the other queries.
+        let _ = ctx.get_async_insert_queue().push(
+            ctx.clone(),
+            QUERY_HISTORY_DATABASE,
+            table.clone(),
+            block,
+        )?;
+
+        if self.should_purge(&ctx.get_tenant()) {
+            let retention_days = ctx.get_config().log.query_history_retention_days;
+            Self::purge(&ctx, table, event.event_time, retention_days).await?;
+        }
+        Ok(())
+    }
+
+    fn should_purge(&self, tenant: &str) -> bool {
+        let mut purged_at = self.purged_at.lock();
+        match purged_at.get(tenant) {
+            Some(instant) if instant.elapsed() < PURGE_INTERVAL => false,
+            _ => {
+                purged_at.insert(tenant.to_string(), Instant::now());
+                true
+            }
+        }
+    }
+
+    /// Deletes the queries finished `retention_days` days before `now`, in milliseconds.
+    async fn purge(
+        ctx: &Arc<QueryContext>,
+        table: Arc<dyn Table>,
+        now: u64,
+        retention_days: u64,
+    ) -> Result<()> {
+        let expire_before = now.saturating_sub(retention_days * 24 * 3600 * 1000) as i64 * 1000;
+        let expire_before = Expression::create_literal_with_type(
+            DataValue::Int64(expire_before),
+            TimestampType::new_impl(6),
+        );
+        let plan = DeletePlan {
+            database_name: QUERY_HISTORY_DATABASE.to_string(),
+            table_name: QUERY_HISTORY_TABLE.to_string(),
+            table_id: table.get_id(),
+            selection: Some(col("event_time").lt(expire_before)),
+        };
+        table.delete(ctx.clone(), plan).await
+    }
+
+    async fn get_or_create_table(ctx: &Arc<QueryContext>) -> Result<Arc<dyn Table>> {
+        let tenant = ctx.get_tenant();
+        let catalog = ctx.get_catalog();
+        match catalog
+            .get_table(&tenant, QUERY_HISTORY_DATABASE, QUERY_HISTORY_TABLE)
+            .await
+        {
+            Err(e)
+                if e.code() == ErrorCode::UnknownDatabase("").code()
+                    || e.code() == ErrorCode::UnknownTable("").code() => {}
+            res => return res,
+        }
+
+        catalog
+            .create_database(CreateDatabaseReq {
+                if_not_exists: true,
+                name_ident: DatabaseNameIdent {
+                    tenant: tenant.clone(),
+                    db_name: QUERY_HISTORY_DATABASE.to_string(),
+                },
+                meta: DatabaseMeta {
+                    engine: "".to_string(),
+                    ..Default::default()
+                },
+            })
+            .await?;
+        let db = catalog
+            .get_database(&tenant, QUERY_HISTORY_DATABASE)
+            .await?;
+
+        let mut table_meta = TableMeta {
+            schema: Self::schema(),
+            engine: "FUSE".to_string(),
+            ..Default::default()
+        };
+        table_meta.options.insert(
+            OPT_KEY_DATABASE_ID.to_owned(),
+            db.get_db_info().ident.db_id.to_string(),
+        );
+        catalog
+            .create_table(CreateTableReq {
+                if_not_exists: true,
+                tenant: tenant.clone(),
+                db_name: QUERY_HISTORY_DATABASE.to_string(),
+                table_name: QUERY_HISTORY_TABLE.to_string(),
+                table_meta,
+            })
+            .await?;
+        catalog
+            .get_table(&tenant, QUERY_HISTORY_DATABASE, QUERY_HISTORY_TABLE)
+            .await
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_cache::Cache;
+use common_cache::DefaultHashBuilder;
+use common_cache::LruCache;
+use common_cache::Meter;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
+use common_infallible::Mutex;
+use common_planners::Expression;
+use common_planners::ExpressionVisitor;
+use common_planners::PlanNode;
+use common_planners::PlanVisitor;
+use common_planners::ReadDataSourcePlan;
+use common_planners::Recursion;
+use common_planners::SourceInfo;
+use common_streams::SendableDataBlockStream;
+use futures::Stream;
+use futures::StreamExt;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::sessions::QueryContext;
+
+/// The result set of a query, which is returned as is until it expires.
+struct CachedResult {
+    blocks: Vec<DataBlock>,
+    data_size: usize,
+    expire_at: Instant,
+}
+
+struct CachedResultMeter;
+
+impl Meter<String, Arc<CachedResult>> for CachedResultMeter {
+    type Measure = usize;
+
+    fn measure<Q: ?Sized>(&self, _: &Q, v: &Arc<CachedResult>) -> usize
+    where String: Borrow<Q> {
+        v.data_size
+    }
+}
+
+type TenantResultCache = LruCache<String, Arc<CachedResult>, DefaultHashBuilder, CachedResultMeter>;
+
+/// Caches the small result sets of the queries, so that the dashboards repeating the same
+/// queries over the tables which are not changed since do not scan them again.
+///
+/// A result is keyed by the hash of the optimized plan, which holds the ids, versions and
+/// snapshot locations of the tables it reads, so any commit to one of them makes it stale.
+/// Only the queries reading fuse tables by deterministic functions are cached. The results are
+/// kept `query_result_cache_ttl` seconds, in at most `query_result_cache_max_bytes` bytes per
+/// tenant, by the settings of the query caching them.
+#[derive(Default)]
+pub struct QueryResultCache {
+    caches: Mutex<HashMap<String, TenantResultCache>>,
+}
+
+impl QueryResultCache {
+    pub fn create() -> Arc<QueryResultCache> {
+        Arc::new(QueryResultCache::default())
+    }
+
+    /// Returns the cache key of the result of the plan, or None if it can not be cached.
+    pub fn cache_key(ctx: &QueryContext, plan: &PlanNode) -> Result<Option<String>> {
+        let mut checker = CacheableChecker {
+            cacheable: true,
+            tables: 0,
+        };
+        checker.visit_plan_node(plan)?;
+        if !checker.cacheable || checker.tables == 0 {
+            return Ok(None);
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(ctx.get_tenant().as_bytes());
+        hasher.update([0]);
+        // the dates and times are formatted by the timezone of the session
+        hasher.update(ctx.get_settings().get_timezone()?);
+        hasher.update([0]);
+        hasher.update(serde_json::to_vec(plan)?);
+        Ok(Some(format!("{:x}", hasher.finalize())))
+    }
+
+    /// Returns the cached result of the key, if it is not expired.
+    pub fn get(&self, ctx: &QueryContext, key: &str) -> Option<Vec<DataBlock>> {
+        let mut caches = self.caches.lock();
+        let cache = caches.get_mut(&ctx.get_tenant())?;
+        let result = cache.get(key)?.clone();
+        if result.expire_at <= Instant::now() {
+            cache.pop(key);
+            return None;
+        }
+        Some(result.blocks.clone())
+    }
+
+    /// Wraps the stream of the result of the key, to cache it once the stream is drained without
+    /// errors, unless the result is larger than `query_result_cache_max_bytes`.
+    pub fn cache_stream(
+        self: &Arc<Self>,
+        ctx: &QueryContext,
+        key: String,
+        input: SendableDataBlockStream,
+    ) -> Result<SendableDataBlockStream> {
+        let settings = ctx.get_settings();
+        Ok(Box::pin(CachingStream {
+            input,
+            cache: self.clone(),
+            tenant: ctx.get_tenant(),
+            key,
+            ttl: Duration::from_secs(settings.get_query_result_cache_ttl()?),
+            max_bytes: settings.get_query_result_cache_max_bytes()? as usize,
+            blocks: Some(vec![]),
+            data_size: 0,
+        }))
+    }
+
+    fn put(&self, tenant: String, key: String, max_bytes: usize, result: CachedResult) {
+        let mut caches = self.caches.lock();
+        let cache = caches
+            .entry(tenant)
+            .or_insert_with(|| LruCache::with_meter(max_bytes as u64, CachedResultMeter));
+        cache.set_capacity(max_bytes as u64);
+        cache.put(key, Arc::new(result));
+    }
+}
+
+/// Checks that the plan reads some fuse tables, by deterministic functions only.
+struct CacheableChecker {
+    cacheable: bool,
+    tables: usize,
+}
+
+impl PlanVisitor for CacheableChecker {
+    fn visit_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<()> {
+        match &plan.source_info {
+            SourceInfo::TableSource(table_info)
+                if table_info.engine().eq_ignore_ascii_case("FUSE") =>
+            {
+                self.tables += 1
+            }
+            _ => self.cacheable = false,
+        }
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, expr: &Expression) -> Result<()> {
+        let checker = expr.accept(DeterministicChecker {
+            deterministic: true,
+            subqueries: vec![],
+        })?;
+        self.cacheable &= checker.deterministic;
+        for subquery in checker.subqueries {
+            self.visit_subquery_plan(&subquery)?;
+        }
+        Ok(())
+    }
+}
+
+struct DeterministicChecker {
+    deterministic: bool,
+    subqueries: Vec<Arc<PlanNode>>,
+}
+
+impl ExpressionVisitor for DeterministicChecker {
+    fn pre_visit(mut self, expr: &Expression) -> Result<Recursion<Self>> {
+        match expr {
+            Expression::ScalarFunction { op, .. }
+            | Expression::BinaryExpression { op, .. }
+            | Expression::UnaryExpression { op, .. } => {
+                // the unknown functions, e.g. the udfs, are not cached either
+                let features = FunctionFactory::instance().get_features(op);
+                self.deterministic &= matches!(features, Ok(f) if f.is_deterministic);
+            }
+            Expression::Subquery { query_plan, .. }
+            | Expression::ScalarSubquery { query_plan, .. } => {
+                self.subqueries.push(query_plan.clone());
+            }
+            _ => {}
+        }
+        Ok(Recursion::Continue(self))
+    }
+}
+
+struct CachingStream {
+    input: SendableDataBlockStream,
+    cache: Arc<QueryResultCache>,
+    tenant: String,
+    key: String,
+    ttl: Duration,
+    max_bytes: usize,
+    /// The blocks read so far, None if the result is not to be cached
+    blocks: Option<Vec<DataBlock>>,
+    data_size: usize,
+}
+
+impl Stream for CachingStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let polled = self.input.poll_next_unpin(ctx);
+        match &polled {
+            Poll::Ready(Some(Ok(block))) => {
+                self.data_size += block.memory_size();
+                if self.data_size > self.max_bytes {
+                    self.blocks = None;
+                } else if let Some(blocks) = self.blocks.as_mut() {
+                    blocks.push(block.clone());
+                }
+            }
+            Poll::Ready(Some(Err(_))) => self.blocks = None,
+            Poll::Ready(None) => {
+                if let Some(blocks) = self.blocks.take() {
+                    let result = CachedResult {
+                        blocks,
+                        data_size: self.data_size,
+                        expire_at: Instant::now() + self.ttl,
+                    };
+                    let (tenant, key) = (self.tenant.clone(), self.key.clone());
+                    self.cache.put(tenant, key, self.max_bytes, result);
+                }
+            }
+            Poll::Pending => {}
+        }
+        polled
+    }
+}
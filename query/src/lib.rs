@@ -29,6 +29,7 @@ pub mod interpreters;
 pub mod metrics;
 pub mod optimizers;
 pub mod pipelines;
+pub mod pipes;
 pub mod procedures;
 pub mod servers;
 pub mod sessions;
@@ -14,16 +14,24 @@
 
 mod metrics;
 mod optimizer;
+mod optimizer_aggregating_index;
+mod optimizer_common_subexpression;
 mod optimizer_constant_folding;
 mod optimizer_expression_transform;
+mod optimizer_metadata_aggregation;
 mod optimizer_scatters;
 mod optimizer_statistics_exact;
 mod optimizer_top_n_push_down;
+mod optimizer_virtual_column;
 
 pub use optimizer::Optimizer;
 pub use optimizer::Optimizers;
+pub use optimizer_aggregating_index::AggregatingIndexOptimizer;
+pub use optimizer_common_subexpression::CommonSubexpressionOptimizer;
 pub use optimizer_constant_folding::ConstantFoldingOptimizer;
 pub use optimizer_expression_transform::ExprTransformOptimizer;
+pub use optimizer_metadata_aggregation::MetadataAggregationOptimizer;
 pub use optimizer_scatters::ScattersOptimizer;
 pub use optimizer_statistics_exact::StatisticsExactOptimizer;
 pub use optimizer_top_n_push_down::TopNPushDownOptimizer;
+pub use optimizer_virtual_column::VirtualColumnOptimizer;
@@ -20,10 +20,14 @@ use common_tracing::tracing;
 use metrics::histogram;
 
 use crate::optimizers::optimizer_scatters::ScattersOptimizer;
+use crate::optimizers::AggregatingIndexOptimizer;
+use crate::optimizers::CommonSubexpressionOptimizer;
 use crate::optimizers::ConstantFoldingOptimizer;
 use crate::optimizers::ExprTransformOptimizer;
+use crate::optimizers::MetadataAggregationOptimizer;
 use crate::optimizers::StatisticsExactOptimizer;
 use crate::optimizers::TopNPushDownOptimizer;
+use crate::optimizers::VirtualColumnOptimizer;
 use crate::sessions::QueryContext;
 
 pub trait Optimizer {
@@ -50,7 +54,11 @@ impl Optimizers {
                 Box::new(ConstantFoldingOptimizer::create(ctx.clone())),
                 Box::new(ExprTransformOptimizer::create(ctx.clone())),
                 Box::new(TopNPushDownOptimizer::create(ctx.clone())),
-                Box::new(StatisticsExactOptimizer::create(ctx)),
+                Box::new(StatisticsExactOptimizer::create(ctx.clone())),
+                Box::new(MetadataAggregationOptimizer::create(ctx.clone())),
+                Box::new(AggregatingIndexOptimizer::create(ctx.clone())),
+                Box::new(VirtualColumnOptimizer::create(ctx.clone())),
+                Box::new(CommonSubexpressionOptimizer::create(ctx)),
             ],
         }
     }
@@ -0,0 +1,172 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::AggregatorFinalPlan;
+use common_planners::Expression;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+use common_planners::RequireColumnsVisitor;
+use common_planners::SourceInfo;
+
+use crate::optimizers::Optimizer;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::FuseTable;
+
+struct AggregatingIndexImpl<'a> {
+    ctx: &'a Arc<QueryContext>,
+}
+
+/// Answers the aggregations over fuse tables with the aggregating indexes of the tables.
+///
+/// An aggregation could be answered by an index which is up to date with the snapshot being
+/// read, if it groups by some of the keys of the index, the rows are filtered by the keys
+/// only, and the aggregates are kept by the index. The partial aggregates kept by the index
+/// are merged instead of aggregating the rows of the table.
+pub struct AggregatingIndexOptimizer {
+    ctx: Arc<QueryContext>,
+}
+
+impl PlanRewriter for AggregatingIndexImpl<'_> {
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        if let Some(new_plan) = futures::executor::block_on(self.index_plan(plan))? {
+            return Ok(new_plan);
+        }
+
+        let input = self.rewrite_plan_node(plan.input.as_ref())?;
+        Ok(PlanNode::AggregatorFinal(AggregatorFinalPlan {
+            schema: plan.schema.clone(),
+            schema_before_group_by: plan.schema_before_group_by.clone(),
+            aggr_expr: plan.aggr_expr.clone(),
+            group_expr: plan.group_expr.clone(),
+            input: Arc::new(input),
+        }))
+    }
+}
+
+impl AggregatingIndexImpl<'_> {
+    async fn index_plan(&self, plan: &AggregatorFinalPlan) -> Result<Option<PlanNode>> {
+        let partial = match plan.input.as_ref() {
+            PlanNode::AggregatorPartial(partial) => partial,
+            _ => return Ok(None),
+        };
+        let (predicate, source) = match partial.input.as_ref() {
+            PlanNode::ReadSource(source) => (None, source),
+            PlanNode::Filter(filter) => match filter.input.as_ref() {
+                PlanNode::ReadSource(source) => (Some(&filter.predicate), source),
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        let indexes = match &source.source_info {
+            SourceInfo::TableSource(table_info) if source.tbl_args.is_none() => {
+                FuseTable::aggregating_indexes(table_info)?
+            }
+            _ => return Ok(None),
+        };
+        if indexes.is_empty() {
+            return Ok(None);
+        }
+
+        // the columns that the index should keep as keys
+        let mut columns = Vec::with_capacity(partial.group_expr.len());
+        for expr in &partial.group_expr {
+            match expr {
+                Expression::Column(column) => columns.push(column.clone()),
+                _ => return Ok(None),
+            }
+        }
+        if let Some(predicate) = predicate {
+            columns.extend(RequireColumnsVisitor::collect_columns_from_expr(predicate)?);
+        }
+
+        let table = self.ctx.build_table_from_source_plan(source)?;
+        let table = FuseTable::try_from_table(table.as_ref())?;
+        let snapshot = match table.read_table_snapshot(self.ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(None),
+        };
+        for (index, definition) in &indexes {
+            if !columns.iter().all(|c| definition.keys.contains(c)) {
+                continue;
+            }
+            let positions = partial
+                .aggr_expr
+                .iter()
+                .map(|expr| definition.position_of(expr))
+                .collect::<Option<Vec<_>>>();
+            let positions = match positions {
+                Some(positions) => positions,
+                None => continue,
+            };
+            let meta = table
+                .read_aggregating_index_meta(self.ctx.as_ref(), index, &snapshot.snapshot_id)
+                .await?;
+            let meta = match meta {
+                // an aggregation without keys yields a row, even if there are no rows at all
+                Some(meta) if !meta.blocks.is_empty() || !partial.group_expr.is_empty() => meta,
+                _ => continue,
+            };
+
+            let index_source = table.aggregating_index_source_plan(index, definition, &meta)?;
+            let mut builder = PlanBuilder::from(&PlanNode::ReadSource(index_source));
+            if let Some(predicate) = predicate {
+                builder = builder.filter(predicate.clone())?;
+            }
+            let schema_before_group_by = builder.build()?.schema();
+
+            // the merged aggregates are renamed after the aggregates which they answer
+            let merge_exprs = positions
+                .iter()
+                .map(|idx| definition.merge_expr(*idx))
+                .collect::<Vec<_>>();
+            let mut projection = partial
+                .aggr_expr
+                .iter()
+                .zip(merge_exprs.iter())
+                .map(|(expr, merge)| {
+                    Expression::Column(merge.column_name()).alias(&expr.column_name())
+                })
+                .collect::<Vec<_>>();
+            projection.extend(partial.group_expr.iter().cloned());
+            let new_plan = builder
+                .aggregate_partial(&merge_exprs, &partial.group_expr)?
+                .aggregate_final(schema_before_group_by, &merge_exprs, &partial.group_expr)?
+                .project(&projection)?
+                .build()?;
+            return Ok(Some(new_plan));
+        }
+        Ok(None)
+    }
+}
+
+impl Optimizer for AggregatingIndexOptimizer {
+    fn name(&self) -> &str {
+        "AggregatingIndex"
+    }
+
+    fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
+        let mut visitor = AggregatingIndexImpl { ctx: &self.ctx };
+        visitor.rewrite_plan_node(plan)
+    }
+}
+
+impl AggregatingIndexOptimizer {
+    pub fn create(ctx: Arc<QueryContext>) -> Self {
+        AggregatingIndexOptimizer { ctx }
+    }
+}
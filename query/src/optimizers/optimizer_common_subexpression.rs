@@ -0,0 +1,225 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
+use common_planners::AggregatorFinalPlan;
+use common_planners::AggregatorPartialPlan;
+use common_planners::Expression;
+use common_planners::ExpressionPlan;
+use common_planners::ExpressionVisitor;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+use common_planners::Recursion;
+
+use crate::optimizers::Optimizer;
+use crate::sessions::QueryContext;
+
+/// Evaluates the subexpressions shared by a filter and the expressions above it once.
+///
+/// The shared subexpressions are computed by a new expression below the filter, so that both
+/// the filter and the expressions above it find them as the columns of their input blocks,
+/// e.g. `select regexp_replace(s, 'a', 'b') from t where regexp_replace(s, 'a', 'b') <> s`.
+pub struct CommonSubexpressionOptimizer {}
+
+struct CommonSubexpressionImpl {
+    before_group_by_schema: Option<DataSchemaRef>,
+}
+
+impl CommonSubexpressionImpl {
+    /// Rewrites the input of the predicate, computing the subexpressions of the predicate used by
+    /// the expressions above it too.
+    fn rewrite_predicate_input(
+        &mut self,
+        input: &PlanNode,
+        predicate: &Expression,
+        exprs: &[Expression],
+    ) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(input)?;
+
+        let mut used = ComputedCollector::default();
+        for expr in exprs {
+            used = expr.accept(used)?;
+        }
+        let shared = predicate.accept(SharedCollector {
+            used: used.names,
+            schema: new_input.schema(),
+            shared: vec![],
+        })?;
+        if shared.shared.is_empty() {
+            return Ok(new_input);
+        }
+        PlanBuilder::from(&new_input)
+            .expression(&shared.shared, "Common Subexpressions")?
+            .build()
+    }
+}
+
+impl PlanRewriter for CommonSubexpressionImpl {
+    fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        match self.before_group_by_schema {
+            Some(_) => Err(ErrorCode::LogicalError(
+                "Logical error: before group by schema must be None",
+            )),
+            None => {
+                self.before_group_by_schema = Some(new_input.schema());
+                let new_aggr_expr = self.rewrite_exprs(&new_input.schema(), &plan.aggr_expr)?;
+                let new_group_expr = self.rewrite_exprs(&new_input.schema(), &plan.group_expr)?;
+                PlanBuilder::from(&new_input)
+                    .aggregate_partial(&new_aggr_expr, &new_group_expr)?
+                    .build()
+            }
+        }
+    }
+
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+
+        match self.before_group_by_schema.take() {
+            None => Err(ErrorCode::LogicalError(
+                "Logical error: before group by schema must be Some",
+            )),
+            Some(schema_before_group_by) => {
+                let new_aggr_expr = self.rewrite_exprs(&new_input.schema(), &plan.aggr_expr)?;
+                let new_group_expr = self.rewrite_exprs(&new_input.schema(), &plan.group_expr)?;
+                PlanBuilder::from(&new_input)
+                    .aggregate_final(schema_before_group_by, &new_aggr_expr, &new_group_expr)?
+                    .build()
+            }
+        }
+    }
+
+    fn rewrite_expression(&mut self, plan: &ExpressionPlan) -> Result<PlanNode> {
+        let new_input = match plan.input.as_ref() {
+            PlanNode::Filter(filter) => {
+                let input =
+                    self.rewrite_predicate_input(&filter.input, &filter.predicate, &plan.exprs)?;
+                let predicate = self.rewrite_expr(&input.schema(), &filter.predicate)?;
+                PlanBuilder::from(&input).filter(predicate)?.build()?
+            }
+            PlanNode::Having(having) => {
+                let input =
+                    self.rewrite_predicate_input(&having.input, &having.predicate, &plan.exprs)?;
+                let predicate = self.rewrite_expr(&input.schema(), &having.predicate)?;
+                PlanBuilder::from(&input).having(predicate)?.build()?
+            }
+            input => self.rewrite_plan_node(input)?,
+        };
+        let new_exprs = self.rewrite_exprs(&new_input.schema(), &plan.exprs)?;
+        PlanBuilder::from(&new_input)
+            .expression(&new_exprs, &plan.desc)?
+            .build()
+    }
+}
+
+impl Optimizer for CommonSubexpressionOptimizer {
+    fn name(&self) -> &str {
+        "CommonSubexpression"
+    }
+
+    fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
+        let mut rewriter = CommonSubexpressionImpl {
+            before_group_by_schema: None,
+        };
+        rewriter.rewrite_plan_node(plan)
+    }
+}
+
+impl CommonSubexpressionOptimizer {
+    pub fn create(_ctx: Arc<QueryContext>) -> Self {
+        CommonSubexpressionOptimizer {}
+    }
+}
+
+/// Whether the expression is computed by a function rather than read from the input.
+fn is_computed(expr: &Expression) -> bool {
+    matches!(
+        expr,
+        Expression::ScalarFunction { .. }
+            | Expression::BinaryExpression { .. }
+            | Expression::UnaryExpression { .. }
+            | Expression::Cast { .. }
+            | Expression::MapAccess { .. }
+    )
+}
+
+/// Collects the names of the computed subexpressions.
+#[derive(Default)]
+struct ComputedCollector {
+    names: HashSet<String>,
+}
+
+impl ExpressionVisitor for ComputedCollector {
+    fn pre_visit(mut self, expr: &Expression) -> Result<Recursion<Self>> {
+        if is_computed(expr) {
+            self.names.insert(expr.column_name());
+        }
+        Ok(Recursion::Continue(self))
+    }
+}
+
+/// Collects the outermost computed subexpressions in `used`, which are not in the input yet and
+/// give the same results wherever they are evaluated.
+struct SharedCollector {
+    used: HashSet<String>,
+    schema: DataSchemaRef,
+    shared: Vec<Expression>,
+}
+
+impl ExpressionVisitor for SharedCollector {
+    fn pre_visit(mut self, expr: &Expression) -> Result<Recursion<Self>> {
+        let name = expr.column_name();
+        if !is_computed(expr)
+            || !self.used.contains(&name)
+            || self.schema.has_field(&name)
+            || !expr.accept(ReusableChecker { reusable: true })?.reusable
+        {
+            return Ok(Recursion::Continue(self));
+        }
+        if !self.shared.iter().any(|e| e.column_name() == name) {
+            self.shared.push(expr.clone());
+        }
+        Ok(Recursion::Stop(self))
+    }
+}
+
+/// Checks that the expression has neither non-deterministic functions nor subqueries.
+struct ReusableChecker {
+    reusable: bool,
+}
+
+impl ExpressionVisitor for ReusableChecker {
+    fn pre_visit(mut self, expr: &Expression) -> Result<Recursion<Self>> {
+        match expr {
+            Expression::ScalarFunction { op, .. }
+            | Expression::BinaryExpression { op, .. }
+            | Expression::UnaryExpression { op, .. } => {
+                let features = FunctionFactory::instance().get_features(op);
+                self.reusable &= matches!(features, Ok(f) if f.is_deterministic);
+            }
+            Expression::Subquery { .. }
+            | Expression::ScalarSubquery { .. }
+            | Expression::AggregateFunction { .. } => self.reusable = false,
+            _ => {}
+        }
+        Ok(Recursion::Continue(self))
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::AggregatorFinalPlan;
+use common_planners::Expression;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+use common_planners::SourceInfo;
+
+use crate::optimizers::Optimizer;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::operations::MetadataAggregation;
+use crate::storages::fuse::FuseTable;
+use crate::storages::ToReadDataSourcePlan;
+
+struct MetadataAggregationImpl<'a> {
+    ctx: &'a Arc<QueryContext>,
+}
+
+/// Answers `count(*)`, `min` and `max` of columns over fuse tables with the statistics of
+/// the blocks, without reading the blocks.
+///
+/// The rows could be filtered, as long as the statistics tell that every block left after
+/// pruning is matched as a whole, which is mostly the case of filtering by the cluster key.
+pub struct MetadataAggregationOptimizer {
+    ctx: Arc<QueryContext>,
+}
+
+impl PlanRewriter for MetadataAggregationImpl<'_> {
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        if let Some(new_plan) = futures::executor::block_on(self.metadata_plan(plan))? {
+            return Ok(new_plan);
+        }
+
+        let input = self.rewrite_plan_node(plan.input.as_ref())?;
+        Ok(PlanNode::AggregatorFinal(AggregatorFinalPlan {
+            schema: plan.schema.clone(),
+            schema_before_group_by: plan.schema_before_group_by.clone(),
+            aggr_expr: plan.aggr_expr.clone(),
+            group_expr: plan.group_expr.clone(),
+            input: Arc::new(input),
+        }))
+    }
+}
+
+impl MetadataAggregationImpl<'_> {
+    async fn metadata_plan(&self, plan: &AggregatorFinalPlan) -> Result<Option<PlanNode>> {
+        let partial = match plan.input.as_ref() {
+            PlanNode::AggregatorPartial(partial) if partial.group_expr.is_empty() => partial,
+            _ => return Ok(None),
+        };
+        let (filters, source) = match partial.input.as_ref() {
+            PlanNode::ReadSource(source) => (vec![], source),
+            PlanNode::Filter(filter) => match filter.input.as_ref() {
+                PlanNode::ReadSource(source) => (vec![filter.predicate.clone()], source),
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        let table_info = match &source.source_info {
+            SourceInfo::TableSource(table_info) if source.tbl_args.is_none() => table_info,
+            _ => return Ok(None),
+        };
+        // sampled rows are never answered by the statistics of all the rows
+        if matches!(&source.push_downs, Some(extras) if extras.sample.is_some()) {
+            return Ok(None);
+        }
+
+        let schema = table_info.schema();
+        let mut aggregations = Vec::with_capacity(partial.aggr_expr.len());
+        for expr in &partial.aggr_expr {
+            let aggregation = match expr {
+                Expression::AggregateFunction {
+                    op,
+                    distinct: false,
+                    params,
+                    args,
+                } if params.is_empty() => match (op.to_lowercase().as_str(), &args[..]) {
+                    ("count", []) => MetadataAggregation::Count,
+                    ("min", [Expression::Column(name)]) => match schema.index_of(name) {
+                        Ok(index) => MetadataAggregation::Min(index),
+                        Err(_) => return Ok(None),
+                    },
+                    ("max", [Expression::Column(name)]) => match schema.index_of(name) {
+                        Ok(index) => MetadataAggregation::Max(index),
+                        Err(_) => return Ok(None),
+                    },
+                    _ => return Ok(None),
+                },
+                _ => return Ok(None),
+            };
+            aggregations.push(aggregation);
+        }
+
+        let table = self.ctx.build_table_from_source_plan(source)?;
+        let table = match table.as_any().downcast_ref::<FuseTable>() {
+            Some(table) => table,
+            None => return Ok(None),
+        };
+        let values = match table
+            .aggregate_by_metadata(self.ctx, &filters, &aggregations)
+            .await?
+        {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+
+        let input_schema = partial.input.schema();
+        let mut literals = Vec::with_capacity(values.len());
+        let mut projection = Vec::with_capacity(values.len());
+        for (expr, value) in partial.aggr_expr.iter().zip(values.into_iter()) {
+            let field = expr.to_data_field(&input_schema)?;
+            // e.g. min of a not null column over no rows
+            if value.is_null() && !field.is_nullable_or_null() {
+                return Ok(None);
+            }
+            let data_type = field.data_type().clone();
+            let literal = Expression::create_literal_with_type(value, data_type);
+            projection.push(literal.alias(&expr.column_name()));
+            literals.push(literal);
+        }
+
+        let dummy_table = self.ctx.get_table("system", "one").await?;
+        let dummy_source = dummy_table.read_plan(self.ctx.clone(), None).await?;
+        let new_plan = PlanBuilder::from(&PlanNode::ReadSource(dummy_source))
+            .expression(&literals, "Metadata Statistics")?
+            .project(&projection)?
+            .build()?;
+        Ok(Some(new_plan))
+    }
+}
+
+impl Optimizer for MetadataAggregationOptimizer {
+    fn name(&self) -> &str {
+        "MetadataAggregation"
+    }
+
+    fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
+        let mut visitor = MetadataAggregationImpl { ctx: &self.ctx };
+        visitor.rewrite_plan_node(plan)
+    }
+}
+
+impl MetadataAggregationOptimizer {
+    pub fn create(ctx: Arc<QueryContext>) -> Self {
+        MetadataAggregationOptimizer { ctx }
+    }
+}
@@ -112,6 +112,7 @@ impl PlanRewriter for TopNPushDownImpl {
                         filters: extras.filters.clone(),
                         limit: Some(new_limit),
                         order_by: self.get_sort_columns(plan.schema())?,
+                        sample: extras.sample,
                     })
                 }
                 None => {
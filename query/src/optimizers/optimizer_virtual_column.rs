@@ -0,0 +1,232 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::AggregatorFinalPlan;
+use common_planners::AggregatorPartialPlan;
+use common_planners::Expression;
+use common_planners::ExpressionRewriter;
+use common_planners::LimitByPlan;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+use common_planners::PlanVisitor;
+use common_planners::ReadDataSourcePlan;
+use common_planners::RequireColumnsVisitor;
+use common_planners::SourceInfo;
+
+use crate::optimizers::Optimizer;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::FuseTable;
+
+/// Reads the virtual columns of fuse tables instead of extracting the paths from the Variant
+/// columns they are defined on.
+///
+/// The virtual columns are only read if they are extracted from all the blocks to be read,
+/// the Variant columns are not read at all if they are used by the virtual columns only.
+pub struct VirtualColumnOptimizer {
+    ctx: Arc<QueryContext>,
+}
+
+/// Collects the sources and the expressions of a plan.
+#[derive(Default)]
+struct PlanCollector {
+    sources: Vec<ReadDataSourcePlan>,
+    exprs: Vec<Expression>,
+    has_subquery: bool,
+}
+
+impl PlanVisitor for PlanCollector {
+    fn visit_expr(&mut self, expr: &Expression) -> Result<()> {
+        if let Expression::Subquery { .. } | Expression::ScalarSubquery { .. } = expr {
+            self.has_subquery = true;
+        }
+        self.exprs.push(expr.clone());
+        Ok(())
+    }
+
+    fn visit_limit_by(&mut self, plan: &LimitByPlan) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref())?;
+        self.visit_exprs(&plan.limit_by)
+    }
+
+    fn visit_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<()> {
+        self.sources.push(plan.clone());
+        Ok(())
+    }
+}
+
+/// Replaces the paths of the virtual columns of `names` with the virtual columns.
+struct VirtualColumnRewriter<'a> {
+    names: &'a HashSet<String>,
+}
+
+impl ExpressionRewriter for VirtualColumnRewriter<'_> {
+    fn mutate_map_access(
+        &mut self,
+        name: &str,
+        args: Vec<Expression>,
+        _origin_expr: &Expression,
+    ) -> Result<Expression> {
+        match self.names.contains(name) {
+            true => Ok(Expression::Column(name.to_string())),
+            false => Ok(Expression::MapAccess {
+                name: name.to_string(),
+                args,
+            }),
+        }
+    }
+}
+
+struct VirtualColumnImpl {
+    names: HashSet<String>,
+    source: ReadDataSourcePlan,
+    before_group_by_schema: Option<DataSchemaRef>,
+}
+
+impl PlanRewriter for VirtualColumnImpl {
+    fn rewrite_expr(&mut self, _schema: &DataSchemaRef, expr: &Expression) -> Result<Expression> {
+        VirtualColumnRewriter { names: &self.names }.mutate(expr)
+    }
+
+    fn rewrite_read_data_source(&mut self, _: &ReadDataSourcePlan) -> Result<PlanNode> {
+        Ok(PlanNode::ReadSource(self.source.clone()))
+    }
+
+    fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        match self.before_group_by_schema {
+            Some(_) => Err(ErrorCode::LogicalError(
+                "Logical error: before group by schema must be None",
+            )),
+            None => {
+                self.before_group_by_schema = Some(new_input.schema());
+                let new_aggr_expr = self.rewrite_exprs(&new_input.schema(), &plan.aggr_expr)?;
+                let new_group_expr = self.rewrite_exprs(&new_input.schema(), &plan.group_expr)?;
+                PlanBuilder::from(&new_input)
+                    .aggregate_partial(&new_aggr_expr, &new_group_expr)?
+                    .build()
+            }
+        }
+    }
+
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+
+        match self.before_group_by_schema.take() {
+            None => Err(ErrorCode::LogicalError(
+                "Logical error: before group by schema must be Some",
+            )),
+            Some(schema_before_group_by) => {
+                let new_aggr_expr = self.rewrite_exprs(&new_input.schema(), &plan.aggr_expr)?;
+                let new_group_expr = self.rewrite_exprs(&new_input.schema(), &plan.group_expr)?;
+                PlanBuilder::from(&new_input)
+                    .aggregate_final(schema_before_group_by, &new_aggr_expr, &new_group_expr)?
+                    .build()
+            }
+        }
+    }
+}
+
+impl VirtualColumnOptimizer {
+    pub fn create(ctx: Arc<QueryContext>) -> Self {
+        VirtualColumnOptimizer { ctx }
+    }
+
+    /// Columns required by the expressions, after the paths of `names` are replaced.
+    fn required_columns(exprs: &[Expression], names: &HashSet<String>) -> Result<HashSet<String>> {
+        let mut columns = HashSet::new();
+        for expr in exprs {
+            let expr = VirtualColumnRewriter { names }.mutate(expr)?;
+            columns.extend(RequireColumnsVisitor::collect_columns_from_expr(&expr)?);
+        }
+        Ok(columns)
+    }
+
+    async fn virtual_columns_plan(&self, plan: &PlanNode) -> Result<Option<PlanNode>> {
+        let mut collector = PlanCollector::default();
+        collector.visit_plan_node(plan)?;
+        if collector.sources.len() != 1 || collector.has_subquery {
+            return Ok(None);
+        }
+        let source = &collector.sources[0];
+        let virtual_columns = match &source.source_info {
+            SourceInfo::TableSource(table_info) if source.tbl_args.is_none() => {
+                FuseTable::virtual_columns(table_info)?
+            }
+            _ => return Ok(None),
+        };
+        if virtual_columns.is_empty() {
+            return Ok(None);
+        }
+
+        let names = virtual_columns.into_keys().collect::<HashSet<_>>();
+        let required = Self::required_columns(&collector.exprs, &names)?;
+        let candidates = names
+            .into_iter()
+            .filter(|name| required.contains(name))
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return Ok(None);
+        }
+
+        let table = self.ctx.build_table_from_source_plan(source)?;
+        let table = FuseTable::try_from_table(table.as_ref())?;
+        let mut materialized = table
+            .materialized_virtual_columns(self.ctx.as_ref(), source, &candidates)
+            .await?;
+        if materialized.is_empty() {
+            return Ok(None);
+        }
+        materialized.sort();
+
+        // the Variant columns are not read if only the virtual columns are required
+        let names = materialized.iter().cloned().collect::<HashSet<_>>();
+        let required = Self::required_columns(&collector.exprs, &names)?;
+        let schema = source.source_info.schema();
+        let projection = source
+            .projections()
+            .into_iter()
+            .filter(|idx| required.contains(schema.field(*idx).name()))
+            .collect::<Vec<_>>();
+        let source = table
+            .virtual_columns_source_plan(self.ctx.clone(), source, projection, &materialized)
+            .await?;
+
+        let mut rewriter = VirtualColumnImpl {
+            names,
+            source,
+            before_group_by_schema: None,
+        };
+        Ok(Some(rewriter.rewrite_plan_node(plan)?))
+    }
+}
+
+impl Optimizer for VirtualColumnOptimizer {
+    fn name(&self) -> &str {
+        "VirtualColumn"
+    }
+
+    fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
+        match futures::executor::block_on(self.virtual_columns_plan(plan))? {
+            Some(new_plan) => Ok(new_plan),
+            None => Ok(plan.clone()),
+        }
+    }
+}
@@ -24,6 +24,8 @@ mod pipe;
 mod pipeline;
 mod pipeline_builder;
 pub mod processors;
+mod profile;
+mod spiller;
 mod unsafe_cell_wrap;
 
 pub use pipe::NewPipe;
@@ -32,3 +34,13 @@ pub use pipe::SourcePipeBuilder;
 pub use pipe::TransformPipeBuilder;
 pub use pipeline::NewPipeline;
 pub use pipeline_builder::QueryPipelineBuilder;
+pub use profile::PipeProfile;
+pub use profile::PipeProfileValues;
+pub use profile::PipelineSampler;
+pub use profile::PlanNodeProfiles;
+pub use profile::ProfilingProcessor;
+pub use profile::SpeedscopeFile;
+pub use profile::SpeedscopeFrame;
+pub use profile::SpeedscopeProfile;
+pub use profile::SpeedscopeShared;
+pub use spiller::Spiller;
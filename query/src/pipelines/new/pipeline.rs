@@ -23,6 +23,8 @@ use crate::pipelines::new::processors::port::InputPort;
 use crate::pipelines::new::processors::port::OutputPort;
 use crate::pipelines::new::processors::processor::ProcessorPtr;
 use crate::pipelines::new::processors::ResizeProcessor;
+use crate::pipelines::new::profile::PipeProfile;
+use crate::pipelines::new::profile::ProfilingProcessor;
 
 /// The struct of new pipeline
 ///                                                                              +----------+
@@ -154,4 +156,46 @@ impl NewPipeline {
             }
         }
     }
+
+    /// Wrap all the processors to collect the metrics of each pipe, used by EXPLAIN ANALYZE.
+    /// The returned profiles are in the same order as the pipes.
+    pub fn profile(&mut self) -> Vec<Arc<PipeProfile>> {
+        let mut profiles = Vec::with_capacity(self.pipes.len());
+        for pipe in &mut self.pipes {
+            let profile = match pipe {
+                NewPipe::SimplePipe {
+                    processors,
+                    outputs_port,
+                    ..
+                } => {
+                    let name = match processors.first() {
+                        None => "EmptyPipe",
+                        Some(processor) => unsafe { processor.name() },
+                    };
+                    let profile = PipeProfile::create(name, processors.len());
+                    for processor in processors.iter_mut() {
+                        *processor = ProfilingProcessor::create(processor.clone(), profile.clone());
+                    }
+                    for output_port in outputs_port.iter() {
+                        unsafe { output_port.set_profile(profile.clone()) };
+                    }
+                    profile
+                }
+                NewPipe::ResizePipe {
+                    processor,
+                    outputs_port,
+                    ..
+                } => {
+                    let profile = PipeProfile::create(unsafe { processor.name() }, 1);
+                    *processor = ProfilingProcessor::create(processor.clone(), profile.clone());
+                    for output_port in outputs_port.iter() {
+                        unsafe { output_port.set_profile(profile.clone()) };
+                    }
+                    profile
+                }
+            };
+            profiles.push(profile);
+        }
+        profiles
+    }
 }
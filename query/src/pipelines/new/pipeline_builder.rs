@@ -46,6 +46,9 @@ use crate::pipelines::new::processors::TransformLimit;
 use crate::pipelines::new::processors::TransformLimitBy;
 use crate::pipelines::new::processors::TransformSortMerge;
 use crate::pipelines::new::processors::TransformSortPartial;
+use crate::pipelines::new::processors::TransformSortSpill;
+use crate::pipelines::new::PlanNodeProfiles;
+use crate::pipelines::new::Spiller;
 use crate::pipelines::transforms::get_sort_descriptions;
 use crate::sessions::QueryContext;
 /// Builder for query pipeline
@@ -58,6 +61,8 @@ pub struct QueryPipelineBuilder {
     pipeline: NewPipeline,
     limit: Option<usize>,
     offset: usize,
+    // The address of the plan node which built each pipe.
+    pipe_owners: Vec<usize>,
 }
 
 impl QueryPipelineBuilder {
@@ -68,20 +73,40 @@ impl QueryPipelineBuilder {
             pipeline: NewPipeline::create(),
             limit: None,
             offset: 0,
+            pipe_owners: vec![],
         }
     }
     /// The core of generating the pipeline
     /// It will recursively visit the entire plan tree, and create a `SimplePipe` for each node,
     /// adding it to the pipeline
-    pub fn finalize(mut self, plan: &SelectPlan) -> Result<NewPipeline> {
+    pub fn finalize(self, plan: &SelectPlan) -> Result<NewPipeline> {
+        let (pipeline, _) = self.finalize_with_profiles(plan)?;
+        Ok(pipeline)
+    }
+
+    /// Same as `finalize`, but also returns the metrics of each pipe collected when executing
+    /// the pipeline, attributed to the plan node which built the pipe. The metrics are also
+    /// registered to the query context, where the pipeline profiling samples them.
+    pub fn finalize_with_profiles(
+        mut self,
+        plan: &SelectPlan,
+    ) -> Result<(NewPipeline, PlanNodeProfiles)> {
         self.visit_select(plan)?;
-        Ok(self.pipeline)
+
+        let pipe_profiles = self.pipeline.profile();
+        self.ctx.add_pipeline_profiles(pipe_profiles.clone());
+
+        let mut profiles = PlanNodeProfiles::default();
+        for (owner, profile) in self.pipe_owners.iter().zip(pipe_profiles) {
+            profiles.add(*owner, profile);
+        }
+        Ok((self.pipeline, profiles))
     }
 }
 
 impl PlanVisitor for QueryPipelineBuilder {
     fn visit_plan_node(&mut self, node: &PlanNode) -> Result<()> {
-        match node {
+        let res = match node {
             PlanNode::Projection(n) => self.visit_projection(n),
             PlanNode::Expression(n) => self.visit_expression(n),
             PlanNode::AggregatorPartial(n) => self.visit_aggregate_partial(n),
@@ -95,23 +120,36 @@ impl PlanVisitor for QueryPipelineBuilder {
             PlanNode::Select(n) => self.visit_select(n),
             PlanNode::SubQueryExpression(n) => self.visit_sub_queries_sets(n),
             _ => Err(ErrorCode::UnImplement("")),
-        }
+        };
+
+        // The pipes of the inputs are already owned, the rest are built by this node.
+        let owner = node as *const PlanNode as usize;
+        self.pipe_owners.resize(self.pipeline.pipes.len(), owner);
+        res
     }
 
     fn visit_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<()> {
         self.visit_plan_node(&plan.input)?;
 
         let aggregator_params = AggregatorParams::try_create_partial(plan)?;
+        let settings = self.ctx.get_settings();
+        let sample_rows = settings.get_partial_group_by_sample_rows()? as usize;
+        let memory_tracker = self.ctx.get_memory_tracker();
         self.pipeline
             .add_transform(|transform_input_port, transform_output_port| {
-                TransformAggregator::try_create_partial(
+                let mut transform_params = AggregatorTransformParams::try_create(
                     transform_input_port.clone(),
                     transform_output_port.clone(),
-                    AggregatorTransformParams::try_create(
-                        transform_input_port,
-                        transform_output_port,
-                        &aggregator_params,
-                    )?,
+                    &aggregator_params,
+                )?;
+                transform_params.memory_tracker =
+                    Some(memory_tracker.create_child("AggregatorPartialTransform"));
+                transform_params.partial_sample_rows = sample_rows;
+
+                TransformAggregator::try_create_partial(
+                    transform_input_port,
+                    transform_output_port,
+                    transform_params,
                 )
             })
     }
@@ -121,16 +159,28 @@ impl PlanVisitor for QueryPipelineBuilder {
 
         self.pipeline.resize(1)?;
         let aggregator_params = AggregatorParams::try_create_final(plan)?;
+        let spill_threshold = self.ctx.get_settings().get_group_by_spill_threshold()? as usize;
+        let ctx = self.ctx.clone();
+        let memory_tracker = self.ctx.get_memory_tracker();
         self.pipeline
             .add_transform(|transform_input_port, transform_output_port| {
-                TransformAggregator::try_create_final(
+                let mut transform_params = AggregatorTransformParams::try_create(
                     transform_input_port.clone(),
                     transform_output_port.clone(),
-                    AggregatorTransformParams::try_create(
-                        transform_input_port,
-                        transform_output_port,
-                        &aggregator_params,
-                    )?,
+                    &aggregator_params,
+                )?;
+                transform_params.memory_tracker =
+                    Some(memory_tracker.create_child("AggregatorFinalTransform"));
+
+                if spill_threshold != 0 {
+                    transform_params.spiller = Some(Spiller::try_create(ctx.clone(), "group_by")?);
+                    transform_params.spill_threshold = spill_threshold;
+                }
+
+                TransformAggregator::try_create_final(
+                    transform_input_port,
+                    transform_output_port,
+                    transform_params,
                 )
             })
     }
@@ -253,18 +303,47 @@ impl PlanVisitor for QueryPipelineBuilder {
                 )
             })?;
 
+        let settings = self.ctx.get_settings();
+        let spill_threshold = settings.get_sort_spill_threshold()? as usize;
+        if spill_threshold != 0 {
+            // processor1 sorted block --
+            //                             \
+            // processor2 sorted block ----> processor  --> spill sorted runs --> merge the runs
+            //                             /
+            // processor3 sorted block --
+            let ctx = self.ctx.clone();
+            let max_block_size = settings.get_max_block_size()? as usize;
+            self.pipeline.resize(1)?;
+            return self
+                .pipeline
+                .add_transform(|transform_input_port, transform_output_port| {
+                    TransformSortSpill::try_create(
+                        transform_input_port,
+                        transform_output_port,
+                        Spiller::try_create(ctx.clone(), "sort")?,
+                        spill_threshold,
+                        rows_limit,
+                        max_block_size,
+                        get_sort_descriptions(&plan.schema, &plan.order_by)?,
+                        Some(ctx.get_memory_tracker().create_child("SortSpillTransform")),
+                    )
+                });
+        }
+
         // processor 1: [sorted blocks ...] ---> merge to one sorted block
         // processor 2: [sorted blocks ...] ---> merge to one sorted block
         // processor 3: [sorted blocks ...] ---> merge to one sorted block
+        let memory_tracker = self.ctx.get_memory_tracker();
         self.pipeline
             .add_transform(|transform_input_port, transform_output_port| {
-                TransformSortMerge::try_create(
+                TransformSortMerge::try_create_with_memory_tracker(
                     transform_input_port,
                     transform_output_port,
                     SortMergeCompactor::new(
                         rows_limit,
                         get_sort_descriptions(&plan.schema, &plan.order_by)?,
                     ),
+                    Some(memory_tracker.create_child("SortMergeTransform")),
                 )
             })?;
 
@@ -276,13 +355,14 @@ impl PlanVisitor for QueryPipelineBuilder {
         self.pipeline.resize(1)?;
         self.pipeline
             .add_transform(|transform_input_port, transform_output_port| {
-                TransformSortMerge::try_create(
+                TransformSortMerge::try_create_with_memory_tracker(
                     transform_input_port,
                     transform_output_port,
                     SortMergeCompactor::new(
                         rows_limit,
                         get_sort_descriptions(&plan.schema, &plan.order_by)?,
                     ),
+                    Some(memory_tracker.create_child("SortMergeTransform")),
                 )
             })
     }
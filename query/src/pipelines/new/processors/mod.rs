@@ -51,6 +51,7 @@ pub use transforms::ExpressionTransform;
 pub use transforms::ProjectionTransform;
 pub use transforms::SortMergeCompactor;
 pub use transforms::SubQueriesPuller;
+pub use transforms::Transform;
 pub use transforms::TransformAddOn;
 pub use transforms::TransformAggregator;
 pub use transforms::TransformBlockCompact;
@@ -62,5 +63,8 @@ pub use transforms::TransformFilter;
 pub use transforms::TransformHaving;
 pub use transforms::TransformLimit;
 pub use transforms::TransformLimitBy;
+pub use transforms::TransformSample;
 pub use transforms::TransformSortMerge;
 pub use transforms::TransformSortPartial;
+pub use transforms::TransformSortSpill;
+pub use transforms::Transformer;
@@ -20,6 +20,7 @@ use common_datablocks::DataBlock;
 use common_exception::Result;
 
 use crate::pipelines::new::processors::UpdateTrigger;
+use crate::pipelines::new::profile::PipeProfile;
 use crate::pipelines::new::unsafe_cell_wrap::UnSafeCellWrap;
 
 const HAS_DATA: usize = 0b1;
@@ -191,6 +192,7 @@ impl InputPort {
 pub struct OutputPort {
     shared: UnSafeCellWrap<Arc<SharedStatus>>,
     update_trigger: UnSafeCellWrap<*mut UpdateTrigger>,
+    profile: UnSafeCellWrap<Option<Arc<PipeProfile>>>,
 }
 
 impl OutputPort {
@@ -198,12 +200,17 @@ impl OutputPort {
         Arc::new(OutputPort {
             shared: UnSafeCellWrap::create(SharedStatus::create()),
             update_trigger: UnSafeCellWrap::create(std::ptr::null_mut()),
+            profile: UnSafeCellWrap::create(None),
         })
     }
 
     #[inline(always)]
     pub fn push_data(&self, data: Result<DataBlock>) {
         unsafe {
+            if let (Some(profile), Ok(block)) = (self.profile.as_ref(), &data) {
+                profile.add_output(block);
+            }
+
             UpdateTrigger::update_output(&self.update_trigger);
 
             let data = Box::into_raw(Box::new(SharedData(data)));
@@ -246,6 +253,13 @@ impl OutputPort {
     pub unsafe fn set_trigger(&self, update_trigger: *mut UpdateTrigger) {
         self.update_trigger.set_value(update_trigger)
     }
+
+    /// # Safety
+    ///
+    /// Method is thread unsafe and require thread safe call
+    pub unsafe fn set_profile(&self, profile: Arc<PipeProfile>) {
+        self.profile.set_value(Some(profile))
+    }
 }
 
 /// Connect input and output ports.
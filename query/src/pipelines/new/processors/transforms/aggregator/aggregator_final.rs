@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 use common_datablocks::DataBlock;
 use common_datablocks::HashMethod;
+use common_datablocks::HashMethodDictionarySerializer;
 use common_datablocks::HashMethodKeysU16;
 use common_datablocks::HashMethodKeysU32;
 use common_datablocks::HashMethodKeysU64;
@@ -49,6 +50,8 @@ pub type SingleStringFinalAggregator<const HAS_AGG: bool> =
 
 pub type SerializerFinalAggregator<const HAS_AGG: bool> =
     FinalAggregator<HAS_AGG, HashMethodSerializer>;
+pub type DictionarySerializerFinalAggregator<const HAS_AGG: bool> =
+    FinalAggregator<HAS_AGG, HashMethodDictionarySerializer>;
 
 pub struct FinalAggregator<
     const HAS_AGG: bool,
@@ -208,6 +211,15 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
             }
         }
     }
+
+    fn reset(&mut self) -> Result<()> {
+        self.reset_state();
+        Ok(())
+    }
+
+    fn memory_size(&self) -> usize {
+        self.state.memory_size()
+    }
 }
 
 impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
@@ -244,6 +256,15 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
             }
         }
     }
+
+    fn reset(&mut self) -> Result<()> {
+        self.reset_state();
+        Ok(())
+    }
+
+    fn memory_size(&self) -> usize {
+        self.state.memory_size()
+    }
 }
 
 impl<const FINAL: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send>
@@ -282,6 +303,19 @@ impl<const FINAL: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Sen
             self.states_dropped = true;
         }
     }
+
+    /// Drops the groups, so the aggregator can aggregate another partition of the groups.
+    fn reset_state(&mut self) {
+        self.drop_states();
+        self.state = self.method.aggregate_state();
+        self.temp_place = if self.params.aggregate_functions.is_empty() {
+            0.into()
+        } else {
+            self.state.alloc_layout2(&self.params)
+        };
+        self.is_generated = false;
+        self.states_dropped = false;
+    }
 }
 
 impl<const FINAL: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Drop
@@ -28,6 +28,8 @@ use common_planners::Expression;
 
 use crate::pipelines::new::processors::port::InputPort;
 use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::Spiller;
+use crate::sessions::QueryMemoryTracker;
 
 pub struct AggregatorParams {
     pub schema: DataSchemaRef,
@@ -163,6 +165,13 @@ pub struct AggregatorTransformParams {
     pub transform_input_port: Arc<InputPort>,
     pub transform_output_port: Arc<OutputPort>,
     pub aggregator_params: Arc<AggregatorParams>,
+    // Spill the partial aggregated blocks of the final aggregation once they reach the threshold.
+    pub spiller: Option<Spiller>,
+    pub spill_threshold: usize,
+    // Reserve the memory of the aggregated groups from the memory of the query.
+    pub memory_tracker: Option<Arc<QueryMemoryTracker>>,
+    // Skip the partial aggregation if the groups are nearly unique in the sampled rows.
+    pub partial_sample_rows: usize,
 }
 
 impl AggregatorTransformParams {
@@ -181,6 +190,10 @@ impl AggregatorTransformParams {
             transform_input_port,
             transform_output_port,
             aggregator_params: aggregator_params.clone(),
+            spiller: None,
+            spill_threshold: 0,
+            memory_tracker: None,
+            partial_sample_rows: 0,
         })
     }
 }
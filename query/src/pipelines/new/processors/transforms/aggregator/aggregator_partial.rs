@@ -17,6 +17,7 @@ use std::sync::Arc;
 use bytes::BytesMut;
 use common_datablocks::DataBlock;
 use common_datablocks::HashMethod;
+use common_datablocks::HashMethodDictionarySerializer;
 use common_datablocks::HashMethodKeysU16;
 use common_datablocks::HashMethodKeysU32;
 use common_datablocks::HashMethodKeysU64;
@@ -48,6 +49,8 @@ pub type KeysU64PartialAggregator<const HAS_AGG: bool> =
     PartialAggregator<HAS_AGG, HashMethodKeysU64>;
 pub type SerializerPartialAggregator<const HAS_AGG: bool> =
     PartialAggregator<HAS_AGG, HashMethodSerializer>;
+pub type DictionarySerializerPartialAggregator<const HAS_AGG: bool> =
+    PartialAggregator<HAS_AGG, HashMethodDictionarySerializer>;
 pub type SingleStringPartialAggregator<const HAS_AGG: bool> =
     PartialAggregator<HAS_AGG, HashMethodSingleString>;
 
@@ -222,15 +225,34 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
     fn consume(&mut self, block: DataBlock) -> Result<()> {
         // 1.1 and 1.2.
         let group_columns = Self::group_columns(&self.params.group_columns_name, &block)?;
-        let group_keys = self.method.build_keys(&group_columns, block.num_rows())?;
+        let (group_keys, indices) = self
+            .method
+            .build_distinct_keys(&group_columns, block.num_rows())?;
 
         let places = Self::lookup_state(&self.params, group_keys, &mut self.state);
+        let places = match indices {
+            None => places,
+            Some(indices) => indices.iter().map(|i| places[*i as usize]).collect(),
+        };
         Self::execute(&self.params, &block, &places)
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
         self.generate_data()
     }
+
+    fn memory_size(&self) -> usize {
+        self.state.memory_size()
+    }
+
+    fn num_groups(&self) -> usize {
+        self.state.len()
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.reset_state();
+        Ok(())
+    }
 }
 
 impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
@@ -241,7 +263,9 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
     fn consume(&mut self, block: DataBlock) -> Result<()> {
         // 1.1 and 1.2.
         let group_columns = Self::group_columns(&self.params.group_columns_name, &block)?;
-        let group_keys = self.method.build_keys(&group_columns, block.num_rows())?;
+        let (group_keys, _) = self
+            .method
+            .build_distinct_keys(&group_columns, block.num_rows())?;
         Self::lookup_key(group_keys, &mut self.state);
         Ok(())
     }
@@ -266,6 +290,19 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
             }
         }
     }
+
+    fn memory_size(&self) -> usize {
+        self.state.memory_size()
+    }
+
+    fn num_groups(&self) -> usize {
+        self.state.len()
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.reset_state();
+        Ok(())
+    }
 }
 
 impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method>>
@@ -299,6 +336,13 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method>>
             self.states_dropped = true;
         }
     }
+
+    fn reset_state(&mut self) {
+        self.drop_states();
+        self.state = self.method.aggregate_state();
+        self.is_generated = false;
+        self.states_dropped = false;
+    }
 }
 
 impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method>> Drop
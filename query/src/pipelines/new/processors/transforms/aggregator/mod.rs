@@ -17,6 +17,7 @@ mod aggregator_params;
 mod aggregator_partial;
 mod aggregator_single_key;
 
+pub use aggregator_final::DictionarySerializerFinalAggregator;
 pub use aggregator_final::FinalAggregator;
 pub use aggregator_final::KeysU16FinalAggregator;
 pub use aggregator_final::KeysU32FinalAggregator;
@@ -26,6 +27,7 @@ pub use aggregator_final::SerializerFinalAggregator;
 pub use aggregator_final::SingleStringFinalAggregator;
 pub use aggregator_params::AggregatorParams;
 pub use aggregator_params::AggregatorTransformParams;
+pub use aggregator_partial::DictionarySerializerPartialAggregator;
 pub use aggregator_partial::KeysU16PartialAggregator;
 pub use aggregator_partial::KeysU32PartialAggregator;
 pub use aggregator_partial::KeysU64PartialAggregator;
@@ -16,6 +16,7 @@ mod aggregator;
 mod transform;
 mod transform_addon;
 mod transform_aggregator;
+mod transform_aggregator_spill;
 mod transform_block_compact;
 mod transform_cast_schema;
 mod transform_compact;
@@ -25,11 +26,15 @@ mod transform_expression;
 mod transform_filter;
 mod transform_limit;
 mod transform_limit_by;
+mod transform_sample;
 mod transform_sort_merge;
 mod transform_sort_partial;
+mod transform_sort_spill;
 
 pub use aggregator::AggregatorParams;
 pub use aggregator::AggregatorTransformParams;
+pub use transform::Transform;
+pub use transform::Transformer;
 pub use transform_addon::TransformAddOn;
 pub use transform_aggregator::TransformAggregator;
 pub use transform_block_compact::BlockCompactor;
@@ -46,6 +51,8 @@ pub use transform_filter::TransformFilter;
 pub use transform_filter::TransformHaving;
 pub use transform_limit::TransformLimit;
 pub use transform_limit_by::TransformLimitBy;
+pub use transform_sample::TransformSample;
 pub use transform_sort_merge::SortMergeCompactor;
 pub use transform_sort_merge::TransformSortMerge;
 pub use transform_sort_partial::TransformSortPartial;
+pub use transform_sort_spill::TransformSortSpill;
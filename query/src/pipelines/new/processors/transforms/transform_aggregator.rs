@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 use common_datablocks::DataBlock;
@@ -19,6 +20,7 @@ use common_datablocks::HashMethodKind;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use super::transform_aggregator_spill::TransformAggregatorSpill;
 use crate::pipelines::new::processors::port::InputPort;
 use crate::pipelines::new::processors::port::OutputPort;
 use crate::pipelines::new::processors::processor::Event;
@@ -26,6 +28,8 @@ use crate::pipelines::new::processors::processor::ProcessorPtr;
 use crate::pipelines::new::processors::transforms::aggregator::*;
 use crate::pipelines::new::processors::AggregatorTransformParams;
 use crate::pipelines::new::processors::Processor;
+use crate::pipelines::new::Spiller;
+use crate::sessions::QueryMemoryTracker;
 
 pub struct TransformAggregator;
 
@@ -36,78 +40,124 @@ impl TransformAggregator {
         transform_params: AggregatorTransformParams,
     ) -> Result<ProcessorPtr> {
         let aggregator_params = transform_params.aggregator_params;
+        let memory_tracker = transform_params.memory_tracker;
 
         if aggregator_params.group_columns_name.is_empty() {
             return AggregatorTransform::create(
                 input_port,
                 output_port,
                 FinalSingleStateAggregator::try_create(&aggregator_params)?,
+                memory_tracker,
             );
         }
 
+        // The group keys follow the states of the aggregate functions in the partial blocks.
+        let keys_index = aggregator_params.aggregate_functions.len();
+        let spilling = transform_params
+            .spiller
+            .map(|spiller| (spiller, transform_params.spill_threshold, keys_index));
+
         match aggregator_params.aggregate_functions.is_empty() {
             true => match transform_params.method {
-                HashMethodKind::KeysU8(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU8(method) => Self::create_final(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU8FinalAggregator::<false>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
                 ),
-                HashMethodKind::KeysU16(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU16(method) => Self::create_final(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU16FinalAggregator::<false>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
                 ),
-                HashMethodKind::KeysU32(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU32(method) => Self::create_final(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU32FinalAggregator::<false>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
                 ),
-                HashMethodKind::KeysU64(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU64(method) => Self::create_final(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU64FinalAggregator::<false>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
                 ),
-                HashMethodKind::SingleString(method) => AggregatorTransform::create(
+                HashMethodKind::SingleString(method) => Self::create_final(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     SingleStringFinalAggregator::<false>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
                 ),
-                HashMethodKind::Serializer(method) => AggregatorTransform::create(
+                HashMethodKind::Serializer(method) => Self::create_final(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     SerializerFinalAggregator::<false>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
+                ),
+                HashMethodKind::DictionarySerializer(method) => Self::create_final(
+                    transform_params.transform_input_port,
+                    transform_params.transform_output_port,
+                    DictionarySerializerFinalAggregator::<false>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
                 ),
             },
             false => match transform_params.method {
-                HashMethodKind::KeysU8(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU8(method) => Self::create_final(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU8FinalAggregator::<true>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
                 ),
-                HashMethodKind::KeysU16(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU16(method) => Self::create_final(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU16FinalAggregator::<true>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
                 ),
-                HashMethodKind::KeysU32(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU32(method) => Self::create_final(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU32FinalAggregator::<true>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
                 ),
-                HashMethodKind::KeysU64(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU64(method) => Self::create_final(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU64FinalAggregator::<true>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
                 ),
-                HashMethodKind::SingleString(method) => AggregatorTransform::create(
+                HashMethodKind::SingleString(method) => Self::create_final(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     SingleStringFinalAggregator::<true>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
                 ),
-                HashMethodKind::Serializer(method) => AggregatorTransform::create(
+                HashMethodKind::Serializer(method) => Self::create_final(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     SerializerFinalAggregator::<true>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
+                ),
+                HashMethodKind::DictionarySerializer(method) => Self::create_final(
+                    transform_params.transform_input_port,
+                    transform_params.transform_output_port,
+                    DictionarySerializerFinalAggregator::<true>::create(method, aggregator_params),
+                    spilling,
+                    memory_tracker,
                 ),
             },
         }
@@ -119,82 +169,154 @@ impl TransformAggregator {
         transform_params: AggregatorTransformParams,
     ) -> Result<ProcessorPtr> {
         let aggregator_params = transform_params.aggregator_params;
+        let memory_tracker = transform_params.memory_tracker;
+        let sample_rows = transform_params.partial_sample_rows;
 
         if aggregator_params.group_columns_name.is_empty() {
             return AggregatorTransform::create(
                 input_port,
                 output_port,
                 PartialSingleStateAggregator::try_create(&aggregator_params)?,
+                memory_tracker,
             );
         }
 
         match aggregator_params.aggregate_functions.is_empty() {
             true => match transform_params.method {
-                HashMethodKind::KeysU8(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU8(method) => AggregatorTransform::create_partial(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU8PartialAggregator::<false>::create(method, aggregator_params),
+                    memory_tracker,
+                    sample_rows,
                 ),
-                HashMethodKind::KeysU16(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU16(method) => AggregatorTransform::create_partial(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU16PartialAggregator::<false>::create(method, aggregator_params),
+                    memory_tracker,
+                    sample_rows,
                 ),
-                HashMethodKind::KeysU32(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU32(method) => AggregatorTransform::create_partial(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU32PartialAggregator::<false>::create(method, aggregator_params),
+                    memory_tracker,
+                    sample_rows,
                 ),
-                HashMethodKind::KeysU64(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU64(method) => AggregatorTransform::create_partial(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU64PartialAggregator::<false>::create(method, aggregator_params),
+                    memory_tracker,
+                    sample_rows,
                 ),
-                HashMethodKind::SingleString(method) => AggregatorTransform::create(
+                HashMethodKind::SingleString(method) => AggregatorTransform::create_partial(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     SingleStringPartialAggregator::<false>::create(method, aggregator_params),
+                    memory_tracker,
+                    sample_rows,
                 ),
-                HashMethodKind::Serializer(method) => AggregatorTransform::create(
+                HashMethodKind::Serializer(method) => AggregatorTransform::create_partial(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     SerializerPartialAggregator::<false>::create(method, aggregator_params),
+                    memory_tracker,
+                    sample_rows,
                 ),
+                HashMethodKind::DictionarySerializer(method) => {
+                    AggregatorTransform::create_partial(
+                        transform_params.transform_input_port,
+                        transform_params.transform_output_port,
+                        DictionarySerializerPartialAggregator::<false>::create(
+                            method,
+                            aggregator_params,
+                        ),
+                        memory_tracker,
+                        sample_rows,
+                    )
+                }
             },
             false => match transform_params.method {
-                HashMethodKind::KeysU8(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU8(method) => AggregatorTransform::create_partial(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU8PartialAggregator::<true>::create(method, aggregator_params),
+                    memory_tracker,
+                    sample_rows,
                 ),
-                HashMethodKind::KeysU16(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU16(method) => AggregatorTransform::create_partial(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU16PartialAggregator::<true>::create(method, aggregator_params),
+                    memory_tracker,
+                    sample_rows,
                 ),
-                HashMethodKind::KeysU32(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU32(method) => AggregatorTransform::create_partial(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU32PartialAggregator::<true>::create(method, aggregator_params),
+                    memory_tracker,
+                    sample_rows,
                 ),
-                HashMethodKind::KeysU64(method) => AggregatorTransform::create(
+                HashMethodKind::KeysU64(method) => AggregatorTransform::create_partial(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     KeysU64PartialAggregator::<true>::create(method, aggregator_params),
+                    memory_tracker,
+                    sample_rows,
                 ),
-                HashMethodKind::SingleString(method) => AggregatorTransform::create(
+                HashMethodKind::SingleString(method) => AggregatorTransform::create_partial(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     SingleStringPartialAggregator::<true>::create(method, aggregator_params),
+                    memory_tracker,
+                    sample_rows,
                 ),
-                HashMethodKind::Serializer(method) => AggregatorTransform::create(
+                HashMethodKind::Serializer(method) => AggregatorTransform::create_partial(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
                     SerializerPartialAggregator::<true>::create(method, aggregator_params),
+                    memory_tracker,
+                    sample_rows,
                 ),
+                HashMethodKind::DictionarySerializer(method) => {
+                    AggregatorTransform::create_partial(
+                        transform_params.transform_input_port,
+                        transform_params.transform_output_port,
+                        DictionarySerializerPartialAggregator::<true>::create(
+                            method,
+                            aggregator_params,
+                        ),
+                        memory_tracker,
+                        sample_rows,
+                    )
+                }
             },
         }
     }
+
+    fn create_final<TAggregator: Aggregator + 'static>(
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        inner: TAggregator,
+        spilling: Option<(Spiller, usize, usize)>,
+        memory_tracker: Option<Arc<QueryMemoryTracker>>,
+    ) -> Result<ProcessorPtr> {
+        match spilling {
+            None => AggregatorTransform::create(input_port, output_port, inner, memory_tracker),
+            Some((spiller, threshold, keys_index)) => TransformAggregatorSpill::create(
+                input_port,
+                output_port,
+                inner,
+                spiller,
+                threshold,
+                keys_index,
+                memory_tracker,
+            ),
+        }
+    }
 }
 
 pub trait Aggregator: Sized + Send {
@@ -202,8 +324,27 @@ pub trait Aggregator: Sized + Send {
 
     fn consume(&mut self, data: DataBlock) -> Result<()>;
     fn generate(&mut self) -> Result<Option<DataBlock>>;
+
+    /// Drops the generated groups, required by the spilling of the final aggregation and the
+    /// skipping of the partial aggregation.
+    fn reset(&mut self) -> Result<()> {
+        Err(ErrorCode::UnImplement("The aggregator cannot be reset"))
+    }
+
+    /// The memory in bytes held by the aggregated groups, reserved from the memory tracker.
+    fn memory_size(&self) -> usize {
+        0
+    }
+
+    /// The number of the aggregated groups, by which the partial aggregation is skipped.
+    fn num_groups(&self) -> usize {
+        0
+    }
 }
 
+/// The partial aggregation is skipped once the groups are as many as this ratio of the rows.
+const PARTIAL_AGGREGATION_SKIP_RATIO: f64 = 0.8;
+
 enum AggregatorTransform<TAggregator: Aggregator> {
     ConsumeData(ConsumeState<TAggregator>),
     Generate(GenerateState<TAggregator>),
@@ -215,6 +356,20 @@ impl<TAggregator: Aggregator + 'static> AggregatorTransform<TAggregator> {
         input_port: Arc<InputPort>,
         output_port: Arc<OutputPort>,
         inner: TAggregator,
+        memory_tracker: Option<Arc<QueryMemoryTracker>>,
+    ) -> Result<ProcessorPtr> {
+        Self::create_partial(input_port, output_port, inner, memory_tracker, 0)
+    }
+
+    /// Once `sample_rows` rows are aggregated, if the groups are nearly as many as the rows, the
+    /// aggregation of the groups is left to the final aggregation, and the partial aggregation
+    /// sends the groups of each block at once instead of growing the hash table of them.
+    pub fn create_partial(
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        inner: TAggregator,
+        memory_tracker: Option<Arc<QueryMemoryTracker>>,
+        sample_rows: usize,
     ) -> Result<ProcessorPtr> {
         Ok(ProcessorPtr::create(Box::new(AggregatorTransform::<
             TAggregator,
@@ -224,6 +379,11 @@ impl<TAggregator: Aggregator + 'static> AggregatorTransform<TAggregator> {
                 input_port,
                 output_port,
                 input_data_block: None,
+                output_data_blocks: VecDeque::new(),
+                memory_tracker,
+                sample_rows,
+                consumed_rows: 0,
+                skipping: false,
             },
         ))))
     }
@@ -236,6 +396,7 @@ impl<TAggregator: Aggregator + 'static> AggregatorTransform<TAggregator> {
                     is_finished: false,
                     output_port: s.output_port,
                     output_data_block: None,
+                    memory_tracker: s.memory_tracker,
                 }))
             }
             _ => Err(ErrorCode::LogicalError("")),
@@ -269,6 +430,24 @@ impl<TAggregator: Aggregator + 'static> AggregatorTransform<TAggregator> {
     #[inline(always)]
     fn consume_event(&mut self) -> Result<Event> {
         if let AggregatorTransform::ConsumeData(state) = self {
+            if !state.output_data_blocks.is_empty() {
+                if state.output_port.is_finished() {
+                    state.input_port.finish();
+                    let mut temp_state = AggregatorTransform::Finished;
+                    std::mem::swap(self, &mut temp_state);
+                    return Ok(Event::Finished);
+                }
+
+                if !state.output_port.can_push() {
+                    state.input_port.set_not_need_data();
+                    return Ok(Event::NeedConsume);
+                }
+
+                let block = state.output_data_blocks.pop_front().unwrap();
+                state.output_port.push_data(Ok(block));
+                return Ok(Event::NeedConsume);
+            }
+
             if state.input_data_block.is_some() {
                 return Ok(Event::Sync);
             }
@@ -337,12 +516,39 @@ struct ConsumeState<TAggregator: Aggregator> {
     input_port: Arc<InputPort>,
     output_port: Arc<OutputPort>,
     input_data_block: Option<DataBlock>,
+    output_data_blocks: VecDeque<DataBlock>,
+    memory_tracker: Option<Arc<QueryMemoryTracker>>,
+    // Sample the rows to skip the partial aggregation, 0 to disable it.
+    sample_rows: usize,
+    consumed_rows: usize,
+    skipping: bool,
 }
 
 impl<TAggregator: Aggregator> ConsumeState<TAggregator> {
     pub fn consume(&mut self) -> Result<()> {
         if let Some(input_data) = self.input_data_block.take() {
+            let rows = input_data.num_rows();
             self.inner.consume(input_data)?;
+
+            if self.sample_rows != 0 && !self.skipping {
+                let sampled_rows = self.consumed_rows + rows;
+                if self.consumed_rows < self.sample_rows && sampled_rows >= self.sample_rows {
+                    let skip_groups = sampled_rows as f64 * PARTIAL_AGGREGATION_SKIP_RATIO;
+                    self.skipping = self.inner.num_groups() as f64 >= skip_groups;
+                }
+                self.consumed_rows = sampled_rows;
+            }
+
+            if self.skipping {
+                while let Some(block) = self.inner.generate()? {
+                    self.output_data_blocks.push_back(block);
+                }
+                self.inner.reset()?;
+            }
+
+            if let Some(memory_tracker) = &self.memory_tracker {
+                memory_tracker.try_update_usage(self.inner.memory_size())?;
+            }
         }
 
         Ok(())
@@ -354,6 +560,7 @@ struct GenerateState<TAggregator: Aggregator> {
     is_finished: bool,
     output_port: Arc<OutputPort>,
     output_data_block: Option<DataBlock>,
+    memory_tracker: Option<Arc<QueryMemoryTracker>>,
 }
 
 impl<TAggregator: Aggregator> GenerateState<TAggregator> {
@@ -362,6 +569,10 @@ impl<TAggregator: Aggregator> GenerateState<TAggregator> {
 
         if generate_data.is_none() {
             self.is_finished = true;
+
+            if let Some(memory_tracker) = &self.memory_tracker {
+                memory_tracker.try_update_usage(0)?;
+            }
         }
 
         self.output_data_block = generate_data;
@@ -0,0 +1,268 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodSerializer;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::Event;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::transforms::transform_aggregator::Aggregator;
+use crate::pipelines::new::processors::Processor;
+use crate::pipelines::new::Spiller;
+use crate::sessions::QueryMemoryTracker;
+
+const SPILL_PARTITIONS: usize = 16;
+
+/// Final aggregation which spills the partial aggregated blocks when they are too large.
+///
+/// The partial aggregated blocks are buffered until their size reaches the threshold, then they
+/// are partitioned by the hash of the group keys and spilled. Once the input is finished, the
+/// partitions are read back and aggregated one by one, so only the groups of one partition are
+/// kept in memory. The buffered blocks are spilled early if they exceed the memory of the query.
+///
+/// The partial aggregated groups of every hash method, the dictionary-encoded strings included,
+/// come in single-level hash tables, so the keys are hashed again here to be partitioned.
+pub struct TransformAggregatorSpill<TAggregator: Aggregator> {
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    inner: TAggregator,
+    state: State,
+    spiller: Spiller,
+    threshold: usize,
+    keys_index: usize,
+    memory_tracker: Option<Arc<QueryMemoryTracker>>,
+
+    buffered_blocks: Vec<DataBlock>,
+    buffered_bytes: usize,
+    partitions: Vec<Vec<String>>,
+    next_partition: usize,
+    output_blocks: VecDeque<DataBlock>,
+}
+
+enum State {
+    Consume,
+    // Partition the buffered blocks by the group keys.
+    Partition,
+    Spill(Vec<(usize, DataBlock)>),
+    // Read the next spilled partition back.
+    Restore,
+    Aggregate(Vec<DataBlock>),
+    Finished,
+}
+
+impl<TAggregator: Aggregator + 'static> TransformAggregatorSpill<TAggregator> {
+    pub fn create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        inner: TAggregator,
+        spiller: Spiller,
+        threshold: usize,
+        keys_index: usize,
+        memory_tracker: Option<Arc<QueryMemoryTracker>>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(TransformAggregatorSpill {
+            input,
+            output,
+            inner,
+            state: State::Consume,
+            spiller,
+            threshold,
+            keys_index,
+            memory_tracker,
+            buffered_blocks: vec![],
+            buffered_bytes: 0,
+            partitions: vec![],
+            next_partition: 0,
+            output_blocks: VecDeque::new(),
+        })))
+    }
+
+    fn partition(&mut self) -> Result<()> {
+        let blocks = std::mem::take(&mut self.buffered_blocks);
+        self.buffered_bytes = 0;
+        self.update_memory_usage(0)?;
+
+        let mut partitioned_blocks = Vec::with_capacity(blocks.len() * SPILL_PARTITIONS);
+        let method = HashMethodSerializer::default();
+        for block in blocks {
+            let keys = method.build_keys(&[block.column(self.keys_index)], block.num_rows())?;
+            let indices = keys
+                .iter()
+                .map(|key| {
+                    let mut hasher = DefaultHasher::new();
+                    key.hash(&mut hasher);
+                    (hasher.finish() % SPILL_PARTITIONS as u64) as usize
+                })
+                .collect::<Vec<_>>();
+
+            let scattered_blocks = DataBlock::scatter_block(&block, &indices, SPILL_PARTITIONS)?;
+            for (partition, block) in scattered_blocks.into_iter().enumerate() {
+                if block.num_rows() > 0 {
+                    partitioned_blocks.push((partition, block));
+                }
+            }
+        }
+
+        self.state = State::Spill(partitioned_blocks);
+        Ok(())
+    }
+
+    fn aggregate(&mut self, blocks: Vec<DataBlock>) -> Result<()> {
+        for block in blocks {
+            self.inner.consume(block)?;
+            // The reservation of the buffered blocks is taken over by the aggregated groups.
+            self.update_memory_usage(self.inner.memory_size())?;
+        }
+
+        while let Some(block) = self.inner.generate()? {
+            self.output_blocks.push_back(block);
+        }
+        self.update_memory_usage(0)?;
+
+        match self.partitions.is_empty() {
+            // Nothing is spilled.
+            true => self.state = State::Finished,
+            false => {
+                self.inner.reset()?;
+                self.state = State::Restore;
+            }
+        }
+        Ok(())
+    }
+
+    // Returns false if the memory of the query is exceeded.
+    fn reserve_memory(&self, size: usize) -> bool {
+        match &self.memory_tracker {
+            None => true,
+            Some(memory_tracker) => memory_tracker.try_alloc(size).is_ok(),
+        }
+    }
+
+    fn update_memory_usage(&self, usage: usize) -> Result<()> {
+        match &self.memory_tracker {
+            None => Ok(()),
+            Some(memory_tracker) => memory_tracker.try_update_usage(usage),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<TAggregator: Aggregator + 'static> Processor for TransformAggregatorSpill<TAggregator> {
+    fn name(&self) -> &'static str {
+        "AggregatorSpillTransform"
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output_blocks.is_empty() {
+            if self.output.can_push() {
+                let block = self.output_blocks.pop_front().unwrap();
+                self.output.push_data(Ok(block));
+            }
+            return Ok(Event::NeedConsume);
+        }
+
+        match &self.state {
+            State::Partition | State::Aggregate(_) => return Ok(Event::Sync),
+            State::Spill(_) => return Ok(Event::Async),
+            State::Restore => {
+                return match self.next_partition < self.partitions.len() {
+                    true => Ok(Event::Async),
+                    false => {
+                        self.output.finish();
+                        Ok(Event::Finished)
+                    }
+                };
+            }
+            State::Finished => {
+                self.output.finish();
+                return Ok(Event::Finished);
+            }
+            State::Consume => {}
+        }
+
+        if self.input.is_finished() {
+            self.state = match (self.buffered_blocks.is_empty(), self.partitions.is_empty()) {
+                (true, false) => State::Restore,
+                (false, false) => State::Partition,
+                (_, true) => State::Aggregate(std::mem::take(&mut self.buffered_blocks)),
+            };
+            return self.event();
+        }
+
+        if self.input.has_data() {
+            let block = self.input.pull_data().unwrap()?;
+            let mut memory_exceeded = false;
+            if block.num_rows() > 0 {
+                memory_exceeded = !self.reserve_memory(block.memory_size());
+                self.buffered_bytes += block.memory_size();
+                self.buffered_blocks.push(block);
+            }
+
+            if self.buffered_bytes >= self.threshold || memory_exceeded {
+                self.state = State::Partition;
+                return Ok(Event::Sync);
+            }
+        }
+
+        self.input.set_need_data();
+        Ok(Event::NeedData)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Consume) {
+            State::Partition => self.partition(),
+            State::Aggregate(blocks) => self.aggregate(blocks),
+            _ => Err(ErrorCode::LogicalError("State invalid. it's a bug.")),
+        }
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Consume) {
+            State::Spill(blocks) => {
+                let (partitions, blocks): (Vec<_>, Vec<_>) = blocks.into_iter().unzip();
+                let locations = self.spiller.spill(blocks).await?;
+
+                self.partitions.resize(SPILL_PARTITIONS, vec![]);
+                for (partition, location) in partitions.into_iter().zip(locations.into_iter()) {
+                    self.partitions[partition].push(location);
+                }
+                Ok(())
+            }
+            State::Restore => {
+                let locations = std::mem::take(&mut self.partitions[self.next_partition]);
+                self.next_partition += 1;
+                let blocks = self.spiller.restore(&locations).await?;
+                self.state = State::Aggregate(blocks);
+                Ok(())
+            }
+            _ => Err(ErrorCode::LogicalError("State invalid. it's a bug.")),
+        }
+    }
+}
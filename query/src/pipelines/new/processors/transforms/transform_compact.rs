@@ -24,10 +24,12 @@ use crate::pipelines::new::processors::port::OutputPort;
 use crate::pipelines::new::processors::processor::Event;
 use crate::pipelines::new::processors::processor::ProcessorPtr;
 use crate::pipelines::new::processors::Processor;
+use crate::sessions::QueryMemoryTracker;
 
 pub struct TransformCompact<T: Compactor + Send + 'static> {
     state: ProcessorState,
     compactor: T,
+    memory_tracker: Option<Arc<QueryMemoryTracker>>,
 }
 
 /// Compactor is a trait that defines how to compact blocks.
@@ -53,6 +55,16 @@ impl<T: Compactor + Send + 'static> TransformCompact<T> {
         input_port: Arc<InputPort>,
         output_port: Arc<OutputPort>,
         compactor: T,
+    ) -> Result<ProcessorPtr> {
+        Self::try_create_with_memory_tracker(input_port, output_port, compactor, None)
+    }
+
+    /// Reserve the memory of the buffered blocks from the tracker, fail if it is exceeded.
+    pub fn try_create_with_memory_tracker(
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        compactor: T,
+        memory_tracker: Option<Arc<QueryMemoryTracker>>,
     ) -> Result<ProcessorPtr> {
         let state = ProcessorState::Consume(ConsumeState {
             input_port,
@@ -61,7 +73,11 @@ impl<T: Compactor + Send + 'static> TransformCompact<T> {
             output_data_blocks: VecDeque::new(),
         });
 
-        Ok(ProcessorPtr::create(Box::new(Self { state, compactor })))
+        Ok(ProcessorPtr::create(Box::new(Self {
+            state,
+            compactor,
+            memory_tracker,
+        })))
     }
 
     #[inline(always)]
@@ -85,9 +101,11 @@ impl<T: Compactor + Send + 'static> TransformCompact<T> {
             }
 
             if state.input_port.has_data() {
-                state
-                    .input_data_blocks
-                    .push(state.input_port.pull_data().unwrap()?);
+                let block = state.input_port.pull_data().unwrap()?;
+                if let Some(memory_tracker) = &self.memory_tracker {
+                    memory_tracker.try_alloc(block.memory_size())?;
+                }
+                state.input_data_blocks.push(block);
 
                 if T::use_partial_compact() {
                     return Ok(Event::Sync);
@@ -144,6 +162,12 @@ impl<T: Compactor + Send + 'static> Processor for TransformCompact<T> {
                     .compactor
                     .compact_partial(&mut state.input_data_blocks)?;
 
+                // The blocks may be merged or taken by the compaction.
+                if let Some(memory_tracker) = &self.memory_tracker {
+                    let usage = state.input_data_blocks.iter().map(|b| b.memory_size());
+                    memory_tracker.try_update_usage(usage.sum())?;
+                }
+
                 for b in compacted_blocks {
                     state.output_data_blocks.push_back(b);
                 }
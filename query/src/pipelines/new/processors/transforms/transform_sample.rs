@@ -0,0 +1,61 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::Series;
+use common_exception::Result;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::transforms::transform::Transform;
+use crate::pipelines::new::processors::transforms::transform::Transformer;
+
+/// Keeps each of the rows independently at the rate, for `TABLESAMPLE ROW (n PERCENT)`.
+pub struct TransformSample {
+    rate: f64,
+    rng: StdRng,
+}
+
+impl TransformSample {
+    pub fn try_create(
+        percent: f64,
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+    ) -> Result<ProcessorPtr> {
+        Ok(Transformer::create(input, output, TransformSample {
+            rate: (percent / 100.0).clamp(0.0, 1.0),
+            rng: StdRng::from_entropy(),
+        }))
+    }
+}
+
+impl Transform for TransformSample {
+    const NAME: &'static str = "SampleTransform";
+
+    const SKIP_EMPTY_DATA_BLOCK: bool = true;
+
+    fn transform(&mut self, data: DataBlock) -> Result<DataBlock> {
+        let rate = self.rate;
+        let filter = (0..data.num_rows())
+            .map(|_| self.rng.gen_bool(rate))
+            .collect::<Vec<_>>();
+        DataBlock::filter_block(&data, &Series::from_data(filter))
+    }
+}
@@ -41,6 +41,27 @@ impl Compactor for SortMergeCompactor {
         "SortMergeTransform"
     }
 
+    fn use_partial_compact() -> bool {
+        true
+    }
+
+    /// With a limit, only the first `limit` rows of the blocks are kept once the blocks have
+    /// twice as many rows, so the memory of the top-n sorts is bounded by the limit.
+    fn compact_partial(&self, blocks: &mut Vec<DataBlock>) -> Result<Vec<DataBlock>> {
+        if let Some(limit) = self.limit {
+            let rows = blocks.iter().map(|b| b.num_rows()).sum::<usize>();
+            if blocks.len() > 1 && rows >= limit.saturating_mul(2) {
+                let block = DataBlock::merge_sort_blocks(
+                    blocks,
+                    &self.sort_columns_descriptions,
+                    Some(limit),
+                )?;
+                *blocks = vec![block];
+            }
+        }
+        Ok(vec![])
+    }
+
     fn compact_final(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>> {
         if blocks.is_empty() {
             Ok(vec![])
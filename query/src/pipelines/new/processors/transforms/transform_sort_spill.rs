@@ -0,0 +1,338 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::compute::merge_sort::build_comparator;
+use common_arrow::arrow::compute::sort::SortOptions;
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::Event;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::Processor;
+use crate::pipelines::new::Spiller;
+use crate::sessions::QueryMemoryTracker;
+
+/// External sort of the partially sorted blocks.
+///
+/// The blocks are buffered until their size reaches the threshold, then they are merged into a
+/// sorted run which is spilled. Once the input is finished, the spilled runs are merged block by
+/// block, so only the current block of every run is kept in memory. If nothing is spilled, the
+/// buffered blocks are merged in memory as `TransformSortMerge` does.
+pub struct TransformSortSpill {
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    state: State,
+    spiller: Spiller,
+    threshold: usize,
+    limit: Option<usize>,
+    max_block_size: usize,
+    sort_columns_descriptions: Vec<SortColumnDescription>,
+    memory_tracker: Option<Arc<QueryMemoryTracker>>,
+
+    input_finished: bool,
+    buffered_blocks: Vec<DataBlock>,
+    buffered_bytes: usize,
+    runs: Vec<SpilledRun>,
+    output_blocks: VecDeque<DataBlock>,
+    output_rows: usize,
+}
+
+enum State {
+    Consume,
+    // Merge the buffered blocks into a sorted run.
+    Sort,
+    Spill(Vec<DataBlock>),
+    // Read the next block of the runs the current block of which is merged.
+    Restore,
+    Merge,
+    Finished,
+}
+
+struct SpilledRun {
+    locations: VecDeque<String>,
+    current: Option<DataBlock>,
+}
+
+impl TransformSortSpill {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        spiller: Spiller,
+        threshold: usize,
+        limit: Option<usize>,
+        max_block_size: usize,
+        sort_columns_descriptions: Vec<SortColumnDescription>,
+        memory_tracker: Option<Arc<QueryMemoryTracker>>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(TransformSortSpill {
+            input,
+            output,
+            state: State::Consume,
+            spiller,
+            threshold,
+            limit,
+            max_block_size,
+            sort_columns_descriptions,
+            memory_tracker,
+            input_finished: false,
+            buffered_blocks: vec![],
+            buffered_bytes: 0,
+            runs: vec![],
+            output_blocks: VecDeque::new(),
+            output_rows: 0,
+        })))
+    }
+
+    fn sort(&mut self) -> Result<()> {
+        let blocks = std::mem::take(&mut self.buffered_blocks);
+        self.buffered_bytes = 0;
+
+        let block =
+            DataBlock::merge_sort_blocks(&blocks, &self.sort_columns_descriptions, self.limit)?;
+        let blocks = DataBlock::split_block_by_size(&block, self.max_block_size)?;
+        if let Some(memory_tracker) = &self.memory_tracker {
+            memory_tracker.try_update_usage(0)?;
+        }
+
+        match self.input_finished && self.runs.is_empty() {
+            true => {
+                self.output_blocks.extend(blocks);
+                self.state = State::Finished;
+            }
+            false => self.state = State::Spill(blocks),
+        }
+        Ok(())
+    }
+
+    /// Merges the rows of the current blocks which are not greater than the last row of the
+    /// smallest current block. The rows after them in the runs are never less than it.
+    fn merge(&mut self) -> Result<()> {
+        let blocks = self
+            .runs
+            .iter()
+            .map(|run| match &run.current {
+                Some(block) => Ok(block),
+                None => Err(ErrorCode::LogicalError(
+                    "Merge the runs before restoring them",
+                )),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let columns = self
+            .sort_columns_descriptions
+            .iter()
+            .map(|f| {
+                blocks
+                    .iter()
+                    .map(|block| Ok(block.try_column_by_name(&f.column_name)?.as_arrow_array()))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let arrays = columns
+            .iter()
+            .map(|c| {
+                c.iter()
+                    .map(|array| array.as_ref())
+                    .collect::<Vec<&dyn Array>>()
+            })
+            .collect::<Vec<_>>();
+        let sort_options = self
+            .sort_columns_descriptions
+            .iter()
+            .map(|f| SortOptions {
+                descending: !f.asc,
+                nulls_first: f.nulls_first,
+            })
+            .collect::<Vec<_>>();
+        let sort_options_with_array = arrays
+            .iter()
+            .zip(sort_options.iter())
+            .map(|(arrays, opt)| (arrays.as_slice(), opt))
+            .collect::<Vec<_>>();
+        let comparator = build_comparator(&sort_options_with_array)?;
+
+        let last_row = |index: usize| blocks[index].num_rows() - 1;
+        let mut bound = 0;
+        for index in 1..blocks.len() {
+            if comparator(index, last_row(index), bound, last_row(bound)) == Ordering::Less {
+                bound = index;
+            }
+        }
+
+        let splits = blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| {
+                let (mut low, mut high) = (0, block.num_rows());
+                while low < high {
+                    let mid = (low + high) / 2;
+                    match comparator(index, mid, bound, last_row(bound)) {
+                        Ordering::Greater => high = mid,
+                        _ => low = mid + 1,
+                    }
+                }
+                low
+            })
+            .collect::<Vec<_>>();
+
+        let mut merging_blocks = Vec::with_capacity(self.runs.len());
+        for (run, split) in self.runs.iter_mut().zip(splits.into_iter()) {
+            if let Some(block) = run.current.take() {
+                if split > 0 {
+                    merging_blocks.push(block.slice(0, split));
+                }
+                if split < block.num_rows() {
+                    run.current = Some(block.slice(split, block.num_rows() - split));
+                }
+            }
+        }
+
+        let limit = self.limit.map(|limit| limit - self.output_rows);
+        let block =
+            DataBlock::merge_sort_blocks(&merging_blocks, &self.sort_columns_descriptions, limit)?;
+        self.output_rows += block.num_rows();
+        self.output_blocks
+            .extend(DataBlock::split_block_by_size(&block, self.max_block_size)?);
+
+        self.runs
+            .retain(|run| run.current.is_some() || !run.locations.is_empty());
+        self.state = match self.runs.is_empty() || Some(self.output_rows) == self.limit {
+            true => State::Finished,
+            false if self.runs.iter().any(|run| run.current.is_none()) => State::Restore,
+            false => State::Merge,
+        };
+        Ok(())
+    }
+
+    async fn restore(&mut self) -> Result<()> {
+        let mut indexes = vec![];
+        let mut locations = vec![];
+        for (index, run) in self.runs.iter_mut().enumerate() {
+            if run.current.is_none() {
+                if let Some(location) = run.locations.pop_front() {
+                    indexes.push(index);
+                    locations.push(location);
+                }
+            }
+        }
+
+        let blocks = self.spiller.restore(&locations).await?;
+        for (index, block) in indexes.into_iter().zip(blocks.into_iter()) {
+            self.runs[index].current = Some(block);
+        }
+
+        self.runs.retain(|run| run.current.is_some());
+        self.state = match self.runs.is_empty() {
+            true => State::Finished,
+            false => State::Merge,
+        };
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformSortSpill {
+    fn name(&self) -> &'static str {
+        "SortSpillTransform"
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output_blocks.is_empty() {
+            if self.output.can_push() {
+                let block = self.output_blocks.pop_front().unwrap();
+                self.output.push_data(Ok(block));
+            }
+            return Ok(Event::NeedConsume);
+        }
+
+        match &self.state {
+            State::Sort | State::Merge => return Ok(Event::Sync),
+            State::Spill(_) | State::Restore => return Ok(Event::Async),
+            State::Finished => {
+                self.input.finish();
+                self.output.finish();
+                return Ok(Event::Finished);
+            }
+            State::Consume => {}
+        }
+
+        if self.input.is_finished() {
+            self.input_finished = true;
+            self.state = match (self.buffered_blocks.is_empty(), self.runs.is_empty()) {
+                (false, _) => State::Sort,
+                (true, false) => State::Restore,
+                (true, true) => State::Finished,
+            };
+            return self.event();
+        }
+
+        if self.input.has_data() {
+            let block = self.input.pull_data().unwrap()?;
+            let mut memory_exceeded = false;
+            if block.num_rows() > 0 {
+                if let Some(memory_tracker) = &self.memory_tracker {
+                    memory_exceeded = memory_tracker.try_alloc(block.memory_size()).is_err();
+                }
+                self.buffered_bytes += block.memory_size();
+                self.buffered_blocks.push(block);
+            }
+
+            // Spill the buffered blocks early if they exceed the memory of the query.
+            if self.buffered_bytes >= self.threshold || memory_exceeded {
+                self.state = State::Sort;
+                return Ok(Event::Sync);
+            }
+        }
+
+        self.input.set_need_data();
+        Ok(Event::NeedData)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match self.state {
+            State::Sort => self.sort(),
+            State::Merge => self.merge(),
+            _ => Err(ErrorCode::LogicalError("State invalid. it's a bug.")),
+        }
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Consume) {
+            State::Spill(blocks) => {
+                let locations = self.spiller.spill(blocks).await?;
+                self.runs.push(SpilledRun {
+                    locations: locations.into(),
+                    current: None,
+                });
+                Ok(())
+            }
+            State::Restore => self.restore().await,
+            _ => Err(ErrorCode::LogicalError("State invalid. it's a bug.")),
+        }
+    }
+}
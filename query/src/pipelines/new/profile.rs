@@ -0,0 +1,291 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_planners::PlanNode;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::pipelines::new::processors::processor::Event;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::Processor;
+
+/// The runtime metrics of all the processors in one pipe, collected by EXPLAIN ANALYZE and
+/// sampled by the pipeline profiling of the running queries.
+pub struct PipeProfile {
+    pub name: &'static str,
+    pub processors: usize,
+    processing: AtomicUsize,
+    elapsed_ns: AtomicU64,
+    output_rows: AtomicUsize,
+    output_bytes: AtomicUsize,
+}
+
+impl PipeProfile {
+    pub fn create(name: &'static str, processors: usize) -> Arc<PipeProfile> {
+        Arc::new(PipeProfile {
+            name,
+            processors,
+            processing: AtomicUsize::new(0),
+            elapsed_ns: AtomicU64::new(0),
+            output_rows: AtomicUsize::new(0),
+            output_bytes: AtomicUsize::new(0),
+        })
+    }
+
+    /// The wall time spent in the processors, summed over all of them.
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.elapsed_ns.load(Ordering::Relaxed))
+    }
+
+    /// The number of the processors of the pipe running `process` right now.
+    pub fn processing(&self) -> usize {
+        self.processing.load(Ordering::Relaxed)
+    }
+
+    pub fn start_processing(&self) {
+        self.processing.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn finish_processing(&self) {
+        self.processing.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub fn output_rows(&self) -> usize {
+        self.output_rows.load(Ordering::Relaxed)
+    }
+
+    pub fn output_bytes(&self) -> usize {
+        self.output_bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn add_elapsed(&self, elapsed: Duration) {
+        self.elapsed_ns
+            .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub fn add_output(&self, block: &DataBlock) {
+        self.output_rows
+            .fetch_add(block.num_rows(), Ordering::Relaxed);
+        self.output_bytes
+            .fetch_add(block.memory_size(), Ordering::Relaxed);
+    }
+
+    pub fn get_values(&self) -> PipeProfileValues {
+        PipeProfileValues {
+            name: self.name.to_string(),
+            processors: self.processors,
+            elapsed_ms: self.elapsed().as_secs_f64() * 1000f64,
+            output_rows: self.output_rows(),
+            output_bytes: self.output_bytes(),
+        }
+    }
+}
+
+/// A snapshot of the metrics of a pipe, persisted with the query history.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PipeProfileValues {
+    pub name: String,
+    pub processors: usize,
+    pub elapsed_ms: f64,
+    pub output_rows: usize,
+    pub output_bytes: usize,
+}
+
+/// Wraps a processor and records the time spent in its work into the profile of its pipe.
+pub struct ProfilingProcessor {
+    inner: ProcessorPtr,
+    profile: Arc<PipeProfile>,
+}
+
+impl ProfilingProcessor {
+    pub fn create(inner: ProcessorPtr, profile: Arc<PipeProfile>) -> ProcessorPtr {
+        ProcessorPtr::create(Box::new(ProfilingProcessor { inner, profile }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for ProfilingProcessor {
+    fn name(&self) -> &'static str {
+        unsafe { self.inner.name() }
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        unsafe { self.inner.event() }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        let start = Instant::now();
+        self.profile.start_processing();
+        let res = unsafe { self.inner.process() };
+        self.profile.finish_processing();
+        self.profile.add_elapsed(start.elapsed());
+        res
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        let start = Instant::now();
+        let res = unsafe { self.inner.async_process() }.await;
+        self.profile.add_elapsed(start.elapsed());
+        res
+    }
+}
+
+/// The profiles of the pipes built for each plan node, keyed by the address of the node.
+#[derive(Default)]
+pub struct PlanNodeProfiles {
+    profiles: HashMap<usize, Vec<Arc<PipeProfile>>>,
+}
+
+impl PlanNodeProfiles {
+    pub fn add(&mut self, node: usize, profile: Arc<PipeProfile>) {
+        self.profiles.entry(node).or_default().push(profile);
+    }
+
+    /// The profiles of the pipes of the node, in pipeline order.
+    pub fn get(&self, node: &PlanNode) -> Option<&[Arc<PipeProfile>]> {
+        self.profiles
+            .get(&(node as *const PlanNode as usize))
+            .map(|profiles| profiles.as_slice())
+    }
+}
+
+/// Samples the pipes being processed in the pipelines of the running queries, and renders them
+/// as a speedscope profile per query. The stack of a sample is the pipes from the source of the
+/// pipeline to the processing pipe, which is how flamegraphs stack the processors.
+#[derive(Default)]
+pub struct PipelineSampler {
+    frames: Vec<SpeedscopeFrame>,
+    frame_indexes: HashMap<&'static str, usize>,
+    profiles: Vec<SpeedscopeProfile>,
+    profile_indexes: HashMap<String, usize>,
+}
+
+impl PipelineSampler {
+    pub fn create() -> PipelineSampler {
+        PipelineSampler::default()
+    }
+
+    /// Records a sample of `interval` for each processor running `process` in the pipelines.
+    pub fn sample(
+        &mut self,
+        query_id: &str,
+        pipelines: &[Vec<Arc<PipeProfile>>],
+        interval: Duration,
+    ) {
+        for pipes in pipelines {
+            for (index, pipe) in pipes.iter().enumerate() {
+                let processing = pipe.processing();
+                if processing == 0 {
+                    continue;
+                }
+
+                let stack = pipes[..=index]
+                    .iter()
+                    .map(|pipe| self.frame_index(pipe.name))
+                    .collect::<Vec<_>>();
+                let weight = interval.as_secs_f64() * 1000f64 * processing as f64;
+                let profile = self.profile(query_id);
+                profile.samples.push(stack);
+                profile.weights.push(weight);
+                profile.end_value += weight;
+            }
+        }
+    }
+
+    pub fn finish(self) -> SpeedscopeFile {
+        SpeedscopeFile {
+            schema: "https://www.speedscope.app/file-format-schema.json".to_string(),
+            shared: SpeedscopeShared {
+                frames: self.frames,
+            },
+            profiles: self.profiles,
+            exporter: "databend-query".to_string(),
+        }
+    }
+
+    fn frame_index(&mut self, name: &'static str) -> usize {
+        let frames = &mut self.frames;
+        *self.frame_indexes.entry(name).or_insert_with(|| {
+            frames.push(SpeedscopeFrame {
+                name: name.to_string(),
+            });
+            frames.len() - 1
+        })
+    }
+
+    fn profile(&mut self, query_id: &str) -> &mut SpeedscopeProfile {
+        let index = match self.profile_indexes.get(query_id) {
+            Some(index) => *index,
+            None => {
+                self.profiles.push(SpeedscopeProfile {
+                    typ: "sampled".to_string(),
+                    name: query_id.to_string(),
+                    unit: "milliseconds".to_string(),
+                    start_value: 0f64,
+                    end_value: 0f64,
+                    samples: vec![],
+                    weights: vec![],
+                });
+                self.profile_indexes
+                    .insert(query_id.to_string(), self.profiles.len() - 1);
+                self.profiles.len() - 1
+            }
+        };
+        &mut self.profiles[index]
+    }
+}
+
+/// The file format of https://www.speedscope.app, see its file-format-schema.json.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SpeedscopeFile {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub shared: SpeedscopeShared,
+    pub profiles: Vec<SpeedscopeProfile>,
+    pub exporter: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SpeedscopeShared {
+    pub frames: Vec<SpeedscopeFrame>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SpeedscopeFrame {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpeedscopeProfile {
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub name: String,
+    pub unit: String,
+    pub start_value: f64,
+    pub end_value: f64,
+    // The indexes of the frames of each sample, from the root.
+    pub samples: Vec<Vec<usize>>,
+    pub weights: Vec<f64>,
+}
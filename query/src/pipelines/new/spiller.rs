@@ -0,0 +1,223 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::io::parquet::write::*;
+use common_arrow::parquet::encoding::Encoding;
+use common_arrow::write_parquet_file;
+use common_base::ProgressValues;
+use common_base::TrySpawn;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_streams::ParquetSourceBuilder;
+use common_streams::Source;
+use common_tracing::tracing;
+use futures::io::Cursor;
+use futures::AsyncReadExt;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use opendal::services::fs;
+use opendal::Operator;
+use uuid::Uuid;
+
+use crate::sessions::QueryContext;
+
+/// Spiller writes the blocks which do not fit in memory to the spill storage, and reads them back.
+///
+/// Every block is written into a parquet file of its own, under the directory of the setting
+/// `spill_path`, or under the `_spill` directory of the table storage if it is not set.
+/// The files are removed once they are read back, or when the spiller is dropped.
+pub struct Spiller {
+    ctx: Arc<QueryContext>,
+    prefix: String,
+    compression: Compression,
+    concurrency: usize,
+    operator: Option<Operator>,
+    schema: Option<DataSchemaRef>,
+    next_file_id: usize,
+    files: HashSet<String>,
+}
+
+impl Spiller {
+    pub fn try_create(ctx: Arc<QueryContext>, purpose: &str) -> Result<Spiller> {
+        let settings = ctx.get_settings();
+        let compression = settings.get_spill_compression()?;
+        let compression = match compression.to_ascii_lowercase().as_slice() {
+            b"none" => Compression::Uncompressed,
+            b"lz4" => Compression::Lz4Raw,
+            b"snappy" => Compression::Snappy,
+            b"zstd" => Compression::Zstd,
+            _ => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Unsupported spill compression: {}, expect none, lz4, snappy or zstd",
+                    String::from_utf8_lossy(&compression)
+                )));
+            }
+        };
+
+        let concurrency = settings.get_spill_concurrency()?.max(1) as usize;
+        let prefix = format!("_spill/{}/{}-{}", ctx.get_id(), purpose, Uuid::new_v4());
+
+        Ok(Spiller {
+            ctx,
+            prefix,
+            compression,
+            concurrency,
+            operator: None,
+            schema: None,
+            next_file_id: 0,
+            files: HashSet::new(),
+        })
+    }
+
+    /// Writes the blocks to the spill storage, returns the locations of them in the same order.
+    pub async fn spill(&mut self, blocks: Vec<DataBlock>) -> Result<Vec<String>> {
+        let operator = self.operator().await?;
+
+        let mut files = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            if self.schema.is_none() {
+                self.schema = Some(block.schema().clone());
+            }
+
+            let location = format!("{}/{}", self.prefix, self.next_file_id);
+            self.next_file_id += 1;
+            let rows = block.num_rows();
+            let data = self.serialize(block)?;
+            self.ctx.get_spill_progress().incr(&ProgressValues {
+                rows,
+                bytes: data.len(),
+            });
+            files.push((location, data));
+        }
+
+        let locations = files.iter().map(|(l, _)| l.clone()).collect::<Vec<_>>();
+        futures::stream::iter(files)
+            .map(|(location, data)| {
+                let operator = operator.clone();
+                async move { operator.object(&location).write(data).await }
+            })
+            .buffer_unordered(self.concurrency)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        self.files.extend(locations.iter().cloned());
+        Ok(locations)
+    }
+
+    /// Reads the spilled blocks back in the order of the locations, and removes their files.
+    pub async fn restore(&mut self, locations: &[String]) -> Result<Vec<DataBlock>> {
+        let operator = self.operator().await?;
+        let schema = match &self.schema {
+            Some(schema) => schema.clone(),
+            None => {
+                return Err(ErrorCode::LogicalError(
+                    "Restore blocks before spilling them",
+                ))
+            }
+        };
+
+        let blocks = futures::stream::iter(locations.iter().cloned())
+            .map(|location| {
+                let operator = operator.clone();
+                let schema = schema.clone();
+                async move {
+                    let object = operator.object(&location);
+                    let mut data = vec![];
+                    object.reader().await?.read_to_end(&mut data).await?;
+                    object.delete().await?;
+                    Self::deserialize(schema, data).await
+                }
+            })
+            .buffered(self.concurrency)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        for location in locations {
+            self.files.remove(location);
+        }
+        Ok(blocks)
+    }
+
+    async fn operator(&mut self) -> Result<Operator> {
+        if let Some(operator) = &self.operator {
+            return Ok(operator.clone());
+        }
+
+        let path = self.ctx.get_settings().get_spill_path()?;
+        let operator = match path.is_empty() {
+            true => self.ctx.get_storage_operator()?,
+            false => {
+                let path = String::from_utf8(path)?;
+                Operator::new(fs::Backend::build().root(&path).finish().await?)
+            }
+        };
+
+        self.operator = Some(operator.clone());
+        Ok(operator)
+    }
+
+    fn serialize(&self, block: DataBlock) -> Result<Vec<u8>> {
+        let arrow_schema = block.schema().to_arrow();
+        let options = WriteOptions {
+            write_statistics: false,
+            compression: self.compression,
+            version: Version::V2,
+        };
+
+        let encodings = vec![Encoding::Plain; arrow_schema.fields.len()];
+        let iter = vec![Ok(Chunk::try_from(block)?)];
+        let row_groups =
+            RowGroupIterator::try_new(iter.into_iter(), &arrow_schema, options, encodings)?;
+
+        let mut data = vec![];
+        match write_parquet_file(&mut data, row_groups, arrow_schema.clone(), options) {
+            Ok(_) => Ok(data),
+            Err(cause) => Err(ErrorCode::ParquetError(cause.to_string())),
+        }
+    }
+
+    async fn deserialize(schema: DataSchemaRef, data: Vec<u8>) -> Result<DataBlock> {
+        let mut source = ParquetSourceBuilder::create(schema).build(Cursor::new(data))?;
+        match source.read().await? {
+            Some(block) => Ok(block),
+            None => Err(ErrorCode::ParquetError("The spilled file has no block")),
+        }
+    }
+}
+
+impl Drop for Spiller {
+    fn drop(&mut self) {
+        // The files are not read back if the query is aborted.
+        if self.files.is_empty() {
+            return;
+        }
+
+        if let Some(operator) = self.operator.clone() {
+            let files = std::mem::take(&mut self.files);
+            self.ctx.get_storage_runtime().spawn(async move {
+                for location in files {
+                    if let Err(cause) = operator.object(&location).delete().await {
+                        tracing::warn!("failed to remove the spilled file {}: {}", location, cause);
+                    }
+                }
+            });
+        }
+    }
+}
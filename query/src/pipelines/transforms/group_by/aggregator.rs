@@ -62,7 +62,8 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
 
                     // 1.1 and 1.2.
                     let group_columns = Self::group_columns(&group_cols, &block)?;
-                    let group_keys = hash_method.build_keys(&group_columns, block.num_rows())?;
+                    let (group_keys, _) =
+                        hash_method.build_distinct_keys(&group_columns, block.num_rows())?;
                     self.lookup_key(group_keys, &mut state);
                 }
             }
@@ -72,9 +73,14 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
 
                     // 1.1 and 1.2.
                     let group_columns = Self::group_columns(&group_cols, &block)?;
-                    let group_keys = hash_method.build_keys(&group_columns, block.num_rows())?;
+                    let (group_keys, indices) =
+                        hash_method.build_distinct_keys(&group_columns, block.num_rows())?;
 
                     let places = self.lookup_state(group_keys, &mut state);
+                    let places = match indices {
+                        None => places,
+                        Some(indices) => indices.iter().map(|i| places[*i as usize]).collect(),
+                    };
                     Self::execute(aggregator_params, &block, &places)?;
                 }
             }
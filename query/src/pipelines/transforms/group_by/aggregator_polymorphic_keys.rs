@@ -14,6 +14,7 @@
 
 use bumpalo::Bump;
 use common_datablocks::HashMethod;
+use common_datablocks::HashMethodDictionarySerializer;
 use common_datablocks::HashMethodKeysU16;
 use common_datablocks::HashMethodKeysU32;
 use common_datablocks::HashMethodKeysU64;
@@ -275,3 +276,35 @@ impl PolymorphicKeysHelper<HashMethodSerializer> for HashMethodSerializer {
         SerializedKeysGroupColumnsBuilder::create(capacity, params)
     }
 }
+
+impl PolymorphicKeysHelper<HashMethodDictionarySerializer> for HashMethodDictionarySerializer {
+    type State = SerializedKeysAggregatorState;
+    fn aggregate_state(&self) -> Self::State {
+        SerializedKeysAggregatorState {
+            keys_area: Bump::new(),
+            state_area: Bump::new(),
+            data_state_map: HashTable::create(),
+        }
+    }
+
+    type ColumnBuilder = SerializedKeysColumnBuilder;
+    fn keys_column_builder(&self, capacity: usize) -> Self::ColumnBuilder {
+        SerializedKeysColumnBuilder {
+            inner_builder: MutableStringColumn::with_capacity(capacity),
+        }
+    }
+
+    type KeysColumnIter = SerializedKeysColumnIter;
+    fn keys_iter_from_column(&self, column: &ColumnRef) -> Result<Self::KeysColumnIter> {
+        SerializedKeysColumnIter::create(Series::check_get::<StringColumn>(column)?)
+    }
+
+    type GroupColumnsBuilder = SerializedKeysGroupColumnsBuilder;
+    fn group_columns_builder(
+        &self,
+        capacity: usize,
+        params: &AggregatorParams,
+    ) -> Self::GroupColumnsBuilder {
+        SerializedKeysGroupColumnsBuilder::create(capacity, params)
+    }
+}
@@ -17,6 +17,7 @@ use std::intrinsics::likely;
 
 use bumpalo::Bump;
 use common_datablocks::HashMethod;
+use common_datablocks::HashMethodDictionarySerializer;
 use common_datablocks::HashMethodFixedKeys;
 use common_datablocks::HashMethodSerializer;
 use common_datablocks::HashMethodSingleString;
@@ -50,6 +51,9 @@ pub trait AggregatorState<Method: HashMethod>: Sync + Send {
 
     fn len(&self) -> usize;
 
+    /// The memory in bytes held by the groups and their aggregate function states.
+    fn memory_size(&self) -> usize;
+
     fn iter(&self) -> Self::Iterator;
 
     fn alloc_place(&self, layout: Layout) -> StateAddr;
@@ -144,6 +148,11 @@ where
         self.size
     }
 
+    fn memory_size(&self) -> usize {
+        let entity_size = std::mem::size_of::<ShortFixedKeysStateEntity<T>>();
+        self.area.allocated_bytes() + self.max_size * entity_size
+    }
+
     #[inline(always)]
     fn iter(&self) -> Self::Iterator {
         Self::Iterator::create(self.data, self.max_size as isize)
@@ -210,6 +219,10 @@ where
         self.data.len()
     }
 
+    fn memory_size(&self) -> usize {
+        self.area.allocated_bytes() + self.data.memory_size()
+    }
+
     #[inline(always)]
     fn iter(&self) -> Self::Iterator {
         self.data.iter()
@@ -257,6 +270,76 @@ impl AggregatorState<HashMethodSerializer> for SerializedKeysAggregatorState {
         self.data_state_map.len()
     }
 
+    fn memory_size(&self) -> usize {
+        self.keys_area.allocated_bytes()
+            + self.state_area.allocated_bytes()
+            + self.data_state_map.memory_size()
+    }
+
+    fn iter(&self) -> Self::Iterator {
+        self.data_state_map.iter()
+    }
+
+    #[inline(always)]
+    fn alloc_place(&self, layout: Layout) -> StateAddr {
+        self.state_area.alloc_layout(layout).into()
+    }
+
+    #[inline(always)]
+    fn entity(&mut self, keys: &SmallVu8, inserted: &mut bool) -> *mut Self::Entity {
+        let mut keys_ref = KeysRef::create(keys.as_ptr() as usize, keys.len());
+        let state_entity = self.data_state_map.insert_key(&keys_ref, inserted);
+
+        if *inserted {
+            unsafe {
+                // Keys will be destroyed after call we need copy the keys to the memory pool.
+                let global_keys = self.keys_area.alloc_slice_copy(keys);
+                let inserted_hash = state_entity.get_hash();
+                keys_ref.address = global_keys.as_ptr() as usize;
+                // TODO: maybe need set key method.
+                state_entity.set_key_and_hash(&keys_ref, inserted_hash)
+            }
+        }
+
+        state_entity
+    }
+
+    #[inline(always)]
+    fn entity_by_key(&mut self, keys_ref: &KeysRef, inserted: &mut bool) -> *mut Self::Entity {
+        let state_entity = self.data_state_map.insert_key(keys_ref, inserted);
+
+        if *inserted {
+            unsafe {
+                // Keys will be destroyed after call we need copy the keys to the memory pool.
+                let data_ptr = keys_ref.address as *mut u8;
+                let keys = std::slice::from_raw_parts_mut(data_ptr, keys_ref.length);
+                let global_keys = self.keys_area.alloc_slice_copy(keys);
+                let inserted_hash = state_entity.get_hash();
+                let address = global_keys.as_ptr() as usize;
+                let new_keys_ref = KeysRef::create(address, keys_ref.length);
+                state_entity.set_key_and_hash(&new_keys_ref, inserted_hash)
+            }
+        }
+
+        state_entity
+    }
+}
+
+impl AggregatorState<HashMethodDictionarySerializer> for SerializedKeysAggregatorState {
+    type Key = KeysRef;
+    type Entity = KeyValueEntity<KeysRef, usize>;
+    type Iterator = HashMapIterator<KeysRef, usize>;
+
+    fn len(&self) -> usize {
+        self.data_state_map.len()
+    }
+
+    fn memory_size(&self) -> usize {
+        self.keys_area.allocated_bytes()
+            + self.state_area.allocated_bytes()
+            + self.data_state_map.memory_size()
+    }
+
     fn iter(&self) -> Self::Iterator {
         self.data_state_map.iter()
     }
@@ -315,6 +398,12 @@ impl AggregatorState<HashMethodSingleString> for SerializedKeysAggregatorState {
         self.data_state_map.len()
     }
 
+    fn memory_size(&self) -> usize {
+        self.keys_area.allocated_bytes()
+            + self.state_area.allocated_bytes()
+            + self.data_state_map.memory_size()
+    }
+
     fn iter(&self) -> Self::Iterator {
         self.data_state_map.iter()
     }
@@ -36,8 +36,10 @@ pub struct AddOnStream {
 
     default_expr_fields: Vec<DataField>,
     default_nonexpr_fields: Vec<DataField>,
+    stored_computed_fields: Vec<DataField>,
 
     expression_executor: ExpressionExecutor,
+    computed_expression_executor: ExpressionExecutor,
     output_schema: DataSchemaRef,
 }
 
@@ -51,10 +53,31 @@ impl AddOnStream {
         let mut default_expr_fields = Vec::new();
         let mut default_exprs = Vec::new();
         let mut default_nonexpr_fields = Vec::new();
+        let mut stored_computed_fields = Vec::new();
+        let mut stored_computed_exprs = Vec::new();
 
         for f in output_schema.fields() {
             if !input_schema.has_field(f.name()) {
-                if let Some(expr) = f.default_expr() {
+                if let Some(computed_expr) = f.computed_expr() {
+                    if computed_expr.is_stored() {
+                        let expression: Expression =
+                            serde_json::from_slice::<Expression>(computed_expr.expr())?;
+                        let expression = Expression::Alias(
+                            f.name().to_string(),
+                            Box::new(Expression::Cast {
+                                expr: Box::new(expression),
+                                data_type: f.data_type().clone(),
+                                pg_style: false,
+                            }),
+                        );
+
+                        stored_computed_fields.push(f.clone());
+                        stored_computed_exprs.push(expression);
+                    } else {
+                        // virtual generated columns are computed on read, keep a placeholder
+                        default_nonexpr_fields.push(f.clone());
+                    }
+                } else if let Some(expr) = f.default_expr() {
                     let expression: Expression = serde_json::from_slice::<Expression>(expr)?;
                     let expression = Expression::Alias(
                         f.name().to_string(),
@@ -74,6 +97,20 @@ impl AddOnStream {
         }
 
         let schema_after_default_expr = Arc::new(DataSchema::new(default_expr_fields.clone()));
+
+        // the stored generated columns are computed from the completed rows
+        let mut fields_before_computed = input_schema.fields().clone();
+        fields_before_computed.extend(default_expr_fields.iter().cloned());
+        fields_before_computed.extend(default_nonexpr_fields.iter().cloned());
+        let computed_expression_executor = ExpressionExecutor::try_create(
+            ctx.clone(),
+            "stream_addon",
+            Arc::new(DataSchema::new(fields_before_computed)),
+            Arc::new(DataSchema::new(stored_computed_fields.clone())),
+            stored_computed_exprs,
+            true,
+        )?;
+
         let expression_executor = ExpressionExecutor::try_create(
             ctx,
             "stream_addon",
@@ -87,7 +124,9 @@ impl AddOnStream {
             input,
             default_expr_fields,
             default_nonexpr_fields,
+            stored_computed_fields,
             expression_executor,
+            computed_expression_executor,
             output_schema,
         })
     }
@@ -110,6 +149,12 @@ impl AddOnStream {
 
             block = block.add_column(column, f.clone())?;
         }
+
+        let computed_block = self.computed_expression_executor.execute(&block)?;
+        for f in self.stored_computed_fields.iter() {
+            let column = computed_block.try_column_by_name(f.name())?.clone();
+            block = block.add_column(column, f.clone())?;
+        }
         block.resort(self.output_schema.clone())
     }
 }
@@ -143,6 +143,9 @@ impl Processor for GroupByPartialTransform {
             HashMethodKind::KeysU64(method) => self.aggregate(method, group_cols).await,
             HashMethodKind::SingleString(method) => self.aggregate(method, group_cols).await,
             HashMethodKind::Serializer(method) => self.aggregate(method, group_cols).await,
+            HashMethodKind::DictionarySerializer(method) => {
+                self.aggregate(method, group_cols).await
+            }
         }
     }
 }
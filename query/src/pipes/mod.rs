@@ -0,0 +1,23 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod pipe_format;
+mod pipe_kafka;
+mod pipe_runner;
+
+pub use pipe_format::read_messages;
+pub use pipe_kafka::KafkaBatch;
+pub use pipe_kafka::KafkaConsumer;
+pub use pipe_kafka::KafkaOffsets;
+pub use pipe_runner::PipeRunner;
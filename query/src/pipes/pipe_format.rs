@@ -0,0 +1,108 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use avro_rs::Reader;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::FileFormatOptions;
+use common_meta_types::StageFileFormatType;
+use common_streams::CsvSourceBuilder;
+use common_streams::NDJsonSourceBuilder;
+use common_streams::Source;
+use futures::io::Cursor;
+
+use crate::sessions::QueryContext;
+
+/// Parses the messages of a pipe into blocks of `schema`, by the file format of the pipe.
+///
+/// Each message holds one or more whole records: lines of CSV or JSON, or an Avro object
+/// container file.
+pub async fn read_messages(
+    ctx: Arc<QueryContext>,
+    schema: DataSchemaRef,
+    options: &FileFormatOptions,
+    messages: Vec<Vec<u8>>,
+) -> Result<Vec<DataBlock>> {
+    let max_block_size = ctx.get_settings().get_max_block_size()? as usize;
+    let mut source: Box<dyn Source> = match &options.format {
+        StageFileFormatType::Csv => {
+            let mut builder = CsvSourceBuilder::create(schema, ctx.get_format_settings()?);
+            builder.block_size(max_block_size);
+            builder.field_delimiter(&options.field_delimiter);
+            builder.record_delimiter(&options.record_delimiter);
+            let data = join_messages(messages, options.record_delimiter.as_bytes());
+            Box::new(builder.build(Cursor::new(data))?)
+        }
+        StageFileFormatType::Json => {
+            let mut builder = NDJsonSourceBuilder::create(schema);
+            builder.block_size(max_block_size);
+            let data = join_messages(messages, b"\n");
+            Box::new(builder.build(Cursor::new(data))?)
+        }
+        StageFileFormatType::Avro => {
+            let mut builder = NDJsonSourceBuilder::create(schema);
+            builder.block_size(max_block_size);
+            let data = avro_to_ndjson(messages)?;
+            Box::new(builder.build(Cursor::new(data))?)
+        }
+        format => {
+            return Err(ErrorCode::LogicalError(format!(
+                "Unsupported file format of pipe: {:?}",
+                format
+            )));
+        }
+    };
+
+    let mut blocks = vec![];
+    while let Some(block) = source.read().await? {
+        blocks.push(block);
+    }
+    Ok(blocks)
+}
+
+// Concatenates the messages, terminating each of them with the delimiter.
+fn join_messages(messages: Vec<Vec<u8>>, delimiter: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(messages.iter().map(|m| m.len() + 1).sum());
+    for message in messages {
+        let terminated = message.ends_with(delimiter);
+        data.extend(message);
+        if !terminated {
+            data.extend_from_slice(delimiter);
+        }
+    }
+    data
+}
+
+// Converts the records of the Avro messages into lines of JSON.
+fn avro_to_ndjson(messages: Vec<Vec<u8>>) -> Result<Vec<u8>> {
+    let mut data = vec![];
+    for message in messages {
+        let reader = Reader::new(message.as_slice()).map_err(avro_error)?;
+        for value in reader {
+            let value =
+                serde_json::Value::try_from(value.map_err(avro_error)?).map_err(avro_error)?;
+            serde_json::to_writer(&mut data, &value)?;
+            data.push(b'\n');
+        }
+    }
+    Ok(data)
+}
+
+fn avro_error(cause: avro_rs::Error) -> ErrorCode {
+    ErrorCode::BadBytes(format!("Cannot decode avro message, cause: {}", cause))
+}
@@ -0,0 +1,128 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::KafkaSource;
+use futures::future::try_join_all;
+use rskafka::client::error::Error as KafkaError;
+use rskafka::client::partition::OffsetAt;
+use rskafka::client::partition::PartitionClient;
+use rskafka::client::Client;
+use rskafka::client::ClientBuilder;
+
+/// The positions of a pipe in the partitions of its topic: the offset of the next message to
+/// consume, by partition.
+pub type KafkaOffsets = BTreeMap<i32, i64>;
+
+/// The messages fetched from a topic, and the positions of the topic after them.
+pub struct KafkaBatch {
+    pub messages: Vec<Vec<u8>>,
+    pub offsets: KafkaOffsets,
+}
+
+pub struct KafkaConsumer {
+    source: KafkaSource,
+    client: Client,
+}
+
+impl KafkaConsumer {
+    pub async fn connect(source: &KafkaSource) -> Result<KafkaConsumer> {
+        let client = ClientBuilder::new(source.brokers.clone())
+            .build()
+            .await
+            .map_err(kafka_error)?;
+        Ok(KafkaConsumer {
+            source: source.clone(),
+            client,
+        })
+    }
+
+    pub fn source(&self) -> &KafkaSource {
+        &self.source
+    }
+
+    /// Fetches the messages following `committed` from all the partitions of the topic. The
+    /// partitions not in `committed` are consumed from their earliest messages.
+    pub async fn fetch(
+        &self,
+        committed: &KafkaOffsets,
+        max_bytes: i32,
+        max_wait_ms: i32,
+    ) -> Result<KafkaBatch> {
+        let topics = self.client.list_topics().await.map_err(kafka_error)?;
+        let topic = topics
+            .into_iter()
+            .find(|t| t.name == self.source.topic)
+            .ok_or_else(|| {
+                ErrorCode::PipeSourceError(format!("Unknown kafka topic {}", self.source.topic))
+            })?;
+
+        let fetches = topic.partitions.into_iter().map(|partition| {
+            let start = committed.get(&partition).cloned();
+            self.fetch_partition(partition, start, max_bytes, max_wait_ms)
+        });
+
+        let mut messages = vec![];
+        let mut offsets = committed.clone();
+        for (partition, partition_messages, next_offset) in try_join_all(fetches).await? {
+            messages.extend(partition_messages);
+            offsets.insert(partition, next_offset);
+        }
+        Ok(KafkaBatch { messages, offsets })
+    }
+
+    async fn fetch_partition(
+        &self,
+        partition: i32,
+        start: Option<i64>,
+        max_bytes: i32,
+        max_wait_ms: i32,
+    ) -> Result<(i32, Vec<Vec<u8>>, i64)> {
+        let client: PartitionClient = self
+            .client
+            .partition_client(self.source.topic.clone(), partition)
+            .map_err(kafka_error)?;
+        let start = match start {
+            Some(start) => start,
+            None => client
+                .get_offset(OffsetAt::Earliest)
+                .await
+                .map_err(kafka_error)?,
+        };
+
+        let (records, _high_watermark) = client
+            .fetch_records(start, 1..max_bytes, max_wait_ms)
+            .await
+            .map_err(kafka_error)?;
+
+        let mut next_offset = start;
+        let mut messages = Vec::with_capacity(records.len());
+        // a fetch may return the records of the whole batch which `start` falls in
+        for record in records.into_iter().filter(|r| r.offset >= start) {
+            next_offset = record.offset + 1;
+            // the records without value, e.g. the tombstones of compacted topics, are skipped
+            if let Some(value) = record.record.value {
+                messages.push(value);
+            }
+        }
+        Ok((partition, messages, next_offset))
+    }
+}
+
+fn kafka_error(cause: KafkaError) -> ErrorCode {
+    ErrorCode::PipeSourceError(format!("Kafka error: {}", cause))
+}
@@ -0,0 +1,354 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_base::tokio::task::JoinHandle;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::S3File;
+use common_meta_types::KafkaSource;
+use common_meta_types::OnErrorMode;
+use common_meta_types::PipeFileInfo;
+use common_meta_types::PipeFileStatus;
+use common_meta_types::PipeInfo;
+use common_meta_types::PipeSource;
+use common_meta_types::StagePipeSource;
+use common_meta_types::UserStageInfo;
+use common_planners::CopyPlan;
+use common_planners::ReadDataSourcePlan;
+use common_planners::S3StageTableInfo;
+use common_planners::SourceInfo;
+use common_planners::ValidationMode;
+use common_streams::DataBlockStream;
+use common_tracing::tracing;
+use futures::TryStreamExt;
+use regex::Regex;
+
+use crate::interpreters::CopyInterpreter;
+use crate::pipes::read_messages;
+use crate::pipes::KafkaConsumer;
+use crate::pipes::KafkaOffsets;
+use crate::sessions::QueryContext;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::sql::statements::location_to_stage_path;
+use crate::storages::fuse::operations::AppendOperationLogEntry;
+use crate::storages::fuse::operations::TableOperationLog;
+use crate::storages::fuse::FuseTable;
+use crate::storages::StageSource;
+use crate::storages::Table;
+
+const PIPE_POLLING_INTERVAL: Duration = Duration::from_secs(1);
+// The maximum bytes fetched from each partition of a topic in one load.
+const PIPE_KAFKA_MAX_FETCH_BYTES: i32 = 16 * 1024 * 1024;
+// The maximum time to wait for the messages of a partition in one load.
+const PIPE_KAFKA_MAX_WAIT_MS: i32 = 500;
+// The maximum number of the new files of a stage loaded in one load.
+const PIPE_STAGE_MAX_FILES: usize = 64;
+
+/// Loads the pipes of the tenant in background.
+///
+/// Each load commits the data along with the new positions of the pipe in its source, which
+/// are kept as an option of the table, so the loads are exactly once even if the pipe is
+/// loaded by several nodes concurrently: the commits of the loads after the same positions
+/// conflict with each other, and only one of them succeeds.
+///
+/// For a stage source, the positions are the files of the last load, and every loaded file is
+/// also recorded in the load history of the pipe, so that it's never loaded again.
+pub struct PipeRunner {
+    session_manager: Arc<SessionManager>,
+    kafka_consumers: HashMap<String, Arc<KafkaConsumer>>,
+}
+
+impl PipeRunner {
+    pub fn create(session_manager: Arc<SessionManager>) -> PipeRunner {
+        PipeRunner {
+            session_manager,
+            kafka_consumers: HashMap::new(),
+        }
+    }
+
+    pub fn start(mut self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                if let Err(cause) = self.load_pipes().await {
+                    tracing::warn!("pipe runner failed to list the pipes: {}", cause);
+                }
+                tokio::time::sleep(PIPE_POLLING_INTERVAL).await
+            }
+        })
+    }
+
+    async fn load_pipes(&mut self) -> Result<()> {
+        let tenant = self.session_manager.get_conf().query.tenant_id;
+        let user_mgr = self.session_manager.get_user_manager();
+        let pipes = user_mgr.get_pipes(&tenant).await?;
+
+        // the consumers of the dropped pipes
+        self.kafka_consumers
+            .retain(|name, _| pipes.iter().any(|p| &p.pipe_name == name));
+
+        for pipe in &pipes {
+            if let Err(cause) = self.load_pipe(pipe).await {
+                tracing::warn!("pipe {} failed to load: {}", pipe.pipe_name, cause);
+                // reconnect to the source in the next load
+                self.kafka_consumers.remove(&pipe.pipe_name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads the data of the source following the committed positions of the pipe.
+    pub async fn load_pipe(&mut self, pipe: &PipeInfo) -> Result<()> {
+        match &pipe.source {
+            PipeSource::Kafka(source) => self.load_kafka_pipe(pipe, source).await,
+            PipeSource::Stage(source) => self.load_stage_pipe(pipe, source).await,
+        }
+    }
+
+    async fn load_kafka_pipe(&mut self, pipe: &PipeInfo, source: &KafkaSource) -> Result<()> {
+        let consumer = self.kafka_consumer(&pipe.pipe_name, source).await?;
+
+        let session = self
+            .session_manager
+            .create_session(SessionType::Pipe)
+            .await?;
+        let ctx = session.create_query_context().await?;
+        let table = ctx.get_table(&pipe.database, &pipe.table).await?;
+        let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+
+        let key = pipe.offsets_option_key();
+        let committed_value = table.get_table_info().options().get(&key).cloned();
+        let committed = match &committed_value {
+            None => KafkaOffsets::new(),
+            Some(v) => serde_json::from_str::<KafkaOffsets>(v)?,
+        };
+
+        let batch = consumer
+            .fetch(
+                &committed,
+                PIPE_KAFKA_MAX_FETCH_BYTES,
+                PIPE_KAFKA_MAX_WAIT_MS,
+            )
+            .await?;
+        if batch.offsets == committed {
+            return Ok(());
+        }
+
+        let messages = batch.messages.len();
+        let schema = table.schema();
+        let options = &pipe.file_format_options;
+        let blocks = read_messages(ctx.clone(), schema.clone(), options, batch.messages).await?;
+        let stream = DataBlockStream::create(schema, None, blocks);
+        let operations = table
+            .append_data(ctx.clone(), Box::pin(stream))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        let operation_log = operations
+            .iter()
+            .map(AppendOperationLogEntry::try_from)
+            .collect::<Result<TableOperationLog>>()?;
+
+        let expected = HashMap::from([(key.clone(), committed_value)]);
+        let offsets = HashMap::from([(key, serde_json::to_string(&batch.offsets)?)]);
+        fuse_table
+            .commit_with_options(ctx, operation_log, false, &expected, &offsets)
+            .await?;
+
+        tracing::info!(
+            "pipe {} loaded {} messages, offsets {:?}",
+            pipe.pipe_name,
+            messages,
+            batch.offsets
+        );
+        Ok(())
+    }
+
+    async fn load_stage_pipe(&self, pipe: &PipeInfo, source: &StagePipeSource) -> Result<()> {
+        let session = self
+            .session_manager
+            .create_session(SessionType::Pipe)
+            .await?;
+        let ctx = session.create_query_context().await?;
+        let tenant = ctx.get_tenant();
+        let user_mgr = ctx.get_user_manager();
+        let table = ctx.get_table(&pipe.database, &pipe.table).await?;
+        let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+
+        let key = pipe.offsets_option_key();
+        let committed_value = table.get_table_info().options().get(&key).cloned();
+        let committed = match &committed_value {
+            None => vec![],
+            Some(v) => serde_json::from_str::<Vec<PipeFileInfo>>(v)?,
+        };
+
+        // the files of the last load may be committed without being recorded in the history
+        let mut history = user_mgr.get_pipe_files(&tenant, &pipe.pipe_name).await?;
+        let unrecorded = committed
+            .into_iter()
+            .filter(|f| !history.iter().any(|h| h.file_name == f.file_name))
+            .collect::<Vec<_>>();
+        if !unrecorded.is_empty() {
+            user_mgr
+                .add_pipe_files(&tenant, &pipe.pipe_name, unrecorded.clone())
+                .await?;
+            history.extend(unrecorded);
+        }
+
+        let (mut stage_info, path) = location_to_stage_path(&source.location, &ctx).await?;
+        stage_info.file_format_options = pipe.file_format_options.clone();
+        stage_info.copy_options = source.copy_options.clone();
+
+        let files = Self::list_stage_files(&ctx, &stage_info, &path, source, &history).await?;
+        if files.is_empty() {
+            return Ok(());
+        }
+
+        let mut operation_log = TableOperationLog::new();
+        let mut loaded = Vec::with_capacity(files.len());
+        for file in files {
+            // a failed file must not leave its written blocks to the next one
+            let file_ctx = session.create_query_context().await?;
+            let plan = Self::copy_plan(pipe, &table, &stage_info, &path);
+            let interpreter = CopyInterpreter::create(file_ctx, plan);
+
+            let mut info = PipeFileInfo {
+                file_name: file.clone(),
+                ..Default::default()
+            };
+            let copied = interpreter.copy_files_to_table(vec![file]).await;
+            match copied.and_then(|operations| {
+                operations
+                    .iter()
+                    .map(AppendOperationLogEntry::try_from)
+                    .collect::<Result<TableOperationLog>>()
+            }) {
+                Ok(log) => {
+                    info.rows = log.iter().map(|e| e.segment_info.summary.row_count).sum();
+                    operation_log.extend(log);
+                }
+                // the load is retried from the same files
+                Err(cause) if source.copy_options.on_error == OnErrorMode::AbortStatement => {
+                    return Err(cause);
+                }
+                Err(cause) => {
+                    info.status = PipeFileStatus::Failed;
+                    info.error = cause.message();
+                }
+            }
+            loaded.push(info);
+        }
+
+        let expected = HashMap::from([(key.clone(), committed_value)]);
+        let files = HashMap::from([(key, serde_json::to_string(&loaded)?)]);
+        fuse_table
+            .commit_with_options(ctx, operation_log, false, &expected, &files)
+            .await?;
+
+        tracing::info!("pipe {} loaded {} files", pipe.pipe_name, loaded.len());
+        user_mgr
+            .add_pipe_files(&tenant, &pipe.pipe_name, loaded)
+            .await
+    }
+
+    // Lists the files of the stage which match the pattern and have not been loaded, in order.
+    async fn list_stage_files(
+        ctx: &Arc<QueryContext>,
+        stage_info: &UserStageInfo,
+        path: &str,
+        source: &StagePipeSource,
+        history: &[PipeFileInfo],
+    ) -> Result<Vec<String>> {
+        let op = StageSource::get_op(ctx, stage_info).await?;
+        let mut files = S3File::list(&op, path).await?;
+
+        if !source.pattern.is_empty() {
+            let regex = Regex::new(&source.pattern).map_err(|e| {
+                ErrorCode::SyntaxException(format!(
+                    "Pattern format invalid, got:{}, error:{:?}",
+                    &source.pattern, e
+                ))
+            })?;
+            files.retain(|file| regex.is_match(file));
+        }
+
+        let loaded = history
+            .iter()
+            .map(|f| f.file_name.as_str())
+            .collect::<HashSet<_>>();
+        files.retain(|file| !loaded.contains(file.as_str()));
+        files.sort();
+        files.truncate(PIPE_STAGE_MAX_FILES);
+        Ok(files)
+    }
+
+    fn copy_plan(
+        pipe: &PipeInfo,
+        table: &Arc<dyn Table>,
+        stage_info: &UserStageInfo,
+        path: &str,
+    ) -> CopyPlan {
+        let schema = table.schema();
+        let from = ReadDataSourcePlan {
+            source_info: SourceInfo::S3StageSource(S3StageTableInfo {
+                schema: schema.clone(),
+                stage_info: stage_info.clone(),
+                path: path.to_string(),
+                files: vec![],
+            }),
+            scan_fields: None,
+            parts: vec![],
+            statistics: Default::default(),
+            description: "".to_string(),
+            tbl_args: None,
+            push_downs: None,
+        };
+
+        CopyPlan {
+            db_name: pipe.database.clone(),
+            tbl_name: pipe.table.clone(),
+            tbl_id: table.get_id(),
+            schema,
+            from,
+            validation_mode: ValidationMode::None,
+            files: vec![],
+            pattern: "".to_string(),
+            transformation: vec![],
+        }
+    }
+
+    async fn kafka_consumer(
+        &mut self,
+        pipe_name: &str,
+        source: &KafkaSource,
+    ) -> Result<Arc<KafkaConsumer>> {
+        if let Some(consumer) = self.kafka_consumers.get(pipe_name) {
+            // the pipe may be recreated with another source
+            if consumer.source() == source {
+                return Ok(consumer.clone());
+            }
+        }
+
+        let consumer = Arc::new(KafkaConsumer::connect(source).await?);
+        self.kafka_consumers
+            .insert(pipe_name.to_string(), consumer.clone());
+        Ok(consumer)
+    }
+}
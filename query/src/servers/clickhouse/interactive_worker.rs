@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -100,16 +101,23 @@ impl ClickHouseSession for InteractiveWorker {
 
     async fn authenticate(&self, user: &str, password: &[u8], client_addr: &str) -> bool {
         // Here we don't handle the create context error.
-        let client_ip = client_addr.split(':').collect::<Vec<_>>()[0];
+        // The ip of an IPv6 address is enclosed in brackets, e.g. "[::1]:9000".
+        let client_ip = match client_addr.parse::<SocketAddr>() {
+            Ok(addr) => addr.ip().to_string(),
+            Err(_) => client_addr.to_string(),
+        };
         let credential = Credential::Password {
             name: user.to_string(),
             password: Some(password.to_owned()),
-            hostname: Some(client_ip.to_string()),
+            hostname: Some(client_ip.clone()),
         };
         let ctx = self.session.create_query_context().await;
         match ctx {
             Ok(c) => {
-                let user_info_auth = c.get_auth_manager().auth(&credential).await;
+                let user_info_auth = c
+                    .get_auth_manager()
+                    .auth(&credential, Some(&client_ip))
+                    .await;
                 match user_info_auth {
                     Ok((tenant_id, user_info)) => {
                         self.session.set_current_user(user_info);
@@ -31,6 +31,7 @@ use common_exception::ToErrorCode;
 use common_planners::InsertPlan;
 use common_planners::PlanNode;
 use common_tracing::tracing;
+use common_tracing::tracing::Instrument;
 use futures::channel::mpsc;
 use futures::channel::mpsc::Receiver;
 use futures::SinkExt;
@@ -61,6 +62,7 @@ pub enum BlockItem {
 }
 
 impl InteractiveWorkerBase {
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn do_query(
         ch_ctx: &mut CHContext,
         session: SessionRef,
@@ -137,10 +139,13 @@ impl InteractiveWorkerBase {
             // the data is coming in async mode
             let sent_all_data = ch_ctx.state.sent_all_data.clone();
             let start = Instant::now();
-            ctx.try_spawn(async move {
-                interpreter.execute(None).await.unwrap();
-                sent_all_data.notify_one();
-            })?;
+            ctx.try_spawn(
+                async move {
+                    interpreter.execute(None).await.unwrap();
+                    sent_all_data.notify_one();
+                }
+                .in_current_span(),
+            )?;
             histogram!(
                 super::clickhouse_metrics::METRIC_INTERPRETER_USEDTIME,
                 start.elapsed(),
@@ -155,13 +160,16 @@ impl InteractiveWorkerBase {
         // the data is coming in async mode
         let sent_all_data = ch_ctx.state.sent_all_data.clone();
         let start = Instant::now();
-        ctx.try_spawn(async move {
-            interpreter
-                .execute(Some(Box::pin(ck_stream)))
-                .await
-                .unwrap();
-            sent_all_data.notify_one();
-        })?;
+        ctx.try_spawn(
+            async move {
+                interpreter
+                    .execute(Some(Box::pin(ck_stream)))
+                    .await
+                    .unwrap();
+                sent_all_data.notify_one();
+            }
+            .in_current_span(),
+        )?;
         histogram!(
             super::clickhouse_metrics::METRIC_INTERPRETER_USEDTIME,
             start.elapsed(),
@@ -209,32 +217,35 @@ impl InteractiveWorkerBase {
             }
         });
 
-        let query_result = ctx.try_spawn(async move {
-            // Query log start.
-            let _ = interpreter
-                .start()
-                .await
-                .map_err(|e| tracing::error!("interpreter.start.error: {:?}", e));
+        let query_result = ctx.try_spawn(
+            async move {
+                // Query log start.
+                let _ = interpreter
+                    .start()
+                    .await
+                    .map_err(|e| tracing::error!("interpreter.start.error: {:?}", e));
 
-            // Execute and read stream data.
-            match interpreter.execute(None).await {
-                Err(e) => {
-                    cancel_clone.store(true, Ordering::Relaxed);
-                    Err(e)
-                }
-                Ok(mut data_stream) => {
-                    while let Some(block) = data_stream.next().await {
-                        data_tx.send(BlockItem::Block(block)).await.ok();
+                // Execute and read stream data.
+                match interpreter.execute(None).await {
+                    Err(e) => {
+                        cancel_clone.store(true, Ordering::Relaxed);
+                        Err(e)
+                    }
+                    Ok(mut data_stream) => {
+                        while let Some(block) = data_stream.next().await {
+                            data_tx.send(BlockItem::Block(block)).await.ok();
+                        }
+                        let _ = interpreter
+                            .finish()
+                            .await
+                            .map_err(|e| tracing::error!("interpreter.finish.error: {:?}", e));
+                        cancel_clone.store(true, Ordering::Relaxed);
+                        Ok::<(), ErrorCode>(())
                     }
-                    let _ = interpreter
-                        .finish()
-                        .await
-                        .map_err(|e| tracing::error!("interpreter.finish.error: {:?}", e));
-                    cancel_clone.store(true, Ordering::Relaxed);
-                    Ok::<(), ErrorCode>(())
                 }
             }
-        })?;
+            .in_current_span(),
+        )?;
         let query_result = query_result
             .await
             .map_err_to_code(ErrorCode::TokioError, || {
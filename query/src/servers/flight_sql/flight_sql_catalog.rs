@@ -0,0 +1,185 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::io::flight::serialize_schema_to_info;
+use common_arrow::arrow::io::ipc::write::default_ipc_fields;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::like_pattern_to_regex;
+use regex::Regex;
+
+use crate::servers::flight_sql::flight_sql_protocol::CommandGetDbSchemas;
+use crate::servers::flight_sql::flight_sql_protocol::CommandGetTables;
+use crate::servers::flight_sql::flight_sql_protocol::FlightSQLCommand;
+use crate::sessions::QueryContext;
+use crate::storages::view::view_table::VIEW_ENGINE;
+
+// The databases are the db schemas of the only catalog.
+const CATALOG_NAME: &str = "default";
+const TABLE_TYPES: [&str; 2] = ["TABLE", "VIEW"];
+
+/// The schema of the result of a metadata command.
+pub fn metadata_schema(command: &FlightSQLCommand) -> Result<DataSchemaRef> {
+    let fields = match command {
+        FlightSQLCommand::GetCatalogs(_) => {
+            vec![DataField::new("catalog_name", Vu8::to_data_type())]
+        }
+        FlightSQLCommand::GetDbSchemas(_) => vec![
+            DataField::new("catalog_name", Vu8::to_data_type()),
+            DataField::new("db_schema_name", Vu8::to_data_type()),
+        ],
+        FlightSQLCommand::GetTables(command) => {
+            let mut fields = vec![
+                DataField::new("catalog_name", Vu8::to_data_type()),
+                DataField::new("db_schema_name", Vu8::to_data_type()),
+                DataField::new("table_name", Vu8::to_data_type()),
+                DataField::new("table_type", Vu8::to_data_type()),
+            ];
+            if command.include_schema {
+                fields.push(DataField::new("table_schema", Vu8::to_data_type()));
+            }
+            fields
+        }
+        FlightSQLCommand::GetTableTypes(_) => {
+            vec![DataField::new("table_type", Vu8::to_data_type())]
+        }
+        _ => {
+            return Err(ErrorCode::LogicalError(
+                "Logical error: not a Flight SQL metadata command.",
+            ))
+        }
+    };
+    Ok(DataSchemaRefExt::create(fields))
+}
+
+/// The result of a metadata command.
+pub async fn metadata_block(ctx: &QueryContext, command: &FlightSQLCommand) -> Result<DataBlock> {
+    let schema = metadata_schema(command)?;
+    let columns = match command {
+        FlightSQLCommand::GetCatalogs(_) => vec![Series::from_data(vec![CATALOG_NAME])],
+        FlightSQLCommand::GetDbSchemas(command) => db_schemas_columns(ctx, command).await?,
+        FlightSQLCommand::GetTables(command) => tables_columns(ctx, command).await?,
+        FlightSQLCommand::GetTableTypes(_) => vec![Series::from_data(TABLE_TYPES.to_vec())],
+        _ => {
+            return Err(ErrorCode::LogicalError(
+                "Logical error: not a Flight SQL metadata command.",
+            ))
+        }
+    };
+    Ok(DataBlock::create(schema, columns))
+}
+
+async fn db_schemas_columns(
+    ctx: &QueryContext,
+    command: &CommandGetDbSchemas,
+) -> Result<Vec<ColumnRef>> {
+    let mut db_schema_names = vec![];
+    if matches_catalog(&command.catalog) {
+        let db_schema_pattern = like_regex(&command.db_schema_filter_pattern)?;
+        let tenant = ctx.get_tenant();
+        for database in ctx.get_catalog().list_databases(&tenant).await? {
+            if is_match(&db_schema_pattern, database.name()) {
+                db_schema_names.push(database.name().to_string());
+            }
+        }
+    }
+
+    db_schema_names.sort();
+    let catalog_names = vec![CATALOG_NAME; db_schema_names.len()];
+    Ok(vec![
+        Series::from_data(catalog_names),
+        Series::from_data(db_schema_names),
+    ])
+}
+
+async fn tables_columns(ctx: &QueryContext, command: &CommandGetTables) -> Result<Vec<ColumnRef>> {
+    let mut tables = vec![];
+    if matches_catalog(&command.catalog) {
+        let db_schema_pattern = like_regex(&command.db_schema_filter_pattern)?;
+        let table_name_pattern = like_regex(&command.table_name_filter_pattern)?;
+        let tenant = ctx.get_tenant();
+        let catalog = ctx.get_catalog();
+        for database in catalog.list_databases(&tenant).await? {
+            if !is_match(&db_schema_pattern, database.name()) {
+                continue;
+            }
+
+            for table in catalog.list_tables(&tenant, database.name()).await? {
+                let table_type = match table.engine() == VIEW_ENGINE {
+                    true => "VIEW",
+                    false => "TABLE",
+                };
+                let table_type_matched = command.table_types.is_empty()
+                    || command.table_types.iter().any(|typ| typ == table_type);
+                if table_type_matched && is_match(&table_name_pattern, table.name()) {
+                    tables.push((database.name().to_string(), table_type, table));
+                }
+            }
+        }
+    }
+
+    tables.sort_by(|(db1, typ1, t1), (db2, typ2, t2)| {
+        (db1, t1.name(), typ1).cmp(&(db2, t2.name(), typ2))
+    });
+    let mut columns = vec![
+        Series::from_data(vec![CATALOG_NAME; tables.len()]),
+        Series::from_data(
+            tables
+                .iter()
+                .map(|(db, _, _)| db.as_str())
+                .collect::<Vec<_>>(),
+        ),
+        Series::from_data(tables.iter().map(|(_, _, t)| t.name()).collect::<Vec<_>>()),
+        Series::from_data(tables.iter().map(|(_, typ, _)| *typ).collect::<Vec<_>>()),
+    ];
+    if command.include_schema {
+        let mut table_schemas = Vec::with_capacity(tables.len());
+        for (_, _, table) in &tables {
+            let arrow_schema = table.schema().to_arrow();
+            let ipc_fields = default_ipc_fields(&arrow_schema.fields);
+            table_schemas.push(serialize_schema_to_info(&arrow_schema, Some(&ipc_fields))?);
+        }
+        columns.push(Series::from_data(table_schemas));
+    }
+    Ok(columns)
+}
+
+fn matches_catalog(catalog: &Option<String>) -> bool {
+    match catalog {
+        None => true,
+        Some(catalog) => catalog.is_empty() || catalog == CATALOG_NAME,
+    }
+}
+
+fn like_regex(pattern: &Option<String>) -> Result<Option<Regex>> {
+    match pattern {
+        None => Ok(None),
+        Some(pattern) => match Regex::new(&like_pattern_to_regex(pattern)) {
+            Ok(regex) => Ok(Some(regex)),
+            Err(cause) => Err(ErrorCode::BadArguments(format!(
+                "Invalid filter pattern {}: {}",
+                pattern, cause
+            ))),
+        },
+    }
+}
+
+fn is_match(pattern: &Option<Regex>, name: &str) -> bool {
+    match pattern {
+        None => true,
+        Some(regex) => regex.is_match(name),
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The messages of the Flight SQL protocol served by `FlightSQLService`, a subset of
+//! https://github.com/apache/arrow/blob/master/format/FlightSql.proto.
+//! The commands are packed in `google.protobuf.Any` into the flight descriptors, tickets and
+//! actions of the Arrow Flight protocol.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use prost::Message;
+
+const TYPE_URL_PREFIX: &str = "type.googleapis.com/arrow.flight.protocol.sql.";
+
+pub const CREATE_PREPARED_STATEMENT: &str = "CreatePreparedStatement";
+pub const CLOSE_PREPARED_STATEMENT: &str = "ClosePreparedStatement";
+
+/// The same wire format as `google.protobuf.Any`.
+#[derive(Clone, PartialEq, Message)]
+pub struct Any {
+    #[prost(string, tag = "1")]
+    pub type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub value: Vec<u8>,
+}
+
+/// A message of the Flight SQL protocol, named as in FlightSql.proto.
+pub trait FlightSQLMessage: Message + Default {
+    const NAME: &'static str;
+
+    /// Encodes the message packed in `google.protobuf.Any`.
+    fn pack(&self) -> Vec<u8> {
+        Any {
+            type_url: format!("{}{}", TYPE_URL_PREFIX, Self::NAME),
+            value: self.encode_to_vec(),
+        }
+        .encode_to_vec()
+    }
+
+    fn decode_from(bytes: &[u8]) -> Result<Self> {
+        Self::decode(bytes).map_err(|cause| {
+            ErrorCode::BadBytes(format!("Cannot decode {}: {}", Self::NAME, cause))
+        })
+    }
+}
+
+macro_rules! flight_sql_message {
+    ($($message:ident),*) => {
+        $(impl FlightSQLMessage for $message {
+            const NAME: &'static str = stringify!($message);
+        })*
+    };
+}
+
+flight_sql_message!(
+    CommandGetCatalogs,
+    CommandGetDbSchemas,
+    CommandGetTables,
+    CommandGetTableTypes,
+    CommandStatementQuery,
+    TicketStatementQuery,
+    CommandPreparedStatementQuery,
+    ActionCreatePreparedStatementRequest,
+    ActionCreatePreparedStatementResult,
+    ActionClosePreparedStatementRequest
+);
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandGetCatalogs {}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandGetDbSchemas {
+    #[prost(string, optional, tag = "1")]
+    pub catalog: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub db_schema_filter_pattern: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandGetTables {
+    #[prost(string, optional, tag = "1")]
+    pub catalog: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub db_schema_filter_pattern: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub table_name_filter_pattern: Option<String>,
+    #[prost(string, repeated, tag = "4")]
+    pub table_types: Vec<String>,
+    #[prost(bool, tag = "5")]
+    pub include_schema: bool,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandGetTableTypes {}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandStatementQuery {
+    #[prost(string, tag = "1")]
+    pub query: String,
+}
+
+/// The ticket of a statement, whose handle is the query itself.
+#[derive(Clone, PartialEq, Message)]
+pub struct TicketStatementQuery {
+    #[prost(bytes = "vec", tag = "1")]
+    pub statement_handle: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandPreparedStatementQuery {
+    #[prost(bytes = "vec", tag = "1")]
+    pub prepared_statement_handle: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ActionCreatePreparedStatementRequest {
+    #[prost(string, tag = "1")]
+    pub query: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ActionCreatePreparedStatementResult {
+    #[prost(bytes = "vec", tag = "1")]
+    pub prepared_statement_handle: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub dataset_schema: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub parameter_schema: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ActionClosePreparedStatementRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub prepared_statement_handle: Vec<u8>,
+}
+
+/// The commands of the flight descriptors and tickets.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FlightSQLCommand {
+    GetCatalogs(CommandGetCatalogs),
+    GetDbSchemas(CommandGetDbSchemas),
+    GetTables(CommandGetTables),
+    GetTableTypes(CommandGetTableTypes),
+    StatementQuery(CommandStatementQuery),
+    TicketStatementQuery(TicketStatementQuery),
+    PreparedStatementQuery(CommandPreparedStatementQuery),
+}
+
+impl FlightSQLCommand {
+    pub fn pack(&self) -> Vec<u8> {
+        match self {
+            FlightSQLCommand::GetCatalogs(command) => command.pack(),
+            FlightSQLCommand::GetDbSchemas(command) => command.pack(),
+            FlightSQLCommand::GetTables(command) => command.pack(),
+            FlightSQLCommand::GetTableTypes(command) => command.pack(),
+            FlightSQLCommand::StatementQuery(command) => command.pack(),
+            FlightSQLCommand::TicketStatementQuery(command) => command.pack(),
+            FlightSQLCommand::PreparedStatementQuery(command) => command.pack(),
+        }
+    }
+
+    pub fn unpack(bytes: &[u8]) -> Result<FlightSQLCommand> {
+        let any = Any::decode(bytes).map_err(|cause| {
+            ErrorCode::BadBytes(format!("Cannot decode the Flight SQL command: {}", cause))
+        })?;
+
+        let name = match any.type_url.strip_prefix(TYPE_URL_PREFIX) {
+            Some(name) => name,
+            None => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Unknown Flight SQL command: {}",
+                    any.type_url
+                )));
+            }
+        };
+
+        let value = any.value.as_slice();
+        match name {
+            CommandGetCatalogs::NAME => Ok(FlightSQLCommand::GetCatalogs(
+                CommandGetCatalogs::decode_from(value)?,
+            )),
+            CommandGetDbSchemas::NAME => Ok(FlightSQLCommand::GetDbSchemas(
+                CommandGetDbSchemas::decode_from(value)?,
+            )),
+            CommandGetTables::NAME => Ok(FlightSQLCommand::GetTables(
+                CommandGetTables::decode_from(value)?,
+            )),
+            CommandGetTableTypes::NAME => Ok(FlightSQLCommand::GetTableTypes(
+                CommandGetTableTypes::decode_from(value)?,
+            )),
+            CommandStatementQuery::NAME => Ok(FlightSQLCommand::StatementQuery(
+                CommandStatementQuery::decode_from(value)?,
+            )),
+            TicketStatementQuery::NAME => Ok(FlightSQLCommand::TicketStatementQuery(
+                TicketStatementQuery::decode_from(value)?,
+            )),
+            CommandPreparedStatementQuery::NAME => Ok(FlightSQLCommand::PreparedStatementQuery(
+                CommandPreparedStatementQuery::decode_from(value)?,
+            )),
+            _ => Err(ErrorCode::UnImplement(format!(
+                "Unsupported Flight SQL command: {}",
+                name
+            ))),
+        }
+    }
+}
+
+/// Decodes the body of an action, which is packed in `google.protobuf.Any` like the commands.
+pub fn unpack_action<M: FlightSQLMessage>(body: &[u8]) -> Result<M> {
+    let any = Any::decode(body).map_err(|cause| {
+        ErrorCode::BadBytes(format!("Cannot decode the Flight SQL action: {}", cause))
+    })?;
+    M::decode_from(&any.value)
+}
@@ -0,0 +1,393 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use common_arrow::arrow::io::flight::serialize_schema;
+use common_arrow::arrow::io::flight::serialize_schema_to_info;
+use common_arrow::arrow::io::ipc::write::default_ipc_fields;
+use common_arrow::arrow_format::flight::data::Action;
+use common_arrow::arrow_format::flight::data::ActionType;
+use common_arrow::arrow_format::flight::data::Criteria;
+use common_arrow::arrow_format::flight::data::Empty;
+use common_arrow::arrow_format::flight::data::FlightData;
+use common_arrow::arrow_format::flight::data::FlightDescriptor;
+use common_arrow::arrow_format::flight::data::FlightEndpoint;
+use common_arrow::arrow_format::flight::data::FlightInfo;
+use common_arrow::arrow_format::flight::data::HandshakeRequest;
+use common_arrow::arrow_format::flight::data::HandshakeResponse;
+use common_arrow::arrow_format::flight::data::PutResult;
+use common_arrow::arrow_format::flight::data::Result as FlightResult;
+use common_arrow::arrow_format::flight::data::SchemaResult;
+use common_arrow::arrow_format::flight::data::Ticket;
+use common_arrow::arrow_format::flight::service::flight_service_server::FlightService;
+use common_base::tokio;
+use common_base::tokio::sync::mpsc::channel;
+use common_base::tokio::sync::mpsc::Receiver;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_meta_types::UserInfo;
+use common_tracing::tracing;
+use futures::StreamExt;
+use tonic::metadata::MetadataMap;
+use tonic::Request;
+use tonic::Response as RawResponse;
+use tonic::Status;
+use tonic::Streaming;
+use uuid::Uuid;
+
+use crate::api::FlightDataStream;
+use crate::api::FlightStream;
+use crate::interpreters::InterpreterFactory;
+use crate::servers::flight_sql::flight_sql_catalog::metadata_block;
+use crate::servers::flight_sql::flight_sql_catalog::metadata_schema;
+use crate::servers::flight_sql::flight_sql_protocol::unpack_action;
+use crate::servers::flight_sql::flight_sql_protocol::ActionClosePreparedStatementRequest;
+use crate::servers::flight_sql::flight_sql_protocol::ActionCreatePreparedStatementRequest;
+use crate::servers::flight_sql::flight_sql_protocol::ActionCreatePreparedStatementResult;
+use crate::servers::flight_sql::flight_sql_protocol::FlightSQLCommand;
+use crate::servers::flight_sql::flight_sql_protocol::FlightSQLMessage;
+use crate::servers::flight_sql::flight_sql_protocol::TicketStatementQuery;
+use crate::servers::flight_sql::flight_sql_protocol::CLOSE_PREPARED_STATEMENT;
+use crate::servers::flight_sql::flight_sql_protocol::CREATE_PREPARED_STATEMENT;
+use crate::servers::http::middleware::get_credential;
+use crate::sessions::QueryContext;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionRef;
+use crate::sessions::SessionType;
+use crate::sql::PlanParser;
+
+type Response<T> = std::result::Result<RawResponse<T>, Status>;
+type StreamReq<T> = Request<Streaming<T>>;
+
+/// Serves the Flight SQL protocol. The clients authenticate each call with the `authorization`
+/// header, which the handshake checks and sends back.
+pub struct FlightSQLService {
+    sessions: Arc<SessionManager>,
+    // The queries of the prepared statements, by their handles.
+    prepared_statements: Mutex<HashMap<Vec<u8>, String>>,
+}
+
+impl FlightSQLService {
+    pub fn create(sessions: Arc<SessionManager>) -> FlightSQLService {
+        FlightSQLService {
+            sessions,
+            prepared_statements: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn authenticate(
+        &self,
+        metadata: &MetadataMap,
+        remote_addr: Option<SocketAddr>,
+    ) -> Result<(Option<String>, UserInfo)> {
+        let auth_manager = self.sessions.get_auth_manager();
+        let client_ip = remote_addr.map(|addr| addr.ip().to_string());
+        let client_ip = client_ip.as_deref();
+        match get_credential(&metadata.clone().into_headers())? {
+            Some(credential) => auth_manager.auth(&credential, client_ip).await,
+            None => Ok((None, auth_manager.no_auth(client_ip).await?)),
+        }
+    }
+
+    async fn create_session(
+        &self,
+        metadata: &MetadataMap,
+        remote_addr: Option<SocketAddr>,
+    ) -> Result<SessionRef> {
+        let (tenant, user) = self.authenticate(metadata, remote_addr).await?;
+        let session = self.sessions.create_session(SessionType::FlightSQL).await?;
+        session.set_current_user(user);
+        if let Some(tenant) = tenant {
+            session.set_current_tenant(tenant);
+        }
+        Ok(session)
+    }
+
+    async fn query_schema(session: &SessionRef, query: &str) -> Result<DataSchemaRef> {
+        let ctx = session.create_query_context().await?;
+        let plan = PlanParser::parse(ctx, query).await?;
+        Ok(plan.schema())
+    }
+
+    fn prepared_query(&self, handle: &[u8]) -> Result<String> {
+        match self.prepared_statements.lock().get(handle) {
+            Some(query) => Ok(query.clone()),
+            None => Err(ErrorCode::BadArguments("Unknown prepared statement handle")),
+        }
+    }
+
+    /// The schema of the result of the command of a flight descriptor, and the command of the
+    /// ticket to get the result.
+    async fn describe(
+        &self,
+        session: &SessionRef,
+        command: FlightSQLCommand,
+    ) -> Result<(DataSchemaRef, FlightSQLCommand)> {
+        let query = match command {
+            FlightSQLCommand::StatementQuery(command) => command.query,
+            FlightSQLCommand::PreparedStatementQuery(command) => {
+                self.prepared_query(&command.prepared_statement_handle)?
+            }
+            FlightSQLCommand::TicketStatementQuery(_) => {
+                return Err(ErrorCode::BadArguments(
+                    "TicketStatementQuery is not a command of flight descriptors",
+                ));
+            }
+            metadata => return Ok((metadata_schema(&metadata)?, metadata)),
+        };
+
+        let schema = Self::query_schema(session, &query).await?;
+        let ticket = FlightSQLCommand::TicketStatementQuery(TicketStatementQuery {
+            statement_handle: query.into_bytes(),
+        });
+        Ok((schema, ticket))
+    }
+
+    async fn execute_query(
+        session: SessionRef,
+        ctx: Arc<QueryContext>,
+        query: &str,
+    ) -> Result<(DataSchemaRef, Receiver<Result<DataBlock>>)> {
+        ctx.attach_query_str(query);
+        let plan = PlanParser::parse(ctx.clone(), query).await?;
+        let schema = plan.schema();
+        let interpreter = InterpreterFactory::get(ctx.clone(), plan)?;
+        interpreter.start().await?;
+        let data_stream = match interpreter.execute(None).await {
+            Ok(data_stream) => data_stream,
+            Err(cause) => {
+                let _ = interpreter.finish().await;
+                return Err(cause);
+            }
+        };
+        let mut data_stream = ctx.try_create_abortable(data_stream)?;
+
+        let (sender, receiver) = channel(2);
+        tokio::spawn(async move {
+            while let Some(block) = data_stream.next().await {
+                if sender.send(block).await.is_err() {
+                    break;
+                }
+            }
+
+            if let Err(cause) = interpreter.finish().await {
+                tracing::error!("interpreter.finish error: {:?}", cause);
+            }
+            drop(session);
+        });
+        Ok((schema, receiver))
+    }
+}
+
+#[async_trait::async_trait]
+impl FlightService for FlightSQLService {
+    type HandshakeStream = FlightStream<HandshakeResponse>;
+
+    async fn handshake(
+        &self,
+        request: StreamReq<HandshakeRequest>,
+    ) -> Response<Self::HandshakeStream> {
+        self.authenticate(request.metadata(), request.remote_addr())
+            .await?;
+
+        let handshake_response = HandshakeResponse {
+            protocol_version: 0,
+            payload: vec![],
+        };
+        let mut response =
+            RawResponse::new(Box::pin(tokio_stream::once(Ok(handshake_response)))
+                as FlightStream<HandshakeResponse>);
+        if let Some(authorization) = request.metadata().get("authorization") {
+            response
+                .metadata_mut()
+                .insert("authorization", authorization.clone());
+        }
+        Ok(response)
+    }
+
+    type ListFlightsStream = FlightStream<FlightInfo>;
+
+    async fn list_flights(&self, _: Request<Criteria>) -> Response<Self::ListFlightsStream> {
+        Err(Status::unimplemented(
+            "DatabendQuery does not implement list_flights.",
+        ))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn get_flight_info(&self, request: Request<FlightDescriptor>) -> Response<FlightInfo> {
+        let session = self
+            .create_session(request.metadata(), request.remote_addr())
+            .await?;
+        let descriptor = request.into_inner();
+        let command = FlightSQLCommand::unpack(&descriptor.cmd)?;
+        let (schema, ticket) = self.describe(&session, command).await?;
+
+        let arrow_schema = schema.to_arrow();
+        let ipc_fields = default_ipc_fields(&arrow_schema.fields);
+        let schema =
+            serialize_schema_to_info(&arrow_schema, Some(&ipc_fields)).map_err(ErrorCode::from)?;
+        Ok(RawResponse::new(FlightInfo {
+            schema,
+            flight_descriptor: Some(descriptor),
+            endpoint: vec![FlightEndpoint {
+                ticket: Some(Ticket {
+                    ticket: ticket.pack(),
+                }),
+                location: vec![],
+            }],
+            total_records: -1,
+            total_bytes: -1,
+        }))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn get_schema(&self, request: Request<FlightDescriptor>) -> Response<SchemaResult> {
+        let session = self
+            .create_session(request.metadata(), request.remote_addr())
+            .await?;
+        let command = FlightSQLCommand::unpack(&request.into_inner().cmd)?;
+        let (schema, _) = self.describe(&session, command).await?;
+
+        let arrow_schema = schema.to_arrow();
+        let ipc_fields = default_ipc_fields(&arrow_schema.fields);
+        let schema =
+            serialize_schema_to_info(&arrow_schema, Some(&ipc_fields)).map_err(ErrorCode::from)?;
+        Ok(RawResponse::new(SchemaResult { schema }))
+    }
+
+    type DoGetStream = FlightStream<FlightData>;
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn do_get(&self, request: Request<Ticket>) -> Response<Self::DoGetStream> {
+        let session = self
+            .create_session(request.metadata(), request.remote_addr())
+            .await?;
+        let command = FlightSQLCommand::unpack(&request.into_inner().ticket)?;
+        let ctx = session.create_query_context().await?;
+
+        let (schema, receiver) = match command {
+            FlightSQLCommand::TicketStatementQuery(ticket) => {
+                let query = String::from_utf8(ticket.statement_handle).map_err(ErrorCode::from)?;
+                Self::execute_query(session, ctx, &query).await?
+            }
+            metadata => {
+                let block = metadata_block(&ctx, &metadata).await?;
+                let (sender, receiver) = channel(1);
+                let schema = block.schema().clone();
+                let _ = sender.send(Ok(block)).await;
+                (schema, receiver)
+            }
+        };
+
+        let arrow_schema = schema.to_arrow();
+        let ipc_fields = default_ipc_fields(&arrow_schema.fields);
+        let schema_data = serialize_schema(&arrow_schema, Some(&ipc_fields));
+        let data_stream = tokio_stream::once(Ok(schema_data))
+            .chain(FlightDataStream::create(receiver, ipc_fields));
+        Ok(RawResponse::new(
+            Box::pin(data_stream) as FlightStream<FlightData>
+        ))
+    }
+
+    type DoPutStream = FlightStream<PutResult>;
+
+    async fn do_put(&self, _: StreamReq<FlightData>) -> Response<Self::DoPutStream> {
+        Err(Status::unimplemented(
+            "DatabendQuery does not implement do_put of Flight SQL.",
+        ))
+    }
+
+    type DoExchangeStream = FlightStream<FlightData>;
+
+    async fn do_exchange(&self, _: StreamReq<FlightData>) -> Response<Self::DoExchangeStream> {
+        Err(Status::unimplemented(
+            "DatabendQuery does not implement do_exchange.",
+        ))
+    }
+
+    type DoActionStream = FlightStream<FlightResult>;
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn do_action(&self, request: Request<Action>) -> Response<Self::DoActionStream> {
+        let session = self
+            .create_session(request.metadata(), request.remote_addr())
+            .await?;
+        let action = request.into_inner();
+
+        let results = match action.r#type.as_str() {
+            CREATE_PREPARED_STATEMENT => {
+                let request: ActionCreatePreparedStatementRequest = unpack_action(&action.body)?;
+                let schema = Self::query_schema(&session, &request.query).await?;
+                let arrow_schema = schema.to_arrow();
+                let ipc_fields = default_ipc_fields(&arrow_schema.fields);
+                let dataset_schema = serialize_schema_to_info(&arrow_schema, Some(&ipc_fields))
+                    .map_err(ErrorCode::from)?;
+
+                // The prepared statements have no parameters.
+                let handle = Uuid::new_v4().to_string().into_bytes();
+                self.prepared_statements
+                    .lock()
+                    .insert(handle.clone(), request.query);
+                let result = ActionCreatePreparedStatementResult {
+                    prepared_statement_handle: handle,
+                    dataset_schema,
+                    parameter_schema: vec![],
+                };
+                vec![Ok(FlightResult {
+                    body: result.pack(),
+                })]
+            }
+            CLOSE_PREPARED_STATEMENT => {
+                let request: ActionClosePreparedStatementRequest = unpack_action(&action.body)?;
+                self.prepared_statements
+                    .lock()
+                    .remove(&request.prepared_statement_handle);
+                vec![]
+            }
+            unknown => {
+                return Err(Status::invalid_argument(format!(
+                    "Unknown Flight SQL action: {}",
+                    unknown
+                )));
+            }
+        };
+
+        Ok(RawResponse::new(
+            Box::pin(tokio_stream::iter(results)) as FlightStream<FlightResult>
+        ))
+    }
+
+    type ListActionsStream = FlightStream<ActionType>;
+
+    async fn list_actions(&self, _: Request<Empty>) -> Response<Self::ListActionsStream> {
+        Ok(RawResponse::new(Box::pin(tokio_stream::iter(vec![
+            Ok(ActionType {
+                r#type: CREATE_PREPARED_STATEMENT.to_string(),
+                description: "Creates a reusable prepared statement resource on the server"
+                    .to_string(),
+            }),
+            Ok(ActionType {
+                r#type: CLOSE_PREPARED_STATEMENT.to_string(),
+                description: "Closes a reusable prepared statement resource on the server"
+                    .to_string(),
+            }),
+        ])) as FlightStream<ActionType>))
+    }
+}
@@ -0,0 +1,25 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use flight_sql_catalog::metadata_block;
+pub use flight_sql_catalog::metadata_schema;
+pub use flight_sql_handler::FlightSQLHandler;
+pub use flight_sql_protocol::FlightSQLCommand;
+pub use flight_sql_protocol::FlightSQLMessage;
+pub use flight_sql_service::FlightSQLService;
+
+mod flight_sql_catalog;
+mod flight_sql_handler;
+pub mod flight_sql_protocol;
+mod flight_sql_service;
@@ -39,7 +39,8 @@ use crate::interpreters::InterpreterFactory;
 use crate::pipelines::new::processors::port::OutputPort;
 use crate::pipelines::new::processors::StreamSource;
 use crate::pipelines::new::SourcePipeBuilder;
-use crate::servers::http::formats::tsv_output::block_to_tsv;
+use crate::servers::http::formats::output_format::OutputFormat;
+use crate::servers::http::formats::output_format::OutputFormatWriter;
 use crate::servers::http::formats::Format;
 use crate::servers::http::v1::HttpQueryContext;
 use crate::sessions::QueryContext;
@@ -65,7 +66,9 @@ async fn execute(
     ctx: Arc<QueryContext>,
     plan: PlanNode,
     input_stream: Option<SendableDataBlockStream>,
+    format: OutputFormat,
 ) -> Result<Body> {
+    let mut writer = OutputFormatWriter::create(format, plan.schema());
     let interpreter = InterpreterFactory::get(ctx.clone(), plan.clone())?;
     let _ = interpreter
         .start()
@@ -90,14 +93,16 @@ async fn execute(
     let mut data_stream = ctx.try_create_abortable(data_stream)?;
 
     let stream = stream! {
+        yield(writer.start());
         while let Some(block) = data_stream.next().await {
             match block{
                 Ok(block) => {
-                    yield(block_to_tsv(&block))
+                    yield(writer.write_block(block))
                 },
                 Err(err) => yield(Err(err)),
             };
         }
+        yield(writer.finish());
 
         let _ = interpreter
             .finish()
@@ -123,8 +128,9 @@ pub async fn clickhouse_handler_get(
         .await
         .map_err(InternalServerError)?;
 
-    let mut sql = params.query;
-    let plan = PlanParser::parse(context.clone(), &sql)
+    let sql = params.query;
+    let (query, format) = try_parse_output_format(&sql).map_err(BadRequest)?;
+    let plan = PlanParser::parse(context.clone(), &query)
         .await
         .map_err(BadRequest)?;
 
@@ -134,11 +140,29 @@ pub async fn clickhouse_handler_get(
         )));
     }
     context.attach_query_str(&sql);
-    execute(context, plan, None)
+    execute(context, plan, None, format)
         .await
         .map_err(InternalServerError)
 }
 
+// Splits the trailing `FORMAT <name>` clause from a query, e.g. `SELECT 1 FORMAT JSONEachRow`.
+// The FORMAT of an INSERT names the input format and is left to `try_parse_insert_formatted`.
+fn try_parse_output_format(sql: &str) -> Result<(String, OutputFormat)> {
+    let trimmed = sql.trim_end().trim_end_matches(';').trim_end();
+    if let Some((rest, name)) = trimmed.rsplit_once(char::is_whitespace) {
+        if let Some((query, keyword)) = rest.trim_end().rsplit_once(char::is_whitespace) {
+            let is_insert = query
+                .trim_start()
+                .get(..6)
+                .map_or(false, |s| s.eq_ignore_ascii_case("insert"));
+            if keyword.eq_ignore_ascii_case("format") && !is_insert {
+                return Ok((query.to_string(), OutputFormat::from_name(name)?));
+            }
+        }
+    }
+    Ok((sql.to_string(), OutputFormat::Tsv))
+}
+
 fn try_parse_insert_formatted(
     sql: &str,
     typ: SessionType,
@@ -187,10 +211,10 @@ pub async fn clickhouse_handler_post(
         .await
         .map_err(InternalServerError)?;
 
-    let mut sql = params.query;
+    let sql = params.query;
 
     // Insert into format sql
-    let (plan, input_stream) = if let Some((format, statements)) =
+    let (plan, input_stream, output_format) = if let Some((format, statements)) =
         try_parse_insert_formatted(&sql, ctx.get_current_session().get_type())
             .map_err(BadRequest)?
     {
@@ -202,23 +226,24 @@ pub async fn clickhouse_handler_post(
         let input_stream = match format {
             Format::NDJson => build_ndjson_stream(&plan, body).await.map_err(BadRequest)?,
         };
-        (plan, Some(input_stream))
+        (plan, Some(input_stream), OutputFormat::Tsv)
     } else {
         // Other sql
         let body = body.into_string().await.map_err(BadRequest)?;
         let sql = format!("{}\n{}", sql, body);
-        let (statements, _) =
-            DfParser::parse_sql(&sql, ctx.get_current_session().get_type()).map_err(BadRequest)?;
+        let (query, output_format) = try_parse_output_format(&sql).map_err(BadRequest)?;
+        let (statements, _) = DfParser::parse_sql(&query, ctx.get_current_session().get_type())
+            .map_err(BadRequest)?;
 
         let plan = PlanParser::build_plan(statements, ctx.clone())
             .await
             .map_err(InternalServerError)?;
         ctx.attach_query_str(&sql);
 
-        (plan, None)
+        (plan, None, output_format)
     };
 
-    execute(ctx, plan, input_stream)
+    execute(ctx, plan, input_stream, output_format)
         .await
         .map_err(InternalServerError)
 }
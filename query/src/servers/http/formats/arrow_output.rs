@@ -0,0 +1,34 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::io::ipc::write::FileWriter;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+
+// Arrow IPC file format, see https://arrow.apache.org/docs/format/Columnar.html#ipc-file-format
+pub fn blocks_to_arrow(schema: &DataSchemaRef, blocks: Vec<DataBlock>) -> Result<Vec<u8>> {
+    let arrow_schema = schema.to_arrow();
+    let options = WriteOptions { compression: None };
+
+    let mut buf = vec![];
+    let mut writer = FileWriter::try_new(&mut buf, &arrow_schema, None, options)?;
+    for block in blocks {
+        writer.write(&Chunk::try_from(block)?, None)?;
+    }
+    writer.finish()?;
+    Ok(buf)
+}
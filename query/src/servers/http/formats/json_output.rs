@@ -0,0 +1,56 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataType;
+use common_datavalues::TypeSerializer;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::Map;
+use serde_json::Value;
+
+const ROW_DELIMITER: u8 = b'\n';
+
+pub fn block_to_json_each_row(block: &DataBlock) -> Result<Vec<u8>> {
+    let rows_size = block.num_rows();
+    let columns_size = block.num_columns();
+
+    let mut col_table = Vec::with_capacity(columns_size);
+    for col_index in 0..columns_size {
+        let column = block.column(col_index);
+        let column = column.convert_full_column();
+        let field = block.schema().field(col_index);
+        let data_type = field.data_type();
+        let serializer = data_type.create_serializer();
+        col_table.push(serializer.serialize_json(&column).map_err(|e| {
+            ErrorCode::UnexpectedError(format!(
+                "fail to serialize filed {}, error = {}",
+                field.name(),
+                e
+            ))
+        })?);
+    }
+
+    let mut buf = vec![];
+    for row_index in 0..rows_size {
+        let mut row = Map::with_capacity(columns_size);
+        for (col_index, col) in col_table.iter_mut().enumerate() {
+            let name = block.schema().field(col_index).name().clone();
+            row.insert(name, std::mem::take(&mut col[row_index]));
+        }
+        serde_json::to_writer(&mut buf, &Value::Object(row))?;
+        buf.push(ROW_DELIMITER);
+    }
+    Ok(buf)
+}
@@ -12,6 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod arrow_output;
+pub mod json_output;
+pub mod output_format;
+pub mod parquet_output;
 pub mod tsv_output;
 
 pub enum Format {
@@ -0,0 +1,115 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::servers::http::formats::arrow_output::blocks_to_arrow;
+use crate::servers::http::formats::json_output::block_to_json_each_row;
+use crate::servers::http::formats::parquet_output::blocks_to_parquet;
+use crate::servers::http::formats::tsv_output::block_to_tsv;
+use crate::servers::http::formats::tsv_output::schema_to_tsv_names;
+
+const FORMAT_TSV: &str = "TabSeparated";
+const FORMAT_TSV_WITH_NAMES: &str = "TabSeparatedWithNames";
+const FORMAT_JSON_EACH_ROW: &str = "JSONEachRow";
+const FORMAT_ARROW: &str = "Arrow";
+const FORMAT_PARQUET: &str = "Parquet";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    Tsv,
+    TsvWithNames,
+    NDJson,
+    Arrow,
+    Parquet,
+}
+
+impl OutputFormat {
+    // Format names are case-sensitive, as in ClickHouse.
+    pub fn from_name(name: &str) -> Result<OutputFormat> {
+        match name {
+            FORMAT_TSV | "TSV" => Ok(OutputFormat::Tsv),
+            FORMAT_TSV_WITH_NAMES | "TSVWithNames" => Ok(OutputFormat::TsvWithNames),
+            FORMAT_JSON_EACH_ROW => Ok(OutputFormat::NDJson),
+            FORMAT_ARROW => Ok(OutputFormat::Arrow),
+            FORMAT_PARQUET => Ok(OutputFormat::Parquet),
+            _ => Err(ErrorCode::SyntaxException(format!(
+                "output format {} not supported; only support: {}",
+                name,
+                Self::supported_formats()
+            ))),
+        }
+    }
+
+    pub fn supported_formats() -> String {
+        vec![
+            FORMAT_TSV,
+            FORMAT_TSV_WITH_NAMES,
+            FORMAT_JSON_EACH_ROW,
+            FORMAT_ARROW,
+            FORMAT_PARQUET,
+        ]
+        .join("|")
+    }
+}
+
+/// Serializes the result blocks of a query into an output format.
+///
+/// Text formats are written block by block; Arrow and Parquet files are buffered
+/// and written by `finish`, since their footers are only known after the last block.
+pub struct OutputFormatWriter {
+    format: OutputFormat,
+    schema: DataSchemaRef,
+    blocks: Vec<DataBlock>,
+}
+
+impl OutputFormatWriter {
+    pub fn create(format: OutputFormat, schema: DataSchemaRef) -> OutputFormatWriter {
+        OutputFormatWriter {
+            format,
+            schema,
+            blocks: vec![],
+        }
+    }
+
+    pub fn start(&mut self) -> Result<Vec<u8>> {
+        match self.format {
+            OutputFormat::TsvWithNames => Ok(schema_to_tsv_names(&self.schema)),
+            _ => Ok(vec![]),
+        }
+    }
+
+    pub fn write_block(&mut self, block: DataBlock) -> Result<Vec<u8>> {
+        match self.format {
+            OutputFormat::Tsv | OutputFormat::TsvWithNames => block_to_tsv(&block),
+            OutputFormat::NDJson => block_to_json_each_row(&block),
+            OutputFormat::Arrow | OutputFormat::Parquet => {
+                self.blocks.push(block);
+                Ok(vec![])
+            }
+        }
+    }
+
+    pub fn finish(&mut self) -> Result<Vec<u8>> {
+        let blocks = std::mem::take(&mut self.blocks);
+        match self.format {
+            OutputFormat::Arrow => blocks_to_arrow(&self.schema, blocks),
+            OutputFormat::Parquet => blocks_to_parquet(&self.schema, blocks),
+            _ => Ok(vec![]),
+        }
+    }
+}
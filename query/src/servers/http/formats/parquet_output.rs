@@ -0,0 +1,49 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::io::parquet::write::Compression;
+use common_arrow::arrow::io::parquet::write::RowGroupIterator;
+use common_arrow::arrow::io::parquet::write::Version;
+use common_arrow::arrow::io::parquet::write::WriteOptions;
+use common_arrow::parquet::encoding::Encoding;
+use common_arrow::write_parquet_file;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+// The parquet footer is written after all the row groups, so the whole result is buffered.
+pub fn blocks_to_parquet(schema: &DataSchemaRef, blocks: Vec<DataBlock>) -> Result<Vec<u8>> {
+    let arrow_schema = schema.to_arrow();
+    let options = WriteOptions {
+        write_statistics: false,
+        compression: Compression::Lz4Raw,
+        version: Version::V2,
+    };
+
+    let encodings = vec![Encoding::Plain; arrow_schema.fields.len()];
+    let mut chunks = Vec::with_capacity(blocks.len());
+    for block in blocks {
+        chunks.push(Ok(Chunk::try_from(block)?));
+    }
+    let row_groups =
+        RowGroupIterator::try_new(chunks.into_iter(), &arrow_schema, options, encodings)?;
+
+    let mut buf = vec![];
+    match write_parquet_file(&mut buf, row_groups, arrow_schema.clone(), options) {
+        Ok(_) => Ok(buf),
+        Err(cause) => Err(ErrorCode::ParquetError(cause.to_string())),
+    }
+}
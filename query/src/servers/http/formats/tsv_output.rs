@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
 use common_datavalues::DataType;
 use common_datavalues::TypeSerializer;
 use common_exception::ErrorCode;
@@ -52,3 +53,14 @@ pub fn block_to_tsv(block: &DataBlock) -> Result<Vec<u8>> {
     }
     Ok(buf)
 }
+
+pub fn schema_to_tsv_names(schema: &DataSchemaRef) -> Vec<u8> {
+    let names = schema
+        .fields()
+        .iter()
+        .map(|f| f.name().as_str())
+        .collect::<Vec<_>>();
+    let mut buf = names.join("\t").into_bytes();
+    buf.push(ROW_DELIMITER);
+    buf
+}
@@ -18,6 +18,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::UserInfo;
 use common_tracing::tracing;
+use common_tracing::tracing::Instrument;
 use headers::authorization::Basic;
 use headers::authorization::Bearer;
 use headers::authorization::Credentials;
@@ -38,7 +39,7 @@ pub struct HTTPSessionMiddleware {
     pub session_manager: Arc<SessionManager>,
 }
 
-fn get_credential(headers: &HeaderMap) -> Result<Option<Credential>> {
+pub(crate) fn get_credential(headers: &HeaderMap) -> Result<Option<Credential>> {
     let auth_headers: Vec<_> = headers.get_all(AUTHORIZATION).iter().collect();
     if auth_headers.len() > 1 {
         let msg = &format!("Multiple {} headers detected", AUTHORIZATION);
@@ -93,9 +94,15 @@ pub struct HTTPSessionEndpoint<E> {
 impl<E> HTTPSessionEndpoint<E> {
     async fn auth(&self, req: &Request) -> Result<(Option<String>, UserInfo)> {
         let credential = get_credential(req.headers())?;
+        let client_ip = req
+            .remote_addr()
+            .as_socket_addr()
+            .map(|addr| addr.ip().to_string());
+        let client_ip = client_ip.as_deref();
+        let auth_manager = self.manager.get_auth_manager();
         match credential {
-            Some(c) => self.manager.get_auth_manager().auth(&c).await,
-            None => Ok((None, self.manager.get_auth_manager().no_auth().await?)),
+            Some(c) => auth_manager.auth(&c, client_ip).await,
+            None => Ok((None, auth_manager.no_auth(client_ip).await?)),
         }
     }
 }
@@ -106,21 +113,35 @@ impl<E: Endpoint> Endpoint for HTTPSessionEndpoint<E> {
 
     async fn call(&self, mut req: Request) -> PoemResult<Self::Output> {
         tracing::debug!("receive http request: {:?},", req);
-        let res = match self.auth(&req).await {
-            Ok((tenant_id, user_info)) => {
-                let ctx = HttpQueryContext {
-                    session_mgr: self.manager.clone(),
-                    user_info,
-                    tenant_id,
-                };
-                req.extensions_mut().insert(ctx);
-                self.ep.call(req).await
+        let span = tracing::info_span!(
+            "http_request",
+            method = %req.method(),
+            path = %req.uri().path()
+        );
+        common_tracing::extract_remote_span_from_http_headers(&span, req.headers());
+
+        let res = async move {
+            match self.auth(&req).await {
+                Ok((tenant_id, user_info)) => {
+                    let ctx = HttpQueryContext {
+                        session_mgr: self.manager.clone(),
+                        user_info,
+                        tenant_id,
+                    };
+                    req.extensions_mut().insert(ctx);
+                    self.ep.call(req).await
+                }
+                Err(err) if err.code() == ErrorCode::network_policy_violation_code() => {
+                    Err(PoemError::from_string(err.message(), StatusCode::FORBIDDEN))
+                }
+                Err(err) => Err(PoemError::from_string(
+                    err.message(),
+                    StatusCode::UNAUTHORIZED,
+                )),
             }
-            Err(err) => Err(PoemError::from_string(
-                err.message(),
-                StatusCode::UNAUTHORIZED,
-            )),
-        };
+        }
+        .instrument(span)
+        .await;
         if let Err(ref err) = res {
             tracing::warn!(
                 "http request error: status={}, msg={}",
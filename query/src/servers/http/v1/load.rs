@@ -12,15 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use async_compat::CompatExt;
 use async_stream::stream;
 use common_base::ProgressValues;
+use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
-use common_exception::ToErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
 use common_io::prelude::parse_escape_string;
 use common_io::prelude::FormatSettings;
+use common_meta_types::FileFormatOptions;
+use common_meta_types::StageFileFormatType;
 use common_planners::InsertInputSource;
 use common_planners::PlanNode;
 use common_streams::CsvSourceBuilder;
@@ -29,24 +34,31 @@ use common_streams::ParquetSourceBuilder;
 use common_streams::SendableDataBlockStream;
 use common_streams::Source;
 use common_tracing::tracing;
+use futures::io::BufReader;
 use futures::io::Cursor;
+use futures::AsyncRead;
+use futures::AsyncReadExt;
 use futures::StreamExt;
 use poem::error::InternalServerError;
 use poem::error::Result as PoemResult;
 use poem::http::StatusCode;
 use poem::web::Json;
 use poem::web::Multipart;
+use poem::Body;
+use poem::FromRequest;
 use poem::Request;
+use poem::RequestBody;
 use serde::Deserialize;
 use serde::Serialize;
 
 use super::HttpQueryContext;
 use crate::interpreters::InterpreterFactory;
 use crate::pipelines::new::processors::port::OutputPort;
-use crate::pipelines::new::processors::StreamSourceV2;
+use crate::pipelines::new::processors::StreamSource;
 use crate::pipelines::new::SourcePipeBuilder;
 use crate::sessions::QueryContext;
 use crate::sessions::SessionType;
+use crate::sql::statements::parse_copy_file_format_options;
 use crate::sql::PlanParser;
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -54,14 +66,43 @@ pub struct LoadResponse {
     pub id: String,
     pub state: String,
     pub stats: ProgressValues,
+    // The progress of each chunk of the body, see `LoadBody`.
+    pub chunks: Vec<ProgressValues>,
     pub error: Option<String>,
 }
 
+/// The body of a streaming load. Each part of a multipart body is a chunk, otherwise the whole
+/// body, usually sent with the chunked transfer encoding, is one chunk.
+enum LoadBody {
+    Multipart(Multipart),
+    Single(Option<Body>),
+}
+
+type ChunkReader = Box<dyn AsyncRead + Unpin + Send>;
+
+impl LoadBody {
+    async fn next_chunk(&mut self) -> Result<Option<ChunkReader>> {
+        match self {
+            LoadBody::Multipart(multipart) => match multipart.next_field().await {
+                Ok(None) => Ok(None),
+                Ok(Some(field)) => Ok(Some(Box::new(field.into_async_read().compat()))),
+                Err(cause) => Err(ErrorCode::BadBytes(format!(
+                    "Read part of the body error: {}",
+                    cause
+                ))),
+            },
+            LoadBody::Single(body) => Ok(body
+                .take()
+                .map(|body| Box::new(body.into_async_read().compat()) as ChunkReader)),
+        }
+    }
+}
+
 #[poem::handler]
 pub async fn streaming_load(
     ctx: &HttpQueryContext,
     req: &Request,
-    mut multipart: Multipart,
+    body: Body,
 ) -> PoemResult<Json<LoadResponse>> {
     let session = ctx
         .create_session(SessionType::HTTPStreamingLoad)
@@ -95,6 +136,43 @@ pub async fn streaming_load(
         .map_err(InternalServerError)?;
     context.attach_query_str(insert_sql);
 
+    // validate plan
+    let format = match &plan {
+        PlanNode::Insert(insert) => match &insert.source {
+            InsertInputSource::StreamingWithFormat(format) => Ok(format.clone()),
+            _non_supported_source => Err(poem::Error::from_string(
+                "Only supports streaming upload. e.g. INSERT INTO $table FORMAT CSV",
+                StatusCode::BAD_REQUEST,
+            )),
+        },
+        non_insert_plan => Err(poem::Error::from_string(
+            format!(
+                "Only supports INSERT statement in streaming load, but got {}",
+                non_insert_plan.name()
+            ),
+            StatusCode::BAD_REQUEST,
+        )),
+    }?;
+
+    let file_format_options = file_format_options(req, &format)
+        .map_err(|e| poem::Error::from_string(e.message(), StatusCode::BAD_REQUEST))?;
+    match file_format_options.format {
+        StageFileFormatType::Csv
+        | StageFileFormatType::Tsv
+        | StageFileFormatType::Json
+        | StageFileFormatType::Parquet => {}
+        _ => {
+            return Err(poem::Error::from_string(
+                format!(
+                    "Streaming load only supports {{ CSV | TSV | NDJSON | PARQUET }} format, \
+                     but got {}",
+                    format
+                ),
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+    }
+
     // Block size.
     let max_block_size = context
         .get_settings()
@@ -103,6 +181,28 @@ pub async fn streaming_load(
 
     let format_settings = context.get_format_settings().map_err(InternalServerError)?;
 
+    let is_multipart = req
+        .content_type()
+        .map(|v| v.starts_with("multipart/"))
+        .unwrap_or(false);
+    let body = if is_multipart {
+        LoadBody::Multipart(Multipart::from_request(req, &mut RequestBody::new(body)).await?)
+    } else {
+        LoadBody::Single(Some(body))
+    };
+
+    // The body is read only as fast as the blocks are written to the table, which
+    // back-pressures the client through the flow control of the connection.
+    let chunks = Arc::new(Mutex::new(vec![]));
+    let source_stream = build_load_stream(
+        plan.schema(),
+        file_format_options,
+        format_settings,
+        max_block_size,
+        body,
+        chunks.clone(),
+    );
+
     if context
         .get_settings()
         .get_enable_new_processor_framework()
@@ -110,47 +210,13 @@ pub async fn streaming_load(
         != 0
         && context.get_cluster().is_empty()
     {
-        let source_pipe_builder = match &plan {
-            PlanNode::Insert(insert) => match &insert.source {
-                InsertInputSource::StreamingWithFormat(format) => {
-                    if format.to_lowercase().as_str() == "csv" {
-                        csv_source_pipe_builder(
-                            context.clone(),
-                            &plan,
-                            &format_settings,
-                            multipart,
-                            max_block_size,
-                        )
-                        .await
-                    } else if format.to_lowercase().as_str() == "parquet" {
-                        parquet_source_pipe_builder(context.clone(), &plan, multipart).await
-                    } else if format.to_lowercase().as_str() == "ndjson"
-                        || format.to_lowercase().as_str() == "jsoneachrow"
-                    {
-                        ndjson_source_pipe_builder(context.clone(), &plan, multipart).await
-                    } else {
-                        Err(poem::Error::from_string(
-                            format!(
-                                "Streaming load only supports csv format, but got {}",
-                                format
-                            ),
-                            StatusCode::BAD_REQUEST,
-                        ))
-                    }
-                }
-                _non_supported_source => Err(poem::Error::from_string(
-                    "Only supports streaming upload. e.g. INSERT INTO $table FORMAT CSV",
-                    StatusCode::BAD_REQUEST,
-                )),
-            },
-            non_insert_plan => Err(poem::Error::from_string(
-                format!(
-                    "Only supports INSERT statement in streaming load, but got {}",
-                    non_insert_plan.name()
-                ),
-                StatusCode::BAD_REQUEST,
-            )),
-        }?;
+        let mut source_pipe_builder = SourcePipeBuilder::create();
+        let output_port = OutputPort::create();
+        let source =
+            StreamSource::create(context.clone(), Some(source_stream), output_port.clone())
+                .map_err(InternalServerError)?;
+        source_pipe_builder.add_source(output_port, source);
+
         let interpreter =
             InterpreterFactory::get(context.clone(), plan.clone()).map_err(InternalServerError)?;
         let _ = interpreter
@@ -167,54 +233,10 @@ pub async fn streaming_load(
             .await
             .map_err(|e| tracing::error!("interpreter.finish error: {:?}", e));
 
-        // TODO generate id
-        // TODO duplicate by insert_label
-        let mut id = uuid::Uuid::new_v4().to_string();
-        return Ok(Json(LoadResponse {
-            id,
-            state: "SUCCESS".to_string(),
-            stats: context.get_scan_progress_value(),
-            error: None,
-        }));
+        return Ok(load_response(&context, &chunks));
     };
 
     // After new processor is ready, the following code can directly delete
-    // validate plan
-    let source_stream = match &plan {
-        PlanNode::Insert(insert) => match &insert.source {
-            InsertInputSource::StreamingWithFormat(format) => {
-                if format.to_lowercase().as_str() == "csv" {
-                    build_csv_stream(&plan, &format_settings, multipart, max_block_size)
-                } else if format.to_lowercase().as_str() == "parquet" {
-                    build_parquet_stream(&plan, multipart)
-                } else if format.to_lowercase().as_str() == "ndjson"
-                    || format.to_lowercase().as_str() == "jsoneachrow"
-                {
-                    build_ndjson_stream(&plan, multipart)
-                } else {
-                    Err(poem::Error::from_string(
-                        format!(
-                            "Streaming load only supports csv format, but got {}",
-                            format
-                        ),
-                        StatusCode::BAD_REQUEST,
-                    ))
-                }
-            }
-            _non_supported_source => Err(poem::Error::from_string(
-                "Only supports streaming upload. e.g. INSERT INTO $table FORMAT CSV",
-                StatusCode::BAD_REQUEST,
-            )),
-        },
-        non_insert_plan => Err(poem::Error::from_string(
-            format!(
-                "Only supports INSERT statement in streaming load, but got {}",
-                non_insert_plan.name()
-            ),
-            StatusCode::BAD_REQUEST,
-        )),
-    }?;
-
     let interpreter =
         InterpreterFactory::get(context.clone(), plan.clone()).map_err(InternalServerError)?;
 
@@ -238,166 +260,139 @@ pub async fn streaming_load(
         .await
         .map_err(|e| tracing::error!("interpreter.finish error: {:?}", e));
 
+    Ok(load_response(&context, &chunks))
+}
+
+fn load_response(
+    context: &Arc<QueryContext>,
+    chunks: &Mutex<Vec<ProgressValues>>,
+) -> Json<LoadResponse> {
     // TODO generate id
     // TODO duplicate by insert_label
-    let mut id = uuid::Uuid::new_v4().to_string();
-    Ok(Json(LoadResponse {
+    let id = uuid::Uuid::new_v4().to_string();
+    Json(LoadResponse {
         id,
         state: "SUCCESS".to_string(),
         stats: context.get_scan_progress_value(),
+        chunks: chunks.lock().clone(),
         error: None,
-    }))
+    })
 }
 
-fn build_parquet_stream(
-    plan: &PlanNode,
-    mut multipart: Multipart,
-) -> PoemResult<SendableDataBlockStream> {
-    let builder = ParquetSourceBuilder::create(plan.schema());
-    let stream = stream! {
-        while let Ok(Some(field)) = multipart.next_field().await {
-            let bytes = field.bytes().await.map_err_to_code(ErrorCode::BadBytes,  || "Read part to field bytes error")?;
-            let cursor = Cursor::new(bytes);
-
-            let mut source = builder.build(cursor)?;
-
-            loop {
-                let block = source.read().await;
-                match block {
-                    Ok(None) => break,
-                    Ok(Some(b)) =>  yield(Ok(b)),
-                    Err(e) => yield(Err(e)),
-                }
-            }
-        }
+// The file format options of the body, as the FILE_FORMAT options of COPY, are given by the
+// headers, except the type, which is the FORMAT of the INSERT.
+fn file_format_options(req: &Request, format: &str) -> Result<FileFormatOptions> {
+    let format = match format.to_lowercase().as_str() {
+        "ndjson" | "jsoneachrow" => "json".to_string(),
+        "tabseparated" => "tsv".to_string(),
+        other => other.to_string(),
     };
 
-    Ok(Box::pin(stream))
-}
-
-fn build_ndjson_stream(
-    plan: &PlanNode,
-    mut multipart: Multipart,
-) -> PoemResult<SendableDataBlockStream> {
-    let builder = NDJsonSourceBuilder::create(plan.schema());
-    let stream = stream! {
-        while let Ok(Some(field)) = multipart.next_field().await {
-            let bytes = field.bytes().await.map_err_to_code(ErrorCode::BadBytes,  || "Read part to field bytes error")?;
-            let cursor = futures::io::Cursor::new(bytes);
-            let mut source = builder.build(cursor)?;
-
-            loop {
-                let block = source.read().await;
-                match block {
-                    Ok(None) => break,
-                    Ok(Some(b)) =>  yield(Ok(b)),
-                    Err(e) => yield(Err(e)),
-                }
-            }
+    let mut options = BTreeMap::from([("type".to_string(), format)]);
+    let keys = [
+        "skip_header",
+        "field_delimiter",
+        "record_delimiter",
+        "enclosed_by",
+        "escaped_by",
+    ];
+    for key in keys {
+        if let Some(value) = req.headers().get(key).and_then(|v| v.to_str().ok()) {
+            let value = value.trim_matches(|p| p == '"' || p == '\'');
+            options.insert(key.to_string(), value.to_string());
         }
-    };
-
-    Ok(Box::pin(stream))
+    }
+    parse_copy_file_format_options(&options)
 }
 
-fn build_csv_stream(
-    plan: &PlanNode,
-    format_settings: &FormatSettings,
-    mut multipart: Multipart,
+fn build_load_stream(
+    schema: DataSchemaRef,
+    options: FileFormatOptions,
+    format_settings: FormatSettings,
     block_size: usize,
-) -> PoemResult<SendableDataBlockStream> {
-    let mut builder = CsvSourceBuilder::create(plan.schema(), format_settings.clone());
-    builder.block_size(block_size);
-
+    mut body: LoadBody,
+    chunks: Arc<Mutex<Vec<ProgressValues>>>,
+) -> SendableDataBlockStream {
     let stream = stream! {
-        while let Ok(Some(field)) = multipart.next_field().await {
-            let reader = field.into_async_read();
-            let mut source = builder.build(reader.compat())?;
+        loop {
+            let source = match body.next_chunk().await {
+                Ok(None) => break,
+                Ok(Some(reader)) => {
+                    let schema = schema.clone();
+                    chunk_source(schema, &options, &format_settings, block_size, reader).await
+                }
+                Err(e) => Err(e),
+            };
+            let mut source = match source {
+                Ok(source) => source,
+                Err(e) => {
+                    yield Err(e);
+                    break;
+                }
+            };
 
+            chunks.lock().push(ProgressValues::default());
             loop {
-                let block = source.read().await;
-                match block {
+                match source.read().await {
                     Ok(None) => break,
-                    Ok(Some(b)) =>  yield(Ok(b)),
-                    Err(e) => yield(Err(e)),
+                    Ok(Some(block)) => {
+                        if let Some(chunk) = chunks.lock().last_mut() {
+                            chunk.rows += block.num_rows();
+                            chunk.bytes += block.memory_size();
+                        }
+                        yield Ok(block);
+                    }
+                    Err(e) => {
+                        yield Err(e);
+                        break;
+                    }
                 }
             }
         }
     };
 
-    Ok(Box::pin(stream))
+    Box::pin(stream)
 }
 
-async fn csv_source_pipe_builder(
-    ctx: Arc<QueryContext>,
-    plan: &PlanNode,
+async fn chunk_source(
+    schema: DataSchemaRef,
+    options: &FileFormatOptions,
     format_settings: &FormatSettings,
-    mut multipart: Multipart,
     block_size: usize,
-) -> PoemResult<SourcePipeBuilder> {
-    let mut builder = CsvSourceBuilder::create(plan.schema(), format_settings.clone());
-    builder.block_size(block_size);
-    let mut source_pipe_builder = SourcePipeBuilder::create();
-    while let Ok(Some(field)) = multipart.next_field().await {
-        let bytes = field
-            .bytes()
-            .await
-            .map_err_to_code(ErrorCode::BadBytes, || "Read part to field bytes error")
-            .unwrap();
-        let cursor = Cursor::new(bytes);
-        let csv_source = builder.build(cursor).unwrap();
-        let output_port = OutputPort::create();
-        let source =
-            StreamSourceV2::create(ctx.clone(), Box::new(csv_source), output_port.clone()).unwrap();
-        source_pipe_builder.add_source(output_port, source);
-    }
-    Ok(source_pipe_builder)
-}
-
-async fn parquet_source_pipe_builder(
-    ctx: Arc<QueryContext>,
-    plan: &PlanNode,
-    mut multipart: Multipart,
-) -> PoemResult<SourcePipeBuilder> {
-    let builder = ParquetSourceBuilder::create(plan.schema());
-    let mut source_pipe_builder = SourcePipeBuilder::create();
-    while let Ok(Some(field)) = multipart.next_field().await {
-        let bytes = field
-            .bytes()
-            .await
-            .map_err_to_code(ErrorCode::BadBytes, || "Read part to field bytes error")
-            .unwrap();
-        let cursor = Cursor::new(bytes);
-        let parquet_source = builder.build(cursor).unwrap();
-        let output_port = OutputPort::create();
-        let source =
-            StreamSourceV2::create(ctx.clone(), Box::new(parquet_source), output_port.clone())
-                .unwrap();
-        source_pipe_builder.add_source(output_port, source);
-    }
-    Ok(source_pipe_builder)
-}
-
-async fn ndjson_source_pipe_builder(
-    ctx: Arc<QueryContext>,
-    plan: &PlanNode,
-    mut multipart: Multipart,
-) -> PoemResult<SourcePipeBuilder> {
-    let builder = NDJsonSourceBuilder::create(plan.schema());
-    let mut source_pipe_builder = SourcePipeBuilder::create();
-    while let Ok(Some(field)) = multipart.next_field().await {
-        let bytes = field
-            .bytes()
-            .await
-            .map_err_to_code(ErrorCode::BadBytes, || "Read part to field bytes error")
-            .unwrap();
-        let cursor = Cursor::new(bytes);
-        let ndjson_source = builder.build(cursor).unwrap();
-        let output_port = OutputPort::create();
-        let source =
-            StreamSourceV2::create(ctx.clone(), Box::new(ndjson_source), output_port.clone())
-                .unwrap();
-        source_pipe_builder.add_source(output_port, source);
+    mut reader: ChunkReader,
+) -> Result<Box<dyn Source>> {
+    match options.format {
+        StageFileFormatType::Csv | StageFileFormatType::Tsv => {
+            let mut builder = match options.format {
+                StageFileFormatType::Tsv => {
+                    CsvSourceBuilder::create_tsv(schema, format_settings.clone())
+                }
+                _ => CsvSourceBuilder::create(schema, format_settings.clone()),
+            };
+            builder
+                .block_size(block_size)
+                .skip_header(options.skip_header > 0)
+                .field_delimiter(&options.field_delimiter)
+                .record_delimiter(&options.record_delimiter)
+                .enclosed_by(&options.enclosed_by)
+                .escaped_by(&options.escaped_by);
+            Ok(Box::new(builder.build(reader)?))
+        }
+        StageFileFormatType::Json => {
+            let mut builder = NDJsonSourceBuilder::create(schema);
+            builder.block_size(block_size);
+            Ok(Box::new(builder.build(BufReader::new(reader))?))
+        }
+        // The parquet metadata is at the end of the file, so the whole chunk is buffered.
+        StageFileFormatType::Parquet => {
+            let mut buffer = vec![];
+            reader.read_to_end(&mut buffer).await?;
+            let builder = ParquetSourceBuilder::create(schema);
+            Ok(Box::new(builder.build(Cursor::new(buffer))?))
+        }
+        ref other => Err(ErrorCode::UnImplement(format!(
+            "Streaming load of {:?} format",
+            other
+        ))),
     }
-    Ok(source_pipe_builder)
 }
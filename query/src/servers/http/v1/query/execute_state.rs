@@ -25,6 +25,7 @@ use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_tracing::tracing;
+use common_tracing::tracing::Instrument;
 use futures::StreamExt;
 use serde::Deserialize;
 use serde::Serialize;
@@ -183,19 +184,22 @@ impl ExecuteState {
 
         let executor_clone = executor.clone();
         let ctx_clone = ctx.clone();
-        ctx.try_spawn(async move {
-            // drop/close block_tx after calling Executor::stop
-            // so handler task can get newest state before return
-            // otherwise the handler task and this task may competing for the executor lock
-            let block_tx_clone = block_tx.clone();
-            match execute(interpreter, ctx_clone, block_tx_clone, &mut abort_rx).await {
-                Ok(_) => Executor::stop(&executor_clone, Ok(()), false).await,
-                Err(err) => {
-                    let kill = err.message().starts_with("aborted");
-                    Executor::stop(&executor_clone, Err(err), kill).await
-                }
-            };
-        })?;
+        ctx.try_spawn(
+            async move {
+                // drop/close block_tx after calling Executor::stop
+                // so handler task can get newest state before return
+                // otherwise the handler task and this task may competing for the executor lock
+                let block_tx_clone = block_tx.clone();
+                match execute(interpreter, ctx_clone, block_tx_clone, &mut abort_rx).await {
+                    Ok(_) => Executor::stop(&executor_clone, Ok(()), false).await,
+                    Err(err) => {
+                        let kill = err.message().starts_with("aborted");
+                        Executor::stop(&executor_clone, Err(err), kill).await
+                    }
+                };
+            }
+            .in_current_span(),
+        )?;
 
         Ok(executor)
     }
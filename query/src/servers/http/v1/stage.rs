@@ -13,8 +13,10 @@
 // limitations under the License.
 
 use common_io::prelude::get_abs_path;
+use common_meta_types::GrantObject;
 use common_meta_types::StageStorage;
 use common_meta_types::StageType;
+use common_meta_types::UserPrivilegeType;
 use poem::error::InternalServerError;
 use poem::error::Result as PoemResult;
 use poem::http::StatusCode;
@@ -71,6 +73,13 @@ pub async fn upload_to_stage(
         .get_stage(context.get_tenant().as_str(), stage_name)
         .await
         .map_err(InternalServerError)?;
+    session
+        .validate_privilege(
+            &GrantObject::Stage(stage_name.to_string()),
+            UserPrivilegeType::Usage,
+        )
+        .await
+        .map_err(|e| poem::Error::from_string(e.message(), StatusCode::FORBIDDEN))?;
 
     let relative_path = req
         .headers()
@@ -15,6 +15,7 @@
 // The servers module used for external communication with user, such as MySQL wired protocol, etc.
 
 pub use clickhouse::ClickHouseHandler;
+pub use flight_sql::FlightSQLHandler;
 pub use server::Server;
 pub use server::ShutdownHandle;
 
@@ -22,8 +23,10 @@ pub use self::http::HttpHandler;
 pub use self::mysql::MySQLConnection;
 pub use self::mysql::MySQLFederated;
 pub use self::mysql::MySQLHandler;
+pub use self::mysql::MySQLPreparedStatement;
 
 pub(crate) mod clickhouse;
+pub mod flight_sql;
 pub mod http;
 mod mysql;
 pub(crate) mod server;
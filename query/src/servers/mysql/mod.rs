@@ -16,6 +16,7 @@ mod mysql_federated;
 mod mysql_handler;
 mod mysql_interactive_worker;
 mod mysql_metrics;
+mod mysql_prepared_statement;
 mod mysql_session;
 #[allow(clippy::unused_io_amount)]
 mod reject_connection;
@@ -23,6 +24,7 @@ mod writers;
 
 pub use self::mysql_federated::MySQLFederated;
 pub use self::mysql_handler::MySQLHandler;
+pub use self::mysql_prepared_statement::MySQLPreparedStatement;
 pub use self::mysql_session::MySQLConnection;
 
 const MYSQL_VERSION: &str = "8.0.26";
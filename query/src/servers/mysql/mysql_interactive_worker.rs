@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -27,6 +29,9 @@ use common_tracing::tracing;
 use common_tracing::tracing::Instrument;
 use metrics::histogram;
 use opensrv_mysql::AsyncMysqlShim;
+use opensrv_mysql::Column;
+use opensrv_mysql::ColumnFlags;
+use opensrv_mysql::ColumnType;
 use opensrv_mysql::ErrorKind;
 use opensrv_mysql::InitWriter;
 use opensrv_mysql::ParamParser;
@@ -42,6 +47,7 @@ use crate::interpreters::SelectInterpreterV2;
 use crate::servers::mysql::writers::DFInitResultWriter;
 use crate::servers::mysql::writers::DFQueryResultWriter;
 use crate::servers::mysql::MySQLFederated;
+use crate::servers::mysql::MySQLPreparedStatement;
 use crate::servers::mysql::MYSQL_VERSION;
 use crate::sessions::QueryContext;
 use crate::sessions::SessionRef;
@@ -51,6 +57,9 @@ use crate::users::CertifiedInfo;
 struct InteractiveWorkerBase<W: std::io::Write> {
     session: SessionRef,
     generic_hold: PhantomData<W>,
+    // The statements prepared by the connection.
+    statements: HashMap<u32, MySQLPreparedStatement>,
+    next_statement_id: u32,
 }
 
 pub struct InteractiveWorker<W: std::io::Write> {
@@ -150,7 +159,24 @@ impl<W: std::io::Write + Send + Sync> AsyncMysqlShim<W> for InteractiveWorker<W>
             ));
         }
 
-        self.base.do_execute(id, param, writer).await
+        let mut writer = DFQueryResultWriter::create(writer, true);
+
+        let instant = Instant::now();
+        let blocks = self.base.do_execute(id, param).await;
+
+        let mut write_result = writer.write(blocks);
+
+        if let Err(cause) = write_result {
+            let suffix = format!("(while in execute of prepared statement {})", id);
+            write_result = Err(cause.add_message_back(suffix));
+        }
+
+        histogram!(
+            super::mysql_metrics::METRIC_MYSQL_PROCESSOR_REQUEST_DURATION,
+            instant.elapsed()
+        );
+
+        write_result
     }
 
     async fn on_close<'a>(&'a mut self, id: u32)
@@ -174,7 +200,7 @@ impl<W: std::io::Write + Send + Sync> AsyncMysqlShim<W> for InteractiveWorker<W>
             ));
         }
 
-        let mut writer = DFQueryResultWriter::create(writer);
+        let mut writer = DFQueryResultWriter::create(writer, false);
 
         let instant = Instant::now();
         let blocks = self.base.do_query(query).await;
@@ -217,43 +243,66 @@ impl<W: std::io::Write + Send + Sync> AsyncMysqlShim<W> for InteractiveWorker<W>
 impl<W: std::io::Write> InteractiveWorkerBase<W> {
     async fn authenticate(&self, salt: &[u8], info: CertifiedInfo) -> Result<bool> {
         let user_name = &info.user_name;
-        let client_ip = info.user_client_address.split(':').collect::<Vec<_>>()[0];
+        // The ip of an IPv6 address is enclosed in brackets, e.g. "[::1]:3307".
+        let client_ip = match info.user_client_address.parse::<SocketAddr>() {
+            Ok(addr) => addr.ip().to_string(),
+            Err(_) => info.user_client_address.clone(),
+        };
 
         let ctx = self.session.create_query_context().await?;
         let user_manager = ctx.get_user_manager();
         let user_info = user_manager
-            .get_user_with_client_ip(&ctx.get_tenant(), user_name, client_ip)
+            .get_user_with_client_ip(&ctx.get_tenant(), user_name, &client_ip)
             .await?;
 
         let authed = user_info.auth_info.auth_mysql(&info.user_password, salt)?;
         if authed {
+            user_manager
+                .check_network_policy(&ctx.get_tenant(), &user_info, Some(&client_ip))
+                .await?;
             self.session.set_current_user(user_info);
         }
         Ok(authed)
     }
 
-    async fn do_prepare(&mut self, _: &str, writer: StatementMetaWriter<'_, W>) -> Result<()> {
-        writer.error(
-            ErrorKind::ER_UNKNOWN_ERROR,
-            "Prepare is not support in Databend.".as_bytes(),
-        )?;
+    async fn do_prepare(&mut self, query: &str, writer: StatementMetaWriter<'_, W>) -> Result<()> {
+        let statement = MySQLPreparedStatement::create(query);
+        let params = (0..statement.num_params())
+            .map(|_| Column {
+                table: "".to_string(),
+                column: "?".to_string(),
+                coltype: ColumnType::MYSQL_TYPE_VAR_STRING,
+                colflags: ColumnFlags::empty(),
+            })
+            .collect::<Vec<_>>();
+
+        self.next_statement_id += 1;
+        let id = self.next_statement_id;
+        // The columns of the result are sent along with the result of each execution.
+        writer.reply(id, &params, &[])?;
+        self.statements.insert(id, statement);
         Ok(())
     }
 
     async fn do_execute(
         &mut self,
-        _: u32,
-        _: ParamParser<'_>,
-        writer: QueryResultWriter<'_, W>,
-    ) -> Result<()> {
-        writer.error(
-            ErrorKind::ER_UNKNOWN_ERROR,
-            "Execute is not support in Databend.".as_bytes(),
-        )?;
-        Ok(())
+        id: u32,
+        params: ParamParser<'_>,
+    ) -> Result<(Vec<DataBlock>, String)> {
+        let statement = self.statements.get(&id).ok_or_else(|| {
+            ErrorCode::BadArguments(format!("Unknown prepared statement: {}", id))
+        })?;
+        let literals = params
+            .into_iter()
+            .map(MySQLPreparedStatement::param_literal)
+            .collect::<Result<Vec<_>>>()?;
+        let query = statement.bind(&literals)?;
+        self.do_query(&query).await
     }
 
-    async fn do_close(&mut self, _: u32) {}
+    async fn do_close(&mut self, id: u32) {
+        self.statements.remove(&id);
+    }
 
     // Check the query is a federated or driver setup command.
     // Here we fake some values for the command which Databend not supported.
@@ -429,6 +478,8 @@ impl<W: std::io::Write> InteractiveWorker<W> {
             base: InteractiveWorkerBase::<W> {
                 session,
                 generic_hold: PhantomData::default(),
+                statements: HashMap::new(),
+                next_statement_id: 0,
             },
             salt: scramble,
             version: format!(
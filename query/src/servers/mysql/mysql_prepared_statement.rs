@@ -0,0 +1,132 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use opensrv_mysql::ColumnType;
+use opensrv_mysql::ParamValue;
+use opensrv_mysql::ValueInner;
+
+/// A statement prepared by COM_STMT_PREPARE.
+///
+/// The parameters are bound on the server, by replacing the `?` placeholders of the query with
+/// the SQL literals of the values sent by COM_STMT_EXECUTE.
+#[derive(Clone, Debug)]
+pub struct MySQLPreparedStatement {
+    query: String,
+    // The byte offsets of the placeholders in the query.
+    placeholders: Vec<usize>,
+}
+
+impl MySQLPreparedStatement {
+    pub fn create(query: &str) -> MySQLPreparedStatement {
+        MySQLPreparedStatement {
+            query: query.to_string(),
+            placeholders: Self::find_placeholders(query),
+        }
+    }
+
+    pub fn num_params(&self) -> usize {
+        self.placeholders.len()
+    }
+
+    /// Returns the query with the placeholders replaced by the literals, in order.
+    pub fn bind(&self, literals: &[String]) -> Result<String> {
+        if literals.len() != self.placeholders.len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Prepared statement needs {} parameters, but got {}",
+                self.placeholders.len(),
+                literals.len()
+            )));
+        }
+
+        let mut query = String::with_capacity(self.query.len());
+        let mut last = 0;
+        for (offset, literal) in self.placeholders.iter().zip(literals) {
+            query.push_str(&self.query[last..*offset]);
+            query.push_str(literal);
+            last = offset + 1;
+        }
+        query.push_str(&self.query[last..]);
+        Ok(query)
+    }
+
+    /// The SQL literal of a parameter value of COM_STMT_EXECUTE.
+    pub fn param_literal(param: ParamValue) -> Result<String> {
+        match param.value.into_inner() {
+            ValueInner::NULL => Ok("NULL".to_string()),
+            ValueInner::Int(v) => Ok(v.to_string()),
+            ValueInner::UInt(v) => Ok(v.to_string()),
+            ValueInner::Double(v) => Ok(v.to_string()),
+            ValueInner::Bytes(v) => Ok(Self::quote(&String::from_utf8_lossy(v))),
+            ValueInner::Date(_) if param.coltype == ColumnType::MYSQL_TYPE_DATE => {
+                let date = NaiveDate::from(param.value);
+                Ok(Self::quote(&date.format("%Y-%m-%d").to_string()))
+            }
+            ValueInner::Date(_) => {
+                let datetime = NaiveDateTime::from(param.value);
+                Ok(Self::quote(
+                    &datetime.format("%Y-%m-%d %H:%M:%S%.f").to_string(),
+                ))
+            }
+            ValueInner::Time(_) => Err(ErrorCode::UnImplement(
+                "Unsupported parameter type of prepared statement: TIME",
+            )),
+        }
+    }
+
+    fn quote(value: &str) -> String {
+        format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+    }
+
+    // The `?` out of the quoted strings, the quoted identifiers and the comments.
+    fn find_placeholders(query: &str) -> Vec<usize> {
+        let bytes = query.as_bytes();
+        let mut placeholders = vec![];
+
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'?' => placeholders.push(i),
+                quote @ (b'\'' | b'"' | b'`') => {
+                    i += 1;
+                    while i < bytes.len() && bytes[i] != quote {
+                        // a backslash escapes the next character of a string
+                        if bytes[i] == b'\\' && quote != b'`' {
+                            i += 1;
+                        }
+                        i += 1;
+                    }
+                }
+                b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                    while i < bytes.len() && bytes[i] != b'\n' {
+                        i += 1;
+                    }
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    i += 2;
+                    while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        placeholders
+    }
+}
@@ -32,17 +32,22 @@ use opensrv_mysql::*;
 
 pub struct DFQueryResultWriter<'a, W: std::io::Write> {
     inner: Option<QueryResultWriter<'a, W>>,
+    // The rows are encoded in the binary protocol, for the prepared statements.
+    binary: bool,
 }
 
 impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
-    pub fn create(inner: QueryResultWriter<'a, W>) -> DFQueryResultWriter<'a, W> {
-        DFQueryResultWriter::<'a, W> { inner: Some(inner) }
+    pub fn create(inner: QueryResultWriter<'a, W>, binary: bool) -> DFQueryResultWriter<'a, W> {
+        DFQueryResultWriter::<'a, W> {
+            inner: Some(inner),
+            binary,
+        }
     }
 
     pub fn write(&mut self, query_result: Result<(Vec<DataBlock>, String)>) -> Result<()> {
         if let Some(writer) = self.inner.take() {
             match query_result {
-                Ok((blocks, extra_info)) => Self::ok(blocks, extra_info, writer)?,
+                Ok((blocks, extra_info)) => Self::ok(blocks, extra_info, self.binary, writer)?,
                 Err(error) => Self::err(&error, writer)?,
             }
         }
@@ -52,6 +57,7 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
     fn ok(
         blocks: Vec<DataBlock>,
         extra_info: String,
+        binary: bool,
         dataset_writer: QueryResultWriter<'a, W>,
     ) -> Result<()> {
         // XXX: num_columns == 0 may is error?
@@ -67,22 +73,23 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
 
         fn convert_field_type(field: &DataField) -> Result<ColumnType> {
             match remove_nullable(field.data_type()).data_type_id() {
-                TypeID::Int8 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::Int16 => Ok(ColumnType::MYSQL_TYPE_LONG),
+                // The binary protocol encodes the values with the sizes of the column types.
+                TypeID::Int8 => Ok(ColumnType::MYSQL_TYPE_TINY),
+                TypeID::Int16 => Ok(ColumnType::MYSQL_TYPE_SHORT),
                 TypeID::Int32 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::Int64 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::UInt8 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::UInt16 => Ok(ColumnType::MYSQL_TYPE_LONG),
+                TypeID::Int64 => Ok(ColumnType::MYSQL_TYPE_LONGLONG),
+                TypeID::UInt8 => Ok(ColumnType::MYSQL_TYPE_TINY),
+                TypeID::UInt16 => Ok(ColumnType::MYSQL_TYPE_SHORT),
                 TypeID::UInt32 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::UInt64 => Ok(ColumnType::MYSQL_TYPE_LONG),
+                TypeID::UInt64 => Ok(ColumnType::MYSQL_TYPE_LONGLONG),
                 TypeID::Float32 => Ok(ColumnType::MYSQL_TYPE_FLOAT),
-                TypeID::Float64 => Ok(ColumnType::MYSQL_TYPE_FLOAT),
+                TypeID::Float64 => Ok(ColumnType::MYSQL_TYPE_DOUBLE),
                 TypeID::String => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
-                TypeID::Boolean => Ok(ColumnType::MYSQL_TYPE_SHORT),
+                TypeID::Boolean => Ok(ColumnType::MYSQL_TYPE_TINY),
                 TypeID::Date => Ok(ColumnType::MYSQL_TYPE_DATE),
                 TypeID::Timestamp => Ok(ColumnType::MYSQL_TYPE_DATETIME),
                 TypeID::Null => Ok(ColumnType::MYSQL_TYPE_NULL),
-                TypeID::Interval => Ok(ColumnType::MYSQL_TYPE_LONG),
+                TypeID::Interval => Ok(ColumnType::MYSQL_TYPE_LONGLONG),
                 TypeID::Struct => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
                 TypeID::Variant => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
                 TypeID::VariantArray => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
@@ -95,11 +102,18 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
         }
 
         fn make_column_from_field(field: &DataField) -> Result<Column> {
+            let data_type = remove_nullable(field.data_type());
+            let colflags = if data_type.data_type_id().is_unsigned_integer() {
+                ColumnFlags::UNSIGNED_FLAG
+            } else {
+                ColumnFlags::empty()
+            };
+
             convert_field_type(field).map(|column_type| Column {
                 table: "".to_string(),
                 column: field.name().to_string(),
                 coltype: column_type,
-                colflags: ColumnFlags::empty(),
+                colflags,
             })
         }
 
@@ -135,6 +149,9 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
                                     let v = v as i32;
                                     row_writer.write_col(v.to_date(&tz).naive_local())?
                                 }
+                                (TypeID::Timestamp, DataValue::Int64(v)) if binary => {
+                                    row_writer.write_col(v.to_timestamp(&tz).naive_local())?
+                                }
                                 (TypeID::Timestamp, DataValue::Int64(v)) => {
                                     let data_type: &TimestampType =
                                         data_type.as_any().downcast_ref().unwrap();
@@ -169,6 +186,9 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
 
                                 (_, DataValue::UInt64(v)) => row_writer.write_col(v)?,
 
+                                (TypeID::Float32, DataValue::Float64(v)) if binary => {
+                                    row_writer.write_col(v as f32)?
+                                }
                                 (_, DataValue::Float64(v)) => row_writer.write_col(v)?,
                                 (_, v) => {
                                     return Err(ErrorCode::BadDataValueType(format!(
@@ -15,6 +15,8 @@
 mod metrics;
 mod query_ctx;
 mod query_ctx_shared;
+mod query_memory_tracker;
+mod query_queue;
 mod session;
 mod session_ctx;
 mod session_info;
@@ -28,6 +30,9 @@ mod session_type;
 
 pub use query_ctx::QueryContext;
 pub use query_ctx_shared::QueryContextShared;
+pub use query_memory_tracker::QueryMemoryTracker;
+pub use query_queue::QueryQueue;
+pub use query_queue::QuerySlot;
 pub use session::Session;
 pub use session_ctx::SessionContext;
 pub use session_info::ProcessInfo;
@@ -18,6 +18,7 @@ use std::net::SocketAddr;
 use std::sync::atomic::Ordering;
 use std::sync::atomic::Ordering::Acquire;
 use std::sync::Arc;
+use std::time::Duration;
 
 use common_base::tokio::task::JoinHandle;
 use common_base::Progress;
@@ -52,9 +53,15 @@ use crate::catalogs::Catalog;
 use crate::catalogs::DatabaseCatalog;
 use crate::clusters::Cluster;
 use crate::configs::Config;
+use crate::interpreters::AsyncInsertQueue;
+use crate::interpreters::QueryHistory;
+use crate::interpreters::QueryResultCache;
+use crate::pipelines::new::PipeProfile;
 use crate::servers::http::v1::HttpQueryHandle;
 use crate::sessions::ProcessInfo;
 use crate::sessions::QueryContextShared;
+use crate::sessions::QueryMemoryTracker;
+use crate::sessions::QuerySlot;
 use crate::sessions::Session;
 use crate::sessions::SessionRef;
 use crate::sessions::Settings;
@@ -163,6 +170,48 @@ impl QueryContext {
         self.shared.result_progress.as_ref().get_values()
     }
 
+    pub fn get_spill_progress(&self) -> Arc<Progress> {
+        self.shared.spill_progress.clone()
+    }
+
+    pub fn get_spill_progress_value(&self) -> ProgressValues {
+        self.shared.spill_progress.as_ref().get_values()
+    }
+
+    /// Registers the profiles of the pipes of a pipeline built for the query.
+    pub fn add_pipeline_profiles(&self, profiles: Vec<Arc<PipeProfile>>) {
+        self.shared.pipeline_profiles.write().push(profiles);
+    }
+
+    pub fn get_pipeline_profiles(&self) -> Vec<Vec<Arc<PipeProfile>>> {
+        self.shared.get_pipeline_profiles()
+    }
+
+    pub fn get_memory_tracker(&self) -> Arc<QueryMemoryTracker> {
+        self.shared.memory_tracker.clone()
+    }
+
+    /// Wait in the queue of the current user for a slot to run the query, the query is shown as
+    /// `Queued` in `system.processes` meanwhile.
+    pub async fn acquire_query_slot(self: &Arc<Self>) -> Result<Option<QuerySlot>> {
+        let settings = self.get_settings();
+        let slots = settings.get_max_concurrent_queries()? as usize;
+        let wait_timeout = Duration::from_secs(settings.get_query_queue_timeout()?);
+        let tenant = self.get_tenant();
+        let user = self.get_current_user()?;
+        let query_queue = self
+            .get_current_session()
+            .get_session_manager()
+            .get_query_queue();
+
+        self.shared.set_queued(true);
+        let slot = query_queue
+            .acquire(&tenant, &user.name, slots, wait_timeout)
+            .await;
+        self.shared.set_queued(false);
+        slot
+    }
+
     pub fn get_error(&self) -> Arc<Mutex<Option<ErrorCode>>> {
         self.shared.error.clone()
     }
@@ -240,7 +289,7 @@ impl QueryContext {
     /// SELECT * FROM (SELECT * FROM db.table_name) as subquery_1, (SELECT * FROM db.table_name) AS subquery_2
     /// ```
     pub async fn get_table(&self, database: &str, table: &str) -> Result<Arc<dyn Table>> {
-        self.shared.get_table(database, table).await
+        self.shared.get_table(self, database, table).await
     }
 
     pub fn get_id(&self) -> String {
@@ -356,6 +405,21 @@ impl QueryContext {
         self.shared.session.session_ctx.get_client_host()
     }
 
+    /// Get the queue buffering the async inserts
+    pub fn get_async_insert_queue(&self) -> Arc<AsyncInsertQueue> {
+        self.shared.session.session_mgr.get_async_insert_queue()
+    }
+
+    /// Get the cache of the query results
+    pub fn get_query_result_cache(&self) -> Arc<QueryResultCache> {
+        self.shared.session.session_mgr.get_query_result_cache()
+    }
+
+    /// Get the persisted history of the finished queries
+    pub fn get_query_history(&self) -> Arc<QueryHistory> {
+        self.shared.session.session_mgr.get_query_history()
+    }
+
     /// Get the storage cache manager
     pub fn get_storage_cache_manager(&self) -> Arc<CacheManager> {
         self.shared.session.session_mgr.get_storage_cache_manager()
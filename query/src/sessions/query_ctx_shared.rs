@@ -14,7 +14,9 @@
 
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use common_base::Progress;
@@ -34,7 +36,10 @@ use crate::catalogs::Catalog;
 use crate::catalogs::DatabaseCatalog;
 use crate::clusters::Cluster;
 use crate::configs::Config;
+use crate::pipelines::new::PipeProfile;
 use crate::servers::http::v1::HttpQueryHandle;
+use crate::sessions::QueryContext;
+use crate::sessions::QueryMemoryTracker;
 use crate::sessions::Session;
 use crate::sessions::Settings;
 use crate::sql::SQLCommon;
@@ -61,6 +66,14 @@ pub struct QueryContextShared {
     pub(in crate::sessions) write_progress: Arc<Progress>,
     /// result_progress for metrics of result datablocks (uncompressed)
     pub(in crate::sessions) result_progress: Arc<Progress>,
+    /// spill_progress for metrics of the datablocks spilled to disk (compressed)
+    pub(in crate::sessions) spill_progress: Arc<Progress>,
+    /// memory_tracker for the memory held by the operators of the query
+    pub(in crate::sessions) memory_tracker: Arc<QueryMemoryTracker>,
+    /// pipeline_profiles for the profiles of the pipes of each pipeline built for the query
+    pub(in crate::sessions) pipeline_profiles: Arc<RwLock<Vec<Vec<Arc<PipeProfile>>>>>,
+    /// queued is true while the query waits for a slot of its user
+    pub(in crate::sessions) queued: Arc<AtomicBool>,
     pub(in crate::sessions) error: Arc<Mutex<Option<ErrorCode>>>,
     pub(in crate::sessions) session: Arc<Session>,
     pub(in crate::sessions) runtime: Arc<RwLock<Option<Arc<Runtime>>>>,
@@ -86,6 +99,7 @@ impl QueryContextShared {
     ) -> Result<Arc<QueryContextShared>> {
         let conf = session.get_config();
         let user_manager = UserApiProvider::create_global(conf.clone()).await?;
+        let max_query_memory = session.get_settings().get_max_query_memory()? as usize;
         Ok(Arc::new(QueryContextShared {
             session,
             cluster_cache,
@@ -93,6 +107,10 @@ impl QueryContextShared {
             scan_progress: Arc::new(Progress::create()),
             result_progress: Arc::new(Progress::create()),
             write_progress: Arc::new(Progress::create()),
+            spill_progress: Arc::new(Progress::create()),
+            memory_tracker: QueryMemoryTracker::create(max_query_memory),
+            pipeline_profiles: Arc::new(RwLock::new(vec![])),
+            queued: Arc::new(AtomicBool::new(false)),
             error: Arc::new(Mutex::new(None)),
             runtime: Arc::new(RwLock::new(None)),
             sources_abort_handle: Arc::new(RwLock::new(Vec::new())),
@@ -133,6 +151,14 @@ impl QueryContextShared {
         // TODO: Wait for the query to be processed (write out the last error)
     }
 
+    pub fn set_queued(&self, queued: bool) {
+        self.queued.store(queued, Ordering::Relaxed);
+    }
+
+    pub fn is_queued(&self) -> bool {
+        self.queued.load(Ordering::Relaxed)
+    }
+
     pub fn get_cluster(&self) -> Arc<Cluster> {
         self.cluster_cache.clone()
     }
@@ -177,13 +203,18 @@ impl QueryContextShared {
         self.session.get_catalog()
     }
 
-    pub async fn get_table(&self, database: &str, table: &str) -> Result<Arc<dyn Table>> {
+    pub async fn get_table(
+        &self,
+        ctx: &QueryContext,
+        database: &str,
+        table: &str,
+    ) -> Result<Arc<dyn Table>> {
         // Always get same table metadata in the same query
         let table_meta_key = (database.to_string(), table.to_string());
 
         let already_in_cache = { self.tables_refs.lock().contains_key(&table_meta_key) };
         match already_in_cache {
-            false => self.get_table_to_cache(database, table).await,
+            false => self.get_table_to_cache(ctx, database, table).await,
             true => Ok(self
                 .tables_refs
                 .lock()
@@ -193,10 +224,19 @@ impl QueryContextShared {
         }
     }
 
-    async fn get_table_to_cache(&self, database: &str, table: &str) -> Result<Arc<dyn Table>> {
+    async fn get_table_to_cache(
+        &self,
+        ctx: &QueryContext,
+        database: &str,
+        table: &str,
+    ) -> Result<Arc<dyn Table>> {
         let tenant = self.get_tenant();
         let catalog = self.get_catalog();
         let cache_table = catalog.get_table(tenant.as_str(), database, table).await?;
+        let cache_table = match cache_table.refresh(ctx).await? {
+            Some(refreshed) => refreshed,
+            None => cache_table,
+        };
 
         let table_meta_key = (database.to_string(), table.to_string());
         let mut tables_refs = self.tables_refs.lock();
@@ -241,6 +281,14 @@ impl QueryContextShared {
         running_query.as_ref().unwrap_or(&"".to_string()).clone()
     }
 
+    pub fn get_query_id(&self) -> String {
+        self.init_query_id.read().clone()
+    }
+
+    pub fn get_pipeline_profiles(&self) -> Vec<Vec<Arc<PipeProfile>>> {
+        self.pipeline_profiles.read().clone()
+    }
+
     pub fn attach_query_plan(&self, plan: &PlanNode) {
         let mut running_plan = self.running_plan.write();
         *running_plan = Some(plan.clone());
@@ -0,0 +1,120 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::convert_byte_size;
+
+/// Tracks the memory held by a query and by each of its operators.
+///
+/// The tracker of an operator is a child of the tracker of the query, so the memory reserved by
+/// the operator is reserved from the query as well. A reservation which makes the query exceed
+/// `max_query_memory` fails, and the operator can spill its data or fail the query with a clear
+/// error instead of exhausting the memory of the node.
+pub struct QueryMemoryTracker {
+    name: String,
+    // The maximum usage in bytes, 0 for unlimited.
+    limit: usize,
+    usage: AtomicUsize,
+    parent: Option<Arc<QueryMemoryTracker>>,
+}
+
+impl QueryMemoryTracker {
+    pub fn create(limit: usize) -> Arc<QueryMemoryTracker> {
+        Arc::new(QueryMemoryTracker {
+            name: "query".to_string(),
+            limit,
+            usage: AtomicUsize::new(0),
+            parent: None,
+        })
+    }
+
+    /// Create the tracker of an operator, which is limited by the limits of its parents only.
+    pub fn create_child(self: &Arc<Self>, name: &str) -> Arc<QueryMemoryTracker> {
+        Arc::new(QueryMemoryTracker {
+            name: name.to_string(),
+            limit: 0,
+            usage: AtomicUsize::new(0),
+            parent: Some(self.clone()),
+        })
+    }
+
+    pub fn get_usage(&self) -> usize {
+        self.usage.load(Ordering::Relaxed)
+    }
+
+    pub fn try_alloc(&self, size: usize) -> Result<()> {
+        match self.reserve(size) {
+            Ok(()) => Ok(()),
+            Err(exceeded) => Err(ErrorCode::QueryMemoryLimitExceeded(format!(
+                "{} failed to allocate {}: the memory usage of the {} is {}, the limit is {}",
+                self.name,
+                convert_byte_size(size as f64),
+                exceeded.name,
+                convert_byte_size(exceeded.get_usage() as f64),
+                convert_byte_size(exceeded.limit as f64),
+            ))),
+        }
+    }
+
+    pub fn dealloc(&self, size: usize) {
+        self.usage.fetch_sub(size, Ordering::Relaxed);
+
+        if let Some(parent) = &self.parent {
+            parent.dealloc(size);
+        }
+    }
+
+    /// Reserve or release the difference between the given usage and the current one.
+    pub fn try_update_usage(&self, usage: usize) -> Result<()> {
+        let current = self.get_usage();
+        match usage > current {
+            true => self.try_alloc(usage - current),
+            false => {
+                self.dealloc(current - usage);
+                Ok(())
+            }
+        }
+    }
+
+    // Returns the tracker the limit of which is exceeded on failure.
+    fn reserve(&self, size: usize) -> std::result::Result<(), &QueryMemoryTracker> {
+        let usage = self.usage.fetch_add(size, Ordering::Relaxed) + size;
+        if self.limit != 0 && usage > self.limit {
+            self.usage.fetch_sub(size, Ordering::Relaxed);
+            return Err(self);
+        }
+
+        if let Some(parent) = &self.parent {
+            if let Err(exceeded) = parent.reserve(size) {
+                self.usage.fetch_sub(size, Ordering::Relaxed);
+                return Err(exceeded);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for QueryMemoryTracker {
+    fn drop(&mut self) {
+        // Release the memory still held by the operator from the query.
+        if let Some(parent) = &self.parent {
+            parent.dealloc(self.get_usage());
+        }
+    }
+}
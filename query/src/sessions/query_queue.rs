@@ -0,0 +1,101 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio::sync::OwnedSemaphorePermit;
+use common_base::tokio::sync::Semaphore;
+use common_base::tokio::time::timeout;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+
+/// Admission control of the queries of the users.
+///
+/// Each user of a tenant owns `max_concurrent_queries` slots, a query waits in the queue of its
+/// user until one of the slots is released, so the heavy queries of a user can not starve the
+/// queries of the others. The query fails if it waits for longer than `query_queue_timeout`.
+#[derive(Default)]
+pub struct QueryQueue {
+    // The slots and the semaphore of the slots of each user.
+    queues: Mutex<HashMap<String, (usize, Arc<Semaphore>)>>,
+}
+
+/// The slot held by a running query, which is released once it is dropped.
+pub struct QuerySlot {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl QueryQueue {
+    pub fn create() -> Arc<QueryQueue> {
+        Arc::new(QueryQueue::default())
+    }
+
+    /// Wait for a slot of the user, None if the queries of the user are unlimited.
+    ///
+    /// A timeout of 0 waits until a slot is released.
+    pub async fn acquire(
+        &self,
+        tenant: &str,
+        user: &str,
+        slots: usize,
+        wait_timeout: Duration,
+    ) -> Result<Option<QuerySlot>> {
+        if slots == 0 {
+            return Ok(None);
+        }
+
+        let semaphore = self.get_semaphore(format!("{}/{}", tenant, user), slots);
+        let permit = match wait_timeout.is_zero() {
+            true => semaphore.acquire_owned().await,
+            false => match timeout(wait_timeout, semaphore.acquire_owned()).await {
+                Ok(permit) => permit,
+                Err(_) => {
+                    return Err(ErrorCode::QueryQueueTimeout(format!(
+                        "The query of the user '{}'@'{}' waited for a slot for more than {:?}, \
+                         the user can run {} queries concurrently",
+                        user, tenant, wait_timeout, slots
+                    )));
+                }
+            },
+        };
+
+        let permit = permit.map_err(|e| ErrorCode::TokioError(e.to_string()))?;
+        Ok(Some(QuerySlot { _permit: permit }))
+    }
+
+    /// The number of the running queries of the user.
+    pub fn running_queries(&self, tenant: &str, user: &str) -> usize {
+        let queues = self.queues.lock();
+        match queues.get(&format!("{}/{}", tenant, user)) {
+            None => 0,
+            Some((slots, semaphore)) => slots - semaphore.available_permits(),
+        }
+    }
+
+    fn get_semaphore(&self, key: String, slots: usize) -> Arc<Semaphore> {
+        let mut queues = self.queues.lock();
+        match queues.get(&key) {
+            Some((queue_slots, semaphore)) if *queue_slots == slots => semaphore.clone(),
+            _ => {
+                // The slots are changed, the running queries keep the slots of the old queue.
+                let semaphore = Arc::new(Semaphore::new(slots));
+                queues.insert(key, (slots, semaphore.clone()));
+                semaphore
+            }
+        }
+    }
+}
@@ -22,6 +22,7 @@ use common_infallible::RwLock;
 use common_macros::MallocSizeOf;
 use common_mem_allocator::malloc_size;
 use common_meta_types::GrantObject;
+use common_meta_types::RoleInfo;
 use common_meta_types::UserInfo;
 use common_meta_types::UserPrivilegeType;
 use futures::channel::*;
@@ -36,6 +37,8 @@ use crate::sessions::SessionManager;
 use crate::sessions::SessionStatus;
 use crate::sessions::SessionType;
 use crate::sessions::Settings;
+use crate::storages::stream::StreamTable;
+use crate::storages::Table;
 
 #[derive(Clone, MallocSizeOf)]
 pub struct Session {
@@ -180,6 +183,19 @@ impl Session {
         self.session_ctx.set_current_user(user)
     }
 
+    /// The roles granted to the current user, with all the roles they inherit.
+    pub async fn get_current_roles(self: &Arc<Self>) -> Result<Vec<RoleInfo>> {
+        let current_user = self.get_current_user()?;
+        let tenant = self.get_current_tenant();
+        let role_cache = self
+            .get_shared_query_context()
+            .await?
+            .get_role_cache_manager();
+        role_cache
+            .find_related_roles(&tenant, &current_user.grants.roles())
+            .await
+    }
+
     pub async fn validate_privilege(
         self: &Arc<Self>,
         object: &GrantObject,
@@ -191,13 +207,8 @@ impl Session {
             return Ok(());
         }
 
-        let tenant = self.get_current_tenant();
-        let role_cache = self
-            .get_shared_query_context()
-            .await?
-            .get_role_cache_manager();
-        let role_verified = role_cache
-            .find_related_roles(&tenant, &current_user.grants.roles())
+        let role_verified = self
+            .get_current_roles()
             .await?
             .iter()
             .any(|r| r.grants.verify_privilege(object, privilege));
@@ -213,6 +224,45 @@ impl Session {
         )))
     }
 
+    /// Reading a table requires the SELECT privilege on it, except the metadata tables of
+    /// `system` and `information_schema`.
+    pub async fn validate_select_privilege(
+        self: &Arc<Self>,
+        database: &str,
+        table: &str,
+    ) -> Result<()> {
+        if database.eq_ignore_ascii_case("system")
+            || database.eq_ignore_ascii_case("information_schema")
+        {
+            return Ok(());
+        }
+        let object = GrantObject::Table(database.to_string(), table.to_string());
+        self.validate_privilege(&object, UserPrivilegeType::Select)
+            .await
+    }
+
+    /// Reading a stream returns the rows of the table it is created on, thus requires the SELECT
+    /// privilege on that table as well. The SELECT privilege on all the tables is required for
+    /// the streams which do not know the database of their tables.
+    pub async fn validate_read_privilege(
+        self: &Arc<Self>,
+        database: &str,
+        table: &dyn Table,
+    ) -> Result<()> {
+        self.validate_select_privilege(database, table.name())
+            .await?;
+        if let Some(stream) = table.as_any().downcast_ref::<StreamTable>() {
+            match stream.source_table_name() {
+                (Some(database), name) => self.validate_select_privilege(database, name).await?,
+                (None, _) => {
+                    self.validate_privilege(&GrantObject::Global, UserPrivilegeType::Select)
+                        .await?
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn get_settings(self: &Arc<Self>) -> Arc<Settings> {
         Arc::new(self.session_settings.clone())
     }
@@ -43,10 +43,15 @@ use opendal::Scheme as DalSchema;
 use crate::catalogs::DatabaseCatalog;
 use crate::clusters::ClusterDiscovery;
 use crate::configs::Config;
+use crate::interpreters::AsyncInsertQueue;
+use crate::interpreters::QueryHistory;
+use crate::interpreters::QueryResultCache;
+use crate::pipelines::new::PipeProfile;
 use crate::servers::http::v1::HttpQueryManager;
 use crate::sessions::session::Session;
 use crate::sessions::session_ref::SessionRef;
 use crate::sessions::ProcessInfo;
+use crate::sessions::QueryQueue;
 use crate::sessions::SessionManagerStatus;
 use crate::sessions::SessionType;
 use crate::storages::cache::CacheManager;
@@ -60,6 +65,10 @@ pub struct SessionManager {
     pub(in crate::sessions) user_manager: RwLock<Arc<UserApiProvider>>,
     pub(in crate::sessions) auth_manager: RwLock<Arc<AuthMgr>>,
     pub(in crate::sessions) http_query_manager: Arc<HttpQueryManager>,
+    pub(in crate::sessions) async_insert_queue: Arc<AsyncInsertQueue>,
+    pub(in crate::sessions) query_result_cache: Arc<QueryResultCache>,
+    pub(in crate::sessions) query_history: Arc<QueryHistory>,
+    pub(in crate::sessions) query_queue: Arc<QueryQueue>,
 
     pub(in crate::sessions) max_sessions: usize,
     pub(in crate::sessions) active_sessions: Arc<RwLock<HashMap<String, Arc<Session>>>>,
@@ -115,6 +124,10 @@ impl SessionManager {
             discovery: RwLock::new(discovery),
             user_manager: RwLock::new(user),
             http_query_manager,
+            async_insert_queue: AsyncInsertQueue::create(),
+            query_result_cache: QueryResultCache::create(),
+            query_history: QueryHistory::create(),
+            query_queue: QueryQueue::create(),
             max_sessions,
             active_sessions,
             auth_manager: RwLock::new(auth_manager),
@@ -139,6 +152,22 @@ impl SessionManager {
         self.http_query_manager.clone()
     }
 
+    pub fn get_async_insert_queue(&self) -> Arc<AsyncInsertQueue> {
+        self.async_insert_queue.clone()
+    }
+
+    pub fn get_query_result_cache(&self) -> Arc<QueryResultCache> {
+        self.query_result_cache.clone()
+    }
+
+    pub fn get_query_history(&self) -> Arc<QueryHistory> {
+        self.query_history.clone()
+    }
+
+    pub fn get_query_queue(&self) -> Arc<QueryQueue> {
+        self.query_queue.clone()
+    }
+
     pub fn get_auth_manager(self: &Arc<Self>) -> Arc<AuthMgr> {
         self.auth_manager.read().clone()
     }
@@ -271,7 +300,11 @@ impl SessionManager {
         timeout_secs: i32,
     ) -> impl Future<Output = ()> {
         let active_sessions = self.active_sessions.clone();
+        let async_insert_queue = self.async_insert_queue.clone();
         async move {
+            // the buffered inserts are committed before the connections are closed
+            async_insert_queue.flush_all().await;
+
             tracing::info!(
                 "Waiting {} secs for connections to close. You can press Ctrl + C again to force shutdown.",
                 timeout_secs);
@@ -306,6 +339,16 @@ impl SessionManager {
             .collect::<Vec<_>>()
     }
 
+    /// The profiles of the pipelines of the running queries, with the id of each query.
+    pub fn running_pipelines(self: &Arc<Self>) -> Vec<(String, Vec<Vec<Arc<PipeProfile>>>)> {
+        let sessions = self.active_sessions.read();
+        sessions
+            .values()
+            .filter_map(|session| session.session_ctx.get_query_context_shared())
+            .map(|shared| (shared.get_query_id(), shared.get_pipeline_profiles()))
+            .collect::<Vec<_>>()
+    }
+
     async fn destroy_idle_sessions(sessions: &Arc<RwLock<HashMap<String, Arc<Session>>>>) -> bool {
         // Read lock does not support reentrant
         // https://github.com/Amanieu/parking_lot/blob/lock_api-0.4.4/lock_api/src/rwlock.rs#L422
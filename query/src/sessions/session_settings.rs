@@ -148,6 +148,114 @@ impl Settings {
                 level: ScopeLevel::Session,
                 desc: "Timezone, default value: UTC,",
             },
+            SettingValue {
+                default_value: DataValue::UInt64(12),
+                user_setting: UserSetting::create("retention_period", DataValue::UInt64(12)),
+                level: ScopeLevel::Session,
+                desc: "The retention period (in hours) of historical data. By default, it is 12 hours.",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("enable_async_insert", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc: "Buffer the small inserts and commit them in batches if value != 0, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(1),
+                user_setting: UserSetting::create("wait_for_async_insert", DataValue::UInt64(1)),
+                level: ScopeLevel::Session,
+                desc: "Wait until the buffered insert is committed if value != 0, default value: 1",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(1048576),
+                user_setting: UserSetting::create("async_insert_max_data_size", DataValue::UInt64(1048576)),
+                level: ScopeLevel::Session,
+                desc: "The size in bytes of the buffered inserts to commit them. By default, it is 1MB.",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(200),
+                user_setting: UserSetting::create("async_insert_busy_timeout", DataValue::UInt64(200)),
+                level: ScopeLevel::Session,
+                desc: "Max duration in milliseconds the inserts are buffered. By default, it is 200 ms.",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("group_by_spill_threshold", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc: "The size in bytes of the grouped data to spill it to disk, 0 to disable it, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("sort_spill_threshold", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc: "The size in bytes of the sorted data to spill it to disk, 0 to disable it, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::String("".as_bytes().to_vec()),
+                user_setting: UserSetting::create("spill_path", DataValue::String("".as_bytes().to_vec())),
+                level: ScopeLevel::Session,
+                desc: "The local directory of the spilled data. By default, the data is spilled to the table storage.",
+            },
+            SettingValue {
+                default_value: DataValue::String("lz4".as_bytes().to_vec()),
+                user_setting: UserSetting::create("spill_compression", DataValue::String("lz4".as_bytes().to_vec())),
+                level: ScopeLevel::Session,
+                desc: "The compression of the spilled data: none, lz4, snappy or zstd, default value: lz4",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(4),
+                user_setting: UserSetting::create("spill_concurrency", DataValue::UInt64(4)),
+                level: ScopeLevel::Session,
+                desc: "The number of the spilled files written or read at the same time, default value: 4",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("enable_query_result_cache", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc: "Cache the small results of the queries on unchanged tables if value != 0, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(300),
+                user_setting: UserSetting::create("query_result_cache_ttl", DataValue::UInt64(300)),
+                level: ScopeLevel::Session,
+                desc: "The seconds the cached query results are kept. By default, it is 300 seconds.",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(1048576),
+                user_setting: UserSetting::create("query_result_cache_max_bytes", DataValue::UInt64(1048576)),
+                level: ScopeLevel::Session,
+                desc: "Max size in bytes of the cached query results of the tenant. By default, it is 1MB.",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("max_query_memory", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc: "The maximum memory in bytes held by the operators of a query, 0 for unlimited, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("max_concurrent_queries", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc: "The maximum number of the running queries of a user, 0 for unlimited, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("query_queue_timeout", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc: "The seconds a query waits for a slot of its user, 0 to wait forever, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(65536),
+                user_setting: UserSetting::create("partial_group_by_sample_rows", DataValue::UInt64(65536)),
+                level: ScopeLevel::Session,
+                desc: "Rows sampled to skip the partial group by of unique keys, 0 to disable it, default value: 65536",
+            },
+            SettingValue {
+                default_value: DataValue::String("json".as_bytes().to_vec()),
+                user_setting: UserSetting::create("snapshot_encoding", DataValue::String("json".as_bytes().to_vec())),
+                level: ScopeLevel::Session,
+                desc: "The encoding of the written snapshots: json or msgpack, default value: json",
+            },
         ];
 
         let settings = Arc::new(RwLock::new(HashMap::default()));
@@ -243,6 +351,99 @@ impl Settings {
         self.try_get_u64(key)
     }
 
+    pub fn get_retention_period(&self) -> Result<u64> {
+        let key = "retention_period";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_enable_async_insert(&self) -> Result<u64> {
+        let key = "enable_async_insert";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_wait_for_async_insert(&self) -> Result<u64> {
+        let key = "wait_for_async_insert";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_async_insert_max_data_size(&self) -> Result<u64> {
+        let key = "async_insert_max_data_size";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_async_insert_busy_timeout(&self) -> Result<u64> {
+        let key = "async_insert_busy_timeout";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_group_by_spill_threshold(&self) -> Result<u64> {
+        let key = "group_by_spill_threshold";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_sort_spill_threshold(&self) -> Result<u64> {
+        let key = "sort_spill_threshold";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_spill_path(&self) -> Result<Vec<u8>> {
+        let key = "spill_path";
+        self.check_and_get_setting_value(key)
+            .and_then(|v| v.user_setting.value.as_string())
+    }
+
+    pub fn get_spill_compression(&self) -> Result<Vec<u8>> {
+        let key = "spill_compression";
+        self.check_and_get_setting_value(key)
+            .and_then(|v| v.user_setting.value.as_string())
+    }
+
+    pub fn get_snapshot_encoding(&self) -> Result<Vec<u8>> {
+        let key = "snapshot_encoding";
+        self.check_and_get_setting_value(key)
+            .and_then(|v| v.user_setting.value.as_string())
+    }
+
+    pub fn get_spill_concurrency(&self) -> Result<u64> {
+        let key = "spill_concurrency";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_enable_query_result_cache(&self) -> Result<u64> {
+        let key = "enable_query_result_cache";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_query_result_cache_ttl(&self) -> Result<u64> {
+        let key = "query_result_cache_ttl";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_query_result_cache_max_bytes(&self) -> Result<u64> {
+        let key = "query_result_cache_max_bytes";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_max_query_memory(&self) -> Result<u64> {
+        let key = "max_query_memory";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_max_concurrent_queries(&self) -> Result<u64> {
+        let key = "max_concurrent_queries";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_query_queue_timeout(&self) -> Result<u64> {
+        let key = "query_queue_timeout";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_partial_group_by_sample_rows(&self) -> Result<u64> {
+        let key = "partial_group_by_sample_rows";
+        self.try_get_u64(key)
+    }
+
     pub fn get_timezone(&self) -> Result<Vec<u8>> {
         let key = "timezone";
         self.check_and_get_setting_value(key)
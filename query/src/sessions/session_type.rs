@@ -22,7 +22,10 @@ pub enum SessionType {
     HTTPStreamingLoad,
     ClickHouseHttpHandler,
     FlightRPC,
+    FlightSQL,
     HTTPAPI(String),
+    // Loads the data of a pipe in background.
+    Pipe,
     Test,
     Fuzz,
 }
@@ -31,7 +34,7 @@ impl SessionType {
     pub fn is_user_session(&self) -> bool {
         !matches!(
             self,
-            SessionType::HTTPAPI(_) | SessionType::Test | SessionType::Fuzz
+            SessionType::HTTPAPI(_) | SessionType::Pipe | SessionType::Test | SessionType::Fuzz
         )
     }
 }
@@ -46,7 +49,9 @@ impl fmt::Display for SessionType {
             SessionType::HTTPStreamingLoad => "HTTPStreamingLoad".to_string(),
             SessionType::Test => "Test".to_string(),
             SessionType::FlightRPC => "FlightRPC".to_string(),
+            SessionType::FlightSQL => "FlightSQL".to_string(),
             SessionType::HTTPAPI(usage) => format!("HTTPAPI({})", usage),
+            SessionType::Pipe => "Pipe".to_string(),
             SessionType::Fuzz => "Fuzz".to_string(),
         };
         write!(f, "{}", name)
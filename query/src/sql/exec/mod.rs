@@ -25,6 +25,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::Expression;
 use common_planners::RewriteHelper;
+use common_planners::TableSample;
 pub use util::decode_field_name;
 pub use util::format_field_name;
 
@@ -34,6 +35,7 @@ use crate::pipelines::new::processors::AggregatorTransformParams;
 use crate::pipelines::new::processors::ProjectionTransform;
 use crate::pipelines::new::processors::TransformAggregator;
 use crate::pipelines::new::processors::TransformFilter;
+use crate::pipelines::new::processors::TransformSample;
 use crate::pipelines::new::NewPipeline;
 use crate::sessions::QueryContext;
 use crate::sql::exec::data_schema_builder::DataSchemaBuilder;
@@ -217,6 +219,19 @@ impl PipelineBuilder {
         self.ctx.try_set_partitions(plan.parts.clone())?;
         table.read2(self.ctx.clone(), &plan, &mut self.pipeline)?;
 
+        // the blocks are sampled by the table while pruning, and the rows are sampled here
+        let sample = plan.push_downs.as_ref().and_then(|extras| extras.sample);
+        if let Some(TableSample::Row(percent)) = sample {
+            self.pipeline
+                .add_transform(|transform_input_port, transform_output_port| {
+                    TransformSample::try_create(
+                        percent,
+                        transform_input_port,
+                        transform_output_port,
+                    )
+                })?;
+        }
+
         let columns: Vec<IndexType> = scan.columns.iter().cloned().collect();
         let projections: Vec<Expression> = columns
             .iter()
@@ -12,19 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod parser_aggregating_index;
+mod parser_analyze;
+mod parser_backup;
 mod parser_call;
+mod parser_connection;
 mod parser_copy;
 mod parser_database;
+mod parser_delete;
 mod parser_explain;
 mod parser_insert;
 mod parser_kill;
+mod parser_merge;
+mod parser_network_policy;
 mod parser_optimize;
+mod parser_pipe;
 mod parser_query;
+mod parser_row_access_policy;
 mod parser_set;
+mod parser_share;
 mod parser_show;
 mod parser_stage;
+mod parser_stream;
 mod parser_table;
 mod parser_udf;
+mod parser_update;
 mod parser_use;
 mod parser_user;
+mod parser_vacuum;
 mod parser_view;
@@ -0,0 +1,52 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// Borrow from apache/arrow/rust/datafusion/src/sql/sql_parser
+// See notice.md
+
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+
+use crate::parser_err;
+use crate::sql::statements::DfCreateAggregatingIndex;
+use crate::sql::statements::DfQueryStatement;
+use crate::sql::DfParser;
+use crate::sql::DfStatement;
+
+impl<'a> DfParser<'a> {
+    // Create aggregating index.
+    // syntax: "CREATE AGGREGATING INDEX [IF NOT EXISTS] name AS SELECT ... GROUP BY ..."
+    pub(crate) fn parse_create_aggregating_index(
+        &mut self,
+    ) -> Result<DfStatement<'a>, ParserError> {
+        self.parser.expect_keyword(Keyword::INDEX)?;
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_identifier()?;
+
+        if self.consume_token("AS") {
+            let query = DfQueryStatement::try_from(self.parser.parse_query()?)?;
+            Ok(DfStatement::CreateAggregatingIndex(
+                DfCreateAggregatingIndex {
+                    if_not_exists,
+                    name,
+                    query,
+                },
+            ))
+        } else {
+            parser_err!("need `AS` after AGGREGATING INDEX NAME")
+        }
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+
+use crate::sql::statements::DfBackupDatabase;
+use crate::sql::statements::DfRestoreDatabase;
+use crate::sql::DfParser;
+use crate::sql::DfStatement;
+
+struct BackupLocation {
+    location: String,
+    connection: String,
+    credential_options: BTreeMap<String, String>,
+    with_users: bool,
+}
+
+impl<'a> DfParser<'a> {
+    // backup database db to '<location>' [connection = '<name>'] [credentials = (...)]
+    // [with users]
+    pub(crate) fn parse_backup(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        self.expect_token("BACKUP")?;
+        self.parser.expect_keyword(Keyword::DATABASE)?;
+        let name = self.parser.parse_identifier()?.value;
+        self.parser.expect_keyword(Keyword::TO)?;
+        let location = self.parse_backup_location()?;
+
+        Ok(DfStatement::BackupDatabase(DfBackupDatabase {
+            name,
+            location: location.location,
+            connection: location.connection,
+            credential_options: location.credential_options,
+            with_users: location.with_users,
+        }))
+    }
+
+    // restore database db from '<location>' [connection = '<name>'] [credentials = (...)]
+    // [with users]
+    pub(crate) fn parse_restore(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        self.expect_token("RESTORE")?;
+        self.parser.expect_keyword(Keyword::DATABASE)?;
+        let name = self.parser.parse_identifier()?.value;
+        self.parser.expect_keyword(Keyword::FROM)?;
+        let location = self.parse_backup_location()?;
+
+        Ok(DfStatement::RestoreDatabase(DfRestoreDatabase {
+            name,
+            location: location.location,
+            connection: location.connection,
+            credential_options: location.credential_options,
+            with_users: location.with_users,
+        }))
+    }
+
+    fn parse_backup_location(&mut self) -> Result<BackupLocation, ParserError> {
+        // '@my_stage/backup' or 's3://mybucket/backup'
+        let location = self.parser.parse_literal_string()?;
+
+        // connection='my_connection'
+        let mut connection = "".to_string();
+        if self.consume_token("CONNECTION") {
+            self.expect_token("=")?;
+            connection = self.parser.parse_literal_string()?;
+        }
+
+        // credentials=(aws_key_id='$AWS_ACCESS_KEY_ID' aws_secret_key='$AWS_SECRET_ACCESS_KEY')
+        let mut credential_options = BTreeMap::default();
+        if self.consume_token("CREDENTIALS") {
+            self.expect_token("=")?;
+            self.expect_token("(")?;
+            credential_options = self.parse_options()?;
+            self.expect_token(")")?;
+        }
+
+        let with_users = if self.parser.parse_keyword(Keyword::WITH) {
+            self.expect_token("USERS")?;
+            true
+        } else {
+            false
+        };
+
+        Ok(BackupLocation {
+            location,
+            connection,
+            credential_options,
+            with_users,
+        })
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
+
+use crate::sql::statements::DfAlterConnection;
+use crate::sql::statements::DfCreateConnection;
+use crate::sql::statements::DfDropConnection;
+use crate::sql::statements::DfShowConnections;
+use crate::sql::DfParser;
+use crate::sql::DfStatement;
+
+impl<'a> DfParser<'a> {
+    // Create connection.
+    // syntax: "CREATE CONNECTION [IF NOT EXISTS] name STORAGE_TYPE = 's3'
+    //          CREDENTIALS = (aws_key_id = '..' aws_secret_key = '..') [COMMENT = '..']"
+    pub(crate) fn parse_create_connection(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_literal_string()?;
+
+        self.expect_token("STORAGE_TYPE")?;
+        self.expect_token("=")?;
+        let storage_type = self.parser.parse_literal_string()?;
+
+        let credential_options = self.parse_connection_credentials()?;
+
+        let comment = if self.consume_token("COMMENT") {
+            self.parser.expect_token(&Token::Eq)?;
+            self.parser.parse_literal_string()?
+        } else {
+            String::from("")
+        };
+
+        Ok(DfStatement::CreateConnection(DfCreateConnection {
+            if_not_exists,
+            name,
+            storage_type,
+            credential_options,
+            comment,
+        }))
+    }
+
+    // Alter connection.
+    // syntax: "ALTER CONNECTION name SET CREDENTIALS = (..)"
+    //         "ALTER CONNECTION name ROTATE MASTER KEY"
+    pub(crate) fn parse_alter_connection(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        let name = self.parser.parse_literal_string()?;
+
+        let credential_options = if self.parser.parse_keyword(Keyword::SET) {
+            Some(self.parse_connection_credentials()?)
+        } else {
+            self.expect_token("ROTATE")?;
+            self.expect_token("MASTER")?;
+            self.expect_token("KEY")?;
+            None
+        };
+
+        Ok(DfStatement::AlterConnection(DfAlterConnection {
+            name,
+            credential_options,
+        }))
+    }
+
+    pub(crate) fn parse_drop_connection(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parser.parse_literal_string()?;
+
+        Ok(DfStatement::DropConnection(DfDropConnection {
+            if_exists,
+            name,
+        }))
+    }
+
+    pub(crate) fn parse_show_connections(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        Ok(DfStatement::ShowConnections(DfShowConnections))
+    }
+
+    // credentials=(aws_key_id='$AWS_ACCESS_KEY_ID' aws_secret_key='$AWS_SECRET_ACCESS_KEY')
+    fn parse_connection_credentials(&mut self) -> Result<BTreeMap<String, String>, ParserError> {
+        self.expect_token("CREDENTIALS")?;
+        self.expect_token("=")?;
+        self.expect_token("(")?;
+        let credential_options = self.parse_options()?;
+        self.expect_token(")")?;
+        Ok(credential_options)
+    }
+}
@@ -17,12 +17,17 @@
 
 use std::collections::BTreeMap;
 
+use sqlparser::ast::Expr;
+use sqlparser::ast::SelectItem;
+use sqlparser::ast::TableFactor;
 use sqlparser::keywords::Keyword;
 use sqlparser::parser::IsOptional;
 use sqlparser::parser::ParserError;
 use sqlparser::tokenizer::Token;
 
+use crate::parser_err;
 use crate::sql::statements::DfCopy;
+use crate::sql::statements::DfQueryStatement;
 use crate::sql::DfParser;
 use crate::sql::DfStatement;
 
@@ -36,8 +41,25 @@ impl<'a> DfParser<'a> {
             .parse_parenthesized_column_list(IsOptional::Optional)?;
 
         // from 's3://mybucket/data/files'
+        // from (select $1, upper($2) from @my_stage)
         self.parser.expect_keyword(Keyword::FROM)?;
-        let location = self.parser.parse_literal_string()?;
+        let mut transformation = vec![];
+        let location = match self.parser.consume_token(&Token::LParen) {
+            true => {
+                let (location, exprs) = self.parse_copy_transformation()?;
+                self.parser.expect_token(&Token::RParen)?;
+                transformation = exprs;
+                location
+            }
+            false => self.parser.parse_literal_string()?,
+        };
+
+        // connection='my_connection'
+        let mut connection = "".to_string();
+        if self.consume_token("CONNECTION") {
+            self.expect_token("=")?;
+            connection = self.parser.parse_literal_string()?;
+        }
 
         // credentials=(aws_key_id='$AWS_ACCESS_KEY_ID' aws_secret_key='$AWS_SECRET_ACCESS_KEY')
         let mut credential_options = BTreeMap::default();
@@ -86,6 +108,8 @@ impl<'a> DfParser<'a> {
          copyOptions ::=
          ON_ERROR = { CONTINUE | SKIP_FILE | SKIP_FILE_<num> | SKIP_FILE_<num>% | ABORT_STATEMENT }
          SIZE_LIMIT = <num>
+         PURGE = TRUE | FALSE
+         REJECTED_RECORDS = '<path>'
         */
         let mut on_error = "".to_string();
         if self.consume_token("ON_ERROR") {
@@ -99,6 +123,18 @@ impl<'a> DfParser<'a> {
             size_limit = self.parse_value_or_ident()?;
         }
 
+        let mut purge = "".to_string();
+        if self.consume_token("PURGE") {
+            self.expect_token("=")?;
+            purge = self.parse_value_or_ident()?;
+        }
+
+        let mut rejected_records = "".to_string();
+        if self.consume_token("REJECTED_RECORDS") {
+            self.expect_token("=")?;
+            rejected_records = self.parser.parse_literal_string()?;
+        }
+
         // VALIDATION_MODE = RETURN_<n>_ROWS | RETURN_ERRORS | RETURN_ALL_ERRORS
         let mut validation_mode = "".to_string();
         if self.consume_token("VALIDATION_MODE") {
@@ -110,6 +146,7 @@ impl<'a> DfParser<'a> {
             name,
             columns,
             location,
+            connection,
             credential_options,
             encryption_options,
             file_format_options,
@@ -117,7 +154,55 @@ impl<'a> DfParser<'a> {
             pattern,
             on_error,
             size_limit,
+            purge,
+            rejected_records,
             validation_mode,
+            transformation,
         }))
     }
+
+    // The transformation of the files of a named stage, only the expressions of the columns:
+    // select $1, upper($2), metadata$filename from @my_stage
+    fn parse_copy_transformation(&mut self) -> Result<(String, Vec<Expr>), ParserError> {
+        let query = DfQueryStatement::try_from(self.parser.parse_query()?)?;
+        let location = match query.from.as_slice() {
+            [from] if from.joins.is_empty() => match &from.relation {
+                TableFactor::Table { name, args, .. }
+                    if args.is_empty() && name.0.len() == 1 && name.0[0].value.starts_with('@') =>
+                {
+                    Some(name.0[0].value.clone())
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let location = match location {
+            Some(location)
+                if !query.distinct
+                    && query.selection.is_none()
+                    && query.group_by.is_empty()
+                    && query.having.is_none()
+                    && query.order_by.is_empty()
+                    && query.limit.is_none()
+                    && query.offset.is_none() =>
+            {
+                location
+            }
+            _ => {
+                return parser_err!("COPY transformation must be SELECT <expr>, ... FROM @<stage>");
+            }
+        };
+
+        let mut exprs = Vec::with_capacity(query.projection.len());
+        for item in query.projection {
+            match item {
+                SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                    exprs.push(expr)
+                }
+                _ => return parser_err!("COPY transformation does not support the wildcard"),
+            }
+        }
+        Ok((location, exprs))
+    }
 }
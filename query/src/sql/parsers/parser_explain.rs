@@ -38,6 +38,10 @@ impl<'a> DfParser<'a> {
                     self.parser.next_token();
                     ExplainType::Graph
                 }
+                "ANALYZE" => {
+                    self.parser.next_token();
+                    ExplainType::Analyze
+                }
                 _ => ExplainType::Syntax,
             },
             _ => ExplainType::Syntax,
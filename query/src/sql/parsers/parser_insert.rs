@@ -19,10 +19,16 @@ use sqlparser::ast::SetExpr;
 use sqlparser::ast::Statement;
 use sqlparser::ast::StreamValues;
 use sqlparser::ast::Values;
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::Parser;
 use sqlparser::parser::ParserError;
 use sqlparser::tokenizer::QueryOffset;
+use sqlparser::tokenizer::Token;
 
 use crate::parser_err;
+use crate::sql::statements::DfInsertMultiTable;
+use crate::sql::statements::DfInsertMultiTableBranch;
+use crate::sql::statements::DfInsertMultiTableInto;
 use crate::sql::statements::DfInsertStatement;
 use crate::sql::statements::InsertSource;
 use crate::sql::DfParser;
@@ -31,6 +37,12 @@ use crate::sql::DfStatement;
 impl<'a> DfParser<'a> {
     pub(crate) fn parse_insert(&mut self) -> Result<DfStatement<'a>, ParserError> {
         self.parser.next_token();
+        if self.consume_token("ALL") {
+            return self.parse_insert_multi_table(false);
+        }
+        if self.consume_token("FIRST") {
+            return self.parse_insert_multi_table(true);
+        }
         match self.parser.parse_stream_values_insert()? {
             Statement::Insert {
                 or,
@@ -74,6 +86,74 @@ impl<'a> DfParser<'a> {
         }
     }
 
+    fn parse_insert_multi_table(&mut self, first: bool) -> Result<DfStatement<'a>, ParserError> {
+        // syntax: "INSERT {FIRST | ALL} WHEN expr THEN into [into ...] [WHEN ...]
+        //          [ELSE into [into ...]] query",
+        // where into is "INTO t [(col [, ...])] [VALUES (expr [, ...])]"
+        let mut branches = vec![];
+        while self.parser.parse_keyword(Keyword::WHEN) {
+            let condition = self.parser.parse_expr()?;
+            self.parser.expect_keyword(Keyword::THEN)?;
+            let intos = self.parse_insert_multi_table_intos()?;
+            branches.push(DfInsertMultiTableBranch {
+                condition: Some(condition),
+                intos,
+            });
+        }
+        if branches.is_empty() {
+            return self.expected("WHEN", self.parser.peek_token());
+        }
+        if self.parser.parse_keyword(Keyword::ELSE) {
+            let intos = self.parse_insert_multi_table_intos()?;
+            branches.push(DfInsertMultiTableBranch {
+                condition: None,
+                intos,
+            });
+        }
+
+        let source = self.parser.parse_query()?;
+        Ok(DfStatement::InsertMultiTable(DfInsertMultiTable {
+            first,
+            branches,
+            source: Box::new(source),
+        }))
+    }
+
+    fn parse_insert_multi_table_intos(
+        &mut self,
+    ) -> Result<Vec<DfInsertMultiTableInto>, ParserError> {
+        let mut intos = vec![];
+        while self.parser.parse_keyword(Keyword::INTO) {
+            let table = self.parser.parse_object_name()?;
+            let columns = if self.parser.consume_token(&Token::LParen) {
+                let columns = self
+                    .parser
+                    .parse_comma_separated(Parser::parse_identifier)?;
+                self.parser.expect_token(&Token::RParen)?;
+                columns
+            } else {
+                vec![]
+            };
+            let values = if self.parser.parse_keyword(Keyword::VALUES) {
+                self.parser.expect_token(&Token::LParen)?;
+                let values = self.parser.parse_comma_separated(Parser::parse_expr)?;
+                self.parser.expect_token(&Token::RParen)?;
+                values
+            } else {
+                vec![]
+            };
+            intos.push(DfInsertMultiTableInto {
+                table,
+                columns,
+                values,
+            });
+        }
+        if intos.is_empty() {
+            return self.expected("INTO", self.parser.peek_token());
+        }
+        Ok(intos)
+    }
+
     fn get_values_str(&self, values_info: &StreamValues) -> Result<&'a str, ParserError> {
         let start = &values_info.start;
         let end = &values_info.end;
@@ -0,0 +1,127 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// Borrow from apache/arrow/rust/datafusion/src/sql/sql_parser
+// See notice.md
+
+use sqlparser::ast::Ident;
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::Parser;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
+
+use crate::sql::statements::DfMergeClause;
+use crate::sql::statements::DfMergeInto;
+use crate::sql::statements::DfMergeSource;
+use crate::sql::DfParser;
+use crate::sql::DfStatement;
+
+impl<'a> DfParser<'a> {
+    pub(crate) fn parse_merge(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        // syntax: "MERGE INTO t [[AS] alias] USING {s | (query)} [[AS] alias] ON expr
+        //          WHEN MATCHED [AND expr] THEN {UPDATE SET col = expr [, ...] | DELETE} ...
+        //          WHEN NOT MATCHED [AND expr] THEN INSERT [(col [, ...])] VALUES (expr [, ...])"
+        self.expect_token("MERGE")?;
+        self.parser.expect_keyword(Keyword::INTO)?;
+        let target = self.parser.parse_object_name()?;
+        let target_alias = self.parse_merge_alias()?;
+
+        self.parser.expect_keyword(Keyword::USING)?;
+        let source = if self.parser.consume_token(&Token::LParen) {
+            let query = self.parser.parse_query()?;
+            self.parser.expect_token(&Token::RParen)?;
+            DfMergeSource::Query(Box::new(query))
+        } else {
+            DfMergeSource::Table(self.parser.parse_object_name()?)
+        };
+        let source_alias = self.parse_merge_alias()?;
+
+        self.parser.expect_keyword(Keyword::ON)?;
+        let on = self.parser.parse_expr()?;
+
+        let mut clauses = vec![];
+        while self.parser.parse_keyword(Keyword::WHEN) {
+            clauses.push(self.parse_merge_clause()?);
+        }
+        if clauses.is_empty() {
+            return self.expected("WHEN", self.parser.peek_token());
+        }
+
+        Ok(DfStatement::MergeInto(DfMergeInto {
+            target,
+            target_alias,
+            source,
+            source_alias,
+            on,
+            clauses,
+        }))
+    }
+
+    fn parse_merge_alias(&mut self) -> Result<Option<Ident>, ParserError> {
+        if self.parser.parse_keyword(Keyword::AS) {
+            return Ok(Some(self.parser.parse_identifier()?));
+        }
+        match self.parser.peek_token() {
+            Token::Word(w) if w.keyword == Keyword::NoKeyword => {
+                Ok(Some(self.parser.parse_identifier()?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn parse_merge_clause(&mut self) -> Result<DfMergeClause, ParserError> {
+        let not_matched = self.parser.parse_keyword(Keyword::NOT);
+        self.expect_token("MATCHED")?;
+        let condition = if self.parser.parse_keyword(Keyword::AND) {
+            Some(self.parser.parse_expr()?)
+        } else {
+            None
+        };
+        self.parser.expect_keyword(Keyword::THEN)?;
+
+        if not_matched {
+            self.parser.expect_keyword(Keyword::INSERT)?;
+            let columns = if self.parser.consume_token(&Token::LParen) {
+                let columns = self
+                    .parser
+                    .parse_comma_separated(Parser::parse_identifier)?;
+                self.parser.expect_token(&Token::RParen)?;
+                columns
+            } else {
+                vec![]
+            };
+            self.parser.expect_keyword(Keyword::VALUES)?;
+            self.parser.expect_token(&Token::LParen)?;
+            let values = self.parser.parse_comma_separated(Parser::parse_expr)?;
+            self.parser.expect_token(&Token::RParen)?;
+            return Ok(DfMergeClause::NotMatchedInsert {
+                condition,
+                columns,
+                values,
+            });
+        }
+
+        if self.parser.parse_keyword(Keyword::UPDATE) {
+            let update_list = self.parse_update_list()?;
+            Ok(DfMergeClause::MatchedUpdate {
+                condition,
+                update_list,
+            })
+        } else if self.parser.parse_keyword(Keyword::DELETE) {
+            Ok(DfMergeClause::MatchedDelete { condition })
+        } else {
+            self.expected("UPDATE or DELETE", self.parser.peek_token())
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
+
+use crate::sql::statements::DfCreateNetworkPolicy;
+use crate::sql::statements::DfDropNetworkPolicy;
+use crate::sql::statements::DfShowNetworkPolicies;
+use crate::sql::DfParser;
+use crate::sql::DfStatement;
+
+impl<'a> DfParser<'a> {
+    // Create network policy.
+    // syntax: "CREATE NETWORK POLICY [IF NOT EXISTS] name ALLOWED_IP_LIST = ('cidr', ..)
+    //          [BLOCKED_IP_LIST = ('cidr', ..)] [COMMENT = '..']"
+    pub(crate) fn parse_create_network_policy(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        self.expect_token("POLICY")?;
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_literal_string()?;
+
+        self.expect_token("ALLOWED_IP_LIST")?;
+        let allowed_ip_list = self.parse_ip_list()?;
+        let blocked_ip_list = if self.consume_token("BLOCKED_IP_LIST") {
+            self.parse_ip_list()?
+        } else {
+            vec![]
+        };
+
+        let comment = if self.consume_token("COMMENT") {
+            self.parser.expect_token(&Token::Eq)?;
+            self.parser.parse_literal_string()?
+        } else {
+            String::from("")
+        };
+
+        Ok(DfStatement::CreateNetworkPolicy(DfCreateNetworkPolicy {
+            if_not_exists,
+            name,
+            allowed_ip_list,
+            blocked_ip_list,
+            comment,
+        }))
+    }
+
+    pub(crate) fn parse_drop_network_policy(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        self.expect_token("POLICY")?;
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parser.parse_literal_string()?;
+
+        Ok(DfStatement::DropNetworkPolicy(DfDropNetworkPolicy {
+            if_exists,
+            name,
+        }))
+    }
+
+    pub(crate) fn parse_show_network_policies(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        self.expect_token("POLICIES")?;
+
+        Ok(DfStatement::ShowNetworkPolicies(DfShowNetworkPolicies))
+    }
+
+    fn parse_ip_list(&mut self) -> Result<Vec<String>, ParserError> {
+        self.parser.expect_token(&Token::Eq)?;
+        self.parser.expect_token(&Token::LParen)?;
+        let ip_list = self
+            .parser
+            .parse_comma_separated(|parser| parser.parse_literal_string())?;
+        self.parser.expect_token(&Token::RParen)?;
+        Ok(ip_list)
+    }
+}
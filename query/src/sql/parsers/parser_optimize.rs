@@ -26,7 +26,8 @@ use crate::sql::DfStatement;
 
 impl<'a> DfParser<'a> {
     pub(crate) fn parse_optimize(&mut self) -> Result<DfStatement<'a>, ParserError> {
-        // syntax: "optimize TABLE t [purge | compact | all]",  default action is "purge"
+        // syntax: "optimize TABLE t [purge | compact [segment] [limit n] | expire | all]",
+        // default action is "purge"
         self.expect_token("OPTIMIZE")?;
         self.parser.expect_keyword(Keyword::TABLE)?;
         let object_name = self.parser.parse_object_name()?;
@@ -36,16 +37,32 @@ impl<'a> DfParser<'a> {
                 Keyword::ALL => Ok(Optimization::ALL),
                 Keyword::PURGE => Ok(Optimization::PURGE),
                 Keyword::NoKeyword if w.value.to_uppercase().as_str() == "COMPACT" => {
-                    Ok(Optimization::COMPACT)
+                    if self.consume_token("SEGMENT") {
+                        Ok(Optimization::COMPACT_SEGMENT)
+                    } else {
+                        Ok(Optimization::COMPACT)
+                    }
                 }
-                _ => self.expected("one of PURGE, COMPACT, ALL", Token::Word(w)),
+                Keyword::NoKeyword if w.value.to_uppercase().as_str() == "EXPIRE" => {
+                    Ok(Optimization::EXPIRE)
+                }
+                _ => self.expected("one of PURGE, COMPACT, EXPIRE, ALL", Token::Word(w)),
             },
-            t => self.expected("Nothing, or one of PURGE, COMPACT, ALL", t),
+            t => self.expected("Nothing, or one of PURGE, COMPACT, EXPIRE, ALL", t),
         }?;
 
+        let limit = if operation.contains(Optimization::COMPACT)
+            && self.parser.parse_keyword(Keyword::LIMIT)
+        {
+            Some(self.parser.parse_literal_uint()? as usize)
+        } else {
+            None
+        };
+
         Ok(DfStatement::OptimizeTable(DfOptimizeTable {
             name: object_name,
             operation,
+            limit,
         }))
     }
 }
@@ -0,0 +1,104 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// Borrow from apache/arrow/rust/datafusion/src/sql/sql_parser
+// See notice.md
+
+use std::collections::BTreeMap;
+
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
+
+use crate::sql::statements::DfCreatePipe;
+use crate::sql::statements::DfDropPipe;
+use crate::sql::DfParser;
+use crate::sql::DfStatement;
+
+impl<'a> DfParser<'a> {
+    // Create pipe.
+    // syntax: "CREATE PIPE [IF NOT EXISTS] name INTO [db.]table
+    //          FROM { KAFKA(BROKERS = '..' TOPIC = '..') | @stage[/path] [PATTERN = '..'] }
+    //          [FILE_FORMAT = (TYPE = ..)] [ON_ERROR = ..] [SIZE_LIMIT = ..] [COMMENTS = '..']"
+    pub(crate) fn parse_create_pipe(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_literal_string()?;
+        self.parser.expect_keyword(Keyword::INTO)?;
+        let table_name = self.parser.parse_object_name()?;
+        self.parser.expect_keyword(Keyword::FROM)?;
+
+        // the source options of a stage are the copy options
+        let (source, mut source_options) = match self.parser.peek_token() {
+            Token::AtString(s) => {
+                self.parser.next_token();
+                (format!("@{}", s), BTreeMap::default())
+            }
+            _ => {
+                let source = self.parser.parse_identifier()?.value.to_uppercase();
+                self.expect_token("(")?;
+                let source_options = self.parse_options()?;
+                self.expect_token(")")?;
+                (source, source_options)
+            }
+        };
+
+        if self.consume_token("PATTERN") {
+            self.expect_token("=")?;
+            source_options.insert("pattern".to_string(), self.parse_value_or_ident()?);
+        }
+
+        // file_format = (type = json)
+        let mut file_format_options = BTreeMap::default();
+        if self.consume_token("FILE_FORMAT") {
+            self.expect_token("=")?;
+            self.expect_token("(")?;
+            file_format_options = self.parse_options()?;
+            self.expect_token(")")?;
+        }
+
+        for option in ["ON_ERROR", "SIZE_LIMIT"] {
+            if self.consume_token(option) {
+                self.expect_token("=")?;
+                let value = self.parse_value_or_ident()?;
+                source_options.insert(option.to_lowercase(), value);
+            }
+        }
+
+        let comments = if self.consume_token("COMMENTS") {
+            self.parser.expect_token(&Token::Eq)?;
+            self.parser.parse_literal_string()?
+        } else {
+            String::from("")
+        };
+
+        Ok(DfStatement::CreatePipe(DfCreatePipe {
+            if_not_exists,
+            name,
+            table_name,
+            source,
+            source_options,
+            file_format_options,
+            comments,
+        }))
+    }
+
+    pub(crate) fn parse_drop_pipe(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parser.parse_literal_string()?;
+
+        Ok(DfStatement::DropPipe(DfDropPipe { if_exists, name }))
+    }
+}
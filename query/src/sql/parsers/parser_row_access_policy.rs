@@ -0,0 +1,122 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Borrow from apache/arrow/rust/datafusion/src/sql/sql_parser
+// See notice.md
+
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
+
+use crate::parser_err;
+use crate::sql::statements::DfCreateRowAccessPolicy;
+use crate::sql::statements::DfDropRowAccessPolicy;
+use crate::sql::statements::DfShowRowAccessPolicies;
+use crate::sql::DfParser;
+use crate::sql::DfStatement;
+
+impl<'a> DfParser<'a> {
+    // Create row access policy.
+    // syntax: "CREATE ROW ACCESS POLICY [IF NOT EXISTS] name AS (arg type, ..)
+    //          RETURNS BOOLEAN -> expr [EXEMPT ROLES = ('role', ..)] [COMMENT = '..']"
+    pub(crate) fn parse_create_row_access_policy(
+        &mut self,
+    ) -> Result<DfStatement<'a>, ParserError> {
+        self.expect_token("ACCESS")?;
+        self.expect_token("POLICY")?;
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_literal_string()?;
+        self.parser.expect_keyword(Keyword::AS)?;
+
+        self.parser.expect_token(&Token::LParen)?;
+        let mut args = vec![];
+        loop {
+            let arg_name = self.parser.parse_identifier()?;
+            if args.iter().any(|(name, _)| name == &arg_name) {
+                return parser_err!(format!(
+                    "Duplicate argument is not allowed, keep only one: {}",
+                    arg_name
+                ));
+            }
+            let data_type = self.parser.parse_data_type()?;
+            args.push((arg_name, data_type));
+            if !self.parser.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        self.parser.expect_token(&Token::RParen)?;
+        self.expect_token("RETURNS")?;
+        self.expect_token("BOOLEAN")?;
+
+        // Match ->
+        self.parser.expect_token(&Token::Minus)?;
+        let next_token = self.parser.next_token_no_skip();
+        if next_token != Some(&Token::Gt) {
+            return parser_err!(format!("Expected >, found: {:#?}", next_token));
+        }
+        let body = self.parser.parse_expr()?;
+
+        let mut exempt_roles = vec![];
+        if self.consume_token("EXEMPT") {
+            self.expect_token("ROLES")?;
+            self.parser.expect_token(&Token::Eq)?;
+            self.parser.expect_token(&Token::LParen)?;
+            exempt_roles = self
+                .parser
+                .parse_comma_separated(|parser| parser.parse_literal_string())?;
+            self.parser.expect_token(&Token::RParen)?;
+        }
+
+        let comment = if self.consume_token("COMMENT") {
+            self.parser.expect_token(&Token::Eq)?;
+            self.parser.parse_literal_string()?
+        } else {
+            String::from("")
+        };
+
+        Ok(DfStatement::CreateRowAccessPolicy(
+            DfCreateRowAccessPolicy {
+                if_not_exists,
+                name,
+                args,
+                body,
+                exempt_roles,
+                comment,
+            },
+        ))
+    }
+
+    pub(crate) fn parse_drop_row_access_policy(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        self.expect_token("ACCESS")?;
+        self.expect_token("POLICY")?;
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parser.parse_literal_string()?;
+
+        Ok(DfStatement::DropRowAccessPolicy(DfDropRowAccessPolicy {
+            if_exists,
+            name,
+        }))
+    }
+
+    pub(crate) fn parse_show_row_access_policies(
+        &mut self,
+    ) -> Result<DfStatement<'a>, ParserError> {
+        self.expect_token("ACCESS")?;
+        self.expect_token("POLICIES")?;
+
+        Ok(DfStatement::ShowRowAccessPolicies(DfShowRowAccessPolicies))
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
+
+use crate::sql::statements::DfAlterShareTenants;
+use crate::sql::statements::DfCreateShare;
+use crate::sql::statements::DfDropShare;
+use crate::sql::statements::DfGrantShareObject;
+use crate::sql::statements::DfRevokeShareObject;
+use crate::sql::statements::DfShareGrantObject;
+use crate::sql::DfParser;
+use crate::sql::DfStatement;
+
+impl<'a> DfParser<'a> {
+    // Create share.
+    // syntax: "CREATE SHARE [IF NOT EXISTS] name"
+    pub(crate) fn parse_create_share(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let share = self.parser.parse_identifier()?.value;
+
+        Ok(DfStatement::CreateShare(DfCreateShare {
+            if_not_exists,
+            share,
+        }))
+    }
+
+    // Drop share.
+    // syntax: "DROP SHARE [IF EXISTS] name"
+    pub(crate) fn parse_drop_share(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let share = self.parser.parse_identifier()?.value;
+
+        Ok(DfStatement::DropShare(DfDropShare { if_exists, share }))
+    }
+
+    // Alter share.
+    // syntax: "ALTER SHARE [IF EXISTS] name { ADD | REMOVE } TENANTS = tenant [, tenant ...]"
+    pub(crate) fn parse_alter_share(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let share = self.parser.parse_identifier()?.value;
+
+        let is_add = if self.parser.parse_keyword(Keyword::ADD) {
+            true
+        } else if self.consume_token("REMOVE") {
+            false
+        } else {
+            return self.expected("ADD or REMOVE", self.parser.peek_token());
+        };
+        if !self.consume_token("TENANTS") {
+            return self.expected("TENANTS", self.parser.peek_token());
+        }
+        self.parser.expect_token(&Token::Eq)?;
+
+        let mut tenants = vec![];
+        loop {
+            tenants.push(self.parser.parse_identifier()?.value);
+            if !self.parser.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+
+        Ok(DfStatement::AlterShareTenants(DfAlterShareTenants {
+            if_exists,
+            share,
+            is_add,
+            tenants,
+        }))
+    }
+
+    /// Parses the object granted to a share, which is either "USAGE ON DATABASE db" or
+    /// "SELECT ON TABLE [db.]table". Nothing is consumed if it is not the case.
+    pub(crate) fn parse_share_grant_object(
+        &mut self,
+    ) -> Result<Option<DfShareGrantObject>, ParserError> {
+        let on_database = if self.consume_token("USAGE") {
+            true
+        } else if self.parser.parse_keyword(Keyword::SELECT) {
+            false
+        } else {
+            return Ok(None);
+        };
+        if !self.parser.parse_keyword(Keyword::ON) {
+            self.parser.prev_token();
+            return Ok(None);
+        }
+
+        if on_database {
+            if !self.parser.parse_keyword(Keyword::DATABASE) {
+                self.parser.prev_token();
+                self.parser.prev_token();
+                return Ok(None);
+            }
+            let db_name = self.parser.parse_identifier()?.value;
+            return Ok(Some(DfShareGrantObject::Database(db_name)));
+        }
+
+        if !self.parser.parse_keyword(Keyword::TABLE) {
+            self.parser.prev_token();
+            self.parser.prev_token();
+            return Ok(None);
+        }
+        let mut names = self.parser.parse_object_name()?.0;
+        match names.len() {
+            1 => Ok(Some(DfShareGrantObject::Table(None, names.remove(0).value))),
+            2 => {
+                let table_name = names.remove(1).value;
+                let db_name = names.remove(0).value;
+                Ok(Some(DfShareGrantObject::Table(Some(db_name), table_name)))
+            }
+            _ => self.expected("[db.]table", self.parser.peek_token()),
+        }
+    }
+
+    // Grant object to share.
+    // syntax: "GRANT { USAGE ON DATABASE db | SELECT ON TABLE [db.]table } TO SHARE name"
+    pub(crate) fn parse_grant_share_object(
+        &mut self,
+        object: DfShareGrantObject,
+    ) -> Result<DfStatement<'a>, ParserError> {
+        self.parser.expect_keyword(Keyword::TO)?;
+        if !self.consume_token("SHARE") {
+            return self.expected("SHARE", self.parser.peek_token());
+        }
+        let share = self.parser.parse_identifier()?.value;
+
+        Ok(DfStatement::GrantShareObject(DfGrantShareObject {
+            share,
+            object,
+        }))
+    }
+
+    // Revoke object from share.
+    // syntax: "REVOKE { USAGE ON DATABASE db | SELECT ON TABLE [db.]table } FROM SHARE name"
+    pub(crate) fn parse_revoke_share_object(
+        &mut self,
+        object: DfShareGrantObject,
+    ) -> Result<DfStatement<'a>, ParserError> {
+        self.parser.expect_keyword(Keyword::FROM)?;
+        if !self.consume_token("SHARE") {
+            return self.expected("SHARE", self.parser.peek_token());
+        }
+        let share = self.parser.parse_identifier()?.value;
+
+        Ok(DfStatement::RevokeShareObject(DfRevokeShareObject {
+            share,
+            object,
+        }))
+    }
+}
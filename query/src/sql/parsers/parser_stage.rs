@@ -35,6 +35,7 @@ impl<'a> DfParser<'a> {
                 .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
         let name = self.parser.parse_literal_string()?;
 
+        let mut connection = "".to_string();
         let mut credential_options = BTreeMap::default();
         let mut encryption_options = BTreeMap::default();
 
@@ -44,6 +45,12 @@ impl<'a> DfParser<'a> {
             self.expect_token("=")?;
             location = self.parser.parse_literal_string()?;
 
+            // connection='my_connection'
+            if self.consume_token("CONNECTION") {
+                self.expect_token("=")?;
+                connection = self.parser.parse_literal_string()?;
+            }
+
             // credentials=(aws_key_id='$AWS_ACCESS_KEY_ID' aws_secret_key='$AWS_SECRET_ACCESS_KEY')
             if self.consume_token("CREDENTIALS") {
                 self.expect_token("=")?;
@@ -105,6 +112,7 @@ impl<'a> DfParser<'a> {
             if_not_exists,
             stage_name: name,
             location,
+            connection,
             credential_options,
             encryption_options,
             on_error,
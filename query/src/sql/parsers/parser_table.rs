@@ -15,10 +15,12 @@
 // Borrow from apache/arrow/rust/datafusion/src/sql/sql_parser
 // See notice.md
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 
 use sqlparser::ast::ColumnDef;
 use sqlparser::ast::ColumnOptionDef;
+use sqlparser::ast::Expr;
 use sqlparser::ast::TableConstraint;
 use sqlparser::keywords::Keyword;
 use sqlparser::parser::Parser;
@@ -29,15 +31,20 @@ use sqlparser::tokenizer::Word;
 use crate::parser_err;
 use crate::sql::statements::AlterTableAction;
 use crate::sql::statements::DfAlterTable;
+use crate::sql::statements::DfAttachTable;
+use crate::sql::statements::DfCloneSource;
 use crate::sql::statements::DfCreateTable;
 use crate::sql::statements::DfDescribeTable;
 use crate::sql::statements::DfDropTable;
+use crate::sql::statements::DfGeneratedColumn;
 use crate::sql::statements::DfQueryStatement;
 use crate::sql::statements::DfRenameTable;
 use crate::sql::statements::DfShowCreateTable;
 use crate::sql::statements::DfTruncateTable;
 use crate::sql::DfParser;
 use crate::sql::DfStatement;
+use crate::storages::fuse::FUSE_OPT_KEY_CLUSTER_ORDER;
+use crate::storages::NavigationPoint;
 
 impl<'a> DfParser<'a> {
     // Create table.
@@ -47,6 +54,36 @@ impl<'a> DfParser<'a> {
                 .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
         let table_name = self.parser.parse_object_name()?;
 
+        // Parse the location of the external fuse table, which is attached as a read only table:
+        // "CREATE TABLE t 'fuse://bucket/prefix' READ_ONLY"
+        if let Token::SingleQuotedString(_) = self.parser.peek_token() {
+            let uri = self.parser.parse_literal_string()?;
+            self.expect_token("READ_ONLY")?;
+            return Ok(DfStatement::AttachTable(DfAttachTable {
+                if_not_exists,
+                name: table_name,
+                uri,
+            }));
+        }
+
+        // Parse the table which we clone from, the table options are inherited from it.
+        if self.consume_token("CLONE") {
+            let clone = self.parse_clone_source()?;
+            let create = DfCreateTable {
+                if_not_exists,
+                name: table_name,
+                columns: vec![],
+                generated_columns: BTreeMap::new(),
+                engine: "FUSE".to_string(),
+                order_keys: vec![],
+                options: BTreeMap::new(),
+                like: None,
+                query: None,
+                clone: Some(clone),
+            };
+            return Ok(DfStatement::CreateTable(create));
+        }
+
         // Parse the table which we copy schema from. This is for create table like statement.
         // https://dev.mysql.com/doc/refman/8.0/en/create-table-like.html
         let mut table_like = None;
@@ -54,23 +91,31 @@ impl<'a> DfParser<'a> {
             table_like = Some(self.parser.parse_object_name()?);
         }
 
-        let (columns, _) = self.parse_columns()?;
+        let (columns, generated_columns, _) = self.parse_columns()?;
         if !columns.is_empty() && table_like.is_some() {
             return parser_err!("mix create table like statement and column definition.");
         }
 
         let engine = self.parse_table_engine()?;
 
-        // parse cluster key
+        // parse cluster key: CLUSTER BY (<expr>, ...) [ORDER = {LINEAR | ZORDER}]
         let mut order_keys = vec![];
+        let mut cluster_order = None;
         if self.parser.parse_keywords(&[Keyword::CLUSTER, Keyword::BY]) {
             self.parser.expect_token(&Token::LParen)?;
             order_keys = self.parser.parse_comma_separated(Parser::parse_expr)?;
             self.parser.expect_token(&Token::RParen)?;
+            if self.parser.parse_keyword(Keyword::ORDER) {
+                self.parser.expect_token(&Token::Eq)?;
+                cluster_order = Some(self.parser.parse_identifier()?.value.to_lowercase());
+            }
         }
 
         // parse table options: https://dev.mysql.com/doc/refman/8.0/en/create-table.html
-        let options = self.parse_options()?;
+        let mut options = self.parse_options()?;
+        if let Some(cluster_order) = cluster_order {
+            options.insert(FUSE_OPT_KEY_CLUSTER_ORDER.to_string(), cluster_order);
+        }
 
         let mut query = None;
         if let Token::Word(Word { keyword, .. }) = self.parser.peek_token() {
@@ -89,16 +134,63 @@ impl<'a> DfParser<'a> {
             if_not_exists,
             name: table_name,
             columns,
+            generated_columns,
             engine,
             order_keys,
             options,
             like: table_like,
             query,
+            clone: None,
         };
 
         Ok(DfStatement::CreateTable(create))
     }
 
+    // syntax: "CLONE [db.]t [AT (SNAPSHOT => 'id' | TIMESTAMP => 'ts')]", after the CLONE
+    fn parse_clone_source(&mut self) -> Result<DfCloneSource, ParserError> {
+        let name = self.parser.parse_object_name()?;
+        let mut at = None;
+        if self.consume_token("AT") {
+            at = Some(self.parse_navigation_point()?);
+        }
+        Ok(DfCloneSource { name, at })
+    }
+
+    // syntax: "(SNAPSHOT => 'id' | TIMESTAMP => 'ts')"
+    fn parse_navigation_point(&mut self) -> Result<NavigationPoint, ParserError> {
+        self.parser.expect_token(&Token::LParen)?;
+        let point = if self.consume_token("SNAPSHOT") {
+            self.expect_token("=>")?;
+            NavigationPoint::SnapshotID(self.parser.parse_literal_string()?)
+        } else {
+            self.expect_token("TIMESTAMP")?;
+            self.expect_token("=>")?;
+            let ts = self.parser.parse_literal_string()?;
+            match NavigationPoint::parse_time_point(&ts) {
+                Some(time_point) => NavigationPoint::TimePoint(time_point),
+                None => return parser_err!(format!("invalid time point {}", ts)),
+            }
+        };
+        self.parser.expect_token(&Token::RParen)?;
+        Ok(point)
+    }
+
+    // Attach table.
+    pub(crate) fn parse_attach_table(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        // syntax: "attach TABLE t FROM 'uri'"
+        self.expect_token("ATTACH")?;
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let name = self.parser.parse_object_name()?;
+        self.parser.expect_keyword(Keyword::FROM)?;
+        let uri = self.parser.parse_literal_string()?;
+
+        Ok(DfStatement::AttachTable(DfAttachTable {
+            if_not_exists: false,
+            name,
+            uri,
+        }))
+    }
+
     // Drop table.
     pub(crate) fn parse_drop_table(&mut self) -> Result<DfStatement<'a>, ParserError> {
         let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
@@ -127,9 +219,122 @@ impl<'a> DfParser<'a> {
             };
 
             Ok(DfStatement::AlterTable(rename))
+        } else if self
+            .parser
+            .parse_keywords(&[Keyword::RENAME, Keyword::COLUMN])
+        {
+            // syntax: "ALTER TABLE t RENAME COLUMN c TO c2"
+            let old_column = self.parser.parse_identifier()?;
+            self.parser.expect_keyword(Keyword::TO)?;
+            let new_column = self.parser.parse_identifier()?;
+
+            let rename_column = DfAlterTable {
+                if_exists,
+                table_name,
+                action: AlterTableAction::RenameColumn {
+                    old_column,
+                    new_column,
+                },
+            };
+
+            Ok(DfStatement::AlterTable(rename_column))
+        } else if self.consume_token("FLASHBACK") {
+            // syntax: "ALTER TABLE t FLASHBACK TO (SNAPSHOT => 'id' | TIMESTAMP => 'ts')"
+            self.parser.expect_keyword(Keyword::TO)?;
+            let point = self.parse_navigation_point()?;
+
+            let flashback = DfAlterTable {
+                if_exists,
+                table_name,
+                action: AlterTableAction::Flashback(point),
+            };
+
+            Ok(DfStatement::AlterTable(flashback))
+        } else if self.consume_token("RECLUSTER") {
+            // syntax: "ALTER TABLE t RECLUSTER [FINAL] [WHERE expr] [LIMIT n]"
+            let is_final = self.consume_token("FINAL");
+            let selection = if self.parser.parse_keyword(Keyword::WHERE) {
+                Some(self.parser.parse_expr()?)
+            } else {
+                None
+            };
+            let limit = if self.parser.parse_keyword(Keyword::LIMIT) {
+                Some(self.parser.parse_literal_uint()? as usize)
+            } else {
+                None
+            };
+
+            let recluster = DfAlterTable {
+                if_exists,
+                table_name,
+                action: AlterTableAction::Recluster {
+                    is_final,
+                    selection,
+                    limit,
+                },
+            };
+
+            Ok(DfStatement::AlterTable(recluster))
+        } else if self.parser.parse_keyword(Keyword::ADD) {
+            let action = if self.consume_token("ROW") {
+                // syntax: "ALTER TABLE t ADD ROW ACCESS POLICY p ON (c1, c2)"
+                self.expect_token("ACCESS")?;
+                self.expect_token("POLICY")?;
+                let policy = self.parser.parse_literal_string()?;
+                self.parser.expect_keyword(Keyword::ON)?;
+                self.parser.expect_token(&Token::LParen)?;
+                let columns = self
+                    .parser
+                    .parse_comma_separated(|parser| parser.parse_identifier())?;
+                self.parser.expect_token(&Token::RParen)?;
+                AlterTableAction::AddRowAccessPolicy { policy, columns }
+            } else if self.consume_token("VIRTUAL") {
+                // syntax: "ALTER TABLE t ADD VIRTUAL COLUMN v:path"
+                self.parser.expect_keyword(Keyword::COLUMN)?;
+                AlterTableAction::AddVirtualColumn(self.parser.parse_expr()?)
+            } else {
+                // syntax: "ALTER TABLE t ADD [COLUMN] c INT [NULL] [DEFAULT expr]"
+                self.consume_token("COLUMN");
+                let (column_def, generated) = self.parse_column_def()?;
+                if generated.is_some() {
+                    return parser_err!(format!(
+                        "generated column {} can not be added to an existing table",
+                        column_def.name
+                    ));
+                }
+                AlterTableAction::AddColumn(column_def)
+            };
+
+            let add_column = DfAlterTable {
+                if_exists,
+                table_name,
+                action,
+            };
+
+            Ok(DfStatement::AlterTable(add_column))
+        } else if self.parser.parse_keyword(Keyword::DROP) {
+            let action = if self.consume_token("ROW") {
+                // syntax: "ALTER TABLE t DROP ROW ACCESS POLICY p"
+                self.expect_token("ACCESS")?;
+                self.expect_token("POLICY")?;
+                AlterTableAction::DropRowAccessPolicy(self.parser.parse_literal_string()?)
+            } else {
+                // syntax: "ALTER TABLE t DROP [COLUMN] c"
+                self.consume_token("COLUMN");
+                AlterTableAction::DropColumn(self.parser.parse_identifier()?)
+            };
+
+            let drop_column = DfAlterTable {
+                if_exists,
+                table_name,
+                action,
+            };
+
+            Ok(DfStatement::AlterTable(drop_column))
         } else {
             Err(ParserError::ParserError(String::from(
-                "Alter table only support rename for now!",
+                "Alter table only support rename, flashback, recluster, add/drop/rename column, \
+                 add virtual column and add/drop row access policy for now!",
             )))
         }
     }
@@ -180,7 +385,7 @@ impl<'a> DfParser<'a> {
         Ok(DfStatement::DescribeTable(desc))
     }
 
-    fn parse_column_def(&mut self) -> Result<ColumnDef, ParserError> {
+    fn parse_column_def(&mut self) -> Result<(ColumnDef, Option<DfGeneratedColumn>), ParserError> {
         let name = self.parser.parse_identifier()?;
         let data_type = self.parser.parse_data_type()?;
         let collation = if self.parser.parse_keyword(Keyword::COLLATE) {
@@ -189,8 +394,24 @@ impl<'a> DfParser<'a> {
             None
         };
         let mut options = vec![];
+        let mut generated = None;
         loop {
-            if self.parser.parse_keyword(Keyword::CONSTRAINT) {
+            if let Some(expr) = self.parse_optional_generated_expr()? {
+                if generated.is_some() {
+                    return parser_err!(format!(
+                        "multiple generated expressions for column {}",
+                        name
+                    ));
+                }
+                // the generated columns are virtual by default
+                let stored = if self.consume_token("STORED") {
+                    true
+                } else {
+                    self.consume_token("VIRTUAL");
+                    false
+                };
+                generated = Some(DfGeneratedColumn { expr, stored });
+            } else if self.parser.parse_keyword(Keyword::CONSTRAINT) {
                 let name = Some(self.parser.parse_identifier()?);
                 if let Some(option) = self.parser.parse_optional_column_option()? {
                     options.push(ColumnOptionDef { name, option });
@@ -206,20 +427,46 @@ impl<'a> DfParser<'a> {
                 break;
             };
         }
-        Ok(ColumnDef {
+        let column = ColumnDef {
             name,
             data_type,
             collation,
             options,
-        })
+        };
+        Ok((column, generated))
+    }
+
+    // syntax: "[GENERATED ALWAYS] AS (expr)"
+    fn parse_optional_generated_expr(&mut self) -> Result<Option<Expr>, ParserError> {
+        if self.consume_token("GENERATED") {
+            self.expect_token("ALWAYS")?;
+            self.parser.expect_keyword(Keyword::AS)?;
+        } else if !self.parser.parse_keyword(Keyword::AS) {
+            return Ok(None);
+        }
+        self.parser.expect_token(&Token::LParen)?;
+        let expr = self.parser.parse_expr()?;
+        self.parser.expect_token(&Token::RParen)?;
+        Ok(Some(expr))
     }
 
     // This is a copy of the equivalent implementation in sqlparser.
-    fn parse_columns(&mut self) -> Result<(Vec<ColumnDef>, Vec<TableConstraint>), ParserError> {
+    #[allow(clippy::type_complexity)]
+    fn parse_columns(
+        &mut self,
+    ) -> Result<
+        (
+            Vec<ColumnDef>,
+            BTreeMap<String, DfGeneratedColumn>,
+            Vec<TableConstraint>,
+        ),
+        ParserError,
+    > {
         let mut columns = vec![];
+        let mut generated_columns = BTreeMap::new();
         let mut constraints = vec![];
         if !self.parser.consume_token(&Token::LParen) || self.parser.consume_token(&Token::RParen) {
-            return Ok((columns, constraints));
+            return Ok((columns, generated_columns, constraints));
         }
 
         loop {
@@ -228,7 +475,10 @@ impl<'a> DfParser<'a> {
             } else {
                 match self.parser.peek_token() {
                     Token::Word(_) | Token::SingleQuotedString(_) | Token::BackQuotedString(_) => {
-                        let column_def = self.parse_column_def()?;
+                        let (column_def, generated) = self.parse_column_def()?;
+                        if let Some(generated) = generated {
+                            generated_columns.insert(column_def.name.value.clone(), generated);
+                        }
                         columns.push(column_def);
                     }
                     unexpected => {
@@ -248,7 +498,7 @@ impl<'a> DfParser<'a> {
             }
         }
 
-        Ok((columns, constraints))
+        Ok((columns, generated_columns, constraints))
     }
 
     /// Parses the set of valid formats
@@ -0,0 +1,63 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// Borrow from apache/arrow/rust/datafusion/src/sql/sql_parser
+// See notice.md
+
+use sqlparser::ast::Expr;
+use sqlparser::ast::Ident;
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
+
+use crate::sql::statements::DfUpdate;
+use crate::sql::DfParser;
+use crate::sql::DfStatement;
+
+impl<'a> DfParser<'a> {
+    pub(crate) fn parse_update(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        // syntax: "update t SET col = expr [, col = expr ...] [WHERE expr]"
+        self.parser.expect_keyword(Keyword::UPDATE)?;
+        let name = self.parser.parse_object_name()?;
+        let update_list = self.parse_update_list()?;
+
+        let selection = if self.parser.parse_keyword(Keyword::WHERE) {
+            Some(self.parser.parse_expr()?)
+        } else {
+            None
+        };
+
+        Ok(DfStatement::Update(DfUpdate {
+            name,
+            update_list,
+            selection,
+        }))
+    }
+
+    /// Parses "SET col = expr [, col = expr ...]"
+    pub(crate) fn parse_update_list(&mut self) -> Result<Vec<(Ident, Expr)>, ParserError> {
+        self.parser.expect_keyword(Keyword::SET)?;
+
+        let mut update_list = vec![];
+        loop {
+            let column = self.parser.parse_identifier()?;
+            self.parser.expect_token(&Token::Eq)?;
+            update_list.push((column, self.parser.parse_expr()?));
+            if !self.parser.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        Ok(update_list)
+    }
+}
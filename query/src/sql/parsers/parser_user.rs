@@ -130,6 +130,9 @@ impl<'a> DfParser<'a> {
         if self.consume_token("ROLE") {
             return self.parse_grant_role();
         }
+        if let Some(object) = self.parse_share_grant_object()? {
+            return self.parse_grant_share_object(object);
+        }
         self.parse_grant_privilege()
     }
 
@@ -170,6 +173,9 @@ impl<'a> DfParser<'a> {
         if self.consume_token("ROLE") {
             return self.parse_revoke_role();
         }
+        if let Some(object) = self.parse_share_grant_object()? {
+            return self.parse_revoke_share_object(object);
+        }
         self.parse_revoke_privilege()
     }
 
@@ -244,7 +250,16 @@ impl<'a> DfParser<'a> {
 
     /// Parse a possibly qualified, possibly quoted identifier or wild card, e.g.
     /// `*` or `myschema`.*. The sub string pattern like "db0%" is not in planned.
+    /// The stages and the user defined functions are prefixed, e.g. `STAGE s` or `FUNCTION f`.
     fn parse_grant_object(&mut self) -> Result<DfGrantObject, ParserError> {
+        if self.consume_token("STAGE") {
+            let name = self.parser.parse_identifier()?;
+            return Ok(DfGrantObject::Stage(name.value));
+        }
+        if self.parser.parse_keyword(Keyword::FUNCTION) {
+            let name = self.parser.parse_identifier()?;
+            return Ok(DfGrantObject::UDF(name.value));
+        }
         let chunk0 = self.parse_grant_object_pattern_chunk()?;
         // "*" as current db or "table" with current db
         if !self.consume_token(".") {
@@ -288,13 +303,20 @@ impl<'a> DfParser<'a> {
             return Ok(user_options);
         }
         loop {
-            match self.parser.peek_token().to_string().as_str().try_into() {
-                Ok(option) => user_options.push(option),
-                Err(_) => {
-                    return self.expected("user option", self.parser.peek_token());
+            if self.consume_token("NETWORKPOLICY") {
+                // NETWORKPOLICY = 'name'
+                self.parser.expect_token(&Token::Eq)?;
+                let name = self.parser.parse_literal_string()?;
+                user_options.push(DfUserWithOption::NetworkPolicy(name));
+            } else {
+                match self.parser.peek_token().to_string().as_str().try_into() {
+                    Ok(option) => user_options.push(option),
+                    Err(_) => {
+                        return self.expected("user option", self.parser.peek_token());
+                    }
                 }
+                self.parser.next_token();
             }
-            self.parser.next_token();
             if !self.parser.consume_token(&Token::Comma) {
                 break;
             }
@@ -360,6 +382,13 @@ impl<'a> DfParser<'a> {
                     // TODO: uncomment this after sqlparser-rs accepts the SUPER keyword
                     // Keyword::SUPER => privileges.set_privilege(UserPrivilegeType::Super)
                     Keyword::GRANT => privileges.set_privilege(UserPrivilegeType::Grant),
+                    _ if w.value.to_uppercase() == "USAGE" => {
+                        privileges.set_privilege(UserPrivilegeType::Usage)
+                    }
+                    // GRANT OWNERSHIP transfers the ownership of the object to a role
+                    _ if w.value.to_uppercase() == "OWNERSHIP" => {
+                        privileges.set_privilege(UserPrivilegeType::Ownership)
+                    }
                     Keyword::ALL => {
                         privileges.set_all_privileges();
                         // GRANT ALL [PRIVILEGES]
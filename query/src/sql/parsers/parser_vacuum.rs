@@ -0,0 +1,53 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// Borrow from apache/arrow/rust/datafusion/src/sql/sql_parser
+// See notice.md
+
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+
+use crate::sql::statements::DfVacuumTable;
+use crate::sql::DfParser;
+use crate::sql::DfStatement;
+
+impl<'a> DfParser<'a> {
+    pub(crate) fn parse_vacuum(&mut self) -> Result<DfStatement<'a>, ParserError> {
+        // syntax: "vacuum TABLE t [retain n hours] [dry run]"
+        self.expect_token("VACUUM")?;
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let object_name = self.parser.parse_object_name()?;
+
+        let retain_hours = if self.consume_token("RETAIN") {
+            let hours = self.parser.parse_literal_uint()?;
+            self.expect_token("HOURS")?;
+            Some(hours)
+        } else {
+            None
+        };
+
+        let dry_run = if self.consume_token("DRY") {
+            self.expect_token("RUN")?;
+            true
+        } else {
+            false
+        };
+
+        Ok(DfStatement::VacuumTable(DfVacuumTable {
+            name: object_name,
+            retain_hours,
+            dry_run,
+        }))
+    }
+}
@@ -85,6 +85,10 @@ impl Binder {
     ) -> Result<Arc<dyn Table>> {
         // Resolve table with catalog
         let table_meta = self.catalog.get_table(tenant, database, table).await?;
+        self.ctx
+            .get_current_session()
+            .validate_read_privilege(database, table_meta.as_ref())
+            .await?;
         Ok(table_meta)
     }
 }
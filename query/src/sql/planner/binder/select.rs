@@ -16,13 +16,23 @@ use std::sync::Arc;
 
 use async_recursion::async_recursion;
 use common_ast::ast::Expr;
+use common_ast::ast::Literal;
 use common_ast::ast::Query;
+use common_ast::ast::SampleMethod;
 use common_ast::ast::SelectStmt;
 use common_ast::ast::SetExpr;
 use common_ast::ast::TableReference;
+use common_ast::ast::TimeTravelPoint;
+use common_ast::parser::parse_expr;
+use common_ast::parser::tokenize_sql;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_types::RowAccessPolicy;
+use common_meta_types::RowAccessPolicyReference;
 use common_planners::Expression;
+use common_planners::Extras;
+use common_planners::TableSample;
 
 use crate::sql::optimizer::SExpr;
 use crate::sql::planner::binder::scalar::ScalarBinder;
@@ -32,8 +42,10 @@ use crate::sql::planner::binder::ColumnBinding;
 use crate::sql::plans::FilterPlan;
 use crate::sql::plans::LogicalGet;
 use crate::sql::plans::Scalar;
+use crate::sql::statements::query::RowAccessPolicyRewriter;
 use crate::sql::IndexType;
 use crate::sql::ScalarExprRef;
+use crate::storages::NavigationPoint;
 use crate::storages::Table;
 use crate::storages::ToReadDataSourcePlan;
 use crate::table_functions::TableFunction;
@@ -94,6 +106,8 @@ impl Binder {
                 database,
                 table,
                 alias,
+                travel_point,
+                sample,
             } => {
                 let database = database
                     .as_ref()
@@ -105,13 +119,30 @@ impl Binder {
                 let tenant = self.ctx.get_tenant();
 
                 // Resolve table with catalog
-                let table_meta: Arc<dyn Table> = self
+                let mut table_meta: Arc<dyn Table> = self
                     .resolve_data_source(tenant.as_str(), database.as_str(), table.as_str())
                     .await?;
-                let source = table_meta.read_plan(self.ctx.clone(), None).await?;
+                let policy =
+                    RowAccessPolicyRewriter::policy_to_apply(&self.ctx, table_meta.as_ref())
+                        .await?;
+                if let Some(travel_point) = travel_point {
+                    let point = self.resolve_travel_point(travel_point, bind_context)?;
+                    table_meta = table_meta.navigate_to(self.ctx.clone(), &point).await?;
+                }
+                let push_downs = match sample {
+                    Some(sample) => Some(Extras {
+                        sample: Some(Self::resolve_table_sample(sample)?),
+                        ..Extras::default()
+                    }),
+                    None => None,
+                };
+                let source = table_meta.read_plan(self.ctx.clone(), push_downs).await?;
                 let table_index = self.metadata.add_table(database, table_meta, source);
 
                 let mut result = self.bind_base_table(table_index).await?;
+                if let Some((policy, reference)) = policy {
+                    self.bind_row_access_policy(&policy, &reference, &mut result)?;
+                }
                 if let Some(alias) = alias {
                     result.apply_table_alias(&table, alias)?;
                 }
@@ -163,7 +194,11 @@ impl Binder {
                 }
                 Ok(result)
             }
-            _ => todo!(),
+            // the row access policies of the tables read are only applied to the base tables
+            _ => Err(ErrorCode::UnImplement(format!(
+                "Unsupported table reference: {}",
+                stmt
+            ))),
         }
     }
 
@@ -193,6 +228,33 @@ impl Binder {
         Ok(bind_context)
     }
 
+    /// Filters the rows of the table with the body of the row access policy, of which the
+    /// arguments are bound to the columns passed to them.
+    fn bind_row_access_policy(
+        &mut self,
+        policy: &RowAccessPolicy,
+        reference: &RowAccessPolicyReference,
+        bind_context: &mut BindContext,
+    ) -> Result<()> {
+        let mut policy_context = BindContext::create();
+        for (arg, column) in policy.args.iter().zip(reference.columns.iter()) {
+            let mut column_binding = bind_context.resolve_column(None, column.clone())?;
+            column_binding.table_name = None;
+            column_binding.column_name = arg.name().clone();
+            policy_context.add_column_binding(column_binding);
+        }
+
+        let tokens = tokenize_sql(&policy.body)?;
+        let body = parse_expr(&tokens)?;
+        let scalar_binder = ScalarBinder::new();
+        let scalar = scalar_binder.bind_expr(&body, &policy_context)?;
+        let filter_plan = FilterPlan { predicate: scalar };
+        let new_expr =
+            SExpr::create_unary(filter_plan.into(), bind_context.expression.clone().unwrap());
+        bind_context.expression = Some(new_expr);
+        Ok(())
+    }
+
     pub(super) fn bind_where(&mut self, expr: &Expr, bind_context: &mut BindContext) -> Result<()> {
         let scalar_binder = ScalarBinder::new();
         let scalar = scalar_binder.bind_expr(expr, bind_context)?;
@@ -202,4 +264,49 @@ impl Binder {
         bind_context.expression = Some(new_expr);
         Ok(())
     }
+
+    fn resolve_table_sample(sample: &common_ast::ast::TableSample) -> Result<TableSample> {
+        let percent = match &sample.percent {
+            Literal::Number(n) => n.parse::<f64>().ok(),
+            _ => None,
+        };
+        match percent {
+            Some(percent) if (0.0..=100.0).contains(&percent) => Ok(match sample.method {
+                SampleMethod::Block => TableSample::Block(percent),
+                SampleMethod::Row => TableSample::Row(percent),
+            }),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Sample percentage must be a number between 0 and 100, but got: {}",
+                sample.percent
+            ))),
+        }
+    }
+
+    fn resolve_travel_point(
+        &self,
+        travel_point: &TimeTravelPoint,
+        bind_context: &BindContext,
+    ) -> Result<NavigationPoint> {
+        match travel_point {
+            TimeTravelPoint::Snapshot(snapshot_id) => {
+                Ok(NavigationPoint::SnapshotID(snapshot_id.clone()))
+            }
+            TimeTravelPoint::Timestamp(expr) => {
+                let scalar = ScalarBinder::new().bind_expr(expr, bind_context)?;
+                let scalar = scalar.as_any().downcast_ref::<Scalar>().unwrap();
+                let time_point = match scalar {
+                    Scalar::Literal {
+                        data_value: DataValue::String(bytes),
+                    } => NavigationPoint::parse_time_point(&String::from_utf8_lossy(bytes)),
+                    _ => None,
+                };
+                time_point.map(NavigationPoint::TimePoint).ok_or_else(|| {
+                    ErrorCode::BadArguments(format!(
+                        "Time travel point must be a timestamp literal, but got: {}",
+                        expr
+                    ))
+                })
+            }
+        }
+    }
 }
@@ -60,7 +60,8 @@ impl<'a> DfParser<'a> {
     /// Parse the specified tokens with dialect
     pub fn new_with_dialect(sql: &'a str, dialect: &'a dyn Dialect) -> Result<Self, ParserError> {
         let mut tokenizer = Tokenizer::new(dialect, sql);
-        let (tokens, position_map) = tokenizer.tokenize()?;
+        let (mut tokens, position_map) = tokenizer.tokenize()?;
+        Self::rewrite_positional_columns(&mut tokens);
 
         Ok(DfParser {
             sql,
@@ -68,6 +69,20 @@ impl<'a> DfParser<'a> {
         })
     }
 
+    // `$1`, the column of the staged files by its position, is an identifier of any dialect.
+    // The number is replaced by a whitespace, so the tokens keep their positions.
+    fn rewrite_positional_columns(tokens: &mut [Token]) {
+        for i in 1..tokens.len() {
+            if let (Token::Char('$'), Token::Number(n, false)) = (&tokens[i - 1], &tokens[i]) {
+                if n.bytes().all(|b| b.is_ascii_digit()) {
+                    let column = format!("${}", n);
+                    tokens[i - 1] = Token::make_word(&column, None);
+                    tokens[i] = Token::Whitespace(Whitespace::Space);
+                }
+            }
+        }
+    }
+
     /// Parse a SQL statement and produce a set of statements with dialect
     pub fn parse_sql(
         sql: &'a str,
@@ -172,10 +187,13 @@ impl<'a> DfParser<'a> {
                         self.parser.next_token();
                         self.parse_show()
                     }
+                    Keyword::ANALYZE => self.parse_analyze(),
                     Keyword::TRUNCATE => self.parse_truncate(),
                     Keyword::RENAME => self.parse_rename(),
                     Keyword::SET => self.parse_set(),
                     Keyword::INSERT => self.parse_insert(),
+                    Keyword::DELETE => self.parse_delete(),
+                    Keyword::UPDATE => self.parse_update(),
                     Keyword::SELECT | Keyword::WITH | Keyword::VALUES => self.parse_query(),
                     Keyword::GRANT => {
                         self.parser.next_token();
@@ -201,11 +219,17 @@ impl<'a> DfParser<'a> {
                         self.parse_list_cmd()
                     }
 
+                    // ATTACH, MERGE, BACKUP and RESTORE are not keywords of every dialect
+                    _ if w.value.to_uppercase() == "ATTACH" => self.parse_attach_table(),
+                    _ if w.value.to_uppercase() == "MERGE" => self.parse_merge(),
+                    _ if w.value.to_uppercase() == "BACKUP" => self.parse_backup(),
+                    _ if w.value.to_uppercase() == "RESTORE" => self.parse_restore(),
                     Keyword::NoKeyword => match w.value.to_uppercase().as_str() {
                         // Use database
                         "USE" => self.parse_use_database(),
                         "KILL" => self.parse_kill_query(),
                         "OPTIMIZE" => self.parse_optimize(),
+                        "VACUUM" => self.parse_vacuum(),
                         _ => self.expected("Keyword", self.parser.peek_token()),
                     },
                     _ => self.expected("an SQL statement", Token::Word(w)),
@@ -228,6 +252,15 @@ impl<'a> DfParser<'a> {
                     Keyword::FUNCTION => self.parse_create_udf(),
                     Keyword::STAGE => self.parse_create_stage(),
                     Keyword::VIEW => self.parse_create_view(),
+                    _ if w.value.to_uppercase() == "STREAM" => self.parse_create_stream(),
+                    _ if w.value.to_uppercase() == "PIPE" => self.parse_create_pipe(),
+                    _ if w.value.to_uppercase() == "ROW" => self.parse_create_row_access_policy(),
+                    _ if w.value.to_uppercase() == "NETWORK" => self.parse_create_network_policy(),
+                    _ if w.value.to_uppercase() == "CONNECTION" => self.parse_create_connection(),
+                    _ if w.value.to_uppercase() == "SHARE" => self.parse_create_share(),
+                    _ if w.value.to_uppercase() == "AGGREGATING" => {
+                        self.parse_create_aggregating_index()
+                    }
                     _ => self.expected("create statement", Token::Word(w)),
                 }
             }
@@ -291,6 +324,8 @@ impl<'a> DfParser<'a> {
                 Keyword::FUNCTION => self.parse_alter_udf(),
                 Keyword::TABLE => self.parse_alter_table(),
                 Keyword::VIEW => self.parse_alter_view(),
+                _ if w.value.to_uppercase() == "SHARE" => self.parse_alter_share(),
+                _ if w.value.to_uppercase() == "CONNECTION" => self.parse_alter_connection(),
                 _ => self.expected("keyword USER or FUNCTION", Token::Word(w)),
             },
             unexpected => self.expected("alter statement", unexpected),
@@ -328,6 +363,13 @@ impl<'a> DfParser<'a> {
                 Keyword::FUNCTION => self.parse_drop_udf(),
                 Keyword::STAGE => self.parse_drop_stage(),
                 Keyword::VIEW => self.parse_drop_view(),
+                // a stream is dropped just like a table
+                _ if w.value.to_uppercase() == "STREAM" => self.parse_drop_table(),
+                _ if w.value.to_uppercase() == "SHARE" => self.parse_drop_share(),
+                _ if w.value.to_uppercase() == "PIPE" => self.parse_drop_pipe(),
+                _ if w.value.to_uppercase() == "ROW" => self.parse_drop_row_access_policy(),
+                _ if w.value.to_uppercase() == "NETWORK" => self.parse_drop_network_policy(),
+                _ if w.value.to_uppercase() == "CONNECTION" => self.parse_drop_connection(),
                 _ => self.expected("drop statement", Token::Word(w)),
             },
             unexpected => self.expected("drop statement", unexpected),
@@ -356,6 +398,12 @@ impl<'a> DfParser<'a> {
             Ok(DfStatement::ShowUsers(DfShowUsers))
         } else if self.consume_token("ROLES") {
             Ok(DfStatement::ShowRoles(DfShowRoles))
+        } else if self.consume_token("ROW") {
+            self.parse_show_row_access_policies()
+        } else if self.consume_token("NETWORK") {
+            self.parse_show_network_policies()
+        } else if self.consume_token("CONNECTIONS") {
+            self.parse_show_connections()
         } else if self.consume_token("GRANTS") {
             self.parse_show_grants()
         } else if self.consume_token("FUNCTIONS") {
@@ -29,30 +29,54 @@ use super::statements::DfDropView;
 use super::statements::DfGrantRoleStatement;
 use super::statements::DfList;
 use super::statements::DfRevokeRoleStatement;
+use crate::sql::statements::DfAlterConnection;
+use crate::sql::statements::DfAlterShareTenants;
 use crate::sql::statements::DfAlterTable;
 use crate::sql::statements::DfAlterUDF;
 use crate::sql::statements::DfAlterUser;
+use crate::sql::statements::DfAnalyzeTable;
+use crate::sql::statements::DfAttachTable;
+use crate::sql::statements::DfBackupDatabase;
+use crate::sql::statements::DfCreateAggregatingIndex;
+use crate::sql::statements::DfCreateConnection;
 use crate::sql::statements::DfCreateDatabase;
+use crate::sql::statements::DfCreateNetworkPolicy;
+use crate::sql::statements::DfCreatePipe;
 use crate::sql::statements::DfCreateRole;
+use crate::sql::statements::DfCreateRowAccessPolicy;
+use crate::sql::statements::DfCreateShare;
+use crate::sql::statements::DfCreateStream;
 use crate::sql::statements::DfCreateTable;
 use crate::sql::statements::DfCreateUDF;
 use crate::sql::statements::DfCreateUser;
 use crate::sql::statements::DfCreateView;
+use crate::sql::statements::DfDelete;
 use crate::sql::statements::DfDescribeTable;
+use crate::sql::statements::DfDropConnection;
 use crate::sql::statements::DfDropDatabase;
+use crate::sql::statements::DfDropNetworkPolicy;
+use crate::sql::statements::DfDropPipe;
 use crate::sql::statements::DfDropRole;
+use crate::sql::statements::DfDropRowAccessPolicy;
+use crate::sql::statements::DfDropShare;
 use crate::sql::statements::DfDropTable;
 use crate::sql::statements::DfDropUDF;
 use crate::sql::statements::DfDropUser;
 use crate::sql::statements::DfExplain;
 use crate::sql::statements::DfGrantPrivilegeStatement;
+use crate::sql::statements::DfGrantShareObject;
+use crate::sql::statements::DfInsertMultiTable;
 use crate::sql::statements::DfInsertStatement;
 use crate::sql::statements::DfKillStatement;
+use crate::sql::statements::DfMergeInto;
 use crate::sql::statements::DfOptimizeTable;
 use crate::sql::statements::DfQueryStatement;
 use crate::sql::statements::DfRenameTable;
+use crate::sql::statements::DfRestoreDatabase;
 use crate::sql::statements::DfRevokePrivilegeStatement;
+use crate::sql::statements::DfRevokeShareObject;
 use crate::sql::statements::DfSetVariable;
+use crate::sql::statements::DfShowConnections;
 use crate::sql::statements::DfShowCreateDatabase;
 use crate::sql::statements::DfShowCreateTable;
 use crate::sql::statements::DfShowDatabases;
@@ -60,14 +84,18 @@ use crate::sql::statements::DfShowEngines;
 use crate::sql::statements::DfShowFunctions;
 use crate::sql::statements::DfShowGrants;
 use crate::sql::statements::DfShowMetrics;
+use crate::sql::statements::DfShowNetworkPolicies;
 use crate::sql::statements::DfShowProcessList;
 use crate::sql::statements::DfShowRoles;
+use crate::sql::statements::DfShowRowAccessPolicies;
 use crate::sql::statements::DfShowSettings;
 use crate::sql::statements::DfShowTabStat;
 use crate::sql::statements::DfShowTables;
 use crate::sql::statements::DfShowUsers;
 use crate::sql::statements::DfTruncateTable;
+use crate::sql::statements::DfUpdate;
 use crate::sql::statements::DfUseDatabase;
+use crate::sql::statements::DfVacuumTable;
 
 /// Tokens parsed by `DFParser` are converted into these values.
 #[derive(Debug, Clone, PartialEq)]
@@ -81,6 +109,8 @@ pub enum DfStatement<'a> {
     ShowCreateDatabase(DfShowCreateDatabase),
     CreateDatabase(DfCreateDatabase),
     DropDatabase(DfDropDatabase),
+    BackupDatabase(DfBackupDatabase),
+    RestoreDatabase(DfRestoreDatabase),
     UseDatabase(DfUseDatabase),
 
     // Tables.
@@ -88,11 +118,14 @@ pub enum DfStatement<'a> {
     ShowCreateTable(DfShowCreateTable),
     ShowTabStat(DfShowTabStat),
     CreateTable(DfCreateTable),
+    AttachTable(DfAttachTable),
     DescribeTable(DfDescribeTable),
     DropTable(DfDropTable),
     AlterTable(DfAlterTable),
     TruncateTable(DfTruncateTable),
     OptimizeTable(DfOptimizeTable),
+    VacuumTable(DfVacuumTable),
+    AnalyzeTable(DfAnalyzeTable),
     RenameTable(DfRenameTable),
 
     // Views.
@@ -101,6 +134,12 @@ pub enum DfStatement<'a> {
     AlterView(DfAlterView),
     DropView(DfDropView),
 
+    // Streams.
+    CreateStream(DfCreateStream),
+
+    // Aggregating indexes.
+    CreateAggregatingIndex(DfCreateAggregatingIndex),
+
     // Settings.
     ShowSettings(DfShowSettings),
 
@@ -121,6 +160,16 @@ pub enum DfStatement<'a> {
 
     // Insert
     InsertQuery(DfInsertStatement<'a>),
+    InsertMultiTable(DfInsertMultiTable),
+
+    // Delete
+    Delete(DfDelete),
+
+    // Update
+    Update(DfUpdate),
+
+    // Merge
+    MergeInto(DfMergeInto),
 
     // User
     CreateUser(DfCreateUser),
@@ -142,6 +191,26 @@ pub enum DfStatement<'a> {
     DescribeStage(DfDescribeUserStage),
     List(DfList),
 
+    // Pipe
+    CreatePipe(DfCreatePipe),
+    DropPipe(DfDropPipe),
+
+    // Row access policy
+    CreateRowAccessPolicy(DfCreateRowAccessPolicy),
+    DropRowAccessPolicy(DfDropRowAccessPolicy),
+    ShowRowAccessPolicies(DfShowRowAccessPolicies),
+
+    // Network policy
+    CreateNetworkPolicy(DfCreateNetworkPolicy),
+    DropNetworkPolicy(DfDropNetworkPolicy),
+    ShowNetworkPolicies(DfShowNetworkPolicies),
+
+    // Connection
+    CreateConnection(DfCreateConnection),
+    AlterConnection(DfAlterConnection),
+    DropConnection(DfDropConnection),
+    ShowConnections(DfShowConnections),
+
     // Call
     Call(DfCall),
 
@@ -159,6 +228,13 @@ pub enum DfStatement<'a> {
     DropUDF(DfDropUDF),
     AlterUDF(DfAlterUDF),
 
+    // Share
+    CreateShare(DfCreateShare),
+    DropShare(DfDropShare),
+    GrantShareObject(DfGrantShareObject),
+    RevokeShareObject(DfRevokeShareObject),
+    AlterShareTenants(DfAlterShareTenants),
+
     // Engine
     ShowEngines(DfShowEngines),
 }
@@ -27,6 +27,8 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::aggregates::AggregateFunctionFactory;
 use common_functions::is_builtin_function;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
 use common_planners::Expression;
 use sqlparser::ast::DateTimeField;
 use sqlparser::ast::Expr;
@@ -664,6 +666,13 @@ impl UDFFetcher for ExprRPNBuilder {
             .get_user_manager()
             .get_udf(&tenant, name)
             .await?;
+        self.context
+            .get_current_session()
+            .validate_privilege(
+                &GrantObject::UDF(udf.name.clone()),
+                UserPrivilegeType::Usage,
+            )
+            .await?;
         let mut udf_parser = UDFParser::default();
         let definition = udf_parser
             .parse(&udf.name, &udf.parameters, &udf.definition)
@@ -146,13 +146,18 @@ impl<'a> AnalyzableStatement for DfStatement<'a> {
             DfStatement::ShowCreateDatabase(v) => v.analyze(ctx).await,
             DfStatement::CreateDatabase(v) => v.analyze(ctx).await,
             DfStatement::DropDatabase(v) => v.analyze(ctx).await,
+            DfStatement::BackupDatabase(v) => v.analyze(ctx).await,
+            DfStatement::RestoreDatabase(v) => v.analyze(ctx).await,
             DfStatement::CreateTable(v) => v.analyze(ctx).await,
+            DfStatement::AttachTable(v) => v.analyze(ctx).await,
             DfStatement::DescribeTable(v) => v.analyze(ctx).await,
             DfStatement::DropTable(v) => v.analyze(ctx).await,
             DfStatement::AlterTable(v) => v.analyze(ctx).await,
             DfStatement::RenameTable(v) => v.analyze(ctx).await,
             DfStatement::TruncateTable(v) => v.analyze(ctx).await,
             DfStatement::OptimizeTable(v) => v.analyze(ctx).await,
+            DfStatement::VacuumTable(v) => v.analyze(ctx).await,
+            DfStatement::AnalyzeTable(v) => v.analyze(ctx).await,
             DfStatement::UseDatabase(v) => v.analyze(ctx).await,
             DfStatement::ShowCreateTable(v) => v.analyze(ctx).await,
             DfStatement::ShowTables(v) => v.analyze(ctx).await,
@@ -163,6 +168,10 @@ impl<'a> AnalyzableStatement for DfStatement<'a> {
             DfStatement::ShowGrants(v) => v.analyze(ctx).await,
             DfStatement::KillStatement(v) => v.analyze(ctx).await,
             DfStatement::InsertQuery(v) => v.analyze(ctx).await,
+            DfStatement::InsertMultiTable(v) => v.analyze(ctx).await,
+            DfStatement::Delete(v) => v.analyze(ctx).await,
+            DfStatement::Update(v) => v.analyze(ctx).await,
+            DfStatement::MergeInto(v) => v.analyze(ctx).await,
             DfStatement::SetVariable(v) => v.analyze(ctx).await,
             DfStatement::CreateUser(v) => v.analyze(ctx).await,
             DfStatement::AlterUser(v) => v.analyze(ctx).await,
@@ -178,16 +187,35 @@ impl<'a> AnalyzableStatement for DfStatement<'a> {
             DfStatement::CreateUDF(v) => v.analyze(ctx).await,
             DfStatement::DropUDF(v) => v.analyze(ctx).await,
             DfStatement::AlterUDF(v) => v.analyze(ctx).await,
+            DfStatement::CreateShare(v) => v.analyze(ctx).await,
+            DfStatement::DropShare(v) => v.analyze(ctx).await,
+            DfStatement::GrantShareObject(v) => v.analyze(ctx).await,
+            DfStatement::RevokeShareObject(v) => v.analyze(ctx).await,
+            DfStatement::AlterShareTenants(v) => v.analyze(ctx).await,
             DfStatement::CreateRole(v) => v.analyze(ctx).await,
             DfStatement::DropRole(v) => v.analyze(ctx).await,
             DfStatement::ShowEngines(v) => v.analyze(ctx).await,
             DfStatement::CreateStage(v) => v.analyze(ctx).await,
             DfStatement::DropStage(v) => v.analyze(ctx).await,
             DfStatement::DescribeStage(v) => v.analyze(ctx).await,
+            DfStatement::CreatePipe(v) => v.analyze(ctx).await,
+            DfStatement::DropPipe(v) => v.analyze(ctx).await,
+            DfStatement::CreateRowAccessPolicy(v) => v.analyze(ctx).await,
+            DfStatement::DropRowAccessPolicy(v) => v.analyze(ctx).await,
+            DfStatement::ShowRowAccessPolicies(v) => v.analyze(ctx).await,
+            DfStatement::CreateNetworkPolicy(v) => v.analyze(ctx).await,
+            DfStatement::DropNetworkPolicy(v) => v.analyze(ctx).await,
+            DfStatement::ShowNetworkPolicies(v) => v.analyze(ctx).await,
+            DfStatement::CreateConnection(v) => v.analyze(ctx).await,
+            DfStatement::AlterConnection(v) => v.analyze(ctx).await,
+            DfStatement::DropConnection(v) => v.analyze(ctx).await,
+            DfStatement::ShowConnections(v) => v.analyze(ctx).await,
             DfStatement::List(v) => v.analyze(ctx).await,
             DfStatement::CreateView(v) => v.analyze(ctx).await,
             DfStatement::AlterView(v) => v.analyze(ctx).await,
             DfStatement::DropView(v) => v.analyze(ctx).await,
+            DfStatement::CreateStream(v) => v.analyze(ctx).await,
+            DfStatement::CreateAggregatingIndex(v) => v.analyze(ctx).await,
             DfStatement::ShowTabStat(v) => v.analyze(ctx).await,
         }
     }
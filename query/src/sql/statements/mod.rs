@@ -17,24 +17,42 @@ pub mod query;
 mod analyzer_expr;
 mod analyzer_statement;
 mod analyzer_value_expr;
+mod statement_alter_connection;
+mod statement_alter_share;
 mod statement_alter_table;
 mod statement_alter_udf;
 mod statement_alter_user;
 mod statement_alter_view;
+mod statement_analyze_table;
+mod statement_attach_table;
+mod statement_backup_database;
 mod statement_call;
 mod statement_common;
 mod statement_copy;
+mod statement_create_aggregating_index;
+mod statement_create_connection;
 mod statement_create_database;
+mod statement_create_network_policy;
+mod statement_create_pipe;
 mod statement_create_role;
+mod statement_create_row_access_policy;
+mod statement_create_share;
+mod statement_create_stream;
 mod statement_create_table;
 mod statement_create_udf;
 mod statement_create_user;
 mod statement_create_user_stage;
 mod statement_create_view;
+mod statement_delete;
 mod statement_describe_table;
 mod statement_describe_user_stage;
+mod statement_drop_connection;
 mod statement_drop_database;
+mod statement_drop_network_policy;
+mod statement_drop_pipe;
 mod statement_drop_role;
+mod statement_drop_row_access_policy;
+mod statement_drop_share;
 mod statement_drop_table;
 mod statement_drop_udf;
 mod statement_drop_user;
@@ -42,15 +60,21 @@ mod statement_drop_user_stage;
 mod statement_drop_view;
 mod statement_explain;
 mod statement_grant;
+mod statement_grant_share;
 mod statement_insert;
+mod statement_insert_multi_table;
 mod statement_kill;
 mod statement_list;
+mod statement_merge;
 mod statement_optimize_table;
 mod statement_rename_table;
+mod statement_restore_database;
 mod statement_revoke;
+mod statement_revoke_share;
 mod statement_select;
 mod statement_select_convert;
 mod statement_set_variable;
+mod statement_show_connections;
 mod statement_show_create_database;
 mod statement_show_create_table;
 mod statement_show_databases;
@@ -59,14 +83,18 @@ mod statement_show_functions;
 mod statement_show_grants;
 mod statement_show_kind;
 mod statement_show_metrics;
+mod statement_show_network_policies;
 mod statement_show_processlist;
 mod statement_show_roles;
+mod statement_show_row_access_policies;
 mod statement_show_settings;
 mod statement_show_tab_stat;
 mod statement_show_tables;
 mod statement_show_users;
 mod statement_truncate_table;
+mod statement_update;
 mod statement_use_database;
+mod statement_vacuum_table;
 mod value_source;
 
 pub use analyzer_expr::ExpressionAnalyzer;
@@ -75,27 +103,47 @@ pub use analyzer_statement::AnalyzedResult;
 pub use analyzer_statement::QueryAnalyzeState;
 pub use analyzer_statement::QueryRelation;
 pub use query::QueryASTIR;
+pub use statement_alter_connection::DfAlterConnection;
+pub use statement_alter_share::DfAlterShareTenants;
 pub use statement_alter_table::AlterTableAction;
 pub use statement_alter_table::DfAlterTable;
 pub use statement_alter_udf::DfAlterUDF;
 pub use statement_alter_user::DfAlterUser;
 pub use statement_alter_view::DfAlterView;
+pub use statement_analyze_table::DfAnalyzeTable;
+pub use statement_attach_table::DfAttachTable;
+pub use statement_backup_database::DfBackupDatabase;
 pub use statement_call::DfCall;
 pub use statement_common::*;
 pub use statement_copy::*;
+pub use statement_create_aggregating_index::DfCreateAggregatingIndex;
+pub use statement_create_connection::DfCreateConnection;
 pub use statement_create_database::DfCreateDatabase;
+pub use statement_create_network_policy::DfCreateNetworkPolicy;
+pub use statement_create_pipe::DfCreatePipe;
 pub use statement_create_role::DfCreateRole;
+pub use statement_create_row_access_policy::DfCreateRowAccessPolicy;
+pub use statement_create_share::DfCreateShare;
+pub use statement_create_stream::DfCreateStream;
+pub use statement_create_table::DfCloneSource;
 pub use statement_create_table::DfCreateTable;
+pub use statement_create_table::DfGeneratedColumn;
 pub use statement_create_udf::DfCreateUDF;
 pub use statement_create_user::DfAuthOption;
 pub use statement_create_user::DfCreateUser;
 pub use statement_create_user::DfUserWithOption;
 pub use statement_create_user_stage::DfCreateUserStage;
 pub use statement_create_view::DfCreateView;
+pub use statement_delete::DfDelete;
 pub use statement_describe_table::DfDescribeTable;
 pub use statement_describe_user_stage::DfDescribeUserStage;
+pub use statement_drop_connection::DfDropConnection;
 pub use statement_drop_database::DfDropDatabase;
+pub use statement_drop_network_policy::DfDropNetworkPolicy;
+pub use statement_drop_pipe::DfDropPipe;
 pub use statement_drop_role::DfDropRole;
+pub use statement_drop_row_access_policy::DfDropRowAccessPolicy;
+pub use statement_drop_share::DfDropShare;
 pub use statement_drop_table::DfDropTable;
 pub use statement_drop_udf::DfDropUDF;
 pub use statement_drop_user::DfDropUser;
@@ -105,16 +153,27 @@ pub use statement_explain::DfExplain;
 pub use statement_grant::DfGrantObject;
 pub use statement_grant::DfGrantPrivilegeStatement;
 pub use statement_grant::DfGrantRoleStatement;
+pub use statement_grant_share::DfGrantShareObject;
+pub use statement_grant_share::DfShareGrantObject;
 pub use statement_insert::DfInsertStatement;
 pub use statement_insert::InsertSource;
+pub use statement_insert_multi_table::DfInsertMultiTable;
+pub use statement_insert_multi_table::DfInsertMultiTableBranch;
+pub use statement_insert_multi_table::DfInsertMultiTableInto;
 pub use statement_kill::DfKillStatement;
 pub use statement_list::DfList;
+pub use statement_merge::DfMergeClause;
+pub use statement_merge::DfMergeInto;
+pub use statement_merge::DfMergeSource;
 pub use statement_optimize_table::DfOptimizeTable;
 pub use statement_rename_table::DfRenameTable;
+pub use statement_restore_database::DfRestoreDatabase;
 pub use statement_revoke::DfRevokePrivilegeStatement;
 pub use statement_revoke::DfRevokeRoleStatement;
+pub use statement_revoke_share::DfRevokeShareObject;
 pub use statement_select::DfQueryStatement;
 pub use statement_set_variable::DfSetVariable;
+pub use statement_show_connections::DfShowConnections;
 pub use statement_show_create_database::DfShowCreateDatabase;
 pub use statement_show_create_table::DfShowCreateTable;
 pub use statement_show_databases::DfShowDatabases;
@@ -123,12 +182,16 @@ pub use statement_show_functions::DfShowFunctions;
 pub use statement_show_grants::DfShowGrants;
 pub use statement_show_kind::DfShowKind;
 pub use statement_show_metrics::DfShowMetrics;
+pub use statement_show_network_policies::DfShowNetworkPolicies;
 pub use statement_show_processlist::DfShowProcessList;
 pub use statement_show_roles::DfShowRoles;
+pub use statement_show_row_access_policies::DfShowRowAccessPolicies;
 pub use statement_show_settings::DfShowSettings;
 pub use statement_show_tab_stat::DfShowTabStat;
 pub use statement_show_tables::DfShowTables;
 pub use statement_show_users::DfShowUsers;
 pub use statement_truncate_table::DfTruncateTable;
+pub use statement_update::DfUpdate;
 pub use statement_use_database::DfUseDatabase;
+pub use statement_vacuum_table::DfVacuumTable;
 pub use value_source::ValueSource;
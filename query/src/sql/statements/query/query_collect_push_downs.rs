@@ -85,6 +85,7 @@ impl QueryCollectPushDowns {
                 filters: self.require_filters.clone(),
                 limit,
                 order_by,
+                sample: None,
             });
         }
 
@@ -20,6 +20,7 @@ use common_planners::Expression;
 
 use crate::sessions::QueryContext;
 use crate::sql::statements::query::query_ast_ir::QueryASTIRVisitor;
+use crate::sql::statements::query::query_schema_joined::JoinedColumnDesc;
 use crate::sql::statements::query::query_schema_joined::JoinedTableDesc;
 use crate::sql::statements::query::JoinedSchema;
 use crate::sql::statements::query::QueryASTIR;
@@ -58,8 +59,20 @@ impl QueryASTIRVisitor<QualifiedRewriter> for QualifiedRewriter {
             match projection_expr {
                 Expression::Wildcard => Self::expand_wildcard(data, &mut new_exprs),
                 _ => {
+                    let column_name = match projection_expr {
+                        Expression::Column(name) => Some(name.clone()),
+                        Expression::QualifiedColumn(names) => names.last().cloned(),
+                        _ => None,
+                    };
                     Self::visit_recursive_expr(projection_expr, data)?;
-                    new_exprs.push(projection_expr.clone());
+                    match column_name {
+                        // The virtual generated column is expanded to its expression.
+                        Some(name) if !matches!(projection_expr, Expression::Column(_)) => {
+                            let expr = Box::new(projection_expr.clone());
+                            new_exprs.push(Expression::Alias(name, expr));
+                        }
+                        _ => new_exprs.push(projection_expr.clone()),
+                    }
                 }
             }
         }
@@ -86,6 +99,10 @@ impl QualifiedRewriter {
         for table_desc in self.tables_schema.get_tables_desc() {
             for column_desc in table_desc.get_columns_desc() {
                 let name = column_desc.short_name.clone();
+                if let Some(expr) = &column_desc.virtual_expr {
+                    columns_expression.push(Expression::Alias(name, Box::new(expr.clone())));
+                    continue;
+                }
                 match column_desc.is_ambiguity {
                     true => {
                         let prefix = table_desc.get_name_parts().join(".");
@@ -98,9 +115,13 @@ impl QualifiedRewriter {
     }
 
     fn rewrite_column(&self, name: &str) -> Result<Expression> {
-        match self.tables_schema.contains_column(name) {
-            true => Ok(Expression::Column(name.to_string())),
-            false => Err(ErrorCode::UnknownColumn(format!("Unknown column {}", name))),
+        match self.tables_schema.get_column(name) {
+            Some(JoinedColumnDesc {
+                virtual_expr: Some(expr),
+                ..
+            }) => Ok(expr.clone()),
+            Some(_) => Ok(Expression::Column(name.to_string())),
+            None => Err(ErrorCode::UnknownColumn(format!("Unknown column {}", name))),
         }
     }
 
@@ -127,6 +148,9 @@ impl QualifiedRewriter {
         let name_parts = table_desc.get_name_parts();
         for column_desc in table_desc.get_columns_desc() {
             if column_desc.short_name == name {
+                if let Some(expr) = &column_desc.virtual_expr {
+                    return Ok(expr.clone());
+                }
                 return match column_desc.is_ambiguity {
                     true => Ok(Expression::Column(format!(
                         "{}.{}",
@@ -0,0 +1,149 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_ast::udfs::UDFParser;
+use common_ast::udfs::UDFTransformer;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::RowAccessPolicy;
+use common_meta_types::RowAccessPolicyReference;
+use common_meta_types::TABLE_OPT_KEY_ROW_ACCESS_POLICY;
+use sqlparser::ast::BinaryOperator;
+use sqlparser::ast::Expr;
+use sqlparser::ast::Ident;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::query::JoinedSchema;
+use crate::sql::statements::query::JoinedTableDesc;
+use crate::sql::statements::DfQueryStatement;
+use crate::storages::stream::StreamTable;
+use crate::storages::Table;
+
+pub struct RowAccessPolicyRewriter;
+
+impl RowAccessPolicyRewriter {
+    /// Filters the rows the query reads from each table with the row access policy attached to
+    /// it, unless one of the roles of the current user is exempt from the policy.
+    ///
+    /// The subqueries read from are rewritten on their own, when they are analyzed.
+    pub async fn rewrite(
+        ctx: Arc<QueryContext>,
+        joined_schema: &JoinedSchema,
+        query: &mut DfQueryStatement,
+    ) -> Result<()> {
+        for table_desc in joined_schema.get_tables_desc() {
+            if let JoinedTableDesc::Table {
+                table, name_parts, ..
+            } = table_desc
+            {
+                if let Some((policy, reference)) =
+                    Self::policy_to_apply(&ctx, table.as_ref()).await?
+                {
+                    Self::apply_policy(&policy, &reference, name_parts, query).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn apply_policy(
+        policy: &RowAccessPolicy,
+        reference: &RowAccessPolicyReference,
+        name_parts: &[String],
+        query: &mut DfQueryStatement,
+    ) -> Result<()> {
+        let params = policy
+            .args
+            .iter()
+            .map(|arg| arg.name().clone())
+            .collect::<Vec<_>>();
+        let body = UDFParser::default()
+            .parse(&policy.name, &params, &policy.body)
+            .await?;
+        let filter = UDFTransformer::clone_expr_with_replacement(&body, &|expr| {
+            if let Expr::Identifier(Ident { value, .. }) = expr {
+                if let Some(pos) = params.iter().position(|param| param == value) {
+                    let mut idents = name_parts.iter().map(Ident::new).collect::<Vec<_>>();
+                    idents.push(Ident::new(&reference.columns[pos]));
+                    return Ok(Some(Expr::CompoundIdentifier(idents)));
+                }
+            }
+
+            Ok(None)
+        })?;
+
+        query.selection = Some(match query.selection.take() {
+            None => filter,
+            Some(selection) => Expr::BinaryOp {
+                left: Box::new(Expr::Nested(Box::new(selection))),
+                op: BinaryOperator::And,
+                right: Box::new(Expr::Nested(Box::new(filter))),
+            },
+        });
+        Ok(())
+    }
+
+    /// The row access policy attached to the table, with the columns passed to it, None if
+    /// there is no policy, or one of the roles of the current user is exempt from it.
+    ///
+    /// The rows of a stream are the ones of the table it is created on, and so is the policy.
+    pub async fn policy_to_apply(
+        ctx: &Arc<QueryContext>,
+        table: &dyn Table,
+    ) -> Result<Option<(RowAccessPolicy, RowAccessPolicyReference)>> {
+        let source_table;
+        let table = match table.as_any().downcast_ref::<StreamTable>() {
+            None => table,
+            Some(stream) => {
+                source_table = stream.source_table(ctx.as_ref()).await?;
+                source_table.as_ref()
+            }
+        };
+        let options = table.get_table_info().options();
+        let reference = match options.get(TABLE_OPT_KEY_ROW_ACCESS_POLICY) {
+            None => return Ok(None),
+            Some(v) => serde_json::from_str::<RowAccessPolicyReference>(v)?,
+        };
+
+        // The query fails if the policy was dropped while still attached, rather than
+        // returning all the rows.
+        let tenant = ctx.get_tenant();
+        let policy = ctx
+            .get_user_manager()
+            .get_row_access_policy(&tenant, &reference.policy)
+            .await?;
+        if policy.args.len() != reference.columns.len() {
+            return Err(ErrorCode::IllegalRowAccessPolicy(format!(
+                "Row access policy {} requires {} columns, but table {} passes {}",
+                policy.name,
+                policy.args.len(),
+                table.name(),
+                reference.columns.len()
+            )));
+        }
+
+        let exempt = ctx
+            .get_current_session()
+            .get_current_roles()
+            .await?
+            .iter()
+            .any(|role| policy.exempt_roles.contains(&role.name));
+        match exempt {
+            true => Ok(None),
+            false => Ok(Some((policy, reference))),
+        }
+    }
+}
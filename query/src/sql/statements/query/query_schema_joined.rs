@@ -21,6 +21,7 @@ use std::sync::Arc;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::Expression;
 use common_planners::Extras;
 
 use crate::sql::statements::QueryAnalyzeState;
@@ -43,7 +44,7 @@ impl JoinedSchema {
     }
 
     pub fn from_table(table: Arc<dyn Table>, prefix: Vec<String>) -> Result<JoinedSchema> {
-        let table_desc = JoinedTableDesc::from_table(table, prefix);
+        let table_desc = JoinedTableDesc::from_table(table, prefix)?;
         Self::from_table_desc(table_desc)
     }
 
@@ -82,6 +83,10 @@ impl JoinedSchema {
         self.short_name_columns.contains_key(column_name)
     }
 
+    pub fn get_column(&self, column_name: &str) -> Option<&JoinedColumnDesc> {
+        self.short_name_columns.get(column_name)
+    }
+
     pub fn get_tables_desc(&self) -> &[JoinedTableDesc] {
         &self.tables_long_name_columns
     }
@@ -174,20 +179,31 @@ pub enum JoinedTableDesc {
 }
 
 impl JoinedTableDesc {
-    pub fn from_table(table: Arc<dyn Table>, prefix: Vec<String>) -> JoinedTableDesc {
+    pub fn from_table(table: Arc<dyn Table>, prefix: Vec<String>) -> Result<JoinedTableDesc> {
         let schema = table.schema();
         let mut columns_desc = Vec::with_capacity(schema.fields().len());
 
         for data_field in schema.fields() {
-            columns_desc.push(JoinedColumnDesc::from_field(data_field, false));
+            let mut column_desc = JoinedColumnDesc::from_field(data_field, false);
+            if let Some(computed_expr) = data_field.computed_expr() {
+                if !computed_expr.is_stored() {
+                    let expr: Expression = serde_json::from_slice(computed_expr.expr())?;
+                    column_desc.virtual_expr = Some(Expression::Cast {
+                        expr: Box::new(expr),
+                        data_type: data_field.data_type().clone(),
+                        pg_style: false,
+                    });
+                }
+            }
+            columns_desc.push(column_desc);
         }
 
-        JoinedTableDesc::Table {
+        Ok(JoinedTableDesc::Table {
             table,
             columns_desc,
             name_parts: prefix,
             push_downs: None,
-        }
+        })
     }
 
     pub fn from_subquery(state: Box<QueryAnalyzeState>, prefix: Vec<String>) -> JoinedTableDesc {
@@ -226,6 +242,8 @@ pub struct JoinedColumnDesc {
     pub data_type: DataTypeImpl,
     pub nullable: bool,
     pub is_ambiguity: bool,
+    // The expression of a virtual generated column, which is computed on read.
+    pub virtual_expr: Option<Expression>,
 }
 
 impl JoinedColumnDesc {
@@ -235,6 +253,7 @@ impl JoinedColumnDesc {
             data_type: field.data_type().clone(),
             nullable: field.is_nullable(),
             is_ambiguity,
+            virtual_expr: None,
         }
     }
 
@@ -244,6 +263,7 @@ impl JoinedColumnDesc {
             data_type,
             nullable,
             is_ambiguity: false,
+            virtual_expr: None,
         }
     }
 }
@@ -12,10 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::str::FromStr;
 use std::sync::Arc;
 
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_io::prelude::S3File;
+use common_meta_types::StageFileFormatType;
+use common_planners::Expression;
+use common_planners::S3StageTableInfo;
+use regex::Regex;
 use sqlparser::ast::FunctionArg;
 use sqlparser::ast::Ident;
 use sqlparser::ast::JoinOperator;
@@ -28,6 +35,7 @@ use sqlparser::ast::TableWithJoins;
 use crate::catalogs::Catalog;
 use crate::sessions::QueryContext;
 use crate::sql::statements::analyzer_expr::ExpressionAnalyzer;
+use crate::sql::statements::location_to_stage_path;
 use crate::sql::statements::query::query_schema_joined::JoinedSchema;
 use crate::sql::statements::AnalyzableStatement;
 use crate::sql::statements::AnalyzedResult;
@@ -36,6 +44,8 @@ use crate::sql::DfParser;
 use crate::sql::DfStatement;
 use crate::storages::view::view_table::QUERY;
 use crate::storages::view::view_table::VIEW_ENGINE;
+use crate::storages::S3StageTable;
+use crate::storages::StageSource;
 
 pub struct JoinedSchemaAnalyzer {
     ctx: Arc<QueryContext>,
@@ -64,6 +74,10 @@ impl JoinedSchemaAnalyzer {
                     let schema = self.table_function(v);
                     analyzed_tables.push(schema.await?);
                 }
+                RelationRPNItem::Stage(v) => {
+                    let schema = self.stage(v);
+                    analyzed_tables.push(schema.await?);
+                }
                 RelationRPNItem::Derived(v) => {
                     let schema = self.subquery(v);
                     analyzed_tables.push(schema.await?);
@@ -101,6 +115,10 @@ impl JoinedSchemaAnalyzer {
         // TODO(Winter): await query_context.get_table
         let (database, table) = self.resolve_table(&item.name)?;
         let read_table = self.ctx.get_table(&database, &table).await?;
+        self.ctx
+            .get_current_session()
+            .validate_read_privilege(&database, read_table.as_ref())
+            .await?;
         let tbl_info = read_table.get_table_info();
 
         if tbl_info.engine() == VIEW_ENGINE {
@@ -164,6 +182,87 @@ impl JoinedSchemaAnalyzer {
         }
     }
 
+    // Select from the files of the named stage:
+    // SELECT $1, metadata$filename FROM @my_stage (FILE_FORMAT => 'csv', PATTERN => '.*[.]csv')
+    async fn stage(&self, item: &StageRPNItem) -> Result<JoinedSchema> {
+        let (mut stage_info, path) = location_to_stage_path(&item.location, &self.ctx).await?;
+
+        let mut file_format = None;
+        let mut pattern = None;
+        let analyzer = ExpressionAnalyzer::create(self.ctx.clone());
+        for stage_arg in &item.args {
+            let (name, arg) = match stage_arg {
+                FunctionArg::Named { name, arg } => (name.value.to_lowercase(), arg),
+                FunctionArg::Unnamed(_) => {
+                    return Err(ErrorCode::BadArguments(
+                        "The arguments of stage must be named, e.g. FILE_FORMAT => 'csv'",
+                    ));
+                }
+            };
+            let value = match analyzer.analyze_function_arg(arg).await? {
+                Expression::Literal {
+                    value: DataValue::String(value),
+                    ..
+                } => String::from_utf8_lossy(&value).to_string(),
+                other => {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "The argument {} of stage must be a string, got: {:?}",
+                        name, other
+                    )));
+                }
+            };
+            match name.as_str() {
+                "file_format" => {
+                    let format = StageFileFormatType::from_str(&value)
+                        .map_err(ErrorCode::SyntaxException)?;
+                    file_format = Some(format);
+                }
+                "pattern" => {
+                    let regex = Regex::new(&value).map_err(|e| {
+                        ErrorCode::SyntaxException(format!(
+                            "Pattern format invalid, got:{}, error:{:?}",
+                            value, e
+                        ))
+                    })?;
+                    pattern = Some(regex);
+                }
+                _ => {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Unknown argument of stage: {}, must be one of {{ FILE_FORMAT | PATTERN }}",
+                        name
+                    )));
+                }
+            }
+        }
+
+        let op = StageSource::get_op(&self.ctx, &stage_info).await?;
+        let mut files = S3File::list(&op, &path).await?;
+        if let Some(pattern) = pattern {
+            files.retain(|file| pattern.is_match(file));
+        }
+
+        // The format is given, or by the extension of the files, or the one of the stage.
+        let file_format = file_format.or_else(|| files.first().and_then(|f| format_of_file(f)));
+        if let Some(file_format) = file_format {
+            stage_info.file_format_options.format = file_format;
+        }
+
+        let schema = StageSource::infer_schema(&self.ctx, &stage_info, &files).await?;
+        let table = S3StageTable::try_create(S3StageTableInfo {
+            schema,
+            stage_info,
+            path,
+            files,
+        })?;
+        match &item.alias {
+            None => JoinedSchema::from_table(table, Vec::new()),
+            Some(table_alias) => {
+                let name_prefix = vec![table_alias.name.value.clone()];
+                JoinedSchema::from_table(table, name_prefix)
+            }
+        }
+    }
+
     fn resolve_table(&self, name: &ObjectName) -> Result<(String, String)> {
         match name.0.len() {
             0 => Err(ErrorCode::SyntaxException("Table name is empty")),
@@ -176,6 +275,18 @@ impl JoinedSchemaAnalyzer {
     }
 }
 
+// The format of the staged file by its extension.
+fn format_of_file(file: &str) -> Option<StageFileFormatType> {
+    let extension = file.rsplit_once('.')?.1.to_lowercase();
+    match extension.as_str() {
+        "csv" => Some(StageFileFormatType::Csv),
+        "tsv" => Some(StageFileFormatType::Tsv),
+        "json" | "ndjson" => Some(StageFileFormatType::Json),
+        "parquet" => Some(StageFileFormatType::Parquet),
+        _ => None,
+    }
+}
+
 struct TableRPNItem {
     name: ObjectName,
     alias: Option<TableAlias>,
@@ -192,9 +303,16 @@ struct TableFunctionRPNItem {
     alias: Option<TableAlias>,
 }
 
+struct StageRPNItem {
+    location: String,
+    args: Vec<FunctionArg>,
+    alias: Option<TableAlias>,
+}
+
 enum RelationRPNItem {
     Table(TableRPNItem),
     TableFunction(TableFunctionRPNItem),
+    Stage(StageRPNItem),
     Derived(DerivedRPNItem),
     Join(JoinOperator),
 }
@@ -264,6 +382,10 @@ impl RelationRPNBuilder {
                     ));
                 }
 
+                if name.0.len() == 1 && name.0[0].value.starts_with('@') {
+                    return self.visit_stage(&name.0[0].value, args, alias);
+                }
+
                 match args.is_empty() {
                     true => self.visit_table(name, alias),
                     false => self.visit_table_function(name, args, alias),
@@ -313,4 +435,18 @@ impl RelationRPNBuilder {
             }));
         Ok(())
     }
+
+    fn visit_stage(
+        &mut self,
+        location: &str,
+        args: &[FunctionArg],
+        alias: &Option<TableAlias>,
+    ) -> Result<()> {
+        self.rpn.push(RelationRPNItem::Stage(StageRPNItem {
+            location: location.to_string(),
+            args: args.to_owned(),
+            alias: alias.clone(),
+        }));
+        Ok(())
+    }
 }
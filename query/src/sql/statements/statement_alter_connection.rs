@@ -0,0 +1,51 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::AlterConnectionPlan;
+use common_planners::PlanNode;
+use common_tracing::tracing;
+
+use super::statement_create_connection::check_credential_options;
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfAlterConnection {
+    pub name: String,
+    /// The new credentials, or None for `ROTATE MASTER KEY`.
+    pub credential_options: Option<BTreeMap<String, String>>,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfAlterConnection {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        if let Some(options) = &self.credential_options {
+            check_credential_options(options)?;
+        }
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::AlterConnection(AlterConnectionPlan {
+                tenant: ctx.get_tenant(),
+                name: self.name.clone(),
+                credentials: self.credential_options.clone(),
+            }),
+        )))
+    }
+}
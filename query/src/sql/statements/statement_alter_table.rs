@@ -14,17 +14,38 @@
 
 use std::sync::Arc;
 
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_types::RowAccessPolicyReference;
+use common_planners::resolve_aliases_to_exprs;
+use common_planners::validate_expression;
+use common_planners::AddVirtualColumnPlan;
+use common_planners::AlterColumnAction;
+use common_planners::AlterColumnPlan;
+use common_planners::AlterRowAccessPolicyPlan;
+use common_planners::Expression;
+use common_planners::FlashbackPoint;
+use common_planners::FlashbackTablePlan;
 use common_planners::PlanNode;
+use common_planners::ReclusterTablePlan;
 use common_planners::RenameTableEntity;
 use common_planners::RenameTablePlan;
+use common_planners::RowAccessPolicyAction;
+use common_planners::VirtualColumnDefinition;
 use common_tracing::tracing;
+use sqlparser::ast::ColumnDef;
+use sqlparser::ast::Expr;
+use sqlparser::ast::Ident;
 use sqlparser::ast::ObjectName;
 
 use crate::sessions::QueryContext;
+use crate::sql::statements::virtual_generated_exprs;
 use crate::sql::statements::AnalyzableStatement;
 use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::DfCreateTable;
+use crate::sql::statements::ExpressionAnalyzer;
+use crate::storages::NavigationPoint;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfAlterTable {
@@ -36,7 +57,27 @@ pub struct DfAlterTable {
 #[derive(Clone, Debug, PartialEq)]
 pub enum AlterTableAction {
     RenameTable(ObjectName),
-    // TODO AddColumn etc.
+    Flashback(NavigationPoint),
+    /// Adds a virtual column of a path of a Variant column, e.g. `v:data.user.id`
+    AddVirtualColumn(Expr),
+    AddColumn(ColumnDef),
+    DropColumn(Ident),
+    RenameColumn {
+        old_column: Ident,
+        new_column: Ident,
+    },
+    /// Rewrites the overlapping blocks of a clustered table, sorted by the cluster keys
+    Recluster {
+        is_final: bool,
+        selection: Option<Expr>,
+        limit: Option<usize>,
+    },
+    /// Attaches a row access policy, whose arguments are bound to the columns
+    AddRowAccessPolicy {
+        policy: String,
+        columns: Vec<Ident>,
+    },
+    DropRowAccessPolicy(String),
 }
 
 #[async_trait::async_trait]
@@ -62,11 +103,152 @@ impl AnalyzableStatement for DfAlterTable {
                     PlanNode::RenameTable(RenameTablePlan { tenant, entities }),
                 )))
             }
+            AlterTableAction::Flashback(point) => {
+                let point = match point {
+                    NavigationPoint::SnapshotID(id) => FlashbackPoint::SnapshotId(id.clone()),
+                    NavigationPoint::TimePoint(t) => {
+                        FlashbackPoint::Timestamp(t.timestamp_micros())
+                    }
+                };
+                Ok(AnalyzedResult::SimpleQuery(Box::new(
+                    PlanNode::FlashbackTable(FlashbackTablePlan {
+                        if_exists: self.if_exists,
+                        database: db,
+                        table: table_name,
+                        point,
+                    }),
+                )))
+            }
+            AlterTableAction::AddVirtualColumn(expr) => {
+                let expr = ExpressionAnalyzer::create(ctx).analyze(expr).await?;
+                let definition = match &expr {
+                    Expression::MapAccess { args, .. } => match args.as_slice() {
+                        [Expression::Column(column), Expression::Literal {
+                            value: DataValue::String(path),
+                            ..
+                        }] => Some(VirtualColumnDefinition {
+                            column: column.clone(),
+                            path: String::from_utf8(path.clone())?,
+                        }),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                match definition {
+                    Some(definition) => Ok(AnalyzedResult::SimpleQuery(Box::new(
+                        PlanNode::AddVirtualColumn(AddVirtualColumnPlan {
+                            if_exists: self.if_exists,
+                            database: db,
+                            table: table_name,
+                            name: expr.column_name(),
+                            definition,
+                        }),
+                    ))),
+                    None => Err(ErrorCode::SyntaxException(format!(
+                        "Virtual column must be a path of a column, like `v:k1.k2`, but got {}",
+                        expr.column_name()
+                    ))),
+                }
+            }
+            AlterTableAction::AddColumn(column) => {
+                let expr_analyzer = ExpressionAnalyzer::create(ctx);
+                let field = DfCreateTable::column_field(&expr_analyzer, column, None).await?;
+                self.alter_column_plan(db, table_name, AlterColumnAction::Add(field))
+            }
+            AlterTableAction::DropColumn(column) => {
+                let action = AlterColumnAction::Drop(column.value.clone());
+                self.alter_column_plan(db, table_name, action)
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => {
+                let action = AlterColumnAction::Rename {
+                    old_column: old_column.value.clone(),
+                    new_column: new_column.value.clone(),
+                };
+                self.alter_column_plan(db, table_name, action)
+            }
+            AlterTableAction::Recluster {
+                is_final,
+                selection,
+                limit,
+            } => {
+                let selection = match selection {
+                    None => None,
+                    Some(expr) => {
+                        let table = ctx.get_table(&db, &table_name).await?;
+                        let expr = ExpressionAnalyzer::create(ctx.clone())
+                            .analyze(expr)
+                            .await?;
+                        validate_expression(&expr, &table.schema())?;
+                        let virtual_exprs = virtual_generated_exprs(&table.schema())?;
+                        Some(resolve_aliases_to_exprs(&expr, &virtual_exprs)?)
+                    }
+                };
+                Ok(AnalyzedResult::SimpleQuery(Box::new(
+                    PlanNode::ReclusterTable(ReclusterTablePlan {
+                        if_exists: self.if_exists,
+                        database: db,
+                        table: table_name,
+                        is_final: *is_final,
+                        selection,
+                        limit: *limit,
+                    }),
+                )))
+            }
+            AlterTableAction::AddRowAccessPolicy { policy, columns } => {
+                let reference = RowAccessPolicyReference {
+                    policy: policy.clone(),
+                    columns: columns.iter().map(|c| c.value.clone()).collect(),
+                };
+                self.alter_row_access_policy_plan(
+                    db,
+                    table_name,
+                    RowAccessPolicyAction::Add(reference),
+                )
+            }
+            AlterTableAction::DropRowAccessPolicy(policy) => {
+                let action = RowAccessPolicyAction::Drop(policy.clone());
+                self.alter_row_access_policy_plan(db, table_name, action)
+            }
         }
     }
 }
 
 impl DfAlterTable {
+    fn alter_column_plan(
+        &self,
+        database: String,
+        table: String,
+        action: AlterColumnAction,
+    ) -> Result<AnalyzedResult> {
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::AlterColumn(AlterColumnPlan {
+                if_exists: self.if_exists,
+                database,
+                table,
+                action,
+            }),
+        )))
+    }
+
+    fn alter_row_access_policy_plan(
+        &self,
+        database: String,
+        table: String,
+        action: RowAccessPolicyAction,
+    ) -> Result<AnalyzedResult> {
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::AlterRowAccessPolicy(AlterRowAccessPolicyPlan {
+                if_exists: self.if_exists,
+                database,
+                table,
+                action,
+            }),
+        )))
+    }
+
     fn resolve_table(
         &self,
         ctx: Arc<QueryContext>,
@@ -0,0 +1,104 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::TableMeta;
+use common_planners::CreateTablePlan;
+use common_planners::PlanNode;
+use common_tracing::tracing;
+use sqlparser::ast::ObjectName;
+
+use crate::catalogs::Catalog;
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::DfCreateTable;
+use crate::sql::OPT_KEY_DATABASE_ID;
+use crate::sql::OPT_KEY_EXTERNAL_LOCATION;
+use crate::sql::OPT_KEY_READ_ONLY_ATTACHED;
+use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::storages::fuse::FuseTable;
+
+/// Attaches the snapshot of a fuse table, which may belong to another cluster sharing the
+/// same storage, as a read only table.
+///
+/// With an external location `fuse://<bucket>/<prefix>`, the table is attached to the prefix of
+/// the fuse table instead, and reads the latest snapshot under it in each query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfAttachTable {
+    pub if_not_exists: bool,
+    pub name: ObjectName,
+    /// Location of the snapshot, relative to the root of the storage, or the external location
+    pub uri: String,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfAttachTable {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let (db, table) = DfCreateTable::resolve_table(ctx.clone(), &self.name, "Table")?;
+        let external = FuseTable::parse_external_location(&ctx.get_config(), &self.uri)?;
+        let (snapshot_location, schema) = match &external {
+            None => {
+                let schema = FuseTable::read_attached_schema(ctx.as_ref(), &self.uri).await?;
+                (self.uri.clone(), schema)
+            }
+            Some(prefix) => match FuseTable::find_latest_snapshot(ctx.as_ref(), prefix).await? {
+                Some((location, snapshot)) => (location, Arc::new(snapshot.schema.clone())),
+                None => {
+                    return Err(ErrorCode::StorageNotFound(format!(
+                        "no snapshot of fuse table is found in {}",
+                        self.uri
+                    )));
+                }
+            },
+        };
+
+        let catalog = ctx.get_catalog();
+        let tenant = ctx.get_tenant();
+        let database = catalog.get_database(tenant.as_str(), &db).await?;
+        let db_id = database.get_db_info().ident.db_id;
+
+        let mut options = BTreeMap::from([
+            (OPT_KEY_DATABASE_ID.to_owned(), db_id.to_string()),
+            (OPT_KEY_SNAPSHOT_LOCATION.to_owned(), snapshot_location),
+            (OPT_KEY_READ_ONLY_ATTACHED.to_owned(), "true".to_owned()),
+        ]);
+        if let Some(prefix) = external {
+            options.insert(OPT_KEY_EXTERNAL_LOCATION.to_owned(), prefix);
+        }
+        let table_meta = TableMeta {
+            schema,
+            engine: "FUSE".to_owned(),
+            options,
+            ..Default::default()
+        };
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::CreateTable(CreateTablePlan {
+                if_not_exists: self.if_not_exists,
+                tenant,
+                db,
+                table,
+                table_meta,
+                order_keys: vec![],
+                as_select: None,
+            }),
+        )))
+    }
+}
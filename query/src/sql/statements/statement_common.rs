@@ -13,19 +13,30 @@
 // limitations under the License.
 
 use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_io::prelude::get_abs_path;
 use common_io::prelude::parse_escape_string;
 use common_meta_types::FileFormatOptions;
+use common_meta_types::GrantObject;
 use common_meta_types::StageFileFormatType;
+use common_meta_types::StageParams;
 use common_meta_types::StageS3Storage;
 use common_meta_types::StageStorage;
 use common_meta_types::StageType;
+use common_meta_types::UserPrivilegeType;
 use common_meta_types::UserStageInfo;
+use common_planners::resolve_aliases_to_exprs;
+use common_planners::Expression;
+use common_planners::RequireColumnsVisitor;
+use sqlparser::ast::Ident;
 
 use crate::sessions::QueryContext;
 
@@ -38,30 +49,69 @@ pub async fn location_to_stage_path(
     // @my_ext_stage/abc
     let names: Vec<&str> = s[1].splitn(2, '/').collect();
     let stage = mgr.get_stage(&ctx.get_tenant(), names[0]).await?;
+    ctx.get_current_session()
+        .validate_privilege(
+            &GrantObject::Stage(stage.stage_name.clone()),
+            UserPrivilegeType::Usage,
+        )
+        .await?;
 
     let path = if names.len() > 1 { names[1] } else { "" };
-    let related_path: String;
+    let related_path = get_abs_path(stage_root_path(&stage).as_str(), path);
+    Ok((stage, related_path))
+}
+
+// The root path of the named stage in its storage.
+pub fn stage_root_path(stage: &UserStageInfo) -> String {
     match stage.stage_type {
         // It's internal, so we already have an op which has the root path
         // need to inject a tenant path
-        StageType::Internal => {
-            let prefix = format!("stage/{}", stage.stage_name);
-            related_path = get_abs_path(prefix.as_str(), path);
-        }
+        StageType::Internal => format!("stage/{}", stage.stage_name),
         // It's  external, so we need to join the root path
         StageType::External => match stage.stage_params.storage {
-            StageStorage::S3(ref s3) => {
-                related_path = get_abs_path(s3.path.as_str(), path);
-            }
+            StageStorage::S3(ref s3) => s3.path.clone(),
         },
     }
-    Ok((stage, related_path))
+}
+
+// The connection referred to by the location must exist.
+pub async fn check_connection(ctx: &Arc<QueryContext>, connection: &str) -> Result<()> {
+    if !connection.is_empty() {
+        let mgr = ctx.get_user_manager();
+        mgr.get_connection(&ctx.get_tenant(), connection).await?;
+    }
+    Ok(())
+}
+
+// The stage and the path in it the location refers to, which is either a named stage, e.g.
+// '@my_stage/backup', or an external one, e.g. 's3://mybucket/backup'.
+pub async fn location_to_stage_info(
+    ctx: &Arc<QueryContext>,
+    location: &str,
+    connection: &str,
+    credential_options: &BTreeMap<String, String>,
+) -> Result<(UserStageInfo, String)> {
+    if location.starts_with('@') {
+        return location_to_stage_path(location, ctx).await;
+    }
+
+    check_connection(ctx, connection).await?;
+    let (storage, path) =
+        parse_stage_storage(location, connection, credential_options, &BTreeMap::new())?;
+    let stage = UserStageInfo {
+        stage_name: location.to_string(),
+        stage_type: StageType::External,
+        stage_params: StageParams { storage },
+        ..Default::default()
+    };
+    Ok((stage, path))
 }
 
 // path_as_root set to true when we create external stage
 // path_as_root set to false when we copy from external stage
 pub fn parse_stage_storage(
     location: &str,
+    connection: &str,
     credential_options: &BTreeMap<String, String>,
     encryption_options: &BTreeMap<String, String>,
 ) -> Result<(StageStorage, String)> {
@@ -91,6 +141,11 @@ pub fn parse_stage_storage(
         Some(v) => match v {
             // AWS s3 plan.
             "s3" => {
+                if !connection.is_empty() && !credential_options.is_empty() {
+                    return Err(ErrorCode::SyntaxException(
+                        "CONNECTION and CREDENTIALS cannot be both specified",
+                    ));
+                }
                 let credentials_aws_key_id = credential_options
                     .get("aws_key_id")
                     .unwrap_or(&"".to_string())
@@ -110,6 +165,7 @@ pub fn parse_stage_storage(
                     credentials_aws_key_id,
                     credentials_aws_secret_key,
                     encryption_master_key,
+                    connection: connection.to_string(),
                 });
 
                 Ok((storage_stage, path))
@@ -155,11 +211,118 @@ pub fn parse_copy_file_format_options(
             .as_bytes(),
     );
 
+    // Enclosed by, as ENCLOSED BY of MySQL.
+    let enclosed_by = parse_format_character(file_format_options, "enclosed_by")?;
+
+    // Escaped by, as ESCAPED BY of MySQL.
+    let escaped_by = parse_format_character(file_format_options, "escaped_by")?;
+
     Ok(FileFormatOptions {
         format: file_format,
         skip_header,
         field_delimiter,
         record_delimiter,
+        enclosed_by,
+        escaped_by,
         compression: Default::default(),
     })
 }
+
+// The option of one character or NONE, empty if it's not given.
+fn parse_format_character(options: &BTreeMap<String, String>, key: &str) -> Result<String> {
+    let value = match options.get(key) {
+        None => return Ok("".to_string()),
+        Some(value) if value.eq_ignore_ascii_case("none") => return Ok("NONE".to_string()),
+        Some(value) => parse_escape_string(value.as_bytes()),
+    };
+
+    match value.len() {
+        1 => Ok(value),
+        _ => Err(ErrorCode::SyntaxException(format!(
+            "{} must be one character or NONE, got: {:?}",
+            key, value
+        ))),
+    }
+}
+
+/// The columns written by INSERT or COPY, the missing columns of the table are filled with their
+/// defaults. If no columns are given, all the columns except the generated ones are written.
+pub fn write_schema(schema: DataSchemaRef, columns: &[Ident]) -> Result<DataSchemaRef> {
+    if columns.is_empty() {
+        if schema.fields().iter().all(|f| f.computed_expr().is_none()) {
+            return Ok(schema);
+        }
+        let fields = schema
+            .fields()
+            .iter()
+            .filter(|f| f.computed_expr().is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+        return Ok(DataSchemaRefExt::create(fields));
+    }
+
+    let fields = columns
+        .iter()
+        .map(|ident| schema.field_with_name(&ident.value).map(|v| v.clone()))
+        .collect::<Result<Vec<_>>>()?;
+    for f in &fields {
+        check_not_generated(f)?;
+    }
+    Ok(DataSchemaRefExt::create(fields))
+}
+
+/// The generated columns are computed from the other columns, and can not be written to.
+pub fn check_not_generated(field: &DataField) -> Result<()> {
+    match field.computed_expr() {
+        Some(_) => Err(ErrorCode::SyntaxException(format!(
+            "Cannot write to the generated column {}",
+            field.name()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// The expressions of the virtual generated columns of a table, by the names of the columns.
+pub fn virtual_generated_exprs(schema: &DataSchemaRef) -> Result<HashMap<String, Expression>> {
+    let mut exprs = HashMap::new();
+    for field in schema.fields() {
+        if let Some(computed_expr) = field.computed_expr() {
+            if !computed_expr.is_stored() {
+                let expr = serde_json::from_slice::<Expression>(computed_expr.expr())?;
+                exprs.insert(field.name().clone(), Expression::Cast {
+                    expr: Box::new(expr),
+                    data_type: field.data_type().clone(),
+                    pg_style: false,
+                });
+            }
+        }
+    }
+    Ok(exprs)
+}
+
+/// The assignments to the stored generated columns of a table, which are computed from the
+/// columns of the given assignments, so that they are updated together.
+pub fn stored_column_assignments(
+    schema: &DataSchemaRef,
+    assignments: &[(String, Expression)],
+) -> Result<Vec<(String, Expression)>> {
+    let values = assignments.iter().cloned().collect::<HashMap<_, _>>();
+    let mut stored_assignments = vec![];
+    for field in schema.fields() {
+        if let Some(computed_expr) = field.computed_expr() {
+            if computed_expr.is_stored() {
+                let expr = serde_json::from_slice::<Expression>(computed_expr.expr())?;
+                let columns = RequireColumnsVisitor::collect_columns_from_expr(&expr)?;
+                if columns.iter().any(|c| values.contains_key(c)) {
+                    let expr = resolve_aliases_to_exprs(&expr, &values)?;
+                    stored_assignments.push((field.name().clone(), Expression::Cast {
+                        expr: Box::new(expr),
+                        data_type: field.data_type().clone(),
+                        pg_style: false,
+                    }));
+                }
+            }
+        }
+    }
+    Ok(stored_assignments)
+}
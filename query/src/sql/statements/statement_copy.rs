@@ -16,26 +16,32 @@ use std::collections::BTreeMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
-use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_io::prelude::get_abs_path;
 use common_meta_types::OnErrorMode;
 use common_meta_types::StageParams;
 use common_meta_types::StageType;
 use common_meta_types::UserStageInfo;
+use common_planners::find_aggregate_exprs;
 use common_planners::CopyPlan;
 use common_planners::PlanNode;
 use common_planners::ReadDataSourcePlan;
 use common_planners::S3StageTableInfo;
 use common_planners::SourceInfo;
 use common_planners::ValidationMode;
+use sqlparser::ast::Expr;
 use sqlparser::ast::Ident;
 use sqlparser::ast::ObjectName;
 
+use super::check_connection;
 use super::location_to_stage_path;
 use super::parse_copy_file_format_options;
 use super::parse_stage_storage;
+use super::stage_root_path;
+use super::write_schema;
 use crate::sessions::QueryContext;
+use crate::sql::statements::analyzer_expr::ExpressionAnalyzer;
 use crate::sql::statements::AnalyzableStatement;
 use crate::sql::statements::AnalyzedResult;
 
@@ -44,6 +50,7 @@ pub struct DfCopy {
     pub name: ObjectName,
     pub columns: Vec<Ident>,
     pub location: String,
+    pub connection: String,
     pub credential_options: BTreeMap<String, String>,
     pub encryption_options: BTreeMap<String, String>,
     pub file_format_options: BTreeMap<String, String>,
@@ -51,7 +58,11 @@ pub struct DfCopy {
     pub pattern: String,
     pub on_error: String,
     pub size_limit: String,
+    pub purge: String,
+    pub rejected_records: String,
     pub validation_mode: String,
+    // The expressions of the columns to write, over the columns of the files.
+    pub transformation: Vec<Expr>,
 }
 
 #[async_trait::async_trait]
@@ -66,24 +77,14 @@ impl AnalyzableStatement for DfCopy {
         }
 
         let table = ctx.get_table(&db_name, &tbl_name).await?;
-        let mut schema = table.schema();
+        let schema = write_schema(table.schema(), &self.columns)?;
         let tbl_id = table.get_id();
 
-        if !self.columns.is_empty() {
-            let fields = self
-                .columns
-                .iter()
-                .map(|ident| schema.field_with_name(&ident.value).map(|v| v.clone()))
-                .collect::<Result<Vec<_>>>()?;
-
-            schema = DataSchemaRefExt::create(fields);
-        }
-
         // Stage info.
         let (mut stage_info, path) = if self.location.starts_with('@') {
             self.analyze_named(&ctx).await?
         } else {
-            self.analyze_location().await?
+            self.analyze_location(&ctx).await?
         };
 
         if !self.file_format_options.is_empty() {
@@ -109,12 +110,55 @@ impl AnalyzableStatement for DfCopy {
                 })?;
                 stage_info.copy_options.size_limit = size_limit;
             }
+
+            // purge.
+            if !self.purge.is_empty() {
+                stage_info.copy_options.purge = match self.purge.to_uppercase().as_str() {
+                    "TRUE" => true,
+                    "FALSE" => false,
+                    _ => {
+                        return Err(ErrorCode::SyntaxException(format!(
+                            "purge must be TRUE or FALSE, got: {}",
+                            self.purge
+                        )))
+                    }
+                };
+            }
+
+            // rejected_records, relative to the named stage or the bucket of the location.
+            if !self.rejected_records.is_empty() {
+                let root = match self.location.starts_with('@') {
+                    true => stage_root_path(&stage_info),
+                    false => "/".to_string(),
+                };
+                stage_info.copy_options.rejected_records =
+                    get_abs_path(root.as_str(), &self.rejected_records);
+            }
         }
 
         // Validation mode.
         let validation_mode = ValidationMode::from_str(self.validation_mode.as_str())
             .map_err(ErrorCode::SyntaxException)?;
 
+        // Transformation.
+        let analyzer = ExpressionAnalyzer::create(ctx.clone());
+        let mut transformation = Vec::with_capacity(self.transformation.len());
+        for expr in &self.transformation {
+            transformation.push(analyzer.analyze(expr).await?);
+        }
+        if !find_aggregate_exprs(&transformation).is_empty() {
+            return Err(ErrorCode::SyntaxException(
+                "COPY transformation cannot contain aggregate functions",
+            ));
+        }
+        if !transformation.is_empty() && transformation.len() != schema.fields().len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "COPY transformation has {} columns, but {} columns to write",
+                transformation.len(),
+                schema.fields().len()
+            )));
+        }
+
         // Read source plan.
         let from = ReadDataSourcePlan {
             source_info: SourceInfo::S3StageSource(S3StageTableInfo {
@@ -144,6 +188,7 @@ impl AnalyzableStatement for DfCopy {
             validation_mode,
             files: self.files.clone(),
             pattern,
+            transformation,
         };
 
         Ok(AnalyzedResult::SimpleQuery(Box::new(PlanNode::Copy(
@@ -165,9 +210,11 @@ impl DfCopy {
     // credentials=(aws_key_id='my_key_id' aws_secret_key='my_secret_key')
     // encryption=(master_key = 'my_master_key')
     // file_format = (type = csv field_delimiter = '|' skip_header = 1)"
-    async fn analyze_location(&self) -> Result<(UserStageInfo, String)> {
+    async fn analyze_location(&self, ctx: &Arc<QueryContext>) -> Result<(UserStageInfo, String)> {
+        check_connection(ctx, &self.connection).await?;
         let (stage_storage, path) = parse_stage_storage(
             &self.location,
+            &self.connection,
             &self.credential_options,
             &self.encryption_options,
         )?;
@@ -0,0 +1,144 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::AggregatingIndexDefinition;
+use common_planners::CreateAggregatingIndexPlan;
+use common_planners::Expression;
+use common_planners::PlanNode;
+use common_tracing::tracing;
+use sqlparser::ast::Ident;
+use sqlparser::ast::TableFactor;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::DfCreateTable;
+use crate::sql::statements::DfQueryStatement;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreateAggregatingIndex {
+    pub if_not_exists: bool,
+    /// Index Name
+    pub name: Ident,
+    /// The aggregation which the index keeps the results of
+    pub query: DfQueryStatement,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfCreateAggregatingIndex {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let query = &self.query;
+        let table_name = match &query.from[..] {
+            [from] if from.joins.is_empty() => match &from.relation {
+                TableFactor::Table { name, args, .. } if args.is_empty() => name,
+                _ => return Err(Self::unsupported("the source must be a table")),
+            },
+            _ => return Err(Self::unsupported("the source must be a single table")),
+        };
+        if query.selection.is_some()
+            || query.having.is_some()
+            || query.distinct
+            || !query.order_by.is_empty()
+            || query.limit.is_some()
+            || query.offset.is_some()
+        {
+            return Err(Self::unsupported(
+                "only SELECT .. FROM .. GROUP BY .. is supported",
+            ));
+        }
+
+        let (db, table) = DfCreateTable::resolve_table(ctx.clone(), table_name, "Table")?;
+        let state = match query.analyze(ctx).await? {
+            AnalyzedResult::SelectQuery(state) => state,
+            _ => {
+                return Err(ErrorCode::LogicalError(
+                    "Logical error: analyze select must be return select query analyze result.",
+                ))
+            }
+        };
+
+        let mut keys = Vec::with_capacity(state.group_by_expressions.len());
+        for expr in &state.group_by_expressions {
+            match expr {
+                Expression::Column(column) => keys.push(column.clone()),
+                _ => {
+                    return Err(Self::unsupported(format!(
+                        "can not group by {}, only columns are supported",
+                        expr.column_name()
+                    )))
+                }
+            }
+        }
+
+        let mut aggregates = Vec::with_capacity(state.aggregate_expressions.len());
+        for expr in &state.aggregate_expressions {
+            let aggregate = match expr {
+                Expression::AggregateFunction {
+                    op,
+                    distinct: false,
+                    params,
+                    args,
+                } if params.is_empty() => {
+                    let op = op.to_lowercase();
+                    match args.as_slice() {
+                        [] if op == "count" => Some((op, None)),
+                        [Expression::Column(column)]
+                            if AggregatingIndexDefinition::FUNCTIONS.contains(&op.as_str()) =>
+                        {
+                            Some((op, Some(column.clone())))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            match aggregate {
+                Some(aggregate) => aggregates.push(aggregate),
+                None => {
+                    return Err(Self::unsupported(format!(
+                        "can not aggregate {}, only {} of columns are supported",
+                        expr.column_name(),
+                        AggregatingIndexDefinition::FUNCTIONS.join(", ")
+                    )))
+                }
+            }
+        }
+        if aggregates.is_empty() {
+            return Err(Self::unsupported(
+                "at least one aggregate function is required",
+            ));
+        }
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::CreateAggregatingIndex(CreateAggregatingIndexPlan {
+                if_not_exists: self.if_not_exists,
+                db,
+                table,
+                index: self.name.value.clone(),
+                definition: AggregatingIndexDefinition { keys, aggregates },
+            }),
+        )))
+    }
+}
+
+impl DfCreateAggregatingIndex {
+    fn unsupported(reason: impl Into<String>) -> ErrorCode {
+        ErrorCode::SyntaxException(format!("Unsupported aggregating index, {}", reason.into()))
+    }
+}
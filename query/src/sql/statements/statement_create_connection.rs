@@ -0,0 +1,83 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::CreateConnectionPlan;
+use common_planners::PlanNode;
+use common_tracing::tracing;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreateConnection {
+    pub if_not_exists: bool,
+    pub name: String,
+    pub storage_type: String,
+    pub credential_options: BTreeMap<String, String>,
+    pub comment: String,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfCreateConnection {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let storage_type = self.storage_type.to_lowercase();
+        if storage_type != "s3" {
+            return Err(ErrorCode::SyntaxException(format!(
+                "Connection storage type unsupported, must be one of [s3], got: {}",
+                self.storage_type
+            )));
+        }
+        check_credential_options(&self.credential_options)?;
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::CreateConnection(CreateConnectionPlan {
+                if_not_exists: self.if_not_exists,
+                tenant: ctx.get_tenant(),
+                name: self.name.clone(),
+                storage_type,
+                credentials: self.credential_options.clone(),
+                comment: self.comment.clone(),
+            }),
+        )))
+    }
+}
+
+// The credentials of a s3 connection are aws_key_id and aws_secret_key.
+pub(crate) fn check_credential_options(options: &BTreeMap<String, String>) -> Result<()> {
+    for key in ["aws_key_id", "aws_secret_key"] {
+        if !options.contains_key(key) {
+            return Err(ErrorCode::SyntaxException(format!(
+                "Connection credentials must contain {}",
+                key
+            )));
+        }
+    }
+    if let Some(key) = options
+        .keys()
+        .find(|key| !matches!(key.as_str(), "aws_key_id" | "aws_secret_key"))
+    {
+        return Err(ErrorCode::SyntaxException(format!(
+            "Unknown connection credentials option: {}",
+            key
+        )));
+    }
+    Ok(())
+}
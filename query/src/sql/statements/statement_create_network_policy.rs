@@ -0,0 +1,56 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::chrono::Utc;
+use common_exception::Result;
+use common_meta_types::NetworkPolicy;
+use common_planners::CreateNetworkPolicyPlan;
+use common_planners::PlanNode;
+use common_tracing::tracing;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreateNetworkPolicy {
+    pub if_not_exists: bool,
+    pub name: String,
+    pub allowed_ip_list: Vec<String>,
+    pub blocked_ip_list: Vec<String>,
+    pub comment: String,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfCreateNetworkPolicy {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let policy = NetworkPolicy {
+            name: self.name.clone(),
+            allowed_ip_list: self.allowed_ip_list.clone(),
+            blocked_ip_list: self.blocked_ip_list.clone(),
+            comment: self.comment.clone(),
+            created_on: Utc::now(),
+        };
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::CreateNetworkPolicy(CreateNetworkPolicyPlan {
+                if_not_exists: self.if_not_exists,
+                tenant: ctx.get_tenant(),
+                policy,
+            }),
+        )))
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::CopyOptions;
+use common_meta_types::FileFormatOptions;
+use common_meta_types::KafkaSource;
+use common_meta_types::OnErrorMode;
+use common_meta_types::PipeInfo;
+use common_meta_types::PipeSource;
+use common_meta_types::StageFileFormatType;
+use common_meta_types::StagePipeSource;
+use common_planners::CreatePipePlan;
+use common_planners::PlanNode;
+use common_tracing::tracing;
+use regex::Regex;
+use sqlparser::ast::ObjectName;
+
+use super::location_to_stage_path;
+use super::parse_copy_file_format_options;
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::DfCreateTable;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreatePipe {
+    pub if_not_exists: bool,
+    pub name: String,
+    /// The table which the pipe loads into
+    pub table_name: ObjectName,
+    /// The kind of the source, such as `KAFKA`, or the `@<stage_name>[/<path>]` location
+    pub source: String,
+    pub source_options: BTreeMap<String, String>,
+    pub file_format_options: BTreeMap<String, String>,
+    pub comments: String,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfCreatePipe {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let (database, table) =
+            DfCreateTable::resolve_table(ctx.clone(), &self.table_name, "Table")?;
+
+        // A stage source inherits the file format and the copy options of the stage.
+        let (source, file_format_options) = if self.source.starts_with('@') {
+            let (stage_info, _) = location_to_stage_path(&self.source, &ctx).await?;
+            let source = self.analyze_stage_source(stage_info.copy_options)?;
+            (source, stage_info.file_format_options)
+        } else {
+            (self.analyze_source()?, FileFormatOptions::default())
+        };
+
+        let mut pipe_info = PipeInfo {
+            pipe_name: self.name.clone(),
+            database,
+            table,
+            source,
+            file_format_options,
+            comment: self.comments.clone(),
+        };
+
+        if !self.file_format_options.is_empty() {
+            pipe_info.file_format_options =
+                parse_copy_file_format_options(&self.file_format_options)?;
+        }
+        let format = &pipe_info.file_format_options.format;
+        match (&pipe_info.source, format) {
+            (PipeSource::Kafka(_), StageFileFormatType::Csv)
+            | (PipeSource::Kafka(_), StageFileFormatType::Json)
+            | (PipeSource::Kafka(_), StageFileFormatType::Avro) => {}
+            (PipeSource::Stage(_), StageFileFormatType::Csv)
+            | (PipeSource::Stage(_), StageFileFormatType::Json)
+            | (PipeSource::Stage(_), StageFileFormatType::Parquet) => {}
+            (PipeSource::Kafka(_), other) => {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "Unsupported file format of pipe: {:?}, must one of {{ CSV | JSON | AVRO }}",
+                    other
+                )));
+            }
+            (PipeSource::Stage(_), other) => {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "Unsupported file format of pipe: {:?}, must one of {{ CSV | JSON | PARQUET }}",
+                    other
+                )));
+            }
+        }
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(PlanNode::CreatePipe(
+            CreatePipePlan {
+                if_not_exists: self.if_not_exists,
+                tenant: ctx.get_tenant(),
+                pipe_info,
+            },
+        ))))
+    }
+}
+
+impl DfCreatePipe {
+    fn analyze_source(&self) -> Result<PipeSource> {
+        match self.source.as_str() {
+            "KAFKA" => {
+                let brokers = self
+                    .source_options
+                    .get("brokers")
+                    .map(|v| {
+                        v.split(',')
+                            .map(|b| b.trim().to_string())
+                            .filter(|b| !b.is_empty())
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                if brokers.is_empty() {
+                    return Err(ErrorCode::SyntaxException(
+                        "BROKERS of the KAFKA source must be specified",
+                    ));
+                }
+
+                let topic = match self.source_options.get("topic") {
+                    Some(topic) if !topic.is_empty() => topic.clone(),
+                    _ => {
+                        return Err(ErrorCode::SyntaxException(
+                            "TOPIC of the KAFKA source must be specified",
+                        ));
+                    }
+                };
+
+                Ok(PipeSource::Kafka(KafkaSource { brokers, topic }))
+            }
+            other => Err(ErrorCode::SyntaxException(format!(
+                "Unsupported pipe source: {}, must one of {{ KAFKA | @<stage_name> }}",
+                other
+            ))),
+        }
+    }
+
+    fn analyze_stage_source(&self, mut copy_options: CopyOptions) -> Result<PipeSource> {
+        // pattern.
+        let pattern = self.source_options.get("pattern").cloned();
+        let pattern = pattern.unwrap_or_default();
+        if !pattern.is_empty() {
+            Regex::new(&pattern).map_err(|e| {
+                ErrorCode::SyntaxException(format!(
+                    "Pattern format invalid, got:{}, error:{:?}",
+                    &pattern, e
+                ))
+            })?;
+        }
+
+        // on_error.
+        if let Some(on_error) = self.source_options.get("on_error") {
+            copy_options.on_error =
+                OnErrorMode::from_str(on_error).map_err(ErrorCode::SyntaxException)?;
+        }
+
+        // size_limit.
+        if let Some(size_limit) = self.source_options.get("size_limit") {
+            copy_options.size_limit = size_limit.parse::<usize>().map_err(|_e| {
+                ErrorCode::SyntaxException(format!(
+                    "size_limit must be number, got: {}",
+                    size_limit
+                ))
+            })?;
+        }
+
+        Ok(PipeSource::Stage(StagePipeSource {
+            location: self.source.clone(),
+            pattern,
+            copy_options,
+        }))
+    }
+}
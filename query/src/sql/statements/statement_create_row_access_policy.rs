@@ -0,0 +1,95 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_ast::udfs::UDFParser;
+use common_datavalues::chrono::Utc;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::RowAccessPolicy;
+use common_planners::validate_expression;
+use common_planners::CreateRowAccessPolicyPlan;
+use common_planners::PlanNode;
+use common_tracing::tracing;
+use sqlparser::ast::DataType;
+use sqlparser::ast::Expr;
+use sqlparser::ast::Ident;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::ExpressionAnalyzer;
+use crate::sql::SQLCommon;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreateRowAccessPolicy {
+    pub if_not_exists: bool,
+    pub name: String,
+    pub args: Vec<(Ident, DataType)>,
+    pub body: Expr,
+    pub exempt_roles: Vec<String>,
+    pub comment: String,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfCreateRowAccessPolicy {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let mut args = Vec::with_capacity(self.args.len());
+        for (name, data_type) in &self.args {
+            args.push(DataField::new(
+                &name.value,
+                SQLCommon::make_data_type(data_type)?,
+            ));
+        }
+
+        // The body must use all the arguments and only them, and must be a boolean.
+        let params = args
+            .iter()
+            .map(|arg| arg.name().clone())
+            .collect::<Vec<_>>();
+        UDFParser::default()
+            .parse(&self.name, &params, &self.body.to_string())
+            .await?;
+        let schema = DataSchemaRefExt::create(args.clone());
+        let expr = ExpressionAnalyzer::create(ctx.clone())
+            .analyze(&self.body)
+            .await?;
+        validate_expression(&expr, &schema)?;
+        let data_type = expr.to_data_type(&schema)?;
+        if remove_nullable(&data_type).data_type_id() != TypeID::Boolean {
+            return Err(ErrorCode::IllegalRowAccessPolicy(format!(
+                "The body of a row access policy must return BOOLEAN, but got {}",
+                data_type.name()
+            )));
+        }
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::CreateRowAccessPolicy(CreateRowAccessPolicyPlan {
+                if_not_exists: self.if_not_exists,
+                tenant: ctx.get_tenant(),
+                policy: RowAccessPolicy {
+                    name: self.name.clone(),
+                    args,
+                    body: self.body.to_string(),
+                    exempt_roles: self.exempt_roles.clone(),
+                    comment: self.comment.clone(),
+                    created_on: Utc::now(),
+                },
+            }),
+        )))
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::CreateStreamPlan;
+use common_planners::PlanNode;
+use common_tracing::tracing;
+use sqlparser::ast::ObjectName;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::DfCreateTable;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreateStream {
+    pub if_not_exists: bool,
+    /// Stream Name
+    pub name: ObjectName,
+    /// The table on which the stream is created
+    pub table_name: ObjectName,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfCreateStream {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let tenant = ctx.get_tenant();
+        let (db, stream) = DfCreateTable::resolve_table(ctx.clone(), &self.name, "Stream")?;
+        let (table_db, table) =
+            DfCreateTable::resolve_table(ctx.clone(), &self.table_name, "Table")?;
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::CreateStream(CreateStreamPlan {
+                if_not_exists: self.if_not_exists,
+                tenant,
+                db,
+                stream,
+                table_db,
+                table,
+            }),
+        )))
+    }
+}
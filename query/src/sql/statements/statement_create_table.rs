@@ -16,6 +16,7 @@ use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use common_datavalues::ComputedExpr;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
@@ -26,6 +27,7 @@ use common_planners::validate_expression;
 use common_planners::CreateTablePlan;
 use common_planners::Expression;
 use common_planners::PlanNode;
+use common_planners::RequireColumnsVisitor;
 use common_tracing::tracing;
 use sqlparser::ast::ColumnDef;
 use sqlparser::ast::ColumnOption;
@@ -43,6 +45,15 @@ use crate::sql::DfStatement;
 use crate::sql::PlanParser;
 use crate::sql::SQLCommon;
 use crate::sql::OPT_KEY_DATABASE_ID;
+use crate::storages::delta::DeltaTable;
+use crate::storages::delta::DELTA_ENGINE;
+use crate::storages::fuse::operations::DataRetention;
+use crate::storages::fuse::ClusterOrder;
+use crate::storages::fuse::FuseTable;
+use crate::storages::iceberg::IcebergTable;
+use crate::storages::iceberg::ICEBERG_ENGINE;
+use crate::storages::NavigationPoint;
+use crate::storages::Table;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfCreateTable {
@@ -50,6 +61,8 @@ pub struct DfCreateTable {
     /// Table name
     pub name: ObjectName,
     pub columns: Vec<ColumnDef>,
+    // The generated columns, keyed by the column name.
+    pub generated_columns: BTreeMap<String, DfGeneratedColumn>,
     pub engine: String,
     pub order_keys: Vec<Expr>,
     pub options: BTreeMap<String, String>,
@@ -59,12 +72,36 @@ pub struct DfCreateTable {
 
     // The query of "create table .. as select" statement.
     pub query: Option<Box<DfQueryStatement>>,
+
+    // The source table of "create table .. clone" statement.
+    pub clone: Option<DfCloneSource>,
+}
+
+/// The expression of a generated column, `AS (expr) [STORED | VIRTUAL]`.
+///
+/// A stored column is evaluated when the rows are written, a virtual one is evaluated when the
+/// column is read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfGeneratedColumn {
+    pub expr: Expr,
+    pub stored: bool,
+}
+
+/// The table (and optionally the point of its history) that a table is cloned from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCloneSource {
+    pub name: ObjectName,
+    pub at: Option<NavigationPoint>,
 }
 
 #[async_trait::async_trait]
 impl AnalyzableStatement for DfCreateTable {
     #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        if let Some(source) = &self.clone {
+            return self.analyze_clone(ctx, source).await;
+        }
+
         let (db, table) = Self::resolve_table(ctx.clone(), &self.name, "Table")?;
         let mut table_meta = self.table_meta(ctx.clone(), db.as_str()).await?;
         let if_not_exists = self.if_not_exists;
@@ -101,6 +138,7 @@ impl AnalyzableStatement for DfCreateTable {
             validate_expression(&expr, &table_meta.schema)?;
             order_keys.push(expr);
         }
+        ClusterOrder::try_create(&self.options, &order_keys, &table_meta.schema)?;
 
         if !order_keys.is_empty() {
             let order_keys_v = serde_json::to_vec(&order_keys)?;
@@ -142,12 +180,62 @@ impl DfCreateTable {
         }
     }
 
+    // The clone shares the segments of the source table, no data is copied.
+    async fn analyze_clone(
+        &self,
+        ctx: Arc<QueryContext>,
+        source: &DfCloneSource,
+    ) -> Result<AnalyzedResult> {
+        let (db, table) = Self::resolve_table(ctx.clone(), &self.name, "Table")?;
+        let (source_db, source_table) = Self::resolve_table(ctx.clone(), &source.name, "Table")?;
+        // the clone has all the rows of the source table
+        ctx.get_current_session()
+            .validate_select_privilege(&source_db, &source_table)
+            .await?;
+        let mut source_table = ctx.get_table(&source_db, &source_table).await?;
+        if let Some(point) = &source.at {
+            source_table = source_table.navigate_to(ctx.clone(), point).await?;
+        }
+        let source_table = FuseTable::try_from_table(source_table.as_ref())?;
+
+        let source_meta = &source_table.get_table_info().meta;
+        let order_keys = match &source_meta.order_keys {
+            Some(keys) => serde_json::from_slice::<Vec<Expression>>(keys)?,
+            None => vec![],
+        };
+        let meta = TableMeta {
+            schema: source_table.schema(),
+            engine: source_meta.engine.clone(),
+            options: source_table.options_of_clone()?,
+            order_keys: source_meta.order_keys.clone(),
+            // the clone starts from the current snapshot of the source table
+            usage: source_meta.usage.clone(),
+            ..Default::default()
+        };
+        let table_meta = self.plan_with_db_id(ctx.as_ref(), &db, meta).await?;
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::CreateTable(CreateTablePlan {
+                if_not_exists: self.if_not_exists,
+                tenant: ctx.get_tenant(),
+                db,
+                table,
+                table_meta,
+                order_keys,
+                as_select: None,
+            }),
+        )))
+    }
+
     async fn table_meta(&self, ctx: Arc<QueryContext>, db_name: &str) -> Result<TableMeta> {
         let engine = self.engine.clone();
         let schema = self.table_schema(ctx.clone()).await?;
 
         self.validate_table_options()?;
+        // the retention of the rows must be told by a DATE or TIMESTAMP column
+        DataRetention::try_create(&self.options, &schema)?;
         self.validata_default_exprs(&schema)?;
+        Self::validate_computed_exprs(&schema)?;
 
         let meta = TableMeta {
             schema,
@@ -171,44 +259,79 @@ impl DfCreateTable {
                 let origin_table = ctx.get_table(&origin_db_name, &origin_table_name).await?;
                 Ok(origin_table.schema())
             }
+            // For the iceberg table without columns, we use the current schema of it.
+            None if self.columns.is_empty() && self.engine.eq_ignore_ascii_case(ICEBERG_ENGINE) => {
+                IcebergTable::read_schema(ctx.as_ref(), &self.options).await
+            }
+            // Likewise, the schema of the delta table is the one of the latest version.
+            None if self.columns.is_empty() && self.engine.eq_ignore_ascii_case(DELTA_ENGINE) => {
+                DeltaTable::read_schema(ctx.as_ref(), &self.options).await
+            }
             None => {
                 let expr_analyzer = ExpressionAnalyzer::create(ctx);
                 let mut fields = Vec::with_capacity(self.columns.len());
 
                 for column in &self.columns {
-                    //  Defaults to not nullable, if you want to use nullable, you should add `null` into table options
-                    // For example: `CREATE TABLE test (id INT NOT NULL, name String NULL)`
-                    // Equals to: `CREATE TABLE test (id INT, name String NULL)`
-                    let mut nullable = false;
-                    let mut default_expr = None;
-                    for opt in &column.options {
-                        match &opt.option {
-                            ColumnOption::Null => {
-                                nullable = true;
-                            }
-                            ColumnOption::Default(expr) => {
-                                let expr = expr_analyzer.analyze(expr).await?;
-                                default_expr = Some(serde_json::to_vec(&expr)?);
-                            }
-                            _ => {}
-                        }
-                    }
-                    let field = SQLCommon::make_data_type(&column.data_type).map(|data_type| {
-                        if nullable {
-                            DataField::new_nullable(&column.name.value, data_type)
-                                .with_default_expr(default_expr)
-                        } else {
-                            DataField::new(&column.name.value, data_type)
-                                .with_default_expr(default_expr)
-                        }
-                    })?;
-                    fields.push(field);
+                    let generated = self.generated_columns.get(&column.name.value);
+                    fields.push(Self::column_field(&expr_analyzer, column, generated).await?);
                 }
                 Ok(DataSchemaRefExt::create(fields))
             }
         }
     }
 
+    /// The field of a column definition, shared with `ALTER TABLE .. ADD COLUMN`
+    pub(crate) async fn column_field(
+        expr_analyzer: &ExpressionAnalyzer,
+        column: &ColumnDef,
+        generated: Option<&DfGeneratedColumn>,
+    ) -> Result<DataField> {
+        //  Defaults to not nullable, if you want to use nullable, you should add `null` into table options
+        // For example: `CREATE TABLE test (id INT NOT NULL, name String NULL)`
+        // Equals to: `CREATE TABLE test (id INT, name String NULL)`
+        let mut nullable = false;
+        let mut default_expr = None;
+        let mut computed_expr = None;
+        for opt in &column.options {
+            match &opt.option {
+                ColumnOption::Null => {
+                    nullable = true;
+                }
+                ColumnOption::Default(expr) => {
+                    let expr = expr_analyzer.analyze(expr).await?;
+                    default_expr = Some(serde_json::to_vec(&expr)?);
+                }
+                _ => {}
+            }
+        }
+        if let Some(generated) = generated {
+            if default_expr.is_some() {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "Generated column {} can not have a default value",
+                    column.name.value
+                )));
+            }
+            let expr = expr_analyzer.analyze(&generated.expr).await?;
+            let expr = serde_json::to_vec(&expr)?;
+            computed_expr = Some(if generated.stored {
+                ComputedExpr::Stored(expr)
+            } else {
+                ComputedExpr::Virtual(expr)
+            });
+        }
+        SQLCommon::make_data_type(&column.data_type).map(|data_type| {
+            if nullable {
+                DataField::new_nullable(&column.name.value, data_type)
+                    .with_default_expr(default_expr)
+                    .with_computed_expr(computed_expr)
+            } else {
+                DataField::new(&column.name.value, data_type)
+                    .with_default_expr(default_expr)
+                    .with_computed_expr(computed_expr)
+            }
+        })
+    }
+
     async fn plan_with_db_id(
         &self,
         ctx: &QueryContext,
@@ -267,4 +390,26 @@ impl DfCreateTable {
         }
         Ok(())
     }
+
+    // The generated columns can only be computed from the ordinary columns.
+    fn validate_computed_exprs(schema: &DataSchemaRef) -> Result<()> {
+        for f in schema.fields() {
+            if let Some(computed_expr) = f.computed_expr() {
+                let expr: Expression = serde_json::from_slice(computed_expr.expr())?;
+                validate_expression(&expr, schema)?;
+
+                for column in RequireColumnsVisitor::collect_columns_from_expr(&expr)? {
+                    let source = schema.field_with_name(&column)?;
+                    if source.computed_expr().is_some() {
+                        return Err(ErrorCode::SyntaxException(format!(
+                            "Generated column {} can not refer to the generated column {}",
+                            f.name(),
+                            column
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
 }
@@ -49,6 +49,8 @@ pub enum DfUserWithOption {
     NoTenantSetting,
     ConfigReload,
     NoConfigReload,
+    NetworkPolicy(String),
+    NoNetworkPolicy,
 }
 
 impl TryFrom<&str> for DfUserWithOption {
@@ -60,6 +62,7 @@ impl TryFrom<&str> for DfUserWithOption {
             "NOTENANTSETTING" => Ok(DfUserWithOption::NoTenantSetting),
             "CONFIGRELOAD" => Ok(DfUserWithOption::ConfigReload),
             "NOCONFIGRELOAD" => Ok(DfUserWithOption::NoConfigReload),
+            "NONETWORKPOLICY" => Ok(DfUserWithOption::NoNetworkPolicy),
             _ => Err(format!("Unknown user option: {}", value)),
         }
     }
@@ -80,6 +83,12 @@ impl DfUserWithOption {
             Self::NoConfigReload => {
                 option.unset_option_flag(UserOptionFlag::ConfigReload);
             }
+            Self::NetworkPolicy(name) => {
+                option.set_network_policy(Some(name.clone()));
+            }
+            Self::NoNetworkPolicy => {
+                option.set_network_policy(None);
+            }
         }
     }
 }
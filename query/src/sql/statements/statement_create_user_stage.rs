@@ -26,6 +26,7 @@ use common_planners::CreateUserStagePlan;
 use common_planners::PlanNode;
 use common_tracing::tracing;
 
+use super::check_connection;
 use super::parse_copy_file_format_options;
 use super::parse_stage_storage;
 use crate::sessions::QueryContext;
@@ -38,6 +39,7 @@ pub struct DfCreateUserStage {
     pub stage_name: String,
 
     pub location: String,
+    pub connection: String,
     pub credential_options: BTreeMap<String, String>,
     pub encryption_options: BTreeMap<String, String>,
 
@@ -54,7 +56,7 @@ impl AnalyzableStatement for DfCreateUserStage {
     async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
         let mut stage_info = match self.location.is_empty() {
             true => self.analyze_internal().await?,
-            false => self.analyze_external().await?,
+            false => self.analyze_external(&ctx).await?,
         };
         stage_info.stage_name = self.stage_name.clone();
 
@@ -100,9 +102,11 @@ impl DfCreateUserStage {
         })
     }
 
-    async fn analyze_external(&self) -> Result<UserStageInfo> {
+    async fn analyze_external(&self, ctx: &Arc<QueryContext>) -> Result<UserStageInfo> {
+        check_connection(ctx, &self.connection).await?;
         let (stage_storage, _) = parse_stage_storage(
             &self.location,
+            &self.connection,
             &self.credential_options,
             &self.encryption_options,
         )?;
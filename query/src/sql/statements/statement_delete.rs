@@ -0,0 +1,84 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::resolve_aliases_to_exprs;
+use common_planners::validate_expression;
+use common_planners::DeletePlan;
+use common_planners::PlanNode;
+use common_tracing::tracing;
+use sqlparser::ast::Expr;
+use sqlparser::ast::ObjectName;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::virtual_generated_exprs;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::ExpressionAnalyzer;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfDelete {
+    pub name: ObjectName,
+    pub selection: Option<Expr>,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfDelete {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let (database_name, table_name) = self.resolve_table(ctx.clone())?;
+        let table = ctx.get_table(&database_name, &table_name).await?;
+
+        let selection = match &self.selection {
+            None => None,
+            Some(expr) => {
+                let expr = ExpressionAnalyzer::create(ctx.clone())
+                    .analyze(expr)
+                    .await?;
+                validate_expression(&expr, &table.schema())?;
+                let virtual_exprs = virtual_generated_exprs(&table.schema())?;
+                Some(resolve_aliases_to_exprs(&expr, &virtual_exprs)?)
+            }
+        };
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(PlanNode::Delete(
+            DeletePlan {
+                database_name,
+                table_name,
+                table_id: table.get_id(),
+                selection,
+            },
+        ))))
+    }
+}
+
+impl DfDelete {
+    fn resolve_table(&self, ctx: Arc<QueryContext>) -> Result<(String, String)> {
+        let DfDelete {
+            name: ObjectName(idents),
+            ..
+        } = self;
+        match idents.len() {
+            0 => Err(ErrorCode::SyntaxException("Delete table name is empty")),
+            1 => Ok((ctx.get_current_database(), idents[0].value.clone())),
+            2 => Ok((idents[0].value.clone(), idents[1].value.clone())),
+            _ => Err(ErrorCode::SyntaxException(
+                "Delete table name must be [`db`].`table`",
+            )),
+        }
+    }
+}
@@ -39,6 +39,8 @@ pub enum DfGrantObject {
     Global,
     Database(Option<String>),
     Table(Option<String>, String),
+    Stage(String),
+    UDF(String),
 }
 
 impl DfGrantObject {
@@ -57,6 +59,8 @@ impl DfGrantObject {
                     .unwrap_or_else(|| ctx.get_current_database());
                 GrantObject::Database(database_name)
             }
+            DfGrantObject::Stage(stage_name) => GrantObject::Stage(stage_name.clone()),
+            DfGrantObject::UDF(udf_name) => GrantObject::UDF(udf_name.clone()),
         }
     }
 }
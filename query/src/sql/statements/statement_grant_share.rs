@@ -0,0 +1,68 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_meta_types::ShareGrantObject;
+use common_planners::GrantShareObjectPlan;
+use common_planners::PlanNode;
+use common_tracing::tracing;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+
+/// The object granted to or revoked from a share
+#[derive(Debug, Clone, PartialEq)]
+pub enum DfShareGrantObject {
+    Database(String),
+    Table(Option<String>, String),
+}
+
+impl DfShareGrantObject {
+    pub fn convert_to_share_grant_object(&self, ctx: &QueryContext) -> ShareGrantObject {
+        match self {
+            DfShareGrantObject::Database(database_name) => {
+                ShareGrantObject::Database(database_name.clone())
+            }
+            DfShareGrantObject::Table(database_name, table_name) => {
+                let database_name = database_name
+                    .clone()
+                    .unwrap_or_else(|| ctx.get_current_database());
+                ShareGrantObject::Table(database_name, table_name.clone())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfGrantShareObject {
+    pub share: String,
+    pub object: DfShareGrantObject,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfGrantShareObject {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::GrantShareObject(GrantShareObjectPlan {
+                tenant: ctx.get_tenant(),
+                share: self.share.clone(),
+                object: self.object.convert_to_share_grant_object(&ctx),
+            }),
+        )))
+    }
+}
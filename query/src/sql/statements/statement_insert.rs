@@ -16,7 +16,6 @@ use std::io::Cursor;
 use std::sync::Arc;
 
 use common_datavalues::DataSchemaRef;
-use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_io::prelude::BufferReader;
@@ -34,6 +33,7 @@ use sqlparser::ast::Query;
 use sqlparser::ast::SqliteOnConflict;
 
 use crate::sessions::QueryContext;
+use crate::sql::statements::write_schema;
 use crate::sql::statements::AnalyzableStatement;
 use crate::sql::statements::AnalyzedResult;
 use crate::sql::statements::DfQueryStatement;
@@ -175,18 +175,6 @@ impl<'a> DfInsertStatement<'a> {
     }
 
     fn insert_schema(&self, read_table: Arc<dyn Table>) -> Result<DataSchemaRef> {
-        match self.columns.is_empty() {
-            true => Ok(read_table.schema()),
-            false => {
-                let schema = read_table.schema();
-                let fields = self
-                    .columns
-                    .iter()
-                    .map(|ident| schema.field_with_name(&ident.value).map(|v| v.clone()))
-                    .collect::<Result<Vec<_>>>()?;
-
-                Ok(DataSchemaRefExt::create(fields))
-            }
-        }
+        write_schema(read_table.schema(), &self.columns)
     }
 }
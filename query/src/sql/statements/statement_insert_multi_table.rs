@@ -0,0 +1,214 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::validate_expression;
+use common_planners::Expression;
+use common_planners::InsertMultiTableBranch;
+use common_planners::InsertMultiTableInto;
+use common_planners::InsertMultiTablePlan;
+use common_planners::PlanNode;
+use common_tracing::tracing;
+use sqlparser::ast::Expr;
+use sqlparser::ast::Ident;
+use sqlparser::ast::ObjectName;
+use sqlparser::ast::Query;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::check_not_generated;
+use crate::sql::statements::stored_column_assignments;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::DfQueryStatement;
+use crate::sql::statements::ExpressionAnalyzer;
+use crate::sql::DfStatement;
+use crate::sql::PlanParser;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfInsertMultiTableInto {
+    pub table: ObjectName,
+    pub columns: Vec<Ident>,
+    /// The values of the columns, the columns of the source by position if empty
+    pub values: Vec<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfInsertMultiTableBranch {
+    /// None for the ELSE branch
+    pub condition: Option<Expr>,
+    pub intos: Vec<DfInsertMultiTableInto>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfInsertMultiTable {
+    pub first: bool,
+    pub branches: Vec<DfInsertMultiTableBranch>,
+    pub source: Box<Query>,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfInsertMultiTable {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let statement = DfQueryStatement::try_from((*self.source).clone())?;
+        let source =
+            PlanParser::build_plan(vec![DfStatement::Query(Box::new(statement))], ctx.clone())
+                .await?;
+        let source_schema = source.schema();
+        let analyzer = ExpressionAnalyzer::create(ctx.clone());
+
+        let mut branches = Vec::with_capacity(self.branches.len());
+        for branch in &self.branches {
+            let condition = match &branch.condition {
+                Some(expr) => Some(resolve(&analyzer, &source_schema, expr).await?),
+                None => None,
+            };
+            let mut intos = Vec::with_capacity(branch.intos.len());
+            for into in &branch.intos {
+                intos.push(analyze_into(&ctx, &analyzer, &source_schema, into).await?);
+            }
+            branches.push(InsertMultiTableBranch { condition, intos });
+        }
+
+        let plan = InsertMultiTablePlan {
+            first: self.first,
+            branches,
+            source: Box::new(source),
+        };
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::InsertMultiTable(plan),
+        )))
+    }
+}
+
+/// Resolves the values of all the columns of the table of `into`, the columns which are not
+/// given are filled with their default values.
+async fn analyze_into(
+    ctx: &Arc<QueryContext>,
+    analyzer: &ExpressionAnalyzer,
+    source_schema: &DataSchemaRef,
+    into: &DfInsertMultiTableInto,
+) -> Result<InsertMultiTableInto> {
+    let (database_name, table_name) = resolve_table(ctx, &into.table)?;
+    let table = ctx.get_table(&database_name, &table_name).await?;
+    let schema = table.schema();
+
+    let columns = match into.columns.is_empty() {
+        true => schema
+            .fields()
+            .iter()
+            .filter(|f| f.computed_expr().is_none())
+            .map(|f| f.name().clone())
+            .collect::<Vec<_>>(),
+        false => into.columns.iter().map(|c| c.value.clone()).collect(),
+    };
+    let values = match into.values.is_empty() {
+        true => source_schema
+            .fields()
+            .iter()
+            .map(|f| Expression::Column(f.name().clone()))
+            .collect::<Vec<_>>(),
+        false => {
+            let mut values = Vec::with_capacity(into.values.len());
+            for value in &into.values {
+                values.push(resolve(analyzer, source_schema, value).await?);
+            }
+            values
+        }
+    };
+    if columns.len() != values.len() {
+        return Err(ErrorCode::SyntaxException(format!(
+            "{} values are given to {} columns of table {}",
+            values.len(),
+            columns.len(),
+            table_name
+        )));
+    }
+
+    let mut given = HashMap::with_capacity(columns.len());
+    for (column, value) in columns.iter().zip(values.into_iter()) {
+        let field = schema.field_with_name(column)?;
+        check_not_generated(field)?;
+        if given.insert(field.name(), value).is_some() {
+            return Err(ErrorCode::SyntaxException(format!(
+                "Column {} is assigned more than once",
+                field.name()
+            )));
+        }
+    }
+
+    let mut new_values = Vec::with_capacity(schema.num_fields());
+    for field in schema.fields() {
+        let value = match (given.remove(field.name()), field.default_expr()) {
+            (Some(value), _) => value,
+            (None, Some(expr)) => serde_json::from_slice::<Expression>(expr)?,
+            (None, None) => Expression::create_literal_with_type(
+                field.data_type().default_value(),
+                field.data_type().clone(),
+            ),
+        };
+        new_values.push(Expression::Cast {
+            expr: Box::new(value),
+            data_type: field.data_type().clone(),
+            pg_style: false,
+        });
+    }
+
+    // the stored generated columns are computed from the inserted values, the virtual ones
+    // are computed on read
+    let assignments = schema
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .zip(new_values.iter().cloned())
+        .collect::<Vec<_>>();
+    for (column, value) in stored_column_assignments(&schema, &assignments)? {
+        let index = schema.index_of(&column)?;
+        new_values[index] = value;
+    }
+
+    Ok(InsertMultiTableInto {
+        database_name,
+        table_name,
+        table_id: table.get_id(),
+        values: new_values,
+    })
+}
+
+fn resolve_table(ctx: &Arc<QueryContext>, name: &ObjectName) -> Result<(String, String)> {
+    let ObjectName(idents) = name;
+    match idents.len() {
+        0 => Err(ErrorCode::SyntaxException("Insert table name is empty")),
+        1 => Ok((ctx.get_current_database(), idents[0].value.clone())),
+        2 => Ok((idents[0].value.clone(), idents[1].value.clone())),
+        _ => Err(ErrorCode::SyntaxException(
+            "Insert table name must be [`db`].`table`",
+        )),
+    }
+}
+
+async fn resolve(
+    analyzer: &ExpressionAnalyzer,
+    schema: &DataSchemaRef,
+    expr: &Expr,
+) -> Result<Expression> {
+    let expr = analyzer.analyze(expr).await?;
+    validate_expression(&expr, schema)?;
+    Ok(expr)
+}
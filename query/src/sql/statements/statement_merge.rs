@@ -0,0 +1,450 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::validate_expression;
+use common_planners::Expression;
+use common_planners::ExpressionRewriter;
+use common_planners::MergeInsertAction;
+use common_planners::MergeMatchedAction;
+use common_planners::MergePlan;
+use common_planners::PlanNode;
+use common_tracing::tracing;
+use sqlparser::ast::Expr;
+use sqlparser::ast::Ident;
+use sqlparser::ast::ObjectName;
+use sqlparser::ast::Query;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::check_not_generated;
+use crate::sql::statements::stored_column_assignments;
+use crate::sql::statements::virtual_generated_exprs;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::DfQueryStatement;
+use crate::sql::statements::ExpressionAnalyzer;
+use crate::sql::DfStatement;
+use crate::sql::PlanParser;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DfMergeSource {
+    Table(ObjectName),
+    Query(Box<Query>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DfMergeClause {
+    MatchedUpdate {
+        condition: Option<Expr>,
+        update_list: Vec<(Ident, Expr)>,
+    },
+    MatchedDelete {
+        condition: Option<Expr>,
+    },
+    NotMatchedInsert {
+        condition: Option<Expr>,
+        columns: Vec<Ident>,
+        values: Vec<Expr>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfMergeInto {
+    pub target: ObjectName,
+    pub target_alias: Option<Ident>,
+    pub source: DfMergeSource,
+    pub source_alias: Option<Ident>,
+    pub on: Expr,
+    pub clauses: Vec<DfMergeClause>,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfMergeInto {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let (database_name, table_name) = self.resolve_target(ctx.clone())?;
+        let table = ctx.get_table(&database_name, &table_name).await?;
+        let target_schema = table.schema();
+        let target_alias = match &self.target_alias {
+            Some(alias) => alias.value.clone(),
+            None => table_name.clone(),
+        };
+
+        let (source, source_alias) = self.analyze_source(ctx.clone()).await?;
+        if source_alias == target_alias {
+            return Err(ErrorCode::SyntaxException(format!(
+                "The target and the source of MERGE are both named {}",
+                source_alias
+            )));
+        }
+        let mut plan = MergePlan {
+            database_name,
+            table_name,
+            table_id: table.get_id(),
+            source: Box::new(source),
+            source_alias: source_alias.clone(),
+            on: vec![],
+            matched: vec![],
+            not_matched: vec![],
+        };
+
+        // the matched rows are evaluated with the columns of the target followed by the ones
+        // of the source, the rows which are not matched with the columns of the source only
+        let source_schema = plan.source_schema();
+        let source_fields = plan.source.schema().fields().clone();
+        let mut fields = target_schema.fields().clone();
+        fields.extend(source_schema.fields().iter().cloned());
+        let schema = DataSchemaRefExt::create(fields);
+        let virtual_exprs = virtual_generated_exprs(&target_schema)?;
+        let resolver = ColumnResolver {
+            target_alias: &target_alias,
+            target_fields: target_schema.fields(),
+            target_virtual_exprs: &virtual_exprs,
+            source_alias: &source_alias,
+            source_fields: &source_fields,
+            allow_target: true,
+        };
+        let analyzer = ExpressionAnalyzer::create(ctx.clone());
+
+        let on = analyzer.analyze(&self.on).await?;
+        let on = resolver.mutate(&on)?;
+        let mut equalities = vec![];
+        collect_equalities(&on, &mut equalities)?;
+        for (left, right) in equalities {
+            let (target_column, source_column) = match (
+                target_schema.has_field(left),
+                source_schema.has_field(right),
+            ) {
+                (true, true) => (left, right),
+                _ if target_schema.has_field(right) && source_schema.has_field(left) => {
+                    (right, left)
+                }
+                _ => return Err(unsupported_on()),
+            };
+            let field = target_schema.field_with_name(target_column)?;
+            let value = Expression::Cast {
+                expr: Box::new(Expression::Column(source_column.to_owned())),
+                data_type: field.data_type().clone(),
+                pg_style: false,
+            };
+            plan.on.push((target_column.to_owned(), value));
+        }
+
+        for clause in &self.clauses {
+            match clause {
+                DfMergeClause::MatchedUpdate {
+                    condition,
+                    update_list,
+                } => {
+                    let condition = match condition {
+                        Some(expr) => Some(resolve(&analyzer, resolver, &schema, expr).await?),
+                        None => None,
+                    };
+                    let mut columns = HashSet::new();
+                    let mut new_update_list = Vec::with_capacity(update_list.len());
+                    for (column, expr) in update_list {
+                        let field = target_schema.field_with_name(&column.value)?;
+                        check_not_generated(field)?;
+                        if !columns.insert(field.name()) {
+                            return Err(ErrorCode::SyntaxException(format!(
+                                "Column {} is assigned more than once",
+                                field.name()
+                            )));
+                        }
+                        let expr = resolve(&analyzer, resolver, &schema, expr).await?;
+                        new_update_list.push((field.name().clone(), cast_to(expr, field)));
+                    }
+                    let stored_assignments =
+                        stored_column_assignments(&target_schema, &new_update_list)?;
+                    new_update_list.extend(stored_assignments);
+                    plan.matched.push(MergeMatchedAction::Update {
+                        condition,
+                        update_list: new_update_list,
+                    });
+                }
+                DfMergeClause::MatchedDelete { condition } => {
+                    let condition = match condition {
+                        Some(expr) => Some(resolve(&analyzer, resolver, &schema, expr).await?),
+                        None => None,
+                    };
+                    plan.matched.push(MergeMatchedAction::Delete { condition });
+                }
+                DfMergeClause::NotMatchedInsert {
+                    condition,
+                    columns,
+                    values,
+                } => {
+                    let resolver = ColumnResolver {
+                        allow_target: false,
+                        ..resolver
+                    };
+                    let condition = match condition {
+                        Some(expr) => {
+                            Some(resolve(&analyzer, resolver, &source_schema, expr).await?)
+                        }
+                        None => None,
+                    };
+
+                    let columns = match columns.is_empty() {
+                        true => target_schema
+                            .fields()
+                            .iter()
+                            .filter(|f| f.computed_expr().is_none())
+                            .map(|f| f.name().clone())
+                            .collect::<Vec<_>>(),
+                        false => columns.iter().map(|c| c.value.clone()).collect(),
+                    };
+                    if columns.len() != values.len() {
+                        return Err(ErrorCode::SyntaxException(format!(
+                            "{} values are given to {} columns in WHEN NOT MATCHED",
+                            values.len(),
+                            columns.len()
+                        )));
+                    }
+                    let mut given = HashMap::with_capacity(columns.len());
+                    for (column, value) in columns.iter().zip(values.iter()) {
+                        let field = target_schema.field_with_name(column)?;
+                        check_not_generated(field)?;
+                        if given.insert(field.name(), value).is_some() {
+                            return Err(ErrorCode::SyntaxException(format!(
+                                "Column {} is assigned more than once",
+                                field.name()
+                            )));
+                        }
+                    }
+
+                    let mut new_values = Vec::with_capacity(target_schema.num_fields());
+                    for field in target_schema.fields() {
+                        let value = match (given.get(field.name()), field.default_expr()) {
+                            (Some(value), _) => {
+                                resolve(&analyzer, resolver, &source_schema, value).await?
+                            }
+                            (None, Some(expr)) => serde_json::from_slice::<Expression>(expr)?,
+                            (None, None) => Expression::create_literal_with_type(
+                                field.data_type().default_value(),
+                                field.data_type().clone(),
+                            ),
+                        };
+                        new_values.push(cast_to(value, field));
+                    }
+
+                    // the stored generated columns are computed from the inserted values, the
+                    // virtual ones are computed on read
+                    let assignments = target_schema
+                        .fields()
+                        .iter()
+                        .map(|f| f.name().clone())
+                        .zip(new_values.iter().cloned())
+                        .collect::<Vec<_>>();
+                    let stored_assignments =
+                        stored_column_assignments(&target_schema, &assignments)?;
+                    for (column, value) in stored_assignments {
+                        let index = target_schema.index_of(&column)?;
+                        new_values[index] = value;
+                    }
+                    plan.not_matched.push(MergeInsertAction {
+                        condition,
+                        values: new_values,
+                    });
+                }
+            }
+        }
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(PlanNode::Merge(plan))))
+    }
+}
+
+impl DfMergeInto {
+    fn resolve_target(&self, ctx: Arc<QueryContext>) -> Result<(String, String)> {
+        let DfMergeInto {
+            target: ObjectName(idents),
+            ..
+        } = self;
+        match idents.len() {
+            0 => Err(ErrorCode::SyntaxException("Merge table name is empty")),
+            1 => Ok((ctx.get_current_database(), idents[0].value.clone())),
+            2 => Ok((idents[0].value.clone(), idents[1].value.clone())),
+            _ => Err(ErrorCode::SyntaxException(
+                "Merge table name must be [`db`].`table`",
+            )),
+        }
+    }
+
+    /// Builds the plan of the source rows, returns it with the name of the source.
+    async fn analyze_source(&self, ctx: Arc<QueryContext>) -> Result<(PlanNode, String)> {
+        match &self.source {
+            DfMergeSource::Table(name) => {
+                let alias = match (&self.source_alias, name.0.last()) {
+                    (Some(alias), _) => alias.value.clone(),
+                    (None, Some(table)) => table.value.clone(),
+                    (None, None) => {
+                        return Err(ErrorCode::SyntaxException("Merge source name is empty"));
+                    }
+                };
+                let plan = PlanParser::parse(ctx, &format!("SELECT * FROM {}", name)).await?;
+                Ok((plan, alias))
+            }
+            DfMergeSource::Query(query) => {
+                let alias = self.source_alias.as_ref().ok_or_else(|| {
+                    ErrorCode::SyntaxException("The subquery of MERGE must have an alias")
+                })?;
+                let statement = DfQueryStatement::try_from((**query).clone())?;
+                let plan =
+                    PlanParser::build_plan(vec![DfStatement::Query(Box::new(statement))], ctx)
+                        .await?;
+                Ok((plan, alias.value.clone()))
+            }
+        }
+    }
+}
+
+/// Resolves the columns referred to by the expressions of MERGE, to the columns of the target
+/// table, or to the columns of the source qualified by the alias of it.
+#[derive(Clone, Copy)]
+struct ColumnResolver<'a> {
+    target_alias: &'a str,
+    target_fields: &'a [DataField],
+    // the virtual generated columns of the target are expanded to their expressions
+    target_virtual_exprs: &'a HashMap<String, Expression>,
+    source_alias: &'a str,
+    source_fields: &'a [DataField],
+    // the columns of the target are not available to the rows which are not matched
+    allow_target: bool,
+}
+
+impl<'a> ColumnResolver<'a> {
+    fn in_target(&self, column: &str) -> bool {
+        self.target_fields.iter().any(|f| f.name() == column)
+    }
+
+    fn in_source(&self, column: &str) -> bool {
+        self.source_fields.iter().any(|f| f.name() == column)
+    }
+
+    fn target_column(&self, column: &str) -> Result<Expression> {
+        match self.allow_target {
+            true => match self.target_virtual_exprs.get(column) {
+                Some(expr) => Ok(expr.clone()),
+                None => Ok(Expression::Column(column.to_owned())),
+            },
+            false => Err(ErrorCode::SyntaxException(format!(
+                "Column {} of the target can not be referred to in WHEN NOT MATCHED",
+                column
+            ))),
+        }
+    }
+
+    fn source_column(&self, column: &str) -> Result<Expression> {
+        Ok(Expression::Column(MergePlan::source_column_name(
+            self.source_alias,
+            column,
+        )))
+    }
+}
+
+impl<'a> ExpressionRewriter for ColumnResolver<'a> {
+    fn mutate_column(
+        &mut self,
+        column_name: &str,
+        _origin_expr: &Expression,
+    ) -> Result<Expression> {
+        match (self.in_target(column_name), self.in_source(column_name)) {
+            (true, true) if self.allow_target => Err(ErrorCode::SyntaxException(format!(
+                "Column {} is ambiguous, it should be qualified by the name of the target or \
+                 the source",
+                column_name
+            ))),
+            (_, true) => self.source_column(column_name),
+            (true, false) => self.target_column(column_name),
+            (false, false) => Err(ErrorCode::UnknownColumn(format!(
+                "Unknown column {}",
+                column_name
+            ))),
+        }
+    }
+
+    fn mutate_qualified_column(
+        &mut self,
+        names: &[String],
+        _origin_expr: &Expression,
+    ) -> Result<Expression> {
+        match names {
+            [table, column] if table == self.target_alias && self.in_target(column) => {
+                self.target_column(column)
+            }
+            [table, column] if table == self.source_alias && self.in_source(column) => {
+                self.source_column(column)
+            }
+            _ => Err(ErrorCode::UnknownColumn(format!(
+                "Unknown column {}",
+                names.join(".")
+            ))),
+        }
+    }
+}
+
+async fn resolve(
+    analyzer: &ExpressionAnalyzer,
+    resolver: ColumnResolver<'_>,
+    schema: &DataSchemaRef,
+    expr: &Expr,
+) -> Result<Expression> {
+    let expr = resolver.mutate(&analyzer.analyze(expr).await?)?;
+    validate_expression(&expr, schema)?;
+    Ok(expr)
+}
+
+fn cast_to(expr: Expression, field: &DataField) -> Expression {
+    Expression::Cast {
+        expr: Box::new(expr),
+        data_type: field.data_type().clone(),
+        pg_style: false,
+    }
+}
+
+fn unsupported_on() -> ErrorCode {
+    ErrorCode::SyntaxException(
+        "The ON clause of MERGE only supports the equalities between the columns of the target \
+         and the source, connected by AND",
+    )
+}
+
+/// Collects the column pairs of `left = right [AND ...]`.
+fn collect_equalities<'e>(
+    expr: &'e Expression,
+    equalities: &mut Vec<(&'e str, &'e str)>,
+) -> Result<()> {
+    match expr {
+        Expression::BinaryExpression { left, op, right } if op.eq_ignore_ascii_case("and") => {
+            collect_equalities(left, equalities)?;
+            collect_equalities(right, equalities)
+        }
+        Expression::BinaryExpression { left, op, right } if op == "=" => {
+            match (left.as_ref(), right.as_ref()) {
+                (Expression::Column(left), Expression::Column(right)) => {
+                    equalities.push((left.as_str(), right.as_str()));
+                    Ok(())
+                }
+                _ => Err(unsupported_on()),
+            }
+        }
+        _ => Err(unsupported_on()),
+    }
+}
@@ -30,6 +30,7 @@ use crate::sql::statements::AnalyzedResult;
 pub struct DfOptimizeTable {
     pub name: ObjectName,
     pub operation: Optimization,
+    pub limit: Option<usize>,
 }
 
 #[async_trait::async_trait]
@@ -41,6 +42,7 @@ impl AnalyzableStatement for DfOptimizeTable {
             database,
             table,
             operation: self.operation,
+            limit: self.limit,
         };
         Ok(AnalyzedResult::SimpleQuery(Box::new(
             PlanNode::OptimizeTable(plan_node),
@@ -0,0 +1,60 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::PlanNode;
+use common_planners::RestoreDatabasePlan;
+use common_tracing::tracing;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::location_to_stage_info;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfRestoreDatabase {
+    pub name: String,
+    pub location: String,
+    pub connection: String,
+    pub credential_options: BTreeMap<String, String>,
+    pub with_users: bool,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfRestoreDatabase {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let (stage_info, path) = location_to_stage_info(
+            &ctx,
+            &self.location,
+            &self.connection,
+            &self.credential_options,
+        )
+        .await?;
+
+        let plan = RestoreDatabasePlan {
+            tenant: ctx.get_tenant(),
+            database: self.name.clone(),
+            stage_info,
+            path,
+            with_users: self.with_users,
+        };
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::RestoreDatabase(plan),
+        )))
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::PlanNode;
+use common_planners::RevokeShareObjectPlan;
+use common_tracing::tracing;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::DfShareGrantObject;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfRevokeShareObject {
+    pub share: String,
+    pub object: DfShareGrantObject,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfRevokeShareObject {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::RevokeShareObject(RevokeShareObjectPlan {
+                tenant: ctx.get_tenant(),
+                share: self.share.clone(),
+                object: self.object.convert_to_share_grant_object(&ctx),
+            }),
+        )))
+    }
+}
@@ -39,6 +39,7 @@ use crate::sql::statements::query::QualifiedRewriter;
 use crate::sql::statements::query::QueryASTIR;
 use crate::sql::statements::query::QueryCollectPushDowns;
 use crate::sql::statements::query::QueryNormalizer;
+use crate::sql::statements::query::RowAccessPolicyRewriter;
 use crate::sql::statements::AnalyzableStatement;
 use crate::sql::statements::AnalyzedResult;
 use crate::sql::statements::QueryRelation;
@@ -64,7 +65,9 @@ impl AnalyzableStatement for DfQueryStatement {
         let analyzer = JoinedSchemaAnalyzer::create(ctx.clone());
         let mut joined_schema = analyzer.analyze(self).await?;
 
-        let mut ir = QueryNormalizer::normalize(ctx.clone(), self).await?;
+        let mut query = self.clone();
+        RowAccessPolicyRewriter::rewrite(ctx.clone(), &joined_schema, &mut query).await?;
+        let mut ir = QueryNormalizer::normalize(ctx.clone(), &query).await?;
 
         QualifiedRewriter::rewrite(&joined_schema, ctx.clone(), &mut ir)?;
 
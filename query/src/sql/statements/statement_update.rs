@@ -0,0 +1,120 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::resolve_aliases_to_exprs;
+use common_planners::validate_expression;
+use common_planners::Expression;
+use common_planners::PlanNode;
+use common_planners::UpdatePlan;
+use common_tracing::tracing;
+use sqlparser::ast::Expr;
+use sqlparser::ast::Ident;
+use sqlparser::ast::ObjectName;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::check_not_generated;
+use crate::sql::statements::stored_column_assignments;
+use crate::sql::statements::virtual_generated_exprs;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::ExpressionAnalyzer;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfUpdate {
+    pub name: ObjectName,
+    pub update_list: Vec<(Ident, Expr)>,
+    pub selection: Option<Expr>,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfUpdate {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let (database_name, table_name) = self.resolve_table(ctx.clone())?;
+        let table = ctx.get_table(&database_name, &table_name).await?;
+        let schema = table.schema();
+        let virtual_exprs = virtual_generated_exprs(&schema)?;
+
+        let mut columns = HashSet::new();
+        let mut update_list = Vec::with_capacity(self.update_list.len());
+        for (column, expr) in &self.update_list {
+            let field = schema.field_with_name(&column.value)?;
+            check_not_generated(field)?;
+            if !columns.insert(field.name()) {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "Column {} is assigned more than once",
+                    field.name()
+                )));
+            }
+
+            let expr = ExpressionAnalyzer::create(ctx.clone())
+                .analyze(expr)
+                .await?;
+            validate_expression(&expr, &schema)?;
+            let expr = resolve_aliases_to_exprs(&expr, &virtual_exprs)?;
+            // the new values have the type of the column
+            let expr = Expression::Cast {
+                expr: Box::new(expr),
+                data_type: field.data_type().clone(),
+                pg_style: false,
+            };
+            update_list.push((field.name().clone(), expr));
+        }
+        let stored_assignments = stored_column_assignments(&schema, &update_list)?;
+        update_list.extend(stored_assignments);
+
+        let selection = match &self.selection {
+            None => None,
+            Some(expr) => {
+                let expr = ExpressionAnalyzer::create(ctx.clone())
+                    .analyze(expr)
+                    .await?;
+                validate_expression(&expr, &schema)?;
+                Some(resolve_aliases_to_exprs(&expr, &virtual_exprs)?)
+            }
+        };
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(PlanNode::Update(
+            UpdatePlan {
+                database_name,
+                table_name,
+                table_id: table.get_id(),
+                update_list,
+                selection,
+            },
+        ))))
+    }
+}
+
+impl DfUpdate {
+    fn resolve_table(&self, ctx: Arc<QueryContext>) -> Result<(String, String)> {
+        let DfUpdate {
+            name: ObjectName(idents),
+            ..
+        } = self;
+        match idents.len() {
+            0 => Err(ErrorCode::SyntaxException("Update table name is empty")),
+            1 => Ok((ctx.get_current_database(), idents[0].value.clone())),
+            2 => Ok((idents[0].value.clone(), idents[1].value.clone())),
+            _ => Err(ErrorCode::SyntaxException(
+                "Update table name must be [`db`].`table`",
+            )),
+        }
+    }
+}
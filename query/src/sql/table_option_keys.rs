@@ -20,6 +20,16 @@ pub const OPT_KEY_DATABASE_ID: &str = "database_id";
 
 pub const OPT_KEY_SNAPSHOT_LOCATION: &str = "snapshot_location";
 
+/// Marks the tables attached from the snapshots of other tables, which are read only
+pub const OPT_KEY_READ_ONLY_ATTACHED: &str = "read_only_attached";
+
+/// Prefix of the fuse table, written by another cluster, which a read only table is attached to.
+/// The table is refreshed to the latest snapshot under the prefix whenever it is queried.
+pub const OPT_KEY_EXTERNAL_LOCATION: &str = "external_location";
+
+/// Ids of the tables that a table is cloned from, directly or indirectly, separated by commas
+pub const OPT_KEY_CLONED_FROM: &str = "cloned_from";
+
 /// Legacy table snapshot location key
 ///
 /// # Deprecated
@@ -38,6 +48,9 @@ lazy_static! {
         let mut r = HashSet::new();
         r.insert(OPT_KEY_DATABASE_ID);
         r.insert(OPT_KEY_SNAPSHOT_LOC);
+        r.insert(OPT_KEY_READ_ONLY_ATTACHED);
+        r.insert(OPT_KEY_CLONED_FROM);
+        r.insert(OPT_KEY_EXTERNAL_LOCATION);
         r
     };
 
@@ -12,16 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_tracing::tracing;
+
 use crate::configs::QueryConfig;
 use crate::storages::fuse::cache;
 use crate::storages::fuse::cache::MemoryCache;
 use crate::storages::fuse::cache::SegmentInfoCache;
+use crate::storages::fuse::cache::TableDiskCache;
 use crate::storages::fuse::cache::TableSnapshotCache;
 
 /// Where all the caches reside
 pub struct CacheManager {
     table_snapshot_cache: Option<TableSnapshotCache>,
     segment_info_cache: Option<SegmentInfoCache>,
+    block_disk_cache: Option<TableDiskCache>,
     cluster_id: String,
     tenant_id: String,
 }
@@ -35,15 +39,18 @@ impl CacheManager {
             Self {
                 table_snapshot_cache: None,
                 segment_info_cache: None,
+                block_disk_cache: None,
                 cluster_id: config.cluster_id.clone(),
                 tenant_id: config.tenant_id.clone(),
             }
         } else {
             let table_snapshot_cache = Self::with_capacity(config.table_cache_snapshot_count);
             let segment_info_cache = Self::with_capacity(config.table_cache_segment_count);
+            let block_disk_cache = Self::disk_cache(config);
             Self {
                 table_snapshot_cache,
                 segment_info_cache,
+                block_disk_cache,
                 cluster_id: config.cluster_id.clone(),
                 tenant_id: config.tenant_id.clone(),
             }
@@ -58,6 +65,10 @@ impl CacheManager {
         self.segment_info_cache.clone()
     }
 
+    pub fn get_block_disk_cache(&self) -> Option<TableDiskCache> {
+        self.block_disk_cache.clone()
+    }
+
     pub fn get_tenant_id(&self) -> &str {
         self.tenant_id.as_str()
     }
@@ -73,4 +84,28 @@ impl CacheManager {
             None
         }
     }
+
+    /// The disk cache is optional, queries go on without it if it can not be created.
+    fn disk_cache(config: &QueryConfig) -> Option<TableDiskCache> {
+        if config.table_disk_cache_mb_size == 0 {
+            return None;
+        }
+        let capacity = config.table_disk_cache_mb_size * 1024 * 1024;
+        match TableDiskCache::try_create(
+            &config.table_disk_cache_root,
+            capacity,
+            &config.tenant_id,
+            &config.cluster_id,
+        ) {
+            Ok(cache) => Some(cache),
+            Err(e) => {
+                tracing::warn!(
+                    "failed to create the disk cache at {}: {}",
+                    config.table_disk_cache_root,
+                    e
+                );
+                None
+            }
+        }
+    }
 }
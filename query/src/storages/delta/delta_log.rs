@@ -0,0 +1,326 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use common_arrow::arrow::array::*;
+use common_arrow::arrow::datatypes::DataType as ArrowType;
+use common_arrow::arrow::io::parquet::read::infer_schema;
+use common_arrow::arrow::io::parquet::read::read_columns_many;
+use common_arrow::arrow::io::parquet::read::read_metadata;
+use common_arrow::arrow::io::parquet::read::RowGroupDeserializer;
+use common_arrow::arrow::types::Offset;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::Value;
+
+use crate::storages::delta::delta_metadata::DeltaType;
+use crate::storages::delta::delta_metadata::Metadata;
+use crate::storages::delta::delta_metadata::Protocol;
+use crate::storages::delta::delta_metadata::MAX_READER_VERSION;
+
+/// The top level columns of the checkpoints needed to resolve the files.
+const CHECKPOINT_COLUMNS: [&str; 4] = ["add", "remove", "metaData", "protocol"];
+
+/// An action of the Delta log, a line of the commit files or a row of the checkpoints, which
+/// holds one of the fields.
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Action {
+    #[serde(default)]
+    pub add: Option<AddFile>,
+    #[serde(default)]
+    pub remove: Option<RemoveFile>,
+    #[serde(default)]
+    pub meta_data: Option<Metadata>,
+    #[serde(default)]
+    pub protocol: Option<Protocol>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AddFile {
+    /// The url encoded path, relative to the location of the table.
+    pub path: String,
+    /// The values of the partition columns, serialized as strings.
+    #[serde(default)]
+    pub partition_values: HashMap<String, Option<String>>,
+    pub size: i64,
+    /// The statistics of the file, serialized in json.
+    #[serde(default)]
+    pub stats: Option<String>,
+    #[serde(default)]
+    pub deletion_vector: Option<Value>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct RemoveFile {
+    pub path: String,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct FileStatistics {
+    #[serde(default)]
+    num_records: Option<u64>,
+}
+
+impl AddFile {
+    pub fn num_records(&self) -> Option<u64> {
+        let stats = self.stats.as_ref()?;
+        let stats = serde_json::from_str::<FileStatistics>(stats).ok()?;
+        stats.num_records
+    }
+
+    /// Parses the values of the partition columns, the missing ones are null.
+    pub fn partition_values(
+        &self,
+        types: &[(String, DeltaType)],
+    ) -> Result<BTreeMap<String, DataValue>> {
+        let mut values = BTreeMap::new();
+        for (column, column_type) in types {
+            let value = match self.partition_values.get(column) {
+                Some(Some(value)) => match column_type.parse(value) {
+                    Some(value) => value,
+                    None => {
+                        return Err(ErrorCode::BadBytes(format!(
+                            "Cannot parse the value {} of partition column {} as {:?}",
+                            value, column, column_type
+                        )));
+                    }
+                },
+                _ => DataValue::Null,
+            };
+            values.insert(column.clone(), value);
+        }
+        Ok(values)
+    }
+}
+
+/// A file of the `_delta_log` directory.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogFile {
+    /// `<version>.json`
+    Commit(i64),
+    /// `<version>.checkpoint.parquet`, or `<version>.checkpoint.<part>.<parts>.parquet` if the
+    /// checkpoint is written in several parts.
+    Checkpoint { version: i64, part: u32, parts: u32 },
+}
+
+impl LogFile {
+    pub fn parse(name: &str) -> Option<LogFile> {
+        if let Some(version) = name.strip_suffix(".json") {
+            return Some(LogFile::Commit(parse_number(version)?));
+        }
+
+        let name = name.strip_suffix(".parquet")?;
+        let mut parts = name.split('.');
+        let version = parse_number(parts.next()?)?;
+        if parts.next()? != "checkpoint" {
+            return None;
+        }
+        match (parts.next(), parts.next(), parts.next()) {
+            (None, _, _) => Some(LogFile::Checkpoint {
+                version,
+                part: 1,
+                parts: 1,
+            }),
+            (Some(part), Some(parts), None) => Some(LogFile::Checkpoint {
+                version,
+                part: parse_number(part)?,
+                parts: parse_number(parts)?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(digits: &str) -> Option<T> {
+    match !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+        true => digits.parse().ok(),
+        false => None,
+    }
+}
+
+/// The state of a Delta table at a version.
+#[derive(Debug, Clone)]
+pub struct DeltaSnapshot {
+    pub version: i64,
+    pub metadata: Metadata,
+    /// The data files of the version, by the paths.
+    pub files: BTreeMap<String, AddFile>,
+}
+
+/// Replays the actions of the checkpoint and the commits after it, in the order of the versions,
+/// into the snapshot of the last version.
+#[derive(Default)]
+pub struct DeltaLogReplay {
+    metadata: Option<Metadata>,
+    files: BTreeMap<String, AddFile>,
+}
+
+impl DeltaLogReplay {
+    pub fn apply(&mut self, action: Action) -> Result<()> {
+        if let Some(protocol) = action.protocol {
+            if protocol.min_reader_version > MAX_READER_VERSION {
+                return Err(ErrorCode::UnImplement(format!(
+                    "Cannot read the Delta table of reader version {}",
+                    protocol.min_reader_version
+                )));
+            }
+        }
+        if let Some(metadata) = action.meta_data {
+            self.metadata = Some(metadata);
+        }
+        if let Some(remove) = action.remove {
+            self.files.remove(&remove.path);
+        }
+        if let Some(add) = action.add {
+            if add.deletion_vector.is_some() {
+                return Err(ErrorCode::UnImplement(
+                    "Cannot read the Delta table with the deletion vectors",
+                ));
+            }
+            self.files.insert(add.path.clone(), add);
+        }
+        Ok(())
+    }
+
+    pub fn finish(self, version: i64) -> Result<DeltaSnapshot> {
+        match self.metadata {
+            Some(metadata) => Ok(DeltaSnapshot {
+                version,
+                metadata,
+                files: self.files,
+            }),
+            None => Err(ErrorCode::BadBytes(format!(
+                "No metadata is found in the Delta log of version {}",
+                version
+            ))),
+        }
+    }
+}
+
+/// Reads the actions of a commit file, one in each line.
+pub fn read_commit(data: Vec<u8>) -> Result<Vec<Action>> {
+    let data = String::from_utf8(data)?;
+    data.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line).map_err(|cause| {
+                ErrorCode::BadBytes(format!("Cannot read the Delta commit: {}", cause))
+            })
+        })
+        .collect()
+}
+
+/// Reads the actions of a checkpoint file, one in each row.
+///
+/// The rows are converted to json, as the lines of the commits, so they are deserialized the
+/// same way.
+pub fn read_checkpoint(data: Vec<u8>) -> Result<Vec<Action>> {
+    let mut reader = std::io::Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let schema = infer_schema(&metadata)?;
+    let fields = schema
+        .fields
+        .into_iter()
+        .filter(|field| CHECKPOINT_COLUMNS.contains(&field.name.as_str()))
+        .collect::<Vec<_>>();
+
+    let mut actions = vec![];
+    for row_group in &metadata.row_groups {
+        let num_rows = row_group.num_rows() as usize;
+        let columns = read_columns_many(&mut reader, row_group, fields.clone(), None)?;
+        for chunk in RowGroupDeserializer::new(columns, num_rows, None) {
+            let chunk = chunk?;
+            for row in 0..chunk.len() {
+                let mut action = serde_json::Map::new();
+                for (field, array) in fields.iter().zip(chunk.arrays()) {
+                    if !array.is_null(row) {
+                        action.insert(field.name.clone(), to_json(array.as_ref(), row)?);
+                    }
+                }
+                actions.push(
+                    serde_json::from_value(Value::Object(action)).map_err(|cause| {
+                        ErrorCode::BadBytes(format!("Cannot read the Delta checkpoint: {}", cause))
+                    })?,
+                );
+            }
+        }
+    }
+    Ok(actions)
+}
+
+/// Converts the value of the row to json, the values of the types not used by the actions
+/// are null.
+fn to_json(array: &dyn Array, row: usize) -> Result<Value> {
+    if array.is_null(row) {
+        return Ok(Value::Null);
+    }
+
+    match array.data_type().to_logical_type() {
+        ArrowType::Boolean => Ok(Value::from(downcast::<BooleanArray>(array)?.value(row))),
+        ArrowType::Int32 => Ok(Value::from(downcast::<Int32Array>(array)?.value(row))),
+        ArrowType::Int64 => Ok(Value::from(downcast::<Int64Array>(array)?.value(row))),
+        ArrowType::Utf8 => Ok(Value::from(downcast::<Utf8Array<i32>>(array)?.value(row))),
+        ArrowType::LargeUtf8 => Ok(Value::from(downcast::<Utf8Array<i64>>(array)?.value(row))),
+        ArrowType::List(_) => list_to_json(downcast::<ListArray<i32>>(array)?, row),
+        ArrowType::LargeList(_) => list_to_json(downcast::<ListArray<i64>>(array)?, row),
+        ArrowType::Struct(fields) => {
+            let values = downcast::<StructArray>(array)?.values();
+            let mut object = serde_json::Map::with_capacity(fields.len());
+            for (field, values) in fields.iter().zip(values) {
+                object.insert(field.name.clone(), to_json(values.as_ref(), row)?);
+            }
+            Ok(Value::Object(object))
+        }
+        ArrowType::Map(_, _) => {
+            let map = downcast::<MapArray>(array)?;
+            let entries = downcast::<StructArray>(map.field().as_ref())?.values();
+            let (start, end) = (map.offsets()[row], map.offsets()[row + 1]);
+            let mut object = serde_json::Map::with_capacity((end - start) as usize);
+            for entry in start as usize..end as usize {
+                if let Value::String(key) = to_json(entries[0].as_ref(), entry)? {
+                    object.insert(key, to_json(entries[1].as_ref(), entry)?);
+                }
+            }
+            Ok(Value::Object(object))
+        }
+        _ => Ok(Value::Null),
+    }
+}
+
+fn list_to_json<O: Offset>(list: &ListArray<O>, row: usize) -> Result<Value> {
+    let (start, end) = (
+        list.offsets()[row].to_usize(),
+        list.offsets()[row + 1].to_usize(),
+    );
+    let values = (start..end)
+        .map(|index| to_json(list.values().as_ref(), index))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Value::Array(values))
+}
+
+fn downcast<T: 'static>(array: &dyn Array) -> Result<&T> {
+    match array.as_any().downcast_ref::<T>() {
+        Some(array) => Ok(array),
+        None => Err(ErrorCode::BadBytes(format!(
+            "Cannot read the Delta checkpoint column of {:?}",
+            array.data_type()
+        ))),
+    }
+}
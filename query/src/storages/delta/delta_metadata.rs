@@ -0,0 +1,205 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// The highest version of the protocol to read, the later ones need the column mapping or the
+/// deletion vectors.
+pub const MAX_READER_VERSION: i32 = 1;
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Protocol {
+    pub min_reader_version: i32,
+    #[serde(default)]
+    pub min_writer_version: i32,
+}
+
+/// The metadata of a Delta table, only the fields needed to read the files are kept.
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+    pub id: String,
+    /// The schema of the table, which is serialized in json as a struct type of Spark.
+    pub schema_string: String,
+    #[serde(default)]
+    pub partition_columns: Vec<String>,
+    #[serde(default)]
+    pub format: Format,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct Format {
+    pub provider: String,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Format {
+            provider: "parquet".to_string(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct DeltaSchema {
+    pub fields: Vec<DeltaField>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct DeltaField {
+    pub name: String,
+    /// The name of a primitive type, or the object of a nested type.
+    #[serde(rename = "type")]
+    pub field_type: serde_json::Value,
+    #[serde(default = "default_nullable")]
+    pub nullable: bool,
+}
+
+fn default_nullable() -> bool {
+    true
+}
+
+impl Metadata {
+    pub fn schema(&self) -> Result<DeltaSchema> {
+        serde_json::from_str(&self.schema_string).map_err(|cause| {
+            ErrorCode::BadBytes(format!("Cannot read the schema of Delta table: {}", cause))
+        })
+    }
+
+    pub fn data_schema(&self) -> Result<DataSchemaRef> {
+        let schema = self.schema()?;
+        let mut fields = Vec::with_capacity(schema.fields.len());
+        for field in &schema.fields {
+            let data_type = DeltaType::try_create(&field.field_type)?.data_type();
+            fields.push(match field.nullable {
+                true => DataField::new_nullable(&field.name, data_type),
+                false => DataField::new(&field.name, data_type),
+            });
+        }
+        Ok(DataSchemaRefExt::create(fields))
+    }
+
+    /// The partition columns and the types of them.
+    pub fn partition_types(&self) -> Result<Vec<(String, DeltaType)>> {
+        let fields = self.schema()?.fields;
+        let mut types = Vec::with_capacity(self.partition_columns.len());
+        for column in &self.partition_columns {
+            let field = match fields.iter().find(|field| &field.name == column) {
+                Some(field) => field,
+                None => {
+                    return Err(ErrorCode::BadBytes(format!(
+                        "The partition column {} is not in the schema of Delta table",
+                        column
+                    )));
+                }
+            };
+            types.push((column.clone(), DeltaType::try_create(&field.field_type)?));
+        }
+        Ok(types)
+    }
+}
+
+/// The primitive types of Delta which can be read.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeltaType {
+    Boolean,
+    Byte,
+    Short,
+    Integer,
+    Long,
+    Float,
+    Double,
+    Date,
+    /// In microseconds.
+    Timestamp,
+    String,
+    Binary,
+}
+
+impl DeltaType {
+    pub fn try_create(field_type: &serde_json::Value) -> Result<DeltaType> {
+        let name = match field_type {
+            serde_json::Value::String(name) => name.as_str(),
+            other => {
+                return Err(ErrorCode::UnImplement(format!(
+                    "Unsupported Delta type: {}",
+                    other
+                )));
+            }
+        };
+        match name {
+            "boolean" => Ok(DeltaType::Boolean),
+            "byte" => Ok(DeltaType::Byte),
+            "short" => Ok(DeltaType::Short),
+            "integer" => Ok(DeltaType::Integer),
+            "long" => Ok(DeltaType::Long),
+            "float" => Ok(DeltaType::Float),
+            "double" => Ok(DeltaType::Double),
+            "date" => Ok(DeltaType::Date),
+            "timestamp" => Ok(DeltaType::Timestamp),
+            "string" => Ok(DeltaType::String),
+            "binary" => Ok(DeltaType::Binary),
+            _ => Err(ErrorCode::UnImplement(format!(
+                "Unsupported Delta type: {}",
+                name
+            ))),
+        }
+    }
+
+    pub fn data_type(&self) -> DataTypeImpl {
+        match self {
+            DeltaType::Boolean => BooleanType::new_impl(),
+            DeltaType::Byte => Int8Type::new_impl(),
+            DeltaType::Short => Int16Type::new_impl(),
+            DeltaType::Integer => Int32Type::new_impl(),
+            DeltaType::Long => Int64Type::new_impl(),
+            DeltaType::Float => Float32Type::new_impl(),
+            DeltaType::Double => Float64Type::new_impl(),
+            DeltaType::Date => DateType::new_impl(),
+            DeltaType::Timestamp => TimestampType::new_impl(6),
+            DeltaType::String | DeltaType::Binary => StringType::new_impl(),
+        }
+    }
+
+    /// Parses the partition value, which is serialized as a string in the log.
+    pub fn parse(&self, value: &str) -> Option<DataValue> {
+        match self {
+            DeltaType::Boolean => value.parse::<bool>().ok().map(DataValue::Boolean),
+            DeltaType::Byte | DeltaType::Short | DeltaType::Integer | DeltaType::Long => {
+                value.parse::<i64>().ok().map(DataValue::Int64)
+            }
+            DeltaType::Float | DeltaType::Double => {
+                value.parse::<f64>().ok().map(DataValue::Float64)
+            }
+            DeltaType::Date => {
+                let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+                let epoch = NaiveDate::from_ymd(1970, 1, 1);
+                Some(DataValue::Int64((date - epoch).num_days()))
+            }
+            DeltaType::Timestamp => {
+                let time = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f").ok()?;
+                let micros = time.timestamp() * 1_000_000 + time.timestamp_subsec_micros() as i64;
+                Some(DataValue::Int64(micros))
+            }
+            DeltaType::String | DeltaType::Binary => {
+                Some(DataValue::String(value.as_bytes().to_vec()))
+            }
+        }
+    }
+}
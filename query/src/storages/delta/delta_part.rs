@@ -0,0 +1,70 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::PartInfo;
+use common_planners::PartInfoPtr;
+
+/// A data file of the Delta table to read.
+#[derive(serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct DeltaPartInfo {
+    /// Relative to the root of the storage.
+    pub location: String,
+    pub size: u64,
+    /// The values of the partition columns, which are not kept in the data file.
+    pub partition_values: BTreeMap<String, DataValue>,
+}
+
+#[typetag::serde(name = "delta")]
+impl PartInfo for DeltaPartInfo {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn equals(&self, info: &Box<dyn PartInfo>) -> bool {
+        match info.as_any().downcast_ref::<DeltaPartInfo>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+impl DeltaPartInfo {
+    pub fn create(
+        location: String,
+        size: u64,
+        partition_values: BTreeMap<String, DataValue>,
+    ) -> PartInfoPtr {
+        Arc::new(Box::new(DeltaPartInfo {
+            location,
+            size,
+            partition_values,
+        }))
+    }
+
+    pub fn from_part(info: &PartInfoPtr) -> Result<&DeltaPartInfo> {
+        match info.as_any().downcast_ref::<DeltaPartInfo>() {
+            Some(part_ref) => Ok(part_ref),
+            None => Err(ErrorCode::LogicalError(
+                "Cannot downcast from PartInfo to DeltaPartInfo.",
+            )),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_planners::Extras;
+
+use crate::sessions::QueryContext;
+use crate::storages::delta::delta_metadata::Metadata;
+use crate::storages::index::ColumnStatistics;
+use crate::storages::index::ColumnsStatistics;
+use crate::storages::index::RangeFilter;
+
+/// Prunes the data files by the values of the partition columns, against the filter pushed
+/// down.
+pub struct DeltaPruner {
+    filter: Option<RangeFilter>,
+    /// The partition columns, and the indexes of them in the schema of the table.
+    columns: Vec<(u32, String)>,
+}
+
+impl DeltaPruner {
+    pub fn try_create(
+        ctx: &Arc<QueryContext>,
+        schema: DataSchemaRef,
+        metadata: &Metadata,
+        push_downs: &Option<Extras>,
+    ) -> Result<Self> {
+        let filter = match push_downs {
+            // for the time being, we only handle the first expr, as the fuse tables
+            Some(extras) if !extras.filters.is_empty() => Some(RangeFilter::try_create(
+                ctx.clone(),
+                &extras.filters[0],
+                schema.clone(),
+            )?),
+            _ => None,
+        };
+
+        let columns = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| metadata.partition_columns.contains(field.name()))
+            .map(|(index, field)| (index as u32, field.name().clone()))
+            .collect();
+        Ok(DeltaPruner { filter, columns })
+    }
+
+    pub fn keep_file(&self, partition_values: &BTreeMap<String, DataValue>) -> Result<bool> {
+        let filter = match &self.filter {
+            None => return Ok(true),
+            Some(filter) => filter,
+        };
+
+        let mut stats = ColumnsStatistics::new();
+        for (index, name) in &self.columns {
+            if let Some(value) = partition_values.get(name).filter(|value| !value.is_null()) {
+                stats.insert(*index, ColumnStatistics {
+                    min: value.clone(),
+                    max: value.clone(),
+                    null_count: 0,
+                    in_memory_size: 0,
+                    distinct_of_values: None,
+                    truncated: false,
+                });
+            }
+        }
+        filter.eval(&stats)
+    }
+}
@@ -0,0 +1,459 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Arc;
+
+use common_arrow::arrow::io::parquet::read::read_metadata;
+use common_datablocks::DataBlock;
+use common_datavalues::Column;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::Extras;
+use common_planners::PartInfoPtr;
+use common_planners::Partitions;
+use common_planners::ReadDataSourcePlan;
+use common_planners::Statistics;
+use common_streams::ParquetSourceBuilder;
+use common_streams::RowGroupFilter;
+use common_streams::SendableDataBlockStream;
+use common_streams::Source;
+use futures::io::Cursor;
+use futures::AsyncReadExt;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use opendal::ObjectMode;
+use opendal::Operator;
+use percent_encoding::percent_decode_str;
+
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::AsyncSource;
+use crate::pipelines::new::processors::AsyncSourcer;
+use crate::pipelines::new::NewPipeline;
+use crate::pipelines::new::SourcePipeBuilder;
+use crate::sessions::QueryContext;
+use crate::storages::delta::delta_log::read_checkpoint;
+use crate::storages::delta::delta_log::read_commit;
+use crate::storages::delta::delta_log::DeltaLogReplay;
+use crate::storages::delta::delta_log::DeltaSnapshot;
+use crate::storages::delta::delta_log::LogFile;
+use crate::storages::delta::delta_part::DeltaPartInfo;
+use crate::storages::delta::delta_pruning::DeltaPruner;
+use crate::storages::index::RowGroupRangeFilter;
+use crate::storages::StorageContext;
+use crate::storages::StorageDescription;
+use crate::storages::Table;
+
+pub const DELTA_ENGINE: &str = "DELTA";
+/// The table option of the location of the Delta table, relative to the root of the storage.
+pub const OPT_KEY_LOCATION: &str = "location";
+
+const DELTA_LOG: &str = "_delta_log";
+
+/// Reads the latest version of a Delta table in the storage, e.g.
+/// `CREATE TABLE t ENGINE = DELTA LOCATION = 'lake/t'`, of which the schema is the one of the
+/// Delta table if the columns are not given.
+///
+/// The log is replayed from the latest checkpoint in each query, so the commits of the other
+/// engines are seen. The data files are skipped by the values of the partition columns, which
+/// are filled into the blocks read, as they are not kept in the files.
+pub struct DeltaTable {
+    table_info: TableInfo,
+    location: String,
+}
+
+impl DeltaTable {
+    pub fn try_create(_ctx: StorageContext, table_info: TableInfo) -> Result<Box<dyn Table>> {
+        let location = Self::location(table_info.engine_options())?;
+        Ok(Box::new(DeltaTable {
+            table_info,
+            location,
+        }))
+    }
+
+    pub fn description() -> StorageDescription {
+        StorageDescription {
+            engine_name: DELTA_ENGINE.to_string(),
+            comment: "DELTA Storage Engine".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn location(options: &BTreeMap<String, String>) -> Result<String> {
+        match options.get(OPT_KEY_LOCATION) {
+            Some(location) => Ok(location.trim_matches('/').to_string()),
+            None => Err(ErrorCode::BadOption(
+                "Delta engine table missing location key",
+            )),
+        }
+    }
+
+    /// The schema of the latest version of the Delta table, for the table created without
+    /// columns.
+    pub async fn read_schema(
+        ctx: &QueryContext,
+        options: &BTreeMap<String, String>,
+    ) -> Result<DataSchemaRef> {
+        let location = Self::location(options)?;
+        let operator = ctx.get_storage_operator()?;
+        Self::read_snapshot(&operator, &location)
+            .await?
+            .metadata
+            .data_schema()
+    }
+
+    /// Replays the latest complete checkpoint, and the commits after it.
+    pub async fn read_snapshot(operator: &Operator, location: &str) -> Result<DeltaSnapshot> {
+        let dir = format!("{}/{}/", location, DELTA_LOG);
+        let mut commits = BTreeMap::new();
+        // The parts of the checkpoints, by the versions and the numbers of the parts.
+        let mut checkpoints = BTreeMap::new();
+        let mut objects = operator.object(&dir).list().await?;
+        while let Some(object) = objects.next().await {
+            let mut object = object?;
+            let meta = object.metadata_cached().await?;
+            if meta.mode() != ObjectMode::FILE {
+                continue;
+            }
+
+            let path = meta.path();
+            let name = path.rsplit('/').next().unwrap_or(path);
+            match LogFile::parse(name) {
+                Some(LogFile::Commit(version)) => {
+                    commits.insert(version, path.to_string());
+                }
+                Some(LogFile::Checkpoint {
+                    version,
+                    part,
+                    parts,
+                }) => {
+                    checkpoints
+                        .entry((version, parts))
+                        .or_insert_with(BTreeMap::new)
+                        .insert(part, path.to_string());
+                }
+                None => continue,
+            }
+        }
+
+        let mut replay = DeltaLogReplay::default();
+        let mut version = -1;
+        let checkpoint = checkpoints
+            .into_iter()
+            .rev()
+            .find(|((_, parts), files)| files.len() == *parts as usize);
+        if let Some(((checkpoint_version, _), files)) = checkpoint {
+            for path in files.values() {
+                for action in read_checkpoint(read_file(operator, path).await?)? {
+                    replay.apply(action)?;
+                }
+            }
+            version = checkpoint_version;
+        }
+
+        for (commit_version, path) in commits.range(version + 1..) {
+            if *commit_version != version + 1 {
+                return Err(ErrorCode::BadBytes(format!(
+                    "The commit {} of Delta table {} is missing",
+                    version + 1,
+                    location
+                )));
+            }
+            for action in read_commit(read_file(operator, path).await?)? {
+                replay.apply(action)?;
+            }
+            version = *commit_version;
+        }
+
+        match version {
+            -1 => Err(ErrorCode::StorageNotFound(format!(
+                "no log of Delta table is found in {}",
+                location
+            ))),
+            _ => replay.finish(version),
+        }
+    }
+
+    /// The path relative to the root of the storage, of the url encoded path relative to the
+    /// location of the Delta table.
+    fn file_location(&self, path: &str) -> Result<String> {
+        if path.contains("://") {
+            return Err(ErrorCode::UnImplement(format!(
+                "Cannot read the file {} out of the location of Delta table",
+                path
+            )));
+        }
+        let path = percent_decode_str(path).decode_utf8().map_err(|cause| {
+            ErrorCode::BadBytes(format!("Cannot decode the path {}: {}", path, cause))
+        })?;
+        Ok(format!(
+            "{}/{}",
+            self.location,
+            path.trim_start_matches('/')
+        ))
+    }
+
+    fn projected_schema(&self, push_downs: &Option<Extras>) -> DataSchemaRef {
+        let schema = self.table_info.schema();
+        match push_downs
+            .as_ref()
+            .and_then(|extras| extras.projection.as_ref())
+        {
+            Some(projection) => Arc::new(schema.project(projection.clone())),
+            None => schema,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for DeltaTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn benefit_column_prune(&self) -> bool {
+        true
+    }
+
+    async fn read_partitions(
+        &self,
+        ctx: Arc<QueryContext>,
+        push_downs: Option<Extras>,
+    ) -> Result<(Statistics, Partitions)> {
+        let operator = ctx.get_storage_operator()?;
+        let snapshot = Self::read_snapshot(&operator, &self.location).await?;
+        let metadata = &snapshot.metadata;
+        if !metadata.format.provider.eq_ignore_ascii_case("parquet") {
+            return Err(ErrorCode::UnImplement(format!(
+                "Cannot read the {} files of Delta table",
+                metadata.format.provider
+            )));
+        }
+
+        let partition_types = metadata.partition_types()?;
+        let pruner = DeltaPruner::try_create(&ctx, self.schema(), metadata, &push_downs)?;
+        let mut statistics = Statistics::default();
+        let mut parts = vec![];
+        for file in snapshot.files.values() {
+            let partition_values = file.partition_values(&partition_types)?;
+            if !pruner.keep_file(&partition_values)? {
+                continue;
+            }
+
+            statistics.read_rows += file.num_records().unwrap_or(0) as usize;
+            statistics.read_bytes += file.size as usize;
+            parts.push(DeltaPartInfo::create(
+                self.file_location(&file.path)?,
+                file.size as u64,
+                partition_values,
+            ));
+        }
+
+        statistics.partitions_scanned = parts.len();
+        statistics.partitions_total = snapshot.files.len();
+        Ok((statistics, parts))
+    }
+
+    async fn read(
+        &self,
+        ctx: Arc<QueryContext>,
+        plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let schema = self.projected_schema(&plan.push_downs);
+        let filter = RowGroupRangeFilter::try_create(&ctx, self.schema(), &plan.push_downs)?;
+        let reader = DeltaFileReader::create(&ctx, schema, filter)?;
+        let iter = std::iter::from_fn(move || match ctx.clone().try_get_partitions(1) {
+            Err(_) => None,
+            Ok(parts) if parts.is_empty() => None,
+            Ok(parts) => Some(parts),
+        })
+        .flatten();
+
+        let stream = futures::stream::iter(iter)
+            .then(move |part| {
+                let reader = reader.clone();
+                async move { reader.read(part).await }
+            })
+            .map_ok(|blocks| futures::stream::iter(blocks.into_iter().map(Ok)))
+            .try_flatten();
+        Ok(Box::pin(stream))
+    }
+
+    fn read2(
+        &self,
+        ctx: Arc<QueryContext>,
+        plan: &ReadDataSourcePlan,
+        pipeline: &mut NewPipeline,
+    ) -> Result<()> {
+        let schema = self.projected_schema(&plan.push_downs);
+        let filter = RowGroupRangeFilter::try_create(&ctx, self.schema(), &plan.push_downs)?;
+        let reader = DeltaFileReader::create(&ctx, schema, filter)?;
+        let max_threads = ctx.get_settings().get_max_threads()? as usize;
+        let max_threads = std::cmp::min(plan.parts.len(), max_threads);
+
+        let mut source_builder = SourcePipeBuilder::create();
+        for _index in 0..std::cmp::max(1, max_threads) {
+            let output = OutputPort::create();
+            source_builder.add_source(
+                output.clone(),
+                DeltaSource::create(ctx.clone(), output, reader.clone())?,
+            );
+        }
+
+        pipeline.add_pipe(source_builder.finalize());
+        Ok(())
+    }
+}
+
+async fn read_file(operator: &Operator, location: &str) -> Result<Vec<u8>> {
+    let mut data = vec![];
+    operator
+        .object(location)
+        .reader()
+        .await?
+        .read_to_end(&mut data)
+        .await?;
+    Ok(data)
+}
+
+/// Reads the blocks of the data files, a block for each row group.
+#[derive(Clone)]
+struct DeltaFileReader {
+    operator: Operator,
+    schema: DataSchemaRef,
+    row_group_filter: Option<Arc<dyn RowGroupFilter>>,
+}
+
+impl DeltaFileReader {
+    fn create(
+        ctx: &QueryContext,
+        schema: DataSchemaRef,
+        row_group_filter: Option<Arc<dyn RowGroupFilter>>,
+    ) -> Result<DeltaFileReader> {
+        Ok(DeltaFileReader {
+            operator: ctx.get_storage_operator()?,
+            schema,
+            row_group_filter,
+        })
+    }
+
+    async fn read(&self, part: PartInfoPtr) -> Result<Vec<DataBlock>> {
+        let part = DeltaPartInfo::from_part(&part)?;
+        let data = read_file(&self.operator, &part.location).await?;
+
+        let file_fields = self
+            .schema
+            .fields()
+            .iter()
+            .filter(|field| !part.partition_values.contains_key(field.name()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let (num_rows, file_blocks) = match file_fields.is_empty() {
+            // Only the partition columns are read, the rows are counted by the row groups.
+            true => {
+                let metadata = read_metadata(&mut std::io::Cursor::new(data))?;
+                let row_groups = metadata.row_groups.iter();
+                let num_rows = row_groups.map(|row_group| row_group.num_rows() as usize);
+                (num_rows.collect::<Vec<_>>(), vec![])
+            }
+            false => {
+                let file_schema = DataSchemaRefExt::create(file_fields);
+                let mut builder = ParquetSourceBuilder::create(file_schema);
+                if let Some(filter) = &self.row_group_filter {
+                    builder.row_group_filter(filter.clone());
+                }
+                let mut source = builder.build(Cursor::new(data))?;
+                let mut blocks = vec![];
+                while let Some(block) = source.read().await? {
+                    blocks.push(block);
+                }
+                (
+                    blocks.iter().map(|block| block.num_rows()).collect(),
+                    blocks,
+                )
+            }
+        };
+
+        let mut blocks = Vec::with_capacity(num_rows.len());
+        for (index, num_rows) in num_rows.into_iter().enumerate() {
+            let mut columns = Vec::with_capacity(self.schema.num_fields());
+            for field in self.schema.fields() {
+                let column = match part.partition_values.get(field.name()) {
+                    Some(value) => {
+                        let column = field.data_type().create_constant_column(value, num_rows)?;
+                        column.convert_full_column()
+                    }
+                    None => file_blocks[index].try_column_by_name(field.name())?.clone(),
+                };
+                columns.push(column);
+            }
+            blocks.push(DataBlock::create(self.schema.clone(), columns));
+        }
+        Ok(blocks)
+    }
+}
+
+struct DeltaSource {
+    ctx: Arc<QueryContext>,
+    reader: DeltaFileReader,
+    blocks: VecDeque<DataBlock>,
+}
+
+impl DeltaSource {
+    fn create(
+        ctx: Arc<QueryContext>,
+        output: Arc<OutputPort>,
+        reader: DeltaFileReader,
+    ) -> Result<ProcessorPtr> {
+        AsyncSourcer::create(ctx.clone(), output, DeltaSource {
+            ctx,
+            reader,
+            blocks: VecDeque::new(),
+        })
+    }
+}
+
+impl AsyncSource for DeltaSource {
+    const NAME: &'static str = "DeltaSource";
+
+    type BlockFuture<'a> = impl Future<Output = Result<Option<DataBlock>>>;
+
+    fn generate(&mut self) -> Self::BlockFuture<'_> {
+        async {
+            loop {
+                if let Some(block) = self.blocks.pop_front() {
+                    return Ok(Some(block));
+                }
+
+                let mut parts = self.ctx.try_get_partitions(1)?;
+                match parts.pop() {
+                    None => return Ok(None),
+                    Some(part) => self.blocks = self.reader.read(part).await?.into(),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod delta_log;
+mod delta_metadata;
+mod delta_part;
+mod delta_pruning;
+mod delta_table;
+
+pub use delta_log::read_checkpoint;
+pub use delta_log::read_commit;
+pub use delta_log::Action;
+pub use delta_log::AddFile;
+pub use delta_log::DeltaLogReplay;
+pub use delta_log::DeltaSnapshot;
+pub use delta_log::LogFile;
+pub use delta_metadata::DeltaType;
+pub use delta_metadata::Metadata;
+pub use delta_part::DeltaPartInfo;
+pub use delta_pruning::DeltaPruner;
+pub use delta_table::DeltaTable;
+pub use delta_table::DELTA_ENGINE;
@@ -0,0 +1,115 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::io::Read;
+use std::sync::Arc;
+
+use common_cache::LruDiskCache;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_tracing::tracing;
+
+use crate::storages::fuse::cache::CacheDeferMetrics;
+use crate::storages::fuse::cache::TenantLabel;
+
+/// A cache of the data read from the storage, e.g. the column chunks of the blocks, kept in
+/// the files under a local directory.
+///
+/// The least recently used files are evicted, once the total size of the files exceeds the
+/// capacity. Entries are never updated, since the objects of the tables are immutable.
+#[derive(Clone)]
+pub struct TableDiskCache {
+    inner: Arc<Mutex<LruDiskCache>>,
+    tenant_id: String,
+    cluster_id: String,
+}
+
+impl TableDiskCache {
+    /// Creates the cache in the directory `root`, the files left there by the previous runs
+    /// are kept, unless they exceed the `capacity` in bytes.
+    pub fn try_create(
+        root: &str,
+        capacity: u64,
+        tenant_id: impl Into<String>,
+        cluster_id: impl Into<String>,
+    ) -> Result<Self> {
+        Ok(Self {
+            inner: Arc::new(Mutex::new(LruDiskCache::new(root, capacity)?)),
+            tenant_id: tenant_id.into(),
+            cluster_id: cluster_id.into(),
+        })
+    }
+
+    /// Key of the column chunk at the range [offset, offset + length) of the block
+    pub fn block_chunk_key(location: &str, offset: u64, length: u64) -> String {
+        format!(
+            "block/{}/{}_{}",
+            location.trim_start_matches('/'),
+            offset,
+            length
+        )
+    }
+
+    /// Returns the cached data, `None` if it is not cached or can not be read.
+    pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+        // the file is read after the lock is released, a file evicted meanwhile is still
+        // readable through the opened handle
+        let file = self.inner.lock().get_file(key);
+        let data = file.ok().and_then(|mut file| {
+            let mut data = vec![];
+            match file.read_to_end(&mut data) {
+                Ok(_) => Some(data),
+                Err(e) => {
+                    tracing::warn!("failed to read {} from the disk cache: {}", key, e);
+                    None
+                }
+            }
+        });
+        if let Some(data) = &data {
+            self.record(true, data.len());
+        }
+        data
+    }
+
+    /// Caches the data which is read from the storage, since it is missed by `get`.
+    ///
+    /// Failures are only logged, as the data could always be read from the storage again.
+    pub fn put(&self, key: &str, data: &[u8]) {
+        self.record(false, data.len());
+        if let Err(e) = self.inner.lock().insert_bytes(key, data) {
+            tracing::warn!("failed to write {} to the disk cache: {}", key, e);
+        }
+    }
+
+    /// Total size of the cached files in bytes
+    pub fn size(&self) -> u64 {
+        self.inner.lock().size()
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.inner.lock().contains_key(key)
+    }
+
+    fn record(&self, cache_hit: bool, read_bytes: usize) {
+        // the metrics are recorded when dropped
+        drop(CacheDeferMetrics {
+            tenant_label: TenantLabel {
+                tenant_id: self.tenant_id.clone(),
+                cluster_id: self.cluster_id.clone(),
+            },
+            cache_hit,
+            read_bytes: read_bytes as u64,
+        });
+    }
+}
@@ -0,0 +1,205 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::ColumnId;
+use crate::storages::fuse::FUSE_OPT_KEY_CLUSTER_ORDER;
+
+/// How the rows of the blocks are ordered by the cluster keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClusterOrder {
+    /// Ordered by the keys one after another, the default.
+    Linear,
+    /// Ordered by the z-order curve of the keys, which interleaves the bits of them, thus the
+    /// rows close in all of the keys are kept together, rather than only in the leading one.
+    ZOrder,
+}
+
+impl FromStr for ClusterOrder {
+    type Err = ErrorCode;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "linear" => Ok(ClusterOrder::Linear),
+            "zorder" => Ok(ClusterOrder::ZOrder),
+            _ => Err(ErrorCode::BadOption(format!(
+                "invalid {} '{}', expecting LINEAR or ZORDER",
+                FUSE_OPT_KEY_CLUSTER_ORDER, s
+            ))),
+        }
+    }
+}
+
+impl ClusterOrder {
+    /// Resolves the order specified by the table `options`.
+    ///
+    /// The keys of the z-order must be columns, of which the values are ordered by their bits,
+    /// i.e. numbers, dates, timestamps, strings and booleans.
+    pub fn try_create(
+        options: &BTreeMap<String, String>,
+        order_keys: &[Expression],
+        schema: &DataSchema,
+    ) -> Result<ClusterOrder> {
+        let order = match options.get(FUSE_OPT_KEY_CLUSTER_ORDER) {
+            Some(value) => value.parse::<ClusterOrder>()?,
+            None => return Ok(ClusterOrder::Linear),
+        };
+        if order == ClusterOrder::Linear {
+            return Ok(order);
+        }
+        if order_keys.is_empty() {
+            return Err(ErrorCode::BadOption(format!(
+                "{} requires the table to be clustered",
+                FUSE_OPT_KEY_CLUSTER_ORDER
+            )));
+        }
+        for key in order_keys {
+            let field = match key {
+                Expression::Column(name) => schema.field_with_name(name)?,
+                _ => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "the cluster keys of the z-order must be columns, but {} is not",
+                        key.column_name()
+                    )));
+                }
+            };
+            let data_type = remove_nullable(field.data_type());
+            let type_id = data_type.data_type_id();
+            if !(type_id.is_numeric()
+                || type_id.is_date_or_date_time()
+                || type_id.is_string()
+                || type_id == TypeID::Boolean)
+            {
+                return Err(ErrorCode::BadOption(format!(
+                    "column {} of the z-order can not be of {}",
+                    field.name(),
+                    data_type.name()
+                )));
+            }
+        }
+        Ok(order)
+    }
+}
+
+/// The key which the blocks of a table are clustered by, of which the ranges in the blocks
+/// are derived from the statistics of the columns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClusterKey {
+    /// The leading cluster key, which is a column.
+    Column(ColumnId),
+    /// The z-order of the cluster keys, which are columns.
+    ZOrder(Vec<ColumnId>),
+}
+
+impl ClusterKey {
+    /// The [min, max] of the key in the block, None if the statistics are not kept.
+    ///
+    /// The z-values are `DataValue::String`s. Since the z-order is monotonic in each of the
+    /// keys, the z-values of the mins and of the maxs of the columns bound the block. NULLs
+    /// are the max of the z-order, so are the maxs of the columns having any NULLs.
+    pub fn range_of(&self, block: &BlockMeta) -> Option<(DataValue, DataValue)> {
+        match self {
+            ClusterKey::Column(id) => block
+                .col_stats
+                .get(id)
+                .map(|s| (s.min.clone(), s.max.clone())),
+            ClusterKey::ZOrder(ids) => {
+                let mut mins = Vec::with_capacity(ids.len());
+                let mut maxs = Vec::with_capacity(ids.len());
+                for id in ids {
+                    // the min is NULL if all the values of the column are
+                    let stats = block.col_stats.get(id)?;
+                    mins.push(ordinal_of(&stats.min));
+                    maxs.push(match stats.null_count {
+                        0 => ordinal_of(&stats.max),
+                        _ => u64::MAX,
+                    });
+                }
+                Some((
+                    DataValue::String(z_value(&mins)),
+                    DataValue::String(z_value(&maxs)),
+                ))
+            }
+        }
+    }
+}
+
+/// Maps a value to an integer of the same order as the values of its type, NULL to the max.
+///
+/// Strings are mapped by their leading 8 bytes, thus the strings of the same leading bytes
+/// are mapped to the same integer.
+pub fn ordinal_of(value: &DataValue) -> u64 {
+    match value {
+        DataValue::Boolean(v) => *v as u64,
+        DataValue::Int64(v) => (*v as u64) ^ (1 << 63),
+        DataValue::UInt64(v) => *v,
+        DataValue::Float64(v) => {
+            let bits = v.to_bits();
+            match bits >> 63 {
+                0 => bits | (1 << 63),
+                _ => !bits,
+            }
+        }
+        DataValue::String(v) => {
+            let mut bytes = [0u8; 8];
+            let len = v.len().min(8);
+            bytes[..len].copy_from_slice(&v[..len]);
+            u64::from_be_bytes(bytes)
+        }
+        DataValue::Null => u64::MAX,
+        _ => 0,
+    }
+}
+
+/// Interleaves the bits of the ordinals, from the most significant ones, of which the bytes
+/// are compared in the z-order.
+pub fn z_value(ordinals: &[u64]) -> Vec<u8> {
+    let mut bytes = vec![0u8; ordinals.len() * 8];
+    let mut pos = 0;
+    for bit in (0..64).rev() {
+        for ordinal in ordinals {
+            if (ordinal >> bit) & 1 == 1 {
+                bytes[pos / 8] |= 0x80 >> (pos % 8);
+            }
+            pos += 1;
+        }
+    }
+    bytes
+}
+
+/// The indices of the rows of the columns, in the z-order of them.
+pub fn zorder_indices(columns: &[ColumnRef]) -> Vec<u32> {
+    let rows = columns.first().map_or(0, |c| c.len());
+    let z_values = (0..rows)
+        .map(|row| {
+            let ordinals = columns
+                .iter()
+                .map(|c| ordinal_of(&c.get(row)))
+                .collect::<Vec<_>>();
+            z_value(&ordinals)
+        })
+        .collect::<Vec<_>>();
+    let mut indices = (0..rows as u32).collect::<Vec<_>>();
+    indices.sort_by(|l, r| z_values[*l as usize].cmp(&z_values[*r as usize]));
+    indices
+}
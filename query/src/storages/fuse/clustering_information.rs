@@ -0,0 +1,168 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use super::meta::BlockMeta;
+use super::statistics::histogram::compare_values;
+use super::ClusterKey;
+use super::FuseTable;
+use crate::sessions::QueryContext;
+
+pub struct ClusteringInformation<'a> {
+    pub ctx: Arc<QueryContext>,
+    pub table: &'a FuseTable,
+}
+
+/// How well the blocks of a table are clustered by the cluster key, see [ClusterKey].
+///
+/// The range of a block is the [min, max] of the cluster key in it. The depth of a point of
+/// the key domain is the number of the blocks whose ranges contain the point, and two blocks
+/// overlap if their ranges intersect. The smaller the depth and the overlaps are, the better
+/// the table is clustered.
+#[derive(Debug, Default, PartialEq)]
+pub struct ClusteringStatistics {
+    pub total_block_count: u64,
+    /// blocks of which the min and max of the cluster key are equal
+    pub constant_block_count: u64,
+    /// average number of the other blocks that a block overlaps with
+    pub average_overlaps: f64,
+    /// average depth of the end points of the block ranges
+    pub average_depth: f64,
+    /// depth => number of the end points at that depth
+    pub block_depth_histogram: BTreeMap<u64, u64>,
+}
+
+impl<'a> ClusteringInformation<'a> {
+    pub fn new(ctx: Arc<QueryContext>, table: &'a FuseTable) -> Self {
+        Self { ctx, table }
+    }
+
+    pub async fn get_clustering_info(&self) -> Result<DataBlock> {
+        let tbl = self.table;
+        let cluster_key = tbl.cluster_key().ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "table {} is not clustered by a column",
+                tbl.table_info.name
+            ))
+        })?;
+
+        let blocks = match tbl.read_table_snapshot(self.ctx.as_ref()).await? {
+            Some(snapshot) => {
+                FuseTable::blocks_of_segments_in_order(self.ctx.as_ref(), &snapshot.segments)
+                    .await?
+            }
+            None => vec![],
+        };
+        let stats = Self::clustering_statistics(&blocks, &cluster_key);
+        let cluster_by_keys = tbl
+            .order_keys
+            .iter()
+            .map(|k| k.column_name())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let histogram = serde_json::to_string(&stats.block_depth_histogram)?;
+
+        Ok(DataBlock::create(ClusteringInformation::schema(), vec![
+            Series::from_data(vec![format!("({})", cluster_by_keys).into_bytes()]),
+            Series::from_data(vec![stats.total_block_count]),
+            Series::from_data(vec![stats.constant_block_count]),
+            Series::from_data(vec![stats.average_overlaps]),
+            Series::from_data(vec![stats.average_depth]),
+            Series::from_data(vec![histogram.into_bytes()]),
+        ]))
+    }
+
+    /// Computes the statistics from the ranges of the cluster key in the block metas.
+    ///
+    /// Blocks without comparable min/max of the key are counted, but do not contribute
+    /// to the overlaps and depths.
+    pub fn clustering_statistics(
+        blocks: &[BlockMeta],
+        cluster_key: &ClusterKey,
+    ) -> ClusteringStatistics {
+        let mut ranges = blocks
+            .iter()
+            .filter_map(|b| cluster_key.range_of(b))
+            .filter(|(min, max)| compare_values(min, max).is_some())
+            .collect::<Vec<_>>();
+
+        let mut stats = ClusteringStatistics {
+            total_block_count: blocks.len() as u64,
+            constant_block_count: ranges
+                .iter()
+                .filter(|(min, max)| cmp(min, max) == Ordering::Equal)
+                .count() as u64,
+            ..Default::default()
+        };
+        if ranges.is_empty() {
+            return stats;
+        }
+
+        // overlaps: sweep the ranges ordered by min
+        ranges.sort_by(|l, r| cmp(&l.0, &r.0));
+        let mut overlaps = 0u64;
+        for (i, (_, max)) in ranges.iter().enumerate() {
+            overlaps += ranges[i + 1..]
+                .iter()
+                .take_while(|(min, _)| cmp(min, max) != Ordering::Greater)
+                .count() as u64;
+        }
+        // each overlapping pair is counted for both of the blocks
+        stats.average_overlaps = (overlaps * 2) as f64 / ranges.len() as f64;
+
+        // depths: number of ranges which contain the end points
+        let mut mins = ranges.iter().map(|r| &r.0).collect::<Vec<_>>();
+        let mut maxs = ranges.iter().map(|r| &r.1).collect::<Vec<_>>();
+        mins.sort_by(|l, r| cmp(l, r));
+        maxs.sort_by(|l, r| cmp(l, r));
+        let mut points = mins.iter().chain(maxs.iter()).copied().collect::<Vec<_>>();
+        points.sort_by(|l, r| cmp(l, r));
+        points.dedup_by(|l, r| cmp(l, r) == Ordering::Equal);
+
+        let mut total_depth = 0u64;
+        for point in &points {
+            let started = mins.partition_point(|v| cmp(v, point) != Ordering::Greater);
+            let ended = maxs.partition_point(|v| cmp(v, point) == Ordering::Less);
+            let depth = (started - ended) as u64;
+            total_depth += depth;
+            *stats.block_depth_histogram.entry(depth).or_default() += 1;
+        }
+        stats.average_depth = total_depth as f64 / points.len() as f64;
+        stats
+    }
+
+    pub fn schema() -> Arc<DataSchema> {
+        DataSchemaRefExt::create(vec![
+            DataField::new("cluster_by_keys", Vu8::to_data_type()),
+            DataField::new("total_block_count", u64::to_data_type()),
+            DataField::new("constant_block_count", u64::to_data_type()),
+            DataField::new("average_overlaps", f64::to_data_type()),
+            DataField::new("average_depth", f64::to_data_type()),
+            DataField::new("block_depth_histogram", Vu8::to_data_type()),
+        ])
+    }
+}
+
+fn cmp(l: &DataValue, r: &DataValue) -> Ordering {
+    compare_values(l, r).unwrap_or(Ordering::Equal)
+}
@@ -0,0 +1,158 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataValue;
+use common_exception::Result;
+
+use crate::storages::fuse::meta::ColumnId;
+use crate::storages::fuse::FUSE_SCHEMA_KEY_COLUMN_IDS;
+use crate::storages::fuse::FUSE_SCHEMA_KEY_MISSING_VALUE_PREFIX;
+use crate::storages::fuse::FUSE_SCHEMA_KEY_NEXT_COLUMN_ID;
+
+/// The ids of the columns of a fuse table, by which the columns are kept in the blocks.
+///
+/// A column keeps its id while the table is altered, a column being added gets a new id, and
+/// the ids of the dropped columns are never reused. So that the blocks written before a column
+/// is added or dropped can still be read by the schema after: the columns missing in the blocks
+/// are filled with the values recorded when they are added, and the dropped ones are ignored.
+///
+/// The ids are kept in the metadata of the schema. The tables that have never been altered have
+/// no ids recorded, the id of a column of them is the index of the column.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColumnIds {
+    ids: Vec<ColumnId>,
+    next_id: ColumnId,
+}
+
+impl ColumnIds {
+    pub fn from_schema(schema: &DataSchema) -> Self {
+        let meta = schema.meta();
+        let mut ids = match meta.get(FUSE_SCHEMA_KEY_COLUMN_IDS) {
+            Some(ids) => ids.split(',').filter_map(|id| id.parse().ok()).collect(),
+            None => vec![],
+        };
+        let mut next_id = meta
+            .get(FUSE_SCHEMA_KEY_NEXT_COLUMN_ID)
+            .and_then(|id| id.parse().ok())
+            .unwrap_or_default();
+
+        // the columns without recorded ids, e.g. those of a table never altered
+        while ids.len() < schema.num_fields() {
+            next_id = next_id.max(ids.len() as ColumnId);
+            ids.push(next_id);
+            next_id += 1;
+        }
+        Self { ids, next_id }
+    }
+
+    /// The id of the column of `index` in the schema
+    pub fn id_of(&self, index: usize) -> ColumnId {
+        self.ids[index]
+    }
+
+    /// The index of the column of `id` in the schema, `None` if the column has been dropped
+    pub fn index_of(&self, id: ColumnId) -> Option<usize> {
+        self.ids.iter().position(|c| *c == id)
+    }
+
+    /// Whether the id of each column is the index of it, as the ones of the tables never
+    /// altered are.
+    pub fn is_identity(&self) -> bool {
+        self.ids
+            .iter()
+            .enumerate()
+            .all(|(idx, id)| idx as ColumnId == *id)
+    }
+
+    /// Re-keys the items of the columns by their ids, instead of their indices.
+    pub fn by_ids<T>(&self, items: HashMap<ColumnId, T>) -> HashMap<ColumnId, T> {
+        if self.is_identity() {
+            return items;
+        }
+        items
+            .into_iter()
+            .filter(|(idx, _)| (*idx as usize) < self.ids.len())
+            .map(|(idx, item)| (self.id_of(idx as usize), item))
+            .collect()
+    }
+
+    /// Re-keys the items of the columns by their indices, instead of their ids. The items of
+    /// the dropped columns are left out.
+    pub fn by_indices<T: Clone>(&self, items: &HashMap<ColumnId, T>) -> HashMap<ColumnId, T> {
+        if self.is_identity() {
+            return items
+                .iter()
+                .filter(|(id, _)| (**id as usize) < self.ids.len())
+                .map(|(id, item)| (*id, item.clone()))
+                .collect();
+        }
+        items
+            .iter()
+            .filter_map(|(id, item)| Some((self.index_of(*id)? as ColumnId, item.clone())))
+            .collect()
+    }
+
+    /// Assigns a new id to the column appended to the schema.
+    pub fn add_column(&mut self) -> ColumnId {
+        let id = self.next_id;
+        self.ids.push(id);
+        self.next_id += 1;
+        id
+    }
+
+    /// Removes the column of `index` from the schema, the id of it is not to be used again.
+    pub fn drop_column(&mut self, index: usize) -> ColumnId {
+        self.ids.remove(index)
+    }
+
+    /// Records the ids into the metadata of a schema.
+    pub fn write_to(&self, meta: &mut BTreeMap<String, String>) {
+        let ids = self
+            .ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        meta.insert(FUSE_SCHEMA_KEY_COLUMN_IDS.to_owned(), ids);
+        meta.insert(
+            FUSE_SCHEMA_KEY_NEXT_COLUMN_ID.to_owned(),
+            self.next_id.to_string(),
+        );
+    }
+
+    /// The values of the columns which are missing in the blocks written before the columns
+    /// are added, by the indices of the columns.
+    pub fn missing_values(&self, schema: &DataSchema) -> Result<HashMap<usize, DataValue>> {
+        let mut values = HashMap::new();
+        for (key, value) in schema.meta() {
+            let id = key
+                .strip_prefix(FUSE_SCHEMA_KEY_MISSING_VALUE_PREFIX)
+                .and_then(|id| id.parse().ok());
+            if let Some(index) = id.and_then(|id| self.index_of(id)) {
+                values.insert(index, serde_json::from_str(value)?);
+            }
+        }
+        Ok(values)
+    }
+
+    /// The key of the schema metadata, which keeps the missing value of the column of `id`
+    pub fn missing_value_key(id: ColumnId) -> String {
+        format!("{}{}", FUSE_SCHEMA_KEY_MISSING_VALUE_PREFIX, id)
+    }
+}
@@ -12,14 +12,42 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+/// Prefix of the keys of the options, which keep the definitions of the aggregating indexes
+pub const FUSE_OPT_KEY_AGGREGATING_INDEX_PREFIX: &str = "aggregating_index.";
 pub const FUSE_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD: &str = "block_size_threshold";
 pub const FUSE_OPT_KEY_BLOCK_PER_SEGMENT: &str = "block_per_segment";
+/// How the rows are ordered by the cluster keys, `linear` (the default) or `zorder`
+pub const FUSE_OPT_KEY_CLUSTER_ORDER: &str = "cluster_order";
+/// Rows older than the retention period, e.g. '30 days', are expired by `OPTIMIZE TABLE EXPIRE`
+pub const FUSE_OPT_KEY_DATA_RETENTION: &str = "data_retention";
+/// The DATE or TIMESTAMP column which tells the ages of the rows
+pub const FUSE_OPT_KEY_DATA_RETENTION_COLUMN: &str = "data_retention_column";
+/// Max number of the blocks rewritten by a round of reclustering
+pub const FUSE_OPT_KEY_RECLUSTER_BLOCK_LIMIT: &str = "recluster_block_limit";
 pub const FUSE_OPT_KEY_ROW_PER_BLOCK: &str = "row_per_block";
+/// The min and the max of the strings longer than it, in bytes, are kept as their prefixes
+pub const FUSE_OPT_KEY_STATS_STRING_PREFIX_LENGTH: &str = "stats_string_prefix_length";
+/// Prefix of the keys of the options, which keep the definitions of the virtual columns
+pub const FUSE_OPT_KEY_VIRTUAL_COLUMN_PREFIX: &str = "virtual_column.";
+
+/// Keys of the metadata of the schema, which keep the ids of the columns
+pub const FUSE_SCHEMA_KEY_COLUMN_IDS: &str = "fuse.column_ids";
+pub const FUSE_SCHEMA_KEY_NEXT_COLUMN_ID: &str = "fuse.next_column_id";
+/// Prefix of the keys of the metadata of the schema, which keep the values of the added
+/// columns in the blocks written before the columns are added
+pub const FUSE_SCHEMA_KEY_MISSING_VALUE_PREFIX: &str = "fuse.missing_value.";
 
 pub const FUSE_TBL_BLOCK_PREFIX: &str = "_b";
 pub const FUSE_TBL_SEGMENT_PREFIX: &str = "_sg";
 pub const FUSE_TBL_SNAPSHOT_PREFIX: &str = "_ss";
+pub const FUSE_TBL_SNAPSHOT_STATISTICS_PREFIX: &str = "_ts";
+pub const FUSE_TBL_DELETION_VECTOR_PREFIX: &str = "_dv";
+pub const FUSE_TBL_AGGREGATING_INDEX_PREFIX: &str = "_i";
+pub const FUSE_TBL_VIRTUAL_BLOCK_PREFIX: &str = "_vb";
+pub const FUSE_TBL_PURGE_CHECKPOINT: &str = "_purge_checkpoint";
 
 pub const DEFAULT_BLOCK_PER_SEGMENT: usize = 1000;
 pub const DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD: usize = 100 * 1024 * 1024;
 pub const DEFAULT_ROW_PER_BLOCK: usize = 1000 * 1000;
+pub const DEFAULT_RECLUSTER_BLOCK_LIMIT: usize = 16;
+pub const DEFAULT_STATS_STRING_PREFIX_LENGTH: usize = 256;
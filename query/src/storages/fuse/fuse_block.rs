@@ -0,0 +1,91 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use super::meta::TableSnapshot;
+use super::FuseTable;
+use crate::sessions::QueryContext;
+
+pub struct FuseBlock<'a> {
+    pub ctx: Arc<QueryContext>,
+    pub table: &'a FuseTable,
+}
+
+impl<'a> FuseBlock<'a> {
+    pub fn new(ctx: Arc<QueryContext>, table: &'a FuseTable) -> Self {
+        Self { ctx, table }
+    }
+
+    pub async fn get_blocks(&self) -> Result<DataBlock> {
+        match self.table.read_table_snapshot(self.ctx.as_ref()).await? {
+            Some(snapshot) => self.blocks_to_block(&snapshot).await,
+            None => Ok(DataBlock::empty_with_schema(FuseBlock::schema())),
+        }
+    }
+
+    async fn blocks_to_block(&self, snapshot: &TableSnapshot) -> Result<DataBlock> {
+        let blocks =
+            FuseTable::blocks_of_segments_in_order(self.ctx.as_ref(), &snapshot.segments).await?;
+        let len = blocks.len();
+        let snapshot_id = snapshot.snapshot_id.to_simple().to_string().into_bytes();
+        let mut snapshot_ids: Vec<Vec<u8>> = Vec::with_capacity(len);
+        let mut block_locations: Vec<Vec<u8>> = Vec::with_capacity(len);
+        let mut block_sizes: Vec<u64> = Vec::with_capacity(len);
+        let mut file_sizes: Vec<u64> = Vec::with_capacity(len);
+        let mut row_counts: Vec<u64> = Vec::with_capacity(len);
+        let mut created_by: Vec<Option<Vec<u8>>> = Vec::with_capacity(len);
+        let mut created_on: Vec<Option<i64>> = Vec::with_capacity(len);
+        for block in blocks {
+            snapshot_ids.push(snapshot_id.clone());
+            block_locations.push(block.location.0.into_bytes());
+            block_sizes.push(block.block_size);
+            file_sizes.push(block.file_size);
+            row_counts.push(block.row_count);
+            created_by.push(
+                block
+                    .created_by
+                    .map(|id| id.to_simple().to_string().into_bytes()),
+            );
+            created_on.push(block.created_on.map(|t| t.timestamp_micros()));
+        }
+
+        Ok(DataBlock::create(FuseBlock::schema(), vec![
+            Series::from_data(snapshot_ids),
+            Series::from_data(block_locations),
+            Series::from_data(block_sizes),
+            Series::from_data(file_sizes),
+            Series::from_data(row_counts),
+            Series::from_data(created_by),
+            Series::from_data(created_on),
+        ]))
+    }
+
+    pub fn schema() -> Arc<DataSchema> {
+        DataSchemaRefExt::create(vec![
+            DataField::new("snapshot_id", Vu8::to_data_type()),
+            DataField::new("block_location", Vu8::to_data_type()),
+            DataField::new("block_size", u64::to_data_type()),
+            DataField::new("file_size", u64::to_data_type()),
+            DataField::new("row_count", u64::to_data_type()),
+            DataField::new_nullable("created_by", Vu8::to_data_type()),
+            DataField::new_nullable("created_on", TimestampType::new_impl(6)),
+        ])
+    }
+}
@@ -63,6 +63,12 @@ impl<'a> FuseHistory<'a> {
         let mut row_count: Vec<u64> = Vec::with_capacity(len);
         let mut compressed: Vec<u64> = Vec::with_capacity(len);
         let mut uncompressed: Vec<u64> = Vec::with_capacity(len);
+        let mut index: Vec<u64> = Vec::with_capacity(len);
+        let mut index_by_type: Vec<Vec<u8>> = Vec::with_capacity(len);
+        let mut timestamps: Vec<Option<i64>> = Vec::with_capacity(len);
+        let mut operations: Vec<Option<Vec<u8>>> = Vec::with_capacity(len);
+        let mut query_ids: Vec<Option<Vec<u8>>> = Vec::with_capacity(len);
+        let mut users: Vec<Option<Vec<u8>>> = Vec::with_capacity(len);
         let mut current_snapshot_version = lastest_snapshot_version;
         let location_generator = &self.table.meta_location_generator;
         for s in snapshots {
@@ -83,6 +89,12 @@ impl<'a> FuseHistory<'a> {
             row_count.push(s.summary.row_count);
             compressed.push(s.summary.compressed_byte_size);
             uncompressed.push(s.summary.uncompressed_byte_size);
+            index.push(s.summary.index_byte_size);
+            index_by_type.push(serde_json::to_vec(s.summary.index_sizes().as_ref())?);
+            timestamps.push(s.timestamp.map(|t| t.timestamp_micros()));
+            operations.push(s.operation.map(|op| op.to_string().into_bytes()));
+            query_ids.push(s.txn_id.clone().map(|id| id.into_bytes()));
+            users.push(s.user.clone().map(|u| u.into_bytes()));
             current_snapshot_version = ver;
         }
 
@@ -96,6 +108,12 @@ impl<'a> FuseHistory<'a> {
             Series::from_data(row_count),
             Series::from_data(uncompressed),
             Series::from_data(compressed),
+            Series::from_data(index),
+            Series::from_data(index_by_type),
+            Series::from_data(timestamps),
+            Series::from_data(operations),
+            Series::from_data(query_ids),
+            Series::from_data(users),
         ]))
     }
 
@@ -110,6 +128,12 @@ impl<'a> FuseHistory<'a> {
             DataField::new("row_count", u64::to_data_type()),
             DataField::new("bytes_uncompressed", u64::to_data_type()),
             DataField::new("bytes_compressed", u64::to_data_type()),
+            DataField::new("bytes_index", u64::to_data_type()),
+            DataField::new("bytes_index_by_type", Vu8::to_data_type()),
+            DataField::new_nullable("timestamp", TimestampType::new_impl(6)),
+            DataField::new_nullable("operation", Vu8::to_data_type()),
+            DataField::new_nullable("query_id", Vu8::to_data_type()),
+            DataField::new_nullable("user", Vu8::to_data_type()),
         ])
     }
 }
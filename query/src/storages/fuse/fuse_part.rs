@@ -22,8 +22,9 @@ use common_planners::PartInfo;
 use common_planners::PartInfoPtr;
 
 use crate::storages::fuse::meta::Compression;
+use crate::storages::fuse::meta::Location;
 
-#[derive(serde::Serialize, serde::Deserialize, PartialEq)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
 pub struct ColumnMeta {
     pub offset: u64,
     pub length: u64,
@@ -40,7 +41,7 @@ impl ColumnMeta {
     }
 }
 
-#[derive(serde::Serialize, serde::Deserialize, PartialEq)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
 pub struct FusePartInfo {
     pub location: String,
     /// FusePartInfo itself is not versioned
@@ -49,6 +50,17 @@ pub struct FusePartInfo {
     pub nums_rows: usize,
     pub columns_meta: HashMap<usize, ColumnMeta>,
     pub compression: Compression,
+    /// location of the deletion vector of the block, if any row of it has been deleted
+    pub deletion_vector: Option<Location>,
+    /// the virtual columns to read, which are kept in a file aside the block
+    pub virtual_block: Option<VirtualBlockPart>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct VirtualBlockPart {
+    pub location: String,
+    /// metas of the virtual columns, by the indices of them in the schema to read
+    pub columns_meta: HashMap<usize, ColumnMeta>,
 }
 
 #[typetag::serde(name = "fuse")]
@@ -72,6 +84,7 @@ impl FusePartInfo {
         rows_count: u64,
         columns_meta: HashMap<usize, ColumnMeta>,
         compression: Compression,
+        deletion_vector: Option<Location>,
     ) -> Arc<Box<dyn PartInfo>> {
         Arc::new(Box::new(FusePartInfo {
             location,
@@ -79,9 +92,27 @@ impl FusePartInfo {
             columns_meta,
             nums_rows: rows_count as usize,
             compression,
+            deletion_vector,
+            virtual_block: None,
         }))
     }
 
+    /// The location of the file keeping the column of `index`, and the meta of the column
+    pub fn column(&self, index: usize) -> Result<(&str, &ColumnMeta)> {
+        if let Some(meta) = self.columns_meta.get(&index) {
+            return Ok((&self.location, meta));
+        }
+        match &self.virtual_block {
+            Some(vb) if vb.columns_meta.contains_key(&index) => {
+                Ok((&vb.location, &vb.columns_meta[&index]))
+            }
+            _ => Err(ErrorCode::LogicalError(format!(
+                "Column {} is not found in the part of {}",
+                index, self.location
+            ))),
+        }
+    }
+
     pub fn from_part(info: &PartInfoPtr) -> Result<&FusePartInfo> {
         match info.as_any().downcast_ref::<FusePartInfo>() {
             Some(part_ref) => Ok(part_ref),
@@ -0,0 +1,77 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use super::FuseTable;
+use crate::sessions::QueryContext;
+
+pub struct FuseSnapshotDiff<'a> {
+    pub ctx: Arc<QueryContext>,
+    pub table: &'a FuseTable,
+}
+
+impl<'a> FuseSnapshotDiff<'a> {
+    pub fn new(ctx: Arc<QueryContext>, table: &'a FuseTable) -> Self {
+        Self { ctx, table }
+    }
+
+    /// One row per column, segment or block that differs between the snapshots
+    pub async fn get_diff(&self, from: &str, to: &str) -> Result<DataBlock> {
+        let entries = self
+            .table
+            .do_diff_snapshots(self.ctx.as_ref(), from, to)
+            .await?;
+        let len = entries.len();
+        let mut object_types: Vec<Vec<u8>> = Vec::with_capacity(len);
+        let mut changes: Vec<Vec<u8>> = Vec::with_capacity(len);
+        let mut names: Vec<Vec<u8>> = Vec::with_capacity(len);
+        let mut row_count_deltas: Vec<i64> = Vec::with_capacity(len);
+        let mut byte_size_deltas: Vec<i64> = Vec::with_capacity(len);
+        let mut details: Vec<Option<Vec<u8>>> = Vec::with_capacity(len);
+        for e in entries {
+            object_types.push(e.object.to_string().into_bytes());
+            changes.push(e.change.to_string().into_bytes());
+            names.push(e.name.into_bytes());
+            row_count_deltas.push(e.row_count_delta);
+            byte_size_deltas.push(e.byte_size_delta);
+            details.push(e.detail.map(|d| d.into_bytes()));
+        }
+
+        Ok(DataBlock::create(FuseSnapshotDiff::schema(), vec![
+            Series::from_data(object_types),
+            Series::from_data(changes),
+            Series::from_data(names),
+            Series::from_data(row_count_deltas),
+            Series::from_data(byte_size_deltas),
+            Series::from_data(details),
+        ]))
+    }
+
+    pub fn schema() -> Arc<DataSchema> {
+        DataSchemaRefExt::create(vec![
+            DataField::new("object_type", Vu8::to_data_type()),
+            DataField::new("change", Vu8::to_data_type()),
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("row_count_delta", i64::to_data_type()),
+            DataField::new("byte_size_delta", i64::to_data_type()),
+            DataField::new_nullable("detail", Vu8::to_data_type()),
+        ])
+    }
+}
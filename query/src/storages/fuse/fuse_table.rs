@@ -21,30 +21,45 @@ use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::TableInfo;
+use common_planners::DeletePlan;
 use common_planners::Expression;
 use common_planners::Extras;
+use common_planners::FlashbackTablePlan;
+use common_planners::MergePlan;
 use common_planners::Partitions;
 use common_planners::ReadDataSourcePlan;
 use common_planners::Statistics;
 use common_planners::TruncateTablePlan;
+use common_planners::UpdatePlan;
+use common_planners::VacuumTablePlan;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 use futures::StreamExt;
+use opendal::Operator;
 
 use crate::pipelines::new::NewPipeline;
 use crate::sessions::QueryContext;
 use crate::sql::OPT_KEY_DATABASE_ID;
+use crate::sql::OPT_KEY_EXTERNAL_LOCATION;
 use crate::sql::OPT_KEY_SNAPSHOT_LOC;
 use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
 use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::io::TableMetaLocationGenerator;
 use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::meta::TableSnapshotStatistics;
 use crate::storages::fuse::meta::Versioned;
 use crate::storages::fuse::operations::AppendOperationLogEntry;
+use crate::storages::fuse::ClusterKey;
+use crate::storages::fuse::ClusterOrder;
+use crate::storages::fuse::ColumnIds;
+use crate::storages::fuse::FUSE_OPT_KEY_CLUSTER_ORDER;
+use crate::storages::NavigationPoint;
 use crate::storages::StorageContext;
 use crate::storages::StorageDescription;
 use crate::storages::Table;
+use crate::storages::TableDataBackup;
 use crate::storages::TableStatistics;
+use crate::storages::VacuumReport;
 
 #[derive(Clone)]
 pub struct FuseTable {
@@ -70,6 +85,10 @@ impl FuseTable {
     }
 
     pub fn parse_storage_prefix(table_info: &TableInfo) -> Result<String> {
+        // the external tables are laid out by the clusters writing them
+        if let Some(prefix) = table_info.options().get(OPT_KEY_EXTERNAL_LOCATION) {
+            return Ok(prefix.clone());
+        }
         let table_id = table_info.ident.table_id;
         let db_id = table_info
             .options()
@@ -83,6 +102,35 @@ impl FuseTable {
         Ok(format!("{}/{}", db_id, table_id))
     }
 
+    pub(crate) fn cluster_order(&self) -> ClusterOrder {
+        self.get_option(FUSE_OPT_KEY_CLUSTER_ORDER, ClusterOrder::Linear)
+    }
+
+    /// The key of which the ranges in the blocks tell how the table is clustered: the leading
+    /// cluster key if it is a plain column, or the z-order of the cluster keys
+    pub(crate) fn cluster_key(&self) -> Option<ClusterKey> {
+        let schema = self.table_info.schema();
+        let column_ids = ColumnIds::from_schema(&schema);
+        let id_of = |key: &Expression| match key {
+            Expression::Column(name) => Some(column_ids.id_of(schema.index_of(name).ok()?)),
+            _ => None,
+        };
+        match self.cluster_order() {
+            ClusterOrder::Linear => self
+                .order_keys
+                .first()
+                .and_then(id_of)
+                .map(ClusterKey::Column),
+            ClusterOrder::ZOrder if self.order_keys.is_empty() => None,
+            ClusterOrder::ZOrder => self
+                .order_keys
+                .iter()
+                .map(id_of)
+                .collect::<Option<Vec<_>>>()
+                .map(ClusterKey::ZOrder),
+        }
+    }
+
     pub fn description() -> StorageDescription {
         StorageDescription {
             engine_name: "FUSE".to_string(),
@@ -106,6 +154,33 @@ impl Table for FuseTable {
         &self.table_info
     }
 
+    async fn refresh(&self, ctx: &QueryContext) -> Result<Option<Arc<dyn Table>>> {
+        if !self.is_external() {
+            return Ok(None);
+        }
+        let prefix = self.meta_location_generator.prefix();
+        let latest = Self::find_latest_snapshot(ctx, prefix).await?;
+        let mut table_info = self.table_info.clone();
+        match latest {
+            Some((location, snapshot)) => {
+                // the schema may have been altered by the cluster writing the table
+                table_info.meta.schema = Arc::new(snapshot.schema.clone());
+                table_info
+                    .meta
+                    .options
+                    .insert(OPT_KEY_SNAPSHOT_LOCATION.to_owned(), location);
+            }
+            None => {
+                table_info.meta.options.remove(OPT_KEY_SNAPSHOT_LOCATION);
+            }
+        }
+        Ok(Some(Arc::new(FuseTable {
+            table_info,
+            meta_location_generator: self.meta_location_generator.clone(),
+            order_keys: self.order_keys.clone(),
+        })))
+    }
+
     fn benefit_column_prune(&self) -> bool {
         true
     }
@@ -143,6 +218,7 @@ impl Table for FuseTable {
     }
 
     fn append2(&self, ctx: Arc<QueryContext>, pipeline: &mut NewPipeline) -> Result<()> {
+        self.check_mutable()?;
         self.do_append2(ctx, pipeline)
     }
 
@@ -152,6 +228,7 @@ impl Table for FuseTable {
         ctx: Arc<QueryContext>,
         stream: SendableDataBlockStream,
     ) -> Result<SendableDataBlockStream> {
+        self.check_mutable()?;
         let log_entry_stream = self.append_trunks(ctx, stream).await?;
         let data_block_stream =
             log_entry_stream.map(|append_log_entry_res| match append_log_entry_res {
@@ -167,6 +244,7 @@ impl Table for FuseTable {
         operations: Vec<DataBlock>,
         overwrite: bool,
     ) -> Result<()> {
+        self.check_mutable()?;
         // only append operation supported currently
         let append_log_entries = operations
             .iter()
@@ -180,13 +258,79 @@ impl Table for FuseTable {
         ctx: Arc<QueryContext>,
         truncate_plan: TruncateTablePlan,
     ) -> Result<()> {
+        self.check_mutable()?;
         self.do_truncate(ctx, truncate_plan).await
     }
 
+    async fn delete(&self, ctx: Arc<QueryContext>, delete_plan: DeletePlan) -> Result<()> {
+        self.check_mutable()?;
+        self.do_delete(&ctx, delete_plan).await
+    }
+
+    async fn update(&self, ctx: Arc<QueryContext>, update_plan: UpdatePlan) -> Result<()> {
+        self.check_mutable()?;
+        self.do_update(&ctx, update_plan).await
+    }
+
+    async fn merge(
+        &self,
+        ctx: Arc<QueryContext>,
+        merge_plan: MergePlan,
+        source: Vec<DataBlock>,
+    ) -> Result<()> {
+        self.check_mutable()?;
+        self.do_merge(&ctx, merge_plan, source).await
+    }
+
     async fn optimize(&self, ctx: Arc<QueryContext>, keep_last_snapshot: bool) -> Result<()> {
+        self.check_mutable()?;
         self.do_optimize(ctx, keep_last_snapshot).await
     }
 
+    async fn compact(&self, ctx: Arc<QueryContext>, limit: Option<usize>) -> Result<()> {
+        self.check_mutable()?;
+        self.do_compact(&ctx, limit).await
+    }
+
+    async fn compact_segments(&self, ctx: Arc<QueryContext>) -> Result<()> {
+        self.check_mutable()?;
+        self.do_compact_segments(&ctx).await
+    }
+
+    async fn expire(&self, ctx: Arc<QueryContext>) -> Result<()> {
+        self.check_mutable()?;
+        self.do_expire(&ctx).await.map(|_| ())
+    }
+
+    async fn vacuum(
+        &self,
+        ctx: Arc<QueryContext>,
+        vacuum_plan: VacuumTablePlan,
+    ) -> Result<VacuumReport> {
+        self.check_mutable()?;
+        self.do_vacuum(ctx, vacuum_plan).await
+    }
+
+    async fn backup(
+        &self,
+        ctx: Arc<QueryContext>,
+        target: &Operator,
+        dir: &str,
+    ) -> Result<Option<TableDataBackup>> {
+        self.do_backup(&ctx, target, dir).await
+    }
+
+    async fn restore(
+        &self,
+        ctx: Arc<QueryContext>,
+        source: &Operator,
+        dir: &str,
+        backup: &TableDataBackup,
+    ) -> Result<()> {
+        self.check_mutable()?;
+        self.do_restore(&ctx, source, dir, backup).await
+    }
+
     async fn statistics(&self, ctx: Arc<QueryContext>) -> Result<Option<TableStatistics>> {
         let snapshot = self.read_table_snapshot(ctx.as_ref()).await?;
         Ok(snapshot.map(|s| {
@@ -195,10 +339,40 @@ impl Table for FuseTable {
                 num_rows: Some(summary.row_count),
                 data_size: Some(summary.uncompressed_byte_size),
                 data_size_compressed: Some(summary.compressed_byte_size),
-                index_length: None,
+                index_length: Some(summary.index_byte_size),
             }
         }))
     }
+
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<()> {
+        self.check_mutable()?;
+        self.do_analyze(&ctx).await
+    }
+
+    async fn column_statistics(
+        &self,
+        ctx: Arc<QueryContext>,
+    ) -> Result<Option<Arc<TableSnapshotStatistics>>> {
+        self.read_table_snapshot_statistics(ctx.as_ref()).await
+    }
+
+    async fn navigate_to(
+        &self,
+        ctx: Arc<QueryContext>,
+        point: &NavigationPoint,
+    ) -> Result<Arc<dyn Table>> {
+        let table: Arc<dyn Table> = self.do_navigate(ctx.as_ref(), point).await?;
+        Ok(table)
+    }
+
+    async fn flashback(
+        &self,
+        ctx: Arc<QueryContext>,
+        flashback_plan: FlashbackTablePlan,
+    ) -> Result<()> {
+        self.check_mutable()?;
+        self.do_flashback(&ctx, flashback_plan).await
+    }
 }
 
 impl FuseTable {
@@ -237,6 +411,26 @@ impl FuseTable {
         }
     }
 
+    /// Loads the statistics built by `ANALYZE TABLE`, note that they may be built from a
+    /// previous snapshot, see [TableSnapshotStatistics::snapshot_id].
+    pub async fn read_table_snapshot_statistics(
+        &self,
+        ctx: &QueryContext,
+    ) -> Result<Option<Arc<TableSnapshotStatistics>>> {
+        let snapshot = match self.read_table_snapshot(ctx).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(None),
+        };
+        match &snapshot.table_statistics_location {
+            Some(loc) => {
+                let reader = MetaReaders::table_snapshot_statistics_reader(ctx);
+                let ver = TableMetaLocationGenerator::snapshot_statistics_version(loc);
+                Ok(Some(reader.read(loc.as_str(), None, ver).await?))
+            }
+            None => Ok(None),
+        }
+    }
+
     pub fn meta_location_generator(&self) -> &TableMetaLocationGenerator {
         &self.meta_location_generator
     }
@@ -0,0 +1,62 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use super::FuseTable;
+use crate::sessions::QueryContext;
+
+pub struct FuseVerify<'a> {
+    pub ctx: Arc<QueryContext>,
+    pub table: &'a FuseTable,
+}
+
+impl<'a> FuseVerify<'a> {
+    pub fn new(ctx: Arc<QueryContext>, table: &'a FuseTable) -> Self {
+        Self { ctx, table }
+    }
+
+    /// One row per problem found, no rows if the table is intact
+    pub async fn get_problems(&self) -> Result<DataBlock> {
+        let problems = self.table.do_verify(self.ctx.as_ref()).await?;
+        let len = problems.len();
+        let mut categories: Vec<Vec<u8>> = Vec::with_capacity(len);
+        let mut locations: Vec<Vec<u8>> = Vec::with_capacity(len);
+        let mut descriptions: Vec<Vec<u8>> = Vec::with_capacity(len);
+        for p in problems {
+            categories.push(p.category.to_string().into_bytes());
+            locations.push(p.location.into_bytes());
+            descriptions.push(p.problem.into_bytes());
+        }
+
+        Ok(DataBlock::create(FuseVerify::schema(), vec![
+            Series::from_data(categories),
+            Series::from_data(locations),
+            Series::from_data(descriptions),
+        ]))
+    }
+
+    pub fn schema() -> Arc<DataSchema> {
+        DataSchemaRefExt::create(vec![
+            DataField::new("category", Vu8::to_data_type()),
+            DataField::new("location", Vu8::to_data_type()),
+            DataField::new("problem", Vu8::to_data_type()),
+        ])
+    }
+}
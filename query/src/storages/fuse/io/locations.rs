@@ -19,15 +19,26 @@ use common_datablocks::DataBlock;
 use common_exception::Result;
 use uuid::Uuid;
 
+use crate::storages::fuse::constants::FUSE_TBL_AGGREGATING_INDEX_PREFIX;
 use crate::storages::fuse::constants::FUSE_TBL_BLOCK_PREFIX;
+use crate::storages::fuse::constants::FUSE_TBL_DELETION_VECTOR_PREFIX;
+use crate::storages::fuse::constants::FUSE_TBL_PURGE_CHECKPOINT;
 use crate::storages::fuse::constants::FUSE_TBL_SEGMENT_PREFIX;
 use crate::storages::fuse::constants::FUSE_TBL_SNAPSHOT_PREFIX;
+use crate::storages::fuse::constants::FUSE_TBL_SNAPSHOT_STATISTICS_PREFIX;
+use crate::storages::fuse::constants::FUSE_TBL_VIRTUAL_BLOCK_PREFIX;
+use crate::storages::fuse::meta::AggregatingIndexMeta;
+use crate::storages::fuse::meta::DeletionVector;
 use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::meta::SnapshotId;
+use crate::storages::fuse::meta::SnapshotStatisticsVersion;
 use crate::storages::fuse::meta::SnapshotVersion;
+use crate::storages::fuse::meta::TableSnapshotStatistics;
 use crate::storages::fuse::meta::Versioned;
 
 static SNAPSHOT_V0: SnapshotVersion = SnapshotVersion::V0(PhantomData);
 static SNAPHOST_V1: SnapshotVersion = SnapshotVersion::V1(PhantomData);
+static SNAPHOST_V2: SnapshotVersion = SnapshotVersion::V2(PhantomData);
 
 #[derive(Clone)]
 pub struct TableMetaLocationGenerator {
@@ -54,6 +65,18 @@ impl TableMetaLocationGenerator {
         )
     }
 
+    /// Generates the location of the file keeping the virtual columns of a block
+    pub fn gen_virtual_block_location(&self) -> String {
+        let part_uuid = Uuid::new_v4().to_simple().to_string();
+        format!(
+            "{}/{}/{}_v{}.parquet",
+            &self.prefix,
+            FUSE_TBL_VIRTUAL_BLOCK_PREFIX,
+            part_uuid,
+            DataBlock::VERSION,
+        )
+    }
+
     pub fn gen_segment_info_location(&self) -> String where {
         let segment_uuid = Uuid::new_v4().to_simple().to_string();
         format!(
@@ -65,13 +88,79 @@ impl TableMetaLocationGenerator {
         )
     }
 
+    pub fn gen_deletion_vector_location(&self) -> String {
+        let dv_uuid = Uuid::new_v4().to_simple().to_string();
+        format!(
+            "{}/{}/{}_v{}.json",
+            &self.prefix,
+            FUSE_TBL_DELETION_VECTOR_PREFIX,
+            dv_uuid,
+            DeletionVector::VERSION,
+        )
+    }
+
+    pub fn gen_aggregating_index_block_location(&self, index: &str) -> String {
+        let block_uuid = Uuid::new_v4().to_simple().to_string();
+        format!(
+            "{}/{}/{}/{}_v{}.parquet",
+            &self.prefix,
+            FUSE_TBL_AGGREGATING_INDEX_PREFIX,
+            index,
+            block_uuid,
+            DataBlock::VERSION,
+        )
+    }
+
+    /// The location of the meta of the aggregating index, which is refreshed for the snapshot
+    pub fn aggregating_index_meta_location(&self, index: &str, snapshot_id: &SnapshotId) -> String {
+        format!(
+            "{}/{}/{}/{}_v{}.json",
+            &self.prefix,
+            FUSE_TBL_AGGREGATING_INDEX_PREFIX,
+            index,
+            snapshot_id.to_simple(),
+            AggregatingIndexMeta::VERSION,
+        )
+    }
+
     pub fn snapshot_location_from_uuid(&self, id: &Uuid, version: u64) -> Result<String> {
         let snaphost_version = SnapshotVersion::try_from(version)?;
         Ok(snaphost_version.create(id, &self.prefix))
     }
 
+    pub fn snapshot_statistics_location_from_uuid(
+        &self,
+        id: &Uuid,
+        version: u64,
+    ) -> Result<String> {
+        let statistics_version = SnapshotStatisticsVersion::try_from(version)?;
+        Ok(format!(
+            "{}/{}/{}_v{}.json",
+            &self.prefix,
+            FUSE_TBL_SNAPSHOT_STATISTICS_PREFIX,
+            id.to_simple(),
+            statistics_version.version(),
+        ))
+    }
+
+    /// The location of the checkpoint of an interrupted purge of the table
+    pub fn purge_checkpoint_location(&self) -> String {
+        format!("{}/{}.json", &self.prefix, FUSE_TBL_PURGE_CHECKPOINT)
+    }
+
+    pub fn snapshot_statistics_version(location: impl AsRef<str>) -> u64 {
+        location
+            .as_ref()
+            .strip_suffix(".json")
+            .and_then(|l| l.rsplit("_v").next())
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(TableSnapshotStatistics::VERSION)
+    }
+
     pub fn snaphost_version(location: impl AsRef<str>) -> u64 {
-        if location.as_ref().ends_with(SNAPHOST_V1.suffix()) {
+        if location.as_ref().ends_with(SNAPHOST_V2.suffix()) {
+            SNAPHOST_V2.version()
+        } else if location.as_ref().ends_with(SNAPHOST_V1.suffix()) {
             SNAPHOST_V1.version()
         } else {
             SNAPSHOT_V0.version()
@@ -99,6 +188,7 @@ impl SnapshotLocationCreator for SnapshotVersion {
         match self {
             SnapshotVersion::V0(_) => "",
             SnapshotVersion::V1(_) => "_v1.json",
+            SnapshotVersion::V2(_) => "_v2.json",
         }
     }
 }
@@ -17,12 +17,17 @@ mod read;
 mod write;
 
 pub use locations::TableMetaLocationGenerator;
+pub use read::AggregatingIndexMetaReader;
 pub use read::BlockReader;
 pub use read::MetaReaders;
 pub use read::SegmentInfoReader;
+pub use read::TableSnapshotLiteReader;
 pub use read::TableSnapshotReader;
+pub use read::TableSnapshotStatisticsReader;
 pub use write::serialize_data_block;
+pub use write::encode_snapshot;
 pub use write::write_block;
+pub use write::write_snapshot;
 pub use write::BlockCompactor;
 pub use write::BlockStreamWriter;
 pub use write::SegmentInfoStream;
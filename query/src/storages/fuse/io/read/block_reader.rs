@@ -12,8 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use common_arrow::arrow::array::ArrayRef;
+use common_arrow::arrow::chunk::Chunk;
 use common_arrow::arrow::datatypes::Field;
 use common_arrow::arrow::datatypes::Schema;
 use common_arrow::arrow::io::parquet::read::column_iter_to_arrays;
@@ -26,7 +29,13 @@ use common_arrow::parquet::metadata::SchemaDescriptor;
 use common_arrow::parquet::read::BasicDecompressor;
 use common_arrow::parquet::read::PageIterator;
 use common_datablocks::DataBlock;
+use common_datavalues::Column;
+use common_datavalues::ColumnRef;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_datavalues::Series;
+use common_datavalues::SeriesFrom;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::PartInfoPtr;
@@ -39,17 +48,27 @@ use futures::TryStreamExt;
 use opendal::Object;
 use opendal::Operator;
 
+use super::versioned_reader::VersionedReader;
+use crate::storages::fuse::cache::TableDiskCache;
 use crate::storages::fuse::fuse_part::ColumnMeta;
 use crate::storages::fuse::fuse_part::FusePartInfo;
 use crate::storages::fuse::meta::Compression;
+use crate::storages::fuse::meta::DeletionVector;
+use crate::storages::fuse::meta::DeletionVectorVersion;
+use crate::storages::fuse::ColumnIds;
 
 #[derive(Clone)]
 pub struct BlockReader {
     operator: Operator,
     projection: Vec<usize>,
+    schema: DataSchemaRef,
     arrow_schema: Arc<Schema>,
     projected_schema: DataSchemaRef,
     parquet_schema_descriptor: SchemaDescriptor,
+    /// values of the columns added after the blocks are written, by the indices of the columns
+    missing_values: HashMap<usize, DataValue>,
+    /// caches the column chunks on the local disk, if enabled
+    disk_cache: Option<TableDiskCache>,
 }
 
 impl BlockReader {
@@ -57,17 +76,22 @@ impl BlockReader {
         operator: Operator,
         schema: DataSchemaRef,
         projection: Vec<usize>,
+        disk_cache: Option<TableDiskCache>,
     ) -> Result<Arc<BlockReader>> {
         let projected_schema = DataSchemaRef::new(schema.project(projection.clone()));
+        let missing_values = ColumnIds::from_schema(&schema).missing_values(&schema)?;
 
         let arrow_schema = schema.to_arrow();
         let parquet_schema_descriptor = to_parquet_schema(&arrow_schema)?;
         Ok(Arc::new(BlockReader {
             operator,
             projection,
+            schema,
             projected_schema,
             parquet_schema_descriptor,
             arrow_schema: Arc::new(arrow_schema),
+            missing_values,
+            disk_cache,
         }))
     }
 
@@ -98,23 +122,111 @@ impl BlockReader {
         )?)
     }
 
-    async fn read_columns(&self, part: PartInfoPtr) -> Result<(usize, Vec<ArrayIter<'static>>)> {
+    /// The projected columns which are kept in the part. The others are missing in the block,
+    /// for they are added to the table after the block is written.
+    fn present_columns(&self, part: &FusePartInfo) -> Vec<usize> {
+        self.projection
+            .iter()
+            .filter(|index| part.column(**index).is_ok())
+            .cloned()
+            .collect()
+    }
+
+    /// Fills the column of `index` which is missing in the block, by the value recorded when
+    /// the column is added.
+    fn missing_column(&self, index: usize, rows: usize) -> Result<ColumnRef> {
+        let data_type = self.schema.field(index).data_type();
+        let value = match self.missing_values.get(&index) {
+            Some(value) => value.clone(),
+            None => data_type.default_value(),
+        };
+        let column = data_type.create_constant_column(&value, rows)?;
+        Ok(column.convert_full_column())
+    }
+
+    /// Builds the projected block from the present columns, see `present_columns`.
+    fn to_block(
+        &self,
+        present: &[usize],
+        num_rows: usize,
+        columns_array_iter: Vec<ArrayIter<'static>>,
+    ) -> Result<DataBlock> {
+        if present.len() == self.projection.len() {
+            let chunk = Self::to_chunk(columns_array_iter, num_rows)?;
+            return DataBlock::from_chunk(&self.projected_schema, &chunk);
+        }
+
+        let mut present_columns = match present.is_empty() {
+            true => vec![],
+            false => {
+                let schema = DataSchemaRef::new(self.schema.project(present.to_vec()));
+                let chunk = Self::to_chunk(columns_array_iter, num_rows)?;
+                DataBlock::from_chunk(&schema, &chunk)?.columns().to_vec()
+            }
+        }
+        .into_iter();
+        let mut columns = Vec::with_capacity(self.projection.len());
+        for index in &self.projection {
+            match present.contains(index) {
+                true => columns.extend(present_columns.next()),
+                false => columns.push(self.missing_column(*index, num_rows)?),
+            }
+        }
+        Ok(DataBlock::create(self.projected_schema.clone(), columns))
+    }
+
+    fn to_chunk(
+        columns_array_iter: Vec<ArrayIter<'static>>,
+        num_rows: usize,
+    ) -> Result<Chunk<ArrayRef>> {
+        let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
+
+        match deserializer.next() {
+            None => Err(ErrorCode::ParquetError("fail to get a chunk")),
+            Some(Err(cause)) => Err(ErrorCode::from(cause)),
+            Some(Ok(chunk)) => Ok(chunk),
+        }
+    }
+
+    /// The disk cache, and the key of the column chunk in it, if the disk cache is enabled.
+    ///
+    /// The chunks missed by the cache are put into it once they are read, thus the blocks
+    /// read by the queries are cached.
+    fn cache_entry(&self, location: &str, meta: &ColumnMeta) -> Option<(TableDiskCache, String)> {
+        self.disk_cache.as_ref().map(|cache| {
+            let key = TableDiskCache::block_chunk_key(location, meta.offset, meta.length);
+            (cache.clone(), key)
+        })
+    }
+
+    async fn read_columns(
+        &self,
+        part: PartInfoPtr,
+    ) -> Result<(usize, Vec<usize>, Vec<ArrayIter<'static>>)> {
         let part = FusePartInfo::from_part(&part)?;
 
         let rows = part.nums_rows;
         // TODO: add prefetch column data.
-        let num_cols = self.projection.len();
+        let present = self.present_columns(part);
+        let num_cols = present.len();
         let mut column_chunk_futs = Vec::with_capacity(num_cols);
         let mut col_idx = Vec::with_capacity(num_cols);
-        for index in &self.projection {
-            let column_meta = &part.columns_meta[index];
-            let column_reader = self.operator.object(&part.location);
+        for index in &present {
+            let (location, column_meta) = part.column(*index)?;
+            let column_reader = self.operator.object(location);
+            let cache_entry = self.cache_entry(location, column_meta);
             let fut = async move {
+                if let Some(column_chunk) = cache_entry.as_ref().and_then(|(c, k)| c.get(k)) {
+                    return Ok(column_chunk);
+                }
                 // NOTE: move chunk inside future so that alloc only
                 // happen when future is ready to go.
                 let column_chunk = column_reader
                     .range_read(column_meta.offset..column_meta.offset + column_meta.length)
                     .await?;
+                if let Some((cache, key)) = &cache_entry {
+                    cache.put(key, &column_chunk);
+                }
                 Ok::<_, ErrorCode>(column_chunk)
             }
             .instrument(debug_span!("read_col_chunk"));
@@ -123,7 +235,7 @@ impl BlockReader {
         }
 
         let chunks = futures::stream::iter(column_chunk_futs)
-            .buffered(std::cmp::min(10, num_cols))
+            .buffered(std::cmp::min(10, num_cols).max(1))
             .try_collect::<Vec<_>>()
             .await?;
 
@@ -132,7 +244,7 @@ impl BlockReader {
             let idx = *col_idx[i];
             let field = self.arrow_schema.fields[idx].clone();
             let column_descriptor = self.parquet_schema_descriptor.column(idx);
-            let column_meta = &part.columns_meta[&idx];
+            let (_, column_meta) = part.column(idx)?;
             columns_array_iter.push(Self::to_deserialize(
                 column_meta,
                 column_chunk,
@@ -143,25 +255,26 @@ impl BlockReader {
             )?);
         }
 
-        Ok((rows, columns_array_iter))
+        Ok((rows, present, columns_array_iter))
     }
 
     pub fn deserialize(&self, part: PartInfoPtr, chunks: Vec<Vec<u8>>) -> Result<DataBlock> {
-        if self.projection.len() != chunks.len() {
+        let part = FusePartInfo::from_part(&part)?;
+        let present = self.present_columns(part);
+        if present.len() != chunks.len() {
             return Err(ErrorCode::LogicalError(
                 "Columns chunk len must be equals projections len.",
             ));
         }
 
-        let part = FusePartInfo::from_part(&part)?;
-        let mut columns_array_iter = Vec::with_capacity(self.projection.len());
+        let mut columns_array_iter = Vec::with_capacity(present.len());
 
         let num_rows = part.nums_rows;
         for (index, column_chunk) in chunks.into_iter().enumerate() {
-            let index = self.projection[index];
+            let index = present[index];
             let field = self.arrow_schema.fields[index].clone();
             let column_descriptor = self.parquet_schema_descriptor.column(index);
-            let column_meta = &part.columns_meta[&index];
+            let (_, column_meta) = part.column(index)?;
             columns_array_iter.push(Self::to_deserialize(
                 column_meta,
                 column_chunk,
@@ -172,24 +285,20 @@ impl BlockReader {
             )?);
         }
 
-        let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
-
-        match deserializer.next() {
-            None => Err(ErrorCode::ParquetError("fail to get a chunk")),
-            Some(Err(cause)) => Err(ErrorCode::from(cause)),
-            Some(Ok(chunk)) => DataBlock::from_chunk(&self.projected_schema, &chunk),
-        }
+        self.to_block(&present, num_rows, columns_array_iter)
     }
 
     pub async fn read_columns_data(&self, part: PartInfoPtr) -> Result<Vec<Vec<u8>>> {
         let part = FusePartInfo::from_part(&part)?;
-        let mut join_handlers = Vec::with_capacity(self.projection.len());
+        let present = self.present_columns(part);
+        let mut join_handlers = Vec::with_capacity(present.len());
 
-        for index in &self.projection {
-            let column_meta = &part.columns_meta[index];
+        for index in &present {
+            let (location, column_meta) = part.column(*index)?;
 
             join_handlers.push(Self::read_column(
-                self.operator.object(&part.location),
+                self.operator.object(location),
+                self.cache_entry(location, column_meta),
                 column_meta.offset,
                 column_meta.length,
             ));
@@ -198,11 +307,22 @@ impl BlockReader {
         futures::future::try_join_all(join_handlers).await
     }
 
-    async fn read_column(o: Object, offset: u64, length: u64) -> Result<Vec<u8>> {
+    async fn read_column(
+        o: Object,
+        cache_entry: Option<(TableDiskCache, String)>,
+        offset: u64,
+        length: u64,
+    ) -> Result<Vec<u8>> {
         let handler = common_base::tokio::spawn(async move {
+            if let Some(chunk) = cache_entry.as_ref().and_then(|(c, k)| c.get(k)) {
+                return Result::Ok(chunk);
+            }
             let mut chunk = vec![0; length as usize];
             let mut r = o.range_reader(offset..offset + length).await?;
             r.read_exact(&mut chunk).await?;
+            if let Some((cache, key)) = &cache_entry {
+                cache.put(key, &chunk);
+            }
             Result::Ok(chunk)
         });
 
@@ -216,16 +336,43 @@ impl BlockReader {
         }
     }
 
+    /// Reads the rows of the part which have not been deleted
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn read(&self, part: PartInfoPtr) -> Result<DataBlock> {
-        let (num_rows, columns_array_iter) = self.read_columns(part).await?;
+        let deletion_vector = self.read_deletion_vector(&part).await?;
+        let block = self.read_all_rows(part).await?;
+        Self::apply_deletion_vector(block, deletion_vector.as_ref())
+    }
 
-        let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
+    /// Reads all the rows of the part, including the deleted ones
+    pub async fn read_all_rows(&self, part: PartInfoPtr) -> Result<DataBlock> {
+        let (num_rows, present, columns_array_iter) = self.read_columns(part).await?;
+        self.to_block(&present, num_rows, columns_array_iter)
+    }
 
-        match deserializer.next() {
-            None => Err(ErrorCode::ParquetError("fail to get a chunk")),
-            Some(Err(cause)) => Err(ErrorCode::from(cause)),
-            Some(Ok(chunk)) => DataBlock::from_chunk(&self.projected_schema, &chunk),
+    pub async fn read_deletion_vector(&self, part: &PartInfoPtr) -> Result<Option<DeletionVector>> {
+        let part = FusePartInfo::from_part(part)?;
+        match &part.deletion_vector {
+            None => Ok(None),
+            Some((location, ver)) => {
+                let reader = self.operator.object(location).reader().await?;
+                let version = DeletionVectorVersion::try_from(*ver)?;
+                Ok(Some(version.read(reader).await?))
+            }
+        }
+    }
+
+    /// Filters out the rows of the block which are marked as deleted by `deletion_vector`
+    pub fn apply_deletion_vector(
+        block: DataBlock,
+        deletion_vector: Option<&DeletionVector>,
+    ) -> Result<DataBlock> {
+        match deletion_vector {
+            Some(dv) if dv.deleted_row_count() > 0 => {
+                let filter = Series::from_data(dv.live_rows());
+                DataBlock::filter_block(&block, &filter)
+            }
+            _ => Ok(block),
         }
     }
 
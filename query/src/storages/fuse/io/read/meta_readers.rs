@@ -17,6 +17,8 @@ use std::sync::Arc;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use futures::io::BufReader;
+use futures::Stream;
+use futures::StreamExt;
 use opendal::BytesReader;
 
 use super::cached_reader::CachedReader;
@@ -26,10 +28,17 @@ use super::versioned_reader::VersionedReader;
 use crate::sessions::QueryContext;
 use crate::storages::fuse::cache::TenantLabel;
 use crate::storages::fuse::io::TableMetaLocationGenerator;
+use crate::storages::fuse::meta::AggregatingIndexMeta;
+use crate::storages::fuse::meta::AggregatingIndexVersion;
+use crate::storages::fuse::meta::Location;
 use crate::storages::fuse::meta::SegmentInfo;
 use crate::storages::fuse::meta::SegmentInfoVersion;
+use crate::storages::fuse::meta::SnapshotId;
+use crate::storages::fuse::meta::SnapshotStatisticsVersion;
 use crate::storages::fuse::meta::SnapshotVersion;
 use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::meta::TableSnapshotLite;
+use crate::storages::fuse::meta::TableSnapshotStatistics;
 
 /// Provider of [BufReader]
 ///
@@ -42,6 +51,10 @@ pub trait BufReaderProvider {
 
 pub type SegmentInfoReader<'a> = CachedReader<SegmentInfo, &'a QueryContext>;
 pub type TableSnapshotReader<'a> = CachedReader<TableSnapshot, &'a QueryContext>;
+pub type TableSnapshotLiteReader<'a> = CachedReader<TableSnapshotLite, &'a QueryContext>;
+pub type TableSnapshotStatisticsReader<'a> =
+    CachedReader<TableSnapshotStatistics, &'a QueryContext>;
+pub type AggregatingIndexMetaReader<'a> = CachedReader<AggregatingIndexMeta, &'a QueryContext>;
 
 pub struct MetaReaders;
 
@@ -61,6 +74,38 @@ impl MetaReaders {
             "SNAPSHOT_CACHE".to_owned(),
         )
     }
+
+    pub fn table_snapshot_lite_reader(ctx: &QueryContext) -> TableSnapshotLiteReader {
+        // lites are read while walking through the history, which is hardly walked twice
+        TableSnapshotLiteReader::new(None, ctx, "SNAPSHOT_LITE_CACHE".to_owned())
+    }
+
+    pub fn table_snapshot_statistics_reader(ctx: &QueryContext) -> TableSnapshotStatisticsReader {
+        // statistics are only loaded while planning, and are not cached
+        TableSnapshotStatisticsReader::new(None, ctx, "SNAPSHOT_STATISTICS_CACHE".to_owned())
+    }
+
+    pub fn aggregating_index_meta_reader(ctx: &QueryContext) -> AggregatingIndexMetaReader {
+        // each snapshot has its own index meta, which is hardly read twice
+        AggregatingIndexMetaReader::new(None, ctx, "AGGREGATING_INDEX_META_CACHE".to_owned())
+    }
+}
+
+impl<'a> SegmentInfoReader<'a> {
+    /// Streams the segments located at `locations` lazily, in the order of `locations`.
+    ///
+    /// At most `concurrency` segments are being loaded at the same time, and nothing
+    /// is loaded until the stream is polled, thus snapshots with huge numbers of segments
+    /// can be visited without loading all the segments into memory at once.
+    pub fn read_segments<'b>(
+        &'b self,
+        locations: &'b [Location],
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<Arc<SegmentInfo>>> + Send + 'b {
+        futures::stream::iter(locations)
+            .map(move |(loc, ver)| self.read(loc, None, *ver))
+            .buffered(std::cmp::max(concurrency, 1))
+    }
 }
 
 impl<'a> TableSnapshotReader<'a> {
@@ -100,6 +145,44 @@ impl<'a> TableSnapshotReader<'a> {
     }
 }
 
+impl<'a> TableSnapshotLiteReader<'a> {
+    /// Walks through the history of snapshots, from the latest to the earliest, keeping only
+    /// the essentials of them, thus deep histories can be visited with bounded memory. Only
+    /// the fields of the essentials are decoded, the segments of the snapshots are not.
+    ///
+    /// The walk stops before the snapshot `until` if it is met, which is told by the returned
+    /// flag.
+    pub async fn read_snapshot_lites(
+        &self,
+        latest_snapshot_location: Option<impl AsRef<str>>,
+        format_version: u64,
+        location_gen: &TableMetaLocationGenerator,
+        until: Option<&SnapshotId>,
+    ) -> Result<(Vec<TableSnapshotLite>, bool)> {
+        let mut lites = vec![];
+        let mut next = latest_snapshot_location.map(|l| (l.as_ref().to_string(), format_version));
+        while let Some((loc, ver)) = next.take() {
+            let lite = match self.read(loc, None, ver).await {
+                Ok(s) => s,
+                Err(e) if e.code() == ErrorCode::storage_not_found_code() => break,
+                Err(e) => return Err(e),
+            };
+            if lites.is_empty() && Some(&lite.snapshot_id) == until {
+                return Ok((lites, true));
+            }
+            let prev_snapshot_id = lite.prev_snapshot_id;
+            lites.push(lite.as_ref().clone());
+            if let Some((id, v)) = prev_snapshot_id {
+                if Some(&id) == until {
+                    return Ok((lites, true));
+                }
+                next = Some((location_gen.snapshot_location_from_uuid(&id, v)?, v));
+            }
+        }
+        Ok((lites, false))
+    }
+}
+
 #[async_trait::async_trait]
 impl<T> Loader<TableSnapshot> for T
 where T: BufReaderProvider + Sync
@@ -116,6 +199,54 @@ where T: BufReaderProvider + Sync
     }
 }
 
+#[async_trait::async_trait]
+impl<T> Loader<TableSnapshotLite> for T
+where T: BufReaderProvider + Sync
+{
+    async fn load(
+        &self,
+        key: &str,
+        length_hint: Option<u64>,
+        version: u64,
+    ) -> Result<TableSnapshotLite> {
+        let version = SnapshotVersion::try_from(version)?;
+        let reader = self.buf_reader(key, length_hint).await?;
+        version.read(reader).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> Loader<TableSnapshotStatistics> for T
+where T: BufReaderProvider + Sync
+{
+    async fn load(
+        &self,
+        key: &str,
+        length_hint: Option<u64>,
+        version: u64,
+    ) -> Result<TableSnapshotStatistics> {
+        let version = SnapshotStatisticsVersion::try_from(version)?;
+        let reader = self.buf_reader(key, length_hint).await?;
+        version.read(reader).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> Loader<AggregatingIndexMeta> for T
+where T: BufReaderProvider + Sync
+{
+    async fn load(
+        &self,
+        key: &str,
+        length_hint: Option<u64>,
+        version: u64,
+    ) -> Result<AggregatingIndexMeta> {
+        let version = AggregatingIndexVersion::try_from(version)?;
+        let reader = self.buf_reader(key, length_hint).await?;
+        version.read(reader).await
+    }
+}
+
 #[async_trait::async_trait]
 impl<T> Loader<SegmentInfo> for T
 where T: BufReaderProvider + Sync
@@ -18,6 +18,9 @@ mod meta_readers;
 mod versioned_reader;
 
 pub use block_reader::BlockReader;
+pub use meta_readers::AggregatingIndexMetaReader;
 pub use meta_readers::MetaReaders;
 pub use meta_readers::SegmentInfoReader;
+pub use meta_readers::TableSnapshotLiteReader;
 pub use meta_readers::TableSnapshotReader;
+pub use meta_readers::TableSnapshotStatisticsReader;
@@ -17,12 +17,20 @@ use std::marker::PhantomData;
 use common_exception::Result;
 use futures::AsyncRead;
 use serde::de::DeserializeOwned;
-use serde_json::from_slice;
 
+use crate::storages::fuse::meta::AggregatingIndexMeta;
+use crate::storages::fuse::meta::AggregatingIndexVersion;
+use crate::storages::fuse::meta::DeletionVector;
+use crate::storages::fuse::meta::DeletionVectorVersion;
+use crate::storages::fuse::meta::Encoding;
 use crate::storages::fuse::meta::SegmentInfo;
 use crate::storages::fuse::meta::SegmentInfoVersion;
+use crate::storages::fuse::meta::SnapshotStatisticsVersion;
 use crate::storages::fuse::meta::SnapshotVersion;
 use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::meta::TableSnapshotHead;
+use crate::storages::fuse::meta::TableSnapshotLite;
+use crate::storages::fuse::meta::TableSnapshotStatistics;
 
 #[async_trait::async_trait]
 pub trait VersionedReader<T> {
@@ -35,13 +43,24 @@ impl VersionedReader<TableSnapshot> for SnapshotVersion {
     async fn read<R>(&self, reader: R) -> Result<TableSnapshot>
     where R: AsyncRead + Unpin + Send {
         let r = match self {
-            SnapshotVersion::V1(v) => load(reader, v).await?,
+            SnapshotVersion::V2(v) => load(reader, v).await?,
+            SnapshotVersion::V1(v) => load(reader, v).await?.into(),
             SnapshotVersion::V0(v) => load(reader, v).await?.into(),
         };
         Ok(r)
     }
 }
 
+#[async_trait::async_trait]
+impl VersionedReader<TableSnapshotLite> for SnapshotVersion {
+    async fn read<R>(&self, reader: R) -> Result<TableSnapshotLite>
+    where R: AsyncRead + Unpin + Send {
+        // the fields of a lite are decoded in the same way for all the versions
+        let head = load(reader, &PhantomData::<TableSnapshotHead>).await?;
+        Ok(TableSnapshotLite::from((head, self.version())))
+    }
+}
+
 #[async_trait::async_trait]
 impl VersionedReader<SegmentInfo> for SegmentInfoVersion {
     async fn read<R>(&self, reader: R) -> Result<SegmentInfo>
@@ -54,6 +73,39 @@ impl VersionedReader<SegmentInfo> for SegmentInfoVersion {
     }
 }
 
+#[async_trait::async_trait]
+impl VersionedReader<TableSnapshotStatistics> for SnapshotStatisticsVersion {
+    async fn read<R>(&self, reader: R) -> Result<TableSnapshotStatistics>
+    where R: AsyncRead + Unpin + Send {
+        let r = match self {
+            SnapshotStatisticsVersion::V0(v) => load(reader, v).await?,
+        };
+        Ok(r)
+    }
+}
+
+#[async_trait::async_trait]
+impl VersionedReader<AggregatingIndexMeta> for AggregatingIndexVersion {
+    async fn read<R>(&self, reader: R) -> Result<AggregatingIndexMeta>
+    where R: AsyncRead + Unpin + Send {
+        let r = match self {
+            AggregatingIndexVersion::V0(v) => load(reader, v).await?,
+        };
+        Ok(r)
+    }
+}
+
+#[async_trait::async_trait]
+impl VersionedReader<DeletionVector> for DeletionVectorVersion {
+    async fn read<R>(&self, reader: R) -> Result<DeletionVector>
+    where R: AsyncRead + Unpin + Send {
+        let r = match self {
+            DeletionVectorVersion::V0(v) => load(reader, v).await?,
+        };
+        Ok(r)
+    }
+}
+
 async fn load<R, T>(mut reader: R, _v: &PhantomData<T>) -> Result<T>
 where
     T: DeserializeOwned,
@@ -62,5 +114,5 @@ where
     let mut buffer: Vec<u8> = vec![];
     use futures::AsyncReadExt;
     reader.read_to_end(&mut buffer).await?;
-    Ok(from_slice::<T>(&buffer)?)
+    Encoding::decode(&buffer)
 }
@@ -31,9 +31,13 @@ use super::block_writer;
 use crate::storages::fuse::io::TableMetaLocationGenerator;
 use crate::storages::fuse::meta::ColumnId;
 use crate::storages::fuse::meta::ColumnMeta;
+use crate::storages::fuse::meta::IndexType;
 use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::meta::SnapshotId;
 use crate::storages::fuse::meta::Statistics;
+use crate::storages::fuse::operations::VirtualColumnsExtractor;
 use crate::storages::fuse::statistics::StatisticsAccumulator;
+use crate::storages::fuse::ColumnIds;
 
 pub type SegmentInfoStream =
     std::pin::Pin<Box<dyn futures::stream::Stream<Item = Result<SegmentInfo>> + Send>>;
@@ -45,9 +49,13 @@ pub struct BlockStreamWriter {
     number_of_blocks_accumulated: usize,
     statistics_accumulator: Option<StatisticsAccumulator>,
     meta_locations: TableMetaLocationGenerator,
+    snapshot_id: SnapshotId,
+    virtual_columns: Option<Arc<VirtualColumnsExtractor>>,
+    string_prefix_length: usize,
 }
 
 impl BlockStreamWriter {
+    #[allow(clippy::too_many_arguments)]
     pub async fn write_block_stream(
         data_accessor: Operator,
         block_stream: SendableDataBlockStream,
@@ -55,6 +63,9 @@ impl BlockStreamWriter {
         row_per_block: usize,
         block_per_segment: usize,
         meta_locations: TableMetaLocationGenerator,
+        snapshot_id: SnapshotId,
+        virtual_columns: Option<Arc<VirtualColumnsExtractor>>,
+        string_prefix_length: usize,
     ) -> SegmentInfoStream {
         // filter out empty blocks
         let block_stream =
@@ -75,6 +86,9 @@ impl BlockStreamWriter {
             data_accessor,
             data_schema,
             meta_locations,
+            snapshot_id,
+            virtual_columns,
+            string_prefix_length,
         );
         let segments = Self::transform(Box::pin(block_stream), block_writer);
 
@@ -86,6 +100,9 @@ impl BlockStreamWriter {
         data_accessor: Operator,
         data_schema: Arc<DataSchema>,
         meta_locations: TableMetaLocationGenerator,
+        snapshot_id: SnapshotId,
+        virtual_columns: Option<Arc<VirtualColumnsExtractor>>,
+        string_prefix_length: usize,
     ) -> Self {
         Self {
             num_block_threshold,
@@ -94,6 +111,9 @@ impl BlockStreamWriter {
             number_of_blocks_accumulated: 0,
             statistics_accumulator: None,
             meta_locations,
+            snapshot_id,
+            virtual_columns,
+            string_prefix_length,
         }
     }
 
@@ -126,8 +146,24 @@ impl BlockStreamWriter {
     }
 
     async fn write_block(&mut self, block: DataBlock) -> Result<Option<SegmentInfo>> {
-        let mut acc = self.statistics_accumulator.take().unwrap_or_default();
+        let mut acc = self.statistics_accumulator.take().unwrap_or_else(|| {
+            let column_ids = ColumnIds::from_schema(&self.data_schema);
+            StatisticsAccumulator::created_by(self.snapshot_id, column_ids)
+                .with_string_prefix_length(self.string_prefix_length)
+        });
         let partial_acc = acc.begin(&block)?;
+        let virtual_block = match &self.virtual_columns {
+            Some(virtual_columns) => {
+                let location = self.meta_locations.gen_virtual_block_location();
+                let (data, meta) = virtual_columns.serialize(&block, location)?;
+                self.data_accessor
+                    .object(&meta.location.0)
+                    .write(data)
+                    .await?;
+                Some(meta)
+            }
+            None => None,
+        };
         let schema = block.schema().to_arrow();
         let location = self.meta_locations.gen_block_location();
         let (file_size, file_meta_data) =
@@ -135,6 +171,12 @@ impl BlockStreamWriter {
                 .await?;
         let col_metas = Self::column_metas(&file_meta_data)?;
         acc = partial_acc.end(file_size, location, col_metas);
+        if let Some(vb) = &virtual_block {
+            acc.add_index_size(IndexType::VirtualColumn, vb.file_size);
+        }
+        if let Some(last) = acc.blocks_metas.last_mut() {
+            last.virtual_block = virtual_block;
+        }
         self.number_of_blocks_accumulated += 1;
         if self.number_of_blocks_accumulated >= self.num_block_threshold {
             let summary = acc.summary(self.data_schema.as_ref())?;
@@ -143,6 +185,8 @@ impl BlockStreamWriter {
                 block_count: acc.summary_block_count,
                 uncompressed_byte_size: acc.in_memory_size,
                 compressed_byte_size: acc.file_size,
+                index_byte_size: acc.index_size,
+                index_byte_sizes: acc.index_sizes,
                 col_stats: summary,
             });
 
@@ -241,6 +285,8 @@ impl Compactor<DataBlock, SegmentInfo> for BlockStreamWriter {
                     block_count: acc.summary_block_count,
                     uncompressed_byte_size: acc.in_memory_size,
                     compressed_byte_size: acc.file_size,
+                    index_byte_size: acc.index_size,
+                    index_byte_sizes: acc.index_sizes,
                     col_stats: summary,
                 });
                 Ok(Some(seg))
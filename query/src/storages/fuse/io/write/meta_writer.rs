@@ -0,0 +1,40 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_exception::Result;
+use opendal::Operator;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::meta::Encoding;
+use crate::storages::fuse::meta::TableSnapshot;
+
+/// Encodes the snapshot in the encoding of the setting `snapshot_encoding`.
+pub fn encode_snapshot(ctx: &QueryContext, snapshot: &TableSnapshot) -> Result<Vec<u8>> {
+    let encoding = ctx.get_settings().get_snapshot_encoding()?;
+    let encoding = String::from_utf8_lossy(&encoding).parse::<Encoding>()?;
+    encoding.encode(snapshot)
+}
+
+/// Writes the snapshot at `location`, in the encoding of the setting `snapshot_encoding`.
+pub async fn write_snapshot(
+    ctx: &QueryContext,
+    operator: &Operator,
+    location: &str,
+    snapshot: &TableSnapshot,
+) -> Result<()> {
+    let bytes = encode_snapshot(ctx, snapshot)?;
+    operator.object(location).write(bytes).await?;
+    Ok(())
+}
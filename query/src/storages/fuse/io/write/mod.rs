@@ -14,6 +14,7 @@
 
 mod block_stream_writer;
 mod block_writer;
+mod meta_writer;
 
 // for testing only
 pub use block_stream_writer::BlockCompactor;
@@ -21,3 +22,5 @@ pub use block_stream_writer::BlockStreamWriter;
 pub use block_stream_writer::SegmentInfoStream;
 pub use block_writer::serialize_data_block;
 pub use block_writer::write_block;
+pub use meta_writer::encode_snapshot;
+pub use meta_writer::write_snapshot;
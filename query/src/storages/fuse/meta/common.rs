@@ -12,12 +12,19 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 
+use common_datavalues::DataSchema;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_meta_types::TableUsage;
 use serde::Deserialize;
 use serde::Serialize;
 use uuid::Uuid;
 
+use crate::storages::fuse::statistics::reduce_block_stats;
 use crate::storages::index::ColumnStatistics;
 
 pub type ColumnId = u32;
@@ -25,6 +32,17 @@ pub type FormatVersion = u64;
 pub type SnapshotId = Uuid;
 pub type Location = (String, FormatVersion);
 
+/// The types of the indexes, which are kept in the files alongside the blocks
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexType {
+    /// The virtual columns extracted from the blocks
+    VirtualColumn,
+}
+
+/// Bytes of the index files, by the types of the indexes
+pub type IndexSizes = BTreeMap<IndexType, u64>;
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Statistics {
     pub row_count: u64,
@@ -32,10 +50,107 @@ pub struct Statistics {
 
     pub uncompressed_byte_size: u64,
     pub compressed_byte_size: u64,
+    /// Bytes of the files kept alongside the blocks, i.e. the virtual columns of them
+    #[serde(default)]
+    pub index_byte_size: u64,
+    /// `index_byte_size` by the types of the indexes, not kept by the legacy versions,
+    /// see [Statistics::index_sizes]
+    #[serde(default)]
+    pub index_byte_sizes: IndexSizes,
 
     pub col_stats: HashMap<ColumnId, ColumnStatistics>,
 }
 
+impl Statistics {
+    /// Returns the (min, max) of column `col_id`, if any statistics of it recorded
+    pub fn min_max_of(&self, col_id: ColumnId) -> Option<(&DataValue, &DataValue)> {
+        self.col_stats.get(&col_id).map(|s| (&s.min, &s.max))
+    }
+
+    /// Returns the number of null values of column `col_id`, if any statistics of it recorded
+    pub fn null_count_of(&self, col_id: ColumnId) -> Option<u64> {
+        self.col_stats.get(&col_id).map(|s| s.null_count)
+    }
+
+    /// Merges the statistics of two disjoint sets of blocks of a table of `schema`.
+    ///
+    /// Neither side is modified and the merge is commutative and associative, so the
+    /// statistics collected by appends and compactions running concurrently may be merged
+    /// in any order. The counts saturate at `u64::MAX` instead of overflowing.
+    pub fn merge(&self, other: &Statistics, schema: &DataSchema) -> Result<Statistics> {
+        let row_count = self.row_count.saturating_add(other.row_count);
+        let mut col_stats = reduce_block_stats(&[&self.col_stats, &other.col_stats], schema)?;
+        // there are no more distinct values than non-null ones
+        for stats in col_stats.values_mut() {
+            if let Some(distinct) = stats.distinct_of_values.as_mut() {
+                *distinct = (*distinct).min(row_count.saturating_sub(stats.null_count));
+            }
+        }
+
+        let mut merged = Statistics {
+            row_count,
+            block_count: self.block_count.saturating_add(other.block_count),
+            uncompressed_byte_size: self
+                .uncompressed_byte_size
+                .saturating_add(other.uncompressed_byte_size),
+            compressed_byte_size: self
+                .compressed_byte_size
+                .saturating_add(other.compressed_byte_size),
+            index_byte_size: self.index_byte_size,
+            index_byte_sizes: self.index_sizes().into_owned(),
+            col_stats,
+        };
+        for (index_type, size) in other.index_sizes().iter() {
+            merged.add_index_size(*index_type, *size);
+        }
+        Ok(merged)
+    }
+
+    /// Returns the bytes of the index files by the types of the indexes.
+    ///
+    /// The legacy versions only kept the total, which was all of the virtual columns, the
+    /// only type of index then.
+    pub fn index_sizes(&self) -> Cow<'_, IndexSizes> {
+        if self.index_byte_sizes.is_empty() && self.index_byte_size > 0 {
+            let sizes = [(IndexType::VirtualColumn, self.index_byte_size)];
+            return Cow::Owned(IndexSizes::from(sizes));
+        }
+        Cow::Borrowed(&self.index_byte_sizes)
+    }
+
+    /// Returns the bytes of the index files of `index_type`
+    pub fn index_size_of(&self, index_type: IndexType) -> u64 {
+        self.index_sizes().get(&index_type).copied().unwrap_or(0)
+    }
+
+    /// Adds `size` bytes of the index files of `index_type` to the summary
+    pub fn add_index_size(&mut self, index_type: IndexType, size: u64) {
+        self.index_byte_sizes = self.index_sizes().into_owned();
+        self.index_byte_size = self.index_byte_size.saturating_add(size);
+        let total = self.index_byte_sizes.entry(index_type).or_default();
+        *total = total.saturating_add(size);
+    }
+
+    /// Returns the distinct-value hint of column `col_id`, see
+    /// [ColumnStatistics::distinct_of_values]
+    pub fn distinct_of_values_of(&self, col_id: ColumnId) -> Option<u64> {
+        self.col_stats
+            .get(&col_id)
+            .and_then(|s| s.distinct_of_values)
+    }
+
+    /// Returns the storage usage of the table, which this is the summary of a snapshot of
+    pub fn table_usage(&self) -> TableUsage {
+        TableUsage {
+            number_of_rows: self.row_count,
+            number_of_blocks: self.block_count,
+            data_bytes: self.uncompressed_byte_size,
+            compressed_data_bytes: self.compressed_byte_size,
+            index_data_bytes: self.index_byte_size,
+        }
+    }
+}
+
 /// Thing has a u64 version nubmer
 pub trait Versioned<const V: u64>
 where Self: Sized
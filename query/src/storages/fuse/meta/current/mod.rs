@@ -12,10 +12,23 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+pub use v0::ColumnHistogram;
 pub use v0::ColumnMeta;
+pub use v0::DeletionVector;
+pub use v0::HistogramBucket;
+pub use v0::MostCommonValue;
+pub use v0::TableSnapshotStatistics;
+pub use v1::AggregatingIndexMeta;
 pub use v1::BlockMeta;
+pub use v1::DeletionVectorMeta;
 pub use v1::SegmentInfo;
-pub use v1::TableSnapshot;
+pub use v1::VirtualBlockMeta;
+pub use v2::SnapshotChanges;
+pub use v2::SnapshotOperation;
+pub use v2::TableSnapshot;
+pub use v2::TableSnapshotHead;
+pub use v2::TableSnapshotLite;
 
 use super::v0;
 use super::v1;
+use super::v2;
@@ -0,0 +1,106 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::io::Read;
+use std::io::Write;
+use std::str::FromStr;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// The first byte of the meta files encoded in MessagePack.
+const MESSAGE_PACK_HEADER: u8 = 0x01;
+
+/// The encoding of the meta files, e.g. the snapshots, told by their first byte on read.
+///
+/// JSON is the encoding of the files written before the encoding could be chosen, and it has
+/// no header byte: a JSON document never starts with the header byte of the other encodings.
+/// Bincode is not offered, since the schemas of the snapshots are encoded as internally tagged
+/// enums, which only self-describing encodings can decode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Json,
+    MessagePack,
+}
+
+impl Encoding {
+    /// Encodes `value` into `writer`, the encoded bytes are streamed into the writer as they
+    /// are produced, instead of being buffered as a whole.
+    pub fn encode_to<T, W>(&self, value: &T, mut writer: W) -> Result<()>
+    where
+        T: Serialize,
+        W: Write,
+    {
+        match self {
+            Encoding::Json => serde_json::to_writer(writer, value)?,
+            Encoding::MessagePack => {
+                writer.write_all(&[MESSAGE_PACK_HEADER])?;
+                // the structs are encoded as maps, thus the fields could be added or skipped
+                rmp_serde::encode::write_named(&mut writer, value)
+                    .map_err(|e| ErrorCode::BadBytes(format!("Cannot encode meta: {}", e)))?
+            }
+        }
+        Ok(())
+    }
+
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        let mut bytes = vec![];
+        self.encode_to(value, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Decodes a value from `reader`, in the encoding told by its first byte. The value is
+    /// decoded as the bytes are read, instead of being buffered as a whole.
+    pub fn decode_from<T, R>(mut reader: R) -> Result<T>
+    where
+        T: DeserializeOwned,
+        R: Read,
+    {
+        let mut first = [0u8; 1];
+        let read = reader.read(&mut first)?;
+        match first[0] {
+            MESSAGE_PACK_HEADER if read == 1 => rmp_serde::decode::from_read(reader)
+                .map_err(|e| ErrorCode::BadBytes(format!("Cannot decode meta: {}", e))),
+            // the first byte of JSON is a part of the document
+            _ => Ok(serde_json::from_reader((&first[..read]).chain(reader))?),
+        }
+    }
+
+    /// Decodes a value from `bytes`, in the encoding told by its first byte.
+    pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+        match bytes.split_first() {
+            Some((&MESSAGE_PACK_HEADER, rest)) => rmp_serde::decode::from_slice(rest)
+                .map_err(|e| ErrorCode::BadBytes(format!("Cannot decode meta: {}", e))),
+            _ => Ok(serde_json::from_slice(bytes)?),
+        }
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = ErrorCode;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Encoding::Json),
+            "msgpack" | "messagepack" => Ok(Encoding::MessagePack),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Unknown encoding {}, expecting json or msgpack",
+                s
+            ))),
+        }
+    }
+}
@@ -14,19 +14,27 @@
 //
 
 mod common;
+mod encoding;
 
-/// Re-exports meta data structures of current version, i.e. v1
+/// Re-exports meta data structures of current version, i.e. v1 (segment) and v2 (snapshot)
 mod current;
 mod v0;
 mod v1;
+mod v2;
 mod versions;
 
 pub use common::ColumnId;
 pub use common::Compression;
+pub use common::IndexSizes;
+pub use common::IndexType;
 pub use common::Location;
 pub use common::SnapshotId;
 pub use common::Statistics;
 pub use common::Versioned;
 pub use current::*;
+pub use encoding::Encoding;
+pub use versions::AggregatingIndexVersion;
+pub use versions::DeletionVectorVersion;
 pub use versions::SegmentInfoVersion;
+pub use versions::SnapshotStatisticsVersion;
 pub use versions::SnapshotVersion;
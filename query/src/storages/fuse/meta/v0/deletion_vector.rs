@@ -0,0 +1,68 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use crate::storages::fuse::meta::common::FormatVersion;
+use crate::storages::fuse::meta::common::Versioned;
+
+/// The rows of a block which have been deleted, kept aside the block so that the block itself
+/// need not be rewritten by `DELETE`.
+///
+/// The rows are identified by their positions in the block, the bit of the i-th row is set if
+/// it has been deleted.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct DeletionVector {
+    /// format version of deletion vector
+    format_version: FormatVersion,
+
+    /// number of rows of the block, including the deleted ones
+    pub row_count: u64,
+
+    /// the bitmap of the deleted rows, 64 rows per word
+    bitmap: Vec<u64>,
+}
+
+impl DeletionVector {
+    /// Creates a deletion vector of a block of `row_count` rows, none of which are deleted
+    pub fn new(row_count: u64) -> Self {
+        Self {
+            format_version: DeletionVector::VERSION,
+            row_count,
+            bitmap: vec![0; ((row_count + 63) / 64) as usize],
+        }
+    }
+
+    pub fn format_version(&self) -> u64 {
+        self.format_version
+    }
+
+    pub fn delete(&mut self, row: usize) {
+        self.bitmap[row / 64] |= 1 << (row % 64);
+    }
+
+    pub fn is_deleted(&self, row: usize) -> bool {
+        self.bitmap[row / 64] & (1 << (row % 64)) != 0
+    }
+
+    pub fn deleted_row_count(&self) -> u64 {
+        self.bitmap.iter().map(|w| w.count_ones() as u64).sum()
+    }
+
+    /// Returns a filter of the rows, which is true for the rows that are not deleted
+    pub fn live_rows(&self) -> Vec<bool> {
+        (0..self.row_count as usize)
+            .map(|row| !self.is_deleted(row))
+            .collect()
+    }
+}
@@ -12,10 +12,17 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod deletion_vector;
 mod segment;
 mod snapshot;
+mod snapshot_statistics;
 
+pub use deletion_vector::DeletionVector;
 pub use segment::BlockMeta;
 pub use segment::ColumnMeta;
 pub use segment::SegmentInfo;
 pub use snapshot::TableSnapshot;
+pub use snapshot_statistics::ColumnHistogram;
+pub use snapshot_statistics::HistogramBucket;
+pub use snapshot_statistics::MostCommonValue;
+pub use snapshot_statistics::TableSnapshotStatistics;
@@ -0,0 +1,96 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+
+use common_datavalues::DataValue;
+
+use crate::storages::fuse::meta::common::ColumnId;
+use crate::storages::fuse::meta::common::SnapshotId;
+use crate::storages::fuse::meta::common::Versioned;
+
+/// Statistics of the data of a snapshot, built by `ANALYZE TABLE`, for the estimation of
+/// cardinalities.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct TableSnapshotStatistics {
+    /// format version of snapshot statistics
+    format_version: u64,
+
+    /// id of the snapshot, which the statistics are built from
+    pub snapshot_id: SnapshotId,
+
+    /// number of rows of the snapshot
+    pub row_count: u64,
+
+    /// number of rows which have been sampled to build the statistics
+    pub sampled_row_count: u64,
+
+    /// histograms of the columns, columns of types that could not be ordered are absent
+    pub column_histograms: HashMap<ColumnId, ColumnHistogram>,
+}
+
+impl TableSnapshotStatistics {
+    pub fn new(
+        snapshot_id: SnapshotId,
+        row_count: u64,
+        sampled_row_count: u64,
+        column_histograms: HashMap<ColumnId, ColumnHistogram>,
+    ) -> Self {
+        Self {
+            format_version: TableSnapshotStatistics::VERSION,
+            snapshot_id,
+            row_count,
+            sampled_row_count,
+            column_histograms,
+        }
+    }
+
+    pub fn format_version(&self) -> u64 {
+        self.format_version
+    }
+}
+
+/// Distribution of the values of a column.
+///
+/// The values which appear most frequently are kept in `most_common_values`, and all the
+/// non-null values (including the most common ones) are summarized by an equi-height histogram:
+/// the buckets are ordered by their bounds, and contain roughly the same number of values.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default)]
+pub struct ColumnHistogram {
+    pub null_count: u64,
+    /// estimated number of distinct non-null values
+    pub distinct_count: u64,
+    pub most_common_values: Vec<MostCommonValue>,
+    pub buckets: Vec<HistogramBucket>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct MostCommonValue {
+    pub value: DataValue,
+    /// estimated number of occurrences
+    pub count: u64,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct HistogramBucket {
+    /// the smallest value of the bucket, inclusive
+    pub lower_bound: DataValue,
+    /// the largest value of the bucket, inclusive
+    pub upper_bound: DataValue,
+    /// estimated number of values in the bucket
+    pub count: u64,
+    /// estimated number of distinct values in the bucket
+    pub distinct_count: u64,
+}
@@ -0,0 +1,49 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use crate::storages::fuse::meta::common::FormatVersion;
+use crate::storages::fuse::meta::common::SnapshotId;
+use crate::storages::fuse::meta::common::Versioned;
+use crate::storages::fuse::meta::v1::BlockMeta;
+
+/// The data of an aggregating index, which is up to date with a snapshot of the table.
+///
+/// Each of the blocks keeps the aggregates of a part of the table, grouped by the keys of the
+/// index, thus the aggregates of the whole table are merged from the ones of the blocks.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct AggregatingIndexMeta {
+    /// format version of aggregating index meta
+    format_version: FormatVersion,
+
+    /// id of the snapshot, which the index is refreshed for
+    pub snapshot_id: SnapshotId,
+
+    /// blocks of the partial aggregates
+    pub blocks: Vec<BlockMeta>,
+}
+
+impl AggregatingIndexMeta {
+    pub fn new(snapshot_id: SnapshotId, blocks: Vec<BlockMeta>) -> Self {
+        Self {
+            format_version: AggregatingIndexMeta::VERSION,
+            snapshot_id,
+            blocks,
+        }
+    }
+
+    pub fn format_version(&self) -> u64 {
+        self.format_version
+    }
+}
@@ -12,9 +12,13 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod aggregating_index;
 mod segment;
 mod snapshot;
 
+pub use aggregating_index::AggregatingIndexMeta;
 pub use segment::BlockMeta;
+pub use segment::DeletionVectorMeta;
 pub use segment::SegmentInfo;
+pub use segment::VirtualBlockMeta;
 pub use snapshot::TableSnapshot;
@@ -13,8 +13,11 @@
 //  limitations under the License.
 //
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 
+use chrono::DateTime;
+use chrono::Utc;
 use common_datablocks::DataBlock;
 use serde::Deserialize;
 use serde::Serialize;
@@ -22,7 +25,9 @@ use serde::Serialize;
 use crate::storages::fuse::meta::common::ColumnId;
 use crate::storages::fuse::meta::common::Compression;
 use crate::storages::fuse::meta::common::FormatVersion;
+use crate::storages::fuse::meta::common::IndexType;
 use crate::storages::fuse::meta::common::Location;
+use crate::storages::fuse::meta::common::SnapshotId;
 use crate::storages::fuse::meta::common::Statistics;
 use crate::storages::fuse::meta::common::Versioned;
 use crate::storages::fuse::meta::v0::ColumnMeta;
@@ -57,6 +62,73 @@ pub struct BlockMeta {
     /// used in the write path.
     #[serde(default = "Compression::legacy")]
     pub compression: Compression,
+
+    /// The snapshot which the block is committed by
+    ///
+    /// Not recorded by the legacy versions, and by the writers of which the snapshot is not
+    /// known while writing the blocks.
+    #[serde(default)]
+    pub created_by: Option<SnapshotId>,
+
+    /// When the block is written, not recorded by the legacy versions
+    #[serde(default)]
+    pub created_on: Option<DateTime<Utc>>,
+
+    /// The rows of the block which have been deleted, if any
+    #[serde(default)]
+    pub deletion_vector: Option<DeletionVectorMeta>,
+
+    /// The virtual columns extracted from the block, if any
+    #[serde(default)]
+    pub virtual_block: Option<VirtualBlockMeta>,
+}
+
+/// Where the deletion vector of a block is kept
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DeletionVectorMeta {
+    pub location: Location,
+    /// number of the rows marked as deleted by the deletion vector
+    pub deleted_row_count: u64,
+}
+
+/// Where the virtual columns extracted from a block are kept
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct VirtualBlockMeta {
+    pub location: Location,
+    pub file_size: u64,
+    /// metas of the virtual columns in the file, by the names of the virtual columns
+    pub col_metas: BTreeMap<String, ColumnMeta>,
+}
+
+impl BlockMeta {
+    /// Number of the rows of the block which have not been deleted
+    pub fn live_row_count(&self) -> u64 {
+        match &self.deletion_vector {
+            Some(dv) => self.row_count - dv.deleted_row_count,
+            None => self.row_count,
+        }
+    }
+
+    /// Bytes of the virtual columns of the block, if any
+    pub fn index_size(&self) -> u64 {
+        self.virtual_block.as_ref().map_or(0, |vb| vb.file_size)
+    }
+
+    /// Bytes of the index files of the block, by the types of the indexes
+    pub fn index_sizes(&self) -> impl Iterator<Item = (IndexType, u64)> + '_ {
+        let virtual_block = self.virtual_block.iter();
+        virtual_block.map(|vb| (IndexType::VirtualColumn, vb.file_size))
+    }
+
+    /// Locations of the files of the block, i.e. the data, the deletion vector and the virtual
+    /// columns of it
+    pub fn file_locations(&self) -> impl Iterator<Item = &String> {
+        let deletion_vector = self.deletion_vector.iter().map(|dv| &dv.location.0);
+        let virtual_block = self.virtual_block.iter().map(|vb| &vb.location.0);
+        std::iter::once(&self.location.0)
+            .chain(deletion_vector)
+            .chain(virtual_block)
+    }
 }
 
 impl SegmentInfo {
@@ -95,6 +167,10 @@ impl From<v0::BlockMeta> for BlockMeta {
             col_metas: s.col_metas,
             location: (s.location.path, DataBlock::VERSION),
             compression: Compression::Lz4,
+            created_by: None,
+            created_on: None,
+            deletion_vector: None,
+            virtual_block: None,
         }
     }
 }
@@ -0,0 +1,290 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use chrono::DateTime;
+use chrono::Utc;
+use common_datavalues::DataSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::storages::fuse::meta::common::FormatVersion;
+use crate::storages::fuse::meta::common::Location;
+use crate::storages::fuse::meta::common::SnapshotId;
+use crate::storages::fuse::meta::common::Statistics;
+use crate::storages::fuse::meta::common::Versioned;
+use crate::storages::fuse::meta::v0;
+use crate::storages::fuse::meta::v1;
+
+/// The blocks inserted into and deleted from the table by a snapshot, compared with its
+/// previous snapshot.
+///
+/// Blocks are tracked at the granularity of segments: segments are immutable, all the
+/// blocks of an inserted (deleted) segment are inserted (deleted).
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SnapshotChanges {
+    pub inserted_segments: Vec<Location>,
+    pub deleted_segments: Vec<Location>,
+}
+
+/// The kind of operation which produced a snapshot
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotOperation {
+    Insert,
+    Overwrite,
+    Truncate,
+    Delete,
+    Update,
+    Merge,
+    Compact,
+    Analyze,
+    Flashback,
+    Recluster,
+    Expire,
+    Restore,
+}
+
+impl fmt::Display for SnapshotOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotOperation::Insert => write!(f, "INSERT"),
+            SnapshotOperation::Overwrite => write!(f, "OVERWRITE"),
+            SnapshotOperation::Truncate => write!(f, "TRUNCATE"),
+            SnapshotOperation::Delete => write!(f, "DELETE"),
+            SnapshotOperation::Update => write!(f, "UPDATE"),
+            SnapshotOperation::Merge => write!(f, "MERGE"),
+            SnapshotOperation::Compact => write!(f, "COMPACT"),
+            SnapshotOperation::Analyze => write!(f, "ANALYZE"),
+            SnapshotOperation::Flashback => write!(f, "FLASHBACK"),
+            SnapshotOperation::Recluster => write!(f, "RECLUSTER"),
+            SnapshotOperation::Expire => write!(f, "EXPIRE"),
+            SnapshotOperation::Restore => write!(f, "RESTORE"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TableSnapshot {
+    /// format version of snapshot
+    format_version: FormatVersion,
+
+    /// id of snapshot
+    pub snapshot_id: SnapshotId,
+
+    pub prev_snapshot_id: Option<(SnapshotId, FormatVersion)>,
+
+    /// When this snapshot was created, not available for snapshots converted from legacy versions
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+
+    /// For each snapshot, we keep a schema for it (in case of schema evolution)
+    pub schema: DataSchema,
+
+    /// Summary Statistics
+    pub summary: Statistics,
+
+    /// Pointers to SegmentInfos (may be of different format)
+    ///
+    /// We rely on background merge tasks to keep merging segments, so that
+    /// this the size of this vector could be kept reasonable
+    pub segments: Vec<Location>,
+
+    /// Version of the node which wrote this snapshot
+    #[serde(default)]
+    pub written_by: Option<String>,
+
+    /// Id of the transaction (query) which committed this snapshot
+    #[serde(default)]
+    pub txn_id: Option<String>,
+
+    /// Changes made by this snapshot, not available for snapshots converted from legacy versions
+    #[serde(default)]
+    pub changes: Option<SnapshotChanges>,
+
+    /// Location of the statistics built by `ANALYZE TABLE`, which may be built from a
+    /// previous snapshot
+    #[serde(default)]
+    pub table_statistics_location: Option<String>,
+
+    /// The operation which produced this snapshot
+    #[serde(default)]
+    pub operation: Option<SnapshotOperation>,
+
+    /// Identity of the user who issued the operation
+    #[serde(default)]
+    pub user: Option<String>,
+}
+
+impl TableSnapshot {
+    pub fn new(
+        snapshot_id: SnapshotId,
+        prev_snapshot_id: Option<(SnapshotId, FormatVersion)>,
+        schema: DataSchema,
+        summary: Statistics,
+        segments: Vec<Location>,
+    ) -> Self {
+        Self {
+            format_version: TableSnapshot::VERSION,
+            snapshot_id,
+            prev_snapshot_id,
+            timestamp: Some(Utc::now()),
+            schema,
+            summary,
+            segments,
+            written_by: None,
+            txn_id: None,
+            changes: None,
+            table_statistics_location: None,
+            operation: None,
+            user: None,
+        }
+    }
+
+    /// Tags the snapshot with the version of the writer node and the id of
+    /// the originating transaction.
+    #[must_use]
+    pub fn with_origin(mut self, written_by: impl Into<String>, txn_id: impl Into<String>) -> Self {
+        self.written_by = Some(written_by.into());
+        self.txn_id = Some(txn_id.into());
+        self
+    }
+
+    /// Tags the snapshot with the operation which produced it and the user who issued it.
+    #[must_use]
+    pub fn with_operation(mut self, operation: SnapshotOperation, user: Option<String>) -> Self {
+        self.operation = Some(operation);
+        self.user = user;
+        self
+    }
+
+    /// Records the changes made by this snapshot, compared with the `previous` one.
+    #[must_use]
+    pub fn with_changes(mut self, previous: Option<&TableSnapshot>) -> Self {
+        let prev_segments: HashSet<&Location> = previous
+            .map(|s| s.segments.iter().collect())
+            .unwrap_or_default();
+        let segments: HashSet<&Location> = self.segments.iter().collect();
+        let inserted_segments = self
+            .segments
+            .iter()
+            .filter(|l| !prev_segments.contains(l))
+            .cloned()
+            .collect();
+        let deleted_segments = previous
+            .map(|s| {
+                s.segments
+                    .iter()
+                    .filter(|l| !segments.contains(l))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.changes = Some(SnapshotChanges {
+            inserted_segments,
+            deleted_segments,
+        });
+        self
+    }
+
+    pub fn format_version(&self) -> u64 {
+        self.format_version
+    }
+}
+
+/// The essentials of a snapshot, which are kept instead of the whole snapshot while walking
+/// through a long history of the table.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TableSnapshotLite {
+    /// The version in which the snapshot is stored, which may be older than the one of the
+    /// [TableSnapshot] it is converted into
+    pub format_version: FormatVersion,
+    pub snapshot_id: SnapshotId,
+    pub prev_snapshot_id: Option<(SnapshotId, FormatVersion)>,
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl From<(&TableSnapshot, FormatVersion)> for TableSnapshotLite {
+    fn from((s, format_version): (&TableSnapshot, FormatVersion)) -> Self {
+        Self {
+            format_version,
+            snapshot_id: s.snapshot_id,
+            prev_snapshot_id: s.prev_snapshot_id,
+            timestamp: s.timestamp,
+        }
+    }
+}
+
+/// The fields of a stored snapshot of any version, which a [TableSnapshotLite] is made of.
+///
+/// The other fields are skipped over instead of being decoded, the schema, the summary and
+/// the segments of a snapshot especially, which are the bulk of it.
+#[derive(Deserialize)]
+pub struct TableSnapshotHead {
+    snapshot_id: SnapshotId,
+    prev_snapshot_id: Option<PrevSnapshotId>,
+    /// Not recorded before version 2
+    #[serde(default)]
+    timestamp: Option<DateTime<Utc>>,
+}
+
+/// The format version of the previous snapshot is not recorded by version 0, of which the
+/// previous snapshots are all of version 0 as well
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PrevSnapshotId {
+    Versioned(SnapshotId, FormatVersion),
+    Legacy(SnapshotId),
+}
+
+impl From<(TableSnapshotHead, FormatVersion)> for TableSnapshotLite {
+    fn from((s, format_version): (TableSnapshotHead, FormatVersion)) -> Self {
+        Self {
+            format_version,
+            snapshot_id: s.snapshot_id,
+            prev_snapshot_id: s.prev_snapshot_id.map(|prev| match prev {
+                PrevSnapshotId::Versioned(id, ver) => (id, ver),
+                PrevSnapshotId::Legacy(id) => (id, 0),
+            }),
+            timestamp: s.timestamp,
+        }
+    }
+}
+
+impl From<v1::TableSnapshot> for TableSnapshot {
+    fn from(s: v1::TableSnapshot) -> Self {
+        Self {
+            format_version: TableSnapshot::VERSION,
+            snapshot_id: s.snapshot_id,
+            prev_snapshot_id: s.prev_snapshot_id,
+            timestamp: None,
+            schema: s.schema,
+            summary: s.summary,
+            segments: s.segments,
+            written_by: None,
+            txn_id: None,
+            changes: None,
+            table_statistics_location: None,
+            operation: None,
+            user: None,
+        }
+    }
+}
+
+impl From<v0::TableSnapshot> for TableSnapshot {
+    fn from(s: v0::TableSnapshot) -> Self {
+        v1::TableSnapshot::from(s).into()
+    }
+}
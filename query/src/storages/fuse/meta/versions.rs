@@ -20,6 +20,7 @@ use common_exception::ErrorCode;
 use crate::storages::fuse::meta::common::Versioned;
 use crate::storages::fuse::meta::v0;
 use crate::storages::fuse::meta::v1;
+use crate::storages::fuse::meta::v2;
 
 // Here versions of meta are tagged with numeric values
 //
@@ -43,10 +44,66 @@ pub enum SegmentInfoVersion {
 
 impl Versioned<0> for v0::TableSnapshot {}
 impl Versioned<1> for v1::TableSnapshot {}
+impl Versioned<2> for v2::TableSnapshot {}
 
 pub enum SnapshotVersion {
     V0(PhantomData<v0::TableSnapshot>),
     V1(PhantomData<v1::TableSnapshot>),
+    V2(PhantomData<v2::TableSnapshot>),
+}
+
+impl Versioned<0> for v0::TableSnapshotStatistics {}
+
+pub enum SnapshotStatisticsVersion {
+    V0(PhantomData<v0::TableSnapshotStatistics>),
+}
+
+impl Versioned<0> for v0::DeletionVector {}
+
+pub enum DeletionVectorVersion {
+    V0(PhantomData<v0::DeletionVector>),
+}
+
+impl Versioned<0> for v1::AggregatingIndexMeta {}
+
+pub enum AggregatingIndexVersion {
+    V0(PhantomData<v1::AggregatingIndexMeta>),
+}
+
+impl AggregatingIndexVersion {
+    pub fn version(&self) -> u64 {
+        match self {
+            AggregatingIndexVersion::V0(a) => Self::ver(a),
+        }
+    }
+
+    fn ver<const V: u64, T: Versioned<V>>(_v: &PhantomData<T>) -> u64 {
+        V
+    }
+}
+
+impl DeletionVectorVersion {
+    pub fn version(&self) -> u64 {
+        match self {
+            DeletionVectorVersion::V0(a) => Self::ver(a),
+        }
+    }
+
+    fn ver<const V: u64, T: Versioned<V>>(_v: &PhantomData<T>) -> u64 {
+        V
+    }
+}
+
+impl SnapshotStatisticsVersion {
+    pub fn version(&self) -> u64 {
+        match self {
+            SnapshotStatisticsVersion::V0(a) => Self::ver(a),
+        }
+    }
+
+    fn ver<const V: u64, T: Versioned<V>>(_v: &PhantomData<T>) -> u64 {
+        V
+    }
 }
 
 impl SnapshotVersion {
@@ -54,6 +111,7 @@ impl SnapshotVersion {
         match self {
             SnapshotVersion::V0(a) => Self::ver(a),
             SnapshotVersion::V1(a) => Self::ver(a),
+            SnapshotVersion::V2(a) => Self::ver(a),
         }
     }
 
@@ -91,8 +149,45 @@ mod converters {
             match value {
                 0 => Ok(SnapshotVersion::V0(ver_eq::<_, 0>(PhantomData))),
                 1 => Ok(SnapshotVersion::V1(ver_eq::<_, 1>(PhantomData))),
+                2 => Ok(SnapshotVersion::V2(ver_eq::<_, 2>(PhantomData))),
+                _ => Err(ErrorCode::LogicalError(format!(
+                    "unknown snapshot segment version {value}, versions supported: 0, 1, 2"
+                ))),
+            }
+        }
+    }
+
+    impl TryFrom<u64> for SnapshotStatisticsVersion {
+        type Error = ErrorCode;
+        fn try_from(value: u64) -> std::result::Result<Self, Self::Error> {
+            match value {
+                0 => Ok(SnapshotStatisticsVersion::V0(ver_eq::<_, 0>(PhantomData))),
+                _ => Err(ErrorCode::LogicalError(format!(
+                    "unknown snapshot statistics version {value}, versions supported: 0"
+                ))),
+            }
+        }
+    }
+
+    impl TryFrom<u64> for DeletionVectorVersion {
+        type Error = ErrorCode;
+        fn try_from(value: u64) -> std::result::Result<Self, Self::Error> {
+            match value {
+                0 => Ok(DeletionVectorVersion::V0(ver_eq::<_, 0>(PhantomData))),
+                _ => Err(ErrorCode::LogicalError(format!(
+                    "unknown deletion vector version {value}, versions supported: 0"
+                ))),
+            }
+        }
+    }
+
+    impl TryFrom<u64> for AggregatingIndexVersion {
+        type Error = ErrorCode;
+        fn try_from(value: u64) -> std::result::Result<Self, Self::Error> {
+            match value {
+                0 => Ok(AggregatingIndexVersion::V0(ver_eq::<_, 0>(PhantomData))),
                 _ => Err(ErrorCode::LogicalError(format!(
-                    "unknown snapshot segment version {value}, versions supported: 0, 1"
+                    "unknown aggregating index version {value}, versions supported: 0"
                 ))),
             }
         }
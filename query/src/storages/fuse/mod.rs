@@ -13,10 +13,16 @@
 //  limitations under the License.
 
 pub mod cache;
+mod cluster_key;
+mod clustering_information;
+mod column_ids;
 mod constants;
+mod fuse_block;
 mod fuse_history;
 mod fuse_part;
+mod fuse_snapshot_diff;
 mod fuse_table;
+mod fuse_verify;
 pub mod io;
 pub mod meta;
 pub mod operations;
@@ -24,8 +30,28 @@ pub mod pruning;
 pub mod statistics;
 mod table_functions;
 
+pub use cluster_key::ordinal_of;
+pub use cluster_key::z_value;
+pub use cluster_key::zorder_indices;
+pub use cluster_key::ClusterKey;
+pub use cluster_key::ClusterOrder;
+pub use clustering_information::ClusteringInformation;
+pub use clustering_information::ClusteringStatistics;
+pub use column_ids::ColumnIds;
 pub use constants::*;
+pub use fuse_block::FuseBlock;
 pub use fuse_history::FuseHistory;
+pub use fuse_part::FusePartInfo;
+pub use fuse_snapshot_diff::FuseSnapshotDiff;
 pub use fuse_table::FuseTable;
+pub use fuse_verify::FuseVerify;
+pub use table_functions::ClusteringInformationTable;
+pub use table_functions::FuseBlockTable;
 pub use table_functions::FuseHistoryTable;
+pub use table_functions::FuseSnapshotDiffTable;
+pub use table_functions::FuseVerifyTable;
+pub use table_functions::FUSE_FUNC_BLOCK;
+pub use table_functions::FUSE_FUNC_CLUSTERING;
 pub use table_functions::FUSE_FUNC_HIST;
+pub use table_functions::FUSE_FUNC_SNAPSHOT_DIFF;
+pub use table_functions::FUSE_FUNC_VERIFY;
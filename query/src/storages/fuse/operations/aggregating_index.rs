@@ -0,0 +1,392 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::AggregatingIndexDefinition;
+use common_planners::Expression;
+use common_planners::Extras;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::ReadDataSourcePlan;
+use common_planners::SelectPlan;
+use common_planners::SourceInfo;
+use common_tracing::tracing;
+
+use crate::catalogs::Catalog;
+use crate::pipelines::new::executor::PipelinePullingExecutor;
+use crate::pipelines::new::QueryPipelineBuilder;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::write_block;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::meta::AggregatingIndexMeta;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::SnapshotId;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::meta::Versioned;
+use crate::storages::fuse::statistics::accumulator::BlockStatistics;
+use crate::storages::fuse::statistics::StatisticsAccumulator;
+use crate::storages::fuse::ColumnIds;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::DEFAULT_ROW_PER_BLOCK;
+use crate::storages::fuse::FUSE_OPT_KEY_AGGREGATING_INDEX_PREFIX;
+use crate::storages::fuse::FUSE_OPT_KEY_ROW_PER_BLOCK;
+
+impl FuseTable {
+    /// Definitions of the aggregating indexes of the table, by the names of the indexes.
+    pub fn aggregating_indexes(
+        table_info: &TableInfo,
+    ) -> Result<BTreeMap<String, AggregatingIndexDefinition>> {
+        table_info
+            .options()
+            .iter()
+            .filter_map(|(key, value)| {
+                key.strip_prefix(FUSE_OPT_KEY_AGGREGATING_INDEX_PREFIX)
+                    .map(|name| (name, value))
+            })
+            .map(|(name, value)| Ok((name.to_owned(), serde_json::from_str(value)?)))
+            .collect()
+    }
+
+    /// The schema of the blocks of an aggregating index, i.e. the keys followed by the
+    /// aggregates, which are named after the aggregate functions.
+    pub fn aggregating_index_schema(
+        table_schema: &DataSchemaRef,
+        definition: &AggregatingIndexDefinition,
+    ) -> Result<DataSchemaRef> {
+        let mut fields = Vec::with_capacity(definition.keys.len() + definition.aggregates.len());
+        for key in &definition.keys {
+            fields.push(table_schema.field_with_name(key)?.clone());
+        }
+        for expr in definition.aggregate_exprs() {
+            fields.push(expr.to_data_field(table_schema)?);
+        }
+        Ok(DataSchemaRefExt::create(fields))
+    }
+
+    /// Reads the meta of the aggregating index which is refreshed for the snapshot, `None` if
+    /// the index is not up to date with the snapshot.
+    pub async fn read_aggregating_index_meta(
+        &self,
+        ctx: &QueryContext,
+        index: &str,
+        snapshot_id: &SnapshotId,
+    ) -> Result<Option<Arc<AggregatingIndexMeta>>> {
+        let location = self
+            .meta_location_generator
+            .aggregating_index_meta_location(index, snapshot_id);
+        let reader = MetaReaders::aggregating_index_meta_reader(ctx);
+        match reader
+            .read(location.as_str(), None, AggregatingIndexMeta::VERSION)
+            .await
+        {
+            Ok(meta) => Ok(Some(meta)),
+            Err(e) if e.code() == ErrorCode::storage_not_found_code() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Plan to read the aggregating index, as if the blocks of it were the data of the table.
+    pub fn aggregating_index_source_plan(
+        &self,
+        index: &str,
+        definition: &AggregatingIndexDefinition,
+        meta: &AggregatingIndexMeta,
+    ) -> Result<ReadDataSourcePlan> {
+        let schema = Self::aggregating_index_schema(&self.table_info.schema(), definition)?;
+        let mut table_info = self.table_info.clone();
+        table_info.meta.schema = schema.clone();
+        table_info.meta.order_keys = None;
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect::<Vec<_>>();
+        let mut plan = Self::read_blocks_plan(&table_info, &meta.blocks, &columns)?;
+        plan.description = format!(
+            "(Read from aggregating index {} of {} table, Read Rows:{}, Partitions Scanned:{})",
+            index, table_info.desc, plan.statistics.read_rows, plan.statistics.partitions_scanned,
+        );
+        Ok(plan)
+    }
+
+    /// Plan to read the columns of the blocks, which are in the schema of `table_info`.
+    fn read_blocks_plan(
+        table_info: &TableInfo,
+        blocks: &[BlockMeta],
+        columns: &[String],
+    ) -> Result<ReadDataSourcePlan> {
+        let schema = table_info.schema();
+        let mut projection = columns
+            .iter()
+            .map(|c| schema.index_of(c))
+            .collect::<Result<Vec<_>>>()?;
+        projection.sort_unstable();
+        projection.dedup();
+        // the rows are still to be counted, even if no columns are aggregated
+        if projection.is_empty() {
+            projection.push(0);
+        }
+
+        let scan_fields = projection
+            .iter()
+            .map(|idx| (*idx, schema.field(*idx).clone()))
+            .collect::<BTreeMap<_, _>>();
+        let push_downs = Some(Extras {
+            projection: Some(projection),
+            ..Extras::default()
+        });
+        let (statistics, parts) = Self::to_partitions(&schema, blocks, push_downs.clone());
+        Ok(ReadDataSourcePlan {
+            source_info: SourceInfo::TableSource(table_info.clone()),
+            scan_fields: Some(scan_fields),
+            parts,
+            statistics,
+            description: format!("(Read from {} table)", table_info.desc),
+            tbl_args: None,
+            push_downs,
+        })
+    }
+
+    /// Refreshes the aggregating indexes of the table, so that they are up to date with the
+    /// current snapshot.
+    pub async fn refresh_aggregating_indexes(&self, ctx: &Arc<QueryContext>) -> Result<()> {
+        let indexes = Self::aggregating_indexes(&self.table_info)?;
+        if indexes.is_empty() {
+            return Ok(());
+        }
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            // nothing to index
+            None => return Ok(()),
+        };
+        for (index, definition) in &indexes {
+            self.refresh_aggregating_index(ctx, index, definition, &snapshot)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Refreshes the aggregating indexes of the latest version of the table, which has just
+    /// been committed. The indexes that fail to be refreshed are left behind, they are
+    /// rebuilt by the next refresh, instead of failing the committed operation.
+    pub(crate) async fn refresh_aggregating_indexes_after_commit(&self, ctx: &Arc<QueryContext>) {
+        if !self
+            .table_info
+            .options()
+            .keys()
+            .any(|key| key.starts_with(FUSE_OPT_KEY_AGGREGATING_INDEX_PREFIX))
+        {
+            return;
+        }
+
+        let refreshed = async {
+            let catalog = ctx.get_catalog();
+            let (ident, meta) = catalog
+                .get_table_meta_by_id(self.table_info.ident.table_id)
+                .await?;
+            let table_info = TableInfo {
+                ident,
+                desc: self.table_info.desc.clone(),
+                name: self.table_info.name.clone(),
+                meta: meta.as_ref().clone(),
+            };
+            let latest = catalog.get_table_by_info(&table_info)?;
+            FuseTable::try_from_table(latest.as_ref())?
+                .refresh_aggregating_indexes(ctx)
+                .await
+        };
+        if let Err(e) = refreshed.await {
+            tracing::warn!(
+                "failed to refresh the aggregating indexes of table {}: {}",
+                self.table_info.desc,
+                e
+            );
+        }
+    }
+
+    async fn refresh_aggregating_index(
+        &self,
+        ctx: &Arc<QueryContext>,
+        index: &str,
+        definition: &AggregatingIndexDefinition,
+        snapshot: &TableSnapshot,
+    ) -> Result<()> {
+        let snapshot_id = snapshot.snapshot_id;
+        if self
+            .read_aggregating_index_meta(ctx.as_ref(), index, &snapshot_id)
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        // the index is refreshed incrementally, if it is up to date with the previous snapshot,
+        // and the data have only been appended since then
+        let prev = match snapshot.prev_snapshot_id {
+            Some((id, _)) => {
+                self.read_aggregating_index_meta(ctx.as_ref(), index, &id)
+                    .await?
+            }
+            None => None,
+        };
+        let (incremental, mut index_blocks, segments) = match (&prev, &snapshot.changes) {
+            (Some(prev), Some(changes)) if changes.deleted_segments.is_empty() => {
+                (true, prev.blocks.clone(), changes.inserted_segments.clone())
+            }
+            _ => (false, vec![], snapshot.segments.clone()),
+        };
+
+        let blocks = Self::blocks_of_segments_in_order(ctx.as_ref(), &segments).await?;
+        let new_blocks = self
+            .write_aggregating_index_blocks(ctx, index, definition, snapshot_id, &blocks)
+            .await?;
+        let new_locations = new_blocks
+            .iter()
+            .map(|b| b.location.0.clone())
+            .collect::<Vec<_>>();
+        index_blocks.extend(new_blocks);
+
+        let operator = ctx.get_storage_operator()?;
+        let meta = AggregatingIndexMeta::new(snapshot_id, index_blocks);
+        let location = self
+            .meta_location_generator
+            .aggregating_index_meta_location(index, &snapshot_id);
+        let bytes = serde_json::to_vec(&meta)?;
+        if let Err(e) = operator.object(&location).write(bytes).await {
+            for location in new_locations {
+                let _ = operator.object(&location).delete().await;
+            }
+            return Err(e.into());
+        }
+
+        // the index of the previous snapshot is no longer used, and neither are the blocks of
+        // it, if the index is rebuilt
+        if let Some(prev) = prev {
+            let location = self
+                .meta_location_generator
+                .aggregating_index_meta_location(index, &prev.snapshot_id);
+            let _ = operator.object(&location).delete().await;
+            if !incremental {
+                for block in &prev.blocks {
+                    let _ = operator.object(&block.location.0).delete().await;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Aggregates the blocks of the table by the definition of the index, and writes the
+    /// results as the blocks of the index.
+    async fn write_aggregating_index_blocks(
+        &self,
+        ctx: &Arc<QueryContext>,
+        index: &str,
+        definition: &AggregatingIndexDefinition,
+        snapshot_id: SnapshotId,
+        blocks: &[BlockMeta],
+    ) -> Result<Vec<BlockMeta>> {
+        if blocks.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let table_schema = self.table_info.schema();
+        let schema = Self::aggregating_index_schema(&table_schema, definition)?;
+        let aggregated = self.aggregate_blocks(ctx, definition, blocks)?;
+        if aggregated.iter().all(|b| b.num_rows() == 0) {
+            return Ok(vec![]);
+        }
+        let block = DataBlock::concat_blocks(&aggregated)?;
+        let block = DataBlock::create(schema, block.columns().to_vec());
+
+        let operator = ctx.get_storage_operator()?;
+        let row_per_block = self.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK);
+        let column_ids = ColumnIds::from_schema(&schema);
+        let mut acc = StatisticsAccumulator::created_by(snapshot_id, column_ids);
+        for block in DataBlock::split_block_by_size(&block, row_per_block)? {
+            let location = self
+                .meta_location_generator
+                .gen_aggregating_index_block_location(index);
+            let block_statistics =
+                BlockStatistics::from(&block, location.clone(), self.string_prefix_length())?;
+            let arrow_schema = block.schema().to_arrow();
+            let written = write_block(&arrow_schema, block, operator.clone(), &location).await;
+            let (file_size, meta) = match written {
+                Ok(written) => written,
+                Err(e) => {
+                    for block in &acc.blocks_metas {
+                        let _ = operator.object(&block.location.0).delete().await;
+                    }
+                    return Err(e);
+                }
+            };
+            acc.add_block(file_size, meta, block_statistics)?;
+        }
+        Ok(acc.blocks_metas)
+    }
+
+    /// Evaluates `SELECT keys, aggregates FROM table GROUP BY keys` over the blocks.
+    fn aggregate_blocks(
+        &self,
+        ctx: &Arc<QueryContext>,
+        definition: &AggregatingIndexDefinition,
+        blocks: &[BlockMeta],
+    ) -> Result<Vec<DataBlock>> {
+        let mut columns = definition.keys.clone();
+        columns.extend(definition.aggregates.iter().filter_map(|(_, c)| c.clone()));
+        let source = Self::read_blocks_plan(&self.table_info, blocks, &columns)?;
+        let source = PlanNode::ReadSource(source);
+
+        let aggr_exprs = definition.aggregate_exprs();
+        let group_exprs = definition
+            .keys
+            .iter()
+            .map(|key| Expression::Column(key.clone()))
+            .collect::<Vec<_>>();
+        let mut projection = group_exprs.clone();
+        projection.extend(
+            aggr_exprs
+                .iter()
+                .map(|expr| Expression::Column(expr.column_name())),
+        );
+        let plan = PlanBuilder::from(&source)
+            .aggregate_partial(&aggr_exprs, &group_exprs)?
+            .aggregate_final(source.schema(), &aggr_exprs, &group_exprs)?
+            .project(&projection)?
+            .build()?;
+
+        let query_ctx = QueryContext::create_from(ctx.clone());
+        let mut pipeline =
+            QueryPipelineBuilder::create(query_ctx.clone()).finalize(&SelectPlan {
+                input: Arc::new(plan),
+            })?;
+        pipeline.set_max_threads(ctx.get_settings().get_max_threads()? as usize);
+        let mut executor =
+            PipelinePullingExecutor::try_create(query_ctx.get_storage_runtime(), pipeline)?;
+        executor.start();
+        let mut aggregated = vec![];
+        while let Some(block) = executor.pull_data()? {
+            aggregated.push(block);
+        }
+        Ok(aggregated)
+    }
+}
@@ -0,0 +1,186 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::UpdateTableMetaReq;
+use common_planners::AlterColumnAction;
+use common_planners::Expression;
+use common_planners::RequireColumnsVisitor;
+
+use crate::catalogs::Catalog;
+use crate::common::ExpressionEvaluator;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::ColumnIds;
+use crate::storages::fuse::FuseTable;
+
+impl FuseTable {
+    /// Adds, drops or renames a column of the table.
+    ///
+    /// Only the schema in the meta of the table is changed, the blocks are not rewritten. The
+    /// columns are kept in the blocks by their ids, see [ColumnIds], so the blocks written
+    /// before are read by the new schema as they are. The space of a dropped column is
+    /// reclaimed when the blocks are rewritten, e.g. by compaction.
+    pub async fn do_alter_column(
+        &self,
+        ctx: &QueryContext,
+        action: &AlterColumnAction,
+    ) -> Result<()> {
+        let schema = self.table_info.schema();
+        let mut column_ids = ColumnIds::from_schema(&schema);
+        let mut fields = schema.fields().clone();
+        let mut meta = schema.meta().clone();
+        match action {
+            AlterColumnAction::Add(field) => {
+                self.check_new_column(field.name())?;
+                let value = Self::missing_value(ctx, field)?;
+                let id = column_ids.add_column();
+                meta.insert(
+                    ColumnIds::missing_value_key(id),
+                    serde_json::to_string(&value)?,
+                );
+                fields.push(field.clone());
+            }
+            AlterColumnAction::Drop(column) => {
+                let index = schema.index_of(column)?;
+                self.check_unreferenced(column)?;
+                if fields.len() == 1 {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Can not drop the only column {} of table {}",
+                        column, self.table_info.name
+                    )));
+                }
+                let id = column_ids.drop_column(index);
+                meta.remove(&ColumnIds::missing_value_key(id));
+                fields.remove(index);
+            }
+            AlterColumnAction::Rename {
+                old_column,
+                new_column,
+            } => {
+                let index = schema.index_of(old_column)?;
+                self.check_new_column(new_column)?;
+                self.check_unreferenced(old_column)?;
+                let field = &fields[index];
+                fields[index] = DataField::new(new_column, field.data_type().clone())
+                    .with_default_expr(field.default_expr().clone())
+                    .with_computed_expr(field.computed_expr().clone());
+            }
+        }
+        column_ids.write_to(&mut meta);
+
+        let mut table_meta = self.table_info.meta.clone();
+        table_meta.schema = Arc::new(DataSchema::new_from(fields, meta));
+        let req = UpdateTableMetaReq::new(&self.table_info.ident, table_meta);
+        ctx.get_catalog().update_table_meta(req).await?;
+        Ok(())
+    }
+
+    fn check_new_column(&self, name: &str) -> Result<()> {
+        let exists = self.table_info.schema().has_field(name)
+            || Self::virtual_columns(&self.table_info)?.contains_key(name);
+        match exists {
+            true => Err(ErrorCode::ColumnAlreadyExists(format!(
+                "Column {} already exists on table {}",
+                name, self.table_info.name
+            ))),
+            false => Ok(()),
+        }
+    }
+
+    /// Checks that the column is not referred to by the cluster keys, the other columns, the
+    /// aggregating indexes or the virtual columns of the table, before it is dropped or renamed.
+    fn check_unreferenced(&self, column: &str) -> Result<()> {
+        let mut referrers = vec![];
+        for expr in &self.order_keys {
+            if RequireColumnsVisitor::collect_columns_from_expr(expr)?.contains(column) {
+                referrers.push("the cluster keys".to_owned());
+                break;
+            }
+        }
+
+        for field in self.table_info.schema().fields() {
+            let expr = match (field.default_expr(), field.computed_expr()) {
+                (Some(expr), _) => expr,
+                (_, Some(computed)) => computed.expr(),
+                _ => continue,
+            };
+            let expr: Expression = serde_json::from_slice(expr)?;
+            if RequireColumnsVisitor::collect_columns_from_expr(&expr)?.contains(column) {
+                referrers.push(format!("column {}", field.name()));
+            }
+        }
+
+        for (name, definition) in Self::aggregating_indexes(&self.table_info)? {
+            let keys = definition.keys.iter();
+            let aggregated = definition.aggregates.iter().filter_map(|(_, c)| c.as_ref());
+            if keys.chain(aggregated).any(|c| c == column) {
+                referrers.push(format!("aggregating index {}", name));
+            }
+        }
+
+        for (name, definition) in Self::virtual_columns(&self.table_info)? {
+            if definition.column == column {
+                referrers.push(format!("virtual column {}", name));
+            }
+        }
+
+        match referrers.is_empty() {
+            true => Ok(()),
+            false => Err(ErrorCode::BadArguments(format!(
+                "Column {} of table {} is referred to by {}",
+                column,
+                self.table_info.name,
+                referrers.join(", ")
+            ))),
+        }
+    }
+
+    /// The value of the column being added in the rows already in the table, which is the
+    /// default value of it, evaluated once when the column is added.
+    fn missing_value(ctx: &QueryContext, field: &DataField) -> Result<DataValue> {
+        if field.computed_expr().is_some() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Generated column {} can not be added to an existing table",
+                field.name()
+            )));
+        }
+        let expr = match field.default_expr() {
+            None => return Ok(field.data_type().default_value()),
+            Some(expr) => serde_json::from_slice::<Expression>(expr)?,
+        };
+        if !RequireColumnsVisitor::collect_columns_from_expr(&expr)?.is_empty() {
+            return Err(ErrorCode::BadArguments(format!(
+                "The default value of the column {} being added can not refer to other columns",
+                field.name()
+            )));
+        }
+
+        let expr = Expression::Cast {
+            expr: Box::new(expr),
+            data_type: field.data_type().clone(),
+            pg_style: false,
+        };
+        let dummy = DataSchemaRefExt::create(vec![DataField::new("dummy", u8::to_data_type())]);
+        let one_row_block = DataBlock::create(dummy, vec![Series::from_data(vec![1u8])]);
+        let func_ctx = ctx.try_get_function_context()?;
+        let column = ExpressionEvaluator::eval(func_ctx, &expr, &one_row_block)?;
+        Ok(column.get(0))
+    }
+}
@@ -0,0 +1,165 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_cache::Cache;
+use common_exception::Result;
+use common_planners::Extras;
+use uuid::Uuid;
+
+use crate::configs::DATABEND_COMMIT_VERSION;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::write_snapshot;
+use crate::storages::fuse::meta::SnapshotOperation;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::meta::TableSnapshotStatistics;
+use crate::storages::fuse::meta::Versioned;
+use crate::storages::fuse::statistics::histogram::DEFAULT_HISTOGRAM_BUCKETS;
+use crate::storages::fuse::statistics::histogram::DEFAULT_MOST_COMMON_VALUES;
+use crate::storages::fuse::statistics::histogram::DEFAULT_SAMPLE_SIZE;
+use crate::storages::fuse::statistics::ColumnHistogramBuilder;
+use crate::storages::fuse::ColumnIds;
+use crate::storages::fuse::FuseTable;
+
+impl FuseTable {
+    /// Builds the statistics of the current snapshot, and commits them with a new snapshot,
+    /// which shares the data with the current one.
+    pub async fn do_analyze(&self, ctx: &Arc<QueryContext>) -> Result<()> {
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            // nothing to analyze
+            None => return Ok(()),
+        };
+
+        let statistics = self.build_snapshot_statistics(ctx, &snapshot).await?;
+        let operator = ctx.get_storage_operator()?;
+        let statistics_loc = self
+            .meta_location_generator
+            .snapshot_statistics_location_from_uuid(
+                &Uuid::new_v4(),
+                TableSnapshotStatistics::VERSION,
+            )?;
+        let bytes = serde_json::to_vec(&statistics)?;
+        operator.object(&statistics_loc).write(bytes).await?;
+
+        let mut new_snapshot = TableSnapshot::new(
+            Uuid::new_v4(),
+            Some((snapshot.snapshot_id, self.snapshot_format_version())),
+            snapshot.schema.clone(),
+            snapshot.summary.clone(),
+            snapshot.segments.clone(),
+        )
+        .with_origin(DATABEND_COMMIT_VERSION.as_str(), ctx.get_id())
+        .with_operation(
+            SnapshotOperation::Analyze,
+            Self::current_user_identity(ctx.as_ref()),
+        )
+        .with_changes(Some(snapshot.as_ref()));
+        new_snapshot.table_statistics_location = Some(statistics_loc.clone());
+
+        let snapshot_loc = self
+            .meta_location_generator
+            .snapshot_location_from_uuid(&new_snapshot.snapshot_id, TableSnapshot::VERSION)?;
+        write_snapshot(ctx, &operator, &snapshot_loc, &new_snapshot).await?;
+
+        // the table may be modified concurrently, in which case the statistics are discarded,
+        // and the table should be analyzed again.
+        match Self::commit_to_meta_server(
+            ctx.as_ref(),
+            &self.table_info,
+            snapshot_loc.clone(),
+            &new_snapshot.summary,
+        )
+        .await
+        {
+            Ok(_) => {
+                if let Some(snapshot_cache) =
+                    ctx.get_storage_cache_manager().get_table_snapshot_cache()
+                {
+                    let cache = &mut snapshot_cache.write().await;
+                    cache.put(snapshot_loc, Arc::new(new_snapshot));
+                }
+                Ok(())
+            }
+            Err(e) => {
+                let _ = operator.object(&snapshot_loc).delete().await;
+                let _ = operator.object(&statistics_loc).delete().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn build_snapshot_statistics(
+        &self,
+        ctx: &Arc<QueryContext>,
+        snapshot: &TableSnapshot,
+    ) -> Result<TableSnapshotStatistics> {
+        let schema = self.table_info.schema();
+        let projection = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| ColumnHistogramBuilder::is_supported(f.data_type()))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+
+        let mut builders = projection
+            .iter()
+            .map(|_| ColumnHistogramBuilder::new(DEFAULT_SAMPLE_SIZE))
+            .collect::<Vec<_>>();
+
+        if !projection.is_empty() {
+            let push_downs = Some(Extras {
+                projection: Some(projection.clone()),
+                ..Extras::default()
+            });
+            let blocks =
+                Self::blocks_of_segments_in_order(ctx.as_ref(), &snapshot.segments).await?;
+            let (_, parts) = Self::to_partitions(&schema, &blocks, push_downs.clone());
+            let block_reader = Self::create_block_reader(ctx, schema.clone(), &push_downs)?;
+            for part in parts {
+                let block = block_reader.read(part).await?;
+                for (idx, builder) in builders.iter_mut().enumerate() {
+                    builder.append(block.column(idx));
+                }
+            }
+        }
+
+        let sampled_row_count = builders
+            .iter()
+            .map(|b| b.sampled_count())
+            .max()
+            .unwrap_or_default();
+        let column_ids = ColumnIds::from_schema(&schema);
+        let column_histograms = projection
+            .into_iter()
+            .zip(builders)
+            .map(|(idx, builder)| {
+                let histogram =
+                    builder.finish(DEFAULT_HISTOGRAM_BUCKETS, DEFAULT_MOST_COMMON_VALUES);
+                (column_ids.id_of(idx), histogram)
+            })
+            .collect::<HashMap<_, _>>();
+
+        Ok(TableSnapshotStatistics::new(
+            snapshot.snapshot_id,
+            snapshot.summary.row_count,
+            sampled_row_count,
+            column_histograms,
+        ))
+    }
+}
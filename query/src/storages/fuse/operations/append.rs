@@ -18,30 +18,39 @@ use std::sync::Arc;
 
 use async_stream::stream;
 use common_cache::Cache;
+use common_datablocks::DataBlock;
 use common_datablocks::SortColumnDescription;
 use common_datavalues::DataSchemaRefExt;
 use common_exception::Result;
 use common_planners::Expression;
 use common_streams::SendableDataBlockStream;
 use futures::StreamExt;
+use uuid::Uuid;
 
 use crate::pipelines::new::processors::port::InputPort;
 use crate::pipelines::new::processors::BlockCompactor;
 use crate::pipelines::new::processors::ExpressionTransform;
 use crate::pipelines::new::processors::ProjectionTransform;
+use crate::pipelines::new::processors::Transform;
 use crate::pipelines::new::processors::TransformCompact;
 use crate::pipelines::new::processors::TransformSortPartial;
+use crate::pipelines::new::processors::Transformer;
 use crate::pipelines::new::NewPipeline;
 use crate::pipelines::new::SinkPipeBuilder;
 use crate::sessions::QueryContext;
 use crate::storages::fuse::io::BlockStreamWriter;
 use crate::storages::fuse::operations::AppendOperationLogEntry;
 use crate::storages::fuse::operations::FuseTableSink;
+use crate::storages::fuse::operations::VirtualColumnsExtractor;
+use crate::storages::fuse::zorder_indices;
+use crate::storages::fuse::ClusterOrder;
 use crate::storages::fuse::FuseTable;
 use crate::storages::fuse::DEFAULT_BLOCK_PER_SEGMENT;
 use crate::storages::fuse::DEFAULT_ROW_PER_BLOCK;
+use crate::storages::fuse::DEFAULT_STATS_STRING_PREFIX_LENGTH;
 use crate::storages::fuse::FUSE_OPT_KEY_BLOCK_PER_SEGMENT;
 use crate::storages::fuse::FUSE_OPT_KEY_ROW_PER_BLOCK;
+use crate::storages::fuse::FUSE_OPT_KEY_STATS_STRING_PREFIX_LENGTH;
 
 pub type AppendOperationLogEntryStream =
     std::pin::Pin<Box<dyn futures::stream::Stream<Item = Result<AppendOperationLogEntry>> + Send>>;
@@ -60,6 +69,7 @@ impl FuseTable {
 
         let da = ctx.get_storage_operator()?;
 
+        // the blocks are committed by the snapshot of the same id, see `do_commit`
         let mut segment_stream = BlockStreamWriter::write_block_stream(
             da.clone(),
             stream,
@@ -67,6 +77,9 @@ impl FuseTable {
             rows_per_block,
             block_per_seg,
             self.meta_location_generator().clone(),
+            Uuid::new_v4(),
+            VirtualColumnsExtractor::try_create(&ctx, &self.table_info)?.map(Arc::new),
+            self.string_prefix_length(),
         )
         .await;
 
@@ -116,7 +129,23 @@ impl FuseTable {
             )
         })?;
 
-        if !self.order_keys.is_empty() {
+        if !self.order_keys.is_empty() && self.cluster_order() == ClusterOrder::ZOrder {
+            // the keys of the z-order are columns, see `ClusterOrder::try_create`
+            let column_names = self
+                .order_keys
+                .iter()
+                .map(|key| key.column_name())
+                .collect::<Vec<_>>();
+            pipeline.add_transform(|transform_input_port, transform_output_port| {
+                Ok(Transformer::create(
+                    transform_input_port,
+                    transform_output_port,
+                    TransformSortZOrder {
+                        column_names: column_names.clone(),
+                    },
+                ))
+            })?;
+        } else if !self.order_keys.is_empty() {
             let input_schema = self.table_info.schema();
             let mut merged = input_schema.fields().clone();
 
@@ -183,6 +212,10 @@ impl FuseTable {
             }
         }
 
+        // the blocks are committed by the snapshot of the same id, see `do_commit`
+        let snapshot_id = Uuid::new_v4();
+        let virtual_columns =
+            VirtualColumnsExtractor::try_create(&ctx, &self.table_info)?.map(Arc::new);
         let mut sink_pipeline_builder = SinkPipeBuilder::create();
         for _ in 0..pipeline.output_len() {
             let input_port = InputPort::create();
@@ -195,6 +228,9 @@ impl FuseTable {
                     da.clone(),
                     self.table_info.schema().clone(),
                     self.meta_location_generator().clone(),
+                    snapshot_id,
+                    virtual_columns.clone(),
+                    self.string_prefix_length(),
                 )?,
             );
         }
@@ -203,11 +239,38 @@ impl FuseTable {
         Ok(())
     }
 
-    fn get_option<T: FromStr>(&self, opt_key: &str, default: T) -> T {
+    pub(crate) fn get_option<T: FromStr>(&self, opt_key: &str, default: T) -> T {
         self.table_info
             .options()
             .get(opt_key)
             .and_then(|s| s.parse::<T>().ok())
             .unwrap_or(default)
     }
+
+    /// The length in bytes which the min and the max of the strings in the statistics of
+    /// the blocks are truncated to
+    pub(crate) fn string_prefix_length(&self) -> usize {
+        self.get_option(
+            FUSE_OPT_KEY_STATS_STRING_PREFIX_LENGTH,
+            DEFAULT_STATS_STRING_PREFIX_LENGTH,
+        )
+    }
+}
+
+/// Sorts the rows of each block by the z-order of the cluster keys.
+struct TransformSortZOrder {
+    column_names: Vec<String>,
+}
+
+impl Transform for TransformSortZOrder {
+    const NAME: &'static str = "SortZOrderTransform";
+
+    fn transform(&mut self, block: DataBlock) -> Result<DataBlock> {
+        let columns = self
+            .column_names
+            .iter()
+            .map(|name| block.try_column_by_name(name).cloned())
+            .collect::<Result<Vec<_>>>()?;
+        DataBlock::block_take_by_indices(&block, &zorder_indices(&columns))
+    }
 }
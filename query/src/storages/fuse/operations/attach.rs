@@ -0,0 +1,167 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::StreamExt;
+use opendal::ObjectMode;
+use opendal::Scheme as DalSchema;
+
+use crate::configs::Config;
+use crate::sessions::QueryContext;
+use crate::sql::OPT_KEY_EXTERNAL_LOCATION;
+use crate::sql::OPT_KEY_READ_ONLY_ATTACHED;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::io::TableMetaLocationGenerator;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::FUSE_TBL_SNAPSHOT_PREFIX;
+
+const EXTERNAL_LOCATION_SCHEME: &str = "fuse://";
+const MAX_CONCURRENT_SNAPSHOT_LOADING: usize = 10;
+
+impl FuseTable {
+    /// Reads the schema of the snapshot to be attached, of which the format version is
+    /// resolved by the location.
+    pub async fn read_attached_schema(ctx: &QueryContext, location: &str) -> Result<DataSchemaRef> {
+        let reader = MetaReaders::table_snapshot_reader(ctx);
+        let ver = TableMetaLocationGenerator::snaphost_version(location);
+        let snapshot = reader.read(location, None, ver).await?;
+        Ok(Arc::new(snapshot.schema.clone()))
+    }
+
+    /// Resolves the prefix of the external fuse table at `uri`, i.e. `fuse://<bucket>/<prefix>`,
+    /// None if `uri` is not of the scheme. The table is read through the storage of this
+    /// cluster, so the bucket must be the one the storage is configured with, or be left
+    /// empty (`fuse:///<prefix>`); the prefix is relative to the root of the storage.
+    pub fn parse_external_location(conf: &Config, uri: &str) -> Result<Option<String>> {
+        let location = match uri.strip_prefix(EXTERNAL_LOCATION_SCHEME) {
+            Some(location) => location,
+            None => return Ok(None),
+        };
+        let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+        let prefix = prefix.trim_matches('/');
+        if prefix.is_empty() {
+            return Err(ErrorCode::BadArguments(format!(
+                "the prefix of the table is missing in {}",
+                uri
+            )));
+        }
+
+        let storage = &conf.storage;
+        let configured = match DalSchema::from_str(&storage.storage_type)? {
+            DalSchema::S3 => storage.s3.bucket.as_str(),
+            DalSchema::Azblob => storage.azblob.container.as_str(),
+            _ => "",
+        };
+        if !bucket.is_empty() && bucket != configured {
+            return Err(ErrorCode::BadArguments(format!(
+                "bucket {} of {} is not the one of the storage, which is '{}'",
+                bucket, uri, configured
+            )));
+        }
+        Ok(Some(prefix.to_owned()))
+    }
+
+    /// Finds the latest snapshot of the fuse table under `prefix`: of the snapshots which are
+    /// not succeeded by others, the one with the latest timestamp.
+    ///
+    /// Snapshots are immutable, and the ones read by the previous refreshes are served by the
+    /// snapshot cache.
+    pub async fn find_latest_snapshot(
+        ctx: &QueryContext,
+        prefix: &str,
+    ) -> Result<Option<(String, Arc<TableSnapshot>)>> {
+        let operator = ctx.get_storage_operator()?;
+        let dir = format!("{}/{}/", prefix, FUSE_TBL_SNAPSHOT_PREFIX);
+        let mut locations = vec![];
+        let mut objects = match operator.object(&dir).list().await {
+            Ok(objects) => objects,
+            Err(e) => {
+                let e = ErrorCode::from(e);
+                return if e.code() == ErrorCode::storage_not_found_code() {
+                    Ok(None)
+                } else {
+                    Err(e)
+                };
+            }
+        };
+        while let Some(object) = objects.next().await {
+            let mut object = object?;
+            let meta = object.metadata_cached().await?;
+            if meta.mode() == ObjectMode::FILE {
+                locations.push(meta.path().to_string());
+            }
+        }
+
+        let reader = MetaReaders::table_snapshot_reader(ctx);
+        let mut loadings = futures::stream::iter(&locations)
+            .map(|loc| reader.read(loc, None, TableMetaLocationGenerator::snaphost_version(loc)))
+            .buffered(MAX_CONCURRENT_SNAPSHOT_LOADING);
+        let mut snapshots = Vec::with_capacity(locations.len());
+        let mut idx = 0;
+        while let Some(res) = loadings.next().await {
+            match res {
+                Ok(snapshot) => snapshots.push((locations[idx].clone(), snapshot)),
+                // purged by the cluster writing the table
+                Err(e) if e.code() == ErrorCode::storage_not_found_code() => {}
+                Err(e) => return Err(e),
+            }
+            idx += 1;
+        }
+
+        let succeeded = snapshots
+            .iter()
+            .filter_map(|(_, s)| s.prev_snapshot_id.map(|(id, _)| id))
+            .collect::<HashSet<_>>();
+        Ok(snapshots
+            .into_iter()
+            .filter(|(_, s)| !succeeded.contains(&s.snapshot_id))
+            .max_by_key(|(_, s)| s.timestamp))
+    }
+
+    /// External tables are attached to the prefixes of the fuse tables written by other
+    /// clusters, instead of the snapshots.
+    pub fn is_external(&self) -> bool {
+        self.table_info
+            .options()
+            .contains_key(OPT_KEY_EXTERNAL_LOCATION)
+    }
+
+    /// Tables attached by `ATTACH TABLE` share the files with the tables they are attached
+    /// from, which are not owned, and thus could not be modified.
+    pub fn is_read_only(&self) -> bool {
+        self.table_info
+            .options()
+            .contains_key(OPT_KEY_READ_ONLY_ATTACHED)
+    }
+
+    pub(crate) fn check_mutable(&self) -> Result<()> {
+        if self.is_read_only() {
+            Err(ErrorCode::ReadOnlyTable(format!(
+                "table {} is attached from {}, and is read only",
+                self.table_info.name,
+                self.snapshot_loc().unwrap_or_default()
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
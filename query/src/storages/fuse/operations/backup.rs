@@ -0,0 +1,206 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use futures::TryStreamExt;
+use opendal::Operator;
+use uuid::Uuid;
+
+use crate::configs::DATABEND_COMMIT_VERSION;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::encode_snapshot;
+use crate::storages::fuse::io::write_snapshot;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::meta::Encoding;
+use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::meta::SnapshotOperation;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::meta::Versioned;
+use crate::storages::fuse::FuseTable;
+use crate::storages::TableDataBackup;
+
+const MAX_CONCURRENT_SEGMENT_LOADING: usize = 10;
+
+/// The location of a file of the table relative to the directory of the table in the backup.
+///
+/// Files are named by uuids, thus keeping the last two parts of the location, e.g.
+/// `_b/<uuid>_v0.parquet`, is enough to tell them apart; the rest of it is the prefix of the
+/// table, which differs after the restore, or of the table it is cloned from.
+fn relative_location(location: &str) -> &str {
+    match location.rmatch_indices('/').nth(1) {
+        Some((idx, _)) => &location[idx + 1..],
+        None => location,
+    }
+}
+
+fn backup_location(dir: &str, location: &str) -> String {
+    format!("{}/{}", dir, relative_location(location))
+}
+
+async fn copy_file(
+    from: &Operator,
+    from_location: &str,
+    to: &Operator,
+    to_location: &str,
+) -> Result<u64> {
+    let bytes = from.object(from_location).read().await?;
+    let len = bytes.len() as u64;
+    to.object(to_location).write(bytes).await?;
+    Ok(len)
+}
+
+/// Copies the file at `location` in the backup at `dir` back under `prefix`, returns the
+/// location of the copy.
+async fn restore_file(
+    source: &Operator,
+    dir: &str,
+    target: &Operator,
+    prefix: &str,
+    location: &str,
+) -> Result<String> {
+    let to_location = backup_location(prefix, location);
+    copy_file(
+        source,
+        &backup_location(dir, location),
+        target,
+        &to_location,
+    )
+    .await?;
+    Ok(to_location)
+}
+
+impl FuseTable {
+    /// Copies the current snapshot of the table, and the files it references, into `dir` of
+    /// `target`. The snapshots and segments are written in the current format versions.
+    ///
+    /// The files referenced by a snapshot never change, so the backup is consistent even if the
+    /// table is being written meanwhile.
+    pub async fn do_backup(
+        &self,
+        ctx: &QueryContext,
+        target: &Operator,
+        dir: &str,
+    ) -> Result<Option<TableDataBackup>> {
+        let snapshot = match self.read_table_snapshot(ctx).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(None),
+        };
+        let operator = ctx.get_storage_operator()?;
+        let mut backup = TableDataBackup::default();
+
+        let reader = MetaReaders::segment_info_reader(ctx);
+        let mut segments = reader.read_segments(&snapshot.segments, MAX_CONCURRENT_SEGMENT_LOADING);
+        let mut idx = 0;
+        while let Some(segment) = segments.try_next().await? {
+            // the data of the blocks, and the deletion vectors and virtual columns of them
+            for location in segment.blocks.iter().flat_map(|b| b.file_locations()) {
+                let to_location = backup_location(dir, location);
+                backup.bytes += copy_file(&operator, location, target, &to_location).await?;
+                backup.files += 1;
+            }
+            let location = backup_location(dir, &snapshot.segments[idx].0);
+            let bytes = serde_json::to_vec(segment.as_ref())?;
+            backup.bytes += bytes.len() as u64;
+            backup.files += 1;
+            target.object(&location).write(bytes).await?;
+            idx += 1;
+        }
+
+        if let Some(location) = &snapshot.table_statistics_location {
+            let to_location = backup_location(dir, location);
+            backup.bytes += copy_file(&operator, location, target, &to_location).await?;
+            backup.files += 1;
+        }
+
+        let location = self
+            .meta_location_generator
+            .snapshot_location_from_uuid(&snapshot.snapshot_id, TableSnapshot::VERSION)?;
+        let bytes = encode_snapshot(ctx, snapshot.as_ref())?;
+        backup.bytes += bytes.len() as u64;
+        backup.files += 1;
+        target
+            .object(&backup_location(dir, &location))
+            .write(bytes)
+            .await?;
+        backup.snapshot = relative_location(&location).to_string();
+
+        Ok(Some(backup))
+    }
+
+    /// Restores the backup at `dir` of `source` into this table, which is just created.
+    ///
+    /// The files are copied under the prefix of this table, and the locations kept by the
+    /// segments and the snapshot are rebound to the copies. The history of the table is not
+    /// restored, the restored snapshot is the first one of the table.
+    pub async fn do_restore(
+        &self,
+        ctx: &QueryContext,
+        source: &Operator,
+        dir: &str,
+        backup: &TableDataBackup,
+    ) -> Result<()> {
+        let location = format!("{}/{}", dir, backup.snapshot);
+        let bytes = source.object(&location).read().await?;
+        let snapshot: TableSnapshot = Encoding::decode(&bytes)?;
+        let operator = ctx.get_storage_operator()?;
+        let locations = self.meta_location_generator();
+        let prefix = locations.prefix();
+
+        let mut segments = Vec::with_capacity(snapshot.segments.len());
+        for (location, _) in &snapshot.segments {
+            let bytes = source
+                .object(&backup_location(dir, location))
+                .read()
+                .await?;
+            let mut segment: SegmentInfo = serde_json::from_slice(&bytes)?;
+            for block in segment.blocks.iter_mut() {
+                let location = &mut block.location.0;
+                *location = restore_file(source, dir, &operator, prefix, location).await?;
+                if let Some(dv) = block.deletion_vector.as_mut() {
+                    let location = &mut dv.location.0;
+                    *location = restore_file(source, dir, &operator, prefix, location).await?;
+                }
+                if let Some(vb) = block.virtual_block.as_mut() {
+                    let location = &mut vb.location.0;
+                    *location = restore_file(source, dir, &operator, prefix, location).await?;
+                }
+            }
+            let location = locations.gen_segment_info_location();
+            let bytes = serde_json::to_vec(&segment)?;
+            operator.object(&location).write(bytes).await?;
+            segments.push((location, SegmentInfo::VERSION));
+        }
+
+        let mut new_snapshot = TableSnapshot::new(
+            Uuid::new_v4(),
+            None,
+            snapshot.schema.clone(),
+            snapshot.summary.clone(),
+            segments,
+        )
+        .with_origin(DATABEND_COMMIT_VERSION.as_str(), ctx.get_id())
+        .with_operation(SnapshotOperation::Restore, Self::current_user_identity(ctx))
+        .with_changes(None);
+        if let Some(location) = &snapshot.table_statistics_location {
+            let location = restore_file(source, dir, &operator, prefix, location).await?;
+            new_snapshot.table_statistics_location = Some(location);
+        }
+
+        let location = locations
+            .snapshot_location_from_uuid(&new_snapshot.snapshot_id, TableSnapshot::VERSION)?;
+        write_snapshot(ctx, &operator, &location, &new_snapshot).await?;
+        Self::commit_to_meta_server(ctx, &self.table_info, location, &new_snapshot.summary).await?;
+        Ok(())
+    }
+}
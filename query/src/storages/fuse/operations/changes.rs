@@ -0,0 +1,152 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashSet;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+use uuid::Uuid;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::Location;
+use crate::storages::fuse::FuseTable;
+
+const MAX_CONCURRENT_SEGMENT_LOADING: usize = 10;
+
+/// The net changes of a table between two of its snapshots
+#[derive(Default)]
+pub struct TableChanges {
+    pub inserted_blocks: Vec<BlockMeta>,
+    pub deleted_blocks: Vec<BlockMeta>,
+}
+
+impl FuseTable {
+    /// Collects the blocks inserted and deleted since the snapshot `offset`, which must be
+    /// in the lineage of the current snapshot. If `offset` is None, the changes are collected
+    /// since the very beginning of the table, i.e. all the blocks are inserted ones.
+    pub async fn changes_since(
+        &self,
+        ctx: &QueryContext,
+        offset: Option<&str>,
+    ) -> Result<TableChanges> {
+        let history = self.snapshot_log(ctx).await?;
+
+        // the snapshots committed after the offset, from the latest to the earliest
+        let (newer, offset_snapshot) = match offset {
+            None => (history.as_slice(), None),
+            Some(offset) => {
+                let id = Uuid::parse_str(offset).map_err(|e| {
+                    ErrorCode::BadArguments(format!("invalid snapshot id {}: {}", offset, e))
+                })?;
+                match history.iter().position(|(s, _)| s.snapshot_id == id) {
+                    Some(idx) => (&history[..idx], Some(&history[idx].0)),
+                    None => {
+                        return Err(ErrorCode::TableHistoricalDataNotFound(format!(
+                            "snapshot {} is not in the history of table {}",
+                            offset, self.table_info.name
+                        )))
+                    }
+                }
+            }
+        };
+
+        let replayable = newer.iter().all(|(s, _)| s.changes.is_some());
+        let (inserted, deleted): (Vec<Location>, Vec<Location>) = if replayable {
+            // replay the changes, from the earliest to the latest
+            let mut inserted = Vec::new();
+            let mut deleted = HashSet::new();
+            for changes in newer.iter().rev().filter_map(|(s, _)| s.changes.as_ref()) {
+                for loc in &changes.deleted_segments {
+                    match inserted.iter().position(|l| l == loc) {
+                        Some(idx) => {
+                            inserted.remove(idx);
+                        }
+                        None => {
+                            deleted.insert(loc.clone());
+                        }
+                    }
+                }
+                for loc in &changes.inserted_segments {
+                    if !deleted.remove(loc) {
+                        inserted.push(loc.clone());
+                    }
+                }
+            }
+            (inserted, deleted.into_iter().collect())
+        } else {
+            // some of the snapshots are converted from legacy versions, which have no changes
+            // recorded, diff the segments of the current snapshot and the offset instead
+            let current: HashSet<&Location> = newer
+                .first()
+                .map(|(s, _)| s.segments.iter().collect())
+                .unwrap_or_default();
+            let base: HashSet<&Location> = offset_snapshot
+                .map(|s| s.segments.iter().collect())
+                .unwrap_or_default();
+            let inserted = newer
+                .first()
+                .map(|(s, _)| {
+                    s.segments
+                        .iter()
+                        .filter(|l| !base.contains(l))
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default();
+            let deleted = base
+                .iter()
+                .filter(|l| !current.contains(*l))
+                .map(|l| (*l).clone())
+                .collect();
+            (inserted, deleted)
+        };
+
+        let mut inserted_blocks = Self::blocks_of_segments_in_order(ctx, &inserted).await?;
+        let mut deleted_blocks = Self::blocks_of_segments_in_order(ctx, &deleted).await?;
+
+        // segments may be rewritten without touching their blocks, e.g. by segment compaction,
+        // such blocks are neither inserted nor deleted. The blocks of which rows are deleted by
+        // deletion vectors are kept, they are taken as deleted and inserted again.
+        let block_version = |b: &BlockMeta| {
+            let deletion_vector = b.deletion_vector.as_ref().map(|dv| dv.location.clone());
+            (b.location.clone(), deletion_vector)
+        };
+        let inserted_versions: HashSet<_> = inserted_blocks.iter().map(block_version).collect();
+        let deleted_versions: HashSet<_> = deleted_blocks.iter().map(block_version).collect();
+        inserted_blocks.retain(|b| !deleted_versions.contains(&block_version(b)));
+        deleted_blocks.retain(|b| !inserted_versions.contains(&block_version(b)));
+
+        Ok(TableChanges {
+            inserted_blocks,
+            deleted_blocks,
+        })
+    }
+
+    pub(crate) async fn blocks_of_segments_in_order(
+        ctx: &QueryContext,
+        segments: &[Location],
+    ) -> Result<Vec<BlockMeta>> {
+        let reader = MetaReaders::segment_info_reader(ctx);
+        let mut segments = reader.read_segments(segments, MAX_CONCURRENT_SEGMENT_LOADING);
+        let mut blocks = vec![];
+        while let Some(segment) = segments.try_next().await? {
+            blocks.extend(segment.blocks.iter().cloned());
+        }
+        Ok(blocks)
+    }
+}
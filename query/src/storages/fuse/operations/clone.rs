@@ -0,0 +1,161 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::MetaId;
+use common_meta_types::TableInfo;
+use common_meta_types::TABLE_OPT_KEY_ROW_ACCESS_POLICY;
+
+use crate::catalogs::Catalog;
+use crate::sessions::QueryContext;
+use crate::sql::OPT_KEY_CLONED_FROM;
+use crate::sql::OPT_KEY_DATABASE_ID;
+use crate::sql::OPT_KEY_EXTERNAL_LOCATION;
+use crate::sql::OPT_KEY_READ_ONLY_ATTACHED;
+use crate::sql::OPT_KEY_SNAPSHOT_LOC;
+use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::FuseTable;
+
+/// Files which the purge and vacuum of a table are allowed to remove.
+///
+/// A table only owns the files under its own prefix, the others are shared with the table it
+/// is cloned from. The owned files are shared as well, if they are referenced by the clones
+/// of the table.
+pub(crate) struct RemovableFiles {
+    prefix: String,
+    shared: HashSet<String>,
+}
+
+impl RemovableFiles {
+    pub fn contains(&self, location: &str) -> bool {
+        location.starts_with(&self.prefix) && !self.shared.contains(location)
+    }
+}
+
+impl FuseTable {
+    /// Ids of the tables which the table is cloned from, directly or indirectly.
+    pub fn cloned_from(table_info: &TableInfo) -> Vec<MetaId> {
+        table_info
+            .options()
+            .get(OPT_KEY_CLONED_FROM)
+            .map(|ids| ids.split(',').filter_map(|id| id.parse().ok()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Options of a table cloned from this one by `CREATE TABLE .. CLONE`.
+    ///
+    /// The clone starts from the current snapshot of this table, the segments and blocks are
+    /// shared instead of being copied.
+    ///
+    /// A table with a row access policy attached is not cloned: the policy would either be
+    /// lost by the clone, or be dropped from the clone by anyone allowed to alter it.
+    pub fn options_of_clone(&self) -> Result<BTreeMap<String, String>> {
+        if self
+            .table_info
+            .options()
+            .contains_key(TABLE_OPT_KEY_ROW_ACCESS_POLICY)
+        {
+            return Err(ErrorCode::IllegalRowAccessPolicy(format!(
+                "Can not clone table {} with a row access policy attached",
+                self.table_info.name
+            )));
+        }
+
+        let mut options = self.table_info.options().clone();
+        for key in [
+            OPT_KEY_DATABASE_ID,
+            OPT_KEY_SNAPSHOT_LOC,
+            OPT_KEY_SNAPSHOT_LOCATION,
+            OPT_KEY_READ_ONLY_ATTACHED,
+            OPT_KEY_EXTERNAL_LOCATION,
+        ] {
+            options.remove(key);
+        }
+        if let Some(loc) = self.snapshot_loc() {
+            options.insert(OPT_KEY_SNAPSHOT_LOCATION.to_owned(), loc);
+        }
+
+        let mut cloned_from = Self::cloned_from(&self.table_info);
+        cloned_from.push(self.table_info.ident.table_id);
+        let cloned_from = cloned_from
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        options.insert(OPT_KEY_CLONED_FROM.to_owned(), cloned_from);
+        Ok(options)
+    }
+
+    /// Collects the files which the purge and vacuum of the table are allowed to remove, i.e.
+    /// the files owned by the table, excluding the ones that are still referenced by any
+    /// snapshot of its clones.
+    pub(crate) async fn removable_files(&self, ctx: &QueryContext) -> Result<RemovableFiles> {
+        let table_id = self.table_info.ident.table_id;
+        let catalog = ctx.get_catalog();
+        let tenant = ctx.get_tenant();
+
+        let mut shared = HashSet::new();
+        for db in catalog.list_databases(tenant.as_str()).await? {
+            for table in catalog.list_tables(tenant.as_str(), db.name()).await? {
+                if Self::cloned_from(table.get_table_info()).contains(&table_id) {
+                    let clone = FuseTable::try_from_table(table.as_ref())?;
+                    clone.collect_referenced_files(ctx, &mut shared).await?;
+                }
+            }
+        }
+
+        Ok(RemovableFiles {
+            prefix: format!("{}/", self.meta_location_generator().prefix()),
+            shared,
+        })
+    }
+
+    /// Collects the locations of the snapshots, statistics, segments, blocks and deletion
+    /// vectors referenced by the history of the table.
+    async fn collect_referenced_files(
+        &self,
+        ctx: &QueryContext,
+        files: &mut HashSet<String>,
+    ) -> Result<()> {
+        // the initial snapshot of a clone is the one of the table it is cloned from
+        files.extend(self.snapshot_loc());
+
+        let locs = self.meta_location_generator();
+        let mut segments = HashSet::new();
+        for (snapshot, ver) in self.snapshot_log(ctx).await? {
+            files.insert(locs.snapshot_location_from_uuid(&snapshot.snapshot_id, ver)?);
+            files.extend(snapshot.table_statistics_location.iter().cloned());
+            segments.extend(snapshot.segments.iter().cloned());
+        }
+
+        let reader = MetaReaders::segment_info_reader(ctx);
+        for (loc, ver) in segments {
+            let segment = reader.read(loc.as_str(), None, ver).await?;
+            files.extend(
+                segment
+                    .blocks
+                    .iter()
+                    .flat_map(|b| b.file_locations().cloned()),
+            );
+            files.insert(loc);
+        }
+        Ok(())
+    }
+}
@@ -14,30 +14,38 @@
 //
 
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 
 use backoff::backoff::Backoff;
+use backoff::ExponentialBackoff;
 use backoff::ExponentialBackoffBuilder;
 use common_base::ProgressValues;
 use common_cache::Cache;
+use common_datablocks::DataBlock;
 use common_datavalues::DataSchema;
 use common_exception::ErrorCode;
 use common_exception::Result;
-use common_meta_types::MatchSeq;
 use common_meta_types::TableInfo;
-use common_meta_types::UpsertTableOptionReply;
-use common_meta_types::UpsertTableOptionReq;
+use common_meta_types::UpdateMultiTableMetaReq;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_tracing::tracing;
 use uuid::Uuid;
 
 use crate::catalogs::Catalog;
+use crate::configs::DATABEND_COMMIT_VERSION;
 use crate::sessions::QueryContext;
 use crate::sql::OPT_KEY_SNAPSHOT_LOC;
 use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::storages::fuse::io::write_snapshot;
+use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::meta::Location;
 use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::meta::SnapshotId;
+use crate::storages::fuse::meta::SnapshotOperation;
 use crate::storages::fuse::meta::Statistics;
 use crate::storages::fuse::meta::TableSnapshot;
 use crate::storages::fuse::meta::Versioned;
@@ -57,6 +65,31 @@ impl FuseTable {
         ctx: Arc<QueryContext>,
         operation_log: TableOperationLog,
         overwrite: bool,
+    ) -> Result<()> {
+        self.commit_with_options(
+            ctx,
+            operation_log,
+            overwrite,
+            &HashMap::new(),
+            &HashMap::new(),
+        )
+        .await
+    }
+
+    /// Commits the operations together with the given table `options`, in one transaction.
+    ///
+    /// `expected` are the values which the options must still have when the operations are
+    /// committed (`None` if an option must be absent). If any of them has been changed by a
+    /// concurrent transaction, the operations are aborted with `TableCommitConflict`. This
+    /// lets a loader record the position of its source along with the data it loads, so that
+    /// no data is loaded twice.
+    pub async fn commit_with_options(
+        &self,
+        ctx: Arc<QueryContext>,
+        operation_log: TableOperationLog,
+        overwrite: bool,
+        expected: &HashMap<String, Option<String>>,
+        options: &HashMap<String, String>,
     ) -> Result<()> {
         let tid = self.table_info.ident.table_id;
 
@@ -65,35 +98,30 @@ impl FuseTable {
 
         let mut retry_times = 0;
 
-        // The initial retry delay in millisecond. By default,  it is 5 ms.
-        let init_delay = OCC_DEFAULT_BACKOFF_INIT_DELAY_MS;
+        // The snapshot which the operation is based on. If other transactions commit
+        // concurrently, the changes of this operation are rebased on top of theirs.
+        let base = self.read_table_snapshot(ctx.as_ref()).await?;
 
-        // The maximum  back off delay in millisecond, once the retry interval reaches this value, it stops increasing.
-        // By default, it is 20 seconds.
-        let max_delay = OCC_DEFAULT_BACKOFF_MAX_DELAY_MS;
-
-        // The maximum elapsed time after the occ starts, beyond which there will be no more retries.
-        // By default, it is 2 minutes
-        let max_elapsed = OCC_DEFAULT_BACKOFF_MAX_ELAPSED_MS;
+        let mut backoff = Self::occ_backoff();
 
-        // see https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/ for more
-        // informations. (The strategy that crate backoff implements is “Equal Jitter”)
-
-        // To simplify the settings, using fixed common values for randomization_factor and multiplier
-        let mut backoff = ExponentialBackoffBuilder::new()
-            .with_initial_interval(init_delay)
-            .with_max_interval(max_delay)
-            .with_randomization_factor(0.5)
-            .with_multiplier(2.0)
-            .with_max_elapsed_time(Some(max_elapsed))
-            .build();
-
-        loop {
+        let committed = loop {
             match tbl
-                .try_commit(ctx.as_ref(), &operation_log, overwrite)
+                .try_commit(
+                    ctx.as_ref(),
+                    &operation_log,
+                    overwrite,
+                    base.as_deref(),
+                    expected,
+                    options,
+                )
                 .await
             {
                 Ok(_) => break Ok(()),
+                Err(e) if e.code() == ErrorCode::table_commit_conflict_code() => {
+                    tracing::info!("aborting operations, {}", e);
+                    let _ = self::utils::abort_operations(ctx.as_ref(), operation_log).await;
+                    break Err(e);
+                }
                 Err(e) if self::utils::is_error_recoverable(&e) => match backoff.next_backoff() {
                     Some(d) => {
                         let name = tbl.table_info.name.clone();
@@ -132,20 +160,261 @@ impl FuseTable {
                 },
                 Err(e) => break Err(e),
             }
+        };
+
+        if committed.is_ok() {
+            tbl.refresh_aggregating_indexes_after_commit(&ctx).await;
+        }
+        committed
+    }
+
+    /// Commits the insertions into several tables atomically: the new snapshots of the tables
+    /// are committed to the meta server in one transaction, so that either all of them are
+    /// committed or none of them is.
+    ///
+    /// `operations` are the log entries of the insertion into each table, as returned by
+    /// `append_data`.
+    pub async fn commit_multi_table_insertion(
+        ctx: Arc<QueryContext>,
+        operations: Vec<(Arc<dyn Table>, Vec<DataBlock>)>,
+    ) -> Result<()> {
+        let mut tables = Vec::with_capacity(operations.len());
+        let mut operation_logs = Vec::with_capacity(operations.len());
+        for (table, blocks) in operations {
+            FuseTable::try_from_table(table.as_ref())?.check_mutable()?;
+            let operation_log = blocks
+                .iter()
+                .map(AppendOperationLogEntry::try_from)
+                .collect::<Result<TableOperationLog>>()?;
+            tables.push(table);
+            operation_logs.push(operation_log);
+        }
+
+        let mut retry_times = 0;
+        let mut backoff = Self::occ_backoff();
+
+        let committed = loop {
+            match Self::try_commit_multi_table(ctx.as_ref(), &tables, &operation_logs).await {
+                Ok(_) => break Ok(()),
+                Err(e) if self::utils::is_error_recoverable(&e) => {
+                    match backoff.next_backoff() {
+                        Some(d) => {
+                            tracing::warn!(
+                            "got error TableVersionMismatched, multi-table tx will be retried {} \
+                             ms later",
+                            d.as_millis(),
+                        );
+                            common_base::tokio::time::sleep(d).await;
+
+                            // reload all the tables, as any of them may be committed concurrently
+                            let catalog = ctx.get_catalog();
+                            for table in tables.iter_mut() {
+                                let info = table.get_table_info();
+                                let (ident, meta) =
+                                    catalog.get_table_meta_by_id(info.ident.table_id).await?;
+                                let table_info = TableInfo {
+                                    ident,
+                                    desc: "".to_owned(),
+                                    name: info.name.clone(),
+                                    meta: meta.as_ref().clone(),
+                                };
+                                *table = catalog.get_table_by_info(&table_info)?;
+                            }
+                            retry_times += 1;
+                            continue;
+                        }
+                        None => {
+                            tracing::info!("aborting operations");
+                            for operation_log in operation_logs {
+                                let _ = self::utils::abort_operations(ctx.as_ref(), operation_log)
+                                    .await;
+                            }
+                            break Err(ErrorCode::OCCRetryFailure(format!(
+                            "can not fulfill the multi-table tx after retries({} times, {} ms), \
+                             aborted. tables {}",
+                            retry_times,
+                            Instant::now().duration_since(backoff.start_time).as_millis(),
+                            tables
+                                .iter()
+                                .map(|t| t.name())
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        )));
+                        }
+                    }
+                }
+                Err(e) => break Err(e),
+            }
+        };
+
+        if committed.is_ok() {
+            for table in &tables {
+                let tbl = FuseTable::try_from_table(table.as_ref())?;
+                tbl.refresh_aggregating_indexes_after_commit(&ctx).await;
+            }
+        }
+        committed
+    }
+
+    /// Writes a new snapshot for each of the tables, and commits all of them to the meta
+    /// server in one transaction.
+    async fn try_commit_multi_table(
+        ctx: &QueryContext,
+        tables: &[Arc<dyn Table>],
+        operation_logs: &[TableOperationLog],
+    ) -> Result<()> {
+        let operator = ctx.get_storage_operator()?;
+        let mut snapshots = Vec::with_capacity(tables.len());
+        let mut update_table_metas = Vec::with_capacity(tables.len());
+        for (table, operation_log) in tables.iter().zip(operation_logs) {
+            let tbl = FuseTable::try_from_table(table.as_ref())?;
+            let new_snapshot = tbl.build_snapshot(ctx, operation_log, false, None).await?;
+            let snapshot_loc = tbl
+                .meta_location_generator()
+                .snapshot_location_from_uuid(&new_snapshot.snapshot_id, TableSnapshot::VERSION)?;
+            write_snapshot(ctx, &operator, &snapshot_loc, &new_snapshot).await?;
+
+            let table_info = tbl.get_table_info();
+            let mut new_table_meta = table_info.meta.clone();
+            new_table_meta
+                .options
+                .insert(OPT_KEY_SNAPSHOT_LOCATION.to_owned(), snapshot_loc.clone());
+            // if there were any legacy options keys, it is a good chance to remove them
+            new_table_meta.options.remove(OPT_KEY_SNAPSHOT_LOC);
+            new_table_meta.usage = new_snapshot.summary.table_usage();
+            update_table_metas.push(UpdateTableMetaReq::new(&table_info.ident, new_table_meta));
+            snapshots.push((snapshot_loc, new_snapshot));
+        }
+
+        let req = UpdateMultiTableMetaReq { update_table_metas };
+        match ctx.get_catalog().update_multi_table_meta(req).await {
+            Ok(_) => {
+                for (snapshot_loc, new_snapshot) in snapshots {
+                    Self::cache_snapshot(ctx, snapshot_loc, new_snapshot).await;
+                }
+                Ok(())
+            }
+            Err(e) => {
+                // none of the snapshots is committed, try to delete them.
+                for (snapshot_loc, _) in &snapshots {
+                    let _ = operator.object(snapshot_loc).delete().await;
+                }
+                Err(e)
+            }
         }
     }
 
+    /// The backoff of the retries of a commit, which fails as the table is committed by
+    /// other transactions concurrently.
+    fn occ_backoff() -> ExponentialBackoff {
+        // The initial retry delay in millisecond. By default,  it is 5 ms.
+        let init_delay = OCC_DEFAULT_BACKOFF_INIT_DELAY_MS;
+
+        // The maximum  back off delay in millisecond, once the retry interval reaches this value, it stops increasing.
+        // By default, it is 20 seconds.
+        let max_delay = OCC_DEFAULT_BACKOFF_MAX_DELAY_MS;
+
+        // The maximum elapsed time after the occ starts, beyond which there will be no more retries.
+        // By default, it is 2 minutes
+        let max_elapsed = OCC_DEFAULT_BACKOFF_MAX_ELAPSED_MS;
+
+        // see https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/ for more
+        // informations. (The strategy that crate backoff implements is “Equal Jitter”)
+
+        // To simplify the settings, using fixed common values for randomization_factor and multiplier
+        ExponentialBackoffBuilder::new()
+            .with_initial_interval(init_delay)
+            .with_max_interval(max_delay)
+            .with_randomization_factor(0.5)
+            .with_multiplier(2.0)
+            .with_max_elapsed_time(Some(max_elapsed))
+            .build()
+    }
+
+    /// Commits the operations as a new snapshot on top of the current snapshot of the table.
+    ///
+    /// `base` is the snapshot which the operations are based on. Appends could always be
+    /// rebased on the current snapshot; while for an overwrite, the segments committed since
+    /// `base` by other transactions are kept, as if they were committed after the overwrite,
+    /// unless some of the segments of `base` have been removed concurrently, in which case
+    /// the transactions conflict with each other.
+    ///
+    /// See `commit_with_options` for `expected` and `options`.
     #[inline]
     pub async fn try_commit(
         &self,
         ctx: &QueryContext,
         operation_log: &TableOperationLog,
         overwrite: bool,
+        base: Option<&TableSnapshot>,
+        expected: &HashMap<String, Option<String>>,
+        options: &HashMap<String, String>,
     ) -> Result<()> {
+        let table_options = self.table_info.options();
+        for (key, value) in expected {
+            if table_options.get(key) != value.as_ref() {
+                return Err(ErrorCode::TableCommitConflict(format!(
+                    "table option {} has been changed by a concurrent transaction, expected {:?}, \
+                     got {:?}",
+                    key,
+                    value,
+                    table_options.get(key),
+                )));
+            }
+        }
+
+        let new_snapshot = self
+            .build_snapshot(ctx, operation_log, overwrite, base)
+            .await?;
+
+        let uuid = new_snapshot.snapshot_id;
+        let snapshot_loc = self
+            .meta_location_generator()
+            .snapshot_location_from_uuid(&uuid, TableSnapshot::VERSION)?;
+        let operator = ctx.get_storage_operator()?;
+        write_snapshot(ctx, &operator, &snapshot_loc, &new_snapshot).await?;
+
+        let options = options
+            .iter()
+            .map(|(k, v)| (k.clone(), Some(v.clone())))
+            .collect();
+        let result = Self::commit_to_meta_server_with_options(
+            ctx,
+            self.get_table_info(),
+            snapshot_loc.clone(),
+            &new_snapshot.summary,
+            options,
+        )
+        .await;
+
+        match result {
+            Ok(_) => {
+                Self::cache_snapshot(ctx, snapshot_loc, new_snapshot).await;
+                Ok(())
+            }
+            Err(e) => {
+                // commit snapshot to meta server failed, try to delete it.
+                // "major GC" will collect this, if deletion failure (even after DAL retried)
+                let _ = operator.object(&snapshot_loc).delete().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Builds the snapshot committing the operations on top of the current snapshot of the
+    /// table, see `try_commit` for `base`.
+    async fn build_snapshot(
+        &self,
+        ctx: &QueryContext,
+        operation_log: &TableOperationLog,
+        overwrite: bool,
+        base: Option<&TableSnapshot>,
+    ) -> Result<TableSnapshot> {
         let prev = self.read_table_snapshot(ctx).await?;
         let prev_version = self.snapshot_format_version();
         let schema = self.table_info.meta.schema.as_ref().clone();
         let (segments, summary) = Self::merge_append_operations(&schema, operation_log)?;
+        let snapshot_id = Self::snapshot_id_of_operations(operation_log);
 
         let progress_values = ProgressValues {
             rows: summary.row_count as usize,
@@ -153,13 +422,23 @@ impl FuseTable {
         };
         ctx.get_write_progress().incr(&progress_values);
 
-        let segments = segments
+        let mut segments: Vec<Location> = segments
             .into_iter()
             .map(|loc| (loc, SegmentInfo::VERSION))
             .collect();
+        let operation = if overwrite {
+            SnapshotOperation::Overwrite
+        } else {
+            SnapshotOperation::Insert
+        };
         let new_snapshot = if overwrite {
+            let concurrent_segments = Self::concurrent_segments(base, prev.as_deref())?;
+            let summary =
+                Self::merge_segments_statistics(ctx, &schema, summary, &concurrent_segments)
+                    .await?;
+            segments.extend(concurrent_segments);
             TableSnapshot::new(
-                Uuid::new_v4(),
+                snapshot_id,
                 prev.as_ref().map(|v| (v.snapshot_id, prev_version)),
                 schema,
                 summary,
@@ -168,45 +447,80 @@ impl FuseTable {
         } else {
             Self::merge_table_operations(
                 self.table_info.meta.schema.as_ref(),
-                prev,
+                snapshot_id,
+                prev.clone(),
                 prev_version,
                 segments,
                 summary,
             )?
+        }
+        .with_origin(DATABEND_COMMIT_VERSION.as_str(), ctx.get_id())
+        .with_operation(operation, Self::current_user_identity(ctx))
+        .with_changes(prev.as_deref());
+        Ok(new_snapshot)
+    }
+
+    async fn cache_snapshot(ctx: &QueryContext, location: String, snapshot: TableSnapshot) {
+        if let Some(snapshot_cache) = ctx.get_storage_cache_manager().get_table_snapshot_cache() {
+            let cache = &mut snapshot_cache.write().await;
+            cache.put(location, Arc::new(snapshot));
+        }
+    }
+
+    /// Returns the segments which are committed by other transactions since the `base`
+    /// snapshot, or a `TableCommitConflict` error if some of the segments of `base` are
+    /// no longer in the `latest` snapshot.
+    fn concurrent_segments(
+        base: Option<&TableSnapshot>,
+        latest: Option<&TableSnapshot>,
+    ) -> Result<Vec<Location>> {
+        let latest = match latest {
+            None => return Ok(vec![]),
+            Some(latest) => latest,
+        };
+        let base = match base {
+            None => return Ok(latest.segments.clone()),
+            Some(base) if base.snapshot_id == latest.snapshot_id => return Ok(vec![]),
+            Some(base) => base,
         };
 
-        let uuid = new_snapshot.snapshot_id;
-        let snapshot_loc = self
-            .meta_location_generator()
-            .snapshot_location_from_uuid(&uuid, TableSnapshot::VERSION)?;
-        let bytes = serde_json::to_vec(&new_snapshot)?;
-        let operator = ctx.get_storage_operator()?;
-        operator.object(&snapshot_loc).write(bytes).await?;
+        let latest_segments: HashSet<&Location> = latest.segments.iter().collect();
+        if let Some((loc, _)) = base.segments.iter().find(|l| !latest_segments.contains(l)) {
+            return Err(ErrorCode::TableCommitConflict(format!(
+                "segment {} of snapshot {} has been removed by a concurrent transaction, latest snapshot {}",
+                loc,
+                base.snapshot_id.to_simple(),
+                latest.snapshot_id.to_simple(),
+            )));
+        }
 
-        let result =
-            Self::commit_to_meta_server(ctx, self.get_table_info(), snapshot_loc.clone()).await;
+        let base_segments: HashSet<&Location> = base.segments.iter().collect();
+        Ok(latest
+            .segments
+            .iter()
+            .filter(|l| !base_segments.contains(l))
+            .cloned()
+            .collect())
+    }
 
-        match result {
-            Ok(_) => {
-                if let Some(snapshot_cache) =
-                    ctx.get_storage_cache_manager().get_table_snapshot_cache()
-                {
-                    let cache = &mut snapshot_cache.write().await;
-                    cache.put(snapshot_loc, Arc::new(new_snapshot));
-                }
-                Ok(())
-            }
-            Err(e) => {
-                // commit snapshot to meta server failed, try to delete it.
-                // "major GC" will collect this, if deletion failure (even after DAL retried)
-                let _ = operator.object(&snapshot_loc).delete().await;
-                Err(e)
-            }
+    async fn merge_segments_statistics(
+        ctx: &QueryContext,
+        schema: &DataSchema,
+        statistics: Statistics,
+        segments: &[Location],
+    ) -> Result<Statistics> {
+        let reader = MetaReaders::segment_info_reader(ctx);
+        let mut acc = statistics;
+        for (loc, ver) in segments {
+            let segment = reader.read(loc, None, *ver).await?;
+            acc = statistics::merge_statistics(schema, &acc, &segment.summary)?;
         }
+        Ok(acc)
     }
 
     fn merge_table_operations(
         schema: &DataSchema,
+        snapshot_id: SnapshotId,
         previous: Option<Arc<TableSnapshot>>,
         prev_version: u64,
         mut new_segments: Vec<Location>,
@@ -227,41 +541,91 @@ impl FuseTable {
             new_segments.append(&mut segments)
         };
 
-        let new_snapshot = TableSnapshot::new(
-            Uuid::new_v4(),
+        let mut new_snapshot = TableSnapshot::new(
+            snapshot_id,
             prev_snapshot_id,
             schema.clone(),
             stats,
             new_segments,
         );
+
+        // 3. the statistics of the previous snapshot are kept, until the table is analyzed again
+        new_snapshot.table_statistics_location = previous
+            .as_ref()
+            .and_then(|s| s.table_statistics_location.clone());
         Ok(new_snapshot)
     }
 
-    async fn commit_to_meta_server(
+    /// Identity of the user who issues the query of `ctx`, which is recorded in the snapshots
+    /// committed by the query.
+    pub(crate) fn current_user_identity(ctx: &QueryContext) -> Option<String> {
+        ctx.get_current_user()
+            .ok()
+            .map(|u| u.identity().to_string())
+    }
+
+    /// Points the table to the new snapshot, of which `summary` is the summary, and refreshes
+    /// the usage of the table along with it.
+    pub(crate) async fn commit_to_meta_server(
+        ctx: &QueryContext,
+        table_info: &TableInfo,
+        new_snapshot_location: String,
+        summary: &Statistics,
+    ) -> Result<UpdateTableMetaReply> {
+        Self::commit_to_meta_server_with_options(
+            ctx,
+            table_info,
+            new_snapshot_location,
+            summary,
+            HashMap::new(),
+        )
+        .await
+    }
+
+    /// Like `commit_to_meta_server`, but also upserts the given table options, in the same
+    /// request as the new snapshot location.
+    pub(crate) async fn commit_to_meta_server_with_options(
         ctx: &QueryContext,
         table_info: &TableInfo,
         new_snapshot_location: String,
-    ) -> Result<UpsertTableOptionReply> {
+        summary: &Statistics,
+        mut options: HashMap<String, Option<String>>,
+    ) -> Result<UpdateTableMetaReply> {
         let catalog = ctx.get_catalog();
-        let mut options = [(
+        options.insert(
             OPT_KEY_SNAPSHOT_LOCATION.to_owned(),
             Some(new_snapshot_location),
-        )]
-        .into_iter()
-        .collect();
+        );
 
         // if there were any legacy options keys, it is a good chance to remove them
         self::utils::gather_legacy_options(table_info, &mut options);
 
-        let table_id = table_info.ident.table_id;
-        let table_version = table_info.ident.version;
-        let req = UpsertTableOptionReq {
-            table_id,
-            seq: MatchSeq::Exact(table_version),
-            options,
-        };
+        let mut new_table_meta = table_info.meta.clone();
+        for (key, value) in options {
+            match value {
+                Some(value) => new_table_meta.options.insert(key, value),
+                None => new_table_meta.options.remove(&key),
+            };
+        }
+        new_table_meta.usage = summary.table_usage();
 
-        catalog.upsert_table_option(req).await
+        // fails if the table is committed by others since `table_info` was loaded
+        let req = UpdateTableMetaReq::new(&table_info.ident, new_table_meta);
+        catalog.update_table_meta(req).await
+    }
+
+    /// Returns the id of the snapshot which the appended blocks are written for, so that the
+    /// blocks record the snapshot they are committed by; or a new id, if the blocks do not
+    /// agree on it.
+    fn snapshot_id_of_operations(append_log_entries: &[AppendOperationLogEntry]) -> SnapshotId {
+        let mut ids = append_log_entries
+            .iter()
+            .flat_map(|e| e.segment_info.blocks.iter())
+            .map(|b| b.created_by);
+        match ids.next() {
+            Some(Some(id)) if ids.all(|v| v == Some(id)) => id,
+            _ => Uuid::new_v4(),
+        }
     }
 
     pub fn merge_append_operations(
@@ -280,6 +644,9 @@ impl FuseTable {
                 acc.block_count += stats.block_count;
                 acc.uncompressed_byte_size += stats.uncompressed_byte_size;
                 acc.compressed_byte_size += stats.compressed_byte_size;
+                for (index_type, size) in stats.index_sizes().iter() {
+                    acc.add_index_size(*index_type, *size);
+                }
                 acc.col_stats =
                     statistics::reduce_block_stats(&[&acc.col_stats, &stats.col_stats], schema)?;
                 seg_acc.push(loc.clone());
@@ -302,10 +669,11 @@ mod utils {
 
         for entry in operation_log {
             for block in &entry.segment_info.blocks {
-                let block_location = &block.location.0;
                 // if deletion operation failed (after DAL retried)
                 // we just left them there, and let the "major GC" collect them
-                let _ = operator.object(block_location).delete().await;
+                for location in block.file_locations() {
+                    let _ = operator.object(location).delete().await;
+                }
             }
             let _ = operator.object(&entry.segment_location).delete().await;
         }
@@ -0,0 +1,616 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_cache::Cache;
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::Result;
+use common_metrics::label_counter_with_val;
+use common_tracing::tracing;
+use uuid::Uuid;
+
+use crate::configs::DATABEND_COMMIT_VERSION;
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::write_block;
+use crate::storages::fuse::io::write_snapshot;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::Location;
+use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::meta::SnapshotId;
+use crate::storages::fuse::meta::SnapshotOperation;
+use crate::storages::fuse::meta::Statistics;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::meta::Versioned;
+use crate::storages::fuse::statistics;
+use crate::storages::fuse::statistics::accumulator::BlockStatistics;
+use crate::storages::fuse::statistics::histogram::compare_values;
+use crate::storages::fuse::statistics::StatisticsAccumulator;
+use crate::storages::fuse::zorder_indices;
+use crate::storages::fuse::ClusterOrder;
+use crate::storages::fuse::ColumnIds;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::DEFAULT_BLOCK_PER_SEGMENT;
+use crate::storages::fuse::DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD;
+use crate::storages::fuse::DEFAULT_ROW_PER_BLOCK;
+use crate::storages::fuse::FUSE_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD;
+use crate::storages::fuse::FUSE_OPT_KEY_BLOCK_PER_SEGMENT;
+use crate::storages::fuse::FUSE_OPT_KEY_ROW_PER_BLOCK;
+
+const METRIC_COMPACT_SEGMENTS_BEFORE: &str = "fuse_compact_segments_before";
+const METRIC_COMPACT_SEGMENTS_AFTER: &str = "fuse_compact_segments_after";
+
+/// Decides which segments are merged by `OPTIMIZE TABLE t COMPACT SEGMENT`.
+///
+/// A segment is a candidate if it holds fewer blocks than `block_per_segment`
+/// (count-based) and less data than `block_per_segment * block_size_threshold`
+/// (size-based). Consecutive candidates are packed together, as long as the merged
+/// segment does not exceed either of the limits.
+#[derive(Clone, Copy, Debug)]
+pub struct SegmentCompactionPolicy {
+    pub block_per_segment: usize,
+    pub max_segment_size: u64,
+}
+
+impl SegmentCompactionPolicy {
+    pub fn new(block_per_segment: usize, block_size_threshold: usize) -> Self {
+        let max_segment_size =
+            (block_per_segment as u64).saturating_mul(block_size_threshold as u64);
+        Self {
+            block_per_segment,
+            max_segment_size,
+        }
+    }
+
+    fn is_small(&self, segment: &SegmentInfo) -> bool {
+        segment.blocks.len() < self.block_per_segment
+            && segment.summary.uncompressed_byte_size < self.max_segment_size
+    }
+
+    /// Groups the indexes of the segments, each group of which will be merged into one segment.
+    ///
+    /// Groups of a single segment are kept as they are.
+    pub fn plan(&self, segments: &[Arc<SegmentInfo>]) -> Vec<Vec<usize>> {
+        let mut groups = vec![];
+        let mut current: Vec<usize> = vec![];
+        let mut blocks = 0;
+        let mut bytes = 0;
+        for (idx, segment) in segments.iter().enumerate() {
+            if !self.is_small(segment) {
+                continue;
+            }
+            let segment_blocks = segment.blocks.len();
+            let segment_bytes = segment.summary.uncompressed_byte_size;
+            if !current.is_empty()
+                && (blocks + segment_blocks > self.block_per_segment
+                    || bytes + segment_bytes > self.max_segment_size)
+            {
+                groups.push(std::mem::take(&mut current));
+                blocks = 0;
+                bytes = 0;
+            }
+            current.push(idx);
+            blocks += segment_blocks;
+            bytes += segment_bytes;
+        }
+        if !current.is_empty() {
+            groups.push(current);
+        }
+        groups.retain(|g| g.len() > 1);
+        groups
+    }
+}
+
+impl FuseTable {
+    pub fn segment_compaction_policy(&self) -> SegmentCompactionPolicy {
+        SegmentCompactionPolicy::new(
+            self.get_option(FUSE_OPT_KEY_BLOCK_PER_SEGMENT, DEFAULT_BLOCK_PER_SEGMENT),
+            self.get_option(
+                FUSE_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD,
+                DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD,
+            ),
+        )
+    }
+
+    /// Merges the small segments of the current snapshot into larger ones.
+    ///
+    /// Blocks are not touched, and the replaced segments are left to be purged, thus the
+    /// queries which are reading the current snapshot are not affected.
+    pub async fn do_compact_segments(&self, ctx: &Arc<QueryContext>) -> Result<()> {
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+
+        let segments = Self::load_segments(ctx.as_ref(), &snapshot.segments).await?;
+        let groups = self.segment_compaction_policy().plan(&segments);
+        let segments_before = snapshot.segments.len();
+        if groups.is_empty() {
+            self.record_segment_compaction(ctx, segments_before, segments_before);
+            return Ok(());
+        }
+
+        let operator = ctx.get_storage_operator()?;
+        let schema = self.table_info.schema();
+        let mut new_segment_locs = vec![];
+        // index of the first segment of a group => the merged segment
+        let mut merged = HashMap::new();
+        let mut replaced = HashSet::new();
+        for group in groups {
+            let mut blocks = vec![];
+            let mut summary = segments[group[0]].summary.clone();
+            for (i, idx) in group.iter().enumerate() {
+                let segment = &segments[*idx];
+                blocks.extend(segment.blocks.iter().cloned());
+                if i > 0 {
+                    summary = statistics::merge_statistics(&schema, &summary, &segment.summary)?;
+                }
+                replaced.insert(*idx);
+            }
+            let new_segment = SegmentInfo::new(blocks, summary);
+            let loc = self.meta_location_generator.gen_segment_info_location();
+            let bytes = serde_json::to_vec(&new_segment)?;
+            operator.object(&loc).write(bytes).await?;
+            new_segment_locs.push(loc.clone());
+            merged.insert(group[0], (loc, SegmentInfo::VERSION));
+        }
+
+        let new_segments: Vec<Location> = snapshot
+            .segments
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, loc)| match merged.remove(&idx) {
+                Some(merged_loc) => Some(merged_loc),
+                None if replaced.contains(&idx) => None,
+                None => Some(loc.clone()),
+            })
+            .collect();
+        let segments_after = new_segments.len();
+
+        let mut new_snapshot = TableSnapshot::new(
+            Uuid::new_v4(),
+            Some((snapshot.snapshot_id, self.snapshot_format_version())),
+            snapshot.schema.clone(),
+            snapshot.summary.clone(),
+            new_segments,
+        )
+        .with_origin(DATABEND_COMMIT_VERSION.as_str(), ctx.get_id())
+        .with_operation(
+            SnapshotOperation::Compact,
+            Self::current_user_identity(ctx.as_ref()),
+        )
+        .with_changes(Some(snapshot.as_ref()));
+        new_snapshot.table_statistics_location = snapshot.table_statistics_location.clone();
+
+        let snapshot_loc = self
+            .meta_location_generator
+            .snapshot_location_from_uuid(&new_snapshot.snapshot_id, TableSnapshot::VERSION)?;
+        write_snapshot(ctx, &operator, &snapshot_loc, &new_snapshot).await?;
+
+        // if the table is modified concurrently, the compaction is abandoned
+        match Self::commit_to_meta_server(
+            ctx.as_ref(),
+            &self.table_info,
+            snapshot_loc.clone(),
+            &new_snapshot.summary,
+        )
+        .await
+        {
+            Ok(_) => {
+                if let Some(snapshot_cache) =
+                    ctx.get_storage_cache_manager().get_table_snapshot_cache()
+                {
+                    let cache = &mut snapshot_cache.write().await;
+                    cache.put(snapshot_loc, Arc::new(new_snapshot));
+                }
+                self.record_segment_compaction(ctx, segments_before, segments_after);
+                Ok(())
+            }
+            Err(e) => {
+                let _ = operator.object(&snapshot_loc).delete().await;
+                for loc in &new_segment_locs {
+                    let _ = operator.object(loc).delete().await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Merges the undersized blocks of the current snapshot into larger ones.
+    ///
+    /// Blocks which have deletion vectors are rewritten as well, without the deleted rows.
+    ///
+    /// If the table is clustered, the undersized blocks are picked in the order of their
+    /// cluster key ranges, and the rows of each merged block are re-sorted by the cluster
+    /// keys, so that the clustering is not undone. At most `limit` blocks are rewritten.
+    pub async fn do_compact(&self, ctx: &Arc<QueryContext>, limit: Option<usize>) -> Result<()> {
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+
+        let max_rows_per_block = self.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK);
+        let min_rows_per_block = (max_rows_per_block as f64 * 0.8) as usize;
+        let block_size_threshold = self.get_option(
+            FUSE_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD,
+            DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD,
+        );
+
+        let segments = Self::load_segments(ctx.as_ref(), &snapshot.segments).await?;
+        let mut candidates = segments
+            .iter()
+            .flat_map(|segment| segment.blocks.iter())
+            .filter(|b| {
+                b.deletion_vector.is_some()
+                    || ((b.row_count as usize) < min_rows_per_block
+                        && (b.block_size as usize) < block_size_threshold)
+            })
+            .collect::<Vec<_>>();
+        if let Some(cluster_key) = self.cluster_key() {
+            candidates.sort_by(
+                |l, r| match (cluster_key.range_of(l), cluster_key.range_of(r)) {
+                    (Some(l), Some(r)) => compare_values(&l.0, &r.0).unwrap_or(Ordering::Equal),
+                    _ => Ordering::Equal,
+                },
+            );
+        }
+        if let Some(limit) = limit {
+            candidates.truncate(limit);
+        }
+
+        // pack the candidates into batches, each of which is merged into one block
+        let mut batches = vec![];
+        let mut batch: Vec<BlockMeta> = vec![];
+        let mut rows = 0;
+        for block in candidates {
+            let live_rows = block.live_row_count() as usize;
+            if !batch.is_empty() && rows + live_rows > max_rows_per_block {
+                batches.push(std::mem::take(&mut batch));
+                rows = 0;
+            }
+            rows += live_rows;
+            batch.push(block.clone());
+        }
+        if !batch.is_empty() {
+            batches.push(batch);
+        }
+        // a single block is only rewritten to get rid of its deleted rows
+        batches.retain(|b| b.len() > 1 || b[0].deletion_vector.is_some());
+        if batches.is_empty() {
+            return Ok(());
+        }
+
+        // the merged blocks record the snapshot which commits them
+        let snapshot_id = Uuid::new_v4();
+        let mut new_locations = vec![];
+        let result = match self
+            .write_compacted(
+                ctx,
+                snapshot_id,
+                &snapshot,
+                &segments,
+                &batches,
+                &mut new_locations,
+            )
+            .await
+        {
+            Ok(segment_locations) => {
+                self.commit_mutation(
+                    ctx,
+                    snapshot_id,
+                    Some(snapshot.as_ref()),
+                    segment_locations,
+                    SnapshotOperation::Compact,
+                    &mut new_locations,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        if result.is_err() {
+            // the data written by this compaction is not referenced by any snapshot
+            let operator = ctx.get_storage_operator()?;
+            for location in &new_locations {
+                let _ = operator.object(location).delete().await;
+            }
+        }
+        result
+    }
+
+    /// Writes the merged blocks, and the segments of them, returns the segment locations of
+    /// the compacted snapshot.
+    async fn write_compacted(
+        &self,
+        ctx: &Arc<QueryContext>,
+        snapshot_id: SnapshotId,
+        snapshot: &TableSnapshot,
+        segments: &[Arc<SegmentInfo>],
+        batches: &[Vec<BlockMeta>],
+        new_locations: &mut Vec<String>,
+    ) -> Result<Vec<Location>> {
+        let operator = ctx.get_storage_operator()?;
+        let schema = self.table_info.schema();
+        let block_reader = Self::create_block_reader(ctx, schema.clone(), &None)?;
+
+        let column_ids = ColumnIds::from_schema(&schema);
+        let mut acc = StatisticsAccumulator::created_by(snapshot_id, column_ids);
+        let mut compacted = HashSet::new();
+        for batch in batches {
+            let (_, parts) = Self::to_partitions(&schema, batch, None);
+            let mut blocks = Vec::with_capacity(parts.len());
+            for part in parts {
+                blocks.push(block_reader.read(part).await?);
+            }
+            let block = self.sort_by_cluster_keys(ctx, DataBlock::concat_blocks(&blocks)?)?;
+            let location = self.meta_location_generator.gen_block_location();
+            new_locations.push(location.clone());
+            let block_statistics =
+                BlockStatistics::from(&block, location.clone(), self.string_prefix_length())?;
+            let arrow_schema = block.schema().to_arrow();
+            let (file_size, meta) =
+                write_block(&arrow_schema, block, operator.clone(), &location).await?;
+            acc.add_block(file_size, meta, block_statistics)?;
+            compacted.extend(batch.iter().map(|b| b.location.clone()));
+        }
+
+        self.write_segments_replacing(
+            ctx,
+            snapshot,
+            segments,
+            &acc.blocks_metas,
+            &compacted,
+            new_locations,
+        )
+        .await
+    }
+
+    /// Writes the segments of `new_blocks`, and of the remaining blocks of the segments which
+    /// have blocks in `replaced`, returns the segment locations of the new snapshot.
+    pub(crate) async fn write_segments_replacing(
+        &self,
+        ctx: &Arc<QueryContext>,
+        snapshot: &TableSnapshot,
+        segments: &[Arc<SegmentInfo>],
+        new_blocks: &[BlockMeta],
+        replaced: &HashSet<Location>,
+        new_locations: &mut Vec<String>,
+    ) -> Result<Vec<Location>> {
+        let operator = ctx.get_storage_operator()?;
+        let schema = self.table_info.schema();
+        let block_per_segment =
+            self.get_option(FUSE_OPT_KEY_BLOCK_PER_SEGMENT, DEFAULT_BLOCK_PER_SEGMENT);
+
+        // the new blocks come first, as if they were newly appended
+        let mut new_segments = vec![];
+        for blocks in new_blocks.chunks(block_per_segment) {
+            new_segments.push(Self::segment_of_blocks(&schema, blocks.to_vec())?);
+        }
+        let mut kept_segments = vec![];
+        for (idx, segment) in segments.iter().enumerate() {
+            if segment
+                .blocks
+                .iter()
+                .any(|b| replaced.contains(&b.location))
+            {
+                let remains = segment
+                    .blocks
+                    .iter()
+                    .filter(|b| !replaced.contains(&b.location))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if !remains.is_empty() {
+                    new_segments.push(Self::segment_of_blocks(&schema, remains)?);
+                }
+            } else {
+                kept_segments.push(snapshot.segments[idx].clone());
+            }
+        }
+
+        let mut segment_locations = Vec::with_capacity(new_segments.len() + kept_segments.len());
+        for segment in &new_segments {
+            let location = self.meta_location_generator.gen_segment_info_location();
+            new_locations.push(location.clone());
+            let bytes = serde_json::to_vec(segment)?;
+            operator.object(&location).write(bytes).await?;
+            segment_locations.push((location, SegmentInfo::VERSION));
+        }
+        segment_locations.extend(kept_segments);
+        Ok(segment_locations)
+    }
+
+    /// Commits a snapshot of `segment_locations`, produced by `operation`, which replaces
+    /// `snapshot`, or which is the first snapshot of the table if `snapshot` is `None`.
+    ///
+    /// The location of the new snapshot is pushed to `new_locations`, so that it can be
+    /// cleaned up along with the other data written by the mutation if the commit fails.
+    pub(crate) async fn commit_mutation(
+        &self,
+        ctx: &Arc<QueryContext>,
+        snapshot_id: SnapshotId,
+        snapshot: Option<&TableSnapshot>,
+        segment_locations: Vec<Location>,
+        operation: SnapshotOperation,
+        new_locations: &mut Vec<String>,
+    ) -> Result<()> {
+        let segments = Self::load_segments(ctx.as_ref(), &segment_locations).await?;
+        let schema = self.table_info.schema();
+        let mut summary = Statistics::default();
+        for segment in &segments {
+            summary = statistics::merge_statistics(&schema, &summary, &segment.summary)?;
+        }
+
+        let mut new_snapshot = TableSnapshot::new(
+            snapshot_id,
+            snapshot.map(|s| (s.snapshot_id, self.snapshot_format_version())),
+            snapshot.map_or_else(|| schema.as_ref().clone(), |s| s.schema.clone()),
+            summary,
+            segment_locations,
+        )
+        .with_origin(DATABEND_COMMIT_VERSION.as_str(), ctx.get_id())
+        .with_operation(operation, Self::current_user_identity(ctx.as_ref()))
+        .with_changes(snapshot);
+        new_snapshot.table_statistics_location =
+            snapshot.and_then(|s| s.table_statistics_location.clone());
+
+        let snapshot_loc = self
+            .meta_location_generator
+            .snapshot_location_from_uuid(&new_snapshot.snapshot_id, TableSnapshot::VERSION)?;
+        let operator = ctx.get_storage_operator()?;
+        write_snapshot(ctx, &operator, &snapshot_loc, &new_snapshot).await?;
+        new_locations.push(snapshot_loc.clone());
+
+        // if the table is modified concurrently, the mutation is abandoned
+        Self::commit_to_meta_server(
+            ctx.as_ref(),
+            &self.table_info,
+            snapshot_loc.clone(),
+            &new_snapshot.summary,
+        )
+        .await?;
+        if let Some(snapshot_cache) = ctx.get_storage_cache_manager().get_table_snapshot_cache() {
+            let cache = &mut snapshot_cache.write().await;
+            cache.put(snapshot_loc, Arc::new(new_snapshot));
+        }
+        self.refresh_aggregating_indexes_after_commit(ctx).await;
+        Ok(())
+    }
+
+    pub(crate) fn sort_by_cluster_keys(
+        &self,
+        ctx: &Arc<QueryContext>,
+        block: DataBlock,
+    ) -> Result<DataBlock> {
+        if self.order_keys.is_empty() {
+            return Ok(block);
+        }
+
+        // the keys of the z-order are columns, see `ClusterOrder::try_create`
+        if self.cluster_order() == ClusterOrder::ZOrder {
+            let columns = self
+                .order_keys
+                .iter()
+                .map(|key| block.try_column_by_name(&key.column_name()).cloned())
+                .collect::<Result<Vec<_>>>()?;
+            return DataBlock::block_take_by_indices(&block, &zorder_indices(&columns));
+        }
+
+        let input_schema = self.table_info.schema();
+        let mut merged = input_schema.fields().clone();
+        for expr in &self.order_keys {
+            let cname = expr.column_name();
+            if !merged.iter().any(|x| x.name() == &cname) {
+                merged.push(expr.to_data_field(&input_schema)?);
+            }
+        }
+        let output_schema = DataSchemaRefExt::create(merged);
+
+        let sort_descs: Vec<SortColumnDescription> = self
+            .order_keys
+            .iter()
+            .map(|expr| SortColumnDescription {
+                column_name: expr.column_name(),
+                asc: true,
+                nulls_first: false,
+            })
+            .collect();
+
+        if output_schema == input_schema {
+            return DataBlock::sort_block(&block, &sort_descs, None);
+        }
+
+        let executor = ExpressionExecutor::try_create(
+            ctx.clone(),
+            "cluster keys executor",
+            input_schema.clone(),
+            output_schema,
+            self.order_keys.clone(),
+            false,
+        )?;
+        let sorted = DataBlock::sort_block(&executor.execute(&block)?, &sort_descs, None)?;
+        // remove the evaluated cluster keys
+        let columns = input_schema
+            .fields()
+            .iter()
+            .map(|f| sorted.try_column_by_name(f.name()).cloned())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(DataBlock::create(input_schema, columns))
+    }
+
+    pub(crate) fn segment_of_blocks(
+        schema: &DataSchema,
+        blocks: Vec<BlockMeta>,
+    ) -> Result<SegmentInfo> {
+        let col_stats = statistics::reduce_block_stats(
+            &blocks.iter().map(|b| &b.col_stats).collect::<Vec<_>>(),
+            schema,
+        )?;
+        let mut summary = Statistics {
+            row_count: blocks.iter().map(|b| b.live_row_count()).sum(),
+            block_count: blocks.len() as u64,
+            uncompressed_byte_size: blocks.iter().map(|b| b.block_size).sum(),
+            compressed_byte_size: blocks.iter().map(|b| b.file_size).sum(),
+            col_stats,
+            ..Default::default()
+        };
+        for (index_type, size) in blocks.iter().flat_map(|b| b.index_sizes()) {
+            summary.add_index_size(index_type, size);
+        }
+        Ok(SegmentInfo::new(blocks, summary))
+    }
+
+    pub(crate) async fn load_segments(
+        ctx: &QueryContext,
+        locations: &[Location],
+    ) -> Result<Vec<Arc<SegmentInfo>>> {
+        let reader = MetaReaders::segment_info_reader(ctx);
+        let mut segments = Vec::with_capacity(locations.len());
+        for (loc, ver) in locations {
+            segments.push(reader.read(loc, None, *ver).await?);
+        }
+        Ok(segments)
+    }
+
+    fn record_segment_compaction(&self, ctx: &QueryContext, before: usize, after: usize) {
+        tracing::info!(
+            "compact segments of table {}, segments before: {}, after: {}",
+            self.table_info.desc,
+            before,
+            after
+        );
+        let conf = ctx.get_config();
+        let tenant_id = &conf.query.tenant_id;
+        let cluster_id = &conf.query.cluster_id;
+        label_counter_with_val(
+            METRIC_COMPACT_SEGMENTS_BEFORE,
+            before as u64,
+            tenant_id,
+            cluster_id,
+        );
+        label_counter_with_val(
+            METRIC_COMPACT_SEGMENTS_AFTER,
+            after as u64,
+            tenant_id,
+            cluster_id,
+        );
+    }
+}
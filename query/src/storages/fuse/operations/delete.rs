@@ -0,0 +1,208 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::Result;
+use common_planners::DeletePlan;
+use common_planners::Expression;
+use common_planners::Extras;
+use common_planners::TruncateTablePlan;
+use uuid::Uuid;
+
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::DeletionVector;
+use crate::storages::fuse::meta::DeletionVectorMeta;
+use crate::storages::fuse::meta::Location;
+use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::meta::SnapshotOperation;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::meta::Versioned;
+use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::FuseTable;
+
+impl FuseTable {
+    /// Deletes the rows matching the selection of `plan`.
+    ///
+    /// The blocks are not rewritten, instead the deleted rows of each block are marked in a
+    /// deletion vector, which is applied by the readers as a filter, and merged back into the
+    /// data by compaction. Blocks of which all the rows are deleted are removed.
+    pub async fn do_delete(&self, ctx: &Arc<QueryContext>, plan: DeletePlan) -> Result<()> {
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+
+        let selection = match plan.selection {
+            Some(selection) => selection,
+            None => {
+                let truncate_plan = TruncateTablePlan {
+                    db: plan.database_name,
+                    table: plan.table_name,
+                    purge: false,
+                };
+                return self.do_truncate(ctx.clone(), truncate_plan).await;
+            }
+        };
+
+        let mut new_locations = vec![];
+        let result = match self
+            .mark_deleted_rows(ctx, &snapshot, &selection, &mut new_locations)
+            .await
+        {
+            // nothing is deleted
+            Ok(None) => Ok(()),
+            Ok(Some(segment_locations)) => {
+                self.commit_mutation(
+                    ctx,
+                    Uuid::new_v4(),
+                    Some(snapshot.as_ref()),
+                    segment_locations,
+                    SnapshotOperation::Delete,
+                    &mut new_locations,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        if result.is_err() {
+            // the data written by this deletion is not referenced by any snapshot
+            let operator = ctx.get_storage_operator()?;
+            for location in &new_locations {
+                let _ = operator.object(location).delete().await;
+            }
+        }
+        result
+    }
+
+    /// Writes the deletion vectors of the blocks which have rows matching `selection`, and
+    /// the segments of them, returns the segment locations of the new snapshot, or `None`
+    /// if no row is matched.
+    async fn mark_deleted_rows(
+        &self,
+        ctx: &Arc<QueryContext>,
+        snapshot: &Arc<TableSnapshot>,
+        selection: &Expression,
+        new_locations: &mut Vec<String>,
+    ) -> Result<Option<Vec<Location>>> {
+        let schema = self.table_info.schema();
+        let push_downs = Some(Extras {
+            filters: vec![selection.clone()],
+            ..Extras::default()
+        });
+        let candidates = BlockPruner::new(snapshot.clone())
+            .apply(ctx.as_ref(), schema.clone(), &push_downs)
+            .await?;
+        if candidates.is_empty() {
+            return Ok(None);
+        }
+
+        let operator = ctx.get_storage_operator()?;
+        let block_reader = Self::create_block_reader(ctx, schema.clone(), &None)?;
+        let executor = ExpressionExecutor::try_create(
+            ctx.clone(),
+            "delete selection executor",
+            schema.clone(),
+            DataSchemaRefExt::create(vec![selection.to_data_field(&schema)?]),
+            vec![selection.clone()],
+            false,
+        )?;
+
+        // location of the block => the new meta of it, or None if all the rows are deleted
+        let mut mutated: HashMap<String, Option<BlockMeta>> = HashMap::new();
+        for block_meta in candidates {
+            let blocks = std::slice::from_ref(&block_meta);
+            let (_, parts) = Self::to_partitions(&schema, blocks, None);
+            let part = parts[0].clone();
+            // the positions of the rows are the ones in the block, including the deleted rows
+            let block = block_reader.read_all_rows(part.clone()).await?;
+            let mut deletion_vector = match block_reader.read_deletion_vector(&part).await? {
+                Some(deletion_vector) => deletion_vector,
+                None => DeletionVector::new(block_meta.row_count),
+            };
+            let deleted_before = deletion_vector.deleted_row_count();
+
+            let predicate = executor.execute(&block)?;
+            let predicate = DataBlock::cast_to_nonull_boolean(predicate.column(0))?;
+            for row in 0..block.num_rows() {
+                if predicate.get_bool(row)? {
+                    deletion_vector.delete(row);
+                }
+            }
+
+            let deleted_row_count = deletion_vector.deleted_row_count();
+            if deleted_row_count == deleted_before {
+                continue;
+            }
+            let location = block_meta.location.0.clone();
+            if deleted_row_count == block_meta.row_count {
+                mutated.insert(location, None);
+                continue;
+            }
+
+            let dv_location = self.meta_location_generator.gen_deletion_vector_location();
+            new_locations.push(dv_location.clone());
+            let bytes = serde_json::to_vec(&deletion_vector)?;
+            operator.object(&dv_location).write(bytes).await?;
+            let new_meta = BlockMeta {
+                deletion_vector: Some(DeletionVectorMeta {
+                    location: (dv_location, DeletionVector::VERSION),
+                    deleted_row_count,
+                }),
+                ..block_meta
+            };
+            mutated.insert(location, Some(new_meta));
+        }
+        if mutated.is_empty() {
+            return Ok(None);
+        }
+
+        let segments = Self::load_segments(ctx.as_ref(), &snapshot.segments).await?;
+        let mut segment_locations = Vec::with_capacity(segments.len());
+        for (idx, segment) in segments.iter().enumerate() {
+            if !segment
+                .blocks
+                .iter()
+                .any(|b| mutated.contains_key(&b.location.0))
+            {
+                segment_locations.push(snapshot.segments[idx].clone());
+                continue;
+            }
+            let blocks = segment
+                .blocks
+                .iter()
+                .filter_map(|b| match mutated.get(&b.location.0) {
+                    Some(new_meta) => new_meta.clone(),
+                    None => Some(b.clone()),
+                })
+                .collect::<Vec<_>>();
+            if blocks.is_empty() {
+                continue;
+            }
+            let new_segment = Self::segment_of_blocks(&schema, blocks)?;
+            let location = self.meta_location_generator.gen_segment_info_location();
+            new_locations.push(location.clone());
+            let bytes = serde_json::to_vec(&new_segment)?;
+            operator.object(&location).write(bytes).await?;
+            segment_locations.push((location, SegmentInfo::VERSION));
+        }
+        Ok(Some(segment_locations))
+    }
+}
@@ -0,0 +1,227 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use common_datavalues::remove_nullable;
+use common_datavalues::DataSchema;
+use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_datavalues::TypeID;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_tracing::tracing;
+use uuid::Uuid;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::ColumnId;
+use crate::storages::fuse::meta::Location;
+use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::meta::SnapshotOperation;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::ColumnIds;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::FUSE_OPT_KEY_DATA_RETENTION;
+use crate::storages::fuse::FUSE_OPT_KEY_DATA_RETENTION_COLUMN;
+
+/// Parses the retention period of the table option `data_retention`, which is an interval
+/// like `'7 days'`, optionally led by `INTERVAL`, e.g. `'INTERVAL 12 HOUR'`.
+pub fn parse_data_retention(value: &str) -> Result<Duration> {
+    let invalid = || {
+        ErrorCode::BadOption(format!(
+            "invalid {} '{}', expect an interval like '7 days'",
+            FUSE_OPT_KEY_DATA_RETENTION, value
+        ))
+    };
+    let mut words = value.split_whitespace().peekable();
+    if matches!(words.peek(), Some(w) if w.eq_ignore_ascii_case("INTERVAL")) {
+        words.next();
+    }
+    let (n, unit) = match (words.next(), words.next(), words.next()) {
+        (Some(n), Some(unit), None) => (n.parse::<i64>().map_err(|_| invalid())?, unit),
+        _ => return Err(invalid()),
+    };
+    if n <= 0 {
+        return Err(invalid());
+    }
+    let unit = unit.to_uppercase();
+    let seconds = match unit.strip_suffix('S').unwrap_or(&unit) {
+        "SECOND" => 1,
+        "MINUTE" => 60,
+        "HOUR" => 3600,
+        "DAY" => 24 * 3600,
+        "WEEK" => 7 * 24 * 3600,
+        _ => return Err(invalid()),
+    };
+    n.checked_mul(seconds)
+        .filter(|s| *s <= Duration::max_value().num_seconds())
+        .map(Duration::seconds)
+        .ok_or_else(invalid)
+}
+
+/// The retention of the rows of a table, told by the values of a `DATE` or `TIMESTAMP` column.
+#[derive(Clone, Debug)]
+pub struct DataRetention {
+    pub period: Duration,
+    pub column_id: ColumnId,
+    pub data_type: DataTypeImpl,
+}
+
+impl DataRetention {
+    /// Resolves the retention specified by the table `options`, None if there is none.
+    pub fn try_create(
+        options: &BTreeMap<String, String>,
+        schema: &DataSchema,
+    ) -> Result<Option<DataRetention>> {
+        let period = match options.get(FUSE_OPT_KEY_DATA_RETENTION) {
+            Some(value) => parse_data_retention(value)?,
+            None => return Ok(None),
+        };
+        let column = options
+            .get(FUSE_OPT_KEY_DATA_RETENTION_COLUMN)
+            .ok_or_else(|| {
+                ErrorCode::BadOption(format!(
+                    "{} requires the table option {}",
+                    FUSE_OPT_KEY_DATA_RETENTION, FUSE_OPT_KEY_DATA_RETENTION_COLUMN
+                ))
+            })?;
+        let idx = schema.index_of(column).map_err(|_| {
+            ErrorCode::BadOption(format!(
+                "column {} of {} does not exist",
+                column, FUSE_OPT_KEY_DATA_RETENTION_COLUMN
+            ))
+        })?;
+        let data_type = remove_nullable(schema.field(idx).data_type());
+        if !data_type.data_type_id().is_date_or_date_time() {
+            return Err(ErrorCode::BadOption(format!(
+                "column {} of {} must be of DATE or TIMESTAMP, but it is {}",
+                column,
+                FUSE_OPT_KEY_DATA_RETENTION_COLUMN,
+                data_type.name()
+            )));
+        }
+        Ok(Some(DataRetention {
+            period,
+            column_id: ColumnIds::from_schema(schema).id_of(idx),
+            data_type,
+        }))
+    }
+
+    /// The values of the retention column older than which are expired at `now`, in the
+    /// representation of the column, i.e. days for `DATE`, microseconds for `TIMESTAMP`.
+    pub fn cutoff(&self, now: DateTime<Utc>) -> Option<i64> {
+        let cutoff = now.checked_sub_signed(self.period)?;
+        match self.data_type.data_type_id() {
+            // a day is expired only if all of it is
+            TypeID::Date => Some(cutoff.timestamp().div_euclid(24 * 3600)),
+            _ => Some(cutoff.timestamp() * 1_000_000 + cutoff.timestamp_subsec_micros() as i64),
+        }
+    }
+
+    /// Whether all the rows of the block are expired, by the max value of the retention column.
+    /// The blocks with NULLs in the column are never expired.
+    pub fn is_expired(&self, block: &BlockMeta, cutoff: i64) -> bool {
+        match block.col_stats.get(&self.column_id) {
+            Some(stats) => {
+                stats.null_count == 0 && matches!(stats.max, DataValue::Int64(v) if v < cutoff)
+            }
+            None => false,
+        }
+    }
+}
+
+impl FuseTable {
+    /// Removes the blocks of which all the rows are beyond the retention period of the table,
+    /// see [DataRetention]. The blocks are told by the statistics of the retention column in
+    /// them, thus no data is read or written, only the segments are rewritten.
+    ///
+    /// Returns the number of the removed blocks.
+    pub async fn do_expire(&self, ctx: &Arc<QueryContext>) -> Result<usize> {
+        let schema = self.table_info.schema();
+        let retention = match DataRetention::try_create(self.table_info.options(), &schema)? {
+            Some(retention) => retention,
+            None => return Ok(0),
+        };
+        let cutoff = match retention.cutoff(Utc::now()) {
+            Some(cutoff) => cutoff,
+            None => return Ok(0),
+        };
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(0),
+        };
+
+        let segments = Self::load_segments(ctx.as_ref(), &snapshot.segments).await?;
+        let expired = segments
+            .iter()
+            .flat_map(|s| s.blocks.iter())
+            .filter(|b| retention.is_expired(b, cutoff))
+            .map(|b| b.location.clone())
+            .collect::<HashSet<_>>();
+        if expired.is_empty() {
+            return Ok(0);
+        }
+
+        let mut new_locations = vec![];
+        let result = self
+            .expire_blocks(ctx, &snapshot, &segments, &expired, &mut new_locations)
+            .await;
+        if result.is_err() {
+            // the segments written are not referenced by any snapshot
+            let operator = ctx.get_storage_operator()?;
+            for location in &new_locations {
+                let _ = operator.object(location).delete().await;
+            }
+        }
+        result?;
+
+        tracing::info!(
+            "expire table {}, blocks removed: {}",
+            self.table_info.desc,
+            expired.len()
+        );
+        Ok(expired.len())
+    }
+
+    /// Commits a snapshot, of which the segments no longer contain the `expired` blocks.
+    async fn expire_blocks(
+        &self,
+        ctx: &Arc<QueryContext>,
+        snapshot: &TableSnapshot,
+        segments: &[Arc<SegmentInfo>],
+        expired: &HashSet<Location>,
+        new_locations: &mut Vec<String>,
+    ) -> Result<()> {
+        let segment_locations = self
+            .write_segments_replacing(ctx, snapshot, segments, &[], expired, new_locations)
+            .await?;
+        self.commit_mutation(
+            ctx,
+            Uuid::new_v4(),
+            Some(snapshot),
+            segment_locations,
+            SnapshotOperation::Expire,
+            new_locations,
+        )
+        .await
+    }
+}
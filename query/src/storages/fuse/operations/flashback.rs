@@ -0,0 +1,132 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use chrono::TimeZone;
+use chrono::Utc;
+use common_cache::Cache;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::FlashbackPoint;
+use common_planners::FlashbackTablePlan;
+use uuid::Uuid;
+
+use crate::configs::DATABEND_COMMIT_VERSION;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::write_snapshot;
+use crate::storages::fuse::meta::SnapshotOperation;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::meta::Versioned;
+use crate::storages::fuse::FuseTable;
+use crate::storages::NavigationPoint;
+
+impl FuseTable {
+    /// Rolls the table back to one of the snapshots in its history.
+    ///
+    /// Instead of pointing the table to the historical snapshot directly, a new snapshot that
+    /// shares the segments of the historical one is committed on top of the current snapshot,
+    /// so that the rollback is recorded in the history, and could be rolled back as well.
+    pub async fn do_flashback(
+        &self,
+        ctx: &Arc<QueryContext>,
+        plan: FlashbackTablePlan,
+    ) -> Result<()> {
+        let point = match plan.point {
+            FlashbackPoint::SnapshotId(id) => NavigationPoint::SnapshotID(id),
+            FlashbackPoint::Timestamp(micros) => {
+                NavigationPoint::TimePoint(Utc.timestamp_nanos(micros * 1000))
+            }
+        };
+        let target = self.do_navigate(ctx.as_ref(), &point).await?;
+        let (current, snapshot) = match (
+            self.read_table_snapshot(ctx.as_ref()).await?,
+            target.read_table_snapshot(ctx.as_ref()).await?,
+        ) {
+            (Some(current), Some(snapshot)) => (current, snapshot),
+            _ => {
+                return Err(ErrorCode::TableHistoricalDataNotFound(format!(
+                    "No historical data found of table {} at {:?}",
+                    self.table_info.name, point
+                )));
+            }
+        };
+        if current.snapshot_id == snapshot.snapshot_id {
+            return Ok(());
+        }
+
+        // the snapshot may be still in the history, while its files have been vacuumed
+        let operator = ctx.get_storage_operator()?;
+        let files = snapshot
+            .segments
+            .iter()
+            .map(|(loc, _)| loc)
+            .chain(snapshot.table_statistics_location.iter());
+        for loc in files {
+            if Self::file_size(&operator, loc).await?.is_none() {
+                return Err(ErrorCode::TableHistoricalDataNotFound(format!(
+                    "Historical data of table {} at snapshot {} has been purged, {} not found",
+                    self.table_info.name,
+                    snapshot.snapshot_id.to_simple(),
+                    loc
+                )));
+            }
+        }
+
+        let mut new_snapshot = TableSnapshot::new(
+            Uuid::new_v4(),
+            Some((current.snapshot_id, self.snapshot_format_version())),
+            snapshot.schema.clone(),
+            snapshot.summary.clone(),
+            snapshot.segments.clone(),
+        )
+        .with_origin(DATABEND_COMMIT_VERSION.as_str(), ctx.get_id())
+        .with_operation(
+            SnapshotOperation::Flashback,
+            Self::current_user_identity(ctx.as_ref()),
+        )
+        .with_changes(Some(current.as_ref()));
+        new_snapshot.table_statistics_location = snapshot.table_statistics_location.clone();
+
+        let snapshot_loc = self
+            .meta_location_generator
+            .snapshot_location_from_uuid(&new_snapshot.snapshot_id, TableSnapshot::VERSION)?;
+        write_snapshot(ctx, &operator, &snapshot_loc, &new_snapshot).await?;
+
+        // if the table is modified concurrently, the flashback is abandoned
+        match Self::commit_to_meta_server(
+            ctx.as_ref(),
+            &self.table_info,
+            snapshot_loc.clone(),
+            &new_snapshot.summary,
+        )
+        .await
+        {
+            Ok(_) => {
+                if let Some(snapshot_cache) =
+                    ctx.get_storage_cache_manager().get_table_snapshot_cache()
+                {
+                    let cache = &mut snapshot_cache.write().await;
+                    cache.put(snapshot_loc, Arc::new(new_snapshot));
+                }
+                Ok(())
+            }
+            Err(e) => {
+                let _ = operator.object(&snapshot_loc).delete().await;
+                Err(e)
+            }
+        }
+    }
+}
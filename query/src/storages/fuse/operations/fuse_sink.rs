@@ -32,10 +32,15 @@ use crate::pipelines::new::processors::Processor;
 use crate::sessions::QueryContext;
 use crate::storages::fuse::io::serialize_data_block;
 use crate::storages::fuse::io::TableMetaLocationGenerator;
+use crate::storages::fuse::meta::IndexType;
 use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::meta::SnapshotId;
 use crate::storages::fuse::meta::Statistics;
+use crate::storages::fuse::meta::VirtualBlockMeta;
+use crate::storages::fuse::operations::VirtualColumnsExtractor;
 use crate::storages::fuse::statistics::accumulator::BlockStatistics;
 use crate::storages::fuse::statistics::StatisticsAccumulator;
+use crate::storages::fuse::ColumnIds;
 
 enum State {
     None,
@@ -45,6 +50,7 @@ enum State {
         size: u64,
         meta_data: Box<FileMetaData>,
         block_statistics: BlockStatistics,
+        virtual_block: Option<(Vec<u8>, VirtualBlockMeta)>,
     },
     GenerateSegment,
     SerializedSegment {
@@ -64,9 +70,13 @@ pub struct FuseTableSink {
     data_schema: DataSchemaRef,
     meta_locations: TableMetaLocationGenerator,
     accumulator: StatisticsAccumulator,
+    snapshot_id: SnapshotId,
+    virtual_columns: Option<Arc<VirtualColumnsExtractor>>,
+    string_prefix_length: usize,
 }
 
 impl FuseTableSink {
+    #[allow(clippy::too_many_arguments)]
     pub fn create(
         input: Arc<InputPort>,
         ctx: Arc<QueryContext>,
@@ -74,7 +84,11 @@ impl FuseTableSink {
         data_accessor: Operator,
         data_schema: Arc<DataSchema>,
         meta_locations: TableMetaLocationGenerator,
+        snapshot_id: SnapshotId,
+        virtual_columns: Option<Arc<VirtualColumnsExtractor>>,
+        string_prefix_length: usize,
     ) -> Result<ProcessorPtr> {
+        let column_ids = ColumnIds::from_schema(&data_schema);
         Ok(ProcessorPtr::create(Box::new(FuseTableSink {
             ctx,
             input,
@@ -82,8 +96,11 @@ impl FuseTableSink {
             data_accessor,
             meta_locations,
             state: State::None,
-            accumulator: Default::default(),
+            accumulator: StatisticsAccumulator::created_by(snapshot_id, column_ids),
             num_block_threshold: num_block_threshold as u64,
+            snapshot_id,
+            virtual_columns,
+            string_prefix_length,
         })))
     }
 }
@@ -132,7 +149,15 @@ impl Processor for FuseTableSink {
         match std::mem::replace(&mut self.state, State::None) {
             State::NeedSerialize(data_block) => {
                 let location = self.meta_locations.gen_block_location();
-                let block_statistics = BlockStatistics::from(&data_block, location)?;
+                let block_statistics =
+                    BlockStatistics::from(&data_block, location, self.string_prefix_length)?;
+                let virtual_block = match &self.virtual_columns {
+                    Some(virtual_columns) => {
+                        let location = self.meta_locations.gen_virtual_block_location();
+                        Some(virtual_columns.serialize(&data_block, location)?)
+                    }
+                    None => None,
+                };
 
                 // we need a configuration of block size threshold here
                 let mut data = Vec::with_capacity(100 * 1024 * 1024);
@@ -142,10 +167,15 @@ impl Processor for FuseTableSink {
                     size,
                     block_statistics,
                     meta_data: Box::new(meta_data),
+                    virtual_block,
                 };
             }
             State::GenerateSegment => {
-                let acc = std::mem::take(&mut self.accumulator);
+                let column_ids = self.accumulator.column_ids.clone();
+                let acc = std::mem::replace(
+                    &mut self.accumulator,
+                    StatisticsAccumulator::created_by(self.snapshot_id, column_ids),
+                );
                 let summary = acc.summary(self.data_schema.as_ref())?;
 
                 let segment_info = SegmentInfo::new(acc.blocks_metas, Statistics {
@@ -153,6 +183,8 @@ impl Processor for FuseTableSink {
                     block_count: acc.summary_block_count,
                     uncompressed_byte_size: acc.in_memory_size,
                     compressed_byte_size: acc.file_size,
+                    index_byte_size: acc.index_size,
+                    index_byte_sizes: acc.index_sizes,
                     col_stats: summary,
                 });
 
@@ -177,14 +209,31 @@ impl Processor for FuseTableSink {
                 size,
                 meta_data,
                 block_statistics,
+                virtual_block,
             } => {
                 self.data_accessor
                     .object(&block_statistics.block_file_location)
                     .write(data)
                     .await?;
 
+                let virtual_block = match virtual_block {
+                    Some((data, meta)) => {
+                        self.data_accessor
+                            .object(&meta.location.0)
+                            .write(data)
+                            .await?;
+                        self.accumulator
+                            .add_index_size(IndexType::VirtualColumn, meta.file_size);
+                        Some(meta)
+                    }
+                    None => None,
+                };
+
                 self.accumulator
                     .add_block(size, *meta_data, block_statistics)?;
+                if let Some(last) = self.accumulator.blocks_metas.last_mut() {
+                    last.virtual_block = virtual_block;
+                }
                 if self.accumulator.summary_block_count >= self.num_block_threshold {
                     self.state = State::GenerateSegment;
                 }
@@ -0,0 +1,497 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodSerializer;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::lit;
+use common_planners::Expression;
+use common_planners::MergeMatchedAction;
+use common_planners::MergePlan;
+use uuid::Uuid;
+
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::write_block;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::Location;
+use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::meta::SnapshotId;
+use crate::storages::fuse::meta::SnapshotOperation;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::meta::Versioned;
+use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::statistics::accumulator::BlockStatistics;
+use crate::storages::fuse::statistics::StatisticsAccumulator;
+use crate::storages::fuse::ColumnIds;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::DEFAULT_BLOCK_PER_SEGMENT;
+use crate::storages::fuse::DEFAULT_ROW_PER_BLOCK;
+use crate::storages::fuse::FUSE_OPT_KEY_BLOCK_PER_SEGMENT;
+use crate::storages::fuse::FUSE_OPT_KEY_ROW_PER_BLOCK;
+
+/// The positions of the evaluated condition and values of an action of `MERGE`.
+struct ActionColumns {
+    condition: usize,
+    /// index of the column of the table => position of the new value of it
+    values: HashMap<usize, usize>,
+}
+
+/// Evaluates the conditions and values of the actions, which are tried in order.
+struct ActionsEvaluator {
+    executor: ExpressionExecutor,
+    actions: Vec<ActionColumns>,
+}
+
+impl ActionsEvaluator {
+    fn try_create(
+        ctx: &Arc<QueryContext>,
+        name: &str,
+        input_schema: DataSchemaRef,
+        actions: Vec<(Option<Expression>, Vec<(usize, Expression)>)>,
+    ) -> Result<Self> {
+        let mut exprs = vec![];
+        let mut action_columns = Vec::with_capacity(actions.len());
+        for (condition, values) in actions {
+            let mut columns = ActionColumns {
+                condition: exprs.len(),
+                values: HashMap::with_capacity(values.len()),
+            };
+            exprs.push(condition.unwrap_or_else(|| lit(true)));
+            for (idx, value) in values {
+                columns.values.insert(idx, exprs.len());
+                exprs.push(value);
+            }
+            action_columns.push(columns);
+        }
+
+        let fields = exprs
+            .iter()
+            .map(|expr| expr.to_data_field(&input_schema))
+            .collect::<Result<Vec<_>>>()?;
+        let executor = ExpressionExecutor::try_create(
+            ctx.clone(),
+            name,
+            input_schema,
+            DataSchemaRefExt::create(fields),
+            exprs,
+            false,
+        )?;
+        Ok(ActionsEvaluator {
+            executor,
+            actions: action_columns,
+        })
+    }
+
+    /// Evaluates the actions, returns the evaluated block and the action of each row, which is
+    /// the first one the condition of which holds.
+    fn evaluate(&self, block: &DataBlock) -> Result<(DataBlock, Vec<Option<usize>>)> {
+        let evaluated = self.executor.execute(block)?;
+        let mut assigned = vec![None; block.num_rows()];
+        for (action_idx, action) in self.actions.iter().enumerate() {
+            let predicate = DataBlock::cast_to_nonull_boolean(evaluated.column(action.condition))?;
+            for (row, assigned) in assigned.iter_mut().enumerate() {
+                if assigned.is_none() && predicate.get_bool(row)? {
+                    *assigned = Some(action_idx);
+                }
+            }
+        }
+        Ok((evaluated, assigned))
+    }
+
+    /// Applies the action to the `rows` of the evaluated block, the columns which are not
+    /// assigned by the action are taken from `base`.
+    fn apply(
+        &self,
+        schema: &DataSchemaRef,
+        base: &DataBlock,
+        evaluated: &DataBlock,
+        action_idx: usize,
+        rows: &[u32],
+    ) -> Result<DataBlock> {
+        let action = &self.actions[action_idx];
+        let columns = (0..schema.num_fields())
+            .map(|idx| match action.values.get(&idx) {
+                Some(pos) => evaluated.column(*pos).convert_full_column(),
+                None => base.column(idx).clone(),
+            })
+            .collect::<Vec<_>>();
+        DataBlock::block_take_by_indices(&DataBlock::create(schema.clone(), columns), rows)
+    }
+}
+
+/// Serializes the keys of the rows, the rows having a null key match nothing.
+fn merge_keys(columns: &[ColumnRef], rows: usize) -> Result<Vec<Option<Vec<u8>>>> {
+    let columns = columns
+        .iter()
+        .map(|c| c.convert_full_column())
+        .collect::<Vec<_>>();
+    let column_refs = columns.iter().collect::<Vec<_>>();
+    let keys = HashMethodSerializer::default().build_keys(&column_refs, rows)?;
+    Ok(keys
+        .into_iter()
+        .enumerate()
+        .map(|(row, key)| match columns.iter().any(|c| c.null_at(row)) {
+            true => None,
+            false => Some(key.to_vec()),
+        })
+        .collect())
+}
+
+impl FuseTable {
+    /// Merges the `source` rows into the table, according to `plan`.
+    ///
+    /// The blocks having matched rows are rewritten as a whole, without the deleted rows and
+    /// with the updated ones, the inserted rows are written as new blocks.
+    pub async fn do_merge(
+        &self,
+        ctx: &Arc<QueryContext>,
+        plan: MergePlan,
+        source: Vec<DataBlock>,
+    ) -> Result<()> {
+        let snapshot = self.read_table_snapshot(ctx.as_ref()).await?;
+
+        // the written blocks record the snapshot which commits them
+        let snapshot_id = Uuid::new_v4();
+        let mut new_locations = vec![];
+        let result = match self
+            .write_merged_blocks(
+                ctx,
+                snapshot_id,
+                snapshot.as_ref(),
+                &plan,
+                source,
+                &mut new_locations,
+            )
+            .await
+        {
+            // nothing is changed
+            Ok(None) => Ok(()),
+            Ok(Some(segment_locations)) => {
+                self.commit_mutation(
+                    ctx,
+                    snapshot_id,
+                    snapshot.as_deref(),
+                    segment_locations,
+                    SnapshotOperation::Merge,
+                    &mut new_locations,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        if result.is_err() {
+            // the data written by this merge is not referenced by any snapshot
+            let operator = ctx.get_storage_operator()?;
+            for location in &new_locations {
+                let _ = operator.object(location).delete().await;
+            }
+        }
+        result
+    }
+
+    /// Writes the rewritten and inserted blocks, and the segments of them, returns the segment
+    /// locations of the new snapshot, or `None` if nothing is changed.
+    async fn write_merged_blocks(
+        &self,
+        ctx: &Arc<QueryContext>,
+        snapshot_id: SnapshotId,
+        snapshot: Option<&Arc<TableSnapshot>>,
+        plan: &MergePlan,
+        source: Vec<DataBlock>,
+        new_locations: &mut Vec<String>,
+    ) -> Result<Option<Vec<Location>>> {
+        if source.iter().all(|b| b.num_rows() == 0) {
+            return Ok(None);
+        }
+        let schema = self.table_info.schema();
+        let source_schema = plan.source_schema();
+        let source = DataBlock::concat_blocks(&source)?;
+        let source = DataBlock::create(source_schema.clone(), source.columns().to_vec());
+
+        // key of the source rows => the source rows
+        let key_exprs = plan.on.iter().map(|(_, v)| v.clone()).collect::<Vec<_>>();
+        let key_fields = key_exprs
+            .iter()
+            .map(|expr| expr.to_data_field(&source_schema))
+            .collect::<Result<Vec<_>>>()?;
+        let key_executor = ExpressionExecutor::try_create(
+            ctx.clone(),
+            "merge key executor",
+            source_schema.clone(),
+            DataSchemaRefExt::create(key_fields),
+            key_exprs,
+            false,
+        )?;
+        let source_keys = key_executor.execute(&source)?;
+        let mut source_index: HashMap<Vec<u8>, Vec<u32>> = HashMap::new();
+        for (row, key) in merge_keys(source_keys.columns(), source.num_rows())?
+            .into_iter()
+            .enumerate()
+        {
+            if let Some(key) = key {
+                source_index.entry(key).or_default().push(row as u32);
+            }
+        }
+
+        let operator = ctx.get_storage_operator()?;
+        let mut matched_source = vec![false; source.num_rows()];
+        // location of the block => the meta of the rewritten block, `None` if it is removed
+        let mut mutated: HashMap<String, Option<BlockMeta>> = HashMap::new();
+        if let Some(snapshot) = snapshot {
+            let column_ids = ColumnIds::from_schema(&schema);
+            let mut acc = StatisticsAccumulator::created_by(snapshot_id, column_ids);
+            let mut rewritten = vec![];
+            let candidates = BlockPruner::new(snapshot.clone())
+                .apply(ctx.as_ref(), schema.clone(), &None)
+                .await?;
+            let block_reader = Self::create_block_reader(ctx, schema.clone(), &None)?;
+
+            // the matched rows are evaluated with the columns of the target followed by the
+            // ones of the source
+            let mut fields = schema.fields().clone();
+            fields.extend(source_schema.fields().iter().cloned());
+            let combined_schema = DataSchemaRefExt::create(fields);
+            let mut actions = Vec::with_capacity(plan.matched.len());
+            for action in &plan.matched {
+                actions.push(match action {
+                    MergeMatchedAction::Update {
+                        condition,
+                        update_list,
+                    } => {
+                        let values = update_list
+                            .iter()
+                            .map(|(column, value)| Ok((schema.index_of(column)?, value.clone())))
+                            .collect::<Result<Vec<_>>>()?;
+                        (condition.clone(), values)
+                    }
+                    MergeMatchedAction::Delete { condition } => (condition.clone(), vec![]),
+                });
+            }
+            let evaluator = ActionsEvaluator::try_create(
+                ctx,
+                "merge matched executor",
+                combined_schema.clone(),
+                actions,
+            )?;
+
+            for block_meta in candidates {
+                let blocks = std::slice::from_ref(&block_meta);
+                let (_, parts) = Self::to_partitions(&schema, blocks, None);
+                let block = block_reader.read(parts[0].clone()).await?;
+                let key_columns = plan
+                    .on
+                    .iter()
+                    .map(|(column, _)| Ok(block.try_column_by_name(column)?.clone()))
+                    .collect::<Result<Vec<_>>>()?;
+                let mut target_rows = vec![];
+                let mut source_rows = vec![];
+                for (row, key) in merge_keys(&key_columns, block.num_rows())?
+                    .into_iter()
+                    .enumerate()
+                {
+                    match key.and_then(|key| source_index.get(&key)) {
+                        Some(rows) if rows.len() > 1 => {
+                            return Err(ErrorCode::BadArguments(format!(
+                                "A row of table {} is matched by {} rows of the source of MERGE",
+                                plan.table_name,
+                                rows.len()
+                            )));
+                        }
+                        Some(rows) => {
+                            target_rows.push(row as u32);
+                            source_rows.push(rows[0]);
+                            matched_source[rows[0] as usize] = true;
+                        }
+                        None => {}
+                    }
+                }
+                if target_rows.is_empty() || plan.matched.is_empty() {
+                    continue;
+                }
+
+                let mut columns = DataBlock::block_take_by_indices(&block, &target_rows)?
+                    .columns()
+                    .to_vec();
+                columns.extend(
+                    DataBlock::block_take_by_indices(&source, &source_rows)?
+                        .columns()
+                        .iter()
+                        .cloned(),
+                );
+                let combined = DataBlock::create(combined_schema.clone(), columns);
+                let (evaluated, assigned) = evaluator.evaluate(&combined)?;
+                if assigned.iter().all(|a| a.is_none()) {
+                    continue;
+                }
+
+                // the rows of the block which are neither updated nor deleted are kept
+                let mut changed = vec![false; block.num_rows()];
+                let mut updated_rows = vec![vec![]; plan.matched.len()];
+                for (pair, action) in assigned.iter().enumerate() {
+                    if let Some(action) = action {
+                        changed[target_rows[pair] as usize] = true;
+                        updated_rows[*action].push(pair as u32);
+                    }
+                }
+                let kept = (0..block.num_rows() as u32)
+                    .filter(|row| !changed[*row as usize])
+                    .collect::<Vec<_>>();
+                let mut blocks = vec![DataBlock::block_take_by_indices(&block, &kept)?];
+                for (action_idx, action) in plan.matched.iter().enumerate() {
+                    let rows = &updated_rows[action_idx];
+                    if let MergeMatchedAction::Update { .. } = action {
+                        if !rows.is_empty() {
+                            let updated = evaluator
+                                .apply(&schema, &combined, &evaluated, action_idx, rows)?;
+                            blocks.push(updated);
+                        }
+                    }
+                }
+                let block = DataBlock::concat_blocks(&blocks)?;
+                if block.num_rows() == 0 {
+                    mutated.insert(block_meta.location.0, None);
+                    continue;
+                }
+
+                let block = self.sort_by_cluster_keys(ctx, block)?;
+                let location = self.meta_location_generator.gen_block_location();
+                new_locations.push(location.clone());
+                let block_statistics =
+                    BlockStatistics::from(&block, location.clone(), self.string_prefix_length())?;
+                let arrow_schema = block.schema().to_arrow();
+                let (file_size, meta) =
+                    write_block(&arrow_schema, block, operator.clone(), &location).await?;
+                acc.add_block(file_size, meta, block_statistics)?;
+                rewritten.push(block_meta.location.0);
+            }
+            mutated.extend(
+                rewritten
+                    .into_iter()
+                    .zip(acc.blocks_metas.into_iter().map(Some)),
+            );
+        }
+
+        // the source rows which are not matched are evaluated with the columns of the source
+        let mut inserted = vec![];
+        let not_matched = (0..source.num_rows() as u32)
+            .filter(|row| !matched_source[*row as usize])
+            .collect::<Vec<_>>();
+        if !not_matched.is_empty() && !plan.not_matched.is_empty() {
+            let actions = plan
+                .not_matched
+                .iter()
+                .map(|action| {
+                    let values = action.values.iter().cloned().enumerate().collect();
+                    (action.condition.clone(), values)
+                })
+                .collect();
+            let evaluator = ActionsEvaluator::try_create(
+                ctx,
+                "merge not matched executor",
+                source_schema.clone(),
+                actions,
+            )?;
+            let source = DataBlock::block_take_by_indices(&source, &not_matched)?;
+            let (evaluated, assigned) = evaluator.evaluate(&source)?;
+            let mut inserted_rows = vec![vec![]; plan.not_matched.len()];
+            for (row, action) in assigned.iter().enumerate() {
+                if let Some(action) = action {
+                    inserted_rows[*action].push(row as u32);
+                }
+            }
+            for (action_idx, rows) in inserted_rows.iter().enumerate() {
+                if !rows.is_empty() {
+                    // all the columns are assigned by the insert actions
+                    let block =
+                        evaluator.apply(&schema, &evaluated, &evaluated, action_idx, rows)?;
+                    inserted.push(block);
+                }
+            }
+        }
+
+        if mutated.is_empty() && inserted.is_empty() {
+            return Ok(None);
+        }
+
+        // the inserted blocks come first, as if they were newly appended
+        let mut new_segments = vec![];
+        if !inserted.is_empty() {
+            let row_per_block = self.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK);
+            let block_per_segment =
+                self.get_option(FUSE_OPT_KEY_BLOCK_PER_SEGMENT, DEFAULT_BLOCK_PER_SEGMENT);
+            let column_ids = ColumnIds::from_schema(&schema);
+            let mut acc = StatisticsAccumulator::created_by(snapshot_id, column_ids);
+            let block = DataBlock::concat_blocks(&inserted)?;
+            let block = self.sort_by_cluster_keys(ctx, block)?;
+            for block in DataBlock::split_block_by_size(&block, row_per_block)? {
+                let location = self.meta_location_generator.gen_block_location();
+                new_locations.push(location.clone());
+                let block_statistics =
+                    BlockStatistics::from(&block, location.clone(), self.string_prefix_length())?;
+                let arrow_schema = block.schema().to_arrow();
+                let (file_size, meta) =
+                    write_block(&arrow_schema, block, operator.clone(), &location).await?;
+                acc.add_block(file_size, meta, block_statistics)?;
+            }
+            for blocks in acc.blocks_metas.chunks(block_per_segment) {
+                new_segments.push(Self::segment_of_blocks(&schema, blocks.to_vec())?);
+            }
+        }
+
+        let mut segment_locations = vec![];
+        let mut kept_segments = vec![];
+        if let Some(snapshot) = snapshot {
+            let segments = Self::load_segments(ctx.as_ref(), &snapshot.segments).await?;
+            for (idx, segment) in segments.iter().enumerate() {
+                if !segment
+                    .blocks
+                    .iter()
+                    .any(|b| mutated.contains_key(&b.location.0))
+                {
+                    kept_segments.push(snapshot.segments[idx].clone());
+                    continue;
+                }
+                let blocks = segment
+                    .blocks
+                    .iter()
+                    .filter_map(|b| match mutated.get(&b.location.0) {
+                        Some(rewritten) => rewritten.clone(),
+                        None => Some(b.clone()),
+                    })
+                    .collect::<Vec<_>>();
+                if !blocks.is_empty() {
+                    new_segments.push(Self::segment_of_blocks(&schema, blocks)?);
+                }
+            }
+        }
+        for segment in &new_segments {
+            let location = self.meta_location_generator.gen_segment_info_location();
+            new_locations.push(location.clone());
+            let bytes = serde_json::to_vec(segment)?;
+            operator.object(&location).write(bytes).await?;
+            segment_locations.push((location, SegmentInfo::VERSION));
+        }
+        segment_locations.extend(kept_segments);
+        Ok(Some(segment_locations))
+    }
+}
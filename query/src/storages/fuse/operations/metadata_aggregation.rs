@@ -0,0 +1,108 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::Extras;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::statistics::reduce_block_stats;
+use crate::storages::fuse::ColumnIds;
+use crate::storages::fuse::FuseTable;
+
+/// An aggregation which may be answered by the statistics of the blocks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MetadataAggregation {
+    /// `count(*)`
+    Count,
+    /// `min` of the column at the index of the schema
+    Min(usize),
+    /// `max` of the column at the index of the schema
+    Max(usize),
+}
+
+impl FuseTable {
+    /// Answers the aggregations over the rows matching all the `filters` by the statistics of
+    /// the blocks, without reading the blocks. None if any of them is not known exactly, e.g.
+    /// some blocks are matched partially, or the statistics of the blocks are stale for rows
+    /// are deleted from them.
+    ///
+    /// Filtering by the cluster key works the best, by which few blocks are matched partially.
+    pub async fn aggregate_by_metadata(
+        &self,
+        ctx: &Arc<QueryContext>,
+        filters: &[Expression],
+        aggregations: &[MetadataAggregation],
+    ) -> Result<Option<Vec<DataValue>>> {
+        let schema = self.table_info.schema();
+        let blocks = match self.read_table_snapshot(ctx.as_ref()).await? {
+            None => vec![],
+            Some(snapshot) => {
+                let push_downs = Some(Extras {
+                    filters: filters.to_vec(),
+                    ..Extras::default()
+                });
+                BlockPruner::new(snapshot)
+                    .apply(ctx.as_ref(), schema.clone(), &push_downs)
+                    .await?
+            }
+        };
+
+        // the statistics of the blocks with deletion vectors cover the deleted rows
+        if blocks.iter().any(|b| b.deletion_vector.is_some()) {
+            return Ok(None);
+        }
+        for filter in filters {
+            if !BlockPruner::all_rows_matched(ctx.as_ref(), schema.clone(), filter, &blocks)? {
+                return Ok(None);
+            }
+        }
+
+        let column_ids = ColumnIds::from_schema(&schema);
+        let stats = reduce_block_stats(
+            &blocks.iter().map(|b| &b.col_stats).collect::<Vec<_>>(),
+            &schema,
+        )?;
+        let mut values = Vec::with_capacity(aggregations.len());
+        for aggregation in aggregations {
+            let (index, is_min) = match aggregation {
+                MetadataAggregation::Count => {
+                    values.push(DataValue::UInt64(blocks.iter().map(|b| b.row_count).sum()));
+                    continue;
+                }
+                MetadataAggregation::Min(index) => (*index, true),
+                MetadataAggregation::Max(index) => (*index, false),
+            };
+            // the blocks written before the column is added keep no statistics of it
+            let id = column_ids.id_of(index);
+            if !blocks.iter().all(|b| b.col_stats.contains_key(&id)) {
+                return Ok(None);
+            }
+            let value = match stats.get(&id) {
+                // the truncated min and max are bounds of the values only
+                Some(s) if s.truncated => return Ok(None),
+                Some(s) if is_min => s.min.clone(),
+                Some(s) => s.max.clone(),
+                None => DataValue::Null,
+            };
+            values.push(value);
+        }
+        Ok(Some(values))
+    }
+}
@@ -12,15 +12,47 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod aggregating_index;
+mod alter_column;
+mod analyze;
 mod append;
+mod attach;
+mod backup;
+mod changes;
+mod clone;
 mod commit;
+mod compact;
+mod delete;
+mod expire;
+mod flashback;
 mod fuse_sink;
+mod merge;
+mod metadata_aggregation;
+mod navigate;
 mod operation_log;
 mod optimize;
 mod read;
 mod read_partitions;
+mod recluster;
+mod snapshot_diff;
 mod truncate;
+mod update;
+mod vacuum;
+mod verify;
+mod virtual_column;
 
+pub use changes::TableChanges;
+pub use compact::SegmentCompactionPolicy;
+pub use expire::parse_data_retention;
+pub use expire::DataRetention;
 pub use fuse_sink::FuseTableSink;
+pub use metadata_aggregation::MetadataAggregation;
 pub use operation_log::AppendOperationLogEntry;
 pub use operation_log::TableOperationLog;
+pub use recluster::ReclusterPolicy;
+pub use snapshot_diff::DiffChange;
+pub use snapshot_diff::DiffObject;
+pub use snapshot_diff::SnapshotDiffEntry;
+pub use verify::VerifyCategory;
+pub use verify::VerifyProblem;
+pub use virtual_column::VirtualColumnsExtractor;
@@ -0,0 +1,179 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Utc;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use uuid::Uuid;
+
+use crate::sessions::QueryContext;
+use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::meta::TableSnapshotLite;
+use crate::storages::fuse::FuseTable;
+use crate::storages::NavigationPoint;
+
+impl FuseTable {
+    pub async fn do_navigate(
+        &self,
+        ctx: &QueryContext,
+        point: &NavigationPoint,
+    ) -> Result<Arc<FuseTable>> {
+        match point {
+            NavigationPoint::SnapshotID(snapshot_id) => {
+                self.navigate_to_snapshot(ctx, snapshot_id.as_str()).await
+            }
+            NavigationPoint::TimePoint(time_point) => {
+                self.navigate_to_time_point(ctx, *time_point).await
+            }
+        }
+    }
+
+    pub async fn navigate_to_snapshot(
+        &self,
+        ctx: &QueryContext,
+        snapshot_id: &str,
+    ) -> Result<Arc<FuseTable>> {
+        let id = Uuid::parse_str(snapshot_id).map_err(|e| {
+            ErrorCode::BadArguments(format!("invalid snapshot id {}: {}", snapshot_id, e))
+        })?;
+        match self.find_snapshot(ctx, |s| s.snapshot_id == id).await? {
+            Some((s, loc)) => self.load_table_at(ctx, loc, s.format_version).await,
+            None => Err(ErrorCode::TableHistoricalDataNotFound(format!(
+                "No historical data found of table {} at snapshot {}",
+                self.table_info.name, snapshot_id
+            ))),
+        }
+    }
+
+    /// Navigates to the latest snapshot which is created no later than `time_point`
+    pub async fn navigate_to_time_point(
+        &self,
+        ctx: &QueryContext,
+        time_point: DateTime<Utc>,
+    ) -> Result<Arc<FuseTable>> {
+        // The history is walked from the latest to the earliest, and so are the timestamps.
+        // Snapshots converted from legacy formats have no timestamps, they could only
+        // appear at the tail of the history, and are treated as the earliest ones.
+        let found = self
+            .find_snapshot(ctx, |s| match s.timestamp {
+                Some(ts) => ts <= time_point,
+                None => true,
+            })
+            .await?;
+
+        match found {
+            Some((s, loc)) if s.timestamp.is_some() => {
+                self.load_table_at(ctx, loc, s.format_version).await
+            }
+            _ => Err(ErrorCode::TableHistoricalDataNotFound(format!(
+                "No historical data found of table {} at time point {}",
+                self.table_info.name, time_point
+            ))),
+        }
+    }
+
+    /// Loads the history of snapshots, from the latest to the earliest, paired with their
+    /// format versions.
+    pub(crate) async fn snapshot_log(
+        &self,
+        ctx: &QueryContext,
+    ) -> Result<Vec<(Arc<TableSnapshot>, u64)>> {
+        let latest_version = self.snapshot_format_version();
+        let reader = MetaReaders::table_snapshot_reader(ctx);
+        let snapshots = reader
+            .read_snapshot_history(
+                self.snapshot_loc(),
+                latest_version,
+                self.meta_location_generator().clone(),
+            )
+            .await?;
+
+        let mut version = latest_version;
+        Ok(snapshots
+            .into_iter()
+            .map(|s| {
+                let current = version;
+                if let Some((_, prev_version)) = s.prev_snapshot_id {
+                    version = prev_version;
+                }
+                (s, current)
+            })
+            .collect())
+    }
+
+    /// Walks the history of snapshots from the latest to the earliest, and stops at the first
+    /// snapshot satisfying `pred`, which is returned along with its location. Only the
+    /// essentials of the visited snapshots are decoded.
+    async fn find_snapshot<P>(
+        &self,
+        ctx: &QueryContext,
+        pred: P,
+    ) -> Result<Option<(Arc<TableSnapshotLite>, String)>>
+    where
+        P: Fn(&TableSnapshotLite) -> bool,
+    {
+        let reader = MetaReaders::table_snapshot_lite_reader(ctx);
+        let mut next = self
+            .snapshot_loc()
+            .map(|loc| (loc, self.snapshot_format_version()));
+        while let Some((loc, ver)) = next.take() {
+            let lite = match reader.read(loc.as_str(), None, ver).await {
+                Ok(s) => s,
+                Err(e) if e.code() == ErrorCode::storage_not_found_code() => break,
+                Err(e) => return Err(e),
+            };
+            if pred(lite.as_ref()) {
+                return Ok(Some((lite, loc)));
+            }
+            if let Some((id, v)) = lite.prev_snapshot_id {
+                next = Some((
+                    self.meta_location_generator
+                        .snapshot_location_from_uuid(&id, v)?,
+                    v,
+                ));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Loads the table as of the snapshot at `snapshot_loc`, with the schema of the snapshot,
+    /// which may differ from the current one if the table has been altered since then.
+    async fn load_table_at(
+        &self,
+        ctx: &QueryContext,
+        snapshot_loc: String,
+        version: u64,
+    ) -> Result<Arc<FuseTable>> {
+        let snapshot = MetaReaders::table_snapshot_reader(ctx)
+            .read(snapshot_loc.as_str(), None, version)
+            .await?;
+        let mut table_info = self.table_info.clone();
+        table_info.meta.schema = Arc::new(snapshot.schema.clone());
+        table_info
+            .meta
+            .options
+            .insert(OPT_KEY_SNAPSHOT_LOCATION.to_owned(), snapshot_loc);
+        Ok(Arc::new(FuseTable {
+            table_info,
+            meta_location_generator: self.meta_location_generator.clone(),
+            order_keys: self.order_keys.clone(),
+        }))
+    }
+}
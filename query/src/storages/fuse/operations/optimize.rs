@@ -14,18 +14,27 @@
 //
 
 use std::collections::HashSet;
+use std::future::Future;
 use std::sync::Arc;
 
 use common_cache::Cache;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_tracing::tracing;
+use futures::AsyncReadExt;
+use futures::StreamExt;
+use futures::TryStreamExt;
 use opendal::Operator;
 
 use crate::sessions::QueryContext;
-use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
 use crate::storages::fuse::io::MetaReaders;
-use crate::storages::fuse::meta::Location;
+use crate::storages::fuse::meta::TableSnapshotLite;
 use crate::storages::fuse::FuseTable;
-use crate::storages::Table;
+
+/// Number of the snapshots purged in a batch, the progress is checkpointed after each batch
+const DEFAULT_PURGE_BATCH_SIZE: usize = 256;
+const MAX_CONCURRENT_PURGE_LOADING: usize = 10;
+const MAX_CONCURRENT_PURGE_DELETION: usize = 16;
 
 impl FuseTable {
     pub async fn do_optimize(
@@ -33,108 +42,189 @@ impl FuseTable {
         ctx: Arc<QueryContext>,
         keep_last_snapshot: bool,
     ) -> Result<()> {
-        let accessor = ctx.get_storage_operator()?;
-        let tbl_info = self.get_table_info();
-        let snapshot_loc = tbl_info.meta.options.get(OPT_KEY_SNAPSHOT_LOCATION);
-        let format_version = self.snapshot_format_version();
-        let reader = MetaReaders::table_snapshot_reader(ctx.as_ref());
-
-        let mut snapshots = reader
-            .read_snapshot_history(
-                snapshot_loc,
-                format_version,
-                self.meta_location_generator.clone(),
+        self.do_purge(ctx.as_ref(), keep_last_snapshot, DEFAULT_PURGE_BATCH_SIZE)
+            .await
+    }
+
+    /// Removes the snapshots of the table, except for the current one if `keep_last_snapshot`,
+    /// together with the segments and blocks which are no longer referenced.
+    ///
+    /// Only the essentials of the snapshots are kept while walking through the history. The
+    /// expired snapshots are purged in batches of `batch_size`, from the earliest to the
+    /// latest, and the files of a batch are removed in parallel.
+    ///
+    /// Before each batch, the snapshots still to be purged are checkpointed: once a snapshot
+    /// is removed, the ones before it are no longer reachable from the latest snapshot, and
+    /// the checkpoint is how an interrupted purge is resumed by the next one.
+    pub async fn do_purge(
+        &self,
+        ctx: &QueryContext,
+        keep_last_snapshot: bool,
+        batch_size: usize,
+    ) -> Result<()> {
+        let operator = ctx.get_storage_operator()?;
+        let locs = self.meta_location_generator();
+        let checkpoint_loc = locs.purge_checkpoint_location();
+        let checkpoint = Self::read_purge_checkpoint(&operator, &checkpoint_loc).await?;
+
+        // the walk stops at the latest snapshot left by the interrupted purge, if any
+        let (mut pending, resumed) = MetaReaders::table_snapshot_lite_reader(ctx)
+            .read_snapshot_lites(
+                self.snapshot_loc(),
+                self.snapshot_format_version(),
+                locs,
+                checkpoint.first().map(|s| &s.snapshot_id),
             )
             .await?;
-
-        let min_history_len = if !keep_last_snapshot { 0 } else { 1 };
-
-        // short cut
-        if snapshots.len() <= min_history_len {
-            return Ok(());
+        if resumed {
+            tracing::info!(
+                "resuming the purge of table {}, {} snapshots left",
+                self.table_info.desc,
+                checkpoint.len()
+            );
+            pending.extend(checkpoint);
+        } else if !checkpoint.is_empty() {
+            tracing::warn!(
+                "the purge checkpoint of table {} is not reachable from its history, ignored",
+                self.table_info.desc
+            );
         }
 
-        let current_segments: HashSet<&Location>;
-        let current_snapshot;
-        if !keep_last_snapshot {
-            // if truncate_all requested, gc root contains nothing;
-            current_segments = HashSet::new();
-        } else {
-            current_snapshot = snapshots.remove(0);
-            current_segments = HashSet::from_iter(&current_snapshot.segments);
+        let mut retained_segments = HashSet::new();
+        if keep_last_snapshot {
+            if let Some(current) = self.read_table_snapshot(ctx).await? {
+                pending.retain(|s| s.snapshot_id != current.snapshot_id);
+                retained_segments.extend(current.segments.iter().cloned());
+            }
         }
+        if pending.is_empty() {
+            return Self::remove_file(&operator, &checkpoint_loc).await;
+        }
+        let retained_blocks = self.blocks_of_segments(ctx, &retained_segments).await?;
+
+        // files shared with the clones of the table, or the table it is cloned from, are kept
+        let removable = self.removable_files(ctx).await?;
+
+        let snapshot_reader = &MetaReaders::table_snapshot_reader(ctx);
+        let segment_reader = &MetaReaders::segment_info_reader(ctx);
+        let mut purged_segments = HashSet::new();
+        while !pending.is_empty() {
+            let bytes = serde_json::to_vec(&pending)?;
+            operator.object(&checkpoint_loc).write(bytes).await?;
+
+            // the earliest snapshots, the ones removed by the interrupted purge are skipped
+            let batch = pending.split_off(pending.len().saturating_sub(batch_size.max(1)));
+            let snapshot_locs = batch
+                .iter()
+                .map(|s| locs.snapshot_location_from_uuid(&s.snapshot_id, s.format_version))
+                .collect::<Result<Vec<_>>>()?;
+            let snapshots = Self::load_existing(
+                batch
+                    .iter()
+                    .zip(&snapshot_locs)
+                    .map(move |(s, loc)| snapshot_reader.read(loc, None, s.format_version)),
+            )
+            .await?;
 
-        let prevs = snapshots.iter().fold(HashSet::new(), |mut acc, s| {
-            acc.extend(&s.segments);
-            acc
-        });
-
-        // segments which no longer need to be kept
-        let seg_delta = prevs.difference(&current_segments).collect::<Vec<_>>();
+            let mut segments = vec![];
+            for loc in snapshots.iter().flat_map(|s| s.segments.iter()) {
+                if !retained_segments.contains(loc)
+                    && removable.contains(&loc.0)
+                    && purged_segments.insert(loc.clone())
+                {
+                    segments.push(loc.clone());
+                }
+            }
 
-        // blocks to be removed
-        let prev_blocks: HashSet<String> = self
-            .blocks_of(ctx.clone(), seg_delta.iter().map(|i| **i))
-            .await?;
-        let current_blocks: HashSet<String> = self
-            .blocks_of(ctx.clone(), current_segments.iter().copied())
+            let segment_infos = Self::load_existing(
+                segments
+                    .iter()
+                    .map(move |(loc, ver)| segment_reader.read(loc, None, *ver)),
+            )
             .await?;
-        let block_delta = prev_blocks.difference(&current_blocks);
+            let blocks: HashSet<&String> = segment_infos
+                .iter()
+                .flat_map(|s| s.blocks.iter().flat_map(|b| b.file_locations()))
+                .filter(|l| !retained_blocks.contains(*l) && removable.contains(l))
+                .collect();
 
-        // NOTE: the following actions are NOT transactional yet
+            // Blocks go first, then the segments, and the snapshots are the last ones, the
+            // files of a batch are never removed before the files they reference.
 
-        // 1. remove blocks
-        for x in block_delta {
-            self.remove_location(accessor.clone(), x).await?;
-        }
+            // 1. remove blocks
+            Self::remove_files(&operator, blocks.into_iter()).await?;
 
-        // 2. remove the segments
-        for (x, _v) in seg_delta {
-            self.remove_location(accessor.clone(), x.as_str()).await?;
+            // 2. remove the segments
+            Self::remove_files(&operator, segments.iter().map(|(l, _)| l)).await?;
             if let Some(c) = ctx.get_storage_cache_manager().get_table_segment_cache() {
                 let cache = &mut *c.write().await;
-                cache.pop(x.as_str());
+                for (loc, _) in &segments {
+                    cache.pop(loc.as_str());
+                }
             }
-        }
 
-        let locs = self.meta_location_generator();
-        // 3. remove the snapshots
-        for s in snapshots.iter().rev() {
-            let loc = locs.snapshot_location_from_uuid(&s.snapshot_id, s.format_version())?;
-            self.remove_location(accessor.clone(), loc.as_str()).await?;
+            // 3. remove the snapshots
+            let snapshot_locs = snapshot_locs
+                .into_iter()
+                .filter(|l| removable.contains(l))
+                .collect::<Vec<_>>();
+            Self::remove_files(&operator, snapshot_locs.iter()).await?;
             if let Some(c) = ctx.get_storage_cache_manager().get_table_snapshot_cache() {
                 let cache = &mut *c.write().await;
-                cache.pop(loc.as_str());
+                for loc in &snapshot_locs {
+                    cache.pop(loc.as_str());
+                }
             }
         }
 
-        Ok(())
+        Self::remove_file(&operator, &checkpoint_loc).await
     }
 
-    async fn blocks_of(
-        &self,
-        ctx: Arc<QueryContext>,
-        //locations: impl Iterator<Item = impl AsRef<Location>>,
-        locations: impl Iterator<Item = &Location>,
-    ) -> Result<HashSet<String>> {
-        let mut result = HashSet::new();
-        let reader = MetaReaders::segment_info_reader(ctx.as_ref());
-        for l in locations {
-            //let (x, ver) = l.as_ref();
-            let (x, ver) = l;
-            let res = reader.read(x, None, *ver).await?;
-            for block_meta in &res.blocks {
-                result.insert(block_meta.location.0.clone());
+    /// Loads the snapshots still to be purged by an interrupted purge, from the latest to the
+    /// earliest.
+    async fn read_purge_checkpoint(
+        operator: &Operator,
+        location: &str,
+    ) -> Result<Vec<TableSnapshotLite>> {
+        if Self::file_size(operator, location).await?.is_none() {
+            return Ok(vec![]);
+        }
+        let mut reader = operator.object(location).reader().await?;
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).await?;
+        match serde_json::from_slice(&bytes) {
+            Ok(pending) => Ok(pending),
+            Err(e) => {
+                tracing::warn!("malformed purge checkpoint {} ignored: {}", location, e);
+                Ok(vec![])
             }
         }
-        Ok(result)
     }
 
-    async fn remove_location(
-        &self,
-        data_accessor: Operator,
-        location: impl AsRef<str>,
+    /// Loads the meta files concurrently, the ones which have been removed are skipped.
+    async fn load_existing<T>(
+        loadings: impl Iterator<Item = impl Future<Output = Result<T>>>,
+    ) -> Result<Vec<T>> {
+        let mut loadings = futures::stream::iter(loadings).buffered(MAX_CONCURRENT_PURGE_LOADING);
+        let mut loaded = vec![];
+        while let Some(res) = loadings.next().await {
+            match res {
+                Ok(v) => loaded.push(v),
+                Err(e) if e.code() == ErrorCode::storage_not_found_code() => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(loaded)
+    }
+
+    async fn remove_files(
+        operator: &Operator,
+        locations: impl Iterator<Item = &String>,
     ) -> Result<()> {
-        Ok(data_accessor.object(location.as_ref()).delete().await?)
+        futures::stream::iter(locations)
+            .map(|loc| Self::remove_file(operator, loc))
+            .buffer_unordered(MAX_CONCURRENT_PURGE_DELETION)
+            .try_collect::<()>()
+            .await
     }
 }
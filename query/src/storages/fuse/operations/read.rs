@@ -13,11 +13,14 @@
 //  limitations under the License.
 //
 
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use common_base::Progress;
 use common_base::ProgressValues;
 use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::Extras;
@@ -35,6 +38,7 @@ use crate::pipelines::new::NewPipeline;
 use crate::pipelines::new::SourcePipeBuilder;
 use crate::sessions::QueryContext;
 use crate::storages::fuse::io::BlockReader;
+use crate::storages::fuse::meta::DeletionVector;
 use crate::storages::fuse::operations::read::State::Generated;
 use crate::storages::fuse::FuseTable;
 
@@ -45,7 +49,16 @@ impl FuseTable {
         ctx: Arc<QueryContext>,
         push_downs: &Option<Extras>,
     ) -> Result<SendableDataBlockStream> {
-        let block_reader = self.create_block_reader(&ctx, push_downs)?;
+        Self::read_blocks(ctx, self.table_info.schema(), push_downs)
+    }
+
+    /// Reads the blocks of the partitions that have been set to `ctx`, according to `schema`.
+    pub fn read_blocks(
+        ctx: Arc<QueryContext>,
+        schema: DataSchemaRef,
+        push_downs: &Option<Extras>,
+    ) -> Result<SendableDataBlockStream> {
+        let block_reader = Self::create_block_reader(&ctx, schema, push_downs)?;
 
         let iter = std::iter::from_fn(move || match ctx.clone().try_get_partitions(1) {
             Err(_) => None,
@@ -66,9 +79,9 @@ impl FuseTable {
         Ok(Box::pin(stream))
     }
 
-    fn create_block_reader(
-        &self,
+    pub(crate) fn create_block_reader(
         ctx: &Arc<QueryContext>,
+        table_schema: DataSchemaRef,
         push_downs: &Option<Extras>,
     ) -> Result<Arc<BlockReader>> {
         let projection = if let Some(Extras {
@@ -78,14 +91,14 @@ impl FuseTable {
         {
             prj.clone()
         } else {
-            (0..self.table_info.schema().fields().len())
+            (0..table_schema.fields().len())
                 .into_iter()
                 .collect::<Vec<usize>>()
         };
 
         let operator = ctx.get_storage_operator()?;
-        let table_schema = self.table_info.schema();
-        BlockReader::create(operator, table_schema, projection)
+        let disk_cache = ctx.get_storage_cache_manager().get_block_disk_cache();
+        BlockReader::create(operator, table_schema, projection, disk_cache)
     }
 
     #[inline]
@@ -95,19 +108,39 @@ impl FuseTable {
         plan: &ReadDataSourcePlan,
         pipeline: &mut NewPipeline,
     ) -> Result<()> {
-        let block_reader = self.create_block_reader(&ctx, &plan.push_downs)?;
+        Self::read_blocks2(ctx, self.table_info.schema(), plan, pipeline)
+    }
+
+    /// The new processor framework version of [FuseTable::read_blocks].
+    pub fn read_blocks2(
+        ctx: Arc<QueryContext>,
+        schema: DataSchemaRef,
+        plan: &ReadDataSourcePlan,
+        pipeline: &mut NewPipeline,
+    ) -> Result<()> {
+        let block_reader = Self::create_block_reader(&ctx, schema, &plan.push_downs)?;
 
         let parts_len = plan.parts.len();
         let max_threads = ctx.get_settings().get_max_threads()? as usize;
         let max_threads = std::cmp::min(parts_len, max_threads);
 
+        // The sources stop taking the partitions once the limit is reached by all of them.
+        let scan_limit = Self::scan_limit(&plan.push_downs);
+        let scanned_rows = Arc::new(AtomicUsize::new(0));
+
         let mut source_builder = SourcePipeBuilder::create();
 
         for _index in 0..std::cmp::max(1, max_threads) {
             let output = OutputPort::create();
             source_builder.add_source(
                 output.clone(),
-                FuseTableSource::create(ctx.clone(), output, block_reader.clone())?,
+                FuseTableSource::create(
+                    ctx.clone(),
+                    output,
+                    block_reader.clone(),
+                    scan_limit,
+                    scanned_rows.clone(),
+                )?,
             );
         }
 
@@ -118,7 +151,7 @@ impl FuseTable {
 
 enum State {
     ReadData(PartInfoPtr),
-    Deserialize(PartInfoPtr, Vec<Vec<u8>>),
+    Deserialize(PartInfoPtr, Vec<Vec<u8>>, Option<DeletionVector>),
     Generated(Option<PartInfoPtr>, DataBlock),
     Finish,
 }
@@ -129,6 +162,8 @@ struct FuseTableSource {
     scan_progress: Arc<Progress>,
     block_reader: Arc<BlockReader>,
     output: Arc<OutputPort>,
+    scan_limit: Option<usize>,
+    scanned_rows: Arc<AtomicUsize>,
 }
 
 impl FuseTableSource {
@@ -136,6 +171,8 @@ impl FuseTableSource {
         ctx: Arc<QueryContext>,
         output: Arc<OutputPort>,
         block_reader: Arc<BlockReader>,
+        scan_limit: Option<usize>,
+        scanned_rows: Arc<AtomicUsize>,
     ) -> Result<ProcessorPtr> {
         let scan_progress = ctx.get_scan_progress();
         let mut partitions = ctx.try_get_partitions(1)?;
@@ -145,6 +182,8 @@ impl FuseTableSource {
                 output,
                 block_reader,
                 scan_progress,
+                scan_limit,
+                scanned_rows,
                 state: State::Finish,
             }))),
             false => Ok(ProcessorPtr::create(Box::new(FuseTableSource {
@@ -152,10 +191,18 @@ impl FuseTableSource {
                 output,
                 block_reader,
                 scan_progress,
+                scan_limit,
+                scanned_rows,
                 state: State::ReadData(partitions.remove(0)),
             }))),
         }
     }
+
+    // Whether the sources have read enough rows for the limit.
+    fn limit_reached(&self, rows: usize) -> bool {
+        let scanned_rows = self.scanned_rows.fetch_add(rows, Ordering::Relaxed) + rows;
+        matches!(self.scan_limit, Some(limit) if scanned_rows >= limit)
+    }
 }
 
 #[async_trait::async_trait]
@@ -193,16 +240,21 @@ impl Processor for FuseTableSource {
         match self.state {
             State::Finish => Ok(Event::Finished),
             State::ReadData(_) => Ok(Event::Async),
-            State::Deserialize(_, _) => Ok(Event::Sync),
+            State::Deserialize(_, _, _) => Ok(Event::Sync),
             State::Generated(_, _) => Err(ErrorCode::LogicalError("It's a bug.")),
         }
     }
 
     fn process(&mut self) -> Result<()> {
         match std::mem::replace(&mut self.state, State::Finish) {
-            State::Deserialize(part, chunks) => {
+            State::Deserialize(part, chunks, deletion_vector) => {
                 let data_block = self.block_reader.deserialize(part, chunks)?;
-                let mut partitions = self.ctx.try_get_partitions(1)?;
+                let data_block =
+                    BlockReader::apply_deletion_vector(data_block, deletion_vector.as_ref())?;
+                let mut partitions = match self.limit_reached(data_block.num_rows()) {
+                    true => vec![],
+                    false => self.ctx.try_get_partitions(1)?,
+                };
 
                 let progress_values = ProgressValues {
                     rows: data_block.num_rows(),
@@ -224,7 +276,8 @@ impl Processor for FuseTableSource {
         match std::mem::replace(&mut self.state, State::Finish) {
             State::ReadData(part) => {
                 let chunks = self.block_reader.read_columns_data(part.clone()).await?;
-                self.state = State::Deserialize(part, chunks);
+                let deletion_vector = self.block_reader.read_deletion_vector(&part).await?;
+                self.state = State::Deserialize(part, chunks, deletion_vector);
                 Ok(())
             }
             _ => Err(ErrorCode::LogicalError("It's a bug.")),
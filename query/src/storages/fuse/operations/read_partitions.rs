@@ -16,6 +16,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use common_datavalues::DataSchema;
 use common_exception::Result;
 use common_planners::Extras;
 use common_planners::PartInfoPtr;
@@ -28,6 +29,7 @@ use crate::storages::fuse::fuse_part::FusePartInfo;
 use crate::storages::fuse::meta::BlockMeta;
 use crate::storages::fuse::meta::TableSnapshot;
 use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::ColumnIds;
 use crate::storages::fuse::FuseTable;
 
 impl FuseTable {
@@ -51,7 +53,10 @@ impl FuseTable {
                 let partitions_scanned = block_metas.len();
                 let partitions_total = snapshot.summary.block_count as usize;
 
-                let (mut statistics, parts) = Self::to_partitions(&block_metas, push_downs);
+                // the virtual columns are read from the virtual blocks instead
+                let (push_downs, virtual_columns) = self.split_virtual_columns(push_downs)?;
+                let (mut statistics, parts) = Self::to_partitions(schema, &block_metas, push_downs);
+                let parts = Self::virtual_columns_parts(&block_metas, parts, &virtual_columns)?;
 
                 // Update planner statistics.
                 statistics.partitions_total = partitions_total;
@@ -71,21 +76,23 @@ impl FuseTable {
         }
     }
 
+    /// Turns the blocks into the partitions to be read by the table of `schema`, by which the
+    /// ids of the columns kept in the blocks are mapped to the indices of the schema.
     pub fn to_partitions(
+        schema: &DataSchema,
         blocks_metas: &[BlockMeta],
         push_down: Option<Extras>,
     ) -> (Statistics, Partitions) {
-        let limit = push_down
-            .as_ref()
-            .filter(|p| p.order_by.is_empty())
-            .and_then(|p| p.limit)
-            .unwrap_or(usize::MAX);
+        let limit = Self::scan_limit(&push_down).unwrap_or(usize::MAX);
 
+        let column_ids = ColumnIds::from_schema(schema);
         let (mut statistics, partitions) = match &push_down {
-            None => Self::all_columns_partitions(blocks_metas, limit),
+            None => Self::all_columns_partitions(&column_ids, blocks_metas, limit),
             Some(extras) => match &extras.projection {
-                None => Self::all_columns_partitions(blocks_metas, limit),
-                Some(projection) => Self::projection_partitions(blocks_metas, projection, limit),
+                None => Self::all_columns_partitions(&column_ids, blocks_metas, limit),
+                Some(projection) => {
+                    Self::projection_partitions(&column_ids, blocks_metas, projection, limit)
+                }
             },
         };
 
@@ -93,15 +100,28 @@ impl FuseTable {
         (statistics, partitions)
     }
 
+    /// The number of the rows after which the blocks need not be read, which is only known if
+    /// the rows read are neither filtered, sampled nor sorted before the limit is applied.
+    pub(crate) fn scan_limit(push_downs: &Option<Extras>) -> Option<usize> {
+        push_downs
+            .as_ref()
+            .filter(|p| p.order_by.is_empty() && p.filters.is_empty() && p.sample.is_none())
+            .and_then(|p| p.limit)
+    }
+
     fn is_exact(push_downs: &Option<Extras>) -> bool {
         match push_downs {
             None => true,
             // We don't have limit push down in parquet reader
-            Some(extra) => extra.filters.is_empty(),
+            Some(extra) => extra.filters.is_empty() && extra.sample.is_none(),
         }
     }
 
-    fn all_columns_partitions(metas: &[BlockMeta], limit: usize) -> (Statistics, Partitions) {
+    fn all_columns_partitions(
+        column_ids: &ColumnIds,
+        metas: &[BlockMeta],
+        limit: usize,
+    ) -> (Statistics, Partitions) {
         let mut statistics = Statistics::default_exact();
         let mut partitions = Partitions::default();
 
@@ -113,15 +133,21 @@ impl FuseTable {
 
         for block_meta in metas {
             let rows = block_meta.row_count as usize;
-            partitions.push(Self::all_columns_part(block_meta));
+            partitions.push(Self::all_columns_part(column_ids, block_meta));
             statistics.read_rows += rows;
             statistics.read_bytes += block_meta.block_size as usize;
+            // the deleted rows are only known after the block is read
+            if block_meta.deletion_vector.is_some() {
+                statistics.is_exact = false;
+            }
 
-            if remaining > rows {
-                remaining -= rows;
+            // the deleted rows do not count towards the limit
+            let live_rows = block_meta.live_row_count() as usize;
+            if remaining > live_rows {
+                remaining -= live_rows;
             } else {
                 // the last block we shall take
-                if remaining != rows {
+                if remaining != live_rows {
                     statistics.is_exact = false;
                 }
                 break;
@@ -132,6 +158,7 @@ impl FuseTable {
     }
 
     fn projection_partitions(
+        column_ids: &ColumnIds,
         metas: &[BlockMeta],
         indices: &[usize],
         limit: usize,
@@ -146,22 +173,29 @@ impl FuseTable {
         let mut remaining = limit;
 
         for block_meta in metas {
-            partitions.push(Self::projection_part(block_meta, indices));
+            partitions.push(Self::projection_part(column_ids, block_meta, indices));
 
             let rows = block_meta.row_count as usize;
 
             statistics.read_rows += rows;
             for projection_index in indices {
-                let column_stats = &block_meta.col_stats;
-                let column_stats = &column_stats[&(*projection_index as u32)];
-                statistics.read_bytes += column_stats.in_memory_size as usize;
+                // the columns added after the block is written are not read
+                let id = column_ids.id_of(*projection_index);
+                if let Some(column_stats) = block_meta.col_stats.get(&id) {
+                    statistics.read_bytes += column_stats.in_memory_size as usize;
+                }
+            }
+            if block_meta.deletion_vector.is_some() {
+                statistics.is_exact = false;
             }
 
-            if remaining > rows {
-                remaining -= rows;
+            // the deleted rows do not count towards the limit
+            let live_rows = block_meta.live_row_count() as usize;
+            if remaining > live_rows {
+                remaining -= live_rows;
             } else {
                 // the last block we shall take
-                if remaining != rows {
+                if remaining != live_rows {
                     statistics.is_exact = false;
                 }
                 break;
@@ -171,12 +205,17 @@ impl FuseTable {
         (statistics, partitions)
     }
 
-    fn all_columns_part(meta: &BlockMeta) -> PartInfoPtr {
+    fn all_columns_part(column_ids: &ColumnIds, meta: &BlockMeta) -> PartInfoPtr {
         let mut columns_meta = HashMap::with_capacity(meta.col_metas.len());
 
-        for (idx, column_meta) in &meta.col_metas {
+        for (id, column_meta) in &meta.col_metas {
+            // the columns dropped after the block is written are not read
+            let idx = match column_ids.index_of(*id) {
+                None => continue,
+                Some(idx) => idx,
+            };
             columns_meta.insert(
-                *idx as usize,
+                idx,
                 ColumnMeta::create(column_meta.offset, column_meta.len, column_meta.num_values),
             );
         }
@@ -190,14 +229,22 @@ impl FuseTable {
             rows_count,
             columns_meta,
             meta.compression,
+            meta.deletion_vector.as_ref().map(|dv| dv.location.clone()),
         )
     }
 
-    fn projection_part(meta: &BlockMeta, projections: &[usize]) -> PartInfoPtr {
+    fn projection_part(
+        column_ids: &ColumnIds,
+        meta: &BlockMeta,
+        projections: &[usize],
+    ) -> PartInfoPtr {
         let mut columns_meta = HashMap::with_capacity(projections.len());
 
         for projection in projections {
-            let column_meta = &meta.col_metas[&(*projection as u32)];
+            let column_meta = match meta.col_metas.get(&column_ids.id_of(*projection)) {
+                None => continue,
+                Some(column_meta) => column_meta,
+            };
 
             columns_meta.insert(
                 *projection,
@@ -214,6 +261,7 @@ impl FuseTable {
             rows_count,
             columns_meta,
             meta.compression,
+            meta.deletion_vector.as_ref().map(|dv| dv.location.clone()),
         )
     }
 
@@ -226,6 +274,7 @@ impl FuseTable {
             Extras {
                 projection: Some(projs),
                 filters,
+                sample: None,
                 ..
             } if projs.is_empty() && filters.is_empty() => {
                 let summary = &snapshot.summary;
@@ -0,0 +1,338 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::Extras;
+use common_tracing::tracing;
+use uuid::Uuid;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::write_block;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::SnapshotOperation;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::statistics::accumulator::BlockStatistics;
+use crate::storages::fuse::statistics::histogram::compare_values;
+use crate::storages::fuse::statistics::StatisticsAccumulator;
+use crate::storages::fuse::ClusterKey;
+use crate::storages::fuse::ColumnIds;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD;
+use crate::storages::fuse::DEFAULT_RECLUSTER_BLOCK_LIMIT;
+use crate::storages::fuse::DEFAULT_ROW_PER_BLOCK;
+use crate::storages::fuse::FUSE_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD;
+use crate::storages::fuse::FUSE_OPT_KEY_RECLUSTER_BLOCK_LIMIT;
+use crate::storages::fuse::FUSE_OPT_KEY_ROW_PER_BLOCK;
+
+/// Decides which blocks are rewritten by a round of `ALTER TABLE t RECLUSTER`.
+///
+/// Two blocks overlap if the ranges of the cluster key in them, see [ClusterKey], have inner
+/// points in common, blocks which merely share an end point are left alone, since sorting
+/// them again would not separate them. The blocks of which the ranges are connected by
+/// overlaps form a group, the group with the most overlapping pairs is picked, and its blocks
+/// are taken in the order of their ranges, until one of the caps is reached.
+#[derive(Clone, Copy, Debug)]
+pub struct ReclusterPolicy {
+    pub max_blocks: usize,
+    pub max_rows: u64,
+    pub max_bytes: u64,
+}
+
+impl ReclusterPolicy {
+    /// The caps of a round are `max_blocks` blocks, and as many rows and bytes as
+    /// `max_blocks` full blocks would hold.
+    pub fn new(max_blocks: usize, row_per_block: usize, block_size_threshold: usize) -> Self {
+        Self {
+            max_blocks,
+            max_rows: (max_blocks as u64).saturating_mul(row_per_block as u64),
+            max_bytes: (max_blocks as u64).saturating_mul(block_size_threshold as u64),
+        }
+    }
+
+    /// Returns the indexes of the blocks to be rewritten, empty if no block overlaps others.
+    ///
+    /// At least two blocks are picked, even if they exceed the caps of rows and bytes.
+    pub fn select(&self, blocks: &[BlockMeta], cluster_key: &ClusterKey) -> Vec<usize> {
+        let group = match Self::overlapping_groups(blocks, cluster_key)
+            .into_iter()
+            .max_by_key(|(overlaps, _)| *overlaps)
+        {
+            Some((_, group)) => group,
+            None => return vec![],
+        };
+
+        let mut picked = vec![];
+        let mut rows = 0;
+        let mut bytes = 0;
+        for idx in group {
+            let block = &blocks[idx];
+            rows += block.live_row_count();
+            bytes += block.block_size;
+            if picked.len() >= 2
+                && (picked.len() >= self.max_blocks
+                    || rows > self.max_rows
+                    || bytes > self.max_bytes)
+            {
+                break;
+            }
+            picked.push(idx);
+        }
+        picked
+    }
+
+    /// Number of the overlapping pairs of the blocks.
+    pub fn overlaps(blocks: &[BlockMeta], cluster_key: &ClusterKey) -> usize {
+        Self::overlapping_groups(blocks, cluster_key)
+            .iter()
+            .map(|(overlaps, _)| overlaps)
+            .sum()
+    }
+
+    /// Groups of the connected blocks, with the numbers of the overlapping pairs in them.
+    ///
+    /// The blocks of a group are ordered by the ranges, groups of a single block are omitted.
+    fn overlapping_groups(
+        blocks: &[BlockMeta],
+        cluster_key: &ClusterKey,
+    ) -> Vec<(usize, Vec<usize>)> {
+        let mut ranges = blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, b)| cluster_key.range_of(b).map(|(min, max)| (idx, min, max)))
+            .filter(|(_, min, max)| compare_values(min, max).is_some())
+            .collect::<Vec<_>>();
+        // blocks of the same min are ordered by max, thus a block only overlaps the blocks
+        // after it if they start before it ends
+        ranges.sort_by(|l, r| cmp(&l.1, &r.1).then_with(|| cmp(&l.2, &r.2)));
+
+        let mut groups = vec![];
+        let mut start = 0;
+        let mut group_max: Option<&DataValue> = None;
+        for (i, (_, min, max)) in ranges.iter().enumerate() {
+            group_max = match group_max {
+                // the block overlaps the current group
+                Some(group_max) if cmp(min, group_max) == Ordering::Less => {
+                    if cmp(max, group_max) == Ordering::Greater {
+                        Some(max)
+                    } else {
+                        Some(group_max)
+                    }
+                }
+                _ => {
+                    groups.push(&ranges[start..i]);
+                    start = i;
+                    Some(max)
+                }
+            };
+        }
+        groups.push(&ranges[start..]);
+
+        groups
+            .into_iter()
+            .filter(|g| g.len() > 1)
+            .map(|group| {
+                let mut overlaps = 0;
+                for (i, (_, _, max)) in group.iter().enumerate() {
+                    overlaps += group[i + 1..]
+                        .iter()
+                        .take_while(|(_, min, _)| cmp(min, max) == Ordering::Less)
+                        .count();
+                }
+                (overlaps, group.iter().map(|(idx, _, _)| *idx).collect())
+            })
+            .collect()
+    }
+}
+
+impl FuseTable {
+    /// The policy of reclustering, of which at most `limit` blocks are rewritten in a round.
+    pub fn recluster_policy(&self, limit: Option<usize>) -> ReclusterPolicy {
+        let max_blocks = limit.unwrap_or_else(|| {
+            self.get_option(
+                FUSE_OPT_KEY_RECLUSTER_BLOCK_LIMIT,
+                DEFAULT_RECLUSTER_BLOCK_LIMIT,
+            )
+        });
+        ReclusterPolicy::new(
+            max_blocks,
+            self.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK),
+            self.get_option(
+                FUSE_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD,
+                DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD,
+            ),
+        )
+    }
+
+    /// Does a round of reclustering: the blocks picked by the `ReclusterPolicy` are merged,
+    /// sorted by the cluster keys, and split into new blocks, which are committed in a new
+    /// snapshot. Only the blocks which may contain rows matching `selection` are considered.
+    ///
+    /// A round only holds the picked blocks in memory. Returns true if the round reduced the
+    /// overlaps of the blocks, i.e. another round may improve the clustering further.
+    pub async fn do_recluster(
+        &self,
+        ctx: &Arc<QueryContext>,
+        selection: &Option<Expression>,
+        limit: Option<usize>,
+    ) -> Result<bool> {
+        let cluster_key = self.cluster_key().ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "table {} is not clustered by a column",
+                self.table_info.name
+            ))
+        })?;
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(false),
+        };
+
+        let blocks = match selection {
+            Some(selection) => {
+                let push_downs = Some(Extras {
+                    filters: vec![selection.clone()],
+                    ..Extras::default()
+                });
+                BlockPruner::new(snapshot.clone())
+                    .apply(ctx.as_ref(), self.table_info.schema(), &push_downs)
+                    .await?
+            }
+            None => Self::blocks_of_segments_in_order(ctx.as_ref(), &snapshot.segments).await?,
+        };
+        let picked = self
+            .recluster_policy(limit)
+            .select(&blocks, &cluster_key)
+            .into_iter()
+            .map(|idx| blocks[idx].clone())
+            .collect::<Vec<_>>();
+        if picked.is_empty() {
+            return Ok(false);
+        }
+
+        let mut new_locations = vec![];
+        let result = self
+            .recluster_blocks(
+                ctx,
+                &snapshot,
+                &cluster_key,
+                &blocks,
+                &picked,
+                &mut new_locations,
+            )
+            .await;
+        if result.is_err() {
+            // the data written by this round is not referenced by any snapshot
+            let operator = ctx.get_storage_operator()?;
+            for location in &new_locations {
+                let _ = operator.object(location).delete().await;
+            }
+        }
+        result
+    }
+
+    /// Rewrites the `picked` ones of the candidate `blocks`, and commits them, returns whether
+    /// the overlaps of the candidates are reduced.
+    async fn recluster_blocks(
+        &self,
+        ctx: &Arc<QueryContext>,
+        snapshot: &TableSnapshot,
+        cluster_key: &ClusterKey,
+        blocks: &[BlockMeta],
+        picked: &[BlockMeta],
+        new_locations: &mut Vec<String>,
+    ) -> Result<bool> {
+        let operator = ctx.get_storage_operator()?;
+        let schema = self.table_info.schema();
+        let row_per_block = self.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK);
+        let block_reader = Self::create_block_reader(ctx, schema.clone(), &None)?;
+
+        let (_, parts) = Self::to_partitions(&schema, picked, None);
+        let mut data_blocks = Vec::with_capacity(parts.len());
+        for part in parts {
+            data_blocks.push(block_reader.read(part).await?);
+        }
+        let sorted = self.sort_by_cluster_keys(ctx, DataBlock::concat_blocks(&data_blocks)?)?;
+        drop(data_blocks);
+
+        // the new blocks record the snapshot which commits them
+        let snapshot_id = Uuid::new_v4();
+        let column_ids = ColumnIds::from_schema(&schema);
+        let mut acc = StatisticsAccumulator::created_by(snapshot_id, column_ids);
+        for block in DataBlock::split_block_by_size(&sorted, row_per_block)? {
+            let location = self.meta_location_generator.gen_block_location();
+            new_locations.push(location.clone());
+            let block_statistics =
+                BlockStatistics::from(&block, location.clone(), self.string_prefix_length())?;
+            let arrow_schema = block.schema().to_arrow();
+            let (file_size, meta) =
+                write_block(&arrow_schema, block, operator.clone(), &location).await?;
+            acc.add_block(file_size, meta, block_statistics)?;
+        }
+
+        let replaced = picked
+            .iter()
+            .map(|b| b.location.clone())
+            .collect::<HashSet<_>>();
+        let overlaps_before = ReclusterPolicy::overlaps(blocks, cluster_key);
+        let mut blocks_after = blocks
+            .iter()
+            .filter(|b| !replaced.contains(&b.location))
+            .cloned()
+            .collect::<Vec<_>>();
+        blocks_after.extend(acc.blocks_metas.iter().cloned());
+        let overlaps_after = ReclusterPolicy::overlaps(&blocks_after, cluster_key);
+        tracing::info!(
+            "recluster table {}, blocks rewritten: {}, overlaps before: {}, after: {}",
+            self.table_info.desc,
+            picked.len(),
+            overlaps_before,
+            overlaps_after
+        );
+
+        let segments = Self::load_segments(ctx.as_ref(), &snapshot.segments).await?;
+        let segment_locations = self
+            .write_segments_replacing(
+                ctx,
+                snapshot,
+                &segments,
+                &acc.blocks_metas,
+                &replaced,
+                new_locations,
+            )
+            .await?;
+        self.commit_mutation(
+            ctx,
+            snapshot_id,
+            Some(snapshot),
+            segment_locations,
+            SnapshotOperation::Recluster,
+            new_locations,
+        )
+        .await?;
+        Ok(overlaps_after < overlaps_before)
+    }
+}
+
+fn cmp(l: &DataValue, r: &DataValue) -> Ordering {
+    compare_values(l, r).unwrap_or(Ordering::Equal)
+}
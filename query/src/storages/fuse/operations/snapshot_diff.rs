@@ -0,0 +1,279 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+use uuid::Uuid;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::meta::Location;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::FuseTable;
+
+const MAX_CONCURRENT_SEGMENT_LOADING: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffObject {
+    Column,
+    Segment,
+    Block,
+}
+
+impl fmt::Display for DiffObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiffObject::Column => write!(f, "column"),
+            DiffObject::Segment => write!(f, "segment"),
+            DiffObject::Block => write!(f, "block"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffChange {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl fmt::Display for DiffChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiffChange::Added => write!(f, "added"),
+            DiffChange::Removed => write!(f, "removed"),
+            DiffChange::Modified => write!(f, "modified"),
+        }
+    }
+}
+
+/// A difference between two snapshots of a table
+#[derive(Clone, Debug, PartialEq)]
+pub struct SnapshotDiffEntry {
+    pub object: DiffObject,
+    pub change: DiffChange,
+    /// name of the column, or location of the segment or block
+    pub name: String,
+    /// rows gained (or lost, if negative) by the change
+    pub row_count_delta: i64,
+    /// uncompressed bytes gained (or lost, if negative) by the change
+    pub byte_size_delta: i64,
+    /// data types of the changed column
+    pub detail: Option<String>,
+}
+
+impl SnapshotDiffEntry {
+    fn new(object: DiffObject, change: DiffChange, name: impl Into<String>) -> Self {
+        Self {
+            object,
+            change,
+            name: name.into(),
+            row_count_delta: 0,
+            byte_size_delta: 0,
+            detail: None,
+        }
+    }
+
+    fn with_delta(mut self, row_count_delta: i64, byte_size_delta: i64) -> Self {
+        self.row_count_delta = row_count_delta;
+        self.byte_size_delta = byte_size_delta;
+        self
+    }
+
+    fn with_detail(mut self, detail: String) -> Self {
+        self.detail = Some(detail);
+        self
+    }
+}
+
+impl FuseTable {
+    /// Diffs the snapshots `from` and `to` of the table, both of which must be in the history
+    /// of the current snapshot.
+    ///
+    /// Only the segments which are not shared by the two snapshots are loaded, one by one,
+    /// and a block is reported as added or removed only if it is not moved from one segment
+    /// to another, e.g. by segment compaction. Blocks of which rows are deleted in place are
+    /// reported as modified.
+    pub async fn do_diff_snapshots(
+        &self,
+        ctx: &QueryContext,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<SnapshotDiffEntry>> {
+        let history = self.snapshot_log(ctx).await?;
+        let from = self.snapshot_of_history(&history, from)?;
+        let to = self.snapshot_of_history(&history, to)?;
+
+        let mut entries = Self::diff_schemas(&from.schema, &to.schema);
+
+        let from_segments: HashSet<&Location> = from.segments.iter().collect();
+        let to_segments: HashSet<&Location> = to.segments.iter().collect();
+        let removed_segments = from
+            .segments
+            .iter()
+            .filter(|l| !to_segments.contains(l))
+            .cloned()
+            .collect::<Vec<_>>();
+        let added_segments = to
+            .segments
+            .iter()
+            .filter(|l| !from_segments.contains(l))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let reader = MetaReaders::segment_info_reader(ctx);
+
+        // (location, live rows, bytes) of the blocks of the removed segments
+        let mut removed_blocks = vec![];
+        let mut segments = reader.read_segments(&removed_segments, MAX_CONCURRENT_SEGMENT_LOADING);
+        let mut idx = 0;
+        while let Some(segment) = segments.try_next().await? {
+            let summary = &segment.summary;
+            entries.push(
+                SnapshotDiffEntry::new(
+                    DiffObject::Segment,
+                    DiffChange::Removed,
+                    &removed_segments[idx].0,
+                )
+                .with_delta(
+                    -(summary.row_count as i64),
+                    -(summary.uncompressed_byte_size as i64),
+                ),
+            );
+            for block in &segment.blocks {
+                removed_blocks.push((
+                    block.location.0.clone(),
+                    block.live_row_count(),
+                    block.block_size,
+                ));
+            }
+            idx += 1;
+        }
+        let removed_index: HashMap<String, usize> = removed_blocks
+            .iter()
+            .enumerate()
+            .map(|(i, (loc, _, _))| (loc.clone(), i))
+            .collect();
+
+        let mut moved = HashSet::new();
+        let mut block_entries = vec![];
+        let mut segments = reader.read_segments(&added_segments, MAX_CONCURRENT_SEGMENT_LOADING);
+        let mut idx = 0;
+        while let Some(segment) = segments.try_next().await? {
+            let summary = &segment.summary;
+            entries.push(
+                SnapshotDiffEntry::new(
+                    DiffObject::Segment,
+                    DiffChange::Added,
+                    &added_segments[idx].0,
+                )
+                .with_delta(
+                    summary.row_count as i64,
+                    summary.uncompressed_byte_size as i64,
+                ),
+            );
+            for block in &segment.blocks {
+                let location = &block.location.0;
+                let live_rows = block.live_row_count() as i64;
+                match removed_index.get(location) {
+                    Some(i) => {
+                        moved.insert(*i);
+                        let removed_live_rows = removed_blocks[*i].1 as i64;
+                        if removed_live_rows != live_rows {
+                            block_entries.push(
+                                SnapshotDiffEntry::new(
+                                    DiffObject::Block,
+                                    DiffChange::Modified,
+                                    location,
+                                )
+                                .with_delta(live_rows - removed_live_rows, 0),
+                            );
+                        }
+                    }
+                    None => block_entries.push(
+                        SnapshotDiffEntry::new(DiffObject::Block, DiffChange::Added, location)
+                            .with_delta(live_rows, block.block_size as i64),
+                    ),
+                }
+            }
+            idx += 1;
+        }
+
+        for (i, (location, live_rows, bytes)) in removed_blocks.into_iter().enumerate() {
+            if !moved.contains(&i) {
+                block_entries.push(
+                    SnapshotDiffEntry::new(DiffObject::Block, DiffChange::Removed, location)
+                        .with_delta(-(live_rows as i64), -(bytes as i64)),
+                );
+            }
+        }
+        entries.extend(block_entries);
+        Ok(entries)
+    }
+
+    fn snapshot_of_history(
+        &self,
+        history: &[(Arc<TableSnapshot>, u64)],
+        snapshot_id: &str,
+    ) -> Result<Arc<TableSnapshot>> {
+        let id = Uuid::parse_str(snapshot_id).map_err(|e| {
+            ErrorCode::BadArguments(format!("invalid snapshot id {}: {}", snapshot_id, e))
+        })?;
+        match history.iter().find(|(s, _)| s.snapshot_id == id) {
+            Some((s, _)) => Ok(s.clone()),
+            None => Err(ErrorCode::TableHistoricalDataNotFound(format!(
+                "snapshot {} is not in the history of table {}",
+                snapshot_id, self.table_info.name
+            ))),
+        }
+    }
+
+    fn diff_schemas(from: &DataSchema, to: &DataSchema) -> Vec<SnapshotDiffEntry> {
+        let mut entries = vec![];
+        for field in from.fields() {
+            match to.field_with_name(field.name()) {
+                Err(_) => entries.push(
+                    SnapshotDiffEntry::new(DiffObject::Column, DiffChange::Removed, field.name())
+                        .with_detail(field.data_type().name()),
+                ),
+                Ok(new_field) if new_field.data_type() != field.data_type() => entries.push(
+                    SnapshotDiffEntry::new(DiffObject::Column, DiffChange::Modified, field.name())
+                        .with_detail(format!(
+                            "{} -> {}",
+                            field.data_type().name(),
+                            new_field.data_type().name()
+                        )),
+                ),
+                Ok(_) => {}
+            }
+        }
+        for field in to.fields() {
+            if from.field_with_name(field.name()).is_err() {
+                entries.push(
+                    SnapshotDiffEntry::new(DiffObject::Column, DiffChange::Added, field.name())
+                        .with_detail(field.data_type().name()),
+                );
+            }
+        }
+        entries
+    }
+}
@@ -16,13 +16,13 @@
 use std::sync::Arc;
 
 use common_exception::Result;
-use common_meta_types::UpsertTableOptionReq;
 use common_planners::TruncateTablePlan;
 use uuid::Uuid;
 
-use crate::catalogs::Catalog;
+use crate::configs::DATABEND_COMMIT_VERSION;
 use crate::sessions::QueryContext;
-use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::storages::fuse::io::write_snapshot;
+use crate::storages::fuse::meta::SnapshotOperation;
 use crate::storages::fuse::meta::TableSnapshot;
 use crate::storages::fuse::meta::Versioned;
 use crate::storages::fuse::FuseTable;
@@ -39,25 +39,30 @@ impl FuseTable {
                 prev_snapshot.schema.clone(),
                 Default::default(),
                 vec![],
-            );
+            )
+            .with_origin(DATABEND_COMMIT_VERSION.as_str(), ctx.get_id())
+            .with_operation(
+                SnapshotOperation::Truncate,
+                Self::current_user_identity(ctx.as_ref()),
+            )
+            .with_changes(Some(prev_snapshot.as_ref()));
             let loc = self.meta_location_generator();
             let new_snapshot_loc =
                 loc.snapshot_location_from_uuid(&new_snapshot.snapshot_id, TableSnapshot::VERSION)?;
             let operator = ctx.get_storage_operator()?;
-            let bytes = serde_json::to_vec(&new_snapshot)?;
-            operator.object(&new_snapshot_loc).write(bytes).await?;
+            write_snapshot(&ctx, &operator, &new_snapshot_loc, &new_snapshot).await?;
 
             if plan.purge {
                 let keep_last_snapshot = false;
                 self.do_optimize(ctx.clone(), keep_last_snapshot).await?
             }
-            ctx.get_catalog()
-                .upsert_table_option(UpsertTableOptionReq::new(
-                    &self.table_info.ident,
-                    OPT_KEY_SNAPSHOT_LOCATION,
-                    new_snapshot_loc,
-                ))
-                .await?;
+            Self::commit_to_meta_server(
+                ctx.as_ref(),
+                &self.table_info,
+                new_snapshot_loc,
+                &new_snapshot.summary,
+            )
+            .await?;
         }
 
         Ok(())
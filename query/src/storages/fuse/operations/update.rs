@@ -0,0 +1,217 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::Result;
+use common_planners::lit;
+use common_planners::Expression;
+use common_planners::Extras;
+use common_planners::UpdatePlan;
+use uuid::Uuid;
+
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::write_block;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::Location;
+use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::meta::SnapshotId;
+use crate::storages::fuse::meta::SnapshotOperation;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::meta::Versioned;
+use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::statistics::accumulator::BlockStatistics;
+use crate::storages::fuse::statistics::StatisticsAccumulator;
+use crate::storages::fuse::ColumnIds;
+use crate::storages::fuse::FuseTable;
+
+impl FuseTable {
+    /// Updates the rows matching the selection of `plan`.
+    ///
+    /// Only the blocks which may have matching rows, according to their statistics, are read.
+    /// The blocks having matching rows are rewritten as a whole, without the rows marked in
+    /// their deletion vectors, and the statistics of them are regenerated.
+    pub async fn do_update(&self, ctx: &Arc<QueryContext>, plan: UpdatePlan) -> Result<()> {
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+
+        // the rewritten blocks record the snapshot which commits them
+        let snapshot_id = Uuid::new_v4();
+        let mut new_locations = vec![];
+        let result = match self
+            .write_updated_blocks(ctx, snapshot_id, &snapshot, &plan, &mut new_locations)
+            .await
+        {
+            // nothing is updated
+            Ok(None) => Ok(()),
+            Ok(Some(segment_locations)) => {
+                self.commit_mutation(
+                    ctx,
+                    snapshot_id,
+                    Some(snapshot.as_ref()),
+                    segment_locations,
+                    SnapshotOperation::Update,
+                    &mut new_locations,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        if result.is_err() {
+            // the data written by this update is not referenced by any snapshot
+            let operator = ctx.get_storage_operator()?;
+            for location in &new_locations {
+                let _ = operator.object(location).delete().await;
+            }
+        }
+        result
+    }
+
+    /// Writes the updated blocks, and the segments of them, returns the segment locations of
+    /// the new snapshot, or `None` if no row is matched.
+    async fn write_updated_blocks(
+        &self,
+        ctx: &Arc<QueryContext>,
+        snapshot_id: SnapshotId,
+        snapshot: &Arc<TableSnapshot>,
+        plan: &UpdatePlan,
+        new_locations: &mut Vec<String>,
+    ) -> Result<Option<Vec<Location>>> {
+        let schema = self.table_info.schema();
+        let push_downs = plan.selection.as_ref().map(|selection| Extras {
+            filters: vec![selection.clone()],
+            ..Extras::default()
+        });
+        let candidates = BlockPruner::new(snapshot.clone())
+            .apply(ctx.as_ref(), schema.clone(), &push_downs)
+            .await?;
+        if candidates.is_empty() {
+            return Ok(None);
+        }
+
+        // the first expression is the selection, followed by the new values of the updated
+        // columns, which are the old values for the rows not selected
+        let mut exprs = Vec::with_capacity(plan.update_list.len() + 1);
+        exprs.push(plan.selection.clone().unwrap_or_else(|| lit(true)));
+        let mut updated = HashMap::with_capacity(plan.update_list.len());
+        for (column, value) in &plan.update_list {
+            updated.insert(schema.index_of(column)?, exprs.len());
+            exprs.push(match &plan.selection {
+                None => value.clone(),
+                Some(selection) => Expression::ScalarFunction {
+                    op: "if".to_owned(),
+                    args: vec![
+                        selection.clone(),
+                        value.clone(),
+                        Expression::Column(column.clone()),
+                    ],
+                },
+            });
+        }
+        let fields = exprs
+            .iter()
+            .map(|expr| expr.to_data_field(&schema))
+            .collect::<Result<Vec<_>>>()?;
+        let executor = ExpressionExecutor::try_create(
+            ctx.clone(),
+            "update executor",
+            schema.clone(),
+            DataSchemaRefExt::create(fields),
+            exprs,
+            false,
+        )?;
+
+        let operator = ctx.get_storage_operator()?;
+        let block_reader = Self::create_block_reader(ctx, schema.clone(), &None)?;
+        let column_ids = ColumnIds::from_schema(&schema);
+        let mut acc = StatisticsAccumulator::created_by(snapshot_id, column_ids);
+        let mut rewritten = vec![];
+        for block_meta in candidates {
+            let blocks = std::slice::from_ref(&block_meta);
+            let (_, parts) = Self::to_partitions(&schema, blocks, None);
+            let block = block_reader.read(parts[0].clone()).await?;
+            let evaluated = executor.execute(&block)?;
+            let predicate = DataBlock::cast_to_nonull_boolean(evaluated.column(0))?;
+            let mut matched = false;
+            for row in 0..block.num_rows() {
+                if predicate.get_bool(row)? {
+                    matched = true;
+                    break;
+                }
+            }
+            if !matched {
+                continue;
+            }
+
+            let columns = (0..schema.num_fields())
+                .map(|idx| match updated.get(&idx) {
+                    Some(pos) => evaluated.column(*pos).convert_full_column(),
+                    None => block.column(idx).clone(),
+                })
+                .collect::<Vec<_>>();
+            let block = DataBlock::create(schema.clone(), columns);
+            let block = self.sort_by_cluster_keys(ctx, block)?;
+
+            let location = self.meta_location_generator.gen_block_location();
+            new_locations.push(location.clone());
+            let block_statistics =
+                BlockStatistics::from(&block, location.clone(), self.string_prefix_length())?;
+            let arrow_schema = block.schema().to_arrow();
+            let (file_size, meta) =
+                write_block(&arrow_schema, block, operator.clone(), &location).await?;
+            acc.add_block(file_size, meta, block_statistics)?;
+            rewritten.push(block_meta.location.0);
+        }
+        if rewritten.is_empty() {
+            return Ok(None);
+        }
+
+        // location of the block => the meta of the rewritten block
+        let mutated: HashMap<String, BlockMeta> = rewritten
+            .into_iter()
+            .zip(acc.blocks_metas.into_iter())
+            .collect();
+        let segments = Self::load_segments(ctx.as_ref(), &snapshot.segments).await?;
+        let mut segment_locations = Vec::with_capacity(segments.len());
+        for (idx, segment) in segments.iter().enumerate() {
+            if !segment
+                .blocks
+                .iter()
+                .any(|b| mutated.contains_key(&b.location.0))
+            {
+                segment_locations.push(snapshot.segments[idx].clone());
+                continue;
+            }
+            let blocks = segment
+                .blocks
+                .iter()
+                .map(|b| mutated.get(&b.location.0).unwrap_or(b).clone())
+                .collect::<Vec<_>>();
+            let new_segment = Self::segment_of_blocks(&schema, blocks)?;
+            let location = self.meta_location_generator.gen_segment_info_location();
+            new_locations.push(location.clone());
+            let bytes = serde_json::to_vec(&new_segment)?;
+            operator.object(&location).write(bytes).await?;
+            segment_locations.push((location, SegmentInfo::VERSION));
+        }
+        Ok(Some(segment_locations))
+    }
+}
@@ -0,0 +1,329 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Utc;
+use common_cache::Cache;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::VacuumTablePlan;
+use common_tracing::tracing;
+use futures::TryStreamExt;
+use opendal::Operator;
+
+use crate::catalogs::Catalog;
+use crate::sessions::QueryContext;
+use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::io::TableMetaLocationGenerator;
+use crate::storages::fuse::meta::Location;
+use crate::storages::fuse::FuseTable;
+use crate::storages::VacuumReport;
+
+const MAX_CONCURRENT_SEGMENT_LOADING: usize = 10;
+
+/// Files which are no longer referenced by any of the retained snapshots, paired with their sizes.
+#[derive(Default)]
+struct VacuumCandidates {
+    snapshots: Vec<(String, u64)>,
+    segments: Vec<(Location, u64)>,
+    blocks: Vec<(String, u64)>,
+    /// Statistics built by `ANALYZE TABLE`, they are not counted as files in the report
+    statistics: Vec<(String, u64)>,
+    /// Segments referenced by the retained snapshots, at the time of marking
+    retained_segments: HashSet<Location>,
+}
+
+impl VacuumCandidates {
+    fn report(&self) -> VacuumReport {
+        VacuumReport {
+            snapshots: self.snapshots.len() as u64,
+            segments: self.segments.len() as u64,
+            blocks: self.blocks.len() as u64,
+            bytes_reclaimed: total_size(&self.snapshots)
+                + total_size(&self.segments)
+                + total_size(&self.blocks)
+                + total_size(&self.statistics),
+        }
+    }
+}
+
+fn total_size<T>(files: &[(T, u64)]) -> u64 {
+    files.iter().map(|(_, size)| size).sum()
+}
+
+impl FuseTable {
+    /// Removes the snapshots which are beyond the retention period, together with the segments
+    /// and blocks that only they reference.
+    ///
+    /// It works in two phases: the files to be removed are marked first, and then, after the
+    /// marks are re-validated against the latest snapshot of the table, purged.
+    pub async fn do_vacuum(
+        &self,
+        ctx: Arc<QueryContext>,
+        plan: VacuumTablePlan,
+    ) -> Result<VacuumReport> {
+        let retention = std::time::Duration::from_secs(plan.retain_hours.saturating_mul(3600));
+        let cutoff = chrono::Duration::from_std(retention)
+            .ok()
+            .and_then(|d| Utc::now().checked_sub_signed(d));
+
+        // nothing is beyond a retention period that long
+        let cutoff = match cutoff {
+            Some(cutoff) => cutoff,
+            None => return Ok(VacuumReport::default()),
+        };
+
+        let candidates = self.mark_vacuum_candidates(ctx.as_ref(), cutoff).await?;
+        if plan.dry_run {
+            return Ok(candidates.report());
+        }
+        self.purge_vacuum_candidates(ctx.as_ref(), candidates).await
+    }
+
+    async fn mark_vacuum_candidates(
+        &self,
+        ctx: &QueryContext,
+        cutoff: DateTime<Utc>,
+    ) -> Result<VacuumCandidates> {
+        let history = self.snapshot_log(ctx).await?;
+
+        // The log is ordered from the latest to the earliest, the current snapshot is always
+        // retained. Legacy snapshots have no timestamps, they are treated as the earliest ones.
+        let retained_len = history
+            .partition_point(|(s, _)| matches!(s.timestamp, Some(ts) if ts >= cutoff))
+            .max(1);
+        if history.len() <= retained_len {
+            return Ok(VacuumCandidates::default());
+        }
+        let (retained, expired) = history.split_at(retained_len);
+
+        let retained_segments: HashSet<Location> = retained
+            .iter()
+            .flat_map(|(s, _)| s.segments.iter().cloned())
+            .collect();
+        let retained_blocks = self.blocks_of_segments(ctx, &retained_segments).await?;
+
+        let expired_segments: HashSet<Location> = expired
+            .iter()
+            .flat_map(|(s, _)| s.segments.iter())
+            .filter(|l| !retained_segments.contains(*l))
+            .cloned()
+            .collect();
+
+        let operator = ctx.get_storage_operator()?;
+        let mut candidates = VacuumCandidates::default();
+
+        let mut visited_blocks = HashSet::new();
+        let locations = expired_segments.into_iter().collect::<Vec<_>>();
+        let reader = MetaReaders::segment_info_reader(ctx);
+        let mut segments = reader.read_segments(&locations, MAX_CONCURRENT_SEGMENT_LOADING);
+        let mut idx = 0;
+        while let Some(segment) = segments.try_next().await? {
+            for block in &segment.blocks {
+                let loc = &block.location.0;
+                if !retained_blocks.contains(loc) && visited_blocks.insert(loc.clone()) {
+                    candidates.blocks.push((loc.clone(), block.file_size));
+                }
+                // deletion vectors are removed along with the blocks
+                if let Some(dv) = &block.deletion_vector {
+                    let loc = &dv.location.0;
+                    if !retained_blocks.contains(loc) && visited_blocks.insert(loc.clone()) {
+                        if let Some(size) = Self::file_size(&operator, loc).await? {
+                            candidates.blocks.push((loc.clone(), size));
+                        }
+                    }
+                }
+                // and so are the virtual columns
+                if let Some(vb) = &block.virtual_block {
+                    let loc = &vb.location.0;
+                    if !retained_blocks.contains(loc) && visited_blocks.insert(loc.clone()) {
+                        candidates.blocks.push((loc.clone(), vb.file_size));
+                    }
+                }
+            }
+            let location = &locations[idx];
+            if let Some(size) = Self::file_size(&operator, &location.0).await? {
+                candidates.segments.push((location.clone(), size));
+            }
+            idx += 1;
+        }
+
+        let locs = self.meta_location_generator();
+        for (snapshot, ver) in expired {
+            let loc = locs.snapshot_location_from_uuid(&snapshot.snapshot_id, *ver)?;
+            if let Some(size) = Self::file_size(&operator, &loc).await? {
+                candidates.snapshots.push((loc, size));
+            }
+        }
+
+        // statistics are shared by the successive snapshots, until the table is analyzed again
+        let retained_statistics: HashSet<&String> = retained
+            .iter()
+            .filter_map(|(s, _)| s.table_statistics_location.as_ref())
+            .collect();
+        let expired_statistics: HashSet<&String> = expired
+            .iter()
+            .filter_map(|(s, _)| s.table_statistics_location.as_ref())
+            .filter(|l| !retained_statistics.contains(l))
+            .collect();
+        for loc in expired_statistics {
+            if let Some(size) = Self::file_size(&operator, loc).await? {
+                candidates.statistics.push((loc.clone(), size));
+            }
+        }
+
+        // files shared with the clones of the table, or the table it is cloned from, are kept
+        let removable = self.removable_files(ctx).await?;
+        candidates.blocks.retain(|(l, _)| removable.contains(l));
+        candidates
+            .segments
+            .retain(|((l, _), _)| removable.contains(l));
+        candidates.snapshots.retain(|(l, _)| removable.contains(l));
+        candidates.statistics.retain(|(l, _)| removable.contains(l));
+
+        candidates.retained_segments = retained_segments;
+        Ok(candidates)
+    }
+
+    async fn purge_vacuum_candidates(
+        &self,
+        ctx: &QueryContext,
+        mut candidates: VacuumCandidates,
+    ) -> Result<VacuumReport> {
+        // The table might have been mutated since the candidates were marked, files that are
+        // referenced by the latest snapshot must be kept.
+        let catalog = ctx.get_catalog();
+        let (_, meta) = catalog
+            .get_table_meta_by_id(self.table_info.ident.table_id)
+            .await?;
+        if let Some(loc) = meta.options.get(OPT_KEY_SNAPSHOT_LOCATION) {
+            let ver = TableMetaLocationGenerator::snaphost_version(loc);
+            let reader = MetaReaders::table_snapshot_reader(ctx);
+            let latest = reader.read(loc.as_str(), None, ver).await?;
+            let newly_referenced: HashSet<Location> = latest
+                .segments
+                .iter()
+                .filter(|l| !candidates.retained_segments.contains(*l))
+                .cloned()
+                .collect();
+            if let Some(statistics_loc) = &latest.table_statistics_location {
+                candidates.statistics.retain(|(l, _)| l != statistics_loc);
+            }
+            if !newly_referenced.is_empty() {
+                let referenced_blocks = self.blocks_of_segments(ctx, &newly_referenced).await?;
+                candidates
+                    .segments
+                    .retain(|(l, _)| !newly_referenced.contains(l));
+                candidates
+                    .blocks
+                    .retain(|(l, _)| !referenced_blocks.contains(l));
+            }
+        }
+
+        let report = candidates.report();
+        let operator = ctx.get_storage_operator()?;
+
+        // Blocks go first, then the segments, and the snapshots are the last ones (from the
+        // earliest to the latest), so that the vacuum could be resumed if it is interrupted.
+
+        // 1. remove blocks
+        for (loc, _) in &candidates.blocks {
+            Self::remove_file(&operator, loc).await?;
+        }
+
+        // 2. remove the segments
+        for ((loc, _), _) in &candidates.segments {
+            Self::remove_file(&operator, loc).await?;
+            if let Some(c) = ctx.get_storage_cache_manager().get_table_segment_cache() {
+                let cache = &mut *c.write().await;
+                cache.pop(loc.as_str());
+            }
+        }
+
+        // 3. remove the statistics
+        for (loc, _) in &candidates.statistics {
+            Self::remove_file(&operator, loc).await?;
+        }
+
+        // 4. remove the snapshots
+        for (loc, _) in candidates.snapshots.iter().rev() {
+            Self::remove_file(&operator, loc).await?;
+            if let Some(c) = ctx.get_storage_cache_manager().get_table_snapshot_cache() {
+                let cache = &mut *c.write().await;
+                cache.pop(loc.as_str());
+            }
+        }
+
+        tracing::info!(
+            "vacuum of table {} done, {:?}",
+            self.table_info.desc,
+            report
+        );
+        Ok(report)
+    }
+
+    pub(crate) async fn blocks_of_segments(
+        &self,
+        ctx: &QueryContext,
+        segments: &HashSet<Location>,
+    ) -> Result<HashSet<String>> {
+        let locations = segments.iter().cloned().collect::<Vec<_>>();
+        let reader = MetaReaders::segment_info_reader(ctx);
+        let mut segments = reader.read_segments(&locations, MAX_CONCURRENT_SEGMENT_LOADING);
+        let mut blocks = HashSet::new();
+        while let Some(segment) = segments.try_next().await? {
+            blocks.extend(
+                segment
+                    .blocks
+                    .iter()
+                    .flat_map(|b| b.file_locations().cloned()),
+            );
+        }
+        Ok(blocks)
+    }
+
+    /// Returns the size of the file, or None if it does not exist (already removed)
+    pub(crate) async fn file_size(operator: &Operator, location: &str) -> Result<Option<u64>> {
+        match operator.object(location).metadata().await {
+            Ok(meta) => Ok(Some(meta.content_length())),
+            Err(e) => {
+                let e = ErrorCode::from(e);
+                if e.code() == ErrorCode::storage_not_found_code() {
+                    Ok(None)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    pub(crate) async fn remove_file(operator: &Operator, location: &str) -> Result<()> {
+        match operator.object(location).delete().await {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                let e = ErrorCode::from(e);
+                if e.code() == ErrorCode::storage_not_found_code() {
+                    Ok(())
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::fmt;
+
+use common_exception::Result;
+use opendal::Operator;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::Location;
+use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::meta::Statistics;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::FuseTable;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyCategory {
+    Snapshot,
+    Segment,
+    Block,
+    Statistics,
+}
+
+impl fmt::Display for VerifyCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerifyCategory::Snapshot => write!(f, "snapshot"),
+            VerifyCategory::Segment => write!(f, "segment"),
+            VerifyCategory::Block => write!(f, "block"),
+            VerifyCategory::Statistics => write!(f, "statistics"),
+        }
+    }
+}
+
+/// A problem found in the files of a table
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerifyProblem {
+    pub category: VerifyCategory,
+    pub location: String,
+    pub problem: String,
+}
+
+#[derive(Default)]
+struct Verifier {
+    problems: Vec<VerifyProblem>,
+    verified_segments: HashSet<Location>,
+    verified_blocks: HashSet<String>,
+}
+
+impl Verifier {
+    fn report(&mut self, category: VerifyCategory, location: &str, problem: impl Into<String>) {
+        self.problems.push(VerifyProblem {
+            category,
+            location: location.to_owned(),
+            problem: problem.into(),
+        })
+    }
+
+    fn check_summary(
+        &mut self,
+        category: VerifyCategory,
+        location: &str,
+        summary: &Statistics,
+        expected: &Statistics,
+    ) {
+        let pairs = [
+            ("row_count", summary.row_count, expected.row_count),
+            ("block_count", summary.block_count, expected.block_count),
+            (
+                "uncompressed_byte_size",
+                summary.uncompressed_byte_size,
+                expected.uncompressed_byte_size,
+            ),
+            (
+                "compressed_byte_size",
+                summary.compressed_byte_size,
+                expected.compressed_byte_size,
+            ),
+            (
+                "index_byte_size",
+                summary.index_byte_size,
+                expected.index_byte_size,
+            ),
+        ];
+        for (name, recorded, actual) in pairs {
+            if recorded != actual {
+                self.report(
+                    category,
+                    location,
+                    format!(
+                        "{} of summary is {}, but {} is summed up",
+                        name, recorded, actual
+                    ),
+                );
+            }
+        }
+
+        let recorded_sizes = summary.index_sizes();
+        let index_types = recorded_sizes
+            .keys()
+            .chain(expected.index_sizes().keys())
+            .copied()
+            .collect::<BTreeSet<_>>();
+        for index_type in index_types {
+            let recorded = summary.index_size_of(index_type);
+            let actual = expected.index_size_of(index_type);
+            if recorded != actual {
+                self.report(
+                    category,
+                    location,
+                    format!(
+                        "index_byte_size of {:?} of summary is {}, but {} is summed up",
+                        index_type, recorded, actual
+                    ),
+                );
+            }
+        }
+    }
+}
+
+impl FuseTable {
+    /// Verifies the referential integrity of the snapshots of the table.
+    ///
+    /// For each of the snapshots that can be reached from the current one, the segments, blocks
+    /// and statistics files it references should exist, the sizes of the blocks should match
+    /// the metas, and the summaries should match the items they summarize. Snapshots that
+    /// have been purged are not treated as problems.
+    pub async fn do_verify(&self, ctx: &QueryContext) -> Result<Vec<VerifyProblem>> {
+        let mut verifier = Verifier::default();
+        let operator = ctx.get_storage_operator()?;
+        let snapshot_reader = MetaReaders::table_snapshot_reader(ctx);
+        let locs = self.meta_location_generator();
+
+        let mut next = self
+            .snapshot_loc()
+            .map(|loc| (loc, self.snapshot_format_version()));
+        let mut is_current = true;
+        while let Some((loc, ver)) = next.take() {
+            if Self::file_size(&operator, &loc).await?.is_none() {
+                // the history before the current snapshot may have been purged
+                if is_current {
+                    verifier.report(VerifyCategory::Snapshot, &loc, "file not found");
+                }
+                break;
+            }
+            is_current = false;
+
+            let snapshot = match snapshot_reader.read(loc.as_str(), None, ver).await {
+                Ok(snapshot) => snapshot,
+                Err(e) => {
+                    verifier.report(VerifyCategory::Snapshot, &loc, format!("unreadable: {}", e));
+                    break;
+                }
+            };
+            self.verify_snapshot(ctx, &operator, &mut verifier, &loc, &snapshot)
+                .await?;
+
+            if let Some((id, v)) = snapshot.prev_snapshot_id {
+                next = Some((locs.snapshot_location_from_uuid(&id, v)?, v));
+            }
+        }
+
+        Ok(verifier.problems)
+    }
+
+    async fn verify_snapshot(
+        &self,
+        ctx: &QueryContext,
+        operator: &Operator,
+        verifier: &mut Verifier,
+        location: &str,
+        snapshot: &TableSnapshot,
+    ) -> Result<()> {
+        if let Some(stats_loc) = &snapshot.table_statistics_location {
+            if Self::file_size(operator, stats_loc).await?.is_none() {
+                verifier.report(VerifyCategory::Statistics, stats_loc, "file not found");
+            }
+        }
+
+        let segment_reader = MetaReaders::segment_info_reader(ctx);
+        let mut segments = Vec::with_capacity(snapshot.segments.len());
+        let mut complete = true;
+        for segment_loc in &snapshot.segments {
+            let (loc, ver) = segment_loc;
+            if Self::file_size(operator, loc).await?.is_none() {
+                verifier.report(VerifyCategory::Segment, loc, "file not found");
+                complete = false;
+                continue;
+            }
+            match segment_reader.read(loc.as_str(), None, *ver).await {
+                Ok(segment) => {
+                    if verifier.verified_segments.insert(segment_loc.clone()) {
+                        Self::verify_segment(operator, verifier, loc, &segment).await?;
+                    }
+                    segments.push(segment);
+                }
+                Err(e) => {
+                    verifier.report(VerifyCategory::Segment, loc, format!("unreadable: {}", e));
+                    complete = false;
+                }
+            }
+        }
+
+        // the summary can only be checked if all the segments are there
+        if complete {
+            let mut expected = Statistics::default();
+            for segment in &segments {
+                let summary = &segment.summary;
+                expected.row_count += summary.row_count;
+                expected.block_count += summary.block_count;
+                expected.uncompressed_byte_size += summary.uncompressed_byte_size;
+                expected.compressed_byte_size += summary.compressed_byte_size;
+                for (index_type, size) in summary.index_sizes().iter() {
+                    expected.add_index_size(*index_type, *size);
+                }
+            }
+            verifier.check_summary(
+                VerifyCategory::Snapshot,
+                location,
+                &snapshot.summary,
+                &expected,
+            );
+        }
+        Ok(())
+    }
+
+    async fn verify_segment(
+        operator: &Operator,
+        verifier: &mut Verifier,
+        location: &str,
+        segment: &SegmentInfo,
+    ) -> Result<()> {
+        let blocks = &segment.blocks;
+        let mut expected = Statistics {
+            row_count: blocks.iter().map(|b| b.live_row_count()).sum(),
+            block_count: blocks.len() as u64,
+            uncompressed_byte_size: blocks.iter().map(|b| b.block_size).sum(),
+            compressed_byte_size: blocks.iter().map(|b| b.file_size).sum(),
+            ..Default::default()
+        };
+        for (index_type, size) in blocks.iter().flat_map(|b| b.index_sizes()) {
+            expected.add_index_size(index_type, size);
+        }
+        verifier.check_summary(
+            VerifyCategory::Segment,
+            location,
+            &segment.summary,
+            &expected,
+        );
+
+        for block in blocks {
+            if verifier.verified_blocks.insert(block.location.0.clone()) {
+                Self::verify_block(operator, verifier, block).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn verify_block(
+        operator: &Operator,
+        verifier: &mut Verifier,
+        block: &BlockMeta,
+    ) -> Result<()> {
+        let location = block.location.0.as_str();
+        let file_size = match Self::file_size(operator, location).await? {
+            None => {
+                verifier.report(VerifyCategory::Block, location, "file not found");
+                return Ok(());
+            }
+            Some(size) => size,
+        };
+        if file_size != block.file_size {
+            verifier.report(
+                VerifyCategory::Block,
+                location,
+                format!(
+                    "file size is {}, but {} is recorded",
+                    file_size, block.file_size
+                ),
+            );
+        }
+        if let Some(dv) = &block.deletion_vector {
+            if Self::file_size(operator, &dv.location.0).await?.is_none() {
+                verifier.report(
+                    VerifyCategory::Block,
+                    &dv.location.0,
+                    "deletion vector not found",
+                );
+            }
+        }
+        for (column_id, meta) in &block.col_metas {
+            if meta.offset + meta.len > file_size {
+                verifier.report(
+                    VerifyCategory::Block,
+                    location,
+                    format!("column {} is out of the bounds of the file", column_id),
+                );
+            }
+            if meta.num_values != block.row_count {
+                verifier.report(
+                    VerifyCategory::Block,
+                    location,
+                    format!(
+                        "column {} has {} values, but the block has {} rows",
+                        column_id, meta.num_values, block.row_count
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,254 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::FunctionContext;
+use common_meta_types::TableInfo;
+use common_planners::Expression;
+use common_planners::Extras;
+use common_planners::PartInfo;
+use common_planners::Partitions;
+use common_planners::ReadDataSourcePlan;
+use common_planners::SourceInfo;
+use common_planners::VirtualColumnDefinition;
+
+use crate::common::ExpressionEvaluator;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::fuse_part::ColumnMeta;
+use crate::storages::fuse::fuse_part::FusePartInfo;
+use crate::storages::fuse::fuse_part::VirtualBlockPart;
+use crate::storages::fuse::io::serialize_data_block;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::VirtualBlockMeta;
+use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::statistics::StatisticsAccumulator;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::FUSE_OPT_KEY_VIRTUAL_COLUMN_PREFIX;
+use crate::storages::ToReadDataSourcePlan;
+
+/// Extracts the virtual columns from the blocks being written to a table.
+pub struct VirtualColumnsExtractor {
+    func_ctx: FunctionContext,
+    exprs: Vec<Expression>,
+}
+
+impl VirtualColumnsExtractor {
+    /// Returns `None` if the table has no virtual columns.
+    pub fn try_create(ctx: &QueryContext, table_info: &TableInfo) -> Result<Option<Self>> {
+        let exprs = FuseTable::virtual_columns(table_info)?
+            .iter()
+            .map(|(name, definition)| definition.expr(name))
+            .collect::<Vec<_>>();
+        match exprs.is_empty() {
+            true => Ok(None),
+            false => Ok(Some(VirtualColumnsExtractor {
+                func_ctx: ctx.try_get_function_context()?,
+                exprs,
+            })),
+        }
+    }
+
+    /// Serializes the virtual columns of the block into a file to be written to `location`.
+    pub fn serialize(
+        &self,
+        block: &DataBlock,
+        location: String,
+    ) -> Result<(Vec<u8>, VirtualBlockMeta)> {
+        let mut fields = Vec::with_capacity(self.exprs.len());
+        let mut columns = Vec::with_capacity(self.exprs.len());
+        for expr in &self.exprs {
+            fields.push(FuseTable::virtual_column_field(&expr.column_name()));
+            columns.push(ExpressionEvaluator::eval(
+                self.func_ctx.clone(),
+                expr,
+                block,
+            )?);
+        }
+        let virtual_block = DataBlock::create(DataSchemaRefExt::create(fields.clone()), columns);
+
+        let mut data = Vec::with_capacity(virtual_block.memory_size());
+        let (file_size, meta) = serialize_data_block(virtual_block, &mut data)?;
+        let col_metas = StatisticsAccumulator::column_metas(&meta)?
+            .into_iter()
+            .map(|(id, meta)| (fields[id as usize].name().clone(), meta))
+            .collect();
+        Ok((data, VirtualBlockMeta {
+            location: (location, DataBlock::VERSION),
+            file_size,
+            col_metas,
+        }))
+    }
+}
+
+impl FuseTable {
+    /// Definitions of the virtual columns of the table, by the names of the virtual columns.
+    pub fn virtual_columns(
+        table_info: &TableInfo,
+    ) -> Result<BTreeMap<String, VirtualColumnDefinition>> {
+        table_info
+            .options()
+            .iter()
+            .filter_map(|(key, value)| {
+                key.strip_prefix(FUSE_OPT_KEY_VIRTUAL_COLUMN_PREFIX)
+                    .map(|name| (name, value))
+            })
+            .map(|(name, value)| Ok((name.to_owned(), serde_json::from_str(value)?)))
+            .collect()
+    }
+
+    /// The field of a virtual column, the type of which is that of `get_path`.
+    pub fn virtual_column_field(name: &str) -> DataField {
+        DataField::new(name, NullableType::new_impl(VariantType::new_impl()))
+    }
+
+    /// The virtual columns of `names`, which are extracted from all the blocks read by `plan`.
+    pub async fn materialized_virtual_columns(
+        &self,
+        ctx: &QueryContext,
+        plan: &ReadDataSourcePlan,
+        names: &[String],
+    ) -> Result<Vec<String>> {
+        let snapshot = match self.read_table_snapshot(ctx).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(vec![]),
+        };
+        let blocks = BlockPruner::new(snapshot)
+            .apply(ctx, self.table_info.schema(), &plan.push_downs)
+            .await?;
+        let materialized = names
+            .iter()
+            .filter(|name| {
+                blocks.iter().all(|b| match &b.virtual_block {
+                    Some(vb) => vb.col_metas.contains_key(*name),
+                    None => false,
+                })
+            })
+            .cloned()
+            .collect();
+        Ok(materialized)
+    }
+
+    /// Plan to read the columns of `projection` of the table, and the virtual columns of
+    /// `names`, instead of extracting the virtual columns from the columns they belong to.
+    ///
+    /// The virtual columns are appended to the schema of the table.
+    pub async fn virtual_columns_source_plan(
+        &self,
+        ctx: Arc<QueryContext>,
+        plan: &ReadDataSourcePlan,
+        projection: Vec<usize>,
+        names: &[String],
+    ) -> Result<ReadDataSourcePlan> {
+        let schema = self.table_info.schema();
+        let mut fields = schema.fields().clone();
+        fields.extend(names.iter().map(|name| Self::virtual_column_field(name)));
+        let mut table_info = self.table_info.clone();
+        // the metadata keeps the ids of the columns, by which the blocks are read
+        table_info.meta.schema = Arc::new(DataSchema::new_from(fields, schema.meta().clone()));
+
+        let mut projection = projection;
+        projection.extend(schema.fields().len()..schema.fields().len() + names.len());
+        let mut push_downs = plan.push_downs.clone().unwrap_or_else(Extras::default);
+        push_downs.projection = Some(projection);
+
+        let mut plan = plan.clone();
+        plan.source_info = SourceInfo::TableSource(table_info.clone());
+        let table = ctx.build_table_from_source_plan(&plan)?;
+        let mut plan = table.read_plan(ctx, Some(push_downs)).await?;
+        plan.description = format!(
+            "(Read from {} table with virtual columns {}, Read Rows:{}, Partitions Scanned:{})",
+            table_info.desc,
+            names.join(", "),
+            plan.statistics.read_rows,
+            plan.statistics.partitions_scanned,
+        );
+        Ok(plan)
+    }
+
+    /// Splits the virtual columns out of the projection, the virtual columns are returned by
+    /// their indices in the schema of the table.
+    pub(crate) fn split_virtual_columns(
+        &self,
+        push_downs: Option<Extras>,
+    ) -> Result<(Option<Extras>, Vec<(usize, String)>)> {
+        let virtual_columns = Self::virtual_columns(&self.table_info)?;
+        let mut push_downs = match push_downs {
+            Some(push_downs) if !virtual_columns.is_empty() => push_downs,
+            push_downs => return Ok((push_downs, vec![])),
+        };
+
+        let schema = self.table_info.schema();
+        let mut projected = vec![];
+        if let Some(projection) = &mut push_downs.projection {
+            projection.retain(|idx| {
+                let name = schema.field(*idx).name();
+                match virtual_columns.contains_key(name) {
+                    true => {
+                        projected.push((*idx, name.clone()));
+                        false
+                    }
+                    false => true,
+                }
+            });
+        }
+        Ok((Some(push_downs), projected))
+    }
+
+    /// Adds the virtual columns to the parts of the blocks, which are in the same order.
+    pub(crate) fn virtual_columns_parts(
+        blocks: &[BlockMeta],
+        parts: Partitions,
+        virtual_columns: &[(usize, String)],
+    ) -> Result<Partitions> {
+        if virtual_columns.is_empty() {
+            return Ok(parts);
+        }
+
+        let mut virtual_parts = Vec::with_capacity(parts.len());
+        for (part, block) in parts.iter().zip(blocks.iter()) {
+            let vb = block.virtual_block.as_ref().ok_or_else(|| {
+                ErrorCode::LogicalError(format!(
+                    "No virtual columns are extracted from the block {}",
+                    block.location.0
+                ))
+            })?;
+
+            let mut columns_meta = Default::default();
+            for (idx, name) in virtual_columns {
+                let meta = vb.col_metas.get(name).ok_or_else(|| {
+                    ErrorCode::LogicalError(format!(
+                        "Virtual column {} is not extracted from the block {}",
+                        name, block.location.0
+                    ))
+                })?;
+                let meta = ColumnMeta::create(meta.offset, meta.len, meta.num_values);
+                columns_meta.insert(*idx, meta);
+            }
+
+            let mut part = FusePartInfo::from_part(part)?.clone();
+            part.virtual_block = Some(VirtualBlockPart {
+                location: vb.location.0.clone(),
+                columns_meta,
+            });
+            virtual_parts.push(Arc::new(Box::new(part) as Box<dyn PartInfo>));
+        }
+        Ok(virtual_parts)
+    }
+}
@@ -13,30 +13,104 @@
 //  limitations under the License.
 //
 
-use std::sync::atomic::AtomicUsize;
-use std::sync::atomic::Ordering;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
 use common_exception::Result;
+use common_planners::Expression;
 use common_planners::Extras;
+use common_planners::RequireColumnsVisitor;
+use common_planners::TableSample;
 use common_tracing::tracing;
-use futures::StreamExt;
 use futures::TryStreamExt;
+use rand::Rng;
 
 use crate::sessions::QueryContext;
 use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::ColumnId;
 use crate::storages::fuse::meta::SegmentInfo;
 use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::statistics::histogram::compare_values;
+use crate::storages::fuse::ColumnIds;
+use crate::storages::fuse::FuseTable;
+use crate::storages::index::ColumnStatistics;
 use crate::storages::index::ColumnsStatistics;
 use crate::storages::index::RangeFilter;
 
+/// Max number of segments being loaded concurrently while pruning
+const MAX_CONCURRENT_SEGMENT_LOADING: usize = 10;
+
 pub struct BlockPruner {
     table_snapshot: Arc<TableSnapshot>,
 }
 
 type Pred = Box<dyn Fn(&ColumnsStatistics) -> Result<bool> + Send + Sync + Unpin>;
+
+/// Maps the statistics kept in the segments, which are keyed by the ids of the columns, to the
+/// ones keyed by the indices of the columns in the schema, against which the predicate is
+/// evaluated.
+struct StatisticsMapper {
+    column_ids: ColumnIds,
+    missing_values: HashMap<usize, DataValue>,
+}
+
+impl StatisticsMapper {
+    fn try_create(schema: &DataSchemaRef) -> Result<Self> {
+        let column_ids = ColumnIds::from_schema(schema);
+        Ok(Self {
+            missing_values: column_ids.missing_values(schema)?,
+            column_ids,
+        })
+    }
+
+    fn is_identity(&self) -> bool {
+        self.column_ids.is_identity() && self.missing_values.is_empty()
+    }
+
+    /// The statistics of the added columns are left out, since they do not cover the blocks
+    /// written before the columns are added.
+    fn segment_stats<'a>(&self, stats: &'a ColumnsStatistics) -> Cow<'a, ColumnsStatistics> {
+        if self.is_identity() {
+            return Cow::Borrowed(stats);
+        }
+        let mut stats = self.column_ids.by_indices(stats);
+        for index in self.missing_values.keys() {
+            stats.remove(&(*index as ColumnId));
+        }
+        Cow::Owned(stats)
+    }
+
+    /// The columns missing in the block are filled with the values recorded when they are
+    /// added, so are the statistics of them.
+    fn block_stats<'a>(&self, block_meta: &'a BlockMeta) -> Cow<'a, ColumnsStatistics> {
+        if self.is_identity() {
+            return Cow::Borrowed(&block_meta.col_stats);
+        }
+        let mut stats = self.column_ids.by_indices(&block_meta.col_stats);
+        for (index, value) in &self.missing_values {
+            stats
+                .entry(*index as ColumnId)
+                .or_insert_with(|| ColumnStatistics {
+                    min: value.clone(),
+                    max: value.clone(),
+                    null_count: match value.is_null() {
+                        true => block_meta.row_count,
+                        false => 0,
+                    },
+                    in_memory_size: 0,
+                    distinct_of_values: None,
+                    truncated: false,
+                });
+        }
+        Cow::Owned(stats)
+    }
+}
+
 impl BlockPruner {
     pub fn new(table_snapshot: Arc<TableSnapshot>) -> Self {
         Self { table_snapshot }
@@ -49,6 +123,7 @@ impl BlockPruner {
         schema: DataSchemaRef,
         push_down: &Option<Extras>,
     ) -> Result<Vec<BlockMeta>> {
+        let mapper = StatisticsMapper::try_create(&schema)?;
         let block_pred: Pred = match push_down {
             Some(exprs) if !exprs.filters.is_empty() => {
                 // for the time being, we only handle the first expr
@@ -59,80 +134,222 @@ impl BlockPruner {
             _ => Box::new(|_: &ColumnsStatistics| Ok(true)),
         };
 
-        let segment_locs = self.table_snapshot.segments.clone();
-        let segment_num = segment_locs.len();
+        // `TABLESAMPLE BLOCK`: each block is kept independently at the rate, without being read
+        let sample_rate = match push_down.as_ref().and_then(|p| p.sample) {
+            Some(TableSample::Block(percent)) => Some((percent / 100.0).clamp(0.0, 1.0)),
+            _ => None,
+        };
 
+        let segment_locs = &self.table_snapshot.segments;
         if segment_locs.is_empty() {
             return Ok(vec![]);
         };
 
-        let limit = push_down
-            .as_ref()
-            .filter(|p| p.order_by.is_empty())
-            .and_then(|p| p.limit)
-            .unwrap_or(usize::MAX);
-
-        // Segments and blocks are accumulated concurrently, thus an atomic counter is used
-        // to **try** collecting as less blocks as possible. But concurrency is preferred to
-        // "accuracy". In [FuseTable::do_read_partitions], the "limit" will be treated precisely.
-
-        let accumulated_rows = AtomicUsize::new(0);
-
-        // A !Copy Wrapper of u64
-        struct NonCopy(u64);
-
-        // convert u64 (which is Copy) into NonCopy( struct which is !Copy)
-        // so that "async move" can be avoided in the latter async block
-        // See https://github.com/rust-lang/rust/issues/81653
-        let segment_locs = segment_locs.into_iter().map(|(s, v)| (s, NonCopy(v)));
-
-        let stream = futures::stream::iter(segment_locs)
-            .map(|(seg_loc, u)| async {
-                let version = { u }.0; // use block expression to force moving
-                if accumulated_rows.load(Ordering::Acquire) < limit {
-                    let reader = MetaReaders::segment_info_reader(ctx);
-                    let segment_info = reader.read(seg_loc, None, version).await?;
-                    Self::filter_segment(
-                        segment_info.as_ref(),
-                        &block_pred,
-                        &accumulated_rows,
-                        limit,
-                    )
-                } else {
-                    Ok(vec![])
+        let limit = FuseTable::scan_limit(push_down).unwrap_or(usize::MAX);
+
+        // Segments are loaded lazily (at most `MAX_CONCURRENT_SEGMENT_LOADING` of them
+        // are in flight at the same time), and once enough rows are accumulated, the
+        // remaining segments will not be loaded at all. In [FuseTable::do_read_partitions],
+        // the "limit" will be treated precisely.
+        let reader = MetaReaders::segment_info_reader(ctx);
+        let mut segments = reader.read_segments(segment_locs, MAX_CONCURRENT_SEGMENT_LOADING);
+
+        let mut accumulated_rows = 0;
+        let mut block_metas = vec![];
+        while accumulated_rows < limit {
+            match segments.try_next().await? {
+                Some(segment_info) => Self::filter_segment(
+                    segment_info.as_ref(),
+                    &block_pred,
+                    &mapper,
+                    sample_rate,
+                    &mut accumulated_rows,
+                    limit,
+                    &mut block_metas,
+                )?,
+                None => break,
+            }
+        }
+
+        match push_down {
+            Some(extras) if extras.filters.is_empty() && extras.sample.is_none() => {
+                Self::prune_top_n(&schema, &mapper, extras, block_metas)
+            }
+            _ => Ok(block_metas),
+        }
+    }
+
+    /// For `ORDER BY key LIMIT n`, prunes the blocks which can not have any of the first n rows,
+    /// according to the min/max statistics of the first key of the blocks.
+    ///
+    /// Taking the blocks by their max values in ascending order, once n rows are taken, the
+    /// largest max value of them is a threshold of the first n rows, the blocks the min values of
+    /// which are larger than it can be pruned. It is the opposite for the descending order.
+    fn prune_top_n(
+        schema: &DataSchemaRef,
+        mapper: &StatisticsMapper,
+        push_down: &Extras,
+        block_metas: Vec<BlockMeta>,
+    ) -> Result<Vec<BlockMeta>> {
+        let (limit, column, asc) = match (push_down.limit, push_down.order_by.first()) {
+            (Some(limit), Some(Expression::Sort { expr, asc, .. })) => match expr.as_ref() {
+                Expression::Column(column) => (limit, column, *asc),
+                _ => return Ok(block_metas),
+            },
+            _ => return Ok(block_metas),
+        };
+        let index = match schema.index_of(column) {
+            Ok(index) => index as ColumnId,
+            Err(_) => return Ok(block_metas),
+        };
+
+        // The NULLs are sorted out of the min/max values, nothing is pruned if there may be any.
+        let mut ranges = Vec::with_capacity(block_metas.len());
+        for block_meta in &block_metas {
+            match mapper.block_stats(block_meta).get(&index) {
+                Some(stats)
+                    if stats.null_count == 0
+                        && compare_values(&stats.min, &stats.max).is_some() =>
+                {
+                    ranges.push((stats.min.clone(), stats.max.clone()))
                 }
+                _ => return Ok(block_metas),
+            }
+        }
+
+        let mut bounds = block_metas
+            .iter()
+            .zip(ranges.iter())
+            .map(|(block_meta, (min, max))| match asc {
+                true => (max, block_meta.live_row_count() as usize),
+                false => (min, block_meta.live_row_count() as usize),
             })
-            // configuration of the max size of buffered futures
-            .buffered(std::cmp::min(10, segment_num))
-            .try_collect::<Vec<_>>()
-            .await?
-            .into_iter()
-            .flatten();
-
-        Ok(stream.collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        bounds.sort_by(|(l, _), (r, _)| {
+            let ordering = compare_values(l, r).unwrap_or(Ordering::Equal);
+            match asc {
+                true => ordering,
+                false => ordering.reverse(),
+            }
+        });
+
+        let mut taken_rows = 0;
+        let mut threshold = None;
+        for (value, rows) in bounds {
+            taken_rows += rows;
+            if taken_rows >= limit {
+                threshold = Some(value.clone());
+                break;
+            }
+        }
+        let threshold = match threshold {
+            Some(threshold) => threshold,
+            None => return Ok(block_metas),
+        };
+
+        let mut pruned = Vec::with_capacity(block_metas.len());
+        for (block_meta, (min, max)) in block_metas.into_iter().zip(ranges) {
+            let ordering = match asc {
+                true => compare_values(&min, &threshold),
+                false => compare_values(&threshold, &max),
+            };
+            if ordering != Some(Ordering::Greater) {
+                pruned.push(block_meta);
+            }
+        }
+        Ok(pruned)
+    }
+
+    /// Whether all the rows of the blocks match `filter`, as told by the statistics of them,
+    /// i.e. none of the blocks may have rows matching the negation of it. False if it is not
+    /// known, e.g. the filter could not be negated, or the columns of it may be NULL.
+    pub fn all_rows_matched(
+        ctx: &QueryContext,
+        schema: DataSchemaRef,
+        filter: &Expression,
+        blocks: &[BlockMeta],
+    ) -> Result<bool> {
+        let negated = match negate(filter) {
+            Some(negated) => negated,
+            None => return Ok(false),
+        };
+        let mut indices = vec![];
+        for column in RequireColumnsVisitor::collect_columns_from_expr(filter)? {
+            match schema.index_of(&column) {
+                Ok(index) => indices.push(index as ColumnId),
+                Err(_) => return Ok(false),
+            }
+        }
+
+        let mapper = StatisticsMapper::try_create(&schema)?;
+        let range_filter = RangeFilter::try_create(Arc::new(ctx.clone()), &negated, schema)?;
+        for block_meta in blocks {
+            let stats = mapper.block_stats(block_meta);
+            let non_null = indices
+                .iter()
+                .all(|idx| matches!(stats.get(idx), Some(s) if s.null_count == 0));
+            if !non_null || range_filter.eval(&stats)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
     }
 
     #[inline]
     fn filter_segment(
         segment_info: &SegmentInfo,
         pred: &Pred,
-        accumulated_rows: &AtomicUsize,
+        mapper: &StatisticsMapper,
+        sample_rate: Option<f64>,
+        accumulated_rows: &mut usize,
         limit: usize,
-    ) -> Result<Vec<BlockMeta>> {
-        if pred(&segment_info.summary.col_stats)? {
-            let block_num = segment_info.blocks.len();
-            let mut acc = Vec::with_capacity(block_num);
+        acc: &mut Vec<BlockMeta>,
+    ) -> Result<()> {
+        if pred(&mapper.segment_stats(&segment_info.summary.col_stats))? {
+            let mut rng = rand::thread_rng();
             for block_meta in &segment_info.blocks {
-                if pred(&block_meta.col_stats)? {
-                    let num_rows = block_meta.row_count as usize;
-                    if accumulated_rows.fetch_add(num_rows, Ordering::Release) < limit {
-                        acc.push(block_meta.clone());
+                if *accumulated_rows >= limit {
+                    break;
+                }
+                if let Some(rate) = sample_rate {
+                    if !rng.gen_bool(rate) {
+                        continue;
                     }
                 }
+                if pred(&mapper.block_stats(block_meta))? {
+                    *accumulated_rows += block_meta.live_row_count() as usize;
+                    acc.push(block_meta.clone());
+                }
             }
-            Ok(acc)
-        } else {
-            Ok(vec![])
         }
+        Ok(())
+    }
+}
+
+/// Negates the comparisons combined by AND / OR, None if there are other kinds of expressions.
+fn negate(expr: &Expression) -> Option<Expression> {
+    match expr {
+        Expression::UnaryExpression { op, expr } if op.to_lowercase() == "not" => {
+            Some(expr.as_ref().clone())
+        }
+        Expression::BinaryExpression { left, op, right } => {
+            let op = match op.to_lowercase().as_str() {
+                "and" => return Some(negate(left)?.or(negate(right)?)),
+                "or" => return Some(negate(left)?.and(negate(right)?)),
+                "=" => "<>",
+                "<>" | "!=" => "=",
+                "<" => ">=",
+                "<=" => ">",
+                ">" => "<=",
+                ">=" => "<",
+                _ => return None,
+            };
+            Some(Expression::BinaryExpression {
+                left: left.clone(),
+                op: op.to_owned(),
+                right: right.clone(),
+            })
+        }
+        _ => None,
     }
 }
@@ -15,6 +15,7 @@
 
 use std::collections::HashMap;
 
+use chrono::Utc;
 use common_arrow::parquet::FileMetaData;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
@@ -27,7 +28,11 @@ use crate::storages::fuse::meta::BlockMeta;
 use crate::storages::fuse::meta::ColumnId;
 use crate::storages::fuse::meta::ColumnMeta;
 use crate::storages::fuse::meta::Compression;
+use crate::storages::fuse::meta::IndexSizes;
+use crate::storages::fuse::meta::IndexType;
+use crate::storages::fuse::meta::SnapshotId;
 use crate::storages::fuse::meta::Versioned;
+use crate::storages::fuse::ColumnIds;
 use crate::storages::index::ColumnStatistics;
 use crate::storages::index::ColumnsStatistics;
 
@@ -39,6 +44,16 @@ pub struct StatisticsAccumulator {
     pub summary_block_count: u64,
     pub in_memory_size: u64,
     pub file_size: u64,
+    pub index_size: u64,
+    /// `index_size` by the types of the indexes
+    pub index_sizes: IndexSizes,
+    /// The snapshot which the accumulated blocks will be committed by, if known
+    pub created_by: Option<SnapshotId>,
+    /// The ids of the columns of the blocks, which the statistics and metas are keyed by
+    pub column_ids: ColumnIds,
+    /// The length in bytes which the min and the max of the strings are truncated to,
+    /// 0 for no truncation
+    pub string_prefix_length: usize,
 }
 
 impl StatisticsAccumulator {
@@ -46,6 +61,25 @@ impl StatisticsAccumulator {
         Default::default()
     }
 
+    pub fn created_by(snapshot_id: SnapshotId, column_ids: ColumnIds) -> Self {
+        Self {
+            created_by: Some(snapshot_id),
+            column_ids,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_string_prefix_length(mut self, string_prefix_length: usize) -> Self {
+        self.string_prefix_length = string_prefix_length;
+        self
+    }
+
+    /// Adds `size` bytes of the index files of `index_type` of the accumulated blocks
+    pub fn add_index_size(&mut self, index_type: IndexType, size: u64) {
+        self.index_size += size;
+        *self.index_sizes.entry(index_type).or_default() += size;
+    }
+
     pub fn begin(mut self, block: &DataBlock) -> Result<PartiallyAccumulated> {
         let row_count = block.num_rows() as u64;
         let block_in_memory_size = block.memory_size() as u64;
@@ -53,7 +87,8 @@ impl StatisticsAccumulator {
         self.summary_block_count += 1;
         self.summary_row_count += row_count;
         self.in_memory_size += block_in_memory_size;
-        let block_stats = Self::acc_columns(block)?;
+        let block_stats = BlockStatistics::columns_statistics(block, self.string_prefix_length)?;
+        let block_stats = self.column_ids.by_ids(block_stats);
         self.blocks_statistics.push(block_stats.clone());
         Ok(PartiallyAccumulated {
             accumulator: self,
@@ -73,23 +108,27 @@ impl StatisticsAccumulator {
         self.summary_block_count += 1;
         self.in_memory_size += statistics.block_bytes_size;
         self.summary_row_count += statistics.block_rows_size;
-        self.blocks_statistics
-            .push(statistics.block_column_statistics.clone());
+        let col_stats = self.column_ids.by_ids(statistics.block_column_statistics);
+        self.blocks_statistics.push(col_stats.clone());
 
         self.blocks_metas.push(BlockMeta {
             file_size,
             compression: Compression::Lz4Raw,
             row_count: statistics.block_rows_size,
             block_size: statistics.block_bytes_size,
-            col_stats: statistics.block_column_statistics.clone(),
+            col_stats,
             location: (statistics.block_file_location, DataBlock::VERSION),
-            col_metas: Self::column_metas(&meta)?,
+            col_metas: self.column_ids.by_ids(Self::column_metas(&meta)?),
+            created_by: self.created_by,
+            created_on: Some(Utc::now()),
+            deletion_vector: None,
+            virtual_block: None,
         });
 
         Ok(())
     }
 
-    fn column_metas(file_meta: &FileMetaData) -> Result<HashMap<ColumnId, ColumnMeta>> {
+    pub fn column_metas(file_meta: &FileMetaData) -> Result<HashMap<ColumnId, ColumnMeta>> {
         // currently we use one group only
         let num_row_groups = file_meta.row_groups.len();
         if num_row_groups != 1 {
@@ -138,44 +177,7 @@ impl StatisticsAccumulator {
     }
 
     pub fn acc_columns(data_block: &DataBlock) -> common_exception::Result<ColumnsStatistics> {
-        let mut statistics = ColumnsStatistics::new();
-
-        let rows = data_block.num_rows();
-        for idx in 0..data_block.num_columns() {
-            let col = data_block.column(idx);
-            let field = data_block.schema().field(idx);
-            let column_field = ColumnWithField::new(col.clone(), field.clone());
-
-            let mut min = DataValue::Null;
-            let mut max = DataValue::Null;
-
-            let mins = eval_aggr("min", vec![], &[column_field.clone()], rows)?;
-            let maxs = eval_aggr("max", vec![], &[column_field], rows)?;
-
-            if mins.len() > 0 {
-                min = mins.get(0);
-            }
-            if maxs.len() > 0 {
-                max = maxs.get(0);
-            }
-            let (is_all_null, bitmap) = col.validity();
-            let null_count = match (is_all_null, bitmap) {
-                (true, _) => rows,
-                (false, Some(bitmap)) => bitmap.null_count(),
-                (false, None) => 0,
-            };
-
-            let in_memory_size = col.memory_size() as u64;
-            let col_stats = ColumnStatistics {
-                min,
-                max,
-                null_count: null_count as u64,
-                in_memory_size,
-            };
-
-            statistics.insert(idx as u32, col_stats);
-        }
-        Ok(statistics)
+        BlockStatistics::columns_statistics(data_block, 0)
     }
 }
 
@@ -187,6 +189,7 @@ pub struct PartiallyAccumulated {
 }
 
 impl PartiallyAccumulated {
+    /// `col_metas` are keyed by the indices of the columns in the block
     pub fn end(
         mut self,
         file_size: u64,
@@ -200,9 +203,13 @@ impl PartiallyAccumulated {
             block_size: self.block_size,
             file_size,
             col_stats: self.block_columns_statistics,
-            col_metas,
+            col_metas: stats.column_ids.by_ids(col_metas),
             location: (location, DataBlock::VERSION),
             compression: Compression::Lz4Raw,
+            created_by: stats.created_by,
+            created_on: Some(Utc::now()),
+            deletion_vector: None,
+            virtual_block: None,
         };
         stats.blocks_metas.push(block_meta);
         self.accumulator
@@ -217,16 +224,25 @@ pub struct BlockStatistics {
 }
 
 impl BlockStatistics {
-    pub fn from(data_block: &DataBlock, location: String) -> Result<BlockStatistics> {
+    pub fn from(
+        data_block: &DataBlock,
+        location: String,
+        string_prefix_length: usize,
+    ) -> Result<BlockStatistics> {
         Ok(BlockStatistics {
             block_file_location: location,
             block_rows_size: data_block.num_rows() as u64,
             block_bytes_size: data_block.memory_size() as u64,
-            block_column_statistics: Self::columns_statistics(data_block)?,
+            block_column_statistics: Self::columns_statistics(data_block, string_prefix_length)?,
         })
     }
 
-    pub fn columns_statistics(data_block: &DataBlock) -> Result<ColumnsStatistics> {
+    /// The min and the max of the strings longer than `string_prefix_length` bytes are
+    /// truncated to their prefixes, see [truncate_min] and [truncate_max]
+    pub fn columns_statistics(
+        data_block: &DataBlock,
+        string_prefix_length: usize,
+    ) -> Result<ColumnsStatistics> {
         let mut statistics = ColumnsStatistics::new();
 
         let rows = data_block.num_rows();
@@ -239,7 +255,7 @@ impl BlockStatistics {
             let mut max = DataValue::Null;
 
             let mins = eval_aggr("min", vec![], &[column_field.clone()], rows)?;
-            let maxs = eval_aggr("max", vec![], &[column_field], rows)?;
+            let maxs = eval_aggr("max", vec![], &[column_field.clone()], rows)?;
 
             if mins.len() > 0 {
                 min = mins.get(0);
@@ -247,6 +263,13 @@ impl BlockStatistics {
             if maxs.len() > 0 {
                 max = maxs.get(0);
             }
+            let (min, min_truncated) = truncate_min(min, string_prefix_length);
+            let (max, max_truncated) = truncate_max(max, string_prefix_length);
+            // a hint only, left unknown for the types which values can not be hashed
+            let distinct_of_values = eval_aggr("uniq", vec![], &[column_field], rows)
+                .ok()
+                .filter(|c| c.len() > 0)
+                .and_then(|c| c.get(0).as_u64().ok());
             let (is_all_null, bitmap) = col.validity();
             let null_count = match (is_all_null, bitmap) {
                 (true, _) => rows,
@@ -260,6 +283,8 @@ impl BlockStatistics {
                 max,
                 null_count: null_count as u64,
                 in_memory_size,
+                distinct_of_values,
+                truncated: min_truncated || max_truncated,
             };
 
             statistics.insert(idx as u32, col_stats);
@@ -267,3 +292,34 @@ impl BlockStatistics {
         Ok(statistics)
     }
 }
+
+/// Truncates a string to its prefix of `prefix_length` bytes, which is still a lower bound
+/// of the string. Returns whether the string is truncated.
+pub fn truncate_min(min: DataValue, prefix_length: usize) -> (DataValue, bool) {
+    match min {
+        DataValue::String(v) if prefix_length > 0 && v.len() > prefix_length => {
+            (DataValue::String(v[..prefix_length].to_vec()), true)
+        }
+        v => (v, false),
+    }
+}
+
+/// Truncates a string to its prefix of `prefix_length` bytes, with the last byte which is
+/// less than 0xFF increased by one, which is an upper bound of the string. The string is
+/// kept as it is if all the bytes of the prefix are 0xFF. Returns whether the string is
+/// truncated.
+pub fn truncate_max(max: DataValue, prefix_length: usize) -> (DataValue, bool) {
+    match max {
+        DataValue::String(v) if prefix_length > 0 && v.len() > prefix_length => {
+            match v[..prefix_length].iter().rposition(|b| *b < u8::MAX) {
+                Some(pos) => {
+                    let mut prefix = v[..=pos].to_vec();
+                    prefix[pos] += 1;
+                    (DataValue::String(prefix), true)
+                }
+                None => (DataValue::String(v), false),
+            }
+        }
+        v => (v, false),
+    }
+}
@@ -0,0 +1,283 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::cmp::Ordering;
+
+use common_datavalues::remove_nullable;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_datavalues::TypeID;
+use rand::Rng;
+
+use crate::storages::fuse::meta::ColumnHistogram;
+use crate::storages::fuse::meta::ColumnId;
+use crate::storages::fuse::meta::HistogramBucket;
+use crate::storages::fuse::meta::MostCommonValue;
+use crate::storages::fuse::meta::TableSnapshotStatistics;
+
+pub const DEFAULT_HISTOGRAM_BUCKETS: usize = 100;
+pub const DEFAULT_MOST_COMMON_VALUES: usize = 10;
+pub const DEFAULT_SAMPLE_SIZE: usize = 100_000;
+
+/// Builds the [ColumnHistogram] of a column, from a uniform sample of its values.
+///
+/// At most `sample_size` non-null values are kept in memory (reservoir sampling), the counts of
+/// the histogram are scaled up if not all the values are sampled.
+pub struct ColumnHistogramBuilder {
+    sample_size: usize,
+    null_count: u64,
+    non_null_count: u64,
+    sample: Vec<DataValue>,
+}
+
+impl ColumnHistogramBuilder {
+    pub fn new(sample_size: usize) -> Self {
+        Self {
+            sample_size: std::cmp::max(sample_size, 1),
+            null_count: 0,
+            non_null_count: 0,
+            sample: vec![],
+        }
+    }
+
+    /// Histograms are only built for columns of which the values could be ordered.
+    pub fn is_supported(data_type: &DataTypeImpl) -> bool {
+        let type_id = remove_nullable(data_type).data_type_id();
+        type_id.is_numeric()
+            || type_id.is_string()
+            || type_id.is_date_or_date_time()
+            || matches!(type_id, TypeID::Boolean)
+    }
+
+    pub fn append(&mut self, column: &ColumnRef) {
+        for row in 0..column.len() {
+            self.add(column.get(row));
+        }
+    }
+
+    fn add(&mut self, value: DataValue) {
+        if matches!(value, DataValue::Null) {
+            self.null_count += 1;
+            return;
+        }
+
+        self.non_null_count += 1;
+        if self.sample.len() < self.sample_size {
+            self.sample.push(value);
+        } else {
+            let idx = rand::thread_rng().gen_range(0..self.non_null_count) as usize;
+            if idx < self.sample_size {
+                self.sample[idx] = value;
+            }
+        }
+    }
+
+    pub fn sampled_count(&self) -> u64 {
+        self.sample.len() as u64
+    }
+
+    pub fn finish(self, num_buckets: usize, num_most_common_values: usize) -> ColumnHistogram {
+        let mut sample = self.sample;
+        if sample.is_empty() {
+            return ColumnHistogram {
+                null_count: self.null_count,
+                ..Default::default()
+            };
+        }
+        sample.sort_by(|a, b| compare_values(a, b).unwrap_or(Ordering::Equal));
+
+        // runs of the same values, in ascending order of the values
+        let mut runs: Vec<(DataValue, u64)> = vec![];
+        for value in sample {
+            match runs.last_mut() {
+                Some((last, count)) if *last == value => *count += 1,
+                _ => runs.push((value, 1)),
+            }
+        }
+
+        let sampled = runs.iter().map(|(_, c)| *c).sum::<u64>();
+        let scale = self.non_null_count as f64 / sampled as f64;
+        let scaled = |count: u64| (count as f64 * scale).round() as u64;
+
+        // the values appear only once in the sample are not regarded as common ones
+        let mut most_common: Vec<&(DataValue, u64)> = runs.iter().filter(|(_, c)| *c > 1).collect();
+        most_common.sort_by(|a, b| b.1.cmp(&a.1));
+        let most_common_values = most_common
+            .into_iter()
+            .take(num_most_common_values)
+            .map(|(value, count)| MostCommonValue {
+                value: value.clone(),
+                count: scaled(*count),
+            })
+            .collect();
+
+        // equi-height buckets, a value never spans more than one bucket
+        let bucket_height = (sampled as f64 / std::cmp::max(num_buckets, 1) as f64).ceil() as u64;
+        let mut buckets = vec![];
+        let mut current: Option<(usize, usize, u64)> = None;
+        for (idx, (_, count)) in runs.iter().enumerate() {
+            let (start, _, height) = current.unwrap_or((idx, idx, 0));
+            let height = height + count;
+            if height >= bucket_height {
+                buckets.push(Self::bucket(&runs[start..=idx], height, scale));
+                current = None;
+            } else {
+                current = Some((start, idx, height));
+            }
+        }
+        if let Some((start, end, height)) = current {
+            buckets.push(Self::bucket(&runs[start..=end], height, scale));
+        }
+
+        ColumnHistogram {
+            null_count: self.null_count,
+            distinct_count: Self::estimate_distinct(&runs, sampled, self.non_null_count),
+            most_common_values,
+            buckets,
+        }
+    }
+
+    fn bucket(runs: &[(DataValue, u64)], height: u64, scale: f64) -> HistogramBucket {
+        let distinct_in_sample = runs.len() as u64;
+        // distinct values are scaled up as well, but never more than the number of values
+        let count = (height as f64 * scale).round() as u64;
+        let distinct_count = std::cmp::min(
+            count,
+            (distinct_in_sample as f64 * scale.sqrt()).round() as u64,
+        );
+        HistogramBucket {
+            lower_bound: runs[0].0.clone(),
+            upper_bound: runs[runs.len() - 1].0.clone(),
+            count,
+            distinct_count: std::cmp::max(distinct_count, 1),
+        }
+    }
+
+    /// The GEE estimator of the number of distinct values: sqrt(N/n) * f1 + (f2 + f3 + ...),
+    /// where f1 is the number of values which appear exactly once in the sample.
+    fn estimate_distinct(runs: &[(DataValue, u64)], sampled: u64, total: u64) -> u64 {
+        if sampled >= total {
+            return runs.len() as u64;
+        }
+        let singletons = runs.iter().filter(|(_, c)| *c == 1).count() as f64;
+        let others = runs.len() as f64 - singletons;
+        let estimated = (total as f64 / sampled as f64).sqrt() * singletons + others;
+        std::cmp::min(estimated.round() as u64, total)
+    }
+}
+
+/// Compares values of the same type, returns None if the values are not comparable.
+pub fn compare_values(l: &DataValue, r: &DataValue) -> Option<Ordering> {
+    match (l, r) {
+        (DataValue::Boolean(l), DataValue::Boolean(r)) => Some(l.cmp(r)),
+        (DataValue::Int64(l), DataValue::Int64(r)) => Some(l.cmp(r)),
+        (DataValue::UInt64(l), DataValue::UInt64(r)) => Some(l.cmp(r)),
+        (DataValue::Float64(l), DataValue::Float64(r)) => l.partial_cmp(r),
+        (DataValue::String(l), DataValue::String(r)) => Some(l.cmp(r)),
+        _ => None,
+    }
+}
+
+/// Estimations of selectivities, for the cardinality estimator of the planner.
+///
+/// Selectivities are fractions of the rows of the table, None if the column has no histogram.
+impl TableSnapshotStatistics {
+    pub fn column_histogram(&self, column_id: ColumnId) -> Option<&ColumnHistogram> {
+        self.column_histograms.get(&column_id)
+    }
+
+    /// Selectivity of `column = value`
+    pub fn selectivity_of_eq(&self, column_id: ColumnId, value: &DataValue) -> Option<f64> {
+        let histogram = self.column_histogram(column_id)?;
+        Some(self.fraction(histogram.estimate_eq(value)))
+    }
+
+    /// Selectivity of `lower <= column AND column <= upper`, an absent bound is unbounded
+    pub fn selectivity_of_range(
+        &self,
+        column_id: ColumnId,
+        lower: Option<&DataValue>,
+        upper: Option<&DataValue>,
+    ) -> Option<f64> {
+        let histogram = self.column_histogram(column_id)?;
+        Some(self.fraction(histogram.estimate_range(lower, upper)))
+    }
+
+    /// Selectivity of `column IS NULL`
+    pub fn selectivity_of_null(&self, column_id: ColumnId) -> Option<f64> {
+        let histogram = self.column_histogram(column_id)?;
+        Some(self.fraction(histogram.null_count as f64))
+    }
+
+    fn fraction(&self, rows: f64) -> f64 {
+        if self.row_count == 0 {
+            0.0
+        } else {
+            (rows / self.row_count as f64).clamp(0.0, 1.0)
+        }
+    }
+}
+
+impl ColumnHistogram {
+    /// Estimated number of rows of which the value equals to `value`
+    pub fn estimate_eq(&self, value: &DataValue) -> f64 {
+        if let Some(mcv) = self.most_common_values.iter().find(|v| &v.value == value) {
+            return mcv.count as f64;
+        }
+        // the value is assumed to be one of the distinct values of the bucket it falls in
+        self.buckets
+            .iter()
+            .find(|b| Self::bucket_contains(b, value))
+            .map(|b| b.count as f64 / b.distinct_count.max(1) as f64)
+            .unwrap_or(0.0)
+    }
+
+    /// Estimated number of rows of which the value is within `[lower, upper]`
+    pub fn estimate_range(&self, lower: Option<&DataValue>, upper: Option<&DataValue>) -> f64 {
+        self.buckets
+            .iter()
+            .map(|b| {
+                let below_lower = lower.map_or(false, |l| {
+                    compare_values(&b.upper_bound, l) == Some(Ordering::Less)
+                });
+                let above_upper = upper.map_or(false, |u| {
+                    compare_values(&b.lower_bound, u) == Some(Ordering::Greater)
+                });
+                if below_lower || above_upper {
+                    return 0.0;
+                }
+                let covers_lower = lower.map_or(true, |l| {
+                    compare_values(&b.lower_bound, l) != Some(Ordering::Less)
+                });
+                let covers_upper = upper.map_or(true, |u| {
+                    compare_values(&b.upper_bound, u) != Some(Ordering::Greater)
+                });
+                match (covers_lower, covers_upper) {
+                    (true, true) => b.count as f64,
+                    // the bucket is partially covered, half of it is assumed to be in range
+                    (true, false) | (false, true) => b.count as f64 / 2.0,
+                    (false, false) => b.count as f64 / 3.0,
+                }
+            })
+            .sum()
+    }
+
+    fn bucket_contains(bucket: &HistogramBucket, value: &DataValue) -> bool {
+        compare_values(&bucket.lower_bound, value).map_or(false, |o| o != Ordering::Greater)
+            && compare_values(&bucket.upper_bound, value).map_or(false, |o| o != Ordering::Less)
+    }
+}
@@ -13,9 +13,11 @@
 //  limitations under the License.
 
 pub mod accumulator;
+pub mod histogram;
 pub mod reducers;
 
 pub use accumulator::PartiallyAccumulated;
 pub use accumulator::StatisticsAccumulator;
+pub use histogram::ColumnHistogramBuilder;
 pub use reducers::merge_statistics;
 pub use reducers::reduce_block_stats;
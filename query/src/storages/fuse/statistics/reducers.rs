@@ -26,6 +26,7 @@ use common_functions::aggregates::eval_aggr;
 
 use crate::storages::fuse::meta::ColumnId;
 use crate::storages::fuse::meta::Statistics;
+use crate::storages::fuse::ColumnIds;
 use crate::storages::index::ColumnStatistics;
 use crate::storages::index::ColumnsStatistics;
 
@@ -34,6 +35,7 @@ pub fn reduce_block_stats<T: Borrow<ColumnsStatistics>>(
     schema: &DataSchema,
 ) -> Result<ColumnsStatistics> {
     let len = stats.len();
+    let column_ids = ColumnIds::from_schema(schema);
 
     // transpose Vec<HashMap<_,(_,_)>> to HashMap<_, (_, Vec<_>)>
     let col_stat_list = stats.iter().fold(HashMap::new(), |acc, item| {
@@ -57,10 +59,18 @@ pub fn reduce_block_stats<T: Borrow<ColumnsStatistics>>(
     col_stat_list
         .iter()
         .try_fold(HashMap::with_capacity(len), |mut acc, (id, stats)| {
+            // the statistics of the dropped columns are left out
+            let field = match column_ids.index_of(*id) {
+                Some(index) => schema.field(index),
+                None => return Ok(acc),
+            };
+
             let mut min_stats = Vec::with_capacity(stats.len());
             let mut max_stats = Vec::with_capacity(stats.len());
-            let mut null_count = 0;
-            let mut in_memory_size = 0;
+            let mut null_count: u64 = 0;
+            let mut in_memory_size: u64 = 0;
+            let mut distinct_of_values = Some(0u64);
+            let mut truncated = false;
 
             for col_stats in stats {
                 // to be optimized, with DataType and the value of data, we may
@@ -68,17 +78,20 @@ pub fn reduce_block_stats<T: Borrow<ColumnsStatistics>>(
                 min_stats.push(col_stats.min.clone());
                 max_stats.push(col_stats.max.clone());
 
-                null_count += col_stats.null_count;
-                in_memory_size += col_stats.in_memory_size;
+                null_count = null_count.saturating_add(col_stats.null_count);
+                in_memory_size = in_memory_size.saturating_add(col_stats.in_memory_size);
+                // the blocks may share values, the sum of them is an upper bound only
+                distinct_of_values = distinct_of_values
+                    .zip(col_stats.distinct_of_values)
+                    .map(|(acc, n)| acc.saturating_add(n));
+                truncated |= col_stats.truncated;
             }
 
-            // TODO panic
-            let data_type = schema.field((*id) as usize).data_type();
+            let data_type = field.data_type();
 
             let mut min = DataValue::Null;
             let mut max = DataValue::Null;
 
-            let field = schema.field((*id) as usize);
             // TODO
             // for some data types, we shall balance the accuracy and the length
             // e.g. for a string col, which max value is "abcdef....", we record the max as something like "b"
@@ -110,18 +123,13 @@ pub fn reduce_block_stats<T: Borrow<ColumnsStatistics>>(
                 max,
                 null_count,
                 in_memory_size,
+                distinct_of_values,
+                truncated,
             });
             Ok(acc)
         })
 }
 
 pub fn merge_statistics(schema: &DataSchema, l: &Statistics, r: &Statistics) -> Result<Statistics> {
-    let s = Statistics {
-        row_count: l.row_count + r.row_count,
-        block_count: l.block_count + r.block_count,
-        uncompressed_byte_size: l.uncompressed_byte_size + r.uncompressed_byte_size,
-        compressed_byte_size: l.compressed_byte_size + r.compressed_byte_size,
-        col_stats: reduce_block_stats(&[&l.col_stats, &r.col_stats], schema)?,
-    };
-    Ok(s)
+    l.merge(r, schema)
 }
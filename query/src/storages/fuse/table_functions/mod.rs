@@ -13,8 +13,20 @@
 //  limitations under the License.
 //
 
+mod clustering_information_table;
+mod fuse_block_table;
 mod fuse_history_table;
+mod fuse_snapshot_diff_table;
+mod fuse_verify_table;
 mod table_arg_util;
 
+pub use clustering_information_table::ClusteringInformationTable;
+pub use clustering_information_table::FUSE_FUNC_CLUSTERING;
+pub use fuse_block_table::FuseBlockTable;
+pub use fuse_block_table::FUSE_FUNC_BLOCK;
 pub use fuse_history_table::FuseHistoryTable;
 pub use fuse_history_table::FUSE_FUNC_HIST;
+pub use fuse_snapshot_diff_table::FuseSnapshotDiffTable;
+pub use fuse_snapshot_diff_table::FUSE_FUNC_SNAPSHOT_DIFF;
+pub use fuse_verify_table::FuseVerifyTable;
+pub use fuse_verify_table::FUSE_FUNC_VERIFY;
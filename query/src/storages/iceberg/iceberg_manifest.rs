@@ -0,0 +1,301 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt;
+
+use avro_rs::Reader;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde::de::DeserializeOwned;
+use serde::de::Error;
+use serde::de::SeqAccess;
+use serde::de::Visitor;
+use serde::Deserialize;
+use serde::Deserializer;
+
+/// The status of the manifest entries of the files removed by the snapshot.
+const ENTRY_STATUS_DELETED: i32 = 2;
+/// The content of the manifests and files of the data, the others are of the deletes.
+pub const CONTENT_DATA: i32 = 0;
+
+/// A manifest of the manifest list of a snapshot.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ManifestFile {
+    pub manifest_path: String,
+    #[serde(default)]
+    pub partition_spec_id: i32,
+    /// Of the format version 2 only, the manifests of the format version 1 are of the data.
+    #[serde(default)]
+    pub content: i32,
+    #[serde(default, alias = "added_files_count")]
+    pub added_data_files_count: Option<i32>,
+    #[serde(default, alias = "existing_files_count")]
+    pub existing_data_files_count: Option<i32>,
+    /// The summaries of the partition values, in the order of the fields of the partition spec.
+    #[serde(default)]
+    pub partitions: Option<Vec<FieldSummary>>,
+}
+
+impl ManifestFile {
+    /// The manifest listed in the metadata by the format version 1.
+    pub fn create(manifest_path: String, partition_spec_id: i32) -> ManifestFile {
+        ManifestFile {
+            manifest_path,
+            partition_spec_id,
+            content: CONTENT_DATA,
+            added_data_files_count: None,
+            existing_data_files_count: None,
+            partitions: None,
+        }
+    }
+
+    /// The number of the live files, None if it is not kept.
+    pub fn files_count(&self) -> Option<usize> {
+        match (self.added_data_files_count, self.existing_data_files_count) {
+            (Some(added), Some(existing)) => Some((added + existing) as usize),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct FieldSummary {
+    pub contains_null: bool,
+    #[serde(default)]
+    pub lower_bound: Option<AvroBytes>,
+    #[serde(default)]
+    pub upper_bound: Option<AvroBytes>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ManifestEntry {
+    pub status: i32,
+    pub data_file: DataFile,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct DataFile {
+    #[serde(default)]
+    pub content: i32,
+    pub file_path: String,
+    pub file_format: String,
+    /// The partition values, by the names of the fields of the partition spec.
+    pub partition: BTreeMap<String, AvroScalar>,
+    pub record_count: i64,
+    pub file_size_in_bytes: i64,
+    #[serde(default)]
+    pub null_value_counts: Option<Vec<KeyValue<i64>>>,
+    #[serde(default)]
+    pub lower_bounds: Option<Vec<KeyValue<AvroBytes>>>,
+    #[serde(default)]
+    pub upper_bounds: Option<Vec<KeyValue<AvroBytes>>>,
+}
+
+impl DataFile {
+    pub fn null_value_counts(&self) -> HashMap<i32, i64> {
+        Self::by_field_ids(&self.null_value_counts, |count| *count)
+    }
+
+    pub fn lower_bounds(&self) -> HashMap<i32, &[u8]> {
+        Self::by_field_ids(&self.lower_bounds, |bound| bound.0.as_slice())
+    }
+
+    pub fn upper_bounds(&self) -> HashMap<i32, &[u8]> {
+        Self::by_field_ids(&self.upper_bounds, |bound| bound.0.as_slice())
+    }
+
+    fn by_field_ids<'a, T, V>(
+        values: &'a Option<Vec<KeyValue<T>>>,
+        f: impl Fn(&'a T) -> V,
+    ) -> HashMap<i32, V> {
+        values
+            .iter()
+            .flatten()
+            .map(|kv| (kv.key, f(&kv.value)))
+            .collect()
+    }
+}
+
+/// The entry of a map keyed by the ids of the fields, which Iceberg keeps as an array.
+#[derive(Deserialize, Debug, Clone)]
+pub struct KeyValue<T> {
+    pub key: i32,
+    pub value: T,
+}
+
+/// The bytes of Avro, which may be deserialized as bytes or as a sequence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AvroBytes(pub Vec<u8>);
+
+impl<'de> Deserialize<'de> for AvroBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = AvroBytes;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("bytes")
+            }
+
+            fn visit_bytes<E: Error>(self, v: &[u8]) -> std::result::Result<AvroBytes, E> {
+                Ok(AvroBytes(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> std::result::Result<AvroBytes, E> {
+                Ok(AvroBytes(v))
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> std::result::Result<AvroBytes, E> {
+                Ok(AvroBytes(v.as_bytes().to_vec()))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<AvroBytes, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+                Ok(AvroBytes(bytes))
+            }
+        }
+
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+/// A partition value, of which the type is told by the partition spec.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AvroScalar {
+    Null,
+    Boolean(bool),
+    Long(i64),
+    Double(f64),
+    Bytes(Vec<u8>),
+}
+
+impl AvroScalar {
+    /// None for the null values.
+    pub fn to_data_value(&self) -> Option<DataValue> {
+        match self {
+            AvroScalar::Null => None,
+            AvroScalar::Boolean(v) => Some(DataValue::Boolean(*v)),
+            AvroScalar::Long(v) => Some(DataValue::Int64(*v)),
+            AvroScalar::Double(v) => Some(DataValue::Float64(*v)),
+            AvroScalar::Bytes(v) => Some(DataValue::String(v.clone())),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AvroScalar {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct ScalarVisitor;
+
+        impl<'de> Visitor<'de> for ScalarVisitor {
+            type Value = AvroScalar;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a primitive value")
+            }
+
+            fn visit_unit<E: Error>(self) -> std::result::Result<AvroScalar, E> {
+                Ok(AvroScalar::Null)
+            }
+
+            fn visit_none<E: Error>(self) -> std::result::Result<AvroScalar, E> {
+                Ok(AvroScalar::Null)
+            }
+
+            fn visit_some<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> std::result::Result<AvroScalar, D::Error> {
+                AvroScalar::deserialize(deserializer)
+            }
+
+            fn visit_bool<E: Error>(self, v: bool) -> std::result::Result<AvroScalar, E> {
+                Ok(AvroScalar::Boolean(v))
+            }
+
+            fn visit_i64<E: Error>(self, v: i64) -> std::result::Result<AvroScalar, E> {
+                Ok(AvroScalar::Long(v))
+            }
+
+            fn visit_u64<E: Error>(self, v: u64) -> std::result::Result<AvroScalar, E> {
+                Ok(AvroScalar::Long(v as i64))
+            }
+
+            fn visit_f64<E: Error>(self, v: f64) -> std::result::Result<AvroScalar, E> {
+                Ok(AvroScalar::Double(v))
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> std::result::Result<AvroScalar, E> {
+                Ok(AvroScalar::Bytes(v.as_bytes().to_vec()))
+            }
+
+            fn visit_bytes<E: Error>(self, v: &[u8]) -> std::result::Result<AvroScalar, E> {
+                Ok(AvroScalar::Bytes(v.to_vec()))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<AvroScalar, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+                Ok(AvroScalar::Bytes(bytes))
+            }
+        }
+
+        deserializer.deserialize_any(ScalarVisitor)
+    }
+}
+
+/// Reads the manifests of a manifest list.
+pub fn read_manifest_list(bytes: &[u8]) -> Result<Vec<ManifestFile>> {
+    read_records(bytes)
+}
+
+/// Reads the live data files of a manifest, the ones removed by the snapshot are skipped.
+pub fn read_manifest(bytes: &[u8]) -> Result<Vec<DataFile>> {
+    let entries: Vec<ManifestEntry> = read_records(bytes)?;
+    Ok(entries
+        .into_iter()
+        .filter(|entry| entry.status != ENTRY_STATUS_DELETED)
+        .map(|entry| entry.data_file)
+        .collect())
+}
+
+fn read_records<T: DeserializeOwned>(bytes: &[u8]) -> Result<Vec<T>> {
+    let reader = Reader::new(bytes).map_err(avro_error)?;
+    let mut records = vec![];
+    for value in reader {
+        // through JSON, since the deserializer of avro-rs can't take the unions of strings
+        // or of bytes, e.g. the optional partition values
+        let value = serde_json::Value::try_from(value.map_err(avro_error)?).map_err(avro_error)?;
+        records.push(serde_json::from_value(value)?);
+    }
+    Ok(records)
+}
+
+fn avro_error(cause: avro_rs::Error) -> ErrorCode {
+    ErrorCode::BadBytes(format!("Cannot read the Avro file of Iceberg: {}", cause))
+}
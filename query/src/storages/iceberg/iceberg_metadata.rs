@@ -0,0 +1,313 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+const MICROS_PER_HOUR: i64 = 3_600_000_000;
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// The metadata file of an Iceberg table, of format version 1 or 2.
+///
+/// Only the fields needed to read the current snapshot are kept.
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableMetadata {
+    pub format_version: i32,
+    /// The base location of the table, which the paths of the files start with.
+    pub location: String,
+    /// The schema of the format version 1.
+    #[serde(default)]
+    pub schema: Option<IcebergSchema>,
+    #[serde(default)]
+    pub schemas: Vec<IcebergSchema>,
+    #[serde(default)]
+    pub current_schema_id: Option<i32>,
+    /// The fields of the partition spec of the format version 1.
+    #[serde(default)]
+    pub partition_spec: Vec<PartitionField>,
+    #[serde(default)]
+    pub partition_specs: Vec<PartitionSpec>,
+    #[serde(default)]
+    pub default_spec_id: Option<i32>,
+    #[serde(default)]
+    pub current_snapshot_id: Option<i64>,
+    #[serde(default)]
+    pub snapshots: Vec<Snapshot>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct IcebergSchema {
+    #[serde(default)]
+    pub schema_id: i32,
+    pub fields: Vec<IcebergField>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct IcebergField {
+    pub id: i32,
+    pub name: String,
+    pub required: bool,
+    /// The name of a primitive type, or the object of a nested type.
+    #[serde(rename = "type")]
+    pub field_type: serde_json::Value,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct PartitionSpec {
+    pub spec_id: i32,
+    pub fields: Vec<PartitionField>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct PartitionField {
+    pub source_id: i32,
+    pub name: String,
+    pub transform: String,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct Snapshot {
+    pub snapshot_id: i64,
+    #[serde(default)]
+    pub manifest_list: Option<String>,
+    /// The manifests listed in the metadata by the format version 1, without a manifest list.
+    #[serde(default)]
+    pub manifests: Vec<String>,
+}
+
+impl TableMetadata {
+    pub fn current_schema(&self) -> Result<&IcebergSchema> {
+        let schema = match self.current_schema_id {
+            Some(id) => self.schemas.iter().find(|schema| schema.schema_id == id),
+            None => self.schema.as_ref().or_else(|| self.schemas.last()),
+        };
+        schema.ok_or_else(|| ErrorCode::BadBytes("The current schema of Iceberg table is missing"))
+    }
+
+    /// The snapshot to read, None if the table has not been written.
+    pub fn current_snapshot(&self) -> Result<Option<&Snapshot>> {
+        match self.current_snapshot_id {
+            None | Some(-1) => Ok(None),
+            Some(id) => match self
+                .snapshots
+                .iter()
+                .find(|snapshot| snapshot.snapshot_id == id)
+            {
+                Some(snapshot) => Ok(Some(snapshot)),
+                None => Err(ErrorCode::BadBytes(format!(
+                    "The current snapshot {} of Iceberg table is missing",
+                    id
+                ))),
+            },
+        }
+    }
+
+    /// The fields of the partition specs, by the ids of the specs.
+    pub fn partition_specs(&self) -> HashMap<i32, Vec<PartitionField>> {
+        let mut specs = HashMap::with_capacity(self.partition_specs.len() + 1);
+        if self.partition_specs.is_empty() {
+            specs.insert(0, self.partition_spec.clone());
+        }
+        for spec in &self.partition_specs {
+            specs.insert(spec.spec_id, spec.fields.clone());
+        }
+        specs
+    }
+
+    pub fn default_spec_id(&self) -> i32 {
+        self.default_spec_id.unwrap_or(0)
+    }
+
+    /// Maps the current schema to the one of the table, the optional fields are nullable.
+    pub fn data_schema(&self) -> Result<DataSchemaRef> {
+        let schema = self.current_schema()?;
+        let mut fields = Vec::with_capacity(schema.fields.len());
+        for field in &schema.fields {
+            let data_type = IcebergType::try_create(&field.field_type)?.data_type();
+            fields.push(match field.required {
+                true => DataField::new(&field.name, data_type),
+                false => DataField::new_nullable(&field.name, data_type),
+            });
+        }
+        Ok(DataSchemaRefExt::create(fields))
+    }
+}
+
+/// The primitive types of Iceberg which can be read.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IcebergType {
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Date,
+    /// In microseconds, with or without the time zone.
+    Timestamp,
+    String,
+    /// The binary, fixed and uuid types.
+    Binary,
+}
+
+impl IcebergType {
+    pub fn try_create(field_type: &serde_json::Value) -> Result<IcebergType> {
+        let name = match field_type {
+            serde_json::Value::String(name) => name.as_str(),
+            other => {
+                return Err(ErrorCode::UnImplement(format!(
+                    "Unsupported Iceberg type: {}",
+                    other
+                )));
+            }
+        };
+        match name {
+            "boolean" => Ok(IcebergType::Boolean),
+            "int" => Ok(IcebergType::Int),
+            "long" => Ok(IcebergType::Long),
+            "float" => Ok(IcebergType::Float),
+            "double" => Ok(IcebergType::Double),
+            "date" => Ok(IcebergType::Date),
+            "timestamp" | "timestamptz" => Ok(IcebergType::Timestamp),
+            "string" => Ok(IcebergType::String),
+            "binary" | "uuid" => Ok(IcebergType::Binary),
+            _ if name.starts_with("fixed[") => Ok(IcebergType::Binary),
+            _ => Err(ErrorCode::UnImplement(format!(
+                "Unsupported Iceberg type: {}",
+                name
+            ))),
+        }
+    }
+
+    pub fn data_type(&self) -> DataTypeImpl {
+        match self {
+            IcebergType::Boolean => BooleanType::new_impl(),
+            IcebergType::Int => Int32Type::new_impl(),
+            IcebergType::Long => Int64Type::new_impl(),
+            IcebergType::Float => Float32Type::new_impl(),
+            IcebergType::Double => Float64Type::new_impl(),
+            IcebergType::Date => DateType::new_impl(),
+            IcebergType::Timestamp => TimestampType::new_impl(6),
+            IcebergType::String | IcebergType::Binary => StringType::new_impl(),
+        }
+    }
+
+    /// Decodes the value of the single-value serialization, which the bounds of the columns and
+    /// the partitions are kept in.
+    pub fn decode(&self, bytes: &[u8]) -> Option<DataValue> {
+        match self {
+            IcebergType::Boolean => bytes.first().map(|v| DataValue::Boolean(*v != 0)),
+            IcebergType::Int | IcebergType::Date => {
+                let bytes = bytes.try_into().ok()?;
+                Some(DataValue::Int64(i32::from_le_bytes(bytes) as i64))
+            }
+            IcebergType::Long | IcebergType::Timestamp => {
+                let bytes = bytes.try_into().ok()?;
+                Some(DataValue::Int64(i64::from_le_bytes(bytes)))
+            }
+            IcebergType::Float => {
+                let bytes = bytes.try_into().ok()?;
+                Some(DataValue::Float64(f32::from_le_bytes(bytes) as f64))
+            }
+            IcebergType::Double => {
+                let bytes = bytes.try_into().ok()?;
+                Some(DataValue::Float64(f64::from_le_bytes(bytes)))
+            }
+            IcebergType::String | IcebergType::Binary => Some(DataValue::String(bytes.to_vec())),
+        }
+    }
+}
+
+impl PartitionField {
+    /// The type of the partition values, of which the source column is of `source`.
+    pub fn result_type(&self, source: &IcebergType) -> IcebergType {
+        match self.transform.as_str() {
+            "year" | "month" | "day" | "hour" => IcebergType::Int,
+            transform if transform.starts_with("bucket[") => IcebergType::Int,
+            _ => source.clone(),
+        }
+    }
+
+    /// The range of the source column, of which the partition values are in [min, max]. None if
+    /// the transform does not keep the order, such as `bucket`.
+    pub fn source_range(
+        &self,
+        source: &IcebergType,
+        min: DataValue,
+        max: DataValue,
+    ) -> Option<(DataValue, DataValue)> {
+        let transform = self.transform.as_str();
+        if transform == "identity" {
+            return Some((min, max));
+        }
+
+        let (min, max) = match (min, max) {
+            (DataValue::Int64(min), DataValue::Int64(max)) => (min, max),
+            _ => return None,
+        };
+        if let Some(width) = transform
+            .strip_prefix("truncate[")
+            .and_then(|width| width.strip_suffix(']'))
+        {
+            let width = width.parse::<i64>().ok()?;
+            return match source {
+                IcebergType::Int | IcebergType::Long => {
+                    Some((DataValue::Int64(min), DataValue::Int64(max + width - 1)))
+                }
+                _ => None,
+            };
+        }
+
+        // The partitions of the time transforms are the ordinals since 1970-01-01.
+        let (start, end) = match (transform, source) {
+            ("hour", IcebergType::Timestamp) => {
+                return Some((
+                    DataValue::Int64(min * MICROS_PER_HOUR),
+                    DataValue::Int64((max + 1) * MICROS_PER_HOUR - 1),
+                ));
+            }
+            ("day", _) => (min, max + 1),
+            ("month", _) => (month_start_days(min)?, month_start_days(max + 1)?),
+            ("year", _) => (
+                month_start_days(min * 12)?,
+                month_start_days((max + 1) * 12)?,
+            ),
+            _ => return None,
+        };
+        match source {
+            IcebergType::Date => Some((DataValue::Int64(start), DataValue::Int64(end - 1))),
+            IcebergType::Timestamp => Some((
+                DataValue::Int64(start * MICROS_PER_DAY),
+                DataValue::Int64(end * MICROS_PER_DAY - 1),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// The days since 1970-01-01 of the first day of the month, `months` months since 1970-01.
+fn month_start_days(months: i64) -> Option<i64> {
+    let year = i32::try_from(1970 + months.div_euclid(12)).ok()?;
+    let month = months.rem_euclid(12) as u32 + 1;
+    let date = NaiveDate::from_ymd_opt(year, month, 1)?;
+    Some((date - NaiveDate::from_ymd(1970, 1, 1)).num_days())
+}
@@ -0,0 +1,63 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::PartInfo;
+use common_planners::PartInfoPtr;
+
+/// A data file of the Iceberg table to read.
+#[derive(serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct IcebergPartInfo {
+    /// Relative to the root of the storage.
+    pub location: String,
+    pub record_count: u64,
+    pub file_size: u64,
+}
+
+#[typetag::serde(name = "iceberg")]
+impl PartInfo for IcebergPartInfo {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn equals(&self, info: &Box<dyn PartInfo>) -> bool {
+        match info.as_any().downcast_ref::<IcebergPartInfo>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+impl IcebergPartInfo {
+    pub fn create(location: String, record_count: u64, file_size: u64) -> PartInfoPtr {
+        Arc::new(Box::new(IcebergPartInfo {
+            location,
+            record_count,
+            file_size,
+        }))
+    }
+
+    pub fn from_part(info: &PartInfoPtr) -> Result<&IcebergPartInfo> {
+        match info.as_any().downcast_ref::<IcebergPartInfo>() {
+            Some(part_ref) => Ok(part_ref),
+            None => Err(ErrorCode::LogicalError(
+                "Cannot downcast from PartInfo to IcebergPartInfo.",
+            )),
+        }
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_planners::Extras;
+
+use crate::sessions::QueryContext;
+use crate::storages::iceberg::iceberg_manifest::DataFile;
+use crate::storages::iceberg::iceberg_manifest::ManifestFile;
+use crate::storages::iceberg::iceberg_metadata::IcebergType;
+use crate::storages::iceberg::iceberg_metadata::PartitionField;
+use crate::storages::iceberg::iceberg_metadata::TableMetadata;
+use crate::storages::index::ColumnStatistics;
+use crate::storages::index::ColumnsStatistics;
+use crate::storages::index::RangeFilter;
+
+/// A column of the table, and the field of it in the Iceberg schema.
+struct PrunedColumn {
+    index: u32,
+    field_id: i32,
+    field_type: IcebergType,
+}
+
+/// Prunes the manifests by the summaries of the partition values, and the data files by the
+/// bounds of the columns and the partition values, against the filter pushed down.
+///
+/// The bounds of the columns are preferred to the ones told by the partition values, which are
+/// only used for the columns without bounds.
+pub struct IcebergPruner {
+    filter: Option<RangeFilter>,
+    columns: Vec<PrunedColumn>,
+    specs: HashMap<i32, Vec<PartitionField>>,
+}
+
+impl IcebergPruner {
+    pub fn try_create(
+        ctx: &Arc<QueryContext>,
+        schema: DataSchemaRef,
+        metadata: &TableMetadata,
+        push_downs: &Option<Extras>,
+    ) -> Result<Self> {
+        let filter = match push_downs {
+            // for the time being, we only handle the first expr, as the fuse tables
+            Some(extras) if !extras.filters.is_empty() => Some(RangeFilter::try_create(
+                ctx.clone(),
+                &extras.filters[0],
+                schema.clone(),
+            )?),
+            _ => None,
+        };
+
+        let fields = &metadata.current_schema()?.fields;
+        let mut columns = Vec::with_capacity(schema.fields().len());
+        for (index, field) in schema.fields().iter().enumerate() {
+            let iceberg_field = match fields.iter().find(|f| &f.name == field.name()) {
+                Some(iceberg_field) => iceberg_field,
+                None => continue,
+            };
+            if let Ok(field_type) = IcebergType::try_create(&iceberg_field.field_type) {
+                columns.push(PrunedColumn {
+                    index: index as u32,
+                    field_id: iceberg_field.id,
+                    field_type,
+                });
+            }
+        }
+
+        Ok(IcebergPruner {
+            filter,
+            columns,
+            specs: metadata.partition_specs(),
+        })
+    }
+
+    fn column(&self, field_id: i32) -> Option<&PrunedColumn> {
+        self.columns.iter().find(|c| c.field_id == field_id)
+    }
+
+    pub fn keep_manifest(&self, manifest: &ManifestFile) -> Result<bool> {
+        let filter = match &self.filter {
+            None => return Ok(true),
+            Some(filter) => filter,
+        };
+        let fields = self.specs.get(&manifest.partition_spec_id);
+        let (fields, summaries) = match (fields, &manifest.partitions) {
+            (Some(fields), Some(summaries)) => (fields, summaries),
+            _ => return Ok(true),
+        };
+
+        let mut stats = ColumnsStatistics::new();
+        for (field, summary) in fields.iter().zip(summaries) {
+            let column = match self.column(field.source_id) {
+                Some(column) => column,
+                None => continue,
+            };
+            let (lower, upper) = match (&summary.lower_bound, &summary.upper_bound) {
+                (Some(lower), Some(upper)) => (lower, upper),
+                _ => continue,
+            };
+            let result_type = field.result_type(&column.field_type);
+            let range = match (result_type.decode(&lower.0), result_type.decode(&upper.0)) {
+                (Some(min), Some(max)) => field.source_range(&column.field_type, min, max),
+                _ => None,
+            };
+            if let Some((min, max)) = range {
+                stats.entry(column.index).or_insert(ColumnStatistics {
+                    min,
+                    max,
+                    null_count: summary.contains_null as u64,
+                    in_memory_size: 0,
+                    distinct_of_values: None,
+                    truncated: false,
+                });
+            }
+        }
+        filter.eval(&stats)
+    }
+
+    pub fn keep_file(&self, partition_spec_id: i32, file: &DataFile) -> Result<bool> {
+        let filter = match &self.filter {
+            None => return Ok(true),
+            Some(filter) => filter,
+        };
+
+        let mut stats = ColumnsStatistics::new();
+        let null_counts = file.null_value_counts();
+        let lower_bounds = file.lower_bounds();
+        let upper_bounds = file.upper_bounds();
+        for column in &self.columns {
+            let bounds = match (
+                lower_bounds.get(&column.field_id),
+                upper_bounds.get(&column.field_id),
+            ) {
+                (Some(lower), Some(upper)) => (
+                    column.field_type.decode(lower),
+                    column.field_type.decode(upper),
+                ),
+                _ => continue,
+            };
+            if let (Some(min), Some(max)) = bounds {
+                // All the values may be null if the count is missing.
+                let null_count = match null_counts.get(&column.field_id) {
+                    Some(count) => *count as u64,
+                    None => file.record_count as u64,
+                };
+                stats.insert(column.index, ColumnStatistics {
+                    min,
+                    max,
+                    null_count,
+                    in_memory_size: 0,
+                    distinct_of_values: None,
+                    truncated: false,
+                });
+            }
+        }
+
+        for field in self.specs.get(&partition_spec_id).into_iter().flatten() {
+            let column = match self.column(field.source_id) {
+                Some(column) if !stats.contains_key(&column.index) => column,
+                _ => continue,
+            };
+            // The source values are all null if the partition value is null.
+            let value = match file
+                .partition
+                .get(&field.name)
+                .and_then(|v| v.to_data_value())
+            {
+                Some(value) => value,
+                None => continue,
+            };
+            let range = field.source_range(&column.field_type, value.clone(), value);
+            if let Some((min, max)) = range {
+                stats.insert(column.index, ColumnStatistics {
+                    min,
+                    max,
+                    null_count: 0,
+                    in_memory_size: 0,
+                    distinct_of_values: None,
+                    truncated: false,
+                });
+            }
+        }
+        filter.eval(&stats)
+    }
+}
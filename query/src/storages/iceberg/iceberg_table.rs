@@ -0,0 +1,463 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::Extras;
+use common_planners::PartInfoPtr;
+use common_planners::Partitions;
+use common_planners::ReadDataSourcePlan;
+use common_planners::Statistics;
+use common_streams::ParquetSourceBuilder;
+use common_streams::RowGroupFilter;
+use common_streams::SendableDataBlockStream;
+use common_streams::Source;
+use futures::io::Cursor;
+use futures::AsyncReadExt;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use opendal::ObjectMode;
+use opendal::Operator;
+
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::AsyncSource;
+use crate::pipelines::new::processors::AsyncSourcer;
+use crate::pipelines::new::NewPipeline;
+use crate::pipelines::new::SourcePipeBuilder;
+use crate::sessions::QueryContext;
+use crate::storages::iceberg::iceberg_manifest::read_manifest;
+use crate::storages::iceberg::iceberg_manifest::read_manifest_list;
+use crate::storages::iceberg::iceberg_manifest::ManifestFile;
+use crate::storages::iceberg::iceberg_manifest::CONTENT_DATA;
+use crate::storages::iceberg::iceberg_metadata::TableMetadata;
+use crate::storages::iceberg::iceberg_part::IcebergPartInfo;
+use crate::storages::iceberg::iceberg_pruning::IcebergPruner;
+use crate::storages::index::RowGroupRangeFilter;
+use crate::storages::StorageContext;
+use crate::storages::StorageDescription;
+use crate::storages::Table;
+
+pub const ICEBERG_ENGINE: &str = "ICEBERG";
+/// The table option of the location of the Iceberg table, relative to the root of the storage.
+pub const OPT_KEY_LOCATION: &str = "location";
+
+const VERSION_HINT: &str = "metadata/version-hint.text";
+const METADATA_SUFFIX: &str = ".metadata.json";
+/// Max number of manifests being loaded concurrently while pruning
+const MAX_CONCURRENT_MANIFEST_LOADING: usize = 10;
+
+/// Reads the current snapshot of an Iceberg table in the storage, e.g.
+/// `CREATE TABLE t ENGINE = ICEBERG LOCATION = 'warehouse/db/t'`, of which the schema is the
+/// current one of the Iceberg table if the columns are not given.
+///
+/// The metadata is read in each query, so the snapshots committed by the other engines are
+/// seen. Only the data files of parquet are read, and the columns are matched by the names.
+pub struct IcebergTable {
+    table_info: TableInfo,
+    location: String,
+}
+
+impl IcebergTable {
+    pub fn try_create(_ctx: StorageContext, table_info: TableInfo) -> Result<Box<dyn Table>> {
+        let location = Self::location(table_info.engine_options())?;
+        Ok(Box::new(IcebergTable {
+            table_info,
+            location,
+        }))
+    }
+
+    pub fn description() -> StorageDescription {
+        StorageDescription {
+            engine_name: ICEBERG_ENGINE.to_string(),
+            comment: "ICEBERG Storage Engine".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn location(options: &BTreeMap<String, String>) -> Result<String> {
+        match options.get(OPT_KEY_LOCATION) {
+            Some(location) => Ok(location.trim_matches('/').to_string()),
+            None => Err(ErrorCode::BadOption(
+                "Iceberg engine table missing location key",
+            )),
+        }
+    }
+
+    /// The current schema of the Iceberg table, for the table created without columns.
+    pub async fn read_schema(
+        ctx: &QueryContext,
+        options: &BTreeMap<String, String>,
+    ) -> Result<DataSchemaRef> {
+        let location = Self::location(options)?;
+        let operator = ctx.get_storage_operator()?;
+        Self::read_metadata(&operator, &location)
+            .await?
+            .data_schema()
+    }
+
+    /// Reads the latest metadata file, which is told by the version hint, or has the greatest
+    /// version if the hint is missing.
+    pub async fn read_metadata(operator: &Operator, location: &str) -> Result<TableMetadata> {
+        let version_hint = format!("{}/{}", location, VERSION_HINT);
+        let metadata_location = match read_file(operator, &version_hint).await {
+            Ok(version) => {
+                let version = String::from_utf8(version)?;
+                format!(
+                    "{}/metadata/v{}{}",
+                    location,
+                    version.trim(),
+                    METADATA_SUFFIX
+                )
+            }
+            Err(e) if e.code() == ErrorCode::storage_not_found_code() => {
+                Self::find_latest_metadata(operator, location).await?
+            }
+            Err(e) => return Err(e),
+        };
+
+        let metadata = read_file(operator, &metadata_location).await?;
+        serde_json::from_slice(&metadata).map_err(|cause| {
+            ErrorCode::BadBytes(format!(
+                "Cannot read the Iceberg metadata {}: {}",
+                metadata_location, cause
+            ))
+        })
+    }
+
+    async fn find_latest_metadata(operator: &Operator, location: &str) -> Result<String> {
+        let dir = format!("{}/metadata/", location);
+        let mut objects = operator.object(&dir).list().await?;
+        let mut latest: Option<(u64, String)> = None;
+        while let Some(object) = objects.next().await {
+            let mut object = object?;
+            let meta = object.metadata_cached().await?;
+            let path = meta.path();
+            if meta.mode() != ObjectMode::FILE || !path.ends_with(METADATA_SUFFIX) {
+                continue;
+            }
+
+            // The metadata files are named `v<version>.metadata.json`, or
+            // `<version>-<uuid>.metadata.json` by the catalogs.
+            let name = path.rsplit('/').next().unwrap_or(path);
+            let name = name.strip_prefix('v').unwrap_or(name);
+            let digits = name
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect::<String>();
+            if let Ok(version) = digits.parse::<u64>() {
+                if latest
+                    .as_ref()
+                    .map_or(true, |(latest, _)| version > *latest)
+                {
+                    latest = Some((version, path.to_string()));
+                }
+            }
+        }
+
+        match latest {
+            Some((_, path)) => Ok(path),
+            None => Err(ErrorCode::StorageNotFound(format!(
+                "no metadata of Iceberg table is found in {}",
+                location
+            ))),
+        }
+    }
+
+    /// The path relative to the root of the storage, of the file of which the path starts with
+    /// the location of the Iceberg table.
+    fn file_location(&self, metadata: &TableMetadata, path: &str) -> Result<String> {
+        match path.strip_prefix(metadata.location.trim_end_matches('/')) {
+            Some(relative) => Ok(format!("{}/{}", self.location, relative.trim_matches('/'))),
+            None => Err(ErrorCode::BadBytes(format!(
+                "The file {} is out of the location of Iceberg table {}",
+                path, metadata.location
+            ))),
+        }
+    }
+
+    async fn read_manifests(
+        &self,
+        operator: &Operator,
+        metadata: &TableMetadata,
+    ) -> Result<Vec<ManifestFile>> {
+        let snapshot = match metadata.current_snapshot()? {
+            Some(snapshot) => snapshot,
+            None => return Ok(vec![]),
+        };
+        let manifests = match &snapshot.manifest_list {
+            Some(manifest_list) => {
+                let location = self.file_location(metadata, manifest_list)?;
+                read_manifest_list(&read_file(operator, &location).await?)?
+            }
+            None => snapshot
+                .manifests
+                .iter()
+                .map(|path| ManifestFile::create(path.clone(), metadata.default_spec_id()))
+                .collect(),
+        };
+
+        if manifests
+            .iter()
+            .any(|manifest| manifest.content != CONTENT_DATA)
+        {
+            return Err(ErrorCode::UnImplement(
+                "Cannot read the Iceberg table with the delete files",
+            ));
+        }
+        Ok(manifests)
+    }
+
+    fn projected_schema(&self, push_downs: &Option<Extras>) -> DataSchemaRef {
+        let schema = self.table_info.schema();
+        match push_downs
+            .as_ref()
+            .and_then(|extras| extras.projection.as_ref())
+        {
+            Some(projection) => Arc::new(schema.project(projection.clone())),
+            None => schema,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for IcebergTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn benefit_column_prune(&self) -> bool {
+        true
+    }
+
+    async fn read_partitions(
+        &self,
+        ctx: Arc<QueryContext>,
+        push_downs: Option<Extras>,
+    ) -> Result<(Statistics, Partitions)> {
+        let operator = ctx.get_storage_operator()?;
+        let metadata = Self::read_metadata(&operator, &self.location).await?;
+        let manifests = self.read_manifests(&operator, &metadata).await?;
+        let pruner = IcebergPruner::try_create(&ctx, self.schema(), &metadata, &push_downs)?;
+
+        let mut partitions_total = 0;
+        let mut kept_manifests = Vec::with_capacity(manifests.len());
+        for manifest in manifests {
+            match pruner.keep_manifest(&manifest)? {
+                true => kept_manifests.push(manifest),
+                false => partitions_total += manifest.files_count().unwrap_or(0),
+            }
+        }
+
+        let mut loadings = futures::stream::iter(&kept_manifests)
+            .map(|manifest| {
+                let operator = operator.clone();
+                let location = self.file_location(&metadata, &manifest.manifest_path);
+                async move { read_manifest(&read_file(&operator, &location?).await?) }
+            })
+            .buffered(MAX_CONCURRENT_MANIFEST_LOADING);
+
+        let mut statistics = Statistics::default();
+        let mut parts = vec![];
+        let mut manifest_index = 0;
+        while let Some(data_files) = loadings.try_next().await? {
+            let partition_spec_id = kept_manifests[manifest_index].partition_spec_id;
+            manifest_index += 1;
+            partitions_total += data_files.len();
+
+            for data_file in data_files {
+                if data_file.content != CONTENT_DATA {
+                    return Err(ErrorCode::UnImplement(
+                        "Cannot read the Iceberg table with the delete files",
+                    ));
+                }
+                if !data_file.file_format.eq_ignore_ascii_case("parquet") {
+                    return Err(ErrorCode::UnImplement(format!(
+                        "Cannot read the {} files of Iceberg table",
+                        data_file.file_format
+                    )));
+                }
+                if !pruner.keep_file(partition_spec_id, &data_file)? {
+                    continue;
+                }
+
+                statistics.read_rows += data_file.record_count as usize;
+                statistics.read_bytes += data_file.file_size_in_bytes as usize;
+                parts.push(IcebergPartInfo::create(
+                    self.file_location(&metadata, &data_file.file_path)?,
+                    data_file.record_count as u64,
+                    data_file.file_size_in_bytes as u64,
+                ));
+            }
+        }
+
+        statistics.partitions_scanned = parts.len();
+        statistics.partitions_total = partitions_total;
+        Ok((statistics, parts))
+    }
+
+    async fn read(
+        &self,
+        ctx: Arc<QueryContext>,
+        plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let schema = self.projected_schema(&plan.push_downs);
+        let filter = RowGroupRangeFilter::try_create(&ctx, self.schema(), &plan.push_downs)?;
+        let reader = IcebergFileReader::create(&ctx, schema, filter)?;
+        let iter = std::iter::from_fn(move || match ctx.clone().try_get_partitions(1) {
+            Err(_) => None,
+            Ok(parts) if parts.is_empty() => None,
+            Ok(parts) => Some(parts),
+        })
+        .flatten();
+
+        let stream = futures::stream::iter(iter)
+            .then(move |part| {
+                let reader = reader.clone();
+                async move { reader.read(part).await }
+            })
+            .map_ok(|blocks| futures::stream::iter(blocks.into_iter().map(Ok)))
+            .try_flatten();
+        Ok(Box::pin(stream))
+    }
+
+    fn read2(
+        &self,
+        ctx: Arc<QueryContext>,
+        plan: &ReadDataSourcePlan,
+        pipeline: &mut NewPipeline,
+    ) -> Result<()> {
+        let schema = self.projected_schema(&plan.push_downs);
+        let filter = RowGroupRangeFilter::try_create(&ctx, self.schema(), &plan.push_downs)?;
+        let reader = IcebergFileReader::create(&ctx, schema, filter)?;
+        let max_threads = ctx.get_settings().get_max_threads()? as usize;
+        let max_threads = std::cmp::min(plan.parts.len(), max_threads);
+
+        let mut source_builder = SourcePipeBuilder::create();
+        for _index in 0..std::cmp::max(1, max_threads) {
+            let output = OutputPort::create();
+            source_builder.add_source(
+                output.clone(),
+                IcebergSource::create(ctx.clone(), output, reader.clone())?,
+            );
+        }
+
+        pipeline.add_pipe(source_builder.finalize());
+        Ok(())
+    }
+}
+
+async fn read_file(operator: &Operator, location: &str) -> Result<Vec<u8>> {
+    let mut data = vec![];
+    operator
+        .object(location)
+        .reader()
+        .await?
+        .read_to_end(&mut data)
+        .await?;
+    Ok(data)
+}
+
+/// Reads the blocks of the data files, a block for each row group.
+#[derive(Clone)]
+struct IcebergFileReader {
+    operator: Operator,
+    schema: DataSchemaRef,
+    row_group_filter: Option<Arc<dyn RowGroupFilter>>,
+}
+
+impl IcebergFileReader {
+    fn create(
+        ctx: &QueryContext,
+        schema: DataSchemaRef,
+        row_group_filter: Option<Arc<dyn RowGroupFilter>>,
+    ) -> Result<IcebergFileReader> {
+        Ok(IcebergFileReader {
+            operator: ctx.get_storage_operator()?,
+            schema,
+            row_group_filter,
+        })
+    }
+
+    async fn read(&self, part: PartInfoPtr) -> Result<Vec<DataBlock>> {
+        let part = IcebergPartInfo::from_part(&part)?;
+        let data = read_file(&self.operator, &part.location).await?;
+
+        let mut builder = ParquetSourceBuilder::create(self.schema.clone());
+        if let Some(filter) = &self.row_group_filter {
+            builder.row_group_filter(filter.clone());
+        }
+        let mut source = builder.build(Cursor::new(data))?;
+        let mut blocks = vec![];
+        while let Some(block) = source.read().await? {
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+}
+
+struct IcebergSource {
+    ctx: Arc<QueryContext>,
+    reader: IcebergFileReader,
+    blocks: VecDeque<DataBlock>,
+}
+
+impl IcebergSource {
+    fn create(
+        ctx: Arc<QueryContext>,
+        output: Arc<OutputPort>,
+        reader: IcebergFileReader,
+    ) -> Result<ProcessorPtr> {
+        AsyncSourcer::create(ctx.clone(), output, IcebergSource {
+            ctx,
+            reader,
+            blocks: VecDeque::new(),
+        })
+    }
+}
+
+impl AsyncSource for IcebergSource {
+    const NAME: &'static str = "IcebergSource";
+
+    type BlockFuture<'a> = impl Future<Output = Result<Option<DataBlock>>>;
+
+    fn generate(&mut self) -> Self::BlockFuture<'_> {
+        async {
+            loop {
+                if let Some(block) = self.blocks.pop_front() {
+                    return Ok(Some(block));
+                }
+
+                let mut parts = self.ctx.try_get_partitions(1)?;
+                match parts.pop() {
+                    None => return Ok(None),
+                    Some(part) => self.blocks = self.reader.read(part).await?.into(),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod iceberg_manifest;
+mod iceberg_metadata;
+mod iceberg_part;
+mod iceberg_pruning;
+mod iceberg_table;
+
+pub use iceberg_manifest::read_manifest;
+pub use iceberg_manifest::read_manifest_list;
+pub use iceberg_manifest::AvroBytes;
+pub use iceberg_manifest::AvroScalar;
+pub use iceberg_manifest::DataFile;
+pub use iceberg_manifest::FieldSummary;
+pub use iceberg_manifest::KeyValue;
+pub use iceberg_manifest::ManifestFile;
+pub use iceberg_metadata::IcebergType;
+pub use iceberg_metadata::PartitionField;
+pub use iceberg_metadata::TableMetadata;
+pub use iceberg_part::IcebergPartInfo;
+pub use iceberg_pruning::IcebergPruner;
+pub use iceberg_table::IcebergTable;
+pub use iceberg_table::ICEBERG_ENGINE;
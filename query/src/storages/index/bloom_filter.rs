@@ -21,6 +21,8 @@ use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_functions::scalars::cast_with_type;
+use common_functions::scalars::DEFAULT_CAST_OPTIONS;
 use common_planners::Expression;
 use common_tracing::tracing;
 
@@ -185,6 +187,17 @@ impl BloomFilterIndexer {
             return Ok(BloomFilterExprEvalResult::NotApplicable);
         }
 
+        // The constant may be of another type than the column, e.g. `int_col = '12'`, it is
+        // cast to the type of the column then. Nothing can be told if the cast fails.
+        let target = if target.as_const_column(&typ, 1).is_ok() {
+            target
+        } else {
+            match Self::try_cast(&target, &typ) {
+                Some(target) => target,
+                None => return Ok(BloomFilterExprEvalResult::Unknown),
+            }
+        };
+
         let bloom_bytes = self.bloom_block.first(&bloom_column)?.as_string()?;
         let bloom_filter = BloomFilter::from_vec(bloom_bytes.as_ref())?;
 
@@ -195,6 +208,18 @@ impl BloomFilterIndexer {
         }
     }
 
+    /// Casts the constant to the data type, None if the cast fails or results in Null.
+    fn try_cast(value: &DataValue, typ: &DataTypeImpl) -> Option<DataValue> {
+        let from_type = value.data_type();
+        let column = value.as_const_column(&from_type, 1).ok()?;
+        let to_type = remove_nullable(typ);
+        let column = cast_with_type(&column, &from_type, &to_type, &DEFAULT_CAST_OPTIONS).ok()?;
+        match column.get(0) {
+            DataValue::Null => None,
+            value => Some(value),
+        }
+    }
+
     /// Returns false when the expression must be false, otherwise true.
     /// The 'true' doesn't really mean the expression is true, but 'maybe true'.
     /// That is to say, you still need the load all data and run the execution.
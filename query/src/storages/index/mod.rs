@@ -16,6 +16,7 @@ mod bloom_filter;
 mod index_min_max;
 mod index_sparse;
 pub mod range_filter;
+mod row_group_filter;
 
 pub use bloom_filter::BloomFilter;
 pub use bloom_filter::BloomFilterExprEvalResult;
@@ -26,6 +27,7 @@ pub use index_sparse::SparseIndexValue;
 pub use range_filter::ColumnStatistics;
 pub use range_filter::ColumnsStatistics;
 pub use range_filter::RangeFilter;
+pub use row_group_filter::RowGroupRangeFilter;
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum IndexSchemaVersion {
@@ -17,6 +17,7 @@ use std::collections::HashSet;
 use std::fmt;
 use std::sync::Arc;
 
+use chrono::NaiveDate;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
@@ -41,6 +42,14 @@ pub struct ColumnStatistics {
     pub max: DataValue,
     pub null_count: u64,
     pub in_memory_size: u64,
+    /// Number of distinct non-null values, a hint only: it is exact for a block, and an
+    /// upper bound once the statistics of blocks are merged.
+    #[serde(default)]
+    pub distinct_of_values: Option<u64>,
+    /// Whether the min and the max are truncated, e.g. the prefixes of long strings, which
+    /// are bounds of the values then rather than the values themselves.
+    #[serde(default)]
+    pub truncated: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -126,10 +135,15 @@ pub fn build_verifiable_expr(
                 let right = build_verifiable_expr(right, schema, stat_columns);
                 return left.or(right);
             }
-            _ => (
-                vec![left.as_ref().clone(), right.as_ref().clone()],
-                op.clone(),
-            ),
+            _ => {
+                if let Some(expr) = invert_date_function(left, op, right, schema) {
+                    return build_verifiable_expr(&expr, schema, stat_columns);
+                }
+                (
+                    vec![left.as_ref().clone(), right.as_ref().clone()],
+                    op.clone(),
+                )
+            }
         },
         _ => return unhandled,
     };
@@ -138,6 +152,87 @@ pub fn build_verifiable_expr(
         .map_or(unhandled.clone(), |mut v| v.build().unwrap_or(unhandled))
 }
 
+/// Rewrites the comparison between a date function of a column and a constant into the range of
+/// the column, e.g. `toYYYYMMDD(ts) = 20230101` into `ts >= '2023-01-01' and ts < '2023-01-02'`,
+/// which is checked against the statistics of the column, however wide the range of a block is.
+/// None if the comparison could not be rewritten.
+fn invert_date_function(
+    left: &Expression,
+    op: &str,
+    right: &Expression,
+    schema: &DataSchemaRef,
+) -> Option<Expression> {
+    let (func, value, op) = match (left, right) {
+        (Expression::ScalarFunction { .. }, Expression::Literal { value, .. }) => (left, value, op),
+        (Expression::Literal { value, .. }, Expression::ScalarFunction { .. }) => {
+            (right, value, inverse_operator(op).ok()?)
+        }
+        _ => return None,
+    };
+    let (name, column) = match func {
+        Expression::ScalarFunction { op, args } => match &args[..] {
+            [Expression::Column(column)] => (op.to_lowercase(), column),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let data_type = remove_nullable(schema.field_with_name(column).ok()?.data_type());
+    let value = value.as_i64().ok()?;
+
+    // the dates [start, end) of which the function yields the value
+    let year = |v: i64| i32::try_from(v).ok();
+    let (start, end) = match name.as_str() {
+        "toyyyymmdd" => {
+            let start = NaiveDate::from_ymd_opt(
+                year(value / 10000)?,
+                (value / 100 % 100) as u32,
+                (value % 100) as u32,
+            )?;
+            (start, start.succ_opt()?)
+        }
+        "toyyyymm" => {
+            let (y, m) = (year(value / 100)?, (value % 100) as u32);
+            let start = NaiveDate::from_ymd_opt(y, m, 1)?;
+            let end = match m {
+                12 => NaiveDate::from_ymd_opt(y.checked_add(1)?, 1, 1)?,
+                _ => NaiveDate::from_ymd_opt(y, m + 1, 1)?,
+            };
+            (start, end)
+        }
+        "toyear" => {
+            let y = year(value)?;
+            let start = NaiveDate::from_ymd_opt(y, 1, 1)?;
+            (start, NaiveDate::from_ymd_opt(y.checked_add(1)?, 1, 1)?)
+        }
+        _ => return None,
+    };
+
+    // in the representation of the column, i.e. days for DATE, microseconds for TIMESTAMP
+    let bound = |date: NaiveDate| {
+        let seconds = date.and_hms(0, 0, 0).timestamp();
+        let value = match data_type.data_type_id() {
+            TypeID::Date => seconds / (24 * 3600),
+            TypeID::Timestamp => seconds.checked_mul(1_000_000)?,
+            _ => return None,
+        };
+        Some(Expression::create_literal_with_type(
+            DataValue::Int64(value),
+            data_type.clone(),
+        ))
+    };
+    let (start, end) = (bound(start)?, bound(end)?);
+    let column = Expression::Column(column.clone());
+    match op {
+        "=" => Some(column.gt_eq(start).and(column.lt(end))),
+        "!=" | "<>" => Some(column.lt(start).or(column.gt_eq(end))),
+        "<" => Some(column.lt(start)),
+        "<=" => Some(column.lt(end)),
+        ">" => Some(column.gt_eq(end)),
+        ">=" => Some(column.gt_eq(start)),
+        _ => None,
+    }
+}
+
 fn inverse_operator(op: &str) -> Result<&str> {
     match op {
         "<" => Ok(">"),
@@ -211,26 +306,24 @@ impl StatColumn {
         if self.stat_type == StatType::Nulls {
             // The len of column_fields is 1.
             let (k, _) = self.column_fields.iter().next().unwrap();
-            let stat = stats.get(k).ok_or_else(|| {
-                ErrorCode::UnknownException(format!(
-                    "Unable to get the colStats by ColumnId: {}",
-                    k
-                ))
-            })?;
+            let stat = match stats.get(k) {
+                Some(stat) => stat,
+                // no statistics of the column are kept, e.g. it is added after the
+                // statistics are collected, the block is kept then.
+                None => return Ok(None),
+            };
             return Ok(Some(Series::from_data(vec![stat.null_count])));
         }
 
         let mut single_point = true;
         let mut variables = HashMap::with_capacity(self.column_fields.len());
         for (k, v) in &self.column_fields {
-            let stat = stats.get(k).ok_or_else(|| {
-                ErrorCode::UnknownException(format!(
-                    "Unable to get the colStats by ColumnId: {}",
-                    k
-                ))
-            })?;
+            let stat = match stats.get(k) {
+                Some(stat) => stat,
+                None => return Ok(None),
+            };
 
-            if single_point && stat.min != stat.max {
+            if single_point && (stat.truncated || stat.min != stat.max) {
                 single_point = false;
             }
 
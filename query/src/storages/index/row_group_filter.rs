@@ -0,0 +1,156 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_arrow::parquet::metadata::ColumnChunkMetaData;
+use common_arrow::parquet::metadata::RowGroupMetaData;
+use common_arrow::parquet::statistics::BinaryStatistics;
+use common_arrow::parquet::statistics::BooleanStatistics;
+use common_arrow::parquet::statistics::PrimitiveStatistics;
+use common_arrow::parquet::statistics::Statistics;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Extras;
+use common_streams::RowGroupFilter;
+
+use crate::sessions::QueryContext;
+use crate::storages::index::ColumnStatistics;
+use crate::storages::index::ColumnsStatistics;
+use crate::storages::index::RangeFilter;
+
+/// Skips the row groups of the parquet files by the statistics of their columns, against the
+/// filter pushed down, as the fuse tables prune the blocks by their zone maps.
+#[derive(Debug)]
+pub struct RowGroupRangeFilter {
+    filter: RangeFilter,
+    schema: DataSchemaRef,
+}
+
+impl RowGroupRangeFilter {
+    /// None if no filter is pushed down.
+    pub fn try_create(
+        ctx: &Arc<QueryContext>,
+        schema: DataSchemaRef,
+        push_downs: &Option<Extras>,
+    ) -> Result<Option<Arc<dyn RowGroupFilter>>> {
+        match push_downs {
+            // for the time being, we only handle the first expr, as the fuse tables
+            Some(extras) if !extras.filters.is_empty() => {
+                let filter = &extras.filters[0];
+                let filter = RangeFilter::try_create(ctx.clone(), filter, schema.clone())?;
+                Ok(Some(Arc::new(RowGroupRangeFilter { filter, schema })))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+impl RowGroupFilter for RowGroupRangeFilter {
+    fn keep(&self, row_group: &RowGroupMetaData) -> Result<bool> {
+        let mut stats = ColumnsStatistics::new();
+        for (index, field) in self.schema.fields().iter().enumerate() {
+            let mut columns = row_group
+                .columns()
+                .iter()
+                .filter(|column| column.descriptor().path_in_schema()[0] == *field.name());
+            // The nested fields are stored in more than one column, which are not handled.
+            if let (Some(column), None) = (columns.next(), columns.next()) {
+                if let Some(stat) = column_statistics(column, field.data_type())? {
+                    stats.insert(index as u32, stat);
+                }
+            }
+        }
+        self.filter.eval(&stats)
+    }
+}
+
+// The statistics of the column as the zone map of fuse, None if they are not known, or of the
+// types whose values may not be compared as they are stored, e.g. the timestamps of any unit,
+// and the unsigned integers of 32 bits or more, which are stored as the signed.
+fn column_statistics(
+    column: &ColumnChunkMetaData,
+    data_type: &DataTypeImpl,
+) -> Result<Option<ColumnStatistics>> {
+    let stats = match column.statistics() {
+        None => return Ok(None),
+        Some(stats) => stats.map_err(|e| ErrorCode::ParquetError(e.to_string()))?,
+    };
+
+    let stats = match remove_nullable(data_type).data_type_id() {
+        TypeID::Boolean => statistics_of::<BooleanStatistics, _>(&stats, |s| {
+            let val = DataValue::Boolean;
+            (s.null_count, s.min_value.map(val), s.max_value.map(val))
+        }),
+        TypeID::Int8 | TypeID::Int16 | TypeID::Int32 | TypeID::Date => {
+            statistics_of::<PrimitiveStatistics<i32>, _>(&stats, |s| {
+                let val = |v: i32| DataValue::Int64(v as i64);
+                (s.null_count, s.min_value.map(val), s.max_value.map(val))
+            })
+        }
+        TypeID::Int64 => statistics_of::<PrimitiveStatistics<i64>, _>(&stats, |s| {
+            let val = DataValue::Int64;
+            (s.null_count, s.min_value.map(val), s.max_value.map(val))
+        }),
+        TypeID::UInt8 | TypeID::UInt16 => {
+            statistics_of::<PrimitiveStatistics<i32>, _>(&stats, |s| {
+                let val = |v: i32| DataValue::UInt64(v as u64);
+                (s.null_count, s.min_value.map(val), s.max_value.map(val))
+            })
+        }
+        TypeID::Float32 => statistics_of::<PrimitiveStatistics<f32>, _>(&stats, |s| {
+            let val = |v: f32| DataValue::Float64(v as f64);
+            (s.null_count, s.min_value.map(val), s.max_value.map(val))
+        }),
+        TypeID::Float64 => statistics_of::<PrimitiveStatistics<f64>, _>(&stats, |s| {
+            let val = DataValue::Float64;
+            (s.null_count, s.min_value.map(val), s.max_value.map(val))
+        }),
+        TypeID::String => statistics_of::<BinaryStatistics, _>(&stats, |s| {
+            let val = DataValue::String;
+            let (min, max) = (s.min_value.clone(), s.max_value.clone());
+            (s.null_count, min.map(val), max.map(val))
+        }),
+        _ => None,
+    };
+    Ok(stats)
+}
+
+// The statistics of the type `S`, None if the column is of another physical type, or the null
+// count, the min or the max is missing, e.g. all the values are NULL.
+fn statistics_of<S, F>(stats: &Arc<dyn Statistics>, f: F) -> Option<ColumnStatistics>
+where
+    S: 'static,
+    F: Fn(&S) -> (Option<i64>, Option<DataValue>, Option<DataValue>),
+{
+    let stats = stats.as_any().downcast_ref::<S>()?;
+    match f(stats) {
+        (Some(null_count), Some(min), Some(max)) if !is_nan(&min) && !is_nan(&max) => {
+            Some(ColumnStatistics {
+                min,
+                max,
+                null_count: null_count as u64,
+                in_memory_size: 0,
+                distinct_of_values: None,
+                truncated: false,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn is_nan(value: &DataValue) -> bool {
+    matches!(value, DataValue::Float64(v) if v.is_nan())
+}
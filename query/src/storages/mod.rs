@@ -13,16 +13,20 @@
 // limitations under the License.
 
 pub mod cache;
+pub mod delta;
 pub mod fuse;
 pub mod github;
+pub mod iceberg;
 pub mod index;
 pub mod information_schema;
 pub mod memory;
 pub mod null;
+pub mod stream;
 pub mod system;
 pub mod view;
 
 mod s3;
+mod storage_backup;
 mod storage_context;
 mod storage_factory;
 mod storage_table;
@@ -30,10 +34,16 @@ mod storage_table_read_plan;
 
 pub use s3::S3StageTable;
 pub use s3::StageSource;
+pub use storage_backup::DatabaseBackup;
+pub use storage_backup::TableBackup;
+pub use storage_backup::TableDataBackup;
+pub use storage_backup::BACKUP_FORMAT_VERSION;
 pub use storage_context::StorageContext;
 pub use storage_factory::StorageCreator;
 pub use storage_factory::StorageDescription;
 pub use storage_factory::StorageFactory;
+pub use storage_table::NavigationPoint;
 pub use storage_table::Table;
 pub use storage_table::TableStatistics;
+pub use storage_table::VacuumReport;
 pub use storage_table_read_plan::ToReadDataSourcePlan;
@@ -16,11 +16,14 @@ use std::collections::VecDeque;
 use std::future::Future;
 use std::sync::Arc;
 
+use common_arrow::arrow::io::parquet::read::infer_schema;
+use common_arrow::arrow::io::parquet::read::read_metadata_async;
 use common_datablocks::DataBlock;
-use common_datavalues::DataSchemaRef;
+use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::Mutex;
+use common_io::prelude::get_file_name;
 use common_io::prelude::S3File;
 use common_meta_types::StageFileFormatType;
 use common_meta_types::StageStorage;
@@ -30,7 +33,9 @@ use common_planners::S3StageTableInfo;
 use common_streams::CsvSourceBuilder;
 use common_streams::NDJsonSourceBuilder;
 use common_streams::ParquetSourceBuilder;
+use common_streams::RejectedRecord;
 use common_streams::Source;
+use common_tracing::tracing;
 use futures::io::BufReader;
 use opendal::io_util::SeekableReader;
 use opendal::BytesReader;
@@ -42,14 +47,27 @@ use crate::pipelines::new::processors::AsyncSource;
 use crate::pipelines::new::processors::AsyncSourcer;
 use crate::sessions::QueryContext;
 
+/// The virtual column of the file a row is read from.
+pub const METADATA_FILENAME: &str = "metadata$filename";
+/// The virtual column of the number of a row in the file it is read from, starting from 1.
+pub const METADATA_FILE_ROW_NUMBER: &str = "metadata$file_row_number";
+
 pub struct StageSource {
     ctx: Arc<QueryContext>,
     schema: DataSchemaRef,
+    // The schema without the virtual columns, which the files are read by.
+    file_schema: DataSchemaRef,
     table_info: S3StageTableInfo,
     initialized: bool,
     source: Option<Box<dyn Source>>,
     files: Arc<Mutex<VecDeque<String>>>,
     current_file: Option<String>,
+    // The rows read from the current file.
+    file_rows: u64,
+    // The blocks of the current file, kept until the file is parsed if it may be skipped.
+    file_blocks: Vec<DataBlock>,
+    // The rows of the current file failing to parse, kept if the rejected records are written.
+    rejected_records: Vec<RejectedRecord>,
 }
 
 impl StageSource {
@@ -60,26 +78,54 @@ impl StageSource {
         table_info: S3StageTableInfo,
         files: Arc<Mutex<VecDeque<String>>>,
     ) -> Result<ProcessorPtr> {
+        let fields = schema
+            .fields()
+            .iter()
+            .filter(|field| !Self::is_metadata_column(field.name()))
+            .cloned()
+            .collect();
+        let file_schema = DataSchemaRefExt::create(fields);
+
         AsyncSourcer::create(ctx.clone(), output, StageSource {
             ctx,
             schema,
+            file_schema,
             table_info,
             initialized: false,
             source: None,
             files,
             current_file: None,
+            file_rows: 0,
+            file_blocks: vec![],
+            rejected_records: vec![],
         })
     }
 
-    // Get csv source stream.
+    fn is_metadata_column(name: &str) -> bool {
+        name == METADATA_FILENAME || name == METADATA_FILE_ROW_NUMBER
+    }
+
+    // Get csv or tsv source stream.
     async fn csv_source(
         ctx: Arc<QueryContext>,
         schema: DataSchemaRef,
         stage_info: &UserStageInfo,
         reader: BytesReader,
     ) -> Result<Box<dyn Source>> {
+        let builder = Self::csv_source_builder(&ctx, schema, stage_info)?;
+        Ok(Box::new(builder.build(reader)?))
+    }
+
+    fn csv_source_builder(
+        ctx: &Arc<QueryContext>,
+        schema: DataSchemaRef,
+        stage_info: &UserStageInfo,
+    ) -> Result<CsvSourceBuilder> {
         let settings = ctx.get_format_settings()?;
-        let mut builder = CsvSourceBuilder::create(schema, settings);
+        let mut builder = match stage_info.file_format_options.format {
+            StageFileFormatType::Tsv => CsvSourceBuilder::create_tsv(schema, settings),
+            _ => CsvSourceBuilder::create(schema, settings),
+        };
         let size_limit = stage_info.copy_options.size_limit;
 
         // Size limit.
@@ -89,6 +135,12 @@ impl StageSource {
             }
         }
 
+        // On error.
+        {
+            builder.skip_error_rows(stage_info.copy_options.on_error.skip_error_rows());
+            builder.keep_rejected_records(!stage_info.copy_options.rejected_records.is_empty());
+        }
+
         // Block size.
         {
             let max_block_size = ctx.get_settings().get_max_block_size()?;
@@ -112,7 +164,17 @@ impl StageSource {
             builder.record_delimiter(record_delimiter);
         }
 
-        Ok(Box::new(builder.build(reader)?))
+        // Enclosed by, default '"' for csv and none for tsv.
+        {
+            builder.enclosed_by(&stage_info.file_format_options.enclosed_by);
+        }
+
+        // Escaped by, default none for csv and '\' for tsv.
+        {
+            builder.escaped_by(&stage_info.file_format_options.escaped_by);
+        }
+
+        Ok(builder)
     }
 
     // Get json source stream.
@@ -132,6 +194,12 @@ impl StageSource {
             }
         }
 
+        // On error.
+        {
+            builder.skip_error_rows(stage_info.copy_options.on_error.skip_error_rows());
+            builder.keep_rejected_records(!stage_info.copy_options.rejected_records.is_empty());
+        }
+
         // Block size.
         {
             let max_block_size = ctx.get_settings().get_max_block_size()?;
@@ -169,15 +237,90 @@ impl StageSource {
                     let endpoint = &ctx.get_config().storage.s3.endpoint_url;
                     let bucket = &s3.bucket;
 
-                    let key_id = &s3.credentials_aws_key_id;
-                    let secret_key = &s3.credentials_aws_secret_key;
+                    if s3.connection.is_empty() {
+                        let key_id = &s3.credentials_aws_key_id;
+                        let secret_key = &s3.credentials_aws_secret_key;
+
+                        return S3File::open(endpoint, bucket, key_id, secret_key, "/").await;
+                    }
 
-                    S3File::open(endpoint, bucket, key_id, secret_key, "/").await
+                    // The credentials are kept by the connection.
+                    let credentials = ctx
+                        .get_user_manager()
+                        .get_connection_credentials(&ctx.get_tenant(), &s3.connection)
+                        .await?;
+                    let key_id = credentials.get("aws_key_id").cloned().unwrap_or_default();
+                    let secret_key = credentials
+                        .get("aws_secret_key")
+                        .cloned()
+                        .unwrap_or_default();
+
+                    S3File::open(endpoint, bucket, &key_id, &secret_key, "/").await
                 }
             }
         }
     }
 
+    /// Infers the schema to SELECT the files of the stage by, from the first of them, followed by
+    /// the virtual columns.
+    ///
+    /// The parquet files have their own schema. The fields of the csv and tsv files are nullable
+    /// strings, named by their positions as `$1`, `$2` and so on.
+    pub async fn infer_schema(
+        ctx: &Arc<QueryContext>,
+        stage_info: &UserStageInfo,
+        files: &[String],
+    ) -> Result<DataSchemaRef> {
+        let mut fields = match files.first() {
+            None => vec![],
+            Some(file) => {
+                let schema = Self::infer_file_schema(ctx, stage_info, file).await?;
+                schema.fields().clone()
+            }
+        };
+        fields.push(DataField::new(METADATA_FILENAME, Vu8::to_data_type()));
+        fields.push(DataField::new(
+            METADATA_FILE_ROW_NUMBER,
+            u64::to_data_type(),
+        ));
+        Ok(DataSchemaRefExt::create(fields))
+    }
+
+    async fn infer_file_schema(
+        ctx: &Arc<QueryContext>,
+        stage_info: &UserStageInfo,
+        file: &str,
+    ) -> Result<DataSchemaRef> {
+        let op = Self::get_op(ctx, stage_info).await?;
+        let object = op.object(file);
+
+        match &stage_info.file_format_options.format {
+            StageFileFormatType::Csv | StageFileFormatType::Tsv => {
+                let schema = DataSchemaRefExt::create(vec![]);
+                let builder = Self::csv_source_builder(ctx, schema, stage_info)?;
+                let reader: BytesReader = Box::new(object.reader().await?);
+                let mut source = builder.build(reader)?;
+                let num_fields = source.read_num_fields().await?.unwrap_or(0);
+                let fields = (1..=num_fields)
+                    .map(|i| DataField::new_nullable(&format!("${}", i), Vu8::to_data_type()))
+                    .collect();
+                Ok(DataSchemaRefExt::create(fields))
+            }
+            StageFileFormatType::Parquet => {
+                let mut reader = object.seekable_reader(..);
+                let metadata = read_metadata_async(&mut reader)
+                    .await
+                    .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+                let schema = infer_schema(&metadata)?;
+                Ok(Arc::new(DataSchema::from(schema)))
+            }
+            format => Err(ErrorCode::UnImplement(format!(
+                "Cannot infer the schema of the {:?} files",
+                format
+            ))),
+        }
+    }
+
     async fn initialize(&mut self, file_name: String) -> Result<()> {
         let ctx = self.ctx.clone();
         let stage = &self.table_info.stage_info;
@@ -189,23 +332,23 @@ impl StageSource {
 
         // Get the format(CSV, Parquet) source stream.
         let source = match &file_format {
-            StageFileFormatType::Csv => Ok(Self::csv_source(
+            StageFileFormatType::Csv | StageFileFormatType::Tsv => Ok(Self::csv_source(
                 ctx.clone(),
-                self.schema.clone(),
+                self.file_schema.clone(),
                 stage,
                 Box::new(object.reader().await?),
             )
             .await?),
             StageFileFormatType::Json => Ok(Self::json_source(
                 ctx.clone(),
-                self.schema.clone(),
+                self.file_schema.clone(),
                 stage,
                 Box::new(object.reader().await?),
             )
             .await?),
             StageFileFormatType::Parquet => Ok(Self::parquet_source(
                 ctx.clone(),
-                self.schema.clone(),
+                self.file_schema.clone(),
                 stage,
                 object.seekable_reader(..),
             )
@@ -218,9 +361,112 @@ impl StageSource {
         }?;
         self.source = Some(source);
         self.current_file = Some(path.clone());
+        self.file_rows = 0;
+
+        Ok(())
+    }
+
+    // Returns the kept blocks of the parsed file, unless the file is skipped for its errors.
+    async fn finish_file(&mut self, error_rows: usize) -> Result<DataBlock> {
+        let blocks = std::mem::take(&mut self.file_blocks);
+        let rejected_records = std::mem::take(&mut self.rejected_records);
+        if !rejected_records.is_empty() {
+            self.write_rejected_records(rejected_records).await?;
+        }
+
+        if error_rows == 0 {
+            return Self::concat_file_blocks(&self.schema, &blocks);
+        }
+
+        let on_error = &self.table_info.stage_info.copy_options.on_error;
+        let rows = blocks.iter().map(|b| b.num_rows()).sum::<usize>();
+        let parsed_rows = rows + error_rows;
+        if on_error.skip_file(error_rows as u64, parsed_rows as u64) {
+            tracing::warn!(
+                "skip file {:?}, {} of {} rows failed to parse, on_error:{:?}",
+                self.current_file,
+                error_rows,
+                parsed_rows,
+                on_error
+            );
+            return Ok(DataBlock::empty_with_schema(self.schema.clone()));
+        }
+
+        tracing::warn!(
+            "skip {} rows failed to parse of file {:?}",
+            error_rows,
+            self.current_file
+        );
+        Self::concat_file_blocks(&self.schema, &blocks)
+    }
+
+    // Writes the rows of the file failing to parse to `<rejected_records>/<query_id>/<file>.ndjson`
+    // in the stage, one json object per row.
+    async fn write_rejected_records(&self, records: Vec<RejectedRecord>) -> Result<()> {
+        let stage_info = &self.table_info.stage_info;
+        let file = self.current_file.clone().unwrap_or_default();
+        let location = format!(
+            "{}/{}/{}.ndjson",
+            stage_info
+                .copy_options
+                .rejected_records
+                .trim_end_matches('/'),
+            self.ctx.get_id(),
+            get_file_name(&file)
+        );
+
+        let mut data = vec![];
+        for record in records {
+            let row = serde_json::json!({
+                "file": file,
+                "row": record.row,
+                "record": record.record,
+                "error": record.error,
+            });
+            data.extend_from_slice(row.to_string().as_bytes());
+            data.push(b'\n');
+        }
 
+        let op = Self::get_op(&self.ctx, stage_info).await?;
+        op.object(&location).write(data).await?;
         Ok(())
     }
+
+    // Appends the virtual columns to the block read from the current file.
+    fn with_metadata_columns(&mut self, block: DataBlock) -> Result<DataBlock> {
+        let num_rows = block.num_rows();
+        let first_row = self.file_rows + 1;
+        self.file_rows += num_rows as u64;
+        if self.file_schema.num_fields() == self.schema.num_fields() {
+            return Ok(block);
+        }
+
+        let mut columns = Vec::with_capacity(self.schema.num_fields());
+        for field in self.schema.fields() {
+            let column = match field.name().as_str() {
+                METADATA_FILENAME => {
+                    let file = self.current_file.clone().unwrap_or_default();
+                    let value = DataValue::String(file.into_bytes());
+                    let column = field.data_type().create_constant_column(&value, num_rows)?;
+                    column.convert_full_column()
+                }
+                METADATA_FILE_ROW_NUMBER => {
+                    let row_numbers = (first_row..=self.file_rows).collect::<Vec<u64>>();
+                    Series::from_data(row_numbers)
+                }
+                _ => block.try_column_by_name(field.name())?.clone(),
+            };
+            columns.push(column);
+        }
+        Ok(DataBlock::create(self.schema.clone(), columns))
+    }
+
+    fn concat_file_blocks(schema: &DataSchemaRef, blocks: &[DataBlock]) -> Result<DataBlock> {
+        match blocks.is_empty() {
+            true => Ok(DataBlock::empty_with_schema(schema.clone())),
+            false => DataBlock::concat_blocks(blocks),
+        }
+    }
 }
 
 impl AsyncSource for StageSource {
@@ -248,15 +494,34 @@ impl AsyncSource for StageSource {
                 self.initialized = true;
             }
 
-            match &mut self.source {
-                None => Err(ErrorCode::LogicalError("Please init source first!")),
-                Some(source) => match source.read().await? {
-                    None => {
-                        self.initialized = false;
-                        Ok(Some(DataBlock::empty_with_schema(self.schema.clone())))
-                    }
-                    Some(data) => Ok(Some(data)),
-                },
+            let source = match &mut self.source {
+                None => return Err(ErrorCode::LogicalError("Please init source first!")),
+                Some(source) => source,
+            };
+
+            let data = source.read().await?;
+            let error_rows = source.error_rows();
+            self.rejected_records.extend(source.take_rejected_records());
+            let data = data
+                .map(|block| self.with_metadata_columns(block))
+                .transpose()?;
+            let keep_blocks = self
+                .table_info
+                .stage_info
+                .copy_options
+                .on_error
+                .is_skip_file();
+            match data {
+                None => {
+                    self.initialized = false;
+                    Ok(Some(self.finish_file(error_rows).await?))
+                }
+                // The file may be skipped, keep its blocks until it is parsed.
+                Some(data) if keep_blocks => {
+                    self.file_blocks.push(data);
+                    Ok(Some(DataBlock::empty_with_schema(self.schema.clone())))
+                }
+                Some(data) => Ok(Some(data)),
             }
         }
     }
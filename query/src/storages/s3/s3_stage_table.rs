@@ -37,7 +37,7 @@ use crate::storages::Table;
 
 pub struct S3StageTable {
     table_info: S3StageTableInfo,
-    // This is no used but a placeholder.
+    // This is a placeholder, with only the schema and the description of the stage.
     // But the Table trait need it:
     // fn get_table_info(&self) -> &TableInfo).
     table_info_placeholder: TableInfo,
@@ -45,12 +45,21 @@ pub struct S3StageTable {
 
 impl S3StageTable {
     pub fn try_create(table_info: S3StageTableInfo) -> Result<Arc<dyn Table>> {
-        let table_info_placeholder = TableInfo::default();
+        let table_info_placeholder = TableInfo {
+            desc: format!("'@{}'", table_info.desc()),
+            name: table_info.desc(),
+            ..Default::default()
+        }
+        .set_schema(table_info.schema());
         Ok(Arc::new(Self {
             table_info,
             table_info_placeholder,
         }))
     }
+
+    pub fn get_stage_table_info(&self) -> &S3StageTableInfo {
+        &self.table_info
+    }
 }
 
 #[async_trait::async_trait]
@@ -0,0 +1,119 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::DateTime;
+use chrono::Utc;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::DatabaseMeta;
+use common_meta_types::MetaId;
+use common_meta_types::TableMeta;
+use common_meta_types::UserInfo;
+use opendal::Operator;
+
+/// The version of the layout of the backups, restoring a backup of another version is refused.
+pub const BACKUP_FORMAT_VERSION: u64 = 1;
+
+/// The manifest is written after everything else of a backup, thus a backup without it is
+/// incomplete.
+const BACKUP_MANIFEST_FILE: &str = "manifest.json";
+
+/// The manifest of a backup of a database, by `BACKUP DATABASE`.
+///
+/// The data of the tables are kept in the directories of them next to the manifest, see
+/// [DatabaseBackup::table_dir].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct DatabaseBackup {
+    pub format_version: u64,
+    /// Name of the database backed up
+    pub database: String,
+    pub database_meta: DatabaseMeta,
+    pub created_on: DateTime<Utc>,
+    pub tables: Vec<TableBackup>,
+    /// The users of the tenant, only if the backup is made `WITH USERS`
+    pub users: Vec<UserInfo>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct TableBackup {
+    pub name: String,
+    /// Id of the table backed up, which names the directory of its data in the backup
+    pub table_id: MetaId,
+    pub meta: TableMeta,
+    /// None if only the meta of the table is backed up, e.g. a view
+    pub data: Option<TableDataBackup>,
+}
+
+/// The data of a table in a backup, as returned by [crate::storages::Table::backup].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TableDataBackup {
+    /// Location of the snapshot of the table, relative to the directory of the table
+    pub snapshot: String,
+    /// Number of the files backed up, and their bytes
+    pub files: u64,
+    pub bytes: u64,
+}
+
+impl DatabaseBackup {
+    /// The directory keeping the data of the table in the backup at `dir`
+    pub fn table_dir(dir: &str, table_id: MetaId) -> String {
+        format!("{}/tables/{}", dir, table_id)
+    }
+
+    pub async fn exists(operator: &Operator, dir: &str) -> Result<bool> {
+        let location = format!("{}/{}", dir, BACKUP_MANIFEST_FILE);
+        match operator.object(&location).metadata().await {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                let e = ErrorCode::from(e);
+                if e.code() == ErrorCode::storage_not_found_code() {
+                    Ok(false)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    pub async fn read(operator: &Operator, dir: &str) -> Result<Self> {
+        let location = format!("{}/{}", dir, BACKUP_MANIFEST_FILE);
+        let bytes = match operator.object(&location).read().await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                let e = ErrorCode::from(e);
+                return Err(if e.code() == ErrorCode::storage_not_found_code() {
+                    ErrorCode::UnknownBackup(format!("no complete backup is found at {}", dir))
+                } else {
+                    e
+                });
+            }
+        };
+        let backup: DatabaseBackup = serde_json::from_slice(&bytes)
+            .map_err(|e| ErrorCode::IllegalBackup(format!("invalid manifest: {}", e)))?;
+        if backup.format_version != BACKUP_FORMAT_VERSION {
+            return Err(ErrorCode::IllegalBackup(format!(
+                "format version {} of the backup is not supported, expecting {}",
+                backup.format_version, BACKUP_FORMAT_VERSION
+            )));
+        }
+        Ok(backup)
+    }
+
+    pub async fn write(&self, operator: &Operator, dir: &str) -> Result<()> {
+        let location = format!("{}/{}", dir, BACKUP_MANIFEST_FILE);
+        let bytes = serde_json::to_vec(self)?;
+        operator.object(&location).write(bytes).await?;
+        Ok(())
+    }
+}
@@ -21,10 +21,15 @@ use common_infallible::RwLock;
 use common_meta_types::TableInfo;
 
 use crate::configs::Config;
+use crate::storages::delta::DeltaTable;
+use crate::storages::delta::DELTA_ENGINE;
 use crate::storages::fuse::FuseTable;
 use crate::storages::github::GithubTable;
+use crate::storages::iceberg::IcebergTable;
+use crate::storages::iceberg::ICEBERG_ENGINE;
 use crate::storages::memory::MemoryTable;
 use crate::storages::null::NullTable;
+use crate::storages::stream::StreamTable;
 use crate::storages::view::ViewTable;
 use crate::storages::StorageContext;
 use crate::storages::Table;
@@ -106,12 +111,30 @@ impl StorageFactory {
             descriptor: Arc::new(FuseTable::description),
         });
 
+        // Register ICEBERG table engine.
+        creators.insert(ICEBERG_ENGINE.to_string(), Storage {
+            creator: Arc::new(IcebergTable::try_create),
+            descriptor: Arc::new(IcebergTable::description),
+        });
+
+        // Register DELTA table engine.
+        creators.insert(DELTA_ENGINE.to_string(), Storage {
+            creator: Arc::new(DeltaTable::try_create),
+            descriptor: Arc::new(DeltaTable::description),
+        });
+
         // Register View table engine
         creators.insert("VIEW".to_string(), Storage {
             creator: Arc::new(ViewTable::try_create),
             descriptor: Arc::new(ViewTable::description),
         });
 
+        // Register Stream table engine
+        creators.insert("STREAM".to_string(), Storage {
+            creator: Arc::new(StreamTable::try_create),
+            descriptor: Arc::new(StreamTable::description),
+        });
+
         StorageFactory {
             storages: RwLock::new(creators),
         }
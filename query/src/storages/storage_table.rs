@@ -16,22 +16,33 @@ use std::any::Any;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::NaiveDateTime;
+use chrono::Utc;
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::MetaId;
 use common_meta_types::TableInfo;
+use common_planners::DeletePlan;
 use common_planners::Expression;
 use common_planners::Extras;
+use common_planners::FlashbackTablePlan;
+use common_planners::MergePlan;
 use common_planners::Partitions;
 use common_planners::ReadDataSourcePlan;
 use common_planners::Statistics;
 use common_planners::TruncateTablePlan;
+use common_planners::UpdatePlan;
+use common_planners::VacuumTablePlan;
 use common_streams::SendableDataBlockStream;
+use opendal::Operator;
 
 use crate::pipelines::new::NewPipeline;
 use crate::sessions::QueryContext;
+use crate::storages::fuse::meta::TableSnapshotStatistics;
+use crate::storages::TableDataBackup;
 
 #[async_trait::async_trait]
 pub trait Table: Sync + Send {
@@ -63,6 +74,12 @@ pub trait Table: Sync + Send {
 
     fn get_table_info(&self) -> &TableInfo;
 
+    /// Refreshes the table when it is loaded by a query, e.g. an external table is refreshed to
+    /// the latest snapshot written by the cluster owning it. None if it is used as it is.
+    async fn refresh(&self, _ctx: &QueryContext) -> Result<Option<Arc<dyn Table>>> {
+        Ok(None)
+    }
+
     /// whether column prune(projection) can help in table read
     fn benefit_column_prune(&self) -> bool {
         false
@@ -140,13 +157,157 @@ pub trait Table: Sync + Send {
         )))
     }
 
+    /// Deletes the rows matching the selection of `delete_plan`.
+    async fn delete(&self, _ctx: Arc<QueryContext>, _delete_plan: DeletePlan) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "delete for table {} is not implemented",
+            self.name()
+        )))
+    }
+
+    /// Updates the rows matching the selection of `update_plan`.
+    async fn update(&self, _ctx: Arc<QueryContext>, _update_plan: UpdatePlan) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "update for table {} is not implemented",
+            self.name()
+        )))
+    }
+
+    /// Merges the `source` rows into the table, according to `merge_plan`.
+    async fn merge(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _merge_plan: MergePlan,
+        _source: Vec<DataBlock>,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "merge for table {} is not implemented",
+            self.name()
+        )))
+    }
+
     async fn optimize(&self, _ctx: Arc<QueryContext>, _keep_last_snapshot: bool) -> Result<()> {
         Ok(())
     }
 
+    /// Merges the undersized blocks of the table, at most `limit` blocks are rewritten.
+    async fn compact(&self, _ctx: Arc<QueryContext>, _limit: Option<usize>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Merges the small segments of the table into larger ones, without rewriting the data.
+    async fn compact_segments(&self, _ctx: Arc<QueryContext>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Removes the rows which are beyond the retention period of the table.
+    async fn expire(&self, _ctx: Arc<QueryContext>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Removes the historical data which is beyond the retention period.
+    async fn vacuum(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _vacuum_plan: VacuumTablePlan,
+    ) -> Result<VacuumReport> {
+        Err(ErrorCode::UnImplement(format!(
+            "vacuum for table {} is not implemented",
+            self.name()
+        )))
+    }
+
     async fn statistics(&self, _ctx: Arc<QueryContext>) -> Result<Option<TableStatistics>> {
         Ok(None)
     }
+
+    /// Builds the statistics of the columns, i.e. histograms and most common values.
+    async fn analyze(&self, _ctx: Arc<QueryContext>) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "analyze for table {} is not implemented",
+            self.name()
+        )))
+    }
+
+    /// Returns the statistics of the columns built by [Table::analyze], if any.
+    async fn column_statistics(
+        &self,
+        _ctx: Arc<QueryContext>,
+    ) -> Result<Option<Arc<TableSnapshotStatistics>>> {
+        Ok(None)
+    }
+
+    /// Returns the table as of the given point of its history, i.e. time travel.
+    async fn navigate_to(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _point: &NavigationPoint,
+    ) -> Result<Arc<dyn Table>> {
+        Err(ErrorCode::UnImplement(format!(
+            "time travel of table {} is not supported, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    /// Rolls the table back to the given point of its history.
+    async fn flashback(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _flashback_plan: FlashbackTablePlan,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "flashback of table {} is not supported, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    /// Copies the data of the table into `dir` of `target`. None if the table keeps no data of
+    /// its own, only the meta of it is backed up then.
+    async fn backup(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _target: &Operator,
+        _dir: &str,
+    ) -> Result<Option<TableDataBackup>> {
+        Ok(None)
+    }
+
+    /// Restores the data, which is copied into `dir` of `source` by [Table::backup], into the
+    /// table, which is just created.
+    async fn restore(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _source: &Operator,
+        _dir: &str,
+        _backup: &TableDataBackup,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "restore of table {} is not supported, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+}
+
+/// A point in the history of a table, e.g. `AT (SNAPSHOT => 'id')` or `AT (TIMESTAMP => ts)`
+#[derive(Clone, Debug, PartialEq)]
+pub enum NavigationPoint {
+    SnapshotID(String),
+    TimePoint(DateTime<Utc>),
+}
+
+impl NavigationPoint {
+    /// Parses a time point in either RFC 3339 or `YYYY-MM-DD hh:mm:ss[.fraction]` (as UTC) format.
+    pub fn parse_time_point(s: &str) -> Option<DateTime<Utc>> {
+        if let Ok(t) = DateTime::parse_from_rfc3339(s) {
+            return Some(t.with_timezone(&Utc));
+        }
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f")
+            .ok()
+            .map(|t| DateTime::<Utc>::from_utc(t, Utc))
+    }
 }
 
 pub struct TableStatistics {
@@ -155,3 +316,12 @@ pub struct TableStatistics {
     pub data_size_compressed: Option<u64>,
     pub index_length: Option<u64>,
 }
+
+/// Number of files (and their bytes) removed, or to be removed in case of dry run, by vacuum
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VacuumReport {
+    pub snapshots: u64,
+    pub segments: u64,
+    pub blocks: u64,
+    pub bytes_reclaimed: u64,
+}
@@ -23,6 +23,7 @@ use common_planners::SourceInfo;
 use common_planners::Statistics;
 
 use crate::sessions::QueryContext;
+use crate::storages::S3StageTable;
 use crate::storages::Table;
 
 #[async_trait::async_trait]
@@ -60,8 +61,16 @@ impl ToReadDataSourcePlan for dyn Table {
             _ => None,
         };
 
+        // The stage is not in the catalog, the plan carries the stage and the files to read.
+        let source_info = match self.as_any().downcast_ref::<S3StageTable>() {
+            Some(stage_table) => {
+                SourceInfo::S3StageSource(stage_table.get_stage_table_info().clone())
+            }
+            None => SourceInfo::TableSource(table_info.clone()),
+        };
+
         Ok(ReadDataSourcePlan {
-            source_info: SourceInfo::TableSource(table_info.clone()),
+            source_info,
             scan_fields,
             parts,
             statistics,
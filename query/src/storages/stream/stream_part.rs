@@ -0,0 +1,79 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::PartInfo;
+use common_planners::PartInfoPtr;
+
+use crate::storages::fuse::FusePartInfo;
+
+/// A partition of the changes of the table of a stream.
+///
+/// The rows of the deleted blocks cancel out the same rows of the inserted ones wherever they
+/// are, thus the rows are partitioned by the hashes of their values: a partition is the rows of
+/// the blocks whose hashes fall into its bucket.
+#[derive(serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct StreamPartInfo {
+    /// The blocks inserted since the offset of the stream, with all the columns.
+    pub inserted: Vec<FusePartInfo>,
+    /// The blocks deleted since the offset of the stream, with all the columns.
+    pub deleted: Vec<FusePartInfo>,
+    /// The bucket of the rows of the partition.
+    pub bucket: usize,
+    /// The number of the buckets, all the rows of the blocks are in the partition if it is 1.
+    pub buckets: usize,
+}
+
+#[typetag::serde(name = "stream")]
+impl PartInfo for StreamPartInfo {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn equals(&self, info: &Box<dyn PartInfo>) -> bool {
+        match info.as_any().downcast_ref::<StreamPartInfo>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+impl StreamPartInfo {
+    pub fn create(
+        inserted: Vec<FusePartInfo>,
+        deleted: Vec<FusePartInfo>,
+        bucket: usize,
+        buckets: usize,
+    ) -> PartInfoPtr {
+        Arc::new(Box::new(StreamPartInfo {
+            inserted,
+            deleted,
+            bucket,
+            buckets,
+        }))
+    }
+
+    pub fn from_part(info: &PartInfoPtr) -> Result<&StreamPartInfo> {
+        match info.as_any().downcast_ref::<StreamPartInfo>() {
+            Some(part_ref) => Ok(part_ref),
+            None => Err(ErrorCode::LogicalError(
+                "Cannot downcast from PartInfo to StreamPartInfo.",
+            )),
+        }
+    }
+}
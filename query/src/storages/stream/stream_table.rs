@@ -0,0 +1,472 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodSerializer;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::MetaId;
+use common_meta_types::TableInfo;
+use common_planners::Extras;
+use common_planners::PartInfo;
+use common_planners::PartInfoPtr;
+use common_planners::Partitions;
+use common_planners::ReadDataSourcePlan;
+use common_planners::Statistics;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::AsyncSource;
+use crate::pipelines::new::processors::AsyncSourcer;
+use crate::pipelines::new::NewPipeline;
+use crate::pipelines::new::SourcePipeBuilder;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::BlockReader;
+use crate::storages::fuse::FusePartInfo;
+use crate::storages::fuse::FuseTable;
+use crate::storages::stream::StreamPartInfo;
+use crate::storages::StorageContext;
+use crate::storages::StorageDescription;
+use crate::storages::Table;
+
+pub const STREAM_ENGINE: &str = "STREAM";
+/// Id of the table on which the stream is created
+pub const OPT_KEY_TABLE_ID: &str = "table_id";
+/// Name of the table on which the stream is created
+pub const OPT_KEY_TABLE_NAME: &str = "table_name";
+/// Database of the table on which the stream is created
+pub const OPT_KEY_TABLE_DATABASE: &str = "table_database";
+/// Id of the snapshot since which the changes are returned, absent if the table was empty
+pub const OPT_KEY_OFFSET: &str = "offset";
+
+/// The column of a stream telling how each row is changed, either `INSERT` or `DELETE`
+pub const CHANGE_TYPE_COLUMN: &str = "_change_type";
+pub const CHANGE_TYPE_INSERT: &str = "INSERT";
+pub const CHANGE_TYPE_DELETE: &str = "DELETE";
+
+/// A stream returns the rows which have been inserted into and deleted from a fuse table,
+/// since the offset snapshot of the stream.
+///
+/// The rows of the rewritten blocks, e.g. by a compaction, a recluster, an UPDATE or a MERGE,
+/// are both deleted and inserted, they cancel out each other and only the rows really changed
+/// are returned.
+pub struct StreamTable {
+    table_info: TableInfo,
+    table_id: MetaId,
+    table_name: String,
+    table_database: Option<String>,
+    offset: Option<String>,
+}
+
+impl StreamTable {
+    pub fn try_create(_ctx: StorageContext, table_info: TableInfo) -> Result<Box<dyn Table>> {
+        let options = table_info.options();
+        let table_id = options
+            .get(OPT_KEY_TABLE_ID)
+            .and_then(|id| id.parse::<MetaId>().ok())
+            .ok_or_else(|| {
+                ErrorCode::LogicalError(format!(
+                    "Invalid stream, table option {} not found",
+                    OPT_KEY_TABLE_ID
+                ))
+            })?;
+        let table_name = options.get(OPT_KEY_TABLE_NAME).cloned().unwrap_or_default();
+        let table_database = options.get(OPT_KEY_TABLE_DATABASE).cloned();
+        let offset = options.get(OPT_KEY_OFFSET).cloned();
+        Ok(Box::new(StreamTable {
+            table_info,
+            table_id,
+            table_name,
+            table_database,
+            offset,
+        }))
+    }
+
+    /// The database and the name of the table on which the stream is created. The database is
+    /// unknown for the streams created before it was recorded.
+    pub fn source_table_name(&self) -> (Option<&str>, &str) {
+        (self.table_database.as_deref(), &self.table_name)
+    }
+
+    pub fn description() -> StorageDescription {
+        StorageDescription {
+            engine_name: STREAM_ENGINE.to_string(),
+            comment: "STREAM Storage Engine".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// The schema of the stream on a table of `schema`: the columns of the table, followed by
+    /// the column of the change type.
+    pub fn stream_schema(schema: &DataSchema) -> Result<DataSchemaRef> {
+        if schema.has_field(CHANGE_TYPE_COLUMN) {
+            return Err(ErrorCode::BadArguments(format!(
+                "Can not create stream on table with column {}",
+                CHANGE_TYPE_COLUMN
+            )));
+        }
+        let mut fields = schema.fields().clone();
+        fields.push(DataField::new(CHANGE_TYPE_COLUMN, Vu8::to_data_type()));
+        Ok(Arc::new(DataSchema::new_from(
+            fields,
+            schema.meta().clone(),
+        )))
+    }
+
+    /// The schema of the rows of the table, i.e. the one of the stream without the column of
+    /// the change type.
+    fn rows_schema(schema: &DataSchema) -> DataSchemaRef {
+        let mut fields = schema.fields().clone();
+        fields.pop();
+        Arc::new(DataSchema::new_from(fields, schema.meta().clone()))
+    }
+
+    /// The table on which the stream is created.
+    pub async fn source_table(&self, ctx: &QueryContext) -> Result<Arc<dyn Table>> {
+        let catalog = ctx.get_catalog();
+        let (ident, meta) = catalog.get_table_meta_by_id(self.table_id).await?;
+        let table_info = TableInfo {
+            ident,
+            desc: "".to_owned(),
+            name: self.table_name.clone(),
+            meta: meta.as_ref().clone(),
+        };
+        catalog.get_table_by_info(&table_info)
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for StreamTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn benefit_column_prune(&self) -> bool {
+        true
+    }
+
+    async fn read_partitions(
+        &self,
+        ctx: Arc<QueryContext>,
+        _push_downs: Option<Extras>,
+    ) -> Result<(Statistics, Partitions)> {
+        let table = self.source_table(ctx.as_ref()).await?;
+        let table = FuseTable::try_from_table(table.as_ref())?;
+        let changes = table
+            .changes_since(ctx.as_ref(), self.offset.as_deref())
+            .await?;
+        if changes.inserted_blocks.is_empty() && changes.deleted_blocks.is_empty() {
+            return Ok((Statistics::default(), vec![]));
+        }
+
+        // all the columns are read, the rows are compared as a whole
+        let schema = Self::rows_schema(&self.table_info.schema());
+        let (mut statistics, inserted) =
+            FuseTable::to_partitions(&schema, &changes.inserted_blocks, None);
+        let (_, deleted) = FuseTable::to_partitions(&schema, &changes.deleted_blocks, None);
+        let to_fuse_parts = |parts: Partitions| {
+            parts
+                .iter()
+                .map(|part| FusePartInfo::from_part(part).cloned())
+                .collect::<Result<Vec<_>>>()
+        };
+        let inserted = to_fuse_parts(inserted)?;
+        let deleted = to_fuse_parts(deleted)?;
+
+        let parts = if inserted.is_empty() || deleted.is_empty() {
+            // nothing is cancelled out, each block is a partition on its own
+            let inserted = inserted
+                .into_iter()
+                .map(|part| StreamPartInfo::create(vec![part], vec![], 0, 1));
+            let deleted = deleted
+                .into_iter()
+                .map(|part| StreamPartInfo::create(vec![], vec![part], 0, 1));
+            inserted.chain(deleted).collect::<Vec<_>>()
+        } else {
+            // the rows are spread over the partitions by their hashes, thus a row and the ones
+            // cancelling it out are always in the same partition
+            let max_threads = ctx.get_settings().get_max_threads()? as usize;
+            let buckets = std::cmp::max(1, std::cmp::min(max_threads, inserted.len()));
+            (0..buckets)
+                .map(|bucket| {
+                    StreamPartInfo::create(inserted.clone(), deleted.clone(), bucket, buckets)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        statistics.partitions_scanned = parts.len();
+        statistics.partitions_total = parts.len();
+        statistics.is_exact = false;
+        Ok((statistics, parts))
+    }
+
+    async fn read(
+        &self,
+        ctx: Arc<QueryContext>,
+        plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let reader = StreamChangesReader::create(&ctx, self.table_info.schema(), &plan.push_downs)?;
+        let stream = futures::stream::try_unfold(None, move |mut cursor| {
+            let ctx = ctx.clone();
+            let reader = reader.clone();
+            async move {
+                let block = reader.next(&ctx, &mut cursor).await?;
+                Ok(block.map(|block| (block, cursor)))
+            }
+        });
+        Ok(Box::pin(stream))
+    }
+
+    fn read2(
+        &self,
+        ctx: Arc<QueryContext>,
+        plan: &ReadDataSourcePlan,
+        pipeline: &mut NewPipeline,
+    ) -> Result<()> {
+        let reader = StreamChangesReader::create(&ctx, self.table_info.schema(), &plan.push_downs)?;
+        let max_threads = ctx.get_settings().get_max_threads()? as usize;
+        let max_threads = std::cmp::min(plan.parts.len(), max_threads);
+
+        let mut source_builder = SourcePipeBuilder::create();
+        for _index in 0..std::cmp::max(1, max_threads) {
+            let output = OutputPort::create();
+            source_builder.add_source(
+                output.clone(),
+                StreamSource::create(ctx.clone(), output, reader.clone())?,
+            );
+        }
+        pipeline.add_pipe(source_builder.finalize());
+        Ok(())
+    }
+}
+
+/// Reads the net changes of a stream: the rows of the inserted blocks which are not the rows of
+/// the deleted blocks, and the other way around.
+///
+/// The blocks are read one by one, only the keys of the deleted rows of the partition being
+/// read are kept in memory.
+#[derive(Clone)]
+struct StreamChangesReader {
+    schema: DataSchemaRef,
+    block_reader: Arc<BlockReader>,
+    projection: Option<Vec<usize>>,
+}
+
+/// The position of the reading of a partition of the changes.
+struct StreamChangesCursor {
+    part: PartInfoPtr,
+    /// The keys of the deleted rows of the partition, with the numbers of them not cancelled
+    /// out yet. `None` if nothing is cancelled out, i.e. the partition has either no inserted
+    /// blocks or no deleted ones.
+    deleted_keys: Option<HashMap<Vec<u8>, usize>>,
+    /// The index of the next block to read, the inserted blocks followed by the deleted ones.
+    next_block: usize,
+}
+
+impl StreamChangesReader {
+    fn create(
+        ctx: &Arc<QueryContext>,
+        schema: DataSchemaRef,
+        push_downs: &Option<Extras>,
+    ) -> Result<StreamChangesReader> {
+        let rows_schema = StreamTable::rows_schema(&schema);
+        Ok(StreamChangesReader {
+            block_reader: FuseTable::create_block_reader(ctx, rows_schema, &None)?,
+            schema,
+            projection: push_downs.as_ref().and_then(|p| p.projection.clone()),
+        })
+    }
+
+    /// Reads the next block of the changes, the next partition is taken once the one of the
+    /// cursor is exhausted.
+    async fn next(
+        &self,
+        ctx: &QueryContext,
+        cursor: &mut Option<StreamChangesCursor>,
+    ) -> Result<Option<DataBlock>> {
+        loop {
+            if let Some(cursor) = cursor {
+                if let Some(block) = self.next_block(cursor).await? {
+                    return Ok(Some(block));
+                }
+            }
+            match ctx.try_get_partitions(1)?.pop() {
+                None => return Ok(None),
+                Some(part) => *cursor = Some(self.open(part).await?),
+            }
+        }
+    }
+
+    /// Opens a partition, the deleted blocks are read to count the keys of its deleted rows.
+    async fn open(&self, part: PartInfoPtr) -> Result<StreamChangesCursor> {
+        let info = StreamPartInfo::from_part(&part)?;
+        let mut deleted_keys = None;
+        if !info.inserted.is_empty() && !info.deleted.is_empty() {
+            let mut counts = HashMap::new();
+            for deleted in &info.deleted {
+                let block = self.read_block(deleted).await?;
+                for (_, key) in Self::partition_keys(info, &block)? {
+                    *counts.entry(key).or_default() += 1;
+                }
+            }
+            deleted_keys = Some(counts);
+        }
+        Ok(StreamChangesCursor {
+            part,
+            deleted_keys,
+            next_block: 0,
+        })
+    }
+
+    /// Reads the changes of the next non-empty block of the partition of the cursor, `None` if
+    /// all of its blocks are read.
+    async fn next_block(&self, cursor: &mut StreamChangesCursor) -> Result<Option<DataBlock>> {
+        let info = StreamPartInfo::from_part(&cursor.part)?;
+        loop {
+            let index = cursor.next_block;
+            let (part, is_inserted) = match index.checked_sub(info.inserted.len()) {
+                None => (&info.inserted[index], true),
+                Some(index) => match info.deleted.get(index) {
+                    None => return Ok(None),
+                    Some(part) => (part, false),
+                },
+            };
+            cursor.next_block += 1;
+
+            let mut block = self.read_block(part).await?;
+            if let Some(counts) = &mut cursor.deleted_keys {
+                // an inserted row is cancelled out by a deleted one of the same key, the deleted
+                // rows left once all the inserted ones are read are the ones not cancelled out
+                let mut rows = vec![];
+                for (row, key) in Self::partition_keys(info, &block)? {
+                    let cancelled = match counts.get_mut(&key) {
+                        Some(count) if *count > 0 => {
+                            *count -= 1;
+                            true
+                        }
+                        _ => false,
+                    };
+                    if cancelled != is_inserted {
+                        rows.push(row);
+                    }
+                }
+                block = DataBlock::block_take_by_indices(&block, &rows)?;
+            }
+            if block.num_rows() == 0 {
+                continue;
+            }
+
+            let change_type = match is_inserted {
+                true => CHANGE_TYPE_INSERT,
+                false => CHANGE_TYPE_DELETE,
+            };
+            return Ok(Some(
+                self.project(self.with_change_type(block, change_type)),
+            ));
+        }
+    }
+
+    async fn read_block(&self, part: &FusePartInfo) -> Result<DataBlock> {
+        let part: Box<dyn PartInfo> = Box::new(part.clone());
+        self.block_reader.read(Arc::new(part)).await
+    }
+
+    /// The rows of the block belonging to the partition, with their keys serializing each row
+    /// as a whole.
+    fn partition_keys(info: &StreamPartInfo, block: &DataBlock) -> Result<Vec<(u32, Vec<u8>)>> {
+        let columns = block
+            .columns()
+            .iter()
+            .map(|c| c.convert_full_column())
+            .collect::<Vec<_>>();
+        let column_refs = columns.iter().collect::<Vec<_>>();
+        let keys = HashMethodSerializer::default().build_keys(&column_refs, block.num_rows())?;
+        Ok(keys
+            .into_iter()
+            .enumerate()
+            .filter(|(_, key)| {
+                if info.buckets <= 1 {
+                    return true;
+                }
+                let mut hasher = DefaultHasher::new();
+                key.hash(&mut hasher);
+                hasher.finish() as usize % info.buckets == info.bucket
+            })
+            .map(|(row, key)| (row as u32, key.to_vec()))
+            .collect())
+    }
+
+    fn with_change_type(&self, block: DataBlock, change_type: &str) -> DataBlock {
+        let mut columns = block.columns().to_vec();
+        columns.push(Series::from_data(vec![change_type; block.num_rows()]));
+        DataBlock::create(self.schema.clone(), columns)
+    }
+
+    fn project(&self, block: DataBlock) -> DataBlock {
+        match &self.projection {
+            None => block,
+            Some(projection) => {
+                let schema = Arc::new(block.schema().project(projection.clone()));
+                let columns = projection.iter().map(|i| block.column(*i).clone());
+                DataBlock::create(schema, columns.collect())
+            }
+        }
+    }
+}
+
+struct StreamSource {
+    ctx: Arc<QueryContext>,
+    reader: StreamChangesReader,
+    cursor: Option<StreamChangesCursor>,
+}
+
+impl StreamSource {
+    fn create(
+        ctx: Arc<QueryContext>,
+        output: Arc<OutputPort>,
+        reader: StreamChangesReader,
+    ) -> Result<ProcessorPtr> {
+        AsyncSourcer::create(ctx.clone(), output, StreamSource {
+            ctx,
+            reader,
+            cursor: None,
+        })
+    }
+}
+
+impl AsyncSource for StreamSource {
+    const NAME: &'static str = "StreamSource";
+
+    type BlockFuture<'a> = impl Future<Output = Result<Option<DataBlock>>>;
+
+    fn generate(&mut self) -> Self::BlockFuture<'_> {
+        async { self.reader.next(&self.ctx, &mut self.cursor).await }
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_types::TableIdent;
+use common_meta_types::TableInfo;
+use common_meta_types::TableMeta;
+
+use crate::sessions::QueryContext;
+use crate::storages::system::table::AsyncOneBlockSystemTable;
+use crate::storages::system::table::AsyncSystemTable;
+use crate::storages::Table;
+
+pub struct ConnectionsTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for ConnectionsTable {
+    const NAME: &'static str = "system.connections";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<QueryContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let connections = ctx.get_user_manager().get_connections(&tenant).await?;
+
+        // The credentials are never listed, even encrypted.
+        let mut names: Vec<String> = Vec::with_capacity(connections.len());
+        let mut storage_types: Vec<String> = Vec::with_capacity(connections.len());
+        let mut master_key_ids: Vec<String> = Vec::with_capacity(connections.len());
+        let mut comments: Vec<String> = Vec::with_capacity(connections.len());
+        let mut created_ons: Vec<i64> = Vec::with_capacity(connections.len());
+        let mut updated_ons: Vec<i64> = Vec::with_capacity(connections.len());
+        for connection in connections {
+            names.push(connection.name);
+            storage_types.push(connection.storage_type);
+            master_key_ids.push(connection.master_key_id);
+            comments.push(connection.comment);
+            created_ons.push(connection.created_on.timestamp());
+            updated_ons.push(connection.updated_on.timestamp());
+        }
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(names),
+            Series::from_data(storage_types),
+            Series::from_data(master_key_ids),
+            Series::from_data(comments),
+            Series::from_data(created_ons),
+            Series::from_data(updated_ons),
+        ]))
+    }
+}
+
+impl ConnectionsTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("storage_type", Vu8::to_data_type()),
+            DataField::new("master_key_id", Vu8::to_data_type()),
+            DataField::new("comment", Vu8::to_data_type()),
+            DataField::new("created_on", TimestampType::new_impl(0)),
+            DataField::new("updated_on", TimestampType::new_impl(0)),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'connections'".to_string(),
+            name: "connections".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemConnections".to_string(),
+                ..Default::default()
+            },
+        };
+        AsyncOneBlockSystemTable::create(ConnectionsTable { table_info })
+    }
+}
@@ -15,19 +15,26 @@
 mod clusters_table;
 mod columns_table;
 mod configs_table;
+mod connections_table;
 mod contributors_table;
 mod credits_table;
 mod databases_table;
 mod engines_table;
 mod functions_table;
 mod metrics_table;
+mod network_policies_table;
 mod one_table;
+mod pipe_errors_table;
+mod pipe_files_table;
 mod processes_table;
 mod query_log_table;
+mod query_profile_table;
 mod roles_table;
+mod row_access_policies_table;
 mod settings_table;
 mod table;
 mod tables_table;
+mod tables_usage_table;
 mod tracing_table;
 mod tracing_table_stream;
 mod users_table;
@@ -36,18 +43,25 @@ mod warehouses_table;
 pub use clusters_table::ClustersTable;
 pub use columns_table::ColumnsTable;
 pub use configs_table::ConfigsTable;
+pub use connections_table::ConnectionsTable;
 pub use contributors_table::ContributorsTable;
 pub use credits_table::CreditsTable;
 pub use databases_table::DatabasesTable;
 pub use engines_table::EnginesTable;
 pub use functions_table::FunctionsTable;
 pub use metrics_table::MetricsTable;
+pub use network_policies_table::NetworkPoliciesTable;
 pub use one_table::OneTable;
+pub use pipe_errors_table::PipeErrorsTable;
+pub use pipe_files_table::PipeFilesTable;
 pub use processes_table::ProcessesTable;
 pub use query_log_table::QueryLogTable;
+pub use query_profile_table::QueryProfileTable;
 pub use roles_table::RolesTable;
+pub use row_access_policies_table::RowAccessPoliciesTable;
 pub use settings_table::SettingsTable;
 pub use tables_table::TablesTable;
+pub use tables_usage_table::TablesUsageTable;
 pub use tracing_table::TracingTable;
 pub use tracing_table_stream::TracingTableStream;
 pub use users_table::UsersTable;
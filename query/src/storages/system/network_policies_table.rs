@@ -0,0 +1,90 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_types::TableIdent;
+use common_meta_types::TableInfo;
+use common_meta_types::TableMeta;
+
+use crate::sessions::QueryContext;
+use crate::storages::system::table::AsyncOneBlockSystemTable;
+use crate::storages::system::table::AsyncSystemTable;
+use crate::storages::Table;
+
+pub struct NetworkPoliciesTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for NetworkPoliciesTable {
+    const NAME: &'static str = "system.network_policies";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<QueryContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let policies = ctx.get_user_manager().get_network_policies(&tenant).await?;
+
+        let mut names: Vec<String> = Vec::with_capacity(policies.len());
+        let mut allowed_ip_lists: Vec<String> = Vec::with_capacity(policies.len());
+        let mut blocked_ip_lists: Vec<String> = Vec::with_capacity(policies.len());
+        let mut comments: Vec<String> = Vec::with_capacity(policies.len());
+        let mut created_ons: Vec<i64> = Vec::with_capacity(policies.len());
+        for policy in policies {
+            names.push(policy.name);
+            allowed_ip_lists.push(policy.allowed_ip_list.join(", "));
+            blocked_ip_lists.push(policy.blocked_ip_list.join(", "));
+            comments.push(policy.comment);
+            created_ons.push(policy.created_on.timestamp());
+        }
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(names),
+            Series::from_data(allowed_ip_lists),
+            Series::from_data(blocked_ip_lists),
+            Series::from_data(comments),
+            Series::from_data(created_ons),
+        ]))
+    }
+}
+
+impl NetworkPoliciesTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("allowed_ip_list", Vu8::to_data_type()),
+            DataField::new("blocked_ip_list", Vu8::to_data_type()),
+            DataField::new("comment", Vu8::to_data_type()),
+            DataField::new("created_on", TimestampType::new_impl(0)),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'network_policies'".to_string(),
+            name: "network_policies".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemNetworkPolicies".to_string(),
+                ..Default::default()
+            },
+        };
+        AsyncOneBlockSystemTable::create(NetworkPoliciesTable { table_info })
+    }
+}
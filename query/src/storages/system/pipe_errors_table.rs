@@ -0,0 +1,92 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_types::PipeFileStatus;
+use common_meta_types::TableIdent;
+use common_meta_types::TableInfo;
+use common_meta_types::TableMeta;
+
+use crate::sessions::QueryContext;
+use crate::storages::system::table::AsyncOneBlockSystemTable;
+use crate::storages::system::table::AsyncSystemTable;
+use crate::storages::Table;
+
+/// The files which the pipes failed to load, with the causes.
+pub struct PipeErrorsTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for PipeErrorsTable {
+    const NAME: &'static str = "system.pipe_errors";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<QueryContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let user_mgr = ctx.get_user_manager();
+
+        let mut pipes: Vec<String> = vec![];
+        let mut file_names: Vec<String> = vec![];
+        let mut errors: Vec<String> = vec![];
+        let mut load_times: Vec<i64> = vec![];
+        for pipe in user_mgr.get_pipes(&tenant).await? {
+            for file in user_mgr.get_pipe_files(&tenant, &pipe.pipe_name).await? {
+                if file.status == PipeFileStatus::Failed {
+                    pipes.push(pipe.pipe_name.clone());
+                    file_names.push(file.file_name);
+                    errors.push(file.error);
+                    load_times.push(file.load_time.timestamp());
+                }
+            }
+        }
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(pipes),
+            Series::from_data(file_names),
+            Series::from_data(errors),
+            Series::from_data(load_times),
+        ]))
+    }
+}
+
+impl PipeErrorsTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("pipe", Vu8::to_data_type()),
+            DataField::new("file_name", Vu8::to_data_type()),
+            DataField::new("error", Vu8::to_data_type()),
+            DataField::new("load_time", TimestampType::new_impl(0)),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'pipe_errors'".to_string(),
+            name: "pipe_errors".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemPipeErrors".to_string(),
+                ..Default::default()
+            },
+        };
+        AsyncOneBlockSystemTable::create(PipeErrorsTable { table_info })
+    }
+}
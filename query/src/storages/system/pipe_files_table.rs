@@ -0,0 +1,93 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_types::TableIdent;
+use common_meta_types::TableInfo;
+use common_meta_types::TableMeta;
+
+use crate::sessions::QueryContext;
+use crate::storages::system::table::AsyncOneBlockSystemTable;
+use crate::storages::system::table::AsyncSystemTable;
+use crate::storages::Table;
+
+/// The load history of the pipes which consume the files of stages.
+pub struct PipeFilesTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for PipeFilesTable {
+    const NAME: &'static str = "system.pipe_files";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<QueryContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let user_mgr = ctx.get_user_manager();
+
+        let mut pipes: Vec<String> = vec![];
+        let mut file_names: Vec<String> = vec![];
+        let mut statuses: Vec<String> = vec![];
+        let mut rows: Vec<u64> = vec![];
+        let mut load_times: Vec<i64> = vec![];
+        for pipe in user_mgr.get_pipes(&tenant).await? {
+            for file in user_mgr.get_pipe_files(&tenant, &pipe.pipe_name).await? {
+                pipes.push(pipe.pipe_name.clone());
+                file_names.push(file.file_name);
+                statuses.push(format!("{:?}", file.status));
+                rows.push(file.rows);
+                load_times.push(file.load_time.timestamp());
+            }
+        }
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(pipes),
+            Series::from_data(file_names),
+            Series::from_data(statuses),
+            Series::from_data(rows),
+            Series::from_data(load_times),
+        ]))
+    }
+}
+
+impl PipeFilesTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("pipe", Vu8::to_data_type()),
+            DataField::new("file_name", Vu8::to_data_type()),
+            DataField::new("status", Vu8::to_data_type()),
+            DataField::new("rows", u64::to_data_type()),
+            DataField::new("load_time", TimestampType::new_impl(0)),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'pipe_files'".to_string(),
+            name: "pipe_files".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemPipeFiles".to_string(),
+                ..Default::default()
+            },
+        };
+        AsyncOneBlockSystemTable::create(PipeFilesTable { table_info })
+    }
+}
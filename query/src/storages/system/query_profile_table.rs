@@ -0,0 +1,70 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_meta_types::TableIdent;
+use common_meta_types::TableInfo;
+use common_meta_types::TableMeta;
+
+use crate::storages::view::view_table::QUERY;
+use crate::storages::view::ViewTable;
+use crate::storages::Table;
+
+/// The finished queries persisted by the query history of the current tenant, with the
+/// metrics of their pipelines in `profile`.
+pub struct QueryProfileTable {}
+
+impl QueryProfileTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let query = "SELECT
+            query_id,
+            query_kind,
+            query_text,
+            handler_type,
+            sql_user,
+            current_database,
+            client_address,
+            event_time,
+            duration_ms,
+            scan_rows,
+            scan_bytes,
+            written_rows,
+            written_bytes,
+            result_rows,
+            result_bytes,
+            memory_usage,
+            status,
+            exception_code,
+            exception,
+            profile
+        FROM system_history.query_history";
+
+        let mut options = BTreeMap::new();
+        options.insert(QUERY.to_string(), query.to_string());
+        let table_info = TableInfo {
+            desc: "'system'.'query_profile'".to_string(),
+            name: "query_profile".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                options,
+                engine: "VIEW".to_string(),
+                ..Default::default()
+            },
+        };
+
+        ViewTable::create(table_info)
+    }
+}
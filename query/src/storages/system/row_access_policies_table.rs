@@ -0,0 +1,102 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_types::TableIdent;
+use common_meta_types::TableInfo;
+use common_meta_types::TableMeta;
+
+use crate::sessions::QueryContext;
+use crate::storages::system::table::AsyncOneBlockSystemTable;
+use crate::storages::system::table::AsyncSystemTable;
+use crate::storages::Table;
+
+pub struct RowAccessPoliciesTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for RowAccessPoliciesTable {
+    const NAME: &'static str = "system.row_access_policies";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<QueryContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let policies = ctx
+            .get_user_manager()
+            .get_row_access_policies(&tenant)
+            .await?;
+
+        let mut names: Vec<String> = Vec::with_capacity(policies.len());
+        let mut signatures: Vec<String> = Vec::with_capacity(policies.len());
+        let mut bodies: Vec<String> = Vec::with_capacity(policies.len());
+        let mut exempt_roles: Vec<String> = Vec::with_capacity(policies.len());
+        let mut comments: Vec<String> = Vec::with_capacity(policies.len());
+        let mut created_ons: Vec<i64> = Vec::with_capacity(policies.len());
+        for policy in policies {
+            let args = policy
+                .args
+                .iter()
+                .map(|arg| format!("{} {}", arg.name(), arg.data_type().sql_name()))
+                .collect::<Vec<_>>();
+            names.push(policy.name);
+            signatures.push(format!("({})", args.join(", ")));
+            bodies.push(policy.body);
+            exempt_roles.push(policy.exempt_roles.join(", "));
+            comments.push(policy.comment);
+            created_ons.push(policy.created_on.timestamp());
+        }
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(names),
+            Series::from_data(signatures),
+            Series::from_data(bodies),
+            Series::from_data(exempt_roles),
+            Series::from_data(comments),
+            Series::from_data(created_ons),
+        ]))
+    }
+}
+
+impl RowAccessPoliciesTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("signature", Vu8::to_data_type()),
+            DataField::new("body", Vu8::to_data_type()),
+            DataField::new("exempt_roles", Vu8::to_data_type()),
+            DataField::new("comment", Vu8::to_data_type()),
+            DataField::new("created_on", TimestampType::new_impl(0)),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'row_access_policies'".to_string(),
+            name: "row_access_policies".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemRowAccessPolicies".to_string(),
+                ..Default::default()
+            },
+        };
+        AsyncOneBlockSystemTable::create(RowAccessPoliciesTable { table_info })
+    }
+}
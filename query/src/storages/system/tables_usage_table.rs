@@ -0,0 +1,117 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_types::TableIdent;
+use common_meta_types::TableInfo;
+use common_meta_types::TableMeta;
+
+use crate::catalogs::Catalog;
+use crate::sessions::QueryContext;
+use crate::storages::system::table::AsyncOneBlockSystemTable;
+use crate::storages::system::table::AsyncSystemTable;
+use crate::storages::Table;
+
+/// The storage usage of the tables, as recorded in the meta of them by the last commits.
+///
+/// Unlike `system.tables`, nothing is read from the storage of the tables.
+pub struct TablesUsageTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for TablesUsageTable {
+    const NAME: &'static str = "system.tables_usage";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<QueryContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let catalog = ctx.get_catalog();
+        let databases = catalog.list_databases(tenant.as_str()).await?;
+
+        let mut database_tables = vec![];
+        for database in databases {
+            let name = database.name();
+            for table in catalog.list_tables(tenant.as_str(), name).await? {
+                database_tables.push((name.to_string(), table));
+            }
+        }
+
+        let databases: Vec<&[u8]> = database_tables.iter().map(|(d, _)| d.as_bytes()).collect();
+        let names: Vec<&[u8]> = database_tables
+            .iter()
+            .map(|(_, v)| v.name().as_bytes())
+            .collect();
+        let engines: Vec<&[u8]> = database_tables
+            .iter()
+            .map(|(_, v)| v.engine().as_bytes())
+            .collect();
+        let usages = database_tables
+            .iter()
+            .map(|(_, v)| &v.get_table_info().meta.usage)
+            .collect::<Vec<_>>();
+        let num_rows: Vec<u64> = usages.iter().map(|u| u.number_of_rows).collect();
+        let num_blocks: Vec<u64> = usages.iter().map(|u| u.number_of_blocks).collect();
+        let data_size: Vec<u64> = usages.iter().map(|u| u.data_bytes).collect();
+        let data_compressed_size: Vec<u64> =
+            usages.iter().map(|u| u.compressed_data_bytes).collect();
+        let index_size: Vec<u64> = usages.iter().map(|u| u.index_data_bytes).collect();
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(databases),
+            Series::from_data(names),
+            Series::from_data(engines),
+            Series::from_data(num_rows),
+            Series::from_data(num_blocks),
+            Series::from_data(data_size),
+            Series::from_data(data_compressed_size),
+            Series::from_data(index_size),
+        ]))
+    }
+}
+
+impl TablesUsageTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("database", Vu8::to_data_type()),
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("engine", Vu8::to_data_type()),
+            DataField::new("num_rows", u64::to_data_type()),
+            DataField::new("num_blocks", u64::to_data_type()),
+            DataField::new("data_size", u64::to_data_type()),
+            DataField::new("data_compressed_size", u64::to_data_type()),
+            DataField::new("index_size", u64::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'tables_usage'".to_string(),
+            name: "tables_usage".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemTablesUsage".to_string(),
+                ..Default::default()
+            },
+        };
+
+        AsyncOneBlockSystemTable::create(TablesUsageTable { table_info })
+    }
+}
@@ -24,8 +24,16 @@ use common_planners::Expression;
 
 use crate::catalogs::SYS_TBL_FUC_ID_END;
 use crate::catalogs::SYS_TBL_FUNC_ID_BEGIN;
+use crate::storages::fuse::ClusteringInformationTable;
+use crate::storages::fuse::FuseBlockTable;
 use crate::storages::fuse::FuseHistoryTable;
+use crate::storages::fuse::FuseSnapshotDiffTable;
+use crate::storages::fuse::FuseVerifyTable;
+use crate::storages::fuse::FUSE_FUNC_BLOCK;
+use crate::storages::fuse::FUSE_FUNC_CLUSTERING;
 use crate::storages::fuse::FUSE_FUNC_HIST;
+use crate::storages::fuse::FUSE_FUNC_SNAPSHOT_DIFF;
+use crate::storages::fuse::FUSE_FUNC_VERIFY;
 use crate::table_functions::NumbersTable;
 use crate::table_functions::TableFunction;
 
@@ -100,6 +108,26 @@ impl TableFunctionFactory {
             (next_id(), Arc::new(FuseHistoryTable::create)),
         );
 
+        creators.insert(
+            FUSE_FUNC_CLUSTERING.to_string(),
+            (next_id(), Arc::new(ClusteringInformationTable::create)),
+        );
+
+        creators.insert(
+            FUSE_FUNC_VERIFY.to_string(),
+            (next_id(), Arc::new(FuseVerifyTable::create)),
+        );
+
+        creators.insert(
+            FUSE_FUNC_BLOCK.to_string(),
+            (next_id(), Arc::new(FuseBlockTable::create)),
+        );
+
+        creators.insert(
+            FUSE_FUNC_SNAPSHOT_DIFF.to_string(),
+            (next_id(), Arc::new(FuseSnapshotDiffTable::create)),
+        );
+
         TableFunctionFactory {
             creators: RwLock::new(creators),
         }
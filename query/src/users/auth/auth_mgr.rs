@@ -22,12 +22,14 @@ use common_meta_types::UserInfo;
 
 pub use crate::configs::Config;
 use crate::users::auth::jwt::JwtAuthenticator;
+use crate::users::auth::oidc::OidcAuthenticator;
 use crate::users::UserApiProvider;
 
 pub struct AuthMgr {
     tenant: String,
     user_mgr: Arc<UserApiProvider>,
     jwt: Option<JwtAuthenticator>,
+    oidc: Option<OidcAuthenticator>,
 }
 
 pub enum Credential {
@@ -46,19 +48,48 @@ impl AuthMgr {
         Ok(AuthMgr {
             user_mgr,
             tenant: cfg.query.tenant_id.clone(),
-            jwt: JwtAuthenticator::try_create(cfg).await?,
+            jwt: JwtAuthenticator::try_create(cfg.clone()).await?,
+            oidc: OidcAuthenticator::try_create(cfg).await?,
         })
     }
 
-    pub async fn no_auth(&self) -> Result<UserInfo> {
-        self.user_mgr
+    pub async fn no_auth(&self, client_ip: Option<&str>) -> Result<UserInfo> {
+        let user_info = self
+            .user_mgr
             .get_user(&self.tenant, UserIdentity::new("root", "127.0.0.1"))
-            .await
+            .await?;
+        self.user_mgr
+            .check_network_policy(&self.tenant, &user_info, client_ip)
+            .await?;
+        Ok(user_info)
+    }
+
+    /// Authenticates the credential, then checks the client address against the network policy
+    /// of the user.
+    pub async fn auth(
+        &self,
+        credential: &Credential,
+        client_ip: Option<&str>,
+    ) -> Result<(Option<String>, UserInfo)> {
+        let (tenant, user_info) = self.auth_credential(credential).await?;
+        self.user_mgr
+            .check_network_policy(
+                tenant.as_ref().unwrap_or(&self.tenant),
+                &user_info,
+                client_ip,
+            )
+            .await?;
+        Ok((tenant, user_info))
     }
 
-    pub async fn auth(&self, credential: &Credential) -> Result<(Option<String>, UserInfo)> {
+    async fn auth_credential(&self, credential: &Credential) -> Result<(Option<String>, UserInfo)> {
         match credential {
             Credential::Jwt { token: t } => {
+                // The bearer tokens issued by the OIDC identity provider are its ID tokens.
+                if let Some(oidc) = self.oidc.as_ref().filter(|oidc| oidc.is_issuer_of(t)) {
+                    return Ok((None, self.auth_oidc(oidc, t).await?));
+                }
+
                 let jwt = match &self.jwt {
                     Some(j) => j.parse_jwt(t.as_str()).await?,
                     None => return Err(ErrorCode::AuthenticateFailure("jwt auth not configured.")),
@@ -118,4 +149,44 @@ impl AuthMgr {
             }
         }
     }
+
+    async fn auth_oidc(&self, oidc: &OidcAuthenticator, token: &str) -> Result<UserInfo> {
+        let identity = oidc.verify(token).await?;
+        let user = UserIdentity::new(&identity.user_name, "%");
+        if oidc.auto_provision() {
+            let mut user_info = UserInfo::new(&identity.user_name, "%", AuthInfo::JWT);
+            for role in identity.roles.iter() {
+                user_info.grants.grant_role(role.clone());
+            }
+            self.user_mgr
+                .add_user(&self.tenant, user_info, true)
+                .await?;
+        }
+
+        // Only the users authenticated by tokens could log in with the ID tokens, the name of a
+        // password user is not a credential.
+        let mut user_info = self.user_mgr.get_user(&self.tenant, user.clone()).await?;
+        if user_info.auth_info != AuthInfo::JWT {
+            return Err(ErrorCode::AuthenticateFailure("wrong auth type"));
+        }
+
+        // The roles of the user are synced to the ones of the claims on each login.
+        for role in user_info.grants.roles() {
+            if !identity.roles.contains(&role) {
+                self.user_mgr
+                    .revoke_role_from_user(&self.tenant, user.clone(), role.clone())
+                    .await?;
+                user_info.grants.revoke_role(&role);
+            }
+        }
+        for role in identity.roles {
+            if !user_info.grants.roles().contains(&role) {
+                self.user_mgr
+                    .grant_role_to_user(&self.tenant, user.clone(), role.clone())
+                    .await?;
+                user_info.grants.grant_role(role);
+            }
+        }
+        Ok(user_info)
+    }
 }
@@ -14,3 +14,4 @@
 
 pub(crate) mod auth_mgr;
 pub mod jwt;
+pub mod oidc;
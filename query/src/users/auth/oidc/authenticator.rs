@@ -0,0 +1,170 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use jwtk::jwk::RemoteJwksVerifier;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::configs::Config;
+
+/// Verifies the ID tokens issued by an OIDC identity provider to the client of databend.
+pub struct OidcAuthenticator {
+    issuer: String,
+    client_id: String,
+    user_claim: String,
+    roles_claim: String,
+    auto_provision: bool,
+    verifier: RemoteJwksVerifier,
+}
+
+/// The user of a verified ID token, mapped from its claims.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OidcIdentity {
+    pub user_name: String,
+    pub roles: Vec<String>,
+}
+
+impl OidcAuthenticator {
+    pub async fn try_create(cfg: Config) -> Result<Option<Self>> {
+        let conf = cfg.query;
+        if conf.oidc_issuer.is_empty() {
+            return Ok(None);
+        }
+        if conf.oidc_client_id.is_empty() {
+            return Err(ErrorCode::InvalidConfig(
+                "oidc_client_id is required by the OIDC authentication",
+            ));
+        }
+
+        let jwks_url = if conf.oidc_jwks_url.is_empty() {
+            discover_jwks_url(&conf.oidc_issuer).await?
+        } else {
+            conf.oidc_jwks_url
+        };
+        let mut verifier = RemoteJwksVerifier::new(jwks_url, None, Duration::from_secs(15 * 60));
+        verifier.set_require_kid(false);
+        Ok(Some(OidcAuthenticator {
+            issuer: conf.oidc_issuer,
+            client_id: conf.oidc_client_id,
+            user_claim: conf.oidc_user_claim,
+            roles_claim: conf.oidc_roles_claim,
+            auto_provision: conf.oidc_auto_provision,
+            verifier,
+        }))
+    }
+
+    /// Whether to create the users on their first login.
+    pub fn auto_provision(&self) -> bool {
+        self.auto_provision
+    }
+
+    /// Whether the token claims to be issued by the identity provider. It only picks the
+    /// authenticator of a bearer token, which is verified by `verify`.
+    pub fn is_issuer_of(&self, token: &str) -> bool {
+        unverified_issuer(token).as_deref() == Some(self.issuer.as_str())
+    }
+
+    pub async fn verify(&self, token: &str) -> Result<OidcIdentity> {
+        // The signature, `exp` and `nbf` are checked by the verifier.
+        let token = self
+            .verifier
+            .verify::<Map<String, Value>>(token)
+            .await
+            .map_err(|e| ErrorCode::AuthenticateFailure(e.to_string()))?;
+        let claims = token.claims();
+        if claims.iss.as_deref() != Some(self.issuer.as_str()) {
+            return Err(ErrorCode::AuthenticateFailure(
+                "wrong issuer of the oidc id token",
+            ));
+        }
+        if !claims.aud.iter().any(|aud| aud == &self.client_id) {
+            return Err(ErrorCode::AuthenticateFailure(
+                "wrong audience of the oidc id token",
+            ));
+        }
+
+        let user_name = match self.user_claim.as_str() {
+            "sub" => claims.sub.clone(),
+            claim => claims
+                .extra
+                .get(claim)
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        };
+        let user_name = user_name.ok_or_else(|| {
+            ErrorCode::AuthenticateFailure(format!(
+                "missing claim `{}` in oidc id token",
+                self.user_claim
+            ))
+        })?;
+
+        let roles = match claims.extra.get(&self.roles_claim) {
+            None => vec![],
+            Some(Value::String(role)) => vec![role.clone()],
+            Some(Value::Array(roles)) => roles
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+            Some(_) => {
+                return Err(ErrorCode::AuthenticateFailure(format!(
+                    "invalid claim `{}` in oidc id token",
+                    self.roles_claim
+                )));
+            }
+        };
+
+        Ok(OidcIdentity { user_name, roles })
+    }
+}
+
+// The JWKS url of the identity provider, from its OpenID provider configuration.
+async fn discover_jwks_url(issuer: &str) -> Result<String> {
+    let url = format!(
+        "{}/.well-known/openid-configuration",
+        issuer.trim_end_matches('/')
+    );
+    let discovery_error = |cause: String| {
+        ErrorCode::InvalidConfig(format!(
+            "Cannot discover the OIDC provider {}: {}",
+            issuer, cause
+        ))
+    };
+
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| discovery_error(e.to_string()))?;
+    let body = response
+        .text()
+        .await
+        .map_err(|e| discovery_error(e.to_string()))?;
+    let provider: Value =
+        serde_json::from_str(&body).map_err(|e| discovery_error(e.to_string()))?;
+    match provider.get("jwks_uri").and_then(Value::as_str) {
+        Some(jwks_url) => Ok(jwks_url.to_string()),
+        None => Err(discovery_error("missing jwks_uri".to_string())),
+    }
+}
+
+// The `iss` claim of a token, without verifying it.
+fn unverified_issuer(token: &str) -> Option<String> {
+    let payload = token.split('.').nth(1)?;
+    let payload = base64::decode_config(payload, base64::URL_SAFE_NO_PAD).ok()?;
+    let claims: Map<String, Value> = serde_json::from_slice(&payload).ok()?;
+    claims.get("iss")?.as_str().map(str::to_string)
+}
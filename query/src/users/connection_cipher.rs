@@ -0,0 +1,160 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use openssl::rand::rand_bytes;
+use openssl::symm::decrypt_aead;
+use openssl::symm::encrypt_aead;
+use openssl::symm::Cipher;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::configs::QueryConfig;
+
+const IV_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// Encrypts the credentials of the connections with AES-256-GCM by the master key of the
+/// query nodes, and decrypts them by the current or the previous master key.
+pub struct ConnectionCipher {
+    master_key: MasterKey,
+    previous_master_key: Option<MasterKey>,
+}
+
+struct MasterKey {
+    // The id identifies the key without revealing it.
+    id: String,
+    key: Vec<u8>,
+}
+
+impl MasterKey {
+    fn try_create(key_file: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(key_file).map_err(|e| {
+            ErrorCode::InvalidConfig(format!(
+                "Cannot read the connection master key file {}: {}",
+                key_file, e
+            ))
+        })?;
+        let key = decode_hex(content.trim())
+            .filter(|key| key.len() == 32)
+            .ok_or_else(|| {
+                ErrorCode::InvalidConfig(format!(
+                    "The connection master key in {} must be 64 hex digits",
+                    key_file
+                ))
+            })?;
+
+        let digest = Sha256::digest(&key);
+        let id = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+        Ok(MasterKey { id, key })
+    }
+}
+
+impl ConnectionCipher {
+    pub fn try_create(conf: &QueryConfig) -> Result<Option<Self>> {
+        if conf.connection_master_key_file.is_empty() {
+            return Ok(None);
+        }
+
+        let master_key = MasterKey::try_create(&conf.connection_master_key_file)?;
+        let previous_master_key = match conf.connection_previous_master_key_file.as_str() {
+            "" => None,
+            key_file => Some(MasterKey::try_create(key_file)?),
+        };
+        Ok(Some(ConnectionCipher {
+            master_key,
+            previous_master_key,
+        }))
+    }
+
+    /// The id of the master key encrypting the credentials.
+    pub fn master_key_id(&self) -> &str {
+        &self.master_key.id
+    }
+
+    /// Encrypts the credentials of the connection, which the encrypted ones are bound to.
+    pub fn encrypt(&self, name: &str, credentials: &BTreeMap<String, String>) -> Result<Vec<u8>> {
+        let plain = serde_json::to_vec(credentials)?;
+        let mut iv = [0u8; IV_LEN];
+        rand_bytes(&mut iv).map_err(|e| ErrorCode::IllegalConnection(e.to_string()))?;
+        let mut tag = [0u8; TAG_LEN];
+        let encrypted = encrypt_aead(
+            Cipher::aes_256_gcm(),
+            &self.master_key.key,
+            Some(&iv),
+            name.as_bytes(),
+            &plain,
+            &mut tag,
+        )
+        .map_err(|e| ErrorCode::IllegalConnection(e.to_string()))?;
+
+        // The layout is iv, ciphertext, tag.
+        Ok([&iv[..], &encrypted[..], &tag[..]].concat())
+    }
+
+    pub fn decrypt(
+        &self,
+        name: &str,
+        master_key_id: &str,
+        encrypted: &[u8],
+    ) -> Result<BTreeMap<String, String>> {
+        let master_key = [Some(&self.master_key), self.previous_master_key.as_ref()]
+            .into_iter()
+            .flatten()
+            .find(|key| key.id == master_key_id)
+            .ok_or_else(|| {
+                ErrorCode::IllegalConnection(format!(
+                    "The master key {} encrypting the connection {} is not configured",
+                    master_key_id, name
+                ))
+            })?;
+        if encrypted.len() < IV_LEN + TAG_LEN {
+            return Err(ErrorCode::IllegalConnection(format!(
+                "The credentials of the connection {} are truncated",
+                name
+            )));
+        }
+
+        let (iv, encrypted) = encrypted.split_at(IV_LEN);
+        let (encrypted, tag) = encrypted.split_at(encrypted.len() - TAG_LEN);
+        let plain = decrypt_aead(
+            Cipher::aes_256_gcm(),
+            &master_key.key,
+            Some(iv),
+            name.as_bytes(),
+            encrypted,
+            tag,
+        )
+        .map_err(|e| {
+            ErrorCode::IllegalConnection(format!(
+                "Cannot decrypt the credentials of the connection {}: {}",
+                name, e
+            ))
+        })?;
+        Ok(serde_json::from_slice(&plain)?)
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
@@ -12,10 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod connection_cipher;
 mod role_mgr;
 mod user;
 mod user_api;
+mod user_connection;
 mod user_mgr;
+mod user_network_policy;
+mod user_pipe;
+mod user_row_access_policy;
 mod user_stage;
 mod user_udf;
 
@@ -26,6 +31,7 @@ mod user_warehouse;
 
 pub use auth::auth_mgr::AuthMgr;
 pub use auth::auth_mgr::Credential;
+pub use connection_cipher::ConnectionCipher;
 pub use role_cache_mgr::RoleCacheMgr;
 pub use user::CertifiedInfo;
 pub use user::User;
@@ -14,9 +14,18 @@
 
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_management::ConnectionApi;
+use common_management::ConnectionMgr;
+use common_management::NetworkPolicyApi;
+use common_management::NetworkPolicyMgr;
+use common_management::PipeApi;
+use common_management::PipeMgr;
 use common_management::RoleApi;
 use common_management::RoleMgr;
+use common_management::RowAccessPolicyApi;
+use common_management::RowAccessPolicyMgr;
 use common_management::SettingApi;
 use common_management::SettingMgr;
 use common_management::StageApi;
@@ -31,9 +40,11 @@ use common_meta_api::KVApi;
 
 use crate::common::MetaClientProvider;
 use crate::configs::Config;
+use crate::users::ConnectionCipher;
 
 pub struct UserApiProvider {
     client: Arc<dyn KVApi>,
+    connection_cipher: Option<ConnectionCipher>,
 }
 
 impl UserApiProvider {
@@ -42,7 +53,12 @@ impl UserApiProvider {
             .try_get_kv_client()
             .await?;
 
-        Ok(Arc::new(UserApiProvider { client }))
+        let connection_cipher = ConnectionCipher::try_create(&conf.query)?;
+
+        Ok(Arc::new(UserApiProvider {
+            client,
+            connection_cipher,
+        }))
     }
 
     pub fn get_user_api_client(&self, tenant: &str) -> Result<Arc<dyn UserApi>> {
@@ -57,6 +73,42 @@ impl UserApiProvider {
         Ok(Arc::new(StageMgr::create(self.client.clone(), tenant)?))
     }
 
+    pub fn get_pipe_api_client(&self, tenant: &str) -> Result<Arc<dyn PipeApi>> {
+        Ok(Arc::new(PipeMgr::create(self.client.clone(), tenant)?))
+    }
+
+    pub fn get_connection_api_client(&self, tenant: &str) -> Result<Arc<dyn ConnectionApi>> {
+        Ok(Arc::new(ConnectionMgr::create(
+            self.client.clone(),
+            tenant,
+        )?))
+    }
+
+    pub(crate) fn get_connection_cipher(&self) -> Result<&ConnectionCipher> {
+        self.connection_cipher.as_ref().ok_or_else(|| {
+            ErrorCode::InvalidConfig(
+                "Connections require the query.connection_master_key_file config",
+            )
+        })
+    }
+
+    pub fn get_network_policy_api_client(&self, tenant: &str) -> Result<Arc<dyn NetworkPolicyApi>> {
+        Ok(Arc::new(NetworkPolicyMgr::create(
+            self.client.clone(),
+            tenant,
+        )?))
+    }
+
+    pub fn get_row_access_policy_api_client(
+        &self,
+        tenant: &str,
+    ) -> Result<Arc<dyn RowAccessPolicyApi>> {
+        Ok(Arc::new(RowAccessPolicyMgr::create(
+            self.client.clone(),
+            tenant,
+        )?))
+    }
+
     pub fn get_udf_api_client(&self, tenant: &str) -> Result<Arc<dyn UdfApi>> {
         Ok(Arc::new(UdfMgr::create(self.client.clone(), tenant)?))
     }
@@ -0,0 +1,154 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_datavalues::chrono::Utc;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::Connection;
+use common_meta_types::StageStorage;
+
+use crate::users::UserApiProvider;
+
+/// connection operations.
+impl UserApiProvider {
+    // Add a new connection, with its credentials encrypted by the master key.
+    pub async fn add_connection(
+        &self,
+        tenant: &str,
+        name: &str,
+        storage_type: &str,
+        credentials: &BTreeMap<String, String>,
+        comment: &str,
+        if_not_exists: bool,
+    ) -> Result<u64> {
+        let cipher = self.get_connection_cipher()?;
+        let now = Utc::now();
+        let connection = Connection {
+            name: name.to_string(),
+            storage_type: storage_type.to_string(),
+            encrypted_credentials: cipher.encrypt(name, credentials)?,
+            master_key_id: cipher.master_key_id().to_string(),
+            comment: comment.to_string(),
+            created_on: now,
+            updated_on: now,
+        };
+
+        let connection_api_provider = self.get_connection_api_client(tenant)?;
+        let add_connection = connection_api_provider.add_connection(connection);
+        match add_connection.await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_not_exists && e.code() == ErrorCode::connection_already_exists_code() {
+                    Ok(u64::MIN)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    // Get one connection by tenant.
+    pub async fn get_connection(&self, tenant: &str, name: &str) -> Result<Connection> {
+        let connection_api_provider = self.get_connection_api_client(tenant)?;
+        let get_connection = connection_api_provider.get_connection(name, None);
+        Ok(get_connection.await?.data)
+    }
+
+    // Get the tenant all connection list.
+    pub async fn get_connections(&self, tenant: &str) -> Result<Vec<Connection>> {
+        let connection_api_provider = self.get_connection_api_client(tenant)?;
+        let get_connections = connection_api_provider.get_connections();
+
+        match get_connections.await {
+            Err(e) => Err(e.add_message_back("(while get connections).")),
+            Ok(connections) => Ok(connections),
+        }
+    }
+
+    // Get the decrypted credentials of a connection.
+    pub async fn get_connection_credentials(
+        &self,
+        tenant: &str,
+        name: &str,
+    ) -> Result<BTreeMap<String, String>> {
+        let connection = self.get_connection(tenant, name).await?;
+        let cipher = self.get_connection_cipher()?;
+        cipher.decrypt(
+            name,
+            &connection.master_key_id,
+            &connection.encrypted_credentials,
+        )
+    }
+
+    // Set the credentials of a connection, or encrypt its credentials again by the current
+    // master key if no credentials are given.
+    pub async fn alter_connection(
+        &self,
+        tenant: &str,
+        name: &str,
+        credentials: Option<&BTreeMap<String, String>>,
+    ) -> Result<u64> {
+        let cipher = self.get_connection_cipher()?;
+        let connection_api_provider = self.get_connection_api_client(tenant)?;
+        let seq_connection = connection_api_provider.get_connection(name, None).await?;
+        let mut connection = seq_connection.data;
+
+        let credentials = match credentials {
+            Some(credentials) => credentials.clone(),
+            None => cipher.decrypt(
+                name,
+                &connection.master_key_id,
+                &connection.encrypted_credentials,
+            )?,
+        };
+        connection.encrypted_credentials = cipher.encrypt(name, &credentials)?;
+        connection.master_key_id = cipher.master_key_id().to_string();
+        connection.updated_on = Utc::now();
+
+        let update_connection =
+            connection_api_provider.update_connection(connection, Some(seq_connection.seq));
+        match update_connection.await {
+            Ok(res) => Ok(res),
+            Err(e) => Err(e.add_message_back("(while alter connection)")),
+        }
+    }
+
+    // Drop a connection by name, which must not be referred to by any stage.
+    pub async fn drop_connection(&self, tenant: &str, name: &str, if_exists: bool) -> Result<()> {
+        for stage in self.get_stages(tenant).await? {
+            let StageStorage::S3(s3) = &stage.stage_params.storage;
+            if s3.connection == name {
+                return Err(ErrorCode::IllegalConnection(format!(
+                    "Connection {} is still referred to by stage {}",
+                    name, stage.stage_name
+                )));
+            }
+        }
+
+        let connection_api_provider = self.get_connection_api_client(tenant)?;
+        let drop_connection = connection_api_provider.drop_connection(name, None);
+        match drop_connection.await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_exists && e.code() == ErrorCode::unknown_connection_code() {
+                    Ok(())
+                } else {
+                    Err(e.add_message_back("(while drop connection)"))
+                }
+            }
+        }
+    }
+}
@@ -24,11 +24,16 @@ use common_meta_types::UserPrivilegeSet;
 use crate::users::UserApiProvider;
 
 impl UserApiProvider {
+    /// The built-in users are not persisted in the meta service.
+    // TODO(BohuTANG): Mock, need removed.
+    pub fn is_builtin_user(username: &str) -> bool {
+        matches!(username, "default" | "" | "root")
+    }
+
     // Get one user from by tenant.
     pub async fn get_user(&self, tenant: &str, user: UserIdentity) -> Result<UserInfo> {
-        match user.username.as_str() {
-            // TODO(BohuTANG): Mock, need removed.
-            "default" | "" | "root" => {
+        match Self::is_builtin_user(&user.username) {
+            true => {
                 let mut user_info = UserInfo::new_no_auth(&user.username, &user.hostname);
                 if user.is_localhost() {
                     user_info.grants.grant_privileges(
@@ -39,7 +44,7 @@ impl UserApiProvider {
                 }
                 Ok(user_info)
             }
-            _ => {
+            false => {
                 let client = self.get_user_api_client(tenant)?;
                 let get_user = client.get_user(user, None);
                 Ok(get_user.await?.data)
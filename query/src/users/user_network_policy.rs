@@ -0,0 +1,121 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::IpAddr;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::NetworkPolicy;
+use common_meta_types::UserInfo;
+
+use crate::users::UserApiProvider;
+
+/// network policy operations.
+impl UserApiProvider {
+    // Add a new network policy.
+    pub async fn add_network_policy(
+        &self,
+        tenant: &str,
+        policy: NetworkPolicy,
+        if_not_exists: bool,
+    ) -> Result<u64> {
+        policy.validate()?;
+        let policy_api_provider = self.get_network_policy_api_client(tenant)?;
+        let add_policy = policy_api_provider.add_policy(policy);
+        match add_policy.await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_not_exists && e.code() == ErrorCode::network_policy_already_exists_code() {
+                    Ok(u64::MIN)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    // Get one network policy by tenant.
+    pub async fn get_network_policy(&self, tenant: &str, name: &str) -> Result<NetworkPolicy> {
+        let policy_api_provider = self.get_network_policy_api_client(tenant)?;
+        let get_policy = policy_api_provider.get_policy(name, None);
+        Ok(get_policy.await?.data)
+    }
+
+    // Get the tenant all network policy list.
+    pub async fn get_network_policies(&self, tenant: &str) -> Result<Vec<NetworkPolicy>> {
+        let policy_api_provider = self.get_network_policy_api_client(tenant)?;
+        let get_policies = policy_api_provider.get_policies();
+
+        match get_policies.await {
+            Err(e) => Err(e.add_message_back("(while get network policies).")),
+            Ok(policies) => Ok(policies),
+        }
+    }
+
+    // Drop a network policy by name, which must not be set to any user.
+    pub async fn drop_network_policy(
+        &self,
+        tenant: &str,
+        name: &str,
+        if_exists: bool,
+    ) -> Result<()> {
+        for user in self.get_users(tenant).await? {
+            if user.option.network_policy().map(String::as_str) == Some(name) {
+                return Err(ErrorCode::IllegalNetworkPolicy(format!(
+                    "Network policy {} is still set to user {}",
+                    name,
+                    user.identity()
+                )));
+            }
+        }
+
+        let policy_api_provider = self.get_network_policy_api_client(tenant)?;
+        let drop_policy = policy_api_provider.drop_policy(name, None);
+        match drop_policy.await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_exists && e.code() == ErrorCode::unknown_network_policy_code() {
+                    Ok(())
+                } else {
+                    Err(e.add_message_back("(while drop network policy)"))
+                }
+            }
+        }
+    }
+
+    // Reject the client if the network policy of the user doesn't allow its address.
+    pub async fn check_network_policy(
+        &self,
+        tenant: &str,
+        user: &UserInfo,
+        client_ip: Option<&str>,
+    ) -> Result<()> {
+        let name = match user.option.network_policy() {
+            None => return Ok(()),
+            Some(name) => name,
+        };
+        let policy = self.get_network_policy(tenant, name).await?;
+
+        let ip = client_ip.and_then(|ip| ip.parse::<IpAddr>().ok());
+        match ip {
+            Some(ip) if policy.is_allowed(&ip)? => Ok(()),
+            _ => Err(ErrorCode::NetworkPolicyViolation(format!(
+                "Client address {} is not allowed by the network policy {} of user {}",
+                client_ip.unwrap_or("unknown"),
+                name,
+                user.identity()
+            ))),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::PipeFileInfo;
+use common_meta_types::PipeInfo;
+
+use crate::users::UserApiProvider;
+
+/// pipe operations.
+impl UserApiProvider {
+    // Add a new pipe.
+    pub async fn add_pipe(&self, tenant: &str, info: PipeInfo, if_not_exists: bool) -> Result<u64> {
+        let pipe_api_provider = self.get_pipe_api_client(tenant)?;
+        let add_pipe = pipe_api_provider.add_pipe(info);
+        match add_pipe.await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_not_exists && e.code() == ErrorCode::pipe_already_exists_code() {
+                    Ok(u64::MIN)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    // Get one pipe from by tenant.
+    pub async fn get_pipe(&self, tenant: &str, pipe_name: &str) -> Result<PipeInfo> {
+        let pipe_api_provider = self.get_pipe_api_client(tenant)?;
+        let get_pipe = pipe_api_provider.get_pipe(pipe_name, None);
+        Ok(get_pipe.await?.data)
+    }
+
+    // Get the tenant all pipe list.
+    pub async fn get_pipes(&self, tenant: &str) -> Result<Vec<PipeInfo>> {
+        let pipe_api_provider = self.get_pipe_api_client(tenant)?;
+        let get_pipes = pipe_api_provider.get_pipes();
+
+        match get_pipes.await {
+            Err(e) => Err(e.add_message_back("(while get pipes).")),
+            Ok(pipes) => Ok(pipes),
+        }
+    }
+
+    // Drop a pipe by name.
+    pub async fn drop_pipe(&self, tenant: &str, name: &str, if_exists: bool) -> Result<()> {
+        let pipe_api_provider = self.get_pipe_api_client(tenant)?;
+        let drop_pipe = pipe_api_provider.drop_pipe(name, None);
+        match drop_pipe.await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_exists && e.code() == ErrorCode::unknown_pipe_code() {
+                    Ok(())
+                } else {
+                    Err(e.add_message_back("(while drop pipe)"))
+                }
+            }
+        }
+    }
+
+    // Add the files to the load history of a pipe.
+    pub async fn add_pipe_files(
+        &self,
+        tenant: &str,
+        pipe_name: &str,
+        files: Vec<PipeFileInfo>,
+    ) -> Result<()> {
+        let pipe_api_provider = self.get_pipe_api_client(tenant)?;
+        pipe_api_provider.add_pipe_files(pipe_name, files).await
+    }
+
+    // Get the load history of a pipe.
+    pub async fn get_pipe_files(&self, tenant: &str, pipe_name: &str) -> Result<Vec<PipeFileInfo>> {
+        let pipe_api_provider = self.get_pipe_api_client(tenant)?;
+        let get_pipe_files = pipe_api_provider.get_pipe_files(pipe_name);
+
+        match get_pipe_files.await {
+            Err(e) => Err(e.add_message_back("(while get pipe files).")),
+            Ok(files) => Ok(files),
+        }
+    }
+}
@@ -39,6 +39,8 @@ clickhouse_handler_port = 9000
 http_handler_host = "127.0.0.1"
 http_handler_port = 8000
 http_handler_result_timeout_millis = 10000
+flight_sql_handler_host = "127.0.0.1"
+flight_sql_handler_port = 8900
 flight_api_address = "127.0.0.1:9090"
 admin_api_address = "127.0.0.1:8080"
 metric_api_address = "127.0.0.1:7070"
@@ -67,11 +69,21 @@ table_disk_cache_root = "_cache"
 table_disk_cache_mb_size = 1024
 management_mode = false
 jwt_key_file = ""
+oidc_issuer = ""
+oidc_client_id = ""
+oidc_jwks_url = ""
+oidc_user_claim = "sub"
+oidc_roles_claim = "groups"
+oidc_auto_provision = false
+connection_master_key_file = ""
+connection_previous_master_key_file = ""
 
 [log]
 level = "INFO"
 dir = "./_logs"
 query_enabled = false
+query_history_enabled = false
+query_history_retention_days = 7
 
 [meta]
 embedded_dir = "./_meta_embedded"
@@ -211,6 +223,8 @@ clickhouse_handler_port = 9000
 http_handler_host = "127.0.0.1"
 http_handler_port = 8000
 http_handler_result_timeout_millis = 10000
+flight_sql_handler_host = "127.0.0.1"
+flight_sql_handler_port = 8900
 flight_api_address = "127.0.0.1:9090"
 admin_api_address = "127.0.0.1:8080"
 metric_api_address = "127.0.0.1:7070"
@@ -239,6 +253,14 @@ table_disk_cache_root = "_cache"
 table_disk_cache_mb_size = 1024
 management_mode = false
 jwt_key_file = ""
+oidc_issuer = ""
+oidc_client_id = ""
+oidc_jwks_url = ""
+oidc_user_claim = "sub"
+oidc_roles_claim = "groups"
+oidc_auto_provision = false
+connection_master_key_file = ""
+connection_previous_master_key_file = ""
 
 [log]
 level = "INFO"
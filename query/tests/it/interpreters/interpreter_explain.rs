@@ -52,3 +52,41 @@ async fn test_explain_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_explain_analyze_interpreter() -> Result<()> {
+    let ctx = crate::tests::create_query_context().await?;
+
+    let query = "EXPLAIN ANALYZE SELECT number FROM numbers_mt(10) WHERE number > 4";
+    let plan = PlanParser::parse(ctx.clone(), query).await?;
+    let executor = InterpreterFactory::get(ctx, plan)?;
+    assert_eq!(executor.name(), "ExplainInterpreter");
+
+    let stream = executor.execute(None).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let block = &result[0];
+    let mut lines = Vec::with_capacity(block.num_rows());
+    for row in 0..block.num_rows() {
+        lines.push(String::from_utf8(block.column(0).get(row).as_string()?)?);
+    }
+
+    // The timings vary from run to run, only check the row counts.
+    let expected = vec![
+        ("Projection: number:UInt64 [", "output_rows: 5,"),
+        ("  Filter: (number > 4) [", "output_rows: 5,"),
+        (
+            "    ReadDataSource: scan schema: [number:UInt64]",
+            "output_rows: 10,",
+        ),
+        ("Total: [", "result_rows: 5,"),
+        ("Scanned: [", "rows: 10,"),
+        ("Spilled: [", "rows: 0,"),
+    ];
+    assert_eq!(lines.len(), expected.len());
+    for (line, (prefix, metric)) in lines.iter().zip(expected) {
+        assert!(line.starts_with(prefix), "{}", line);
+        assert!(line.contains(metric), "{}", line);
+    }
+
+    Ok(())
+}
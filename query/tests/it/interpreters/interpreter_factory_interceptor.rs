@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Duration;
+
 use common_base::tokio;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use databend_query::interpreters::*;
 use databend_query::sql::*;
@@ -114,3 +117,47 @@ async fn test_interpreter_interceptor_for_insert() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_interpreter_interceptor_with_query_queue() -> Result<()> {
+    common_tracing::init_default_ut_tracing();
+    let ctx = crate::tests::create_query_context().await?;
+    let settings = ctx.get_settings();
+    settings.set_settings("max_concurrent_queries".to_string(), "1".to_string(), false)?;
+
+    let query = "select number from numbers_mt(1)";
+    let session = ctx.get_current_session();
+    let ctx1 = session.create_query_context().await?;
+    let plan1 = PlanParser::parse(ctx1.clone(), query).await?;
+    let interpreter1 = InterpreterFactory::get(ctx1, plan1)?;
+    interpreter1.start().await?;
+
+    // The second query waits for the slot of the first one.
+    let ctx2 = session.create_query_context().await?;
+    let plan2 = PlanParser::parse(ctx2.clone(), query).await?;
+    let interpreter2 = InterpreterFactory::get(ctx2, plan2)?;
+    let queued = interpreter2.clone();
+    let handle = tokio::spawn(async move { queued.start().await });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(session.process_info().state, "Queued");
+
+    interpreter1.finish().await?;
+    handle.await.unwrap()?;
+    assert_eq!(session.process_info().state, "Query");
+
+    // The third query fails once it waits for longer than the timeout.
+    settings.set_settings("query_queue_timeout".to_string(), "1".to_string(), false)?;
+    let ctx3 = session.create_query_context().await?;
+    let plan3 = PlanParser::parse(ctx3.clone(), query).await?;
+    let interpreter3 = InterpreterFactory::get(ctx3, plan3)?;
+    let result = interpreter3.start().await;
+    assert_eq!(
+        result.unwrap_err().code(),
+        ErrorCode::query_queue_timeout_code()
+    );
+
+    interpreter2.finish().await?;
+    interpreter3.start().await?;
+    interpreter3.finish().await?;
+    Ok(())
+}
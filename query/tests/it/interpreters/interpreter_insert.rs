@@ -18,6 +18,8 @@ use databend_query::interpreters::*;
 use databend_query::sql::*;
 use futures::TryStreamExt;
 
+use crate::storages::fuse::table_test_fixture::*;
+
 #[tokio::test]
 async fn test_insert_into_interpreter() -> Result<()> {
     let ctx = crate::tests::create_query_context().await?;
@@ -83,6 +85,33 @@ async fn test_insert_into_interpreter() -> Result<()> {
         }
     }
 
+    // The non-constant defaults are evaluated for each row.
+    {
+        let query = "create table default.default_expr_table(a Int32, b String DEFAULT uuid(), c Timestamp DEFAULT now()) Engine = Memory";
+        let plan = PlanParser::parse(ctx.clone(), query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan.clone())?;
+        let _ = executor.execute(None).await?;
+
+        let query = "insert into default.default_expr_table(a) values(1), (2), (3)";
+        let plan = PlanParser::parse(ctx.clone(), query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan.clone())?;
+        let _ = executor.execute(None).await?;
+
+        let query = "select uniq(b) as b, count(c) as c from default.default_expr_table";
+        let plan = PlanParser::parse(ctx.clone(), query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan.clone())?;
+        let stream = executor.execute(None).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+---+---+",
+            "| b | c |",
+            "+---+---+",
+            "| 3 | 3 |",
+            "+---+---+",
+        ];
+        common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    }
+
     // Insert into input table.
     {
         let query = "insert into default.input_table values(1,1,1,1,1), (2,2,2,2,2)";
@@ -120,3 +149,84 @@ async fn test_insert_into_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_async_insert_interpreter() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    for qry in [
+        format!("create table {}.t(a int, b varchar default 'b')", db),
+        "set enable_async_insert = 1".to_string(),
+        "set async_insert_busy_timeout = 100".to_string(),
+    ] {
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+
+    // the concurrent inserts are buffered and committed together
+    let inserts = (1..=3).map(|i| {
+        let ctx = ctx.clone();
+        let qry = format!("insert into {}.t(a) values ({})", db, i);
+        async move { execute_command(ctx, qry.as_str()).await }
+    });
+    futures::future::try_join_all(inserts).await?;
+
+    let qry = format!("select a, b from {}.t order by a", db);
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | b |",
+        "| 2 | b |",
+        "| 3 | b |",
+        "+---+---+",
+    ];
+    expects_ok(
+        "buffered",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    let qry = format!("select count(*) from fuse_history('{}', 't')", db);
+    let expected = vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        "| 1        |",
+        "+----------+",
+    ];
+    expects_ok(
+        "one_snapshot",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // the buffered inserts exceeding the max data size are committed at once
+    for qry in [
+        "set async_insert_busy_timeout = 1000000".to_string(),
+        "set async_insert_max_data_size = 1".to_string(),
+        format!("insert into {}.t values (4, 'x')", db),
+    ] {
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+
+    let qry = format!("select count(*) from {}.t", db);
+    let expected = vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        "| 4        |",
+        "+----------+",
+    ];
+    expects_ok(
+        "max_data_size",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    Ok(())
+}
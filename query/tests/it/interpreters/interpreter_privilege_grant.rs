@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use common_base::tokio;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::AuthInfo;
 use common_meta_types::GrantObject;
@@ -23,6 +26,7 @@ use common_meta_types::UserGrantSet;
 use common_meta_types::UserInfo;
 use common_meta_types::UserPrivilegeType;
 use databend_query::interpreters::*;
+use databend_query::sessions::QueryContext;
 use databend_query::sql::PlanParser;
 use futures::stream::StreamExt;
 use pretty_assertions::assert_eq;
@@ -143,3 +147,70 @@ async fn test_grant_privilege_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_grant_ownership_interpreter() -> Result<()> {
+    common_tracing::init_default_ut_tracing();
+
+    let ctx = crate::tests::create_query_context().await?;
+    let tenant = ctx.get_tenant();
+    let user_mgr = ctx.get_user_manager();
+
+    let mut user_info = UserInfo::new_no_auth("owner", "%");
+    user_info
+        .grants
+        .grant_privileges(&GrantObject::Global, vec![UserPrivilegeType::Create].into());
+    user_mgr.add_user(&tenant, user_info.clone(), false).await?;
+    user_mgr
+        .add_role(&tenant, RoleInfo::new("role1"), false)
+        .await?;
+    ctx.get_current_session()
+        .set_current_user(user_info.clone());
+
+    // the creator owns the objects, and has all the privileges on them
+    execute_command(ctx.clone(), "CREATE DATABASE db_owned").await?;
+    execute_command(
+        ctx.clone(),
+        "CREATE TABLE db_owned.t(a int) Engine = Memory",
+    )
+    .await?;
+    let owner = user_mgr.get_user(&tenant, user_info.identity()).await?;
+    assert_eq!(owner.grants.owned_objects(&GrantObject::Global), vec![
+        GrantObject::Database("db_owned".into()),
+        GrantObject::Table("db_owned".into(), "t".into()),
+    ]);
+
+    // the ownership is transferred
+    let query = "GRANT OWNERSHIP ON db_owned.t TO ROLE 'role1'";
+    execute_command(ctx.clone(), query).await?;
+    let owner = user_mgr.get_user(&tenant, user_info.identity()).await?;
+    assert_eq!(owner.grants.owned_objects(&GrantObject::Global), vec![
+        GrantObject::Database("db_owned".into())
+    ]);
+    let role = user_mgr.get_role(&tenant, "role1".to_string()).await?;
+    assert_eq!(role.grants.owned_objects(&GrantObject::Global), vec![
+        GrantObject::Table("db_owned".into(), "t".into())
+    ]);
+
+    // the ownership goes away with the objects
+    execute_command(ctx.clone(), "DROP DATABASE db_owned").await?;
+    let role = user_mgr.get_role(&tenant, "role1".to_string()).await?;
+    assert!(role.grants.owned_objects(&GrantObject::Global).is_empty());
+
+    // the other privileges are granted alone
+    let query = "GRANT OWNERSHIP, SELECT ON default.* TO ROLE 'role1'";
+    let res = execute_command(ctx.clone(), query).await;
+    assert_eq!(res.unwrap_err().code(), ErrorCode::IllegalGrant("").code());
+
+    Ok(())
+}
+
+async fn execute_command(ctx: Arc<QueryContext>, query: &str) -> Result<()> {
+    let plan = PlanParser::parse(ctx.clone(), query).await?;
+    let executor = InterpreterFactory::get(ctx, plan)?;
+    let mut stream = executor.execute(None).await?;
+    while let Some(block) = stream.next().await {
+        block?;
+    }
+    Ok(())
+}
@@ -100,5 +100,18 @@ async fn test_grant_role_interpreter() -> Result<()> {
         assert_eq!(roles.len(), 1);
         assert_eq!(roles[0], "test".to_string());
     }
+
+    // Grant role to its granted role.
+    {
+        let query = "GRANT ROLE 'test_role' TO ROLE 'test'";
+        let plan = PlanParser::parse(ctx.clone(), query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan.clone())?;
+        let res = executor.execute(None).await;
+        assert!(res.is_err());
+        assert_eq!(
+            res.err().unwrap().code(),
+            ErrorCode::IllegalGrant("").code()
+        )
+    }
     Ok(())
 }
@@ -12,12 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::path::Path;
+
 use common_base::tokio;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use databend_query::interpreters::*;
 use databend_query::sql::*;
 use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+use crate::storages::fuse::table_test_fixture::*;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_select_interpreter() -> Result<()> {
@@ -76,3 +83,201 @@ async fn test_select_interpreter() -> Result<()> {
     }
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_select_interpreter_with_spilling() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let ctx = crate::tests::create_query_context().await?;
+    let settings = ctx.get_settings();
+    settings.set_max_threads(4)?;
+    for (key, value) in [
+        ("max_block_size", "100".to_string()),
+        ("group_by_spill_threshold", "1".to_string()),
+        ("sort_spill_threshold", "1".to_string()),
+        ("spill_path", tmp_dir.path().display().to_string()),
+    ] {
+        settings.set_settings(key.to_string(), value, false)?;
+    }
+
+    {
+        let query = "select number % 3 as c1, count(*) as c2, sum(number) as c3 \
+            from numbers_mt(10000) group by number % 3 order by c1";
+        let plan = PlanParser::parse(ctx.clone(), query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+
+        let stream = executor.execute(None).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+----+------+----------+",
+            "| c1 | c2   | c3       |",
+            "+----+------+----------+",
+            "| 0  | 3334 | 16668333 |",
+            "| 1  | 3333 | 16661667 |",
+            "| 2  | 3333 | 16665000 |",
+            "+----+------+----------+",
+        ];
+        common_datablocks::assert_blocks_eq(expected, result.as_slice());
+    }
+
+    // Group by strings, whose keys are encoded by dictionaries.
+    {
+        let query = "select cast(number % 3 as varchar) as c1, count(*) as c2 \
+            from numbers_mt(10000) group by cast(number % 3 as varchar) order by c1";
+        let plan = PlanParser::parse(ctx.clone(), query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+
+        let stream = executor.execute(None).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+----+------+",
+            "| c1 | c2   |",
+            "+----+------+",
+            "| 0  | 3334 |",
+            "| 1  | 3333 |",
+            "| 2  | 3333 |",
+            "+----+------+",
+        ];
+        common_datablocks::assert_blocks_eq(expected, result.as_slice());
+    }
+
+    {
+        let query = "select number from numbers_mt(10000) order by number desc";
+        let plan = PlanParser::parse(ctx.clone(), query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+
+        let stream = executor.execute(None).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let numbers = result
+            .iter()
+            .flat_map(|block| block.column(0).to_values())
+            .collect::<Vec<_>>();
+        let expected = (0..10000u64)
+            .rev()
+            .map(DataValue::UInt64)
+            .collect::<Vec<_>>();
+        assert!(numbers == expected, "the spilled numbers are not sorted");
+    }
+
+    // The spilled files are removed once they are read back.
+    assert_eq!(count_files(tmp_dir.path())?, 0);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_select_interpreter_with_memory_limit() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let ctx = crate::tests::create_query_context().await?;
+    let settings = ctx.get_settings();
+    for (key, value) in [
+        ("max_block_size", "100".to_string()),
+        ("max_query_memory", "10000".to_string()),
+        ("spill_path", tmp_dir.path().display().to_string()),
+    ] {
+        settings.set_settings(key.to_string(), value, false)?;
+    }
+
+    // The limit of the memory is read when the query context is created.
+    let query = "select number from numbers_mt(10000) order by number desc";
+    let ctx = ctx.get_current_session().create_query_context().await?;
+    let plan = PlanParser::parse(ctx.clone(), query).await?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let result = executor.execute(None).await?.try_collect::<Vec<_>>().await;
+    assert_eq!(
+        result.unwrap_err().code(),
+        ErrorCode::query_memory_limit_exceeded_code()
+    );
+
+    // The sorted blocks are spilled once they exceed the memory of the query.
+    let threshold = (1u64 << 40).to_string();
+    settings.set_settings("sort_spill_threshold".to_string(), threshold, false)?;
+    let ctx = ctx.get_current_session().create_query_context().await?;
+    let plan = PlanParser::parse(ctx.clone(), query).await?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let result = executor
+        .execute(None)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let numbers = result
+        .iter()
+        .flat_map(|block| block.column(0).to_values())
+        .collect::<Vec<_>>();
+    let expected = (0..10000u64)
+        .rev()
+        .map(DataValue::UInt64)
+        .collect::<Vec<_>>();
+    assert!(numbers == expected, "the spilled numbers are not sorted");
+    assert!(ctx.get_spill_progress_value().rows > 0);
+    assert_eq!(ctx.get_memory_tracker().get_usage(), 0);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_select_interpreter_with_result_cache() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    for qry in [
+        format!("create table {}.t(a int)", db),
+        format!("insert into {}.t values (1), (2)", db),
+        "set enable_query_result_cache = 1".to_string(),
+    ] {
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+
+    let qry = format!("select sum(a) as s from {}.t", db);
+    let expected = vec!["+---+", "| s |", "+---+", "| 3 |", "+---+"];
+    let ctx = ctx.get_current_session().create_query_context().await?;
+    expects_ok(
+        "cache_miss",
+        execute_query(ctx.clone(), &qry).await,
+        expected.clone(),
+    )
+    .await?;
+    assert_eq!(ctx.get_scan_progress_value().rows, 2);
+
+    // the cached result is returned without scanning the table
+    let ctx = ctx.get_current_session().create_query_context().await?;
+    expects_ok(
+        "cache_hit",
+        execute_query(ctx.clone(), &qry).await,
+        expected,
+    )
+    .await?;
+    assert_eq!(ctx.get_scan_progress_value().rows, 0);
+
+    // the new snapshot of the table is read
+    let ctx = ctx.get_current_session().create_query_context().await?;
+    execute_command(ctx.clone(), &format!("insert into {}.t values (3)", db)).await?;
+    let ctx = ctx.get_current_session().create_query_context().await?;
+    let expected = vec!["+---+", "| s |", "+---+", "| 6 |", "+---+"];
+    expects_ok(
+        "table_changed",
+        execute_query(ctx.clone(), &qry).await,
+        expected,
+    )
+    .await?;
+    assert_eq!(ctx.get_scan_progress_value().rows, 3);
+
+    // the results of the non-deterministic functions are not cached
+    let qry = format!("select sum(a) + rand() * 0 as s from {}.t", db);
+    for _ in 0..2 {
+        let ctx = ctx.get_current_session().create_query_context().await?;
+        execute_command(ctx.clone(), &qry).await?;
+        assert_eq!(ctx.get_scan_progress_value().rows, 3);
+    }
+    Ok(())
+}
+
+fn count_files(path: &Path) -> Result<usize> {
+    let mut count = 0;
+    for entry in std::fs::read_dir(path)? {
+        let path = entry?.path();
+        count += match path.is_dir() {
+            true => count_files(&path)?,
+            false => 1,
+        };
+    }
+    Ok(count)
+}
@@ -52,11 +52,11 @@ async fn test_user_stage_interpreter() -> Result<()> {
 
         common_datablocks::assert_blocks_eq(
             vec![
-                "+------------+------------+------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------+-----------------------------------------------+--------------------------------------------------------------------------------------------------------------------+---------+",
-                "| name       | stage_type | stage_params                                                                                                                                                                       | copy_options                                  | file_format_options                                                                                                | comment |",
-                "+------------+------------+------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------+-----------------------------------------------+--------------------------------------------------------------------------------------------------------------------+---------+",
-                "| test_stage | External   | StageParams { storage: S3(StageS3Storage { bucket: \"load\", path: \"/files/\", credentials_aws_key_id: \"1a2b3c\", credentials_aws_secret_key: \"4x5y6z\", encryption_master_key: \"\" }) } | CopyOptions { on_error: None, size_limit: 0 } | FileFormatOptions { format: Csv, skip_header: 0, field_delimiter: \",\", record_delimiter: \"\\n\", compression: None } |         |",
-                "+------------+------------+------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------+-----------------------------------------------+--------------------------------------------------------------------------------------------------------------------+---------+",
+                "+------------+------------+----------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------+-----------------------------------------------------------------------------------+-----------------------------------------------------------------------------------------------------------------------------------------------------+---------+",
+                "| name       | stage_type | stage_params                                                                                                                                                                                       | copy_options                                                                      | file_format_options                                                                                                                                 | comment |",
+                "+------------+------------+----------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------+-----------------------------------------------------------------------------------+-----------------------------------------------------------------------------------------------------------------------------------------------------+---------+",
+                "| test_stage | External   | StageParams { storage: S3(StageS3Storage { bucket: \"load\", path: \"/files/\", credentials_aws_key_id: \"1a2b3c\", credentials_aws_secret_key: \"4x5y6z\", encryption_master_key: \"\", connection: \"\" }) } | CopyOptions { on_error: None, size_limit: 0, purge: false, rejected_records: \"\" } | FileFormatOptions { format: Csv, skip_header: 0, field_delimiter: \",\", record_delimiter: \"\\n\", enclosed_by: \"\", escaped_by: \"\", compression: None } |         |",
+                "+------------+------------+----------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------+-----------------------------------------------------------------------------------+-----------------------------------------------------------------------------------------------------------------------------------------------------+---------+",
             ],
             &blocks,
         );
@@ -52,3 +52,4 @@ mod interpreter_user_udf_alter;
 mod interpreter_user_udf_create;
 mod interpreter_user_udf_drop;
 mod plan_schedulers;
+mod query_history;
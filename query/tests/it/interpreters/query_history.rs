@@ -0,0 +1,122 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use common_base::tokio;
+use common_exception::Result;
+use databend_query::interpreters::*;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+fn log_event(query_id: &str, event_time: u64) -> LogEvent {
+    LogEvent {
+        log_type: LogType::Finish,
+        handler_type: "MySQL".to_string(),
+        tenant_id: "".to_string(),
+        cluster_id: "".to_string(),
+        sql_user: "root".to_string(),
+        sql_user_quota: "".to_string(),
+        sql_user_privileges: "".to_string(),
+        query_id: query_id.to_string(),
+        query_kind: "SelectPlan".to_string(),
+        query_text: "select 1".to_string(),
+        event_date: (event_time / (24 * 3600000)) as i32,
+        event_time,
+        current_database: "default".to_string(),
+        databases: "".to_string(),
+        tables: "".to_string(),
+        columns: "".to_string(),
+        projections: "".to_string(),
+        written_rows: 0,
+        written_bytes: 0,
+        written_io_bytes: 0,
+        written_io_bytes_cost_ms: 0,
+        scan_rows: 1,
+        scan_bytes: 1,
+        scan_io_bytes: 0,
+        scan_io_bytes_cost_ms: 0,
+        scan_partitions: 0,
+        total_partitions: 0,
+        result_rows: 1,
+        result_bytes: 2,
+        cpu_usage: 8,
+        memory_usage: 0,
+        client_info: "".to_string(),
+        client_address: "".to_string(),
+        exception_code: 0,
+        exception: "".to_string(),
+        stack_trace: "".to_string(),
+        server_version: "".to_string(),
+        session_settings: "".to_string(),
+        extra: "".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_query_history() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as u64;
+    let expired = now - 30 * 24 * 3600 * 1000;
+    let duration = Duration::from_millis(5);
+
+    // the table is created by the first query recorded
+    let history = ctx.get_query_history();
+    history
+        .record(ctx.clone(), &log_event("q1", expired), duration)
+        .await?;
+    history
+        .record(ctx.clone(), &log_event("q2", now), duration)
+        .await?;
+    ctx.get_async_insert_queue().flush_all().await;
+
+    let qry = "select query_id, duration_ms, scan_rows, status, profile \
+        from system_history.query_history order by query_id";
+    let expected = vec![
+        "+----------+-------------+-----------+--------+---------+",
+        "| query_id | duration_ms | scan_rows | status | profile |",
+        "+----------+-------------+-----------+--------+---------+",
+        "| q1       | 5           | 1         | Finish | []      |",
+        "| q2       | 5           | 1         | Finish | []      |",
+        "+----------+-------------+-----------+--------+---------+",
+    ];
+    expects_ok("recorded", execute_query(ctx.clone(), qry).await, expected).await?;
+
+    // the queries older than the retention are purged
+    let history = QueryHistory::create();
+    history
+        .record(ctx.clone(), &log_event("q3", now), duration)
+        .await?;
+    ctx.get_async_insert_queue().flush_all().await;
+
+    let qry = "select query_id from system_history.query_history order by query_id";
+    let expected = vec![
+        "+----------+",
+        "| query_id |",
+        "+----------+",
+        "| q2       |",
+        "| q3       |",
+        "+----------+",
+    ];
+    expects_ok("purged", execute_query(ctx.clone(), qry).await, expected).await?;
+
+    Ok(())
+}
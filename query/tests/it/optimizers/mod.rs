@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod optimizer;
+mod optimizer_common_subexpression;
 mod optimizer_constant_folding;
 mod optimizer_expression_transform;
 mod optimizer_scatters;
@@ -0,0 +1,70 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use databend_query::optimizers::*;
+use databend_query::sql::PlanParser;
+use pretty_assertions::assert_eq;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_common_subexpression_optimizer() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        expect: &'static str,
+    }
+
+    let tests: Vec<Test> = vec![
+        Test {
+            name: "shared-by-filter-and-projection",
+            query: "select (number + 1) * 2 as c from numbers(10) where (number + 1) > 5",
+            expect: "\
+            Projection: ((number + 1) * 2) as c:UInt64\
+            \n  Expression: ((number + 1) * 2):UInt64 (Before Projection)\
+            \n    Filter: ((number + 1) > 5)\
+            \n      Expression: (number + 1):UInt64 (Common Subexpressions)\
+            \n        ReadDataSource: scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80, partitions_scanned: 1, partitions_total: 1], push_downs: [projections: [0], filters: [((number + 1) > 5)]]",
+        },
+        Test {
+            name: "not-shared",
+            query: "select number * 2 as c from numbers(10) where (number + 1) > 5",
+            expect: "\
+            Projection: (number * 2) as c:UInt64\
+            \n  Expression: (number * 2):UInt64 (Before Projection)\
+            \n    Filter: ((number + 1) > 5)\
+            \n      ReadDataSource: scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80, partitions_scanned: 1, partitions_total: 1], push_downs: [projections: [0], filters: [((number + 1) > 5)]]",
+        },
+        Test {
+            name: "non-deterministic",
+            query: "select (number + rand()) as c from numbers(10) where (number + rand()) > 5",
+            expect: "\
+            Projection: (number + rand()) as c:Float64\
+            \n  Expression: (number + rand()):Float64 (Before Projection)\
+            \n    Filter: ((number + rand()) > 5)\
+            \n      ReadDataSource: scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80, partitions_scanned: 1, partitions_total: 1], push_downs: [projections: [0], filters: [((number + rand()) > 5)]]",
+        },
+    ];
+
+    for test in tests {
+        let ctx = crate::tests::create_query_context().await?;
+
+        let plan = PlanParser::parse(ctx.clone(), test.query).await?;
+        let mut optimizer = CommonSubexpressionOptimizer::create(ctx);
+        let optimized = optimizer.optimize(&plan)?;
+        let actual = format!("{:?}", optimized);
+        assert_eq!(test.expect, actual, "{:#?}", test.name);
+    }
+    Ok(())
+}
@@ -14,3 +14,4 @@
 
 mod executor;
 mod processors;
+mod profile;
@@ -0,0 +1,80 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use databend_query::pipelines::new::PipeProfile;
+use databend_query::pipelines::new::PipelineSampler;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_pipeline_sampler() {
+    let source = PipeProfile::create("SyncReadDataSource", 2);
+    let transform = PipeProfile::create("TransformFilter", 2);
+    let sink = PipeProfile::create("SyncSenderSink", 1);
+    let pipelines = vec![vec![source.clone(), transform.clone(), sink]];
+
+    let mut sampler = PipelineSampler::create();
+    let interval = Duration::from_millis(10);
+
+    // Nothing is processing.
+    sampler.sample("query_1", &pipelines, interval);
+
+    source.start_processing();
+    transform.start_processing();
+    transform.start_processing();
+    sampler.sample("query_1", &pipelines, interval);
+    transform.finish_processing();
+    sampler.sample("query_2", &pipelines, interval);
+
+    let file = sampler.finish();
+    let frames = file
+        .shared
+        .frames
+        .iter()
+        .map(|frame| frame.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(frames, vec!["SyncReadDataSource", "TransformFilter"]);
+
+    let profiles = file
+        .profiles
+        .iter()
+        .map(|profile| {
+            (
+                profile.name.as_str(),
+                profile.samples.clone(),
+                profile.weights.clone(),
+                profile.end_value,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(profiles, vec![
+        (
+            "query_1",
+            vec![vec![0], vec![0, 1]],
+            vec![10f64, 20f64],
+            30f64
+        ),
+        (
+            "query_2",
+            vec![vec![0], vec![0, 1]],
+            vec![10f64, 10f64],
+            20f64
+        ),
+    ]);
+
+    let json = serde_json::to_value(&file).unwrap();
+    assert_eq!(json["profiles"][0]["type"], "sampled");
+    assert_eq!(json["profiles"][0]["endValue"], 30f64);
+}
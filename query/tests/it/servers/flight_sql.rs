@@ -0,0 +1,97 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow_format::flight::data::FlightDescriptor;
+use common_arrow::arrow_format::flight::data::Ticket;
+use common_arrow::arrow_format::flight::service::flight_service_server::FlightService;
+use common_base::tokio;
+use common_exception::Result;
+use databend_query::servers::flight_sql::flight_sql_protocol::CommandGetTableTypes;
+use databend_query::servers::flight_sql::flight_sql_protocol::CommandGetTables;
+use databend_query::servers::flight_sql::flight_sql_protocol::CommandStatementQuery;
+use databend_query::servers::flight_sql::flight_sql_protocol::TicketStatementQuery;
+use databend_query::servers::flight_sql::FlightSQLCommand;
+use databend_query::servers::flight_sql::FlightSQLService;
+use futures::TryStreamExt;
+use tonic::Request;
+
+use crate::tests::SessionManagerBuilder;
+
+#[test]
+fn test_flight_sql_command_pack() -> Result<()> {
+    let command = FlightSQLCommand::GetTables(CommandGetTables {
+        catalog: Some("default".to_string()),
+        db_schema_filter_pattern: Some("sys%".to_string()),
+        table_name_filter_pattern: None,
+        table_types: vec!["TABLE".to_string()],
+        include_schema: true,
+    });
+    assert_eq!(FlightSQLCommand::unpack(&command.pack())?, command);
+
+    let command = FlightSQLCommand::TicketStatementQuery(TicketStatementQuery {
+        statement_handle: b"SELECT 1".to_vec(),
+    });
+    assert_eq!(FlightSQLCommand::unpack(&command.pack())?, command);
+
+    assert!(FlightSQLCommand::unpack(b"not a command").is_err());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_flight_sql_statement_query() -> Result<()> {
+    let service = FlightSQLService::create(SessionManagerBuilder::create().build()?);
+
+    let command = FlightSQLCommand::StatementQuery(CommandStatementQuery {
+        query: "SELECT number FROM numbers(3)".to_string(),
+    });
+    let flight_info = service
+        .get_flight_info(Request::new(FlightDescriptor {
+            r#type: 2,
+            cmd: command.pack(),
+            path: vec![],
+        }))
+        .await?
+        .into_inner();
+    assert_eq!(flight_info.endpoint.len(), 1);
+
+    let ticket = flight_info.endpoint[0].ticket.clone().unwrap();
+    assert_eq!(
+        FlightSQLCommand::unpack(&ticket.ticket)?,
+        FlightSQLCommand::TicketStatementQuery(TicketStatementQuery {
+            statement_handle: b"SELECT number FROM numbers(3)".to_vec(),
+        })
+    );
+
+    let stream = service.do_get(Request::new(ticket)).await?.into_inner();
+    let flight_data: Vec<_> = stream.try_collect().await?;
+    // The schema, then the batches.
+    assert!(flight_data.len() >= 2);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_flight_sql_get_table_types() -> Result<()> {
+    let service = FlightSQLService::create(SessionManagerBuilder::create().build()?);
+
+    let command = FlightSQLCommand::GetTableTypes(CommandGetTableTypes {});
+    let stream = service
+        .do_get(Request::new(Ticket {
+            ticket: command.pack(),
+        }))
+        .await?
+        .into_inner();
+    let flight_data: Vec<_> = stream.try_collect().await?;
+    assert_eq!(flight_data.len(), 2);
+    Ok(())
+}
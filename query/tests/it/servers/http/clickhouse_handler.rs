@@ -98,6 +98,54 @@ async fn test_select() -> PoemResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_select_format() -> PoemResult<()> {
+    let server = Server::new();
+    let sql = "select number, 'a' as s from numbers(2) order by number";
+
+    {
+        let (status, body) = server.get(&format!("{} format TabSeparated", sql)).await;
+        assert_ok!(status, body);
+        assert_eq!(&body, "0\ta\n1\ta\n");
+    }
+
+    {
+        let (status, body) = server.get(&format!("{} FORMAT TSVWithNames;", sql)).await;
+        assert_ok!(status, body);
+        assert_eq!(&body, "number\ts\n0\ta\n1\ta\n");
+    }
+
+    {
+        let (status, body) = server.post(sql, " FORMAT JSONEachRow").await;
+        assert_ok!(status, body);
+        assert_eq!(
+            &body,
+            "{\"number\":0,\"s\":\"a\"}\n{\"number\":1,\"s\":\"a\"}\n"
+        );
+    }
+
+    {
+        let (status, body) = server.get_bytes(&format!("{} FORMAT Arrow", sql)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.starts_with(b"ARROW1"));
+        assert!(body.ends_with(b"ARROW1"));
+    }
+
+    {
+        let (status, body) = server.get_bytes(&format!("{} FORMAT Parquet", sql)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.starts_with(b"PAR1"));
+        assert!(body.ends_with(b"PAR1"));
+    }
+
+    {
+        let (status, body) = server.get(&format!("{} FORMAT XML", sql)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_error!(body, "output format XML not supported");
+    }
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_insert_values() -> PoemResult<()> {
     let server = Server::new();
@@ -261,6 +309,16 @@ impl Server {
         self.get_response(QueryBuilder::new(sql).build()).await
     }
 
+    pub async fn get_bytes(&self, sql: &str) -> (StatusCode, Vec<u8>) {
+        let response = self
+            .endpoint
+            .get_response(QueryBuilder::new(sql).build())
+            .await;
+        let status = response.status();
+        let body = response.into_body().into_vec().await.unwrap();
+        (status, body)
+    }
+
     pub async fn post(&self, sql: &str, body: &str) -> (StatusCode, String) {
         self.get_response(QueryBuilder::new(sql).body(body.to_string()).build())
             .await
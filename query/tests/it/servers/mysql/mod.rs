@@ -14,3 +14,4 @@
 
 mod mysql_federated;
 mod mysql_handler;
+mod mysql_prepared_statement;
@@ -45,6 +45,25 @@ async fn test_generic_code_with_on_query() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_prepared_statement() -> Result<()> {
+    let mut handler =
+        MySQLHandler::create(SessionManagerBuilder::create().max_sessions(1).build()?);
+
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port()).await?;
+
+    let params = (1u64, "it's a '?'", None::<u64>);
+    let row: Option<(u64, String, Option<u64>)> = connection
+        .exec_first("SELECT ? + 1, ?, ?", params)
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute prepared statement")?;
+    assert_eq!(row, Some((2, "it's a '?'".to_string(), None)));
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_rejected_session_with_sequence() -> Result<()> {
     let mut handler =
@@ -0,0 +1,48 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use databend_query::servers::MySQLPreparedStatement;
+
+#[test]
+fn test_mysql_prepared_statement() -> Result<()> {
+    // placeholders
+    {
+        let statement = MySQLPreparedStatement::create("SELECT * FROM t WHERE a = ? AND b > ?");
+        assert_eq!(statement.num_params(), 2);
+
+        let literals = vec!["1".to_string(), "'x'".to_string()];
+        let query = statement.bind(&literals)?;
+        assert_eq!(query, "SELECT * FROM t WHERE a = 1 AND b > 'x'");
+    }
+
+    // no placeholders in the strings, the quoted identifiers and the comments
+    {
+        let query = "SELECT '?', 'it\\'s ?', `a?`, \"?\" /* ? */ FROM t -- ?\nWHERE a = ?";
+        let statement = MySQLPreparedStatement::create(query);
+        assert_eq!(statement.num_params(), 1);
+
+        let query = statement.bind(&["NULL".to_string()])?;
+        assert!(query.ends_with("WHERE a = NULL"));
+    }
+
+    // wrong number of parameters
+    {
+        let statement = MySQLPreparedStatement::create("SELECT ?, ?");
+        let result = statement.bind(&["1".to_string()]);
+        assert!(result.is_err());
+    }
+
+    Ok(())
+}
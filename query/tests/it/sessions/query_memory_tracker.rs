@@ -0,0 +1,54 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::sessions::QueryMemoryTracker;
+
+#[test]
+fn test_query_memory_tracker() -> Result<()> {
+    let query = QueryMemoryTracker::create(100);
+    let operator1 = query.create_child("operator1");
+    let operator2 = query.create_child("operator2");
+
+    operator1.try_alloc(60)?;
+    operator2.try_alloc(30)?;
+    assert_eq!(query.get_usage(), 90);
+
+    // The reservation exceeding the limit of the query is rolled back.
+    let result = operator2.try_alloc(20);
+    assert_eq!(
+        result.unwrap_err().code(),
+        ErrorCode::query_memory_limit_exceeded_code()
+    );
+    assert_eq!(operator2.get_usage(), 30);
+    assert_eq!(query.get_usage(), 90);
+
+    operator1.try_update_usage(10)?;
+    assert_eq!(query.get_usage(), 40);
+    operator2.try_update_usage(90)?;
+    assert_eq!(query.get_usage(), 100);
+
+    // The memory held by an operator is released once it is dropped.
+    drop(operator2);
+    assert_eq!(query.get_usage(), 10);
+    operator1.dealloc(10);
+    assert_eq!(query.get_usage(), 0);
+
+    // A limit of 0 is unlimited.
+    let query = QueryMemoryTracker::create(0);
+    query.create_child("operator").try_alloc(usize::MAX / 2)?;
+    assert_eq!(query.get_usage(), 0);
+    Ok(())
+}
@@ -0,0 +1,100 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_exception::Result;
+use databend_query::sql::statements::DfBackupDatabase;
+use databend_query::sql::statements::DfRestoreDatabase;
+use databend_query::sql::*;
+
+use crate::sql::sql_parser::*;
+
+#[test]
+fn backup_database() -> Result<()> {
+    {
+        let sql = "BACKUP DATABASE db1 TO '@my_stage/backup'";
+        let expected = DfStatement::BackupDatabase(DfBackupDatabase {
+            name: "db1".to_string(),
+            location: "@my_stage/backup".to_string(),
+            connection: "".to_string(),
+            credential_options: BTreeMap::new(),
+            with_users: false,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "backup database db1 to 's3://mybucket/backup' connection = 'my_conn' with users";
+        let expected = DfStatement::BackupDatabase(DfBackupDatabase {
+            name: "db1".to_string(),
+            location: "s3://mybucket/backup".to_string(),
+            connection: "my_conn".to_string(),
+            credential_options: BTreeMap::new(),
+            with_users: true,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "backup database db1 from '@my_stage/backup'";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected TO, found: from".to_string(),
+        )?;
+    }
+
+    {
+        let sql = "backup database db1 to '@my_stage/backup' with roles";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected USERS, found: roles".to_string(),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn restore_database() -> Result<()> {
+    {
+        let sql = "RESTORE DATABASE db2 FROM 's3://mybucket/backup' \
+            CREDENTIALS = (aws_key_id='my_key_id' aws_secret_key='my_secret_key')";
+        let expected = DfStatement::RestoreDatabase(DfRestoreDatabase {
+            name: "db2".to_string(),
+            location: "s3://mybucket/backup".to_string(),
+            connection: "".to_string(),
+            credential_options: maplit::btreemap! {
+                "aws_key_id".into() => "my_key_id".into(),
+                "aws_secret_key".into() => "my_secret_key".into(),
+            },
+            with_users: false,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "restore database db2 from '@my_stage/backup' with users";
+        let expected = DfStatement::RestoreDatabase(DfRestoreDatabase {
+            name: "db2".to_string(),
+            location: "@my_stage/backup".to_string(),
+            connection: "".to_string(),
+            credential_options: BTreeMap::new(),
+            with_users: true,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_exception::Result;
+use databend_query::sql::statements::DfAlterConnection;
+use databend_query::sql::statements::DfCreateConnection;
+use databend_query::sql::statements::DfDropConnection;
+use databend_query::sql::statements::DfShowConnections;
+use databend_query::sql::*;
+
+use crate::sql::sql_parser::*;
+
+#[test]
+fn create_connection() -> Result<()> {
+    {
+        let sql = "CREATE CONNECTION my_s3 STORAGE_TYPE = 's3' \
+                   CREDENTIALS = (aws_key_id = '1a2b3c' aws_secret_key = '4x5y6z')";
+        let expected = DfStatement::CreateConnection(DfCreateConnection {
+            if_not_exists: false,
+            name: "my_s3".to_string(),
+            storage_type: "s3".to_string(),
+            credential_options: BTreeMap::from([
+                ("aws_key_id".to_string(), "1a2b3c".to_string()),
+                ("aws_secret_key".to_string(), "4x5y6z".to_string()),
+            ]),
+            comment: "".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "create connection if not exists my_s3 storage_type = 's3' \
+                   credentials = (aws_key_id = '1a2b3c' aws_secret_key = '4x5y6z') \
+                   comment = 'the load bucket'";
+        let expected = DfStatement::CreateConnection(DfCreateConnection {
+            if_not_exists: true,
+            name: "my_s3".to_string(),
+            storage_type: "s3".to_string(),
+            credential_options: BTreeMap::from([
+                ("aws_key_id".to_string(), "1a2b3c".to_string()),
+                ("aws_secret_key".to_string(), "4x5y6z".to_string()),
+            ]),
+            comment: "the load bucket".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "CREATE CONNECTION my_s3 CREDENTIALS = (aws_key_id = '1a2b3c')";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected STORAGE_TYPE, found: CREDENTIALS".to_string(),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn alter_connection() -> Result<()> {
+    {
+        let sql = "ALTER CONNECTION my_s3 \
+                   SET CREDENTIALS = (aws_key_id = '7a8b9c' aws_secret_key = '0x1y2z')";
+        let expected = DfStatement::AlterConnection(DfAlterConnection {
+            name: "my_s3".to_string(),
+            credential_options: Some(BTreeMap::from([
+                ("aws_key_id".to_string(), "7a8b9c".to_string()),
+                ("aws_secret_key".to_string(), "0x1y2z".to_string()),
+            ])),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ALTER CONNECTION my_s3 ROTATE MASTER KEY";
+        let expected = DfStatement::AlterConnection(DfAlterConnection {
+            name: "my_s3".to_string(),
+            credential_options: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn drop_connection() -> Result<()> {
+    {
+        let sql = "DROP CONNECTION my_s3";
+        let expected = DfStatement::DropConnection(DfDropConnection {
+            if_exists: false,
+            name: "my_s3".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "DROP CONNECTION IF EXISTS my_s3";
+        let expected = DfStatement::DropConnection(DfDropConnection {
+            if_exists: true,
+            name: "my_s3".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn show_connections() -> Result<()> {
+    expect_parse_ok(
+        "SHOW CONNECTIONS",
+        DfStatement::ShowConnections(DfShowConnections),
+    )?;
+
+    Ok(())
+}
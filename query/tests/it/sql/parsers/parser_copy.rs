@@ -15,8 +15,11 @@
 use common_exception::Result;
 use databend_query::sql::statements::DfCopy;
 use databend_query::sql::DfStatement;
+use sqlparser::ast::BinaryOperator;
+use sqlparser::ast::Expr;
 use sqlparser::ast::Ident;
 use sqlparser::ast::ObjectName;
+use sqlparser::ast::Value;
 
 use crate::sql::sql_parser::expect_parse_err;
 use crate::sql::sql_parser::expect_parse_ok;
@@ -40,6 +43,7 @@ fn copy_from_external_test() -> Result<()> {
             name: ObjectName(vec![Ident::new("mytable")]),
             columns: vec![],
             location: "s3://mybucket/data/files".to_string(),
+            connection: "".to_string(),
             credential_options: maplit::btreemap! {
                    "aws_key_id".into() => "my_key_id".into(),
                    "aws_secret_key".into() => "my_secret_key".into(),
@@ -57,7 +61,10 @@ fn copy_from_external_test() -> Result<()> {
             pattern: "".to_string(),
             on_error: "".to_string(),
             size_limit: "".to_string(),
+            purge: "".to_string(),
+            rejected_records: "".to_string(),
             validation_mode: "".to_string(),
+            transformation: vec![],
         }),
     }];
 
@@ -71,3 +78,52 @@ fn copy_from_external_test() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn copy_transformation_test() -> Result<()> {
+    let expect = DfCopy {
+        name: ObjectName(vec![Ident::new("mytable")]),
+        columns: vec![],
+        location: "@my_stage".to_string(),
+        connection: "".to_string(),
+        credential_options: Default::default(),
+        encryption_options: Default::default(),
+        file_format_options: maplit::btreemap! {
+               "type".into() => "csv".into(),
+        },
+        files: vec![],
+        pattern: "".to_string(),
+        on_error: "".to_string(),
+        size_limit: "".to_string(),
+        purge: "".to_string(),
+        rejected_records: "".to_string(),
+        validation_mode: "".to_string(),
+        transformation: vec![
+            Expr::Identifier(Ident::new("$2")),
+            Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("$1"))),
+                op: BinaryOperator::Plus,
+                right: Box::new(Expr::Value(Value::Number("1".to_string(), false))),
+            },
+            Expr::Identifier(Ident::new("metadata$filename")),
+        ],
+    };
+    expect_parse_ok(
+        "copy into mytable
+        from (select $2, $1 + 1 as id, metadata$filename from @my_stage)
+        file_format = (type = csv);",
+        DfStatement::Copy(expect),
+    )?;
+
+    expect_parse_err(
+        "copy into mytable from (select $1 from @my_stage where $2 > 1)",
+        "sql parser error: COPY transformation must be SELECT <expr>, ... FROM @<stage>"
+            .to_string(),
+    )?;
+    expect_parse_err(
+        "copy into mytable from (select * from @my_stage)",
+        "sql parser error: COPY transformation does not support the wildcard".to_string(),
+    )?;
+
+    Ok(())
+}
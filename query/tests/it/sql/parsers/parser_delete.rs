@@ -0,0 +1,55 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use databend_query::sql::statements::DfDelete;
+use databend_query::sql::*;
+use sqlparser::ast::*;
+
+use crate::sql::sql_parser::*;
+
+#[test]
+fn delete_from() -> Result<()> {
+    {
+        let sql = "delete from t1";
+        let expected = DfStatement::Delete(DfDelete {
+            name: ObjectName(vec![Ident::new("t1")]),
+            selection: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "DELETE FROM db1.t1 WHERE a > 1";
+        let expected = DfStatement::Delete(DfDelete {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            selection: Some(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("a"))),
+                op: BinaryOperator::Gt,
+                right: Box::new(Expr::Value(Value::Number("1".to_string(), false))),
+            }),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "delete t1";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected FROM, found: t1".to_string(),
+        )?;
+    }
+
+    Ok(())
+}
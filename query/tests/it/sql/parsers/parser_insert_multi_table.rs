@@ -0,0 +1,107 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use databend_query::sql::statements::DfInsertMultiTable;
+use databend_query::sql::statements::DfInsertMultiTableBranch;
+use databend_query::sql::statements::DfInsertMultiTableInto;
+use databend_query::sql::*;
+use sqlparser::ast::*;
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::Tokenizer;
+
+use crate::sql::sql_parser::*;
+
+fn number(n: &str) -> Expr {
+    Expr::Value(Value::Number(n.to_string(), false))
+}
+
+fn parse_query(sql: &str) -> Query {
+    let dialect = GenericDialect {};
+    let mut tokenizer = Tokenizer::new(&dialect, sql);
+    let (tokens, position_map) = tokenizer.tokenize().unwrap();
+    let mut parser = Parser::new(tokens, position_map, &dialect);
+    parser.parse_query().unwrap()
+}
+
+fn into(table: &str) -> DfInsertMultiTableInto {
+    DfInsertMultiTableInto {
+        table: ObjectName(vec![Ident::new(table)]),
+        columns: vec![],
+        values: vec![],
+    }
+}
+
+#[test]
+fn insert_multi_table() -> Result<()> {
+    {
+        let sql = "INSERT ALL WHEN a > 1 THEN INTO t1 INTO t2 SELECT a FROM s";
+        let expected = DfStatement::InsertMultiTable(DfInsertMultiTable {
+            first: false,
+            branches: vec![DfInsertMultiTableBranch {
+                condition: Some(Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier(Ident::new("a"))),
+                    op: BinaryOperator::Gt,
+                    right: Box::new(number("1")),
+                }),
+                intos: vec![into("t1"), into("t2")],
+            }],
+            source: Box::new(parse_query("SELECT a FROM s")),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "insert first when true then into db1.t1 (x, y) values (a, 1) \
+                   else into t2 select a from s";
+        let expected = DfStatement::InsertMultiTable(DfInsertMultiTable {
+            first: true,
+            branches: vec![
+                DfInsertMultiTableBranch {
+                    condition: Some(Expr::Value(Value::Boolean(true))),
+                    intos: vec![DfInsertMultiTableInto {
+                        table: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+                        columns: vec![Ident::new("x"), Ident::new("y")],
+                        values: vec![Expr::Identifier(Ident::new("a")), number("1")],
+                    }],
+                },
+                DfInsertMultiTableBranch {
+                    condition: None,
+                    intos: vec![into("t2")],
+                },
+            ],
+            source: Box::new(parse_query("select a from s")),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "insert all into t1 select a from s";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected WHEN, found: into".to_string(),
+        )?;
+    }
+
+    {
+        let sql = "insert first when a > 1 then select a from s";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected INTO, found: select".to_string(),
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use databend_query::sql::statements::DfMergeClause;
+use databend_query::sql::statements::DfMergeInto;
+use databend_query::sql::statements::DfMergeSource;
+use databend_query::sql::*;
+use sqlparser::ast::*;
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::Tokenizer;
+
+use crate::sql::sql_parser::*;
+
+fn number(n: &str) -> Expr {
+    Expr::Value(Value::Number(n.to_string(), false))
+}
+
+fn qualified(table: &str, column: &str) -> Expr {
+    Expr::CompoundIdentifier(vec![Ident::new(table), Ident::new(column)])
+}
+
+fn parse_query(sql: &str) -> Query {
+    let dialect = GenericDialect {};
+    let mut tokenizer = Tokenizer::new(&dialect, sql);
+    let (tokens, position_map) = tokenizer.tokenize().unwrap();
+    let mut parser = Parser::new(tokens, position_map, &dialect);
+    parser.parse_query().unwrap()
+}
+
+#[test]
+fn merge_into() -> Result<()> {
+    {
+        let sql = "MERGE INTO t USING s ON t.a = s.a WHEN MATCHED THEN DELETE";
+        let expected = DfStatement::MergeInto(DfMergeInto {
+            target: ObjectName(vec![Ident::new("t")]),
+            target_alias: None,
+            source: DfMergeSource::Table(ObjectName(vec![Ident::new("s")])),
+            source_alias: None,
+            on: Expr::BinaryOp {
+                left: Box::new(qualified("t", "a")),
+                op: BinaryOperator::Eq,
+                right: Box::new(qualified("s", "a")),
+            },
+            clauses: vec![DfMergeClause::MatchedDelete { condition: None }],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "merge into db1.t as x using (select a, b from s) y on x.a = y.a \
+                   when matched and y.b > 1 then update set b = y.b \
+                   when matched then delete \
+                   when not matched then insert (a, b) values (y.a, 1) \
+                   when not matched and y.b = 0 then insert values (y.a, y.b)";
+        let expected = DfStatement::MergeInto(DfMergeInto {
+            target: ObjectName(vec![Ident::new("db1"), Ident::new("t")]),
+            target_alias: Some(Ident::new("x")),
+            source: DfMergeSource::Query(Box::new(parse_query("select a, b from s"))),
+            source_alias: Some(Ident::new("y")),
+            on: Expr::BinaryOp {
+                left: Box::new(qualified("x", "a")),
+                op: BinaryOperator::Eq,
+                right: Box::new(qualified("y", "a")),
+            },
+            clauses: vec![
+                DfMergeClause::MatchedUpdate {
+                    condition: Some(Expr::BinaryOp {
+                        left: Box::new(qualified("y", "b")),
+                        op: BinaryOperator::Gt,
+                        right: Box::new(number("1")),
+                    }),
+                    update_list: vec![(Ident::new("b"), qualified("y", "b"))],
+                },
+                DfMergeClause::MatchedDelete { condition: None },
+                DfMergeClause::NotMatchedInsert {
+                    condition: None,
+                    columns: vec![Ident::new("a"), Ident::new("b")],
+                    values: vec![qualified("y", "a"), number("1")],
+                },
+                DfMergeClause::NotMatchedInsert {
+                    condition: Some(Expr::BinaryOp {
+                        left: Box::new(qualified("y", "b")),
+                        op: BinaryOperator::Eq,
+                        right: Box::new(number("0")),
+                    }),
+                    columns: vec![],
+                    values: vec![qualified("y", "a"), qualified("y", "b")],
+                },
+            ],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "merge into t using s on t.a = s.a";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected WHEN, found: EOF".to_string(),
+        )?;
+    }
+
+    {
+        let sql = "merge into t using s on t.a = s.a when matched then insert values (1)";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected UPDATE or DELETE, found: insert".to_string(),
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use databend_query::sql::statements::DfCreateNetworkPolicy;
+use databend_query::sql::statements::DfDropNetworkPolicy;
+use databend_query::sql::statements::DfShowNetworkPolicies;
+use databend_query::sql::*;
+
+use crate::sql::sql_parser::*;
+
+#[test]
+fn create_network_policy() -> Result<()> {
+    {
+        let sql = "CREATE NETWORK POLICY office ALLOWED_IP_LIST = ('192.168.1.0/24')";
+        let expected = DfStatement::CreateNetworkPolicy(DfCreateNetworkPolicy {
+            if_not_exists: false,
+            name: "office".to_string(),
+            allowed_ip_list: vec!["192.168.1.0/24".to_string()],
+            blocked_ip_list: vec![],
+            comment: "".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "create network policy if not exists office \
+                   allowed_ip_list = ('192.168.1.0/24', '10.0.0.1') \
+                   blocked_ip_list = ('192.168.1.99') comment = 'the office network'";
+        let expected = DfStatement::CreateNetworkPolicy(DfCreateNetworkPolicy {
+            if_not_exists: true,
+            name: "office".to_string(),
+            allowed_ip_list: vec!["192.168.1.0/24".to_string(), "10.0.0.1".to_string()],
+            blocked_ip_list: vec!["192.168.1.99".to_string()],
+            comment: "the office network".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "CREATE NETWORK POLICY office BLOCKED_IP_LIST = ('192.168.1.99')";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected ALLOWED_IP_LIST, found: BLOCKED_IP_LIST".to_string(),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn drop_network_policy() -> Result<()> {
+    {
+        let sql = "DROP NETWORK POLICY office";
+        let expected = DfStatement::DropNetworkPolicy(DfDropNetworkPolicy {
+            if_exists: false,
+            name: "office".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "DROP NETWORK POLICY IF EXISTS office";
+        let expected = DfStatement::DropNetworkPolicy(DfDropNetworkPolicy {
+            if_exists: true,
+            name: "office".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn show_network_policies() -> Result<()> {
+    expect_parse_ok(
+        "SHOW NETWORK POLICIES",
+        DfStatement::ShowNetworkPolicies(DfShowNetworkPolicies),
+    )?;
+
+    Ok(())
+}
@@ -27,6 +27,7 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             operation: Optimization::PURGE,
+            limit: None,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -36,6 +37,7 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             operation: Optimization::PURGE,
+            limit: None,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -45,6 +47,7 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             operation: Optimization::PURGE,
+            limit: None,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -54,6 +57,37 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             operation: Optimization::COMPACT,
+            limit: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "optimize TABLE t1 compact segment";
+        let expected = DfStatement::OptimizeTable(DfOptimizeTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            operation: Optimization::COMPACT_SEGMENT,
+            limit: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "optimize TABLE t1 compact limit 10";
+        let expected = DfStatement::OptimizeTable(DfOptimizeTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            operation: Optimization::COMPACT,
+            limit: Some(10),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "optimize TABLE t1 expire";
+        let expected = DfStatement::OptimizeTable(DfOptimizeTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            operation: Optimization::EXPIRE,
+            limit: None,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -63,6 +97,7 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             operation: Optimization::ALL,
+            limit: None,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -71,7 +106,7 @@ fn optimize_table() -> Result<()> {
         let sql = "optimize TABLE t1 unacceptable";
         expect_parse_err(
             sql,
-            "sql parser error: Expected one of PURGE, COMPACT, ALL, found: unacceptable"
+            "sql parser error: Expected one of PURGE, COMPACT, EXPIRE, ALL, found: unacceptable"
                 .to_string(),
         )?;
     }
@@ -80,7 +115,7 @@ fn optimize_table() -> Result<()> {
         let sql = "optimize TABLE t1 (";
         expect_parse_err(
             sql,
-            "sql parser error: Expected Nothing, or one of PURGE, COMPACT, ALL, found: ("
+            "sql parser error: Expected Nothing, or one of PURGE, COMPACT, EXPIRE, ALL, found: ("
                 .to_string(),
         )?;
     }
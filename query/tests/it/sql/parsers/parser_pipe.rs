@@ -0,0 +1,115 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_exception::Result;
+use databend_query::sql::statements::DfCreatePipe;
+use databend_query::sql::statements::DfDropPipe;
+use databend_query::sql::*;
+use sqlparser::ast::*;
+
+use crate::sql::sql_parser::*;
+
+#[test]
+fn create_pipe() -> Result<()> {
+    {
+        let sql =
+            "CREATE PIPE p1 INTO db1.t1 FROM KAFKA(BROKERS = 'b1:9092,b2:9092' TOPIC = 'events')";
+        let expected = DfStatement::CreatePipe(DfCreatePipe {
+            if_not_exists: false,
+            name: "p1".to_string(),
+            table_name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            source: "KAFKA".to_string(),
+            source_options: BTreeMap::from([
+                ("brokers".to_string(), "b1:9092,b2:9092".to_string()),
+                ("topic".to_string(), "events".to_string()),
+            ]),
+            file_format_options: BTreeMap::new(),
+            comments: "".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "create pipe if not exists p1 into t1 \
+                   from kafka(brokers = 'b1:9092' topic = 'events') \
+                   file_format = (type = json) comments = 'events from kafka'";
+        let expected = DfStatement::CreatePipe(DfCreatePipe {
+            if_not_exists: true,
+            name: "p1".to_string(),
+            table_name: ObjectName(vec![Ident::new("t1")]),
+            source: "KAFKA".to_string(),
+            source_options: BTreeMap::from([
+                ("brokers".to_string(), "b1:9092".to_string()),
+                ("topic".to_string(), "events".to_string()),
+            ]),
+            file_format_options: BTreeMap::from([("type".to_string(), "json".to_string())]),
+            comments: "events from kafka".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "CREATE PIPE p1 INTO t1 FROM @s1/logs/ PATTERN = '.*[.]csv' \
+                   FILE_FORMAT = (TYPE = CSV) ON_ERROR = CONTINUE SIZE_LIMIT = 10";
+        let expected = DfStatement::CreatePipe(DfCreatePipe {
+            if_not_exists: false,
+            name: "p1".to_string(),
+            table_name: ObjectName(vec![Ident::new("t1")]),
+            source: "@s1/logs/".to_string(),
+            source_options: BTreeMap::from([
+                ("pattern".to_string(), ".*[.]csv".to_string()),
+                ("on_error".to_string(), "CONTINUE".to_string()),
+                ("size_limit".to_string(), "10".to_string()),
+            ]),
+            file_format_options: BTreeMap::from([("type".to_string(), "CSV".to_string())]),
+            comments: "".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "create pipe p1 from kafka(brokers = 'b1:9092' topic = 'events')";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected INTO, found: from".to_string(),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn drop_pipe() -> Result<()> {
+    {
+        let sql = "DROP PIPE p1";
+        let expected = DfStatement::DropPipe(DfDropPipe {
+            if_exists: false,
+            name: "p1".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "DROP PIPE IF EXISTS p1";
+        let expected = DfStatement::DropPipe(DfDropPipe {
+            if_exists: true,
+            name: "p1".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    Ok(())
+}
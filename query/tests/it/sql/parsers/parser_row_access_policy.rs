@@ -0,0 +1,107 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use databend_query::sql::statements::DfCreateRowAccessPolicy;
+use databend_query::sql::statements::DfDropRowAccessPolicy;
+use databend_query::sql::statements::DfShowRowAccessPolicies;
+use databend_query::sql::*;
+use sqlparser::ast::*;
+
+use crate::sql::sql_parser::*;
+
+#[test]
+fn create_row_access_policy() -> Result<()> {
+    {
+        let sql = "CREATE ROW ACCESS POLICY p1 AS (r VARCHAR) RETURNS BOOLEAN -> r = 'us'";
+        let expected = DfStatement::CreateRowAccessPolicy(DfCreateRowAccessPolicy {
+            if_not_exists: false,
+            name: "p1".to_string(),
+            args: vec![(Ident::new("r"), DataType::Varchar(None))],
+            body: Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("r"))),
+                op: BinaryOperator::Eq,
+                right: Box::new(Expr::Value(Value::SingleQuotedString("us".to_string()))),
+            },
+            exempt_roles: vec![],
+            comment: "".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "create row access policy if not exists p1 as (r varchar, id int) \
+                   returns boolean -> id > 10 exempt roles = ('admin', 'auditor') \
+                   comment = 'only the recent rows'";
+        let expected = DfStatement::CreateRowAccessPolicy(DfCreateRowAccessPolicy {
+            if_not_exists: true,
+            name: "p1".to_string(),
+            args: vec![
+                (Ident::new("r"), DataType::Varchar(None)),
+                (Ident::new("id"), DataType::Int(None)),
+            ],
+            body: Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("id"))),
+                op: BinaryOperator::Gt,
+                right: Box::new(Expr::Value(Value::Number("10".to_string(), false))),
+            },
+            exempt_roles: vec!["admin".to_string(), "auditor".to_string()],
+            comment: "only the recent rows".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "CREATE ROW ACCESS POLICY p1 AS (r VARCHAR, r INT) RETURNS BOOLEAN -> r = 1";
+        expect_parse_err_contains(
+            sql,
+            "Duplicate argument is not allowed, keep only one: r".to_string(),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn drop_row_access_policy() -> Result<()> {
+    {
+        let sql = "DROP ROW ACCESS POLICY p1";
+        let expected = DfStatement::DropRowAccessPolicy(DfDropRowAccessPolicy {
+            if_exists: false,
+            name: "p1".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "DROP ROW ACCESS POLICY IF EXISTS p1";
+        let expected = DfStatement::DropRowAccessPolicy(DfDropRowAccessPolicy {
+            if_exists: true,
+            name: "p1".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn show_row_access_policies() -> Result<()> {
+    expect_parse_ok(
+        "SHOW ROW ACCESS POLICIES",
+        DfStatement::ShowRowAccessPolicies(DfShowRowAccessPolicies),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use databend_query::sql::statements::DfAlterShareTenants;
+use databend_query::sql::statements::DfCreateShare;
+use databend_query::sql::statements::DfDropShare;
+use databend_query::sql::statements::DfGrantShareObject;
+use databend_query::sql::statements::DfRevokeShareObject;
+use databend_query::sql::statements::DfShareGrantObject;
+use databend_query::sql::*;
+
+use crate::sql::sql_parser::*;
+
+#[test]
+fn create_and_drop_share() -> Result<()> {
+    expect_parse_ok(
+        "CREATE SHARE s1",
+        DfStatement::CreateShare(DfCreateShare {
+            if_not_exists: false,
+            share: "s1".to_string(),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "create share if not exists s1",
+        DfStatement::CreateShare(DfCreateShare {
+            if_not_exists: true,
+            share: "s1".to_string(),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "DROP SHARE s1",
+        DfStatement::DropShare(DfDropShare {
+            if_exists: false,
+            share: "s1".to_string(),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "drop share if exists s1",
+        DfStatement::DropShare(DfDropShare {
+            if_exists: true,
+            share: "s1".to_string(),
+        }),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn grant_and_revoke_share_object() -> Result<()> {
+    expect_parse_ok(
+        "GRANT USAGE ON DATABASE db1 TO SHARE s1",
+        DfStatement::GrantShareObject(DfGrantShareObject {
+            share: "s1".to_string(),
+            object: DfShareGrantObject::Database("db1".to_string()),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "grant select on table db1.t1 to share s1",
+        DfStatement::GrantShareObject(DfGrantShareObject {
+            share: "s1".to_string(),
+            object: DfShareGrantObject::Table(Some("db1".to_string()), "t1".to_string()),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "GRANT SELECT ON TABLE t1 TO SHARE s1",
+        DfStatement::GrantShareObject(DfGrantShareObject {
+            share: "s1".to_string(),
+            object: DfShareGrantObject::Table(None, "t1".to_string()),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "REVOKE USAGE ON DATABASE db1 FROM SHARE s1",
+        DfStatement::RevokeShareObject(DfRevokeShareObject {
+            share: "s1".to_string(),
+            object: DfShareGrantObject::Database("db1".to_string()),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "revoke select on table db1.t1 from share s1",
+        DfStatement::RevokeShareObject(DfRevokeShareObject {
+            share: "s1".to_string(),
+            object: DfShareGrantObject::Table(Some("db1".to_string()), "t1".to_string()),
+        }),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn alter_share_tenants() -> Result<()> {
+    expect_parse_ok(
+        "ALTER SHARE s1 ADD TENANTS = t1, t2",
+        DfStatement::AlterShareTenants(DfAlterShareTenants {
+            if_exists: false,
+            share: "s1".to_string(),
+            is_add: true,
+            tenants: vec!["t1".to_string(), "t2".to_string()],
+        }),
+    )?;
+
+    expect_parse_ok(
+        "alter share if exists s1 remove tenants = t1",
+        DfStatement::AlterShareTenants(DfAlterShareTenants {
+            if_exists: true,
+            share: "s1".to_string(),
+            is_add: false,
+            tenants: vec!["t1".to_string()],
+        }),
+    )?;
+
+    expect_parse_err(
+        "ALTER SHARE s1 SET TENANTS = t1",
+        "sql parser error: Expected ADD or REMOVE, found: SET".to_string(),
+    )?;
+
+    Ok(())
+}
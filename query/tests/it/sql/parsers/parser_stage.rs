@@ -68,6 +68,17 @@ fn create_stage_test() -> Result<()> {
           ..Default::default()}),
     )?;
 
+    expect_parse_ok(
+        "CREATE STAGE test_stage url='s3://load/files/' connection='my_s3'",
+        DfStatement::CreateStage(DfCreateUserStage {
+            if_not_exists: false,
+            stage_name: "test_stage".to_string(),
+            location: "s3://load/files/".to_string(),
+            connection: "my_s3".to_string(),
+            ..Default::default()
+        }),
+    )?;
+
     expect_parse_ok(
         "list @abc pattern = '*.csv'",
         DfStatement::List(DfList {
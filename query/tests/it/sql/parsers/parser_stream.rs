@@ -0,0 +1,53 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use databend_query::sql::statements::DfCreateStream;
+use databend_query::sql::*;
+use sqlparser::ast::*;
+
+use crate::sql::sql_parser::*;
+
+#[test]
+fn create_stream() -> Result<()> {
+    {
+        let sql = "CREATE STREAM s1 ON TABLE t1";
+        let expected = DfStatement::CreateStream(DfCreateStream {
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("s1")]),
+            table_name: ObjectName(vec![Ident::new("t1")]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "create stream if not exists db1.s1 on table db2.t1";
+        let expected = DfStatement::CreateStream(DfCreateStream {
+            if_not_exists: true,
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("s1")]),
+            table_name: ObjectName(vec![Ident::new("db2"), Ident::new("t1")]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "create stream s1 on t1";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected TABLE, found: t1".to_string(),
+        )?;
+    }
+
+    Ok(())
+}
@@ -12,19 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 
+use chrono::TimeZone;
+use chrono::Utc;
 use common_exception::Result;
 use databend_query::sql::statements::AlterTableAction;
 use databend_query::sql::statements::DfAlterTable;
+use databend_query::sql::statements::DfAttachTable;
+use databend_query::sql::statements::DfCloneSource;
 use databend_query::sql::statements::DfCreateTable;
 use databend_query::sql::statements::DfDescribeTable;
 use databend_query::sql::statements::DfDropTable;
+use databend_query::sql::statements::DfGeneratedColumn;
 use databend_query::sql::statements::DfQueryStatement;
 use databend_query::sql::statements::DfRenameTable;
 use databend_query::sql::statements::DfShowCreateTable;
 use databend_query::sql::statements::DfTruncateTable;
 use databend_query::sql::*;
+use databend_query::storages::NavigationPoint;
 use sqlparser::ast::*;
 
 use crate::sql::sql_parser::*;
@@ -37,10 +44,12 @@ fn create_table() -> Result<()> {
         if_not_exists: false,
         name: ObjectName(vec![Ident::new("t")]),
         columns: vec![make_column_def("c1", None, DataType::Int(None))],
+        generated_columns: BTreeMap::new(),
         engine: "Fuse".to_string(),
         options: maplit::btreemap! {"location".into() => "/data/33.csv".into()},
         like: None,
         query: None,
+        clone: None,
         order_keys: vec![],
     });
     expect_parse_ok(sql, expected)?;
@@ -50,10 +59,12 @@ fn create_table() -> Result<()> {
         if_not_exists: false,
         name: ObjectName(vec![Ident::new("t")]),
         columns: vec![make_column_def("c1", Some('`'), DataType::Int(None))],
+        generated_columns: BTreeMap::new(),
         engine: "Fuse".to_string(),
         options: maplit::btreemap! {"location".into() => "/data/33.csv".into()},
         like: None,
         query: None,
+        clone: None,
         order_keys: vec![],
     });
     expect_parse_ok(sql, expected)?;
@@ -63,10 +74,12 @@ fn create_table() -> Result<()> {
         if_not_exists: false,
         name: ObjectName(vec![Ident::new("t")]),
         columns: vec![make_column_def("c1", Some('\''), DataType::Int(None))],
+        generated_columns: BTreeMap::new(),
         engine: "Fuse".to_string(),
         options: maplit::btreemap! {"location".into() => "/data/33.csv".into()},
         like: None,
         query: None,
+        clone: None,
         order_keys: vec![],
     });
     expect_parse_ok(sql, expected)?;
@@ -81,6 +94,7 @@ fn create_table() -> Result<()> {
             make_column_def("c2", None, DataType::BigInt(None)),
             make_column_def("c3", None, DataType::Varchar(Some(255))),
         ],
+        generated_columns: BTreeMap::new(),
         engine: "Fuse".to_string(),
 
         options: maplit::btreemap! {
@@ -89,21 +103,50 @@ fn create_table() -> Result<()> {
         },
         like: None,
         query: None,
+        clone: None,
         order_keys: vec![],
     });
     expect_parse_ok(sql, expected)?;
 
+    // cluster keys in the z-order
+    let sql =
+        "CREATE TABLE t(c1 int, c2 int) CLUSTER BY (c1, c2) ORDER = ZORDER row_per_block = 10";
+    let expected = DfStatement::CreateTable(DfCreateTable {
+        if_not_exists: false,
+        name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![
+            make_column_def("c1", None, DataType::Int(None)),
+            make_column_def("c2", None, DataType::Int(None)),
+        ],
+        generated_columns: BTreeMap::new(),
+        engine: "FUSE".to_string(),
+        options: maplit::btreemap! {
+            "cluster_order".into() => "zorder".into(),
+            "row_per_block".into() => "10".into(),
+        },
+        like: None,
+        query: None,
+        clone: None,
+        order_keys: vec![
+            Expr::Identifier(Ident::new("c1")),
+            Expr::Identifier(Ident::new("c2")),
+        ],
+    });
+    expect_parse_ok(sql, expected)?;
+
     // create table like statement
     let sql = "CREATE TABLE db1.test1 LIKE db2.test2 ENGINE = Parquet location = 'batcave'";
     let expected = DfStatement::CreateTable(DfCreateTable {
         if_not_exists: false,
         name: ObjectName(vec![Ident::new("db1"), Ident::new("test1")]),
         columns: vec![],
+        generated_columns: BTreeMap::new(),
         engine: "Parquet".to_string(),
 
         options: maplit::btreemap! {"location".into() => "batcave".into()},
         like: Some(ObjectName(vec![Ident::new("db2"), Ident::new("test2")])),
         query: None,
+        clone: None,
         order_keys: vec![],
     });
     expect_parse_ok(sql, expected)?;
@@ -117,6 +160,7 @@ fn create_table() -> Result<()> {
             make_column_def("c1", None, DataType::Int(None)),
             make_column_def("c2", None, DataType::Varchar(Some(255))),
         ],
+        generated_columns: BTreeMap::new(),
         engine: "Parquet".to_string(),
 
         options: maplit::btreemap! {"location".into() => "batcave".into()},
@@ -140,9 +184,58 @@ fn create_table() -> Result<()> {
             limit: None,
             offset: None,
         })),
+        clone: None,
         order_keys: vec![],
     });
     expect_parse_ok(sql, expected)?;
+
+    // generated columns
+    let sql = "CREATE TABLE t(c1 int, c2 int AS (c1 * 2) STORED, \
+               c3 int GENERATED ALWAYS AS (c1 + 1) VIRTUAL, c4 int AS (c1))";
+    let c1 = || Box::new(Expr::Identifier(Ident::new("c1")));
+    let number = |n: &str| Box::new(Expr::Value(Value::Number(n.to_string(), false)));
+    let expected = DfStatement::CreateTable(DfCreateTable {
+        if_not_exists: false,
+        name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![
+            make_column_def("c1", None, DataType::Int(None)),
+            make_column_def("c2", None, DataType::Int(None)),
+            make_column_def("c3", None, DataType::Int(None)),
+            make_column_def("c4", None, DataType::Int(None)),
+        ],
+        generated_columns: maplit::btreemap! {
+            "c2".into() => DfGeneratedColumn {
+                expr: Expr::BinaryOp {
+                    left: c1(),
+                    op: BinaryOperator::Multiply,
+                    right: number("2"),
+                },
+                stored: true,
+            },
+            "c3".into() => DfGeneratedColumn {
+                expr: Expr::BinaryOp {
+                    left: c1(),
+                    op: BinaryOperator::Plus,
+                    right: number("1"),
+                },
+                stored: false,
+            },
+            "c4".into() => DfGeneratedColumn {
+                expr: *c1(),
+                stored: false,
+            },
+        },
+        engine: "FUSE".to_string(),
+        options: BTreeMap::new(),
+        like: None,
+        query: None,
+        clone: None,
+        order_keys: vec![],
+    });
+    expect_parse_ok(sql, expected)?;
+
+    let sql = "CREATE TABLE t(c1 int, c2 int AS c1)";
+    expect_parse_err_contains(sql, "Expected (".to_string())?;
     Ok(())
 }
 
@@ -154,10 +247,12 @@ fn create_table_select() -> Result<()> {
             if_not_exists: false,
             name: ObjectName(vec![Ident::new("foo")]),
             columns: vec![],
+            generated_columns: BTreeMap::new(),
             engine: "FUSE".to_string(),
             options: maplit::btreemap! {},
             like: None,
             query: Some(verified_query("SELECT a, b FROM bar")?),
+            clone: None,
             order_keys: vec![],
         }),
     )?;
@@ -168,10 +263,12 @@ fn create_table_select() -> Result<()> {
             if_not_exists: false,
             name: ObjectName(vec![Ident::new("foo")]),
             columns: vec![make_column_def("a", None, DataType::Int(None))],
+            generated_columns: BTreeMap::new(),
             engine: "FUSE".to_string(),
             options: maplit::btreemap! {},
             like: None,
             query: Some(verified_query("SELECT a, b FROM bar")?),
+            clone: None,
             order_keys: vec![],
         }),
     )?;
@@ -179,6 +276,114 @@ fn create_table_select() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn clone_table() -> Result<()> {
+    {
+        let sql = "CREATE TABLE t2 CLONE db1.t1";
+        let expected = DfStatement::CreateTable(DfCreateTable {
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("t2")]),
+            columns: vec![],
+            generated_columns: BTreeMap::new(),
+            engine: "FUSE".to_string(),
+            options: maplit::btreemap! {},
+            like: None,
+            query: None,
+            clone: Some(DfCloneSource {
+                name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+                at: None,
+            }),
+            order_keys: vec![],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "CREATE TABLE t2 CLONE t1 AT (SNAPSHOT => 'c2b5d8e5b4e84ef4a0a2bb94ee33ad2d')";
+        let expected = DfStatement::CreateTable(DfCreateTable {
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("t2")]),
+            columns: vec![],
+            generated_columns: BTreeMap::new(),
+            engine: "FUSE".to_string(),
+            options: maplit::btreemap! {},
+            like: None,
+            query: None,
+            clone: Some(DfCloneSource {
+                name: ObjectName(vec![Ident::new("t1")]),
+                at: Some(NavigationPoint::SnapshotID(
+                    "c2b5d8e5b4e84ef4a0a2bb94ee33ad2d".to_string(),
+                )),
+            }),
+            order_keys: vec![],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "CREATE TABLE t2 CLONE t1 AT (TIMESTAMP => '2022-05-01 10:00:00')";
+        let time_point = Utc.ymd(2022, 5, 1).and_hms(10, 0, 0);
+        let expected = DfStatement::CreateTable(DfCreateTable {
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("t2")]),
+            columns: vec![],
+            generated_columns: BTreeMap::new(),
+            engine: "FUSE".to_string(),
+            options: maplit::btreemap! {},
+            like: None,
+            query: None,
+            clone: Some(DfCloneSource {
+                name: ObjectName(vec![Ident::new("t1")]),
+                at: Some(NavigationPoint::TimePoint(time_point)),
+            }),
+            order_keys: vec![],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "CREATE TABLE t2 CLONE t1 AT (TIMESTAMP => 'yesterday')";
+        expect_parse_err_contains(sql, "invalid time point yesterday".to_string())?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn attach_table() -> Result<()> {
+    {
+        let sql = "ATTACH TABLE db1.t1 FROM '1/2/_ss/c2b5d8e5b4e84ef4a0a2bb94ee33ad2d_v2.json'";
+        let expected = DfStatement::AttachTable(DfAttachTable {
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            uri: "1/2/_ss/c2b5d8e5b4e84ef4a0a2bb94ee33ad2d_v2.json".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "attach table t1 'uri'";
+        expect_parse_err_contains(sql, "Expected FROM".to_string())?;
+    }
+
+    {
+        let sql = "CREATE TABLE IF NOT EXISTS db1.t1 'fuse:///1/2' READ_ONLY";
+        let expected = DfStatement::AttachTable(DfAttachTable {
+            if_not_exists: true,
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            uri: "fuse:///1/2".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "create table t1 'fuse:///1/2'";
+        expect_parse_err_contains(sql, "Expected READ_ONLY".to_string())?;
+    }
+
+    Ok(())
+}
+
 #[test]
 fn drop_table() -> Result<()> {
     {
@@ -217,6 +422,148 @@ fn alter_table() -> Result<()> {
         expect_parse_ok(sql, expected)?;
     }
 
+    // alter table flashback
+    {
+        let sql = "ALTER TABLE IF EXISTS db1.t1 FLASHBACK TO (SNAPSHOT => 'c2b5d8e5b4e84ef4')";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: true,
+            table_name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            action: AlterTableAction::Flashback(NavigationPoint::SnapshotID(
+                "c2b5d8e5b4e84ef4".to_string(),
+            )),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ALTER TABLE t1 FLASHBACK TO (TIMESTAMP => '2022-06-01 10:00:00')";
+        let time_point = Utc.ymd(2022, 6, 1).and_hms(10, 0, 0);
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: false,
+            table_name: ObjectName(vec![Ident::new("t1")]),
+            action: AlterTableAction::Flashback(NavigationPoint::TimePoint(time_point)),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ALTER TABLE t1 FLASHBACK (SNAPSHOT => 'c2b5d8e5b4e84ef4')";
+        expect_parse_err_contains(sql, "Expected TO".to_string())?;
+    }
+
+    // alter table add/drop/rename column
+    {
+        let sql = "ALTER TABLE t1 ADD COLUMN c2 INT";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: false,
+            table_name: ObjectName(vec![Ident::new("t1")]),
+            action: AlterTableAction::AddColumn(make_column_def("c2", None, DataType::Int(None))),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ALTER TABLE t1 ADD c2 INT NULL DEFAULT 1";
+        let mut column = make_column_def("c2", None, DataType::Int(None));
+        column.options = vec![
+            ColumnOptionDef {
+                name: None,
+                option: ColumnOption::Null,
+            },
+            ColumnOptionDef {
+                name: None,
+                option: ColumnOption::Default(Expr::Value(Value::Number("1".to_string(), false))),
+            },
+        ];
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: false,
+            table_name: ObjectName(vec![Ident::new("t1")]),
+            action: AlterTableAction::AddColumn(column),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ALTER TABLE t1 ADD COLUMN c2 INT AS (c1 + 1)";
+        expect_parse_err_contains(sql, "can not be added to an existing table".to_string())?;
+    }
+
+    {
+        let sql = "ALTER TABLE IF EXISTS t1 DROP COLUMN c1";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: true,
+            table_name: ObjectName(vec![Ident::new("t1")]),
+            action: AlterTableAction::DropColumn(Ident::new("c1")),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ALTER TABLE t1 RENAME COLUMN c1 TO c2";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: false,
+            table_name: ObjectName(vec![Ident::new("t1")]),
+            action: AlterTableAction::RenameColumn {
+                old_column: Ident::new("c1"),
+                new_column: Ident::new("c2"),
+            },
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    // alter table recluster
+    {
+        let sql = "ALTER TABLE t1 RECLUSTER";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: false,
+            table_name: ObjectName(vec![Ident::new("t1")]),
+            action: AlterTableAction::Recluster {
+                is_final: false,
+                selection: None,
+                limit: None,
+            },
+        });
+        expect_parse_ok(sql, expected)?;
+
+        let sql = "ALTER TABLE t1 RECLUSTER FINAL WHERE a > 1 LIMIT 10";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: false,
+            table_name: ObjectName(vec![Ident::new("t1")]),
+            action: AlterTableAction::Recluster {
+                is_final: true,
+                selection: Some(Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier(Ident::new("a"))),
+                    op: BinaryOperator::Gt,
+                    right: Box::new(Expr::Value(Value::Number("1".to_string(), false))),
+                }),
+                limit: Some(10),
+            },
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    // alter table row access policy
+    {
+        let sql = "ALTER TABLE t1 ADD ROW ACCESS POLICY p1 ON (region, owner)";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: false,
+            table_name: ObjectName(vec![Ident::new("t1")]),
+            action: AlterTableAction::AddRowAccessPolicy {
+                policy: "p1".to_string(),
+                columns: vec![Ident::new("region"), Ident::new("owner")],
+            },
+        });
+        expect_parse_ok(sql, expected)?;
+
+        let sql = "ALTER TABLE t1 DROP ROW ACCESS POLICY p1";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: false,
+            table_name: ObjectName(vec![Ident::new("t1")]),
+            action: AlterTableAction::DropRowAccessPolicy("p1".to_string()),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,67 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use databend_query::sql::statements::DfUpdate;
+use databend_query::sql::*;
+use sqlparser::ast::*;
+
+use crate::sql::sql_parser::*;
+
+#[test]
+fn update() -> Result<()> {
+    {
+        let sql = "update t1 set a = 1";
+        let expected = DfStatement::Update(DfUpdate {
+            name: ObjectName(vec![Ident::new("t1")]),
+            update_list: vec![(
+                Ident::new("a"),
+                Expr::Value(Value::Number("1".to_string(), false)),
+            )],
+            selection: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "UPDATE db1.t1 SET a = a + 1, b = 'x' WHERE a > 1";
+        let expected = DfStatement::Update(DfUpdate {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            update_list: vec![
+                (Ident::new("a"), Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier(Ident::new("a"))),
+                    op: BinaryOperator::Plus,
+                    right: Box::new(Expr::Value(Value::Number("1".to_string(), false))),
+                }),
+                (
+                    Ident::new("b"),
+                    Expr::Value(Value::SingleQuotedString("x".to_string())),
+                ),
+            ],
+            selection: Some(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("a"))),
+                op: BinaryOperator::Gt,
+                right: Box::new(Expr::Value(Value::Number("1".to_string(), false))),
+            }),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "update t1 a = 1";
+        expect_parse_err(sql, "sql parser error: Expected SET, found: a".to_string())?;
+    }
+
+    Ok(())
+}
@@ -148,6 +148,17 @@ fn create_user_test() -> Result<()> {
         }),
     )?;
 
+    let with_options = vec![DfUserWithOption::NetworkPolicy("office".to_string())];
+    expect_parse_ok(
+        "CREATE USER 'operator' WITH NETWORKPOLICY = 'office' NOT IDENTIFIED",
+        DfStatement::CreateUser(DfCreateUser {
+            if_not_exists: false,
+            user: UserIdentity::new("operator", "%"),
+            auth_option: DfAuthOption::no_password(),
+            with_options,
+        }),
+    )?;
+
     // create user with option
     expect_parse_err(
         "CREATE USER 'operator' NOT IDENTIFIED WITH TENANTSETTINGS",
@@ -256,6 +267,20 @@ fn alter_user_test() -> Result<()> {
         }),
     )?;
 
+    let with_options = vec![
+        DfUserWithOption::NoNetworkPolicy,
+        DfUserWithOption::ConfigReload,
+    ];
+    expect_parse_ok(
+        "ALTER USER 'test'@'%' WITH NONETWORKPOLICY, CONFIGRELOAD",
+        DfStatement::AlterUser(DfAlterUser {
+            if_current_user: false,
+            user: UserIdentity::new("test", "%"),
+            auth_option: None,
+            with_options,
+        }),
+    )?;
+
     expect_parse_err(
         "ALTER USER 'test'@'localhost' IDENTIFIED WITH no_password BY 'password'",
         String::from("sql parser error: Expected end of statement, found: BY"),
@@ -483,6 +508,45 @@ fn grant_privilege_test() -> Result<()> {
         }),
     )?;
 
+    expect_parse_ok(
+        "GRANT USAGE ON STAGE s1 TO ROLE 'myrole'",
+        DfStatement::GrantPrivilege(DfGrantPrivilegeStatement {
+            principal: PrincipalIdentity::role("myrole".to_string()),
+            on: DfGrantObject::Stage("s1".into()),
+            priv_types: {
+                let mut privileges = UserPrivilegeSet::empty();
+                privileges.set_privilege(UserPrivilegeType::Usage);
+                privileges
+            },
+        }),
+    )?;
+
+    expect_parse_ok(
+        "GRANT USAGE ON FUNCTION f1 TO 'test'@'localhost'",
+        DfStatement::GrantPrivilege(DfGrantPrivilegeStatement {
+            principal: PrincipalIdentity::user("test".to_string(), "localhost".to_string()),
+            on: DfGrantObject::UDF("f1".into()),
+            priv_types: {
+                let mut privileges = UserPrivilegeSet::empty();
+                privileges.set_privilege(UserPrivilegeType::Usage);
+                privileges
+            },
+        }),
+    )?;
+
+    expect_parse_ok(
+        "GRANT OWNERSHIP ON db1.tb1 TO ROLE 'myrole'",
+        DfStatement::GrantPrivilege(DfGrantPrivilegeStatement {
+            principal: PrincipalIdentity::role("myrole".to_string()),
+            on: DfGrantObject::Table(Some("db1".into()), "tb1".into()),
+            priv_types: {
+                let mut privileges = UserPrivilegeSet::empty();
+                privileges.set_privilege(UserPrivilegeType::Ownership);
+                privileges
+            },
+        }),
+    )?;
+
     expect_parse_err(
         "GRANT TEST, ON * TO 'test'@'localhost'",
         String::from("sql parser error: Expected privilege type, found: TEST"),
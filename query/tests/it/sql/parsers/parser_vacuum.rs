@@ -0,0 +1,73 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use databend_query::sql::statements::DfVacuumTable;
+use databend_query::sql::*;
+use sqlparser::ast::*;
+
+use crate::sql::sql_parser::*;
+
+#[test]
+fn vacuum_table() -> Result<()> {
+    {
+        let sql = "vacuum TABLE t1";
+        let expected = DfStatement::VacuumTable(DfVacuumTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            retain_hours: None,
+            dry_run: false,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "VACUUM TABLE db1.t1 RETAIN 24 HOURS";
+        let expected = DfStatement::VacuumTable(DfVacuumTable {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            retain_hours: Some(24),
+            dry_run: false,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "vacuum table t1 dry run";
+        let expected = DfStatement::VacuumTable(DfVacuumTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            retain_hours: None,
+            dry_run: true,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "vacuum table t1 retain 0 hours dry run";
+        let expected = DfStatement::VacuumTable(DfVacuumTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            retain_hours: Some(0),
+            dry_run: true,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "vacuum table t1 retain 3 days";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected HOURS, found: days".to_string(),
+        )?;
+    }
+
+    Ok(())
+}
@@ -143,3 +143,32 @@ fn hint_test() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn select_from_stage_test() -> Result<()> {
+    let query = verified_query(
+        "SELECT $1, metadata$filename FROM @my_stage (FILE_FORMAT => 'csv') WHERE $2 > 1",
+    )?;
+
+    assert_eq!(query.projection, vec![
+        SelectItem::UnnamedExpr(Expr::Identifier(Ident::new("$1"))),
+        SelectItem::UnnamedExpr(Expr::Identifier(Ident::new("metadata$filename"))),
+    ]);
+    assert_eq!(
+        query.selection,
+        Some(Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("$2"))),
+            op: BinaryOperator::Gt,
+            right: Box::new(Expr::Value(Value::Number("1".to_string(), false))),
+        })
+    );
+    match &query.from[0].relation {
+        TableFactor::Table { name, args, .. } => {
+            assert_eq!(name, &ObjectName(vec![Ident::new("@my_stage")]));
+            assert_eq!(args.len(), 1);
+        }
+        other => panic!("Expect the stage as a table, got: {:?}", other),
+    }
+
+    Ok(())
+}
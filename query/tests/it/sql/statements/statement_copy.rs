@@ -38,7 +38,7 @@ async fn test_statement_copy() -> Result<()> {
         credentials=(aws_key_id='my_key_id' aws_secret_key='my_secret_key')
         encryption=(master_key = 'my_master_key')
         file_format = (type = csv field_delimiter = '|' skip_header = 1)",
-            expect: r#"Copy into system.configs, ReadDataSourcePlan { source_info: S3StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StageS3Storage { bucket: "mybucket", path: "/data/files", credentials_aws_key_id: "my_key_id", credentials_aws_secret_key: "my_secret_key", encryption_master_key: "my_master_key" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0 }, comment: "" }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,validation_mode:None"#,
+            expect: r#"Copy into system.configs, ReadDataSourcePlan { source_info: S3StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StageS3Storage { bucket: "mybucket", path: "/data/files", credentials_aws_key_id: "my_key_id", credentials_aws_secret_key: "my_secret_key", encryption_master_key: "my_master_key", connection: "" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", enclosed_by: "", escaped_by: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0, purge: false, rejected_records: "" }, comment: "" }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,validation_mode:None"#,
             err: "",
         },
 
@@ -51,7 +51,7 @@ async fn test_statement_copy() -> Result<()> {
         file_format = (type = csv field_delimiter = '|' skip_header = 1)
         VALIDATION_MODE = RETURN_13_ROWS
         ",
-            expect: r#"Copy into system.configs, ReadDataSourcePlan { source_info: S3StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StageS3Storage { bucket: "mybucket", path: "/data/files", credentials_aws_key_id: "my_key_id", credentials_aws_secret_key: "my_secret_key", encryption_master_key: "my_master_key" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0 }, comment: "" }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,validation_mode:ReturnNRows(13)"#,
+            expect: r#"Copy into system.configs, ReadDataSourcePlan { source_info: S3StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StageS3Storage { bucket: "mybucket", path: "/data/files", credentials_aws_key_id: "my_key_id", credentials_aws_secret_key: "my_secret_key", encryption_master_key: "my_master_key", connection: "" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", enclosed_by: "", escaped_by: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0, purge: false, rejected_records: "" }, comment: "" }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,validation_mode:ReturnNRows(13)"#,
             err: "",
         },
 
@@ -65,7 +65,7 @@ async fn test_statement_copy() -> Result<()> {
         file_format = (type = csv field_delimiter = '|' skip_header = 1)
         VALIDATION_MODE = RETURN_13_ROWS
         ",
-            expect: r#"Copy into system.configs, ReadDataSourcePlan { source_info: S3StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StageS3Storage { bucket: "mybucket", path: "/data/files", credentials_aws_key_id: "my_key_id", credentials_aws_secret_key: "my_secret_key", encryption_master_key: "my_master_key" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0 }, comment: "" }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,files:["file1.csv", "file2.csv"] ,validation_mode:ReturnNRows(13)"#,
+            expect: r#"Copy into system.configs, ReadDataSourcePlan { source_info: S3StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StageS3Storage { bucket: "mybucket", path: "/data/files", credentials_aws_key_id: "my_key_id", credentials_aws_secret_key: "my_secret_key", encryption_master_key: "my_master_key", connection: "" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", enclosed_by: "", escaped_by: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0, purge: false, rejected_records: "" }, comment: "" }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,files:["file1.csv", "file2.csv"] ,validation_mode:ReturnNRows(13)"#,
             err: "",
         },
 
@@ -77,10 +77,25 @@ async fn test_statement_copy() -> Result<()> {
         encryption=(master_key = 'my_master_key')
         files = ('file1.csv', 'file2.csv')
         file_format = (type = csv field_delimiter = '|' skip_header = 1)
-        on_error = CONTINUE size_limit = 10
+        on_error = CONTINUE size_limit = 10 rejected_records = '_rejected_records'
+        VALIDATION_MODE = RETURN_13_ROWS
+        ",
+            expect: r#"Copy into system.configs, ReadDataSourcePlan { source_info: S3StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StageS3Storage { bucket: "mybucket", path: "/data/files", credentials_aws_key_id: "my_key_id", credentials_aws_secret_key: "my_secret_key", encryption_master_key: "my_master_key", connection: "" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", enclosed_by: "", escaped_by: "", compression: None }, copy_options: CopyOptions { on_error: Continue, size_limit: 10, purge: false, rejected_records: "/_rejected_records" }, comment: "" }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,files:["file1.csv", "file2.csv"] ,validation_mode:ReturnNRows(13)"#,
+            err: "",
+        },
+
+        TestCase {
+            name: "copy-external-purge-ok",
+            query: "copy into system.configs
+        from 's3://mybucket/data/files'
+        credentials=(aws_key_id='my_key_id' aws_secret_key='my_secret_key')
+        encryption=(master_key = 'my_master_key')
+        files = ('file1.csv', 'file2.csv')
+        file_format = (type = csv field_delimiter = '|' skip_header = 1)
+        purge = true
         VALIDATION_MODE = RETURN_13_ROWS
         ",
-            expect: r#"Copy into system.configs, ReadDataSourcePlan { source_info: S3StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StageS3Storage { bucket: "mybucket", path: "/data/files", credentials_aws_key_id: "my_key_id", credentials_aws_secret_key: "my_secret_key", encryption_master_key: "my_master_key" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", compression: None }, copy_options: CopyOptions { on_error: Continue, size_limit: 10 }, comment: "" }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,files:["file1.csv", "file2.csv"] ,validation_mode:ReturnNRows(13)"#,
+            expect: r#"Copy into system.configs, ReadDataSourcePlan { source_info: S3StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StageS3Storage { bucket: "mybucket", path: "/data/files", credentials_aws_key_id: "my_key_id", credentials_aws_secret_key: "my_secret_key", encryption_master_key: "my_master_key", connection: "" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", enclosed_by: "", escaped_by: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0, purge: true, rejected_records: "" }, comment: "" }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,files:["file1.csv", "file2.csv"] ,validation_mode:ReturnNRows(13)"#,
             err: "",
         },
 
@@ -99,6 +114,42 @@ async fn test_statement_copy() -> Result<()> {
             err: "Code: 1005, displayText = size_limit must be number, got: x0.",
         },
 
+        TestCase {
+            name: "copy-external-purge-error",
+            query: "copy into system.configs
+        from 's3://mybucket/data/files'
+        credentials=(aws_key_id='my_key_id' aws_secret_key='my_secret_key')
+        file_format = (type = csv field_delimiter = '|' skip_header = 1)
+        purge = yes
+        ",
+            expect: "",
+            err: "Code: 1005, displayText = purge must be TRUE or FALSE, got: yes.",
+        },
+
+        TestCase {
+            name: "copy-external-tsv-ok",
+            query: "copy into system.configs
+        from 's3://mybucket/data/files'
+        credentials=(aws_key_id='my_key_id' aws_secret_key='my_secret_key')
+        encryption=(master_key = 'my_master_key')
+        files = ('file1.csv', 'file2.csv')
+        file_format = (type = tsv enclosed_by = '\"' escaped_by = NONE)
+        ",
+            expect: r#"Copy into system.configs, ReadDataSourcePlan { source_info: S3StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StageS3Storage { bucket: "mybucket", path: "/data/files", credentials_aws_key_id: "my_key_id", credentials_aws_secret_key: "my_secret_key", encryption_master_key: "my_master_key", connection: "" }) }, file_format_options: FileFormatOptions { format: Tsv, skip_header: 0, field_delimiter: "", record_delimiter: "", enclosed_by: "\"", escaped_by: "NONE", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0, purge: false, rejected_records: "" }, comment: "" }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,files:["file1.csv", "file2.csv"] ,validation_mode:None"#,
+            err: "",
+        },
+
+        TestCase {
+            name: "copy-external-enclosed-by-error",
+            query: "copy into system.configs
+        from 's3://mybucket/data/files'
+        credentials=(aws_key_id='my_key_id' aws_secret_key='my_secret_key')
+        file_format = (type = tsv enclosed_by = '\"\"')
+        ",
+            expect: "",
+            err: r#"Code: 1005, displayText = enclosed_by must be one character or NONE, got: "\"\""."#,
+        },
+
         TestCase {
             name: "copy-external-validation-mode-error",
             query: "copy into system.configs
@@ -0,0 +1,138 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_base::tokio;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::col;
+use common_planners::lit;
+use common_planners::Extras;
+use databend_query::storages::delta::read_commit;
+use databend_query::storages::delta::DeltaLogReplay;
+use databend_query::storages::delta::DeltaPruner;
+use databend_query::storages::delta::DeltaSnapshot;
+use databend_query::storages::delta::LogFile;
+
+const COMMIT_0: &str = r#"{"commitInfo":{"timestamp":1655096143000,"operation":"WRITE"}}
+{"protocol":{"minReaderVersion":1,"minWriterVersion":2}}
+{"metaData":{"id":"6a2b3c1e-6b7e-4e52-8a5e-7b1e3e0f1c2d","format":{"provider":"parquet","options":{}},"schemaString":"{\"type\":\"struct\",\"fields\":[{\"name\":\"id\",\"type\":\"long\",\"nullable\":false,\"metadata\":{}},{\"name\":\"name\",\"type\":\"string\",\"nullable\":true,\"metadata\":{}},{\"name\":\"date\",\"type\":\"date\",\"nullable\":true,\"metadata\":{}}]}","partitionColumns":["date"],"configuration":{},"createdTime":1655096142000}}
+{"add":{"path":"date=2022-06-01/part-00000.snappy.parquet","partitionValues":{"date":"2022-06-01"},"size":1024,"modificationTime":1655096143000,"dataChange":true,"stats":"{\"numRecords\":10}"}}
+{"add":{"path":"date=2022-06-02/part-00000.snappy.parquet","partitionValues":{"date":"2022-06-02"},"size":2048,"modificationTime":1655096143000,"dataChange":true}}
+"#;
+
+const COMMIT_1: &str = r#"{"remove":{"path":"date=2022-06-01/part-00000.snappy.parquet","deletionTimestamp":1655096144000,"dataChange":true}}
+{"add":{"path":"date=2022-06-01/part-00001.snappy.parquet","partitionValues":{"date":"2022-06-01"},"size":512,"modificationTime":1655096144000,"dataChange":true,"stats":"{\"numRecords\":5}"}}
+{"add":{"path":"date=__HIVE_DEFAULT_PARTITION__/part-00000.snappy.parquet","partitionValues":{"date":null},"size":256,"modificationTime":1655096144000,"dataChange":true}}
+"#;
+
+fn replay(commits: &[&str]) -> Result<DeltaSnapshot> {
+    let mut replay = DeltaLogReplay::default();
+    for commit in commits {
+        for action in read_commit(commit.as_bytes().to_vec())? {
+            replay.apply(action)?;
+        }
+    }
+    replay.finish(commits.len() as i64 - 1)
+}
+
+#[test]
+fn test_delta_log_files() -> Result<()> {
+    assert_eq!(
+        LogFile::parse("00000000000000000010.json"),
+        Some(LogFile::Commit(10))
+    );
+    assert_eq!(
+        LogFile::parse("00000000000000000010.checkpoint.parquet"),
+        Some(LogFile::Checkpoint {
+            version: 10,
+            part: 1,
+            parts: 1,
+        })
+    );
+    assert_eq!(
+        LogFile::parse("00000000000000000010.checkpoint.0000000002.0000000003.parquet"),
+        Some(LogFile::Checkpoint {
+            version: 10,
+            part: 2,
+            parts: 3,
+        })
+    );
+    assert_eq!(LogFile::parse("_last_checkpoint"), None);
+    assert_eq!(LogFile::parse("00000000000000000010.crc"), None);
+    assert_eq!(LogFile::parse(".00000000000000000010.json.crc"), None);
+    Ok(())
+}
+
+#[test]
+fn test_delta_log_replay() -> Result<()> {
+    let snapshot = replay(&[COMMIT_0, COMMIT_1])?;
+    assert_eq!(snapshot.version, 1);
+
+    let schema = snapshot.metadata.data_schema()?;
+    let expected = DataSchemaRefExt::create(vec![
+        DataField::new("id", i64::to_data_type()),
+        DataField::new_nullable("name", Vu8::to_data_type()),
+        DataField::new_nullable("date", DateType::new_impl()),
+    ]);
+    assert_eq!(schema, expected);
+
+    // the removed file is not read
+    let paths = snapshot.files.keys().cloned().collect::<Vec<_>>();
+    assert_eq!(paths, vec![
+        "date=2022-06-01/part-00001.snappy.parquet".to_string(),
+        "date=2022-06-02/part-00000.snappy.parquet".to_string(),
+        "date=__HIVE_DEFAULT_PARTITION__/part-00000.snappy.parquet".to_string(),
+    ]);
+
+    let types = snapshot.metadata.partition_types()?;
+    let file = &snapshot.files["date=2022-06-01/part-00001.snappy.parquet"];
+    assert_eq!(file.num_records(), Some(5));
+    assert_eq!(
+        file.partition_values(&types)?,
+        BTreeMap::from([("date".to_string(), DataValue::Int64(19144))])
+    );
+    let file = &snapshot.files["date=__HIVE_DEFAULT_PARTITION__/part-00000.snappy.parquet"];
+    assert_eq!(file.num_records(), None);
+    assert_eq!(
+        file.partition_values(&types)?,
+        BTreeMap::from([("date".to_string(), DataValue::Null)])
+    );
+
+    // the log without the metadata
+    assert!(replay(&[COMMIT_1]).is_err());
+    // the protocol which is not supported
+    let commit = r#"{"protocol":{"minReaderVersion":3,"minWriterVersion":7}}"#;
+    assert!(replay(&[COMMIT_0, commit]).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delta_pruner() -> Result<()> {
+    let ctx = crate::tests::create_query_context().await?;
+    let snapshot = replay(&[COMMIT_0, COMMIT_1])?;
+    let schema = snapshot.metadata.data_schema()?;
+    let push_downs = Some(Extras {
+        filters: vec![col("date").gt(lit(19144i64))],
+        ..Extras::default()
+    });
+    let pruner = DeltaPruner::try_create(&ctx, schema, &snapshot.metadata, &push_downs)?;
+
+    let date = |value: DataValue| BTreeMap::from([("date".to_string(), value)]);
+    assert!(!pruner.keep_file(&date(DataValue::Int64(19144)))?);
+    assert!(pruner.keep_file(&date(DataValue::Int64(19145)))?);
+    assert!(pruner.keep_file(&date(DataValue::Null))?);
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+use common_base::tokio;
+use common_datablocks::pretty_format_blocks;
+use common_exception::Result;
+use databend_query::storages::fuse::cache::TableDiskCache;
+use futures::TryStreamExt;
+use tempfile::TempDir;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+#[test]
+fn test_table_disk_cache() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let root = tmp_dir.path().to_str().unwrap();
+    let cache = TableDiskCache::try_create(root, 10, "test_tenant", "test_cluster")?;
+
+    let key = TableDiskCache::block_chunk_key("1/2/_b/block.parquet", 4, 6);
+    assert_eq!(key, "block/1/2/_b/block.parquet/4_6");
+    assert_eq!(cache.get(&key), None);
+    cache.put(&key, b"abcdef");
+    assert_eq!(cache.get(&key), Some(b"abcdef".to_vec()));
+
+    // the least recently used chunk is evicted once the capacity is exceeded
+    let key2 = TableDiskCache::block_chunk_key("1/2/_b/block.parquet", 10, 4);
+    cache.put(&key2, b"ghij");
+    assert_eq!(cache.size(), 10);
+    let key3 = TableDiskCache::block_chunk_key("1/2/_b/block.parquet", 14, 2);
+    cache.put(&key3, b"kl");
+    assert!(!cache.contains_key(&key));
+    assert!(cache.contains_key(&key2));
+    assert!(cache.contains_key(&key3));
+
+    // too large to be cached
+    let key4 = TableDiskCache::block_chunk_key("1/2/_b/block.parquet", 16, 11);
+    cache.put(&key4, b"mnopqrstuvw");
+    assert!(!cache.contains_key(&key4));
+
+    // the files cached by the previous runs are kept
+    drop(cache);
+    let cache = TableDiskCache::try_create(root, 10, "test_tenant", "test_cluster")?;
+    assert_eq!(cache.get(&key3), Some(b"kl".to_vec()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_table_disk_cache_read_blocks() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let mut conf = crate::tests::ConfigBuilder::create().config();
+    conf.storage.storage_type = "fs".to_string();
+    conf.storage.fs.data_path = tmp_dir.path().join("data").to_str().unwrap().to_string();
+    conf.query.table_cache_enabled = true;
+    conf.query.table_disk_cache_root = tmp_dir.path().join("cache").to_str().unwrap().to_string();
+    let ctx = crate::tests::create_query_context_with_config(conf, None).await?;
+    let cache = ctx
+        .get_storage_cache_manager()
+        .get_block_disk_cache()
+        .unwrap();
+
+    execute_command(ctx.clone(), "create database db_cache").await?;
+    execute_command(ctx.clone(), "create table db_cache.t(a int, b int)").await?;
+    execute_command(ctx.clone(), "insert into db_cache.t values (1, 2), (3, 4)").await?;
+    assert_eq!(cache.size(), 0);
+
+    // the column chunks read by the first query are cached, and read by the second one
+    let mut results = vec![];
+    for _ in 0..2 {
+        let blocks = execute_query(ctx.clone(), "select a, b from db_cache.t order by a")
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        results.push(pretty_format_blocks(&blocks)?);
+        assert!(cache.size() > 0);
+    }
+    assert_eq!(results[0], results[1]);
+    Ok(())
+}
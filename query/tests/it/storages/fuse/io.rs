@@ -26,6 +26,7 @@ use databend_query::storages::fuse::io::TableMetaLocationGenerator;
 use databend_query::storages::fuse::meta::TableSnapshot;
 use databend_query::storages::fuse::meta::Versioned;
 use databend_query::storages::fuse::DEFAULT_BLOCK_PER_SEGMENT;
+use databend_query::storages::fuse::DEFAULT_STATS_STRING_PREFIX_LENGTH;
 use futures::StreamExt;
 use futures::TryStreamExt;
 use num::Integer;
@@ -54,6 +55,7 @@ async fn test_fuse_table_block_appender() {
     let block_stream = futures::stream::iter(vec![Ok(block)]);
 
     let locs = TableMetaLocationGenerator::with_prefix(".".to_owned());
+    let snapshot_id = Uuid::new_v4();
     let segments = BlockStreamWriter::write_block_stream(
         local_fs.clone(),
         Box::pin(block_stream),
@@ -61,6 +63,9 @@ async fn test_fuse_table_block_appender() {
         DEFAULT_BLOCK_PER_SEGMENT,
         0,
         locs.clone(),
+        snapshot_id,
+        None,
+        DEFAULT_STATS_STRING_PREFIX_LENGTH,
     )
     .await
     .collect::<Vec<_>>()
@@ -72,6 +77,11 @@ async fn test_fuse_table_block_appender() {
         "oops, unexpected result: {:?}",
         segments[0]
     );
+    // the blocks record the snapshot which they are written for
+    for block in &segments[0].as_ref().unwrap().blocks {
+        assert_eq!(block.created_by, Some(snapshot_id));
+        assert!(block.created_on.is_some());
+    }
 
     // multiple segments
     let number_of_blocks = 30;
@@ -88,6 +98,9 @@ async fn test_fuse_table_block_appender() {
         max_rows_per_block,
         max_blocks_per_segment,
         locs.clone(),
+        Uuid::new_v4(),
+        None,
+        DEFAULT_STATS_STRING_PREFIX_LENGTH,
     )
     .await
     .collect::<Vec<_>>()
@@ -108,6 +121,9 @@ async fn test_fuse_table_block_appender() {
         DEFAULT_BLOCK_PER_SEGMENT,
         0,
         locs,
+        Uuid::new_v4(),
+        None,
+        DEFAULT_STATS_STRING_PREFIX_LENGTH,
     )
     .await
     .collect::<Vec<_>>()
@@ -278,6 +294,9 @@ async fn test_block_stream_writer() -> common_exception::Result<()> {
             max_rows_per_block,
             max_blocks_per_segment,
             locs,
+            Uuid::new_v4(),
+            None,
+            DEFAULT_STATS_STRING_PREFIX_LENGTH,
         )
         .await;
         let segs = stream.try_collect::<Vec<_>>().await?;
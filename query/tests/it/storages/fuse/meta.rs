@@ -0,0 +1,215 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use databend_query::storages::fuse::io::TableMetaLocationGenerator;
+use databend_query::storages::fuse::meta::Encoding;
+use databend_query::storages::fuse::meta::IndexType;
+use databend_query::storages::fuse::meta::SnapshotOperation;
+use databend_query::storages::fuse::meta::Statistics;
+use databend_query::storages::fuse::meta::TableSnapshot;
+use databend_query::storages::fuse::meta::TableSnapshotHead;
+use databend_query::storages::fuse::meta::TableSnapshotLite;
+use databend_query::storages::fuse::meta::Versioned;
+use databend_query::storages::index::ColumnStatistics;
+use uuid::Uuid;
+
+fn sample_snapshot() -> TableSnapshot {
+    let schema = DataSchema::new(vec![DataField::new("a", i32::to_data_type())]);
+    let col_stats = HashMap::from([(0, ColumnStatistics {
+        min: DataValue::Int64(1),
+        max: DataValue::Int64(10),
+        null_count: 2,
+        in_memory_size: 40,
+        distinct_of_values: None,
+        truncated: false,
+    })]);
+    let summary = Statistics {
+        row_count: 10,
+        block_count: 1,
+        uncompressed_byte_size: 40,
+        compressed_byte_size: 20,
+        index_byte_size: 0,
+        index_byte_sizes: Default::default(),
+        col_stats,
+    };
+    TableSnapshot::new(Uuid::new_v4(), None, schema, summary, vec![])
+}
+
+#[test]
+fn test_snapshot_origin_and_operation() -> Result<()> {
+    let snapshot = sample_snapshot();
+    assert_eq!(snapshot.format_version(), TableSnapshot::VERSION);
+    assert!(snapshot.written_by.is_none());
+    assert!(snapshot.txn_id.is_none());
+
+    let snapshot = snapshot.with_origin("test-version", "test-query-id");
+    assert_eq!(snapshot.written_by.as_deref(), Some("test-version"));
+    assert_eq!(snapshot.txn_id.as_deref(), Some("test-query-id"));
+
+    assert!(snapshot.operation.is_none());
+    let snapshot = snapshot.with_operation(SnapshotOperation::Delete, Some("'u'@'%'".to_owned()));
+    assert_eq!(snapshot.operation, Some(SnapshotOperation::Delete));
+    assert_eq!(snapshot.operation.unwrap().to_string(), "DELETE");
+    assert_eq!(snapshot.user.as_deref(), Some("'u'@'%'"));
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_serde_compat() -> Result<()> {
+    let snapshot = sample_snapshot()
+        .with_origin("test-version", "test-query-id")
+        .with_operation(SnapshotOperation::Insert, Some("root".to_owned()));
+    let mut value = serde_json::to_value(&snapshot)?;
+    let obj = value.as_object_mut().unwrap();
+
+    // fields introduced by later versions should be ignored
+    obj.insert(
+        "some_future_field".to_owned(),
+        serde_json::Value::Bool(true),
+    );
+    let s: TableSnapshot = serde_json::from_value(value.clone())?;
+    assert_eq!(s.snapshot_id, snapshot.snapshot_id);
+    assert_eq!(s.written_by, snapshot.written_by);
+    assert_eq!(s.txn_id, snapshot.txn_id);
+    assert_eq!(s.operation, snapshot.operation);
+    assert_eq!(s.user, snapshot.user);
+
+    // fields introduced by this version are optional
+    let obj = value.as_object_mut().unwrap();
+    obj.remove("written_by");
+    obj.remove("txn_id");
+    obj.remove("operation");
+    obj.remove("user");
+    let s: TableSnapshot = serde_json::from_value(value)?;
+    assert_eq!(s.snapshot_id, snapshot.snapshot_id);
+    assert!(s.written_by.is_none());
+    assert!(s.txn_id.is_none());
+    assert!(s.operation.is_none());
+    assert!(s.user.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_summary_index_sizes() -> Result<()> {
+    let schema = DataSchema::new(vec![DataField::new("a", i32::to_data_type())]);
+    let mut summary = sample_snapshot().summary;
+    summary.add_index_size(IndexType::VirtualColumn, 10);
+    summary.add_index_size(IndexType::VirtualColumn, 5);
+    assert_eq!(summary.index_byte_size, 15);
+    assert_eq!(summary.index_size_of(IndexType::VirtualColumn), 15);
+
+    let value = serde_json::to_value(&summary)?;
+    assert_eq!(
+        value["index_byte_sizes"],
+        serde_json::json!({"virtual_column": 15})
+    );
+
+    // the legacy summaries only kept the total, which is of the virtual columns
+    let mut value = value;
+    value.as_object_mut().unwrap().remove("index_byte_sizes");
+    let legacy: Statistics = serde_json::from_value(value)?;
+    assert!(legacy.index_byte_sizes.is_empty());
+    assert_eq!(legacy.index_size_of(IndexType::VirtualColumn), 15);
+
+    let merged = legacy.merge(&summary, &schema)?;
+    assert_eq!(merged.index_byte_size, 30);
+    assert_eq!(merged.index_size_of(IndexType::VirtualColumn), 30);
+    assert_eq!(merged.index_byte_sizes.values().sum::<u64>(), 30);
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_lite_of_head() -> Result<()> {
+    let prev_id = Uuid::new_v4();
+    let mut snapshot = sample_snapshot();
+    snapshot.prev_snapshot_id = Some((prev_id, 1));
+    snapshot.timestamp = Some(Utc::now());
+    let head: TableSnapshotHead = serde_json::from_slice(&serde_json::to_vec(&snapshot)?)?;
+    assert_eq!(
+        TableSnapshotLite::from((head, TableSnapshot::VERSION)),
+        TableSnapshotLite::from((&snapshot, TableSnapshot::VERSION))
+    );
+
+    // the previous snapshots of version 0 are recorded without their versions
+    let legacy = serde_json::json!({
+        "snapshot_id": snapshot.snapshot_id,
+        "prev_snapshot_id": prev_id,
+        "schema": {},
+        "summary": {},
+        "segments": ["_sg/1", "_sg/2"],
+    });
+    let head: TableSnapshotHead = serde_json::from_value(legacy)?;
+    let lite = TableSnapshotLite::from((head, 0));
+    assert_eq!(lite.format_version, 0);
+    assert_eq!(lite.snapshot_id, snapshot.snapshot_id);
+    assert_eq!(lite.prev_snapshot_id, Some((prev_id, 0)));
+    assert!(lite.timestamp.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_encodings() -> Result<()> {
+    let mut snapshot = sample_snapshot().with_operation(SnapshotOperation::Insert, None);
+    snapshot.prev_snapshot_id = Some((Uuid::new_v4(), 1));
+    snapshot.timestamp = Some(Utc::now());
+
+    // the snapshots in JSON have no header byte, as the ones written before the encodings
+    let json = Encoding::Json.encode(&snapshot)?;
+    assert_eq!(json, serde_json::to_vec(&snapshot)?);
+    let msgpack = Encoding::MessagePack.encode(&snapshot)?;
+    assert_ne!(msgpack[0], json[0]);
+
+    for bytes in [&json, &msgpack] {
+        for decoded in [
+            Encoding::decode::<TableSnapshot>(bytes)?,
+            Encoding::decode_from::<TableSnapshot, _>(bytes.as_slice())?,
+        ] {
+            assert_eq!(decoded.snapshot_id, snapshot.snapshot_id);
+            assert_eq!(decoded.prev_snapshot_id, snapshot.prev_snapshot_id);
+            assert_eq!(decoded.timestamp, snapshot.timestamp);
+            assert_eq!(decoded.schema, snapshot.schema);
+            assert_eq!(decoded.summary.row_count, snapshot.summary.row_count);
+            assert_eq!(decoded.summary.col_stats[&0].max, DataValue::Int64(10));
+            assert_eq!(decoded.operation, snapshot.operation);
+        }
+
+        // the lites are decoded in the same way
+        let head: TableSnapshotHead = Encoding::decode(bytes)?;
+        assert_eq!(
+            TableSnapshotLite::from((head, TableSnapshot::VERSION)),
+            TableSnapshotLite::from((&snapshot, TableSnapshot::VERSION))
+        );
+    }
+
+    assert_eq!("msgpack".parse::<Encoding>()?, Encoding::MessagePack);
+    assert!("bincode".parse::<Encoding>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_version_of_location() -> Result<()> {
+    let locs = TableMetaLocationGenerator::with_prefix("pref".to_owned());
+    let id = Uuid::new_v4();
+    for ver in 0..=TableSnapshot::VERSION {
+        let loc = locs.snapshot_location_from_uuid(&id, ver)?;
+        assert_eq!(TableMetaLocationGenerator::snaphost_version(&loc), ver);
+    }
+    Ok(())
+}
@@ -12,7 +12,9 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod cache;
 mod io;
+mod meta;
 mod operations;
 mod pruning;
 mod statistics;
@@ -0,0 +1,129 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_base::tokio;
+use common_datablocks::pretty_format_blocks;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+#[tokio::test]
+async fn test_fuse_aggregating_index() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!("create table {}.t(k int, v int)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("insert into {}.t values (1, 1), (1, 2), (2, 3)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!(
+        "create aggregating index idx as select k, sum(v), count(*) from {}.t group by k",
+        db
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the index is refreshed incrementally by the commits
+    let qry = format!("insert into {}.t values (2, 4), (3, 5)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    let select = format!(
+        "select k, sum(v), count(*) from {}.t where k > 1 group by k order by k",
+        db
+    );
+    let explain = execute_query(ctx.clone(), format!("explain {}", select).as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert!(pretty_format_blocks(&explain)?.contains("aggregating index idx"));
+    let expected = vec![
+        "+---+--------+----------+",
+        "| k | sum(v) | count(*) |",
+        "+---+--------+----------+",
+        "| 2 | 7      | 2        |",
+        "| 3 | 5      | 1        |",
+        "+---+--------+----------+",
+    ];
+    expects_ok(
+        "indexed",
+        execute_query(ctx.clone(), select.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // deletions rebuild the index
+    let qry = format!("delete from {}.t where v = 4", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let expected = vec![
+        "+---+--------+----------+",
+        "| k | sum(v) | count(*) |",
+        "+---+--------+----------+",
+        "| 2 | 3      | 1        |",
+        "| 3 | 5      | 1        |",
+        "+---+--------+----------+",
+    ];
+    expects_ok(
+        "rebuilt",
+        execute_query(ctx.clone(), select.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // aggregates which are not kept by the index are read from the table
+    let qry = format!("select k, avg(v) from {}.t group by k order by k", db);
+    let expected = vec![
+        "+---+--------+",
+        "| k | avg(v) |",
+        "+---+--------+",
+        "| 1 | 1.5    |",
+        "| 2 | 3      |",
+        "| 3 | 5      |",
+        "+---+--------+",
+    ];
+    expects_ok(
+        "not_indexed",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    let qry = format!(
+        "create aggregating index idx as select k, min(v) from {}.t group by k",
+        db
+    );
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err(
+        "exists",
+        ErrorCode::aggregating_index_already_exists_code(),
+        res,
+    );
+    let qry = format!(
+        "create aggregating index if not exists idx as select k, min(v) from {}.t group by k",
+        db
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // aggregations that could not be merged
+    let qry = format!(
+        "create aggregating index idx2 as select k, avg(v) from {}.t group by k",
+        db
+    );
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("unsupported", ErrorCode::syntax_exception_code(), res);
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+use common_base::tokio;
+use common_datablocks::pretty_format_blocks;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+#[tokio::test]
+async fn test_fuse_alter_column() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!("create table {}.t(a int, b int)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("insert into {}.t values (1, 10), (2, 20)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the rows already in the table get the default values of the added columns
+    for column in ["c int default 2 + 3", "d varchar null"] {
+        let qry = format!("alter table {}.t add column {}", db, column);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+    let qry = format!("insert into {}.t values (3, 30, 7, 'x')", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    let expected = vec![
+        "+---+----+---+------+",
+        "| a | b  | c | d    |",
+        "+---+----+---+------+",
+        "| 1 | 10 | 5 | NULL |",
+        "| 2 | 20 | 5 | NULL |",
+        "| 3 | 30 | 7 | x    |",
+        "+---+----+---+------+",
+    ];
+    let select = format!("select * from {}.t order by a", db);
+    expects_ok(
+        "added",
+        execute_query(ctx.clone(), select.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // the blocks written before the column is added are pruned by its default value
+    let select = format!("select a from {}.t where c = 7", db);
+    let explain = execute_query(ctx.clone(), format!("explain {}", select).as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert!(pretty_format_blocks(&explain)?.contains("Partitions Scanned:1, Partitions Total:2"));
+    let expected = vec!["+---+", "| a |", "+---+", "| 3 |", "+---+"];
+    expects_ok(
+        "pruned",
+        execute_query(ctx.clone(), select.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // the values of the dropped column do not come back with a new column of the same name
+    for action in ["drop column b", "add column b int", "rename column c to e"] {
+        let qry = format!("alter table {}.t {}", db, action);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+    let expected = vec![
+        "+---+------+---+---+",
+        "| a | d    | b | e |",
+        "+---+------+---+---+",
+        "| 1 | NULL | 0 | 5 |",
+        "| 2 | NULL | 0 | 5 |",
+        "| 3 | x    | 0 | 7 |",
+        "+---+------+---+---+",
+    ];
+    let select = format!("select a, d, b, e from {}.t order by a", db);
+    expects_ok(
+        "dropped",
+        execute_query(ctx.clone(), select.as_str()).await,
+        expected.clone(),
+    )
+    .await?;
+
+    // the blocks are rewritten by the new schema
+    let qry = format!("optimize table {}.t compact", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    expects_ok(
+        "compacted",
+        execute_query(ctx.clone(), select.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    let qry = format!("alter table {}.t add column a int", db);
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("exists", ErrorCode::column_already_exists_code(), res);
+
+    let qry = format!("alter table {}.t rename column a to e", db);
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err(
+        "rename_exists",
+        ErrorCode::column_already_exists_code(),
+        res,
+    );
+
+    let qry = format!("create table {}.t2(a int, b int) cluster by (a)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("alter table {}.t2 drop column a", db);
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("cluster_key", ErrorCode::bad_arguments_code(), res);
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_base::tokio;
+use common_datavalues::DataValue;
+use common_exception::Result;
+
+use crate::storages::fuse::table_test_fixture::append_sample_data;
+use crate::storages::fuse::table_test_fixture::append_sample_data_overwrite;
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_analyze() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    let analyze = format!(
+        "analyze table {}.{}",
+        fixture.default_db_name(),
+        fixture.default_table_name()
+    );
+
+    // 3 blocks, 3 rows per block, of values 1, 2 and 3 respectively
+    append_sample_data(3, &fixture).await?;
+    let table = fixture.latest_default_table().await?;
+    assert!(table.column_statistics(ctx.clone()).await?.is_none());
+
+    execute_command(ctx.clone(), analyze.as_str()).await?;
+    let table = fixture.latest_default_table().await?;
+    let statistics = table.column_statistics(ctx.clone()).await?.unwrap();
+    assert_eq!(statistics.row_count, 9);
+    assert_eq!(statistics.sampled_row_count, 9);
+
+    let histogram = statistics.column_histogram(0).unwrap();
+    assert_eq!(histogram.null_count, 0);
+    assert_eq!(histogram.distinct_count, 3);
+    assert_eq!(histogram.most_common_values.len(), 3);
+    assert_eq!(histogram.buckets.len(), 3);
+    assert_eq!(histogram.buckets.iter().map(|b| b.count).sum::<u64>(), 9);
+
+    let eq = statistics.selectivity_of_eq(0, &DataValue::Int64(2));
+    assert_eq!(eq, Some(3.0 / 9.0));
+    let eq = statistics.selectivity_of_eq(0, &DataValue::Int64(4));
+    assert_eq!(eq, Some(0.0));
+    let range = statistics.selectivity_of_range(0, Some(&DataValue::Int64(2)), None);
+    assert_eq!(range, Some(6.0 / 9.0));
+    assert_eq!(statistics.selectivity_of_null(0), Some(0.0));
+    assert_eq!(statistics.selectivity_of_eq(1, &DataValue::Int64(2)), None);
+
+    // statistics are kept by appends
+    append_sample_data(1, &fixture).await?;
+    let table = fixture.latest_default_table().await?;
+    let statistics_after_append = table.column_statistics(ctx.clone()).await?.unwrap();
+    assert_eq!(statistics_after_append.snapshot_id, statistics.snapshot_id);
+
+    // but not by overwrites
+    append_sample_data_overwrite(1, true, &fixture).await?;
+    let table = fixture.latest_default_table().await?;
+    assert!(table.column_statistics(ctx.clone()).await?.is_none());
+
+    Ok(())
+}
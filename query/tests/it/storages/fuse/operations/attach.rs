@@ -0,0 +1,187 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_base::tokio;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::storages::fuse::FuseTable;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+#[tokio::test]
+async fn test_fuse_attach_table() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(2, &fixture).await?;
+
+    let qry = format!(
+        "select snapshot_location from fuse_history('{}', '{}') limit 1",
+        db, tbl
+    );
+    let blocks = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let location = String::from_utf8(blocks[0].column(0).get(0).as_string()?)?;
+
+    let attach = format!("attach table {}.attached from '{}'", db, location);
+    execute_command(ctx.clone(), attach.as_str()).await?;
+
+    // the data of the snapshot is visible
+    let qry = format!("select count(*) from {}.attached", db);
+    expects_ok(
+        "count_attached",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        vec![
+            "+----------+",
+            "| count(*) |",
+            "+----------+",
+            "| 6        |",
+            "+----------+",
+        ],
+    )
+    .await?;
+
+    // but the table could not be modified
+    for qry in [
+        format!("insert into {}.attached values(1)", db),
+        format!("truncate table {}.attached", db),
+        format!("optimize table {}.attached all", db),
+    ] {
+        expects_err(
+            qry.as_str(),
+            ErrorCode::read_only_table_code(),
+            execute_command(ctx.clone(), qry.as_str()).await,
+        );
+    }
+
+    // the attached snapshot must exist
+    let qry = format!("attach table {}.missing from '{}_missing'", db, location);
+    expects_err(
+        "missing_snapshot",
+        ErrorCode::storage_not_found_code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_external_location_table() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(2, &fixture).await?;
+
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let prefix = fuse_table.meta_location_generator().prefix().to_owned();
+
+    let create = format!("create table {}.ext 'fuse:///{}' READ_ONLY", db, prefix);
+    execute_command(ctx.clone(), create.as_str()).await?;
+
+    let qry = format!("select count(*) from {}.ext", db);
+    expects_ok(
+        "count_external",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        vec![
+            "+----------+",
+            "| count(*) |",
+            "+----------+",
+            "| 6        |",
+            "+----------+",
+        ],
+    )
+    .await?;
+
+    // the latest snapshot is read by the following queries
+    append_sample_data(1, &fixture).await?;
+    let ctx = ctx.get_current_session().create_query_context().await?;
+    expects_ok(
+        "count_external_refreshed",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        vec![
+            "+----------+",
+            "| count(*) |",
+            "+----------+",
+            "| 9        |",
+            "+----------+",
+        ],
+    )
+    .await?;
+
+    // the table could not be modified
+    for qry in [
+        format!("insert into {}.ext values(1)", db),
+        format!("truncate table {}.ext", db),
+        format!("optimize table {}.ext all", db),
+    ] {
+        expects_err(
+            qry.as_str(),
+            ErrorCode::read_only_table_code(),
+            execute_command(ctx.clone(), qry.as_str()).await,
+        );
+    }
+
+    // the bucket must be the one of the storage
+    let qry = format!(
+        "create table {}.other 'fuse://other/{}' READ_ONLY",
+        db, prefix
+    );
+    expects_err(
+        "other_bucket",
+        ErrorCode::bad_arguments_code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    // there must be fuse table under the prefix
+    let qry = format!(
+        "create table {}.missing 'fuse:///{}_missing' READ_ONLY",
+        db, prefix
+    );
+    expects_err(
+        "missing_table",
+        ErrorCode::storage_not_found_code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    // dropping the table keeps the data of the external table
+    execute_command(ctx.clone(), format!("drop table {}.ext", db).as_str()).await?;
+    let qry = format!(
+        "select count(*) from {}.{}",
+        db,
+        fixture.default_table_name()
+    );
+    expects_ok(
+        "count_source",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        vec![
+            "+----------+",
+            "| count(*) |",
+            "+----------+",
+            "| 9        |",
+            "+----------+",
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
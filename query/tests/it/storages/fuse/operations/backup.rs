@@ -0,0 +1,98 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::catalogs::Catalog;
+
+use crate::storages::fuse::table_test_fixture::append_sample_data;
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_err;
+use crate::storages::fuse::table_test_fixture::expects_ok;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_backup_and_restore_database() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // 3 blocks, 3 rows per block
+    append_sample_data(3, &fixture).await?;
+    let qry = format!("delete from {}.{} where id = 1", db, tbl);
+    execute_command(ctx.clone(), &qry).await?;
+    let qry = format!("create view {}.v as select 1 as c", db);
+    execute_command(ctx.clone(), &qry).await?;
+    execute_command(ctx.clone(), "create stage backup_stage").await?;
+
+    let qry = format!("backup database {} to '@backup_stage/b1'", db);
+    execute_command(ctx.clone(), &qry).await?;
+
+    // a backup is never overwritten
+    expects_err(
+        "backup exists",
+        ErrorCode::backup_already_exists_code(),
+        execute_command(ctx.clone(), &qry).await,
+    );
+
+    // the data written after the backup are not restored
+    append_sample_data(1, &fixture).await?;
+
+    let qry = "restore database db_restored from '@backup_stage/b1'";
+    execute_command(ctx.clone(), qry).await?;
+
+    let qry = format!("select count(*) as c from db_restored.{}", tbl);
+    let expected = vec!["+---+", "| c |", "+---+", "| 6 |", "+---+"];
+    expects_ok("restored", execute_query(ctx.clone(), &qry).await, expected).await?;
+
+    let expected = vec!["+---+", "| c |", "+---+", "| 1 |", "+---+"];
+    let qry = "select c from db_restored.v";
+    expects_ok("view", execute_query(ctx.clone(), qry).await, expected).await?;
+
+    // the restored table is a table of its own
+    let tenant = fixture.default_tenant();
+    let catalog = ctx.get_catalog();
+    let restored = catalog.get_table(&tenant, "db_restored", &tbl).await?;
+    let origin = fixture.latest_default_table().await?;
+    assert_ne!(restored.get_id(), origin.get_id());
+    assert!(restored.get_table_info().meta.usage.number_of_blocks > 0);
+    let qry = format!("truncate table db_restored.{}", tbl);
+    execute_command(ctx.clone(), &qry).await?;
+    let qry = format!("select count(*) as c from {}.{}", db, tbl);
+    let expected = vec!["+---+", "| c |", "+---+", "| 9 |", "+---+"];
+    expects_ok("origin", execute_query(ctx.clone(), &qry).await, expected).await?;
+
+    // the database to restore as must not exist
+    expects_err(
+        "database exists",
+        ErrorCode::database_already_exists_code(),
+        execute_command(
+            ctx.clone(),
+            "restore database db_restored from '@backup_stage/b1'",
+        )
+        .await,
+    );
+
+    expects_err(
+        "no backup",
+        ErrorCode::unknown_backup_code(),
+        execute_command(ctx.clone(), "restore database db2 from '@backup_stage/b2'").await,
+    );
+
+    Ok(())
+}
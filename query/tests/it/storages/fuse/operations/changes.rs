@@ -0,0 +1,291 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UserInfo;
+use common_meta_types::UserPrivilegeType;
+use databend_query::storages::fuse::FuseTable;
+use futures::TryStreamExt;
+use uuid::Uuid;
+
+use crate::storages::fuse::table_test_fixture::append_sample_data;
+use crate::storages::fuse::table_test_fixture::append_sample_data_overwrite;
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_err;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_changes_since() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // 1st snapshot, with 1 block
+    append_sample_data(1, &fixture).await?;
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let offset = fuse_table
+        .snapshot_loc()
+        .as_ref()
+        .and_then(|loc| loc.rsplit('/').next())
+        .and_then(|name| name.split('_').next())
+        .unwrap()
+        .to_owned();
+
+    let changes = fuse_table.changes_since(ctx.as_ref(), None).await?;
+    assert_eq!(changes.inserted_blocks.len(), 1);
+    assert!(changes.deleted_blocks.is_empty());
+
+    let changes = fuse_table
+        .changes_since(ctx.as_ref(), Some(&offset))
+        .await?;
+    assert!(changes.inserted_blocks.is_empty());
+    assert!(changes.deleted_blocks.is_empty());
+
+    // 2nd snapshot, 2 more blocks
+    append_sample_data(2, &fixture).await?;
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let changes = fuse_table
+        .changes_since(ctx.as_ref(), Some(&offset))
+        .await?;
+    assert_eq!(changes.inserted_blocks.len(), 2);
+    assert!(changes.deleted_blocks.is_empty());
+
+    // 3rd snapshot, overwrites all the blocks with a new one: the blocks inserted by the
+    // 2nd snapshot are cancelled out, and the block of the offset snapshot is deleted
+    append_sample_data_overwrite(1, true, &fixture).await?;
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let changes = fuse_table
+        .changes_since(ctx.as_ref(), Some(&offset))
+        .await?;
+    assert_eq!(changes.inserted_blocks.len(), 1);
+    assert_eq!(changes.deleted_blocks.len(), 1);
+
+    let unknown = Uuid::new_v4().to_simple().to_string();
+    expects_err(
+        "changes_since_unknown_snapshot",
+        ErrorCode::table_historical_data_not_found_code(),
+        fuse_table.changes_since(ctx.as_ref(), Some(&unknown)).await,
+    );
+
+    expects_err(
+        "changes_since_invalid_snapshot_id",
+        ErrorCode::bad_arguments_code(),
+        fuse_table
+            .changes_since(ctx.as_ref(), Some("not_a_snapshot_id"))
+            .await,
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_stream() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+
+    // rows inserted before the stream is created are not visible
+    append_sample_data(1, &fixture).await?;
+    let qry = format!("create stream {}.s1 on table {}.{}", db, db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // 2 blocks, 3 rows per block
+    append_sample_data(2, &fixture).await?;
+    let qry = format!("select * from {}.s1", db);
+    let stream = execute_query(ctx.clone(), qry.as_str()).await?;
+    let blocks = stream.try_collect::<Vec<DataBlock>>().await?;
+    let rows: usize = blocks.iter().map(|b| b.num_rows()).sum();
+    assert_eq!(rows, 6);
+
+    // only fuse tables could be tracked
+    let qry = format!("create table {}.t_memory(a int) engine = Memory", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("create stream {}.s2 on table {}.t_memory", db, db);
+    expects_err(
+        "create_stream_on_non_fuse_table",
+        ErrorCode::logical_error_code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_stream_rewritten_blocks() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+
+    let qry = format!("create stream {}.s on table {}.{}", db, db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    append_sample_data(2, &fixture).await?;
+    let qry = format!("select * from {}.s", db);
+    let stream = execute_query(ctx.clone(), qry.as_str()).await?;
+    let blocks = stream.try_collect::<Vec<DataBlock>>().await?;
+    let rows: usize = blocks.iter().map(|b| b.num_rows()).sum();
+    assert_eq!(rows, 6);
+
+    // consumes the stream, by moving its offset to the latest snapshot
+    let qry = format!("drop stream {}.s", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("create stream {}.s on table {}.{}", db, db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the 2 blocks are merged into a new one, of which the rows are not new
+    let qry = format!("optimize table {}.{} compact", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let changes = fuse_table.changes_since(ctx.as_ref(), None).await?;
+    assert_eq!(changes.inserted_blocks.len(), 1);
+
+    let qry = format!("select * from {}.s", db);
+    let stream = execute_query(ctx.clone(), qry.as_str()).await?;
+    let blocks = stream.try_collect::<Vec<DataBlock>>().await?;
+    let rows: usize = blocks.iter().map(|b| b.num_rows()).sum();
+    assert_eq!(rows, 0);
+
+    // only the rows really inserted are returned after the compaction
+    append_sample_data(1, &fixture).await?;
+    let stream = execute_query(ctx.clone(), qry.as_str()).await?;
+    let blocks = stream.try_collect::<Vec<DataBlock>>().await?;
+    let rows: usize = blocks.iter().map(|b| b.num_rows()).sum();
+    assert_eq!(rows, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_stream_deleted_rows() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    let db = fixture.default_db_name();
+    fixture.create_default_table().await?;
+
+    let qry = format!("create table {}.t(a int, b int)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("insert into {}.t values(1, 1), (2, 2), (3, 3)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("create stream {}.s on table {}.t", db, db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the row is deleted by the deletion vector of the block
+    let qry = format!("delete from {}.t where a = 2", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("insert into {}.t values(4, 4)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    let qry = format!("select * from {}.s", db);
+    let blocks = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    let expected = vec![
+        "+---+---+--------------+",
+        "| a | b | _change_type |",
+        "+---+---+--------------+",
+        "| 2 | 2 | DELETE       |",
+        "| 4 | 4 | INSERT       |",
+        "+---+---+--------------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, blocks.as_slice());
+
+    // the column of the change type could not be shadowed
+    let qry = format!("create table {}.t1(a int, _change_type int)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("create stream {}.s1 on table {}.t1", db, db);
+    expects_err(
+        "create_stream_on_table_with_change_type",
+        ErrorCode::bad_arguments_code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_stream_privileges() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    let db = fixture.default_db_name();
+
+    let qry = format!("create table {}.t(a int)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("create stream {}.s on table {}.t", db, db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("insert into {}.t values(1)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the user could read the stream, but not the table it is created on
+    let tenant = ctx.get_tenant();
+    let user_mgr = ctx.get_user_manager();
+    let mut user_info = UserInfo::new_no_auth("stream_reader", "%");
+    user_info.grants.grant_privileges(
+        &GrantObject::Database(db.clone()),
+        vec![UserPrivilegeType::Create].into(),
+    );
+    user_info.grants.grant_privileges(
+        &GrantObject::Table(db.clone(), "s".to_owned()),
+        vec![UserPrivilegeType::Select].into(),
+    );
+    user_mgr.add_user(&tenant, user_info.clone(), false).await?;
+    ctx.get_current_session()
+        .set_current_user(user_info.clone());
+
+    let select = format!("select * from {}.s", db);
+    expects_err(
+        "read_stream_without_select_on_table",
+        ErrorCode::permission_denied_code(),
+        execute_query(ctx.clone(), select.as_str()).await,
+    );
+    let create = format!("create stream {}.s1 on table {}.t", db, db);
+    expects_err(
+        "create_stream_without_select_on_table",
+        ErrorCode::permission_denied_code(),
+        execute_command(ctx.clone(), create.as_str()).await,
+    );
+
+    user_mgr
+        .grant_privileges_to_user(
+            &tenant,
+            user_info.identity(),
+            GrantObject::Table(db.clone(), "t".to_owned()),
+            vec![UserPrivilegeType::Select].into(),
+        )
+        .await?;
+    let user_info = user_mgr.get_user(&tenant, user_info.identity()).await?;
+    ctx.get_current_session().set_current_user(user_info);
+
+    let blocks = execute_query(ctx.clone(), select.as_str())
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    assert_eq!(blocks.iter().map(|b| b.num_rows()).sum::<usize>(), 1);
+    execute_command(ctx.clone(), create.as_str()).await?;
+
+    Ok(())
+}
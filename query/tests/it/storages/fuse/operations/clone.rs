@@ -0,0 +1,178 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UserInfo;
+use common_meta_types::UserPrivilegeType;
+use databend_query::sessions::QueryContext;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+// the filter makes the blocks be read, instead of counting by the statistics
+async fn expects_count(ctx: Arc<QueryContext>, table: &str, count: u64) -> Result<()> {
+    let qry = format!("select count(*) from {} where id > 0", table);
+    let count = format!("| {: <8} |", count);
+    expects_ok(table, execute_query(ctx, qry.as_str()).await, vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        count.as_str(),
+        "+----------+",
+    ])
+    .await
+}
+
+#[tokio::test]
+async fn test_fuse_clone_table() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = format!("{}.{}", db, fixture.default_table_name());
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(2, &fixture).await?;
+
+    let clone1 = format!("{}.clone1", db);
+    let clone2 = format!("{}.clone2", db);
+    for clone in [&clone1, &clone2] {
+        let qry = format!("create table {} clone {}", clone, tbl);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+        expects_count(ctx.clone(), clone, 6).await?;
+    }
+
+    // the clones and the source table are mutated independently
+    let qry = format!("insert into {} values(1)", clone1);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    expects_count(ctx.clone(), &clone1, 7).await?;
+    expects_count(ctx.clone(), &tbl, 6).await?;
+
+    // purging a clone does not remove the files of the source table
+    let qry = format!("truncate table {} purge", clone2);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    expects_count(ctx.clone(), &clone2, 0).await?;
+    expects_count(ctx.clone(), &tbl, 6).await?;
+
+    // nor does purging the source table remove the files still referenced by the clones
+    let qry = format!("insert overwrite {} values(1)", tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("optimize table {} purge", tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    expects_count(ctx.clone(), &tbl, 1).await?;
+    expects_count(ctx.clone(), &clone1, 7).await?;
+
+    let qry = format!("select * from fuse_verify('{}', 'clone1')", db);
+    expects_ok(
+        "verify_clone",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        vec!["++", "++"],
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_clone_table_at_snapshot() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(1, &fixture).await?;
+    append_sample_data(1, &fixture).await?;
+
+    // the earliest snapshot, of which the table has 3 rows
+    let qry = format!(
+        "select snapshot_id from fuse_history('{}', '{}') where row_count = 3",
+        db, tbl
+    );
+    let blocks = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let snapshot_id = String::from_utf8(blocks[0].column(0).get(0).as_string()?)?;
+
+    let qry = format!(
+        "create table {}.cloned clone {}.{} at (snapshot => '{}')",
+        db, db, tbl, snapshot_id
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    expects_count(ctx.clone(), &format!("{}.cloned", db), 3).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_clone_table_privileges() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!("create table {}.t(id int)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("insert into {}.t values(1), (2)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the tables with row access policies are not cloned
+    let qry = "create row access policy p as (a int) returns boolean -> a > 1";
+    execute_command(ctx.clone(), qry).await?;
+    let qry = format!("alter table {}.t add row access policy p on (id)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let clone = format!("create table {}.cloned clone {}.t", db, db);
+    expects_err(
+        "clone_table_with_row_access_policy",
+        ErrorCode::illegal_row_access_policy_code(),
+        execute_command(ctx.clone(), clone.as_str()).await,
+    );
+    let qry = format!("alter table {}.t drop row access policy p", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the clone has all the rows of the source table, which the user could not read
+    let tenant = ctx.get_tenant();
+    let user_mgr = ctx.get_user_manager();
+    let mut user_info = UserInfo::new_no_auth("cloner", "%");
+    user_info.grants.grant_privileges(
+        &GrantObject::Database(db.clone()),
+        vec![UserPrivilegeType::Create].into(),
+    );
+    user_mgr.add_user(&tenant, user_info.clone(), false).await?;
+    ctx.get_current_session()
+        .set_current_user(user_info.clone());
+    expects_err(
+        "clone_table_without_select",
+        ErrorCode::permission_denied_code(),
+        execute_command(ctx.clone(), clone.as_str()).await,
+    );
+
+    user_mgr
+        .grant_privileges_to_user(
+            &tenant,
+            user_info.identity(),
+            GrantObject::Table(db.clone(), "t".to_owned()),
+            vec![UserPrivilegeType::Select].into(),
+        )
+        .await?;
+    let user_info = user_mgr.get_user(&tenant, user_info.identity()).await?;
+    ctx.get_current_session().set_current_user(user_info);
+    execute_command(ctx.clone(), clone.as_str()).await?;
+
+    Ok(())
+}
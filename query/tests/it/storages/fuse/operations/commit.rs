@@ -14,10 +14,13 @@
 //
 use common_base::tokio;
 use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use futures::TryStreamExt;
 
+use crate::storages::fuse::table_test_fixture::execute_command;
 use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_err;
 use crate::storages::fuse::table_test_fixture::TestFixture;
 
 #[tokio::test]
@@ -77,3 +80,87 @@ async fn test_fuse_occ_retry() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_fuse_occ_rebase_overwrite() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    let qry = format!("insert into '{}'.'{}' values (1)", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let table = fixture.latest_default_table().await?;
+
+    // overwrite the table with row `id = 9`, without committing
+    let pending = {
+        let stream = TestFixture::gen_sample_blocks_stream_ex(1, 1, 9);
+        table.append_data(ctx.clone(), stream).await?
+    };
+
+    // insert row `id = 5` concurrently
+    let qry = format!("insert into '{}'.'{}' values (5)", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the overwriting is rebased on the concurrent insertion
+    table
+        .commit_insertion(ctx.clone(), pending.try_collect().await?, true)
+        .await?;
+
+    let qry = format!("select * from '{}'.'{}' order by id ", db, tbl);
+    let blocks = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+
+    let expected = vec![
+        "+----+", //
+        "| id |", //
+        "+----+", //
+        "| 5  |", //
+        "| 9  |", //
+        "+----+", //
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, blocks.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_occ_conflict() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    let qry = format!("insert into '{}'.'{}' values (1)", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let table = fixture.latest_default_table().await?;
+
+    // overwrite the table with row `id = 9`, without committing
+    let pending = {
+        let stream = TestFixture::gen_sample_blocks_stream_ex(1, 1, 9);
+        table.append_data(ctx.clone(), stream).await?
+    };
+
+    // the data which the overwriting is based on is removed concurrently
+    let qry = format!("truncate table '{}'.'{}'", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    let r = table
+        .commit_insertion(ctx.clone(), pending.try_collect().await?, true)
+        .await;
+    expects_err("occ_conflict", ErrorCode::table_commit_conflict_code(), r);
+
+    // the table stays truncated
+    let qry = format!("select * from '{}'.'{}'", db, tbl);
+    let blocks = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    assert_eq!(blocks.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+
+    Ok(())
+}
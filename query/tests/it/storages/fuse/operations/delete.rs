@@ -0,0 +1,174 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::Result;
+use databend_query::sessions::QueryContext;
+use databend_query::storages::fuse::FUSE_TBL_DELETION_VECTOR_PREFIX;
+use futures::TryStreamExt;
+use walkdir::WalkDir;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+async fn block_locations(ctx: Arc<QueryContext>, db: &str) -> Result<Vec<String>> {
+    let qry = format!("select block_location from fuse_block('{}', 't')", db);
+    let blocks = execute_query(ctx, qry.as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let mut locations = vec![];
+    for block in blocks {
+        for row in 0..block.num_rows() {
+            locations.push(String::from_utf8(block.column(0).get(row).as_string()?)?);
+        }
+    }
+    locations.sort();
+    Ok(locations)
+}
+
+fn deletion_vector_count(fixture: &TestFixture) -> usize {
+    let data_path = fixture.ctx().get_config().storage.fs.data_path;
+    WalkDir::new(data_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            let path = e.path().to_str().unwrap();
+            path.contains(&format!("/{}/", FUSE_TBL_DELETION_VECTOR_PREFIX))
+        })
+        .count()
+}
+
+#[tokio::test]
+async fn test_fuse_delete() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!("create table {}.t(a int)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    for values in ["(1), (2), (3)", "(4), (5), (6)"] {
+        let qry = format!("insert into {}.t values {}", db, values);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+    let blocks_before = block_locations(ctx.clone(), &db).await?;
+
+    // the deleted rows are marked, the blocks are not rewritten
+    let qry = format!("delete from {}.t where a = 2 or a = 5", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    assert_eq!(block_locations(ctx.clone(), &db).await?, blocks_before);
+    assert_eq!(deletion_vector_count(&fixture), 2);
+
+    // the rows are deleted, whether they are counted by the statistics or read
+    let qry = format!("select count(*) from {}.t", db);
+    let expected = vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        "| 4        |",
+        "+----------+",
+    ];
+    expects_ok(
+        "count",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+    let qry = format!("select a from {}.t where a > 0 order by a", db);
+    let expected = vec![
+        "+---+", //
+        "| a |", //
+        "+---+", //
+        "| 1 |", //
+        "| 3 |", //
+        "| 4 |", //
+        "| 6 |", //
+        "+---+", //
+    ];
+    expects_ok(
+        "read",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // the block of which all the rows are deleted is removed
+    let qry = format!("delete from {}.t where a > 3", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    assert_eq!(block_locations(ctx.clone(), &db).await?.len(), 1);
+    let qry = format!("select a from {}.t order by a", db);
+    let expected = vec!["+---+", "| a |", "+---+", "| 1 |", "| 3 |", "+---+"];
+    expects_ok(
+        "all_deleted",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // nothing to delete, no new snapshot
+    let qry = format!("delete from {}.t where a = 100", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("select count(*) from fuse_history('{}', 't')", db);
+    let expected = vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        "| 4        |",
+        "+----------+",
+    ];
+    expects_ok(
+        "history",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // compaction merges the deletion vectors back into the data
+    let qry = format!("optimize table {}.t compact", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let blocks_after = block_locations(ctx.clone(), &db).await?;
+    assert_eq!(blocks_after.len(), 1);
+    assert!(!blocks_before.contains(&blocks_after[0]));
+    let qry = format!("select a from {}.t order by a", db);
+    let expected = vec!["+---+", "| a |", "+---+", "| 1 |", "| 3 |", "+---+"];
+    expects_ok(
+        "compacted",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    let qry = format!("select * from fuse_verify('{}', 't')", db);
+    expects_ok(
+        "verify",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        vec!["++", "++"],
+    )
+    .await?;
+
+    // and the deletion vectors are purged along with the blocks
+    let qry = format!("optimize table {}.t purge", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    assert_eq!(deletion_vector_count(&fixture), 0);
+
+    // delete without selection removes all the rows
+    let qry = format!("delete from {}.t", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    assert!(block_locations(ctx.clone(), &db).await?.is_empty());
+
+    Ok(())
+}
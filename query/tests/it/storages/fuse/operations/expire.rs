@@ -0,0 +1,151 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use chrono::Duration;
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::storages::fuse::operations::parse_data_retention;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+#[test]
+fn test_parse_data_retention() -> Result<()> {
+    assert_eq!(parse_data_retention("7 days")?, Duration::days(7));
+    assert_eq!(parse_data_retention("1 Week")?, Duration::weeks(1));
+    assert_eq!(
+        parse_data_retention("INTERVAL 12 HOUR")?,
+        Duration::hours(12)
+    );
+    assert_eq!(
+        parse_data_retention(" 30  minutes ")?,
+        Duration::minutes(30)
+    );
+    for invalid in [
+        "",
+        "7",
+        "days",
+        "0 day",
+        "-1 day",
+        "7 fortnights",
+        "7 days ago",
+    ] {
+        assert!(parse_data_retention(invalid).is_err(), "{}", invalid);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_expire() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!(
+        "create table {}.t(id int, ts timestamp) \
+         data_retention = '1 day' data_retention_column = 'ts'",
+        db
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    // a block for each insertion, the second one holds a row which is not expired
+    for values in [
+        "(1, '2000-01-01 00:00:00'), (2, '2000-01-02 00:00:00')",
+        "(3, '2000-01-03 00:00:00'), (4, '2999-01-01 00:00:00')",
+        "(5, '2999-01-01 00:00:00')",
+    ] {
+        let qry = format!("insert into {}.t values {}", db, values);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+
+    let expires = format!(
+        "select count(*) from fuse_history('{}', 't') where operation = 'EXPIRE'",
+        db
+    );
+    let expected = vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        "| 1        |",
+        "+----------+",
+    ];
+
+    // only the first block is removed
+    let qry = format!("optimize table {}.t expire", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let stream = execute_query(ctx.clone(), expires.as_str()).await;
+    expects_ok("expire", stream, expected.clone()).await?;
+
+    let qry = format!("select id from {}.t order by id", db);
+    let blocks = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let values = DataBlock::concat_blocks(&blocks)?.column(0).to_values();
+    assert_eq!(values, [3, 4, 5].map(DataValue::Int64).to_vec());
+
+    // nothing else is expired, no snapshot is committed
+    let qry = format!("optimize table {}.t expire", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let stream = execute_query(ctx.clone(), expires.as_str()).await;
+    expects_ok("expire_again", stream, expected).await?;
+
+    // tables without retention are left alone
+    fixture.create_default_table().await?;
+    let qry = format!(
+        "optimize table {}.{} expire",
+        db,
+        fixture.default_table_name()
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_expire_invalid_options() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    for (case, options) in [
+        (
+            "invalid_interval",
+            "data_retention = '7 fortnights' data_retention_column = 'ts'",
+        ),
+        ("without_column", "data_retention = '7 days'"),
+        (
+            "unknown_column",
+            "data_retention = '7 days' data_retention_column = 'x'",
+        ),
+        (
+            "not_temporal",
+            "data_retention = '7 days' data_retention_column = 'id'",
+        ),
+    ] {
+        let qry = format!("create table {}.t(id int, ts timestamp) {}", db, options);
+        let res = execute_command(ctx.clone(), qry.as_str()).await;
+        expects_err(case, ErrorCode::bad_option_code(), res);
+    }
+
+    // a DATE column tells the ages as well
+    let qry = format!(
+        "create table {}.t(id int, d date) data_retention = '7 days' data_retention_column = 'd'",
+        db
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    Ok(())
+}
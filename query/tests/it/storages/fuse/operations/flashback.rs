@@ -0,0 +1,120 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::sessions::QueryContext;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+async fn snapshot_id_of(ctx: Arc<QueryContext>, db: &str, tbl: &str, rows: u64) -> Result<String> {
+    let qry = format!(
+        "select snapshot_id from fuse_history('{}', '{}') where row_count = {}",
+        db, tbl, rows
+    );
+    let blocks = execute_query(ctx, qry.as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    Ok(String::from_utf8(blocks[0].column(0).get(0).as_string()?)?)
+}
+
+// the filter makes the blocks be read, instead of counting by the statistics
+async fn expects_count(ctx: Arc<QueryContext>, table: &str, count: u64) -> Result<()> {
+    let qry = format!("select count(*) from {} where id > 0", table);
+    let count = format!("| {: <8} |", count);
+    expects_ok(table, execute_query(ctx, qry.as_str()).await, vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        count.as_str(),
+        "+----------+",
+    ])
+    .await
+}
+
+#[tokio::test]
+async fn test_fuse_flashback_table() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let full_name = format!("{}.{}", db, tbl);
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(1, &fixture).await?;
+    append_sample_data(1, &fixture).await?;
+
+    let first = snapshot_id_of(ctx.clone(), &db, &tbl, 3).await?;
+    let second = snapshot_id_of(ctx.clone(), &db, &tbl, 6).await?;
+
+    let qry = format!(
+        "alter table {} flashback to (snapshot => '{}')",
+        full_name, first
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    expects_count(ctx.clone(), &full_name, 3).await?;
+
+    // the flashback is recorded in the history, which still has the rolled back snapshot
+    let qry = format!(
+        "select operation, row_count from fuse_history('{}', '{}') limit 1",
+        db, tbl
+    );
+    expects_ok(
+        "flashback_snapshot",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        vec![
+            "+-----------+-----------+",
+            "| operation | row_count |",
+            "+-----------+-----------+",
+            "| FLASHBACK | 3         |",
+            "+-----------+-----------+",
+        ],
+    )
+    .await?;
+
+    let qry = format!(
+        "alter table {} flashback to (snapshot => '{}')",
+        full_name, second
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    expects_count(ctx.clone(), &full_name, 6).await?;
+
+    // the history is gone once purged
+    let qry = format!("optimize table {} purge", full_name);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!(
+        "alter table {} flashback to (snapshot => '{}')",
+        full_name, first
+    );
+    expects_err(
+        "purged_snapshot",
+        ErrorCode::table_historical_data_not_found_code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    // unknown table
+    let qry = format!(
+        "alter table if exists {}.not_exists flashback to (snapshot => '{}')",
+        db, first
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+#[tokio::test]
+async fn test_fuse_generated_column() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!(
+        "create table {}.t(a int, b int as (a * 2) stored, c int as (a + 1) virtual)",
+        db
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the generated columns are left out of the insertion
+    let qry = format!("insert into {}.t values (1), (2)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("insert into {}.t(a) values (3)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("select * from {}.t order by a", db);
+    let expected = vec![
+        "+---+---+---+",
+        "| a | b | c |",
+        "+---+---+---+",
+        "| 1 | 2 | 2 |",
+        "| 2 | 4 | 3 |",
+        "| 3 | 6 | 4 |",
+        "+---+---+---+",
+    ];
+    expects_ok(
+        "inserted",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // the stored column is updated with the columns it is computed from
+    let qry = format!("update {}.t set a = a * 10 where c = 4", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("select a, b, c from {}.t where c > 4", db);
+    let expected = vec![
+        "+----+----+----+",
+        "| a  | b  | c  |",
+        "+----+----+----+",
+        "| 30 | 60 | 31 |",
+        "+----+----+----+",
+    ];
+    expects_ok(
+        "updated",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // the generated columns can not be written to
+    let qry = format!("insert into {}.t(a, b) values (1, 1)", db);
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("insert", ErrorCode::syntax_exception_code(), res);
+    let qry = format!("update {}.t set c = 1", db);
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("update", ErrorCode::syntax_exception_code(), res);
+
+    // the generated columns can not refer to each other
+    let qry = format!(
+        "create table {}.t1(a int, b int as (a * 2), c int as (b + 1) stored)",
+        db
+    );
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("refer_generated", ErrorCode::syntax_exception_code(), res);
+
+    Ok(())
+}
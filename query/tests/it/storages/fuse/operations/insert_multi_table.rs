@@ -0,0 +1,164 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+#[tokio::test]
+async fn test_fuse_insert_multi_table() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    for qry in [
+        format!("create table {}.s(a int, b varchar)", db),
+        format!("create table {}.t1(a int, b varchar)", db),
+        format!("create table {}.t2(x int, y int default 7)", db),
+        format!("create table {}.t3(a int, b varchar)", db),
+        format!(
+            "insert into {}.s values (1, 'x'), (2, 'y'), (3, 'z'), (4, 'w')",
+            db
+        ),
+        // a row goes to every branch it matches
+        format!(
+            "insert all \
+             when a > 1 then into {0}.t1 \
+             when a > 2 then into {0}.t1 into {0}.t2 (x) values (a * 10) \
+             else into {0}.t3 \
+             select a, b from {0}.s",
+            db
+        ),
+    ] {
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+
+    let qry = format!("select a, b from {}.t1 order by a", db);
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 2 | y |",
+        "| 3 | z |",
+        "| 3 | z |",
+        "| 4 | w |",
+        "| 4 | w |",
+        "+---+---+",
+    ];
+    expects_ok(
+        "all_t1",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    let qry = format!("select x, y from {}.t2 order by x", db);
+    let expected = vec![
+        "+----+---+",
+        "| x  | y |",
+        "+----+---+",
+        "| 30 | 7 |",
+        "| 40 | 7 |",
+        "+----+---+",
+    ];
+    expects_ok(
+        "all_t2",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    let qry = format!("select a, b from {}.t3 order by a", db);
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | x |",
+        "+---+---+",
+    ];
+    expects_ok(
+        "all_t3",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // a row goes to the first branch it matches only
+    for qry in [
+        format!("truncate table {}.t1", db),
+        format!("truncate table {}.t3", db),
+        format!(
+            "insert first \
+             when a > 2 then into {0}.t1 \
+             when a > 1 then into {0}.t3 \
+             select a, b from {0}.s",
+            db
+        ),
+    ] {
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+
+    let qry = format!("select a, b from {}.t1 order by a", db);
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 3 | z |",
+        "| 4 | w |",
+        "+---+---+",
+    ];
+    expects_ok(
+        "first_t1",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    let qry = format!("select a, b from {}.t3 order by a", db);
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 2 | y |",
+        "+---+---+",
+    ];
+    expects_ok(
+        "first_t3",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    for table in ["t1", "t2", "t3"] {
+        let qry = format!("select * from fuse_verify('{}', '{}')", db, table);
+        expects_ok(
+            "verify",
+            execute_query(ctx.clone(), qry.as_str()).await,
+            vec!["++", "++"],
+        )
+        .await?;
+    }
+
+    // the number of the values does not agree with the number of the columns
+    let qry = format!(
+        "insert all when a > 1 then into {0}.t2 select a, b, a + 1 from {0}.s",
+        db
+    );
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("values_count", ErrorCode::syntax_exception_code(), res);
+
+    Ok(())
+}
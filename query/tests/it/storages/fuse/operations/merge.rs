@@ -0,0 +1,137 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+#[tokio::test]
+async fn test_fuse_merge() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    // the target table has no snapshot yet, all the source rows are inserted
+    for qry in [
+        format!("create table {}.t(a int, b varchar, c int default 7)", db),
+        format!("create table {}.s(a int, b varchar)", db),
+        format!("insert into {}.s values (1, 'x'), (2, 'y')", db),
+        format!(
+            "merge into {0}.t using {0}.s on t.a = s.a \
+             when not matched then insert (a, b) values (s.a, s.b)",
+            db
+        ),
+    ] {
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+    let qry = format!("select a, b, c from {}.t order by a", db);
+    let expected = vec![
+        "+---+---+---+",
+        "| a | b | c |",
+        "+---+---+---+",
+        "| 1 | x | 7 |",
+        "| 2 | y | 7 |",
+        "+---+---+---+",
+    ];
+    expects_ok(
+        "inserted",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // the matched rows are updated or deleted, the others are inserted
+    for qry in [
+        format!("insert into {}.t values (3, 'z', 3)", db),
+        format!("truncate table {}.s", db),
+        format!(
+            "insert into {}.s values (1, 'u'), (2, 'd'), (4, 'i'), (5, 'd')",
+            db
+        ),
+        format!(
+            "merge into {0}.t as x using (select a, b from {0}.s) as y on x.a = y.a \
+             when matched and y.b = 'd' then delete \
+             when matched then update set b = y.b, c = x.c + 10 \
+             when not matched and y.b <> 'd' then insert values (y.a, y.b, 0)",
+            db
+        ),
+    ] {
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+    let qry = format!("select a, b, c from {}.t order by a", db);
+    let expected = vec![
+        "+---+---+----+",
+        "| a | b | c  |",
+        "+---+---+----+",
+        "| 1 | u | 17 |",
+        "| 3 | z | 3  |",
+        "| 4 | i | 0  |",
+        "+---+---+----+",
+    ];
+    expects_ok(
+        "merged",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    let qry = format!(
+        "select count(*) from fuse_history('{}', 't') where operation = 'MERGE'",
+        db
+    );
+    let expected = vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        "| 2        |",
+        "+----------+",
+    ];
+    expects_ok(
+        "history",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    let qry = format!("select * from fuse_verify('{}', 't')", db);
+    expects_ok(
+        "verify",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        vec!["++", "++"],
+    )
+    .await?;
+
+    // a target row is matched by more than one source row
+    let qry = format!("insert into {}.s values (1, 'v')", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!(
+        "merge into {0}.t using {0}.s on t.a = s.a when matched then update set b = s.b",
+        db
+    );
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("ambiguous_match", ErrorCode::bad_arguments_code(), res);
+
+    // the columns of the target are not available to the rows which are not matched
+    let qry = format!(
+        "merge into {0}.t using {0}.s on t.a = s.a \
+         when not matched then insert values (t.a, s.b, 0)",
+        db
+    );
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("target_column", ErrorCode::syntax_exception_code(), res);
+
+    Ok(())
+}
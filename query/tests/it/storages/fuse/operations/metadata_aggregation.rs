@@ -0,0 +1,107 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datablocks::pretty_format_blocks;
+use common_exception::Result;
+use databend_query::sessions::QueryContext;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+async fn explain(ctx: Arc<QueryContext>, select: &str) -> Result<String> {
+    let blocks = execute_query(ctx, format!("explain {}", select).as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    Ok(pretty_format_blocks(&blocks)?)
+}
+
+#[tokio::test]
+async fn test_fuse_metadata_aggregation() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!("create table {}.t(a int, b int) cluster by (a)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    for values in ["(1, 10), (2, 20), (3, 30)", "(4, 40), (5, 50), (6, 60)"] {
+        let qry = format!("insert into {}.t values {}", db, values);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+
+    // every block left after pruning is matched as a whole
+    let select = format!("select count(*), min(b), max(b) from {}.t where a > 3", db);
+    assert!(explain(ctx.clone(), &select)
+        .await?
+        .contains("Metadata Statistics"));
+    let expected = vec![
+        "+----------+--------+--------+",
+        "| count(*) | min(b) | max(b) |",
+        "+----------+--------+--------+",
+        "| 3        | 40     | 60     |",
+        "+----------+--------+--------+",
+    ];
+    expects_ok(
+        "matched",
+        execute_query(ctx.clone(), select.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // some block is matched partially
+    let select = format!("select count(*), min(b) from {}.t where a > 2", db);
+    assert!(!explain(ctx.clone(), &select)
+        .await?
+        .contains("Metadata Statistics"));
+    let expected = vec![
+        "+----------+--------+",
+        "| count(*) | min(b) |",
+        "+----------+--------+",
+        "| 4        | 30     |",
+        "+----------+--------+",
+    ];
+    expects_ok(
+        "partial",
+        execute_query(ctx.clone(), select.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // the statistics of the blocks with deleted rows are stale
+    let qry = format!("delete from {}.t where b = 60", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let select = format!("select count(*), max(b) from {}.t where a > 3", db);
+    assert!(!explain(ctx.clone(), &select)
+        .await?
+        .contains("Metadata Statistics"));
+    let expected = vec![
+        "+----------+--------+",
+        "| count(*) | max(b) |",
+        "+----------+--------+",
+        "| 2        | 50     |",
+        "+----------+--------+",
+    ];
+    expects_ok(
+        "deleted",
+        execute_query(ctx.clone(), select.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    Ok(())
+}
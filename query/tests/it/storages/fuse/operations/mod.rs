@@ -13,8 +13,28 @@
 //  limitations under the License.
 //
 
+mod aggregating_index;
+mod alter_column;
+mod analyze;
+mod attach;
+mod backup;
+mod changes;
+mod clone;
 mod commit;
+mod delete;
+mod expire;
+mod flashback;
+mod generated_column;
+mod insert_multi_table;
+mod merge;
+mod metadata_aggregation;
+mod navigate;
 mod optimize;
 mod purge_drop;
 mod purge_truncate;
 mod read_plan;
+mod recluster;
+mod table_usage;
+mod update;
+mod vacuum;
+mod virtual_column;
@@ -0,0 +1,153 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::time::Duration;
+
+use chrono::Utc;
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::storages::fuse::FuseTable;
+use databend_query::storages::NavigationPoint;
+use futures::TryStreamExt;
+use uuid::Uuid;
+
+use crate::storages::fuse::table_test_fixture::append_sample_data;
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_err;
+use crate::storages::fuse::table_test_fixture::expects_ok;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_navigate() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    let before_any_data = Utc::now();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    // 1st snapshot
+    append_sample_data(1, &fixture).await?;
+    let table = fixture.latest_default_table().await?;
+    let first_loc = FuseTable::try_from_table(table.as_ref())?.snapshot_loc();
+    assert!(first_loc.is_some());
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let after_first_snapshot = Utc::now();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    // 2nd snapshot
+    append_sample_data(1, &fixture).await?;
+    let table = fixture.latest_default_table().await?;
+    let second_loc = FuseTable::try_from_table(table.as_ref())?.snapshot_loc();
+    assert_ne!(first_loc, second_loc);
+
+    // navigate by time point
+    let point = NavigationPoint::TimePoint(after_first_snapshot);
+    let navigated = table.navigate_to(ctx.clone(), &point).await?;
+    let loc = FuseTable::try_from_table(navigated.as_ref())?.snapshot_loc();
+    assert_eq!(first_loc, loc);
+
+    let point = NavigationPoint::TimePoint(Utc::now());
+    let navigated = table.navigate_to(ctx.clone(), &point).await?;
+    let loc = FuseTable::try_from_table(navigated.as_ref())?.snapshot_loc();
+    assert_eq!(second_loc, loc);
+
+    // navigate by snapshot id, the snapshot id is the file stem of the location
+    let first_snapshot_id = first_loc
+        .as_ref()
+        .and_then(|loc| loc.rsplit('/').next())
+        .and_then(|name| name.split('_').next())
+        .unwrap()
+        .to_owned();
+    let point = NavigationPoint::SnapshotID(first_snapshot_id);
+    let navigated = table.navigate_to(ctx.clone(), &point).await?;
+    let loc = FuseTable::try_from_table(navigated.as_ref())?.snapshot_loc();
+    assert_eq!(first_loc, loc);
+
+    // no historical data
+    let point = NavigationPoint::TimePoint(before_any_data);
+    expects_err(
+        "navigate_before_any_data",
+        ErrorCode::table_historical_data_not_found_code(),
+        table.navigate_to(ctx.clone(), &point).await,
+    );
+
+    let point = NavigationPoint::SnapshotID(Uuid::new_v4().to_simple().to_string());
+    expects_err(
+        "navigate_to_unknown_snapshot",
+        ErrorCode::table_historical_data_not_found_code(),
+        table.navigate_to(ctx.clone(), &point).await,
+    );
+
+    let point = NavigationPoint::SnapshotID("not_a_snapshot_id".to_owned());
+    expects_err(
+        "navigate_to_invalid_snapshot_id",
+        ErrorCode::bad_arguments_code(),
+        table.navigate_to(ctx.clone(), &point).await,
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_navigate_before_add_column() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!("create table {}.t(a int)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("insert into {}.t values (1), (2)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    let qry = format!("select snapshot_id from fuse_history('{}', 't')", db);
+    let blocks = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let snapshot_id = String::from_utf8(blocks[0].column(0).get(0).as_string()?)?;
+
+    let qry = format!("alter table {}.t add column b int default 10", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("insert into {}.t values (3, 30)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the table navigated to has the schema of the snapshot, not the current one
+    let table = ctx.get_table(&db, "t").await?;
+    let point = NavigationPoint::SnapshotID(snapshot_id.clone());
+    let navigated = table.navigate_to(ctx.clone(), &point).await?;
+    assert_eq!(navigated.schema().num_fields(), 1);
+    assert_eq!(table.schema().num_fields(), 2);
+
+    // and so is the table cloned at the snapshot
+    let qry = format!(
+        "create table {}.cloned clone {}.t at (snapshot => '{}')",
+        db, db, snapshot_id
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let expected = vec!["+---+", "| a |", "+---+", "| 1 |", "| 2 |", "+---+"];
+    let select = format!("select * from {}.cloned order by a", db);
+    expects_ok(
+        "cloned_before_add_column",
+        execute_query(ctx.clone(), select.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    Ok(())
+}
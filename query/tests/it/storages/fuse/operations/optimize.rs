@@ -13,8 +13,13 @@
 //  limitations under the License.
 //
 
+use std::path::Path;
+
 use common_base::tokio;
+use common_datavalues::prelude::*;
 use common_exception::Result;
+use databend_query::storages::fuse::io::MetaReaders;
+use databend_query::storages::fuse::FuseTable;
 use futures::TryStreamExt;
 
 use crate::storages::fuse::table_test_fixture::append_sample_data;
@@ -53,6 +58,78 @@ async fn do_purge_test(case_name: &str, operation: &str) -> Result<()> {
     history_should_have_only_one_item(&fixture, case_name).await
 }
 
+#[tokio::test]
+async fn test_fuse_purge_in_batches() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(1, &fixture).await?;
+    for _ in 0..3 {
+        append_sample_data_overwrite(1, true, &fixture).await?;
+    }
+    check_data_dir(&fixture, "purge_in_batches_before", 4, 4, 4).await;
+
+    // one snapshot per batch
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    fuse_table.do_purge(ctx.as_ref(), true, 1).await?;
+    check_data_dir(&fixture, "purge_in_batches_after", 1, 1, 1).await;
+    history_should_have_only_one_item(&fixture, "purge_in_batches").await?;
+
+    // the checkpoint is removed once the purge is done
+    let data_path = ctx.get_config().storage.fs.data_path;
+    let checkpoint_loc = fuse_table
+        .meta_location_generator()
+        .purge_checkpoint_location();
+    assert!(!Path::new(&data_path).join(checkpoint_loc).exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_purge_resume() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(1, &fixture).await?;
+    for _ in 0..3 {
+        append_sample_data_overwrite(1, true, &fixture).await?;
+    }
+
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let locs = fuse_table.meta_location_generator();
+    let reader = MetaReaders::table_snapshot_lite_reader(ctx.as_ref());
+    let (history, _) = reader
+        .read_snapshot_lites(
+            fuse_table.snapshot_loc(),
+            fuse_table.snapshot_format_version(),
+            locs,
+            None,
+        )
+        .await?;
+    assert_eq!(history.len(), 4);
+
+    // emulates a purge interrupted after the latest expired snapshot is removed, the earlier
+    // ones are no longer reachable from the history of the table, but from the checkpoint
+    let data_path = ctx.get_config().storage.fs.data_path;
+    let root = Path::new(&data_path);
+    let expired = &history[1..];
+    let checkpoint_loc = locs.purge_checkpoint_location();
+    std::fs::write(root.join(&checkpoint_loc), serde_json::to_vec(expired)?)?;
+    let latest_expired =
+        locs.snapshot_location_from_uuid(&expired[0].snapshot_id, expired[0].format_version)?;
+    std::fs::remove_file(root.join(latest_expired))?;
+    check_data_dir(&fixture, "purge_resume_interrupted", 3, 4, 4).await;
+
+    let qry = format!("optimize table '{}'.'{}' purge", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    check_data_dir(&fixture, "purge_resume_after", 1, 1, 1).await;
+    assert!(!root.join(&checkpoint_loc).exists());
+    Ok(())
+}
+
 async fn insert_test_data(qry: &str, fixture: &TestFixture) -> Result<()> {
     let ctx = fixture.ctx();
     fixture.create_default_table().await?;
@@ -106,3 +183,153 @@ async fn test_fuse_history_optimize_compact() -> Result<()> {
     )
     .await
 }
+
+#[tokio::test]
+async fn test_fuse_optimize_compact_segment() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // insert 5 times, 1 block, 1 segment, 1 snapshot for each insertion
+    for _ in 0..5 {
+        append_sample_data(1, &fixture).await?;
+    }
+
+    let qry = format!("optimize table '{}'.'{}' compact segment", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the 5 segments are merged into a new one, blocks are not touched
+    check_data_dir(&fixture, "compact segment", 6, 6, 5).await;
+
+    // the latest snapshot comes first
+    let expected = vec![
+        "+---------------+-------------+-----------+",
+        "| segment_count | block_count | row_count |",
+        "+---------------+-------------+-----------+",
+        "| 1             | 5           | 15        |",
+        "+---------------+-------------+-----------+",
+    ];
+    let qry = format!(
+        "select segment_count, block_count, row_count from fuse_history('{}', '{}') limit 1",
+        db, tbl
+    );
+    expects_ok(
+        "segments_after_compact_segment",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    let expected = vec![
+        "+-------+",
+        "| count |",
+        "+-------+",
+        "| 15    |",
+        "+-------+",
+    ];
+    let qry = format!("select count(*) as count from '{}'.'{}'", db, tbl);
+    expects_ok(
+        "count_after_compact_segment",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // compacting again changes nothing
+    let qry = format!("optimize table '{}'.'{}' compact segment", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    check_data_dir(&fixture, "compact segment again", 6, 6, 5).await;
+
+    // the replaced segments are purged
+    let qry = format!("optimize table '{}'.'{}' purge", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    check_data_dir(&fixture, "purge after compact segment", 1, 1, 5).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_optimize_compact_limit() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // 5 undersized blocks
+    for _ in 0..5 {
+        append_sample_data(1, &fixture).await?;
+    }
+
+    // at most 2 blocks are merged
+    let qry = format!("optimize table '{}'.'{}' compact limit 2", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!(
+        "select block_count, row_count from fuse_history('{}', '{}') limit 1",
+        db, tbl
+    );
+    let expected = vec![
+        "+-------------+-----------+",
+        "| block_count | row_count |",
+        "+-------------+-----------+",
+        "| 4           | 15        |",
+        "+-------------+-----------+",
+    ];
+    expects_ok(
+        "compact_with_limit",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // all the undersized blocks are merged
+    let compact = format!("optimize table '{}'.'{}' compact", db, tbl);
+    execute_command(ctx.clone(), compact.as_str()).await?;
+    let expected = vec![
+        "+-------------+-----------+",
+        "| block_count | row_count |",
+        "+-------------+-----------+",
+        "| 1           | 15        |",
+        "+-------------+-----------+",
+    ];
+    expects_ok(
+        "compact_without_limit",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_fuse_optimize_compact_cluster_key() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let create = format!("create table '{}'.t(a int) cluster by (a)", db);
+    execute_command(ctx.clone(), create.as_str()).await?;
+    for v in [3, 1, 2] {
+        let insert = format!("insert into '{}'.t values({})", db, v);
+        execute_command(ctx.clone(), insert.as_str()).await?;
+    }
+
+    let compact = format!("optimize table '{}'.t compact", db);
+    execute_command(ctx.clone(), compact.as_str()).await?;
+
+    // the merged block is sorted by the cluster key
+    let qry = format!("select a from '{}'.t", db);
+    let blocks = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(blocks.len(), 1);
+    let values = blocks[0].column(0).to_values();
+    assert_eq!(values, vec![
+        DataValue::Int64(1),
+        DataValue::Int64(2),
+        DataValue::Int64(3)
+    ]);
+    Ok(())
+}
@@ -17,10 +17,14 @@ use std::collections::HashMap;
 use std::iter::Iterator;
 
 use common_base::tokio;
-use common_datavalues::DataValue;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::Extras;
+use common_planners::TableSample;
 use databend_query::interpreters::CreateTableInterpreter;
+use databend_query::interpreters::SelectInterpreterV2;
 use databend_query::storages::fuse::meta::BlockMeta;
 use databend_query::storages::fuse::meta::ColumnMeta;
 use databend_query::storages::fuse::meta::Compression;
@@ -28,6 +32,7 @@ use databend_query::storages::fuse::FuseTable;
 use databend_query::storages::index::ColumnStatistics;
 use futures::TryStreamExt;
 
+use crate::storages::fuse::table_test_fixture::append_sample_data;
 use crate::storages::fuse::table_test_fixture::TestFixture;
 
 #[test]
@@ -41,6 +46,8 @@ fn test_to_partitions() -> Result<()> {
         max: DataValue::Int64(2),
         null_count: 0,
         in_memory_size: col_size as u64,
+        distinct_of_values: None,
+        truncated: false,
     };
 
     let col_metas_gen = || ColumnMeta {
@@ -70,15 +77,25 @@ fn test_to_partitions() -> Result<()> {
         col_metas: cols_metas,
         location: ("".to_owned(), 0),
         compression: Compression::Lz4Raw,
+        created_by: None,
+        created_on: None,
+        deletion_vector: None,
+        virtual_block: None,
     };
 
+    let fields = (0..num_of_col)
+        .into_iter()
+        .map(|col_id| DataField::new(&format!("c{}", col_id), i64::to_data_type()))
+        .collect::<Vec<_>>();
+    let schema = DataSchema::new(fields);
+
     let blocks_metas = (0..num_of_block)
         .into_iter()
         .map(|_| block_meta.clone())
         .collect::<Vec<_>>();
 
     // CASE I:  no projection
-    let (s, _) = FuseTable::to_partitions(&blocks_metas, None);
+    let (s, _) = FuseTable::to_partitions(&schema, &blocks_metas, None);
     let expected_block_size: u64 = cols_stats
         .iter()
         .map(|(_, col_stats)| col_stats.in_memory_size)
@@ -105,8 +122,9 @@ fn test_to_partitions() -> Result<()> {
         filters: vec![],
         limit: None,
         order_by: vec![],
+        sample: None,
     });
-    let (stats, _) = FuseTable::to_partitions(&blocks_metas, push_down);
+    let (stats, _) = FuseTable::to_partitions(&schema, &blocks_metas, push_down);
     assert_eq!(expected_block_size * num_of_block, stats.read_bytes as u64);
     Ok(())
 }
@@ -142,6 +160,7 @@ async fn test_fuse_table_exact_statistic() -> Result<()> {
             filters: vec![],
             limit: None,
             order_by: vec![],
+            sample: None,
         };
         let (stats, parts) = table.read_partitions(ctx.clone(), Some(push_downs)).await?;
         assert_eq!(stats.read_rows, num_blocks * rows_per_block);
@@ -150,3 +169,56 @@ async fn test_fuse_table_exact_statistic() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_fuse_table_sample() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    let num_blocks = 5;
+    append_sample_data(num_blocks, &fixture).await?;
+    let table = fixture.latest_default_table().await?;
+
+    // blocks are sampled while pruning, even if no column is to be read
+    for (percent, expected_parts) in [(0.0, 0), (100.0, num_blocks)] {
+        let push_downs = Extras {
+            projection: Some(vec![]),
+            sample: Some(TableSample::Block(percent)),
+            ..Extras::default()
+        };
+        let (stats, parts) = table.read_partitions(ctx.clone(), Some(push_downs)).await?;
+        assert_eq!(parts.len(), expected_parts);
+        assert!(!stats.is_exact);
+    }
+
+    // rows are sampled after being read
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    for (sample, expected_rows) in [
+        ("block (0 percent)", 0),
+        ("block (100 percent)", num_blocks * 3),
+        ("row (0 percent)", 0),
+        ("row (100 percent)", num_blocks * 3),
+    ] {
+        let qry = format!("select * from {}.{} tablesample {}", db, tbl, sample);
+        let blocks = SelectInterpreterV2::try_create(ctx.clone(), qry.as_str())?
+            .execute(None)
+            .await?
+            .try_collect::<Vec<DataBlock>>()
+            .await?;
+        let rows = blocks.iter().map(|b| b.num_rows()).sum::<usize>();
+        assert_eq!(rows, expected_rows, "{}", sample);
+    }
+
+    // the percentage is validated
+    let qry = format!("select * from {}.{} tablesample row (101 percent)", db, tbl);
+    let res = SelectInterpreterV2::try_create(ctx.clone(), qry.as_str())?
+        .execute(None)
+        .await;
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::bad_arguments_code())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,351 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+use std::collections::HashMap;
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::storages::fuse::meta::BlockMeta;
+use databend_query::storages::fuse::meta::Compression;
+use databend_query::storages::fuse::operations::ReclusterPolicy;
+use databend_query::storages::fuse::ordinal_of;
+use databend_query::storages::fuse::z_value;
+use databend_query::storages::fuse::ClusterKey;
+use databend_query::storages::index::ColumnStatistics;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+fn block_of_range(min: i64, max: i64) -> BlockMeta {
+    BlockMeta {
+        row_count: 2,
+        block_size: 0,
+        file_size: 0,
+        col_stats: HashMap::from([(0, ColumnStatistics {
+            min: DataValue::Int64(min),
+            max: DataValue::Int64(max),
+            null_count: 0,
+            in_memory_size: 0,
+            distinct_of_values: None,
+            truncated: false,
+        })]),
+        col_metas: HashMap::new(),
+        location: (format!("{}_{}", min, max), 0),
+        compression: Compression::Lz4Raw,
+        created_by: None,
+        created_on: None,
+        deletion_vector: None,
+        virtual_block: None,
+    }
+}
+
+#[test]
+fn test_recluster_policy() -> Result<()> {
+    let blocks = vec![
+        block_of_range(20, 30),
+        block_of_range(2, 8),
+        block_of_range(25, 35),
+        block_of_range(1, 9),
+        block_of_range(40, 40),
+        block_of_range(3, 7),
+        block_of_range(40, 40),
+    ];
+    // [1, 9], [2, 8] and [3, 7] overlap each other, [20, 30] and [25, 35] overlap,
+    // the constant blocks of the same value do not
+    assert_eq!(
+        ReclusterPolicy::overlaps(&blocks, &ClusterKey::Column(0)),
+        4
+    );
+
+    // the most overlapping group is picked, in the order of the ranges
+    let policy = ReclusterPolicy::new(16, 100, 1024);
+    assert_eq!(policy.select(&blocks, &ClusterKey::Column(0)), vec![
+        3, 1, 5
+    ]);
+
+    // capped by the number of blocks, and by the number of rows
+    let policy = ReclusterPolicy::new(2, 100, 1024);
+    assert_eq!(policy.select(&blocks, &ClusterKey::Column(0)), vec![3, 1]);
+    let policy = ReclusterPolicy {
+        max_blocks: 16,
+        max_rows: 5,
+        max_bytes: u64::MAX,
+    };
+    assert_eq!(policy.select(&blocks, &ClusterKey::Column(0)), vec![3, 1]);
+
+    // blocks sharing the end points are not picked
+    let blocks = vec![
+        block_of_range(1, 2),
+        block_of_range(2, 2),
+        block_of_range(2, 3),
+    ];
+    assert_eq!(
+        ReclusterPolicy::overlaps(&blocks, &ClusterKey::Column(0)),
+        0
+    );
+    assert!(policy.select(&blocks, &ClusterKey::Column(0)).is_empty());
+
+    // columns without statistics are ignored
+    assert!(policy.select(&blocks, &ClusterKey::Column(1)).is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_recluster() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!(
+        "create table {}.t(a int) cluster by (a) row_per_block = 2",
+        db
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    for values in ["(1), (9)", "(2), (8)", "(3), (7)", "(4), (6)"] {
+        let qry = format!("insert into {}.t values {}", db, values);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+
+    let clustering = format!(
+        "select total_block_count, block_depth_histogram \
+         from clustering_information('{}', 't')",
+        db
+    );
+    let reclusters = format!(
+        "select count(*) from fuse_history('{}', 't') where operation = 'RECLUSTER'",
+        db
+    );
+
+    // no block may contain the matching rows
+    let qry = format!("alter table {}.t recluster where a > 100", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let expected = vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        "| 0        |",
+        "+----------+",
+    ];
+    let stream = execute_query(ctx.clone(), reclusters.as_str()).await;
+    expects_ok("recluster_nothing", stream, expected).await?;
+
+    // one round of two blocks: [1, 9] and [2, 8] are rewritten as [1, 2] and [8, 9]
+    let qry = format!("alter table {}.t recluster limit 2", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let expected = vec![
+        "+-------------------+-----------------------+",
+        "| total_block_count | block_depth_histogram |",
+        "+-------------------+-----------------------+",
+        "| 4                 | {\"1\":6,\"2\":2}         |",
+        "+-------------------+-----------------------+",
+    ];
+    let stream = execute_query(ctx.clone(), clustering.as_str()).await;
+    expects_ok("recluster_limit", stream, expected).await?;
+
+    // rounds go on until no block overlaps others
+    let qry = format!("alter table {}.t recluster final", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let expected = vec![
+        "+-------------------+-----------------------+",
+        "| total_block_count | block_depth_histogram |",
+        "+-------------------+-----------------------+",
+        "| 4                 | {\"1\":8}               |",
+        "+-------------------+-----------------------+",
+    ];
+    let stream = execute_query(ctx.clone(), clustering.as_str()).await;
+    expects_ok("recluster_final", stream, expected).await?;
+    let expected = vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        "| 2        |",
+        "+----------+",
+    ];
+    let stream = execute_query(ctx.clone(), reclusters.as_str()).await;
+    expects_ok("recluster_rounds", stream, expected).await?;
+
+    let qry = format!("select a from {}.t order by a", db);
+    let blocks = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let values = DataBlock::concat_blocks(&blocks)?.column(0).to_values();
+    let expected = [1, 2, 3, 4, 6, 7, 8, 9].map(DataValue::Int64).to_vec();
+    assert_eq!(values, expected);
+
+    // table which is not clustered
+    fixture.create_default_table().await?;
+    let qry = format!(
+        "alter table {}.{} recluster",
+        db,
+        fixture.default_table_name()
+    );
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("not_clustered", ErrorCode::bad_arguments_code(), res);
+
+    let qry = format!("alter table if exists {}.not_exists recluster", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    Ok(())
+}
+
+#[test]
+fn test_cluster_key_zorder() -> Result<()> {
+    // the ordinals keep the orders of the values
+    let values = [i64::MIN, -1, 0, 1, i64::MAX].map(DataValue::Int64);
+    let ordinals = values.iter().map(ordinal_of).collect::<Vec<_>>();
+    assert!(ordinals.windows(2).all(|w| w[0] < w[1]));
+    let values = [f64::MIN, -1.5, -0.0, 0.0, 1.5, f64::MAX].map(DataValue::Float64);
+    let ordinals = values.iter().map(ordinal_of).collect::<Vec<_>>();
+    assert!(ordinals.windows(2).all(|w| w[0] < w[1]));
+    let ab = ordinal_of(&DataValue::String(b"ab".to_vec()));
+    let abc = ordinal_of(&DataValue::String(b"abc".to_vec()));
+    let b = ordinal_of(&DataValue::String(b"b".to_vec()));
+    assert!(ab < abc && abc < b);
+
+    // the bits are interleaved from the most significant ones
+    let z = z_value(&[0b10 << 62, 0b01 << 62]);
+    assert_eq!(z.len(), 16);
+    assert_eq!(z[0], 0b1001 << 4);
+    assert!(z[1..].iter().all(|b| *b == 0));
+    assert!(z_value(&[1, 2]) < z_value(&[2, 1]));
+
+    // blocks of the z-order: [(0, 0), (3, 3)] contains [(1, 1), (2, 2)], while
+    // [(0, 0), (1, 3)] and [(2, 0), (3, 3)] are apart
+    let block_of = |min: (i64, i64), max: (i64, i64)| {
+        let mut block = block_of_range(min.0, max.0);
+        let b = block.col_stats[&0].clone();
+        block.col_stats.insert(1, ColumnStatistics {
+            min: DataValue::Int64(min.1),
+            max: DataValue::Int64(max.1),
+            ..b
+        });
+        block
+    };
+    let key = ClusterKey::ZOrder(vec![0, 1]);
+    let blocks = vec![block_of((0, 0), (3, 3)), block_of((1, 1), (2, 2))];
+    assert_eq!(ReclusterPolicy::overlaps(&blocks, &key), 1);
+    let blocks = vec![block_of((0, 0), (1, 3)), block_of((2, 0), (3, 3))];
+    assert_eq!(ReclusterPolicy::overlaps(&blocks, &key), 0);
+    // yet the ranges of b in them overlap
+    assert_eq!(
+        ReclusterPolicy::overlaps(&blocks, &ClusterKey::Column(1)),
+        1
+    );
+
+    // columns without statistics are ignored
+    assert!(ClusterKey::ZOrder(vec![0, 2])
+        .range_of(&blocks[0])
+        .is_none());
+
+    // the rows of NULL keys are the last ones, thus the maxs of the keys having NULLs
+    let mut block = block_of((0, 0), (1, 1));
+    block.col_stats.get_mut(&1).unwrap().null_count = 1;
+    let (min, max) = key.range_of(&block).unwrap();
+    assert_eq!(
+        min,
+        DataValue::String(z_value(&[0, 0].map(|v| ordinal_of(&DataValue::Int64(v)))))
+    );
+    let max_of_a = ordinal_of(&DataValue::Int64(1));
+    assert_eq!(max, DataValue::String(z_value(&[max_of_a, u64::MAX])));
+    let blocks = vec![block, block_of((0, 2), (1, 3))];
+    assert_eq!(ReclusterPolicy::overlaps(&blocks, &key), 1);
+    // so are the mins of the keys of NULLs only
+    let mut block = block_of((0, 0), (1, 0));
+    let b = block.col_stats.get_mut(&1).unwrap();
+    b.min = DataValue::Null;
+    b.max = DataValue::Null;
+    b.null_count = 2;
+    let (min, max) = key.range_of(&block).unwrap();
+    let min_of_a = ordinal_of(&DataValue::Int64(0));
+    assert_eq!(min, DataValue::String(z_value(&[min_of_a, u64::MAX])));
+    assert_eq!(max, DataValue::String(z_value(&[max_of_a, u64::MAX])));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_recluster_zorder() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!(
+        "create table {}.t(a int, b int) cluster by (a, b) order = zorder row_per_block = 4",
+        db
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    for values in [
+        "(0, 0), (3, 3), (0, 3), (3, 0)",
+        "(1, 1), (2, 2), (1, 2), (2, 1)",
+    ] {
+        let qry = format!("insert into {}.t values {}", db, values);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+
+    let clustering = format!(
+        "select total_block_count, block_depth_histogram \
+         from clustering_information('{}', 't')",
+        db
+    );
+    let expected = vec![
+        "+-------------------+-----------------------+",
+        "| total_block_count | block_depth_histogram |",
+        "+-------------------+-----------------------+",
+        "| 2                 | {\"1\":2,\"2\":2}         |",
+        "+-------------------+-----------------------+",
+    ];
+    let stream = execute_query(ctx.clone(), clustering.as_str()).await;
+    expects_ok("zorder_before", stream, expected).await?;
+
+    // the rows are sorted by the z-order, thus a block holds the points of a <= 1
+    let qry = format!("alter table {}.t recluster final", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let expected = vec![
+        "+-------------------+-----------------------+",
+        "| total_block_count | block_depth_histogram |",
+        "+-------------------+-----------------------+",
+        "| 2                 | {\"1\":4}               |",
+        "+-------------------+-----------------------+",
+    ];
+    let stream = execute_query(ctx.clone(), clustering.as_str()).await;
+    expects_ok("zorder_after", stream, expected).await?;
+
+    let qry = format!("select a, b from {}.t where a <= 1", db);
+    let blocks = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let block = DataBlock::concat_blocks(&blocks)?;
+    let expected = [0, 1, 0, 1].map(DataValue::Int64).to_vec();
+    assert_eq!(block.column(0).to_values(), expected);
+    let expected = [0, 1, 3, 2].map(DataValue::Int64).to_vec();
+    assert_eq!(block.column(1).to_values(), expected);
+
+    // the keys of the z-order must be columns
+    let qry = format!(
+        "create table {}.t1(a int, b int) cluster by (a + 1, b) order = zorder",
+        db
+    );
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("zorder_of_expr", ErrorCode::bad_option_code(), res);
+    let qry = format!(
+        "create table {}.t1(a int) cluster by (a) order = hilbert",
+        db
+    );
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("unknown_order", ErrorCode::bad_option_code(), res);
+    Ok(())
+}
@@ -0,0 +1,100 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use common_meta_types::TableUsage;
+use databend_query::catalogs::Catalog;
+
+use crate::storages::fuse::table_test_fixture::append_sample_data;
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_ok;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_table_usage() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    let table = fixture.latest_default_table().await?;
+    assert_eq!(table.get_table_info().meta.usage, TableUsage::default());
+
+    // 3 blocks, 3 rows per block
+    append_sample_data(3, &fixture).await?;
+    let table = fixture.latest_default_table().await?;
+    let usage = &table.get_table_info().meta.usage;
+    assert_eq!(usage.number_of_rows, 9);
+    assert_eq!(usage.number_of_blocks, 3);
+    assert_eq!(usage.index_data_bytes, 0);
+
+    // the same as the summary of the current snapshot
+    let statistics = table.statistics(ctx.clone()).await?.unwrap();
+    assert_eq!(statistics.data_size, Some(usage.data_bytes));
+    assert_eq!(
+        statistics.data_size_compressed,
+        Some(usage.compressed_data_bytes)
+    );
+
+    let qry = format!(
+        "select num_rows, num_blocks from system.tables_usage \
+         where database = '{}' and name = '{}'",
+        db, tbl
+    );
+    let expected = vec![
+        "+----------+------------+",
+        "| num_rows | num_blocks |",
+        "+----------+------------+",
+        "| 9        | 3          |",
+        "+----------+------------+",
+    ];
+    expects_ok("appended", execute_query(ctx.clone(), &qry).await, expected).await?;
+
+    let truncate = format!("truncate table {}.{}", db, tbl);
+    execute_command(ctx.clone(), &truncate).await?;
+    let table = fixture.latest_default_table().await?;
+    assert_eq!(table.get_table_info().meta.usage, TableUsage::default());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_usage_of_virtual_columns() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!("create table {}.t(id int, v variant)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("alter table {}.t add virtual column v:a", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("insert into {}.t select 1, parse_json('{{\"a\":1}}')", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the virtual columns are accounted as the index data of the table
+    let tenant = fixture.default_tenant();
+    let table = ctx.get_catalog().get_table(&tenant, &db, "t").await?;
+    let usage = &table.get_table_info().meta.usage;
+    assert_eq!(usage.number_of_rows, 1);
+    assert_eq!(usage.number_of_blocks, 1);
+    assert!(usage.index_data_bytes > 0);
+
+    let statistics = table.statistics(ctx.clone()).await?.unwrap();
+    assert_eq!(statistics.index_length, Some(usage.index_data_bytes));
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::sessions::QueryContext;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+async fn block_locations(ctx: Arc<QueryContext>, db: &str) -> Result<Vec<String>> {
+    let qry = format!("select block_location from fuse_block('{}', 't')", db);
+    let blocks = execute_query(ctx, qry.as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let mut locations = vec![];
+    for block in blocks {
+        for row in 0..block.num_rows() {
+            locations.push(String::from_utf8(block.column(0).get(row).as_string()?)?);
+        }
+    }
+    locations.sort();
+    Ok(locations)
+}
+
+#[tokio::test]
+async fn test_fuse_update() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!("create table {}.t(a int, b varchar)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    for values in ["(1, 'x'), (2, 'x'), (3, 'x')", "(4, 'x'), (5, 'x')"] {
+        let qry = format!("insert into {}.t values {}", db, values);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+    let blocks_before = block_locations(ctx.clone(), &db).await?;
+
+    // only the block having matching rows is rewritten
+    let qry = format!("update {}.t set b = 'y', a = a * 10 where a = 2", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let blocks_after = block_locations(ctx.clone(), &db).await?;
+    assert_eq!(blocks_after.len(), 2);
+    assert_eq!(
+        blocks_after
+            .iter()
+            .filter(|loc| blocks_before.contains(loc))
+            .count(),
+        1
+    );
+    let qry = format!("select a, b from {}.t order by a", db);
+    let expected = vec![
+        "+----+---+",
+        "| a  | b |",
+        "+----+---+",
+        "| 1  | x |",
+        "| 3  | x |",
+        "| 4  | x |",
+        "| 5  | x |",
+        "| 20 | y |",
+        "+----+---+",
+    ];
+    expects_ok(
+        "updated",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // the statistics of the rewritten block are regenerated
+    let qry = format!("select count(*) from {}.t where a > 10", db);
+    let expected = vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        "| 1        |",
+        "+----------+",
+    ];
+    expects_ok(
+        "pruned",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // nothing to update, no new snapshot
+    let qry = format!("update {}.t set b = 'z' where a = 100", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!(
+        "select count(*) from fuse_history('{}', 't') where operation = 'UPDATE'",
+        db
+    );
+    let expected = vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        "| 1        |",
+        "+----------+",
+    ];
+    expects_ok(
+        "history",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // the deleted rows are left out of the rewritten blocks
+    let qry = format!("delete from {}.t where a = 1", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("update {}.t set b = 'z'", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("select a, b from {}.t order by a", db);
+    let expected = vec![
+        "+----+---+",
+        "| a  | b |",
+        "+----+---+",
+        "| 3  | z |",
+        "| 4  | z |",
+        "| 5  | z |",
+        "| 20 | z |",
+        "+----+---+",
+    ];
+    expects_ok(
+        "all_updated",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    let qry = format!("select * from fuse_verify('{}', 't')", db);
+    expects_ok(
+        "verify",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        vec!["++", "++"],
+    )
+    .await?;
+
+    // unknown columns
+    let qry = format!("update {}.t set c = 1", db);
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("unknown_column", ErrorCode::bad_arguments_code(), res);
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::VacuumTablePlan;
+use databend_query::storages::VacuumReport;
+
+use crate::storages::fuse::table_test_fixture::append_sample_data;
+use crate::storages::fuse::table_test_fixture::append_sample_data_overwrite;
+use crate::storages::fuse::table_test_fixture::check_data_dir;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_vacuum() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // 3 snapshots, the last one overwrites the previous data
+    append_sample_data(1, &fixture).await?;
+    append_sample_data(1, &fixture).await?;
+    append_sample_data_overwrite(1, true, &fixture).await?;
+    check_data_dir(&fixture, "vacuum_before", 3, 3, 3).await;
+
+    let plan = |retain_hours, dry_run| VacuumTablePlan {
+        database: fixture.default_db_name(),
+        table: fixture.default_table_name(),
+        retain_hours,
+        dry_run,
+    };
+
+    // nothing is beyond the retention period
+    let table = fixture.latest_default_table().await?;
+    let report = table.vacuum(ctx.clone(), plan(1, false)).await?;
+    assert_eq!(report, VacuumReport::default());
+    check_data_dir(&fixture, "vacuum_within_retention", 3, 3, 3).await;
+
+    // dry run reports, but removes nothing
+    let report = table.vacuum(ctx.clone(), plan(0, true)).await?;
+    assert_eq!(report.snapshots, 2);
+    assert_eq!(report.segments, 2);
+    assert_eq!(report.blocks, 2);
+    assert!(report.bytes_reclaimed > 0);
+    check_data_dir(&fixture, "vacuum_dry_run", 3, 3, 3).await;
+
+    // only the current snapshot, and the data it references, are kept
+    let dry_run_report = report;
+    let report = table.vacuum(ctx.clone(), plan(0, false)).await?;
+    assert_eq!(report, dry_run_report);
+    check_data_dir(&fixture, "vacuum_after", 1, 1, 1).await;
+
+    // the current snapshot is always retained
+    let table = fixture.latest_default_table().await?;
+    let report = table.vacuum(ctx.clone(), plan(0, false)).await?;
+    assert_eq!(report, VacuumReport::default());
+    check_data_dir(&fixture, "vacuum_again", 1, 1, 1).await;
+
+    Ok(())
+}
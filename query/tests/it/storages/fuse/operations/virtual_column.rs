@@ -0,0 +1,105 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+use common_base::tokio;
+use common_datablocks::pretty_format_blocks;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+#[tokio::test]
+async fn test_fuse_virtual_column() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!("create table {}.t(id int, v variant)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!(
+        "insert into {}.t select 1, parse_json('{{\"a\":{{\"b\":1}}}}')",
+        db
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("alter table {}.t add virtual column v:a.b", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // only the blocks written from now on extract the virtual column
+    for (id, json) in [(2, "{\"a\":{\"b\":2}}"), (3, "{\"a\":\"x\"}")] {
+        let qry = format!("insert into {}.t select {}, parse_json('{}')", db, id, json);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+
+    let select = format!(
+        "select id, v:a.b as b from {}.t where id > 1 order by id",
+        db
+    );
+    let explain = execute_query(ctx.clone(), format!("explain {}", select).as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert!(pretty_format_blocks(&explain)?.contains("with virtual columns v:a.b"));
+    let expected = vec![
+        "+----+------+",
+        "| id | b    |",
+        "+----+------+",
+        "| 2  | 2    |",
+        "| 3  | NULL |",
+        "+----+------+",
+    ];
+    expects_ok(
+        "virtual",
+        execute_query(ctx.clone(), select.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // the first block does not have the virtual column, which is extracted from `v` instead
+    let select = format!("select id, v:a.b as b from {}.t order by id", db);
+    let explain = execute_query(ctx.clone(), format!("explain {}", select).as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert!(!pretty_format_blocks(&explain)?.contains("virtual columns"));
+    let expected = vec![
+        "+----+------+",
+        "| id | b    |",
+        "+----+------+",
+        "| 1  | 1    |",
+        "| 2  | 2    |",
+        "| 3  | NULL |",
+        "+----+------+",
+    ];
+    expects_ok(
+        "extracted",
+        execute_query(ctx.clone(), select.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    let qry = format!("alter table {}.t add virtual column v:a.b", db);
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err(
+        "exists",
+        ErrorCode::virtual_column_already_exists_code(),
+        res,
+    );
+
+    let qry = format!("alter table {}.t add virtual column id + 1", db);
+    let res = execute_command(ctx.clone(), qry.as_str()).await;
+    expects_err("not_path", ErrorCode::syntax_exception_code(), res);
+
+    Ok(())
+}
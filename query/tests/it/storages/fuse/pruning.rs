@@ -23,6 +23,7 @@ use common_meta_types::TableMeta;
 use common_planners::add;
 use common_planners::col;
 use common_planners::lit;
+use common_planners::sort;
 use common_planners::sub;
 use common_planners::CreateTablePlan;
 use common_planners::Extras;
@@ -34,6 +35,7 @@ use databend_query::sql::OPT_KEY_SNAPSHOT_LOCATION;
 use databend_query::storages::fuse::io::MetaReaders;
 use databend_query::storages::fuse::meta::BlockMeta;
 use databend_query::storages::fuse::meta::TableSnapshot;
+use databend_query::storages::fuse::meta::Versioned;
 use databend_query::storages::fuse::pruning::BlockPruner;
 use databend_query::storages::fuse::FUSE_OPT_KEY_BLOCK_PER_SEGMENT;
 use databend_query::storages::fuse::FUSE_OPT_KEY_ROW_PER_BLOCK;
@@ -143,7 +145,9 @@ async fn test_block_pruner() -> Result<()> {
         .unwrap();
 
     let reader = MetaReaders::table_snapshot_reader(ctx.as_ref());
-    let snapshot = reader.read(snapshot_loc.as_str(), None, 1).await?;
+    let snapshot = reader
+        .read(snapshot_loc.as_str(), None, TableSnapshot::VERSION)
+        .await?;
 
     // nothing will be pruned
     let push_downs = None;
@@ -189,6 +193,57 @@ async fn test_block_pruner() -> Result<()> {
 
     assert_eq!((num_blocks - max_val_of_b as usize - 1), blocks.len());
 
+    // limit pushed down, segments beyond the limit are not loaded
+    let mut extra = Extras::default();
+    extra.limit = Some(row_per_block + 1);
+    let blocks = apply_block_pruning(
+        snapshot.clone(),
+        table.get_table_info().schema(),
+        &Some(extra),
+        ctx.clone(),
+    )
+    .await?;
+    assert_eq!(2, blocks.len());
+
+    // limit pushed down along with the filters, the blocks matched may have fewer rows
+    let mut extra = Extras::default();
+    extra.limit = Some(row_per_block + 1);
+    extra.filters = vec![col("a").gt(lit(0u64))];
+    let blocks = apply_block_pruning(
+        snapshot.clone(),
+        table.get_table_info().schema(),
+        &Some(extra),
+        ctx.clone(),
+    )
+    .await?;
+    assert_eq!(num_blocks, blocks.len());
+
+    // top-n pushed down, the blocks beyond the threshold of the first n rows are pruned
+    for (asc, expected_values_of_b) in [(true, vec![0, 1]), (false, vec![8, 9])] {
+        let mut extra = Extras::default();
+        extra.limit = Some(row_per_block + 5);
+        extra.order_by = vec![sort("b", asc, false)];
+        let blocks = apply_block_pruning(
+            snapshot.clone(),
+            table.get_table_info().schema(),
+            &Some(extra),
+            ctx.clone(),
+        )
+        .await?;
+        let mut values_of_b = blocks
+            .iter()
+            .map(|b| b.col_stats[&1].min.as_u64())
+            .collect::<Result<Vec<_>>>()?;
+        values_of_b.sort_unstable();
+        assert_eq!(expected_values_of_b, values_of_b);
+    }
+
+    // min/max statistics of the table summary
+    let (min, max) = snapshot.summary.min_max_of(1).unwrap();
+    assert_eq!(min, &DataValue::UInt64(0));
+    assert_eq!(max, &DataValue::UInt64(num_blocks as u64 - 1));
+    assert_eq!(snapshot.summary.null_count_of(1), Some(0));
+
     Ok(())
 }
 
@@ -278,7 +333,9 @@ async fn test_block_pruner_monotonic() -> Result<()> {
         .get(OPT_KEY_SNAPSHOT_LOCATION)
         .unwrap();
     let reader = MetaReaders::table_snapshot_reader(ctx.as_ref());
-    let snapshot = reader.read(snapshot_loc.as_str(), None, 1).await?;
+    let snapshot = reader
+        .read(snapshot_loc.as_str(), None, TableSnapshot::VERSION)
+        .await?;
 
     // a + b > 20; some blocks pruned
     let mut extra = Extras::default();
@@ -17,9 +17,13 @@ use std::collections::HashMap;
 
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
+use databend_query::storages::fuse::meta::Statistics;
 use databend_query::storages::fuse::statistics::accumulator;
 use databend_query::storages::fuse::statistics::reducers;
+use databend_query::storages::fuse::statistics::ColumnHistogramBuilder;
 use databend_query::storages::fuse::statistics::StatisticsAccumulator;
+use databend_query::storages::index::ColumnStatistics;
+use rand::Rng;
 
 use crate::storages::fuse::table_test_fixture::TestFixture;
 
@@ -32,6 +36,59 @@ fn test_ft_stats_block_stats() -> common_exception::Result<()> {
     let col_stats = r.get(&0).unwrap();
     assert_eq!(col_stats.min, DataValue::Int64(1));
     assert_eq!(col_stats.max, DataValue::Int64(3));
+    assert_eq!(col_stats.null_count, 0);
+    assert_eq!(col_stats.distinct_of_values, Some(3));
+
+    let block = DataBlock::create(
+        DataSchemaRefExt::create(vec![DataField::new_nullable("a", i32::to_data_type())]),
+        vec![Series::from_data(vec![Some(1), None, Some(1), None])],
+    );
+    let r = StatisticsAccumulator::acc_columns(&block)?;
+    let col_stats = r.get(&0).unwrap();
+    assert_eq!(col_stats.null_count, 2);
+    assert_eq!(col_stats.distinct_of_values, Some(1));
+    Ok(())
+}
+
+#[test]
+fn test_ft_stats_string_prefix() -> common_exception::Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", Vu8::to_data_type())]);
+    let column = Series::from_data(vec!["abcfoo", "abczzz", "abcbar"]);
+    let block = DataBlock::create(schema, vec![column]);
+
+    // no truncation
+    let r = accumulator::BlockStatistics::columns_statistics(&block, 0)?;
+    let col_stats = r.get(&0).unwrap();
+    assert_eq!(col_stats.min, DataValue::String(b"abcbar".to_vec()));
+    assert_eq!(col_stats.max, DataValue::String(b"abczzz".to_vec()));
+    assert!(!col_stats.truncated);
+
+    // the strings are not longer than the prefix
+    let r = accumulator::BlockStatistics::columns_statistics(&block, 6)?;
+    assert!(!r.get(&0).unwrap().truncated);
+
+    let r = accumulator::BlockStatistics::columns_statistics(&block, 4)?;
+    let col_stats = r.get(&0).unwrap();
+    assert_eq!(col_stats.min, DataValue::String(b"abcb".to_vec()));
+    assert_eq!(col_stats.max, DataValue::String(b"abc{".to_vec()));
+    assert!(col_stats.truncated);
+
+    // the truncated flag is kept by the reduced statistics
+    let schema = block.schema().clone();
+    let stats = vec![r, StatisticsAccumulator::acc_columns(&block)?];
+    let r = reducers::reduce_block_stats(&stats, &schema)?;
+    assert!(r.get(&0).unwrap().truncated);
+
+    // the bytes of 0xFF are dropped from the end of the prefix of the max
+    let (max, truncated) = accumulator::truncate_max(DataValue::String(vec![1, 0xFF, 2]), 2);
+    assert_eq!(max, DataValue::String(vec![2]));
+    assert!(truncated);
+    let (max, truncated) = accumulator::truncate_max(DataValue::String(vec![0xFF, 0xFF, 2]), 2);
+    assert_eq!(max, DataValue::String(vec![0xFF, 0xFF, 2]));
+    assert!(!truncated);
+    let (min, truncated) = accumulator::truncate_min(DataValue::Int64(1), 2);
+    assert_eq!(min, DataValue::Int64(1));
+    assert!(!truncated);
     Ok(())
 }
 
@@ -70,3 +127,135 @@ fn test_ft_stats_accumulator() -> common_exception::Result<()> {
     // TODO more cases here pls
     Ok(())
 }
+
+#[test]
+fn test_ft_stats_histogram_sampling() -> common_exception::Result<()> {
+    let values = (0..1000).map(|v| v % 100).collect::<Vec<i32>>();
+    let column = Series::from_data(values);
+
+    let sample_size = 200;
+    let mut builder = ColumnHistogramBuilder::new(sample_size);
+    builder.append(&column);
+    builder.append(&Series::from_data(vec![None::<i32>, None]));
+    assert_eq!(builder.sampled_count(), sample_size as u64);
+
+    let histogram = builder.finish(10, 5);
+    assert_eq!(histogram.null_count, 2);
+    assert!(histogram.distinct_count <= 1000);
+    assert!(histogram.buckets.len() <= 10);
+    assert!(histogram.most_common_values.len() <= 5);
+
+    // counts of the sample are scaled up to all the values
+    let total = histogram.buckets.iter().map(|b| b.count).sum::<u64>();
+    assert!((990..=1010).contains(&total));
+
+    // buckets are ordered and disjoint
+    for pair in histogram.buckets.windows(2) {
+        match (&pair[0].upper_bound, &pair[1].lower_bound) {
+            (DataValue::Int64(upper), DataValue::Int64(lower)) => assert!(upper < lower),
+            _ => unreachable!(),
+        }
+    }
+    Ok(())
+}
+
+fn random_statistics(rng: &mut impl Rng) -> Statistics {
+    let row_count = rng.gen_range(1..1000u64);
+    let null_count = rng.gen_range(0..=row_count);
+    let min = rng.gen_range(-1000..1000i64);
+    let max = min + rng.gen_range(0..1000i64);
+    let col_stats = HashMap::from([(0, ColumnStatistics {
+        min: DataValue::Int64(min),
+        max: DataValue::Int64(max),
+        null_count,
+        in_memory_size: row_count * 4,
+        distinct_of_values: match rng.gen_bool(0.8) {
+            true => Some(rng.gen_range(0..=row_count - null_count)),
+            false => None,
+        },
+        truncated: false,
+    })]);
+    Statistics {
+        row_count,
+        block_count: 1,
+        uncompressed_byte_size: row_count * 4,
+        compressed_byte_size: row_count * 2,
+        index_byte_size: 0,
+        index_byte_sizes: Default::default(),
+        col_stats,
+    }
+}
+
+fn assert_statistics_eq(l: &Statistics, r: &Statistics) {
+    assert_eq!(l.row_count, r.row_count);
+    assert_eq!(l.block_count, r.block_count);
+    assert_eq!(l.uncompressed_byte_size, r.uncompressed_byte_size);
+    assert_eq!(l.compressed_byte_size, r.compressed_byte_size);
+    assert_eq!(l.index_byte_size, r.index_byte_size);
+    assert_eq!(l.min_max_of(0), r.min_max_of(0));
+    assert_eq!(l.null_count_of(0), r.null_count_of(0));
+    assert_eq!(l.distinct_of_values_of(0), r.distinct_of_values_of(0));
+}
+
+#[test]
+fn test_ft_stats_merge_properties() -> common_exception::Result<()> {
+    let schema = DataSchema::new(vec![DataField::new("a", i32::to_data_type())]);
+    let mut rng = rand::thread_rng();
+    for _ in 0..100 {
+        let a = random_statistics(&mut rng);
+        let b = random_statistics(&mut rng);
+        let c = random_statistics(&mut rng);
+
+        // commutative
+        let ab = a.merge(&b, &schema)?;
+        assert_statistics_eq(&ab, &b.merge(&a, &schema)?);
+
+        // associative
+        let ab_c = ab.merge(&c, &schema)?;
+        let a_bc = a.merge(&b.merge(&c, &schema)?, &schema)?;
+        assert_statistics_eq(&ab_c, &a_bc);
+
+        // the counts add up, the bounds cover both sides
+        assert_eq!(ab_c.row_count, a.row_count + b.row_count + c.row_count);
+        assert_eq!(ab_c.block_count, 3);
+        let null_count = [&a, &b, &c]
+            .iter()
+            .map(|s| s.null_count_of(0).unwrap())
+            .sum::<u64>();
+        assert_eq!(ab_c.null_count_of(0), Some(null_count));
+        let (min, max) = ab_c.min_max_of(0).unwrap();
+        for s in [&a, &b, &c] {
+            let (l, r) = s.min_max_of(0).unwrap();
+            assert!(min.as_i64()? <= l.as_i64()? && max.as_i64()? >= r.as_i64()?);
+        }
+
+        // the distinct values are known only if they are known on all sides
+        let distinct = [&a, &b, &c]
+            .iter()
+            .map(|s| s.distinct_of_values_of(0))
+            .sum::<Option<u64>>();
+        assert_eq!(ab_c.distinct_of_values_of(0), distinct);
+        if let Some(distinct) = ab_c.distinct_of_values_of(0) {
+            assert!(distinct <= ab_c.row_count - null_count);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_ft_stats_merge_saturating() -> common_exception::Result<()> {
+    let schema = DataSchema::new(vec![DataField::new("a", i32::to_data_type())]);
+    let mut rng = rand::thread_rng();
+    let mut a = random_statistics(&mut rng);
+    a.row_count = u64::MAX;
+    a.uncompressed_byte_size = u64::MAX - 1;
+    a.col_stats.get_mut(&0).unwrap().null_count = u64::MAX;
+    let b = random_statistics(&mut rng);
+
+    let merged = a.merge(&b, &schema)?;
+    assert_eq!(merged.row_count, u64::MAX);
+    assert_eq!(merged.uncompressed_byte_size, u64::MAX);
+    assert_eq!(merged.null_count_of(0), Some(u64::MAX));
+    assert_eq!(merged.block_count, 2);
+    Ok(())
+}
@@ -16,6 +16,7 @@
 use std::default::Default;
 
 use common_base::tokio;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::TableInfo;
 use common_planners::ReadDataSourcePlan;
@@ -25,10 +26,17 @@ use databend_query::interpreters::CreateTableInterpreter;
 use databend_query::interpreters::InterpreterFactory;
 use databend_query::sql::PlanParser;
 use databend_query::sql::OPT_KEY_DATABASE_ID;
+use databend_query::storages::fuse::meta::Encoding;
+use databend_query::storages::fuse::meta::TableSnapshot;
 use databend_query::storages::fuse::FuseTable;
+use databend_query::storages::fuse::FUSE_OPT_KEY_ROW_PER_BLOCK;
 use databend_query::storages::ToReadDataSourcePlan;
 use futures::TryStreamExt;
 
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_err;
+use crate::storages::fuse::table_test_fixture::expects_ok;
 use crate::storages::fuse::table_test_fixture::TestFixture;
 
 #[tokio::test]
@@ -278,6 +286,103 @@ async fn test_fuse_table_optimize() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_fuse_table_limit() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    // one block for each of the values 1..=10, 3 rows per block
+    let mut create_table_plan = fixture.default_crate_table_plan();
+    create_table_plan
+        .table_meta
+        .options
+        .insert(FUSE_OPT_KEY_ROW_PER_BLOCK.to_owned(), "3".to_owned());
+    let interpreter = CreateTableInterpreter::try_create(ctx.clone(), create_table_plan)?;
+    interpreter.execute(None).await?;
+    let table = fixture.latest_default_table().await?;
+    let stream = TestFixture::gen_sample_blocks_stream_ex(10, 3, 1);
+    let r = table.append_data(ctx.clone(), stream).await?;
+    table
+        .commit_insertion(ctx.clone(), r.try_collect().await?, false)
+        .await?;
+
+    let table_name = format!(
+        "{}.{}",
+        fixture.default_db_name(),
+        fixture.default_table_name()
+    );
+    let cases = [
+        // only the blocks needed by the limit are read
+        ("", 4, 4, 6),
+        // the filtered blocks may have no rows matched, all of them may be read
+        ("where id % 5 = 0", 4, 4, 30),
+        ("where id > 8", 10, 6, 6),
+    ];
+    for (filter, limit, expected_rows, max_scanned_rows) in cases {
+        let query = format!("select * from {} {} limit {}", table_name, filter, limit);
+        let ctx = ctx.get_current_session().create_query_context().await?;
+        let plan = PlanParser::parse(ctx.clone(), &query).await?;
+        let interpreter = InterpreterFactory::get(ctx.clone(), plan)?;
+        let blocks = interpreter
+            .execute(None)
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        let rows = blocks.iter().map(|b| b.num_rows()).sum::<usize>();
+        assert_eq!(rows, expected_rows, "{}", query);
+        assert!(
+            ctx.get_scan_progress_value().rows <= max_scanned_rows,
+            "{}",
+            query
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_snapshot_encoding() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!("create table {}.t(a int)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    execute_command(ctx.clone(), "set snapshot_encoding = 'msgpack'").await?;
+    let qry = format!("insert into {}.t values(1)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    let table = ctx.get_table(&db, "t").await?;
+    let loc = FuseTable::try_from_table(table.as_ref())?
+        .snapshot_loc()
+        .unwrap();
+    let bytes = ctx.get_storage_operator()?.object(&loc).read().await?;
+    assert_ne!(bytes[0], b'{');
+    let snapshot: TableSnapshot = Encoding::decode(&bytes)?;
+    assert_eq!(snapshot.summary.row_count, 1);
+
+    // the snapshots in different encodings are read alike
+    execute_command(ctx.clone(), "set snapshot_encoding = 'json'").await?;
+    let qry = format!("insert into {}.t values(2)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let expected = vec!["+---+", "| a |", "+---+", "| 1 |", "| 2 |", "+---+"];
+    let select = format!("select * from {}.t order by a", db);
+    expects_ok(
+        "mixed_encodings",
+        execute_query(ctx.clone(), select.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    execute_command(ctx.clone(), "set snapshot_encoding = 'bincode'").await?;
+    expects_err(
+        "unknown_encoding",
+        ErrorCode::bad_arguments_code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+    Ok(())
+}
+
 #[test]
 fn test_parse_storage_prefix() -> Result<()> {
     let mut tbl_info = TableInfo::default();
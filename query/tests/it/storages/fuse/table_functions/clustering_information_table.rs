@@ -0,0 +1,140 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::storages::fuse::meta::BlockMeta;
+use databend_query::storages::fuse::meta::Compression;
+use databend_query::storages::fuse::ClusterKey;
+use databend_query::storages::fuse::ClusteringInformation;
+use databend_query::storages::index::ColumnStatistics;
+use tokio_stream::StreamExt;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+fn block_of_range(min: i64, max: i64) -> BlockMeta {
+    BlockMeta {
+        row_count: 2,
+        block_size: 0,
+        file_size: 0,
+        col_stats: HashMap::from([(0, ColumnStatistics {
+            min: DataValue::Int64(min),
+            max: DataValue::Int64(max),
+            null_count: 0,
+            in_memory_size: 0,
+            distinct_of_values: None,
+            truncated: false,
+        })]),
+        col_metas: HashMap::new(),
+        location: ("".to_owned(), 0),
+        compression: Compression::Lz4Raw,
+        created_by: None,
+        created_on: None,
+        deletion_vector: None,
+        virtual_block: None,
+    }
+}
+
+#[test]
+fn test_clustering_statistics() -> Result<()> {
+    // no blocks
+    let stats = ClusteringInformation::clustering_statistics(&[], &ClusterKey::Column(0));
+    assert_eq!(stats.total_block_count, 0);
+    assert_eq!(stats.average_depth, 0.0);
+
+    // [1, 3] and [2, 4] overlap, [5, 5] is constant
+    let blocks = vec![
+        block_of_range(1, 3),
+        block_of_range(2, 4),
+        block_of_range(5, 5),
+    ];
+    let stats = ClusteringInformation::clustering_statistics(&blocks, &ClusterKey::Column(0));
+    assert_eq!(stats.total_block_count, 3);
+    assert_eq!(stats.constant_block_count, 1);
+    assert_eq!(stats.average_overlaps, 2.0 / 3.0);
+    // depths of the points 1, 2, 3, 4, 5 are 1, 2, 2, 1, 1
+    assert_eq!(stats.average_depth, 7.0 / 5.0);
+    assert_eq!(
+        stats.block_depth_histogram,
+        BTreeMap::from([(1, 3), (2, 2)])
+    );
+
+    // well clustered
+    let blocks = vec![block_of_range(1, 2), block_of_range(3, 4)];
+    let stats = ClusteringInformation::clustering_statistics(&blocks, &ClusterKey::Column(0));
+    assert_eq!(stats.average_overlaps, 0.0);
+    assert_eq!(stats.average_depth, 1.0);
+
+    // columns without statistics are ignored
+    let stats = ClusteringInformation::clustering_statistics(&blocks, &ClusterKey::Column(1));
+    assert_eq!(stats.total_block_count, 2);
+    assert_eq!(stats.average_depth, 0.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_clustering_information_table_read() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!("create table {}.t(a int) cluster by (a)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    for values in ["(1), (3)", "(2), (4)", "(5), (5)"] {
+        let qry = format!("insert into {}.t values {}", db, values);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+    }
+
+    let expected = vec![
+        "+-----------------+-------------------+----------------------+-----------------------+",
+        "| cluster_by_keys | total_block_count | constant_block_count | block_depth_histogram |",
+        "+-----------------+-------------------+----------------------+-----------------------+",
+        "| (a)             | 3                 | 1                    | {\"1\":3,\"2\":2}         |",
+        "+-----------------+-------------------+----------------------+-----------------------+",
+    ];
+    let qry = format!(
+        "select cluster_by_keys, total_block_count, constant_block_count, block_depth_histogram \
+         from clustering_information('{}', 't')",
+        db
+    );
+    expects_ok(
+        "clustering_information",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // table which is not clustered
+    fixture.create_default_table().await?;
+    let qry = format!(
+        "select * from clustering_information('{}', '{}')",
+        db,
+        fixture.default_table_name()
+    );
+    let output_stream = execute_query(ctx.clone(), qry.as_str()).await?;
+    expects_err(
+        "not_clustered",
+        ErrorCode::bad_arguments_code(),
+        output_stream.collect::<Result<Vec<DataBlock>>>().await,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use databend_query::sessions::QueryContext;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+async fn query_values(ctx: Arc<QueryContext>, qry: String) -> Result<Vec<String>> {
+    let blocks = execute_query(ctx, qry.as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let mut values = blocks
+        .iter()
+        .flat_map(|b| b.column(0).to_values())
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>();
+    values.sort();
+    Ok(values)
+}
+
+#[tokio::test]
+async fn test_fuse_block_table_read() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // empty table
+    let qry = format!("select count(*) from fuse_block('{}', '{}')", db, tbl);
+    expects_ok(
+        "empty_table",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        vec![
+            "+----------+",
+            "| count(*) |",
+            "+----------+",
+            "| 0        |",
+            "+----------+",
+        ],
+    )
+    .await?;
+
+    append_sample_data(1, &fixture).await?;
+    append_sample_data(1, &fixture).await?;
+
+    let qry = format!(
+        "select row_count from fuse_block('{}', '{}') where created_on is not null",
+        db, tbl
+    );
+    expects_ok(
+        "blocks",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        vec![
+            "+-----------+",
+            "| row_count |",
+            "+-----------+",
+            "| 3         |",
+            "| 3         |",
+            "+-----------+",
+        ],
+    )
+    .await?;
+
+    // each block is created by the snapshot which appends it
+    let created_by = format!("select created_by from fuse_block('{}', '{}')", db, tbl);
+    let snapshots = format!("select snapshot_id from fuse_history('{}', '{}')", db, tbl);
+    assert_eq!(
+        query_values(ctx.clone(), created_by.clone()).await?,
+        query_values(ctx.clone(), snapshots.clone()).await?
+    );
+
+    // the merged block is created by the snapshot of the compaction
+    let compact = format!("optimize table {}.{} compact", db, tbl);
+    execute_command(ctx.clone(), compact.as_str()).await?;
+    let latest = format!("{} limit 1", snapshots);
+    assert_eq!(
+        query_values(ctx.clone(), created_by).await?,
+        query_values(ctx.clone(), latest).await?
+    );
+
+    Ok(())
+}
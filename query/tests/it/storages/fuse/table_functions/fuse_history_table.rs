@@ -88,10 +88,10 @@ async fn test_fuse_history_table_read() -> Result<()> {
 
     {
         let expected = vec![
-            "+-------------+-------------------+----------------+----------------------+---------------+-------------+-----------+--------------------+------------------+",
-            "| snapshot_id | snapshot_location | format_version | previous_snapshot_id | segment_count | block_count | row_count | bytes_uncompressed | bytes_compressed |",
-            "+-------------+-------------------+----------------+----------------------+---------------+-------------+-----------+--------------------+------------------+",
-            "+-------------+-------------------+----------------+----------------------+---------------+-------------+-----------+--------------------+------------------+",
+            "+-------------+-------------------+----------------+----------------------+---------------+-------------+-----------+--------------------+------------------+-------------+---------------------+-----------+-----------+----------+------+",
+            "| snapshot_id | snapshot_location | format_version | previous_snapshot_id | segment_count | block_count | row_count | bytes_uncompressed | bytes_compressed | bytes_index | bytes_index_by_type | timestamp | operation | query_id | user |",
+            "+-------------+-------------------+----------------+----------------------+---------------+-------------+-----------+--------------------+------------------+-------------+---------------------+-----------+-----------+----------+------+",
+            "+-------------+-------------------+----------------+----------------------+---------------+-------------+-----------+--------------------+------------------+-------------+---------------------+-----------+-----------+----------+------+",
 
         ];
 
@@ -147,6 +147,26 @@ async fn test_fuse_history_table_read() -> Result<()> {
         .await?;
     }
 
+    {
+        let expected = vec![
+            "+-----------+",
+            "| operation |",
+            "+-----------+",
+            "| INSERT    |",
+            "+-----------+",
+        ];
+        let qry = format!(
+            "select operation from fuse_history('{}', '{}') where query_id is not null",
+            db, tbl
+        );
+        expects_ok(
+            "check_operation",
+            execute_query(ctx.clone(), qry.as_str()).await,
+            expected,
+        )
+        .await?;
+    }
+
     {
         // another 5 blocks, 15 rows here
         append_sample_data(5, &fixture).await?;
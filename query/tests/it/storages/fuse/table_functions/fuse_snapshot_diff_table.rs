@@ -0,0 +1,110 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::sessions::QueryContext;
+use futures::TryStreamExt;
+use tokio_stream::StreamExt;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+async fn snapshot_id_of(ctx: Arc<QueryContext>, db: &str, tbl: &str, rows: u64) -> Result<String> {
+    let qry = format!(
+        "select snapshot_id from fuse_history('{}', '{}') where row_count = {}",
+        db, tbl, rows
+    );
+    let blocks = execute_query(ctx, qry.as_str())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    Ok(String::from_utf8(blocks[0].column(0).get(0).as_string()?)?)
+}
+
+#[tokio::test]
+async fn test_fuse_snapshot_diff_table_read() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(1, &fixture).await?;
+    append_sample_data(1, &fixture).await?;
+
+    let first = snapshot_id_of(ctx.clone(), &db, &tbl, 3).await?;
+    let second = snapshot_id_of(ctx.clone(), &db, &tbl, 6).await?;
+    let qry = |from: &str, to: &str| {
+        format!(
+            "select object_type, change, row_count_delta \
+             from fuse_snapshot_diff('{}', '{}', '{}', '{}') order by object_type",
+            db, tbl, from, to
+        )
+    };
+
+    expects_ok(
+        "appended",
+        execute_query(ctx.clone(), qry(&first, &second).as_str()).await,
+        vec![
+            "+-------------+--------+-----------------+",
+            "| object_type | change | row_count_delta |",
+            "+-------------+--------+-----------------+",
+            "| block       | added  | 3               |",
+            "| segment     | added  | 3               |",
+            "+-------------+--------+-----------------+",
+        ],
+    )
+    .await?;
+
+    expects_ok(
+        "reversed",
+        execute_query(ctx.clone(), qry(&second, &first).as_str()).await,
+        vec![
+            "+-------------+---------+-----------------+",
+            "| object_type | change  | row_count_delta |",
+            "+-------------+---------+-----------------+",
+            "| block       | removed | -3              |",
+            "| segment     | removed | -3              |",
+            "+-------------+---------+-----------------+",
+        ],
+    )
+    .await?;
+
+    expects_ok(
+        "same_snapshot",
+        execute_query(ctx.clone(), qry(&first, &first).as_str()).await,
+        vec![
+            "+-------------+--------+-----------------+",
+            "| object_type | change | row_count_delta |",
+            "+-------------+--------+-----------------+",
+            "+-------------+--------+-----------------+",
+        ],
+    )
+    .await?;
+
+    // snapshot which is not in the history of the table
+    let unknown = "00000000000000000000000000000000";
+    let output_stream = execute_query(ctx.clone(), qry(&first, unknown).as_str()).await?;
+    expects_err(
+        "unknown_snapshot",
+        ErrorCode::table_historical_data_not_found_code(),
+        output_stream.collect::<Result<Vec<DataBlock>>>().await,
+    );
+
+    Ok(())
+}
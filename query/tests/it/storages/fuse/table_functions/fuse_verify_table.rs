@@ -0,0 +1,96 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_base::tokio;
+use common_exception::Result;
+use databend_query::storages::fuse::FUSE_TBL_BLOCK_PREFIX;
+use walkdir::WalkDir;
+
+use crate::storages::fuse::table_test_fixture::*;
+
+#[tokio::test]
+async fn test_fuse_verify_table_read() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    let qry = format!(
+        "select category, problem from fuse_verify('{}', '{}')",
+        db, tbl
+    );
+    let empty = vec![
+        "+----------+---------+",
+        "| category | problem |",
+        "+----------+---------+",
+        "+----------+---------+",
+    ];
+
+    // empty table
+    expects_ok(
+        "empty_table",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        empty.clone(),
+    )
+    .await?;
+
+    // intact table
+    append_sample_data(1, &fixture).await?;
+    append_sample_data(1, &fixture).await?;
+    expects_ok(
+        "intact_table",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        empty.clone(),
+    )
+    .await?;
+
+    // the purged history is not a problem
+    let purge = format!("optimize table {}.{} purge", db, tbl);
+    execute_command(ctx.clone(), purge.as_str()).await?;
+    expects_ok(
+        "purged_table",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        empty,
+    )
+    .await?;
+
+    // remove one of the blocks
+    let data_path = ctx.get_config().storage.fs.data_path;
+    let block = WalkDir::new(data_path)
+        .into_iter()
+        .map(|e| e.unwrap())
+        .find(|e| {
+            e.file_type().is_file() && e.path().to_str().unwrap().contains(FUSE_TBL_BLOCK_PREFIX)
+        })
+        .unwrap();
+    std::fs::remove_file(block.path())?;
+
+    let expected = vec![
+        "+----------+----------------+",
+        "| category | problem        |",
+        "+----------+----------------+",
+        "| block    | file not found |",
+        "+----------+----------------+",
+    ];
+    expects_ok(
+        "missing_block",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    Ok(())
+}
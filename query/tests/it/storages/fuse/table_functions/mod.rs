@@ -13,4 +13,8 @@
 //  limitations under the License.
 //
 
+mod clustering_information_table;
+mod fuse_block_table;
 mod fuse_history_table;
+mod fuse_snapshot_diff_table;
+mod fuse_verify_table;
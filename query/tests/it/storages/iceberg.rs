@@ -0,0 +1,211 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_base::tokio;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::col;
+use common_planners::lit;
+use common_planners::Extras;
+use databend_query::storages::iceberg::AvroBytes;
+use databend_query::storages::iceberg::AvroScalar;
+use databend_query::storages::iceberg::DataFile;
+use databend_query::storages::iceberg::FieldSummary;
+use databend_query::storages::iceberg::IcebergPruner;
+use databend_query::storages::iceberg::IcebergType;
+use databend_query::storages::iceberg::KeyValue;
+use databend_query::storages::iceberg::ManifestFile;
+use databend_query::storages::iceberg::PartitionField;
+use databend_query::storages::iceberg::TableMetadata;
+
+const METADATA: &str = r#"{
+  "format-version": 2,
+  "table-uuid": "9c12d441-03fe-4693-9a96-a0705ddf69c1",
+  "location": "s3://bucket/warehouse/db/t",
+  "last-sequence-number": 1,
+  "last-updated-ms": 1602638573590,
+  "last-column-id": 3,
+  "current-schema-id": 1,
+  "schemas": [
+    {"type": "struct", "schema-id": 0, "fields": [
+      {"id": 1, "name": "id", "required": true, "type": "long"}
+    ]},
+    {"type": "struct", "schema-id": 1, "fields": [
+      {"id": 1, "name": "id", "required": true, "type": "long"},
+      {"id": 2, "name": "ts", "required": false, "type": "timestamptz"},
+      {"id": 3, "name": "name", "required": false, "type": "string"}
+    ]}
+  ],
+  "default-spec-id": 0,
+  "partition-specs": [
+    {"spec-id": 0, "fields": [
+      {"name": "id", "transform": "identity", "source-id": 1, "field-id": 1000},
+      {"name": "ts_day", "transform": "day", "source-id": 2, "field-id": 1001}
+    ]}
+  ],
+  "current-snapshot-id": 3055729675574597004,
+  "snapshots": [
+    {
+      "snapshot-id": 3055729675574597004,
+      "timestamp-ms": 1555100955770,
+      "manifest-list": "s3://bucket/warehouse/db/t/metadata/snap-3055729675574597004.avro"
+    }
+  ]
+}"#;
+
+fn long_bytes(v: i64) -> Option<AvroBytes> {
+    Some(AvroBytes(v.to_le_bytes().to_vec()))
+}
+
+fn data_file(bounds: Option<(i64, i64)>, id: AvroScalar) -> DataFile {
+    let bound = |v: Option<i64>| {
+        v.map(|v| {
+            vec![KeyValue {
+                key: 1,
+                value: AvroBytes(v.to_le_bytes().to_vec()),
+            }]
+        })
+    };
+    DataFile {
+        content: 0,
+        file_path: "s3://bucket/warehouse/db/t/data/00000-0.parquet".to_string(),
+        file_format: "PARQUET".to_string(),
+        partition: BTreeMap::from([
+            ("id".to_string(), id),
+            ("ts_day".to_string(), AvroScalar::Null),
+        ]),
+        record_count: 10,
+        file_size_in_bytes: 1024,
+        null_value_counts: Some(vec![KeyValue { key: 1, value: 0 }]),
+        lower_bounds: bound(bounds.map(|(min, _)| min)),
+        upper_bounds: bound(bounds.map(|(_, max)| max)),
+    }
+}
+
+#[test]
+fn test_iceberg_metadata() -> Result<()> {
+    let metadata: TableMetadata = serde_json::from_str(METADATA)?;
+    assert_eq!(metadata.format_version, 2);
+    assert_eq!(metadata.current_schema()?.schema_id, 1);
+
+    let schema = metadata.data_schema()?;
+    let expected = DataSchemaRefExt::create(vec![
+        DataField::new("id", i64::to_data_type()),
+        DataField::new_nullable("ts", TimestampType::new_impl(6)),
+        DataField::new_nullable("name", Vu8::to_data_type()),
+    ]);
+    assert_eq!(schema, expected);
+
+    let snapshot = metadata.current_snapshot()?.unwrap();
+    assert_eq!(
+        snapshot.manifest_list.as_deref(),
+        Some("s3://bucket/warehouse/db/t/metadata/snap-3055729675574597004.avro")
+    );
+    assert_eq!(metadata.partition_specs()[&0].len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_iceberg_partition_transforms() -> Result<()> {
+    let field = |transform: &str| PartitionField {
+        source_id: 1,
+        name: "p".to_string(),
+        transform: transform.to_string(),
+    };
+    let range = |transform: &str, source: IcebergType, min: i64, max: i64| {
+        field(transform).source_range(&source, DataValue::Int64(min), DataValue::Int64(max))
+    };
+    let int64 = |min: i64, max: i64| Some((DataValue::Int64(min), DataValue::Int64(max)));
+
+    // 2022-01-01 and 2022-02-28
+    assert_eq!(
+        range("day", IcebergType::Date, 18993, 19051),
+        int64(18993, 19051)
+    );
+    // 1970-02 to 1970-03
+    assert_eq!(range("month", IcebergType::Date, 1, 2), int64(31, 89));
+    assert_eq!(range("year", IcebergType::Date, 1, 1), int64(365, 729));
+    assert_eq!(
+        range("day", IcebergType::Timestamp, 1, 1),
+        int64(86_400_000_000, 172_800_000_000 - 1)
+    );
+    assert_eq!(
+        range("hour", IcebergType::Timestamp, 2, 3),
+        int64(7_200_000_000, 14_400_000_000 - 1)
+    );
+    assert_eq!(range("hour", IcebergType::Date, 2, 3), None);
+    assert_eq!(
+        range("truncate[10]", IcebergType::Int, 20, 30),
+        int64(20, 39)
+    );
+    assert_eq!(range("bucket[16]", IcebergType::Long, 3, 3), None);
+    assert_eq!(range("identity", IcebergType::Long, 3, 5), int64(3, 5));
+
+    assert_eq!(
+        field("month").result_type(&IcebergType::Timestamp),
+        IcebergType::Int
+    );
+    assert_eq!(
+        IcebergType::Int.decode(&7i32.to_le_bytes()),
+        Some(DataValue::Int64(7))
+    );
+    assert_eq!(IcebergType::Long.decode(&[1, 2]), None);
+    assert_eq!(
+        IcebergType::String.decode(b"abc"),
+        Some(DataValue::String(b"abc".to_vec()))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_iceberg_pruner() -> Result<()> {
+    let ctx = crate::tests::create_query_context().await?;
+    let metadata: TableMetadata = serde_json::from_str(METADATA)?;
+    let schema = metadata.data_schema()?;
+    let push_downs = Some(Extras {
+        filters: vec![col("id").gt(lit(100i64))],
+        ..Extras::default()
+    });
+    let pruner = IcebergPruner::try_create(&ctx, schema, &metadata, &push_downs)?;
+
+    // by the bounds of the columns
+    assert!(!pruner.keep_file(0, &data_file(Some((1, 50)), AvroScalar::Null))?);
+    assert!(pruner.keep_file(0, &data_file(Some((90, 200)), AvroScalar::Null))?);
+    // by the partition values, if the bounds are missing
+    assert!(!pruner.keep_file(0, &data_file(None, AvroScalar::Long(7)))?);
+    assert!(pruner.keep_file(0, &data_file(None, AvroScalar::Long(101)))?);
+    assert!(pruner.keep_file(0, &data_file(None, AvroScalar::Null))?);
+
+    // by the summaries of the partition values
+    let manifest = |lower: i64, upper: i64| ManifestFile {
+        partitions: Some(vec![
+            FieldSummary {
+                contains_null: false,
+                lower_bound: long_bytes(lower),
+                upper_bound: long_bytes(upper),
+            },
+            FieldSummary {
+                contains_null: true,
+                lower_bound: None,
+                upper_bound: None,
+            },
+        ]),
+        ..ManifestFile::create("s3://bucket/warehouse/db/t/metadata/m0.avro".to_string(), 0)
+    };
+    assert!(!pruner.keep_manifest(&manifest(1, 50))?);
+    assert!(pruner.keep_manifest(&manifest(1, 500))?);
+    Ok(())
+}
@@ -387,6 +387,23 @@ async fn test_bloom_indexer_single_column_prune() -> Result<()> {
             expr: col("ColumnString").eq(lit("batman".as_bytes())),
             expected_eval_result: BloomFilterExprEvalResult::False,
         },
+        Test {
+            // the constant is cast to the type of the column
+            name: "ColumnUInt32 = '1213'",
+            expr: col("ColumnUInt32").eq(lit("1213".as_bytes())),
+            expected_eval_result: BloomFilterExprEvalResult::False,
+        },
+        Test {
+            name: "ColumnUInt32 = '3'",
+            expr: col("ColumnUInt32").eq(lit("3".as_bytes())),
+            expected_eval_result: BloomFilterExprEvalResult::Unknown,
+        },
+        Test {
+            // the constant can't be cast to the type of the column
+            name: "ColumnUInt32 = 'Batman'",
+            expr: col("ColumnUInt32").eq(lit("Batman".as_bytes())),
+            expected_eval_result: BloomFilterExprEvalResult::Unknown,
+        },
     ];
 
     let indexer = create_bloom_indexer().await?;
@@ -16,3 +16,4 @@ mod bloom_filter;
 mod index_min_max;
 mod index_sparse;
 mod range_filter;
+mod row_group_filter;
@@ -42,18 +42,24 @@ async fn test_range_filter() -> Result<()> {
         max: DataValue::Int64(20),
         null_count: 1,
         in_memory_size: 0,
+        distinct_of_values: None,
+        truncated: false,
     });
     stats.insert(1u32, ColumnStatistics {
         min: DataValue::Int64(3),
         max: DataValue::Int64(10),
         null_count: 0,
         in_memory_size: 0,
+        distinct_of_values: None,
+        truncated: false,
     });
     stats.insert(2u32, ColumnStatistics {
         min: DataValue::String("abc".as_bytes().to_vec()),
         max: DataValue::String("bcd".as_bytes().to_vec()),
         null_count: 0,
         in_memory_size: 0,
+        distinct_of_values: None,
+        truncated: false,
     });
 
     struct Test {
@@ -184,6 +190,123 @@ async fn test_range_filter() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_range_filter_date_functions() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("ts", TimestampType::new_impl(6)),
+        DataField::new("d", DateType::new_impl()),
+    ]);
+
+    // ts in ['2022-12-31 10:00:00', '2023-01-01 10:00:00'], d = '2023-01-01'
+    let mut stats: ColumnsStatistics = HashMap::new();
+    stats.insert(0u32, ColumnStatistics {
+        min: DataValue::Int64(1672480800000000),
+        max: DataValue::Int64(1672567200000000),
+        null_count: 0,
+        in_memory_size: 0,
+        distinct_of_values: None,
+        truncated: false,
+    });
+    stats.insert(1u32, ColumnStatistics {
+        min: DataValue::Int64(19358),
+        max: DataValue::Int64(19358),
+        null_count: 0,
+        in_memory_size: 0,
+        distinct_of_values: None,
+        truncated: false,
+    });
+
+    let func =
+        |name: &str, column: &str| Expression::create_scalar_function(name, vec![col(column)]);
+    let tests = vec![
+        (
+            "toYYYYMMDD(ts) = 20230101",
+            func("toYYYYMMDD", "ts").eq(lit(20230101u32)),
+            true,
+        ),
+        (
+            "toYYYYMMDD(ts) = 20230102",
+            func("toYYYYMMDD", "ts").eq(lit(20230102u32)),
+            false,
+        ),
+        (
+            "toYYYYMMDD(ts) > 20221230",
+            func("toYYYYMMDD", "ts").gt(lit(20221230u32)),
+            true,
+        ),
+        (
+            "20230101 < toYYYYMMDD(ts)",
+            lit(20230101u32).lt(func("toYYYYMMDD", "ts")),
+            false,
+        ),
+        (
+            "toYYYYMM(ts) = 202302",
+            func("toYYYYMM", "ts").eq(lit(202302u32)),
+            false,
+        ),
+        (
+            "toYYYYMM(ts) <= 202212",
+            func("toYYYYMM", "ts").lt_eq(lit(202212u32)),
+            true,
+        ),
+        (
+            "toYear(ts) < 2022",
+            func("toYear", "ts").lt(lit(2022u16)),
+            false,
+        ),
+        (
+            "toYYYYMMDD(d) != 20230101",
+            func("toYYYYMMDD", "d").not_eq(lit(20230101u32)),
+            false,
+        ),
+        (
+            "toYYYYMMDD(d) >= 20230101",
+            func("toYYYYMMDD", "d").gt_eq(lit(20230101u32)),
+            true,
+        ),
+    ];
+
+    let ctx = create_query_context().await?;
+    for (name, expr, expect) in tests {
+        let prune = RangeFilter::try_create(ctx.clone(), &expr, schema.clone())?;
+        assert_eq!(expect, prune.eval(&stats)?, "{}", name);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_range_filter_truncated_strings() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("c", Vu8::to_data_type())]);
+
+    // c in ['abcfoo', 'abczzz'], of which the min and the max are truncated to 3 bytes
+    let mut stats: ColumnsStatistics = HashMap::new();
+    stats.insert(0u32, ColumnStatistics {
+        min: DataValue::String("abc".as_bytes().to_vec()),
+        max: DataValue::String("abd".as_bytes().to_vec()),
+        null_count: 0,
+        in_memory_size: 0,
+        distinct_of_values: None,
+        truncated: true,
+    });
+
+    let tests = vec![
+        ("c = 'abcfoo'", col("c").eq(lit("abcfoo".as_bytes())), true),
+        ("c = 'abczzz'", col("c").eq(lit("abczzz".as_bytes())), true),
+        ("c > 'abd'", col("c").gt(lit("abd".as_bytes())), false),
+        ("c < 'abc'", col("c").lt(lit("abc".as_bytes())), false),
+        ("c >= 'abcz'", col("c").gt_eq(lit("abcz".as_bytes())), true),
+    ];
+
+    let ctx = create_query_context().await?;
+    for (name, expr, expect) in tests {
+        let prune = RangeFilter::try_create(ctx.clone(), &expr, schema.clone())?;
+        assert_eq!(expect, prune.eval(&stats)?, "{}", name);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_build_verifiable_function() -> Result<()> {
     let schema = DataSchemaRefExt::create(vec![
@@ -0,0 +1,106 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Cursor;
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::io::parquet::read::read_metadata;
+use common_arrow::arrow::io::parquet::write::Compression;
+use common_arrow::arrow::io::parquet::write::RowGroupIterator;
+use common_arrow::arrow::io::parquet::write::Version;
+use common_arrow::arrow::io::parquet::write::WriteOptions;
+use common_arrow::parquet::encoding::Encoding;
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::col;
+use common_planners::lit;
+use common_planners::Extras;
+use common_streams::RowGroupFilter;
+use databend_query::storages::index::RowGroupRangeFilter;
+
+use crate::tests::create_query_context;
+
+#[tokio::test]
+async fn test_row_group_range_filter() -> Result<()> {
+    let ctx = create_query_context().await?;
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", i64::to_data_type()),
+        DataField::new("b", Vu8::to_data_type()),
+    ]);
+
+    // Two row groups, of `a` in [1, 3] and [10, 12].
+    let chunks = vec![
+        Chunk::try_from(DataBlock::create(schema.clone(), vec![
+            Series::from_data(vec![1i64, 2, 3]),
+            Series::from_data(vec!["a", "b", "c"]),
+        ]))?,
+        Chunk::try_from(DataBlock::create(schema.clone(), vec![
+            Series::from_data(vec![10i64, 11, 12]),
+            Series::from_data(vec!["x", "y", "z"]),
+        ]))?,
+    ];
+
+    let arrow_schema = schema.to_arrow();
+    let options = WriteOptions {
+        write_statistics: true,
+        compression: Compression::Lz4Raw,
+        version: Version::V2,
+    };
+    let encodings = vec![Encoding::Plain; arrow_schema.fields.len()];
+    let row_groups = RowGroupIterator::try_new(
+        chunks.into_iter().map(Ok),
+        &arrow_schema,
+        options,
+        encodings,
+    )?;
+    let mut data = vec![];
+    common_arrow::write_parquet_file(&mut data, row_groups, arrow_schema, options)
+        .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+    let metadata = read_metadata(&mut Cursor::new(data))?;
+    assert_eq!(metadata.row_groups.len(), 2);
+
+    let no_filter = RowGroupRangeFilter::try_create(&ctx, schema.clone(), &None)?;
+    assert!(no_filter.is_none());
+
+    let tests = vec![
+        (col("a").gt(lit(5i64)), [false, true]),
+        (col("a").lt(lit(2i64)), [true, false]),
+        (col("a").gt(lit(12i64)), [false, false]),
+        (col("b").eq(lit("y".as_bytes())), [false, true]),
+        (
+            col("a").gt(lit(5i64)).and(col("b").eq(lit("a".as_bytes()))),
+            [false, false],
+        ),
+        (
+            col("a").lt(lit(2i64)).or(col("b").eq(lit("y".as_bytes()))),
+            [true, true],
+        ),
+    ];
+
+    for (expr, expects) in tests {
+        let push_downs = Some(Extras {
+            filters: vec![expr.clone()],
+            ..Extras::default()
+        });
+        let filter = RowGroupRangeFilter::try_create(&ctx, schema.clone(), &push_downs)?.unwrap();
+        for (row_group, expect) in metadata.row_groups.iter().zip(expects) {
+            assert_eq!(filter.keep(row_group)?, expect, "{:?}", expr);
+        }
+    }
+
+    Ok(())
+}
@@ -40,6 +40,8 @@ async fn test_configs_table() -> Result<()> {
         "| log     | dir                                  | ./_logs                  |             |",
         "| log     | level                                | INFO                     |             |",
         "| log     | query_enabled                        | false                    |             |",
+        "| log     | query_history_enabled                | false                    |             |",
+        "| log     | query_history_retention_days         | 7                        |             |",
         "| meta    | address                              |                          |             |",
         "| meta    | client_timeout_in_second             | 10                       |             |",
         "| meta    | embedded_dir                         | ./_meta_embedded         |             |",
@@ -54,8 +56,12 @@ async fn test_configs_table() -> Result<()> {
         "| query   | clickhouse_handler_host              | 127.0.0.1                |             |",
         "| query   | clickhouse_handler_port              | 9000                     |             |",
         "| query   | cluster_id                           |                          |             |",
+        "| query   | connection_master_key_file           |                          |             |",
+        "| query   | connection_previous_master_key_file  |                          |             |",
         "| query   | database_engine_github_enabled       | true                     |             |",
         "| query   | flight_api_address                   | 127.0.0.1:9090           |             |",
+        "| query   | flight_sql_handler_host              | 127.0.0.1                |             |",
+        "| query   | flight_sql_handler_port              | 8900                     |             |",
         "| query   | http_handler_host                    | 127.0.0.1                |             |",
         "| query   | http_handler_port                    | 8000                     |             |",
         "| query   | http_handler_result_timeout_millis   | 10000                    |             |",
@@ -70,6 +76,12 @@ async fn test_configs_table() -> Result<()> {
         "| query   | mysql_handler_host                   | 127.0.0.1                |             |",
         "| query   | mysql_handler_port                   | 3307                     |             |",
         "| query   | num_cpus                             | 0                        |             |",
+        "| query   | oidc_auto_provision                  | false                    |             |",
+        "| query   | oidc_client_id                       |                          |             |",
+        "| query   | oidc_issuer                          |                          |             |",
+        "| query   | oidc_jwks_url                        |                          |             |",
+        "| query   | oidc_roles_claim                     | groups                   |             |",
+        "| query   | oidc_user_claim                      | sub                      |             |",
         "| query   | rpc_tls_query_server_root_ca_cert    |                          |             |",
         "| query   | rpc_tls_query_service_domain_name    | localhost                |             |",
         "| query   | rpc_tls_server_cert                  |                          |             |",
@@ -129,6 +141,8 @@ async fn test_configs_table_redact() -> Result<()> {
         "| log     | dir                                  | ./_logs                  |             |",
         "| log     | level                                | INFO                     |             |",
         "| log     | query_enabled                        | false                    |             |",
+        "| log     | query_history_enabled                | false                    |             |",
+        "| log     | query_history_retention_days         | 7                        |             |",
         "| meta    | address                              |                          |             |",
         "| meta    | client_timeout_in_second             | 10                       |             |",
         "| meta    | embedded_dir                         | ./_meta_embedded         |             |",
@@ -143,8 +157,12 @@ async fn test_configs_table_redact() -> Result<()> {
         "| query   | clickhouse_handler_host              | 127.0.0.1                |             |",
         "| query   | clickhouse_handler_port              | 9000                     |             |",
         "| query   | cluster_id                           |                          |             |",
+        "| query   | connection_master_key_file           |                          |             |",
+        "| query   | connection_previous_master_key_file  |                          |             |",
         "| query   | database_engine_github_enabled       | true                     |             |",
         "| query   | flight_api_address                   | 127.0.0.1:9090           |             |",
+        "| query   | flight_sql_handler_host              | 127.0.0.1                |             |",
+        "| query   | flight_sql_handler_port              | 8900                     |             |",
         "| query   | http_handler_host                    | 127.0.0.1                |             |",
         "| query   | http_handler_port                    | 8000                     |             |",
         "| query   | http_handler_result_timeout_millis   | 10000                    |             |",
@@ -159,6 +177,12 @@ async fn test_configs_table_redact() -> Result<()> {
         "| query   | mysql_handler_host                   | 127.0.0.1                |             |",
         "| query   | mysql_handler_port                   | 3307                     |             |",
         "| query   | num_cpus                             | 0                        |             |",
+        "| query   | oidc_auto_provision                  | false                    |             |",
+        "| query   | oidc_client_id                       |                          |             |",
+        "| query   | oidc_issuer                          |                          |             |",
+        "| query   | oidc_jwks_url                        |                          |             |",
+        "| query   | oidc_roles_claim                     | groups                   |             |",
+        "| query   | oidc_user_claim                      | sub                      |             |",
         "| query   | rpc_tls_query_server_root_ca_cert    |                          |             |",
         "| query   | rpc_tls_query_service_domain_name    | localhost                |             |",
         "| query   | rpc_tls_server_cert                  |                          |             |",
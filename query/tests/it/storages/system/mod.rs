@@ -21,6 +21,8 @@ mod databases_table;
 mod engines_table;
 mod functions_table;
 mod metrics_table;
+mod pipe_errors_table;
+mod pipe_files_table;
 mod query_log_table;
 mod roles_table;
 mod settings_table;
@@ -0,0 +1,76 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::DateTime;
+use chrono::NaiveDateTime;
+use chrono::Utc;
+use common_base::tokio;
+use common_exception::Result;
+use common_meta_types::PipeFileInfo;
+use common_meta_types::PipeFileStatus;
+use common_meta_types::PipeInfo;
+use databend_query::storages::system::PipeFilesTable;
+use databend_query::storages::ToReadDataSourcePlan;
+use futures::TryStreamExt;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_pipe_files_table() -> Result<()> {
+    let ctx = crate::tests::create_query_context().await?;
+    let tenant = ctx.get_tenant();
+    ctx.get_settings().set_max_threads(2)?;
+
+    let time = DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp(61, 0), Utc);
+    let user_mgr = ctx.get_user_manager();
+    let pipe_info = PipeInfo {
+        pipe_name: "mypipe".to_string(),
+        ..Default::default()
+    };
+    user_mgr.add_pipe(&tenant, pipe_info, false).await?;
+    user_mgr
+        .add_pipe_files(&tenant, "mypipe", vec![
+            PipeFileInfo {
+                file_name: "a.csv".to_string(),
+                rows: 3,
+                load_time: time,
+                ..Default::default()
+            },
+            PipeFileInfo {
+                file_name: "b.csv".to_string(),
+                status: PipeFileStatus::Failed,
+                error: "invalid csv row".to_string(),
+                load_time: time,
+                ..Default::default()
+            },
+        ])
+        .await?;
+
+    let table = PipeFilesTable::create(1);
+    let source_plan = table.read_plan(ctx.clone(), None).await?;
+
+    let stream = table.read(ctx, &source_plan).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let block = &result[0];
+    assert_eq!(block.num_columns(), 5);
+
+    let expected = vec![
+        "+--------+-----------+--------+------+-----------+",
+        "| pipe   | file_name | status | rows | load_time |",
+        "+--------+-----------+--------+------+-----------+",
+        "| mypipe | a.csv     | Loaded | 3    | 61        |",
+        "| mypipe | b.csv     | Failed | 0    | 61        |",
+        "+--------+-----------+--------+------+-----------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    Ok(())
+}
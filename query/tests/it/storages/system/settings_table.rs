@@ -34,17 +34,35 @@ async fn test_settings_table() -> Result<()> {
         "| name                           | value   | default | level   | description                                                                                        | type   |",
         "+--------------------------------+---------+---------+---------+----------------------------------------------------------------------------------------------------+--------+",
         "|                                |         |         |         |                                                                                                    |        |",
+        "| async_insert_busy_timeout      | 200     | 200     | SESSION | Max duration in milliseconds the inserts are buffered. By default, it is 200 ms.                   | UInt64 |",
+        "| async_insert_max_data_size     | 1048576 | 1048576 | SESSION | The size in bytes of the buffered inserts to commit them. By default, it is 1MB.                   | UInt64 |",
         "| empty_as_default               | 1       | 1       | SESSION | Format empty_as_default, default value: 1                                                          | UInt64 |",
+        "| enable_async_insert            | 0       | 0       | SESSION | Buffer the small inserts and commit them in batches if value != 0, default value: 0                | UInt64 |",
         "| enable_new_processor_framework | 1       | 1       | SESSION | Enable new processor framework if value != 0, default value: 1                                     | UInt64 |",
         "| enable_planner_v2              | 0       | 0       | SESSION | Enable planner v2 by setting this variable to 1, default value: 0                                  | UInt64 |",
+        "| enable_query_result_cache      | 0       | 0       | SESSION | Cache the small results of the queries on unchanged tables if value != 0, default value: 0         | UInt64 |",
         "| field_delimiter                | ,       | ,       | SESSION | Format field delimiter, default value: ,                                                           | String |",
         "| flight_client_timeout          | 60      | 60      | SESSION | Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds | UInt64 |",
+        "| group_by_spill_threshold       | 0       | 0       | SESSION | The size in bytes of the grouped data to spill it to disk, 0 to disable it, default value: 0       | UInt64 |",
         "| max_block_size                 | 10000   | 10000   | SESSION | Maximum block size for reading                                                                     | UInt64 |",
+        "| max_concurrent_queries         | 0       | 0       | SESSION | The maximum number of the running queries of a user, 0 for unlimited, default value: 0             | UInt64 |",
+        "| max_query_memory               | 0       | 0       | SESSION | The maximum memory in bytes held by the operators of a query, 0 for unlimited, default value: 0    | UInt64 |",
         "| max_threads                    | 2       | 16      | SESSION | The maximum number of threads to execute the request. By default, it is determined automatically.  | UInt64 |",
+        "| partial_group_by_sample_rows   | 65536   | 65536   | SESSION | Rows sampled to skip the partial group by of unique keys, 0 to disable it, default value: 65536    | UInt64 |",
+        "| query_queue_timeout            | 0       | 0       | SESSION | The seconds a query waits for a slot of its user, 0 to wait forever, default value: 0              | UInt64 |",
+        "| query_result_cache_max_bytes   | 1048576 | 1048576 | SESSION | Max size in bytes of the cached query results of the tenant. By default, it is 1MB.                | UInt64 |",
+        "| query_result_cache_ttl         | 300     | 300     | SESSION | The seconds the cached query results are kept. By default, it is 300 seconds.                      | UInt64 |",
         "| record_delimiter               |         |         | SESSION | Format record_delimiter, default value:                                                            | String |",
+        "| retention_period               | 12      | 12      | SESSION | The retention period (in hours) of historical data. By default, it is 12 hours.                    | UInt64 |",
         "| skip_header                    | 0       | 0       | SESSION | Whether to skip the input header, default value: 0                                                 | UInt64 |",
+        "| snapshot_encoding              | json    | json    | SESSION | The encoding of the written snapshots: json or msgpack, default value: json                        | String |",
+        "| sort_spill_threshold           | 0       | 0       | SESSION | The size in bytes of the sorted data to spill it to disk, 0 to disable it, default value: 0        | UInt64 |",
+        "| spill_compression              | lz4     | lz4     | SESSION | The compression of the spilled data: none, lz4, snappy or zstd, default value: lz4                 | String |",
+        "| spill_concurrency              | 4       | 4       | SESSION | The number of the spilled files written or read at the same time, default value: 4                 | UInt64 |",
+        "| spill_path                     |         |         | SESSION | The local directory of the spilled data. By default, the data is spilled to the table storage.     | String |",
         "| storage_read_buffer_size       | 1048576 | 1048576 | SESSION | The size of buffer in bytes for buffered reader of dal. By default, it is 1MB.                     | UInt64 |",
         "| timezone                       | UTC     | UTC     | SESSION | Timezone, default value: UTC,                                                                      | String |",
+        "| wait_for_async_insert          | 1       | 1       | SESSION | Wait until the buffered insert is committed if value != 0, default value: 1                        | UInt64 |",
         "+--------------------------------+---------+---------+---------+----------------------------------------------------------------------------------------------------+--------+",
     ];
     common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
@@ -15,8 +15,13 @@
 use base64::encode_config;
 use base64::URL_SAFE_NO_PAD;
 use common_base::tokio;
+use common_datavalues::chrono::Utc;
 use common_exception::Result;
+use common_meta_types::AuthInfo;
+use common_meta_types::NetworkPolicy;
+use common_meta_types::PasswordHashMethod;
 use common_meta_types::UserIdentity;
+use common_meta_types::UserInfo;
 use databend_query::users::auth::jwt::CustomClaims;
 use databend_query::users::auth::jwt::EnsureUser;
 use databend_query::users::AuthMgr;
@@ -64,7 +69,7 @@ async fn test_auth_mgr_with_jwt() -> Result<()> {
         let claims = Claims::create(Duration::from_hours(2));
         let token = key_pair.sign(claims)?;
 
-        let res = auth_mgr.auth(&Credential::Jwt { token }).await;
+        let res = auth_mgr.auth(&Credential::Jwt { token }, None).await;
         assert!(res.is_err());
         assert_eq!(
             "Code: 1051, displayText = missing field `subject` in jwt.",
@@ -77,7 +82,7 @@ async fn test_auth_mgr_with_jwt() -> Result<()> {
         let claims = Claims::create(Duration::from_hours(2)).with_subject(user_name.to_string());
         let token = key_pair.sign(claims)?;
 
-        let res = auth_mgr.auth(&Credential::Jwt { token }).await;
+        let res = auth_mgr.auth(&Credential::Jwt { token }, None).await;
         assert!(res.is_err());
         assert_eq!(
             "Code: 2201, displayText = unknown user 'test'@'%'.",
@@ -92,7 +97,7 @@ async fn test_auth_mgr_with_jwt() -> Result<()> {
             .with_subject(user_name.to_string());
         let token = key_pair.sign(claims)?;
 
-        let res = auth_mgr.auth(&Credential::Jwt { token }).await;
+        let res = auth_mgr.auth(&Credential::Jwt { token }, None).await;
         assert!(res.is_err());
         assert_eq!(
             "Code: 2201, displayText = unknown user 'test'@'%'.",
@@ -110,7 +115,7 @@ async fn test_auth_mgr_with_jwt() -> Result<()> {
             .with_subject(user_name.to_string());
         let token = key_pair.sign(claims)?;
 
-        let (currnet_tenant, user_info) = auth_mgr.auth(&Credential::Jwt { token }).await?;
+        let (currnet_tenant, user_info) = auth_mgr.auth(&Credential::Jwt { token }, None).await?;
         assert!(currnet_tenant.is_some());
         assert_eq!(currnet_tenant.unwrap(), tenant.to_string());
         assert_eq!(user_info.grants.roles().len(), 0);
@@ -123,7 +128,7 @@ async fn test_auth_mgr_with_jwt() -> Result<()> {
             .with_subject(user_name.to_string());
         let token = key_pair.sign(claims)?;
 
-        let (_, user_info) = auth_mgr.auth(&Credential::Jwt { token }).await?;
+        let (_, user_info) = auth_mgr.auth(&Credential::Jwt { token }, None).await?;
         assert_eq!(user_info.grants.roles().len(), 0);
     }
 
@@ -136,7 +141,7 @@ async fn test_auth_mgr_with_jwt() -> Result<()> {
             .with_subject(user_name.to_string());
         let token = key_pair.sign(claims)?;
 
-        let (_, user_info) = auth_mgr.auth(&Credential::Jwt { token }).await?;
+        let (_, user_info) = auth_mgr.auth(&Credential::Jwt { token }, None).await?;
         assert_eq!(user_info.grants.roles().len(), 0);
     }
 
@@ -151,7 +156,7 @@ async fn test_auth_mgr_with_jwt() -> Result<()> {
             .with_subject(user_name.to_string());
         let token = key_pair.sign(claims)?;
 
-        let res = auth_mgr.auth(&Credential::Jwt { token }).await;
+        let res = auth_mgr.auth(&Credential::Jwt { token }, None).await;
         assert!(res.is_ok());
 
         let user_info = user_mgr
@@ -163,3 +168,189 @@ async fn test_auth_mgr_with_jwt() -> Result<()> {
 
     Ok(())
 }
+
+#[derive(Default, Deserialize, Serialize)]
+struct OidcClaims {
+    groups: Vec<String>,
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_auth_mgr_with_oidc() -> Result<()> {
+    let key_pair = RS256KeyPair::generate(2048)?.with_key_id("test_kid");
+    let rsa_components = key_pair.public_key().to_components();
+    let e = encode_config(rsa_components.e, URL_SAFE_NO_PAD);
+    let n = encode_config(rsa_components.n, URL_SAFE_NO_PAD);
+    let jwks =
+        serde_json::json!({"keys": [ {"kty": "RSA", "kid": "test_kid", "e": e, "n": n, } ] });
+
+    let server = MockServer::start().await;
+    let issuer = server.uri();
+    let discovery = serde_json::json!({
+        "issuer": issuer,
+        "jwks_uri": format!("{}/jwks.json", issuer),
+    });
+    Mock::given(method("GET"))
+        .and(path("/.well-known/openid-configuration"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(discovery))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/jwks.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(jwks))
+        .expect(1..)
+        .mount(&server)
+        .await;
+
+    let mut conf = crate::tests::ConfigBuilder::create().config();
+    conf.query.oidc_issuer = issuer.clone();
+    conf.query.oidc_client_id = "databend".to_string();
+    conf.query.oidc_auto_provision = true;
+    let tenant = conf.query.tenant_id.clone();
+    let user_mgr = UserApiProvider::create_global(conf.clone()).await?;
+    let auth_mgr = AuthMgr::create(conf, user_mgr.clone()).await?;
+
+    let id_token = |subject: &str, audience: &str, groups: &[&str]| {
+        let custom_claims = OidcClaims {
+            groups: groups.iter().map(|g| g.to_string()).collect(),
+        };
+        let claims = Claims::with_custom_claims(custom_claims, Duration::from_hours(2))
+            .with_issuer(&issuer)
+            .with_audience(audience)
+            .with_subject(subject);
+        key_pair.sign(claims)
+    };
+
+    // wrong audience
+    {
+        let token = id_token("alice", "other", &[])?;
+        let res = auth_mgr.auth(&Credential::Jwt { token }, None).await;
+        assert!(res.is_err());
+        assert_eq!(
+            "Code: 1051, displayText = wrong audience of the oidc id token.",
+            res.err().unwrap().to_string()
+        );
+    }
+
+    // provisioned on the first login
+    {
+        let token = id_token("alice", "databend", &["analyst"])?;
+        let (tenant_id, user_info) = auth_mgr.auth(&Credential::Jwt { token }, None).await?;
+        assert!(tenant_id.is_none());
+        assert_eq!(user_info.name, "alice");
+        assert_eq!(user_info.grants.roles(), vec!["analyst".to_string()]);
+    }
+
+    // the new roles of the claims are granted
+    {
+        let token = id_token("alice", "databend", &["admin", "analyst"])?;
+        auth_mgr.auth(&Credential::Jwt { token }, None).await?;
+
+        let user_info = user_mgr
+            .get_user(&tenant, UserIdentity::new("alice", "%"))
+            .await?;
+        let mut roles = user_info.grants.roles();
+        roles.sort();
+        assert_eq!(roles, vec!["admin".to_string(), "analyst".to_string()]);
+    }
+
+    // the roles no longer in the claims are revoked
+    {
+        let token = id_token("alice", "databend", &["admin"])?;
+        let (_, user_info) = auth_mgr.auth(&Credential::Jwt { token }, None).await?;
+        assert_eq!(user_info.grants.roles(), vec!["admin".to_string()]);
+
+        let user_info = user_mgr
+            .get_user(&tenant, UserIdentity::new("alice", "%"))
+            .await?;
+        assert_eq!(user_info.grants.roles(), vec!["admin".to_string()]);
+    }
+
+    // the users authenticated by passwords could not log in with the ID tokens
+    {
+        let user_info = UserInfo::new("bob", "%", AuthInfo::Password {
+            hash_value: Vec::from("pass"),
+            hash_method: PasswordHashMethod::Sha256,
+        });
+        user_mgr.add_user(&tenant, user_info, false).await?;
+
+        let token = id_token("bob", "databend", &["admin"])?;
+        let res = auth_mgr.auth(&Credential::Jwt { token }, None).await;
+        assert!(res.is_err());
+        assert_eq!(
+            "Code: 1051, displayText = wrong auth type.",
+            res.err().unwrap().to_string()
+        );
+
+        let user_info = user_mgr
+            .get_user(&tenant, UserIdentity::new("bob", "%"))
+            .await?;
+        assert!(user_info.grants.roles().is_empty());
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_auth_mgr_with_network_policy() -> Result<()> {
+    let conf = crate::tests::ConfigBuilder::create().config();
+    let tenant = conf.query.tenant_id.clone();
+    let user_mgr = UserApiProvider::create_global(conf.clone()).await?;
+    let auth_mgr = AuthMgr::create(conf, user_mgr.clone()).await?;
+
+    let policy = NetworkPolicy {
+        name: "office".to_string(),
+        allowed_ip_list: vec!["192.168.1.0/24".to_string()],
+        blocked_ip_list: vec!["192.168.1.99".to_string()],
+        comment: "".to_string(),
+        created_on: Utc::now(),
+    };
+    user_mgr.add_network_policy(&tenant, policy, false).await?;
+
+    let mut user_info = UserInfo::new("test-user", "%", AuthInfo::None);
+    user_info
+        .option
+        .set_network_policy(Some("office".to_string()));
+    user_mgr.add_user(&tenant, user_info, false).await?;
+
+    let credential = |client_ip: &str| Credential::Password {
+        name: "test-user".to_string(),
+        password: None,
+        hostname: Some(client_ip.to_string()),
+    };
+
+    // allowed
+    {
+        let res = auth_mgr
+            .auth(&credential("192.168.1.10"), Some("192.168.1.10"))
+            .await;
+        assert!(res.is_ok());
+    }
+
+    // blocked
+    {
+        let res = auth_mgr
+            .auth(&credential("192.168.1.99"), Some("192.168.1.99"))
+            .await;
+        assert!(res.is_err());
+        assert_eq!(res.err().unwrap().code(), 2624);
+    }
+
+    // not in the allowed list
+    {
+        let res = auth_mgr
+            .auth(&credential("10.0.0.1"), Some("10.0.0.1"))
+            .await;
+        assert!(res.is_err());
+        assert_eq!(res.err().unwrap().code(), 2624);
+    }
+
+    // unknown client address
+    {
+        let res = auth_mgr.auth(&credential("192.168.1.10"), None).await;
+        assert!(res.is_err());
+        assert_eq!(res.err().unwrap().code(), 2624);
+    }
+
+    Ok(())
+}
@@ -15,6 +15,7 @@
 mod auth;
 mod role_cache_mgr;
 mod role_mgr;
+mod user_connection;
 mod user_mgr;
 mod user_udf;
 mod user_warehouse;
@@ -0,0 +1,153 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::configs::Config;
+use databend_query::users::ConnectionCipher;
+use databend_query::users::UserApiProvider;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+const KEY_1: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+const KEY_2: &str = "f0e0d0c0b0a090807060504030201000f0e0d0c0b0a090807060504030201000";
+
+fn key_config(dir: &TempDir, key: &str, previous_key: Option<&str>) -> Result<Config> {
+    let mut conf = crate::tests::ConfigBuilder::create().config();
+
+    let key_file = dir.path().join(format!("{}.key", &key[..8]));
+    std::fs::write(&key_file, key)?;
+    conf.query.connection_master_key_file = key_file.to_string_lossy().to_string();
+    if let Some(previous_key) = previous_key {
+        let key_file = dir.path().join(format!("{}.key", &previous_key[..8]));
+        std::fs::write(&key_file, previous_key)?;
+        conf.query.connection_previous_master_key_file = key_file.to_string_lossy().to_string();
+    }
+    Ok(conf)
+}
+
+fn credentials(key_id: &str, secret_key: &str) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("aws_key_id".to_string(), key_id.to_string()),
+        ("aws_secret_key".to_string(), secret_key.to_string()),
+    ])
+}
+
+#[test]
+fn test_connection_cipher() -> Result<()> {
+    let dir = TempDir::new()?;
+    let creds = credentials("1a2b3c", "4x5y6z");
+
+    let conf = key_config(&dir, KEY_1, None)?;
+    let cipher_1 = ConnectionCipher::try_create(&conf.query)?.unwrap();
+    let encrypted = cipher_1.encrypt("my_s3", &creds)?;
+    assert!(!String::from_utf8_lossy(&encrypted).contains("4x5y6z"));
+    assert_eq!(
+        cipher_1.decrypt("my_s3", cipher_1.master_key_id(), &encrypted)?,
+        creds
+    );
+
+    // The encrypted credentials are bound to the connection.
+    let res = cipher_1.decrypt("other_s3", cipher_1.master_key_id(), &encrypted);
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::illegal_connection_code()
+    );
+
+    // The previous master key still decrypts the credentials it encrypted.
+    let conf = key_config(&dir, KEY_2, Some(KEY_1))?;
+    let cipher_2 = ConnectionCipher::try_create(&conf.query)?.unwrap();
+    assert_ne!(cipher_2.master_key_id(), cipher_1.master_key_id());
+    assert_eq!(
+        cipher_2.decrypt("my_s3", cipher_1.master_key_id(), &encrypted)?,
+        creds
+    );
+
+    // Without the previous master key, the credentials can't be decrypted.
+    let conf = key_config(&dir, KEY_2, None)?;
+    let cipher_3 = ConnectionCipher::try_create(&conf.query)?.unwrap();
+    let res = cipher_3.decrypt("my_s3", cipher_1.master_key_id(), &encrypted);
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::illegal_connection_code()
+    );
+
+    // No master key, no cipher.
+    let conf = crate::tests::ConfigBuilder::create().config();
+    assert!(ConnectionCipher::try_create(&conf.query)?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_user_connection() -> Result<()> {
+    let dir = TempDir::new()?;
+    let conf = key_config(&dir, KEY_1, None)?;
+    let tenant = "test";
+    let user_mgr = UserApiProvider::create_global(conf).await?;
+
+    // add connection
+    {
+        let creds = credentials("1a2b3c", "4x5y6z");
+        user_mgr
+            .add_connection(tenant, "my_s3", "s3", &creds, "", false)
+            .await?;
+        assert_eq!(
+            user_mgr.get_connection_credentials(tenant, "my_s3").await?,
+            creds
+        );
+
+        let res = user_mgr
+            .add_connection(tenant, "my_s3", "s3", &creds, "", false)
+            .await;
+        assert_eq!(
+            res.unwrap_err().code(),
+            ErrorCode::connection_already_exists_code()
+        );
+        user_mgr
+            .add_connection(tenant, "my_s3", "s3", &creds, "", true)
+            .await?;
+    }
+
+    // alter connection
+    {
+        let creds = credentials("7a8b9c", "0x1y2z");
+        user_mgr
+            .alter_connection(tenant, "my_s3", Some(&creds))
+            .await?;
+        user_mgr.alter_connection(tenant, "my_s3", None).await?;
+        assert_eq!(
+            user_mgr.get_connection_credentials(tenant, "my_s3").await?,
+            creds
+        );
+    }
+
+    // drop connection
+    {
+        user_mgr.drop_connection(tenant, "my_s3", false).await?;
+        assert_eq!(user_mgr.get_connections(tenant).await?.len(), 0);
+
+        let res = user_mgr.drop_connection(tenant, "my_s3", false).await;
+        assert_eq!(
+            res.unwrap_err().code(),
+            ErrorCode::unknown_connection_code()
+        );
+        user_mgr.drop_connection(tenant, "my_s3", true).await?;
+    }
+
+    Ok(())
+}
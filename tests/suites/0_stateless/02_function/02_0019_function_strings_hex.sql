@@ -2,3 +2,4 @@ select hex(100);
 select hex(-100);
 select hex('abc');
 select hex(null);
+select to_hex('abc');
@@ -2,3 +2,10 @@ select unhex('616263');
 select unhex('hello'); -- {ErrorCode 1054}
 select unhex(hex('hello'));
 select unhex(null);
+select from_hex('616263');
+select from_hex(to_hex('hello'));
+select from_hex('hello'); -- {ErrorCode 1054}
+select try_from_hex('hello');
+select try_unhex('616263');
+select try_unhex(null);
+select length(from_hex(to_hex(repeat('a', 100))));
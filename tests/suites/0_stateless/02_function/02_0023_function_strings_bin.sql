@@ -5,4 +5,5 @@ select bin(255);
 select bin(65536);
 select bin(13835058055282163712);
 select bin(9223372036854775808);
+select to_binary_string(12);
 
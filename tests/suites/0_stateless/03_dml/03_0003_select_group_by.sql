@@ -41,3 +41,7 @@ select created_at, sum(count) from t_datetime group by created_at order by creat
 select created_time, sum(count) from t_datetime group by created_time order by created_time;
 
 drop table t_datetime;
+
+SELECT '==GROUP BY STRINGS==';
+
+select to_varchar(number % 3) as a, to_varchar(number % 2) as b, count(*) from numbers(10) group by a, b order by a, b;
@@ -0,0 +1,8 @@
+SET max_block_size = 100;
+SET group_by_spill_threshold = 1;
+SET sort_spill_threshold = 1;
+
+SELECT number % 3 AS c1, count(*) AS c2, sum(number) AS c3 FROM numbers_mt(10000) GROUP BY number % 3 ORDER BY c1;
+SELECT number % 1000 AS c1 FROM numbers_mt(10000) GROUP BY number % 1000 ORDER BY c1 LIMIT 3;
+SELECT number FROM numbers_mt(10000) ORDER BY number DESC LIMIT 3;
+SELECT number FROM numbers_mt(10000) ORDER BY number LIMIT 2 OFFSET 5000;
@@ -0,0 +1,16 @@
+SET max_block_size = 10;
+
+SELECT number FROM numbers_mt(1000) ORDER BY number DESC LIMIT 3;
+SELECT number FROM numbers_mt(1000) ORDER BY number LIMIT 2 OFFSET 500;
+
+DROP TABLE IF EXISTS t_top_n;
+CREATE TABLE t_top_n(a UInt64, b UInt64);
+INSERT INTO t_top_n SELECT number, number % 7 FROM numbers(100);
+INSERT INTO t_top_n SELECT number + 100, (number + 100) % 7 FROM numbers(100);
+INSERT INTO t_top_n SELECT number + 200, (number + 200) % 7 FROM numbers(100);
+
+SELECT a FROM t_top_n ORDER BY a DESC LIMIT 3;
+SELECT a FROM t_top_n ORDER BY a LIMIT 2 OFFSET 150;
+SELECT a, b FROM t_top_n ORDER BY b DESC, a LIMIT 3;
+
+DROP TABLE t_top_n;
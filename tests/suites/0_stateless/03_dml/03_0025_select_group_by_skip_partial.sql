@@ -0,0 +1,6 @@
+SET max_block_size = 100;
+SET partial_group_by_sample_rows = 100;
+
+SELECT number % 3 AS c1, count(*) AS c2, sum(number) AS c3 FROM numbers_mt(10000) GROUP BY number % 3 ORDER BY c1;
+SELECT number % 5000 AS c1, count(*) AS c2, sum(number) AS c3 FROM numbers_mt(10000) GROUP BY number % 5000 ORDER BY c1 LIMIT 3;
+SELECT number % 5000 AS c1 FROM numbers_mt(10000) GROUP BY number % 5000 ORDER BY c1 DESC LIMIT 2;
@@ -0,0 +1,23 @@
+DROP TABLE IF EXISTS sales;
+DROP ROW ACCESS POLICY IF EXISTS region_policy;
+CREATE TABLE sales(region VARCHAR, amount INT);
+INSERT INTO sales VALUES ('us', 1), ('eu', 2), ('us', 3);
+CREATE ROW ACCESS POLICY region_policy AS (r VARCHAR) RETURNS BOOLEAN -> r = 'us';
+ALTER TABLE sales ADD ROW ACCESS POLICY region_policy ON (region);
+SELECT * FROM sales ORDER BY amount;
+DROP STREAM IF EXISTS sales_stream;
+CREATE STREAM sales_stream ON TABLE sales;
+INSERT INTO sales VALUES ('eu', 4), ('us', 5);
+SELECT region, amount FROM sales_stream ORDER BY amount;
+SELECT * FROM (SELECT * FROM sales) AS s WHERE amount > 1 ORDER BY amount;
+SELECT * FROM sales, sales_stream; -- {ErrorCode 1002}
+SET enable_planner_v2 = 1;
+SELECT * FROM sales WHERE amount > 1 AND amount < 5;
+SELECT region, amount FROM sales_stream;
+SELECT * FROM sales, sales_stream; -- {ErrorCode 1002}
+SET enable_planner_v2 = 0;
+DROP STREAM sales_stream;
+ALTER TABLE sales DROP ROW ACCESS POLICY region_policy;
+SELECT * FROM sales ORDER BY amount;
+DROP ROW ACCESS POLICY region_policy;
+DROP TABLE sales;
@@ -0,0 +1,19 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+aws --endpoint-url http://127.0.0.1:9900/ s3 cp s3://testbucket/admin/data/ontime_200.csv s3://testbucket/admin/stage/s2/ontime_200.csv > /dev/null 2>&1
+aws --endpoint-url http://127.0.0.1:9900/ s3 cp s3://testbucket/admin/data/ontime_200.parquet s3://testbucket/admin/stage/s2/ontime_200.parquet  > /dev/null 2>&1
+
+echo "CREATE STAGE s2;" | $MYSQL_CLIENT_CONNECT
+
+## Select csv by the positions of the columns, the header is the first row.
+echo "select count(1), max(metadata\$file_row_number), count(distinct metadata\$filename) from @s2 (FILE_FORMAT => 'csv', PATTERN => '.*csv')" | $MYSQL_CLIENT_CONNECT
+echo "select \$1, \$5 from @s2 (FILE_FORMAT => 'csv', PATTERN => '.*csv') where metadata\$file_row_number = 2" | $MYSQL_CLIENT_CONNECT
+echo "select sum(cast(\$5 as int)) from @s2 (FILE_FORMAT => 'csv', PATTERN => '.*csv') where metadata\$file_row_number > 1" | $MYSQL_CLIENT_CONNECT
+
+## The format is inferred by the extension of the files.
+echo "select count(1), avg(Year), sum(DayOfWeek) from @s2 (PATTERN => '.*parquet')" | $MYSQL_CLIENT_CONNECT
+
+echo "drop stage if exists s2" | $MYSQL_CLIENT_CONNECT
@@ -0,0 +1,18 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+aws --endpoint-url http://127.0.0.1:9900/ s3 cp s3://testbucket/admin/data/ontime_200.csv s3://testbucket/admin/stage/s3/ontime_200.csv > /dev/null 2>&1
+
+echo "CREATE STAGE s3;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists ontime_transformed;" | $MYSQL_CLIENT_CONNECT
+echo "create table ontime_transformed(carrier varchar, day_of_week int, year int, file varchar);" | $MYSQL_CLIENT_CONNECT
+
+## The columns are reordered, cast and transformed by functions while loading.
+echo "copy into ontime_transformed from (select lower(\$7), \$5, \$1, metadata\$filename from @s3) PATTERN = '.*csv' FILE_FORMAT = (type = 'CSV' skip_header = 1)" | $MYSQL_CLIENT_CONNECT
+echo "select count(1), sum(year), sum(day_of_week), count(distinct file) from ontime_transformed" | $MYSQL_CLIENT_CONNECT
+echo "select count(1) from ontime_transformed where carrier = lower(carrier)" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table ontime_transformed" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists s3" | $MYSQL_CLIENT_CONNECT
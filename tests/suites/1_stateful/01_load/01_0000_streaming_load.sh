@@ -48,4 +48,9 @@ curl -H "insert_sql:insert into ontime_streaming_load format NdJson" -H "skip_he
 echo "select count(1) ,avg(Year), sum(DayOfWeek)  from ontime_streaming_load;" | $MYSQL_CLIENT_CONNECT
 
 
+# the whole body as one chunk, sent with the chunked transfer encoding
+curl -H "insert_sql:insert into ontime_streaming_load format Csv" -H "skip_header:1" -H "Transfer-Encoding: chunked" -T /tmp/ontime_200.csv -XPUT "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/streaming_load" > /dev/null 2>&1
+echo "select count(1) ,avg(Year), sum(DayOfWeek)  from ontime_streaming_load;" | $MYSQL_CLIENT_CONNECT
+
+
 echo "drop table ontime_streaming_load;" | $MYSQL_CLIENT_CONNECT